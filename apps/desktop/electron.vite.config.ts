@@ -30,12 +30,17 @@ const desktopRendererPort = resolveDesktopRendererPort(process.env.COWORK_DESKTO
 const safePublicTelemetryDefine = {
   "globalThis.__COWORK_PUBLIC_TELEMETRY_ENV__": JSON.stringify(pickPublicTelemetryEnv(process.env)),
 };
+const sidecarUpdatePublicKeyDefine = {
+  "globalThis.__COWORK_SIDECAR_UPDATE_PUBLIC_KEY__": JSON.stringify(
+    process.env.COWORK_SIDECAR_UPDATE_PUBLIC_KEY?.trim() ?? "",
+  ),
+};
 
 export default defineConfig({
   main: {
     plugins: [externalizeDepsPlugin()],
     future: "warn",
-    define: safePublicTelemetryDefine,
+    define: { ...safePublicTelemetryDefine, ...sidecarUpdatePublicKeyDefine },
     resolve: {
       alias: coworkAlias,
     },
@@ -6,6 +6,7 @@ import { DESKTOP_IPC_CHANNELS } from "../src/lib/desktopApi";
 import { registerFilesIpc } from "./ipc/files";
 import { handleDesktopInvoke } from "./ipc/invoke";
 import { registerMobileRelayIpc } from "./ipc/mobileRelay";
+import { registerOperationsIpc } from "./ipc/operations";
 import { parseWithSchema } from "./ipc/parse";
 import { registerSystemIpc } from "./ipc/system";
 import type { DesktopIpcDeps } from "./ipc/types";
@@ -16,7 +17,13 @@ import { WorkspaceRootsController } from "./ipc/workspaceRoots";
 const require = createRequire(import.meta.url);
 const { ipcMain } = require("electron") as typeof Electron;
 
-export function registerDesktopIpc(deps: DesktopIpcDeps): () => void {
+export type DesktopIpcRegistration = {
+  /** Closes directory watchers and other listeners while leaving handlers registered. */
+  stopWatchers: () => void;
+  unregister: () => void;
+};
+
+export function registerDesktopIpc(deps: DesktopIpcDeps): DesktopIpcRegistration {
   const workspaceRoots = deps.workspaceRoots ?? new WorkspaceRootsController(deps.persistence);
   const context = {
     deps,
@@ -25,17 +32,25 @@ export function registerDesktopIpc(deps: DesktopIpcDeps): () => void {
     parseWithSchema,
   };
 
-  registerWorkspaceIpc(context);
-  const unregisterFilesIpc = registerFilesIpc(context);
+  const workspace = registerWorkspaceIpc(context);
+  const unregisterFilesIpc = registerFilesIpc(context, workspace.appendTranscriptEvents);
   registerWindowIpc(context);
   registerSystemIpc(context);
+  registerOperationsIpc(context, workspace.tasks);
   const unregisterMobileRelayIpc = registerMobileRelayIpc(context);
 
-  return () => {
+  const stopWatchers = () => {
     unregisterFilesIpc();
     unregisterMobileRelayIpc();
-    for (const channel of Object.values(DESKTOP_IPC_CHANNELS)) {
-      ipcMain.removeHandler(channel);
-    }
+  };
+
+  return {
+    stopWatchers,
+    unregister: () => {
+      stopWatchers();
+      for (const channel of Object.values(DESKTOP_IPC_CHANNELS)) {
+        ipcMain.removeHandler(channel);
+      }
+    },
   };
 }
@@ -14,6 +14,7 @@ import { MAX_ATTACHMENT_UPLOAD_BYTE_SIZE } from "../../../../src/shared/attachme
 import type { WorkspaceFileChangeEvent as PreviewFileChangeEvent } from "../../../../src/shared/fileVersion";
 import { isPathInside, resolvePathInsideRootForBoundaryCheck } from "../../../../src/utils/paths";
 import {
+  type AttachmentReferencePayload,
   type AuthorizeUploadSourceInput,
   type CopyFileToWorkspaceUploadsInput,
   type CopyFileToWorkspaceUploadsOutput,
@@ -22,6 +23,8 @@ import {
   DESKTOP_EVENT_CHANNELS,
   DESKTOP_IPC_CHANNELS,
   type ExplorerEntry,
+  type GetAttachmentInput,
+  type GetThumbnailInput,
   type ListDirectoryInput,
   type OpenPathInput,
   type PickCanvasSavePathInput,
@@ -33,6 +36,8 @@ import {
   type RenamePathInput,
   type RevealPathInput,
   type SaveExportedFileInput,
+  type StopWorkspaceServerInput,
+  type StoreAttachmentInput,
   type TrashPathInput,
   type WatchWorkspaceDirectoryInput,
   type WriteFileInput,
@@ -43,6 +48,8 @@ import {
   copyPathInputSchema,
   copyTextInputSchema,
   createDirectoryInputSchema,
+  getAttachmentInputSchema,
+  getThumbnailInputSchema,
   listDirectoryInputSchema,
   openPathInputSchema,
   pickCanvasSavePathInputSchema,
@@ -54,6 +61,8 @@ import {
   renamePathInputSchema,
   revealPathInputSchema,
   saveExportedFileInputSchema,
+  stopWorkspaceServerInputSchema,
+  storeAttachmentInputSchema,
   trashPathInputSchema,
   watchWorkspaceDirectoryInputSchema,
   writeFileInputSchema,
@@ -72,7 +81,7 @@ import {
   resolveAllowedSaveExportSourcePath,
 } from "../services/ipcSecurity";
 import { WorkspaceDirectoryWatcher } from "../services/workspaceDirectoryWatcher";
-import type { DesktopIpcModuleContext } from "./types";
+import type { DesktopIpcModuleContext, TranscriptAppender } from "./types";
 
 const execFile = promisify(execFileCallback);
 const require = createRequire(import.meta.url);
@@ -230,14 +239,17 @@ async function readExplorerDirectory(
     });
 }
 
-export function registerFilesIpc(context: DesktopIpcModuleContext): () => void {
-  const { handleDesktopInvoke, parseWithSchema, workspaceRoots } = context;
+export function registerFilesIpc(
+  context: DesktopIpcModuleContext,
+  appendTranscriptEvents?: TranscriptAppender,
+): () => void {
+  const { deps, handleDesktopInvoke, parseWithSchema, workspaceRoots } = context;
   const directoryListings = new DirectoryListingCoordinator<ExplorerEntry>({
     cacheResults: false,
     isEntryEqual: explorerEntriesEqual,
     readDirectory: async (input) => await readExplorerDirectory(input.path, input.includeHidden),
   });
-  const directoryWatcher = new WorkspaceDirectoryWatcher();
+  const directoryWatcher = deps.directoryWatcher ?? new WorkspaceDirectoryWatcher();
   const destroyListenerBySenderId = new Set<number>();
 
   const invalidateWorkspaceFileChange = (event: DirectoryWorkspaceFileChangeEvent): void => {
@@ -361,6 +373,18 @@ export function registerFilesIpc(context: DesktopIpcModuleContext): () => void {
     },
   );
 
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getWatcherStatus,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "getWatcherStatus options",
+      );
+      return directoryWatcher.getStatus(input.workspaceId);
+    },
+  );
+
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.readFile, async (_event, args: ReadFileInput) => {
     const input = parseWithSchema(readFileInputSchema, args, "readFile options");
     await workspaceRoots.ensureApprovedWorkspaceRoots();
@@ -476,6 +500,7 @@ export function registerFilesIpc(context: DesktopIpcModuleContext): () => void {
         return null;
       }
 
+      await deps.storageGuard?.assertWritable("export", path.dirname(result.filePath));
       await fs.copyFile(safeSourcePath, result.filePath);
       return result.filePath;
     },
@@ -593,6 +618,57 @@ export function registerFilesIpc(context: DesktopIpcModuleContext): () => void {
     },
   );
 
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.storeAttachment,
+    async (event, args: StoreAttachmentInput) => {
+      const input = parseWithSchema(storeAttachmentInputSchema, args, "storeAttachment options");
+      if (!deps.attachments || !appendTranscriptEvents) {
+        throw new Error("Attachment storage is unavailable in this build.");
+      }
+      let bytes: Uint8Array;
+      let filename: string;
+      if ("sourcePath" in input) {
+        const sourcePath = path.resolve(input.sourcePath);
+        bytes = await readAuthorizedUploadSource(
+          authorizedUploadSources,
+          uploadAuthorizationOwnerKey(event),
+          sourcePath,
+        );
+        filename = input.filename ?? path.basename(sourcePath);
+      } else {
+        bytes = input.bytes;
+        filename = input.filename;
+      }
+      const { deduplicated, ...stored } = await deps.attachments.store(bytes);
+      const payload: AttachmentReferencePayload = { type: "attachment", filename, ...stored };
+      await appendTranscriptEvents([
+        {
+          ts: new Date().toISOString(),
+          threadId: input.threadId,
+          direction: "client",
+          payload,
+        },
+      ]);
+      return { ...stored, filename, deduplicated };
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getAttachment,
+    async (_event, args: GetAttachmentInput) => {
+      const input = parseWithSchema(getAttachmentInputSchema, args, "getAttachment options");
+      return (await deps.attachments?.read(input.hash)) ?? null;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getThumbnail,
+    async (_event, args: GetThumbnailInput) => {
+      const input = parseWithSchema(getThumbnailInputSchema, args, "getThumbnail options");
+      return (await deps.attachmentThumbnails?.get(input.hash, input.maxDim)) ?? null;
+    },
+  );
+
   handleDesktopInvoke(
     DESKTOP_IPC_CHANNELS.createDirectory,
     async (_event, args: CreateDirectoryInput) => {
@@ -748,6 +824,33 @@ async function copyFileToWorkspaceUploads(
   }
 }
 
+/** Consumes the picker's authorization for `sourcePath` and reads the file it vouched for. */
+async function readAuthorizedUploadSource(
+  authorizedUploadSources: AuthorizedUploadSources,
+  uploadAuthorizationOwnerKey: UploadAuthorizationOwnerKey,
+  sourcePath: string,
+): Promise<Buffer> {
+  const authorizedSource = consumeAuthorizedUploadSource(
+    authorizedUploadSources,
+    uploadAuthorizationOwnerKey,
+    sourcePath,
+  );
+  if (!authorizedSource) {
+    throw new Error(
+      "Upload source path is not authorized. Select the file through the desktop file picker.",
+    );
+  }
+  if (authorizedSource.size > MAX_ATTACHMENT_UPLOAD_BYTE_SIZE) {
+    throw new Error("File too large to upload (max 100MB)");
+  }
+  const sourceHandle = await openAuthorizedUploadSource(sourcePath, authorizedSource);
+  try {
+    return await sourceHandle.readFile();
+  } finally {
+    await sourceHandle.close();
+  }
+}
+
 async function openAuthorizedUploadSource(
   sourcePath: string,
   authorizedSource: AuthorizedUploadSource,
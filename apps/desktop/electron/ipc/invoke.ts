@@ -2,11 +2,17 @@ import { type IpcMainInvokeEvent, ipcMain } from "electron";
 
 import { assertTrustedSender } from "./trustedSender";
 
+const inFlightInvocations = new Set<Promise<unknown>>();
+let localizeError: ((error: Error) => Error) | null = null;
+
+/** Rewrites errors crossing IPC in the user's language; null sends them as thrown. */
+export function setDesktopErrorLocalizer(localize: ((error: Error) => Error) | null): void {
+  localizeError = localize;
+}
+
 function toIpcError(error: unknown): Error {
-  if (error instanceof Error) {
-    return error;
-  }
-  return new Error(String(error));
+  const ipcError = error instanceof Error ? error : new Error(String(error));
+  return localizeError?.(ipcError) ?? ipcError;
 }
 
 export function handleDesktopInvoke<TArgs extends unknown[], TResult>(
@@ -16,9 +22,20 @@ export function handleDesktopInvoke<TArgs extends unknown[], TResult>(
   ipcMain.handle(channel, async (event, ...args) => {
     try {
       assertTrustedSender(event);
-      return await handler(event, ...(args as TArgs));
+      const invocation = Promise.resolve(handler(event, ...(args as TArgs)));
+      inFlightInvocations.add(invocation);
+      try {
+        return await invocation;
+      } finally {
+        inFlightInvocations.delete(invocation);
+      }
     } catch (error) {
       throw toIpcError(error);
     }
   });
 }
+
+/** Resolves once every desktop command that was in flight when called has settled. */
+export async function drainDesktopInvocations(): Promise<void> {
+  await Promise.allSettled([...inFlightInvocations]);
+}
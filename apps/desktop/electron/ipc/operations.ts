@@ -0,0 +1,131 @@
+import {
+  type ConfigureBackupSyncInput,
+  DESKTOP_IPC_CHANNELS,
+  type OperationIdInput,
+  type SaveBackupCredentialsInput,
+  type StartOperationInput,
+} from "../../src/lib/desktopApi";
+import {
+  configureBackupSyncInputSchema,
+  operationIdInputSchema,
+  saveBackupCredentialsInputSchema,
+  startOperationInputSchema,
+} from "../../src/lib/desktopSchemas";
+import type { DesktopIpcModuleContext, WorkspaceOperationTasks } from "./types";
+
+export function registerOperationsIpc(
+  context: DesktopIpcModuleContext,
+  workspaceTasks: WorkspaceOperationTasks,
+): void {
+  const { deps, handleDesktopInvoke, parseWithSchema } = context;
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.startOperation,
+    async (event, args: StartOperationInput) => {
+      const input = parseWithSchema(startOperationInputSchema, args, "startOperation options");
+      switch (input.kind) {
+        case "createDiagnosticsBundle":
+          return deps.operations.start(input.kind, ({ signal, reportProgress }) =>
+            deps.diagnostics.createBundle({ signal, onProgress: reportProgress }),
+          );
+        case "uploadDiagnosticsBundle":
+          return deps.operations.start(input.kind, ({ signal, reportProgress }) =>
+            deps.diagnostics.uploadBundle(input.path, input.confirmed, {
+              signal,
+              onProgress: reportProgress,
+            }),
+          );
+        case "prewarmWorkspaceServers": {
+          const { kind, ...prewarm } = input;
+          return deps.operations.start(kind, (operation) =>
+            workspaceTasks.prewarmWorkspaceServers(event.sender, prewarm, operation),
+          );
+        }
+        case "importAppData": {
+          const { kind, ...options } = input;
+          return deps.operations.start(kind, (operation) =>
+            workspaceTasks.importAppData(event.sender, options, operation),
+          );
+        }
+        case "encryptExistingTranscripts":
+          return deps.operations.start(input.kind, ({ signal, reportProgress }) =>
+            deps.persistence.encryptExistingTranscripts({ signal, onProgress: reportProgress }),
+          );
+        case "migrateTranscriptStore":
+          return deps.operations.start(input.kind, ({ signal, reportProgress }) =>
+            deps.persistence.migrateTranscriptStore(input.target, {
+              signal,
+              onProgress: reportProgress,
+            }),
+          );
+      }
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.listOperations, async () => deps.operations.list());
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getOperation, async (_event, args: OperationIdInput) => {
+    const input = parseWithSchema(operationIdInputSchema, args, "getOperation options");
+    return deps.operations.get(input.operationId);
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.cancelOperation,
+    async (_event, args: OperationIdInput) => {
+      const input = parseWithSchema(operationIdInputSchema, args, "cancelOperation options");
+      return deps.operations.cancel(input.operationId);
+    },
+  );
+
+  const requireTranscriptBackup = () => {
+    if (!deps.transcriptBackup) {
+      throw new Error("Backup sync is unavailable in this build.");
+    }
+    return deps.transcriptBackup;
+  };
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.saveBackupCredentials,
+    async (_event, args: SaveBackupCredentialsInput) => {
+      const input = parseWithSchema(
+        saveBackupCredentialsInputSchema,
+        args,
+        "saveBackupCredentials options",
+      );
+      return await requireTranscriptBackup().saveCredentials(input.credentials);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.configureBackupSync,
+    async (_event, args: ConfigureBackupSyncInput) => {
+      const input = parseWithSchema(
+        configureBackupSyncInputSchema,
+        args,
+        "configureBackupSync options",
+      );
+      return await requireTranscriptBackup().configure(input);
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getBackupSyncConfig, async () =>
+    requireTranscriptBackup().getConfig(),
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.syncBackupNow, async () => {
+    const backup = requireTranscriptBackup();
+    const running = deps.operations
+      .list()
+      .find(
+        (snapshot) =>
+          snapshot.kind === "syncBackup" &&
+          (snapshot.status === "queued" || snapshot.status === "running"),
+      );
+    return (
+      running ??
+      deps.operations.start("syncBackup", ({ signal, reportProgress }) =>
+        backup.syncNow({ signal, onProgress: reportProgress }),
+      )
+    );
+  });
+}
@@ -1,27 +1,54 @@
 import { app, BrowserWindow, dialog, Notification, shell } from "electron";
 
 import {
+  type AppendVoiceAudioInput,
   type CaptureProductEventInput,
   type ConfirmActionInput,
+  type CreateProfileInput,
   DESKTOP_IPC_CHANNELS,
   type DesktopNotificationInput,
   type DiagnosticsBundlePathInput,
+  type GetUsageSummaryInput,
   type OpenExternalUrlInput,
+  type PreviewTranscriptRetentionInput,
+  type ReadAppLogsInput,
   type RendererLogInput,
+  type SetLocaleInput,
+  type SetLogLevelInput,
   type SetWindowAppearanceInput,
+  type StartVoiceCaptureInput,
+  type StopVoiceCaptureInput,
+  type SwitchProfileInput,
   type TelemetryStatusInput,
+  type TestProxyConnectivityInput,
+  type UpdateAppSettingsInput,
   type UploadDiagnosticsBundleInput,
+  type ValidateProviderCredentialsInput,
 } from "../../src/lib/desktopApi";
+import { describeDesktopBackendCapabilities } from "../../src/lib/desktopIpcContract";
 import {
+  appendVoiceAudioInputSchema,
   captureProductEventInputSchema,
   confirmActionInputSchema,
+  createProfileInputSchema,
   desktopNotificationInputSchema,
   diagnosticsBundlePathInputSchema,
+  getUsageSummaryInputSchema,
   openExternalUrlInputSchema,
+  previewTranscriptRetentionInputSchema,
+  readAppLogsInputSchema,
   rendererLogInputSchema,
+  setLocaleInputSchema,
+  setLogLevelInputSchema,
   setWindowAppearanceInputSchema,
+  startVoiceCaptureInputSchema,
+  stopVoiceCaptureInputSchema,
+  switchProfileInputSchema,
   telemetryStatusInputSchema,
+  testProxyConnectivityInputSchema,
+  updateAppSettingsInputSchema,
   uploadDiagnosticsBundleInputSchema,
+  validateProviderCredentialsInputSchema,
 } from "../../src/lib/desktopSchemas";
 import {
   applyThemeSourcePreference,
@@ -29,13 +56,42 @@ import {
   getSystemAppearanceSnapshot,
 } from "../services/appearance";
 import { buildConfirmDialog } from "../services/dialogs";
-import { writeLocalLog } from "../services/localLogs";
+import { describeLocale } from "../services/messageCatalog";
+import {
+  getLocalLogLevel,
+  logInfo,
+  readLocalLogs,
+  setLocalLogLevel,
+  writeLocalLog,
+} from "../services/localLogs";
+import { testProxyConnectivity } from "../services/networkProxy";
 import { resolveDesktopTelemetryStatus } from "../services/telemetryStatus";
 import type { DesktopIpcModuleContext } from "./types";
 
 export function registerSystemIpc(context: DesktopIpcModuleContext): void {
   const { handleDesktopInvoke, parseWithSchema } = context;
 
+  const requireOnboarding = () => {
+    if (!context.deps.onboarding) {
+      throw new Error("Setup checks are unavailable in this build.");
+    }
+    return context.deps.onboarding;
+  };
+
+  const requireProfile = () => {
+    if (!context.deps.profile) {
+      throw new Error("Profiles are unavailable in this build.");
+    }
+    return context.deps.profile;
+  };
+
+  const requireVoiceCapture = () => {
+    if (!context.deps.voiceCapture) {
+      throw new Error("Dictation is unavailable in this build.");
+    }
+    return context.deps.voiceCapture;
+  };
+
   handleDesktopInvoke(
     DESKTOP_IPC_CHANNELS.confirmAction,
     async (event, args: ConfirmActionInput) => {
@@ -64,6 +120,9 @@ export function registerSystemIpc(context: DesktopIpcModuleContext): void {
       if (!Notification.isSupported()) {
         return false;
       }
+      if (!(await context.deps.appSettings.get()).notificationsEnabled) {
+        return false;
+      }
       const notification = new Notification({
         title: input.title.trim(),
         body: input.body?.trim(),
@@ -88,6 +147,20 @@ export function registerSystemIpc(context: DesktopIpcModuleContext): void {
     },
   );
 
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.setLogLevel, async (_event, args: SetLogLevelInput) => {
+    const { level } = parseWithSchema(setLogLevelInputSchema, args, "setLogLevel options");
+    const previous = getLocalLogLevel();
+    setLocalLogLevel(level);
+    if (level !== previous) {
+      logInfo("logging", "log level changed", { previous, level });
+    }
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.readAppLogs, async (_event, args?: ReadAppLogsInput) => {
+    const input = parseWithSchema(readAppLogsInputSchema, args ?? {}, "readAppLogs options");
+    return await readLocalLogs(input);
+  });
+
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.createDiagnosticsBundle, async () => {
     return await context.deps.diagnostics.createBundle();
   });
@@ -120,6 +193,26 @@ export function registerSystemIpc(context: DesktopIpcModuleContext): void {
     },
   );
 
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.validateProviderCredentials,
+    async (_event, args: ValidateProviderCredentialsInput) => {
+      const input = parseWithSchema(
+        validateProviderCredentialsInputSchema,
+        args,
+        "validateProviderCredentials options",
+      );
+      return await context.deps.providerCredentials.validate(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getUsageSummary,
+    async (_event, args: GetUsageSummaryInput) => {
+      const input = parseWithSchema(getUsageSummaryInputSchema, args, "getUsageSummary options");
+      return await context.deps.usageAnalytics.getSummary(input);
+    },
+  );
+
   handleDesktopInvoke(
     DESKTOP_IPC_CHANNELS.getTelemetryStatus,
     async (_event, args: TelemetryStatusInput | undefined) => {
@@ -165,6 +258,103 @@ export function registerSystemIpc(context: DesktopIpcModuleContext): void {
     },
   );
 
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getAppSettings, async () => {
+    return await context.deps.appSettings.get();
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.updateAppSettings,
+    async (_event, args: UpdateAppSettingsInput) => {
+      const patch = parseWithSchema(updateAppSettingsInputSchema, args, "updateAppSettings patch");
+      return await context.deps.appSettings.update(patch);
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.listProfiles, async () => {
+    const { store, profileId } = requireProfile();
+    return store.list(profileId);
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.createProfile,
+    async (_event, args: CreateProfileInput) => {
+      const input = parseWithSchema(createProfileInputSchema, args, "createProfile options");
+      const profile = requireProfile().store.create(input.name);
+      logInfo("profiles", "profile created", { profileId: profile.id });
+      return profile;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.switchProfile,
+    async (_event, args: SwitchProfileInput) => {
+      const input = parseWithSchema(switchProfileInputSchema, args, "switchProfile options");
+      const { store, profileId } = requireProfile();
+      if (!store.has(input.profileId)) {
+        throw new Error(`Unknown profile: ${input.profileId}`);
+      }
+      if (input.profileId === profileId) {
+        return;
+      }
+      const relaunch = context.deps.relaunchIntoProfile;
+      if (!relaunch) {
+        throw new Error("Switching profiles is unavailable in this build.");
+      }
+      store.setLastUsed(input.profileId);
+      logInfo("profiles", "switching profile", { from: profileId, to: input.profileId });
+      // Let the reply reach the renderer before this instance starts quitting.
+      setImmediate(() => relaunch(input.profileId));
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.previewTranscriptRetention,
+    async (_event, args?: PreviewTranscriptRetentionInput) => {
+      const input = parseWithSchema(
+        previewTranscriptRetentionInputSchema,
+        args ?? {},
+        "previewTranscriptRetention options",
+      );
+      return await context.deps.transcriptRetention.preview(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.testProxyConnectivity,
+    async (_event, args: TestProxyConnectivityInput) => {
+      const input = parseWithSchema(
+        testProxyConnectivityInputSchema,
+        args,
+        "testProxyConnectivity options",
+      );
+      return await testProxyConnectivity(input, await context.deps.appSettings.get());
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.flushBufferedTranscripts, async () => {
+    return await context.deps.persistence.flushBufferedTranscripts();
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getConnectivityStatus, async () => {
+    const connectivity = context.deps.connectivity;
+    if (!connectivity) {
+      throw new Error("Connectivity monitoring is unavailable in this build.");
+    }
+    return connectivity.getStatus();
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.verifyInstallation, async () => {
+    return await context.deps.serverManager.verifyInstallation();
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getOnboardingStatus, async () => {
+    return await requireOnboarding().getStatus();
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.runEnvironmentChecks, async () => {
+    return await requireOnboarding().runEnvironmentChecks();
+  });
+
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getUpdateState, async () => {
     return context.deps.updater.getState();
   });
@@ -185,13 +375,32 @@ export function registerSystemIpc(context: DesktopIpcModuleContext): void {
   );
 
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.checkForUpdates, async () => {
-    await context.deps.updater.checkForUpdates();
+    await Promise.all([
+      context.deps.updater.checkForUpdates(),
+      context.deps.sidecarUpdater?.check(),
+    ]);
   });
 
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.quitAndInstallUpdate, async () => {
     context.deps.updater.quitAndInstall();
   });
 
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getSidecarUpdateState, async () => {
+    const sidecarUpdater = context.deps.sidecarUpdater;
+    if (!sidecarUpdater) {
+      throw new Error("Server updates are unavailable in this build.");
+    }
+    return await sidecarUpdater.getState();
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.applySidecarUpdate, async () => {
+    const sidecarUpdater = context.deps.sidecarUpdater;
+    if (!sidecarUpdater) {
+      throw new Error("Server updates are unavailable in this build.");
+    }
+    return await sidecarUpdater.apply();
+  });
+
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getSystemAppearance, async () => {
     return getSystemAppearanceSnapshot();
   });
@@ -212,9 +421,52 @@ export function registerSystemIpc(context: DesktopIpcModuleContext): void {
           : getSystemAppearanceSnapshot()
         : applyWindowAppearance(ownerWindow, input);
       if (input.themeSource) {
-        await context.deps.appearancePreferences.saveThemeSource(input.themeSource);
+        await context.deps.appSettings.update({ themeSource: input.themeSource });
       }
       return appearance;
     },
   );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.setLocale, async (_event, args: SetLocaleInput) => {
+    const input = parseWithSchema(setLocaleInputSchema, args, "setLocale options");
+    // The main process's appSettings subscription switches the message catalog.
+    const settings = await context.deps.appSettings.update({ locale: input.locale });
+    return describeLocale(settings.locale, app.getLocale());
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getBackendCapabilities, () =>
+    describeDesktopBackendCapabilities(),
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.startVoiceCapture,
+    async (_event, args: StartVoiceCaptureInput) => {
+      const input = parseWithSchema(
+        startVoiceCaptureInputSchema,
+        args,
+        "startVoiceCapture options",
+      );
+      return requireVoiceCapture().start(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.appendVoiceAudio,
+    async (_event, args: AppendVoiceAudioInput) => {
+      const input = parseWithSchema(appendVoiceAudioInputSchema, args, "appendVoiceAudio options");
+      requireVoiceCapture().append(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.stopVoiceCapture,
+    async (_event, args: StopVoiceCaptureInput | undefined) => {
+      const input = parseWithSchema(
+        stopVoiceCaptureInputSchema,
+        args ?? {},
+        "stopVoiceCapture options",
+      );
+      return (await context.deps.voiceCapture?.stop(input)) ?? null;
+    },
+  );
 }
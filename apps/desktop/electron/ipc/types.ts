@@ -3,34 +3,119 @@ import type { z } from "zod";
 import type { CloudSyncStatus } from "../../../../src/sync/types";
 import type { PersistedState } from "../../src/app/types";
 import type {
+  DesktopDeepLinkNavigation,
   DesktopMenuCommand,
+  ImportAppDataInput,
+  ImportAppDataResult,
+  PrewarmWorkspaceServersInput,
+  PrewarmWorkspaceServersResult,
   ShowCanvasWindowInput,
   ShowQuickChatWindowInput,
+  TranscriptBatchInput,
   WindowCloseResponseInput,
 } from "../../src/lib/desktopApi";
-import type { AppearancePreferences } from "../services/appearancePreferences";
+import type { AppDataTransferService } from "../services/appDataTransfer";
+import type { AttachmentStore } from "../services/attachmentStore";
+import type { AttachmentThumbnailer } from "../services/attachmentThumbnails";
+import type { ApprovalQueue } from "../services/approvalQueue";
+import type { AppSettingsStore } from "../services/appSettings";
+import type { ConnectivityMonitor } from "../services/connectivityMonitor";
 import type { DiagnosticsService } from "../services/diagnostics";
+import type { IsolatedSessionPool } from "../services/isolatedSessions";
 import type { MobileRelayBridge } from "../services/mobileRelayBridge";
+import type { ModelCatalogService } from "../services/modelCatalog";
+import type { OnboardingService } from "../services/onboarding";
+import type { OperationContext, OperationManager } from "../services/operations";
+import type { OrphanedServerService } from "../services/orphanedServers";
 import type { PersistenceService } from "../services/persistence";
+import type { ServerPowerManager } from "../services/powerManagement";
 import type { DesktopProductAnalyticsService } from "../services/productAnalytics";
+import type { LaunchProfile } from "../services/profiles";
+import type { PromptLibraryStore } from "../services/promptLibrary";
+import type { ProtocolReplayService } from "../services/protocolReplay";
+import type { ProviderCredentialValidator } from "../services/providerCredentials";
+import type { ScheduledTaskService } from "../services/scheduledTasks";
 import type { ServerManager } from "../services/serverManager";
+import type { SidecarUpdater } from "../services/sidecarUpdater";
+import type { SshRemoteWorkspaces } from "../services/sshRemoteWorkspaces";
+import type { StorageGuard } from "../services/storageGuard";
+import type { ThreadDraftStore } from "../services/threadDrafts";
+import type { TranscriptAnnotationStore } from "../services/transcriptAnnotations";
+import type { TranscriptBackupService } from "../services/transcriptBackup";
+import type { TranscriptMirrorService } from "../services/transcriptMirror";
+import type { TranscriptRetentionService } from "../services/transcriptRetention";
+import type { TranscriptSubscriptions } from "../services/transcriptSubscriptions";
+import type { TranscriptThrottle } from "../services/transcriptThrottle";
+import type { TranscriptViewerRegistry } from "../services/transcriptViewer";
+import type { TrashService } from "../services/trash";
+import type { UsageAnalyticsService } from "../services/usageAnalytics";
 import type { DesktopUpdaterService } from "../services/updater";
+import type { VoiceCaptureService } from "../services/voiceCapture";
 import type { NativeCloseWebContents } from "../services/windowCloseCoordinator";
+import type { WorkspaceCheckpointService } from "../services/workspaceCheckpoints";
+import type { WorkspaceConfigService } from "../services/workspaceConfig";
+import type { WorkspaceDirectoryWatcher } from "../services/workspaceDirectoryWatcher";
+import type { WorkspaceGitService } from "../services/workspaceGit";
+import type { WorkspaceHealthService } from "../services/workspaceHealth";
+import type { YoloGrants } from "../services/yoloGrants";
 
 export type DesktopIpcDeps = {
-  appearancePreferences: AppearancePreferences;
+  appDataTransfer?: AppDataTransferService;
+  approvalQueue?: ApprovalQueue;
+  appSettings: AppSettingsStore;
+  attachments?: AttachmentStore;
+  attachmentThumbnails?: AttachmentThumbnailer;
   mobileRelayBridge: MobileRelayBridge;
   persistence: PersistenceService;
   productAnalytics?: DesktopProductAnalyticsService;
+  /** The profile this instance launched into, and the registry of the others. */
+  profile?: LaunchProfile;
+  /** Quits and starts the app again in `profileId`. */
+  relaunchIntoProfile?: (profileId: string) => void;
   cloudSync?: {
     enqueuePersistedState(state: PersistedState): Promise<unknown> | unknown;
     getStatus?(): CloudSyncStatus;
   };
+  connectivity?: ConnectivityMonitor;
   diagnostics: DiagnosticsService;
+  isolatedSessions?: IsolatedSessionPool;
+  modelCatalog?: ModelCatalogService;
+  onboarding?: OnboardingService;
+  operations: OperationManager;
+  orphanedServers: OrphanedServerService;
+  promptLibrary?: PromptLibraryStore;
+  protocolReplay: ProtocolReplayService;
+  providerCredentials: ProviderCredentialValidator;
+  remoteWorkspaces?: SshRemoteWorkspaces;
+  scheduledTasks?: ScheduledTaskService;
   serverManager: ServerManager;
+  serverPower?: ServerPowerManager;
+  sidecarUpdater?: SidecarUpdater;
+  storageGuard?: StorageGuard;
+  threadDrafts?: ThreadDraftStore;
+  transcriptAnnotations?: TranscriptAnnotationStore;
+  transcriptBackup?: TranscriptBackupService;
+  /** Re-rendered after a transcript is rewritten in place, e.g. by redaction. */
+  transcriptMirror?: TranscriptMirrorService;
+  transcriptRetention: TranscriptRetentionService;
+  transcriptSubscriptions: TranscriptSubscriptions;
+  /** Caps what live appends may write per thread; bulk imports bypass it. */
+  transcriptThrottle?: TranscriptThrottle;
+  /** Shared with the launch handling that opens exported threads from the OS. */
+  transcriptViewer?: TranscriptViewerRegistry;
+  trash?: TrashService;
   updater: DesktopUpdaterService;
+  usageAnalytics: UsageAnalyticsService;
+  voiceCapture?: VoiceCaptureService;
+  workspaceCheckpoints: WorkspaceCheckpointService;
+  workspaceConfig: WorkspaceConfigService;
+  workspaceGit: WorkspaceGitService;
+  workspaceHealth: WorkspaceHealthService;
+  /** Without it, `yolo` is taken from the request as before. */
+  yoloGrants?: YoloGrants;
   showMainWindow: () => Promise<void> | void;
   consumePendingMenuCommands: () => DesktopMenuCommand[];
+  consumePendingDeepLinks?: () => DesktopDeepLinkNavigation[];
   showQuickChatWindow: (opts?: ShowQuickChatWindowInput) => Promise<void> | void;
   showCanvasWindow: (opts: ShowCanvasWindowInput) => Promise<void> | void;
   resolveWindowCloseRequest: (
@@ -47,6 +132,11 @@ export type DesktopIpcDeps = {
    * controller is created.
    */
   workspaceRoots?: WorkspaceRootsAccess;
+  /**
+   * Shared with services that follow workspace file changes (git status) so a
+   * folder the explorer is already watching is not watched twice.
+   */
+  directoryWatcher?: WorkspaceDirectoryWatcher;
 };
 
 type HandleDesktopInvoke = <TArgs extends unknown[], TResult>(
@@ -61,6 +151,8 @@ export interface WorkspaceRootsAccess {
   refreshApprovedWorkspaceRootsFromState(state: PersistedState): Promise<void>;
   assertApprovedWorkspacePath(workspacePath: string): Promise<string>;
   addApprovedWorkspacePath(workspacePath: string): Promise<string>;
+  assertApprovedServerPath(workspacePath: string): Promise<string>;
+  addApprovedRemoteWorkspace(url: string): string;
   setApprovedWorkspaceRoots(paths: Iterable<string>): void;
   getApprovedWorkspaceRoots(): string[];
 }
@@ -71,3 +163,26 @@ export type DesktopIpcModuleContext = {
   handleDesktopInvoke: HandleDesktopInvoke;
   parseWithSchema: ParseWithSchema;
 };
+
+type OperationTaskContext = Pick<OperationContext, "signal" | "reportProgress">;
+
+/**
+ * Appends events the way renderer appends are written: read-only viewer
+ * threads are refused and scratch threads stay in memory.
+ */
+export type TranscriptAppender = (events: TranscriptBatchInput[]) => Promise<void>;
+
+/** Workspace commands that can also run as cancellable background operations. */
+export type WorkspaceOperationTasks = {
+  prewarmWorkspaceServers(
+    sender: IpcMainInvokeEvent["sender"],
+    input: PrewarmWorkspaceServersInput,
+    context?: OperationTaskContext,
+  ): Promise<PrewarmWorkspaceServersResult>;
+  /** Resolves to null when the user cancels the file picker. */
+  importAppData(
+    sender: IpcMainInvokeEvent["sender"],
+    input: ImportAppDataInput,
+    context?: OperationTaskContext,
+  ): Promise<ImportAppDataResult | null>;
+};
@@ -195,6 +195,10 @@ export function registerWindowIpc(context: DesktopIpcModuleContext): void {
     return deps.consumePendingMenuCommands();
   });
 
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.consumePendingDeepLinks, () => {
+    return deps.consumePendingDeepLinks?.() ?? [];
+  });
+
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.showCanvasWindow, async (_event, args) => {
     const input = parseWithSchema(
       showCanvasWindowInputSchema,
@@ -1,32 +1,222 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
 import * as electron from "electron";
 import { BrowserWindow } from "electron";
 import { z } from "zod";
 import { createOneOffChatWorkspace } from "../../../../src/utils/oneOffChats";
+import { parseSshWorkspaceUrl } from "../../src/app/remoteWorkspaces";
+import { isStandardChatThread } from "../../src/app/threadFilters";
 import { hydrateTranscriptSnapshot } from "../../src/app/transcriptHydration";
-import type { PersistedState } from "../../src/app/types";
+import type { PersistedState, ThreadRecord } from "../../src/app/types";
+import { evaluatePathPolicy, pathPolicyEnvValue } from "../../src/app/workspacePathPolicy";
+import { transcriptMirrorApprovalPath } from "../../src/app/workspaceTranscriptMirror";
 import {
+  type AnnotateEventInput,
+  type ArchiveThreadInput,
+  type CleanupWorkspaceStorageInput,
+  type CleanupWorkspaceStorageResult,
+  type CopyTranscriptSelectionInput,
   type CreateOneOffChatWorkspaceInput,
+  type CreateScheduledTaskInput,
+  type CreateScratchThreadInput,
+  type DeletePromptTemplateInput,
+  type DeleteScheduledTaskInput,
   DESKTOP_EVENT_CHANNELS,
   DESKTOP_IPC_CHANNELS,
   type DeleteTranscriptInput,
+  type DeleteWorkspaceInput,
+  type DestructiveCommand,
+  type DestructiveCommandResult,
+  type DuplicateWorkspaceInput,
+  type EnqueueExportInput,
+  type ExportAppDataInput,
+  type ForkThreadInput,
+  type GenerateHandoffLinkInput,
+  type GetThreadFileChangesInput,
+  type GetThreadPreviewsInput,
+  type GetWorkspaceStorageReportInput,
+  type GlobalSearchInput,
+  type ImportAppDataInput,
+  type ListAnnotationsInput,
+  type ListAvailableModelsInput,
+  type ListPendingApprovalsInput,
+  type ListPromptTemplatesInput,
+  type LoadThreadDraftInput,
+  type MergeThreadsInput,
+  type MergeThreadsResult,
+  type MigrateTranscriptStoreInput,
+  type OpenTranscriptFileInput,
+  type PersistScratchThreadInput,
+  type PreviewPathPolicyInput,
+  type PrewarmWorkspaceServersInput,
+  type PurgeThreadsInput,
   type ReadTranscriptInput,
+  type ReadTranscriptPageInput,
+  type ReadWorkspaceConfigInput,
+  type RebuildStateInput,
+  type RecordPendingApprovalInput,
+  type RedactTranscriptInput,
+  type RegisterRemoteWorkspaceInput,
+  type ReleaseThreadServerInput,
+  type RenderPromptInput,
+  type ReorderWorkspacesInput,
+  type RequestYoloGrantInput,
+  type ResolveApprovalInput,
+  type ResolveDroppedWorkspaceFolderInput,
+  type RestoreFromTrashInput,
+  type RestoreStateSnapshotInput,
+  type RestoreWorkspaceCheckpointInput,
+  type RevokeHandoffInput,
+  type SavePromptTemplateInput,
+  type SaveStateResult,
+  type SaveThreadDraftInput,
+  type ScanForWorkspacesInput,
+  type SetScheduledTaskEnabledInput,
+  type SetTranscriptEncryptionInput,
+  type SetWorkspaceIdlePolicyInput,
+  type SetWorkspaceLaunchProfilesInput,
+  type SetWorkspacePathPolicyInput,
+  type SetWorkspacePinnedInput,
+  type SetWorkspaceTagsInput,
+  type ShareTranscriptInput,
+  type StartReplayInput,
   type StartWorkspaceServerInput,
+  type StartWorkspaceServerResult,
+  type StopReplayInput,
   type StopWorkspaceServerInput,
+  type SuggestThreadTitleInput,
   type TranscriptBatchInput,
+  type WorkspaceServerPrewarmProgress,
+  type WorkspaceServerPrewarmResult,
+  type WorkspaceStorageCleanupTarget,
+  type WriteWorkspaceConfigInput,
 } from "../../src/lib/desktopApi";
 import {
+  annotateEventInputSchema,
+  archiveThreadInputSchema,
+  cleanupWorkspaceStorageInputSchema,
+  copyTranscriptSelectionInputSchema,
   createOneOffChatWorkspaceInputSchema,
+  createScheduledTaskInputSchema,
+  createScratchThreadInputSchema,
+  deletePromptTemplateInputSchema,
+  deleteScheduledTaskInputSchema,
   deleteTranscriptInputSchema,
+  deleteWorkspaceInputSchema,
+  duplicateWorkspaceInputSchema,
+  enqueueExportInputSchema,
+  exportAppDataInputSchema,
+  forkThreadInputSchema,
+  generateHandoffLinkInputSchema,
+  getThreadFileChangesInputSchema,
+  getThreadPreviewsInputSchema,
+  getWorkspaceStorageReportInputSchema,
+  globalSearchInputSchema,
+  importAppDataInputSchema,
+  listAnnotationsInputSchema,
+  listAvailableModelsInputSchema,
+  listPendingApprovalsInputSchema,
+  listPromptTemplatesInputSchema,
+  loadThreadDraftInputSchema,
+  mergeThreadsInputSchema,
+  migrateTranscriptStoreInputSchema,
+  openTranscriptFileInputSchema,
   persistedStateInputSchema,
+  persistScratchThreadInputSchema,
+  previewPathPolicyInputSchema,
+  prewarmWorkspaceServersInputSchema,
+  purgeThreadsInputSchema,
   readTranscriptInputSchema,
+  readTranscriptPageInputSchema,
+  readWorkspaceConfigInputSchema,
+  rebuildStateInputSchema,
+  recordPendingApprovalInputSchema,
+  redactTranscriptInputSchema,
+  registerRemoteWorkspaceInputSchema,
+  releaseThreadServerInputSchema,
+  renderPromptInputSchema,
+  reorderWorkspacesInputSchema,
+  resolveApprovalInputSchema,
+  resolveDroppedWorkspaceFolderInputSchema,
+  restoreFromTrashInputSchema,
+  restoreStateSnapshotInputSchema,
+  requestYoloGrantInputSchema,
+  restoreWorkspaceCheckpointInputSchema,
+  revokeHandoffInputSchema,
+  savePromptTemplateInputSchema,
+  saveThreadDraftInputSchema,
+  scanForWorkspacesInputSchema,
+  setScheduledTaskEnabledInputSchema,
+  setTranscriptEncryptionInputSchema,
+  setWorkspaceIdlePolicyInputSchema,
+  setWorkspaceLaunchProfilesInputSchema,
+  setWorkspacePathPolicyInputSchema,
+  setWorkspacePinnedInputSchema,
+  setWorkspaceTagsInputSchema,
+  shareTranscriptInputSchema,
+  startReplayInputSchema,
   startWorkspaceServerInputSchema,
+  stopReplayInputSchema,
   stopWorkspaceServerInputSchema,
+  suggestThreadTitleInputSchema,
   transcriptBatchInputSchema,
+  writeWorkspaceConfigInputSchema,
 } from "../../src/lib/desktopSchemas";
-import type { DesktopIpcModuleContext } from "./types";
+import { APP_DATA_ARCHIVE_EXTENSION } from "../services/appDataTransfer";
+import { withConnectivityContext } from "../services/connectivityMonitor";
+import { DestructiveConfirmations } from "../services/destructiveConfirmations";
+import { buildConfirmDialog } from "../services/dialogs";
+import { resolveDroppedWorkspaceFolder } from "../services/droppedWorkspaceFolder";
+import { globalSearch } from "../services/globalSearch";
+import { logInfo } from "../services/localLogs";
+import { EXPORT_OPERATION_QUEUE } from "../services/operations";
+import { StateMergeRequiredError } from "../services/persistence";
+import { ScratchThreadRegistry } from "../services/scratchThreads";
+import { heuristicThreadTitle } from "../services/stateRebuild";
+import {
+  readWorkspaceEnvSecrets,
+  redactTranscriptText,
+  renderTranscriptMarkdown,
+} from "../services/transcriptCopy";
+import {
+  emptyTranscriptIndex,
+  recordIndexedEvent,
+  toThreadPreview,
+} from "../services/transcriptIndex";
+import {
+  decodeTranscriptCursor,
+  pageTranscriptEvents,
+  resolveTranscriptPageLimits,
+} from "../services/transcriptPages";
+import {
+  isViewerThreadId,
+  ReadOnlyThreadError,
+  renderTranscriptExport,
+  TRANSCRIPT_EXPORT_EXTENSION,
+  TranscriptViewerRegistry,
+} from "../services/transcriptViewer";
+import { summarizeThreadFileChanges } from "../services/threadFileChanges";
+import { scanForWorkspaces } from "../services/workspaceDiscovery";
+import { copyWorkspaceDirectory } from "../services/workspaceDuplicate";
+import type {
+  DesktopIpcModuleContext,
+  TranscriptAppender,
+  WorkspaceOperationTasks,
+} from "./types";
+import { approvedPathsForWorkspace } from "./workspaceRoots";
 
 type DesktopWindowMode = "main" | "quick-chat" | "utility";
 
+const DEFAULT_WORKSPACE_SERVER_PREWARM_CONCURRENCY = 3;
+const DEFAULT_HANDOFF_TTL_SECONDS = 15 * 60;
+
+type DesktopEventSender = {
+  id?: number;
+  isDestroyed(): boolean;
+  send(channel: string, payload: unknown): void;
+};
+
 function resolveDesktopWindowMode(event: {
   sender?: { getURL?: () => string };
 }): DesktopWindowMode {
@@ -44,6 +234,31 @@ function resolveDesktopWindowMode(event: {
   }
 }
 
+type ScratchOwnerEvent = {
+  sender?: { id?: number; once?: (event: "destroyed", listener: () => void) => unknown };
+};
+
+function resolveScratchOwnerId(event: ScratchOwnerEvent): number {
+  return typeof event.sender?.id === "number" ? event.sender.id : 0;
+}
+
+/** Runs `task` for every item with at most `limit` in flight; tasks must not reject. */
+async function runWithConcurrency<T>(
+  items: readonly T[],
+  limit: number,
+  task: (item: T) => Promise<void>,
+): Promise<void> {
+  let next = 0;
+  const worker = async () => {
+    while (next < items.length) {
+      const item = items[next] as T;
+      next += 1;
+      await task(item);
+    }
+  };
+  await Promise.all(Array.from({ length: Math.min(limit, items.length) }, worker));
+}
+
 function compareIsoTimestamp(left: string, right: string): number {
   return Date.parse(left) - Date.parse(right);
 }
@@ -159,10 +374,120 @@ async function mergePopupPersistedState(
   };
 }
 
-export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
+export function registerWorkspaceIpc(context: DesktopIpcModuleContext): {
+  tasks: WorkspaceOperationTasks;
+  appendTranscriptEvents: TranscriptAppender;
+} {
   const { deps, handleDesktopInvoke, parseWithSchema, workspaceRoots } = context;
   const removedThreadIds = new Set<string>();
   const popupThreadIds = new Set<string>();
+  const scratchThreads = new ScratchThreadRegistry();
+  const viewerTranscripts = deps.transcriptViewer ?? new TranscriptViewerRegistry();
+  const scratchOwners = new Set<number>();
+  const confirmations = new DestructiveConfirmations();
+
+  deps.scheduledTasks?.onThreadRecorded((thread) => {
+    popupThreadIds.add(thread.id);
+    removedThreadIds.delete(thread.id);
+  });
+
+  const requireApprovalQueue = () => {
+    if (!deps.approvalQueue) {
+      throw new Error("The approval queue is unavailable in this build.");
+    }
+    return deps.approvalQueue;
+  };
+
+  const requireScheduledTasks = () => {
+    if (!deps.scheduledTasks) {
+      throw new Error("Scheduled tasks are unavailable in this build.");
+    }
+    return deps.scheduledTasks;
+  };
+
+  const readInMemoryTranscript = (threadId: string) =>
+    viewerTranscripts.readTranscript(threadId) ?? scratchThreads.readTranscript(threadId);
+
+  const assertWritableThreads = (events: TranscriptBatchInput[]) => {
+    const viewerEvent = events.find((event) => isViewerThreadId(event.threadId));
+    if (viewerEvent) {
+      throw new ReadOnlyThreadError(viewerEvent.threadId);
+    }
+  };
+
+  const requireAppDataTransfer = () => {
+    if (!deps.appDataTransfer) {
+      throw new Error("App data export and import are unavailable in this build.");
+    }
+    return deps.appDataTransfer;
+  };
+
+  const requireTranscriptAnnotations = () => {
+    if (!deps.transcriptAnnotations) {
+      throw new Error("Transcript annotations are unavailable in this build.");
+    }
+    return deps.transcriptAnnotations;
+  };
+
+  const requirePromptLibrary = () => {
+    if (!deps.promptLibrary) {
+      throw new Error("The prompt library is unavailable in this build.");
+    }
+    return deps.promptLibrary;
+  };
+
+  const loadThreadRecording = async (threadId: string) => {
+    const [transcript, state] = await Promise.all([
+      readInMemoryTranscript(threadId) ?? deps.persistence.readTranscript(threadId),
+      deps.persistence.loadState(),
+    ]);
+    const thread = state.threads.find((entry) => entry.id === threadId);
+    const workspace = state.workspaces.find((entry) => entry.id === thread?.workspaceId);
+    return { transcript, thread, workspacePath: workspace?.path ?? null };
+  };
+
+  // Destructive commands first answer with a challenge describing the impact
+  // and only run when called again with the token from that challenge.
+  const runConfirmedRemoval = async (
+    event: ScratchOwnerEvent,
+    command: DestructiveCommand,
+    target: { workspaceIds?: string[]; threadIds?: string[] },
+    confirmationToken: string | undefined,
+    beforeRemove?: () => Promise<void>,
+  ): Promise<DestructiveCommandResult> => {
+    const ownerId = resolveScratchOwnerId(event);
+    const targetKey = JSON.stringify([
+      [...new Set(target.workspaceIds ?? [])].sort(),
+      [...new Set(target.threadIds ?? [])].sort(),
+    ]);
+    if (!confirmationToken) {
+      const impact = await deps.persistence.measureRecordRemoval(target);
+      return {
+        status: "confirmationRequired",
+        challenge: confirmations.issue(command, targetKey, ownerId, impact),
+      };
+    }
+    confirmations.consume(command, targetKey, ownerId, confirmationToken);
+    await beforeRemove?.();
+    const previous = await deps.persistence.loadState();
+    const state = await deps.persistence.removeRecords(target);
+    trackRemovedThreadIds(removedThreadIds, previous.threads, state.threads);
+    workspaceRoots.setApprovedWorkspaceRoots(state.workspaces.flatMap(approvedPathsForWorkspace));
+    deps.applyPersistedState?.(state);
+    return { status: "completed" };
+  };
+
+  const trackScratchOwner = (event: ScratchOwnerEvent): number => {
+    const ownerId = resolveScratchOwnerId(event);
+    if (!scratchOwners.has(ownerId)) {
+      scratchOwners.add(ownerId);
+      event.sender?.once?.("destroyed", () => {
+        scratchOwners.delete(ownerId);
+        scratchThreads.releaseOwner(ownerId);
+      });
+    }
+    return ownerId;
+  };
 
   handleDesktopInvoke(
     DESKTOP_IPC_CHANNELS.createOneOffChatWorkspace,
@@ -180,33 +505,257 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
     },
   );
 
-  handleDesktopInvoke(
-    DESKTOP_IPC_CHANNELS.startWorkspaceServer,
-    async (_event, args: StartWorkspaceServerInput) => {
-      const input = parseWithSchema(
-        startWorkspaceServerInputSchema,
-        args,
-        "startWorkspaceServer options",
-      );
-      const workspacePath = await workspaceRoots.assertApprovedWorkspacePath(input.workspacePath);
-      const { preserveMobileRelay, ...serverInput } = input;
-      const shouldPreserveMobileRelay =
-        preserveMobileRelay === true &&
-        deps.mobileRelayBridge.isActiveForWorkspace(input.workspaceId);
-      const listening = await deps.serverManager.startWorkspaceServer({
+  // Threads only get their own server while the setting is on; otherwise they
+  // share the workspace's server as before.
+  const acquireThreadServer = async (workspaceId: string, threadId: string | undefined) => {
+    if (!threadId || !deps.isolatedSessions) {
+      return null;
+    }
+    if (!(await deps.appSettings.get()).isolatedSessions) {
+      return null;
+    }
+    return await deps.isolatedSessions.acquire(workspaceId, threadId);
+  };
+
+  const stopAllWorkspaceServers = async (workspaceId: string) => {
+    const isolated = deps.isolatedSessions?.drainWorkspace(workspaceId) ?? [];
+    await Promise.all(
+      [workspaceId, ...isolated].map((serverId) =>
+        deps.serverManager.stopWorkspaceServer(serverId),
+      ),
+    );
+  };
+
+  // A token turns yolo on for the workspace; without one, yolo only sticks while
+  // an earlier grant is active, and otherwise the server starts sandboxed.
+  const resolveYoloExpiry = async (
+    ownerId: number,
+    input: StartWorkspaceServerInput,
+  ): Promise<Date | null> => {
+    const grants = deps.yoloGrants;
+    if (!grants) {
+      return null;
+    }
+    if (!input.yolo) {
+      grants.revoke(input.workspaceId);
+      return null;
+    }
+    if (!input.yoloToken) {
+      return grants.activeUntil(input.workspaceId);
+    }
+    const expiresAt = await grants.redeem(input.workspaceId, ownerId, input.yoloToken);
+    logInfo("audit", "enabled yolo mode", {
+      workspaceId: input.workspaceId,
+      expiresAt: expiresAt.toISOString(),
+    });
+    return expiresAt;
+  };
+
+  const startWorkspaceServer = async (
+    sender: DesktopEventSender,
+    input: StartWorkspaceServerInput,
+  ): Promise<StartWorkspaceServerResult> => {
+    const workspacePath = await workspaceRoots.assertApprovedServerPath(input.workspacePath);
+    const yoloExpiresAt = await resolveYoloExpiry(resolveScratchOwnerId({ sender }), input);
+    const {
+      preserveMobileRelay,
+      launchProfile: launchProfileName,
+      threadId,
+      yoloToken: _yoloToken,
+      ...serverInput
+    } = input;
+    const lease = await acquireThreadServer(input.workspaceId, threadId);
+    // The mobile relay belongs to the workspace's shared server.
+    const shouldPreserveMobileRelay =
+      !lease &&
+      preserveMobileRelay === true &&
+      deps.mobileRelayBridge.isActiveForWorkspace(input.workspaceId);
+    const { workspaces } = await deps.persistence.loadState();
+    const workspace = workspaces.find((candidate) => candidate.id === input.workspaceId);
+    const resourceLimits = workspace?.resourceLimits;
+    const container = workspace?.container;
+    // Profiles come from the persisted record, which load has already validated
+    // against the flag allowlist; the renderer only picks one by name.
+    const launchProfile = launchProfileName
+      ? workspace?.launchProfiles?.find((profile) => profile.name === launchProfileName)
+      : undefined;
+    if (launchProfileName && !launchProfile) {
+      throw new Error(`Unknown launch profile: ${launchProfileName}`);
+    }
+    const additionalRoots = await Promise.all(
+      (workspace?.additionalRoots ?? []).map((rootPath) =>
+        workspaceRoots.assertApprovedWorkspacePath(rootPath),
+      ),
+    );
+    const pathPolicy = workspace ? pathPolicyEnvValue(workspace) : null;
+    const listening = await deps.serverManager
+      .startWorkspaceServer({
         ...serverInput,
+        ...(deps.yoloGrants ? { yolo: yoloExpiresAt !== null } : {}),
+        workspaceId: lease?.serverId ?? input.workspaceId,
         workspacePath,
+        ...(resourceLimits ? { resourceLimits } : {}),
+        ...(container ? { container } : {}),
+        ...(additionalRoots.length > 0 ? { additionalRoots } : {}),
+        ...(launchProfile ? { launchProfile } : {}),
+        ...(pathPolicy ? { pathPolicy } : {}),
         ...(shouldPreserveMobileRelay ? { mobileH3: true } : {}),
         productAnalyticsState: deps.productAnalytics?.getPersistedState(),
         onCoworkRuntimeBootstrapProgress: (progress) => {
-          if (_event.sender.isDestroyed()) return;
-          _event.sender.send(DESKTOP_EVENT_CHANNELS.workspaceServerStartupProgress, {
+          if (sender.isDestroyed()) return;
+          sender.send(DESKTOP_EVENT_CHANNELS.workspaceServerStartupProgress, {
             workspaceId: input.workspaceId,
             progress,
           });
         },
+      })
+      .catch(async (error: unknown) => {
+        if (lease) {
+          deps.isolatedSessions?.forget(lease.serverId);
+        }
+        // A fresh check tells "you're offline" apart from a broken server.
+        if (!deps.connectivity) {
+          throw error;
+        }
+        throw withConnectivityContext(error, await deps.connectivity.check());
+      });
+    deps.serverPower?.recordServerStarted(lease?.serverId ?? input.workspaceId);
+    return {
+      url: listening.url,
+      authToken: listening.authToken,
+      ...(deps.yoloGrants ? { yoloExpiresAt: yoloExpiresAt?.toISOString() ?? null } : {}),
+    };
+  };
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.requestYoloGrant,
+    async (event, args: RequestYoloGrantInput) => {
+      const input = parseWithSchema(requestYoloGrantInputSchema, args, "requestYoloGrant options");
+      if (!deps.yoloGrants) {
+        throw new Error("Yolo grants are unavailable in this build.");
+      }
+      const { workspaces } = await deps.persistence.loadState();
+      const workspace = workspaces.find((candidate) => candidate.id === input.workspaceId);
+      if (!workspace) {
+        throw new Error(`Unknown workspace: ${input.workspaceId}`);
+      }
+      // Asked here rather than in the renderer so a compromised renderer cannot grant itself yolo.
+      const ownerWindow =
+        BrowserWindow.fromWebContents(event.sender) ??
+        BrowserWindow.getFocusedWindow() ??
+        undefined;
+      const built = buildConfirmDialog({
+        title: "Enable YOLO mode",
+        message: `Enable YOLO mode for "${workspace.name}"?`,
+        detail:
+          "The agent will run shell commands on your machine without asking for approval and " +
+          "without the OS sandbox. Commands get full file and network access. The server will " +
+          "restart to apply this change.",
+        confirmLabel: "Enable",
+        cancelLabel: "Cancel",
+        kind: "warning",
+        defaultAction: "cancel",
+      });
+      const response = ownerWindow
+        ? await electron.dialog.showMessageBox(ownerWindow, built.options)
+        : await electron.dialog.showMessageBox(built.options);
+      if (response.response !== built.confirmButtonIndex) {
+        return null;
+      }
+      const grant = deps.yoloGrants.issue(input.workspaceId, resolveScratchOwnerId(event));
+      logInfo("audit", "issued yolo grant", {
+        workspaceId: input.workspaceId,
+        tokenExpiresAt: grant.expiresAt,
+      });
+      return grant;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.startWorkspaceServer,
+    async (_event, args: StartWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        startWorkspaceServerInputSchema,
+        args,
+        "startWorkspaceServer options",
+      );
+      return await startWorkspaceServer(_event.sender, input);
+    },
+  );
+
+  // Starts several saved workspaces at once so reopening the app does not
+  // queue one cold start behind another. Failures are reported per workspace;
+  // once cancelled, workspaces that have not started yet are skipped.
+  const prewarmWorkspaceServers: WorkspaceOperationTasks["prewarmWorkspaceServers"] = async (
+    sender,
+    input,
+    operation,
+  ) => {
+    const workspaceIds = [...new Set(input.workspaceIds)];
+    const { workspaces } = await deps.persistence.loadState();
+    const sendProgress = (progress: WorkspaceServerPrewarmProgress) => {
+      if (sender.isDestroyed()) return;
+      sender.send(DESKTOP_EVENT_CHANNELS.workspaceServerPrewarmProgress, progress);
+    };
+    for (const workspaceId of workspaceIds) {
+      sendProgress({ workspaceId, status: "queued" });
+    }
+
+    const results: Record<string, WorkspaceServerPrewarmResult> = {};
+    let settled = 0;
+    const settle = (workspaceId: string, result: WorkspaceServerPrewarmResult) => {
+      results[workspaceId] = result;
+      sendProgress({ workspaceId, ...result });
+      settled += 1;
+      operation?.reportProgress({
+        completed: settled,
+        total: workspaceIds.length,
+        message: "Starting workspace servers",
       });
-      return { url: listening.url };
+    };
+    await runWithConcurrency(
+      workspaceIds,
+      input.concurrency ?? DEFAULT_WORKSPACE_SERVER_PREWARM_CONCURRENCY,
+      async (workspaceId) => {
+        if (operation?.signal.aborted) {
+          return;
+        }
+        sendProgress({ workspaceId, status: "starting" });
+        try {
+          const workspace = workspaces.find((candidate) => candidate.id === workspaceId);
+          if (!workspace) {
+            throw new Error(`Unknown workspace: ${workspaceId}`);
+          }
+          const { url } = await startWorkspaceServer(sender, {
+            workspaceId,
+            workspacePath: workspace.path,
+            yolo: workspace.yolo,
+            preserveMobileRelay: true,
+            ...(input.featureFlags ? { featureFlags: input.featureFlags } : {}),
+            ...(input.privacyTelemetrySettings
+              ? { privacyTelemetrySettings: input.privacyTelemetrySettings }
+              : {}),
+          });
+          settle(workspaceId, { status: "ready", url });
+        } catch (error) {
+          const message = error instanceof Error ? error.message : String(error);
+          settle(workspaceId, { status: "failed", error: message });
+        }
+      },
+    );
+    operation?.signal.throwIfAborted();
+    return { results };
+  };
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.prewarmWorkspaceServers,
+    async (_event, args: PrewarmWorkspaceServersInput) => {
+      const input = parseWithSchema(
+        prewarmWorkspaceServersInputSchema,
+        args,
+        "prewarmWorkspaceServers options",
+      );
+      return await prewarmWorkspaceServers(_event.sender, input);
     },
   );
 
@@ -218,7 +767,20 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
         args,
         "stopWorkspaceServer options",
       );
-      await deps.serverManager.stopWorkspaceServer(input.workspaceId);
+      await stopAllWorkspaceServers(input.workspaceId);
+    },
+  );
+
+  // Leaves the thread's isolated server running so the next thread can reuse it.
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.releaseThreadServer,
+    async (_event, args: ReleaseThreadServerInput) => {
+      const input = parseWithSchema(
+        releaseThreadServerInputSchema,
+        args,
+        "releaseThreadServer options",
+      );
+      deps.isolatedSessions?.release(input.workspaceId, input.threadId);
     },
   );
 
@@ -234,6 +796,183 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
     },
   );
 
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getWorkspaceProxyUrl,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "getWorkspaceProxyUrl options",
+      );
+      return await deps.serverManager.getProxyUrl(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.generateHandoffLink,
+    async (_event, args: GenerateHandoffLinkInput) => {
+      const input = parseWithSchema(
+        generateHandoffLinkInputSchema,
+        args,
+        "generateHandoffLink options",
+      );
+      // A thread on its own isolated server is handed off from that server.
+      const serverId =
+        deps.isolatedSessions?.serverIdForThread(input.workspaceId, input.threadId) ??
+        input.workspaceId;
+      return await deps.serverManager.createHandoffLink(serverId, {
+        workspaceId: input.workspaceId,
+        threadId: input.threadId,
+        access: input.access ?? "read",
+        ttlSeconds: input.ttlSeconds ?? DEFAULT_HANDOFF_TTL_SECONDS,
+      });
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.revokeHandoff,
+    async (_event, args: RevokeHandoffInput) => {
+      const input = parseWithSchema(revokeHandoffInputSchema, args, "revokeHandoff options");
+      return await deps.serverManager.revokeHandoff(input.tokenId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getServerVersion,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "getServerVersion options",
+      );
+      return deps.serverManager.getServerVersion(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getServerStartupDiagnostics,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "getServerStartupDiagnostics options",
+      );
+      return deps.serverManager.getStartupDiagnostics(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getServerStats,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(stopWorkspaceServerInputSchema, args, "getServerStats options");
+      return await deps.serverManager.getServerStats(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getWorkspaceHealth,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "getWorkspaceHealth options",
+      );
+      return await deps.workspaceHealth.getHealth(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.listOrphanedServers, async () => {
+    return await deps.orphanedServers.list();
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.cleanupOrphanedServers, async () => {
+    return await deps.orphanedServers.cleanup();
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.readWorkspaceConfig,
+    async (_event, args: ReadWorkspaceConfigInput) => {
+      const input = parseWithSchema(
+        readWorkspaceConfigInputSchema,
+        args,
+        "readWorkspaceConfig options",
+      );
+      return await deps.workspaceConfig.read(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.writeWorkspaceConfig,
+    async (_event, args: WriteWorkspaceConfigInput) => {
+      const input = parseWithSchema(
+        writeWorkspaceConfigInputSchema,
+        args,
+        "writeWorkspaceConfig options",
+      );
+      const model = input.config.model;
+      if (model && deps.modelCatalog) {
+        const provider =
+          input.config.provider ??
+          (await deps.workspaceConfig.read(input.workspaceId)).effective.provider;
+        if (provider) {
+          await deps.modelCatalog.assertKnownModel(provider, model);
+        }
+      }
+      return await deps.workspaceConfig.write(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.listAvailableModels,
+    async (_event, args: ListAvailableModelsInput) => {
+      const input = parseWithSchema(
+        listAvailableModelsInputSchema,
+        args,
+        "listAvailableModels options",
+      );
+      if (!deps.modelCatalog) {
+        throw new Error("The model catalog is unavailable in this build.");
+      }
+      return await deps.modelCatalog.list(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getWorkspaceGitStatus,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "getWorkspaceGitStatus options",
+      );
+      return await deps.workspaceGit.getStatus(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.listWorkspaceCheckpoints,
+    async (_event, args: StopWorkspaceServerInput) => {
+      const input = parseWithSchema(
+        stopWorkspaceServerInputSchema,
+        args,
+        "listWorkspaceCheckpoints options",
+      );
+      return await deps.workspaceCheckpoints.list(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.restoreWorkspaceCheckpoint,
+    async (_event, args: RestoreWorkspaceCheckpointInput) => {
+      const input = parseWithSchema(
+        restoreWorkspaceCheckpointInputSchema,
+        args,
+        "restoreWorkspaceCheckpoint options",
+      );
+      return await deps.workspaceCheckpoints.restore(input);
+    },
+  );
+
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.loadState, async (_event) => {
     const state = await deps.persistence.loadState();
     if (resolveDesktopWindowMode(_event) === "main") {
@@ -247,7 +986,13 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
   });
 
   handleDesktopInvoke(DESKTOP_IPC_CHANNELS.saveState, async (_event, state: PersistedState) => {
-    const input = parseWithSchema(persistedStateInputSchema, state, "state");
+    const parsedInput = parseWithSchema(persistedStateInputSchema, state, "state");
+    const input = {
+      ...parsedInput,
+      threads: (Array.isArray(parsedInput.threads) ? parsedInput.threads : []).filter(
+        (thread) => !scratchThreads.has(thread.id),
+      ),
+    } satisfies PersistedState;
     const windowMode = resolveDesktopWindowMode(_event);
     const nextState = await (async () => {
       if (windowMode !== "main") {
@@ -270,7 +1015,26 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
       const workspaces = await Promise.all(
         input.workspaces.map(async (workspace) => ({
           ...workspace,
-          path: await workspaceRoots.assertApprovedWorkspacePath(workspace.path),
+          path: await workspaceRoots.assertApprovedServerPath(workspace.path),
+          ...(workspace.additionalRoots
+            ? {
+                additionalRoots: await Promise.all(
+                  workspace.additionalRoots.map((rootPath) =>
+                    workspaceRoots.assertApprovedWorkspacePath(rootPath),
+                  ),
+                ),
+              }
+            : {}),
+          ...(workspace.transcriptMirror && transcriptMirrorApprovalPath(workspace)
+            ? {
+                transcriptMirror: {
+                  ...workspace.transcriptMirror,
+                  directory: await workspaceRoots.assertApprovedWorkspacePath(
+                    workspace.transcriptMirror.directory,
+                  ),
+                },
+              }
+            : {}),
         })),
       );
       const workspaceIds = new Set(workspaces.map((workspace) => workspace.id));
@@ -294,40 +1058,595 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
 
     const preparedState =
       deps.productAnalytics?.preparePersistedState(nextState).state ?? nextState;
-    await deps.persistence.saveState(preparedState);
+    let generation: number;
+    try {
+      generation = await deps.persistence.saveState(preparedState);
+    } catch (error) {
+      if (error instanceof StateMergeRequiredError) {
+        return {
+          status: "mergeRequired",
+          baseGeneration: error.baseGeneration,
+          currentGeneration: error.currentGeneration,
+          workspaces: error.workspaces,
+          threads: error.threads,
+        } satisfies SaveStateResult;
+      }
+      throw error;
+    }
     if (deps.cloudSync) {
       void Promise.resolve(deps.cloudSync.enqueuePersistedState(preparedState)).catch(() => {
         // Cloud sync is best-effort and must not affect local persistence.
       });
     }
     workspaceRoots.setApprovedWorkspaceRoots(
-      preparedState.workspaces.map((workspace) => workspace.path),
+      preparedState.workspaces.flatMap(approvedPathsForWorkspace),
     );
     deps.applyPersistedState?.(preparedState);
+    return { status: "saved", generation } satisfies SaveStateResult;
   });
 
   handleDesktopInvoke(
-    DESKTOP_IPC_CHANNELS.readTranscript,
-    async (_event, args: ReadTranscriptInput) => {
-      const input = parseWithSchema(readTranscriptInputSchema, args, "readTranscript options");
-      return await deps.persistence.readTranscript(input.threadId);
+    DESKTOP_IPC_CHANNELS.createScratchThread,
+    async (_event, args: CreateScratchThreadInput) => {
+      const input = parseWithSchema(
+        createScratchThreadInputSchema,
+        args,
+        "createScratchThread options",
+      );
+      const state = await deps.persistence.loadState();
+      if (!state.workspaces.some((workspace) => workspace.id === input.workspaceId)) {
+        throw new Error(`Unknown workspace: ${input.workspaceId}`);
+      }
+      return scratchThreads.create(trackScratchOwner(_event), input.workspaceId);
     },
   );
 
   handleDesktopInvoke(
-    DESKTOP_IPC_CHANNELS.hydrateTranscript,
-    async (_event, args: ReadTranscriptInput) => {
-      const input = parseWithSchema(readTranscriptInputSchema, args, "hydrateTranscript options");
-      const transcript = await deps.persistence.readTranscript(input.threadId);
-      return hydrateTranscriptSnapshot(transcript);
-    },
-  );
+    DESKTOP_IPC_CHANNELS.persistScratchThread,
+    async (_event, args: PersistScratchThreadInput) => {
+      const input = parseWithSchema(
+        persistScratchThreadInputSchema,
+        args,
+        "persistScratchThread options",
+      );
+      const ownerId = resolveScratchOwnerId(_event);
+      const { thread, events } = scratchThreads.take(ownerId, input.threadId);
+      let transcriptWritten = false;
+      try {
+        const currentState = await deps.persistence.loadState();
+        if (!currentState.workspaces.some((workspace) => workspace.id === thread.workspaceId)) {
+          throw new Error(`Unknown workspace: ${thread.workspaceId}`);
+        }
+        const lastEventTs = events.at(-1)?.ts;
+        const record: ThreadRecord = {
+          id: thread.threadId,
+          workspaceId: thread.workspaceId,
+          title: input.title ?? "New thread",
+          titleSource: input.title ? "manual" : "default",
+          createdAt: thread.createdAt,
+          lastMessageAt:
+            lastEventTs && !Number.isNaN(Date.parse(lastEventTs)) ? lastEventTs : thread.createdAt,
+          status: "disconnected",
+          sessionId: input.sessionId ?? null,
+          messageCount: input.messageCount ?? 0,
+          lastEventSeq: input.lastEventSeq ?? 0,
+        };
 
-  handleDesktopInvoke(
-    DESKTOP_IPC_CHANNELS.appendTranscriptEvent,
-    async (_event, args: TranscriptBatchInput) => {
-      const input = parseWithSchema(transcriptBatchInputSchema, args, "transcript event");
-      await deps.persistence.appendTranscriptEvent(input);
+        await deps.persistence.appendTranscriptBatch(events);
+        transcriptWritten = events.length > 0;
+        await deps.persistence.saveState({
+          ...currentState,
+          threads: [...currentState.threads, record],
+        });
+        // Keep the promoted thread alive until the main window saves a state that includes it.
+        popupThreadIds.add(record.id);
+        removedThreadIds.delete(record.id);
+        return record;
+      } catch (error) {
+        if (transcriptWritten) {
+          await deps.persistence.deleteTranscript(thread.threadId).catch(() => {});
+        }
+        scratchThreads.restore(ownerId, thread, events);
+        throw error;
+      }
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.listStateSnapshots, async () => {
+    return await deps.persistence.listStateSnapshots();
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.restoreStateSnapshot,
+    async (_event, args: RestoreStateSnapshotInput) => {
+      const input = parseWithSchema(
+        restoreStateSnapshotInputSchema,
+        args,
+        "restoreStateSnapshot options",
+      );
+      const state = await deps.persistence.restoreStateSnapshot(input.name);
+      popupThreadIds.clear();
+      removedThreadIds.clear();
+      await workspaceRoots.refreshApprovedWorkspaceRootsFromState(state);
+      deps.applyPersistedState?.(state);
+      return state;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.rebuildStateFromTranscripts,
+    async (_event, args: RebuildStateInput) => {
+      const input = parseWithSchema(
+        rebuildStateInputSchema,
+        args,
+        "rebuildStateFromTranscripts options",
+      );
+      const { report, state } = await deps.persistence.rebuildStateFromTranscripts(input);
+      if (report.applied) {
+        await workspaceRoots.refreshApprovedWorkspaceRootsFromState(state);
+        deps.applyPersistedState?.(state);
+      }
+      return report;
+    },
+  );
+
+  // The renderer only suggests paths; the user picks the archive in a native
+  // dialog so a compromised renderer cannot read or write arbitrary files.
+  const chooseAppDataExportPath = async (
+    sender: Electron.WebContents,
+    destPath: string | undefined,
+  ): Promise<string | null> => {
+    const ownerWindow =
+      BrowserWindow.fromWebContents(sender) ?? BrowserWindow.getFocusedWindow() ?? undefined;
+    const dialogOptions = {
+      title: "Export Cowork data",
+      defaultPath:
+        destPath ??
+        path.join(electron.app.getPath("downloads"), `cowork-data.${APP_DATA_ARCHIVE_EXTENSION}`),
+      filters: [{ name: "Cowork data", extensions: [APP_DATA_ARCHIVE_EXTENSION] }],
+    };
+    const result = ownerWindow
+      ? await electron.dialog.showSaveDialog(ownerWindow, dialogOptions)
+      : await electron.dialog.showSaveDialog(dialogOptions);
+    if (result.canceled || !result.filePath) {
+      return null;
+    }
+    await deps.storageGuard?.assertWritable("export", path.dirname(result.filePath));
+    return result.filePath;
+  };
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.exportAppData,
+    async (event, args: ExportAppDataInput) => {
+      const input = parseWithSchema(exportAppDataInputSchema, args, "exportAppData options");
+      const appData = requireAppDataTransfer();
+      const destPath = await chooseAppDataExportPath(event.sender, input.destPath);
+      return destPath ? await appData.exportTo(destPath, input.includeTranscripts) : null;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.enqueueExport,
+    async (event, args: EnqueueExportInput) => {
+      const input = parseWithSchema(enqueueExportInputSchema, args, "enqueueExport options");
+      const queue = { queue: EXPORT_OPERATION_QUEUE };
+      if (input.kind === "diagnosticsBundle") {
+        return deps.operations.start(
+          "createDiagnosticsBundle",
+          ({ signal, reportProgress }) =>
+            deps.diagnostics.createBundle({ signal, onProgress: reportProgress }),
+          queue,
+        );
+      }
+      const appData = requireAppDataTransfer();
+      const destPath = await chooseAppDataExportPath(event.sender, input.destPath);
+      if (!destPath) {
+        return null;
+      }
+      return deps.operations.start(
+        "exportAppData",
+        ({ signal, reportProgress }) =>
+          appData.exportTo(destPath, input.includeTranscripts, {
+            signal,
+            onProgress: reportProgress,
+          }),
+        queue,
+      );
+    },
+  );
+
+  const importAppData: WorkspaceOperationTasks["importAppData"] = async (
+    sender,
+    input,
+    operation,
+  ) => {
+    const appData = requireAppDataTransfer();
+    const ownerWindow =
+      BrowserWindow.fromWebContents(sender) ?? BrowserWindow.getFocusedWindow() ?? undefined;
+    const dialogOptions = {
+      title: "Import Cowork data",
+      ...(input.archivePath ? { defaultPath: input.archivePath } : {}),
+      filters: [{ name: "Cowork data", extensions: [APP_DATA_ARCHIVE_EXTENSION] }],
+      properties: ["openFile"] as Array<"openFile">,
+    };
+    const selection = ownerWindow
+      ? await electron.dialog.showOpenDialog(ownerWindow, dialogOptions)
+      : await electron.dialog.showOpenDialog(dialogOptions);
+    const archivePath = selection.canceled ? undefined : selection.filePaths[0];
+    if (!archivePath) {
+      return null;
+    }
+    // The import replaces state in one step, so cancellation only applies
+    // while the picker is open.
+    operation?.signal.throwIfAborted();
+    operation?.reportProgress({ completed: 0, total: null, message: "Importing data" });
+    const result = await appData.importFrom(archivePath, input.mergeStrategy);
+    popupThreadIds.clear();
+    removedThreadIds.clear();
+    await workspaceRoots.refreshApprovedWorkspaceRootsFromState(result.state);
+    deps.applyPersistedState?.(result.state);
+    return result;
+  };
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.importAppData,
+    async (event, args: ImportAppDataInput) => {
+      const input = parseWithSchema(importAppDataInputSchema, args, "importAppData options");
+      return await importAppData(event.sender, input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.readTranscript,
+    async (_event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(readTranscriptInputSchema, args, "readTranscript options");
+      return (
+        readInMemoryTranscript(input.threadId) ??
+        (await deps.persistence.readTranscript(input.threadId))
+      );
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.readTranscriptPage,
+    async (_event, args: ReadTranscriptPageInput) => {
+      const input = parseWithSchema(
+        readTranscriptPageInputSchema,
+        args,
+        "readTranscriptPage options",
+      );
+      const inMemory = readInMemoryTranscript(input.threadId);
+      if (inMemory) {
+        return pageTranscriptEvents(
+          inMemory,
+          input.cursor ? decodeTranscriptCursor(input.cursor) : null,
+          resolveTranscriptPageLimits(input),
+        );
+      }
+      return await deps.persistence.readTranscriptPage(input.threadId, input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getThreadPreviews,
+    async (_event, args: GetThreadPreviewsInput) => {
+      const input = parseWithSchema(
+        getThreadPreviewsInputSchema,
+        args,
+        "getThreadPreviews options",
+      );
+      // Viewer and scratch threads have no index; their few events are summarized directly.
+      const inMemory = new Map(
+        input.threadIds.flatMap((threadId) => {
+          const events = readInMemoryTranscript(threadId);
+          return events ? [[threadId, events] as const] : [];
+        }),
+      );
+      const persisted = await deps.persistence.getThreadPreviews(
+        input.threadIds.filter((threadId) => !inMemory.has(threadId)),
+      );
+      const previews = new Map(persisted.map((preview) => [preview.threadId, preview]));
+      for (const [threadId, events] of inMemory) {
+        const index = events.reduce(
+          (current, event) => recordIndexedEvent(current, event, false),
+          emptyTranscriptIndex(),
+        );
+        previews.set(threadId, toThreadPreview(threadId, index));
+      }
+      return input.threadIds.flatMap((threadId) => previews.get(threadId) ?? []);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.inspectTranscript,
+    async (_event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(readTranscriptInputSchema, args, "inspectTranscript options");
+      const { events, corruptedLines } = await deps.persistence.readTranscriptWithReport(
+        input.threadId,
+      );
+      return { threadId: input.threadId, eventCount: events.length, corruptedLines };
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.repairTranscript,
+    async (_event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(readTranscriptInputSchema, args, "repairTranscript options");
+      return await deps.persistence.repairTranscript(input.threadId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.normalizeTranscriptTimestamps,
+    async (_event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(
+        readTranscriptInputSchema,
+        args,
+        "normalizeTranscriptTimestamps options",
+      );
+      const result = await deps.persistence.normalizeTranscriptTimestamps(input.threadId);
+      if (result.normalizedCount > 0) {
+        await deps.transcriptMirror?.mirrorTranscript(input.threadId);
+        logInfo("audit", "normalized transcript timestamps", {
+          threadId: input.threadId,
+          normalizedCount: result.normalizedCount,
+          unparseableCount: result.unparseableCount,
+        });
+      }
+      return result;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.redactTranscript,
+    async (_event, args: RedactTranscriptInput) => {
+      const input = parseWithSchema(redactTranscriptInputSchema, args, "redactTranscript options");
+      const result = await deps.persistence.redactTranscript(input);
+      if (!result.dryRun && result.redactionCount > 0) {
+        // The mirrored markdown is a second copy of the secrets; search reads the rewritten file.
+        await deps.transcriptMirror?.mirrorTranscript(input.threadId);
+        logInfo("audit", "redacted transcript", {
+          threadId: input.threadId,
+          redactionCount: result.redactionCount,
+          redactedEventCount: result.redactedEventCount,
+          // A custom pattern may spell out the secret itself, so only their number is logged.
+          customPatternCount: input.patterns?.length ?? 0,
+        });
+      }
+      return result;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.archiveThread,
+    async (_event, args: ArchiveThreadInput) => {
+      const input = parseWithSchema(archiveThreadInputSchema, args, "archiveThread options");
+      return await deps.persistence.archiveThread(input.threadId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.unarchiveThread,
+    async (_event, args: ArchiveThreadInput) => {
+      const input = parseWithSchema(archiveThreadInputSchema, args, "unarchiveThread options");
+      return await deps.persistence.unarchiveThread(input.threadId);
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.forkThread, async (_event, args: ForkThreadInput) => {
+    const input = parseWithSchema(forkThreadInputSchema, args, "forkThread options");
+    const thread = await deps.persistence.forkThread(input);
+    // Keep the fork when the main window saves state it fetched before the fork existed.
+    popupThreadIds.add(thread.id);
+    removedThreadIds.delete(thread.id);
+    return thread;
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.mergeThreads,
+    async (_event, args: MergeThreadsInput): Promise<MergeThreadsResult> => {
+      const input = parseWithSchema(mergeThreadsInputSchema, args, "mergeThreads options");
+      const { eventIndexes, ...result } = await deps.persistence.mergeThreads(input);
+      await deps.transcriptAnnotations?.merge(
+        input.sourceThreadId,
+        input.targetThreadId,
+        eventIndexes,
+      );
+      if (input.trashSource) {
+        // Keep popups that still list the source from saving it back.
+        removedThreadIds.add(input.sourceThreadId);
+      }
+      return result;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.suggestThreadTitle,
+    async (_event, args: SuggestThreadTitleInput) => {
+      const input = parseWithSchema(
+        suggestThreadTitleInputSchema,
+        args,
+        "suggestThreadTitle options",
+      );
+      const { transcript, thread } = await loadThreadRecording(input.threadId);
+      if (!thread) {
+        throw new Error(`Unknown thread: ${input.threadId}`);
+      }
+      const firstUserMessage = hydrateTranscriptSnapshot(transcript).feed.find(
+        (item) => item.kind === "message" && item.role === "user" && item.text.trim(),
+      );
+      if (firstUserMessage?.kind !== "message" || thread.titleSource === "manual") {
+        return thread;
+      }
+      // Prefer the workspace server's model; without one, title from the message itself.
+      const generated = await deps.serverManager.generateThreadTitle(
+        thread.workspaceId,
+        firstUserMessage.text,
+      );
+      return await deps.persistence.setThreadTitle(
+        thread.id,
+        generated?.title ?? heuristicThreadTitle(firstUserMessage.text),
+        generated?.source ?? "heuristic",
+      );
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.copyTranscriptSelection,
+    async (_event, args: CopyTranscriptSelectionInput) => {
+      const input = parseWithSchema(
+        copyTranscriptSelectionInputSchema,
+        args,
+        "copyTranscriptSelection options",
+      );
+      const { transcript, workspacePath } = await loadThreadRecording(input.threadId);
+      const { feed } = hydrateTranscriptSnapshot(transcript);
+      if (input.toIndex >= feed.length) {
+        throw new Error(`Selection ends past the last of ${feed.length} transcript items`);
+      }
+      const selection = feed.slice(input.fromIndex, input.toIndex + 1);
+      const { markdown, itemCount } = renderTranscriptMarkdown(selection);
+      const { text, redactionCount } =
+        input.redactSecrets === false
+          ? { text: markdown, redactionCount: 0 }
+          : redactTranscriptText(
+              markdown,
+              workspacePath ? await readWorkspaceEnvSecrets(workspacePath) : [],
+            );
+      electron.clipboard.writeText(text);
+      return { itemCount, redactionCount };
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.getTranscriptEncryption, async () => {
+    return await deps.persistence.getTranscriptEncryption();
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setTranscriptEncryption,
+    async (_event, args: SetTranscriptEncryptionInput) => {
+      const input = parseWithSchema(
+        setTranscriptEncryptionInputSchema,
+        args,
+        "setTranscriptEncryption options",
+      );
+      return await deps.persistence.setTranscriptEncryption(input.enabled);
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.encryptExistingTranscripts, async () => {
+    return await deps.persistence.encryptExistingTranscripts();
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.migrateTranscriptStore,
+    async (_event, args: MigrateTranscriptStoreInput) => {
+      const input = parseWithSchema(
+        migrateTranscriptStoreInputSchema,
+        args,
+        "migrateTranscriptStore options",
+      );
+      return await deps.persistence.migrateTranscriptStore(input.target);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.openTranscriptFile,
+    async (_event, args: OpenTranscriptFileInput) => {
+      const input = parseWithSchema(
+        openTranscriptFileInputSchema,
+        args,
+        "openTranscriptFile options",
+      );
+      return await viewerTranscripts.open(input.path);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.shareTranscript,
+    async (event, args: ShareTranscriptInput) => {
+      const input = parseWithSchema(shareTranscriptInputSchema, args, "shareTranscript options");
+      if (process.platform !== "darwin") {
+        throw new Error("Sharing transcripts is only available on macOS.");
+      }
+      const { transcript, thread } = await loadThreadRecording(input.threadId);
+      if (transcript.length === 0) {
+        throw new Error(`Thread ${input.threadId} has no transcript to share.`);
+      }
+      // The share sheet hands the file to another app, so it gets a readable name.
+      const baseName =
+        (thread?.title ?? "")
+          .replace(/[^\w .-]+/g, "-")
+          .trim()
+          .slice(0, 80) || "thread";
+      const shareDir = path.join(electron.app.getPath("temp"), "cowork-share", input.threadId);
+      await fs.mkdir(shareDir, { recursive: true });
+      const filePath = path.join(shareDir, `${baseName}${TRANSCRIPT_EXPORT_EXTENSION}`);
+      await fs.writeFile(filePath, renderTranscriptExport(transcript), "utf8");
+      const ownerWindow = BrowserWindow.fromWebContents(event.sender) ?? undefined;
+      new electron.ShareMenu({ filePaths: [filePath] }).popup({ window: ownerWindow });
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.hydrateTranscript,
+    async (_event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(readTranscriptInputSchema, args, "hydrateTranscript options");
+      const transcript =
+        readInMemoryTranscript(input.threadId) ??
+        (await deps.persistence.readTranscript(input.threadId));
+      return hydrateTranscriptSnapshot(transcript);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getThreadFileChanges,
+    async (_event, args: GetThreadFileChangesInput) => {
+      const input = parseWithSchema(
+        getThreadFileChangesInputSchema,
+        args,
+        "getThreadFileChanges options",
+      );
+      const { transcript, workspacePath } = await loadThreadRecording(input.threadId);
+      return summarizeThreadFileChanges(
+        input.threadId,
+        hydrateTranscriptSnapshot(transcript).feed,
+        workspacePath,
+      );
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.startReplay, async (_event, args: StartReplayInput) => {
+    const input = parseWithSchema(startReplayInputSchema, args, "startReplay options");
+    const { transcript, thread, workspacePath } = await loadThreadRecording(input.threadId);
+    return await deps.protocolReplay.start(
+      {
+        threadId: input.threadId,
+        title: thread?.title ?? input.threadId,
+        createdAt: thread?.createdAt ?? transcript[0]?.ts ?? new Date().toISOString(),
+        workspacePath,
+        transcript,
+      },
+      input.speed,
+    );
+  });
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.stopReplay, async (_event, args: StopReplayInput) => {
+    const input = parseWithSchema(stopReplayInputSchema, args, "stopReplay options");
+    await deps.protocolReplay.stop(input.replayId);
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.appendTranscriptEvent,
+    async (_event, args: TranscriptBatchInput) => {
+      const input = parseWithSchema(transcriptBatchInputSchema, args, "transcript event");
+      assertWritableThreads([input]);
+      deps.serverPower?.recordTranscriptActivity([input.threadId]);
+      const admitted = deps.transcriptThrottle?.admit([input]) ?? [input];
+      const [event] = scratchThreads.captureEvents(admitted);
+      if (event) {
+        await deps.persistence.appendTranscriptEvent(event);
+      }
     },
   );
 
@@ -335,15 +1654,444 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
     DESKTOP_IPC_CHANNELS.appendTranscriptBatch,
     async (_event, args: TranscriptBatchInput[]) => {
       const input = parseWithSchema(z.array(transcriptBatchInputSchema), args, "transcript batch");
-      await deps.persistence.appendTranscriptBatch(input);
+      assertWritableThreads(input);
+      deps.serverPower?.recordTranscriptActivity(input.map((event) => event.threadId));
+      const admitted = deps.transcriptThrottle?.admit(input) ?? input;
+      await deps.persistence.appendTranscriptBatch(scratchThreads.captureEvents(admitted));
+    },
+  );
+
+  const appendTranscriptEvents: TranscriptAppender = async (events) => {
+    assertWritableThreads(events);
+    await deps.persistence.appendTranscriptBatch(scratchThreads.captureEvents(events));
+  };
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.subscribeTranscript,
+    async (event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(readTranscriptInputSchema, args, "subscribeTranscript options");
+      deps.transcriptSubscriptions.subscribe(event.sender, input.threadId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.unsubscribeTranscript,
+    async (event, args: ReadTranscriptInput) => {
+      const input = parseWithSchema(
+        readTranscriptInputSchema,
+        args,
+        "unsubscribeTranscript options",
+      );
+      deps.transcriptSubscriptions.unsubscribe(event.sender, input.threadId);
     },
   );
 
   handleDesktopInvoke(
     DESKTOP_IPC_CHANNELS.deleteTranscript,
-    async (_event, args: DeleteTranscriptInput) => {
+    async (event, args: DeleteTranscriptInput): Promise<DestructiveCommandResult> => {
       const input = parseWithSchema(deleteTranscriptInputSchema, args, "deleteTranscript options");
-      await deps.persistence.deleteTranscript(input.threadId);
+      if (viewerTranscripts.close(input.threadId) || scratchThreads.discard(input.threadId)) {
+        return { status: "completed" };
+      }
+      const ownerId = resolveScratchOwnerId(event);
+      if (!input.confirmationToken) {
+        const transcripts = await deps.persistence.getTranscriptStats([input.threadId]);
+        return {
+          status: "confirmationRequired",
+          challenge: confirmations.issue("deleteTranscript", input.threadId, ownerId, {
+            workspaceCount: 0,
+            threadCount: 0,
+            transcriptCount: transcripts.fileCount,
+            transcriptBytes: transcripts.totalBytes,
+          }),
+        };
+      }
+      confirmations.consume("deleteTranscript", input.threadId, ownerId, input.confirmationToken);
+      await deps.persistence.trashTranscript(input.threadId);
+      return { status: "completed" };
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.purgeThreads, async (event, args: PurgeThreadsInput) => {
+    const input = parseWithSchema(purgeThreadsInputSchema, args, "purgeThreads options");
+    return await runConfirmedRemoval(
+      event,
+      "purgeThreads",
+      { threadIds: input.threadIds },
+      input.confirmationToken,
+    );
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.deleteWorkspace,
+    async (event, args: DeleteWorkspaceInput) => {
+      const input = parseWithSchema(deleteWorkspaceInputSchema, args, "deleteWorkspace options");
+      return await runConfirmedRemoval(
+        event,
+        "deleteWorkspace",
+        { workspaceIds: [input.workspaceId] },
+        input.confirmationToken,
+        async () => {
+          deps.workspaceConfig.unwatch(input.workspaceId);
+          deps.workspaceGit.unwatch(input.workspaceId);
+          await stopAllWorkspaceServers(input.workspaceId);
+        },
+      );
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.getWorkspaceStorageReport,
+    async (_event, args: GetWorkspaceStorageReportInput) => {
+      const input = parseWithSchema(
+        getWorkspaceStorageReportInputSchema,
+        args,
+        "getWorkspaceStorageReport options",
+      );
+      return await deps.persistence.getWorkspaceStorageReport(input.workspaceId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.cleanupWorkspaceStorage,
+    async (event, args: CleanupWorkspaceStorageInput): Promise<CleanupWorkspaceStorageResult> => {
+      const input = parseWithSchema(
+        cleanupWorkspaceStorageInputSchema,
+        args,
+        "cleanupWorkspaceStorage options",
+      );
+      const actions = await deps.persistence.planWorkspaceStorageCleanup(
+        input.workspaceId,
+        input.targets,
+      );
+      if (input.dryRun) {
+        const report = await deps.persistence.getWorkspaceStorageReport(input.workspaceId);
+        return { status: "completed", dryRun: true, actions, report };
+      }
+      const threadIdsFor = (target: WorkspaceStorageCleanupTarget) =>
+        actions.find((action) => action.target === target)?.threadIds ?? [];
+      // Purging deletes threads, so it goes through the same confirmation as purgeThreads.
+      const purged = threadIdsFor("purgeArchivedThreads");
+      if (purged.length > 0) {
+        const removal = await runConfirmedRemoval(
+          event,
+          "purgeThreads",
+          { threadIds: purged },
+          input.confirmationToken,
+        );
+        if (removal.status === "confirmationRequired") {
+          return removal;
+        }
+      }
+      await deps.persistence.compactTranscripts(threadIdsFor("compactTranscripts"));
+      await deps.persistence.clearTranscriptQuarantine(threadIdsFor("clearQuarantine"));
+      const report = await deps.persistence.getWorkspaceStorageReport(input.workspaceId);
+      return { status: "completed", dryRun: false, actions, report };
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.listTrash, async () => {
+    return (await deps.trash?.list()) ?? [];
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.restoreFromTrash,
+    async (_event, args: RestoreFromTrashInput) => {
+      const input = parseWithSchema(
+        restoreFromTrashInputSchema,
+        args,
+        "restoreFromTrash options",
+      );
+      const result = await deps.persistence.restoreFromTrash(input.entryId);
+      for (const thread of result.threads) {
+        popupThreadIds.add(thread.id);
+        removedThreadIds.delete(thread.id);
+      }
+      const state = await deps.persistence.loadState();
+      await workspaceRoots.refreshApprovedWorkspaceRootsFromState(state);
+      deps.applyPersistedState?.(state);
+      return result;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setWorkspacePinned,
+    async (_event, args: SetWorkspacePinnedInput) => {
+      const input = parseWithSchema(
+        setWorkspacePinnedInputSchema,
+        args,
+        "setWorkspacePinned options",
+      );
+      return await deps.persistence.setWorkspacePinned(input.workspaceId, input.pinned);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setWorkspaceTags,
+    async (_event, args: SetWorkspaceTagsInput) => {
+      const input = parseWithSchema(setWorkspaceTagsInputSchema, args, "setWorkspaceTags options");
+      return await deps.persistence.setWorkspaceTags(input.workspaceId, input.tags);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setWorkspaceLaunchProfiles,
+    async (_event, args: SetWorkspaceLaunchProfilesInput) => {
+      const input = parseWithSchema(
+        setWorkspaceLaunchProfilesInputSchema,
+        args,
+        "setWorkspaceLaunchProfiles options",
+      );
+      return await deps.persistence.setWorkspaceLaunchProfiles(input.workspaceId, input.profiles);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setWorkspaceIdlePolicy,
+    async (_event, args: SetWorkspaceIdlePolicyInput) => {
+      const input = parseWithSchema(
+        setWorkspaceIdlePolicyInputSchema,
+        args,
+        "setWorkspaceIdlePolicy options",
+      );
+      return await deps.persistence.setWorkspaceIdlePolicy(input.workspaceId, input.policy);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setWorkspacePathPolicy,
+    async (_event, args: SetWorkspacePathPolicyInput) => {
+      const input = parseWithSchema(
+        setWorkspacePathPolicyInputSchema,
+        args,
+        "setWorkspacePathPolicy options",
+      );
+      return await deps.persistence.setWorkspacePathPolicy(input.workspaceId, input.policy);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.previewPathPolicy,
+    async (_event, args: PreviewPathPolicyInput) => {
+      const input = parseWithSchema(previewPathPolicyInputSchema, args, "previewPathPolicy options");
+      const { workspaces } = await deps.persistence.loadState();
+      const workspace = workspaces.find((candidate) => candidate.id === input.workspaceId);
+      if (!workspace) {
+        throw new Error(`Unknown workspace: ${input.workspaceId}`);
+      }
+      return evaluatePathPolicy(workspace, path.resolve(workspace.path, input.path));
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.duplicateWorkspace,
+    async (_event, args: DuplicateWorkspaceInput) => {
+      const input = parseWithSchema(
+        duplicateWorkspaceInputSchema,
+        args,
+        "duplicateWorkspace options",
+      );
+      const { workspaces, threads } = await deps.persistence.loadState();
+      const source = workspaces.find((workspace) => workspace.id === input.workspaceId);
+      if (!source) {
+        throw new Error(`Unknown workspace: ${input.workspaceId}`);
+      }
+      const chatThreadIds = threads
+        .filter((thread) => thread.workspaceId === source.id && isStandardChatThread(thread))
+        .map((thread) => thread.id);
+      const threadIds = input.copyThreads ? (input.threadIds ?? chatThreadIds) : [];
+      let workspacePath = await workspaceRoots.assertApprovedWorkspacePath(source.path);
+      if (input.newPath) {
+        await copyWorkspaceDirectory(workspacePath, input.newPath);
+        workspacePath = await workspaceRoots.addApprovedWorkspacePath(input.newPath);
+      }
+      const result = await deps.persistence.duplicateWorkspace(source.id, {
+        path: workspacePath,
+        threadIds,
+      });
+      for (const thread of result.threads) {
+        popupThreadIds.add(thread.id);
+        removedThreadIds.delete(thread.id);
+      }
+      return result;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.reorderWorkspaces,
+    async (_event, args: ReorderWorkspacesInput) => {
+      const input = parseWithSchema(
+        reorderWorkspacesInputSchema,
+        args,
+        "reorderWorkspaces options",
+      );
+      return await deps.persistence.reorderWorkspaces(input.workspaceIds);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.globalSearch,
+    async (_event, args: GlobalSearchInput) => {
+      const input = parseWithSchema(globalSearchInputSchema, args, "globalSearch options");
+      const annotations = deps.transcriptAnnotations;
+      const promptLibrary = deps.promptLibrary;
+      const state = await deps.persistence.loadState();
+      return await globalSearch(state, input, {
+        readTranscript: (threadId) => deps.persistence.readTranscript(threadId),
+        readAnnotations: annotations ? (threadId) => annotations.list(threadId) : undefined,
+        readPromptTemplates: promptLibrary ? () => promptLibrary.listAll(state) : undefined,
+      });
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.annotateEvent,
+    async (_event, args: AnnotateEventInput) => {
+      const input = parseWithSchema(annotateEventInputSchema, args, "annotateEvent options");
+      if (isViewerThreadId(input.threadId)) {
+        throw new ReadOnlyThreadError(input.threadId);
+      }
+      return await requireTranscriptAnnotations().annotate(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.listAnnotations,
+    async (_event, args: ListAnnotationsInput) => {
+      const input = parseWithSchema(listAnnotationsInputSchema, args, "listAnnotations options");
+      return (await deps.transcriptAnnotations?.list(input.threadId)) ?? [];
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.saveThreadDraft,
+    async (_event, args: SaveThreadDraftInput) => {
+      const input = parseWithSchema(saveThreadDraftInputSchema, args, "saveThreadDraft options");
+      return deps.threadDrafts?.save(input) ?? null;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.loadThreadDraft,
+    async (_event, args: LoadThreadDraftInput) => {
+      const input = parseWithSchema(loadThreadDraftInputSchema, args, "loadThreadDraft options");
+      return (await deps.threadDrafts?.load(input.threadId)) ?? null;
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.listPromptTemplates,
+    async (_event, args: ListPromptTemplatesInput) => {
+      const input = parseWithSchema(
+        listPromptTemplatesInputSchema,
+        args,
+        "listPromptTemplates options",
+      );
+      return (await deps.promptLibrary?.list(input.workspaceId)) ?? [];
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.savePromptTemplate,
+    async (_event, args: SavePromptTemplateInput) => {
+      const input = parseWithSchema(
+        savePromptTemplateInputSchema,
+        args,
+        "savePromptTemplate options",
+      );
+      return await requirePromptLibrary().save(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.deletePromptTemplate,
+    async (_event, args: DeletePromptTemplateInput) => {
+      const input = parseWithSchema(
+        deletePromptTemplateInputSchema,
+        args,
+        "deletePromptTemplate options",
+      );
+      await requirePromptLibrary().delete(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.renderPrompt,
+    async (_event, args: RenderPromptInput) => {
+      const input = parseWithSchema(renderPromptInputSchema, args, "renderPrompt options");
+      return await requirePromptLibrary().render(input);
+    },
+  );
+
+  handleDesktopInvoke(DESKTOP_IPC_CHANNELS.listScheduledTasks, async () => {
+    return (await deps.scheduledTasks?.list()) ?? [];
+  });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.createScheduledTask,
+    async (_event, args: CreateScheduledTaskInput) => {
+      const input = parseWithSchema(
+        createScheduledTaskInputSchema,
+        args,
+        "createScheduledTask options",
+      );
+      return await requireScheduledTasks().create(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.setScheduledTaskEnabled,
+    async (_event, args: SetScheduledTaskEnabledInput) => {
+      const input = parseWithSchema(
+        setScheduledTaskEnabledInputSchema,
+        args,
+        "setScheduledTaskEnabled options",
+      );
+      return await requireScheduledTasks().setEnabled(input.taskId, input.enabled);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.deleteScheduledTask,
+    async (_event, args: DeleteScheduledTaskInput) => {
+      const input = parseWithSchema(
+        deleteScheduledTaskInputSchema,
+        args,
+        "deleteScheduledTask options",
+      );
+      await requireScheduledTasks().remove(input.taskId);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.recordPendingApproval,
+    async (_event, args: RecordPendingApprovalInput) => {
+      const input = parseWithSchema(
+        recordPendingApprovalInputSchema,
+        args,
+        "recordPendingApproval options",
+      );
+      return await requireApprovalQueue().record(input);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.listPendingApprovals,
+    async (_event, args: ListPendingApprovalsInput) => {
+      const input = parseWithSchema(
+        listPendingApprovalsInputSchema,
+        args,
+        "listPendingApprovals options",
+      );
+      return (await deps.approvalQueue?.list(input.workspaceId)) ?? [];
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.resolveApproval,
+    async (_event, args: ResolveApprovalInput) => {
+      const input = parseWithSchema(resolveApprovalInputSchema, args, "resolveApproval options");
+      return await requireApprovalQueue().resolve(input);
     },
   );
 
@@ -373,4 +2121,73 @@ export function registerWorkspaceIpc(context: DesktopIpcModuleContext): void {
 
     return await workspaceRoots.addApprovedWorkspacePath(selectedPath);
   });
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.registerRemoteWorkspace,
+    async (_event, args: RegisterRemoteWorkspaceInput) => {
+      const input = parseWithSchema(
+        registerRemoteWorkspaceInputSchema,
+        args,
+        "registerRemoteWorkspace options",
+      );
+      if (!deps.remoteWorkspaces) {
+        throw new Error("Remote workspaces are unavailable in this build.");
+      }
+      const target = parseSshWorkspaceUrl(input.url);
+      if (!target) {
+        throw new Error(`Not a remote workspace URL: ${input.url}`);
+      }
+      await deps.remoteWorkspaces.probe(target);
+      await workspaceRoots.ensureApprovedWorkspaceRoots();
+      return workspaceRoots.addApprovedRemoteWorkspace(input.url);
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.resolveDroppedWorkspaceFolder,
+    async (_event, args: ResolveDroppedWorkspaceFolderInput) => {
+      const input = parseWithSchema(
+        resolveDroppedWorkspaceFolderInputSchema,
+        args,
+        "resolveDroppedWorkspaceFolder options",
+      );
+      const userDataPath = electron.app?.getPath("userData");
+      const { workspaces } = await deps.persistence.loadState();
+      const folder = await resolveDroppedWorkspaceFolder(input.path, {
+        appDataPaths: userDataPath ? [userDataPath] : [],
+        workspaces,
+      });
+      return { ...folder, path: await workspaceRoots.addApprovedWorkspacePath(folder.path) };
+    },
+  );
+
+  handleDesktopInvoke(
+    DESKTOP_IPC_CHANNELS.scanForWorkspaces,
+    async (_event, args: ScanForWorkspacesInput) => {
+      const input = parseWithSchema(
+        scanForWorkspacesInputSchema,
+        args,
+        "scanForWorkspaces options",
+      );
+      const rootPaths = await Promise.all(
+        input.rootPaths.map((rootPath) => workspaceRoots.assertApprovedWorkspacePath(rootPath)),
+      );
+      const userDataPath = electron.app?.getPath("userData");
+      const { workspaces } = await deps.persistence.loadState();
+      const result = await scanForWorkspaces(rootPaths, input.maxDepth, {
+        appDataPaths: userDataPath ? [userDataPath] : [],
+        workspaces,
+      });
+      // Found inside roots the user picked, so they can be added in one step.
+      const candidates = await Promise.all(
+        result.candidates.map(async (candidate) => ({
+          ...candidate,
+          path: await workspaceRoots.addApprovedWorkspacePath(candidate.path),
+        })),
+      );
+      return { ...result, candidates };
+    },
+  );
+
+  return { tasks: { prewarmWorkspaceServers, importAppData }, appendTranscriptEvents };
 }
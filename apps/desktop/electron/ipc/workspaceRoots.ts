@@ -1,7 +1,20 @@
 import fs from "node:fs/promises";
 import path from "node:path";
 
-import type { PersistedState } from "../../src/app/types";
+import {
+  formatSshWorkspaceUrl,
+  isSshWorkspacePath,
+  parseSshWorkspaceUrl,
+} from "../../src/app/remoteWorkspaces";
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import { workspaceRootPaths } from "../../src/app/workspaceRootPaths";
+import { transcriptMirrorApprovalPath } from "../../src/app/workspaceTranscriptMirror";
+import {
+  canonicalLocalPath,
+  fromFileSystemPath,
+  pathIdentityKey,
+  toFileSystemPath,
+} from "../services/pathBoundary";
 import type { PersistenceService } from "../services/persistence";
 import type { WorkspaceRootsAccess } from "./types";
 
@@ -9,37 +22,72 @@ async function normalizeWorkspacePath(workspacePath: string): Promise<string> {
   if (!workspacePath.trim()) {
     throw new Error("workspacePath must be a non-empty string");
   }
+  if (isSshWorkspacePath(workspacePath)) {
+    throw new Error(`Remote workspaces have no local folder: ${workspacePath}`);
+  }
 
-  const resolved = path.resolve(workspacePath);
-  const stat = await fs.stat(resolved);
+  const resolved = path.resolve(fromFileSystemPath(workspacePath));
+  const stat = await fs.stat(toFileSystemPath(resolved));
   if (!stat.isDirectory()) {
     throw new Error(`workspacePath is not a directory: ${workspacePath}`);
   }
-  return await fs.realpath(resolved);
+  return await canonicalLocalPath(resolved);
+}
+
+/** Workspace roots plus any user-picked transcript mirror folder outside them. */
+export function approvedPathsForWorkspace(
+  workspace: Pick<WorkspaceRecord, "path" | "additionalRoots" | "transcriptMirror">,
+): string[] {
+  const mirrorPath = transcriptMirrorApprovalPath(workspace);
+  const roots = workspaceRootPaths(workspace);
+  return mirrorPath ? [...roots, mirrorPath] : roots;
+}
+
+/** The canonical form of an `ssh://` workspace path; null for local and malformed paths. */
+function canonicalRemoteWorkspace(workspacePath: string): string | null {
+  try {
+    const target = parseSshWorkspaceUrl(workspacePath);
+    return target ? formatSshWorkspaceUrl(target) : null;
+  } catch {
+    return null;
+  }
 }
 
 async function getNormalizedWorkspaceRoots(state: PersistedState): Promise<string[]> {
   const roots: string[] = [];
   for (const workspace of state.workspaces) {
-    try {
-      roots.push(await normalizeWorkspacePath(workspace.path));
-    } catch {
-      // Ignore invalid paths from persisted state.
+    for (const rootPath of approvedPathsForWorkspace(workspace)) {
+      try {
+        roots.push(await normalizeWorkspacePath(rootPath));
+      } catch {
+        // Ignore invalid paths from persisted state.
+      }
     }
   }
   return roots;
 }
 
 export class WorkspaceRootsController implements WorkspaceRootsAccess {
-  private readonly approvedWorkspaceRoots = new Set<string>();
+  /** Approved local roots by `pathIdentityKey`, so differently cased Windows spellings match. */
+  private readonly approvedWorkspaceRoots = new Map<string, string>();
+  /** `ssh://` workspaces, kept apart so local file access never sees them as roots. */
+  private readonly approvedRemoteWorkspaces = new Set<string>();
   private approvedWorkspaceRootsInitialized = false;
 
   constructor(private readonly persistence: PersistenceService) {}
 
   private resetApprovedWorkspaceRoots(paths: Iterable<string>): void {
     this.approvedWorkspaceRoots.clear();
+    this.approvedRemoteWorkspaces.clear();
     for (const workspacePath of paths) {
-      this.approvedWorkspaceRoots.add(workspacePath);
+      if (!isSshWorkspacePath(workspacePath)) {
+        this.approvedWorkspaceRoots.set(pathIdentityKey(workspacePath), workspacePath);
+        continue;
+      }
+      const remote = canonicalRemoteWorkspace(workspacePath);
+      if (remote) {
+        this.approvedRemoteWorkspaces.add(remote);
+      }
     }
     this.approvedWorkspaceRootsInitialized = true;
   }
@@ -50,7 +98,10 @@ export class WorkspaceRootsController implements WorkspaceRootsAccess {
 
   async refreshApprovedWorkspaceRootsFromState(state: PersistedState): Promise<void> {
     const roots = await getNormalizedWorkspaceRoots(state);
-    this.resetApprovedWorkspaceRoots(roots);
+    const remotes = state.workspaces
+      .map((workspace) => workspace.path)
+      .filter((workspacePath) => isSshWorkspacePath(workspacePath));
+    this.resetApprovedWorkspaceRoots([...roots, ...remotes]);
   }
 
   async ensureApprovedWorkspaceRoots(): Promise<void> {
@@ -64,7 +115,7 @@ export class WorkspaceRootsController implements WorkspaceRootsAccess {
   async assertApprovedWorkspacePath(workspacePath: string): Promise<string> {
     await this.ensureApprovedWorkspaceRoots();
     const normalized = await normalizeWorkspacePath(workspacePath);
-    if (!this.approvedWorkspaceRoots.has(normalized)) {
+    if (!this.approvedWorkspaceRoots.has(pathIdentityKey(normalized))) {
       throw new Error(
         "Workspace path is not approved. Use the workspace picker before saving or starting.",
       );
@@ -72,9 +123,33 @@ export class WorkspaceRootsController implements WorkspaceRootsAccess {
     return normalized;
   }
 
+  /** Accepts approved local folders and registered `ssh://` workspaces, for starting servers. */
+  async assertApprovedServerPath(workspacePath: string): Promise<string> {
+    if (!isSshWorkspacePath(workspacePath)) {
+      return await this.assertApprovedWorkspacePath(workspacePath);
+    }
+    await this.ensureApprovedWorkspaceRoots();
+    const remote = canonicalRemoteWorkspace(workspacePath);
+    if (!remote || !this.approvedRemoteWorkspaces.has(remote)) {
+      throw new Error("Remote workspace is not registered. Add it before saving or starting.");
+    }
+    return remote;
+  }
+
+  /** Approves an `ssh://` workspace the caller has already checked; returns its canonical URL. */
+  addApprovedRemoteWorkspace(url: string): string {
+    const target = parseSshWorkspaceUrl(url);
+    if (!target) {
+      throw new Error(`Not a remote workspace URL: ${url}`);
+    }
+    const remote = formatSshWorkspaceUrl(target);
+    this.approvedRemoteWorkspaces.add(remote);
+    return remote;
+  }
+
   async addApprovedWorkspacePath(workspacePath: string): Promise<string> {
     const normalized = await normalizeWorkspacePath(workspacePath);
-    this.approvedWorkspaceRoots.add(normalized);
+    this.approvedWorkspaceRoots.set(pathIdentityKey(normalized), normalized);
     this.approvedWorkspaceRootsInitialized = true;
     return normalized;
   }
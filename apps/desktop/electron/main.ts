@@ -1,12 +1,15 @@
 import fs from "node:fs/promises";
 import { createRequire } from "node:module";
 import path from "node:path";
+import { DatabaseSync } from "node:sqlite";
 import { fileURLToPath } from "node:url";
 
 import type * as Electron from "electron";
 import { hostPlatform } from "../../../src/platform/host";
+import { resolveDesktopFeatureFlags } from "../../../src/shared/featureFlags";
 import { CloudSyncService } from "../../../src/sync/service";
-import type { PersistedState } from "../src/app/types";
+import type { PersistedState, WorkspaceRecord } from "../src/app/types";
+import { pathPolicyEnvValue } from "../src/app/workspacePathPolicy";
 import { MAIN_WINDOW_MIN_WIDTH } from "../src/lib/adaptiveLayout";
 import {
   getCanvasCaptionSymbolTone,
@@ -14,15 +17,22 @@ import {
 } from "../src/lib/canvasAppearance";
 import {
   DESKTOP_EVENT_CHANNELS,
+  type DesktopDeepLinkNavigation,
   type DesktopMenuCommand,
+  type ScheduledTaskRun,
   type ShowCanvasWindowInput,
   type ShowQuickChatWindowInput,
   type SystemAppearance,
   type ThemeSource,
   type UpdaterState,
 } from "../src/lib/desktopApi";
-import { registerDesktopIpc } from "./ipc";
+import { type DesktopIpcRegistration, registerDesktopIpc } from "./ipc";
+import { drainDesktopInvocations, setDesktopErrorLocalizer } from "./ipc/invoke";
 import { WorkspaceRootsController } from "./ipc/workspaceRoots";
+import { AppDataTransferService } from "./services/appDataTransfer";
+import { AttachmentStore } from "./services/attachmentStore";
+import { AttachmentThumbnailer } from "./services/attachmentThumbnails";
+import { ApprovalQueue } from "./services/approvalQueue";
 import {
   applySystemAppearanceToWindow,
   applyThemeSourcePreference,
@@ -33,32 +43,88 @@ import {
   syncWindowAppearance,
 } from "./services/appearance";
 import { AppearancePreferences } from "./services/appearancePreferences";
+import { AppSettingsStore } from "./services/appSettings";
+import { ConnectivityMonitor } from "./services/connectivityMonitor";
+import { DockerWorkspaces } from "./services/containerWorkspaces";
 import {
   captureCrashReportingError,
   initElectronMainCrashReporting,
 } from "./services/crashReporting";
+import { DEEP_LINK_SCHEME, DeepLinkService } from "./services/deepLinks";
 import { runDesktopSmokePromptLoadCheck } from "./services/desktopSmoke";
 import { DiagnosticsService } from "./services/diagnostics";
-import { logError, logInfo, logWarn } from "./services/localLogs";
+import { buildConfirmDialog } from "./services/dialogs";
+import {
+  HEADLESS_FLAG,
+  type HeadlessOptions,
+  parseHeadlessArgs,
+  runHeadless,
+} from "./services/headless";
+import { IsolatedSessionPool } from "./services/isolatedSessions";
+import { flushLocalLogWrites, getLogsDir, logError, logInfo, logWarn } from "./services/localLogs";
 import {
   registerDesktopMediaProtocolHandler,
   registerDesktopMediaSchemePrivileges,
 } from "./services/mediaProtocol";
 import { installDesktopApplicationMenu } from "./services/menu";
 import { createMenuCommandDispatcher } from "./services/menuCommandDispatcher";
+import { MessageCatalog, type MessageKey } from "./services/messageCatalog";
 import { MobileRelayBridge } from "./services/mobileRelayBridge";
+import { ModelCatalogService } from "./services/modelCatalog";
+import { OnboardingService } from "./services/onboarding";
+import {
+  proxyEnvFromSettings,
+  testProxyConnectivity,
+  validateNetworkSettings,
+} from "./services/networkProxy";
+import { EXPORT_OPERATION_QUEUE, OperationManager } from "./services/operations";
+import { OrphanedServerService, SpawnedServerLedger } from "./services/orphanedServers";
 import { isPathEqualOrInside } from "./services/pathBoundary";
 import { PersistenceService } from "./services/persistence";
+import { ServerPowerManager } from "./services/powerManagement";
 import { DesktopProductAnalyticsService } from "./services/productAnalytics";
+import { applyLaunchProfile, relaunchArgsForProfile } from "./services/profiles";
+import { PromptLibraryStore } from "./services/promptLibrary";
+import { ProtocolReplayService } from "./services/protocolReplay";
+import { ProviderCredentialValidator } from "./services/providerCredentials";
 import { applyPublicTelemetryEnv } from "./services/publicTelemetryEnv";
 import { QuickChatController } from "./services/quickChatController";
 import { resolveElectronRemoteDebugConfig } from "./services/remoteDebug";
 import { resolveDesktopRendererUrl } from "./services/rendererUrl";
-import { ServerManager } from "./services/serverManager";
+import { ScheduledTaskService } from "./services/scheduledTasks";
+import { resolveSourceServerDir, ServerManager } from "./services/serverManager";
+import { ServerStatsMonitor } from "./services/serverStatsMonitor";
 import { createBeforeQuitHandler } from "./services/shutdown";
+import { isSidecarAdoptionEnabled, SidecarRegistry } from "./services/sidecarAdoption";
+import { SidecarUpdater } from "./services/sidecarUpdater";
+import { SourceReloadWatcher } from "./services/sourceReload";
+import {
+  createSingleInstanceLaunchData,
+  launchArgs,
+  openPathDeepLink,
+  resolveLaunchDeepLink,
+  resolveLaunchTranscriptFile,
+  resolveSecondInstanceLaunch,
+} from "./services/singleInstance";
+import { SleepClock } from "./services/sleepClock";
+import { SshRemoteWorkspaces } from "./services/sshRemoteWorkspaces";
+import { StateFileLock } from "./services/stateFileLock";
+import { StateSnapshotScheduler } from "./services/stateSnapshots";
+import { StorageGuard } from "./services/storageGuard";
+import { ThreadDraftStore } from "./services/threadDrafts";
+import { TranscriptAnnotationStore } from "./services/transcriptAnnotations";
+import { TranscriptBackupService } from "./services/transcriptBackup";
+import { TranscriptMirrorService } from "./services/transcriptMirror";
+import { TranscriptRetentionService } from "./services/transcriptRetention";
+import { TranscriptSubscriptions } from "./services/transcriptSubscriptions";
+import { TranscriptThrottle } from "./services/transcriptThrottle";
+import { isTranscriptExportPath, TranscriptViewerRegistry } from "./services/transcriptViewer";
+import { TrashService } from "./services/trash";
 import { resolveTrayIconPath } from "./services/trayIcon";
 import { DesktopUpdaterService } from "./services/updater";
+import { UsageAnalyticsService } from "./services/usageAnalytics";
 import { applyElectronUserDataDirOverride } from "./services/userDataOverride";
+import { createVoiceTranscriber, VoiceCaptureService } from "./services/voiceCapture";
 import { revealAndActivateWindow } from "./services/windowActivation";
 import {
   type NativeCloseWindow,
@@ -70,10 +136,28 @@ import {
   shouldUseMacosNativeGlass,
 } from "./services/windowEnhancements";
 import { loadMainWindowBounds, trackMainWindowBounds } from "./services/windowState";
+import { WorkspaceCheckpointService } from "./services/workspaceCheckpoints";
+import { WorkspaceConfigService } from "./services/workspaceConfig";
+import { WorkspaceDirectoryWatcher } from "./services/workspaceDirectoryWatcher";
+import { WorkspaceGitService } from "./services/workspaceGit";
+import { WorkspaceHealthService } from "./services/workspaceHealth";
+import { YoloGrants } from "./services/yoloGrants";
 
 const require = createRequire(import.meta.url);
-const { app, BrowserWindow, Menu, Notification, net, protocol, screen, shell } =
-  require("electron") as typeof Electron;
+const {
+  app,
+  BrowserWindow,
+  dialog,
+  Menu,
+  Notification,
+  net,
+  powerMonitor,
+  powerSaveBlocker,
+  protocol,
+  safeStorage,
+  screen,
+  shell,
+} = require("electron") as typeof Electron;
 
 const __filename = fileURLToPath(import.meta.url);
 const __dirname = path.dirname(__filename);
@@ -82,10 +166,14 @@ const DESKTOP_SMOKE_WORKSPACE_ENV = "COWORK_DESKTOP_SMOKE_WORKSPACE";
 const DESKTOP_SMOKE_OUTPUT_ENV = "COWORK_DESKTOP_SMOKE_OUTPUT";
 const DESKTOP_APP_NAME = "Cowork";
 const WINDOWS_APP_USER_MODEL_ID = "com.cowork.desktop";
+const CONNECTIVITY_PROBE_TIMEOUT_MS = 5_000;
 
 // App identity must be established before any service resolves `userData`.
 app.setName(DESKTOP_APP_NAME);
 const electronUserDataDirOverride = applyElectronUserDataDirOverride(app, process.env);
+const launchArgsOptions = { defaultApp: process.defaultApp === true };
+// Profiles nest inside the (possibly overridden) app-data root.
+const launchProfile = applyLaunchProfile(app, launchArgs(process.argv, launchArgsOptions));
 
 if (process.platform === "win32") {
   app.setAppUserModelId(WINDOWS_APP_USER_MODEL_ID);
@@ -98,6 +186,15 @@ applyPublicTelemetryEnv(process.env);
 // Must run before app ready so cowork-media images load in renderer <img> tags.
 registerDesktopMediaSchemePrivileges(protocol);
 
+// Unpackaged runs go through the electron binary, which needs the app entry to relaunch.
+if (process.defaultApp && process.argv[1]) {
+  app.setAsDefaultProtocolClient(DEEP_LINK_SCHEME, process.execPath, [
+    path.resolve(process.argv[1]),
+  ]);
+} else {
+  app.setAsDefaultProtocolClient(DEEP_LINK_SCHEME);
+}
+
 const productAnalytics = new DesktopProductAnalyticsService();
 const cloudSync = new CloudSyncService({
   env: process.env,
@@ -113,15 +210,247 @@ const cloudSync = new CloudSyncService({
     logInfo("cloud-sync", message, meta);
   },
 });
+const sidecarRegistry = isSidecarAdoptionEnabled(app.isPackaged)
+  ? new SidecarRegistry(app.getPath("userData"))
+  : null;
+const spawnLedger = new SpawnedServerLedger(app.getPath("userData"));
+const sidecarUpdater = new SidecarUpdater({
+  rootDir: path.join(app.getPath("userData"), "sidecar-updates"),
+  bundledVersion: app.getVersion(),
+  publicKey: globalThis.__COWORK_SIDECAR_UPDATE_PUBLIC_KEY__ ?? null,
+  enabled: app.isPackaged && !process.env.COWORK_DESKTOP_SIDECAR_PATH,
+  onStateChange: (state) => {
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.sidecarUpdateStateChanged, state);
+  },
+});
+const appSettings = new AppSettingsStore(app, {
+  validate: (next, current) => validateNetworkSettings(next, current),
+  emit: (settings) => emitDesktopEvent(DESKTOP_EVENT_CHANNELS.appSettingsChanged, settings),
+});
+const connectivity = new ConnectivityMonitor({
+  isOnline: () => net.isOnline(),
+  probe: async (url) => {
+    const result = await testProxyConnectivity({ url }, await appSettings.get(), {
+      timeoutMs: CONNECTIVITY_PROBE_TIMEOUT_MS,
+    });
+    return result.ok;
+  },
+  onChange: (status) => {
+    logInfo("connectivity", "connectivity changed", {
+      state: status.state,
+      unreachableHosts: status.unreachableHosts,
+    });
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.connectivityChanged, status);
+  },
+});
+const remoteWorkspaces = new SshRemoteWorkspaces();
+const approvalQueue = new ApprovalQueue({
+  filePath: path.join(app.getPath("userData"), "approvals.json"),
+});
+const sleepClock = new SleepClock();
 const serverManager = new ServerManager({
   getProductAnalyticsState: () => productAnalytics.getPersistedState(),
+  getNetworkEnv: async () => proxyEnvFromSettings(await appSettings.get()),
+  sidecarAdoption: sidecarRegistry ? { registry: sidecarRegistry } : null,
+  spawnLedger,
+  sidecarUpdates: sidecarUpdater,
+  remoteWorkspaces,
+  containerWorkspaces: new DockerWorkspaces(),
+  sleepClock,
+  preventAppSuspension: () => {
+    const blockerId = powerSaveBlocker.start("prevent-app-suspension");
+    return () => powerSaveBlocker.stop(blockerId);
+  },
+  beforeYoloStart: async (serverId) => {
+    const workspaceId = isolatedSessions.resolve(serverId)?.workspaceId ?? serverId;
+    const checkpoint = await workspaceCheckpoints.checkpointBeforeYoloStart(workspaceId);
+    if (checkpoint) {
+      logInfo("workspace-checkpoints", "Checkpointed workspace before YOLO start", {
+        workspaceId,
+        checkpointId: checkpoint.id,
+      });
+    }
+  },
+  onProxyConnectionStateChanged: (event) => {
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.workspaceProxyConnectionChanged, event);
+  },
   onWorkspaceServerExited: (event) => {
-    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.workspaceServerExited, event);
+    const slot = isolatedSessions.resolve(event.workspaceId);
+    if (!slot) {
+      void approvalQueue.clearPending(event.workspaceId).catch((error) => {
+        logWarn("approvals", "Failed to clear pending approvals", {
+          workspaceId: event.workspaceId,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      });
+      emitDesktopEvent(DESKTOP_EVENT_CHANNELS.workspaceServerExited, event);
+      return;
+    }
+    isolatedSessions.forget(event.workspaceId);
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.workspaceServerExited, {
+      ...event,
+      workspaceId: slot.workspaceId,
+      ...(slot.threadId ? { threadId: slot.threadId } : {}),
+    });
+  },
+});
+const isolatedSessions = new IsolatedSessionPool({
+  getMaxSidecars: async () => (await appSettings.get()).maxIsolatedSidecars,
+  stopServer: (serverId) => serverManager.stopWorkspaceServer(serverId),
+});
+// Once a grant lapses its servers are stopped, and the next start comes up sandboxed.
+const yoloGrants = new YoloGrants({
+  getDurationMs: async () => (await appSettings.get()).yoloGrantMinutes * 60_000,
+  onExpire: (workspaceId) => {
+    logInfo("audit", "yolo grant expired", { workspaceId });
+    for (const serverId of [workspaceId, ...isolatedSessions.drainWorkspace(workspaceId)]) {
+      void serverManager.stopWorkspaceServer(serverId).catch((error: unknown) => {
+        logError("server", error, { operation: "expire_yolo_grant", serverId });
+      });
+    }
   },
 });
 const mobileRelayBridge = new MobileRelayBridge({ serverManager });
-const persistence = new PersistenceService();
+const storageGuard = new StorageGuard({
+  getMinFreeMb: async () => (await appSettings.get()).minFreeDiskMb,
+});
+const trash = new TrashService({
+  dataDir: app.getPath("userData"),
+  getRetentionDays: async () => (await appSettings.get()).trashRetentionDays,
+  onError: (error) => {
+    logError("persistence", error, { operation: "purge_trash" });
+  },
+});
+const persistence = new PersistenceService({
+  storageGuard,
+  trash,
+  stateFileLock: new StateFileLock({
+    lockPath: path.join(app.getPath("userData"), "state.lock"),
+    openDatabase: (lockPath) => new DatabaseSync(lockPath),
+  }),
+  getTranscriptValidationMode: async () => (await appSettings.get()).transcriptValidation,
+  getTranscriptStoreKind: async () => (await appSettings.get()).transcriptStore,
+  setTranscriptStoreKind: async (transcriptStore) => {
+    await appSettings.update({ transcriptStore });
+  },
+  openTranscriptDatabase: (filePath) => new DatabaseSync(filePath),
+  onDiskSpaceChanged: (status) => {
+    if (status.low) {
+      logWarn("persistence", "free disk space is below the configured minimum", {
+        freeBytes: status.freeBytes,
+        minFreeBytes: status.minFreeBytes,
+      });
+    }
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.lowDiskSpace, status);
+  },
+});
+const transcriptSubscriptions = new TranscriptSubscriptions();
+const voiceCapture = new VoiceCaptureService({
+  transcribe: createVoiceTranscriber({ getSettings: () => appSettings.get() }),
+  emit: (event) => emitDesktopEvent(DESKTOP_EVENT_CHANNELS.voiceTranscript, event),
+  onInterimError: (error) => {
+    logWarn("voice", "interim transcription failed", { error: String(error) });
+  },
+});
+const transcriptThrottle = new TranscriptThrottle({
+  onThrottled: (event) => {
+    logWarn("persistence", "throttled transcript appends", {
+      threadId: event.threadId,
+      droppedEvents: event.droppedEvents,
+      droppedBytes: event.droppedBytes,
+      truncatedEvents: event.truncatedEvents,
+    });
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.transcriptThrottled, event);
+  },
+});
+persistence.onTranscriptAppended((events) => transcriptSubscriptions.publish(events));
+const transcriptMirror = new TranscriptMirrorService({
+  loadState: () => persistence.loadState(),
+  readTranscript: (transcriptId) => persistence.readTranscript(transcriptId),
+  onError: (transcriptId, error) => {
+    console.warn(`[desktop] Failed to mirror transcript ${transcriptId}: ${String(error)}`);
+  },
+});
+persistence.onTranscriptAppended((events) => transcriptMirror.handleAppended(events));
+const usageAnalytics = new UsageAnalyticsService({
+  openDatabase: () => new DatabaseSync(path.join(app.getPath("userData"), "usage.sqlite")),
+  loadState: () => persistence.loadState(),
+  onError: (error) => {
+    logError("usage-analytics", error);
+  },
+});
+persistence.onTranscriptAppended((events) => usageAnalytics.handleAppended(events));
+const stateSnapshots = new StateSnapshotScheduler({
+  capture: () => persistence.captureStateSnapshot(),
+  onError: (error) => {
+    logError("persistence", error, { operation: "capture_state_snapshot" });
+  },
+});
 const appearancePreferences = new AppearancePreferences(app);
+const transcriptAnnotations = new TranscriptAnnotationStore({
+  directory: path.join(app.getPath("userData"), "transcript-annotations"),
+  readTranscript: (threadId) => persistence.readTranscript(threadId),
+});
+const attachments = new AttachmentStore({
+  directory: path.join(app.getPath("userData"), "attachments"),
+});
+const attachmentThumbnails = new AttachmentThumbnailer({
+  attachments,
+  directory: path.join(app.getPath("userData"), "attachment-thumbnails"),
+});
+const threadDrafts = new ThreadDraftStore({
+  directory: path.join(app.getPath("userData"), "drafts"),
+  onError: (threadId, error) => {
+    logError("persistence", error, { operation: "save_thread_draft", threadId });
+  },
+});
+const appDataTransfer = new AppDataTransferService({
+  appVersion: app.getVersion(),
+  persistence,
+  settings: appSettings,
+  annotations: transcriptAnnotations,
+});
+const transcriptBackup = new TranscriptBackupService({
+  stateFilePath: path.join(app.getPath("userData"), "backup-sync.json"),
+  credentialsFilePath: path.join(app.getPath("userData"), "backup-credentials.json"),
+  keychain: safeStorage,
+  persistence,
+});
+const transcriptRetention = new TranscriptRetentionService({
+  listTranscriptFiles: () => persistence.listTranscriptFiles(),
+  deleteTranscript: async (transcriptId) => {
+    await persistence.deleteTranscript(transcriptId);
+    await transcriptAnnotations.delete(transcriptId);
+  },
+  loadState: () => persistence.loadState(),
+  getSettings: () => appSettings.get(),
+  emit: (summary) => {
+    logInfo("persistence", "pruned transcripts past the retention limits", {
+      deletedCount: summary.deletedCount,
+      reclaimedBytes: summary.reclaimedBytes,
+      failedCount: summary.failures.length,
+    });
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.transcriptRetentionCompleted, summary);
+  },
+  onError: (error) => {
+    logError("persistence", error, { operation: "transcript_retention" });
+  },
+});
+appSettings.subscribe((settings) => transcriptRetention.handleSettingsChanged(settings));
+const messages = new MessageCatalog();
+setDesktopErrorLocalizer((error) => messages.localizeError(error));
+appSettings.subscribe((settings) => {
+  const previous = messages.getLocale();
+  if (messages.setLocale(settings.locale ?? app.getLocale()) !== previous) {
+    quickChatController?.refreshLabels();
+  }
+});
+appSettings.subscribe((settings) => {
+  // nativeTheme reports the change, which repaints windows and notifies the renderer.
+  if (getSystemAppearanceSnapshot().themeSource !== settings.themeSource) {
+    applyThemeSourcePreference(settings.themeSource);
+  }
+});
 // Shared between the cowork-media protocol handler and desktop IPC so both
 // enforce (and observe approvals against) the same workspace-root boundary.
 const workspaceRoots = new WorkspaceRootsController(persistence);
@@ -146,9 +475,195 @@ const diagnostics = new DiagnosticsService({
   persistence,
   updater,
   serverDiagnostics: () => serverManager.getDiagnostics(),
+  storageGuard,
+});
+const serverStatsMonitor = new ServerStatsMonitor({
+  sample: () => serverManager.getAllServerStats(),
+  emit: (event) => emitDesktopEvent(DESKTOP_EVENT_CHANNELS.serverStats, event),
+  onError: (error) => {
+    logError("server", error, { operation: "sample_server_stats" });
+  },
+});
+const serverPower = new ServerPowerManager({
+  getSettings: () => appSettings.get(),
+  getRunningServerIds: () => serverManager.getRunningServerIds(),
+  // Isolated thread servers are already capped by their pool, and the mobile
+  // relay needs its workspace's server even while the desktop is idle.
+  canSuspend: (serverId) =>
+    !isolatedSessions.resolve(serverId) && !mobileRelayBridge.isActiveForWorkspace(serverId),
+  loadThreads: async () => (await persistence.loadState()).threads,
+  loadIdlePolicies: async () => (await persistence.loadState()).workspaces,
+  flushServer: (serverId) => serverManager.flushWorkspaceServer(serverId),
+  stopServer: (serverId) => serverManager.stopWorkspaceServer(serverId),
+  onSuspended: (event) => {
+    logInfo("server", "suspended idle workspace server", {
+      workspaceId: event.workspaceId,
+      idleMinutes: event.idleMinutes,
+      onBattery: event.onBattery,
+    });
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.serverSuspended, event);
+  },
+  onResumed: (event) => emitDesktopEvent(DESKTOP_EVENT_CHANNELS.serverResumed, event),
+  onError: (error) => {
+    logError("server", error, { operation: "suspend_idle_servers" });
+  },
+});
+// Dev only: servers run from `src/server` restart when their source changes.
+const sourceServerDir = resolveSourceServerDir();
+const sourceReload = sourceServerDir
+  ? new SourceReloadWatcher({
+      sourceDir: sourceServerDir,
+      getServerIds: () => serverManager.getSourceServerIds(),
+      reloadServer: (serverId) => serverManager.reloadSourceServer(serverId),
+      onReloaded: ({ serverId, reason, changedPaths }) => {
+        logInfo("server", "reloaded workspace server after source change", { serverId, reason });
+        serverPower.recordServerStarted(serverId);
+        const slot = isolatedSessions.resolve(serverId);
+        emitDesktopEvent(DESKTOP_EVENT_CHANNELS.serverReloaded, {
+          workspaceId: slot?.workspaceId ?? serverId,
+          ...(slot?.threadId ? { threadId: slot.threadId } : {}),
+          reason,
+          changedPaths,
+          reloadedAt: new Date().toISOString(),
+        });
+      },
+      onError: (error, serverId) => {
+        logError("server", error, { operation: "reload_source_server", serverId });
+      },
+    })
+  : null;
+const workspaceHealth = new WorkspaceHealthService({ persistence, serverManager });
+const onboarding = new OnboardingService({
+  loadState: () => persistence.loadState(),
+  writableDirs: [app.getPath("userData"), getLogsDir()],
+  isPackaged: app.isPackaged,
+  verifyInstallation: () => serverManager.verifyInstallation(),
+  keychain: safeStorage,
+});
+const promptLibrary = new PromptLibraryStore({
+  globalFilePath: path.join(app.getPath("userData"), "prompt-library.json"),
+  loadState: () => persistence.loadState(),
+});
+const workspaceConfig = new WorkspaceConfigService({
+  loadState: () => persistence.loadState(),
+  emit: (snapshot) => emitDesktopEvent(DESKTOP_EVENT_CHANNELS.workspaceConfigChanged, snapshot),
+  onError: (workspaceId, error) => {
+    logError("workspace-config", error, { workspaceId });
+  },
 });
+const directoryWatcher = new WorkspaceDirectoryWatcher();
+const workspaceGit = new WorkspaceGitService({
+  loadState: () => persistence.loadState(),
+  emit: (status) => emitDesktopEvent(DESKTOP_EVENT_CHANNELS.workspaceGitChanged, status),
+  directoryWatcher,
+  onError: (workspaceId, error) => {
+    logError("workspace-git", error, { workspaceId });
+  },
+});
+const workspaceCheckpoints = new WorkspaceCheckpointService({
+  loadState: () => persistence.loadState(),
+});
+const providerCredentials = new ProviderCredentialValidator();
+const protocolReplay = new ProtocolReplayService();
+const orphanedServers = new OrphanedServerService({
+  ledger: spawnLedger,
+  // Registered sidecars stay protected until a workspace opens and adopts them.
+  getProtectedPids: async () => [
+    ...serverManager.getManagedPids(),
+    ...((await sidecarRegistry?.list()) ?? []).map((entry) => entry.pid),
+  ],
+});
+const operations = new OperationManager({
+  emit: (snapshot, queue) => {
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.operationUpdated, snapshot);
+    if (queue === EXPORT_OPERATION_QUEUE) {
+      const finished = snapshot.status !== "queued" && snapshot.status !== "running";
+      emitDesktopEvent(
+        finished ? DESKTOP_EVENT_CHANNELS.exportComplete : DESKTOP_EVENT_CHANNELS.exportProgress,
+        snapshot,
+      );
+    }
+  },
+});
+/** Starts a workspace's server from the main process, as the renderer would. */
+async function startServerForWorkspace(workspace: WorkspaceRecord, state: PersistedState) {
+  const workspacePath = await workspaceRoots.assertApprovedServerPath(workspace.path);
+  const additionalRoots = await Promise.all(
+    (workspace.additionalRoots ?? []).map((rootPath) =>
+      workspaceRoots.assertApprovedWorkspacePath(rootPath),
+    ),
+  );
+  const pathPolicy = pathPolicyEnvValue(workspace);
+  const started = await serverManager.startWorkspaceServer({
+    workspaceId: workspace.id,
+    workspacePath,
+    yolo: workspace.yolo && yoloGrants.activeUntil(workspace.id) !== null,
+    featureFlags: resolveDesktopFeatureFlags({
+      isPackaged: app.isPackaged,
+      env: process.env,
+      ...(state.desktopFeatureFlagOverrides
+        ? { overrides: state.desktopFeatureFlagOverrides }
+        : {}),
+    }),
+    privacyTelemetrySettings: state.privacyTelemetrySettings,
+    ...(workspace.resourceLimits ? { resourceLimits: workspace.resourceLimits } : {}),
+    ...(workspace.container ? { container: workspace.container } : {}),
+    ...(additionalRoots.length > 0 ? { additionalRoots } : {}),
+    ...(pathPolicy ? { pathPolicy } : {}),
+    // Matches servers the renderer starts, whichever path starts this one.
+    productAnalyticsState: productAnalytics.getPersistedState(),
+  });
+  serverPower.recordServerStarted(workspace.id);
+  return started;
+}
+
+const transcriptViewer = new TranscriptViewerRegistry();
+const deepLinks = new DeepLinkService({
+  loadState: () => persistence.loadState(),
+  startWorkspaceServer: startServerForWorkspace,
+  confirmOpenPath: async (directory) => {
+    const built = buildConfirmDialog({
+      title: "Open Folder",
+      message: `Open "${path.basename(directory) || directory}" as a workspace?`,
+      detail: `A link asked ${DESKTOP_APP_NAME} to open ${directory}.`,
+      kind: "info",
+      confirmLabel: "Open Workspace",
+    });
+    const ownerWindow = await ensureMainWindow();
+    const response = await dialog.showMessageBox(ownerWindow, built.options);
+    return response.response === built.confirmButtonIndex;
+  },
+  approveWorkspacePath: (directory) => workspaceRoots.addApprovedWorkspacePath(directory),
+  openTranscriptFile: (filePath) => transcriptViewer.open(filePath),
+  deliver: (navigation) => deliverDeepLink(navigation),
+});
+const modelCatalog = new ModelCatalogService({
+  filePath: path.join(app.getPath("userData"), "model-catalog.json"),
+  clientVersion: app.getVersion(),
+  loadState: () => persistence.loadState(),
+  startWorkspaceServer: startServerForWorkspace,
+});
+const scheduledTasks = new ScheduledTaskService({
+  filePath: path.join(app.getPath("userData"), "scheduled-tasks.json"),
+  clientVersion: app.getVersion(),
+  loadState: () => persistence.loadState(),
+  startWorkspaceServer: startServerForWorkspace,
+  recordRun: (input) => persistence.recordThread(input),
+  onRunCompleted: (run) => {
+    emitDesktopEvent(DESKTOP_EVENT_CHANNELS.scheduledTaskRunCompleted, run);
+    showScheduledTaskNotification(run);
+  },
+  onError: (error) => {
+    logError("scheduled_tasks", error);
+  },
+});
+// Links can arrive (open-url, argv) before the main window exists; they wait for startup.
+let deepLinksReady = false;
+const queuedDeepLinkUrls: string[] = [];
+const queuedTranscriptFiles: string[] = [];
 const windowCloseCoordinator = new NativeWindowCloseCoordinator();
 let unregisterAppearanceListener = () => {};
+let desktopIpc: DesktopIpcRegistration | null = null;
 let mainWindow: Electron.BrowserWindow | null = null;
 let quickChatController: QuickChatController | null = null;
 const menuCommandDispatcher = createMenuCommandDispatcher();
@@ -172,8 +687,21 @@ logInfo("main", "desktop process starting", {
   ...(electronUserDataDirOverride.applied
     ? { userDataDirOverride: electronUserDataDirOverride.path }
     : {}),
+  profileId: launchProfile.profileId,
 });
 
+/** Starts a new instance in `profileId`, keeping the other launch args, and quits this one. */
+function relaunchIntoProfile(profileId: string): void {
+  const args = launchArgs(process.argv, launchArgsOptions);
+  app.relaunch({
+    args: [
+      ...process.argv.slice(1, process.argv.length - args.length),
+      ...relaunchArgsForProfile(args, profileId),
+    ],
+  });
+  app.quit();
+}
+
 function emitDesktopEvent(channel: string, payload: unknown): void {
   for (const win of BrowserWindow.getAllWindows()) {
     if (win.isDestroyed()) {
@@ -183,10 +711,57 @@ function emitDesktopEvent(channel: string, payload: unknown): void {
   }
 }
 
+/**
+ * The theme override lives in app settings; builds before that kept it in
+ * appearance.json, which is carried over once and then reset so a later
+ * switch back to "system" sticks.
+ */
+async function loadInitialThemeSource(): Promise<ThemeSource> {
+  try {
+    const settings = await appSettings.get();
+    if (settings.themeSource !== "system") {
+      return settings.themeSource;
+    }
+    const legacy = await appearancePreferences.loadThemeSource();
+    if (legacy !== "system") {
+      await appSettings.update({ themeSource: legacy });
+      await appearancePreferences.saveThemeSource("system");
+    }
+    return legacy;
+  } catch {
+    return "system";
+  }
+}
+
 function emitSystemAppearance(): void {
   emitDesktopEvent(DESKTOP_EVENT_CHANNELS.systemAppearanceChanged, getSystemAppearanceSnapshot());
 }
 
+const SCHEDULED_TASK_NOTIFICATION_TITLES: Record<ScheduledTaskRun["status"], MessageKey> = {
+  completed: "notification.scheduledTask.completed",
+  failed: "notification.scheduledTask.failed",
+  interrupted: "notification.scheduledTask.interrupted",
+};
+
+function showScheduledTaskNotification(run: ScheduledTaskRun): void {
+  if (!Notification.isSupported()) {
+    return;
+  }
+  const notification = new Notification({
+    title: messages.t(SCHEDULED_TASK_NOTIFICATION_TITLES[run.status]),
+    body: run.error ? `${run.taskName}: ${run.error}` : run.taskName,
+    silent: false,
+  });
+  notification.on("click", () => {
+    const win = mainWindow ?? BrowserWindow.getAllWindows()[0];
+    if (win && !win.isDestroyed()) {
+      if (win.isMinimized()) win.restore();
+      win.focus();
+    }
+  });
+  notification.show();
+}
+
 function showUpdateReadyNotification(state: UpdaterState): void {
   if (!Notification.isSupported()) {
     return;
@@ -194,16 +769,16 @@ function showUpdateReadyNotification(state: UpdaterState): void {
   const version = state.release?.version;
   const isWindows = process.platform === "win32";
   const notification = new Notification({
-    title: "Update ready",
+    title: messages.t("notification.updateReady.title"),
     body: version
-      ? `Cowork ${version} is ready. Restart to install.`
-      : "Cowork update is ready. Restart to install.",
+      ? messages.t("notification.updateReady.bodyWithVersion", { version })
+      : messages.t("notification.updateReady.body"),
     silent: false,
     ...(isWindows
       ? {
           actions: [
-            { type: "button" as const, text: "Restart Now" },
-            { type: "button" as const, text: "Later" },
+            { type: "button" as const, text: messages.t("notification.updateReady.restartNow") },
+            { type: "button" as const, text: messages.t("notification.updateReady.later") },
           ],
         }
       : {}),
@@ -244,6 +819,50 @@ async function sendMenuCommand(command: DesktopMenuCommand): Promise<void> {
   );
 }
 
+async function deliverDeepLink(navigation: DesktopDeepLinkNavigation): Promise<boolean> {
+  const existingMainWindow = mainWindow && !mainWindow.isDestroyed() ? mainWindow : null;
+  const target = await ensureMainWindow();
+  // A loading renderer drains queued links once it subscribes.
+  if (!existingMainWindow || target.webContents.isLoading()) {
+    return false;
+  }
+  target.webContents.send(DESKTOP_EVENT_CHANNELS.deepLinkNavigation, navigation);
+  return true;
+}
+
+function openDeepLink(rawUrl: string): void {
+  if (!deepLinksReady) {
+    queuedDeepLinkUrls.push(rawUrl);
+    return;
+  }
+  void deepLinks.handle(rawUrl).catch((error: unknown) => {
+    const message = error instanceof Error ? error.message : String(error);
+    logWarn("deep-link", "deep link rejected", { error: message });
+    dialog.showErrorBox("Couldn't open link", message);
+  });
+}
+
+function openTranscriptExport(filePath: string): void {
+  if (!deepLinksReady) {
+    queuedTranscriptFiles.push(filePath);
+    return;
+  }
+  void deepLinks.openTranscriptFile(filePath).catch((error: unknown) => {
+    const message = error instanceof Error ? error.message : String(error);
+    logWarn("deep-link", "exported thread rejected", { error: message });
+    dialog.showErrorBox("Couldn't open thread", message);
+  });
+}
+
+/** Routes a file or folder the OS hands the app: exported threads open read-only. */
+function openLaunchPath(target: string): void {
+  if (isTranscriptExportPath(target)) {
+    openTranscriptExport(target);
+    return;
+  }
+  openDeepLink(openPathDeepLink(target));
+}
+
 function isExternalUrl(rawUrl: string): boolean {
   try {
     const parsed = new URL(rawUrl);
@@ -333,13 +952,19 @@ function applyWindowSecurity(win: Electron.BrowserWindow): void {
     event.preventDefault();
   });
 
+  // The app's own pages may open the microphone for dictation; nothing else is granted.
   win.webContents.session.setPermissionRequestHandler(
     (
-      _webContents: Electron.WebContents,
-      _permission: string,
+      webContents: Electron.WebContents,
+      permission: string,
       callback: (permissionGranted: boolean) => void,
+      details: { mediaTypes?: string[] },
     ) => {
-      callback(false);
+      const microphoneOnly =
+        permission === "media" &&
+        (details.mediaTypes?.length ?? 0) > 0 &&
+        details.mediaTypes?.every((type) => type === "audio") === true;
+      callback(microphoneOnly && isTrustedRendererNavigation(webContents.getURL()));
     },
   );
 }
@@ -769,23 +1394,105 @@ async function ensureMainWindow(): Promise<Electron.BrowserWindow> {
   return win;
 }
 
-const gotSingleInstanceLock = app.requestSingleInstanceLock();
+async function runHeadlessLaunch(): Promise<void> {
+  let options: HeadlessOptions | null;
+  try {
+    options = parseHeadlessArgs(launchArgs(process.argv, launchArgsOptions));
+  } catch (error) {
+    const message = error instanceof Error ? error.message : String(error);
+    process.stderr.write(`${JSON.stringify({ type: "error", message })}\n`);
+    app.exit(2);
+    return;
+  }
+  if (!options) {
+    app.exit(0);
+    return;
+  }
+  const abort = new AbortController();
+  process.once("SIGINT", () => abort.abort());
+  process.once("SIGTERM", () => abort.abort());
+  const exitCode = await runHeadless(options, {
+    loadState: () => persistence.loadState(),
+    readTranscript: (threadId) => persistence.readTranscript(threadId),
+    startServer: async (opts) => (await serverManager.startWorkspaceServer(opts)).url,
+    stopServer: (workspaceId) => serverManager.stopWorkspaceServer(workspaceId),
+    write: (line) => process.stdout.write(`${line}\n`),
+    writeError: (line) => process.stderr.write(`${line}\n`),
+    signal: abort.signal,
+    cwd: process.cwd(),
+  });
+  await serverManager.stopAll();
+  app.exit(exitCode);
+}
+
+const isHeadlessLaunch = launchArgs(process.argv, launchArgsOptions).includes(HEADLESS_FLAG);
+// A second launch hands its arguments to the running instance and exits before touching
+// state.json or starting any workspace server. Headless runs skip the lock so scripts
+// can use the app while a window is open.
+const gotSingleInstanceLock =
+  isHeadlessLaunch ||
+  app.requestSingleInstanceLock(
+    createSingleInstanceLaunchData(process.argv, process.cwd(), launchArgsOptions),
+  );
 if (!gotSingleInstanceLock) {
   app.quit();
+} else if (isHeadlessLaunch) {
+  app.dock?.hide();
+  void app.whenReady().then(runHeadlessLaunch);
 } else {
-  app.on("second-instance", () => {
+  app.on("second-instance", (_event, argv, workingDirectory, additionalData) => {
+    const launch = resolveSecondInstanceLaunch(
+      argv,
+      workingDirectory,
+      additionalData,
+      launchArgsOptions,
+    );
+    const deepLinkUrl = resolveLaunchDeepLink(launch);
+    if (deepLinkUrl) {
+      openDeepLink(deepLinkUrl);
+      return;
+    }
+    const transcriptFile = resolveLaunchTranscriptFile(launch);
+    if (transcriptFile) {
+      openTranscriptExport(transcriptFile);
+      return;
+    }
     void quickChatController?.showMainWindow();
   });
 
+  app.on("open-url", (event, url) => {
+    event.preventDefault();
+    openDeepLink(url);
+  });
+
+  // macOS delivers Finder "Open With", drops on the Dock icon, and share targets here
+  // instead of argv, and may do so before the app is ready.
+  app.on("open-file", (event, filePath) => {
+    event.preventDefault();
+    openLaunchPath(filePath);
+  });
+
+  const initialLaunch = createSingleInstanceLaunchData(
+    process.argv,
+    process.cwd(),
+    launchArgsOptions,
+  );
+  const launchDeepLinkUrl = resolveLaunchDeepLink(initialLaunch);
+  const launchTranscriptFile = resolveLaunchTranscriptFile(initialLaunch);
+  if (launchDeepLinkUrl) {
+    queuedDeepLinkUrls.push(launchDeepLinkUrl);
+  } else if (launchTranscriptFile) {
+    queuedTranscriptFiles.push(launchTranscriptFile);
+  }
+
   app
     .whenReady()
     .then(async () => {
       registerDesktopMediaProtocolHandler(protocol, net, workspaceRoots);
+      await stateSnapshots.runOnce();
       const initialState: PersistedState | null = await persistence.loadState().catch(() => null);
-      const initialThemeSource = await appearancePreferences
-        .loadThemeSource()
-        .catch((): ThemeSource => "system");
-      applyThemeSourcePreference(initialThemeSource);
+      applyThemeSourcePreference(await loadInitialThemeSource());
+      messages.setLocale((await appSettings.get().catch(() => null))?.locale ?? app.getLocale());
       await initElectronMainCrashReporting(initialState?.privacyTelemetrySettings);
       let preparedInitialState = initialState;
       if (preparedInitialState) {
@@ -816,24 +1523,63 @@ if (!gotSingleInstanceLock) {
         createQuickChatWindow,
         retargetQuickChatWindow,
         createUtilityWindow,
+        messages,
       });
       if (preparedInitialState) {
         quickChatController.applyPersistedState(preparedInitialState);
       }
       quickChatController.initialize();
 
-      registerDesktopIpc({
-        appearancePreferences,
+      desktopIpc = registerDesktopIpc({
+        appDataTransfer,
+        approvalQueue,
+        appSettings,
+        attachments,
+        attachmentThumbnails,
         mobileRelayBridge,
         persistence,
         workspaceRoots,
+        directoryWatcher,
         productAnalytics,
+        profile: launchProfile,
         cloudSync,
+        connectivity,
         diagnostics,
+        isolatedSessions,
+        modelCatalog,
+        onboarding,
+        operations,
+        orphanedServers,
+        promptLibrary,
+        protocolReplay,
+        providerCredentials,
+        remoteWorkspaces,
+        scheduledTasks,
         serverManager,
+        serverPower,
+        sidecarUpdater,
+        storageGuard,
+        threadDrafts,
+        transcriptAnnotations,
+        transcriptBackup,
+        transcriptMirror,
+        transcriptRetention,
+        transcriptSubscriptions,
+        transcriptThrottle,
+        transcriptViewer,
+        trash,
         updater,
+        usageAnalytics,
+        voiceCapture,
+        workspaceCheckpoints,
+        workspaceConfig,
+        workspaceGit,
+        workspaceHealth,
+        yoloGrants,
         showMainWindow: () => quickChatController?.showMainWindow(),
+        relaunchIntoProfile,
         consumePendingMenuCommands: () => menuCommandDispatcher.drainPending(),
+        consumePendingDeepLinks: () => deepLinks.drainPending(),
         showQuickChatWindow: (opts?: ShowQuickChatWindowInput) =>
           quickChatController?.showQuickChatWindow(opts),
         showCanvasWindow: (opts: ShowCanvasWindowInput) => {
@@ -879,7 +1625,36 @@ if (!gotSingleInstanceLock) {
       });
 
       updater.start();
-      void ensureMainWindow();
+      stateSnapshots.start();
+      transcriptRetention.start();
+      trash.start();
+      scheduledTasks.start();
+      serverStatsMonitor.start();
+      serverPower.start(powerMonitor);
+      sleepClock.attach(powerMonitor);
+      sourceReload?.start();
+      connectivity.start();
+      void orphanedServers
+        .list()
+        .then((orphans) => {
+          if (orphans.length > 0) {
+            logWarn("server", "found workspace servers left running by an earlier launch", {
+              pids: orphans.map((orphan) => orphan.pid),
+            });
+          }
+        })
+        .catch((error) => {
+          logError("server", error, { operation: "scan_orphaned_servers" });
+        });
+      void ensureMainWindow().then(() => {
+        deepLinksReady = true;
+        for (const url of queuedDeepLinkUrls.splice(0, queuedDeepLinkUrls.length)) {
+          openDeepLink(url);
+        }
+        for (const filePath of queuedTranscriptFiles.splice(0, queuedTranscriptFiles.length)) {
+          openTranscriptExport(filePath);
+        }
+      });
 
       app.on("activate", () => {
         void ensureMainWindow();
@@ -898,20 +1673,64 @@ if (!gotSingleInstanceLock) {
     "before-quit",
     createBeforeQuitHandler({
       unregisterAppearanceListener: () => unregisterAppearanceListener(),
+      stopSchedulers: () => {
+        stateSnapshots.dispose();
+        transcriptRetention.dispose();
+        trash.dispose();
+        scheduledTasks.dispose();
+        serverStatsMonitor.dispose();
+        serverPower.dispose();
+        sleepClock.dispose();
+        sourceReload?.dispose();
+        transcriptThrottle.dispose();
+        voiceCapture.dispose();
+        connectivity.dispose();
+        workspaceConfig.dispose();
+        workspaceGit.dispose();
+        void protocolReplay.dispose();
+      },
       stopUpdater: () => updater.dispose(),
+      drainRunQueue: async () => {
+        operations.cancelAll();
+        await Promise.all([drainDesktopInvocations(), operations.drain()]);
+      },
+      flushTranscripts: async () => {
+        await persistence.flushBufferedTranscripts().catch((error: unknown) => {
+          logError("persistence", error, { operation: "flush_buffered_transcripts" });
+        });
+        await persistence.flushTranscriptWrites();
+        await transcriptMirror.flush();
+        await usageAnalytics.flush();
+        usageAnalytics.dispose();
+        persistence.closeTranscriptDatabase();
+      },
+      persistState: async () => {
+        await persistence.flushStateWrites();
+        await threadDrafts.flush();
+        await stateSnapshots.runOnce();
+      },
       stopMobileRelayBridge: async () => {
         mobileRelayBridge.stopForShutdown();
       },
       stopQuickChat: () => quickChatController?.dispose(),
       stopProductAnalytics: () => productAnalytics.shutdown(),
       stopCloudSync: () => cloudSync.shutdown(),
-      stopAllServers: () => serverManager.stopAll(),
-      quit: () => app.quit(),
-      onError: (error) => {
-        logError("shutdown", error, { operation: "stop_workspace_servers" });
-        console.error(
-          `[desktop] Failed to stop workspace servers during shutdown: ${String(error)}`,
-        );
+      stopAllServers: () =>
+        updater.isInstallingUpdate() ? serverManager.handOffAll() : serverManager.stopAll(),
+      stopWatchers: () => desktopIpc?.stopWatchers(),
+      log: (level, message, meta) => {
+        if (level === "warn") {
+          logWarn("shutdown", message, meta);
+          return;
+        }
+        logInfo("shutdown", message, meta);
+      },
+      quit: () => {
+        void flushLocalLogWrites().finally(() => app.quit());
+      },
+      onError: (error, phase) => {
+        logError("shutdown", error, { operation: "shutdown_phase", phase });
+        console.error(`[desktop] Shutdown phase ${phase ?? "unknown"} failed: ${String(error)}`);
       },
     }),
   );
@@ -14,117 +14,376 @@ import {
 import { resolveProductAnalyticsConfig } from "../../../src/telemetry/productAnalytics";
 import type { PersistedState } from "../src/app/types";
 import {
+  type AnnotateEventInput,
+  type AppendVoiceAudioInput,
+  type AppLogsResult,
+  type AppSettings,
+  type ArchiveThreadInput,
+  type BackupSyncConfig,
   type CaptureProductEventInput,
+  type CleanupWorkspaceStorageInput,
+  type ConfigureBackupSyncInput,
   type ConfirmActionInput,
+  type ConnectivityStatus,
   type CopyFileToWorkspaceUploadsInput,
   type CopyPathInput,
+  type CopyTranscriptSelectionInput,
+  type CopyTranscriptSelectionResult,
   type CreateDirectoryInput,
   type CreateOneOffChatWorkspaceInput,
+  type CreateProfileInput,
+  type CreateScheduledTaskInput,
+  type CreateScratchThreadInput,
+  type DeletePromptTemplateInput,
+  type DeleteScheduledTaskInput,
   DESKTOP_EVENT_CHANNELS,
   DESKTOP_IPC_CHANNELS,
   type DeleteTranscriptInput,
+  type DeleteWorkspaceInput,
   type DesktopApi,
+  type DesktopBackendCapabilities,
   type DesktopCrashReportingConfig,
+  type DesktopDeepLinkNavigation,
   type DesktopMenuCommand,
   type DesktopNotificationInput,
   type DesktopProductAnalyticsConfig,
+  type DesktopProfile,
+  type DesktopProfileList,
   type DiagnosticsBundlePathInput,
+  type DiskSpaceStatus,
+  type DroppedWorkspaceFolder,
+  type DuplicateWorkspaceInput,
+  type EnqueueExportInput,
+  type ExportAppDataInput,
+  type ForkThreadInput,
+  type GenerateHandoffLinkInput,
+  type GetAttachmentInput,
+  type GetThumbnailInput,
+  type GetThreadFileChangesInput,
+  type GetThreadPreviewsInput,
+  type GetUsageSummaryInput,
+  type GetWorkspaceStorageReportInput,
+  type GlobalSearchInput,
+  type ImportAppDataInput,
+  type ListAnnotationsInput,
+  type ListAvailableModelsInput,
   type ListDirectoryInput,
+  type ListPendingApprovalsInput,
+  type ListPromptTemplatesInput,
+  type LoadThreadDraftInput,
+  type MergeThreadsInput,
+  type MigrateTranscriptStoreInput,
   type MobileRelayBridgeState,
   type MobileRelayForgetTrustedPhoneInput,
   type MobileRelayStartInput,
   type MobileRelayUpdateTrustedPhonePermissionsInput,
   type OpenExternalUrlInput,
+  type OpenTranscriptFileInput,
+  type OperationIdInput,
+  type OperationSnapshot,
+  type OrphanedServer,
+  type OrphanedServerCleanupResult,
   type OpenPathInput,
+  type PersistScratchThreadInput,
   type PickCanvasSavePathInput,
   type PickDirectoryInput,
   type PlatformChromeInfo,
   type PreferredFileAppInput,
   type PreviewOSFileInput,
+  type PreviewPathPolicyInput,
+  type PreviewTranscriptRetentionInput,
+  type PrewarmWorkspaceServersInput,
+  type PrewarmWorkspaceServersResult,
+  type ProviderCredentialValidation,
+  type ProxyConnectivityResult,
+  type PurgeThreadsInput,
+  type ReadAppLogsInput,
   type ReadFileForPreviewInput,
   type ReadFileInput,
   type ReadTranscriptInput,
+  type ReadTranscriptPageInput,
+  type ReadWorkspaceConfigInput,
+  type RebuildStateInput,
+  type RecordPendingApprovalInput,
+  type RedactTranscriptInput,
+  type RegisterRemoteWorkspaceInput,
+  type ReleaseThreadServerInput,
   type RenamePathInput,
   type RendererLogInput,
+  type RenderPromptInput,
+  type ReorderWorkspacesInput,
+  type ReplaySession,
+  type RequestYoloGrantInput,
+  type ResolveApprovalInput,
+  type RestoreFromTrashInput,
+  type RestoreStateSnapshotInput,
+  type RestoreWorkspaceCheckpointInput,
+  type RestoreWorkspaceCheckpointResult,
   type RevealPathInput,
+  type RevokeHandoffInput,
+  type SaveBackupCredentialsInput,
+  type SaveBackupCredentialsResult,
   type SaveExportedFileInput,
+  type SavePromptTemplateInput,
+  type SaveThreadDraftInput,
+  type ScanForWorkspacesInput,
+  type ScanForWorkspacesResult,
+  type ScheduledTaskRun,
+  type ServerReloadedEvent,
+  type ServerResumedEvent,
+  type ServerStatsEvent,
+  type ServerSuspendedEvent,
+  type SetLocaleInput,
+  type SetLogLevelInput,
+  type SetScheduledTaskEnabledInput,
+  type SetTranscriptEncryptionInput,
   type SetWindowAppearanceInput,
+  type SetWorkspaceIdlePolicyInput,
+  type SetWorkspaceLaunchProfilesInput,
+  type SetWorkspacePathPolicyInput,
+  type SetWorkspacePinnedInput,
+  type SetWorkspaceTagsInput,
+  type ShareTranscriptInput,
   type ShowCanvasWindowInput,
   type ShowContextMenuInput,
   type ShowQuickChatWindowInput,
+  type SidecarUpdateState,
+  type StartOperationInput,
+  type StartReplayInput,
+  type StartVoiceCaptureInput,
   type StartWorkspaceServerInput,
+  type StopReplayInput,
+  type StopVoiceCaptureInput,
   type StopWorkspaceServerInput,
+  type StoreAttachmentInput,
+  type SuggestThreadTitleInput,
+  type SwitchProfileInput,
   type SystemAppearance,
   type TelemetryStatusInput,
   type TelemetryStatusSnapshot,
+  type TestProxyConnectivityInput,
+  type ThreadFileChanges,
+  type TranscriptAppendedEvent,
   type TranscriptBatchInput,
+  type TranscriptInspection,
+  type TranscriptRedactionResult,
+  type TranscriptRepairResult,
+  type TranscriptRetentionPreview,
+  type TranscriptRetentionSummary,
+  type TranscriptThrottledEvent,
+  type TranscriptTimestampNormalizationResult,
   type TrashPathInput,
+  type UpdateAppSettingsInput,
   type UpdaterState,
   type UploadDiagnosticsBundleInput,
+  type UsageSummary,
+  type ValidateProviderCredentialsInput,
+  type VoiceTranscriptEvent,
   type WatchWorkspaceDirectoryInput,
   type WindowCloseRequest,
   type WindowCloseResponseInput,
   type WindowDragPointInput,
+  type WorkspaceCheckpointList,
+  type WorkspaceConfigSnapshot,
+  type WorkspaceGitStatus,
+  type WorkspaceHealthReport,
+  type WorkspaceProxyConnectionEvent,
   type WorkspaceServerExitedEvent,
+  type WorkspaceServerPrewarmProgress,
+  type WorkspaceServerStartupDiagnostics,
   type WorkspaceServerStartupProgress,
+  type WorkspaceServerStats,
   type WorkspaceServerStatus,
+  type WorkspaceServerVersionInfo,
+  type WorkspaceWatcherStatus,
   type WriteFileInput,
+  type WriteWorkspaceConfigInput,
 } from "../src/lib/desktopApi";
 import {
+  annotateEventInputSchema,
+  appendVoiceAudioInputSchema,
+  appLogsResultSchema,
+  appSettingsSchema,
+  archiveThreadInputSchema,
+  backupSyncConfigSchema,
   captureProductEventInputSchema,
+  cleanupWorkspaceStorageInputSchema,
+  configureBackupSyncInputSchema,
   confirmActionInputSchema,
+  connectivityStatusSchema,
   copyFileToWorkspaceUploadsInputSchema,
   copyPathInputSchema,
   copyTextInputSchema,
+  copyTranscriptSelectionInputSchema,
+  copyTranscriptSelectionResultSchema,
   createDirectoryInputSchema,
   createOneOffChatWorkspaceInputSchema,
+  createProfileInputSchema,
+  createScheduledTaskInputSchema,
+  createScratchThreadInputSchema,
+  deletePromptTemplateInputSchema,
+  deleteScheduledTaskInputSchema,
   deleteTranscriptInputSchema,
+  deleteWorkspaceInputSchema,
+  desktopBackendCapabilitiesSchema,
+  desktopDeepLinkNavigationSchema,
   desktopMenuCommandSchema,
   desktopNotificationInputSchema,
+  desktopProfileListSchema,
+  desktopProfileSchema,
   diagnosticsBundlePathInputSchema,
+  diskSpaceStatusSchema,
+  droppedWorkspaceFolderSchema,
+  duplicateWorkspaceInputSchema,
+  enqueueExportInputSchema,
+  exportAppDataInputSchema,
+  forkThreadInputSchema,
+  generateHandoffLinkInputSchema,
+  getAttachmentInputSchema,
+  getThumbnailInputSchema,
+  getThreadFileChangesInputSchema,
+  getThreadPreviewsInputSchema,
+  getUsageSummaryInputSchema,
+  getWorkspaceStorageReportInputSchema,
+  globalSearchInputSchema,
+  importAppDataInputSchema,
+  listAnnotationsInputSchema,
+  listAvailableModelsInputSchema,
   listDirectoryInputSchema,
+  listPendingApprovalsInputSchema,
+  listPromptTemplatesInputSchema,
+  loadThreadDraftInputSchema,
+  mergeThreadsInputSchema,
+  migrateTranscriptStoreInputSchema,
   mobileRelayBridgeStateSchema,
   mobileRelayForgetTrustedPhoneInputSchema,
   mobileRelayStartInputSchema,
   mobileRelayUpdateTrustedPhonePermissionsInputSchema,
   openExternalUrlInputSchema,
   openPathInputSchema,
+  openTranscriptFileInputSchema,
+  operationIdInputSchema,
+  operationSnapshotSchema,
+  orphanedServerCleanupResultSchema,
+  orphanedServerListSchema,
   persistedStateInputSchema,
+  persistScratchThreadInputSchema,
   pickCanvasSavePathInputSchema,
   pickDirectoryInputSchema,
   platformChromeInfoSchema,
   preferredFileAppInputSchema,
   previewFileChangeEventSchema,
   previewOSFileInputSchema,
+  previewPathPolicyInputSchema,
+  previewTranscriptRetentionInputSchema,
+  prewarmWorkspaceServersInputSchema,
+  prewarmWorkspaceServersResultSchema,
+  providerCredentialValidationSchema,
+  proxyConnectivityResultSchema,
+  purgeThreadsInputSchema,
+  readAppLogsInputSchema,
   readFileForPreviewInputSchema,
   readFileInputSchema,
   readTranscriptInputSchema,
+  readTranscriptPageInputSchema,
+  readWorkspaceConfigInputSchema,
+  rebuildStateInputSchema,
+  recordPendingApprovalInputSchema,
+  redactTranscriptInputSchema,
+  registerRemoteWorkspaceInputSchema,
+  releaseThreadServerInputSchema,
   renamePathInputSchema,
   rendererLogInputSchema,
+  renderPromptInputSchema,
+  reorderWorkspacesInputSchema,
+  replaySessionSchema,
+  requestYoloGrantInputSchema,
+  resolveApprovalInputSchema,
+  resolveDroppedWorkspaceFolderInputSchema,
+  restoreFromTrashInputSchema,
+  restoreStateSnapshotInputSchema,
+  restoreWorkspaceCheckpointInputSchema,
+  restoreWorkspaceCheckpointResultSchema,
   revealPathInputSchema,
+  revokeHandoffInputSchema,
+  saveBackupCredentialsInputSchema,
+  saveBackupCredentialsResultSchema,
   saveExportedFileInputSchema,
+  savePromptTemplateInputSchema,
+  saveThreadDraftInputSchema,
+  scanForWorkspacesInputSchema,
+  scanForWorkspacesResultSchema,
+  scheduledTaskRunSchema,
+  serverReloadedEventSchema,
+  serverResumedEventSchema,
+  serverStatsEventSchema,
+  serverSuspendedEventSchema,
+  setLocaleInputSchema,
+  setLogLevelInputSchema,
+  setScheduledTaskEnabledInputSchema,
+  setTranscriptEncryptionInputSchema,
   setWindowAppearanceInputSchema,
+  setWorkspaceIdlePolicyInputSchema,
+  setWorkspaceLaunchProfilesInputSchema,
+  setWorkspacePathPolicyInputSchema,
+  setWorkspacePinnedInputSchema,
+  setWorkspaceTagsInputSchema,
+  shareTranscriptInputSchema,
   showCanvasWindowInputSchema,
   showContextMenuInputSchema,
   showQuickChatWindowInputSchema,
+  sidecarUpdateStateSchema,
+  startOperationInputSchema,
+  startReplayInputSchema,
+  startVoiceCaptureInputSchema,
   startWorkspaceServerInputSchema,
+  stopReplayInputSchema,
+  stopVoiceCaptureInputSchema,
   stopWorkspaceServerInputSchema,
+  storeAttachmentInputSchema,
+  suggestThreadTitleInputSchema,
+  switchProfileInputSchema,
   systemAppearanceSchema,
   telemetryStatusInputSchema,
   telemetryStatusSnapshotSchema,
+  testProxyConnectivityInputSchema,
+  threadFileChangesSchema,
+  transcriptAppendedEventSchema,
   transcriptBatchInputSchema,
+  transcriptInspectionSchema,
+  transcriptRedactionResultSchema,
+  transcriptRepairResultSchema,
+  transcriptRetentionPreviewSchema,
+  transcriptRetentionSummarySchema,
+  transcriptThrottledEventSchema,
+  transcriptTimestampNormalizationResultSchema,
   trashPathInputSchema,
+  updateAppSettingsInputSchema,
   updaterStateSchema,
   uploadDiagnosticsBundleInputSchema,
+  usageSummarySchema,
+  validateProviderCredentialsInputSchema,
+  voiceTranscriptEventSchema,
   watchWorkspaceDirectoryInputSchema,
   windowCloseRequestSchema,
   windowCloseResponseInputSchema,
   windowDragPointInputSchema,
+  workspaceCheckpointListSchema,
+  workspaceConfigSnapshotSchema,
   workspaceFileChangeEventSchema,
+  workspaceGitStatusSchema,
+  workspaceHealthReportSchema,
+  workspaceProxyConnectionEventSchema,
   workspaceServerExitedEventSchema,
+  workspaceServerPrewarmProgressSchema,
+  workspaceServerStartupDiagnosticsSchema,
   workspaceServerStartupProgressSchema,
   workspaceServerStatusSchema,
+  workspaceServerStatsSchema,
+  workspaceServerVersionInfoSchema,
+  workspaceWatcherStatusSchema,
   writeFileInputSchema,
+  writeWorkspaceConfigInputSchema,
 } from "../src/lib/desktopSchemas";
 import type { PublicTelemetryEnv } from "./services/publicTelemetryEnv";
 import { resolveDesktopTelemetryStatus } from "./services/telemetryStatus";
@@ -155,6 +414,20 @@ function assertStartWorkspaceServerInput(opts: StartWorkspaceServerInput): void
   parseWithSchema(startWorkspaceServerInputSchema, opts, "startWorkspaceServer options");
 }
 
+function assertRequestYoloGrantInput(opts: RequestYoloGrantInput): void {
+  parseWithSchema(requestYoloGrantInputSchema, opts, "requestYoloGrant options");
+}
+
+function assertPrewarmWorkspaceServersInput(opts: PrewarmWorkspaceServersInput): void {
+  parseWithSchema(prewarmWorkspaceServersInputSchema, opts, "prewarmWorkspaceServers options");
+}
+
+function assertPrewarmWorkspaceServersResult(
+  value: unknown,
+): asserts value is PrewarmWorkspaceServersResult {
+  parseWithSchema(prewarmWorkspaceServersResultSchema, value, "workspace server prewarm result");
+}
+
 function assertCreateOneOffChatWorkspaceInput(opts: CreateOneOffChatWorkspaceInput): void {
   parseWithSchema(createOneOffChatWorkspaceInputSchema, opts, "createOneOffChatWorkspace options");
 }
@@ -163,18 +436,270 @@ function assertStopWorkspaceServerInput(opts: StopWorkspaceServerInput): void {
   parseWithSchema(stopWorkspaceServerInputSchema, opts, "stopWorkspaceServer options");
 }
 
+function assertReleaseThreadServerInput(opts: ReleaseThreadServerInput): void {
+  parseWithSchema(releaseThreadServerInputSchema, opts, "releaseThreadServer options");
+}
+
+function assertGenerateHandoffLinkInput(opts: GenerateHandoffLinkInput): void {
+  parseWithSchema(generateHandoffLinkInputSchema, opts, "generateHandoffLink options");
+}
+
+function assertRevokeHandoffInput(opts: RevokeHandoffInput): void {
+  parseWithSchema(revokeHandoffInputSchema, opts, "revokeHandoff options");
+}
+
 function assertWorkspaceServerStatus(value: unknown): asserts value is WorkspaceServerStatus {
   parseWithSchema(workspaceServerStatusSchema, value, "workspace server status");
 }
 
+function assertWorkspaceServerVersionInfo(
+  value: unknown,
+): asserts value is WorkspaceServerVersionInfo {
+  parseWithSchema(workspaceServerVersionInfoSchema, value, "workspace server version");
+}
+
+function assertWorkspaceServerStartupDiagnostics(
+  value: unknown,
+): asserts value is WorkspaceServerStartupDiagnostics | null {
+  if (value !== null) {
+    parseWithSchema(workspaceServerStartupDiagnosticsSchema, value, "server startup diagnostics");
+  }
+}
+
+function assertOpenTranscriptFileInput(opts: OpenTranscriptFileInput): void {
+  parseWithSchema(openTranscriptFileInputSchema, opts, "openTranscriptFile options");
+}
+
+function assertShareTranscriptInput(opts: ShareTranscriptInput): void {
+  parseWithSchema(shareTranscriptInputSchema, opts, "shareTranscript options");
+}
+
 function assertReadTranscriptInput(opts: ReadTranscriptInput): void {
   parseWithSchema(readTranscriptInputSchema, opts, "readTranscript options");
 }
 
+function assertReadTranscriptPageInput(opts: ReadTranscriptPageInput): void {
+  parseWithSchema(readTranscriptPageInputSchema, opts, "readTranscriptPage options");
+}
+
+function assertGetThreadPreviewsInput(opts: GetThreadPreviewsInput): void {
+  parseWithSchema(getThreadPreviewsInputSchema, opts, "getThreadPreviews options");
+}
+
+function assertArchiveThreadInput(opts: ArchiveThreadInput): void {
+  parseWithSchema(archiveThreadInputSchema, opts, "archiveThread options");
+}
+
+function assertForkThreadInput(opts: ForkThreadInput): void {
+  parseWithSchema(forkThreadInputSchema, opts, "forkThread options");
+}
+
+function assertMergeThreadsInput(opts: MergeThreadsInput): void {
+  parseWithSchema(mergeThreadsInputSchema, opts, "mergeThreads options");
+}
+
+function assertSuggestThreadTitleInput(opts: SuggestThreadTitleInput): void {
+  parseWithSchema(suggestThreadTitleInputSchema, opts, "suggestThreadTitle options");
+}
+
+function assertTranscriptInspection(value: unknown): asserts value is TranscriptInspection {
+  parseWithSchema(transcriptInspectionSchema, value, "transcript inspection");
+}
+
+function assertTranscriptRepairResult(value: unknown): asserts value is TranscriptRepairResult {
+  parseWithSchema(transcriptRepairResultSchema, value, "transcript repair result");
+}
+
+function assertTranscriptTimestampNormalizationResult(
+  value: unknown,
+): asserts value is TranscriptTimestampNormalizationResult {
+  parseWithSchema(
+    transcriptTimestampNormalizationResultSchema,
+    value,
+    "transcript timestamp normalization result",
+  );
+}
+
+function assertRedactTranscriptInput(opts: RedactTranscriptInput): void {
+  parseWithSchema(redactTranscriptInputSchema, opts, "redactTranscript options");
+}
+
+function assertTranscriptRedactionResult(
+  value: unknown,
+): asserts value is TranscriptRedactionResult {
+  parseWithSchema(transcriptRedactionResultSchema, value, "transcript redaction result");
+}
+
+function assertCopyTranscriptSelectionInput(opts: CopyTranscriptSelectionInput): void {
+  parseWithSchema(copyTranscriptSelectionInputSchema, opts, "copyTranscriptSelection options");
+}
+
+function assertCopyTranscriptSelectionResult(
+  value: unknown,
+): asserts value is CopyTranscriptSelectionResult {
+  parseWithSchema(copyTranscriptSelectionResultSchema, value, "copyTranscriptSelection result");
+}
+
+function assertGetThreadFileChangesInput(opts: GetThreadFileChangesInput): void {
+  parseWithSchema(getThreadFileChangesInputSchema, opts, "getThreadFileChanges options");
+}
+
+function assertThreadFileChanges(value: unknown): asserts value is ThreadFileChanges {
+  parseWithSchema(threadFileChangesSchema, value, "thread file changes");
+}
+
+function assertStartReplayInput(opts: StartReplayInput): void {
+  parseWithSchema(startReplayInputSchema, opts, "startReplay options");
+}
+
+function assertReplaySession(value: unknown): asserts value is ReplaySession {
+  parseWithSchema(replaySessionSchema, value, "replay session");
+}
+
+function assertStopReplayInput(opts: StopReplayInput): void {
+  parseWithSchema(stopReplayInputSchema, opts, "stopReplay options");
+}
+
+function assertSetTranscriptEncryptionInput(opts: SetTranscriptEncryptionInput): void {
+  parseWithSchema(setTranscriptEncryptionInputSchema, opts, "setTranscriptEncryption options");
+}
+
+function assertMigrateTranscriptStoreInput(opts: MigrateTranscriptStoreInput): void {
+  parseWithSchema(migrateTranscriptStoreInputSchema, opts, "migrateTranscriptStore options");
+}
+
 function assertDeleteTranscriptInput(opts: DeleteTranscriptInput): void {
   parseWithSchema(deleteTranscriptInputSchema, opts, "deleteTranscript options");
 }
 
+function assertPurgeThreadsInput(opts: PurgeThreadsInput): void {
+  parseWithSchema(purgeThreadsInputSchema, opts, "purgeThreads options");
+}
+
+function assertDeleteWorkspaceInput(opts: DeleteWorkspaceInput): void {
+  parseWithSchema(deleteWorkspaceInputSchema, opts, "deleteWorkspace options");
+}
+
+function assertGetWorkspaceStorageReportInput(opts: GetWorkspaceStorageReportInput): void {
+  parseWithSchema(getWorkspaceStorageReportInputSchema, opts, "getWorkspaceStorageReport options");
+}
+
+function assertCleanupWorkspaceStorageInput(opts: CleanupWorkspaceStorageInput): void {
+  parseWithSchema(cleanupWorkspaceStorageInputSchema, opts, "cleanupWorkspaceStorage options");
+}
+
+function assertSetWorkspacePinnedInput(opts: SetWorkspacePinnedInput): void {
+  parseWithSchema(setWorkspacePinnedInputSchema, opts, "setWorkspacePinned options");
+}
+
+function assertSetWorkspaceTagsInput(opts: SetWorkspaceTagsInput): void {
+  parseWithSchema(setWorkspaceTagsInputSchema, opts, "setWorkspaceTags options");
+}
+
+function assertSetWorkspaceLaunchProfilesInput(opts: SetWorkspaceLaunchProfilesInput): void {
+  parseWithSchema(
+    setWorkspaceLaunchProfilesInputSchema,
+    opts,
+    "setWorkspaceLaunchProfiles options",
+  );
+}
+
+function assertSetWorkspaceIdlePolicyInput(opts: SetWorkspaceIdlePolicyInput): void {
+  parseWithSchema(setWorkspaceIdlePolicyInputSchema, opts, "setWorkspaceIdlePolicy options");
+}
+
+function assertSetWorkspacePathPolicyInput(opts: SetWorkspacePathPolicyInput): void {
+  parseWithSchema(setWorkspacePathPolicyInputSchema, opts, "setWorkspacePathPolicy options");
+}
+
+function assertPreviewPathPolicyInput(opts: PreviewPathPolicyInput): void {
+  parseWithSchema(previewPathPolicyInputSchema, opts, "previewPathPolicy options");
+}
+
+function assertDuplicateWorkspaceInput(opts: DuplicateWorkspaceInput): void {
+  parseWithSchema(duplicateWorkspaceInputSchema, opts, "duplicateWorkspace options");
+}
+
+function assertReorderWorkspacesInput(opts: ReorderWorkspacesInput): void {
+  parseWithSchema(reorderWorkspacesInputSchema, opts, "reorderWorkspaces options");
+}
+
+function assertGlobalSearchInput(opts: GlobalSearchInput): void {
+  parseWithSchema(globalSearchInputSchema, opts, "globalSearch options");
+}
+
+function assertAnnotateEventInput(opts: AnnotateEventInput): void {
+  parseWithSchema(annotateEventInputSchema, opts, "annotateEvent options");
+}
+
+function assertListAnnotationsInput(opts: ListAnnotationsInput): void {
+  parseWithSchema(listAnnotationsInputSchema, opts, "listAnnotations options");
+}
+
+function assertSaveThreadDraftInput(opts: SaveThreadDraftInput): void {
+  parseWithSchema(saveThreadDraftInputSchema, opts, "saveThreadDraft options");
+}
+
+function assertLoadThreadDraftInput(opts: LoadThreadDraftInput): void {
+  parseWithSchema(loadThreadDraftInputSchema, opts, "loadThreadDraft options");
+}
+
+function assertListPromptTemplatesInput(opts: ListPromptTemplatesInput): void {
+  parseWithSchema(listPromptTemplatesInputSchema, opts, "listPromptTemplates options");
+}
+
+function assertSavePromptTemplateInput(opts: SavePromptTemplateInput): void {
+  parseWithSchema(savePromptTemplateInputSchema, opts, "savePromptTemplate options");
+}
+
+function assertDeletePromptTemplateInput(opts: DeletePromptTemplateInput): void {
+  parseWithSchema(deletePromptTemplateInputSchema, opts, "deletePromptTemplate options");
+}
+
+function assertRenderPromptInput(opts: RenderPromptInput): void {
+  parseWithSchema(renderPromptInputSchema, opts, "renderPrompt options");
+}
+
+function assertCreateScheduledTaskInput(opts: CreateScheduledTaskInput): void {
+  parseWithSchema(createScheduledTaskInputSchema, opts, "createScheduledTask options");
+}
+
+function assertSetScheduledTaskEnabledInput(opts: SetScheduledTaskEnabledInput): void {
+  parseWithSchema(setScheduledTaskEnabledInputSchema, opts, "setScheduledTaskEnabled options");
+}
+
+function assertRegisterRemoteWorkspaceInput(opts: RegisterRemoteWorkspaceInput): void {
+  parseWithSchema(registerRemoteWorkspaceInputSchema, opts, "registerRemoteWorkspace options");
+}
+
+function assertDeleteScheduledTaskInput(opts: DeleteScheduledTaskInput): void {
+  parseWithSchema(deleteScheduledTaskInputSchema, opts, "deleteScheduledTask options");
+}
+
+function assertRecordPendingApprovalInput(opts: RecordPendingApprovalInput): void {
+  parseWithSchema(recordPendingApprovalInputSchema, opts, "recordPendingApproval options");
+}
+
+function assertListPendingApprovalsInput(opts: ListPendingApprovalsInput): void {
+  parseWithSchema(listPendingApprovalsInputSchema, opts, "listPendingApprovals options");
+}
+
+function assertResolveApprovalInput(opts: ResolveApprovalInput): void {
+  parseWithSchema(resolveApprovalInputSchema, opts, "resolveApproval options");
+}
+
+function assertStartVoiceCaptureInput(opts: StartVoiceCaptureInput): void {
+  parseWithSchema(startVoiceCaptureInputSchema, opts, "startVoiceCapture options");
+}
+
+function assertAppendVoiceAudioInput(opts: AppendVoiceAudioInput): void {
+  parseWithSchema(appendVoiceAudioInputSchema, opts, "appendVoiceAudio options");
+}
+
+function assertStopVoiceCaptureInput(opts: StopVoiceCaptureInput): void {
+  parseWithSchema(stopVoiceCaptureInputSchema, opts, "stopVoiceCapture options");
+}
+
 function assertTranscriptBatchInput(opts: TranscriptBatchInput): void {
   parseWithSchema(transcriptBatchInputSchema, opts, "transcript event");
 }
@@ -263,6 +788,18 @@ function assertCopyFileToWorkspaceUploadsInput(opts: CopyFileToWorkspaceUploadsI
   );
 }
 
+function assertStoreAttachmentInput(opts: StoreAttachmentInput): void {
+  parseWithSchema(storeAttachmentInputSchema, opts, "storeAttachment options");
+}
+
+function assertGetAttachmentInput(opts: GetAttachmentInput): void {
+  parseWithSchema(getAttachmentInputSchema, opts, "getAttachment options");
+}
+
+function assertGetThumbnailInput(opts: GetThumbnailInput): void {
+  parseWithSchema(getThumbnailInputSchema, opts, "getThumbnail options");
+}
+
 function assertCreateDirectoryInput(opts: CreateDirectoryInput): void {
   parseWithSchema(createDirectoryInputSchema, opts, "createDirectory options");
 }
@@ -279,6 +816,34 @@ function assertPersistedState(state: PersistedState): void {
   parseWithSchema(persistedStateInputSchema, state, "state");
 }
 
+function assertRestoreFromTrashInput(opts: RestoreFromTrashInput): void {
+  parseWithSchema(restoreFromTrashInputSchema, opts, "restoreFromTrash options");
+}
+
+function assertRestoreStateSnapshotInput(opts: RestoreStateSnapshotInput): void {
+  parseWithSchema(restoreStateSnapshotInputSchema, opts, "restoreStateSnapshot options");
+}
+
+function assertRebuildStateInput(opts: RebuildStateInput): void {
+  parseWithSchema(rebuildStateInputSchema, opts, "rebuildStateFromTranscripts options");
+}
+
+function assertExportAppDataInput(opts: ExportAppDataInput): void {
+  parseWithSchema(exportAppDataInputSchema, opts, "exportAppData options");
+}
+
+function assertImportAppDataInput(opts: ImportAppDataInput): void {
+  parseWithSchema(importAppDataInputSchema, opts, "importAppData options");
+}
+
+function assertCreateScratchThreadInput(opts: CreateScratchThreadInput): void {
+  parseWithSchema(createScratchThreadInputSchema, opts, "createScratchThread options");
+}
+
+function assertPersistScratchThreadInput(opts: PersistScratchThreadInput): void {
+  parseWithSchema(persistScratchThreadInputSchema, opts, "persistScratchThread options");
+}
+
 function assertCaptureProductEventInput(input: CaptureProductEventInput): void {
   parseWithSchema(captureProductEventInputSchema, input, "product analytics event");
 }
@@ -303,6 +868,226 @@ function assertUploadDiagnosticsBundleInput(opts: UploadDiagnosticsBundleInput):
   parseWithSchema(uploadDiagnosticsBundleInputSchema, opts, "uploadDiagnosticsBundle options");
 }
 
+function assertWorkspaceWatcherStatus(value: unknown): asserts value is WorkspaceWatcherStatus {
+  parseWithSchema(workspaceWatcherStatusSchema, value, "workspace watcher status");
+}
+
+function assertWorkspaceServerStats(value: unknown): asserts value is WorkspaceServerStats {
+  parseWithSchema(workspaceServerStatsSchema, value, "workspace server stats");
+}
+
+function assertWorkspaceHealthReport(value: unknown): asserts value is WorkspaceHealthReport {
+  parseWithSchema(workspaceHealthReportSchema, value, "workspace health report");
+}
+
+function assertOrphanedServers(value: unknown): asserts value is OrphanedServer[] {
+  parseWithSchema(orphanedServerListSchema, value, "orphaned servers");
+}
+
+function assertOrphanedServerCleanupResult(
+  value: unknown,
+): asserts value is OrphanedServerCleanupResult {
+  parseWithSchema(orphanedServerCleanupResultSchema, value, "orphaned server cleanup result");
+}
+
+function assertReadWorkspaceConfigInput(opts: ReadWorkspaceConfigInput): void {
+  parseWithSchema(readWorkspaceConfigInputSchema, opts, "readWorkspaceConfig options");
+}
+
+function assertListAvailableModelsInput(opts: ListAvailableModelsInput): void {
+  parseWithSchema(listAvailableModelsInputSchema, opts, "listAvailableModels options");
+}
+
+function assertWriteWorkspaceConfigInput(opts: WriteWorkspaceConfigInput): void {
+  parseWithSchema(writeWorkspaceConfigInputSchema, opts, "writeWorkspaceConfig options");
+}
+
+function assertWorkspaceConfigSnapshot(value: unknown): asserts value is WorkspaceConfigSnapshot {
+  parseWithSchema(workspaceConfigSnapshotSchema, value, "workspace config");
+}
+
+function assertWorkspaceGitStatus(value: unknown): asserts value is WorkspaceGitStatus {
+  parseWithSchema(workspaceGitStatusSchema, value, "workspace git status");
+}
+
+function assertWorkspaceCheckpointList(value: unknown): asserts value is WorkspaceCheckpointList {
+  parseWithSchema(workspaceCheckpointListSchema, value, "workspace checkpoints");
+}
+
+function assertRestoreWorkspaceCheckpointInput(opts: RestoreWorkspaceCheckpointInput): void {
+  parseWithSchema(
+    restoreWorkspaceCheckpointInputSchema,
+    opts,
+    "restoreWorkspaceCheckpoint options",
+  );
+}
+
+function assertRestoreWorkspaceCheckpointResult(
+  value: unknown,
+): asserts value is RestoreWorkspaceCheckpointResult {
+  parseWithSchema(restoreWorkspaceCheckpointResultSchema, value, "checkpoint restore result");
+}
+
+function assertValidateProviderCredentialsInput(opts: ValidateProviderCredentialsInput): void {
+  parseWithSchema(
+    validateProviderCredentialsInputSchema,
+    opts,
+    "validateProviderCredentials options",
+  );
+}
+
+function assertProviderCredentialValidation(
+  value: unknown,
+): asserts value is ProviderCredentialValidation {
+  parseWithSchema(providerCredentialValidationSchema, value, "provider credential validation");
+}
+
+function assertGetUsageSummaryInput(opts: GetUsageSummaryInput): void {
+  parseWithSchema(getUsageSummaryInputSchema, opts, "getUsageSummary options");
+}
+
+function assertUsageSummary(value: unknown): asserts value is UsageSummary {
+  parseWithSchema(usageSummarySchema, value, "usage summary");
+}
+
+function assertUpdateAppSettingsInput(patch: UpdateAppSettingsInput): void {
+  parseWithSchema(updateAppSettingsInputSchema, patch, "updateAppSettings patch");
+}
+
+function assertCreateProfileInput(opts: CreateProfileInput): void {
+  parseWithSchema(createProfileInputSchema, opts, "createProfile options");
+}
+
+function assertSwitchProfileInput(opts: SwitchProfileInput): void {
+  parseWithSchema(switchProfileInputSchema, opts, "switchProfile options");
+}
+
+function assertDesktopProfile(value: unknown): asserts value is DesktopProfile {
+  parseWithSchema(desktopProfileSchema, value, "profile");
+}
+
+function assertDesktopProfileList(value: unknown): asserts value is DesktopProfileList {
+  parseWithSchema(desktopProfileListSchema, value, "profile list");
+}
+
+function assertAppSettings(value: unknown): asserts value is AppSettings {
+  parseWithSchema(appSettingsSchema, value, "app settings");
+}
+
+function assertPreviewTranscriptRetentionInput(opts: PreviewTranscriptRetentionInput): void {
+  parseWithSchema(
+    previewTranscriptRetentionInputSchema,
+    opts,
+    "previewTranscriptRetention options",
+  );
+}
+
+function assertTranscriptRetentionPreview(
+  value: unknown,
+): asserts value is TranscriptRetentionPreview {
+  parseWithSchema(transcriptRetentionPreviewSchema, value, "transcript retention preview");
+}
+
+function assertDroppedWorkspaceFolder(value: unknown): asserts value is DroppedWorkspaceFolder {
+  parseWithSchema(droppedWorkspaceFolderSchema, value, "dropped workspace folder");
+}
+
+function assertScanForWorkspacesResult(value: unknown): asserts value is ScanForWorkspacesResult {
+  parseWithSchema(scanForWorkspacesResultSchema, value, "workspace scan result");
+}
+
+function assertAppLogsResult(value: unknown): asserts value is AppLogsResult {
+  parseWithSchema(appLogsResultSchema, value, "app logs");
+}
+
+function assertTestProxyConnectivityInput(opts: TestProxyConnectivityInput): void {
+  parseWithSchema(testProxyConnectivityInputSchema, opts, "testProxyConnectivity options");
+}
+
+function assertProxyConnectivityResult(value: unknown): asserts value is ProxyConnectivityResult {
+  parseWithSchema(proxyConnectivityResultSchema, value, "proxy connectivity result");
+}
+
+function assertTranscriptRetentionSummary(
+  value: unknown,
+): asserts value is TranscriptRetentionSummary {
+  parseWithSchema(transcriptRetentionSummarySchema, value, "transcript retention summary");
+}
+
+function assertDiskSpaceStatus(value: unknown): asserts value is DiskSpaceStatus {
+  parseWithSchema(diskSpaceStatusSchema, value, "disk space status");
+}
+
+function assertTranscriptThrottledEvent(value: unknown): asserts value is TranscriptThrottledEvent {
+  parseWithSchema(transcriptThrottledEventSchema, value, "transcript throttled event");
+}
+
+function assertVoiceTranscriptEvent(value: unknown): asserts value is VoiceTranscriptEvent {
+  parseWithSchema(voiceTranscriptEventSchema, value, "voice transcript event");
+}
+
+function assertConnectivityStatus(value: unknown): asserts value is ConnectivityStatus {
+  parseWithSchema(connectivityStatusSchema, value, "connectivity status");
+}
+
+function assertScheduledTaskRun(value: unknown): asserts value is ScheduledTaskRun {
+  parseWithSchema(scheduledTaskRunSchema, value, "scheduled task run");
+}
+
+function assertServerStatsEvent(value: unknown): asserts value is ServerStatsEvent {
+  parseWithSchema(serverStatsEventSchema, value, "server stats event");
+}
+
+function assertTranscriptAppendedEvent(value: unknown): asserts value is TranscriptAppendedEvent {
+  parseWithSchema(transcriptAppendedEventSchema, value, "transcript appended event");
+}
+
+function assertStartOperationInput(opts: StartOperationInput): void {
+  parseWithSchema(startOperationInputSchema, opts, "startOperation options");
+}
+
+function assertEnqueueExportInput(opts: EnqueueExportInput): void {
+  parseWithSchema(enqueueExportInputSchema, opts, "enqueueExport options");
+}
+
+function assertSaveBackupCredentialsInput(opts: SaveBackupCredentialsInput): void {
+  parseWithSchema(saveBackupCredentialsInputSchema, opts, "saveBackupCredentials options");
+}
+
+function assertSaveBackupCredentialsResult(
+  value: unknown,
+): asserts value is SaveBackupCredentialsResult {
+  parseWithSchema(saveBackupCredentialsResultSchema, value, "saveBackupCredentials result");
+}
+
+function assertConfigureBackupSyncInput(opts: ConfigureBackupSyncInput): void {
+  parseWithSchema(configureBackupSyncInputSchema, opts, "configureBackupSync options");
+}
+
+function assertBackupSyncConfig(value: unknown): asserts value is BackupSyncConfig {
+  parseWithSchema(backupSyncConfigSchema, value, "backup sync config");
+}
+
+function assertOperationIdInput(opts: OperationIdInput): void {
+  parseWithSchema(operationIdInputSchema, opts, "operation id options");
+}
+
+function assertOperationSnapshot(value: unknown): asserts value is OperationSnapshot {
+  parseWithSchema(operationSnapshotSchema, value, "operation snapshot");
+}
+
+function assertNullableOperationSnapshot(
+  value: unknown,
+): asserts value is OperationSnapshot | null {
+  if (value !== null) {
+    assertOperationSnapshot(value);
+  }
+}
+
+function assertSetLocaleInput(opts: SetLocaleInput): void {
+  parseWithSchema(setLocaleInputSchema, opts, "setLocale options");
+}
+
 function assertSetWindowAppearanceInput(opts: SetWindowAppearanceInput): void {
   parseWithSchema(setWindowAppearanceInputSchema, opts, "setWindowAppearance options");
 }
@@ -311,18 +1096,46 @@ function assertUpdaterState(value: unknown): asserts value is UpdaterState {
   parseWithSchema(updaterStateSchema, value, "update state");
 }
 
+function assertSidecarUpdateState(value: unknown): asserts value is SidecarUpdateState {
+  parseWithSchema(sidecarUpdateStateSchema, value, "server update state");
+}
+
 function assertWorkspaceServerStartupProgress(
   value: unknown,
 ): asserts value is WorkspaceServerStartupProgress {
   parseWithSchema(workspaceServerStartupProgressSchema, value, "workspace server startup progress");
 }
 
+function assertWorkspaceServerPrewarmProgress(
+  value: unknown,
+): asserts value is WorkspaceServerPrewarmProgress {
+  parseWithSchema(workspaceServerPrewarmProgressSchema, value, "workspace server prewarm progress");
+}
+
 function assertWorkspaceServerExitedEvent(
   value: unknown,
 ): asserts value is WorkspaceServerExitedEvent {
   parseWithSchema(workspaceServerExitedEventSchema, value, "workspace server exited event");
 }
 
+function assertServerSuspendedEvent(value: unknown): asserts value is ServerSuspendedEvent {
+  parseWithSchema(serverSuspendedEventSchema, value, "server suspended event");
+}
+
+function assertServerResumedEvent(value: unknown): asserts value is ServerResumedEvent {
+  parseWithSchema(serverResumedEventSchema, value, "server resumed event");
+}
+
+function assertServerReloadedEvent(value: unknown): asserts value is ServerReloadedEvent {
+  parseWithSchema(serverReloadedEventSchema, value, "server reloaded event");
+}
+
+function assertWorkspaceProxyConnectionEvent(
+  value: unknown,
+): asserts value is WorkspaceProxyConnectionEvent {
+  parseWithSchema(workspaceProxyConnectionEventSchema, value, "workspace proxy connection event");
+}
+
 function assertWorkspaceFileChangeEvent(value: unknown): asserts value is WorkspaceFileChangeEvent {
   parseWithSchema(workspaceFileChangeEventSchema, value, "workspace file change event");
 }
@@ -331,6 +1144,12 @@ function assertDesktopMenuCommand(value: unknown): asserts value is DesktopMenuC
   parseWithSchema(desktopMenuCommandSchema, value, "menu command");
 }
 
+function assertDesktopDeepLinkNavigation(
+  value: unknown,
+): asserts value is DesktopDeepLinkNavigation {
+  parseWithSchema(desktopDeepLinkNavigationSchema, value, "deep link navigation");
+}
+
 function assertMobileRelayStartInput(opts: MobileRelayStartInput): void {
   parseWithSchema(mobileRelayStartInputSchema, opts, "mobileRelay.start options");
 }
@@ -365,6 +1184,12 @@ function assertPlatformChromeInfo(value: unknown): asserts value is PlatformChro
   parseWithSchema(platformChromeInfoSchema, value, "platform chrome");
 }
 
+function assertDesktopBackendCapabilities(
+  value: unknown,
+): asserts value is DesktopBackendCapabilities {
+  parseWithSchema(desktopBackendCapabilitiesSchema, value, "backend capabilities");
+}
+
 function assertTelemetryStatusSnapshot(value: unknown): asserts value is TelemetryStatusSnapshot {
   parseWithSchema(telemetryStatusSnapshotSchema, value, "telemetry status");
 }
@@ -471,11 +1296,28 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.startWorkspaceServer, opts);
   },
 
+  requestYoloGrant: (opts: RequestYoloGrantInput) => {
+    assertRequestYoloGrantInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.requestYoloGrant, opts);
+  },
+
+  prewarmWorkspaceServers: async (opts: PrewarmWorkspaceServersInput) => {
+    assertPrewarmWorkspaceServersInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.prewarmWorkspaceServers, opts);
+    assertPrewarmWorkspaceServersResult(result);
+    return result;
+  },
+
   stopWorkspaceServer: (opts: StopWorkspaceServerInput) => {
     assertStopWorkspaceServerInput(opts);
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.stopWorkspaceServer, opts);
   },
 
+  releaseThreadServer: (opts: ReleaseThreadServerInput) => {
+    assertReleaseThreadServerInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.releaseThreadServer, opts);
+  },
+
   getWorkspaceServerStatus: async (opts: StopWorkspaceServerInput) => {
     assertStopWorkspaceServerInput(opts);
     const status = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getWorkspaceServerStatus, opts);
@@ -483,6 +1325,181 @@ const desktopApi = Object.freeze<DesktopApi>({
     return status;
   },
 
+  getWorkspaceProxyUrl: (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getWorkspaceProxyUrl, opts);
+  },
+
+  generateHandoffLink: (opts: GenerateHandoffLinkInput) => {
+    assertGenerateHandoffLinkInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.generateHandoffLink, opts);
+  },
+
+  revokeHandoff: (opts: RevokeHandoffInput) => {
+    assertRevokeHandoffInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.revokeHandoff, opts);
+  },
+
+  getServerVersion: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const info = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getServerVersion, opts);
+    assertWorkspaceServerVersionInfo(info);
+    return info;
+  },
+
+  getServerStartupDiagnostics: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const diagnostics = await ipcRenderer.invoke(
+      DESKTOP_IPC_CHANNELS.getServerStartupDiagnostics,
+      opts,
+    );
+    assertWorkspaceServerStartupDiagnostics(diagnostics);
+    return diagnostics;
+  },
+
+  getServerStats: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const stats = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getServerStats, opts);
+    assertWorkspaceServerStats(stats);
+    return stats;
+  },
+
+  getWorkspaceHealth: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const report = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getWorkspaceHealth, opts);
+    assertWorkspaceHealthReport(report);
+    return report;
+  },
+
+  listOrphanedServers: async () => {
+    const servers = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listOrphanedServers);
+    assertOrphanedServers(servers);
+    return servers;
+  },
+
+  cleanupOrphanedServers: async () => {
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.cleanupOrphanedServers);
+    assertOrphanedServerCleanupResult(result);
+    return result;
+  },
+
+  readWorkspaceConfig: async (opts: ReadWorkspaceConfigInput) => {
+    assertReadWorkspaceConfigInput(opts);
+    const snapshot = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.readWorkspaceConfig, opts);
+    assertWorkspaceConfigSnapshot(snapshot);
+    return snapshot;
+  },
+
+  writeWorkspaceConfig: async (opts: WriteWorkspaceConfigInput) => {
+    assertWriteWorkspaceConfigInput(opts);
+    const snapshot = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.writeWorkspaceConfig, opts);
+    assertWorkspaceConfigSnapshot(snapshot);
+    return snapshot;
+  },
+
+  listAvailableModels: (opts: ListAvailableModelsInput) => {
+    assertListAvailableModelsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listAvailableModels, opts);
+  },
+
+  getWorkspaceGitStatus: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const status = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getWorkspaceGitStatus, opts);
+    assertWorkspaceGitStatus(status);
+    return status;
+  },
+
+  listWorkspaceCheckpoints: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const list = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listWorkspaceCheckpoints, opts);
+    assertWorkspaceCheckpointList(list);
+    return list;
+  },
+
+  restoreWorkspaceCheckpoint: async (opts: RestoreWorkspaceCheckpointInput) => {
+    assertRestoreWorkspaceCheckpointInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.restoreWorkspaceCheckpoint, opts);
+    assertRestoreWorkspaceCheckpointResult(result);
+    return result;
+  },
+
+  validateProviderCredentials: async (opts: ValidateProviderCredentialsInput) => {
+    assertValidateProviderCredentialsInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.validateProviderCredentials, opts);
+    assertProviderCredentialValidation(result);
+    return result;
+  },
+
+  getUsageSummary: async (opts: GetUsageSummaryInput) => {
+    assertGetUsageSummaryInput(opts);
+    const summary = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getUsageSummary, opts);
+    assertUsageSummary(summary);
+    return summary;
+  },
+
+  getAppSettings: async () => {
+    const settings = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getAppSettings);
+    assertAppSettings(settings);
+    return settings;
+  },
+
+  updateAppSettings: async (patch: UpdateAppSettingsInput) => {
+    assertUpdateAppSettingsInput(patch);
+    const settings = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.updateAppSettings, patch);
+    assertAppSettings(settings);
+    return settings;
+  },
+
+  listProfiles: async () => {
+    const profiles = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listProfiles);
+    assertDesktopProfileList(profiles);
+    return profiles;
+  },
+
+  createProfile: async (opts: CreateProfileInput) => {
+    assertCreateProfileInput(opts);
+    const profile = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.createProfile, opts);
+    assertDesktopProfile(profile);
+    return profile;
+  },
+
+  switchProfile: async (opts: SwitchProfileInput) => {
+    assertSwitchProfileInput(opts);
+    await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.switchProfile, opts);
+  },
+
+  previewTranscriptRetention: async (opts: PreviewTranscriptRetentionInput = {}) => {
+    assertPreviewTranscriptRetentionInput(opts);
+    const preview = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.previewTranscriptRetention, opts);
+    assertTranscriptRetentionPreview(preview);
+    return preview;
+  },
+
+  testProxyConnectivity: async (opts: TestProxyConnectivityInput) => {
+    assertTestProxyConnectivityInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.testProxyConnectivity, opts);
+    assertProxyConnectivityResult(result);
+    return result;
+  },
+
+  flushBufferedTranscripts: async () => {
+    const status = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.flushBufferedTranscripts);
+    assertDiskSpaceStatus(status);
+    return status;
+  },
+
+  getConnectivityStatus: async () => {
+    const status = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getConnectivityStatus);
+    assertConnectivityStatus(status);
+    return status;
+  },
+
+  verifyInstallation: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.verifyInstallation),
+
+  getOnboardingStatus: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getOnboardingStatus),
+
+  runEnvironmentChecks: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.runEnvironmentChecks),
+
   startMobileRelay: async (opts: MobileRelayStartInput) => {
     assertMobileRelayStartInput(opts);
     const state = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.mobileRelayStart, opts);
@@ -543,16 +1560,164 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.saveState, state);
   },
 
+  listStateSnapshots: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listStateSnapshots),
+
+  restoreStateSnapshot: (opts: RestoreStateSnapshotInput) => {
+    assertRestoreStateSnapshotInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.restoreStateSnapshot, opts);
+  },
+
+  rebuildStateFromTranscripts: (opts: RebuildStateInput) => {
+    assertRebuildStateInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.rebuildStateFromTranscripts, opts);
+  },
+
+  exportAppData: (opts: ExportAppDataInput) => {
+    assertExportAppDataInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.exportAppData, opts);
+  },
+
+  importAppData: (opts: ImportAppDataInput) => {
+    assertImportAppDataInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.importAppData, opts);
+  },
+
   captureProductEvent: (input: CaptureProductEventInput) => {
     assertCaptureProductEventInput(input);
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.captureProductEvent, input);
   },
 
+  createScratchThread: (opts: CreateScratchThreadInput) => {
+    assertCreateScratchThreadInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.createScratchThread, opts);
+  },
+
+  persistScratchThread: (opts: PersistScratchThreadInput) => {
+    assertPersistScratchThreadInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.persistScratchThread, opts);
+  },
+
   readTranscript: (opts: ReadTranscriptInput) => {
     assertReadTranscriptInput(opts);
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.readTranscript, opts);
   },
 
+  readTranscriptPage: (opts: ReadTranscriptPageInput) => {
+    assertReadTranscriptPageInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.readTranscriptPage, opts);
+  },
+
+  getThreadPreviews: (opts: GetThreadPreviewsInput) => {
+    assertGetThreadPreviewsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getThreadPreviews, opts);
+  },
+
+  inspectTranscript: async (opts: ReadTranscriptInput) => {
+    assertReadTranscriptInput(opts);
+    const inspection = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.inspectTranscript, opts);
+    assertTranscriptInspection(inspection);
+    return inspection;
+  },
+
+  repairTranscript: async (opts: ReadTranscriptInput) => {
+    assertReadTranscriptInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.repairTranscript, opts);
+    assertTranscriptRepairResult(result);
+    return result;
+  },
+
+  normalizeTranscriptTimestamps: async (opts: ReadTranscriptInput) => {
+    assertReadTranscriptInput(opts);
+    const result = await ipcRenderer.invoke(
+      DESKTOP_IPC_CHANNELS.normalizeTranscriptTimestamps,
+      opts,
+    );
+    assertTranscriptTimestampNormalizationResult(result);
+    return result;
+  },
+
+  redactTranscript: async (opts: RedactTranscriptInput) => {
+    assertRedactTranscriptInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.redactTranscript, opts);
+    assertTranscriptRedactionResult(result);
+    return result;
+  },
+
+  archiveThread: (opts: ArchiveThreadInput) => {
+    assertArchiveThreadInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.archiveThread, opts);
+  },
+
+  unarchiveThread: (opts: ArchiveThreadInput) => {
+    assertArchiveThreadInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.unarchiveThread, opts);
+  },
+
+  forkThread: (opts: ForkThreadInput) => {
+    assertForkThreadInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.forkThread, opts);
+  },
+
+  mergeThreads: (opts: MergeThreadsInput) => {
+    assertMergeThreadsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.mergeThreads, opts);
+  },
+
+  suggestThreadTitle: (opts: SuggestThreadTitleInput) => {
+    assertSuggestThreadTitleInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.suggestThreadTitle, opts);
+  },
+
+  copyTranscriptSelection: async (opts: CopyTranscriptSelectionInput) => {
+    assertCopyTranscriptSelectionInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.copyTranscriptSelection, opts);
+    assertCopyTranscriptSelectionResult(result);
+    return result;
+  },
+
+  getThreadFileChanges: async (opts: GetThreadFileChangesInput) => {
+    assertGetThreadFileChangesInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getThreadFileChanges, opts);
+    assertThreadFileChanges(result);
+    return result;
+  },
+
+  startReplay: async (opts: StartReplayInput) => {
+    assertStartReplayInput(opts);
+    const session = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.startReplay, opts);
+    assertReplaySession(session);
+    return session;
+  },
+
+  stopReplay: (opts: StopReplayInput) => {
+    assertStopReplayInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.stopReplay, opts);
+  },
+
+  getTranscriptEncryption: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getTranscriptEncryption),
+
+  setTranscriptEncryption: (opts: SetTranscriptEncryptionInput) => {
+    assertSetTranscriptEncryptionInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setTranscriptEncryption, opts);
+  },
+
+  encryptExistingTranscripts: () =>
+    ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.encryptExistingTranscripts),
+
+  migrateTranscriptStore: (opts: MigrateTranscriptStoreInput) => {
+    assertMigrateTranscriptStoreInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.migrateTranscriptStore, opts);
+  },
+
+  openTranscriptFile: (opts: OpenTranscriptFileInput) => {
+    assertOpenTranscriptFileInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.openTranscriptFile, opts);
+  },
+  shareTranscript: (opts: ShareTranscriptInput) => {
+    assertShareTranscriptInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.shareTranscript, opts);
+  },
+
   hydrateTranscript: (opts: ReadTranscriptInput) => {
     assertReadTranscriptInput(opts);
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.hydrateTranscript, opts);
@@ -563,18 +1728,221 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.appendTranscriptEvent, opts);
   },
 
-  appendTranscriptBatch: (events: TranscriptBatchInput[]) => {
-    events.forEach(assertTranscriptBatchInput);
-    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.appendTranscriptBatch, events);
+  appendTranscriptBatch: (events: TranscriptBatchInput[]) => {
+    events.forEach(assertTranscriptBatchInput);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.appendTranscriptBatch, events);
+  },
+
+  subscribeTranscript: (opts: ReadTranscriptInput) => {
+    assertReadTranscriptInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.subscribeTranscript, opts);
+  },
+
+  unsubscribeTranscript: (opts: ReadTranscriptInput) => {
+    assertReadTranscriptInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.unsubscribeTranscript, opts);
+  },
+
+  deleteTranscript: (opts: DeleteTranscriptInput) => {
+    assertDeleteTranscriptInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.deleteTranscript, opts);
+  },
+
+  purgeThreads: (opts: PurgeThreadsInput) => {
+    assertPurgeThreadsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.purgeThreads, opts);
+  },
+
+  deleteWorkspace: (opts: DeleteWorkspaceInput) => {
+    assertDeleteWorkspaceInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.deleteWorkspace, opts);
+  },
+
+  getWorkspaceStorageReport: (opts: GetWorkspaceStorageReportInput) => {
+    assertGetWorkspaceStorageReportInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getWorkspaceStorageReport, opts);
+  },
+
+  cleanupWorkspaceStorage: (opts: CleanupWorkspaceStorageInput) => {
+    assertCleanupWorkspaceStorageInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.cleanupWorkspaceStorage, opts);
+  },
+
+  listTrash: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listTrash),
+
+  restoreFromTrash: (opts: RestoreFromTrashInput) => {
+    assertRestoreFromTrashInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.restoreFromTrash, opts);
+  },
+
+  setWorkspacePinned: (opts: SetWorkspacePinnedInput) => {
+    assertSetWorkspacePinnedInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setWorkspacePinned, opts);
+  },
+
+  setWorkspaceTags: (opts: SetWorkspaceTagsInput) => {
+    assertSetWorkspaceTagsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setWorkspaceTags, opts);
+  },
+
+  setWorkspaceLaunchProfiles: (opts: SetWorkspaceLaunchProfilesInput) => {
+    assertSetWorkspaceLaunchProfilesInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setWorkspaceLaunchProfiles, opts);
+  },
+
+  setWorkspaceIdlePolicy: (opts: SetWorkspaceIdlePolicyInput) => {
+    assertSetWorkspaceIdlePolicyInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setWorkspaceIdlePolicy, opts);
+  },
+
+  setWorkspacePathPolicy: (opts: SetWorkspacePathPolicyInput) => {
+    assertSetWorkspacePathPolicyInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setWorkspacePathPolicy, opts);
+  },
+
+  previewPathPolicy: (opts: PreviewPathPolicyInput) => {
+    assertPreviewPathPolicyInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.previewPathPolicy, opts);
+  },
+
+  duplicateWorkspace: (opts: DuplicateWorkspaceInput) => {
+    assertDuplicateWorkspaceInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.duplicateWorkspace, opts);
+  },
+
+  reorderWorkspaces: (opts: ReorderWorkspacesInput) => {
+    assertReorderWorkspacesInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.reorderWorkspaces, opts);
+  },
+
+  globalSearch: (opts: GlobalSearchInput) => {
+    assertGlobalSearchInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.globalSearch, opts);
+  },
+
+  annotateEvent: (opts: AnnotateEventInput) => {
+    assertAnnotateEventInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.annotateEvent, opts);
+  },
+
+  listAnnotations: (opts: ListAnnotationsInput) => {
+    assertListAnnotationsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listAnnotations, opts);
+  },
+
+  saveThreadDraft: (opts: SaveThreadDraftInput) => {
+    assertSaveThreadDraftInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.saveThreadDraft, opts);
+  },
+
+  loadThreadDraft: (opts: LoadThreadDraftInput) => {
+    assertLoadThreadDraftInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.loadThreadDraft, opts);
+  },
+
+  listPromptTemplates: (opts: ListPromptTemplatesInput) => {
+    assertListPromptTemplatesInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listPromptTemplates, opts);
+  },
+
+  savePromptTemplate: (opts: SavePromptTemplateInput) => {
+    assertSavePromptTemplateInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.savePromptTemplate, opts);
+  },
+
+  deletePromptTemplate: (opts: DeletePromptTemplateInput) => {
+    assertDeletePromptTemplateInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.deletePromptTemplate, opts);
+  },
+
+  renderPrompt: (opts: RenderPromptInput) => {
+    assertRenderPromptInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.renderPrompt, opts);
+  },
+
+  listScheduledTasks: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listScheduledTasks),
+
+  createScheduledTask: (opts: CreateScheduledTaskInput) => {
+    assertCreateScheduledTaskInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.createScheduledTask, opts);
+  },
+
+  setScheduledTaskEnabled: (opts: SetScheduledTaskEnabledInput) => {
+    assertSetScheduledTaskEnabledInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setScheduledTaskEnabled, opts);
+  },
+
+  deleteScheduledTask: (opts: DeleteScheduledTaskInput) => {
+    assertDeleteScheduledTaskInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.deleteScheduledTask, opts);
+  },
+
+  recordPendingApproval: (opts: RecordPendingApprovalInput) => {
+    assertRecordPendingApprovalInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.recordPendingApproval, opts);
+  },
+
+  listPendingApprovals: (opts: ListPendingApprovalsInput) => {
+    assertListPendingApprovalsInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listPendingApprovals, opts);
+  },
+
+  resolveApproval: (opts: ResolveApprovalInput) => {
+    assertResolveApprovalInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.resolveApproval, opts);
+  },
+
+  startVoiceCapture: (opts: StartVoiceCaptureInput) => {
+    assertStartVoiceCaptureInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.startVoiceCapture, opts);
+  },
+
+  appendVoiceAudio: (opts: AppendVoiceAudioInput) => {
+    assertAppendVoiceAudioInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.appendVoiceAudio, opts);
+  },
+
+  stopVoiceCapture: (opts: StopVoiceCaptureInput) => {
+    assertStopVoiceCaptureInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.stopVoiceCapture, opts);
+  },
+
+  pickWorkspaceDirectory: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.pickWorkspaceDirectory),
+
+  resolveDroppedWorkspaceFolder: async (file: unknown) => {
+    // Like getPathForFile, only genuine OS drops resolve to a path, so the
+    // renderer cannot ask main to approve an arbitrary folder through this.
+    let droppedPath: string | null;
+    try {
+      droppedPath = webUtils.getPathForFile(file as File) || null;
+    } catch {
+      return null;
+    }
+    if (!droppedPath) {
+      return null;
+    }
+    const input = { path: droppedPath };
+    parseWithSchema(resolveDroppedWorkspaceFolderInputSchema, input, "dropped workspace folder");
+    const folder = await ipcRenderer.invoke(
+      DESKTOP_IPC_CHANNELS.resolveDroppedWorkspaceFolder,
+      input,
+    );
+    assertDroppedWorkspaceFolder(folder);
+    return folder;
+  },
+
+  registerRemoteWorkspace: (opts: RegisterRemoteWorkspaceInput) => {
+    assertRegisterRemoteWorkspaceInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.registerRemoteWorkspace, opts);
   },
 
-  deleteTranscript: (opts: DeleteTranscriptInput) => {
-    assertDeleteTranscriptInput(opts);
-    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.deleteTranscript, opts);
+  scanForWorkspaces: async (opts: ScanForWorkspacesInput) => {
+    parseWithSchema(scanForWorkspacesInputSchema, opts, "scanForWorkspaces options");
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.scanForWorkspaces, opts);
+    assertScanForWorkspacesResult(result);
+    return result;
   },
 
-  pickWorkspaceDirectory: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.pickWorkspaceDirectory),
-
   pickDirectory: (opts?: PickDirectoryInput) => {
     if (opts !== undefined) {
       assertPickDirectoryInput(opts);
@@ -645,6 +2013,13 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.unwatchWorkspaceDirectory, opts);
   },
 
+  getWatcherStatus: async (opts: StopWorkspaceServerInput) => {
+    assertStopWorkspaceServerInput(opts);
+    const status = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getWatcherStatus, opts);
+    assertWorkspaceWatcherStatus(status);
+    return status;
+  },
+
   readFile: (opts: ReadFileInput) => {
     assertReadFileInput(opts);
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.readFile, opts);
@@ -733,6 +2108,21 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.copyFileToWorkspaceUploads, opts);
   },
 
+  storeAttachment: (opts: StoreAttachmentInput) => {
+    assertStoreAttachmentInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.storeAttachment, opts);
+  },
+
+  getAttachment: (opts: GetAttachmentInput) => {
+    assertGetAttachmentInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getAttachment, opts);
+  },
+
+  getThumbnail: (opts: GetThumbnailInput) => {
+    assertGetThumbnailInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getThumbnail, opts);
+  },
+
   createDirectory: (opts: CreateDirectoryInput) => {
     assertCreateDirectoryInput(opts);
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.createDirectory, opts);
@@ -762,6 +2152,18 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.writeRendererLog, opts);
   },
 
+  setLogLevel: async (opts: SetLogLevelInput) => {
+    parseWithSchema(setLogLevelInputSchema, opts, "setLogLevel options");
+    await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setLogLevel, opts);
+  },
+
+  readAppLogs: async (opts: ReadAppLogsInput = {}) => {
+    parseWithSchema(readAppLogsInputSchema, opts, "readAppLogs options");
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.readAppLogs, opts);
+    assertAppLogsResult(result);
+    return result;
+  },
+
   createDiagnosticsBundle: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.createDiagnosticsBundle),
 
   revealDiagnosticsBundle: (opts: DiagnosticsBundlePathInput) => {
@@ -776,6 +2178,69 @@ const desktopApi = Object.freeze<DesktopApi>({
     return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.uploadDiagnosticsBundle, opts);
   },
 
+  startOperation: async (opts: StartOperationInput) => {
+    assertStartOperationInput(opts);
+    const snapshot = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.startOperation, opts);
+    assertOperationSnapshot(snapshot);
+    return snapshot;
+  },
+
+  listOperations: async () => {
+    const snapshots = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.listOperations);
+    if (!Array.isArray(snapshots)) {
+      throw new Error("listOperations returned an invalid result");
+    }
+    for (const snapshot of snapshots) {
+      assertOperationSnapshot(snapshot);
+    }
+    return snapshots as OperationSnapshot[];
+  },
+
+  getOperation: async (opts: OperationIdInput) => {
+    assertOperationIdInput(opts);
+    const snapshot = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getOperation, opts);
+    assertNullableOperationSnapshot(snapshot);
+    return snapshot;
+  },
+
+  cancelOperation: async (opts: OperationIdInput) => {
+    assertOperationIdInput(opts);
+    return (await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.cancelOperation, opts)) === true;
+  },
+
+  enqueueExport: async (opts: EnqueueExportInput) => {
+    assertEnqueueExportInput(opts);
+    const snapshot = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.enqueueExport, opts);
+    assertNullableOperationSnapshot(snapshot);
+    return snapshot;
+  },
+
+  saveBackupCredentials: async (opts: SaveBackupCredentialsInput) => {
+    assertSaveBackupCredentialsInput(opts);
+    const result = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.saveBackupCredentials, opts);
+    assertSaveBackupCredentialsResult(result);
+    return result;
+  },
+
+  configureBackupSync: async (opts: ConfigureBackupSyncInput) => {
+    assertConfigureBackupSyncInput(opts);
+    const config = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.configureBackupSync, opts);
+    assertBackupSyncConfig(config);
+    return config;
+  },
+
+  getBackupSyncConfig: async () => {
+    const config = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getBackupSyncConfig);
+    assertBackupSyncConfig(config);
+    return config;
+  },
+
+  syncBackupNow: async () => {
+    const snapshot = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.syncBackupNow);
+    assertOperationSnapshot(snapshot);
+    return snapshot;
+  },
+
   getTelemetryStatus: async (opts: TelemetryStatusInput = {}) => {
     assertTelemetryStatusInput(opts);
     const status = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getTelemetryStatus, opts);
@@ -793,6 +2258,18 @@ const desktopApi = Object.freeze<DesktopApi>({
 
   quitAndInstallUpdate: () => ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.quitAndInstallUpdate),
 
+  getSidecarUpdateState: async () => {
+    const state = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getSidecarUpdateState);
+    assertSidecarUpdateState(state);
+    return state;
+  },
+
+  applySidecarUpdate: async () => {
+    const state = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.applySidecarUpdate);
+    assertSidecarUpdateState(state);
+    return state;
+  },
+
   getSystemAppearance: async () => {
     const appearance = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getSystemAppearance);
     assertSystemAppearance(appearance);
@@ -812,6 +2289,17 @@ const desktopApi = Object.freeze<DesktopApi>({
     return appearance;
   },
 
+  setLocale: (opts: SetLocaleInput) => {
+    assertSetLocaleInput(opts);
+    return ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.setLocale, opts);
+  },
+
+  getBackendCapabilities: async () => {
+    const capabilities = await ipcRenderer.invoke(DESKTOP_IPC_CHANNELS.getBackendCapabilities);
+    assertDesktopBackendCapabilities(capabilities);
+    return capabilities;
+  },
+
   onSystemAppearanceChanged: (listener: (appearance: SystemAppearance) => void) => {
     if (typeof listener !== "function") {
       throw new Error("onSystemAppearanceChanged listener must be a function");
@@ -840,6 +2328,20 @@ const desktopApi = Object.freeze<DesktopApi>({
     };
   },
 
+  onSidecarUpdateStateChanged: (listener: (state: SidecarUpdateState) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onSidecarUpdateStateChanged listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertSidecarUpdateState(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.sidecarUpdateStateChanged, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.sidecarUpdateStateChanged, wrapped);
+    };
+  },
+
   onWorkspaceServerStartupProgress: (listener: (event: WorkspaceServerStartupProgress) => void) => {
     if (typeof listener !== "function") {
       throw new Error("onWorkspaceServerStartupProgress listener must be a function");
@@ -854,6 +2356,20 @@ const desktopApi = Object.freeze<DesktopApi>({
     };
   },
 
+  onWorkspaceServerPrewarmProgress: (listener: (event: WorkspaceServerPrewarmProgress) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onWorkspaceServerPrewarmProgress listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertWorkspaceServerPrewarmProgress(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.workspaceServerPrewarmProgress, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.workspaceServerPrewarmProgress, wrapped);
+    };
+  },
+
   onWorkspaceServerExited: (listener: (event: WorkspaceServerExitedEvent) => void) => {
     if (typeof listener !== "function") {
       throw new Error("onWorkspaceServerExited listener must be a function");
@@ -868,6 +2384,260 @@ const desktopApi = Object.freeze<DesktopApi>({
     };
   },
 
+  onServerSuspended: (listener: (event: ServerSuspendedEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onServerSuspended listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertServerSuspendedEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.serverSuspended, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.serverSuspended, wrapped);
+    };
+  },
+
+  onServerResumed: (listener: (event: ServerResumedEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onServerResumed listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertServerResumedEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.serverResumed, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.serverResumed, wrapped);
+    };
+  },
+
+  onServerReloaded: (listener: (event: ServerReloadedEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onServerReloaded listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertServerReloadedEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.serverReloaded, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.serverReloaded, wrapped);
+    };
+  },
+
+  onWorkspaceProxyConnectionChanged: (
+    listener: (event: WorkspaceProxyConnectionEvent) => void,
+  ) => {
+    if (typeof listener !== "function") {
+      throw new Error("onWorkspaceProxyConnectionChanged listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertWorkspaceProxyConnectionEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.workspaceProxyConnectionChanged, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.workspaceProxyConnectionChanged, wrapped);
+    };
+  },
+
+  onOperationUpdated: (listener: (snapshot: OperationSnapshot) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onOperationUpdated listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertOperationSnapshot(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.operationUpdated, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.operationUpdated, wrapped);
+    };
+  },
+
+  onExportProgress: (listener: (snapshot: OperationSnapshot) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onExportProgress listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertOperationSnapshot(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.exportProgress, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.exportProgress, wrapped);
+    };
+  },
+
+  onExportComplete: (listener: (snapshot: OperationSnapshot) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onExportComplete listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertOperationSnapshot(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.exportComplete, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.exportComplete, wrapped);
+    };
+  },
+
+  onTranscriptAppended: (listener: (event: TranscriptAppendedEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onTranscriptAppended listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertTranscriptAppendedEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.transcriptAppended, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.transcriptAppended, wrapped);
+    };
+  },
+
+  onServerStats: (listener: (event: ServerStatsEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onServerStats listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertServerStatsEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.serverStats, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.serverStats, wrapped);
+    };
+  },
+
+  onWorkspaceConfigChanged: (listener: (event: WorkspaceConfigSnapshot) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onWorkspaceConfigChanged listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertWorkspaceConfigSnapshot(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.workspaceConfigChanged, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.workspaceConfigChanged, wrapped);
+    };
+  },
+
+  onWorkspaceGitChanged: (listener: (status: WorkspaceGitStatus) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onWorkspaceGitChanged listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertWorkspaceGitStatus(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.workspaceGitChanged, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.workspaceGitChanged, wrapped);
+    };
+  },
+
+  onAppSettingsChanged: (listener: (settings: AppSettings) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onAppSettingsChanged listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertAppSettings(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.appSettingsChanged, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.appSettingsChanged, wrapped);
+    };
+  },
+
+  onTranscriptRetentionCompleted: (listener: (summary: TranscriptRetentionSummary) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onTranscriptRetentionCompleted listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertTranscriptRetentionSummary(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.transcriptRetentionCompleted, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.transcriptRetentionCompleted, wrapped);
+    };
+  },
+
+  onLowDiskSpace: (listener: (status: DiskSpaceStatus) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onLowDiskSpace listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertDiskSpaceStatus(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.lowDiskSpace, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.lowDiskSpace, wrapped);
+    };
+  },
+
+  onTranscriptThrottled: (listener: (event: TranscriptThrottledEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onTranscriptThrottled listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertTranscriptThrottledEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.transcriptThrottled, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.transcriptThrottled, wrapped);
+    };
+  },
+
+  onVoiceTranscript: (listener: (event: VoiceTranscriptEvent) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onVoiceTranscript listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertVoiceTranscriptEvent(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.voiceTranscript, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.voiceTranscript, wrapped);
+    };
+  },
+
+  onConnectivityChanged: (listener: (status: ConnectivityStatus) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onConnectivityChanged listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertConnectivityStatus(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.connectivityChanged, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.connectivityChanged, wrapped);
+    };
+  },
+
+  onScheduledTaskRunCompleted: (listener: (run: ScheduledTaskRun) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onScheduledTaskRunCompleted listener must be a function");
+    }
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertScheduledTaskRun(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.scheduledTaskRunCompleted, wrapped);
+    return () => {
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.scheduledTaskRunCompleted, wrapped);
+    };
+  },
+
   onWindowCloseRequested: (listener: (request: WindowCloseRequest) => void) => {
     if (typeof listener !== "function") {
       throw new Error("onWindowCloseRequested listener must be a function");
@@ -926,6 +2696,36 @@ const desktopApi = Object.freeze<DesktopApi>({
     };
   },
 
+  onDeepLinkNavigation: (listener: (navigation: DesktopDeepLinkNavigation) => void) => {
+    if (typeof listener !== "function") {
+      throw new Error("onDeepLinkNavigation listener must be a function");
+    }
+    let active = true;
+    const wrapped = (_event: unknown, payload: unknown) => {
+      assertDesktopDeepLinkNavigation(payload);
+      listener(payload);
+    };
+    ipcRenderer.on(DESKTOP_EVENT_CHANNELS.deepLinkNavigation, wrapped);
+    void ipcRenderer
+      .invoke(DESKTOP_IPC_CHANNELS.consumePendingDeepLinks)
+      .then((payload: unknown) => {
+        if (!active || !Array.isArray(payload)) {
+          return;
+        }
+        for (const navigation of payload) {
+          assertDesktopDeepLinkNavigation(navigation);
+          listener(navigation);
+        }
+      })
+      .catch(() => {
+        // Links opened while the app is running are still delivered live.
+      });
+    return () => {
+      active = false;
+      ipcRenderer.off(DESKTOP_EVENT_CHANNELS.deepLinkNavigation, wrapped);
+    };
+  },
+
   onMobileRelayStateChanged: (listener: (state: MobileRelayBridgeState) => void) => {
     if (typeof listener !== "function") {
       throw new Error("onMobileRelayStateChanged listener must be a function");
@@ -1894,7 +1894,7 @@ async function handleIpc(
     case DESKTOP_IPC_CHANNELS.createOneOffChatWorkspace:
       return { name: "Quality quick chat", path: "/quality/new-quick-chat" };
     case DESKTOP_IPC_CHANNELS.startWorkspaceServer:
-      return { url: mockServerUrl };
+      return { url: mockServerUrl, authToken: null };
     case DESKTOP_IPC_CHANNELS.getWorkspaceServerStatus:
       return {
         workspaceId:
@@ -0,0 +1,379 @@
+import { createReadStream, createWriteStream } from "node:fs";
+import fs from "node:fs/promises";
+import readline from "node:readline";
+import { Readable } from "node:stream";
+import { pipeline } from "node:stream/promises";
+import zlib from "node:zlib";
+
+import type { PersistedState, TranscriptEvent } from "../../src/app/types";
+import type {
+  AppDataArchiveManifest,
+  AppDataMergeStrategy,
+  AppSettings,
+  ExportAppDataResult,
+  ImportAppDataResult,
+  OperationProgress,
+  TranscriptAnnotation,
+  TranscriptBatchInput,
+  UpdateAppSettingsInput,
+} from "../../src/lib/desktopApi";
+import { normalizeAppSettings } from "./appSettings";
+import type { PersistenceService } from "./persistence";
+import type { TranscriptAnnotationStore } from "./transcriptAnnotations";
+import { coerceLegacyTranscriptTimestamp } from "./transcriptTimestamps";
+import { assertSafeId } from "./validation";
+
+export const APP_DATA_ARCHIVE_FORMAT = "cowork-app-data";
+export const APP_DATA_ARCHIVE_VERSION = 1;
+export const APP_DATA_ARCHIVE_EXTENSION = "coworkdata";
+
+const PRIVATE_FILE_MODE = 0o600;
+
+/**
+ * One line of the archive. The manifest comes first and `end` last, so a
+ * truncated copy is rejected instead of half-imported.
+ */
+type ArchiveRecord =
+  | { kind: "manifest"; manifest: AppDataArchiveManifest }
+  | { kind: "state"; state: PersistedState }
+  | { kind: "settings"; settings: AppSettings }
+  | { kind: "transcript"; threadId: string; events: TranscriptEvent[] }
+  | { kind: "annotations"; threadId: string; annotations: TranscriptAnnotation[] }
+  | { kind: "end" };
+
+type AppDataExportTaskOptions = {
+  signal?: AbortSignal;
+  onProgress?: (progress: OperationProgress) => void;
+};
+
+type AppDataTransferOptions = {
+  appVersion: string;
+  persistence: Pick<
+    PersistenceService,
+    | "loadState"
+    | "saveState"
+    | "captureStateSnapshot"
+    | "readTranscript"
+    | "deleteTranscript"
+    | "appendTranscriptBatch"
+  >;
+  settings: {
+    get(): Promise<AppSettings>;
+    update(patch: UpdateAppSettingsInput): Promise<AppSettings>;
+  };
+  annotations?: Pick<TranscriptAnnotationStore, "list" | "replace">;
+  now?: () => Date;
+};
+
+type ParsedArchive = {
+  manifest: AppDataArchiveManifest;
+  state: PersistedState;
+  settings: AppSettings | null;
+  annotations: Map<string, TranscriptAnnotation[]>;
+};
+
+export class AppDataArchiveError extends Error {
+  readonly code = "invalid_app_data_archive";
+
+  constructor(message: string) {
+    super(message);
+    this.name = "AppDataArchiveError";
+  }
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+function checkManifest(value: unknown): AppDataArchiveManifest {
+  if (!isRecord(value) || value.format !== APP_DATA_ARCHIVE_FORMAT) {
+    throw new AppDataArchiveError("This file is not a Cowork data export.");
+  }
+  if (typeof value.formatVersion !== "number" || value.formatVersion < 1) {
+    throw new AppDataArchiveError("The export has an invalid format version.");
+  }
+  if (value.formatVersion > APP_DATA_ARCHIVE_VERSION) {
+    throw new AppDataArchiveError(
+      `The export was made by a newer version of Cowork (${String(value.appVersion)}). ` +
+        "Update the app and try again.",
+    );
+  }
+  return value as AppDataArchiveManifest;
+}
+
+async function* readArchiveRecords(archivePath: string): AsyncGenerator<ArchiveRecord> {
+  const file = createReadStream(archivePath);
+  const gunzip = zlib.createGunzip();
+  // pipe() does not forward errors, so a missing file would otherwise go unhandled.
+  file.on("error", (error) => gunzip.destroy(error));
+  const lines = readline.createInterface({
+    input: file.pipe(gunzip),
+    crlfDelay: Number.POSITIVE_INFINITY,
+  });
+  try {
+    for await (const line of lines) {
+      if (!line.trim()) {
+        continue;
+      }
+      let record: unknown;
+      try {
+        record = JSON.parse(line);
+      } catch {
+        throw new AppDataArchiveError("The export is corrupted.");
+      }
+      if (!isRecord(record) || typeof record.kind !== "string") {
+        throw new AppDataArchiveError("The export is corrupted.");
+      }
+      yield record as ArchiveRecord;
+    }
+  } catch (error) {
+    if (error instanceof AppDataArchiveError) {
+      throw error;
+    }
+    const code = (error as NodeJS.ErrnoException).code;
+    if (code === "ENOENT") {
+      throw error;
+    }
+    throw new AppDataArchiveError("The export is corrupted or not a Cowork data export.");
+  } finally {
+    lines.close();
+    file.destroy();
+  }
+}
+
+/**
+ * Moves the app's data between machines as one archive: a gzipped file of
+ * JSON lines holding the manifest, state.json, settings, and optionally each
+ * thread's transcript and annotations. Transcripts are stored decrypted so the
+ * archive opens on a machine with a different keychain; the file is written
+ * owner-only. Imports are checked in full before anything is written, and the
+ * current state is snapshotted first.
+ */
+export class AppDataTransferService {
+  constructor(private readonly options: AppDataTransferOptions) {}
+
+  async exportTo(
+    destPath: string,
+    includeTranscripts: boolean,
+    task: AppDataExportTaskOptions = {},
+  ): Promise<ExportAppDataResult> {
+    const { signal, onProgress } = task;
+    const state = await this.options.persistence.loadState();
+    const total = includeTranscripts ? state.threads.length : 0;
+    const manifest: AppDataArchiveManifest = {
+      format: APP_DATA_ARCHIVE_FORMAT,
+      formatVersion: APP_DATA_ARCHIVE_VERSION,
+      appVersion: this.options.appVersion,
+      createdAt: (this.options.now?.() ?? new Date()).toISOString(),
+      includesTranscripts: includeTranscripts,
+      workspaceCount: state.workspaces.length,
+      threadCount: state.threads.length,
+    };
+    const { generation: _generation, ...portableState } = state;
+    const settings = await this.options.settings.get();
+    const { persistence, annotations } = this.options;
+
+    async function* lines(): AsyncGenerator<string> {
+      const line = (record: ArchiveRecord) => `${JSON.stringify(record)}\n`;
+      yield line({ kind: "manifest", manifest });
+      yield line({ kind: "state", state: portableState });
+      yield line({ kind: "settings", settings });
+      if (includeTranscripts) {
+        for (const [index, thread] of state.threads.entries()) {
+          signal?.throwIfAborted();
+          onProgress?.({ completed: index, total, message: `Exporting ${thread.title}` });
+          const events = await persistence.readTranscript(thread.id);
+          yield line({ kind: "transcript", threadId: thread.id, events });
+          const notes = (await annotations?.list(thread.id)) ?? [];
+          if (notes.length > 0) {
+            yield line({ kind: "annotations", threadId: thread.id, annotations: notes });
+          }
+        }
+      }
+      yield line({ kind: "end" });
+    }
+
+    const partialPath = `${destPath}.partial`;
+    try {
+      await pipeline(
+        Readable.from(lines()),
+        zlib.createGzip(),
+        createWriteStream(partialPath, { mode: PRIVATE_FILE_MODE }),
+        { signal },
+      );
+      await fs.rename(partialPath, destPath);
+    } catch (error) {
+      await fs.rm(partialPath, { force: true });
+      throw error;
+    }
+    const { size } = await fs.stat(destPath);
+    return { path: destPath, sizeBytes: size, manifest };
+  }
+
+  async importFrom(
+    archivePath: string,
+    mergeStrategy: AppDataMergeStrategy,
+  ): Promise<ImportAppDataResult> {
+    const archive = await this.parse(archivePath);
+    const { persistence } = this.options;
+    const current = await persistence.loadState();
+
+    let nextState: PersistedState;
+    let importedThreadIds: Set<string>;
+    let workspacesImported: number;
+    if (mergeStrategy === "replace") {
+      nextState = {
+        ...archive.state,
+        // The analytics install id belongs to this machine, not the old one.
+        productAnalytics: current.productAnalytics,
+        generation: undefined,
+      };
+      importedThreadIds = new Set(archive.state.threads.map((thread) => thread.id));
+      workspacesImported = archive.state.workspaces.length;
+    } else {
+      const workspaceIds = new Set(current.workspaces.map((workspace) => workspace.id));
+      const threadIds = new Set(current.threads.map((thread) => thread.id));
+      const workspaces = archive.state.workspaces.filter(
+        (workspace) => !workspaceIds.has(workspace.id),
+      );
+      for (const workspace of workspaces) {
+        workspaceIds.add(workspace.id);
+      }
+      const threads = archive.state.threads.filter(
+        (thread) => !threadIds.has(thread.id) && workspaceIds.has(thread.workspaceId),
+      );
+      nextState = {
+        ...current,
+        workspaces: [...current.workspaces, ...workspaces],
+        threads: [...current.threads, ...threads],
+        generation: undefined,
+      };
+      importedThreadIds = new Set(threads.map((thread) => thread.id));
+      workspacesImported = workspaces.length;
+    }
+
+    const snapshot = await persistence.captureStateSnapshot();
+    let transcriptsImported = 0;
+    let transcriptEventsSkipped = 0;
+    if (archive.manifest.includesTranscripts && importedThreadIds.size > 0) {
+      for await (const record of readArchiveRecords(archivePath)) {
+        if (record.kind !== "transcript" || !importedThreadIds.has(record.threadId)) {
+          continue;
+        }
+        // Exports carry timestamps as they were stored, so ones written before
+        // appends were validated are fixed here. The whole record is checked
+        // before the local transcript is deleted, so append cannot reject it.
+        const batch: TranscriptBatchInput[] = [];
+        for (const event of record.events) {
+          const ts = coerceLegacyTranscriptTimestamp(String(event.ts));
+          if (ts === null || (event.direction !== "server" && event.direction !== "client")) {
+            transcriptEventsSkipped += 1;
+            continue;
+          }
+          batch.push({
+            ts,
+            threadId: record.threadId,
+            direction: event.direction,
+            payload: event.payload,
+          });
+        }
+        await persistence.deleteTranscript(record.threadId);
+        await persistence.appendTranscriptBatch(batch);
+        transcriptsImported += 1;
+      }
+      for (const [threadId, annotations] of archive.annotations) {
+        if (importedThreadIds.has(threadId)) {
+          await this.options.annotations?.replace(threadId, annotations);
+        }
+      }
+    }
+    await persistence.saveState(nextState);
+
+    let settingsApplied = false;
+    if (mergeStrategy === "replace" && archive.settings) {
+      try {
+        // Transcripts were imported into this installation's store; keep using it.
+        const { transcriptStore: _transcriptStore, ...portable } = archive.settings;
+        await this.options.settings.update(portable);
+        settingsApplied = true;
+      } catch {
+        settingsApplied = false;
+      }
+    }
+
+    return {
+      manifest: archive.manifest,
+      mergeStrategy,
+      snapshotName: snapshot?.name ?? null,
+      workspacesImported,
+      threadsImported: importedThreadIds.size,
+      threadsSkipped: archive.state.threads.length - importedThreadIds.size,
+      transcriptsImported,
+      transcriptEventsSkipped,
+      settingsApplied,
+      state: await persistence.loadState(),
+    };
+  }
+
+  /** Reads the whole archive once to check it before any of it is applied. */
+  private async parse(archivePath: string): Promise<ParsedArchive> {
+    let manifest: AppDataArchiveManifest | null = null;
+    let state: PersistedState | null = null;
+    let settings: AppSettings | null = null;
+    const annotations = new Map<string, TranscriptAnnotation[]>();
+    let ended = false;
+    for await (const record of readArchiveRecords(archivePath)) {
+      if (!manifest) {
+        if (record.kind !== "manifest") {
+          throw new AppDataArchiveError("This file is not a Cowork data export.");
+        }
+        manifest = checkManifest(record.manifest);
+        continue;
+      }
+      if (ended) {
+        throw new AppDataArchiveError("The export has data after its end marker.");
+      }
+      switch (record.kind) {
+        case "state":
+          if (!isRecord(record.state) || !Array.isArray(record.state.workspaces)) {
+            throw new AppDataArchiveError("The export's state is invalid.");
+          }
+          state = record.state;
+          break;
+        case "settings":
+          settings = normalizeAppSettings(record.settings);
+          break;
+        case "transcript":
+          assertSafeId(record.threadId, "threadId");
+          if (!Array.isArray(record.events)) {
+            throw new AppDataArchiveError(`The transcript of ${record.threadId} is invalid.`);
+          }
+          break;
+        case "annotations":
+          assertSafeId(record.threadId, "threadId");
+          if (Array.isArray(record.annotations)) {
+            annotations.set(record.threadId, record.annotations);
+          }
+          break;
+        case "end":
+          ended = true;
+          break;
+      }
+    }
+    if (!manifest) {
+      throw new AppDataArchiveError("This file is not a Cowork data export.");
+    }
+    if (!state) {
+      throw new AppDataArchiveError("The export has no saved state.");
+    }
+    if (!ended) {
+      throw new AppDataArchiveError("The export is incomplete; it may have been cut off.");
+    }
+    return {
+      manifest,
+      state: { ...state, threads: Array.isArray(state.threads) ? state.threads : [] },
+      settings,
+      annotations,
+    };
+  }
+}
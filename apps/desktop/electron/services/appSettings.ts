@@ -0,0 +1,273 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import type { App } from "electron";
+
+import { hardenPrivateDir, hardenPrivateFile, writeFileAtomic } from "../../../../src/platform/fs";
+import { isProviderName } from "../../../../src/types";
+import type { AppSettings, UpdateAppSettingsInput } from "../../src/lib/desktopApi";
+import {
+  LOCALE_TAG_PATTERN,
+  MAX_IDLE_SERVER_TIMEOUT_MINUTES,
+  MAX_ISOLATED_SIDECARS,
+  MAX_MIN_FREE_DISK_MB,
+  MAX_TRANSCRIPT_RETENTION_DAYS,
+  MAX_TRANSCRIPT_RETENTION_TOTAL_MB,
+  MAX_TRASH_RETENTION_DAYS,
+  MAX_YOLO_GRANT_MINUTES,
+} from "../../src/lib/desktopSchemas";
+import { normalizeThemeSource } from "./appearancePreferences";
+
+export const APP_SETTINGS_FILE_NAME = "settings.json";
+
+const PRIVATE_FILE_MODE = 0o600;
+const PRIVATE_DIR_MODE = 0o700;
+
+export const DEFAULT_APP_SETTINGS: AppSettings = {
+  defaultProvider: null,
+  startupBehavior: "restoreLastThread",
+  transcriptRetentionDays: 0,
+  transcriptRetentionMaxTotalMb: 0,
+  minFreeDiskMb: 256,
+  trashRetentionDays: 30,
+  transcriptValidation: "annotate",
+  notificationsEnabled: true,
+  httpProxy: null,
+  httpsProxy: null,
+  noProxy: null,
+  caBundlePath: null,
+  themeSource: "system",
+  isolatedSessions: false,
+  maxIsolatedSidecars: 4,
+  locale: null,
+  idleServerSuspend: "onBattery",
+  idleServerTimeoutMinutes: 30,
+  yoloGrantMinutes: 60,
+  voiceTranscriptionBaseUrl: null,
+  voiceTranscriptionModel: null,
+  transcriptStore: "jsonl",
+};
+
+type AppSettingsListener = (settings: AppSettings) => void;
+
+type AppSettingsStoreOptions = {
+  emit?: (settings: AppSettings) => void;
+  /** Runs before a changed value is written; a rejection leaves the settings as they were. */
+  validate?: (next: AppSettings, current: AppSettings) => Promise<void>;
+  hardenPrivateDir?: (directory: string) => Promise<void>;
+  hardenPrivateFile?: (filePath: string) => Promise<void>;
+  writeFileAtomic?: typeof writeFileAtomic;
+};
+
+function isNotFound(error: unknown): boolean {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    (error as NodeJS.ErrnoException).code === "ENOENT"
+  );
+}
+
+function clampWholeNumber(value: unknown, max: number, fallback: number): number {
+  return typeof value === "number" && Number.isFinite(value)
+    ? Math.min(max, Math.max(0, Math.floor(value)))
+    : fallback;
+}
+
+function optionalTrimmedString(value: unknown): string | null {
+  return typeof value === "string" && value.trim() ? value.trim() : null;
+}
+
+function normalizeHttpUrl(value: unknown): string | null {
+  const trimmed = optionalTrimmedString(value);
+  if (!trimmed) {
+    return null;
+  }
+  try {
+    const { protocol } = new URL(trimmed);
+    return protocol === "http:" || protocol === "https:" ? trimmed : null;
+  } catch {
+    return null;
+  }
+}
+
+function normalizeAbsolutePath(value: unknown): string | null {
+  const trimmed = optionalTrimmedString(value);
+  return trimmed && path.isAbsolute(trimmed) ? trimmed : null;
+}
+
+/** Keeps a BCP 47-shaped tag such as `es-MX`; anything else follows the OS. */
+function normalizeLocaleTag(value: unknown): string | null {
+  const trimmed = optionalTrimmedString(value);
+  return trimmed && LOCALE_TAG_PATTERN.test(trimmed) ? trimmed : null;
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+/** Fills missing or malformed keys from the defaults so older files keep loading. */
+export function normalizeAppSettings(value: unknown): AppSettings {
+  const raw = isPlainObject(value) ? value : {};
+  return {
+    defaultProvider: isProviderName(raw.defaultProvider)
+      ? raw.defaultProvider
+      : DEFAULT_APP_SETTINGS.defaultProvider,
+    startupBehavior:
+      raw.startupBehavior === "newChat" || raw.startupBehavior === "restoreLastThread"
+        ? raw.startupBehavior
+        : DEFAULT_APP_SETTINGS.startupBehavior,
+    transcriptRetentionDays: clampWholeNumber(
+      raw.transcriptRetentionDays,
+      MAX_TRANSCRIPT_RETENTION_DAYS,
+      DEFAULT_APP_SETTINGS.transcriptRetentionDays,
+    ),
+    transcriptRetentionMaxTotalMb: clampWholeNumber(
+      raw.transcriptRetentionMaxTotalMb,
+      MAX_TRANSCRIPT_RETENTION_TOTAL_MB,
+      DEFAULT_APP_SETTINGS.transcriptRetentionMaxTotalMb,
+    ),
+    minFreeDiskMb: clampWholeNumber(
+      raw.minFreeDiskMb,
+      MAX_MIN_FREE_DISK_MB,
+      DEFAULT_APP_SETTINGS.minFreeDiskMb,
+    ),
+    trashRetentionDays: clampWholeNumber(
+      raw.trashRetentionDays,
+      MAX_TRASH_RETENTION_DAYS,
+      DEFAULT_APP_SETTINGS.trashRetentionDays,
+    ),
+    transcriptValidation:
+      raw.transcriptValidation === "off" ||
+      raw.transcriptValidation === "annotate" ||
+      raw.transcriptValidation === "strict"
+        ? raw.transcriptValidation
+        : DEFAULT_APP_SETTINGS.transcriptValidation,
+    notificationsEnabled:
+      typeof raw.notificationsEnabled === "boolean"
+        ? raw.notificationsEnabled
+        : DEFAULT_APP_SETTINGS.notificationsEnabled,
+    httpProxy: normalizeHttpUrl(raw.httpProxy),
+    httpsProxy: normalizeHttpUrl(raw.httpsProxy),
+    noProxy: optionalTrimmedString(raw.noProxy),
+    caBundlePath: normalizeAbsolutePath(raw.caBundlePath),
+    themeSource: normalizeThemeSource(raw.themeSource),
+    isolatedSessions:
+      typeof raw.isolatedSessions === "boolean"
+        ? raw.isolatedSessions
+        : DEFAULT_APP_SETTINGS.isolatedSessions,
+    maxIsolatedSidecars: Math.max(
+      1,
+      clampWholeNumber(
+        raw.maxIsolatedSidecars,
+        MAX_ISOLATED_SIDECARS,
+        DEFAULT_APP_SETTINGS.maxIsolatedSidecars,
+      ),
+    ),
+    locale: normalizeLocaleTag(raw.locale),
+    idleServerSuspend:
+      raw.idleServerSuspend === "never" ||
+      raw.idleServerSuspend === "onBattery" ||
+      raw.idleServerSuspend === "always"
+        ? raw.idleServerSuspend
+        : DEFAULT_APP_SETTINGS.idleServerSuspend,
+    idleServerTimeoutMinutes: Math.max(
+      1,
+      clampWholeNumber(
+        raw.idleServerTimeoutMinutes,
+        MAX_IDLE_SERVER_TIMEOUT_MINUTES,
+        DEFAULT_APP_SETTINGS.idleServerTimeoutMinutes,
+      ),
+    ),
+    yoloGrantMinutes: Math.max(
+      1,
+      clampWholeNumber(
+        raw.yoloGrantMinutes,
+        MAX_YOLO_GRANT_MINUTES,
+        DEFAULT_APP_SETTINGS.yoloGrantMinutes,
+      ),
+    ),
+    voiceTranscriptionBaseUrl: normalizeHttpUrl(raw.voiceTranscriptionBaseUrl),
+    voiceTranscriptionModel: optionalTrimmedString(raw.voiceTranscriptionModel),
+    transcriptStore:
+      raw.transcriptStore === "jsonl" || raw.transcriptStore === "sqlite"
+        ? raw.transcriptStore
+        : DEFAULT_APP_SETTINGS.transcriptStore,
+  };
+}
+
+function sameSettings(left: AppSettings, right: AppSettings): boolean {
+  return (Object.keys(left) as Array<keyof AppSettings>).every((key) => left[key] === right[key]);
+}
+
+/**
+ * Owns `settings.json` in the user data directory. Reads are cached after the
+ * first load; updates merge a patch over the current values, write the file,
+ * and notify both the renderer and in-process subscribers.
+ */
+export class AppSettingsStore {
+  private cached: AppSettings | null = null;
+  private pendingWrite: Promise<unknown> = Promise.resolve();
+  private readonly listeners = new Set<AppSettingsListener>();
+
+  constructor(
+    private readonly electronApp: Pick<App, "getPath">,
+    private readonly options: AppSettingsStoreOptions = {},
+  ) {}
+
+  get filePath(): string {
+    return path.join(this.electronApp.getPath("userData"), APP_SETTINGS_FILE_NAME);
+  }
+
+  async get(): Promise<AppSettings> {
+    if (this.cached) {
+      return this.cached;
+    }
+    let parsed: unknown = {};
+    try {
+      parsed = JSON.parse(await fs.readFile(this.filePath, "utf8"));
+    } catch (error) {
+      if (!isNotFound(error) && !(error instanceof SyntaxError)) {
+        throw error;
+      }
+    }
+    this.cached ??= normalizeAppSettings(parsed);
+    return this.cached;
+  }
+
+  /** Unlike the renderer, the main process may also switch `transcriptStore`. */
+  async update(patch: UpdateAppSettingsInput | Partial<AppSettings>): Promise<AppSettings> {
+    const write = this.pendingWrite.then(async () => {
+      const current = await this.get();
+      const next = normalizeAppSettings({ ...current, ...patch });
+      if (sameSettings(current, next)) {
+        return current;
+      }
+      await this.options.validate?.(next, current);
+      const directory = path.dirname(this.filePath);
+      await fs.mkdir(directory, { recursive: true, mode: PRIVATE_DIR_MODE });
+      await (this.options.hardenPrivateDir ?? hardenPrivateDir)(directory);
+      await (this.options.writeFileAtomic ?? writeFileAtomic)(
+        this.filePath,
+        `${JSON.stringify(next, null, 2)}\n`,
+        { mode: PRIVATE_FILE_MODE },
+      );
+      await (this.options.hardenPrivateFile ?? hardenPrivateFile)(this.filePath);
+      this.cached = next;
+      this.options.emit?.(next);
+      for (const listener of this.listeners) {
+        listener(next);
+      }
+      return next;
+    });
+    this.pendingWrite = write.catch(() => {});
+    return await write;
+  }
+
+  subscribe(listener: AppSettingsListener): () => void {
+    this.listeners.add(listener);
+    return () => {
+      this.listeners.delete(listener);
+    };
+  }
+}
@@ -1,9 +1,15 @@
-import { type BrowserWindow, type BrowserWindowConstructorOptions, nativeTheme } from "electron";
+import {
+  type BrowserWindow,
+  type BrowserWindowConstructorOptions,
+  nativeTheme,
+  systemPreferences,
+} from "electron";
 
 import { hostPlatform } from "../../../../src/platform/host";
 import type {
   SetWindowAppearanceInput,
   SystemAppearance,
+  SystemColorScheme,
   ThemeSource,
   WindowsBackgroundMaterial,
 } from "../../src/lib/desktopApi";
@@ -11,9 +17,42 @@ import type { CaptionSymbolTone } from "../../src/styles/tokens/native";
 import { resolveWindowChromePaint } from "./windowAppearancePaint";
 import { shouldUseMacosNativeGlass, syncWindowChromeAppearance } from "./windowEnhancements";
 
+/** Posted by macOS when the accent or highlight color changes in System Settings. */
+const MACOS_COLOR_PREFERENCES_NOTIFICATION = "AppleColorPreferencesChangedNotification";
+
+export function resolveSystemColorScheme(
+  appearance: Pick<
+    SystemAppearance,
+    "shouldUseDarkColors" | "shouldUseHighContrastColors" | "inForcedColorsMode"
+  >,
+): SystemColorScheme {
+  if (appearance.shouldUseHighContrastColors || appearance.inForcedColorsMode) {
+    return "high-contrast";
+  }
+  return appearance.shouldUseDarkColors ? "dark" : "light";
+}
+
+/** `systemPreferences.getAccentColor` returns `RRGGBBAA`; the alpha is dropped. */
+export function normalizeAccentColor(value: string | null | undefined): string | null {
+  const match = /^#?([0-9a-f]{6})(?:[0-9a-f]{2})?$/i.exec(value?.trim() ?? "");
+  return match?.[1] ? `#${match[1].toLowerCase()}` : null;
+}
+
+function readAccentColor(platform: string): string | null {
+  if (platform !== "darwin" && platform !== "win32") {
+    return null;
+  }
+  try {
+    return normalizeAccentColor(systemPreferences.getAccentColor());
+  } catch {
+    return null;
+  }
+}
+
 export function getSystemAppearanceSnapshot(): SystemAppearance {
-  return {
-    platform: hostPlatform(),
+  const platform = hostPlatform();
+  const appearance: SystemAppearance = {
+    platform,
     themeSource: nativeTheme.themeSource,
     shouldUseDarkColors: nativeTheme.shouldUseDarkColors,
     shouldUseDarkColorsForSystemIntegratedUI: nativeTheme.shouldUseDarkColorsForSystemIntegratedUI,
@@ -22,6 +61,11 @@ export function getSystemAppearanceSnapshot(): SystemAppearance {
     prefersReducedTransparency: nativeTheme.prefersReducedTransparency,
     inForcedColorsMode: nativeTheme.inForcedColorsMode,
   };
+  return {
+    ...appearance,
+    colorScheme: resolveSystemColorScheme(appearance),
+    accentColor: readAccentColor(platform),
+  };
 }
 
 export function applyThemeSourcePreference(themeSource: ThemeSource): SystemAppearance {
@@ -181,10 +225,45 @@ export function applyWindowAppearance(
   return getSystemAppearanceSnapshot();
 }
 
+/**
+ * Sends a snapshot whenever the theme, contrast, or accent color changes. The
+ * accent color comes from a distributed notification on macOS and from the
+ * settings-change broadcast behind `accent-color-changed` on Windows. One OS
+ * change can fire several of these, so unchanged snapshots are not resent.
+ */
 export function registerSystemAppearanceListener(
   send: (appearance: SystemAppearance) => void,
 ): () => void {
-  const handler = () => send(getSystemAppearanceSnapshot());
+  const platform = hostPlatform();
+  let last = JSON.stringify(getSystemAppearanceSnapshot());
+  const handler = () => {
+    const appearance = getSystemAppearanceSnapshot();
+    const serialized = JSON.stringify(appearance);
+    if (serialized === last) {
+      return;
+    }
+    last = serialized;
+    send(appearance);
+  };
   nativeTheme.on("updated", handler);
-  return () => nativeTheme.off("updated", handler);
+  const cleanups = [() => nativeTheme.off("updated", handler)];
+  if (platform === "win32") {
+    systemPreferences.on("accent-color-changed", handler);
+    systemPreferences.on("color-changed", handler);
+    cleanups.push(() => {
+      systemPreferences.off("accent-color-changed", handler);
+      systemPreferences.off("color-changed", handler);
+    });
+  } else if (platform === "darwin") {
+    const subscription = systemPreferences.subscribeNotification(
+      MACOS_COLOR_PREFERENCES_NOTIFICATION,
+      handler,
+    );
+    cleanups.push(() => systemPreferences.unsubscribeNotification(subscription));
+  }
+  return () => {
+    for (const cleanup of cleanups) {
+      cleanup();
+    }
+  };
 }
@@ -22,6 +22,10 @@ export function normalizeThemeSource(value: unknown): ThemeSource {
   return value === "light" || value === "dark" ? value : "system";
 }
 
+/**
+ * `appearance.json`, where the theme source was kept before it moved into app
+ * settings. Startup reads it once to carry an existing choice over.
+ */
 export class AppearancePreferences {
   private pendingWrite: Promise<void> = Promise.resolve();
 
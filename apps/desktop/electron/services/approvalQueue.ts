@@ -0,0 +1,188 @@
+import fs from "node:fs/promises";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type {
+  PendingApproval,
+  RecordPendingApprovalInput,
+  RecordPendingApprovalResult,
+  ResolveApprovalInput,
+} from "../../src/lib/desktopApi";
+
+const MAX_PENDING_APPROVALS = 500;
+const MAX_RULES_PER_WORKSPACE = 200;
+
+/** A command the user chose to always approve in one workspace. */
+export type ApprovalAllowRule = {
+  command: string;
+  category?: PendingApproval["category"];
+  createdAt: string;
+};
+
+type ApprovalState = {
+  pending: PendingApproval[];
+  rules: Record<string, ApprovalAllowRule[]>;
+};
+
+type ApprovalsFile = ApprovalState & { version: 1 };
+
+type ApprovalQueueOptions = {
+  filePath: string;
+  now?: () => Date;
+};
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+function parseApprovalsFile(value: unknown): ApprovalState {
+  if (!isRecord(value)) {
+    return { pending: [], rules: {} };
+  }
+  const pending = Array.isArray(value.pending)
+    ? value.pending.filter(
+        (entry): entry is PendingApproval =>
+          isRecord(entry) &&
+          typeof entry.id === "string" &&
+          typeof entry.workspaceId === "string" &&
+          typeof entry.threadId === "string" &&
+          typeof entry.command === "string" &&
+          typeof entry.receivedAt === "string",
+      )
+    : [];
+  const rules: Record<string, ApprovalAllowRule[]> = {};
+  if (isRecord(value.rules)) {
+    for (const [workspaceId, entries] of Object.entries(value.rules)) {
+      if (!Array.isArray(entries)) continue;
+      const valid = entries.filter(
+        (entry): entry is ApprovalAllowRule =>
+          isRecord(entry) && typeof entry.command === "string" && entry.command.length > 0,
+      );
+      if (valid.length > 0) {
+        rules[workspaceId] = valid;
+      }
+    }
+  }
+  return { pending, rules };
+}
+
+function ruleMatches(rule: ApprovalAllowRule, approval: RecordPendingApprovalInput): boolean {
+  return rule.command === approval.command && rule.category === approval.category;
+}
+
+/**
+ * Tool-call approvals waiting on the user, and the allow-rules they chose to
+ * remember, kept in one file so both survive a reload or restart. The
+ * renderer owns the sidecar connections, so it reports approvals as they
+ * arrive and sends the decision itself; this queue decides whether a
+ * remembered rule already covers a request. Rules match the exact command and
+ * never cover requests the server flags as dangerous.
+ */
+export class ApprovalQueue {
+  private state: ApprovalState | null = null;
+  private updates: Promise<unknown> = Promise.resolve();
+
+  constructor(private readonly options: ApprovalQueueOptions) {}
+
+  async record(input: RecordPendingApprovalInput): Promise<RecordPendingApprovalResult> {
+    return await this.update((state) => {
+      const rules = state.rules[input.workspaceId] ?? [];
+      if (!input.dangerous && rules.some((rule) => ruleMatches(rule, input))) {
+        return { result: { autoApproved: true }, changed: false };
+      }
+      // Servers replay unanswered requests on reconnect; keep the first receipt.
+      if (state.pending.some((entry) => entry.id === input.id)) {
+        return { result: { autoApproved: false }, changed: false };
+      }
+      state.pending.push({ ...input, receivedAt: this.now().toISOString() });
+      if (state.pending.length > MAX_PENDING_APPROVALS) {
+        state.pending.splice(0, state.pending.length - MAX_PENDING_APPROVALS);
+      }
+      return { result: { autoApproved: false }, changed: true };
+    });
+  }
+
+  async list(workspaceId: string): Promise<PendingApproval[]> {
+    const state = await this.load();
+    return state.pending.filter((entry) => entry.workspaceId === workspaceId);
+  }
+
+  /** Removes the approval and, when asked, remembers its command as allowed. */
+  async resolve(input: ResolveApprovalInput): Promise<PendingApproval | null> {
+    return await this.update((state) => {
+      const approval = state.pending.find((entry) => entry.id === input.id);
+      if (!approval) {
+        return { result: null, changed: false };
+      }
+      const remember = input.rememberRule === true && input.decision === "approve";
+      if (remember && approval.dangerous) {
+        throw new Error("Dangerous commands cannot be approved automatically.");
+      }
+      state.pending = state.pending.filter((entry) => entry !== approval);
+      const rules = state.rules[approval.workspaceId] ?? [];
+      if (remember && !rules.some((rule) => ruleMatches(rule, approval))) {
+        rules.push({
+          command: approval.command,
+          ...(approval.category ? { category: approval.category } : {}),
+          createdAt: this.now().toISOString(),
+        });
+        state.rules[approval.workspaceId] = rules.slice(-MAX_RULES_PER_WORKSPACE);
+      }
+      return { result: approval, changed: true };
+    });
+  }
+
+  /** Drops a workspace's pending approvals once its server exits and can no longer take answers. */
+  async clearPending(workspaceId: string): Promise<void> {
+    await this.update((state) => {
+      const remaining = state.pending.filter((entry) => entry.workspaceId !== workspaceId);
+      const changed = remaining.length !== state.pending.length;
+      state.pending = remaining;
+      return { result: undefined, changed };
+    });
+  }
+
+  private now(): Date {
+    return this.options.now?.() ?? new Date();
+  }
+
+  private async load(): Promise<ApprovalState> {
+    if (this.state) {
+      return this.state;
+    }
+    let raw: string | null = null;
+    try {
+      raw = await fs.readFile(this.options.filePath, "utf8");
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code !== "ENOENT") {
+        throw error;
+      }
+    }
+    let parsed: unknown = null;
+    try {
+      parsed = raw === null ? null : JSON.parse(raw);
+    } catch {
+      parsed = null;
+    }
+    this.state ??= parseApprovalsFile(parsed);
+    return this.state;
+  }
+
+  /** Applies `fn` to the state, one update at a time, writing the file when it changed. */
+  private async update<T>(
+    fn: (state: ApprovalState) => { result: T; changed: boolean },
+  ): Promise<T> {
+    const next = this.updates
+      .catch(() => {})
+      .then(async () => {
+        const state = await this.load();
+        const { result, changed } = fn(state);
+        if (changed) {
+          const file: ApprovalsFile = { version: 1, ...state };
+          await writeFileAtomic(this.options.filePath, `${JSON.stringify(file, null, 2)}\n`);
+        }
+        return result;
+      });
+    this.updates = next;
+    return await next;
+  }
+}
@@ -0,0 +1,139 @@
+import crypto from "node:crypto";
+import fs, { type FileHandle } from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import { MAX_ATTACHMENT_UPLOAD_BYTE_SIZE } from "../../../../src/shared/attachments";
+import type { AttachmentContents, StoredAttachment } from "../../src/lib/desktopApi";
+
+const ATTACHMENT_HASH = /^[a-f0-9]{64}$/;
+const SNIFF_BYTES = 512;
+
+type AttachmentStoreOptions = {
+  directory: string;
+  maxBytes?: number;
+};
+
+function startsWith(bytes: Uint8Array, signature: number[], offset = 0): boolean {
+  return signature.every((byte, index) => bytes[offset + index] === byte);
+}
+
+function ascii(bytes: Uint8Array, start: number, end: number): string {
+  return Buffer.from(bytes.subarray(start, end)).toString("latin1");
+}
+
+function looksLikeText(bytes: Uint8Array): boolean {
+  if (bytes.includes(0)) {
+    return false;
+  }
+  try {
+    new TextDecoder("utf-8", { fatal: true }).decode(bytes);
+    return true;
+  } catch {
+    // A multi-byte character cut off at the end of the sample is still text.
+    return bytes.length >= SNIFF_BYTES && looksLikeText(bytes.subarray(0, bytes.length - 3));
+  }
+}
+
+/**
+ * Identifies a file from its leading bytes, so a renamed or extensionless file
+ * is still labelled by what it contains. Unrecognised binary data is
+ * `application/octet-stream`.
+ */
+export function sniffMimeType(bytes: Uint8Array): string {
+  const head = bytes.subarray(0, SNIFF_BYTES);
+  if (startsWith(head, [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a])) return "image/png";
+  if (startsWith(head, [0xff, 0xd8, 0xff])) return "image/jpeg";
+  if (ascii(head, 0, 6) === "GIF87a" || ascii(head, 0, 6) === "GIF89a") return "image/gif";
+  if (ascii(head, 0, 4) === "RIFF" && ascii(head, 8, 12) === "WEBP") return "image/webp";
+  if (ascii(head, 0, 4) === "RIFF" && ascii(head, 8, 12) === "WAVE") return "audio/wav";
+  if (ascii(head, 0, 2) === "BM") return "image/bmp";
+  if (ascii(head, 0, 5) === "%PDF-") return "application/pdf";
+  if (startsWith(head, [0x50, 0x4b, 0x03, 0x04])) return "application/zip";
+  if (startsWith(head, [0x1f, 0x8b])) return "application/gzip";
+  if (ascii(head, 0, 3) === "ID3" || startsWith(head, [0xff, 0xfb])) return "audio/mpeg";
+  if (ascii(head, 4, 8) === "ftyp") {
+    return ascii(head, 8, 12).startsWith("M4A") ? "audio/mp4" : "video/mp4";
+  }
+  if (looksLikeText(head)) {
+    const text = Buffer.from(head).toString("utf8").trimStart();
+    if (/^(?:<\?xml[^>]*>\s*)?<svg[\s>]/i.test(text)) return "image/svg+xml";
+    return "text/plain";
+  }
+  return "application/octet-stream";
+}
+
+/**
+ * Files sent to the agent, kept once each under the sha256 of their contents
+ * so the same screenshot attached to ten threads is stored once and
+ * transcripts only carry its hash. Blobs are never modified after they are
+ * written, and the MIME type is sniffed again on read rather than stored.
+ */
+export class AttachmentStore {
+  private readonly maxBytes: number;
+
+  constructor(private readonly options: AttachmentStoreOptions) {
+    this.maxBytes = options.maxBytes ?? MAX_ATTACHMENT_UPLOAD_BYTE_SIZE;
+  }
+
+  async store(bytes: Uint8Array): Promise<StoredAttachment & { deduplicated: boolean }> {
+    if (bytes.byteLength > this.maxBytes) {
+      const limitMb = Math.floor(this.maxBytes / (1024 * 1024));
+      throw new Error(`Attachments are limited to ${limitMb}MB`);
+    }
+    const hash = crypto.createHash("sha256").update(bytes).digest("hex");
+    const filePath = this.pathFor(hash);
+    const deduplicated = await fs
+      .stat(filePath)
+      .then((stat) => stat.size === bytes.byteLength)
+      .catch(() => false);
+    if (!deduplicated) {
+      await writeFileAtomic(filePath, bytes);
+    }
+    return { hash, sizeBytes: bytes.byteLength, mimeType: sniffMimeType(bytes), deduplicated };
+  }
+
+  /** Resolves null when no attachment has this hash. */
+  async stat(hash: string): Promise<StoredAttachment | null> {
+    const filePath = this.pathFor(hash);
+    let handle: FileHandle;
+    try {
+      handle = await fs.open(filePath, "r");
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+        return null;
+      }
+      throw error;
+    }
+    try {
+      const { size } = await handle.stat();
+      const head = Buffer.alloc(Math.min(size, SNIFF_BYTES));
+      await handle.read(head, 0, head.length, 0);
+      return { hash, sizeBytes: size, mimeType: sniffMimeType(head) };
+    } finally {
+      await handle.close();
+    }
+  }
+
+  /** Resolves null when no attachment has this hash. */
+  async read(hash: string): Promise<AttachmentContents | null> {
+    let bytes: Buffer;
+    try {
+      bytes = await fs.readFile(this.pathFor(hash));
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+        return null;
+      }
+      throw error;
+    }
+    return { hash, sizeBytes: bytes.byteLength, mimeType: sniffMimeType(bytes), bytes };
+  }
+
+  /** Where the blob for `hash` lives, whether or not it has been stored. */
+  pathFor(hash: string): string {
+    if (!ATTACHMENT_HASH.test(hash)) {
+      throw new Error("Attachment hash must be a lowercase sha256 hex digest");
+    }
+    return path.join(this.options.directory, hash.slice(0, 2), hash);
+  }
+}
@@ -0,0 +1,94 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type { AttachmentThumbnail } from "../../src/lib/desktopApi";
+import type { AttachmentStore } from "./attachmentStore";
+
+/** The formats Chromium's decoder handles on every platform. */
+const THUMBNAIL_SOURCE_TYPES = new Set(["image/png", "image/jpeg", "image/gif", "image/bmp"]);
+
+/** The slice of Electron's `NativeImage` the thumbnailer needs. */
+export type DecodedImage = {
+  isEmpty(): boolean;
+  getSize(): { width: number; height: number };
+  resize(options: { width: number; height: number; quality: "good" }): DecodedImage;
+  toPNG(): Buffer;
+};
+
+type AttachmentThumbnailerOptions = {
+  attachments: Pick<AttachmentStore, "stat" | "read">;
+  directory: string;
+  decodeImage?: (bytes: Uint8Array) => DecodedImage;
+};
+
+function decodeWithNativeImage(bytes: Uint8Array): DecodedImage {
+  const { nativeImage } = require("electron") as typeof import("electron");
+  return nativeImage.createFromBuffer(Buffer.from(bytes));
+}
+
+function fitWithin(size: { width: number; height: number }, maxDim: number) {
+  const scale = Math.min(1, maxDim / Math.max(size.width, size.height));
+  return {
+    width: Math.max(1, Math.round(size.width * scale)),
+    height: Math.max(1, Math.round(size.height * scale)),
+  };
+}
+
+/**
+ * Downscaled PNG previews of image attachments, so the chat list never decodes
+ * a multi-megabyte screenshot to draw a 200px tile. Previews are cached on
+ * disk by attachment hash and requested size; attachments never change, so a
+ * cached preview never goes stale.
+ */
+export class AttachmentThumbnailer {
+  private readonly inFlight = new Map<string, Promise<AttachmentThumbnail | null>>();
+
+  constructor(private readonly options: AttachmentThumbnailerOptions) {}
+
+  /** Resolves null when the hash is unknown or the attachment is not a decodable image. */
+  async get(hash: string, maxDim: number): Promise<AttachmentThumbnail | null> {
+    const key = `${hash}-${maxDim}`;
+    const existing = this.inFlight.get(key);
+    if (existing) {
+      return await existing;
+    }
+    const next = this.generate(hash, maxDim).finally(() => {
+      this.inFlight.delete(key);
+    });
+    this.inFlight.set(key, next);
+    return await next;
+  }
+
+  private async generate(hash: string, maxDim: number): Promise<AttachmentThumbnail | null> {
+    const stat = await this.options.attachments.stat(hash);
+    if (!stat || !THUMBNAIL_SOURCE_TYPES.has(stat.mimeType)) {
+      return null;
+    }
+    const filePath = path.join(this.options.directory, hash.slice(0, 2), `${hash}-${maxDim}.png`);
+    const cached = await fs.readFile(filePath).catch(() => null);
+    if (cached && cached.byteLength >= 24) {
+      // Width and height sit at fixed offsets in the PNG's IHDR chunk.
+      const width = cached.readUInt32BE(16);
+      const height = cached.readUInt32BE(20);
+      return { hash, maxDim, width, height, path: filePath, bytes: cached };
+    }
+
+    const source = await this.options.attachments.read(hash);
+    if (!source) {
+      return null;
+    }
+    const image = this.decode(source.bytes);
+    if (image.isEmpty()) {
+      return null;
+    }
+    const size = fitWithin(image.getSize(), maxDim);
+    const bytes = image.resize({ ...size, quality: "good" }).toPNG();
+    await writeFileAtomic(filePath, bytes);
+    return { hash, maxDim, ...size, path: filePath, bytes };
+  }
+
+  private decode(bytes: Uint8Array): DecodedImage {
+    return (this.options.decodeImage ?? decodeWithNativeImage)(bytes);
+  }
+}
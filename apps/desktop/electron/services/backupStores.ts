@@ -0,0 +1,278 @@
+import crypto from "node:crypto";
+
+import type { BackupCredentials, BackupTarget } from "../../src/lib/desktopApi";
+
+const REQUEST_TIMEOUT_MS = 60_000;
+
+type FetchLike = (url: string, init: RequestInit) => Promise<Response>;
+
+/**
+ * Object storage a backup is written to. Keys are `/`-separated and use only
+ * URL-safe characters. Nothing is ever overwritten, which keeps backups from
+ * several devices or interrupted runs append-only.
+ */
+export interface BackupStore {
+  has(key: string): Promise<boolean>;
+  /** Writes `body` unless `key` already exists. */
+  putIfAbsent(key: string, body: Uint8Array, contentType: string): Promise<"created" | "exists">;
+}
+
+type BackupStoreOptions = {
+  fetch?: FetchLike;
+  now?: () => Date;
+};
+
+function sha256Hex(data: string | Uint8Array): string {
+  return crypto.createHash("sha256").update(data).digest("hex");
+}
+
+function hmac(key: crypto.BinaryLike, data: string): Buffer {
+  return crypto.createHmac("sha256", key).update(data).digest();
+}
+
+function joinUrl(base: string, key: string): string {
+  return `${base.replace(/\/+$/, "")}/${key}`;
+}
+
+async function request(
+  fetchImpl: FetchLike,
+  url: string,
+  init: RequestInit,
+  signal?: AbortSignal,
+): Promise<Response> {
+  const timeout = AbortSignal.timeout(REQUEST_TIMEOUT_MS);
+  return await fetchImpl(url, {
+    ...init,
+    signal: signal ? AbortSignal.any([signal, timeout]) : timeout,
+  });
+}
+
+function unexpectedStatus(method: string, key: string, response: Response): Error {
+  return new Error(`Backup ${method} ${key} failed with HTTP ${response.status}`);
+}
+
+/** AWS Signature Version 4 headers for a path-style S3 request. */
+export function signS3Request(input: {
+  method: string;
+  url: URL;
+  payloadHash: string;
+  region: string;
+  credentials: Extract<BackupCredentials, { kind: "s3" }>;
+  now: Date;
+  headers?: Record<string, string>;
+}): Record<string, string> {
+  const amzDate = input.now.toISOString().replace(/[-:]/g, "").replace(/\.\d{3}/, "");
+  const date = amzDate.slice(0, 8);
+  const headers: Record<string, string> = {
+    ...Object.fromEntries(
+      Object.entries(input.headers ?? {}).map(([name, value]) => [name.toLowerCase(), value]),
+    ),
+    host: input.url.host,
+    "x-amz-content-sha256": input.payloadHash,
+    "x-amz-date": amzDate,
+  };
+  const names = Object.keys(headers).sort();
+  const signedHeaders = names.join(";");
+  const canonicalRequest = [
+    input.method,
+    input.url.pathname,
+    input.url.searchParams.toString(),
+    ...names.map((name) => `${name}:${headers[name]?.trim()}`),
+    "",
+    signedHeaders,
+    input.payloadHash,
+  ].join("\n");
+  const scope = `${date}/${input.region}/s3/aws4_request`;
+  const stringToSign = ["AWS4-HMAC-SHA256", amzDate, scope, sha256Hex(canonicalRequest)].join(
+    "\n",
+  );
+  const signingKey = hmac(
+    hmac(hmac(hmac(`AWS4${input.credentials.secretAccessKey}`, date), input.region), "s3"),
+    "aws4_request",
+  );
+  const signature = crypto.createHmac("sha256", signingKey).update(stringToSign).digest("hex");
+  return {
+    ...headers,
+    authorization:
+      `AWS4-HMAC-SHA256 Credential=${input.credentials.accessKeyId}/${scope}, ` +
+      `SignedHeaders=${signedHeaders}, Signature=${signature}`,
+  };
+}
+
+/**
+ * S3 and S3-compatible services, addressed path-style so custom endpoints
+ * work without wildcard DNS. Conditional `If-None-Match` writes keep a
+ * concurrent writer from replacing an object; services that ignore the header
+ * are still protected by the existence check callers make first.
+ */
+class S3BackupStore implements BackupStore {
+  private readonly fetch: FetchLike;
+  private readonly now: () => Date;
+
+  constructor(
+    private readonly target: Extract<BackupTarget, { kind: "s3" }>,
+    private readonly credentials: Extract<BackupCredentials, { kind: "s3" }>,
+    options: BackupStoreOptions,
+    private readonly signal?: AbortSignal,
+  ) {
+    this.fetch = options.fetch ?? fetch;
+    this.now = options.now ?? (() => new Date());
+  }
+
+  private url(key: string): URL {
+    const prefix = this.target.prefix ? `${this.target.prefix}/` : "";
+    return new URL(joinUrl(this.target.endpoint, `${this.target.bucket}/${prefix}${key}`));
+  }
+
+  private async send(
+    method: string,
+    key: string,
+    body?: Uint8Array,
+    headers: Record<string, string> = {},
+  ): Promise<Response> {
+    const url = this.url(key);
+    const signed = signS3Request({
+      method,
+      url,
+      payloadHash: sha256Hex(body ?? ""),
+      region: this.target.region,
+      credentials: this.credentials,
+      now: this.now(),
+      headers,
+    });
+    const { host: _host, ...requestHeaders } = signed;
+    return await request(
+      this.fetch,
+      url.toString(),
+      { method, headers: requestHeaders, ...(body ? { body } : {}) },
+      this.signal,
+    );
+  }
+
+  async has(key: string): Promise<boolean> {
+    const response = await this.send("HEAD", key);
+    if (response.status === 404) {
+      return false;
+    }
+    if (!response.ok) {
+      throw unexpectedStatus("HEAD", key, response);
+    }
+    return true;
+  }
+
+  async putIfAbsent(
+    key: string,
+    body: Uint8Array,
+    contentType: string,
+  ): Promise<"created" | "exists"> {
+    const response = await this.send("PUT", key, body, {
+      "content-type": contentType,
+      "if-none-match": "*",
+    });
+    if (response.status === 412) {
+      return "exists";
+    }
+    if (!response.ok) {
+      throw unexpectedStatus("PUT", key, response);
+    }
+    return "created";
+  }
+}
+
+/** WebDAV servers such as Nextcloud; missing collections are created on demand. */
+class WebDavBackupStore implements BackupStore {
+  private readonly fetch: FetchLike;
+  private readonly authorization: string;
+  private readonly createdCollections = new Set<string>();
+
+  constructor(
+    private readonly target: Extract<BackupTarget, { kind: "webdav" }>,
+    credentials: Extract<BackupCredentials, { kind: "webdav" }>,
+    options: BackupStoreOptions,
+    private readonly signal?: AbortSignal,
+  ) {
+    this.fetch = options.fetch ?? fetch;
+    this.authorization = `Basic ${Buffer.from(
+      `${credentials.username}:${credentials.password}`,
+    ).toString("base64")}`;
+  }
+
+  private async send(method: string, key: string, init: RequestInit = {}): Promise<Response> {
+    return await request(
+      this.fetch,
+      joinUrl(this.target.url, key),
+      {
+        ...init,
+        method,
+        headers: { authorization: this.authorization, ...(init.headers ?? {}) },
+      },
+      this.signal,
+    );
+  }
+
+  private async ensureCollections(key: string): Promise<void> {
+    const segments = key.split("/").slice(0, -1);
+    for (let index = 1; index <= segments.length; index += 1) {
+      const collection = `${segments.slice(0, index).join("/")}/`;
+      if (this.createdCollections.has(collection)) {
+        continue;
+      }
+      const response = await this.send("MKCOL", collection);
+      // 405 means the collection already exists.
+      if (!response.ok && response.status !== 405) {
+        throw unexpectedStatus("MKCOL", collection, response);
+      }
+      this.createdCollections.add(collection);
+    }
+  }
+
+  async has(key: string): Promise<boolean> {
+    const response = await this.send("HEAD", key);
+    if (response.status === 404) {
+      return false;
+    }
+    if (!response.ok) {
+      throw unexpectedStatus("HEAD", key, response);
+    }
+    return true;
+  }
+
+  async putIfAbsent(
+    key: string,
+    body: Uint8Array,
+    contentType: string,
+  ): Promise<"created" | "exists"> {
+    const put = () =>
+      this.send("PUT", key, {
+        body,
+        headers: { "content-type": contentType, "if-none-match": "*" },
+      });
+    let response = await put();
+    if (response.status === 409) {
+      await this.ensureCollections(key);
+      response = await put();
+    }
+    if (response.status === 412) {
+      return "exists";
+    }
+    if (!response.ok) {
+      throw unexpectedStatus("PUT", key, response);
+    }
+    return "created";
+  }
+}
+
+export function createBackupStore(
+  target: BackupTarget,
+  credentials: BackupCredentials,
+  options: BackupStoreOptions = {},
+  signal?: AbortSignal,
+): BackupStore {
+  if (target.kind === "s3" && credentials.kind === "s3") {
+    return new S3BackupStore(target, credentials, options, signal);
+  }
+  if (target.kind === "webdav" && credentials.kind === "webdav") {
+    return new WebDavBackupStore(target, credentials, options, signal);
+  }
+  throw new Error(`Saved credentials are for ${credentials.kind}, not ${target.kind}.`);
+}
@@ -0,0 +1,167 @@
+import { type ConnectivityStatus, NETWORK_UNAVAILABLE_ERROR_CODE } from "../../src/lib/desktopApi";
+
+const DEFAULT_CONNECTIVITY_INTERVAL_MS = 60_000;
+// Recovery should show up quickly, so checks run more often while degraded.
+const DEFAULT_DEGRADED_INTERVAL_MS = 15_000;
+
+export const DEFAULT_CONNECTIVITY_ENDPOINTS = [
+  "https://api.openai.com",
+  "https://api.anthropic.com",
+  "https://generativelanguage.googleapis.com",
+];
+
+type ConnectivityMonitorOptions = {
+  /** Whether the OS reports any network interface as up. */
+  isOnline: () => boolean;
+  /** Resolves true once `url` answers with any HTTP status, through the configured proxy. */
+  probe: (url: string) => Promise<boolean>;
+  endpoints?: string[];
+  intervalMs?: number;
+  degradedIntervalMs?: number;
+  now?: () => number;
+  /** Called only when the state or the set of unreachable hosts changes. */
+  onChange?: (status: ConnectivityStatus) => void;
+};
+
+function statusKey(status: ConnectivityStatus): string {
+  return `${status.state}|${status.unreachableHosts.join(",")}`;
+}
+
+/**
+ * Tracks whether provider endpoints are reachable so failures can be told
+ * apart: "offline" means no network or no endpoint answered, "degraded" means
+ * only some of them did.
+ */
+export class ConnectivityMonitor {
+  private readonly endpoints: string[];
+  private readonly intervalMs: number;
+  private readonly degradedIntervalMs: number;
+  private readonly now: () => number;
+  private timer: ReturnType<typeof setTimeout> | null = null;
+  private started = false;
+  private inFlight: Promise<ConnectivityStatus> | null = null;
+  private status: ConnectivityStatus = {
+    state: "online",
+    unreachableHosts: [],
+    checkedAt: null,
+    message: "Connectivity has not been checked yet.",
+  };
+
+  constructor(private readonly options: ConnectivityMonitorOptions) {
+    this.endpoints = options.endpoints ?? DEFAULT_CONNECTIVITY_ENDPOINTS;
+    this.intervalMs = Math.max(1_000, options.intervalMs ?? DEFAULT_CONNECTIVITY_INTERVAL_MS);
+    this.degradedIntervalMs = Math.max(
+      1_000,
+      options.degradedIntervalMs ?? DEFAULT_DEGRADED_INTERVAL_MS,
+    );
+    this.now = options.now ?? Date.now;
+  }
+
+  start(): void {
+    if (this.started) {
+      return;
+    }
+    this.started = true;
+    void this.check();
+  }
+
+  /** Last known status; does not probe. */
+  getStatus(): ConnectivityStatus {
+    return this.status;
+  }
+
+  /** Probes now; concurrent callers share one check. */
+  async check(): Promise<ConnectivityStatus> {
+    if (this.inFlight) {
+      return await this.inFlight;
+    }
+    this.inFlight = this.runCheck();
+    try {
+      return await this.inFlight;
+    } finally {
+      this.inFlight = null;
+      this.scheduleNext();
+    }
+  }
+
+  dispose(): void {
+    this.started = false;
+    if (this.timer) {
+      clearTimeout(this.timer);
+      this.timer = null;
+    }
+  }
+
+  private async runCheck(): Promise<ConnectivityStatus> {
+    const next = await this.measure();
+    const changed = statusKey(next) !== statusKey(this.status);
+    this.status = next;
+    if (changed) {
+      this.options.onChange?.(next);
+    }
+    return next;
+  }
+
+  private async measure(): Promise<ConnectivityStatus> {
+    const checkedAt = new Date(this.now()).toISOString();
+    if (!this.options.isOnline()) {
+      return {
+        state: "offline",
+        unreachableHosts: [],
+        checkedAt,
+        message: "This computer is offline.",
+      };
+    }
+    const results = await Promise.all(
+      this.endpoints.map(async (url) => {
+        const reachable = await this.options.probe(url).catch(() => false);
+        return { host: new URL(url).host, reachable };
+      }),
+    );
+    const unreachableHosts = results.filter((r) => !r.reachable).map((r) => r.host);
+    if (unreachableHosts.length === 0) {
+      return { state: "online", unreachableHosts, checkedAt, message: "Online." };
+    }
+    if (unreachableHosts.length === results.length) {
+      return {
+        state: "offline",
+        unreachableHosts,
+        checkedAt,
+        message: "No provider endpoint could be reached.",
+      };
+    }
+    return {
+      state: "degraded",
+      unreachableHosts,
+      checkedAt,
+      message: `Could not reach ${unreachableHosts.join(", ")}.`,
+    };
+  }
+
+  private scheduleNext(): void {
+    if (!this.started) {
+      return;
+    }
+    if (this.timer) {
+      clearTimeout(this.timer);
+    }
+    const delay = this.status.state === "online" ? this.intervalMs : this.degradedIntervalMs;
+    this.timer = setTimeout(() => {
+      this.timer = null;
+      void this.check();
+    }, delay);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+}
+
+/**
+ * Prefixes `error` with the connectivity code when the network is the likely
+ * cause, so the renderer can show "you're offline" instead of a server fault.
+ */
+export function withConnectivityContext(error: unknown, status: ConnectivityStatus): unknown {
+  if (status.state === "online") {
+    return error;
+  }
+  const message = error instanceof Error ? error.message : String(error);
+  return new Error(`${NETWORK_UNAVAILABLE_ERROR_CODE}: ${status.message} ${message}`);
+}
@@ -0,0 +1,270 @@
+import { type ChildProcessByStdio, spawn } from "node:child_process";
+import fs from "node:fs/promises";
+import path from "node:path";
+import type { Readable } from "node:stream";
+
+import { isValidContainerImage } from "../../src/app/workspaceContainer";
+import type { WorkspaceContainerLaunch } from "../../src/app/types";
+import { allocateLoopbackPort } from "./loopbackPorts";
+
+const DEFAULT_DOCKER_COMMAND = "docker";
+const DEFAULT_CONTAINER_SERVER_COMMAND = "cowork-server";
+const CONTAINER_SERVER_COMMAND_ENV = "COWORK_DESKTOP_CONTAINER_SERVER_COMMAND";
+/** Fixed port the sidecar binds inside the container; it is published on a free host port. */
+export const CONTAINER_SERVER_PORT = 7337;
+const CONTAINER_NAME_PREFIX = "cowork-workspace-";
+const CONTAINER_LABEL = "dev.cowork.workspace";
+const DEVCONTAINER_CONFIG_PATHS = [
+  path.join(".devcontainer", "devcontainer.json"),
+  ".devcontainer.json",
+] as const;
+const REMOVE_TIMEOUT_MS = 15_000;
+const ENV_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+export type ContainerServerSession = ChildProcessByStdio<null, Readable, Readable>;
+
+/** What `DockerWorkspaces.launchServer` runs: the image and where the workspace is mounted. */
+export type ResolvedContainerLaunch = {
+  image: string;
+  /** Absolute path of the workspace inside the container. */
+  workspaceFolder: string;
+  /** The devcontainer.json the image or folder came from, if any. */
+  configPath: string | null;
+};
+
+type DockerWorkspacesOptions = {
+  dockerCommand?: string;
+  /** The sidecar on the image's PATH; `COWORK_DESKTOP_CONTAINER_SERVER_COMMAND` overrides it. */
+  serverCommand?: string;
+  spawn?: typeof spawn;
+  allocateHostPort?: () => Promise<number>;
+};
+
+/** Strips the comments and trailing commas devcontainer.json allows, leaving strings alone. */
+export function stripJsonComments(text: string): string {
+  let output = "";
+  let inString = false;
+  for (let index = 0; index < text.length; index += 1) {
+    const char = text[index];
+    const next = text[index + 1];
+    if (inString) {
+      output += char;
+      if (char === "\\") {
+        output += next ?? "";
+        index += 1;
+      } else if (char === '"') {
+        inString = false;
+      }
+      continue;
+    }
+    if (char === '"') {
+      inString = true;
+      output += char;
+    } else if (char === "/" && next === "/") {
+      while (index < text.length && text[index] !== "\n") index += 1;
+      output += "\n";
+    } else if (char === "/" && next === "*") {
+      const end = text.indexOf("*/", index + 2);
+      index = end === -1 ? text.length : end + 1;
+    } else {
+      output += char;
+    }
+  }
+  return output.replace(/,(\s*[}\]])/g, "$1");
+}
+
+/**
+ * Works out the image and mount point for a workspace: the configured image
+ * wins, then the `image` of its devcontainer.json. Dev containers that build
+ * from a Dockerfile or compose file need an image configured explicitly.
+ */
+export async function resolveContainerLaunch(
+  workspacePath: string,
+  launch: WorkspaceContainerLaunch,
+): Promise<ResolvedContainerLaunch> {
+  const defaultFolder = `/workspaces/${path.basename(workspacePath)}`;
+  let config: Record<string, unknown> | null = null;
+  let configPath: string | null = null;
+  for (const relativePath of DEVCONTAINER_CONFIG_PATHS) {
+    const candidate = path.join(workspacePath, relativePath);
+    let text: string;
+    try {
+      text = await fs.readFile(candidate, "utf8");
+    } catch {
+      continue;
+    }
+    try {
+      const parsed: unknown = JSON.parse(stripJsonComments(text));
+      config = parsed && typeof parsed === "object" ? (parsed as Record<string, unknown>) : {};
+    } catch (error) {
+      throw new Error(
+        `Could not parse ${relativePath}: ${error instanceof Error ? error.message : error}`,
+      );
+    }
+    configPath = candidate;
+    break;
+  }
+
+  const configuredImage = typeof config?.image === "string" ? config.image.trim() : "";
+  const image = launch.image ?? configuredImage;
+  if (!image) {
+    throw new Error(
+      config
+        ? "The devcontainer.json builds its own image; set a container image for this workspace."
+        : "No container image is set for this workspace and it has no devcontainer.json.",
+    );
+  }
+  if (!isValidContainerImage(image)) {
+    throw new Error(`Invalid container image: ${image}`);
+  }
+  const folder = typeof config?.workspaceFolder === "string" ? config.workspaceFolder : "";
+  return {
+    image,
+    workspaceFolder: folder.startsWith("/") ? folder : defaultFolder,
+    configPath,
+  };
+}
+
+function slashed(value: string): string {
+  return value.replace(/\\/g, "/").replace(/\/+$/, "");
+}
+
+/**
+ * Rewrites an `AGENT_PATH_POLICY` value from host paths to the paths the
+ * workspace has inside the container. Only the workspace folder is mounted, so
+ * deny entries outside it are dropped (nothing there is reachable anyway) and
+ * an allow entry outside it is an error: the sidecar could not honor it.
+ */
+export function containerPathPolicy(
+  pathPolicy: string,
+  workspacePath: string,
+  workspaceFolder: string,
+): string {
+  const parsed: unknown = JSON.parse(pathPolicy);
+  const record = parsed && typeof parsed === "object" ? (parsed as Record<string, unknown>) : {};
+  const root = slashed(workspacePath);
+  const toContainer = (entry: string): string | null => {
+    const hostPath = slashed(entry);
+    if (hostPath === root) return workspaceFolder;
+    if (!hostPath.startsWith(`${root}/`)) return null;
+    return path.posix.join(workspaceFolder, hostPath.slice(root.length + 1));
+  };
+  const entries = (value: unknown) =>
+    Array.isArray(value) ? value.filter((entry): entry is string => typeof entry === "string") : [];
+
+  const allow = entries(record.allow).map((entry) => {
+    const mapped = toContainer(entry);
+    if (mapped === null) {
+      throw new Error(
+        `The path policy allows ${entry}, which is not mounted in the container.`,
+      );
+    }
+    return mapped;
+  });
+  const deny = entries(record.deny)
+    .map(toContainer)
+    .filter((entry): entry is string => entry !== null);
+  return JSON.stringify({ allow, deny });
+}
+
+/** Docker names allow `[a-zA-Z0-9][a-zA-Z0-9_.-]`; safe ids already fit. */
+export function containerNameForServer(serverId: string): string {
+  return `${CONTAINER_NAME_PREFIX}${serverId.replace(/[^A-Za-z0-9_.-]/g, "-")}`;
+}
+
+/**
+ * Runs workspace servers in Docker containers through the `docker` CLI. The
+ * container is started attached, so its output reaches the server manager
+ * like a local sidecar's and a terminate signal is proxied to it; each server
+ * gets a fixed container name so a stop, kill, or crash left over from a
+ * previous run can always be cleaned up by name.
+ */
+export class DockerWorkspaces {
+  private readonly dockerCommand: string;
+  private readonly serverCommand: string;
+  private readonly spawnImpl: typeof spawn;
+  readonly allocateHostPort: () => Promise<number>;
+
+  constructor(options: DockerWorkspacesOptions = {}) {
+    this.dockerCommand = options.dockerCommand ?? DEFAULT_DOCKER_COMMAND;
+    this.serverCommand =
+      options.serverCommand ??
+      (process.env[CONTAINER_SERVER_COMMAND_ENV]?.trim() || DEFAULT_CONTAINER_SERVER_COMMAND);
+    this.spawnImpl = options.spawn ?? spawn;
+    this.allocateHostPort = options.allocateHostPort ?? allocateLoopbackPort;
+  }
+
+  /**
+   * Starts the sidecar in `containerName` with the workspace bind-mounted and
+   * the server port published on `hostPort`. Environment values are passed by
+   * name, so they never appear on the docker command line.
+   */
+  launchServer(opts: {
+    containerName: string;
+    serverId: string;
+    workspacePath: string;
+    launch: ResolvedContainerLaunch;
+    hostPort: number;
+    args: readonly string[];
+    env: Readonly<Record<string, string>>;
+  }): ContainerServerSession {
+    const envFlags: string[] = [];
+    for (const name of Object.keys(opts.env)) {
+      if (!ENV_NAME_PATTERN.test(name)) {
+        throw new Error(`Invalid environment variable name: ${name}`);
+      }
+      envFlags.push("-e", name);
+    }
+    const args = [
+      "run",
+      "--rm",
+      "--init",
+      "--name",
+      opts.containerName,
+      "--label",
+      `${CONTAINER_LABEL}=${opts.serverId}`,
+      "-v",
+      `${opts.workspacePath}:${opts.launch.workspaceFolder}`,
+      "-w",
+      opts.launch.workspaceFolder,
+      "-p",
+      `127.0.0.1:${opts.hostPort}:${CONTAINER_SERVER_PORT}`,
+      ...envFlags,
+      "--",
+      opts.launch.image,
+      this.serverCommand,
+      ...opts.args,
+    ];
+    return this.spawnImpl(this.dockerCommand, args, {
+      stdio: ["ignore", "pipe", "pipe"],
+      env: { ...process.env, ...opts.env },
+      windowsHide: true,
+    });
+  }
+
+  /** Force-removes the container if it still exists; never throws. */
+  async removeContainer(containerName: string): Promise<void> {
+    await new Promise<void>((resolve) => {
+      let child: ReturnType<typeof spawn>;
+      try {
+        child = this.spawnImpl(this.dockerCommand, ["rm", "-f", containerName], {
+          stdio: "ignore",
+          windowsHide: true,
+        });
+      } catch {
+        resolve();
+        return;
+      }
+      const timer = setTimeout(() => {
+        child.kill();
+        resolve();
+      }, REMOVE_TIMEOUT_MS);
+      const done = () => {
+        clearTimeout(timer);
+        resolve();
+      };
+      child.once("error", done);
+      child.once("exit", done);
+    });
+  }
+}
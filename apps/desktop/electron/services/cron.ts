@@ -0,0 +1,145 @@
+type CronField = {
+  min: number;
+  max: number;
+  names?: string[];
+};
+
+const MINUTE: CronField = { min: 0, max: 59 };
+const HOUR: CronField = { min: 0, max: 23 };
+const DAY_OF_MONTH: CronField = { min: 1, max: 31 };
+const MONTH: CronField = {
+  min: 1,
+  max: 12,
+  names: ["jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec"],
+};
+// 7 is accepted as a second spelling of Sunday.
+const DAY_OF_WEEK: CronField = {
+  min: 0,
+  max: 7,
+  names: ["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+};
+
+const MACROS: Record<string, string> = {
+  "@yearly": "0 0 1 1 *",
+  "@annually": "0 0 1 1 *",
+  "@monthly": "0 0 1 * *",
+  "@weekly": "0 0 * * 0",
+  "@daily": "0 0 * * *",
+  "@midnight": "0 0 * * *",
+  "@hourly": "0 * * * *",
+};
+
+/** Dates further out than this are treated as never matching (e.g. `0 0 30 2 *`). */
+const MAX_LOOKAHEAD_MS = 5 * 366 * 24 * 60 * 60_000;
+
+export type CronSchedule = {
+  minutes: ReadonlySet<number>;
+  hours: ReadonlySet<number>;
+  daysOfMonth: ReadonlySet<number>;
+  months: ReadonlySet<number>;
+  daysOfWeek: ReadonlySet<number>;
+  /** Cron matches either day field when both are restricted, and both otherwise. */
+  dayOfMonthRestricted: boolean;
+  dayOfWeekRestricted: boolean;
+};
+
+function parseValue(raw: string, field: CronField, expression: string): number {
+  const named = field.names?.indexOf(raw.toLowerCase()) ?? -1;
+  const value = named >= 0 ? named + field.min : /^\d+$/.test(raw) ? Number(raw) : Number.NaN;
+  if (!Number.isInteger(value) || value < field.min || value > field.max) {
+    throw new Error(`Invalid cron expression "${expression}": ${raw} is out of range`);
+  }
+  return value;
+}
+
+function parseField(raw: string, field: CronField, expression: string): Set<number> {
+  const values = new Set<number>();
+  for (const part of raw.split(",")) {
+    const [range = "", stepText] = part.split("/");
+    const step = stepText === undefined ? 1 : Number(stepText);
+    if (!Number.isInteger(step) || step < 1) {
+      throw new Error(`Invalid cron expression "${expression}": bad step in ${part}`);
+    }
+    let start: number;
+    let end: number;
+    if (range === "*") {
+      start = field.min;
+      end = field.max;
+    } else {
+      const [from = "", to] = range.split("-");
+      start = parseValue(from, field, expression);
+      if (to !== undefined) {
+        end = parseValue(to, field, expression);
+      } else {
+        // `5/15` runs from 5 to the end of the range, like `5-59/15`.
+        end = stepText === undefined ? start : field.max;
+      }
+    }
+    if (start > end) {
+      throw new Error(`Invalid cron expression "${expression}": ${range} is backwards`);
+    }
+    for (let value = start; value <= end; value += step) {
+      values.add(value);
+    }
+  }
+  return values;
+}
+
+/**
+ * Parses a five-field cron expression (minute, hour, day of month, month, day
+ * of week) or one of the `@daily`-style macros. Fields accept `*`, lists,
+ * ranges, steps, and month or weekday names.
+ */
+export function parseCronExpression(expression: string): CronSchedule {
+  const normalized = MACROS[expression.trim().toLowerCase()] ?? expression.trim();
+  const fields = normalized.split(/\s+/);
+  if (fields.length !== 5) {
+    throw new Error(`Invalid cron expression "${expression}": expected 5 fields`);
+  }
+  const [minute = "", hour = "", dayOfMonth = "", month = "", dayOfWeek = ""] = fields;
+  const daysOfWeek = parseField(dayOfWeek, DAY_OF_WEEK, expression);
+  if (daysOfWeek.delete(7)) {
+    daysOfWeek.add(0);
+  }
+  return {
+    minutes: parseField(minute, MINUTE, expression),
+    hours: parseField(hour, HOUR, expression),
+    daysOfMonth: parseField(dayOfMonth, DAY_OF_MONTH, expression),
+    months: parseField(month, MONTH, expression),
+    daysOfWeek,
+    dayOfMonthRestricted: dayOfMonth !== "*",
+    dayOfWeekRestricted: dayOfWeek !== "*",
+  };
+}
+
+function dayMatches(schedule: CronSchedule, date: Date): boolean {
+  const dayOfMonth = schedule.daysOfMonth.has(date.getDate());
+  const dayOfWeek = schedule.daysOfWeek.has(date.getDay());
+  return schedule.dayOfMonthRestricted && schedule.dayOfWeekRestricted
+    ? dayOfMonth || dayOfWeek
+    : dayOfMonth && dayOfWeek;
+}
+
+/** The first matching minute strictly after `after`, in local time; null if there is none. */
+export function nextCronOccurrence(schedule: CronSchedule, after: Date): Date | null {
+  const limit = after.getTime() + MAX_LOOKAHEAD_MS;
+  const candidate = new Date(after.getTime());
+  candidate.setSeconds(0, 0);
+  candidate.setMinutes(candidate.getMinutes() + 1);
+  while (candidate.getTime() <= limit) {
+    if (!schedule.months.has(candidate.getMonth() + 1)) {
+      candidate.setMonth(candidate.getMonth() + 1, 1);
+      candidate.setHours(0, 0);
+    } else if (!dayMatches(schedule, candidate)) {
+      candidate.setDate(candidate.getDate() + 1);
+      candidate.setHours(0, 0);
+    } else if (!schedule.hours.has(candidate.getHours())) {
+      candidate.setHours(candidate.getHours() + 1, 0);
+    } else if (!schedule.minutes.has(candidate.getMinutes())) {
+      candidate.setMinutes(candidate.getMinutes() + 1);
+    } else {
+      return candidate;
+    }
+  }
+  return null;
+}
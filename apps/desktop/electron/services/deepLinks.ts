@@ -0,0 +1,178 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import type { DesktopDeepLinkNavigation, OpenedTranscriptFile } from "../../src/lib/desktopApi";
+import { assertSafeId } from "./validation";
+
+export const DEEP_LINK_SCHEME = "cowork";
+const DEEP_LINK_PROTOCOL = `${DEEP_LINK_SCHEME}:`;
+
+/** What a `cowork://` URL can ask for; opening files never comes from a link. */
+type ParsedDeepLink = Exclude<DesktopDeepLinkNavigation, { kind: "transcriptFile" }>;
+
+type DeepLinkServiceOptions = {
+  loadState: () => Promise<PersistedState>;
+  /** Starts (or reuses) the workspace sidecar so navigation lands on a live server. */
+  startWorkspaceServer: (workspace: WorkspaceRecord, state: PersistedState) => Promise<unknown>;
+  /** Asks the user before a link adds a folder that is not yet a workspace. */
+  confirmOpenPath: (directory: string) => Promise<boolean>;
+  approveWorkspacePath: (directory: string) => Promise<string>;
+  /** Loads an exported thread into the read-only viewer. */
+  openTranscriptFile?: (filePath: string) => Promise<OpenedTranscriptFile>;
+  /** Delivers navigation to the main window; returns false when no window is listening yet. */
+  deliver: (navigation: DesktopDeepLinkNavigation) => Promise<boolean> | boolean;
+};
+
+/** Returns the first `cowork://` URL in a process argv, as passed on Windows and Linux. */
+export function findDeepLinkArg(argv: readonly string[]): string | null {
+  return argv.find((arg) => arg.toLowerCase().startsWith(`${DEEP_LINK_PROTOCOL}//`)) ?? null;
+}
+
+/**
+ * Parses `cowork://workspace/<id>`, `cowork://workspace/<id>/thread/<id>`,
+ * and `cowork://open?path=<absolute dir>`. Anything else, including ids that
+ * are not safe record ids, is rejected.
+ */
+export function parseDeepLink(rawUrl: string): ParsedDeepLink {
+  let parsed: URL;
+  try {
+    parsed = new URL(rawUrl);
+  } catch {
+    throw new Error("Deep link is not a valid URL.");
+  }
+  if (parsed.protocol !== DEEP_LINK_PROTOCOL) {
+    throw new Error(`Deep link must use the ${DEEP_LINK_SCHEME}:// scheme.`);
+  }
+
+  const segments = [parsed.hostname, ...parsed.pathname.split("/")]
+    .filter((segment) => segment.length > 0)
+    .map((segment) => decodeURIComponent(segment));
+  const [action, workspaceId, child, threadId, ...rest] = segments;
+
+  if (action === "open" && segments.length === 1) {
+    const target = parsed.searchParams.get("path")?.trim();
+    if (!target || !path.isAbsolute(target)) {
+      throw new Error("cowork://open requires an absolute path parameter.");
+    }
+    return { kind: "openPath", path: path.resolve(target) };
+  }
+  if (action === "workspace" && workspaceId && rest.length === 0) {
+    assertSafeId(workspaceId, "workspaceId");
+    if (child === undefined) {
+      return { kind: "workspace", workspaceId };
+    }
+    if (child === "thread" && threadId) {
+      assertSafeId(threadId, "threadId");
+      return { kind: "thread", workspaceId, threadId };
+    }
+  }
+  throw new Error(`Unsupported deep link: ${parsed.hostname || "(empty)"}`);
+}
+
+async function resolveDirectory(target: string): Promise<string> {
+  let resolved: string;
+  try {
+    resolved = await fs.realpath(target);
+  } catch {
+    throw new Error(`Deep link path does not exist: ${target}`);
+  }
+  if (!(await fs.stat(resolved)).isDirectory()) {
+    throw new Error(`Deep link path is not a directory: ${target}`);
+  }
+  return resolved;
+}
+
+/**
+ * Validates deep links against persisted state, starts the sidecar the target
+ * needs, and hands the renderer a navigation. Links that arrive before the main
+ * window is listening are queued and drained when it subscribes.
+ */
+export class DeepLinkService {
+  private readonly pending: DesktopDeepLinkNavigation[] = [];
+
+  constructor(private readonly options: DeepLinkServiceOptions) {}
+
+  async handle(rawUrl: string): Promise<DesktopDeepLinkNavigation | null> {
+    const navigation = await this.resolve(parseDeepLink(rawUrl));
+    if (!navigation) {
+      return null;
+    }
+    await this.deliver(navigation);
+    return navigation;
+  }
+
+  /**
+   * Opens an exported thread handed over by the OS ("open with", a file
+   * argument, or macOS `open-file`) in the read-only viewer.
+   */
+  async openTranscriptFile(filePath: string): Promise<DesktopDeepLinkNavigation> {
+    if (!this.options.openTranscriptFile) {
+      throw new Error("Opening exported threads is unavailable in this build.");
+    }
+    const opened = await this.options.openTranscriptFile(filePath);
+    const navigation: DesktopDeepLinkNavigation = {
+      kind: "transcriptFile",
+      threadId: opened.threadId,
+      title: opened.title,
+      sourcePath: opened.sourcePath,
+    };
+    await this.deliver(navigation);
+    return navigation;
+  }
+
+  drainPending(): DesktopDeepLinkNavigation[] {
+    return this.pending.splice(0, this.pending.length);
+  }
+
+  private async deliver(navigation: DesktopDeepLinkNavigation): Promise<void> {
+    if (!(await this.options.deliver(navigation))) {
+      this.pending.push(navigation);
+    }
+  }
+
+  private async resolve(link: ParsedDeepLink): Promise<DesktopDeepLinkNavigation | null> {
+    const state = await this.options.loadState();
+
+    if (link.kind === "openPath") {
+      const directory = await resolveDirectory(link.path);
+      const existing = await this.findWorkspaceByPath(state.workspaces, directory);
+      if (existing) {
+        await this.options.startWorkspaceServer(existing, state);
+        return { kind: "workspace", workspaceId: existing.id };
+      }
+      if (!(await this.options.confirmOpenPath(directory))) {
+        return null;
+      }
+      return { kind: "openPath", path: await this.options.approveWorkspacePath(directory) };
+    }
+
+    const workspace = state.workspaces.find((candidate) => candidate.id === link.workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${link.workspaceId}`);
+    }
+    if (link.kind === "thread") {
+      const thread = state.threads.find((candidate) => candidate.id === link.threadId);
+      if (!thread || thread.workspaceId !== workspace.id) {
+        throw new Error(`Unknown thread in workspace ${workspace.id}: ${link.threadId}`);
+      }
+    }
+    await this.options.startWorkspaceServer(workspace, state);
+    return link.kind === "thread"
+      ? { kind: "thread", workspaceId: workspace.id, threadId: link.threadId }
+      : { kind: "workspace", workspaceId: workspace.id };
+  }
+
+  private async findWorkspaceByPath(
+    workspaces: WorkspaceRecord[],
+    directory: string,
+  ): Promise<WorkspaceRecord | null> {
+    for (const workspace of workspaces) {
+      const workspacePath = await fs.realpath(workspace.path).catch(() => workspace.path);
+      if (workspacePath === directory) {
+        return workspace;
+      }
+    }
+    return null;
+  }
+}
@@ -57,6 +57,10 @@ export type DesktopSmokeJsonRpcConnection = {
 export type ConnectDesktopSmokeJsonRpcOptions = DesktopSmokeTimerFns & {
   url: string;
   clientVersion: string;
+  /** Reported in `initialize`; defaults to the smoke client's name. */
+  clientName?: string;
+  /** Sent as a bearer token; servers started by the app require it. */
+  authToken?: string | null;
   createWebSocket?: (url: string, protocols?: string | string[]) => DesktopSmokeSocket;
 };
 
@@ -85,7 +89,12 @@ export async function connectDesktopSmokeJsonRpc(
 ): Promise<DesktopSmokeJsonRpcConnection> {
   const createWebSocket =
     options.createWebSocket ??
-    ((url: string) => new WebSocket(url, "cowork.jsonrpc.v1") as unknown as DesktopSmokeSocket);
+    ((url: string) =>
+      new WebSocket(
+        url,
+        "cowork.jsonrpc.v1",
+        options.authToken ? { headers: { authorization: `Bearer ${options.authToken}` } } : {},
+      ) as unknown as DesktopSmokeSocket);
   const setTimeoutFn = options.setTimeoutFn ?? setTimeout;
   const clearTimeoutFn = options.clearTimeoutFn ?? clearTimeout;
 
@@ -219,7 +228,7 @@ export async function connectDesktopSmokeJsonRpc(
   try {
     const initializeResponse = await sendRequest("initialize", {
       clientInfo: {
-        name: DESKTOP_SMOKE_CLIENT_NAME,
+        name: options.clientName ?? DESKTOP_SMOKE_CLIENT_NAME,
         version: options.clientVersion,
       },
     });
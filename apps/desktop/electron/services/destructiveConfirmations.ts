@@ -0,0 +1,142 @@
+import crypto from "node:crypto";
+
+import type {
+  DestructiveCommand,
+  DestructiveCommandImpact,
+  DestructiveConfirmationChallenge,
+} from "../../src/lib/desktopApi";
+
+const DEFAULT_CHALLENGE_TTL_MS = 2 * 60_000;
+
+type PendingToken<TCommand extends string> = {
+  command: TCommand;
+  target: string;
+  ownerId: number;
+  expiresAtMs: number;
+};
+
+export type ConfirmationTokensOptions = {
+  now?: () => number;
+  ttlMs?: number;
+  createToken?: () => string;
+};
+
+export type ConfirmationTokenCheck = "valid" | "invalid" | "expired";
+
+function formatBytes(bytes: number): string {
+  if (bytes < 1024) {
+    return `${bytes} B`;
+  }
+  if (bytes < 1024 * 1024) {
+    return `${(bytes / 1024).toFixed(1)} KB`;
+  }
+  return `${(bytes / (1024 * 1024)).toFixed(1)} MB`;
+}
+
+export function describeDestructiveImpact(
+  command: DestructiveCommand,
+  impact: DestructiveCommandImpact,
+): string {
+  const transcripts =
+    `${impact.transcriptCount} transcript file(s) ` +
+    `(${formatBytes(impact.transcriptBytes)}) will be deleted.`;
+  switch (command) {
+    case "deleteTranscript":
+      return `The thread history will be deleted; ${transcripts}`;
+    case "purgeThreads":
+      return `${impact.threadCount} thread(s) will be removed; ${transcripts}`;
+    case "deleteWorkspace":
+      return (
+        `The workspace and its ${impact.threadCount} thread(s) will be removed; ` + transcripts
+      );
+  }
+}
+
+/**
+ * Single-use tokens bound to a command, its exact target, and the window that
+ * asked for them, so a renderer cannot reuse one for something else.
+ */
+export class ConfirmationTokens<TCommand extends string> {
+  private readonly pending = new Map<string, PendingToken<TCommand>>();
+  private readonly now: () => number;
+  private readonly ttlMs: number;
+  private readonly createToken: () => string;
+
+  constructor(options: ConfirmationTokensOptions = {}) {
+    this.now = options.now ?? Date.now;
+    this.ttlMs = options.ttlMs ?? DEFAULT_CHALLENGE_TTL_MS;
+    this.createToken = options.createToken ?? (() => crypto.randomBytes(24).toString("base64url"));
+  }
+
+  issue(command: TCommand, target: string, ownerId: number): { token: string; expiresAt: Date } {
+    this.pruneExpired();
+    const token = this.createToken();
+    const expiresAtMs = this.now() + this.ttlMs;
+    this.pending.set(token, { command, target, ownerId, expiresAtMs });
+    return { token, expiresAt: new Date(expiresAtMs) };
+  }
+
+  /** Consumes `token` whether or not it matches, so a wrong guess burns it. */
+  consume(
+    command: TCommand,
+    target: string,
+    ownerId: number,
+    token: string,
+  ): ConfirmationTokenCheck {
+    const pending = this.pending.get(token);
+    this.pending.delete(token);
+    if (
+      !pending ||
+      pending.command !== command ||
+      pending.target !== target ||
+      pending.ownerId !== ownerId
+    ) {
+      return "invalid";
+    }
+    return pending.expiresAtMs <= this.now() ? "expired" : "valid";
+  }
+
+  private pruneExpired(): void {
+    const now = this.now();
+    for (const [token, pending] of this.pending) {
+      if (pending.expiresAtMs <= now) {
+        this.pending.delete(token);
+      }
+    }
+  }
+}
+
+/** Confirmation challenges for destructive IPC commands, with their impact spelled out. */
+export class DestructiveConfirmations {
+  private readonly tokens: ConfirmationTokens<DestructiveCommand>;
+
+  constructor(options: ConfirmationTokensOptions = {}) {
+    this.tokens = new ConfirmationTokens(options);
+  }
+
+  issue(
+    command: DestructiveCommand,
+    target: string,
+    ownerId: number,
+    impact: DestructiveCommandImpact,
+  ): DestructiveConfirmationChallenge {
+    const { token, expiresAt } = this.tokens.issue(command, target, ownerId);
+    return {
+      command,
+      token,
+      summary: describeDestructiveImpact(command, impact),
+      impact,
+      expiresAt: expiresAt.toISOString(),
+    };
+  }
+
+  /** Consumes `token`; throws unless it was issued for this command, target, and window. */
+  consume(command: DestructiveCommand, target: string, ownerId: number, token: string): void {
+    switch (this.tokens.consume(command, target, ownerId, token)) {
+      case "invalid":
+        throw new Error(`Invalid confirmation token for ${command}.`);
+      case "expired":
+        throw new Error(`Confirmation for ${command} expired; request a new one.`);
+    }
+  }
+}
@@ -13,11 +13,13 @@ import { getRecentCrashReportIds } from "../../../../src/telemetry/crashReportin
 import { normalizePrivacyTelemetrySettings, type PersistedState } from "../../src/app/types";
 import type {
   CreateDiagnosticsBundleOutput,
+  OperationProgress,
   UploadDiagnosticsBundleOutput,
 } from "../../src/lib/desktopApi";
 import { getLocalLogPath, getLogsDir, logError, logInfo, logWarn, tailLog } from "./localLogs";
 import type { PersistenceService } from "./persistence";
 import type { ServerManagerDiagnostics } from "./serverManager";
+import type { StorageGuard } from "./storageGuard";
 import type { DesktopUpdaterService } from "./updater";
 import {
   readWindowsSandboxReadiness,
@@ -40,6 +42,12 @@ type DiagnosticsServiceOptions = {
   platform?: NodeJS.Platform;
   arch?: string;
   serverDiagnostics?: () => ServerManagerDiagnostics;
+  storageGuard?: StorageGuard;
+};
+
+type DiagnosticsTaskOptions = {
+  signal?: AbortSignal;
+  onProgress?: (progress: OperationProgress) => void;
 };
 
 type DiagnosticsBundle = {
@@ -189,6 +197,7 @@ export class DiagnosticsService {
   private readonly platform: NodeJS.Platform;
   private readonly arch: string;
   private readonly serverDiagnostics?: () => ServerManagerDiagnostics;
+  private readonly storageGuard?: StorageGuard;
 
   constructor(options: DiagnosticsServiceOptions) {
     this.persistence = options.persistence;
@@ -201,13 +210,16 @@ export class DiagnosticsService {
     this.platform = options.platform ?? process.platform;
     this.arch = options.arch ?? process.arch;
     this.serverDiagnostics = options.serverDiagnostics;
+    this.storageGuard = options.storageGuard;
   }
 
   getDiagnosticsDir(): string {
     return path.join(app.getPath("userData"), DIAGNOSTICS_DIR_NAME);
   }
 
-  async createBundle(): Promise<CreateDiagnosticsBundleOutput> {
+  async createBundle(options: DiagnosticsTaskOptions = {}): Promise<CreateDiagnosticsBundleOutput> {
+    const { signal, onProgress } = options;
+    onProgress?.({ completed: 0, total: 3, message: "Reading app state" });
     const state = await this.persistence.loadState();
     const createdAt = this.now().toISOString();
     const settings = normalizePrivacyTelemetrySettings(state.privacyTelemetrySettings);
@@ -215,6 +227,8 @@ export class DiagnosticsService {
       !isNetworkTelemetryGloballyDisabled(this.env) && settings.diagnosticsUploadEnabled;
     const uploadConfigured = Boolean(readUploadUrl(this.env));
     const context = this.buildRedactionContext(state);
+    signal?.throwIfAborted();
+    onProgress?.({ completed: 1, total: 3, message: "Collecting logs" });
     const logs = await this.collectLogTails(context);
     const includedLogs = Object.keys(logs).sort();
     const bundle: DiagnosticsBundle = {
@@ -252,7 +266,10 @@ export class DiagnosticsService {
       logs,
     };
 
+    signal?.throwIfAborted();
+    onProgress?.({ completed: 2, total: 3, message: "Writing bundle" });
     const diagnosticsDir = this.getDiagnosticsDir();
+    await this.storageGuard?.assertWritable("export", app.getPath("userData"));
     await fs.mkdir(diagnosticsDir, { recursive: true, mode: 0o700 });
     const bundlePath = path.join(
       diagnosticsDir,
@@ -305,6 +322,7 @@ export class DiagnosticsService {
   async uploadBundle(
     bundlePath: string,
     confirmed: boolean,
+    options: DiagnosticsTaskOptions = {},
   ): Promise<UploadDiagnosticsBundleOutput> {
     const safeBundlePath = await this.resolveBundlePath(bundlePath);
     const state = await this.persistence.loadState();
@@ -340,6 +358,7 @@ export class DiagnosticsService {
     }
 
     const payload = await fs.readFile(safeBundlePath, "utf8");
+    options.onProgress?.({ completed: 0, total: 1, message: "Uploading bundle" });
     try {
       const response = await this.fetchImpl(endpoint, {
         method: "POST",
@@ -347,6 +366,7 @@ export class DiagnosticsService {
           "content-type": "application/json",
         },
         body: payload,
+        signal: options.signal,
       });
       if (!response.ok) {
         throw new Error(`Diagnostics upload failed with HTTP ${response.status}.`);
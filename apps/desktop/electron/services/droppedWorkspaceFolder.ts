@@ -0,0 +1,51 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { sameWorkspacePath } from "../../../../src/utils/workspacePath";
+import type { WorkspaceRecord } from "../../src/app/types";
+import type { DroppedWorkspaceFolder } from "../../src/lib/desktopApi";
+import {
+  canonicalLocalPath,
+  fromFileSystemPath,
+  isPathEqualOrInside,
+  toFileSystemPath,
+} from "./pathBoundary";
+
+type DroppedWorkspaceFolderOptions = {
+  /** The app's own data directories; folders inside them cannot become workspaces. */
+  appDataPaths: string[];
+  workspaces: Array<Pick<WorkspaceRecord, "id" | "path">>;
+};
+
+/**
+ * Checks a folder dropped onto the window before it becomes a workspace: it
+ * must still exist, be a directory, and sit outside the app's data. A folder
+ * that is already registered comes back with that workspace's id.
+ */
+export async function resolveDroppedWorkspaceFolder(
+  droppedPath: string,
+  options: DroppedWorkspaceFolderOptions,
+): Promise<DroppedWorkspaceFolder> {
+  const resolved = path.resolve(fromFileSystemPath(droppedPath));
+  let isDirectory: boolean;
+  try {
+    isDirectory = (await fs.stat(toFileSystemPath(resolved))).isDirectory();
+  } catch {
+    throw new Error(`The dropped folder no longer exists: ${droppedPath}`);
+  }
+  if (!isDirectory) {
+    throw new Error(`Only folders can be added as workspaces: ${path.basename(resolved)}`);
+  }
+  const folderPath = await canonicalLocalPath(resolved);
+  for (const appDataPath of options.appDataPaths) {
+    if (isPathEqualOrInside(await canonicalLocalPath(appDataPath), folderPath)) {
+      throw new Error("Folders inside the app's data directory cannot be added as workspaces.");
+    }
+  }
+  for (const workspace of options.workspaces) {
+    if (sameWorkspacePath(await canonicalLocalPath(workspace.path), folderPath)) {
+      return { path: folderPath, existingWorkspaceId: workspace.id };
+    }
+  }
+  return { path: folderPath, existingWorkspaceId: null };
+}
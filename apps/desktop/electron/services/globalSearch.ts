@@ -0,0 +1,249 @@
+import type { PersistedState, ThreadRecord, TranscriptEvent } from "../../src/app/types";
+import type {
+  GlobalSearchInput,
+  GlobalSearchResult,
+  PromptTemplate,
+  TranscriptAnnotation,
+} from "../../src/lib/desktopApi";
+
+const DEFAULT_LIMIT = 20;
+/** Transcripts are read newest thread first; older ones are skipped past this many. */
+const MAX_TRANSCRIPTS_SCANNED = 200;
+const MAX_EVENT_MATCHES_PER_THREAD = 3;
+const MIN_EVENT_QUERY_LENGTH = 2;
+const SNIPPET_CONTEXT_CHARS = 40;
+
+// Names and titles outrank a message that merely mentions the query.
+const KIND_WEIGHTS: Record<GlobalSearchResult["kind"], number> = {
+  workspace: 1,
+  thread: 0.9,
+  prompt: 0.8,
+  // A bookmark was placed deliberately, so it outranks an incidental mention.
+  annotation: 0.7,
+  event: 0.5,
+};
+
+type GlobalSearchOptions = {
+  readTranscript: (threadId: string) => Promise<TranscriptEvent[]>;
+  readAnnotations?: (threadId: string) => Promise<TranscriptAnnotation[]>;
+  readPromptTemplates?: () => Promise<PromptTemplate[]>;
+};
+
+/** 1 for an exact match, then prefix, word start, and substring; null when absent. */
+function matchScore(text: string, query: string): number | null {
+  const haystack = text.toLowerCase();
+  const index = haystack.indexOf(query);
+  if (index < 0) {
+    return null;
+  }
+  if (haystack === query) {
+    return 1;
+  }
+  if (index === 0) {
+    return 0.8;
+  }
+  return /[\s\-_/.:]/.test(haystack.charAt(index - 1)) ? 0.6 : 0.4;
+}
+
+/** Text of the messages worth searching; tool output and protocol events are skipped. */
+function eventText(event: TranscriptEvent): string | null {
+  if (event.direction !== "server") {
+    return null;
+  }
+  const payload = event.payload as { type?: unknown; text?: unknown } | null;
+  if (payload?.type !== "user_message" && payload?.type !== "assistant_message") {
+    return null;
+  }
+  return typeof payload.text === "string" ? payload.text : null;
+}
+
+function snippetAround(text: string, index: number, length: number): string {
+  const start = Math.max(0, index - SNIPPET_CONTEXT_CHARS);
+  const end = Math.min(text.length, index + length + SNIPPET_CONTEXT_CHARS);
+  const body = text.slice(start, end).replace(/\s+/g, " ").trim();
+  return `${start > 0 ? "…" : ""}${body}${end < text.length ? "…" : ""}`;
+}
+
+function compareResults(left: GlobalSearchResult, right: GlobalSearchResult): number {
+  return right.score - left.score || right.timestamp.localeCompare(left.timestamp);
+}
+
+async function searchTranscripts(
+  threads: ThreadRecord[],
+  query: string,
+  options: GlobalSearchOptions,
+): Promise<GlobalSearchResult[]> {
+  const results: GlobalSearchResult[] = [];
+  const newestFirst = [...threads]
+    .sort((left, right) => right.lastMessageAt.localeCompare(left.lastMessageAt))
+    .slice(0, MAX_TRANSCRIPTS_SCANNED);
+  for (const thread of newestFirst) {
+    let events: TranscriptEvent[];
+    try {
+      events = await options.readTranscript(thread.id);
+    } catch {
+      // An unreadable transcript should not hide matches from the others.
+      continue;
+    }
+    let matches = 0;
+    for (let index = events.length - 1; index >= 0; index -= 1) {
+      const event = events[index];
+      const text = event ? eventText(event) : null;
+      if (!event || !text) {
+        continue;
+      }
+      const score = matchScore(text, query);
+      if (score === null) {
+        continue;
+      }
+      results.push({
+        kind: "event",
+        score: score * KIND_WEIGHTS.event,
+        workspaceId: thread.workspaceId,
+        threadId: thread.id,
+        title: thread.title,
+        snippet: snippetAround(text, text.toLowerCase().indexOf(query), query.length),
+        timestamp: event.ts,
+      });
+      matches += 1;
+      if (matches >= MAX_EVENT_MATCHES_PER_THREAD) {
+        break;
+      }
+    }
+  }
+  return results;
+}
+
+async function searchAnnotations(
+  threads: ThreadRecord[],
+  query: string,
+  readAnnotations: (threadId: string) => Promise<TranscriptAnnotation[]>,
+): Promise<GlobalSearchResult[]> {
+  const results: GlobalSearchResult[] = [];
+  for (const thread of threads) {
+    let annotations: TranscriptAnnotation[];
+    try {
+      annotations = await readAnnotations(thread.id);
+    } catch {
+      continue;
+    }
+    for (const annotation of annotations) {
+      // Tags are stored lowercased, like the query.
+      const score = annotation.tags.includes(query) ? 1 : matchScore(annotation.note, query);
+      if (score === null) {
+        continue;
+      }
+      const noteIndex = Math.max(0, annotation.note.toLowerCase().indexOf(query));
+      results.push({
+        kind: "annotation",
+        score: score * KIND_WEIGHTS.annotation,
+        workspaceId: thread.workspaceId,
+        threadId: thread.id,
+        eventIndex: annotation.eventIndex,
+        title: thread.title,
+        snippet: snippetAround(annotation.note, noteIndex, query.length),
+        tags: [...annotation.tags],
+        timestamp: annotation.eventTs,
+      });
+    }
+  }
+  return results;
+}
+
+/** Names and tags rank a template; a mention in its description or body ranks it lower. */
+function searchPromptTemplates(templates: PromptTemplate[], query: string): GlobalSearchResult[] {
+  const results: GlobalSearchResult[] = [];
+  for (const template of templates) {
+    const text =
+      query.length >= MIN_EVENT_QUERY_LENGTH
+        ? [template.description, template.body].find((candidate) =>
+            candidate.toLowerCase().includes(query),
+          )
+        : undefined;
+    const score = Math.max(
+      matchScore(template.name, query) ?? 0,
+      template.tags.includes(query) ? 1 : 0,
+      text ? 0.4 : 0,
+    );
+    if (score === 0) {
+      continue;
+    }
+    results.push({
+      kind: "prompt",
+      score: score * KIND_WEIGHTS.prompt,
+      templateId: template.id,
+      scope: template.scope,
+      ...(template.workspaceId ? { workspaceId: template.workspaceId } : {}),
+      title: template.name,
+      snippet: text
+        ? snippetAround(text, text.toLowerCase().indexOf(query), query.length)
+        : snippetAround(template.description || template.body, 0, 0),
+      tags: [...template.tags],
+      timestamp: template.updatedAt,
+    });
+  }
+  return results;
+}
+
+/**
+ * Matches the query against workspace names, prompt templates, thread titles,
+ * annotation notes and tags, and message text in transcripts, and returns one
+ * list ranked by match quality and kind, with newer items first among equals.
+ */
+export async function globalSearch(
+  state: PersistedState,
+  input: GlobalSearchInput,
+  options: GlobalSearchOptions,
+): Promise<GlobalSearchResult[]> {
+  const query = input.query.trim().toLowerCase();
+  if (!query) {
+    return [];
+  }
+  const results: GlobalSearchResult[] = [];
+  const workspaceIds = new Set<string>();
+  for (const workspace of state.workspaces) {
+    workspaceIds.add(workspace.id);
+    const score = matchScore(workspace.name, query);
+    if (score !== null) {
+      results.push({
+        kind: "workspace",
+        score: score * KIND_WEIGHTS.workspace,
+        workspaceId: workspace.id,
+        title: workspace.name,
+        path: workspace.path,
+        timestamp: workspace.lastOpenedAt,
+      });
+    }
+  }
+  // Merged threads would only repeat hits from the thread that now holds their history.
+  const threads = state.threads.filter(
+    (thread) => workspaceIds.has(thread.workspaceId) && !thread.mergedIntoThreadId,
+  );
+  for (const thread of threads) {
+    const score = matchScore(thread.title, query);
+    if (score !== null) {
+      results.push({
+        kind: "thread",
+        score: score * KIND_WEIGHTS.thread,
+        workspaceId: thread.workspaceId,
+        threadId: thread.id,
+        title: thread.title,
+        timestamp: thread.lastMessageAt,
+      });
+    }
+  }
+  if (options.readPromptTemplates) {
+    try {
+      results.push(...searchPromptTemplates(await options.readPromptTemplates(), query));
+    } catch {
+      // An unreadable library should not hide the other matches.
+    }
+  }
+  if (options.readAnnotations) {
+    results.push(...(await searchAnnotations(threads, query, options.readAnnotations)));
+  }
+  if (query.length >= MIN_EVENT_QUERY_LENGTH) {
+    results.push(...(await searchTranscripts(threads, query, options)));
+  }
+  return results.sort(compareResults).slice(0, input.limit ?? DEFAULT_LIMIT);
+}
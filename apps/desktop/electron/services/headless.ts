@@ -0,0 +1,204 @@
+import path from "node:path";
+
+import { sameWorkspacePath } from "../../../../src/utils/workspacePath";
+import type { PersistedState, TranscriptEvent, WorkspaceRecord } from "../../src/app/types";
+import { pathPolicyEnvValue } from "../../src/app/workspacePathPolicy";
+import type { StartWorkspaceServerOptions } from "./serverManager";
+import { assertSafeId } from "./validation";
+
+export const HEADLESS_FLAG = "--headless";
+
+/** Used for a folder that is not one of the saved workspaces. */
+export const HEADLESS_WORKSPACE_ID = "__headless__";
+
+const DEFAULT_POLL_INTERVAL_MS = 1_000;
+
+export type HeadlessOptions = {
+  /** Saved workspace id, or a folder path resolved against the working directory. */
+  workspace: string | null;
+  /** Threads whose transcripts are written to stdout. */
+  threadIds: string[];
+  /** Keep printing new transcript events until interrupted. */
+  follow: boolean;
+  yolo: boolean;
+};
+
+type HeadlessServerOptions = Pick<
+  StartWorkspaceServerOptions,
+  | "workspaceId"
+  | "workspacePath"
+  | "yolo"
+  | "resourceLimits"
+  | "container"
+  | "additionalRoots"
+  | "pathPolicy"
+  | "privacyTelemetrySettings"
+>;
+
+type HeadlessDeps = {
+  loadState: () => Promise<Pick<PersistedState, "workspaces" | "privacyTelemetrySettings">>;
+  readTranscript: (threadId: string) => Promise<TranscriptEvent[]>;
+  /** Starts the workspace's server and resolves its URL. */
+  startServer: (opts: HeadlessServerOptions) => Promise<string>;
+  stopServer: (workspaceId: string) => Promise<void>;
+  /** Writes one line to stdout; errors go to `writeError`. */
+  write: (line: string) => void;
+  writeError: (line: string) => void;
+  /** Aborted on SIGINT/SIGTERM to end a run that keeps going. */
+  signal: AbortSignal;
+  cwd: string;
+  pollIntervalMs?: number;
+};
+
+export class HeadlessArgsError extends Error {
+  readonly code = "invalid_headless_args";
+
+  constructor(message: string) {
+    super(message);
+    this.name = "HeadlessArgsError";
+  }
+}
+
+function readFlagValue(args: string[], index: number, flag: string): [string, number] {
+  const arg = args[index] ?? "";
+  if (arg.startsWith(`${flag}=`)) {
+    return [arg.slice(flag.length + 1), index];
+  }
+  const value = args[index + 1];
+  if (value === undefined || value.startsWith("--")) {
+    throw new HeadlessArgsError(`${flag} needs a value`);
+  }
+  return [value, index + 1];
+}
+
+/**
+ * Returns the options for a `--headless` launch, or null for a normal one.
+ * Unknown flags are ignored so Chromium switches can ride along.
+ */
+export function parseHeadlessArgs(args: string[]): HeadlessOptions | null {
+  if (!args.includes(HEADLESS_FLAG)) {
+    return null;
+  }
+  const options: HeadlessOptions = { workspace: null, threadIds: [], follow: false, yolo: false };
+  for (let index = 0; index < args.length; index += 1) {
+    const arg = args[index] ?? "";
+    const flag = arg.split("=", 1)[0];
+    if (flag === "--workspace") {
+      let value: string;
+      [value, index] = readFlagValue(args, index, flag);
+      options.workspace = value.trim() || null;
+    } else if (flag === "--tail") {
+      let value: string;
+      [value, index] = readFlagValue(args, index, flag);
+      try {
+        assertSafeId(value, "--tail thread id");
+      } catch (error) {
+        throw new HeadlessArgsError(error instanceof Error ? error.message : String(error));
+      }
+      options.threadIds.push(value);
+    } else if (arg === "--follow") {
+      options.follow = true;
+    } else if (arg === "--yolo") {
+      options.yolo = true;
+    }
+  }
+  if (!options.workspace && options.threadIds.length === 0) {
+    throw new HeadlessArgsError("--headless needs --workspace <id|path> and/or --tail <threadId>");
+  }
+  return options;
+}
+
+function resolveHeadlessWorkspace(
+  value: string,
+  workspaces: WorkspaceRecord[],
+  cwd: string,
+): HeadlessServerOptions {
+  const resolvedPath = path.resolve(cwd, value);
+  const workspace =
+    workspaces.find((candidate) => candidate.id === value) ??
+    workspaces.find((candidate) => sameWorkspacePath(candidate.path, resolvedPath));
+  if (!workspace) {
+    return { workspaceId: HEADLESS_WORKSPACE_ID, workspacePath: resolvedPath, yolo: false };
+  }
+  const pathPolicy = pathPolicyEnvValue(workspace);
+  return {
+    workspaceId: workspace.id,
+    workspacePath: workspace.path,
+    yolo: false,
+    ...(workspace.resourceLimits ? { resourceLimits: workspace.resourceLimits } : {}),
+    ...(workspace.container ? { container: workspace.container } : {}),
+    ...(workspace.additionalRoots?.length ? { additionalRoots: workspace.additionalRoots } : {}),
+    ...(pathPolicy ? { pathPolicy } : {}),
+  };
+}
+
+function waitForPoll(ms: number, signal: AbortSignal): Promise<void> {
+  return new Promise((resolve) => {
+    const done = () => {
+      clearTimeout(timer);
+      signal.removeEventListener("abort", done);
+      resolve();
+    };
+    const timer = setTimeout(done, ms);
+    signal.addEventListener("abort", done, { once: true });
+  });
+}
+
+/**
+ * Runs the app without windows for scripts and CI. Starts the workspace's
+ * server through the same server manager the app uses and prints its URL,
+ * then prints each tailed thread's transcript. Output is one JSON object per
+ * line. The run keeps going while a server is up or `--follow` is set, until
+ * the signal aborts, and resolves the process exit code.
+ */
+export async function runHeadless(options: HeadlessOptions, deps: HeadlessDeps): Promise<number> {
+  const write = (record: Record<string, unknown>) => deps.write(JSON.stringify(record));
+  let startedWorkspaceId: string | null = null;
+  try {
+    if (options.workspace) {
+      const { workspaces, privacyTelemetrySettings } = await deps.loadState();
+      const server = resolveHeadlessWorkspace(options.workspace, workspaces, deps.cwd);
+      const url = await deps.startServer({
+        ...server,
+        yolo: options.yolo,
+        ...(privacyTelemetrySettings ? { privacyTelemetrySettings } : {}),
+      });
+      startedWorkspaceId = server.workspaceId;
+      write({ type: "server_listening", workspaceId: server.workspaceId, url });
+    }
+
+    const printed = new Map(options.threadIds.map((threadId) => [threadId, 0]));
+    const printNewEvents = async () => {
+      for (const [threadId, count] of printed) {
+        const events = await deps.readTranscript(threadId);
+        for (const event of events.slice(count)) {
+          write({ type: "transcript_event", event });
+        }
+        printed.set(threadId, Math.max(count, events.length));
+      }
+    };
+
+    await printNewEvents();
+    if (options.follow || startedWorkspaceId) {
+      while (!deps.signal.aborted) {
+        await waitForPoll(deps.pollIntervalMs ?? DEFAULT_POLL_INTERVAL_MS, deps.signal);
+        if (options.follow) {
+          await printNewEvents();
+        }
+      }
+    }
+    return 0;
+  } catch (error) {
+    deps.writeError(
+      JSON.stringify({
+        type: "error",
+        message: error instanceof Error ? error.message : String(error),
+      }),
+    );
+    return 1;
+  } finally {
+    if (startedWorkspaceId) {
+      await deps.stopServer(startedWorkspaceId).catch(() => {});
+    }
+  }
+}
@@ -0,0 +1,180 @@
+import fs from "node:fs";
+import path from "node:path";
+
+import {
+  WINDOWS_SANDBOX_COMMAND_RUNNER_NAME,
+  WINDOWS_SANDBOX_HASH_MANIFEST_NAME,
+  WINDOWS_SANDBOX_HELPER_NAME,
+  WINDOWS_SANDBOX_SETUP_NAME,
+} from "../../../../src/platform/sandbox/windows";
+import type {
+  InstallationIntegrityReport,
+  InstallationResourceIntegrity,
+} from "../../src/lib/desktopApi";
+import {
+  FOUNDATION_MODELS_SDK_DIR_NAME,
+  findPackagedSidecarLaunchCommand,
+  hasPackagedFoundationModelsSdk,
+  hasPackagedWindowsAiElectronPackage,
+  hashFileSha256,
+  SIDECAR_BASE_NAME,
+  shouldBundleFoundationModelsSdk,
+  shouldBundleWindowsAiElectronPackage,
+  WINDOWS_AI_ELECTRON_DIR_NAME,
+} from "./sidecar";
+
+const WINDOWS_SANDBOX_BINARY_NAMES = [
+  WINDOWS_SANDBOX_HELPER_NAME,
+  WINDOWS_SANDBOX_SETUP_NAME,
+  WINDOWS_SANDBOX_COMMAND_RUNNER_NAME,
+];
+
+type VerifyInstallationOptions = {
+  searchDirs: string[];
+  isPackaged: boolean;
+  explicitSidecarPath?: string;
+  platform?: NodeJS.Platform;
+  arch?: string;
+  now?: () => Date;
+  hashFile?: (filePath: string) => Promise<string>;
+};
+
+async function checkFile(
+  name: string,
+  filePath: string,
+  expectedSha256: string | null,
+  hashFile: (filePath: string) => Promise<string>,
+): Promise<InstallationResourceIntegrity> {
+  const actualSha256 = await hashFile(filePath).catch(() => null);
+  let status: InstallationResourceIntegrity["status"];
+  if (actualSha256 === null) {
+    status = "missing";
+  } else if (expectedSha256 === null) {
+    status = "unverified";
+  } else {
+    status = actualSha256 === expectedSha256 ? "verified" : "mismatch";
+  }
+  return { name, path: filePath, status, expectedSha256, actualSha256 };
+}
+
+function missing(name: string): InstallationResourceIntegrity {
+  return { name, path: null, status: "missing", expectedSha256: null, actualSha256: null };
+}
+
+async function checkSidecar(
+  options: VerifyInstallationOptions,
+  hashFile: (filePath: string) => Promise<string>,
+): Promise<InstallationResourceIntegrity[]> {
+  let launch: ReturnType<typeof findPackagedSidecarLaunchCommand>;
+  try {
+    launch = findPackagedSidecarLaunchCommand(options.searchDirs, {
+      explicitPath: options.explicitSidecarPath,
+      platform: options.platform,
+      arch: options.arch,
+    });
+  } catch {
+    return [missing(SIDECAR_BASE_NAME)];
+  }
+  if (!launch.integrity || launch.integrity.length === 0) {
+    return [await checkFile(path.basename(launch.command), launch.command, null, hashFile)];
+  }
+  return await Promise.all(
+    launch.integrity.map((file) =>
+      checkFile(path.basename(file.path), file.path, file.sha256, hashFile),
+    ),
+  );
+}
+
+async function checkWindowsSandbox(
+  searchDirs: string[],
+  hashFile: (filePath: string) => Promise<string>,
+): Promise<InstallationResourceIntegrity[]> {
+  for (const dir of searchDirs) {
+    let files: Record<string, unknown>;
+    try {
+      const manifestPath = path.join(dir, WINDOWS_SANDBOX_HASH_MANIFEST_NAME);
+      const manifest = JSON.parse(fs.readFileSync(manifestPath, "utf8")) as { files?: unknown };
+      files = (manifest.files ?? {}) as Record<string, unknown>;
+    } catch {
+      continue;
+    }
+    return await Promise.all(
+      WINDOWS_SANDBOX_BINARY_NAMES.map((name) => {
+        const expected = files[name];
+        return checkFile(
+          name,
+          path.join(dir, name),
+          typeof expected === "string" ? expected : null,
+          hashFile,
+        );
+      }),
+    );
+  }
+  return WINDOWS_SANDBOX_BINARY_NAMES.map(missing);
+}
+
+// Packages bundled as directories ship no digests; only their presence is checked.
+function checkBundledPackage(
+  name: string,
+  searchDirs: string[],
+  isPresent: (candidateDir: string) => boolean,
+): InstallationResourceIntegrity {
+  for (const dir of searchDirs) {
+    const candidate = path.join(dir, name);
+    if (isPresent(candidate)) {
+      return {
+        name,
+        path: candidate,
+        status: "unverified",
+        expectedSha256: null,
+        actualSha256: null,
+      };
+    }
+  }
+  return missing(name);
+}
+
+/**
+ * Hashes the bundled sidecar and helper binaries against the digests shipped
+ * with them. The report fails when anything is missing or altered, and is
+ * unverified when a resource ships without digests.
+ */
+export async function verifyInstallation(
+  options: VerifyInstallationOptions,
+): Promise<InstallationIntegrityReport> {
+  const platform = options.platform ?? process.platform;
+  const arch = options.arch ?? process.arch;
+  const hashFile = options.hashFile ?? hashFileSha256;
+  const resources = await checkSidecar({ ...options, platform, arch }, hashFile);
+  if (platform === "win32") {
+    resources.push(...(await checkWindowsSandbox(options.searchDirs, hashFile)));
+  }
+  if (shouldBundleFoundationModelsSdk(platform, arch)) {
+    resources.push(
+      checkBundledPackage(FOUNDATION_MODELS_SDK_DIR_NAME, options.searchDirs, (candidate) =>
+        hasPackagedFoundationModelsSdk(candidate),
+      ),
+    );
+  }
+  if (shouldBundleWindowsAiElectronPackage(platform, arch)) {
+    resources.push(
+      checkBundledPackage(WINDOWS_AI_ELECTRON_DIR_NAME, options.searchDirs, (candidate) =>
+        hasPackagedWindowsAiElectronPackage(candidate, platform, arch),
+      ),
+    );
+  }
+
+  const statuses = new Set(resources.map((resource) => resource.status));
+  let status: InstallationIntegrityReport["status"] = "ok";
+  if (statuses.has("mismatch") || statuses.has("missing")) {
+    status = "failed";
+  } else if (statuses.has("unverified")) {
+    status = "unverified";
+  }
+  return {
+    status,
+    checkedAt: (options.now?.() ?? new Date()).toISOString(),
+    packaged: options.isPackaged,
+    resources,
+  };
+}
@@ -0,0 +1,152 @@
+import { assertSafeId } from "./validation";
+
+type IsolatedSlot = {
+  serverId: string;
+  workspaceId: string;
+  /** Thread the server is running for; null while it idles in the pool. */
+  threadId: string | null;
+  lastUsedAt: number;
+};
+
+type IsolatedSessionPoolOptions = {
+  /** Current cap on isolated servers across all workspaces. */
+  getMaxSidecars: () => Promise<number>;
+  /** Stops an idle server that is evicted to make room for another thread. */
+  stopServer: (serverId: string) => Promise<void>;
+  now?: () => number;
+};
+
+export type IsolatedSessionLease = {
+  /** Id the server manager tracks the thread's server under. */
+  serverId: string;
+  /** True when the thread gets a server that was already started. */
+  reused: boolean;
+};
+
+export class SidecarLimitError extends Error {
+  readonly code = "sidecar_limit_reached";
+
+  constructor(readonly max: number) {
+    super(`All ${max} isolated workspace servers are busy. Close a thread or raise the limit.`);
+    this.name = "SidecarLimitError";
+  }
+}
+
+/**
+ * Assigns threads their own workspace server when isolated sessions are on.
+ * Each server lives in a slot keyed by (workspace, thread); a released slot
+ * stays running so the next thread in that workspace can reuse it, and the
+ * least recently used idle slot is stopped when a new one would exceed the
+ * cap. Slot ids are stable per workspace so a restarted app can adopt them.
+ */
+export class IsolatedSessionPool {
+  private readonly slots = new Map<string, IsolatedSlot>();
+  private queue: Promise<unknown> = Promise.resolve();
+
+  constructor(private readonly options: IsolatedSessionPoolOptions) {}
+
+  acquire(workspaceId: string, threadId: string): Promise<IsolatedSessionLease> {
+    assertSafeId(workspaceId, "workspaceId");
+    assertSafeId(threadId, "threadId");
+    const next = this.queue.then(() => this.assign(workspaceId, threadId));
+    this.queue = next.catch(() => {});
+    return next;
+  }
+
+  /** Returns the thread's server to the pool without stopping it. */
+  release(workspaceId: string, threadId: string): void {
+    const slot = this.findThreadSlot(workspaceId, threadId);
+    if (slot) {
+      slot.threadId = null;
+      slot.lastUsedAt = this.now();
+    }
+  }
+
+  /** Drops a slot whose server exited or failed to start. */
+  forget(serverId: string): void {
+    this.slots.delete(serverId);
+  }
+
+  /** Removes every slot of a workspace and returns their server ids so they can be stopped. */
+  drainWorkspace(workspaceId: string): string[] {
+    const serverIds = [...this.slots.values()]
+      .filter((slot) => slot.workspaceId === workspaceId)
+      .map((slot) => slot.serverId);
+    for (const serverId of serverIds) {
+      this.slots.delete(serverId);
+    }
+    return serverIds;
+  }
+
+  /** Maps a server id back to its workspace and thread; null for shared workspace servers. */
+  resolve(serverId: string): { workspaceId: string; threadId: string | null } | null {
+    const slot = this.slots.get(serverId);
+    return slot ? { workspaceId: slot.workspaceId, threadId: slot.threadId } : null;
+  }
+
+  /** Id of the server a thread currently holds; null when it shares the workspace's server. */
+  serverIdForThread(workspaceId: string, threadId: string): string | null {
+    return this.findThreadSlot(workspaceId, threadId)?.serverId ?? null;
+  }
+
+  private async assign(workspaceId: string, threadId: string): Promise<IsolatedSessionLease> {
+    const owned = this.findThreadSlot(workspaceId, threadId);
+    if (owned) {
+      owned.lastUsedAt = this.now();
+      return { serverId: owned.serverId, reused: true };
+    }
+
+    const idle = this.idleSlots()
+      .filter((slot) => slot.workspaceId === workspaceId)
+      .at(-1);
+    if (idle) {
+      idle.threadId = threadId;
+      idle.lastUsedAt = this.now();
+      return { serverId: idle.serverId, reused: true };
+    }
+
+    const max = Math.max(1, await this.options.getMaxSidecars());
+    while (this.slots.size >= max) {
+      const evicted = this.idleSlots()[0];
+      if (!evicted) {
+        throw new SidecarLimitError(max);
+      }
+      this.slots.delete(evicted.serverId);
+      await this.options.stopServer(evicted.serverId);
+    }
+
+    const serverId = this.nextServerId(workspaceId);
+    this.slots.set(serverId, { serverId, workspaceId, threadId, lastUsedAt: this.now() });
+    return { serverId, reused: false };
+  }
+
+  private findThreadSlot(workspaceId: string, threadId: string): IsolatedSlot | undefined {
+    for (const slot of this.slots.values()) {
+      if (slot.workspaceId === workspaceId && slot.threadId === threadId) {
+        return slot;
+      }
+    }
+    return undefined;
+  }
+
+  /** Idle slots, least recently used first. */
+  private idleSlots(): IsolatedSlot[] {
+    return [...this.slots.values()]
+      .filter((slot) => slot.threadId === null)
+      .sort((left, right) => left.lastUsedAt - right.lastUsedAt);
+  }
+
+  private nextServerId(workspaceId: string): string {
+    for (let index = 1; ; index += 1) {
+      const serverId = `${workspaceId}--iso${index}`;
+      if (!this.slots.has(serverId)) {
+        assertSafeId(serverId, "serverId");
+        return serverId;
+      }
+    }
+  }
+
+  private now(): number {
+    return this.options.now?.() ?? Date.now();
+  }
+}
@@ -9,10 +9,18 @@ import {
   redactDiagnosticText,
   sanitizeLogMeta,
 } from "../../../../src/diagnostics/redaction";
+import type { AppLogEntry, AppLogsResult, ReadAppLogsInput } from "../../src/lib/desktopApi";
 
 export type LocalLogFileName = "desktop-main.log" | "server.log" | "renderer.log" | "updater.log";
 
-export type LocalLogLevel = "info" | "warn" | "error";
+export type LocalLogLevel = "debug" | "info" | "warn" | "error";
+
+const LOG_LEVEL_RANK: Record<LocalLogLevel, number> = { debug: 0, info: 1, warn: 2, error: 3 };
+/** Rotated files older than this many days are deleted on the next rotation. */
+export const LOG_RETENTION_DAYS = 7;
+const DEFAULT_READ_TAIL = 200;
+const MAX_READ_TAIL = 2000;
+const MAX_READ_BYTES_PER_FILE = 1024 * 1024;
 
 const LOG_FILE_NAMES = new Set<LocalLogFileName>([
   "desktop-main.log",
@@ -22,6 +30,8 @@ const LOG_FILE_NAMES = new Set<LocalLogFileName>([
 ]);
 
 const pendingWrites = new Map<LocalLogFileName, Promise<void>>();
+const rotationCheckedDay = new Map<LocalLogFileName, string>();
+let minimumLevel: LocalLogLevel = "info";
 
 function ensureLogFileName(fileName: LocalLogFileName): LocalLogFileName {
   if (!LOG_FILE_NAMES.has(fileName)) {
@@ -38,6 +48,85 @@ export function getLocalLogPath(fileName: LocalLogFileName): string {
   return path.join(getLogsDir(), ensureLogFileName(fileName));
 }
 
+/** Entries below this level are dropped until it is changed again or the app restarts. */
+export function setLocalLogLevel(level: LocalLogLevel): void {
+  minimumLevel = level;
+}
+
+export function getLocalLogLevel(): LocalLogLevel {
+  return minimumLevel;
+}
+
+function isLevelEnabled(level: LocalLogLevel, threshold: LocalLogLevel): boolean {
+  return LOG_LEVEL_RANK[level] >= LOG_LEVEL_RANK[threshold];
+}
+
+function localDayKey(ms: number): string {
+  const date = new Date(ms);
+  const month = String(date.getMonth() + 1).padStart(2, "0");
+  const day = String(date.getDate()).padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+function rotatedLogPattern(fileName: LocalLogFileName): RegExp {
+  const stem = fileName.replace(/\.log$/, "");
+  return new RegExp(`^${stem}\\.(\\d{4}-\\d{2}-\\d{2})\\.log$`);
+}
+
+/** Rotated copies of `fileName`, newest first. */
+async function listRotatedLogs(fileName: LocalLogFileName): Promise<string[]> {
+  const pattern = rotatedLogPattern(fileName);
+  let names: string[];
+  try {
+    names = await fs.readdir(getLogsDir());
+  } catch {
+    return [];
+  }
+  return names
+    .filter((name) => pattern.test(name))
+    .sort()
+    .reverse();
+}
+
+/**
+ * Moves yesterday's (or older) log aside as `<name>.<day>.log` before the
+ * first write of a new day, then drops rotated files past the retention window.
+ */
+async function rotateIfStale(fileName: LocalLogFileName, logPath: string): Promise<void> {
+  const today = localDayKey(Date.now());
+  if (rotationCheckedDay.get(fileName) === today) {
+    return;
+  }
+  rotationCheckedDay.set(fileName, today);
+  let modifiedDay: string;
+  try {
+    modifiedDay = localDayKey((await fs.stat(logPath)).mtimeMs);
+  } catch {
+    return;
+  }
+  if (modifiedDay === today) {
+    return;
+  }
+  const rotatedPath = path.join(
+    path.dirname(logPath),
+    `${fileName.replace(/\.log$/, "")}.${modifiedDay}.log`,
+  );
+  try {
+    await fs.access(rotatedPath);
+    // A rotated file for that day already exists (clock skew); append instead of clobbering it.
+    await fs.appendFile(rotatedPath, await fs.readFile(logPath));
+    await fs.rm(logPath, { force: true });
+  } catch {
+    await fs.rename(logPath, rotatedPath);
+  }
+  const rotated = await listRotatedLogs(fileName);
+  await Promise.all(
+    rotated
+      .slice(LOG_RETENTION_DAYS)
+      .map((name) => fs.rm(path.join(path.dirname(logPath), name), { force: true })),
+  );
+}
+
 function errorMeta(error: unknown): Record<string, unknown> {
   if (error instanceof Error) {
     return {
@@ -74,6 +163,9 @@ export function writeLocalLog(
   context?: DiagnosticsRedactionContext,
 ): void {
   const safeFileName = ensureLogFileName(fileName);
+  if (!isLevelEnabled(level, minimumLevel)) {
+    return;
+  }
   const entry = makeLogEntry(level, category, message, meta, context);
   const pending = pendingWrites.get(safeFileName) ?? Promise.resolve();
   const next = pending
@@ -84,6 +176,9 @@ export function writeLocalLog(
       try {
         const logPath = getLocalLogPath(safeFileName);
         await fs.mkdir(path.dirname(logPath), { recursive: true, mode: 0o700 });
+        await rotateIfStale(safeFileName, logPath).catch(() => {
+          // A failed rotation should not cost the entry being written.
+        });
         await fs.appendFile(logPath, entry, { encoding: "utf8", mode: 0o600 });
       } catch {
         // Local logs are best-effort diagnostics only.
@@ -92,6 +187,10 @@ export function writeLocalLog(
   pendingWrites.set(safeFileName, next);
 }
 
+export function logDebug(category: string, message: string, meta?: unknown): void {
+  writeLocalLog("desktop-main.log", "debug", category, message, meta);
+}
+
 export function logInfo(category: string, message: string, meta?: unknown): void {
   writeLocalLog("desktop-main.log", "info", category, message, meta);
 }
@@ -140,4 +239,71 @@ export async function tailLog(file: string, maxBytes: number): Promise<string> {
   }
 }
 
+function parseLogLine(line: string, file: string): AppLogEntry | null {
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(line);
+  } catch {
+    return null;
+  }
+  if (typeof parsed !== "object" || parsed === null) {
+    return null;
+  }
+  const { ts, level, category, message, meta } = parsed as Record<string, unknown>;
+  if (
+    typeof ts !== "string" ||
+    typeof level !== "string" ||
+    !(level in LOG_LEVEL_RANK) ||
+    typeof category !== "string" ||
+    typeof message !== "string"
+  ) {
+    return null;
+  }
+  return {
+    ts,
+    level: level as LocalLogLevel,
+    category,
+    message,
+    ...(meta !== undefined ? { meta } : {}),
+    file,
+  };
+}
+
+/**
+ * Returns the newest `tail` entries at or above `levelFilter`, oldest first,
+ * reading the live log and then its rotated copies until enough are found.
+ */
+export async function readLocalLogs(input: ReadAppLogsInput = {}): Promise<AppLogsResult> {
+  const fileName = ensureLogFileName(input.file ?? "desktop-main.log");
+  const tail = Math.max(1, Math.min(MAX_READ_TAIL, input.tail ?? DEFAULT_READ_TAIL));
+  const levelFilter = input.levelFilter ?? "debug";
+  await flushLocalLogWrites(fileName);
+
+  const newestFirst: AppLogEntry[] = [];
+  let truncated = false;
+  for (const name of [fileName, ...(await listRotatedLogs(fileName))]) {
+    const text = await tailLog(path.join(getLogsDir(), name), MAX_READ_BYTES_PER_FILE);
+    const lines = text.split("\n");
+    if (Buffer.byteLength(text) >= MAX_READ_BYTES_PER_FILE) {
+      // The first line is likely cut mid-entry, and older ones were not read.
+      lines.shift();
+      truncated = true;
+    }
+    for (const line of lines.reverse()) {
+      const entry = line.trim() ? parseLogLine(line, name) : null;
+      if (!entry || !isLevelEnabled(entry.level, levelFilter)) {
+        continue;
+      }
+      if (newestFirst.length === tail) {
+        return { level: minimumLevel, entries: newestFirst.reverse(), truncated: true };
+      }
+      newestFirst.push(entry);
+    }
+    if (truncated) {
+      break;
+    }
+  }
+  return { level: minimumLevel, entries: newestFirst.reverse(), truncated };
+}
+
 export { sanitizeLogMeta };
@@ -0,0 +1,27 @@
+import net from "node:net";
+
+/** A free port on 127.0.0.1; another process may still take it before it is bound. */
+export async function allocateLoopbackPort(): Promise<number> {
+  return await new Promise((resolve, reject) => {
+    const server = net.createServer();
+    server.once("error", reject);
+    server.listen(0, "127.0.0.1", () => {
+      const address = server.address();
+      server.close(() => {
+        if (address && typeof address === "object") {
+          resolve(address.port);
+        } else {
+          reject(new Error("Could not allocate a local port"));
+        }
+      });
+    });
+  });
+}
+
+/** `url` with its host and port replaced by a local port forwarded or published to it. */
+export function forwardedServerUrl(url: string, localPort: number): string {
+  const parsed = new URL(url);
+  parsed.hostname = "127.0.0.1";
+  parsed.port = String(localPort);
+  return parsed.toString();
+}
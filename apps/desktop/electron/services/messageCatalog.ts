@@ -0,0 +1,207 @@
+import type { LocaleInfo, MessageLocale } from "../../src/lib/desktopApi";
+
+export const DEFAULT_MESSAGE_LOCALE: MessageLocale = "en";
+export const SUPPORTED_MESSAGE_LOCALES: readonly MessageLocale[] = ["en", "es", "fr", "de"];
+
+/**
+ * English text for every user-facing string the main process emits. Error
+ * entries are keyed by the error's `code`; English errors keep their own,
+ * more detailed message, so these entries only anchor the translations.
+ */
+const EN = {
+  "error.sidecar_integrity_failed":
+    "The bundled workspace server failed its integrity check. Reinstall Cowork and try again.",
+  "error.sidecar_limit_reached":
+    "All {max} isolated workspace servers are busy. Close a thread or raise the limit.",
+  "error.invalid_app_data_archive": "This file is not a valid Cowork data export.",
+  "error.read_only_thread": "Thread {threadId} is a read-only transcript viewer.",
+  "error.low_disk_space": "There is not enough free disk space to save changes.",
+  "error.invalid_transcript_payload": "Rejected a malformed {payloadType} transcript event.",
+  "error.unknown_model": "{model} is not an available {provider} model.",
+  "notification.scheduledTask.completed": "Scheduled task finished",
+  "notification.scheduledTask.failed": "Scheduled task failed",
+  "notification.scheduledTask.interrupted": "Scheduled task interrupted",
+  "notification.updateReady.title": "Update ready",
+  "notification.updateReady.body": "Cowork update is ready. Restart to install.",
+  "notification.updateReady.bodyWithVersion": "Cowork {version} is ready. Restart to install.",
+  "notification.updateReady.restartNow": "Restart Now",
+  "notification.updateReady.later": "Later",
+  "tray.tooltip": "{appName} quick chat",
+  "tray.openQuickChat": "Open Quick Chat{shortcut}",
+  "tray.openApp": "Open {appName}",
+  "tray.quit": "Quit {appName}",
+};
+
+export type MessageKey = keyof typeof EN;
+
+type MessageBundle = Partial<Record<MessageKey, string>>;
+
+const BUNDLES: Record<Exclude<MessageLocale, "en">, MessageBundle> = {
+  es: {
+    "error.sidecar_integrity_failed":
+      "El servidor de espacio de trabajo incluido no superó la comprobación de integridad. " +
+      "Reinstala Cowork e inténtalo de nuevo.",
+    "error.sidecar_limit_reached":
+      "Los {max} servidores aislados de espacio de trabajo están ocupados. " +
+      "Cierra un hilo o aumenta el límite.",
+    "error.invalid_app_data_archive":
+      "Este archivo no es una exportación de datos de Cowork válida.",
+    "error.read_only_thread": "El hilo {threadId} es un visor de transcripciones de solo lectura.",
+    "error.low_disk_space": "No hay suficiente espacio libre en disco para guardar los cambios.",
+    "error.invalid_transcript_payload":
+      "Se rechazó un evento de transcripción {payloadType} mal formado.",
+    "error.unknown_model": "{model} no es un modelo disponible de {provider}.",
+    "notification.scheduledTask.completed": "Tarea programada terminada",
+    "notification.scheduledTask.failed": "La tarea programada falló",
+    "notification.scheduledTask.interrupted": "Tarea programada interrumpida",
+    "notification.updateReady.title": "Actualización lista",
+    "notification.updateReady.body":
+      "La actualización de Cowork está lista. Reinicia para instalarla.",
+    "notification.updateReady.bodyWithVersion":
+      "Cowork {version} está listo. Reinicia para instalarlo.",
+    "notification.updateReady.restartNow": "Reiniciar ahora",
+    "notification.updateReady.later": "Más tarde",
+    "tray.tooltip": "Chat rápido de {appName}",
+    "tray.openQuickChat": "Abrir chat rápido{shortcut}",
+    "tray.openApp": "Abrir {appName}",
+    "tray.quit": "Salir de {appName}",
+  },
+  fr: {
+    "error.sidecar_integrity_failed":
+      "Le serveur d'espace de travail intégré a échoué au contrôle d'intégrité. " +
+      "Réinstallez Cowork puis réessayez.",
+    "error.sidecar_limit_reached":
+      "Les {max} serveurs d'espace de travail isolés sont occupés. " +
+      "Fermez un fil ou augmentez la limite.",
+    "error.invalid_app_data_archive":
+      "Ce fichier n'est pas une exportation de données Cowork valide.",
+    "error.read_only_thread":
+      "Le fil {threadId} est une visionneuse de transcription en lecture seule.",
+    "error.low_disk_space":
+      "L'espace disque disponible est insuffisant pour enregistrer les modifications.",
+    "error.invalid_transcript_payload":
+      "Un événement de transcription {payloadType} mal formé a été rejeté.",
+    "error.unknown_model": "{model} n'est pas un modèle {provider} disponible.",
+    "notification.scheduledTask.completed": "Tâche planifiée terminée",
+    "notification.scheduledTask.failed": "Échec de la tâche planifiée",
+    "notification.scheduledTask.interrupted": "Tâche planifiée interrompue",
+    "notification.updateReady.title": "Mise à jour prête",
+    "notification.updateReady.body":
+      "La mise à jour de Cowork est prête. Redémarrez pour l'installer.",
+    "notification.updateReady.bodyWithVersion":
+      "Cowork {version} est prêt. Redémarrez pour l'installer.",
+    "notification.updateReady.restartNow": "Redémarrer maintenant",
+    "notification.updateReady.later": "Plus tard",
+    "tray.tooltip": "Chat rapide {appName}",
+    "tray.openQuickChat": "Ouvrir le chat rapide{shortcut}",
+    "tray.openApp": "Ouvrir {appName}",
+    "tray.quit": "Quitter {appName}",
+  },
+  de: {
+    "error.sidecar_integrity_failed":
+      "Der mitgelieferte Workspace-Server hat die Integritätsprüfung nicht bestanden. " +
+      "Installiere Cowork neu und versuche es erneut.",
+    "error.sidecar_limit_reached":
+      "Alle {max} isolierten Workspace-Server sind belegt. " +
+      "Schließe einen Thread oder erhöhe das Limit.",
+    "error.invalid_app_data_archive": "Diese Datei ist kein gültiger Cowork-Datenexport.",
+    "error.read_only_thread": "Thread {threadId} ist eine schreibgeschützte Transkriptansicht.",
+    "error.low_disk_space": "Nicht genug freier Speicherplatz, um Änderungen zu speichern.",
+    "error.invalid_transcript_payload":
+      "Ein fehlerhaftes {payloadType}-Transkriptereignis wurde abgelehnt.",
+    "error.unknown_model": "{model} ist kein verfügbares {provider}-Modell.",
+    "notification.scheduledTask.completed": "Geplante Aufgabe abgeschlossen",
+    "notification.scheduledTask.failed": "Geplante Aufgabe fehlgeschlagen",
+    "notification.scheduledTask.interrupted": "Geplante Aufgabe unterbrochen",
+    "notification.updateReady.title": "Update bereit",
+    "notification.updateReady.body":
+      "Das Cowork-Update ist bereit. Starte neu, um es zu installieren.",
+    "notification.updateReady.bodyWithVersion":
+      "Cowork {version} ist bereit. Starte neu, um es zu installieren.",
+    "notification.updateReady.restartNow": "Jetzt neu starten",
+    "notification.updateReady.later": "Später",
+    "tray.tooltip": "{appName}-Schnellchat",
+    "tray.openQuickChat": "Schnellchat öffnen{shortcut}",
+    "tray.openApp": "{appName} öffnen",
+    "tray.quit": "{appName} beenden",
+  },
+};
+
+export type MessageParams = Record<string, string | number>;
+
+/** Maps a BCP 47 tag such as `es-MX` or `de_AT` to the closest bundled locale. */
+export function resolveMessageLocale(tag: string | null | undefined): MessageLocale {
+  const language = tag?.trim().toLowerCase().split(/[-_]/)[0] ?? "";
+  return (
+    SUPPORTED_MESSAGE_LOCALES.find((locale) => locale === language) ?? DEFAULT_MESSAGE_LOCALE
+  );
+}
+
+/** The saved override, the OS locale, and the bundle they resolve to. */
+export function describeLocale(requested: string | null, systemLocale: string): LocaleInfo {
+  return {
+    requested,
+    systemLocale,
+    resolved: resolveMessageLocale(requested ?? systemLocale),
+    supported: [...SUPPORTED_MESSAGE_LOCALES],
+  };
+}
+
+function interpolate(template: string, params: MessageParams): string {
+  return template.replace(/\{(\w+)\}/g, (placeholder, name: string) =>
+    name in params ? String(params[name]) : placeholder,
+  );
+}
+
+function errorParams(error: Error): MessageParams {
+  const params: MessageParams = {};
+  for (const [name, value] of Object.entries(error)) {
+    if (typeof value === "string" || typeof value === "number") {
+      params[name] = value;
+    }
+  }
+  return params;
+}
+
+/**
+ * Message bundles for the strings the main process shows users: IPC error
+ * messages, notifications, and tray labels. A key missing from the active
+ * locale falls back to English, so a partial bundle never fails a lookup.
+ */
+export class MessageCatalog {
+  private locale: MessageLocale = DEFAULT_MESSAGE_LOCALE;
+
+  getLocale(): MessageLocale {
+    return this.locale;
+  }
+
+  /** Switches to the closest bundled locale and returns it. */
+  setLocale(tag: string | null | undefined): MessageLocale {
+    this.locale = resolveMessageLocale(tag);
+    return this.locale;
+  }
+
+  t(key: MessageKey, params: MessageParams = {}): string {
+    const template = this.locale === "en" ? EN[key] : (BUNDLES[this.locale][key] ?? EN[key]);
+    return interpolate(template, params);
+  }
+
+  /**
+   * Rewrites a coded error's message in the active locale, keeping its name
+   * and code so callers can still branch on them. English, uncoded, and
+   * untranslated errors pass through with their original message.
+   */
+  localizeError(error: Error): Error {
+    const code = (error as { code?: unknown }).code;
+    if (this.locale === "en" || typeof code !== "string") {
+      return error;
+    }
+    const template = BUNDLES[this.locale][`error.${code}` as MessageKey];
+    if (!template) {
+      return error;
+    }
+    const localized = new Error(interpolate(template, errorParams(error)));
+    localized.name = error.name;
+    return Object.assign(localized, { code });
+  }
+}
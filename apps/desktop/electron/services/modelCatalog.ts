@@ -0,0 +1,275 @@
+import fs from "node:fs/promises";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import { type ModelPricing, resolveModelPricing } from "../../../../src/session/pricing";
+import type { ProviderName } from "../../../../src/types";
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import type {
+  AvailableModel,
+  AvailableModelsResult,
+  ListAvailableModelsInput,
+  ModelPricingTier,
+} from "../../src/lib/desktopApi";
+import {
+  type ConnectDesktopSmokeJsonRpcOptions,
+  connectDesktopSmokeJsonRpc,
+  type DesktopSmokeJsonRpcConnection,
+} from "./desktopSmoke";
+
+const DEFAULT_TTL_MS = 6 * 60 * 60 * 1000;
+const CATALOG_READ_TIMEOUT_MS = 30_000;
+const CLIENT_NAME = "desktop-model-catalog";
+
+type CachedProviderModels = Omit<AvailableModelsResult, "expiresAt" | "stale">;
+
+type ModelCatalogFile = {
+  version: 1;
+  providers: Partial<Record<ProviderName, CachedProviderModels>>;
+};
+
+type CatalogModelEntry = {
+  id: string;
+  displayName?: string;
+  description?: string;
+  supportsImageInput?: boolean;
+  reasoning?: unknown;
+  runtimeOptions?: Record<string, unknown>;
+};
+
+type CatalogProviderEntry = {
+  id: string;
+  models?: CatalogModelEntry[];
+  defaultModel?: string;
+  state?: AvailableModelsResult["state"];
+  message?: string;
+};
+
+type ModelCatalogServiceOptions = {
+  filePath: string;
+  clientVersion: string;
+  loadState: () => Promise<PersistedState>;
+  /** Starts the workspace's server, or returns the one already running. */
+  startWorkspaceServer: (
+    workspace: WorkspaceRecord,
+    state: PersistedState,
+  ) => Promise<{ url: string; authToken: string | null }>;
+  connectJsonRpc?: (
+    options: ConnectDesktopSmokeJsonRpcOptions,
+  ) => Promise<DesktopSmokeJsonRpcConnection>;
+  resolvePricing?: (provider: ProviderName, model: string) => ModelPricing | null;
+  ttlMs?: number;
+  now?: () => Date;
+};
+
+export class UnknownModelError extends Error {
+  readonly code = "unknown_model";
+
+  constructor(
+    readonly provider: ProviderName,
+    readonly model: string,
+  ) {
+    super(`${model} is not an available ${provider} model`);
+    this.name = "UnknownModelError";
+  }
+}
+
+/** Buckets by output price, which dominates the cost of agent turns. */
+export function pricingTierFor(pricing: ModelPricing | null): ModelPricingTier | null {
+  if (!pricing) {
+    return null;
+  }
+  if (pricing.inputPerMillion === 0 && pricing.outputPerMillion === 0) {
+    return "free";
+  }
+  if (pricing.outputPerMillion <= 2) {
+    return "low";
+  }
+  return pricing.outputPerMillion <= 15 ? "standard" : "premium";
+}
+
+function readContextWindow(entry: CatalogModelEntry): number | null {
+  const value = entry.runtimeOptions?.contextWindow ?? entry.runtimeOptions?.contextLength;
+  return typeof value === "number" && Number.isFinite(value) && value > 0 ? value : null;
+}
+
+function parseCatalogFile(value: unknown): ModelCatalogFile["providers"] {
+  const file = value as { version?: unknown; providers?: unknown } | null;
+  if (file?.version !== 1 || typeof file.providers !== "object" || file.providers === null) {
+    return {};
+  }
+  return file.providers as ModelCatalogFile["providers"];
+}
+
+/**
+ * Lists each provider's models from a workspace server's provider catalog and
+ * caches them in userData, so model pickers open without a server round trip
+ * and model choices can be checked in the main process. An expired entry is
+ * still served, marked stale, when the refresh fails.
+ */
+export class ModelCatalogService {
+  private cache: ModelCatalogFile["providers"] | null = null;
+  private readonly inflight = new Map<ProviderName, Promise<CachedProviderModels>>();
+  private readonly ttlMs: number;
+
+  constructor(private readonly options: ModelCatalogServiceOptions) {
+    this.ttlMs = options.ttlMs ?? DEFAULT_TTL_MS;
+  }
+
+  async list(input: ListAvailableModelsInput): Promise<AvailableModelsResult> {
+    const cached = (await this.loadCache())[input.provider];
+    if (cached && !input.refresh && !this.isExpired(cached)) {
+      return this.toResult(cached, false);
+    }
+    try {
+      return this.toResult(await this.fetch(input), false);
+    } catch (error) {
+      if (cached) {
+        return this.toResult(cached, true);
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Throws when the cached catalog for `provider` lists models and `model` is
+   * not one of them. The catalog includes added custom models; providers not
+   * fetched yet are not checked.
+   */
+  async assertKnownModel(provider: ProviderName, model: string): Promise<void> {
+    const cached = (await this.loadCache())[provider];
+    if (!cached || cached.models.length === 0) {
+      return;
+    }
+    if (!cached.models.some((entry) => entry.id === model)) {
+      throw new UnknownModelError(provider, model);
+    }
+  }
+
+  private fetch(input: ListAvailableModelsInput): Promise<CachedProviderModels> {
+    const pending = this.inflight.get(input.provider);
+    if (pending) {
+      return pending;
+    }
+    const request = this.fetchFromServer(input).finally(() => {
+      this.inflight.delete(input.provider);
+    });
+    this.inflight.set(input.provider, request);
+    return request;
+  }
+
+  private async fetchFromServer(input: ListAvailableModelsInput): Promise<CachedProviderModels> {
+    const entry = await this.readCatalogEntry(input);
+    const models = (entry.models ?? []).map((model) =>
+      this.toAvailableModel(input.provider, entry, model),
+    );
+    const next: CachedProviderModels = {
+      provider: input.provider,
+      models,
+      defaultModel: entry.defaultModel || null,
+      state: entry.state ?? (models.length > 0 ? "ready" : "empty"),
+      fetchedAt: this.now().toISOString(),
+    };
+    // An unreachable provider reports no models; keep serving the last good list.
+    if (next.state === "unreachable") {
+      throw new Error(entry.message ?? `${input.provider} is unreachable`);
+    }
+    const cache = await this.loadCache();
+    cache[input.provider] = next;
+    await this.saveCache(cache);
+    return next;
+  }
+
+  private async readCatalogEntry(input: ListAvailableModelsInput): Promise<CatalogProviderEntry> {
+    const state = await this.options.loadState();
+    const workspace = input.workspaceId
+      ? state.workspaces.find((candidate) => candidate.id === input.workspaceId)
+      : state.workspaces[0];
+    if (!workspace) {
+      throw new Error(
+        input.workspaceId
+          ? `Unknown workspace: ${input.workspaceId}`
+          : "Add a workspace to load the model catalog",
+      );
+    }
+    const server = await this.options.startWorkspaceServer(workspace, state);
+    const rpc = await (this.options.connectJsonRpc ?? connectDesktopSmokeJsonRpc)({
+      url: server.url,
+      clientVersion: this.options.clientVersion,
+      clientName: CLIENT_NAME,
+      authToken: server.authToken,
+    });
+    let entry: CatalogProviderEntry | undefined;
+    try {
+      const response = await rpc.sendRequest(
+        "cowork/provider/catalog/read",
+        { cwd: workspace.path, ...(input.refresh ? { refresh: true } : {}) },
+        { timeoutMs: CATALOG_READ_TIMEOUT_MS },
+      );
+      if (response.error) {
+        throw new Error(response.error.message ?? "Provider catalog request failed");
+      }
+      const event = (response.result as { event?: { all?: CatalogProviderEntry[] } } | undefined)
+        ?.event;
+      entry = event?.all?.find((candidate) => candidate.id === input.provider);
+    } finally {
+      rpc.close();
+    }
+    if (!entry) {
+      throw new Error(`The server's catalog does not include ${input.provider}`);
+    }
+    return entry;
+  }
+
+  private toAvailableModel(
+    provider: ProviderName,
+    entry: CatalogProviderEntry,
+    model: CatalogModelEntry,
+  ): AvailableModel {
+    const resolvePricing = this.options.resolvePricing ?? resolveModelPricing;
+    return {
+      id: model.id,
+      displayName: model.displayName || model.id,
+      description: model.description ?? null,
+      contextWindow: readContextWindow(model),
+      modalities: model.supportsImageInput ? ["text", "image"] : ["text"],
+      pricingTier: pricingTierFor(resolvePricing(provider, model.id)),
+      supportsReasoning: model.reasoning !== undefined,
+      isDefault: model.id === entry.defaultModel,
+    };
+  }
+
+  private toResult(cached: CachedProviderModels, stale: boolean): AvailableModelsResult {
+    return {
+      ...cached,
+      models: cached.models.map((model) => ({ ...model, modalities: [...model.modalities] })),
+      expiresAt: new Date(Date.parse(cached.fetchedAt) + this.ttlMs).toISOString(),
+      stale,
+    };
+  }
+
+  private isExpired(cached: CachedProviderModels): boolean {
+    const fetchedAt = Date.parse(cached.fetchedAt);
+    return Number.isNaN(fetchedAt) || this.now().getTime() - fetchedAt >= this.ttlMs;
+  }
+
+  private async loadCache(): Promise<ModelCatalogFile["providers"]> {
+    if (this.cache) {
+      return this.cache;
+    }
+    try {
+      this.cache = parseCatalogFile(JSON.parse(await fs.readFile(this.options.filePath, "utf8")));
+    } catch {
+      this.cache = {};
+    }
+    return this.cache;
+  }
+
+  private async saveCache(providers: ModelCatalogFile["providers"]): Promise<void> {
+    const file: ModelCatalogFile = { version: 1, providers };
+    await writeFileAtomic(this.options.filePath, `${JSON.stringify(file, null, 2)}\n`);
+  }
+
+  private now(): Date {
+    return this.options.now?.() ?? new Date();
+  }
+}
@@ -0,0 +1,307 @@
+import fs from "node:fs/promises";
+import http from "node:http";
+import https from "node:https";
+import type { Socket } from "node:net";
+import path from "node:path";
+import tls from "node:tls";
+
+import type {
+  AppSettings,
+  ProxyConnectivityResult,
+  TestProxyConnectivityInput,
+} from "../../src/lib/desktopApi";
+
+const DEFAULT_CONNECTIVITY_TIMEOUT_MS = 10_000;
+const PEM_CERTIFICATE_MARKER = "-----BEGIN CERTIFICATE-----";
+
+type NetworkSettings = Pick<AppSettings, "httpProxy" | "httpsProxy" | "noProxy" | "caBundlePath">;
+
+type ProxyConnectivityOptions = {
+  /** Environment the settings are layered over; defaults to this process's. */
+  env?: NodeJS.ProcessEnv;
+  timeoutMs?: number;
+  now?: () => number;
+  readFile?: (filePath: string) => Promise<string>;
+};
+
+/**
+ * Maps the proxy and CA settings onto the variables workspace servers read.
+ * Unset values are left out so the inherited environment still applies.
+ */
+export function proxyEnvFromSettings(settings: NetworkSettings): Record<string, string> {
+  const env: Record<string, string> = {};
+  const assign = (names: string[], value: string | null): void => {
+    if (!value) {
+      return;
+    }
+    for (const name of names) {
+      env[name] = value;
+    }
+  };
+  assign(["HTTP_PROXY", "http_proxy"], settings.httpProxy);
+  assign(["HTTPS_PROXY", "https_proxy"], settings.httpsProxy);
+  assign(["NO_PROXY", "no_proxy"], settings.noProxy);
+  assign(["NODE_EXTRA_CA_CERTS"], settings.caBundlePath);
+  return env;
+}
+
+async function readCaBundle(
+  caBundlePath: string,
+  readFile: (filePath: string) => Promise<string>,
+): Promise<string> {
+  if (!path.isAbsolute(caBundlePath)) {
+    throw new Error(`CA bundle path must be absolute: ${caBundlePath}`);
+  }
+  let contents: string;
+  try {
+    contents = await readFile(caBundlePath);
+  } catch (error) {
+    const detail = error instanceof Error ? error.message : String(error);
+    throw new Error(`CA bundle ${caBundlePath} could not be read: ${detail}`);
+  }
+  if (!contents.includes(PEM_CERTIFICATE_MARKER)) {
+    throw new Error(`CA bundle ${caBundlePath} does not contain a PEM certificate`);
+  }
+  return contents;
+}
+
+/** Rejects a newly chosen CA bundle that is missing, unreadable, or not PEM. */
+export async function validateNetworkSettings(
+  next: AppSettings,
+  current: AppSettings,
+  readFile: (filePath: string) => Promise<string> = (filePath) => fs.readFile(filePath, "utf8"),
+): Promise<void> {
+  if (next.caBundlePath && next.caBundlePath !== current.caBundlePath) {
+    await readCaBundle(next.caBundlePath, readFile);
+  }
+}
+
+function unbracketHost(host: string): string {
+  return host.replace(/^\[(.*)\]$/, "$1").toLowerCase();
+}
+
+function defaultPort(url: URL): string {
+  return url.port || (url.protocol === "https:" ? "443" : "80");
+}
+
+/**
+ * Matches `NO_PROXY` the way most clients do: `*` bypasses everything, and an
+ * entry matches its host and any subdomain, optionally pinned to a port.
+ * CIDR ranges are not supported.
+ */
+export function shouldBypassProxy(target: URL, noProxy: string | undefined): boolean {
+  if (!noProxy) {
+    return false;
+  }
+  const hostname = unbracketHost(target.hostname);
+  const port = defaultPort(target);
+  return noProxy.split(/[\s,]+/).some((rawEntry) => {
+    const entry = rawEntry.trim().toLowerCase();
+    if (!entry) {
+      return false;
+    }
+    if (entry === "*") {
+      return true;
+    }
+    const match = /^(\[[^\]]+\]|[^:]+)(?::(\d+))?$/.exec(entry);
+    const entryHost = unbracketHost(match?.[1] ?? entry).replace(/^\*/, "");
+    const entryPort = match?.[2];
+    if (entryPort && entryPort !== port) {
+      return false;
+    }
+    if (entryHost.startsWith(".")) {
+      return hostname.endsWith(entryHost);
+    }
+    return hostname === entryHost || hostname.endsWith(`.${entryHost}`);
+  });
+}
+
+/** Picks the proxy a workspace server would use for `target`, or null for a direct request. */
+export function resolveProxyForUrl(target: URL, env: NodeJS.ProcessEnv): URL | null {
+  const noProxy = env.NO_PROXY ?? env.no_proxy;
+  if (shouldBypassProxy(target, noProxy)) {
+    return null;
+  }
+  const raw =
+    target.protocol === "https:"
+      ? (env.HTTPS_PROXY ?? env.https_proxy)
+      : (env.HTTP_PROXY ?? env.http_proxy);
+  if (!raw) {
+    return null;
+  }
+  try {
+    return new URL(raw.includes("://") ? raw : `http://${raw}`);
+  } catch {
+    throw new Error(`Proxy URL is invalid: ${redactUrl(raw)}`);
+  }
+}
+
+function redactUrl(raw: string): string {
+  try {
+    const parsed = new URL(raw);
+    parsed.username = "";
+    parsed.password = "";
+    return parsed.href.replace(/\/$/, "");
+  } catch {
+    return raw.replace(/\/\/[^@/]*@/, "//");
+  }
+}
+
+function proxyAuthHeaders(proxy: URL): Record<string, string> {
+  if (!proxy.username) {
+    return {};
+  }
+  const credentials = `${decodeURIComponent(proxy.username)}:${decodeURIComponent(proxy.password)}`;
+  return { "proxy-authorization": `Basic ${Buffer.from(credentials).toString("base64")}` };
+}
+
+function clientFor(url: URL): typeof http | typeof https {
+  return url.protocol === "https:" ? https : http;
+}
+
+function sendHeadRequest(
+  client: typeof http | typeof https,
+  options: https.RequestOptions,
+  timeoutMs: number,
+): Promise<number> {
+  return new Promise((resolve, reject) => {
+    const request = client.request({ ...options, method: "HEAD", timeout: timeoutMs });
+    request.once("response", (response) => {
+      response.resume();
+      resolve(response.statusCode ?? 0);
+    });
+    request.once("timeout", () => {
+      request.destroy(new Error(`Timed out after ${timeoutMs}ms`));
+    });
+    request.once("error", reject);
+    request.end();
+  });
+}
+
+function openTunnel(
+  proxy: URL,
+  target: URL,
+  ca: string[] | undefined,
+  timeoutMs: number,
+): Promise<Socket> {
+  const authority = `${target.hostname}:${defaultPort(target)}`;
+  return new Promise((resolve, reject) => {
+    const request = clientFor(proxy).request({
+      host: unbracketHost(proxy.hostname),
+      port: defaultPort(proxy),
+      method: "CONNECT",
+      path: authority,
+      headers: { host: authority, ...proxyAuthHeaders(proxy) },
+      agent: false,
+      ca,
+      timeout: timeoutMs,
+    });
+    request.once("connect", (response, socket) => {
+      if (response.statusCode === 200) {
+        resolve(socket);
+        return;
+      }
+      socket.destroy();
+      reject(new Error(`Proxy refused the tunnel with HTTP ${response.statusCode}`));
+    });
+    request.once("timeout", () => {
+      request.destroy(new Error(`Timed out after ${timeoutMs}ms waiting for the proxy`));
+    });
+    request.once("error", reject);
+    request.end();
+  });
+}
+
+function urlToOptions(url: URL): https.RequestOptions {
+  return {
+    protocol: url.protocol,
+    host: unbracketHost(url.hostname),
+    port: defaultPort(url),
+    path: `${url.pathname}${url.search}`,
+  };
+}
+
+async function probe(
+  target: URL,
+  proxy: URL | null,
+  ca: string[] | undefined,
+  timeoutMs: number,
+): Promise<number> {
+  if (!proxy) {
+    return await sendHeadRequest(
+      clientFor(target),
+      { ...urlToOptions(target), agent: false, ca },
+      timeoutMs,
+    );
+  }
+  if (target.protocol === "http:") {
+    return await sendHeadRequest(
+      clientFor(proxy),
+      {
+        host: unbracketHost(proxy.hostname),
+        port: defaultPort(proxy),
+        path: target.href,
+        headers: { host: target.host, ...proxyAuthHeaders(proxy) },
+        agent: false,
+        ca,
+      },
+      timeoutMs,
+    );
+  }
+  const socket = await openTunnel(proxy, target, ca, timeoutMs);
+  // Leaving `agent` unset lets the request run over the tunnelled socket.
+  return await sendHeadRequest(
+    https,
+    {
+      ...urlToOptions(target),
+      createConnection: () =>
+        tls.connect({ socket, servername: unbracketHost(target.hostname), ca }),
+    },
+    timeoutMs,
+  );
+}
+
+/**
+ * Sends a HEAD request to `url` with the proxy, bypass list, and CA bundle a
+ * workspace server would get, and reports whether the target answered.
+ */
+export async function testProxyConnectivity(
+  input: TestProxyConnectivityInput,
+  settings: AppSettings,
+  options: ProxyConnectivityOptions = {},
+): Promise<ProxyConnectivityResult> {
+  const now = options.now ?? Date.now;
+  const timeoutMs = options.timeoutMs ?? DEFAULT_CONNECTIVITY_TIMEOUT_MS;
+  const readFile = options.readFile ?? ((filePath: string) => fs.readFile(filePath, "utf8"));
+  const env = { ...(options.env ?? process.env), ...proxyEnvFromSettings(settings) };
+  const target = new URL(input.url);
+  const startedAt = now();
+  let proxy: URL | null = null;
+  const result = (
+    fields: Pick<ProxyConnectivityResult, "ok" | "httpStatus" | "message">,
+  ): ProxyConnectivityResult => ({
+    url: target.href,
+    proxy: proxy ? redactUrl(proxy.href) : null,
+    latencyMs: Math.max(0, Math.round(now() - startedAt)),
+    ...fields,
+  });
+
+  try {
+    proxy = resolveProxyForUrl(target, env);
+    const caPath = env.NODE_EXTRA_CA_CERTS;
+    const ca = caPath ? [...tls.rootCertificates, await readCaBundle(caPath, readFile)] : undefined;
+    const httpStatus = await probe(target, proxy, ca, timeoutMs);
+    const route = proxy ? `through ${redactUrl(proxy.href)}` : "directly";
+    return result({
+      ok: true,
+      httpStatus,
+      message: `Reached ${target.host} ${route} (HTTP ${httpStatus})`,
+    });
+  } catch (error) {
+    return result({
+      ok: false,
+      httpStatus: null,
+      message: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
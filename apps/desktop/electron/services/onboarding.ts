@@ -0,0 +1,284 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+import path from "node:path";
+import { promisify } from "node:util";
+
+import type { safeStorage } from "electron";
+
+import type { PersistedState } from "../../src/app/types";
+import type {
+  DesktopOnboardingStatus,
+  EnvironmentCheck,
+  EnvironmentCheckReport,
+  EnvironmentCheckStatus,
+  InstallationIntegrityReport,
+} from "../../src/lib/desktopApi";
+
+const execFileAsync = promisify(execFile);
+
+const COMMAND_TIMEOUT_MS = 5_000;
+const KEYCHAIN_PROBE = "cowork-keychain-check";
+const STATUS_RANK: Record<EnvironmentCheckStatus, number> = { pass: 0, warn: 1, fail: 2 };
+
+export type OnboardingKeychain = Pick<
+  typeof safeStorage,
+  "isEncryptionAvailable" | "encryptString" | "decryptString"
+> &
+  Partial<Pick<typeof safeStorage, "getSelectedStorageBackend">>;
+
+type OnboardingServiceOptions = {
+  loadState: () => Promise<PersistedState>;
+  /** Directories the app must be able to write to, such as userData. */
+  writableDirs: string[];
+  isPackaged: boolean;
+  verifyInstallation: () => Promise<InstallationIntegrityReport>;
+  keychain: OnboardingKeychain;
+  /** Runs a command and resolves its stdout; rejects when it is missing or fails. */
+  runCommand?: (command: string, args: string[]) => Promise<string>;
+  platform?: NodeJS.Platform;
+  now?: () => Date;
+};
+
+async function runCommand(command: string, args: string[]): Promise<string> {
+  const { stdout } = await execFileAsync(command, args, {
+    timeout: COMMAND_TIMEOUT_MS,
+    windowsHide: true,
+  });
+  return stdout;
+}
+
+function errorMessage(error: unknown): string {
+  const code = (error as NodeJS.ErrnoException | null)?.code;
+  if (typeof code === "string") {
+    return code;
+  }
+  return error instanceof Error ? error.message : String(error);
+}
+
+export function worstEnvironmentStatus(checks: EnvironmentCheck[]): EnvironmentCheckStatus {
+  return checks.reduce<EnvironmentCheckStatus>(
+    (worst, check) => (STATUS_RANK[check.status] > STATUS_RANK[worst] ? check.status : worst),
+    "pass",
+  );
+}
+
+/**
+ * Backs the first-run setup checklist: whether any workspace exists yet, and
+ * checks of the runtimes, bundled server, keychain, and folders the app needs
+ * before the first workspace is created. Each check reports pass, warn, or
+ * fail with a summary and, when something is wrong, what to do about it.
+ */
+export class OnboardingService {
+  private lastReport: EnvironmentCheckReport | null = null;
+  private inflight: Promise<EnvironmentCheckReport> | null = null;
+
+  constructor(private readonly options: OnboardingServiceOptions) {}
+
+  async getStatus(): Promise<DesktopOnboardingStatus> {
+    const state = await this.options.loadState();
+    return {
+      firstRun: state.workspaces.length === 0,
+      workspaceCount: state.workspaces.length,
+      onboarding: state.onboarding ?? null,
+      environment: this.lastReport,
+    };
+  }
+
+  runEnvironmentChecks(): Promise<EnvironmentCheckReport> {
+    this.inflight ??= this.collect().finally(() => {
+      this.inflight = null;
+    });
+    return this.inflight;
+  }
+
+  private async collect(): Promise<EnvironmentCheckReport> {
+    const checks = await Promise.all([
+      this.checkBun(),
+      this.checkGit(),
+      this.checkSidecar(),
+      this.checkKeychain(),
+      this.checkWritePermissions(),
+    ]);
+    const report: EnvironmentCheckReport = {
+      status: worstEnvironmentStatus(checks),
+      checks,
+      checkedAt: (this.options.now?.() ?? new Date()).toISOString(),
+    };
+    this.lastReport = report;
+    return report;
+  }
+
+  private async readVersion(command: string): Promise<string | null> {
+    try {
+      const stdout = await (this.options.runCommand ?? runCommand)(command, ["--version"]);
+      return stdout.trim().split(/\r?\n/)[0] ?? "";
+    } catch {
+      return null;
+    }
+  }
+
+  private async checkBun(): Promise<EnvironmentCheck> {
+    const version = await this.readVersion("bun");
+    if (version !== null) {
+      return {
+        id: "bun",
+        label: "Bun",
+        status: "pass",
+        summary: `Bun ${version} is installed.`,
+        recommendation: null,
+      };
+    }
+    if (this.options.isPackaged) {
+      return {
+        id: "bun",
+        label: "Bun",
+        status: "pass",
+        summary: "Bun is not installed; the bundled server does not need it.",
+        recommendation: null,
+      };
+    }
+    return {
+      id: "bun",
+      label: "Bun",
+      status: "fail",
+      summary: "Bun was not found, and development builds run the server with it.",
+      recommendation: "Install Bun from https://bun.sh and make sure it is on your PATH.",
+    };
+  }
+
+  private async checkGit(): Promise<EnvironmentCheck> {
+    const version = await this.readVersion("git");
+    if (version !== null) {
+      return {
+        id: "git",
+        label: "Git",
+        status: "pass",
+        summary: `${version.replace(/^git version\s*/i, "Git ")} is installed.`,
+        recommendation: null,
+      };
+    }
+    return {
+      id: "git",
+      label: "Git",
+      status: "warn",
+      summary: "Git was not found, so checkpoints and change tracking are unavailable.",
+      recommendation: "Install Git and make sure it is on your PATH.",
+    };
+  }
+
+  private async checkSidecar(): Promise<EnvironmentCheck> {
+    let report: InstallationIntegrityReport;
+    try {
+      report = await this.options.verifyInstallation();
+    } catch (error) {
+      return {
+        id: "sidecar",
+        label: "Agent server",
+        status: "fail",
+        summary: `The agent server could not be checked: ${errorMessage(error)}.`,
+        recommendation: "Reinstall the app.",
+      };
+    }
+    if (!report.packaged) {
+      return {
+        id: "sidecar",
+        label: "Agent server",
+        status: "pass",
+        summary: "Development builds run the agent server from source.",
+        recommendation: null,
+      };
+    }
+    if (report.status === "failed") {
+      const broken = report.resources
+        .filter((resource) => resource.status === "missing" || resource.status === "mismatch")
+        .map((resource) => resource.name);
+      return {
+        id: "sidecar",
+        label: "Agent server",
+        status: "fail",
+        summary: `The bundled agent server is missing or altered: ${broken.join(", ")}.`,
+        recommendation: "Reinstall the app to restore the bundled server.",
+      };
+    }
+    if (report.status === "unverified") {
+      return {
+        id: "sidecar",
+        label: "Agent server",
+        status: "warn",
+        summary: "The bundled agent server is present but has no checksums to verify it against.",
+        recommendation: null,
+      };
+    }
+    return {
+      id: "sidecar",
+      label: "Agent server",
+      status: "pass",
+      summary: "The bundled agent server matches its recorded checksums.",
+      recommendation: null,
+    };
+  }
+
+  private async checkKeychain(): Promise<EnvironmentCheck> {
+    const { keychain } = this.options;
+    const unavailable = (summary: string): EnvironmentCheck => ({
+      id: "keychain",
+      label: "Keychain",
+      status: "warn",
+      summary,
+      recommendation:
+        (this.options.platform ?? process.platform) === "linux"
+          ? "Install and unlock a Secret Service keyring such as GNOME Keyring or KWallet."
+          : "Unlock the system keychain and allow the app to use it.",
+    });
+    if (!keychain.isEncryptionAvailable()) {
+      return unavailable("The OS keychain is not available, so transcripts cannot be encrypted.");
+    }
+    if (keychain.getSelectedStorageBackend?.() === "basic_text") {
+      return unavailable("No OS keyring was found, so secrets would be stored unprotected.");
+    }
+    try {
+      if (keychain.decryptString(keychain.encryptString(KEYCHAIN_PROBE)) !== KEYCHAIN_PROBE) {
+        throw new Error("round trip mismatch");
+      }
+    } catch (error) {
+      return unavailable(`The OS keychain refused access: ${errorMessage(error)}.`);
+    }
+    return {
+      id: "keychain",
+      label: "Keychain",
+      status: "pass",
+      summary: "The OS keychain is available for protecting secrets.",
+      recommendation: null,
+    };
+  }
+
+  private async checkWritePermissions(): Promise<EnvironmentCheck> {
+    const failures: string[] = [];
+    for (const dir of this.options.writableDirs) {
+      const probe = path.join(dir, `.write-check-${process.pid}`);
+      try {
+        await fs.mkdir(dir, { recursive: true });
+        await fs.writeFile(probe, "");
+        await fs.rm(probe, { force: true });
+      } catch (error) {
+        failures.push(`${dir} (${errorMessage(error)})`);
+      }
+    }
+    if (failures.length > 0) {
+      return {
+        id: "writePermissions",
+        label: "Write access",
+        status: "fail",
+        summary: `The app cannot write to ${failures.join(", ")}.`,
+        recommendation: "Fix the folder's permissions or free up disk space, then check again.",
+      };
+    }
+    return {
+      id: "writePermissions",
+      label: "Write access",
+      status: "pass",
+      summary: "The app can write to its data folders.",
+      recommendation: null,
+    };
+  }
+}
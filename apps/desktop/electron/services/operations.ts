@@ -0,0 +1,208 @@
+import crypto from "node:crypto";
+
+import type { OperationKind, OperationProgress, OperationSnapshot } from "../../src/lib/desktopApi";
+
+const DEFAULT_RETAINED_FINISHED_OPERATIONS = 50;
+
+/** Exports share one queue so two large archives never compete for the disk. */
+export const EXPORT_OPERATION_QUEUE = "export";
+
+function isUnfinished(snapshot: OperationSnapshot): boolean {
+  return snapshot.status === "running" || snapshot.status === "queued";
+}
+
+export type OperationContext = {
+  operationId: string;
+  /** Aborted when the operation is cancelled; pass it to fetch/streams where possible. */
+  signal: AbortSignal;
+  reportProgress: (progress: OperationProgress) => void;
+};
+
+type OperationEntry = {
+  snapshot: OperationSnapshot;
+  controller: AbortController;
+  queue?: string;
+  run: () => void;
+};
+
+/** The operation a queue is running and the ones waiting behind it. */
+type OperationQueue = {
+  active: OperationEntry;
+  waiting: OperationEntry[];
+};
+
+export type StartOperationOptions = {
+  /** Operations sharing a queue run one at a time, in the order they were started. */
+  queue?: string;
+};
+
+type OperationManagerOptions = {
+  /** `queue` is the queue the operation was started on, if any. */
+  emit?: (snapshot: OperationSnapshot, queue?: string) => void;
+  now?: () => Date;
+  createId?: () => string;
+  retainFinished?: number;
+};
+
+/**
+ * Runs long desktop commands in the background. Callers get an operation id
+ * straight away and follow progress through `emit`. Cancellation always wins:
+ * once `cancel` is called the operation reports `cancelled`, and whatever the
+ * task later returns or throws is dropped. An operation started on a busy queue
+ * reports `queued` until the one ahead of it has finished unwinding.
+ */
+export class OperationManager {
+  private readonly operations = new Map<string, OperationEntry>();
+  private readonly queues = new Map<string, OperationQueue>();
+  private readonly unsettled = new Set<Promise<void>>();
+  private readonly emit: (snapshot: OperationSnapshot, queue?: string) => void;
+  private readonly now: () => Date;
+  private readonly createId: () => string;
+  private readonly retainFinished: number;
+
+  constructor(options: OperationManagerOptions = {}) {
+    this.emit = options.emit ?? (() => {});
+    this.now = options.now ?? (() => new Date());
+    this.createId = options.createId ?? (() => `op-${crypto.randomUUID()}`);
+    this.retainFinished = Math.max(
+      0,
+      options.retainFinished ?? DEFAULT_RETAINED_FINISHED_OPERATIONS,
+    );
+  }
+
+  start(
+    kind: OperationKind,
+    task: (context: OperationContext) => Promise<unknown>,
+    options: StartOperationOptions = {},
+  ): OperationSnapshot {
+    const operationId = this.createId();
+    const controller = new AbortController();
+    const queue = options.queue ? this.queues.get(options.queue) : undefined;
+    const entry: OperationEntry = {
+      snapshot: {
+        operationId,
+        kind,
+        status: queue ? "queued" : "running",
+        progress: null,
+        result: null,
+        error: null,
+        startedAt: this.now().toISOString(),
+        finishedAt: null,
+      },
+      controller,
+      queue: options.queue,
+      run: () => {
+        const context: OperationContext = {
+          operationId,
+          signal: controller.signal,
+          reportProgress: (progress) => {
+            if (entry.snapshot.status === "running") {
+              this.update(entry, { progress });
+            }
+          },
+        };
+        const settled = (async () => {
+          try {
+            const result = await task(context);
+            this.finish(entry, { status: "succeeded", result: result ?? null });
+          } catch (error) {
+            this.finish(entry, {
+              status: "failed",
+              error: error instanceof Error ? error.message : String(error),
+            });
+          }
+        })();
+        this.unsettled.add(settled);
+        void settled.finally(() => {
+          this.unsettled.delete(settled);
+          this.runNext(entry);
+        });
+      },
+    };
+    this.operations.set(operationId, entry);
+    this.emit(entry.snapshot, entry.queue);
+
+    if (queue) {
+      queue.waiting.push(entry);
+    } else {
+      if (options.queue) {
+        this.queues.set(options.queue, { active: entry, waiting: [] });
+      }
+      entry.run();
+    }
+    return entry.snapshot;
+  }
+
+  get(operationId: string): OperationSnapshot | null {
+    return this.operations.get(operationId)?.snapshot ?? null;
+  }
+
+  list(): OperationSnapshot[] {
+    return [...this.operations.values()].map((entry) => entry.snapshot);
+  }
+
+  /** Returns false when the operation is unknown or already finished. */
+  cancel(operationId: string): boolean {
+    const entry = this.operations.get(operationId);
+    if (!entry || !isUnfinished(entry.snapshot)) {
+      return false;
+    }
+    if (entry.snapshot.status === "queued" && entry.queue) {
+      const queue = this.queues.get(entry.queue);
+      queue?.waiting.splice(queue.waiting.indexOf(entry), 1);
+    }
+    entry.controller.abort();
+    this.finish(entry, { status: "cancelled" });
+    return true;
+  }
+
+  cancelAll(): void {
+    for (const operationId of [...this.operations.keys()]) {
+      this.cancel(operationId);
+    }
+  }
+
+  /** Resolves once every task has returned, including cancelled ones still unwinding. */
+  async drain(): Promise<void> {
+    await Promise.all([...this.unsettled]);
+  }
+
+  /** Starts whatever is waiting behind `entry` once its task has returned. */
+  private runNext(entry: OperationEntry): void {
+    const queue = entry.queue ? this.queues.get(entry.queue) : undefined;
+    if (!entry.queue || queue?.active !== entry) {
+      return;
+    }
+    const next = queue.waiting.shift();
+    if (!next) {
+      this.queues.delete(entry.queue);
+      return;
+    }
+    queue.active = next;
+    this.update(next, { status: "running", startedAt: this.now().toISOString() });
+    next.run();
+  }
+
+  private finish(
+    entry: OperationEntry,
+    patch: Pick<OperationSnapshot, "status"> & Partial<Pick<OperationSnapshot, "result" | "error">>,
+  ): void {
+    if (!isUnfinished(entry.snapshot)) {
+      return;
+    }
+    this.update(entry, { ...patch, finishedAt: this.now().toISOString() });
+    this.pruneFinished();
+  }
+
+  private update(entry: OperationEntry, patch: Partial<OperationSnapshot>): void {
+    entry.snapshot = { ...entry.snapshot, ...patch };
+    this.emit(entry.snapshot, entry.queue);
+  }
+
+  private pruneFinished(): void {
+    const finished = [...this.operations.values()].filter((entry) => !isUnfinished(entry.snapshot));
+    for (const entry of finished.slice(0, Math.max(0, finished.length - this.retainFinished))) {
+      this.operations.delete(entry.snapshot.operationId);
+    }
+  }
+}
@@ -0,0 +1,314 @@
+import { execFile } from "node:child_process";
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import path from "node:path";
+import { promisify } from "node:util";
+import { z } from "zod";
+
+import type { OrphanedServer, OrphanedServerCleanupResult } from "../../src/lib/desktopApi";
+import { SIDECAR_BASE_NAME } from "./sidecar";
+
+const execFileAsync = promisify(execFile);
+
+export const SPAWNED_SERVERS_FILE = "spawned-servers.json";
+
+const PROCESS_LIST_COMMAND_TIMEOUT_MS = 10_000;
+/** Spawn times are recorded just after `spawn()` returns; allow for slow starts and rounding. */
+const START_TIME_TOLERANCE_MS = 30_000;
+
+const spawnedServerEntrySchema = z.object({
+  pid: z.number().int().positive(),
+  workspaceId: z.string().min(1),
+  sessionId: z.string().min(1),
+  startedAt: z.number(),
+});
+
+const spawnedServersFileSchema = z.object({
+  schemaVersion: z.literal(1),
+  servers: z.array(spawnedServerEntrySchema),
+});
+
+export type SpawnedServerEntry = z.infer<typeof spawnedServerEntrySchema>;
+
+export type RunningProcess = {
+  pid: number;
+  /** Epoch milliseconds, or null when the platform did not report it. */
+  startedAt: number | null;
+  command: string;
+};
+
+export type ProcessLister = () => Promise<RunningProcess[]>;
+
+/**
+ * Records every workspace server this app instance spawns, tagged with a
+ * per-launch session id, so the next launch can tell which still-running
+ * servers were left behind by a crash. The file is rewritten as servers exit.
+ */
+export class SpawnedServerLedger {
+  readonly sessionId: string;
+  private writeChain: Promise<void> = Promise.resolve();
+
+  constructor(
+    private readonly userDataDir: string,
+    sessionId: string = crypto.randomUUID(),
+  ) {
+    this.sessionId = sessionId;
+  }
+
+  get filePath(): string {
+    return path.join(this.userDataDir, SPAWNED_SERVERS_FILE);
+  }
+
+  async list(): Promise<SpawnedServerEntry[]> {
+    await this.writeChain;
+    return await this.readEntries();
+  }
+
+  record(entry: Omit<SpawnedServerEntry, "sessionId">): Promise<void> {
+    return this.update((entries) => [
+      ...entries.filter((existing) => existing.pid !== entry.pid),
+      { ...entry, sessionId: this.sessionId },
+    ]);
+  }
+
+  remove(pids: Iterable<number>): Promise<void> {
+    const removed = new Set(pids);
+    return this.update((entries) => entries.filter((entry) => !removed.has(entry.pid)));
+  }
+
+  private update(
+    transform: (entries: SpawnedServerEntry[]) => SpawnedServerEntry[],
+  ): Promise<void> {
+    const next = this.writeChain.then(async () => {
+      await this.writeEntries(transform(await this.readEntries()));
+    });
+    this.writeChain = next.catch(() => {});
+    return next;
+  }
+
+  private async readEntries(): Promise<SpawnedServerEntry[]> {
+    try {
+      const parsed = spawnedServersFileSchema.safeParse(
+        JSON.parse(await fs.readFile(this.filePath, "utf8")),
+      );
+      return parsed.success ? parsed.data.servers : [];
+    } catch {
+      return [];
+    }
+  }
+
+  private async writeEntries(entries: SpawnedServerEntry[]): Promise<void> {
+    if (entries.length === 0) {
+      await fs.rm(this.filePath, { force: true });
+      return;
+    }
+    await fs.mkdir(this.userDataDir, { recursive: true });
+    const temporary = `${this.filePath}.tmp-${process.pid}`;
+    await fs.writeFile(
+      temporary,
+      `${JSON.stringify({ schemaVersion: 1, servers: entries }, null, 2)}\n`,
+      "utf8",
+    );
+    await fs.rename(temporary, this.filePath);
+  }
+}
+
+/** Parses `ps -x -o pid=,lstart=,command=`, whose `lstart` column is always 24 characters wide. */
+export function parsePsProcessList(stdout: string): RunningProcess[] {
+  const processes: RunningProcess[] = [];
+  for (const line of stdout.split("\n")) {
+    const match = /^\s*(\d+)\s+(\S.{23})\s+(.*)$/.exec(line);
+    if (!match) {
+      continue;
+    }
+    const [, pid, lstart = "", command = ""] = match;
+    const startedAt = Date.parse(lstart);
+    processes.push({
+      pid: Number(pid),
+      startedAt: Number.isFinite(startedAt) ? startedAt : null,
+      command: command.trim(),
+    });
+  }
+  return processes;
+}
+
+/** Parses the JSON printed by the Windows process query below. */
+export function parseWindowsProcessList(stdout: string): RunningProcess[] {
+  const trimmed = stdout.trim();
+  if (!trimmed) {
+    return [];
+  }
+  const parsed: unknown = JSON.parse(trimmed);
+  const rows = Array.isArray(parsed) ? parsed : [parsed];
+  return rows.flatMap((row) => {
+    const record = row as { ProcessId?: unknown; StartedAt?: unknown; CommandLine?: unknown };
+    if (typeof record.ProcessId !== "number") {
+      return [];
+    }
+    const startedAt = typeof record.StartedAt === "number" ? record.StartedAt : null;
+    const command = typeof record.CommandLine === "string" ? record.CommandLine : "";
+    return [{ pid: record.ProcessId, startedAt, command }];
+  });
+}
+
+/** Lists the current user's processes; on Windows only sidecar-named processes are queried. */
+export async function listRunningProcesses(
+  platform: NodeJS.Platform = process.platform,
+): Promise<RunningProcess[]> {
+  if (platform === "win32") {
+    const script =
+      `Get-CimInstance Win32_Process -Filter "Name LIKE '${SIDECAR_BASE_NAME}%'" | ` +
+      "Select-Object ProcessId, CommandLine, " +
+      "@{n='StartedAt';e={[DateTimeOffset]::new($_.CreationDate).ToUnixTimeMilliseconds()}} | " +
+      "ConvertTo-Json -Compress";
+    const { stdout } = await execFileAsync(
+      "powershell.exe",
+      ["-NoProfile", "-NonInteractive", "-Command", script],
+      { timeout: PROCESS_LIST_COMMAND_TIMEOUT_MS, windowsHide: true },
+    );
+    return parseWindowsProcessList(stdout);
+  }
+  const { stdout } = await execFileAsync("ps", ["-x", "-o", "pid=,lstart=,command="], {
+    timeout: PROCESS_LIST_COMMAND_TIMEOUT_MS,
+    env: { ...process.env, LC_ALL: "C" },
+    maxBuffer: 16 * 1024 * 1024,
+  });
+  return parsePsProcessList(stdout);
+}
+
+function isSidecarCommand(command: string): boolean {
+  // Windows quotes executable paths that contain spaces, such as `"C:\Program Files\..."`.
+  const executable = command.startsWith('"')
+    ? (command.slice(1).split('"', 1)[0] ?? "")
+    : (command.split(/\s+/, 1)[0] ?? "");
+  return path.basename(executable.replace(/\\/g, "/")).startsWith(SIDECAR_BASE_NAME);
+}
+
+function startTimeMatches(entry: SpawnedServerEntry, running: RunningProcess): boolean {
+  return (
+    running.startedAt === null ||
+    Math.abs(running.startedAt - entry.startedAt) <= START_TIME_TOLERANCE_MS
+  );
+}
+
+type FindOrphanedServersInput = {
+  ledger: SpawnedServerEntry[];
+  processes: RunningProcess[];
+  sessionId: string;
+  /** Servers this instance is running or adopting; never reported. */
+  protectedPids: ReadonlySet<number>;
+  selfPid?: number;
+};
+
+/**
+ * A server is orphaned when an earlier launch recorded it and it is still the
+ * same process (pid and start time agree), or when it is a sidecar binary that
+ * nothing in this launch owns. Ledger matches guard against pid reuse; the
+ * binary scan catches servers from launches that never wrote the ledger.
+ */
+export function findOrphanedServers(input: FindOrphanedServersInput): OrphanedServer[] {
+  const running = new Map(input.processes.map((entry) => [entry.pid, entry]));
+  const orphans = new Map<number, OrphanedServer>();
+  const isCandidate = (pid: number) =>
+    !input.protectedPids.has(pid) && pid !== (input.selfPid ?? process.pid);
+
+  for (const entry of input.ledger) {
+    const processInfo = running.get(entry.pid);
+    if (
+      entry.sessionId === input.sessionId ||
+      !processInfo ||
+      !isCandidate(entry.pid) ||
+      !startTimeMatches(entry, processInfo)
+    ) {
+      continue;
+    }
+    orphans.set(entry.pid, {
+      pid: entry.pid,
+      workspaceId: entry.workspaceId,
+      startedAt: new Date(processInfo.startedAt ?? entry.startedAt).toISOString(),
+      command: processInfo.command,
+    });
+  }
+
+  for (const processInfo of input.processes) {
+    if (orphans.has(processInfo.pid) || !isCandidate(processInfo.pid)) {
+      continue;
+    }
+    if (!isSidecarCommand(processInfo.command)) {
+      continue;
+    }
+    orphans.set(processInfo.pid, {
+      pid: processInfo.pid,
+      workspaceId: null,
+      startedAt:
+        processInfo.startedAt === null ? null : new Date(processInfo.startedAt).toISOString(),
+      command: processInfo.command,
+    });
+  }
+
+  return [...orphans.values()].sort((left, right) => left.pid - right.pid);
+}
+
+type OrphanedServerServiceOptions = {
+  ledger: SpawnedServerLedger;
+  /** Servers this launch spawned or adopted, plus adoptable sidecars it has yet to claim. */
+  getProtectedPids: () => Promise<Iterable<number>> | Iterable<number>;
+  listProcesses?: ProcessLister;
+  signalProcess?: (pid: number, signal: NodeJS.Signals) => void;
+};
+
+/** Finds and stops workspace servers left running by a previous launch. */
+export class OrphanedServerService {
+  private readonly listProcesses: ProcessLister;
+  private readonly signalProcess: (pid: number, signal: NodeJS.Signals) => void;
+
+  constructor(private readonly options: OrphanedServerServiceOptions) {
+    this.listProcesses = options.listProcesses ?? (() => listRunningProcesses());
+    this.signalProcess =
+      options.signalProcess ?? ((pid, signal) => void process.kill(pid, signal));
+  }
+
+  async list(): Promise<OrphanedServer[]> {
+    const [ledger, processes, protectedPids] = await Promise.all([
+      this.options.ledger.list(),
+      this.listProcesses(),
+      this.options.getProtectedPids(),
+    ]);
+    const orphans = findOrphanedServers({
+      ledger,
+      processes,
+      sessionId: this.options.ledger.sessionId,
+      protectedPids: new Set(protectedPids),
+    });
+    // Entries from earlier launches whose process is gone are only noise now.
+    const alive = new Set(processes.map((entry) => entry.pid));
+    const stale = ledger
+      .filter((entry) => entry.sessionId !== this.options.ledger.sessionId)
+      .filter((entry) => !alive.has(entry.pid))
+      .map((entry) => entry.pid);
+    if (stale.length > 0) {
+      await this.options.ledger.remove(stale);
+    }
+    return orphans;
+  }
+
+  /** Re-scans before signalling so only servers that are still orphaned are stopped. */
+  async cleanup(): Promise<OrphanedServerCleanupResult> {
+    const orphans = await this.list();
+    const stopped: number[] = [];
+    const failed: OrphanedServerCleanupResult["failed"] = [];
+    for (const orphan of orphans) {
+      try {
+        this.signalProcess(orphan.pid, "SIGTERM");
+        stopped.push(orphan.pid);
+      } catch (error) {
+        failed.push({
+          pid: orphan.pid,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+    await this.options.ledger.remove(stopped);
+    return { stopped, failed };
+  }
+}
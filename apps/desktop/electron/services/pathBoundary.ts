@@ -1,11 +1,72 @@
+import fs from "node:fs/promises";
 import path from "node:path";
 
-export function isPathEqualOrInside(root: string, target: string): boolean {
-  const normalizedRoot = path.resolve(root);
-  const normalizedTarget = path.resolve(target);
-  const relative = path.relative(normalizedRoot, normalizedTarget);
+import {
+  canonicalWorkspacePath,
+  stripWindowsLongPathPrefix,
+  toWindowsLongPath,
+} from "../../../../src/utils/workspacePath";
+
+/**
+ * `target` is `root` or sits inside it. On Windows, long-path prefixes are
+ * ignored and the comparison is case-insensitive, so `\\?\C:\Repo\src` is
+ * inside `c:\repo`.
+ */
+export function isPathEqualOrInside(
+  root: string,
+  target: string,
+  platform: NodeJS.Platform = process.platform,
+): boolean {
+  const pathApi = platform === "win32" ? path.win32 : path.posix;
+  const normalizedRoot = pathApi.resolve(comparablePath(root, platform));
+  const normalizedTarget = pathApi.resolve(comparablePath(target, platform));
+  const relative = pathApi.relative(normalizedRoot, normalizedTarget);
   return (
     relative === "" ||
-    (relative !== ".." && !relative.startsWith(`..${path.sep}`) && !path.isAbsolute(relative))
+    (relative !== ".." &&
+      !relative.startsWith(`..${pathApi.sep}`) &&
+      !pathApi.isAbsolute(relative))
   );
 }
+
+function comparablePath(value: string, platform: NodeJS.Platform): string {
+  return platform === "win32" ? canonicalWorkspacePath(value, platform) : value;
+}
+
+/** The form to hand to `fs`: on Windows, paths past `MAX_PATH` get the `\\?\` prefix. */
+export function toFileSystemPath(
+  value: string,
+  platform: NodeJS.Platform = process.platform,
+): string {
+  return platform === "win32" ? toWindowsLongPath(value) : value;
+}
+
+/** The form to store and show: `fs` results on Windows lose any `\\?\` prefix. */
+export function fromFileSystemPath(
+  value: string,
+  platform: NodeJS.Platform = process.platform,
+): string {
+  return platform === "win32" ? stripWindowsLongPathPrefix(value) : value;
+}
+
+/** Key that two spellings of one local folder share, for deduplicating roots. */
+export function pathIdentityKey(
+  value: string,
+  platform: NodeJS.Platform = process.platform,
+): string {
+  return canonicalWorkspacePath(fromFileSystemPath(value, platform), platform);
+}
+
+/**
+ * Realpath of an existing local path in stored form. Falls back to the
+ * resolved path when only realpath fails, as it can for cloud-synced
+ * folders such as OneDrive's that still stat fine.
+ */
+export async function canonicalLocalPath(value: string): Promise<string> {
+  const resolved = path.resolve(fromFileSystemPath(value));
+  try {
+    return fromFileSystemPath(await fs.realpath(toFileSystemPath(resolved)));
+  } catch {
+    return resolved;
+  }
+}
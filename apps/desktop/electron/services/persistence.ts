@@ -1,5 +1,8 @@
-import fs from "node:fs/promises";
+import crypto from "node:crypto";
+import fs, { type FileHandle } from "node:fs/promises";
 import path from "node:path";
+import { promisify } from "node:util";
+import zlib from "node:zlib";
 import { app } from "electron";
 import { z } from "zod";
 import { home } from "../../../../src/platform/paths";
@@ -19,6 +22,7 @@ import {
   deriveDefaultLmStudioUiEnabled,
   normalizePersistedProviderUiState,
 } from "../../src/app/providerUiState";
+import { normalizeThreadRouting } from "../../src/app/threadRouting";
 import type {
   PersistedCloudSyncSettings,
   PersistedDesktopSettings,
@@ -28,8 +32,12 @@ import type {
   PersistedState,
   ThreadRecord,
   TranscriptEvent,
+  WorkspaceIdlePolicy,
   WorkspaceKind,
+  WorkspaceLaunchProfile,
+  WorkspacePathPolicy,
   WorkspaceRecord,
+  WorkspaceTranscriptMirror,
   WorkspaceUserProfile,
 } from "../../src/app/types";
 import {
@@ -39,12 +47,114 @@ import {
   normalizePrivacyTelemetrySettings,
   normalizeWorkspaceUserProfile,
 } from "../../src/app/types";
-import type { TranscriptBatchInput } from "../../src/lib/desktopApi";
-
+import { normalizeWorkspaceContainerLaunch } from "../../src/app/workspaceContainer";
+import { normalizeWorkspaceIdlePolicy } from "../../src/app/workspaceIdlePolicy";
+import {
+  launchProfilesError,
+  normalizeWorkspaceLaunchProfiles,
+} from "../../src/app/workspaceLaunchProfiles";
+import { normalizeWorkspacePathPolicy, pathPolicyError } from "../../src/app/workspacePathPolicy";
+import { normalizeWorkspaceResourceLimits } from "../../src/app/workspaceResourceLimits";
+import { normalizeAdditionalRoots } from "../../src/app/workspaceRootPaths";
+import { normalizeWorkspaceTags } from "../../src/app/workspaceTags";
+import {
+  normalizeWorkspaceTranscriptMirror,
+  transcriptMirrorApprovalPath,
+} from "../../src/app/workspaceTranscriptMirror";
+import type {
+  DestructiveCommandImpact,
+  DiskSpaceStatus,
+  DuplicateWorkspaceResult,
+  ForkThreadInput,
+  MergeThreadsInput,
+  MergeThreadsResult,
+  OperationProgress,
+  RedactTranscriptInput,
+  RestoreFromTrashResult,
+  StateRebuildReport,
+  StateSnapshotInfo,
+  ThreadPreview,
+  TranscriptBatchInput,
+  TranscriptCorruptedLine,
+  TranscriptEncryptionMigrationResult,
+  TranscriptEncryptionStatus,
+  TranscriptPage,
+  TranscriptRedactionResult,
+  TranscriptRepairResult,
+  TranscriptTimestampNormalizationResult,
+  TranscriptStoreKind,
+  TranscriptStoreMigrationResult,
+  TranscriptValidationMode,
+  TrashEntryKind,
+  WorkspaceStorageCleanupAction,
+  WorkspaceStorageCleanupTarget,
+  WorkspaceStorageReport,
+} from "../../src/lib/desktopApi";
+
+import { getLocalLogPath, logError } from "./localLogs";
+import { canonicalLocalPath, fromFileSystemPath, toFileSystemPath } from "./pathBoundary";
+import type { StateFileLock } from "./stateFileLock";
+import {
+  inferThreadFromTranscript,
+  parseServerLogWorkspaceIds,
+  rebuildRecordsFromTranscripts,
+} from "./stateRebuild";
+import { type DiskSpaceCheck, LowDiskSpaceError, type StorageGuard } from "./storageGuard";
+import {
+  decryptTranscriptLine,
+  encryptTranscriptLine,
+  isEncryptedTranscriptLine,
+  TranscriptEncryption,
+  type TranscriptKeychain,
+} from "./transcriptEncryption";
+import {
+  advanceSqliteTranscriptIndex,
+  advanceTranscriptIndex,
+  emptyTranscriptIndex,
+  type IndexedTranscriptLine,
+  recordIndexedEvent,
+  toThreadPreview,
+  type TranscriptIndexFile,
+  TranscriptIndexStore,
+} from "./transcriptIndex";
+import {
+  decodeTranscriptCursor,
+  encodeTranscriptCursor,
+  isTranscriptPageFull,
+  readTranscriptLines,
+  resolveTranscriptPageLimits,
+} from "./transcriptPages";
+import { TranscriptRedactor } from "./transcriptRedaction";
+import {
+  JsonlTranscriptStore,
+  SqliteTranscriptStore,
+  type StoredTranscriptLine,
+  type TranscriptDatabase,
+  type TranscriptLineSource,
+  type TranscriptStore,
+} from "./transcriptStore";
+import {
+  coerceLegacyTranscriptTimestamp,
+  normalizeTranscriptTimestamp,
+} from "./transcriptTimestamps";
+import { applyTranscriptValidation } from "./transcriptValidation";
+import { TranscriptWriteQueue } from "./transcriptWriteQueue";
+import type { TrashService } from "./trash";
 import { assertDirection, assertSafeId, assertWithinTranscriptsDir } from "./validation";
 
 const PRIVATE_FILE_MODE = 0o600;
 const PRIVATE_DIR_MODE = 0o700;
+const MAX_STATE_SNAPSHOTS = 10;
+const STATE_SNAPSHOT_NAME = /^state-(\d{8}T\d{9}Z)\.json$/;
+const TRANSCRIPT_EXTENSION = ".jsonl";
+const ARCHIVED_TRANSCRIPT_EXTENSION = ".jsonl.zst";
+const MAX_BUFFERED_TRANSCRIPT_EVENTS = 5_000;
+const TRANSCRIPT_DATABASE_FILE = "transcripts.sqlite";
+/** Where a thread's events are read from, in order. */
+const TRANSCRIPT_SOURCES = ["archived", "live", "sqlite"] as const;
+type TranscriptSource = (typeof TRANSCRIPT_SOURCES)[number];
+/** Storage cleanup only compacts the live transcripts of threads idle this long. */
+const TRANSCRIPT_COMPACTION_IDLE_MS = 24 * 60 * 60 * 1000;
 
 class AsyncLock {
   private pending: Promise<void> = Promise.resolve();
@@ -242,7 +352,7 @@ async function resolveWorkspacePath(
     return null;
   }
 
-  const resolved = path.resolve(candidate);
+  const resolved = path.resolve(fromFileSystemPath(candidate));
   if (workspaceKind === "oneOffChat") {
     const canonicalHome = home();
     if (!isPathInsideOneOffChatsRoot(resolved, canonicalHome)) {
@@ -256,14 +366,41 @@ async function resolveWorkspacePath(
   }
 
   try {
-    const stat = await fs.stat(resolved);
+    const stat = await fs.stat(toFileSystemPath(resolved));
     if (!stat.isDirectory()) {
       return null;
     }
-    return await fs.realpath(resolved);
   } catch {
     return null;
   }
+  return await canonicalLocalPath(resolved);
+}
+
+/** Resolves extra roots like the primary path, dropping any that are no longer directories. */
+async function resolveAdditionalRoots(
+  value: unknown,
+  primaryPath: string,
+): Promise<string[] | undefined> {
+  const resolved: string[] = [];
+  for (const root of normalizeAdditionalRoots(value, primaryPath) ?? []) {
+    const rootPath = await resolveWorkspacePath(root);
+    if (rootPath) {
+      resolved.push(rootPath);
+    }
+  }
+  return normalizeAdditionalRoots(resolved, primaryPath);
+}
+
+/** Canonicalizes a user-picked mirror folder and drops the mirror if that folder is gone. */
+async function resolveTranscriptMirror(
+  value: unknown,
+): Promise<WorkspaceTranscriptMirror | undefined> {
+  const mirror = normalizeWorkspaceTranscriptMirror(value);
+  if (!mirror || !transcriptMirrorApprovalPath({ transcriptMirror: mirror })) {
+    return mirror;
+  }
+  const directory = await resolveWorkspacePath(mirror.directory);
+  return directory ? { ...mirror, directory } : undefined;
 }
 
 async function sanitizeWorkspaces(value: unknown): Promise<WorkspaceRecord[]> {
@@ -298,6 +435,10 @@ async function sanitizeWorkspaces(value: unknown): Promise<WorkspaceRecord[]> {
       id,
       name,
       path: workspacePath,
+      additionalRoots:
+        workspaceKind === "project"
+          ? await resolveAdditionalRoots(item.additionalRoots, workspacePath)
+          : undefined,
       workspaceKind,
       createdAt,
       lastOpenedAt,
@@ -335,7 +476,18 @@ async function sanitizeWorkspaces(value: unknown): Promise<WorkspaceRecord[]> {
       defaultSkillImprovementExcludedSkills: asOptionalStringArray(
         item.defaultSkillImprovementExcludedSkills,
       ),
+      resourceLimits: normalizeWorkspaceResourceLimits(item.resourceLimits),
+      idlePolicy: normalizeWorkspaceIdlePolicy(item.idlePolicy),
+      container: normalizeWorkspaceContainerLaunch(item.container),
+      transcriptMirror: await resolveTranscriptMirror(item.transcriptMirror),
       yolo: typeof item.yolo === "boolean" ? item.yolo : false,
+      yoloCheckpoints: item.yoloCheckpoints === true ? true : undefined,
+      pinned: item.pinned === true ? true : undefined,
+      // List order is authoritative; older records without a sortOrder get theirs here.
+      sortOrder: workspaces.length,
+      tags: normalizeWorkspaceTags(item.tags),
+      launchProfiles: normalizeWorkspaceLaunchProfiles(item.launchProfiles),
+      pathPolicy: normalizeWorkspacePathPolicy(item.pathPolicy),
     });
     seenWorkspaceIds.add(id);
   }
@@ -368,6 +520,9 @@ function sanitizeThreads(value: unknown, workspaceIds: Set<string>): ThreadRecor
     }
     const taskId = asSafeId(item.taskId);
     const taskThreadId = taskId ? asSafeId(item.taskThreadId) : null;
+    const mergedIntoThreadId = asSafeId(item.mergedIntoThreadId);
+    const mergedAt = mergedIntoThreadId ? asTimestamp(item.mergedAt) : null;
+    const routing = normalizeThreadRouting(item.routing);
 
     threads.push({
       id,
@@ -383,9 +538,12 @@ function sanitizeThreads(value: unknown, workspaceIds: Set<string>): ThreadRecor
       legacyTranscriptId: asNonEmptyString(item.legacyTranscriptId) ?? null,
       archived: typeof item.archived === "boolean" ? item.archived : false,
       archivedAt: typeof item.archivedAt === "string" ? item.archivedAt : undefined,
+      ...(mergedIntoThreadId ? { mergedIntoThreadId } : {}),
+      ...(mergedAt ? { mergedAt } : {}),
       ...(isReasoningEffortValue(item.reasoningEffort)
         ? { reasoningEffort: item.reasoningEffort }
         : {}),
+      ...(routing ? { routing } : {}),
       ...(taskId ? { taskId } : {}),
       ...(taskThreadId ? { taskThreadId } : {}),
     });
@@ -416,8 +574,13 @@ async function sanitizePersistedState(value: unknown): Promise<PersistedState> {
       : 0;
   const onboarding = sanitizeOnboarding(value.onboarding);
   const productAnalytics = sanitizeProductAnalyticsState(value.productAnalytics);
+  const generation =
+    typeof value.generation === "number" && Number.isInteger(value.generation)
+      ? Math.max(0, value.generation)
+      : undefined;
   return {
     version: parsedVersion >= 2 ? parsedVersion : 2,
+    ...(generation !== undefined ? { generation } : {}),
     workspaces,
     threads,
     developerMode: typeof value.developerMode === "boolean" ? value.developerMode : false,
@@ -438,6 +601,87 @@ async function sanitizePersistedState(value: unknown): Promise<PersistedState> {
   };
 }
 
+/** One transcript file on disk, live (`.jsonl`) or compressed by archiving. */
+export type TranscriptFileInfo = {
+  transcriptId: string;
+  archived: boolean;
+  sizeBytes: number;
+  modifiedAtMs: number;
+};
+
+/**
+ * A finished merge. `eventIndexes` maps each event's index in the target's and
+ * the source's own transcript to its index in the merged one, for annotations.
+ */
+export type ThreadMergeResult = MergeThreadsResult & {
+  eventIndexes: { target: number[]; source: number[] };
+};
+
+type RecordRemovalTarget = { workspaceIds?: string[]; threadIds?: string[] };
+
+type ThreadStorageUsage = {
+  thread: ThreadRecord;
+  liveBytes: number;
+  archiveBytes: number;
+  quarantineBytes: number;
+};
+
+/** Transcripts can be keyed by the thread id, its session id, or a legacy id. */
+function transcriptIdsForThread(thread: ThreadRecord): string[] {
+  return [thread.legacyTranscriptId, thread.sessionId, thread.id].filter(
+    (id): id is string => typeof id === "string" && asSafeId(id) !== null,
+  );
+}
+
+function selectRemovedRecords(state: PersistedState, target: RecordRemovalTarget) {
+  const workspaceIds = new Set(target.workspaceIds ?? []);
+  const threadIds = new Set(target.threadIds ?? []);
+  const threads = state.threads.filter(
+    (thread) => threadIds.has(thread.id) || workspaceIds.has(thread.workspaceId),
+  );
+  const transcriptIds = new Set(threads.flatMap(transcriptIdsForThread));
+  return {
+    workspaces: state.workspaces.filter((workspace) => workspaceIds.has(workspace.id)),
+    threads,
+    transcriptIds: [...transcriptIds],
+  };
+}
+
+function describeTrashEntry(
+  removed: ReturnType<typeof selectRemovedRecords>,
+): { kind: TrashEntryKind; label: string } {
+  if (removed.workspaces.length > 0) {
+    return {
+      kind: "workspace",
+      label: removed.workspaces.map((workspace) => workspace.name).join(", "),
+    };
+  }
+  if (removed.threads.length === 1) {
+    return { kind: "thread", label: removed.threads[0]?.title ?? "" };
+  }
+  return { kind: "threads", label: `${removed.threads.length} threads` };
+}
+
+function formatStateSnapshotStamp(date: Date): string {
+  return date.toISOString().replace(/[-:.]/g, "");
+}
+
+function parseStateSnapshotName(name: string): string | null {
+  const match = STATE_SNAPSHOT_NAME.exec(name);
+  if (!match?.[1]) {
+    return null;
+  }
+  const iso = match[1].replace(
+    /^(\d{4})(\d{2})(\d{2})T(\d{2})(\d{2})(\d{2})(\d{3})Z$/,
+    "$1-$2-$3T$4:$5:$6.$7Z",
+  );
+  return Number.isNaN(Date.parse(iso)) ? null : iso;
+}
+
+function compareSnapshotsNewestFirst(left: { name: string }, right: { name: string }): number {
+  return left.name < right.name ? 1 : left.name > right.name ? -1 : 0;
+}
+
 function isNotFound(error: unknown): boolean {
   return (
     typeof error === "object" &&
@@ -447,9 +691,243 @@ function isNotFound(error: unknown): boolean {
   );
 }
 
+async function fileSize(filePath: string): Promise<number> {
+  try {
+    return (await fs.stat(filePath)).size;
+  } catch (error) {
+    if (isNotFound(error)) {
+      return 0;
+    }
+    throw error;
+  }
+}
+
+/** Bytes `target` would touch for the thread, or null when it leaves the thread alone. */
+function storageCleanupBytes(
+  target: WorkspaceStorageCleanupTarget,
+  usage: ThreadStorageUsage,
+  idleBeforeMs: number,
+): number | null {
+  switch (target) {
+    case "compactTranscripts":
+      return !usage.thread.archived &&
+        usage.liveBytes > 0 &&
+        Date.parse(usage.thread.lastMessageAt) < idleBeforeMs
+        ? usage.liveBytes
+        : null;
+    case "purgeArchivedThreads":
+      return usage.thread.archived
+        ? usage.liveBytes + usage.archiveBytes + usage.quarantineBytes
+        : null;
+    case "clearQuarantine":
+      return usage.quarantineBytes > 0 ? usage.quarantineBytes : null;
+  }
+}
+
+type DecodedTranscriptLine =
+  | { kind: "event"; event: TranscriptEvent }
+  /** Encrypted with a key this device does not have; kept as is. */
+  | { kind: "sealed" }
+  | { kind: "corrupt"; reason: TranscriptCorruptedLine["reason"] };
+
+type ParsedTranscriptLine = { line: number; raw: string } & DecodedTranscriptLine;
+
+async function readFileIfExists(filePath: string): Promise<Buffer | null> {
+  try {
+    return await fs.readFile(filePath);
+  } catch (error) {
+    if (isNotFound(error)) {
+      return null;
+    }
+    throw error;
+  }
+}
+
+export class StateMergeRequiredError extends Error {
+  readonly code = "state_merge_required";
+
+  constructor(
+    readonly baseGeneration: number,
+    readonly currentGeneration: number,
+    /** Records another process added since `baseGeneration`. */
+    readonly workspaces: WorkspaceRecord[],
+    readonly threads: ThreadRecord[],
+  ) {
+    super(
+      `State was changed by another process (generation ${currentGeneration}, ` +
+        `save based on ${baseGeneration}); merge and save again`,
+    );
+    this.name = "StateMergeRequiredError";
+  }
+}
+
+/** Lets a background operation follow and stop a transcript-wide rewrite between files. */
+type TranscriptMaintenanceOptions = {
+  signal?: AbortSignal;
+  onProgress?: (progress: OperationProgress) => void;
+};
+
+type PersistenceServiceOptions = {
+  now?: () => Date;
+  maxStateSnapshots?: number;
+  transcriptKeychain?: TranscriptKeychain;
+  createId?: () => string;
+  storageGuard?: StorageGuard;
+  /** Called when free space on the app data volume crosses the guard threshold. */
+  onDiskSpaceChanged?: (status: DiskSpaceStatus) => void;
+  maxBufferedTranscriptEvents?: number;
+  transcriptSyncIntervalMs?: number;
+  /** When set, removed records and their transcripts go to the trash instead of being deleted. */
+  trash?: TrashService;
+  /** Current `transcriptValidation` setting; payloads are not checked without it. */
+  getTranscriptValidationMode?: () => Promise<TranscriptValidationMode>;
+  /** Cross-process lock held around every state.json read and write. */
+  stateFileLock?: StateFileLock;
+  /** Current `transcriptStore` setting; appends go to JSONL without it. */
+  getTranscriptStoreKind?: () => Promise<TranscriptStoreKind>;
+  /** Saves the `transcriptStore` setting once `migrateTranscriptStore` has moved transcripts. */
+  setTranscriptStoreKind?: (kind: TranscriptStoreKind) => Promise<void>;
+  /** Opens the SQLite transcript store; without it only JSONL transcripts are available. */
+  openTranscriptDatabase?: (filePath: string) => TranscriptDatabase;
+};
+
+function normalizeTranscriptBatchInput(event: TranscriptBatchInput): TranscriptBatchInput {
+  assertSafeId(event.threadId, "threadId");
+  return {
+    ...event,
+    ts: normalizeTranscriptTimestamp(event.threadId, event.ts),
+    direction: assertDirection(event.direction),
+  };
+}
+
+/** Seals every plaintext line of a JSONL payload; returns null when nothing needed sealing. */
+function encryptTranscriptPayload(key: Buffer, raw: Buffer): Buffer | null {
+  let changed = false;
+  const lines = raw
+    .toString("utf8")
+    .split(/\r?\n/)
+    .filter((line) => line.trim())
+    .map((line) => {
+      if (isEncryptedTranscriptLine(line)) {
+        return line;
+      }
+      changed = true;
+      return encryptTranscriptLine(key, line);
+    });
+  return changed ? Buffer.from(`${lines.join("\n")}\n`, "utf8") : null;
+}
+
 export class PersistenceService {
   private readonly stateLock = new AsyncLock();
+  /** Serializes transcript appends with whole-file rewrites such as the encryption migration. */
+  private readonly transcriptLock = new AsyncLock();
+  private readonly pendingTranscriptWrites = new Set<Promise<void>>();
+  private readonly transcriptAppendListeners = new Set<(events: TranscriptEvent[]) => void>();
   private storageReady: Promise<void> | null = null;
+  private readonly now: () => Date;
+  private readonly createId: () => string;
+  private readonly maxStateSnapshots: number;
+  private readonly transcriptKeychain: TranscriptKeychain | undefined;
+  private transcriptEncryptionService: TranscriptEncryption | null = null;
+  private readonly storageGuard: StorageGuard | undefined;
+  private readonly onDiskSpaceChanged: ((status: DiskSpaceStatus) => void) | undefined;
+  private readonly maxBufferedTranscriptEvents: number;
+  /** Transcript events held back while the disk is low, in append order. */
+  private bufferedTranscriptEvents: TranscriptBatchInput[] = [];
+  private diskSpaceLow = false;
+  private readonly transcriptWriter: TranscriptWriteQueue<TranscriptBatchInput>;
+  private readonly trash: TrashService | undefined;
+  private readonly getTranscriptValidationMode: () => Promise<TranscriptValidationMode>;
+  private readonly stateFileLock: StateFileLock | undefined;
+  /** Generation of state.json as of this process's last read or write; null before the first. */
+  private observedStateGeneration: number | null = null;
+  /** Newest generation written by another process, or -1 if none has been seen. */
+  private latestForeignStateGeneration = -1;
+  /** Workspace and thread ids this process has loaded or had saved by the renderer. */
+  private readonly knownStateRecordIds = new Set<string>();
+  private readonly getTranscriptStoreKind: () => Promise<TranscriptStoreKind>;
+  private readonly setTranscriptStoreKind:
+    | ((kind: TranscriptStoreKind) => Promise<void>)
+    | undefined;
+  private readonly openTranscriptDatabase: ((filePath: string) => TranscriptDatabase) | undefined;
+  private jsonlTranscriptStore: JsonlTranscriptStore | null = null;
+  private sqliteTranscriptStore: SqliteTranscriptStore | null = null;
+  private transcriptIndexStore: TranscriptIndexStore | null = null;
+
+  constructor(options: PersistenceServiceOptions = {}) {
+    this.now = options.now ?? (() => new Date());
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+    this.maxStateSnapshots = Math.max(1, options.maxStateSnapshots ?? MAX_STATE_SNAPSHOTS);
+    this.transcriptKeychain = options.transcriptKeychain;
+    this.storageGuard = options.storageGuard;
+    this.onDiskSpaceChanged = options.onDiskSpaceChanged;
+    this.trash = options.trash;
+    this.stateFileLock = options.stateFileLock;
+    this.getTranscriptValidationMode =
+      options.getTranscriptValidationMode ?? (async () => "off" as const);
+    this.maxBufferedTranscriptEvents =
+      options.maxBufferedTranscriptEvents ?? MAX_BUFFERED_TRANSCRIPT_EVENTS;
+    this.getTranscriptStoreKind = options.getTranscriptStoreKind ?? (async () => "jsonl" as const);
+    this.setTranscriptStoreKind = options.setTranscriptStoreKind;
+    this.openTranscriptDatabase = options.openTranscriptDatabase;
+    this.transcriptWriter = new TranscriptWriteQueue({
+      write: (threadId, chunk) => this.appendTranscriptChunk(threadId, chunk),
+      sync: (threadId) => this.syncTranscriptFile(threadId),
+      syncIntervalMs: options.transcriptSyncIntervalMs,
+      onSyncError: (threadId, error) => {
+        logError("persistence", error, { operation: "sync_transcript", threadId });
+      },
+    });
+  }
+
+  private get transcriptEncryption(): TranscriptEncryption {
+    this.transcriptEncryptionService ??= new TranscriptEncryption(
+      path.join(this.appDataDir, "transcript-encryption.json"),
+      this.transcriptKeychain,
+    );
+    return this.transcriptEncryptionService;
+  }
+
+  private get jsonlTranscripts(): JsonlTranscriptStore {
+    this.jsonlTranscriptStore ??= new JsonlTranscriptStore({
+      dir: this.transcriptsDir,
+      filePath: (threadId) => this.transcriptFilePath(threadId),
+    });
+    return this.jsonlTranscriptStore;
+  }
+
+  private get transcriptIndex(): TranscriptIndexStore {
+    this.transcriptIndexStore ??= new TranscriptIndexStore(
+      path.join(this.appDataDir, "transcript-index"),
+    );
+    return this.transcriptIndexStore;
+  }
+
+  /** Null in builds that cannot open SQLite. */
+  private get sqliteTranscripts(): SqliteTranscriptStore | null {
+    if (!this.openTranscriptDatabase) {
+      return null;
+    }
+    this.sqliteTranscriptStore ??= new SqliteTranscriptStore({
+      filePath: path.join(this.appDataDir, TRANSCRIPT_DATABASE_FILE),
+      openDatabase: this.openTranscriptDatabase,
+    });
+    return this.sqliteTranscriptStore;
+  }
+
+  private requireSqliteTranscripts(): SqliteTranscriptStore {
+    const store = this.sqliteTranscripts;
+    if (!store) {
+      throw new Error("The SQLite transcript store is unavailable in this build.");
+    }
+    return store;
+  }
+
+  private async activeTranscriptStore(): Promise<TranscriptStore> {
+    return (await this.getTranscriptStoreKind()) === "sqlite"
+      ? this.requireSqliteTranscripts()
+      : this.jsonlTranscripts;
+  }
 
   private get appDataDir(): string {
     return app.getPath("userData");
@@ -463,17 +941,62 @@ export class PersistenceService {
     return path.join(this.appDataDir, "state.json");
   }
 
+  private get stateBackupsDir(): string {
+    return path.join(this.appDataDir, "state-backups");
+  }
+
+  private stateSnapshotFilePath(name: string): string {
+    if (!parseStateSnapshotName(name)) {
+      throw new Error("snapshot name is invalid");
+    }
+    const file = path.join(this.stateBackupsDir, name);
+    if (path.dirname(file) !== this.stateBackupsDir) {
+      throw new Error("Resolved snapshot path escapes state backups root");
+    }
+    return file;
+  }
+
   private get transcriptsDir(): string {
     return path.join(this.appDataDir, "transcripts");
   }
 
   private transcriptFilePath(threadId: string): string {
     assertSafeId(threadId, "threadId");
-    const file = path.join(this.transcriptsDir, `${threadId}.jsonl`);
+    const file = path.join(this.transcriptsDir, `${threadId}${TRANSCRIPT_EXTENSION}`);
     assertWithinTranscriptsDir(this.transcriptsDir, file);
     return file;
   }
 
+  private get archivedTranscriptsDir(): string {
+    return path.join(this.appDataDir, "transcripts-archive");
+  }
+
+  private archivedTranscriptFilePath(threadId: string): string {
+    assertSafeId(threadId, "threadId");
+    const file = path.join(
+      this.archivedTranscriptsDir,
+      `${threadId}${ARCHIVED_TRANSCRIPT_EXTENSION}`,
+    );
+    assertWithinTranscriptsDir(this.archivedTranscriptsDir, file);
+    return file;
+  }
+
+  private quarantinedTranscriptFilePath(threadId: string): string {
+    assertSafeId(threadId, "threadId");
+    const file = path.join(this.transcriptsDir, `${threadId}.corrupt`);
+    assertWithinTranscriptsDir(this.transcriptsDir, file);
+    return file;
+  }
+
+  /** Every file `deleteTranscript` removes for `threadId`. */
+  private transcriptFilePaths(threadId: string): string[] {
+    return [
+      this.transcriptFilePath(threadId),
+      this.archivedTranscriptFilePath(threadId),
+      this.quarantinedTranscriptFilePath(threadId),
+    ];
+  }
+
   private async ensureStorageReady(): Promise<void> {
     if (!this.storageReady) {
       this.storageReady = this.migrateLegacyUserDataIfNeeded();
@@ -555,129 +1078,2133 @@ export class PersistenceService {
 
   async loadState(): Promise<PersistedState> {
     await this.ensureStorageReady();
-    return await this.stateLock.run(async () => {
-      try {
-        const raw = await fs.readFile(this.stateFilePath, "utf8");
-        let parsed: unknown;
-        try {
-          parsed = JSON.parse(raw);
-        } catch {
-          return defaultState();
-        }
-        return await sanitizePersistedState(parsed);
-      } catch (error) {
-        if (isNotFound(error)) {
-          return defaultState();
+    return await this.runStateLocked(async () => await this.readStateFileLocked());
+  }
+
+  /**
+   * Writes the renderer's state and returns the new generation. When the state
+   * carries the generation it was based on and another process has written
+   * since then, throws `StateMergeRequiredError` with the records that process
+   * added so they can be merged before retrying. Writes made by this process
+   * never conflict, since they already reach the renderer.
+   */
+  async saveState(state: PersistedState): Promise<number> {
+    await this.ensureStorageReady();
+    const diskSpace = await this.checkDiskSpace();
+    if (diskSpace.low) {
+      throw new LowDiskSpaceError("save_state", diskSpace);
+    }
+    return await this.runStateLocked(async () => {
+      const baseGeneration = state.generation;
+      if (typeof baseGeneration === "number") {
+        const current = await this.readStateFileLocked();
+        if (baseGeneration < this.latestForeignStateGeneration) {
+          const isForeign = (record: { id: string }) => !this.knownStateRecordIds.has(record.id);
+          throw new StateMergeRequiredError(
+            baseGeneration,
+            current.generation ?? 0,
+            current.workspaces.filter(isForeign),
+            current.threads.filter(isForeign),
+          );
         }
-        throw new Error(`Failed to load state: ${String(error)}`);
       }
+      const next = await sanitizePersistedState(state);
+      const generation = await this.writeStateFileLocked(next);
+      this.markStateRecordsKnown(next);
+      return generation;
     });
   }
 
-  async saveState(state: PersistedState): Promise<void> {
+  /**
+   * Copies the current state.json into `state-backups/` and prunes the oldest
+   * snapshots beyond the retention limit. Skips the write when the state file
+   * is missing or unchanged since the newest snapshot.
+   */
+  async captureStateSnapshot(): Promise<StateSnapshotInfo | null> {
     await this.ensureStorageReady();
-    await this.stateLock.run(async () => {
-      await fs.mkdir(this.appDataDir, { recursive: true, mode: PRIVATE_DIR_MODE });
-
-      const sanitizedState = await sanitizePersistedState(state);
-      const tempPath = `${this.stateFilePath}.tmp`;
-      const payload = JSON.stringify(
-        { ...sanitizedState, version: sanitizedState.version || 2 },
-        null,
-        2,
-      );
-
-      await fs.writeFile(tempPath, payload, { encoding: "utf8", mode: PRIVATE_FILE_MODE });
-      await fs.rename(tempPath, this.stateFilePath);
-      await fs.chmod(this.stateFilePath, PRIVATE_FILE_MODE);
-    });
+    return await this.runStateLocked(async () => await this.captureStateSnapshotLocked());
   }
 
-  async readTranscript(threadId: string): Promise<TranscriptEvent[]> {
+  async listStateSnapshots(): Promise<StateSnapshotInfo[]> {
     await this.ensureStorageReady();
-    const filePath = this.transcriptFilePath(threadId);
-
-    let raw: string;
+    let names: string[];
     try {
-      raw = await fs.readFile(filePath, "utf8");
+      names = await fs.readdir(this.stateBackupsDir);
     } catch (error) {
       if (isNotFound(error)) {
         return [];
       }
-      throw new Error(`Failed to read transcript: ${String(error)}`);
+      throw new Error(`Failed to list state snapshots: ${String(error)}`);
     }
 
-    const events: TranscriptEvent[] = [];
-    for (const line of raw.split(/\r?\n/)) {
-      const trimmed = line.trim();
-      if (!trimmed) {
+    const snapshots: StateSnapshotInfo[] = [];
+    for (const name of names) {
+      const createdAt = parseStateSnapshotName(name);
+      if (!createdAt) {
         continue;
       }
+      try {
+        const stat = await fs.stat(path.join(this.stateBackupsDir, name));
+        if (!stat.isFile()) {
+          continue;
+        }
+        snapshots.push({ name, createdAt, sizeBytes: stat.size });
+      } catch {
+        // Pruned concurrently; skip it.
+      }
+    }
+    return snapshots.sort(compareSnapshotsNewestFirst);
+  }
+
+  /**
+   * Replaces state.json with the named snapshot. The current state is
+   * snapshotted first so a restore can itself be undone.
+   */
+  async restoreStateSnapshot(name: string): Promise<PersistedState> {
+    await this.ensureStorageReady();
+    const snapshotPath = this.stateSnapshotFilePath(name);
+    return await this.runStateLocked(async () => {
+      let raw: string;
+      try {
+        raw = await fs.readFile(snapshotPath, "utf8");
+      } catch (error) {
+        if (isNotFound(error)) {
+          throw new Error(`State snapshot not found: ${name}`);
+        }
+        throw new Error(`Failed to read state snapshot: ${String(error)}`);
+      }
 
-      let parsedJson: unknown;
+      let parsed: unknown;
       try {
-        parsedJson = JSON.parse(trimmed);
+        parsed = JSON.parse(raw);
       } catch {
-        continue;
+        throw new Error(`State snapshot is not valid JSON: ${name}`);
+      }
+      if (!isRecord(parsed)) {
+        throw new Error(`State snapshot is not a state object: ${name}`);
       }
 
-      const parsedLine = transcriptEventSchema.safeParse(parsedJson);
-      if (!parsedLine.success) {
-        continue;
+      await this.captureStateSnapshotLocked();
+      const restored = await sanitizePersistedState(parsed);
+      await this.writeStateFileLocked(restored);
+      return restored;
+    });
+  }
+
+  /**
+   * Recovers workspace and thread records from transcripts when state.json is
+   * lost and no snapshot helps. Workspace ids come from server.log where it
+   * still names them. A dry run only reports what would be recovered;
+   * otherwise the current state is snapshotted and the records merged into it.
+   */
+  async rebuildStateFromTranscripts(options: {
+    dryRun: boolean;
+  }): Promise<{ report: StateRebuildReport; state: PersistedState }> {
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+
+    const threadIds = new Set<string>();
+    for (const [dir, extension] of [
+      [this.transcriptsDir, TRANSCRIPT_EXTENSION],
+      [this.archivedTranscriptsDir, ARCHIVED_TRANSCRIPT_EXTENSION],
+    ] as const) {
+      try {
+        for (const name of await fs.readdir(dir)) {
+          if (name.endsWith(extension)) {
+            threadIds.add(name.slice(0, -extension.length));
+          }
+        }
+      } catch (error) {
+        if (!isNotFound(error)) {
+          throw new Error(`Failed to list transcripts: ${String(error)}`);
+        }
+      }
+    }
+    for (const transcriptId of (await this.sqliteTranscripts?.listTranscriptIds()) ?? []) {
+      threadIds.add(transcriptId);
+    }
+    const transcripts: Array<{ threadId: string; events: TranscriptEvent[] }> = [];
+    const unreadable: StateRebuildReport["skipped"] = [];
+    for (const threadId of [...threadIds].sort()) {
+      try {
+        transcripts.push({ threadId, events: await this.readTranscript(threadId) });
+      } catch (error) {
+        unreadable.push({ threadId, reason: `The transcript could not be read: ${String(error)}` });
       }
-      events.push(parsedLine.data as TranscriptEvent);
     }
+    const serverLog = await fs.readFile(getLocalLogPath("server.log"), "utf8").catch(() => "");
+
+    return await this.runStateLocked(async () => {
+      const current = await this.readStateFileLocked();
+      const rebuilt = await rebuildRecordsFromTranscripts({
+        transcripts,
+        loggedWorkspaceIds: parseServerLogWorkspaceIds(serverLog),
+        existingWorkspaces: current.workspaces,
+        existingThreadIds: new Set(current.threads.map((thread) => thread.id)),
+        resolveWorkspacePath: async (workspacePath) => await resolveWorkspacePath(workspacePath),
+        now: this.now,
+      });
+      const report: StateRebuildReport = {
+        ...rebuilt.report,
+        dryRun: options.dryRun,
+        applied: false,
+        snapshotName: null,
+        scannedTranscripts: rebuilt.report.scannedTranscripts + unreadable.length,
+        skipped: [...unreadable, ...rebuilt.report.skipped],
+      };
+      if (options.dryRun || rebuilt.threads.length === 0) {
+        return { report, state: current };
+      }
 
-    return events;
+      const snapshot = await this.captureStateSnapshotLocked();
+      const state = await sanitizePersistedState({
+        ...current,
+        workspaces: [...current.workspaces, ...rebuilt.workspaces],
+        threads: [...current.threads, ...rebuilt.threads],
+      });
+      await this.writeStateFileLocked(state);
+      return {
+        report: { ...report, applied: true, snapshotName: snapshot?.name ?? null },
+        state,
+      };
+    });
   }
 
-  async appendTranscriptEvent(event: TranscriptBatchInput): Promise<void> {
-    await this.appendTranscriptBatch([event]);
+  private async runStateLocked<T>(fn: () => Promise<T>): Promise<T> {
+    const fileLock = this.stateFileLock;
+    return await this.stateLock.run(async () => (fileLock ? await fileLock.run(fn) : await fn()));
   }
 
-  async appendTranscriptBatch(events: TranscriptBatchInput[]): Promise<void> {
-    if (events.length === 0) {
-      return;
+  private async readStateFileLocked(): Promise<PersistedState> {
+    const firstRead = this.observedStateGeneration === null;
+    const state = await this.readSanitizedStateFile();
+    this.observeStateGeneration(state.generation ?? 0);
+    if (firstRead) {
+      // The renderer hydrates from the first load, so everything in it is known.
+      this.markStateRecordsKnown(state);
     }
+    return state;
+  }
 
-    await this.ensureStorageReady();
-    await fs.mkdir(this.transcriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+  private async readSanitizedStateFile(): Promise<PersistedState> {
+    try {
+      const raw = await fs.readFile(this.stateFilePath, "utf8");
+      let parsed: unknown;
+      try {
+        parsed = JSON.parse(raw);
+      } catch {
+        return defaultState();
+      }
+      return await sanitizePersistedState(parsed);
+    } catch (error) {
+      if (isNotFound(error)) {
+        return defaultState();
+      }
+      throw new Error(`Failed to load state: ${String(error)}`);
+    }
+  }
 
-    const grouped = new Map<string, TranscriptBatchInput[]>();
-    for (const event of events) {
-      assertSafeId(event.threadId, "threadId");
-      const direction = assertDirection(event.direction);
-      const normalized = { ...event, direction };
+  private async readStateGenerationLocked(): Promise<number> {
+    try {
+      const parsed: unknown = JSON.parse(await fs.readFile(this.stateFilePath, "utf8"));
+      const generation = isRecord(parsed) ? parsed.generation : undefined;
+      return typeof generation === "number" && Number.isInteger(generation)
+        ? Math.max(0, generation)
+        : 0;
+    } catch {
+      return 0;
+    }
+  }
 
-      const bucket = grouped.get(normalized.threadId);
-      if (bucket) {
-        bucket.push(normalized);
-      } else {
-        grouped.set(normalized.threadId, [normalized]);
-      }
+  private observeStateGeneration(generation: number): void {
+    if (this.observedStateGeneration !== null && generation !== this.observedStateGeneration) {
+      this.latestForeignStateGeneration = Math.max(this.latestForeignStateGeneration, generation);
     }
+    this.observedStateGeneration = generation;
+  }
 
-    for (const [threadId, chunk] of grouped) {
-      const filePath = this.transcriptFilePath(threadId);
-      const payload = `${chunk.map((event) => JSON.stringify(event)).join("\n")}\n`;
-      await fs.appendFile(filePath, payload, { encoding: "utf8", mode: PRIVATE_FILE_MODE });
-      await fs.chmod(filePath, PRIVATE_FILE_MODE);
+  private markStateRecordsKnown(state: PersistedState): void {
+    for (const record of [...state.workspaces, ...state.threads]) {
+      this.knownStateRecordIds.add(record.id);
     }
   }
 
-  async deleteTranscript(threadId: string): Promise<void> {
-    await this.ensureStorageReady();
-    const filePath = this.transcriptFilePath(threadId);
+  /** Writes `state` as the next generation after the one on disk and returns it. */
+  private async writeStateFileLocked(state: PersistedState): Promise<number> {
+    await fs.mkdir(this.appDataDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    this.observeStateGeneration(await this.readStateGenerationLocked());
+    const generation = (this.observedStateGeneration ?? 0) + 1;
+
+    const tempPath = `${this.stateFilePath}.tmp`;
+    const payload = JSON.stringify({ ...state, version: state.version || 2, generation }, null, 2);
 
+    await fs.writeFile(tempPath, payload, { encoding: "utf8", mode: PRIVATE_FILE_MODE });
+    await fs.rename(tempPath, this.stateFilePath);
+    await fs.chmod(this.stateFilePath, PRIVATE_FILE_MODE);
+    this.observedStateGeneration = generation;
+    return generation;
+  }
+
+  private async captureStateSnapshotLocked(): Promise<StateSnapshotInfo | null> {
+    let raw: string;
     try {
-      await fs.unlink(filePath);
+      raw = await fs.readFile(this.stateFilePath, "utf8");
     } catch (error) {
       if (isNotFound(error)) {
-        return;
+        return null;
+      }
+      throw new Error(`Failed to read state for snapshot: ${String(error)}`);
+    }
+    if (!raw.trim()) {
+      return null;
+    }
+
+    const existing = await this.listStateSnapshots();
+    const newest = existing[0];
+    if (newest) {
+      const newestRaw = await fs
+        .readFile(path.join(this.stateBackupsDir, newest.name), "utf8")
+        .catch(() => null);
+      if (newestRaw === raw) {
+        return null;
+      }
+    }
+
+    await fs.mkdir(this.stateBackupsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    const taken = new Set(existing.map((snapshot) => snapshot.name));
+    let createdAt = this.now();
+    let name = `state-${formatStateSnapshotStamp(createdAt)}.json`;
+    while (taken.has(name)) {
+      createdAt = new Date(createdAt.getTime() + 1);
+      name = `state-${formatStateSnapshotStamp(createdAt)}.json`;
+    }
+
+    const snapshotPath = path.join(this.stateBackupsDir, name);
+    await fs.writeFile(snapshotPath, raw, { encoding: "utf8", mode: PRIVATE_FILE_MODE });
+    await fs.chmod(snapshotPath, PRIVATE_FILE_MODE);
+
+    const retained = [{ name }, ...existing].sort(compareSnapshotsNewestFirst);
+    for (const stale of retained.slice(this.maxStateSnapshots)) {
+      await fs.rm(path.join(this.stateBackupsDir, stale.name), { force: true });
+    }
+
+    return { name, createdAt: createdAt.toISOString(), sizeBytes: Buffer.byteLength(raw) };
+  }
+
+  async readTranscript(threadId: string): Promise<TranscriptEvent[]> {
+    return (await this.readTranscriptWithReport(threadId)).events;
+  }
+
+  /**
+   * Reads a transcript, skipping lines that cannot be decoded instead of
+   * failing the whole read, and reports where the skipped lines were. Files
+   * are streamed line by line, so only the decoded events are held at once.
+   */
+  async readTranscriptWithReport(
+    threadId: string,
+  ): Promise<{ events: TranscriptEvent[]; corruptedLines: TranscriptCorruptedLine[] }> {
+    await this.ensureStorageReady();
+    const events: TranscriptEvent[] = [];
+    const corruptedLines: TranscriptCorruptedLine[] = [];
+    const readKey = this.createTranscriptReadKeyLoader();
+    // Archived history comes first; anything appended after archiving follows it.
+    for (const source of TRANSCRIPT_SOURCES) {
+      const stream = await this.openTranscriptSource(threadId, source, 0);
+      if (!stream) {
+        continue;
+      }
+      let lineNumber = 0;
+      try {
+        for await (const { text } of stream.lines) {
+          lineNumber += 1;
+          const decoded = await this.decodeTranscriptLine(text.trim(), readKey);
+          if (decoded?.kind === "event") {
+            events.push(decoded.event);
+          } else if (decoded?.kind === "corrupt") {
+            corruptedLines.push({ source, line: lineNumber, reason: decoded.reason });
+          }
+        }
+      } catch (error) {
+        throw new Error(`Failed to read transcript: ${String(error)}`);
+      } finally {
+        await stream.close();
+      }
+    }
+
+    return { events, corruptedLines };
+  }
+
+  /**
+   * Reads one page of a transcript, stopping at `maxEvents` events or
+   * `maxBytes` of raw lines, whichever comes first. Pass the returned
+   * `nextCursor` back to continue; it is null once the transcript is done.
+   * Lines that cannot be decoded are skipped, as with `readTranscript`.
+   */
+  async readTranscriptPage(
+    threadId: string,
+    options: { cursor?: string; maxEvents?: number; maxBytes?: number } = {},
+  ): Promise<TranscriptPage> {
+    await this.ensureStorageReady();
+    const cursor = options.cursor
+      ? decodeTranscriptCursor(options.cursor)
+      : ({ source: "archived", offset: 0 } as const);
+    if (cursor.source === "memory") {
+      throw new Error("Invalid transcript cursor.");
+    }
+    const limits = resolveTranscriptPageLimits(options);
+    const readKey = this.createTranscriptReadKeyLoader();
+    const events: TranscriptEvent[] = [];
+    let bytes = 0;
+    // Sources before the cursor's were already read.
+    for (const source of TRANSCRIPT_SOURCES.slice(TRANSCRIPT_SOURCES.indexOf(cursor.source))) {
+      const start = source === cursor.source ? cursor.offset : 0;
+      const stream = await this.openTranscriptSource(threadId, source, start);
+      if (!stream) {
+        continue;
+      }
+      let lineStart = start;
+      try {
+        for await (const line of stream.lines) {
+          if (isTranscriptPageFull(limits, events.length, bytes, line.bytes)) {
+            return {
+              events,
+              nextCursor: encodeTranscriptCursor({ source, offset: lineStart }),
+              truncated: true,
+            };
+          }
+          const decoded = await this.decodeTranscriptLine(line.text.trim(), readKey);
+          if (decoded?.kind === "event") {
+            events.push(decoded.event);
+            bytes += line.bytes;
+          }
+          lineStart = line.end;
+        }
+      } catch (error) {
+        throw new Error(`Failed to read transcript: ${String(error)}`);
+      } finally {
+        await stream.close();
       }
-      throw new Error(`Failed to delete transcript: ${String(error)}`);
     }
+    return { events, nextCursor: null, truncated: false };
   }
+
+  /**
+   * Moves lines that cannot be decoded into `{threadId}.corrupt` next to the
+   * live transcript, then atomically rewrites the live and archived files
+   * without them. Sealed lines whose key is unavailable are kept.
+   */
+  async repairTranscript(threadId: string): Promise<TranscriptRepairResult> {
+    await this.ensureStorageReady();
+    const livePath = this.transcriptFilePath(threadId);
+    const archivedPath = this.archivedTranscriptFilePath(threadId);
+    await this.flushTranscriptWrites();
+    await this.spillSqliteTranscripts([threadId]);
+
+    return await this.transcriptLock.run(async () => {
+      const readKey = this.createTranscriptReadKeyLoader();
+      const quarantined: string[] = [];
+      const rewrites: Array<{ filePath: string; lines: string[]; compress: boolean }> = [];
+      const archived = await this.readArchivedTranscript(threadId);
+      const live = await readFileIfExists(livePath);
+      const files = [
+        { filePath: archivedPath, raw: archived, compress: true },
+        { filePath: livePath, raw: live, compress: false },
+      ];
+      for (const { filePath, raw, compress } of files) {
+        if (!raw) {
+          continue;
+        }
+        const parsed = await this.parseTranscriptLines(raw, readKey);
+        const corrupt = parsed.filter((line) => line.kind === "corrupt");
+        if (corrupt.length === 0) {
+          continue;
+        }
+        quarantined.push(...corrupt.map((line) => line.raw));
+        rewrites.push({
+          filePath,
+          lines: parsed.filter((line) => line.kind !== "corrupt").map((line) => line.raw),
+          compress,
+        });
+      }
+
+      if (quarantined.length === 0) {
+        return { threadId, quarantinedLineCount: 0, quarantinePath: null };
+      }
+
+      // The bad lines reach disk before any file is rewritten without them.
+      const quarantinePath = this.quarantinedTranscriptFilePath(threadId);
+      assertWithinTranscriptsDir(this.transcriptsDir, quarantinePath);
+      await fs.mkdir(this.transcriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+      await fs.appendFile(quarantinePath, `${quarantined.join("\n")}\n`, {
+        encoding: "utf8",
+        mode: PRIVATE_FILE_MODE,
+      });
+      await fs.chmod(quarantinePath, PRIVATE_FILE_MODE);
+
+      for (const { filePath, lines, compress } of rewrites) {
+        const payload = Buffer.from(lines.length > 0 ? `${lines.join("\n")}\n` : "", "utf8");
+        await writePrivateFileAtomic(
+          filePath,
+          compress ? await promisify(zlib.zstdCompress)(payload) : payload,
+        );
+      }
+      await this.transcriptIndex.delete(threadId);
+      return { threadId, quarantinedLineCount: quarantined.length, quarantinePath };
+    });
+  }
+
+  /**
+   * Rewrites `ts` values written before appends were validated as UTC RFC 3339
+   * timestamps, so the transcript sorts by time again. Values that cannot be
+   * read as a time are counted and left alone; sealed lines without their key
+   * are skipped.
+   */
+  async normalizeTranscriptTimestamps(
+    threadId: string,
+  ): Promise<TranscriptTimestampNormalizationResult> {
+    await this.ensureStorageReady();
+    const livePath = this.transcriptFilePath(threadId);
+    const archivedPath = this.archivedTranscriptFilePath(threadId);
+    await this.flushTranscriptWrites();
+    await this.spillSqliteTranscripts([threadId]);
+
+    return await this.transcriptLock.run(async () => {
+      const readKey = this.createTranscriptReadKeyLoader();
+      let normalizedCount = 0;
+      let unparseableCount = 0;
+      const archived = await this.readArchivedTranscript(threadId);
+      const live = await readFileIfExists(livePath);
+      const files = [
+        { filePath: archivedPath, raw: archived, compress: true },
+        { filePath: livePath, raw: live, compress: false },
+      ];
+      for (const { filePath, raw, compress } of files) {
+        if (!raw) {
+          continue;
+        }
+        const result = await this.normalizeLegacyTimestamps(raw, readKey);
+        normalizedCount += result.normalizedCount;
+        unparseableCount += result.unparseableCount;
+        if (result.payload) {
+          await writePrivateFileAtomic(
+            filePath,
+            compress ? await promisify(zlib.zstdCompress)(result.payload) : result.payload,
+          );
+        }
+      }
+      if (normalizedCount > 0) {
+        await this.transcriptIndex.delete(threadId);
+      }
+      return { threadId, normalizedCount, unparseableCount };
+    });
+  }
+
+  /**
+   * Rewrites the lines of a JSONL payload whose `ts` is not already a UTC
+   * RFC 3339 timestamp, keeping each line's encryption. `payload` is null when
+   * no line changed.
+   */
+  private async normalizeLegacyTimestamps(
+    raw: Buffer,
+    readKey: () => Promise<Buffer | null>,
+  ): Promise<{ payload: Buffer | null; normalizedCount: number; unparseableCount: number }> {
+    let normalizedCount = 0;
+    let unparseableCount = 0;
+    const lines: string[] = [];
+    for (const line of await this.parseTranscriptLines(raw, readKey)) {
+      const ts = line.kind === "event" ? coerceLegacyTranscriptTimestamp(line.event.ts) : null;
+      if (line.kind === "event" && ts === null) {
+        unparseableCount += 1;
+      }
+      if (line.kind !== "event" || ts === null || ts === line.event.ts) {
+        lines.push(line.raw);
+        continue;
+      }
+      const json = JSON.stringify({ ...line.event, ts });
+      const key = isEncryptedTranscriptLine(line.raw) ? await readKey() : null;
+      lines.push(key ? encryptTranscriptLine(key, json) : json);
+      normalizedCount += 1;
+    }
+    const payload = normalizedCount > 0 ? Buffer.from(`${lines.join("\n")}\n`, "utf8") : null;
+    return { payload, normalizedCount, unparseableCount };
+  }
+
+  /**
+   * Replaces credentials and `patterns` matches in the live and archived
+   * transcripts with `[REDACTED:<hash>]`, then atomically rewrites the files
+   * that changed. Lines keep their encryption; sealed lines whose key is
+   * unavailable cannot be scanned and are counted instead. With `dryRun`
+   * nothing is written.
+   */
+  async redactTranscript(input: RedactTranscriptInput): Promise<TranscriptRedactionResult> {
+    await this.ensureStorageReady();
+    const { threadId, dryRun } = input;
+    const livePath = this.transcriptFilePath(threadId);
+    const archivedPath = this.archivedTranscriptFilePath(threadId);
+    await this.flushTranscriptWrites();
+    if (!dryRun) {
+      await this.spillSqliteTranscripts([threadId]);
+    }
+
+    return await this.transcriptLock.run(async () => {
+      const readKey = this.createTranscriptReadKeyLoader();
+      const redactor = new TranscriptRedactor(input.patterns);
+      const rewrites: Array<{ filePath: string; lines: string[]; compress: boolean }> = [];
+      let redactedEventCount = 0;
+      let sealedLineCount = 0;
+      const archived = await this.readArchivedTranscript(threadId);
+      const live = await readFileIfExists(livePath);
+      // A dry run reads SQLite rows in place rather than spilling them to the file.
+      const sqlite = dryRun ? this.sqliteTranscripts : null;
+      const sqliteLines = sqlite ? await this.readStoredLines(sqlite, threadId, readKey) : [];
+      const files: Array<{ filePath: string | null; raw: Buffer | null; compress: boolean }> = [
+        { filePath: archivedPath, raw: archived, compress: true },
+        { filePath: livePath, raw: live, compress: false },
+        {
+          filePath: null,
+          raw: Buffer.from(sqliteLines.map((line) => line.text).join("\n"), "utf8"),
+          compress: false,
+        },
+      ];
+      for (const { filePath, raw, compress } of files) {
+        if (!raw || raw.length === 0) {
+          continue;
+        }
+        let changed = false;
+        const lines: string[] = [];
+        for (const line of await this.parseTranscriptLines(raw, readKey)) {
+          const redacted = line.kind === "event" ? redactor.redactEvent(line.event) : null;
+          sealedLineCount += line.kind === "sealed" ? 1 : 0;
+          if (!redacted) {
+            lines.push(line.raw);
+            continue;
+          }
+          const json = JSON.stringify(redacted);
+          const key = isEncryptedTranscriptLine(line.raw) ? await readKey() : null;
+          lines.push(key ? encryptTranscriptLine(key, json) : json);
+          redactedEventCount += 1;
+          changed = true;
+        }
+        if (changed && filePath) {
+          rewrites.push({ filePath, lines, compress });
+        }
+      }
+
+      if (!dryRun) {
+        for (const { filePath, lines, compress } of rewrites) {
+          const payload = Buffer.from(`${lines.join("\n")}\n`, "utf8");
+          await writePrivateFileAtomic(
+            filePath,
+            compress ? await promisify(zlib.zstdCompress)(payload) : payload,
+          );
+        }
+        // A redacted line can keep its length, so the index's snippet is dropped explicitly.
+        if (rewrites.length > 0) {
+          await this.transcriptIndex.delete(threadId);
+        }
+      }
+      return {
+        threadId,
+        dryRun,
+        redactionCount: redactor.redactionCount,
+        redactedEventCount,
+        matches: redactor.matches,
+        sealedLineCount,
+      };
+    });
+  }
+
+  private createTranscriptReadKeyLoader(): () => Promise<Buffer | null> {
+    let key: Promise<Buffer | null> | undefined;
+    return async () => {
+      key ??= this.transcriptEncryption.getReadKey();
+      return await key;
+    };
+  }
+
+  private async parseTranscriptLines(
+    raw: Buffer,
+    readKey: () => Promise<Buffer | null>,
+  ): Promise<ParsedTranscriptLine[]> {
+    const parsed: ParsedTranscriptLine[] = [];
+    const lines = raw.toString("utf8").split(/\r?\n/);
+    for (const [index, line] of lines.entries()) {
+      const trimmed = line.trim();
+      const decoded = await this.decodeTranscriptLine(trimmed, readKey);
+      if (decoded) {
+        parsed.push({ ...decoded, line: index + 1, raw: trimmed });
+      }
+    }
+    return parsed;
+  }
+
+  /** Decodes one trimmed transcript line; blank lines decode to null. */
+  private async decodeTranscriptLine(
+    trimmed: string,
+    readKey: () => Promise<Buffer | null>,
+  ): Promise<DecodedTranscriptLine | null> {
+    if (!trimmed) {
+      return null;
+    }
+    let parsedJson: unknown;
+    try {
+      let plaintext = trimmed;
+      if (isEncryptedTranscriptLine(trimmed)) {
+        const key = await readKey();
+        if (!key) {
+          return { kind: "sealed" };
+        }
+        plaintext = decryptTranscriptLine(key, trimmed);
+      }
+      parsedJson = JSON.parse(plaintext);
+    } catch {
+      return { kind: "corrupt", reason: "unparseable" };
+    }
+
+    const parsedLine = transcriptEventSchema.safeParse(parsedJson);
+    if (!parsedLine.success) {
+      return { kind: "corrupt", reason: "invalid_event" };
+    }
+    return { kind: "event", event: parsedLine.data as TranscriptEvent };
+  }
+
+  /**
+   * Streams the lines of one transcript source from `start`, or returns null
+   * when it holds nothing for the thread. `start` is a byte offset for files
+   * and a sequence number for SQLite. The archive is compressed, so it is
+   * decompressed from the beginning and skipped ahead.
+   */
+  private async openTranscriptSource(
+    threadId: string,
+    source: TranscriptSource,
+    start: number,
+  ): Promise<TranscriptLineSource | null> {
+    if (source === "live") {
+      return await this.jsonlTranscripts.openLines(threadId, start);
+    }
+    if (source === "sqlite") {
+      return (await this.sqliteTranscripts?.openLines(threadId, start)) ?? null;
+    }
+    let handle: FileHandle;
+    try {
+      handle = await fs.open(this.archivedTranscriptFilePath(threadId), "r");
+    } catch (error) {
+      if (isNotFound(error)) {
+        return null;
+      }
+      throw new Error(`Failed to read transcript: ${String(error)}`);
+    }
+    const raw = handle.createReadStream();
+    const decompressed = raw.pipe(zlib.createZstdDecompress());
+    raw.on("error", (error) => decompressed.destroy(error));
+    return {
+      lines: readTranscriptLines(decompressed, { skip: start }),
+      close: async () => {
+        decompressed.destroy();
+        raw.destroy();
+        await handle.close().catch(() => {});
+      },
+    };
+  }
+
+  async appendTranscriptEvent(event: TranscriptBatchInput): Promise<void> {
+    await this.appendTranscriptBatch([event]);
+  }
+
+  /**
+   * Appends events after checking their payloads per the `transcriptValidation`
+   * setting; in strict mode a malformed payload rejects the whole batch.
+   */
+  async appendTranscriptBatch(events: TranscriptBatchInput[]): Promise<void> {
+    if (events.length === 0) {
+      return;
+    }
+
+    const checked = applyTranscriptValidation(events, await this.getTranscriptValidationMode());
+    const write = this.writeOrBufferTranscriptBatch(checked);
+    this.pendingTranscriptWrites.add(write);
+    try {
+      await write;
+    } finally {
+      this.pendingTranscriptWrites.delete(write);
+    }
+  }
+
+  /**
+   * Writes transcript events held back while the disk was low. Throws
+   * `LowDiskSpaceError` and keeps the buffer when space has not come back.
+   */
+  async flushBufferedTranscripts(): Promise<DiskSpaceStatus> {
+    const diskSpace = await this.checkDiskSpace({ refresh: true });
+    if (diskSpace.low) {
+      throw new LowDiskSpaceError("transcript_append", diskSpace);
+    }
+    if (this.bufferedTranscriptEvents.length > 0) {
+      await this.writeTranscriptBatchWithBuffered([]);
+    }
+    return this.diskSpaceStatus(diskSpace);
+  }
+
+  private diskSpaceStatus(check: DiskSpaceCheck): DiskSpaceStatus {
+    return { ...check, bufferedTranscriptEvents: this.bufferedTranscriptEvents.length };
+  }
+
+  private async checkDiskSpace(opts: { refresh?: boolean } = {}): Promise<DiskSpaceCheck> {
+    if (!this.storageGuard) {
+      return { freeBytes: null, minFreeBytes: 0, low: false };
+    }
+    const check = await this.storageGuard.check(this.appDataDir, opts);
+    if (check.low !== this.diskSpaceLow) {
+      this.diskSpaceLow = check.low;
+      this.onDiskSpaceChanged?.(this.diskSpaceStatus(check));
+    }
+    return check;
+  }
+
+  /**
+   * Appends straight to disk while space allows. Below the guard threshold the
+   * events wait in a bounded in-memory buffer rather than risk a half-written
+   * JSONL line, and are written ahead of the next batch once space returns.
+   */
+  private async writeOrBufferTranscriptBatch(events: TranscriptBatchInput[]): Promise<void> {
+    const diskSpace = await this.checkDiskSpace();
+    if (!diskSpace.low) {
+      await this.writeTranscriptBatchWithBuffered(events);
+      return;
+    }
+    if (this.bufferedTranscriptEvents.length + events.length > this.maxBufferedTranscriptEvents) {
+      throw new LowDiskSpaceError("transcript_append", diskSpace);
+    }
+    this.bufferedTranscriptEvents.push(...events.map(normalizeTranscriptBatchInput));
+  }
+
+  private async writeTranscriptBatchWithBuffered(events: TranscriptBatchInput[]): Promise<void> {
+    const buffered = this.bufferedTranscriptEvents;
+    if (buffered.length === 0) {
+      await this.writeTranscriptBatch(events);
+      return;
+    }
+    this.bufferedTranscriptEvents = [];
+    try {
+      await this.writeTranscriptBatch([...buffered, ...events]);
+    } catch (error) {
+      this.bufferedTranscriptEvents = [...buffered, ...this.bufferedTranscriptEvents];
+      throw error;
+    }
+  }
+
+  /** Called with each thread's events once they have been appended to its transcript. */
+  onTranscriptAppended(listener: (events: TranscriptEvent[]) => void): () => void {
+    this.transcriptAppendListeners.add(listener);
+    return () => {
+      this.transcriptAppendListeners.delete(listener);
+    };
+  }
+
+  /** Waits for transcript appends that are already in flight and syncs them to disk. */
+  async flushTranscriptWrites(): Promise<void> {
+    await Promise.allSettled([...this.pendingTranscriptWrites]);
+    await this.transcriptWriter.flush();
+  }
+
+  /** Closes the SQLite transcript store; it reopens if transcripts are touched again. */
+  closeTranscriptDatabase(): void {
+    this.sqliteTranscriptStore?.close();
+  }
+
+  /** Waits for queued state saves and restores to reach disk. */
+  async flushStateWrites(): Promise<void> {
+    await this.runStateLocked(async () => {});
+  }
+
+  private async writeTranscriptBatch(events: TranscriptBatchInput[]): Promise<void> {
+    await this.ensureStorageReady();
+    await fs.mkdir(this.transcriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+
+    const grouped = new Map<string, TranscriptBatchInput[]>();
+    for (const event of events) {
+      const normalized = normalizeTranscriptBatchInput(event);
+      const bucket = grouped.get(normalized.threadId);
+      if (bucket) {
+        bucket.push(normalized);
+      } else {
+        grouped.set(normalized.threadId, [normalized]);
+      }
+    }
+
+    await Promise.all(
+      [...grouped].map(([threadId, chunk]) => this.transcriptWriter.enqueue(threadId, chunk)),
+    );
+  }
+
+  /** Called by the write queue with every chunk coalesced for one thread since its last append. */
+  private async appendTranscriptChunk(
+    threadId: string,
+    chunk: TranscriptBatchInput[],
+  ): Promise<void> {
+    const key = await this.transcriptEncryption.getWriteKey();
+    const lines = chunk.map((event) => {
+      const line = JSON.stringify(event);
+      return { ts: event.ts, text: key ? encryptTranscriptLine(key, line) : line };
+    });
+    // The store is picked under the lock so appends follow a migration that holds it.
+    await this.transcriptLock.run(async () => {
+      const store = await this.activeTranscriptStore();
+      await store.append(threadId, lines);
+      await this.advanceTranscriptIndex(
+        threadId,
+        store.kind,
+        lines.map((line, index) => ({
+          bytes: Buffer.byteLength(line.text) + 1,
+          event: chunk[index],
+          sealed: key !== null,
+        })),
+      );
+    });
+    for (const listener of this.transcriptAppendListeners) {
+      listener(chunk);
+    }
+  }
+
+  /**
+   * Keeps the thread's index in step with an append to `kind`. An index that
+   * no longer matches the live file's size or the SQLite row count is dropped
+   * instead, and the next preview rebuilds it; a failure here never fails the
+   * append.
+   */
+  private async advanceTranscriptIndex(
+    threadId: string,
+    kind: TranscriptStoreKind,
+    appended: IndexedTranscriptLine[],
+  ): Promise<void> {
+    try {
+      const { liveBytes, sqliteLines } = await this.transcriptIndexSizes(threadId);
+      const appendedBytes =
+        kind === "jsonl" ? appended.reduce((total, line) => total + line.bytes, 0) : 0;
+      const appendedRows = kind === "sqlite" ? appended.length : 0;
+      let current = await this.transcriptIndex.read(threadId);
+      // A brand new transcript needs no rebuild to start its index.
+      if (
+        !current &&
+        liveBytes === appendedBytes &&
+        sqliteLines === appendedRows &&
+        (await fileSize(this.archivedTranscriptFilePath(threadId))) === 0
+      ) {
+        current = emptyTranscriptIndex();
+      }
+      if (
+        !current ||
+        current.liveBytes + appendedBytes !== liveBytes ||
+        current.sqliteLines + appendedRows !== sqliteLines
+      ) {
+        await this.transcriptIndex.delete(threadId);
+        return;
+      }
+      await this.transcriptIndex.write(
+        threadId,
+        kind === "jsonl"
+          ? advanceTranscriptIndex(current, appended)
+          : advanceSqliteTranscriptIndex(current, appended),
+      );
+    } catch (error) {
+      logError("persistence", error, { operation: "update_transcript_index", threadId });
+      await this.transcriptIndex.delete(threadId).catch(() => {});
+    }
+  }
+
+  /** What an up-to-date index of the thread would record for its live file and SQLite rows. */
+  private async transcriptIndexSizes(
+    threadId: string,
+  ): Promise<{ liveBytes: number; sqliteLines: number }> {
+    return {
+      liveBytes: await fileSize(this.transcriptFilePath(threadId)),
+      sqliteLines: (await this.sqliteTranscripts?.countLines(threadId)) ?? 0,
+    };
+  }
+
+  /**
+   * Event counts and last-message snippets for the sidebar, read from each
+   * thread's index rather than its transcript. Indexes that are missing or
+   * stale are rebuilt once from the transcript.
+   */
+  async getThreadPreviews(threadIds: string[]): Promise<ThreadPreview[]> {
+    await this.ensureStorageReady();
+    const previews: ThreadPreview[] = [];
+    for (const threadId of threadIds) {
+      const cached = await this.transcriptIndex.read(threadId);
+      if (cached) {
+        const { liveBytes, sqliteLines } = await this.transcriptIndexSizes(threadId);
+        if (cached.liveBytes === liveBytes && cached.sqliteLines === sqliteLines) {
+          previews.push(toThreadPreview(threadId, cached));
+          continue;
+        }
+      }
+      const rebuilt = await this.transcriptLock.run(async () => {
+        const index = await this.buildTranscriptIndex(threadId);
+        await this.transcriptIndex.write(threadId, index);
+        return index;
+      });
+      previews.push(toThreadPreview(threadId, rebuilt));
+    }
+    return previews;
+  }
+
+  /** Reads every source of the transcript once; call with the transcript lock held. */
+  private async buildTranscriptIndex(threadId: string): Promise<TranscriptIndexFile> {
+    const readKey = this.createTranscriptReadKeyLoader();
+    let index = emptyTranscriptIndex();
+    for (const source of TRANSCRIPT_SOURCES) {
+      const stream = await this.openTranscriptSource(threadId, source, 0);
+      if (!stream) {
+        continue;
+      }
+      let position = 0;
+      try {
+        for await (const line of stream.lines) {
+          const trimmed = line.text.trim();
+          const decoded = await this.decodeTranscriptLine(trimmed, readKey);
+          const event = decoded?.kind === "event" ? decoded.event : null;
+          const sealed = isEncryptedTranscriptLine(trimmed);
+          if (source === "live") {
+            index = advanceTranscriptIndex(index, [
+              { bytes: line.end - position, event, sealed },
+            ]);
+            position = line.end;
+          } else if (source === "sqlite") {
+            index = advanceSqliteTranscriptIndex(index, [{ bytes: line.bytes, event, sealed }]);
+          } else if (event) {
+            index = recordIndexedEvent(index, event, sealed);
+          }
+        }
+      } finally {
+        await stream.close();
+      }
+    }
+    return index;
+  }
+
+  private async syncTranscriptFile(threadId: string): Promise<void> {
+    await (await this.activeTranscriptStore()).sync(threadId);
+  }
+
+  async getTranscriptEncryption(): Promise<TranscriptEncryptionStatus> {
+    await this.ensureStorageReady();
+    return await this.transcriptEncryption.getStatus();
+  }
+
+  /**
+   * Turns encryption of newly appended transcript lines on or off. Existing
+   * files are left as they are; see `encryptExistingTranscripts`.
+   */
+  async setTranscriptEncryption(enabled: boolean): Promise<TranscriptEncryptionStatus> {
+    await this.ensureStorageReady();
+    return await this.transcriptEncryption.setEnabled(enabled);
+  }
+
+  /** Rewrites live and archived transcripts so every line is encrypted. */
+  async encryptExistingTranscripts(
+    options: TranscriptMaintenanceOptions = {},
+  ): Promise<TranscriptEncryptionMigrationResult> {
+    const { signal, onProgress } = options;
+    await this.ensureStorageReady();
+    const key = await this.transcriptEncryption.getWriteKey();
+    if (!key) {
+      throw new Error("Turn on transcript encryption before encrypting existing transcripts.");
+    }
+    await this.flushTranscriptWrites();
+    // Indexes keep message snippets in the clear; they are rebuilt from the sealed files.
+    await this.transcriptIndex.clear();
+
+    const result: TranscriptEncryptionMigrationResult = { encryptedFiles: 0, unchangedFiles: 0 };
+    // Every file is rewritten on its own, so stopping between two leaves a mix reads handle.
+    const reportProgress = () => {
+      signal?.throwIfAborted();
+      onProgress?.({
+        completed: result.encryptedFiles + result.unchangedFiles,
+        total: null,
+        message: "Encrypting transcripts",
+      });
+    };
+    const migrate = async (
+      dir: string,
+      extension: string,
+      rewrite: (raw: Buffer) => Promise<Buffer | null>,
+    ) => {
+      let names: string[];
+      try {
+        names = await fs.readdir(dir);
+      } catch (error) {
+        if (isNotFound(error)) {
+          return;
+        }
+        throw new Error(`Failed to list transcripts: ${String(error)}`);
+      }
+      for (const name of names.filter((entry) => entry.endsWith(extension)).sort()) {
+        const filePath = path.join(dir, name);
+        assertWithinTranscriptsDir(dir, filePath);
+        reportProgress();
+        await this.transcriptLock.run(async () => {
+          const next = await rewrite(await fs.readFile(filePath));
+          if (next) {
+            await writePrivateFileAtomic(filePath, next);
+            result.encryptedFiles += 1;
+          } else {
+            result.unchangedFiles += 1;
+          }
+        });
+      }
+    };
+
+    await migrate(this.transcriptsDir, TRANSCRIPT_EXTENSION, async (raw) =>
+      encryptTranscriptPayload(key, raw),
+    );
+    await migrate(this.archivedTranscriptsDir, ARCHIVED_TRANSCRIPT_EXTENSION, async (raw) => {
+      const sealed = encryptTranscriptPayload(key, await promisify(zlib.zstdDecompress)(raw));
+      return sealed ? await promisify(zlib.zstdCompress)(sealed) : null;
+    });
+    // Each thread's SQLite rows count as one file.
+    const sqlite = this.sqliteTranscripts;
+    if (sqlite) {
+      for (const transcriptId of await sqlite.listTranscriptIds()) {
+        reportProgress();
+        await this.transcriptLock.run(async () => {
+          const lines = await this.readStoredLines(sqlite, transcriptId, async () => key);
+          if (lines.every((line) => isEncryptedTranscriptLine(line.text))) {
+            result.unchangedFiles += 1;
+            return;
+          }
+          await sqlite.replace(
+            transcriptId,
+            lines.map((line) =>
+              isEncryptedTranscriptLine(line.text)
+                ? line
+                : { ...line, text: encryptTranscriptLine(key, line.text) },
+            ),
+          );
+          result.encryptedFiles += 1;
+        });
+      }
+    }
+    return result;
+  }
+
+  /**
+   * Moves every live transcript into `target` and switches appends to it.
+   * Holds the transcript lock throughout, so appends waiting on it land in
+   * the target. Archived transcripts stay in cold storage either way. When
+   * stopped part way, reads still merge both stores and appends keep going to
+   * the old one.
+   */
+  async migrateTranscriptStore(
+    target: TranscriptStoreKind,
+    options: TranscriptMaintenanceOptions = {},
+  ): Promise<TranscriptStoreMigrationResult> {
+    const { signal, onProgress } = options;
+    await this.ensureStorageReady();
+    const setKind = this.setTranscriptStoreKind;
+    if (!setKind) {
+      throw new Error("Switching the transcript store is unavailable in this build.");
+    }
+    const sqlite = this.requireSqliteTranscripts();
+    const [from, to]: TranscriptStore[] =
+      target === "sqlite" ? [this.jsonlTranscripts, sqlite] : [sqlite, this.jsonlTranscripts];
+    await this.flushTranscriptWrites();
+    const readKey = this.createTranscriptReadKeyLoader();
+    return await this.transcriptLock.run(async () => {
+      const result: TranscriptStoreMigrationResult = {
+        target,
+        migratedTranscripts: 0,
+        migratedLines: 0,
+      };
+      const transcriptIds = await from.listTranscriptIds();
+      for (const transcriptId of transcriptIds) {
+        signal?.throwIfAborted();
+        onProgress?.({
+          completed: result.migratedTranscripts,
+          total: transcriptIds.length,
+          message: `Moving transcripts to ${target === "sqlite" ? "SQLite" : "JSONL files"}`,
+        });
+        // Reads take JSONL lines before SQLite rows; the copy keeps that order.
+        const lines = [
+          ...(await this.readStoredLines(this.jsonlTranscripts, transcriptId, readKey)),
+          ...(await this.readStoredLines(sqlite, transcriptId, readKey)),
+        ];
+        await to.replace(transcriptId, lines);
+        await from.delete(transcriptId);
+        result.migratedTranscripts += 1;
+        result.migratedLines += lines.length;
+      }
+      await setKind(target);
+      return result;
+    });
+  }
+
+  /**
+   * Moves SQLite rows for `transcriptIds` to the end of their JSONL files, so
+   * file maintenance such as repair, archiving, and the trash sees the whole
+   * transcript. Later appends still go to the active store.
+   */
+  private async spillSqliteTranscripts(transcriptIds: string[]): Promise<void> {
+    const sqlite = this.sqliteTranscripts;
+    if (!sqlite) {
+      return;
+    }
+    const readKey = this.createTranscriptReadKeyLoader();
+    await this.transcriptLock.run(async () => {
+      for (const transcriptId of new Set(transcriptIds)) {
+        const lines = await this.readStoredLines(sqlite, transcriptId, readKey);
+        if (lines.length > 0) {
+          await this.jsonlTranscripts.append(transcriptId, lines);
+          await sqlite.delete(transcriptId);
+        }
+      }
+    });
+  }
+
+  /** Reads a store's encoded lines for a thread as they are, skipping blanks. */
+  private async readStoredLines(
+    store: TranscriptStore,
+    threadId: string,
+    readKey: () => Promise<Buffer | null>,
+  ): Promise<StoredTranscriptLine[]> {
+    const source = await store.openLines(threadId, 0);
+    if (!source) {
+      return [];
+    }
+    const lines: StoredTranscriptLine[] = [];
+    try {
+      for await (const { text } of source.lines) {
+        const trimmed = text.trim();
+        if (!trimmed) {
+          continue;
+        }
+        // Sealed lines without a key and corrupt lines move along without a timestamp.
+        const decoded = await this.decodeTranscriptLine(trimmed, readKey);
+        lines.push({ ts: decoded?.kind === "event" ? decoded.event.ts : "", text: trimmed });
+      }
+    } finally {
+      await source.close();
+    }
+    return lines;
+  }
+
+  /** Sizes the JSONL transcripts for `threadIds`; threads without a file count as empty. */
+  async getTranscriptStats(
+    threadIds: string[],
+  ): Promise<{ fileCount: number; totalBytes: number; largestBytes: number }> {
+    await this.ensureStorageReady();
+    const stats = { fileCount: 0, totalBytes: 0, largestBytes: 0 };
+    const filePaths = threadIds.flatMap((threadId) => [
+      this.transcriptFilePath(threadId),
+      this.archivedTranscriptFilePath(threadId),
+    ]);
+    for (const filePath of filePaths) {
+      try {
+        const { size } = await fs.stat(filePath);
+        stats.fileCount += 1;
+        stats.totalBytes += size;
+        stats.largestBytes = Math.max(stats.largestBytes, size);
+      } catch (error) {
+        if (!isNotFound(error)) {
+          throw error;
+        }
+      }
+    }
+    return stats;
+  }
+
+  /** Counts what `removeRecords` would delete for the same target. */
+  async measureRecordRemoval(target: RecordRemovalTarget): Promise<DestructiveCommandImpact> {
+    const removed = selectRemovedRecords(await this.loadState(), target);
+    const transcripts = await this.getTranscriptStats(removed.transcriptIds);
+    return {
+      workspaceCount: removed.workspaces.length,
+      threadCount: removed.threads.length,
+      transcriptCount: transcripts.fileCount,
+      transcriptBytes: transcripts.totalBytes,
+    };
+  }
+
+  private async measureWorkspaceStorage(workspaceId: string): Promise<ThreadStorageUsage[]> {
+    assertSafeId(workspaceId, "workspaceId");
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const state = await this.loadState();
+    if (!state.workspaces.some((workspace) => workspace.id === workspaceId)) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    const usage: ThreadStorageUsage[] = [];
+    for (const thread of state.threads) {
+      if (thread.workspaceId !== workspaceId) {
+        continue;
+      }
+      const entry = { thread, liveBytes: 0, archiveBytes: 0, quarantineBytes: 0 };
+      for (const transcriptId of new Set(transcriptIdsForThread(thread))) {
+        entry.liveBytes += await fileSize(this.transcriptFilePath(transcriptId));
+        entry.archiveBytes += await fileSize(this.archivedTranscriptFilePath(transcriptId));
+        entry.quarantineBytes += await fileSize(this.quarantinedTranscriptFilePath(transcriptId));
+      }
+      usage.push(entry);
+    }
+    return usage;
+  }
+
+  /** Sizes the live, archived, and quarantined transcripts of the workspace's threads. */
+  async getWorkspaceStorageReport(workspaceId: string): Promise<WorkspaceStorageReport> {
+    const usage = await this.measureWorkspaceStorage(workspaceId);
+    const report: WorkspaceStorageReport = {
+      workspaceId,
+      threadCount: usage.length,
+      transcriptBytes: 0,
+      archiveBytes: 0,
+      quarantineBytes: 0,
+      totalBytes: 0,
+    };
+    for (const entry of usage) {
+      report.transcriptBytes += entry.liveBytes;
+      report.archiveBytes += entry.archiveBytes;
+      report.quarantineBytes += entry.quarantineBytes;
+    }
+    report.totalBytes = report.transcriptBytes + report.archiveBytes + report.quarantineBytes;
+    return report;
+  }
+
+  /** Lists the threads and bytes each cleanup target would touch, without changing anything. */
+  async planWorkspaceStorageCleanup(
+    workspaceId: string,
+    targets: WorkspaceStorageCleanupTarget[],
+  ): Promise<WorkspaceStorageCleanupAction[]> {
+    const usage = await this.measureWorkspaceStorage(workspaceId);
+    const idleBeforeMs = this.now().getTime() - TRANSCRIPT_COMPACTION_IDLE_MS;
+    return [...new Set(targets)].map((target) => {
+      const action: WorkspaceStorageCleanupAction = { target, threadIds: [], bytes: 0 };
+      for (const entry of usage) {
+        const bytes = storageCleanupBytes(target, entry, idleBeforeMs);
+        if (bytes !== null) {
+          action.threadIds.push(entry.thread.id);
+          action.bytes += bytes;
+        }
+      }
+      return action;
+    });
+  }
+
+  /**
+   * Compresses the live transcripts of `threadIds` into the archive without
+   * archiving the threads. Reads fall back to the archive and new events start
+   * a fresh live file, so the threads stay usable.
+   */
+  async compactTranscripts(threadIds: string[]): Promise<void> {
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const wanted = new Set(threadIds);
+    const threads = (await this.loadState()).threads.filter((thread) => wanted.has(thread.id));
+    const transcriptIds = [...new Set(threads.flatMap(transcriptIdsForThread))];
+    await this.spillSqliteTranscripts(transcriptIds);
+    await this.transcriptLock.run(async () => {
+      for (const transcriptId of transcriptIds) {
+        await this.archiveTranscriptFile(transcriptId);
+      }
+    });
+  }
+
+  /** Deletes the lines `repairTranscript` quarantined for `threadIds`. */
+  async clearTranscriptQuarantine(threadIds: string[]): Promise<void> {
+    await this.ensureStorageReady();
+    const wanted = new Set(threadIds);
+    const threads = (await this.loadState()).threads.filter((thread) => wanted.has(thread.id));
+    for (const transcriptId of new Set(threads.flatMap(transcriptIdsForThread))) {
+      await fs.rm(this.quarantinedTranscriptFilePath(transcriptId), { force: true });
+    }
+  }
+
+  /**
+   * Removes workspaces and threads from state, then deletes the transcripts
+   * that belonged to them, or moves them to the trash together with the
+   * removed records when a trash is configured. The previous state is
+   * snapshotted first.
+   */
+  async removeRecords(target: RecordRemovalTarget): Promise<PersistedState> {
+    await this.ensureStorageReady();
+    if (this.trash) {
+      await this.flushTranscriptWrites();
+    }
+    const { state, removed } = await this.runStateLocked(async () => {
+      const current = await this.readStateFileLocked();
+      const removed = selectRemovedRecords(current, target);
+      const removedWorkspaceIds = new Set(removed.workspaces.map((workspace) => workspace.id));
+      const removedThreadIds = new Set(removed.threads.map((thread) => thread.id));
+      const next: PersistedState = {
+        ...current,
+        workspaces: current.workspaces.filter(
+          (workspace) => !removedWorkspaceIds.has(workspace.id),
+        ),
+        threads: current.threads.filter((thread) => !removedThreadIds.has(thread.id)),
+      };
+      if (removedWorkspaceIds.size > 0 || removedThreadIds.size > 0) {
+        await this.captureStateSnapshotLocked();
+        await this.writeStateFileLocked(next);
+      }
+      return { state: next, removed };
+    });
+    if (this.trash) {
+      if (removed.workspaces.length > 0 || removed.threads.length > 0) {
+        await this.spillSqliteTranscripts(removed.transcriptIds);
+        await this.trash.put({
+          ...describeTrashEntry(removed),
+          files: removed.transcriptIds.flatMap((id) => this.transcriptFilePaths(id)),
+          records: { workspaces: removed.workspaces, threads: removed.threads },
+        });
+        for (const transcriptId of removed.transcriptIds) {
+          await this.transcriptIndex.delete(transcriptId);
+        }
+      }
+      return state;
+    }
+    for (const transcriptId of removed.transcriptIds) {
+      await this.deleteTranscript(transcriptId);
+    }
+    return state;
+  }
+
+  /**
+   * Moves a thread's transcripts to the trash along with the thread record,
+   * so restoring brings the thread back if it has since been removed from
+   * state. Every transcript id of the thread goes into one entry; later calls
+   * for its other ids find nothing left to move. Deletes outright when no
+   * trash is configured.
+   */
+  async trashTranscript(threadId: string): Promise<void> {
+    const trash = this.trash;
+    if (!trash) {
+      await this.deleteTranscript(threadId);
+      return;
+    }
+    assertSafeId(threadId, "threadId");
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const state = await this.loadState();
+    const thread = state.threads.find((candidate) =>
+      transcriptIdsForThread(candidate).includes(threadId),
+    );
+    const transcriptIds = thread ? [...new Set(transcriptIdsForThread(thread))] : [threadId];
+    await this.spillSqliteTranscripts(transcriptIds);
+    const files: string[] = [];
+    for (const filePath of transcriptIds.flatMap((id) => this.transcriptFilePaths(id))) {
+      try {
+        await fs.access(filePath);
+        files.push(filePath);
+      } catch (error) {
+        if (!isNotFound(error)) {
+          throw error;
+        }
+      }
+    }
+    if (files.length === 0) {
+      return;
+    }
+    await trash.put({
+      kind: "thread",
+      label: thread?.title ?? threadId,
+      files,
+      records: { workspaces: [], threads: thread ? [thread] : [] },
+    });
+    for (const transcriptId of transcriptIds) {
+      await this.transcriptIndex.delete(transcriptId);
+    }
+  }
+
+  /**
+   * Brings back a trash entry's transcripts and re-adds its workspaces and
+   * threads that are no longer in state. Fails without changing anything when
+   * a restored thread's workspace is gone.
+   */
+  async restoreFromTrash(entryId: string): Promise<RestoreFromTrashResult> {
+    const trash = this.trash;
+    if (!trash) {
+      throw new Error("Trash is not configured.");
+    }
+    await this.ensureStorageReady();
+    return await trash.restore(entryId, async (records) => {
+      return await this.runStateLocked(async () => {
+        const current = await this.readStateFileLocked();
+        const workspaceIds = new Set(current.workspaces.map((workspace) => workspace.id));
+        const threadIds = new Set(current.threads.map((thread) => thread.id));
+        const workspaces = records.workspaces.filter(
+          (workspace) => !workspaceIds.has(workspace.id),
+        );
+        for (const workspace of workspaces) {
+          workspaceIds.add(workspace.id);
+        }
+        const threads = records.threads.filter((thread) => !threadIds.has(thread.id));
+        const orphan = threads.find((thread) => !workspaceIds.has(thread.workspaceId));
+        if (orphan) {
+          throw new Error(`Cannot restore "${orphan.title}": its workspace no longer exists.`);
+        }
+        if (workspaces.length > 0 || threads.length > 0) {
+          await this.writeStateFileLocked({
+            ...current,
+            workspaces: [...current.workspaces, ...workspaces],
+            threads: [...current.threads, ...threads],
+          });
+        }
+        return { workspaces, threads };
+      });
+    });
+  }
+
+  /**
+   * Moves the thread's transcripts into zstd-compressed cold storage and marks
+   * it archived. Reads fall back to the archive, so the history stays
+   * available without keeping the JSONL in the transcripts directory.
+   */
+  async archiveThread(threadId: string): Promise<ThreadRecord> {
+    return await this.updateThreadArchive(threadId, true);
+  }
+
+  /** Restores archived transcripts to plain JSONL and clears the archived flag. */
+  async unarchiveThread(threadId: string): Promise<ThreadRecord> {
+    return await this.updateThreadArchive(threadId, false);
+  }
+
+  /**
+   * Sets a thread's title under the state lock. Titles the user typed win over
+   * generated ones: a non-manual title leaves a manual one in place.
+   */
+  async setThreadTitle(
+    threadId: string,
+    title: string,
+    titleSource: NonNullable<ThreadRecord["titleSource"]>,
+  ): Promise<ThreadRecord> {
+    assertSafeId(threadId, "threadId");
+    const trimmed = title.trim();
+    if (!trimmed) {
+      throw new Error("Thread title must not be empty");
+    }
+    await this.ensureStorageReady();
+    return await this.runStateLocked(async () => {
+      const state = await this.readStateFileLocked();
+      const thread = state.threads.find((candidate) => candidate.id === threadId);
+      if (!thread) {
+        throw new Error(`Unknown thread: ${threadId}`);
+      }
+      if (thread.titleSource === "manual" && titleSource !== "manual") {
+        return thread;
+      }
+      const next: ThreadRecord = { ...thread, title: trimmed, titleSource };
+      await this.writeStateFileLocked({
+        ...state,
+        threads: state.threads.map((candidate) => (candidate.id === threadId ? next : candidate)),
+      });
+      return next;
+    });
+  }
+
+  async setWorkspacePinned(workspaceId: string, pinned: boolean): Promise<WorkspaceRecord> {
+    return await this.updateWorkspaceRecord(workspaceId, (workspace) => ({
+      ...workspace,
+      pinned: pinned ? true : undefined,
+    }));
+  }
+
+  /** Replaces a workspace's tags after normalizing them; an empty list clears them. */
+  async setWorkspaceTags(workspaceId: string, tags: string[]): Promise<WorkspaceRecord> {
+    return await this.updateWorkspaceRecord(workspaceId, (workspace) => ({
+      ...workspace,
+      tags: normalizeWorkspaceTags(tags),
+    }));
+  }
+
+  /** Replaces a workspace's launch profiles; an empty list clears them. */
+  async setWorkspaceLaunchProfiles(
+    workspaceId: string,
+    profiles: WorkspaceLaunchProfile[],
+  ): Promise<WorkspaceRecord> {
+    const normalized = profiles.map((profile) => ({ ...profile, name: profile.name.trim() }));
+    const error = launchProfilesError(normalized);
+    if (error) {
+      throw new Error(error);
+    }
+    return await this.updateWorkspaceRecord(workspaceId, (workspace) => ({
+      ...workspace,
+      launchProfiles: normalized.length > 0 ? normalized : undefined,
+    }));
+  }
+
+  /** Replaces a workspace's path policy after checking it against the workspace roots. */
+  async setWorkspacePathPolicy(
+    workspaceId: string,
+    policy: WorkspacePathPolicy,
+  ): Promise<WorkspaceRecord> {
+    const trimmed = {
+      allow: policy.allow.map((rule) => ({ ...rule, path: rule.path.trim() })),
+      deny: policy.deny.map((rule) => ({ ...rule, path: rule.path.trim() })),
+    };
+    return await this.updateWorkspaceRecord(workspaceId, (workspace) => {
+      const error = pathPolicyError(workspace, trimmed);
+      if (error) {
+        throw new Error(error);
+      }
+      return { ...workspace, pathPolicy: normalizeWorkspacePathPolicy(trimmed) };
+    });
+  }
+
+  /** Sets the workspace's own idle policy; null goes back to the app settings. */
+  async setWorkspaceIdlePolicy(
+    workspaceId: string,
+    policy: WorkspaceIdlePolicy | null,
+  ): Promise<WorkspaceRecord> {
+    return await this.updateWorkspaceRecord(workspaceId, (workspace) => ({
+      ...workspace,
+      idlePolicy: normalizeWorkspaceIdlePolicy(policy),
+    }));
+  }
+
+  /**
+   * Rewrites the workspace list in the given order. The ids must name every
+   * workspace exactly once, so a stale renderer cannot silently drop one.
+   */
+  async reorderWorkspaces(workspaceIds: string[]): Promise<WorkspaceRecord[]> {
+    for (const workspaceId of workspaceIds) {
+      assertSafeId(workspaceId, "workspaceId");
+    }
+    if (new Set(workspaceIds).size !== workspaceIds.length) {
+      throw new Error("Workspace order lists a workspace more than once");
+    }
+    await this.ensureStorageReady();
+    return await this.runStateLocked(async () => {
+      const state = await this.readStateFileLocked();
+      const byId = new Map(state.workspaces.map((workspace) => [workspace.id, workspace]));
+      const unknownId = workspaceIds.find((workspaceId) => !byId.has(workspaceId));
+      if (unknownId) {
+        throw new Error(`Unknown workspace: ${unknownId}`);
+      }
+      if (workspaceIds.length !== state.workspaces.length) {
+        throw new Error("Workspace order must list every workspace");
+      }
+      const workspaces = workspaceIds.flatMap((workspaceId, sortOrder) => {
+        const workspace = byId.get(workspaceId);
+        return workspace ? [{ ...workspace, sortOrder }] : [];
+      });
+      await this.writeStateFileLocked({ ...state, workspaces });
+      return workspaces;
+    });
+  }
+
+  private async updateWorkspaceRecord(
+    workspaceId: string,
+    update: (workspace: WorkspaceRecord) => WorkspaceRecord,
+  ): Promise<WorkspaceRecord> {
+    assertSafeId(workspaceId, "workspaceId");
+    await this.ensureStorageReady();
+    return await this.runStateLocked(async () => {
+      const state = await this.readStateFileLocked();
+      const workspace = state.workspaces.find((candidate) => candidate.id === workspaceId);
+      if (!workspace) {
+        throw new Error(`Unknown workspace: ${workspaceId}`);
+      }
+      const next = update(workspace);
+      await this.writeStateFileLocked({
+        ...state,
+        workspaces: state.workspaces.map((candidate) =>
+          candidate.id === workspaceId ? next : candidate,
+        ),
+      });
+      return next;
+    });
+  }
+
+  private async updateThreadArchive(threadId: string, archived: boolean): Promise<ThreadRecord> {
+    assertSafeId(threadId, "threadId");
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    return await this.runStateLocked(async () => {
+      const state = await this.readStateFileLocked();
+      const thread = state.threads.find((candidate) => candidate.id === threadId);
+      if (!thread) {
+        throw new Error(`Unknown thread: ${threadId}`);
+      }
+      if (archived) {
+        await this.spillSqliteTranscripts(transcriptIdsForThread(thread));
+      }
+      for (const transcriptId of transcriptIdsForThread(thread)) {
+        if (archived) {
+          await this.archiveTranscriptFile(transcriptId);
+        } else {
+          await this.restoreArchivedTranscriptFile(transcriptId);
+        }
+      }
+      const next: ThreadRecord = archived
+        ? { ...thread, archived: true, archivedAt: thread.archivedAt ?? this.now().toISOString() }
+        : { ...thread, archived: false, archivedAt: undefined };
+      await this.writeStateFileLocked({
+        ...state,
+        threads: state.threads.map((candidate) => (candidate.id === threadId ? next : candidate)),
+      });
+      return next;
+    });
+  }
+
+  /**
+   * Copies a thread's transcript, optionally only through `throughEventIndex`,
+   * into a new thread recorded next to the original. The fork has no server
+   * session yet, so its first message continues from the copied history.
+   * Transcript append listeners are not notified; nothing new happened.
+   */
+  async forkThread(input: ForkThreadInput): Promise<ThreadRecord> {
+    assertSafeId(input.threadId, "threadId");
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const source = (await this.loadState()).threads.find(
+      (candidate) => candidate.id === input.threadId,
+    );
+    if (!source) {
+      throw new Error(`Unknown thread: ${input.threadId}`);
+    }
+
+    const history = await this.readThreadHistory(source);
+    if (history.length === 0) {
+      throw new Error("Thread has no transcript to fork");
+    }
+    const lastIndex = input.throughEventIndex ?? history.length - 1;
+    if (lastIndex >= history.length) {
+      throw new Error(`Event index ${lastIndex} is past the end of the transcript`);
+    }
+
+    const thread = await this.copyThreadTranscript(source, history.slice(0, lastIndex + 1), {
+      workspaceId: source.workspaceId,
+      title: input.title ?? `${source.title} (fork)`,
+    });
+    await this.runStateLocked(async () => {
+      const state = await this.readStateFileLocked();
+      await this.writeStateFileLocked({ ...state, threads: [...state.threads, thread] });
+    });
+    return thread;
+  }
+
+  /**
+   * Adds the source thread's history to the target's transcript, ordered by
+   * timestamp with the target's events first among equals, then marks the
+   * source as merged or moves it to the trash. The target's own lines are kept
+   * as written, so lines sealed with another device's key survive the rewrite.
+   */
+  async mergeThreads(input: MergeThreadsInput): Promise<ThreadMergeResult> {
+    assertSafeId(input.sourceThreadId, "sourceThreadId");
+    assertSafeId(input.targetThreadId, "targetThreadId");
+    if (input.sourceThreadId === input.targetThreadId) {
+      throw new Error("A thread cannot be merged into itself");
+    }
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const state = await this.loadState();
+    const findThread = (threadId: string) => {
+      const thread = state.threads.find((candidate) => candidate.id === threadId);
+      if (!thread) {
+        throw new Error(`Unknown thread: ${threadId}`);
+      }
+      if (thread.mergedIntoThreadId) {
+        throw new Error(`Thread ${threadId} was already merged into ${thread.mergedIntoThreadId}`);
+      }
+      return thread;
+    };
+    const source = findThread(input.sourceThreadId);
+    const target = findThread(input.targetThreadId);
+    if (source.workspaceId !== target.workspaceId) {
+      throw new Error("Only threads in the same workspace can be merged");
+    }
+    if (target.archived) {
+      throw new Error("Unarchive the target thread before merging into it");
+    }
+
+    // Annotations address events by their index in the transcript named after the thread.
+    const sourceEvents: Array<{ event: TranscriptEvent; index: number | null }> = [];
+    for (const transcriptId of new Set(transcriptIdsForThread(source))) {
+      const events = await this.readTranscript(transcriptId);
+      events.forEach((event, index) => {
+        sourceEvents.push({ event, index: transcriptId === source.id ? index : null });
+      });
+    }
+
+    await this.spillSqliteTranscripts([target.id]);
+    const eventIndexes = await this.transcriptLock.run(async () => {
+      const filePath = this.transcriptFilePath(target.id);
+      if (await this.readArchivedTranscript(target.id)) {
+        throw new Error("Unarchive the target thread before merging into it");
+      }
+      const live = await readFileIfExists(filePath);
+      const parsed = live
+        ? await this.parseTranscriptLines(live, this.createTranscriptReadKeyLoader())
+        : [];
+      if (parsed.some((line) => line.kind === "corrupt")) {
+        throw new Error("Repair the target thread's transcript before merging into it");
+      }
+
+      type MergeEntry = {
+        ts: string;
+        raw: string;
+        isEvent: boolean;
+        targetIndex: number | null;
+        sourceIndex: number | null;
+      };
+      // Sealed lines have no readable timestamp and stay after the event before them.
+      let lastTs = "";
+      let targetIndex = 0;
+      const entries: MergeEntry[] = parsed.map((line) => {
+        if (line.kind !== "event") {
+          return {
+            ts: lastTs,
+            raw: line.raw,
+            isEvent: false,
+            targetIndex: null,
+            sourceIndex: null,
+          };
+        }
+        lastTs = line.event.ts;
+        return {
+          ts: lastTs,
+          raw: line.raw,
+          isEvent: true,
+          targetIndex: targetIndex++,
+          sourceIndex: null,
+        };
+      });
+      const key = await this.transcriptEncryption.getWriteKey();
+      for (const { event, index } of sourceEvents) {
+        const line = JSON.stringify({ ...event, threadId: target.id });
+        entries.push({
+          ts: event.ts,
+          raw: key ? encryptTranscriptLine(key, line) : line,
+          isEvent: true,
+          targetIndex: null,
+          sourceIndex: index,
+        });
+      }
+      entries.sort((left, right) => left.ts.localeCompare(right.ts));
+
+      const indexes = { target: [] as number[], source: [] as number[] };
+      let mergedIndex = 0;
+      for (const entry of entries) {
+        if (!entry.isEvent) {
+          continue;
+        }
+        if (entry.targetIndex !== null) {
+          indexes.target[entry.targetIndex] = mergedIndex;
+        }
+        if (entry.sourceIndex !== null) {
+          indexes.source[entry.sourceIndex] = mergedIndex;
+        }
+        mergedIndex += 1;
+      }
+      if (sourceEvents.length > 0) {
+        await fs.mkdir(this.transcriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+        await writePrivateFileAtomic(
+          filePath,
+          Buffer.from(`${entries.map((entry) => entry.raw).join("\n")}\n`, "utf8"),
+        );
+      }
+      return indexes;
+    });
+
+    const mergedAt = this.now().toISOString();
+    const { thread, merged } = await this.runStateLocked(async () => {
+      const current = await this.readStateFileLocked();
+      const latest = current.threads.find((candidate) => candidate.id === target.id) ?? target;
+      const thread: ThreadRecord = {
+        ...latest,
+        createdAt: latest.createdAt < source.createdAt ? latest.createdAt : source.createdAt,
+        lastMessageAt:
+          latest.lastMessageAt > source.lastMessageAt ? latest.lastMessageAt : source.lastMessageAt,
+        messageCount: latest.messageCount + source.messageCount,
+      };
+      const merged: ThreadRecord | null = input.trashSource
+        ? null
+        : { ...source, mergedIntoThreadId: target.id, mergedAt };
+      await this.writeStateFileLocked({
+        ...current,
+        threads: current.threads.map((candidate) => {
+          if (candidate.id === target.id) {
+            return thread;
+          }
+          return merged && candidate.id === source.id ? merged : candidate;
+        }),
+      });
+      return { thread, merged };
+    });
+    if (input.trashSource) {
+      await this.removeRecords({ threadIds: [source.id] });
+    }
+    return { thread, source: merged, mergedEventCount: sourceEvents.length, eventIndexes };
+  }
+
+  /**
+   * Records a copy of a workspace's settings at `options.path` and copies the
+   * listed threads, with their transcripts, under new ids. Like forks, the
+   * copied threads have no server session yet. The caller copies the
+   * directory itself; `path` may also be the original one.
+   */
+  async duplicateWorkspace(
+    workspaceId: string,
+    options: { path: string; threadIds: string[] },
+  ): Promise<DuplicateWorkspaceResult> {
+    assertSafeId(workspaceId, "workspaceId");
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const state = await this.loadState();
+    const source = state.workspaces.find((candidate) => candidate.id === workspaceId);
+    if (!source) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    const sourceThreads = [...new Set(options.threadIds)].map((threadId) => {
+      const thread = state.threads.find((candidate) => candidate.id === threadId);
+      if (thread?.workspaceId !== workspaceId) {
+        throw new Error(`Thread ${threadId} does not belong to workspace ${workspaceId}`);
+      }
+      return thread;
+    });
+
+    const now = this.now().toISOString();
+    // List placement and the mirror folder belong to the original.
+    const {
+      pinned: _pinned,
+      sortOrder: _sortOrder,
+      transcriptMirror: _transcriptMirror,
+      ...settings
+    } = source;
+    const workspace: WorkspaceRecord = {
+      ...settings,
+      id: this.createId(),
+      name: `${source.name} (copy)`,
+      path: options.path,
+      createdAt: now,
+      lastOpenedAt: now,
+    };
+    const threads: ThreadRecord[] = [];
+    for (const thread of sourceThreads) {
+      threads.push(
+        await this.copyThreadTranscript(thread, await this.readThreadHistory(thread), {
+          workspaceId: workspace.id,
+          title: thread.title,
+        }),
+      );
+    }
+    await this.runStateLocked(async () => {
+      const current = await this.readStateFileLocked();
+      await this.writeStateFileLocked({
+        ...current,
+        workspaces: [...current.workspaces, workspace],
+        threads: [...current.threads, ...threads],
+      });
+    });
+    return { workspace, threads };
+  }
+
+  /**
+   * Saves a conversation that ran without a window, such as a scheduled task,
+   * as a new thread that can be resumed from its server session.
+   */
+  async recordThread(input: {
+    workspaceId: string;
+    title: string;
+    sessionId: string;
+    events: Array<Omit<TranscriptBatchInput, "threadId">>;
+  }): Promise<ThreadRecord> {
+    assertSafeId(input.workspaceId, "workspaceId");
+    await this.ensureStorageReady();
+    const id = this.createId();
+    const events = input.events.map((event) => ({ ...event, threadId: id }));
+    await this.appendTranscriptBatch(events);
+    await this.flushTranscriptWrites();
+
+    const createdAt = events[0]?.ts ?? this.now().toISOString();
+    const inferred = inferThreadFromTranscript(id, events);
+    const thread: ThreadRecord = {
+      id,
+      workspaceId: input.workspaceId,
+      title: input.title,
+      titleSource: "manual",
+      createdAt,
+      lastMessageAt: inferred?.lastMessageAt ?? createdAt,
+      status: "disconnected",
+      sessionId: input.sessionId,
+      messageCount: inferred?.messageCount ?? 0,
+      lastEventSeq: 0,
+    };
+    await this.runStateLocked(async () => {
+      const current = await this.readStateFileLocked();
+      if (!current.workspaces.some((workspace) => workspace.id === input.workspaceId)) {
+        throw new Error(`Unknown workspace: ${input.workspaceId}`);
+      }
+      await this.writeStateFileLocked({ ...current, threads: [...current.threads, thread] });
+    });
+    return thread;
+  }
+
+  private async readThreadHistory(thread: ThreadRecord): Promise<TranscriptEvent[]> {
+    const history: TranscriptEvent[] = [];
+    for (const transcriptId of new Set(transcriptIdsForThread(thread))) {
+      history.push(...(await this.readTranscript(transcriptId)));
+    }
+    return history.sort((left, right) => left.ts.localeCompare(right.ts));
+  }
+
+  /** Writes `history` as the transcript of a new thread and returns its unsaved record. */
+  private async copyThreadTranscript(
+    source: ThreadRecord,
+    history: TranscriptEvent[],
+    target: { workspaceId: string; title: string },
+  ): Promise<ThreadRecord> {
+    const id = this.createId();
+    const events = history.map((event) => ({ ...event, threadId: id }));
+    if (events.length > 0) {
+      const key = await this.transcriptEncryption.getWriteKey();
+      const lines = events.map((event) => {
+        const line = JSON.stringify(event);
+        return key ? encryptTranscriptLine(key, line) : line;
+      });
+      await fs.mkdir(this.transcriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+      await this.transcriptLock.run(async () => {
+        await writePrivateFileAtomic(
+          this.transcriptFilePath(id),
+          Buffer.from(`${lines.join("\n")}\n`, "utf8"),
+        );
+      });
+    }
+
+    const createdAt = this.now().toISOString();
+    const inferred = inferThreadFromTranscript(id, events);
+    return {
+      id,
+      workspaceId: target.workspaceId,
+      title: target.title,
+      titleSource: "manual",
+      createdAt,
+      lastMessageAt: inferred?.lastMessageAt ?? createdAt,
+      status: "disconnected",
+      sessionId: null,
+      messageCount: inferred?.messageCount ?? 0,
+      lastEventSeq: 0,
+      ...(source.reasoningEffort ? { reasoningEffort: source.reasoningEffort } : {}),
+    };
+  }
+
+  private async readArchivedTranscript(threadId: string): Promise<Buffer | null> {
+    let compressed: Buffer;
+    try {
+      compressed = await fs.readFile(this.archivedTranscriptFilePath(threadId));
+    } catch (error) {
+      if (isNotFound(error)) {
+        return null;
+      }
+      throw error;
+    }
+    return await promisify(zlib.zstdDecompress)(compressed);
+  }
+
+  private async archiveTranscriptFile(threadId: string): Promise<void> {
+    const livePath = this.transcriptFilePath(threadId);
+    let live: Buffer;
+    try {
+      live = await fs.readFile(livePath);
+    } catch (error) {
+      if (isNotFound(error)) {
+        return;
+      }
+      throw new Error(`Failed to archive transcript: ${String(error)}`);
+    }
+    const previous = await this.readArchivedTranscript(threadId);
+    const combined = previous ? Buffer.concat([previous, live]) : live;
+    // Compaction rewrites the whole archive anyway, so legacy timestamps in
+    // both the archive and the live file are fixed on the way.
+    const { payload: normalized } = await this.normalizeLegacyTimestamps(
+      combined,
+      this.createTranscriptReadKeyLoader(),
+    );
+    const payload = normalized ?? combined;
+    await fs.mkdir(this.archivedTranscriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    await writePrivateFileAtomic(
+      this.archivedTranscriptFilePath(threadId),
+      await promisify(zlib.zstdCompress)(payload),
+    );
+    await fs.unlink(livePath);
+  }
+
+  private async restoreArchivedTranscriptFile(threadId: string): Promise<void> {
+    const archived = await this.readArchivedTranscript(threadId);
+    if (!archived) {
+      return;
+    }
+    const livePath = this.transcriptFilePath(threadId);
+    const live = await fs.readFile(livePath).catch((error: unknown) => {
+      if (isNotFound(error)) {
+        return null;
+      }
+      throw error;
+    });
+    await fs.mkdir(this.transcriptsDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    await writePrivateFileAtomic(livePath, live ? Buffer.concat([archived, live]) : archived);
+    await fs.rm(this.archivedTranscriptFilePath(threadId), { force: true });
+  }
+
+  async listTranscriptFiles(): Promise<TranscriptFileInfo[]> {
+    await this.ensureStorageReady();
+    await this.flushTranscriptWrites();
+    const files: TranscriptFileInfo[] = [];
+    for (const [dir, extension, archived] of [
+      [this.transcriptsDir, TRANSCRIPT_EXTENSION, false],
+      [this.archivedTranscriptsDir, ARCHIVED_TRANSCRIPT_EXTENSION, true],
+    ] as const) {
+      let names: string[];
+      try {
+        names = await fs.readdir(dir);
+      } catch (error) {
+        if (isNotFound(error)) {
+          continue;
+        }
+        throw new Error(`Failed to list transcripts: ${String(error)}`);
+      }
+      for (const name of names) {
+        if (!name.endsWith(extension)) {
+          continue;
+        }
+        const stats = await fs.stat(path.join(dir, name)).catch(() => null);
+        if (!stats?.isFile()) {
+          continue;
+        }
+        files.push({
+          transcriptId: name.slice(0, -extension.length),
+          archived,
+          sizeBytes: stats.size,
+          modifiedAtMs: stats.mtimeMs,
+        });
+      }
+    }
+    return files;
+  }
+
+  async deleteTranscript(threadId: string): Promise<void> {
+    await this.ensureStorageReady();
+    const filePath = this.transcriptFilePath(threadId);
+    await fs.rm(this.archivedTranscriptFilePath(threadId), { force: true });
+    await fs.rm(this.quarantinedTranscriptFilePath(threadId), { force: true });
+    await this.sqliteTranscripts?.delete(threadId);
+    await this.transcriptIndex.delete(threadId);
+
+    try {
+      await fs.unlink(filePath);
+    } catch (error) {
+      if (isNotFound(error)) {
+        return;
+      }
+      throw new Error(`Failed to delete transcript: ${String(error)}`);
+    }
+  }
+}
+
+async function writePrivateFileAtomic(filePath: string, payload: Buffer): Promise<void> {
+  const tempPath = `${filePath}.tmp`;
+  await fs.writeFile(tempPath, payload, { mode: PRIVATE_FILE_MODE });
+  await fs.rename(tempPath, filePath);
+  await fs.chmod(filePath, PRIVATE_FILE_MODE);
 }
 
 async function copyLegacyEntry(from: string, to: string): Promise<void> {
@@ -0,0 +1,258 @@
+import type { PowerMonitor } from "electron";
+
+import type { WorkspaceIdlePolicy } from "../../src/app/types";
+import type {
+  AppSettings,
+  ServerResumedEvent,
+  ServerSuspendedEvent,
+} from "../../src/lib/desktopApi";
+
+const DEFAULT_SWEEP_INTERVAL_MS = 60_000;
+const MINUTE_MS = 60_000;
+
+type PowerSource = Pick<PowerMonitor, "on" | "off" | "isOnBatteryPower">;
+
+type ServerPowerManagerOptions = {
+  getSettings: () => Promise<Pick<AppSettings, "idleServerSuspend" | "idleServerTimeoutMinutes">>;
+  /** Ids of the servers that are up right now. */
+  getRunningServerIds: () => string[];
+  /** False for servers that must keep running, such as one serving a mobile relay. */
+  canSuspend?: (serverId: string) => boolean;
+  /** Persisted threads, used to credit transcript activity to their workspace's server. */
+  loadThreads: () => Promise<Array<{ id: string; workspaceId: string }>>;
+  /** Workspaces whose own idle policy overrides the app settings for their server. */
+  loadIdlePolicies?: () => Promise<Array<{ id: string; idlePolicy?: WorkspaceIdlePolicy }>>;
+  /** Asks a server to write out its session state before it is stopped. */
+  flushServer?: (serverId: string) => Promise<void>;
+  stopServer: (serverId: string) => Promise<void>;
+  onSuspended?: (event: ServerSuspendedEvent) => void;
+  onResumed?: (event: ServerResumedEvent) => void;
+  onError?: (error: unknown) => void;
+  sweepIntervalMs?: number;
+  now?: () => number;
+  setInterval?: (callback: () => void, ms: number) => ReturnType<typeof setInterval>;
+  clearInterval?: (handle: ReturnType<typeof setInterval>) => void;
+};
+
+/**
+ * Stops workspace servers that have gone idle, per the `idleServerSuspend`
+ * setting or the workspace's own idle policy, and reports them as suspended.
+ * A server is idle once none of its workspace's threads has appended to a
+ * transcript for the configured timeout; time the machine spends asleep does
+ * not count. Each server is asked to flush its session state before it is
+ * stopped. Nothing restarts a suspended server eagerly: the next start
+ * request for the workspace does, and is reported as a resume.
+ */
+export class ServerPowerManager {
+  private readonly lastActivityByServer = new Map<string, number>();
+  private readonly pendingThreadActivity = new Map<string, number>();
+  private readonly suspendedAtByServer = new Map<string, number>();
+  private readonly sweepIntervalMs: number;
+  private readonly now: () => number;
+  private readonly onError: (error: unknown) => void;
+  private readonly setIntervalImpl: NonNullable<ServerPowerManagerOptions["setInterval"]>;
+  private readonly clearIntervalImpl: NonNullable<ServerPowerManagerOptions["clearInterval"]>;
+  private timer: ReturnType<typeof setInterval> | null = null;
+  private detachPowerSource: (() => void) | null = null;
+  private inFlight: Promise<string[]> | null = null;
+  private onBattery = false;
+  private asleepSince: number | null = null;
+
+  constructor(private readonly options: ServerPowerManagerOptions) {
+    this.sweepIntervalMs = Math.max(1_000, options.sweepIntervalMs ?? DEFAULT_SWEEP_INTERVAL_MS);
+    this.now = options.now ?? Date.now;
+    this.onError = options.onError ?? (() => {});
+    this.setIntervalImpl = options.setInterval ?? setInterval;
+    this.clearIntervalImpl = options.clearInterval ?? clearInterval;
+  }
+
+  /** Follows sleep/wake and power source changes from `power` and starts sweeping. */
+  start(power: PowerSource): void {
+    if (this.timer) {
+      return;
+    }
+    this.onBattery = power.isOnBatteryPower();
+    const onSuspend = () => this.handleSleep();
+    const onResume = () => this.handleWake();
+    const onBattery = () => this.handlePowerSourceChange(true);
+    const onAc = () => this.handlePowerSourceChange(false);
+    power.on("suspend", onSuspend);
+    power.on("resume", onResume);
+    power.on("on-battery", onBattery);
+    power.on("on-ac", onAc);
+    this.detachPowerSource = () => {
+      power.off("suspend", onSuspend);
+      power.off("resume", onResume);
+      power.off("on-battery", onBattery);
+      power.off("on-ac", onAc);
+    };
+    this.timer = this.setIntervalImpl(() => {
+      void this.sweep();
+    }, this.sweepIntervalMs);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  recordTranscriptActivity(threadIds: Iterable<string>): void {
+    const now = this.now();
+    for (const threadId of threadIds) {
+      this.pendingThreadActivity.set(threadId, now);
+    }
+  }
+
+  /** Restarts the server's idle clock, reporting a resume if it was suspended. */
+  recordServerStarted(serverId: string): void {
+    const now = this.now();
+    this.lastActivityByServer.set(serverId, now);
+    const suspendedAt = this.suspendedAtByServer.get(serverId);
+    if (suspendedAt === undefined) {
+      return;
+    }
+    this.suspendedAtByServer.delete(serverId);
+    this.options.onResumed?.({
+      workspaceId: serverId,
+      suspendedAt: new Date(suspendedAt).toISOString(),
+      resumedAt: new Date(now).toISOString(),
+    });
+  }
+
+  isSuspended(serverId: string): boolean {
+    return this.suspendedAtByServer.has(serverId);
+  }
+
+  /** Stops servers idle past the timeout and returns their ids; concurrent calls share a pass. */
+  async sweep(): Promise<string[]> {
+    if (this.inFlight) {
+      return await this.inFlight;
+    }
+    this.inFlight = this.runSweep().catch((error: unknown) => {
+      this.onError(error);
+      return [];
+    });
+    try {
+      return await this.inFlight;
+    } finally {
+      this.inFlight = null;
+    }
+  }
+
+  dispose(): void {
+    if (this.timer) {
+      this.clearIntervalImpl(this.timer);
+      this.timer = null;
+    }
+    this.detachPowerSource?.();
+    this.detachPowerSource = null;
+  }
+
+  private handleSleep(): void {
+    this.asleepSince ??= this.now();
+  }
+
+  private handleWake(): void {
+    if (this.asleepSince === null) {
+      return;
+    }
+    const sleptMs = Math.max(0, this.now() - this.asleepSince);
+    this.asleepSince = null;
+    for (const clock of [this.lastActivityByServer, this.pendingThreadActivity]) {
+      for (const [id, at] of clock) {
+        clock.set(id, at + sleptMs);
+      }
+    }
+  }
+
+  private handlePowerSourceChange(onBattery: boolean): void {
+    this.onBattery = onBattery;
+    if (onBattery) {
+      void this.sweep();
+    }
+  }
+
+  private async creditThreadActivity(): Promise<void> {
+    if (this.pendingThreadActivity.size === 0) {
+      return;
+    }
+    const workspaceByThread = new Map(
+      (await this.options.loadThreads()).map((thread) => [thread.id, thread.workspaceId]),
+    );
+    // Threads not in the saved state yet (or ever, like scratch threads) are dropped.
+    for (const [threadId, at] of this.pendingThreadActivity) {
+      const workspaceId = workspaceByThread.get(threadId);
+      if (workspaceId) {
+        this.lastActivityByServer.set(
+          workspaceId,
+          Math.max(at, this.lastActivityByServer.get(workspaceId) ?? 0),
+        );
+      }
+    }
+    this.pendingThreadActivity.clear();
+  }
+
+  private async runSweep(): Promise<string[]> {
+    if (this.asleepSince !== null) {
+      return [];
+    }
+    const settings = await this.options.getSettings();
+    await this.creditThreadActivity();
+    const running = new Set(this.options.getRunningServerIds());
+    for (const serverId of this.lastActivityByServer.keys()) {
+      if (!running.has(serverId)) {
+        this.lastActivityByServer.delete(serverId);
+      }
+    }
+    const appPolicy: WorkspaceIdlePolicy = {
+      suspend: settings.idleServerSuspend,
+      timeoutMinutes: settings.idleServerTimeoutMinutes,
+    };
+    const workspacePolicies = new Map<string, WorkspaceIdlePolicy>();
+    for (const workspace of (await this.options.loadIdlePolicies?.()) ?? []) {
+      if (workspace.idlePolicy) {
+        workspacePolicies.set(workspace.id, workspace.idlePolicy);
+      }
+    }
+
+    const now = this.now();
+    const suspended: string[] = [];
+    for (const serverId of running) {
+      if (this.options.canSuspend && !this.options.canSuspend(serverId)) {
+        continue;
+      }
+      const lastActivity = this.lastActivityByServer.get(serverId);
+      if (lastActivity === undefined) {
+        // Running since before it was first seen; start its clock now.
+        this.lastActivityByServer.set(serverId, now);
+        continue;
+      }
+      const policy = workspacePolicies.get(serverId) ?? appPolicy;
+      if (policy.suspend === "never" || (policy.suspend === "onBattery" && !this.onBattery)) {
+        continue;
+      }
+      const idleMs = now - lastActivity;
+      if (idleMs < policy.timeoutMinutes * MINUTE_MS) {
+        continue;
+      }
+      try {
+        await this.options.flushServer?.(serverId);
+      } catch (error) {
+        // Stopping still ends the server gracefully, which writes its state too.
+        this.onError(error);
+      }
+      try {
+        await this.options.stopServer(serverId);
+      } catch (error) {
+        this.onError(error);
+        continue;
+      }
+      this.lastActivityByServer.delete(serverId);
+      this.suspendedAtByServer.set(serverId, now);
+      suspended.push(serverId);
+      this.options.onSuspended?.({
+        workspaceId: serverId,
+        idleMinutes: Math.floor(idleMs / MINUTE_MS),
+        onBattery: this.onBattery,
+        suspendedAt: new Date(now).toISOString(),
+      });
+    }
+    return suspended;
+  }
+}
@@ -0,0 +1,142 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+import { promisify } from "node:util";
+
+const execFileAsync = promisify(execFile);
+
+const PROCESS_STATS_COMMAND_TIMEOUT_MS = 5_000;
+/** `USER_HZ` is 100 on every Linux architecture Electron ships for. */
+const LINUX_CLOCK_TICKS_PER_SECOND = 100;
+
+export type ProcessUsage = {
+  /** User plus system CPU time consumed since the process started. */
+  cpuTimeMs: number;
+  rssBytes: number;
+};
+
+export type ProcessUsageReader = (pid: number) => Promise<ProcessUsage | null>;
+
+/** Parses utime + stime out of `/proc/<pid>/stat`. */
+export function parseLinuxProcStatCpuTimeMs(stat: string): number | null {
+  // The command name is parenthesised and may itself contain spaces or parens.
+  const fields = stat
+    .slice(stat.lastIndexOf(")") + 1)
+    .trim()
+    .split(/\s+/);
+  const utime = Number(fields[11]);
+  const stime = Number(fields[12]);
+  if (!Number.isFinite(utime) || !Number.isFinite(stime)) {
+    return null;
+  }
+  return ((utime + stime) * 1000) / LINUX_CLOCK_TICKS_PER_SECOND;
+}
+
+/** Parses `VmRSS` out of `/proc/<pid>/status`. */
+export function parseLinuxProcStatusRssBytes(status: string): number | null {
+  const match = /^VmRSS:\s+(\d+)\s+kB$/m.exec(status);
+  return match ? Number(match[1]) * 1024 : null;
+}
+
+/** Parses the `[[dd-]hh:]mm:ss[.cc]` cumulative CPU time printed by `ps -o time=`. */
+export function parsePsCpuTimeMs(value: string): number | null {
+  const match = /^(?:(\d+)-)?(?:(\d+):)?(\d+):(\d+(?:\.\d+)?)$/.exec(value.trim());
+  if (!match) {
+    return null;
+  }
+  const [, days = "0", hours = "0", minutes = "0", seconds = "0"] = match;
+  const totalSeconds =
+    Number(days) * 86_400 + Number(hours) * 3_600 + Number(minutes) * 60 + Number(seconds);
+  return Math.round(totalSeconds * 1000);
+}
+
+async function readLinuxProcessUsage(pid: number): Promise<ProcessUsage | null> {
+  const [stat, status] = await Promise.all([
+    fs.readFile(`/proc/${pid}/stat`, "utf8"),
+    fs.readFile(`/proc/${pid}/status`, "utf8"),
+  ]);
+  const cpuTimeMs = parseLinuxProcStatCpuTimeMs(stat);
+  const rssBytes = parseLinuxProcStatusRssBytes(status);
+  return cpuTimeMs === null || rssBytes === null ? null : { cpuTimeMs, rssBytes };
+}
+
+async function readPsProcessUsage(pid: number): Promise<ProcessUsage | null> {
+  const { stdout } = await execFileAsync("ps", ["-o", "rss=,time=", "-p", String(pid)], {
+    timeout: PROCESS_STATS_COMMAND_TIMEOUT_MS,
+  });
+  const [rssKb, time] = stdout.trim().split(/\s+/);
+  const cpuTimeMs = time ? parsePsCpuTimeMs(time) : null;
+  const rssBytes = Number(rssKb) * 1024;
+  return cpuTimeMs === null || !Number.isFinite(rssBytes) ? null : { cpuTimeMs, rssBytes };
+}
+
+async function readWindowsProcessUsage(pid: number): Promise<ProcessUsage | null> {
+  const script =
+    `$p = Get-Process -Id ${pid}; ` +
+    `"$($p.TotalProcessorTime.TotalMilliseconds) $($p.WorkingSet64)"`;
+  const { stdout } = await execFileAsync(
+    "powershell.exe",
+    ["-NoProfile", "-NonInteractive", "-Command", script],
+    { timeout: PROCESS_STATS_COMMAND_TIMEOUT_MS, windowsHide: true },
+  );
+  const [cpuTimeMs, rssBytes] = stdout.trim().split(/\s+/).map(Number);
+  return Number.isFinite(cpuTimeMs) && Number.isFinite(rssBytes)
+    ? { cpuTimeMs: cpuTimeMs as number, rssBytes: rssBytes as number }
+    : null;
+}
+
+/** Returns null when the process is gone or the platform tooling is unavailable. */
+export async function readProcessUsage(
+  pid: number,
+  platform: NodeJS.Platform = process.platform,
+): Promise<ProcessUsage | null> {
+  try {
+    if (platform === "linux") {
+      return await readLinuxProcessUsage(pid);
+    }
+    if (platform === "win32") {
+      return await readWindowsProcessUsage(pid);
+    }
+    return await readPsProcessUsage(pid);
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Turns cumulative CPU time into a utilisation percentage. The first sample
+ * for a pid averages over the process lifetime; later samples cover the time
+ * since the previous one. 100% is one fully busy core, so multi-threaded
+ * sidecars can report more.
+ */
+export class ProcessStatsSampler {
+  private readonly previous = new Map<number, { cpuTimeMs: number; sampledAt: number }>();
+
+  constructor(
+    private readonly read: ProcessUsageReader = readProcessUsage,
+    private readonly now: () => number = Date.now,
+  ) {}
+
+  async sample(
+    pid: number,
+    startedAt: number,
+  ): Promise<{ cpuPercent: number | null; rssBytes: number; sampledAt: number } | null> {
+    const usage = await this.read(pid);
+    const sampledAt = this.now();
+    if (!usage) {
+      this.previous.delete(pid);
+      return null;
+    }
+    const baseline = this.previous.get(pid) ?? { cpuTimeMs: 0, sampledAt: startedAt };
+    const elapsedMs = sampledAt - baseline.sampledAt;
+    this.previous.set(pid, { cpuTimeMs: usage.cpuTimeMs, sampledAt });
+    const cpuPercent =
+      elapsedMs > 0
+        ? Math.round((Math.max(0, usage.cpuTimeMs - baseline.cpuTimeMs) / elapsedMs) * 1000) / 10
+        : null;
+    return { cpuPercent, rssBytes: usage.rssBytes, sampledAt };
+  }
+
+  forget(pid: number): void {
+    this.previous.delete(pid);
+  }
+}
@@ -0,0 +1,241 @@
+import fs from "node:fs";
+import path from "node:path";
+
+import type * as Electron from "electron";
+
+import type { DesktopProfile, DesktopProfileList } from "../../src/lib/desktopApi";
+import { profileIdSchema } from "../../src/lib/desktopSchemas";
+
+export const PROFILE_FLAG = "--profile";
+export const DEFAULT_PROFILE_ID = "default";
+
+const PROFILES_FILE_NAME = "profiles.json";
+const PROFILES_DIR_NAME = "profiles";
+const PRIVATE_DIR_MODE = 0o700;
+const PRIVATE_FILE_MODE = 0o600;
+
+type ProfilesFile = {
+  /** Opened by a launch without `--profile`. */
+  lastUsedProfileId: string;
+  /** Every profile but the default one, which always exists. */
+  profiles: DesktopProfile[];
+};
+
+export class ProfileArgsError extends Error {}
+
+export function isValidProfileId(value: string): boolean {
+  return profileIdSchema.safeParse(value).success;
+}
+
+/** Turns a display name into a profile id such as `client-acme`; empty if nothing is left. */
+export function profileIdFromName(name: string): string {
+  return name
+    .normalize("NFKD")
+    .toLowerCase()
+    .replace(/[^a-z0-9]+/g, "-")
+    .replace(/^-+|-+$/g, "")
+    .slice(0, 40)
+    .replace(/-+$/, "");
+}
+
+/** The `--profile <id>` or `--profile=<id>` value in launch args, or null without one. */
+export function parseProfileArg(args: readonly string[]): string | null {
+  for (let index = 0; index < args.length; index += 1) {
+    const arg = args[index] ?? "";
+    let value: string | undefined;
+    if (arg.startsWith(`${PROFILE_FLAG}=`)) {
+      value = arg.slice(PROFILE_FLAG.length + 1);
+    } else if (arg === PROFILE_FLAG) {
+      value = args[index + 1];
+      if (value === undefined || value.startsWith("--")) {
+        throw new ProfileArgsError(`${PROFILE_FLAG} needs a profile id`);
+      }
+    } else {
+      continue;
+    }
+    const profileId = value.trim().toLowerCase();
+    if (!isValidProfileId(profileId)) {
+      throw new ProfileArgsError(
+        `Invalid profile id "${value}": use lowercase letters, digits, and dashes.`,
+      );
+    }
+    return profileId;
+  }
+  return null;
+}
+
+function defaultProfile(): DesktopProfile {
+  return { id: DEFAULT_PROFILE_ID, name: "Default", createdAt: new Date(0).toISOString() };
+}
+
+function sanitizeProfilesFile(value: unknown): ProfilesFile {
+  const record = value && typeof value === "object" ? (value as Record<string, unknown>) : {};
+  const profiles: DesktopProfile[] = [];
+  for (const entry of Array.isArray(record.profiles) ? record.profiles : []) {
+    const profile = entry as Partial<DesktopProfile> | null;
+    if (
+      typeof profile?.id === "string" &&
+      isValidProfileId(profile.id) &&
+      profile.id !== DEFAULT_PROFILE_ID &&
+      !profiles.some((existing) => existing.id === profile.id)
+    ) {
+      profiles.push({
+        id: profile.id,
+        name: typeof profile.name === "string" && profile.name.trim() ? profile.name : profile.id,
+        createdAt: typeof profile.createdAt === "string" ? profile.createdAt : "",
+      });
+    }
+  }
+  const lastUsed = record.lastUsedProfileId;
+  return {
+    lastUsedProfileId:
+      typeof lastUsed === "string" && profiles.some((profile) => profile.id === lastUsed)
+        ? lastUsed
+        : DEFAULT_PROFILE_ID,
+    profiles,
+  };
+}
+
+/**
+ * Work and personal profiles. Each profile keeps its own app-data directory,
+ * so state, transcripts, settings, and keychain-wrapped secrets never mix.
+ * The default profile is the app-data root itself, which keeps existing
+ * installs where they were; others live under `profiles/<id>`. The registry
+ * is read synchronously at launch, before anything resolves `userData`.
+ */
+export class ProfileStore {
+  private readonly filePath: string;
+
+  constructor(
+    private readonly options: {
+      /** The app-data root, resolved before a profile is applied. */
+      rootDir: string;
+      now?: () => Date;
+    },
+  ) {
+    this.filePath = path.join(options.rootDir, PROFILES_FILE_NAME);
+  }
+
+  profileDir(profileId: string): string {
+    if (profileId === DEFAULT_PROFILE_ID) {
+      return this.options.rootDir;
+    }
+    if (!isValidProfileId(profileId)) {
+      throw new Error(`Invalid profile id: ${profileId}`);
+    }
+    return path.join(this.options.rootDir, PROFILES_DIR_NAME, profileId);
+  }
+
+  read(): ProfilesFile {
+    try {
+      return sanitizeProfilesFile(JSON.parse(fs.readFileSync(this.filePath, "utf8")));
+    } catch {
+      return sanitizeProfilesFile(null);
+    }
+  }
+
+  list(currentProfileId: string): DesktopProfileList {
+    const file = this.read();
+    return {
+      currentProfileId,
+      lastUsedProfileId: file.lastUsedProfileId,
+      profiles: [defaultProfile(), ...file.profiles],
+    };
+  }
+
+  has(profileId: string): boolean {
+    return (
+      profileId === DEFAULT_PROFILE_ID ||
+      this.read().profiles.some((profile) => profile.id === profileId)
+    );
+  }
+
+  /** Adds a profile named `name`; the id is derived from it and made unique. */
+  create(name: string, profileId = profileIdFromName(name)): DesktopProfile {
+    const trimmed = name.trim();
+    if (!trimmed) {
+      throw new Error("Profile name is required.");
+    }
+    const baseId = isValidProfileId(profileId) ? profileId : "profile";
+    const file = this.read();
+    const taken = new Set([DEFAULT_PROFILE_ID, ...file.profiles.map((profile) => profile.id)]);
+    let id = baseId;
+    for (let suffix = 2; taken.has(id); suffix += 1) {
+      id = `${baseId.slice(0, 36)}-${suffix}`;
+    }
+    const profile: DesktopProfile = {
+      id,
+      name: trimmed,
+      createdAt: (this.options.now?.() ?? new Date()).toISOString(),
+    };
+    fs.mkdirSync(this.profileDir(id), { recursive: true, mode: PRIVATE_DIR_MODE });
+    this.write({ ...file, profiles: [...file.profiles, profile] });
+    return profile;
+  }
+
+  setLastUsed(profileId: string): void {
+    if (!this.has(profileId)) {
+      throw new Error(`Unknown profile: ${profileId}`);
+    }
+    const file = this.read();
+    if (file.lastUsedProfileId !== profileId) {
+      this.write({ ...file, lastUsedProfileId: profileId });
+    }
+  }
+
+  private write(file: ProfilesFile): void {
+    fs.mkdirSync(this.options.rootDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    const tempPath = `${this.filePath}.tmp`;
+    fs.writeFileSync(tempPath, `${JSON.stringify(file, null, 2)}\n`, {
+      encoding: "utf8",
+      mode: PRIVATE_FILE_MODE,
+    });
+    fs.renameSync(tempPath, this.filePath);
+  }
+}
+
+export type LaunchProfile = {
+  store: ProfileStore;
+  profileId: string;
+  userDataDir: string;
+};
+
+type LaunchProfileApp = Pick<Electron.App, "getPath" | "setPath">;
+
+/**
+ * Points `userData` at the profile named by `--profile`, creating it on first
+ * use, or else at the profile used last. Must run before anything resolves
+ * `userData`; the single-instance lock is keyed on it too, so two profiles can
+ * run side by side.
+ */
+export function applyLaunchProfile(app: LaunchProfileApp, args: readonly string[]): LaunchProfile {
+  const store = new ProfileStore({ rootDir: app.getPath("userData") });
+  const requested = parseProfileArg(args);
+  if (requested && !store.has(requested)) {
+    store.create(requested, requested);
+  }
+  const profileId = requested ?? store.read().lastUsedProfileId;
+  store.setLastUsed(profileId);
+  const userDataDir = store.profileDir(profileId);
+  if (profileId !== DEFAULT_PROFILE_ID) {
+    fs.mkdirSync(userDataDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    app.setPath("userData", userDataDir);
+  }
+  return { store, profileId, userDataDir };
+}
+
+/** Launch args for a relaunch into `profileId`, keeping every other arg. */
+export function relaunchArgsForProfile(args: readonly string[], profileId: string): string[] {
+  const kept: string[] = [];
+  for (let index = 0; index < args.length; index += 1) {
+    const arg = args[index] ?? "";
+    if (arg === PROFILE_FLAG) {
+      index += 1;
+      continue;
+    }
+    if (!arg.startsWith(`${PROFILE_FLAG}=`)) {
+      kept.push(arg);
+    }
+  }
+  return [...kept, `${PROFILE_FLAG}=${profileId}`];
+}
@@ -0,0 +1,295 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import { extractPromptVariables, renderPromptTemplate } from "../../src/app/promptTemplates";
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import type {
+  DeletePromptTemplateInput,
+  PromptTemplate,
+  RenderPromptInput,
+  RenderPromptResult,
+  SavePromptTemplateInput,
+} from "../../src/lib/desktopApi";
+import { WORKSPACE_CONFIG_DIR_NAME } from "./workspaceConfig";
+
+export const WORKSPACE_PROMPTS_FILE_NAME = "prompts.json";
+
+const MAX_NAME_LENGTH = 200;
+const MAX_DESCRIPTION_LENGTH = 2_000;
+const MAX_BODY_LENGTH = 100_000;
+const MAX_TAGS = 20;
+
+/** What the files hold; scope, workspace, and variables are derived when read. */
+type StoredPromptTemplate = Pick<
+  PromptTemplate,
+  "id" | "name" | "description" | "body" | "tags" | "createdAt" | "updatedAt"
+>;
+
+type PromptLibraryFile = {
+  version: 1;
+  prompts: StoredPromptTemplate[];
+};
+
+type Library = {
+  filePath: string;
+  workspaceId?: string;
+};
+
+type LibraryContents = {
+  prompts: StoredPromptTemplate[];
+  /** Set when the file exists but could not be parsed, so it is not overwritten. */
+  error: string | null;
+};
+
+type PromptLibraryStoreOptions = {
+  globalFilePath: string;
+  loadState: () => Promise<PersistedState>;
+  now?: () => Date;
+  createId?: () => string;
+};
+
+export function resolveWorkspacePromptsPath(workspacePath: string): string {
+  return path.join(workspacePath, WORKSPACE_CONFIG_DIR_NAME, WORKSPACE_PROMPTS_FILE_NAME);
+}
+
+function normalizeTags(tags: string[] = []): string[] {
+  const normalized = new Set<string>();
+  for (const tag of tags) {
+    const trimmed = tag.trim().toLowerCase();
+    if (trimmed) {
+      normalized.add(trimmed);
+    }
+  }
+  if (normalized.size > MAX_TAGS) {
+    throw new Error(`A prompt template can have at most ${MAX_TAGS} tags`);
+  }
+  return [...normalized];
+}
+
+function isStoredPromptTemplate(value: unknown): value is StoredPromptTemplate {
+  const entry = value as Partial<StoredPromptTemplate> | null;
+  return (
+    typeof entry?.id === "string" &&
+    typeof entry.name === "string" &&
+    typeof entry.description === "string" &&
+    typeof entry.body === "string" &&
+    Array.isArray(entry.tags) &&
+    entry.tags.every((tag) => typeof tag === "string") &&
+    typeof entry.createdAt === "string" &&
+    typeof entry.updatedAt === "string"
+  );
+}
+
+function toPromptTemplate(stored: StoredPromptTemplate, library: Library): PromptTemplate {
+  return {
+    ...stored,
+    scope: library.workspaceId ? "workspace" : "global",
+    ...(library.workspaceId ? { workspaceId: library.workspaceId } : {}),
+    variables: extractPromptVariables(stored.body),
+    tags: [...stored.tags],
+  };
+}
+
+function compareByName(left: PromptTemplate, right: PromptTemplate): number {
+  return left.name.localeCompare(right.name) || left.id.localeCompare(right.id);
+}
+
+/**
+ * Named prompt templates in two libraries: a global one in app data, and one
+ * per workspace in `<workspace>/.cowork/prompts.json` so a team can commit
+ * theirs beside the code. Names are unique within a library, and a workspace
+ * file that fails to parse is left alone rather than overwritten.
+ */
+export class PromptLibraryStore {
+  private readonly locks = new Map<string, Promise<unknown>>();
+  private readonly createId: () => string;
+
+  constructor(private readonly options: PromptLibraryStoreOptions) {
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+  }
+
+  async list(workspaceId?: string): Promise<PromptTemplate[]> {
+    return (await this.readLibraries(await this.librariesFor(workspaceId))).sort(compareByName);
+  }
+
+  /** Every template in the global library and each known workspace's; used by search. */
+  async listAll(state?: PersistedState): Promise<PromptTemplate[]> {
+    const workspaces = (state ?? (await this.options.loadState())).workspaces;
+    return await this.readLibraries([
+      this.globalLibrary(),
+      ...workspaces.map((workspace) => this.workspaceLibrary(workspace)),
+    ]);
+  }
+
+  async save(input: SavePromptTemplateInput): Promise<PromptTemplate> {
+    const name = input.name.trim();
+    if (!name) {
+      throw new Error("Prompt templates need a name");
+    }
+    if (name.length > MAX_NAME_LENGTH) {
+      throw new Error(`Prompt template names are limited to ${MAX_NAME_LENGTH} characters`);
+    }
+    const description = input.description?.trim() ?? "";
+    if (description.length > MAX_DESCRIPTION_LENGTH) {
+      throw new Error(
+        `Prompt template descriptions are limited to ${MAX_DESCRIPTION_LENGTH} characters`,
+      );
+    }
+    if (!input.body.trim()) {
+      throw new Error("Prompt templates need a body");
+    }
+    if (input.body.length > MAX_BODY_LENGTH) {
+      throw new Error(`Prompt template bodies are limited to ${MAX_BODY_LENGTH} characters`);
+    }
+    const tags = normalizeTags(input.tags);
+    const library = await this.resolveLibrary(input.workspaceId);
+
+    return await this.withLock(library.filePath, async () => {
+      const prompts = await this.readWritable(library);
+      const index = input.id ? prompts.findIndex((entry) => entry.id === input.id) : -1;
+      if (input.id && index < 0) {
+        throw new Error(`Unknown prompt template: ${input.id}`);
+      }
+      const duplicate = prompts.find(
+        (entry) => entry.id !== input.id && entry.name.toLowerCase() === name.toLowerCase(),
+      );
+      if (duplicate) {
+        throw new Error(`A prompt template named "${duplicate.name}" already exists`);
+      }
+      const existing = index >= 0 ? prompts[index] : undefined;
+      const now = (this.options.now?.() ?? new Date()).toISOString();
+      const stored: StoredPromptTemplate = {
+        id: existing?.id ?? this.createId(),
+        name,
+        description,
+        body: input.body,
+        tags,
+        createdAt: existing?.createdAt ?? now,
+        updatedAt: now,
+      };
+      if (existing) {
+        prompts[index] = stored;
+      } else {
+        prompts.push(stored);
+      }
+      await this.write(library, prompts);
+      return toPromptTemplate(stored, library);
+    });
+  }
+
+  async delete(input: DeletePromptTemplateInput): Promise<void> {
+    const library = await this.resolveLibrary(input.workspaceId);
+    await this.withLock(library.filePath, async () => {
+      const prompts = await this.readWritable(library);
+      const remaining = prompts.filter((entry) => entry.id !== input.id);
+      if (remaining.length === prompts.length) {
+        throw new Error(`Unknown prompt template: ${input.id}`);
+      }
+      await this.write(library, remaining);
+    });
+  }
+
+  /** Fills the template's variables; a workspace template wins over a global one. */
+  async render(input: RenderPromptInput): Promise<RenderPromptResult> {
+    const templates = await this.readLibraries(await this.librariesFor(input.workspaceId));
+    const template = templates.find((candidate) => candidate.id === input.templateId);
+    if (!template) {
+      throw new Error(`Unknown prompt template: ${input.templateId}`);
+    }
+    return { template, text: renderPromptTemplate(template.body, input.vars) };
+  }
+
+  private globalLibrary(): Library {
+    return { filePath: this.options.globalFilePath };
+  }
+
+  private workspaceLibrary(workspace: WorkspaceRecord): Library {
+    return { filePath: resolveWorkspacePromptsPath(workspace.path), workspaceId: workspace.id };
+  }
+
+  private async librariesFor(workspaceId: string | undefined): Promise<Library[]> {
+    const libraries = [this.globalLibrary()];
+    if (workspaceId) {
+      libraries.push(this.workspaceLibrary(await this.findWorkspace(workspaceId)));
+    }
+    return libraries;
+  }
+
+  private async resolveLibrary(workspaceId: string | undefined): Promise<Library> {
+    return workspaceId
+      ? this.workspaceLibrary(await this.findWorkspace(workspaceId))
+      : this.globalLibrary();
+  }
+
+  private async findWorkspace(workspaceId: string): Promise<WorkspaceRecord> {
+    const state = await this.options.loadState();
+    const workspace = state.workspaces.find((candidate) => candidate.id === workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    return workspace;
+  }
+
+  /** Reads the libraries last first, so workspace templates win a lookup by id. */
+  private async readLibraries(libraries: Library[]): Promise<PromptTemplate[]> {
+    const templates: PromptTemplate[] = [];
+    for (const library of [...libraries].reverse()) {
+      const { prompts } = await this.read(library);
+      templates.push(...prompts.map((stored) => toPromptTemplate(stored, library)));
+    }
+    return templates;
+  }
+
+  private async read(library: Library): Promise<LibraryContents> {
+    let raw: string;
+    try {
+      raw = await fs.readFile(library.filePath, "utf8");
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+        return { prompts: [], error: null };
+      }
+      throw error;
+    }
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(raw);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      return { prompts: [], error: `Invalid JSON: ${message}` };
+    }
+    const prompts = (parsed as { prompts?: unknown } | null)?.prompts;
+    if (!Array.isArray(prompts)) {
+      return { prompts: [], error: 'Expected an object with a "prompts" array.' };
+    }
+    return { prompts: prompts.filter(isStoredPromptTemplate), error: null };
+  }
+
+  private async readWritable(library: Library): Promise<StoredPromptTemplate[]> {
+    const contents = await this.read(library);
+    if (contents.error) {
+      throw new Error(`Cannot update ${library.filePath}: ${contents.error}`);
+    }
+    return contents.prompts;
+  }
+
+  private async write(library: Library, prompts: StoredPromptTemplate[]): Promise<void> {
+    await fs.mkdir(path.dirname(library.filePath), { recursive: true });
+    const file: PromptLibraryFile = { version: 1, prompts };
+    await writeFileAtomic(library.filePath, `${JSON.stringify(file, null, 2)}\n`);
+  }
+
+  private async withLock<T>(key: string, fn: () => Promise<T>): Promise<T> {
+    const previous = this.locks.get(key) ?? Promise.resolve();
+    const next = previous.catch(() => {}).then(fn);
+    this.locks.set(key, next);
+    try {
+      return await next;
+    } finally {
+      if (this.locks.get(key) === next) {
+        this.locks.delete(key);
+      }
+    }
+  }
+}
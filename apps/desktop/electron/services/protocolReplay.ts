@@ -0,0 +1,324 @@
+import { randomUUID } from "node:crypto";
+
+import { type RawData, WebSocket, WebSocketServer } from "ws";
+
+import {
+  createJsonRpcNotificationProjector,
+} from "../../../../src/server/jsonrpc/notificationProjector";
+import {
+  JSONRPC_ERROR_CODES,
+  JSONRPC_PROTOCOL_VERSION,
+} from "../../../../src/server/jsonrpc/protocol";
+import type { SessionEvent } from "../../../../src/server/protocol";
+import type { TranscriptEvent } from "../../src/app/types";
+import type { ReplaySession } from "../../src/lib/desktopApi";
+
+const JSONRPC_SUBPROTOCOL = "cowork.jsonrpc.v1";
+/** Idle stretches in a recording are shortened to this before the speed applies. */
+export const MAX_REPLAY_GAP_MS = 5_000;
+
+type TimerHandle = ReturnType<typeof setTimeout>;
+
+export type ReplayTimelineEntry = {
+  /** Milliseconds after playback starts. */
+  offsetMs: number;
+  event: SessionEvent;
+};
+
+export type ReplayRecording = {
+  threadId: string;
+  title: string;
+  createdAt: string;
+  /** Workspace path reported as the thread's cwd unless the recording names one. */
+  workspacePath: string | null;
+  transcript: TranscriptEvent[];
+};
+
+type ProtocolReplayServiceOptions = {
+  createId?: () => string;
+  maxGapMs?: number;
+  setTimeout?: (callback: () => void, ms: number) => TimerHandle;
+  clearTimeout?: (handle: TimerHandle) => void;
+};
+
+type ReplayThread = {
+  id: string;
+  title: string;
+  createdAt: string;
+  updatedAt: string;
+  modelProvider: string;
+  model: string;
+  cwd: string;
+  messageCount: number;
+  lastEventSeq: number;
+  status: { type: "idle" };
+};
+
+type ActiveReplay = {
+  session: ReplaySession;
+  server: WebSocketServer;
+};
+
+type JsonRpcRequest = {
+  id: string | number;
+  method: string;
+  params?: unknown;
+};
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+function isSessionEvent(value: unknown): value is SessionEvent {
+  return isRecord(value) && typeof value.type === "string" && typeof value.sessionId === "string";
+}
+
+/**
+ * Turns the server-direction events of a transcript into a playback schedule,
+ * keeping the recorded spacing but capping each pause at `maxGapMs`.
+ */
+export function buildReplayTimeline(
+  transcript: TranscriptEvent[],
+  speed: number,
+  maxGapMs = MAX_REPLAY_GAP_MS,
+): ReplayTimelineEntry[] {
+  const timeline: ReplayTimelineEntry[] = [];
+  let previousAt: number | null = null;
+  let offsetMs = 0;
+  for (const entry of transcript) {
+    if (entry.direction !== "server" || !isSessionEvent(entry.payload)) {
+      continue;
+    }
+    const at = Date.parse(entry.ts);
+    if (previousAt !== null && Number.isFinite(at)) {
+      offsetMs += Math.min(maxGapMs, Math.max(0, at - previousAt)) / speed;
+    }
+    if (Number.isFinite(at)) {
+      previousAt = at;
+    }
+    timeline.push({ offsetMs, event: entry.payload });
+  }
+  return timeline;
+}
+
+function buildReplayThread(
+  recording: ReplayRecording,
+  timeline: ReplayTimelineEntry[],
+  sessionId: string,
+): ReplayThread {
+  let hello: Extract<SessionEvent, { type: "server_hello" }> | undefined;
+  for (const { event } of timeline) {
+    if (event.type === "server_hello") {
+      hello = event;
+    }
+  }
+  return {
+    id: sessionId,
+    title: recording.title,
+    createdAt: recording.createdAt,
+    updatedAt: recording.transcript.at(-1)?.ts ?? recording.createdAt,
+    modelProvider: hello?.config.provider ?? "replay",
+    model: hello?.config.model ?? "replay",
+    cwd: hello?.config.workingDirectory ?? recording.workspacePath ?? "",
+    messageCount: 0,
+    lastEventSeq: 0,
+    status: { type: "idle" },
+  };
+}
+
+function parseRequest(raw: RawData): JsonRpcRequest | null {
+  let message: unknown;
+  try {
+    message = JSON.parse(raw.toString());
+  } catch {
+    return null;
+  }
+  if (
+    !isRecord(message) ||
+    typeof message.method !== "string" ||
+    (typeof message.id !== "string" && typeof message.id !== "number")
+  ) {
+    return null;
+  }
+  return { id: message.id, method: message.method, params: message.params };
+}
+
+/**
+ * Serves recorded threads back over the workspace server's JSON-RPC WebSocket
+ * contract. Each replay listens on its own loopback port; every client that
+ * resumes the recorded thread gets the server events re-emitted on the
+ * recorded schedule, so rendering bugs reproduce without a provider or sidecar.
+ */
+export class ProtocolReplayService {
+  private readonly replays = new Map<string, ActiveReplay>();
+  private readonly createId: () => string;
+  private readonly maxGapMs: number;
+  private readonly setTimeoutImpl: NonNullable<ProtocolReplayServiceOptions["setTimeout"]>;
+  private readonly clearTimeoutImpl: NonNullable<ProtocolReplayServiceOptions["clearTimeout"]>;
+
+  constructor(options: ProtocolReplayServiceOptions = {}) {
+    this.createId = options.createId ?? (() => `replay-${randomUUID()}`);
+    this.maxGapMs = options.maxGapMs ?? MAX_REPLAY_GAP_MS;
+    this.setTimeoutImpl = options.setTimeout ?? setTimeout;
+    this.clearTimeoutImpl = options.clearTimeout ?? clearTimeout;
+  }
+
+  async start(recording: ReplayRecording, speed = 1): Promise<ReplaySession> {
+    const timeline = buildReplayTimeline(recording.transcript, speed, this.maxGapMs);
+    if (timeline.length === 0) {
+      throw new Error(`Thread ${recording.threadId} has no recorded server events to replay`);
+    }
+    const sessionId = timeline[0]?.event.sessionId ?? recording.threadId;
+    const thread = buildReplayThread(recording, timeline, sessionId);
+    const server = new WebSocketServer({
+      host: "127.0.0.1",
+      port: 0,
+      path: "/ws",
+      handleProtocols: (protocols) =>
+        protocols.has(JSONRPC_SUBPROTOCOL) ? JSONRPC_SUBPROTOCOL : false,
+    });
+    await new Promise<void>((resolve, reject) => {
+      server.once("listening", resolve);
+      server.once("error", reject);
+    });
+    const address = server.address();
+    if (!address || typeof address === "string") {
+      server.close();
+      throw new Error("Replay server did not expose a TCP address");
+    }
+    server.on("connection", (socket) => {
+      this.handleConnection(socket, thread, timeline);
+    });
+
+    const session: ReplaySession = {
+      replayId: this.createId(),
+      threadId: recording.threadId,
+      sessionId,
+      url: `ws://127.0.0.1:${address.port}/ws`,
+      speed,
+      eventCount: timeline.length,
+      durationMs: Math.round(timeline.at(-1)?.offsetMs ?? 0),
+    };
+    this.replays.set(session.replayId, { session, server });
+    return session;
+  }
+
+  async stop(replayId: string): Promise<boolean> {
+    const replay = this.replays.get(replayId);
+    if (!replay) {
+      return false;
+    }
+    this.replays.delete(replayId);
+    for (const client of replay.server.clients) {
+      client.terminate();
+    }
+    await new Promise<void>((resolve) => {
+      replay.server.close(() => resolve());
+    });
+    return true;
+  }
+
+  async dispose(): Promise<void> {
+    await Promise.all([...this.replays.keys()].map((replayId) => this.stop(replayId)));
+  }
+
+  private handleConnection(
+    socket: WebSocket,
+    thread: ReplayThread,
+    timeline: ReplayTimelineEntry[],
+  ): void {
+    let stopPlayback: (() => void) | null = null;
+    const send = (message: unknown): void => {
+      if (socket.readyState === WebSocket.OPEN) {
+        socket.send(JSON.stringify(message));
+      }
+    };
+    const respond = (id: string | number, result: unknown): void => send({ id, result });
+    const fail = (id: string | number, code: number, message: string): void =>
+      send({ id, error: { code, message } });
+
+    socket.on("close", () => stopPlayback?.());
+    socket.on("message", (raw) => {
+      const request = parseRequest(raw);
+      if (!request) {
+        return;
+      }
+      const params = isRecord(request.params) ? request.params : {};
+      switch (request.method) {
+        case "initialize":
+          respond(request.id, {
+            protocolVersion: JSONRPC_PROTOCOL_VERSION,
+            serverInfo: { name: "cowork-replay", version: JSONRPC_PROTOCOL_VERSION },
+            capabilities: {},
+          });
+          return;
+        case "thread/list":
+          respond(request.id, { threads: [thread], total: 1 });
+          return;
+        case "thread/read":
+          respond(request.id, { thread, coworkSnapshot: null });
+          return;
+        case "thread/resume":
+          if (params.threadId !== thread.id) {
+            fail(
+              request.id,
+              JSONRPC_ERROR_CODES.invalidParams,
+              `Unknown thread: ${String(params.threadId)}`,
+            );
+            return;
+          }
+          respond(request.id, { thread });
+          send({ method: "thread/started", params: { thread } });
+          stopPlayback?.();
+          stopPlayback = this.play(thread.id, timeline, send);
+          return;
+        default:
+          fail(
+            request.id,
+            JSONRPC_ERROR_CODES.methodNotFound,
+            `${request.method} is not available while replaying a recording`,
+          );
+      }
+    });
+  }
+
+  /** Emits the timeline from the start; returns a function that cancels what is left. */
+  private play(
+    threadId: string,
+    timeline: ReplayTimelineEntry[],
+    send: (message: unknown) => void,
+  ): () => void {
+    const projector = createJsonRpcNotificationProjector({
+      threadId,
+      send,
+      onServerRequest: (request) => {
+        send({ id: request.id, method: request.method, params: request.params });
+      },
+    });
+    let timer: TimerHandle | null = null;
+    let index = 0;
+    const emitDue = (): void => {
+      const dueAt = timeline[index]?.offsetMs ?? 0;
+      while (index < timeline.length && (timeline[index]?.offsetMs ?? 0) <= dueAt) {
+        const entry = timeline[index];
+        index += 1;
+        if (entry) {
+          projector.handle(entry.event);
+        }
+      }
+      scheduleNext(dueAt);
+    };
+    const scheduleNext = (previousOffsetMs: number): void => {
+      const next = timeline[index];
+      timer = next ? this.setTimeoutImpl(emitDue, next.offsetMs - previousOffsetMs) : null;
+    };
+    scheduleNext(0);
+    return () => {
+      if (timer) {
+        this.clearTimeoutImpl(timer);
+        timer = null;
+      }
+      index = timeline.length;
+    };
+  }
+}
@@ -0,0 +1,359 @@
+import { createHash } from "node:crypto";
+
+import { BASETEN_BASE_URL, resolveBasetenApiKey } from "../../../../src/providers/basetenShared";
+import {
+  FIREWORKS_INFERENCE_BASE_URL,
+  isFireworksInferenceProvider,
+  resolveFireworksInferenceApiKey,
+} from "../../../../src/providers/fireworksShared";
+import { MINIMAX_BASE_URL, resolveMinimaxApiKey } from "../../../../src/providers/minimaxShared";
+import { NVIDIA_BASE_URL, resolveNvidiaApiKey } from "../../../../src/providers/nvidiaShared";
+import {
+  getOpenCodeProviderConfig,
+  isOpenCodeProviderName,
+  resolveOpenCodeApiKey,
+} from "../../../../src/providers/opencodeShared";
+import { resolveTogetherApiKey, TOGETHER_BASE_URL } from "../../../../src/providers/togetherShared";
+import {
+  type ConnectionStore,
+  getAiCoworkerPaths,
+  readConnectionStore,
+} from "../../../../src/store/connections";
+import { maskApiKey } from "../../../../src/tools/api-keys";
+import type { ProviderName } from "../../../../src/types";
+import type {
+  ProviderCredentialQuota,
+  ProviderCredentialValidation,
+  ValidateProviderCredentialsInput,
+} from "../../src/lib/desktopApi";
+
+const DEFAULT_CACHE_TTL_MS = 5 * 60_000;
+const DEFAULT_TIMEOUT_MS = 10_000;
+
+type FetchLike = (url: string, init: RequestInit) => Promise<Response>;
+
+/** The cheapest authenticated request a provider offers, usually a one-item model list. */
+export type ProviderProbe = {
+  url: string;
+  headers: Record<string, string>;
+};
+
+type ProviderCredentialValidatorOptions = {
+  fetch?: FetchLike;
+  readStore?: () => Promise<ConnectionStore>;
+  env?: NodeJS.ProcessEnv;
+  now?: () => number;
+  cacheTtlMs?: number;
+  timeoutMs?: number;
+};
+
+type CacheEntry = {
+  expiresAt: number;
+  result: ProviderCredentialValidation;
+};
+
+function bearerProbe(baseUrl: string, apiKey: string): ProviderProbe {
+  return {
+    url: `${baseUrl.replace(/\/+$/, "")}/models`,
+    headers: { authorization: `Bearer ${apiKey}` },
+  };
+}
+
+/** Providers missing here sign in without an API key or run locally. */
+const PROVIDER_PROBES: Partial<Record<ProviderName, (apiKey: string) => ProviderProbe>> = {
+  openai: (apiKey) => bearerProbe("https://api.openai.com/v1", apiKey),
+  anthropic: (apiKey) => ({
+    url: "https://api.anthropic.com/v1/models?limit=1",
+    headers: { "x-api-key": apiKey, "anthropic-version": "2023-06-01" },
+  }),
+  google: (apiKey) => ({
+    url: "https://generativelanguage.googleapis.com/v1beta/models?pageSize=1",
+    headers: { "x-goog-api-key": apiKey },
+  }),
+  baseten: (apiKey) => ({
+    url: `${BASETEN_BASE_URL}/models`,
+    headers: { authorization: `Api-Key ${apiKey}` },
+  }),
+  together: (apiKey) => bearerProbe(TOGETHER_BASE_URL, apiKey),
+  fireworks: (apiKey) => bearerProbe(FIREWORKS_INFERENCE_BASE_URL, apiKey),
+  firepass: (apiKey) => bearerProbe(FIREWORKS_INFERENCE_BASE_URL, apiKey),
+  nvidia: (apiKey) => bearerProbe(NVIDIA_BASE_URL, apiKey),
+  minimax: (apiKey) => bearerProbe(MINIMAX_BASE_URL, apiKey),
+  "opencode-go": (apiKey) => bearerProbe(getOpenCodeProviderConfig("opencode-go").baseUrl, apiKey),
+  "opencode-zen": (apiKey) =>
+    bearerProbe(getOpenCodeProviderConfig("opencode-zen").baseUrl, apiKey),
+};
+
+export function buildProviderProbe(provider: ProviderName, apiKey: string): ProviderProbe | null {
+  return PROVIDER_PROBES[provider]?.(apiKey) ?? null;
+}
+
+function firstEnvValue(env: NodeJS.ProcessEnv, names: string[]): string | undefined {
+  for (const name of names) {
+    const value = env[name]?.trim();
+    if (value) return value;
+  }
+  return undefined;
+}
+
+export function resolveProviderEnvApiKey(
+  provider: ProviderName,
+  env: NodeJS.ProcessEnv,
+): string | undefined {
+  if (isFireworksInferenceProvider(provider)) {
+    return resolveFireworksInferenceApiKey(provider, { env });
+  }
+  if (isOpenCodeProviderName(provider)) {
+    return resolveOpenCodeApiKey(provider, { env });
+  }
+  switch (provider) {
+    case "openai":
+      return firstEnvValue(env, ["OPENAI_API_KEY"]);
+    case "anthropic":
+      return firstEnvValue(env, ["ANTHROPIC_API_KEY"]);
+    case "google":
+      return firstEnvValue(env, [
+        "GOOGLE_GENERATIVE_AI_API_KEY",
+        "GEMINI_API_KEY",
+        "GOOGLE_API_KEY",
+      ]);
+    case "baseten":
+      return resolveBasetenApiKey({ env });
+    case "together":
+      return resolveTogetherApiKey({ env });
+    case "nvidia":
+      return resolveNvidiaApiKey({ env });
+    case "minimax":
+      return resolveMinimaxApiKey({ env });
+    default:
+      return undefined;
+  }
+}
+
+function parseHeaderNumber(headers: Headers, names: string[]): number | null {
+  for (const name of names) {
+    const raw = headers.get(name);
+    if (raw === null) continue;
+    const value = Number(raw.trim());
+    if (Number.isFinite(value) && value >= 0) return value;
+  }
+  return null;
+}
+
+/** Reads the OpenAI-style `x-ratelimit-*` and Anthropic `anthropic-ratelimit-*` headers. */
+export function parseProviderQuota(headers: Headers, now: number): ProviderCredentialQuota | null {
+  const quota: ProviderCredentialQuota = {
+    requestsLimit: parseHeaderNumber(headers, [
+      "x-ratelimit-limit-requests",
+      "anthropic-ratelimit-requests-limit",
+    ]),
+    requestsRemaining: parseHeaderNumber(headers, [
+      "x-ratelimit-remaining-requests",
+      "anthropic-ratelimit-requests-remaining",
+    ]),
+    tokensLimit: parseHeaderNumber(headers, [
+      "x-ratelimit-limit-tokens",
+      "anthropic-ratelimit-tokens-limit",
+    ]),
+    tokensRemaining: parseHeaderNumber(headers, [
+      "x-ratelimit-remaining-tokens",
+      "anthropic-ratelimit-tokens-remaining",
+    ]),
+    resetAt: parseResetAt(headers, now),
+  };
+  return Object.values(quota).some((value) => value !== null) ? quota : null;
+}
+
+function parseResetAt(headers: Headers, now: number): string | null {
+  const absolute = headers.get("anthropic-ratelimit-requests-reset");
+  if (absolute && !Number.isNaN(Date.parse(absolute))) {
+    return new Date(absolute).toISOString();
+  }
+  // OpenAI reports a duration such as "6m0s", "1.5s", or "120ms".
+  const relative = headers.get("x-ratelimit-reset-requests")?.trim();
+  if (!relative) return null;
+  const unitMs: Record<string, number> = { h: 3_600_000, m: 60_000, s: 1_000, ms: 1 };
+  let totalMs = 0;
+  let matched = "";
+  for (const match of relative.matchAll(/(\d+(?:\.\d+)?)(ms|h|m|s)/g)) {
+    totalMs += Number(match[1]) * (unitMs[match[2] ?? ""] ?? 0);
+    matched += match[0];
+  }
+  return matched === relative ? new Date(now + totalMs).toISOString() : null;
+}
+
+function hashKey(apiKey: string): string {
+  return createHash("sha256").update(apiKey).digest("hex");
+}
+
+/**
+ * Checks an API key with a single cheap request before a server is started with it. Results are
+ * cached per provider and key so reopening settings does not spend quota; network failures are
+ * never cached.
+ */
+export class ProviderCredentialValidator {
+  private readonly fetch: FetchLike;
+  private readonly readStore: () => Promise<ConnectionStore>;
+  private readonly env: NodeJS.ProcessEnv;
+  private readonly now: () => number;
+  private readonly cacheTtlMs: number;
+  private readonly timeoutMs: number;
+  private readonly cache = new Map<string, CacheEntry>();
+
+  constructor(options: ProviderCredentialValidatorOptions = {}) {
+    this.fetch = options.fetch ?? ((url, init) => fetch(url, init));
+    this.readStore = options.readStore ?? (() => readConnectionStore(getAiCoworkerPaths()));
+    this.env = options.env ?? process.env;
+    this.now = options.now ?? Date.now;
+    this.cacheTtlMs = options.cacheTtlMs ?? DEFAULT_CACHE_TTL_MS;
+    this.timeoutMs = options.timeoutMs ?? DEFAULT_TIMEOUT_MS;
+  }
+
+  async validate(input: ValidateProviderCredentialsInput): Promise<ProviderCredentialValidation> {
+    const { provider } = input;
+    const base = {
+      provider,
+      keySource: null,
+      keyMask: null,
+      httpStatus: null,
+      latencyMs: null,
+      quota: null,
+      cached: false,
+    } satisfies Partial<ProviderCredentialValidation>;
+
+    const buildProbe = PROVIDER_PROBES[provider];
+    if (!buildProbe) {
+      return {
+        ...base,
+        status: "unsupported",
+        message: "This provider does not sign in with an API key that can be checked.",
+        checkedAt: new Date(this.now()).toISOString(),
+      };
+    }
+
+    const resolved = await this.resolveKey(input);
+    if (!resolved) {
+      return {
+        ...base,
+        status: "missing",
+        message: "No API key is saved or set in the environment.",
+        checkedAt: new Date(this.now()).toISOString(),
+      };
+    }
+
+    const cacheKey = `${provider}:${hashKey(resolved.apiKey)}`;
+    const cachedEntry = this.cache.get(cacheKey);
+    if (!input.force && cachedEntry && cachedEntry.expiresAt > this.now()) {
+      return { ...cachedEntry.result, keySource: resolved.source, cached: true };
+    }
+
+    const result = await this.probe(provider, buildProbe(resolved.apiKey), {
+      keySource: resolved.source,
+      keyMask: maskApiKey(resolved.apiKey),
+    });
+    if (result.status === "unreachable") {
+      this.cache.delete(cacheKey);
+    } else {
+      this.cache.set(cacheKey, { expiresAt: this.now() + this.cacheTtlMs, result });
+    }
+    return result;
+  }
+
+  clearCache(): void {
+    this.cache.clear();
+  }
+
+  private async resolveKey(
+    input: ValidateProviderCredentialsInput,
+  ): Promise<{ apiKey: string; source: "input" | "saved" | "environment" } | null> {
+    const inputKey = input.apiKey?.trim();
+    if (inputKey) {
+      return { apiKey: inputKey, source: "input" };
+    }
+    const store = await this.readStore();
+    const saved = store.services[input.provider];
+    const savedKey = saved?.mode === "api_key" ? saved.apiKey?.trim() : undefined;
+    if (savedKey) {
+      return { apiKey: savedKey, source: "saved" };
+    }
+    const envKey = resolveProviderEnvApiKey(input.provider, this.env);
+    return envKey ? { apiKey: envKey, source: "environment" } : null;
+  }
+
+  private async probe(
+    provider: ProviderName,
+    probe: ProviderProbe,
+    key: Pick<ProviderCredentialValidation, "keySource" | "keyMask">,
+  ): Promise<ProviderCredentialValidation> {
+    const startedAt = this.now();
+    const base = { provider, ...key, cached: false };
+
+    let response: Response;
+    try {
+      response = await this.fetch(probe.url, {
+        method: "GET",
+        headers: { accept: "application/json", ...probe.headers },
+        signal: AbortSignal.timeout(this.timeoutMs),
+      });
+    } catch (error) {
+      const timedOut = error instanceof Error && error.name === "TimeoutError";
+      const reason = error instanceof Error ? error.message : String(error);
+      return {
+        ...base,
+        status: "unreachable",
+        httpStatus: null,
+        latencyMs: null,
+        quota: null,
+        message: timedOut
+          ? `No response within ${Math.round(this.timeoutMs / 1000)}s.`
+          : `Could not reach the provider: ${reason}`,
+        checkedAt: new Date(this.now()).toISOString(),
+      };
+    }
+
+    const finishedAt = this.now();
+    const latencyMs = Math.max(0, finishedAt - startedAt);
+    const quota = parseProviderQuota(response.headers, finishedAt);
+    const checkedAt = new Date(finishedAt).toISOString();
+    const httpStatus = response.status;
+    await response.body?.cancel().catch(() => {});
+
+    if (response.ok || httpStatus === 429) {
+      return {
+        ...base,
+        status: "valid",
+        httpStatus,
+        latencyMs,
+        quota,
+        message:
+          httpStatus === 429
+            ? "Key accepted, but the provider is rate limiting it right now."
+            : "Key accepted.",
+        checkedAt,
+      };
+    }
+    // Gemini answers a bad key with 400 API_KEY_INVALID rather than 401.
+    const rejected =
+      httpStatus === 401 || httpStatus === 403 || (provider === "google" && httpStatus === 400);
+    if (rejected) {
+      return {
+        ...base,
+        status: "invalid",
+        httpStatus,
+        latencyMs,
+        quota,
+        message: "The provider rejected this key.",
+        checkedAt,
+      };
+    }
+    return {
+      ...base,
+      status: "unreachable",
+      httpStatus,
+      latencyMs,
+      quota,
+      message: `The provider returned HTTP ${httpStatus}; the key could not be checked.`,
+      checkedAt,
+    };
+  }
+}
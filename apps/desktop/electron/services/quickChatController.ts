@@ -10,6 +10,7 @@ import {
   DEFAULT_QUICK_CHAT_SHORTCUT_ACCELERATOR,
   formatQuickChatShortcutLabel,
 } from "../../src/lib/quickChatShortcut";
+import { MessageCatalog } from "./messageCatalog";
 import { createTrayMaskBitmap } from "./trayImage";
 import { revealAndActivateWindow } from "./windowActivation";
 
@@ -29,6 +30,8 @@ type QuickChatControllerOptions = {
   createQuickChatWindow: (opts?: ShowQuickChatWindowInput) => Promise<BrowserWindow>;
   retargetQuickChatWindow: (win: BrowserWindow, opts?: ShowQuickChatWindowInput) => Promise<void>;
   createUtilityWindow: () => Promise<BrowserWindow>;
+  /** Translates the tray tooltip and menu; English when omitted. */
+  messages?: Pick<MessageCatalog, "t">;
 };
 
 const shortcutBridge =
@@ -53,6 +56,7 @@ export class QuickChatController {
     opts?: ShowQuickChatWindowInput,
   ) => Promise<void>;
   private readonly createUtilityWindow: () => Promise<BrowserWindow>;
+  private readonly messages: Pick<MessageCatalog, "t">;
 
   private tray: Tray | null = null;
   private quickChatWindow: BrowserWindow | null = null;
@@ -73,6 +77,13 @@ export class QuickChatController {
     this.createQuickChatWindow = options.createQuickChatWindow;
     this.retargetQuickChatWindow = options.retargetQuickChatWindow;
     this.createUtilityWindow = options.createUtilityWindow;
+    this.messages = options.messages ?? new MessageCatalog();
+  }
+
+  /** Re-renders the tray tooltip and menu after the app language changes. */
+  refreshLabels(): void {
+    this.tray?.setToolTip(this.messages.t("tray.tooltip", { appName: this.appName }));
+    this.refreshTrayMenu();
   }
 
   initialize(): void {
@@ -205,7 +216,7 @@ export class QuickChatController {
     }
     const icon = this.buildTrayIcon();
     this.tray = new electron.Tray(icon);
-    this.tray.setToolTip(`${this.appName} quick chat`);
+    this.tray.setToolTip(this.messages.t("tray.tooltip", { appName: this.appName }));
     this.tray.on("click", () => {
       void this.toggleUtilityWindow(this.tray?.getBounds());
     });
@@ -254,20 +265,20 @@ export class QuickChatController {
       : "";
     return electron.Menu.buildFromTemplate([
       {
-        label: `Open Quick Chat${shortcutSuffix}`,
+        label: this.messages.t("tray.openQuickChat", { shortcut: shortcutSuffix }),
         click: () => {
           void this.showQuickChatWindow({ anchorBounds: this.tray?.getBounds() });
         },
       },
       {
-        label: `Open ${this.appName}`,
+        label: this.messages.t("tray.openApp", { appName: this.appName }),
         click: () => {
           void this.showMainWindow();
         },
       },
       { type: "separator" },
       {
-        label: `Quit ${this.appName}`,
+        label: this.messages.t("tray.quit", { appName: this.appName }),
         click: () => {
           this.quitting = true;
           electron.app.quit();
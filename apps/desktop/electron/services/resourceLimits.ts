@@ -0,0 +1,101 @@
+import os from "node:os";
+
+import type { WorkspaceResourceLimits } from "../../src/app/types";
+import type { ProcessUsageReader } from "./processStats";
+
+const RESOURCE_LIMIT_POLL_INTERVAL_MS = 5_000;
+
+export type ResourceLimitBreach =
+  | { limit: "memory"; rssBytes: number; maxBytes: number }
+  | { limit: "lifetime"; uptimeMs: number; maxMs: number };
+
+type ResourceLimitWatchOptions = {
+  pid: number;
+  startedAt: number;
+  limits: WorkspaceResourceLimits;
+  readUsage: ProcessUsageReader;
+  onExceeded: (breach: ResourceLimitBreach) => void;
+  pollIntervalMs?: number;
+  now?: () => number;
+};
+
+/**
+ * Lowers the scheduling priority of `pid`. Node exposes no setrlimit or Job
+ * Object bindings, so niceness is the only limit applied at spawn; memory and
+ * lifetime are enforced by `watchResourceLimits`.
+ */
+export function applyProcessNiceness(
+  pid: number,
+  niceness: number,
+  setPriority: (pid: number, priority: number) => void = os.setPriority,
+): boolean {
+  try {
+    setPriority(pid, niceness);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Polls resident memory and arms a lifetime timer for `pid`, reporting the
+ * first limit it crosses. Returns a function that stops watching.
+ */
+export function watchResourceLimits(options: ResourceLimitWatchOptions): () => void {
+  const { pid, startedAt, limits, readUsage, onExceeded } = options;
+  const now = options.now ?? Date.now;
+  let stopped = false;
+  let pollTimer: ReturnType<typeof setInterval> | null = null;
+  let lifetimeTimer: ReturnType<typeof setTimeout> | null = null;
+
+  const stop = () => {
+    stopped = true;
+    if (pollTimer) {
+      clearInterval(pollTimer);
+      pollTimer = null;
+    }
+    if (lifetimeTimer) {
+      clearTimeout(lifetimeTimer);
+      lifetimeTimer = null;
+    }
+  };
+  const exceed = (breach: ResourceLimitBreach) => {
+    if (stopped) {
+      return;
+    }
+    stop();
+    onExceeded(breach);
+  };
+
+  if (limits.maxLifetimeMinutes !== undefined) {
+    const maxMs = limits.maxLifetimeMinutes * 60_000;
+    lifetimeTimer = setTimeout(
+      () => exceed({ limit: "lifetime", uptimeMs: now() - startedAt, maxMs }),
+      Math.max(0, startedAt + maxMs - now()),
+    );
+    (lifetimeTimer as { unref?: () => void }).unref?.();
+  }
+
+  if (limits.maxMemoryMb !== undefined) {
+    const maxBytes = limits.maxMemoryMb * 1024 * 1024;
+    let polling = false;
+    pollTimer = setInterval(() => {
+      if (polling) {
+        return;
+      }
+      polling = true;
+      void readUsage(pid)
+        .then((usage) => {
+          if (usage && usage.rssBytes > maxBytes) {
+            exceed({ limit: "memory", rssBytes: usage.rssBytes, maxBytes });
+          }
+        })
+        .finally(() => {
+          polling = false;
+        });
+    }, options.pollIntervalMs ?? RESOURCE_LIMIT_POLL_INTERVAL_MS);
+    (pollTimer as { unref?: () => void }).unref?.();
+  }
+
+  return stop;
+}
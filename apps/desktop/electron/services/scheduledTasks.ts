@@ -0,0 +1,399 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type { PersistedState, ThreadRecord, WorkspaceRecord } from "../../src/app/types";
+import type {
+  CreateScheduledTaskInput,
+  ScheduledTask,
+  ScheduledTaskRun,
+  TranscriptBatchInput,
+} from "../../src/lib/desktopApi";
+import { nextCronOccurrence, parseCronExpression } from "./cron";
+import {
+  type ConnectDesktopSmokeJsonRpcOptions,
+  connectDesktopSmokeJsonRpc,
+  type DesktopSmokeJsonRpcConnection,
+} from "./desktopSmoke";
+
+const DEFAULT_TICK_MS = 60_000;
+const DEFAULT_TIMEOUT_MINUTES = 30;
+const TURN_START_TIMEOUT_MS = 180_000;
+const CLIENT_NAME = "desktop-scheduler";
+const PRIVATE_FILE_MODE = 0o600;
+
+type StoredScheduledTask = Omit<ScheduledTask, "nextRunAt">;
+
+type ScheduledTasksFile = {
+  version: 1;
+  tasks: StoredScheduledTask[];
+};
+
+export type RecordScheduledRunInput = {
+  workspaceId: string;
+  title: string;
+  sessionId: string;
+  events: Array<Omit<TranscriptBatchInput, "threadId">>;
+};
+
+type ScheduledTaskServiceOptions = {
+  filePath: string;
+  clientVersion: string;
+  loadState: () => Promise<PersistedState>;
+  /** Starts the workspace's server, or returns the one already running. */
+  startWorkspaceServer: (
+    workspace: WorkspaceRecord,
+    state: PersistedState,
+  ) => Promise<{ url: string; authToken: string | null }>;
+  /** Saves a finished run as a new thread and returns it. */
+  recordRun: (input: RecordScheduledRunInput) => Promise<ThreadRecord>;
+  onRunCompleted?: (run: ScheduledTaskRun) => void;
+  onError?: (error: unknown) => void;
+  connectJsonRpc?: (
+    options: ConnectDesktopSmokeJsonRpcOptions,
+  ) => Promise<DesktopSmokeJsonRpcConnection>;
+  now?: () => Date;
+  createId?: () => string;
+  tickMs?: number;
+};
+
+function errorMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+function defaultTaskName(prompt: string): string {
+  const firstLine = prompt.trim().split(/\r?\n/)[0] ?? "";
+  return firstLine.length > 80 ? `${firstLine.slice(0, 79)}…` : firstLine;
+}
+
+function parseTasksFile(value: unknown): StoredScheduledTask[] {
+  const tasks = (value as { tasks?: unknown } | null)?.tasks;
+  if (!Array.isArray(tasks)) {
+    return [];
+  }
+  return tasks.filter(
+    (task): task is StoredScheduledTask =>
+      typeof task?.id === "string" &&
+      typeof task.workspaceId === "string" &&
+      typeof task.cronExpr === "string" &&
+      typeof task.prompt === "string",
+  );
+}
+
+/**
+ * Runs saved prompts on a cron schedule. When a task is due, its workspace
+ * server is started if needed, the prompt is sent as a new turn, and the
+ * exchange is recorded as a thread once the turn finishes. Runs missed while
+ * the app was closed are made up once at the next start.
+ *
+ * Runs are unattended, so a turn that waits on a tool approval stalls until
+ * its timeout unless the workspace runs in YOLO mode.
+ */
+export class ScheduledTaskService {
+  private readonly now: () => Date;
+  private readonly createId: () => string;
+  private readonly tickMs: number;
+  private readonly onError: (error: unknown) => void;
+  private readonly connectJsonRpc: (
+    options: ConnectDesktopSmokeJsonRpcOptions,
+  ) => Promise<DesktopSmokeJsonRpcConnection>;
+  private readonly running = new Set<string>();
+  private readonly threadListeners = new Set<(thread: ThreadRecord) => void>();
+  private timer: ReturnType<typeof setInterval> | null = null;
+  private pending: Promise<unknown> = Promise.resolve();
+  /** Due times computed at startup or after each run; absent for disabled tasks. */
+  private readonly dueAt = new Map<string, Date | null>();
+
+  constructor(private readonly options: ScheduledTaskServiceOptions) {
+    this.now = options.now ?? (() => new Date());
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+    this.tickMs = Math.max(1_000, options.tickMs ?? DEFAULT_TICK_MS);
+    this.onError = options.onError ?? (() => {});
+    this.connectJsonRpc = options.connectJsonRpc ?? connectDesktopSmokeJsonRpc;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+    void this.runDueTasks().catch(this.onError);
+    this.timer = setInterval(() => {
+      void this.runDueTasks().catch(this.onError);
+    }, this.tickMs);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  dispose(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = null;
+    }
+  }
+
+  /** Called with each thread a run records, e.g. so window saves keep it. */
+  onThreadRecorded(listener: (thread: ThreadRecord) => void): () => void {
+    this.threadListeners.add(listener);
+    return () => {
+      this.threadListeners.delete(listener);
+    };
+  }
+
+  async list(): Promise<ScheduledTask[]> {
+    const tasks = await this.run(() => this.readTasks());
+    return tasks
+      .map((task) => this.toScheduledTask(task))
+      .sort((left, right) => right.createdAt.localeCompare(left.createdAt));
+  }
+
+  async create(input: CreateScheduledTaskInput): Promise<ScheduledTask> {
+    const schedule = parseCronExpression(input.cronExpr);
+    const { workspaces } = await this.options.loadState();
+    const workspace = workspaces.find((candidate) => candidate.id === input.workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${input.workspaceId}`);
+    }
+    const now = this.now();
+    if (!nextCronOccurrence(schedule, now)) {
+      throw new Error(`Cron expression "${input.cronExpr}" never matches a future time`);
+    }
+    const task: StoredScheduledTask = {
+      id: this.createId(),
+      workspaceId: workspace.id,
+      name: input.options?.name?.trim() || defaultTaskName(input.prompt),
+      cronExpr: input.cronExpr.trim(),
+      prompt: input.prompt,
+      enabled: input.options?.enabled ?? true,
+      timeoutMinutes: input.options?.timeoutMinutes ?? DEFAULT_TIMEOUT_MINUTES,
+      createdAt: now.toISOString(),
+      lastRun: null,
+    };
+    await this.run(async () => {
+      await this.writeTasks([...(await this.readTasks()), task]);
+    });
+    this.scheduleNext(task, now);
+    return this.toScheduledTask(task);
+  }
+
+  async setEnabled(taskId: string, enabled: boolean): Promise<ScheduledTask> {
+    const task = await this.update(taskId, (current) => ({ ...current, enabled }));
+    this.scheduleNext(task, this.now());
+    return this.toScheduledTask(task);
+  }
+
+  async remove(taskId: string): Promise<void> {
+    await this.run(async () => {
+      const tasks = await this.readTasks();
+      if (!tasks.some((task) => task.id === taskId)) {
+        throw new Error(`Unknown scheduled task: ${taskId}`);
+      }
+      await this.writeTasks(tasks.filter((task) => task.id !== taskId));
+    });
+    this.dueAt.delete(taskId);
+  }
+
+  /** Starts every enabled task whose due time has passed; returns how many started. */
+  async runDueTasks(): Promise<number> {
+    const tasks = await this.run(() => this.readTasks());
+    const now = this.now();
+    const due = tasks.filter((task) => {
+      if (!task.enabled || this.running.has(task.id)) {
+        return false;
+      }
+      if (!this.dueAt.has(task.id)) {
+        // First sight since startup: a run missed while the app was closed is due now.
+        this.scheduleNext(task, new Date(Date.parse(task.lastRun?.startedAt ?? task.createdAt)));
+      }
+      const dueAt = this.dueAt.get(task.id);
+      return dueAt ? dueAt.getTime() <= now.getTime() : false;
+    });
+    for (const task of due) {
+      void this.runTask(task).catch(this.onError);
+    }
+    return due.length;
+  }
+
+  private async runTask(task: StoredScheduledTask): Promise<ScheduledTaskRun> {
+    this.running.add(task.id);
+    const startedAt = this.now();
+    let threadId: string | null = null;
+    let status: ScheduledTaskRun["status"] = "failed";
+    let error: string | null = null;
+    try {
+      const result = await this.executeTask(task, startedAt);
+      threadId = result.thread.id;
+      status = result.status;
+      for (const listener of this.threadListeners) {
+        listener(result.thread);
+      }
+    } catch (caught) {
+      error = errorMessage(caught);
+    }
+    const run: ScheduledTaskRun = {
+      taskId: task.id,
+      workspaceId: task.workspaceId,
+      taskName: task.name,
+      startedAt: startedAt.toISOString(),
+      finishedAt: this.now().toISOString(),
+      status,
+      threadId,
+      error,
+    };
+    try {
+      const updated = await this.update(task.id, (current) => ({ ...current, lastRun: run }));
+      this.scheduleNext(updated, this.now());
+    } catch (caught) {
+      // Deleted while running; the run still happened, so it is still reported.
+      this.dueAt.delete(task.id);
+      this.onError(caught);
+    } finally {
+      this.running.delete(task.id);
+    }
+    this.options.onRunCompleted?.(run);
+    return run;
+  }
+
+  private async executeTask(
+    task: StoredScheduledTask,
+    startedAt: Date,
+  ): Promise<{ thread: ThreadRecord; status: ScheduledTaskRun["status"] }> {
+    const state = await this.options.loadState();
+    const workspace = state.workspaces.find((candidate) => candidate.id === task.workspaceId);
+    if (!workspace) {
+      throw new Error("The task's workspace no longer exists");
+    }
+    const server = await this.options.startWorkspaceServer(workspace, state);
+    const rpc = await this.connectJsonRpc({
+      url: server.url,
+      clientVersion: this.options.clientVersion,
+      clientName: CLIENT_NAME,
+      authToken: server.authToken,
+    });
+    try {
+      const started = await rpc.sendRequest("thread/start", { cwd: workspace.path });
+      const sessionId = (started.result as { thread?: { id?: string } } | undefined)?.thread?.id;
+      if (!sessionId) {
+        throw new Error("thread/start did not return a thread id");
+      }
+      const turnStarted = await rpc.sendRequest(
+        "turn/start",
+        { threadId: sessionId, input: task.prompt },
+        { timeoutMs: TURN_START_TIMEOUT_MS },
+      );
+      const turnId = (turnStarted.result as { turn?: { id?: string } } | undefined)?.turn?.id;
+      if (!turnId) {
+        throw new Error("turn/start did not return a turn id");
+      }
+
+      const replies: Array<{ ts: string; text: string }> = [];
+      let status: ScheduledTaskRun["status"] = "interrupted";
+      try {
+        const completed = await rpc.waitFor(
+          (message) => {
+            const params = message.params as
+              | { turnId?: string | null; item?: { type?: string; text?: unknown } }
+              | undefined;
+            if (
+              message.method === "item/completed" &&
+              params?.turnId === turnId &&
+              params.item?.type === "agentMessage" &&
+              typeof params.item.text === "string"
+            ) {
+              replies.push({ ts: this.now().toISOString(), text: params.item.text });
+            }
+            return (
+              message.method === "turn/completed" &&
+              (message.params as { turn?: { id?: string } } | undefined)?.turn?.id === turnId
+            );
+          },
+          {
+            timeoutMs: (task.timeoutMinutes ?? DEFAULT_TIMEOUT_MINUTES) * 60_000,
+            label: `turn/completed for ${turnId}`,
+          },
+        );
+        const turnStatus = (completed.params as { turn?: { status?: string } } | undefined)?.turn
+          ?.status;
+        status = turnStatus === "completed" || turnStatus === "failed" ? turnStatus : "interrupted";
+      } catch {
+        // Timed out or disconnected; record what arrived so far.
+        await rpc.sendRequest("turn/interrupt", { threadId: sessionId, turnId }).catch(() => {});
+      }
+
+      const prompt = { type: "user_message", sessionId, text: task.prompt };
+      const thread = await this.options.recordRun({
+        workspaceId: workspace.id,
+        title: task.name,
+        sessionId,
+        events: [
+          { ts: startedAt.toISOString(), direction: "client", payload: prompt },
+          { ts: startedAt.toISOString(), direction: "server", payload: prompt },
+          ...replies.map((reply) => ({
+            ts: reply.ts,
+            direction: "server" as const,
+            payload: { type: "assistant_message", sessionId, text: reply.text },
+          })),
+        ],
+      });
+      return { thread, status };
+    } finally {
+      rpc.close();
+    }
+  }
+
+  private scheduleNext(task: StoredScheduledTask, after: Date): void {
+    if (!task.enabled) {
+      this.dueAt.delete(task.id);
+      return;
+    }
+    try {
+      this.dueAt.set(task.id, nextCronOccurrence(parseCronExpression(task.cronExpr), after));
+    } catch (error) {
+      this.dueAt.set(task.id, null);
+      this.onError(error);
+    }
+  }
+
+  private toScheduledTask(task: StoredScheduledTask): ScheduledTask {
+    const dueAt = task.enabled ? this.dueAt.get(task.id) : null;
+    return { ...task, nextRunAt: dueAt ? dueAt.toISOString() : null };
+  }
+
+  private async update(
+    taskId: string,
+    change: (task: StoredScheduledTask) => StoredScheduledTask,
+  ): Promise<StoredScheduledTask> {
+    return await this.run(async () => {
+      const tasks = await this.readTasks();
+      const current = tasks.find((task) => task.id === taskId);
+      if (!current) {
+        throw new Error(`Unknown scheduled task: ${taskId}`);
+      }
+      const updated = change(current);
+      await this.writeTasks(tasks.map((task) => (task.id === taskId ? updated : task)));
+      return updated;
+    });
+  }
+
+  private async run<T>(fn: () => Promise<T>): Promise<T> {
+    const next = this.pending.then(fn);
+    this.pending = next.catch(() => {});
+    return await next;
+  }
+
+  private async readTasks(): Promise<StoredScheduledTask[]> {
+    try {
+      return parseTasksFile(JSON.parse(await fs.readFile(this.options.filePath, "utf8")));
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException | null)?.code === "ENOENT") {
+        return [];
+      }
+      throw error;
+    }
+  }
+
+  private async writeTasks(tasks: StoredScheduledTask[]): Promise<void> {
+    const file: ScheduledTasksFile = { version: 1, tasks };
+    await writeFileAtomic(this.options.filePath, `${JSON.stringify(file, null, 2)}\n`, {
+      mode: PRIVATE_FILE_MODE,
+    });
+  }
+}
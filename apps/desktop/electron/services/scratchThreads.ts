@@ -0,0 +1,118 @@
+import crypto from "node:crypto";
+
+import type { TranscriptEvent } from "../../src/app/types";
+import type { ScratchThreadInfo, TranscriptBatchInput } from "../../src/lib/desktopApi";
+
+type ScratchThreadEntry = ScratchThreadInfo & {
+  ownerId: number;
+  events: TranscriptEvent[];
+};
+
+type ScratchThreadRegistryOptions = {
+  now?: () => Date;
+  createId?: () => string;
+};
+
+/**
+ * In-memory threads owned by a single renderer window. Scratch threads never
+ * get a `ThreadRecord` or a transcript file unless explicitly promoted, and are
+ * dropped when their owning window goes away.
+ */
+export class ScratchThreadRegistry {
+  private readonly threads = new Map<string, ScratchThreadEntry>();
+  private readonly now: () => Date;
+  private readonly createId: () => string;
+
+  constructor(options: ScratchThreadRegistryOptions = {}) {
+    this.now = options.now ?? (() => new Date());
+    this.createId = options.createId ?? (() => `scratch-${crypto.randomUUID()}`);
+  }
+
+  create(ownerId: number, workspaceId: string): ScratchThreadInfo {
+    const thread: ScratchThreadEntry = {
+      threadId: this.createId(),
+      workspaceId,
+      createdAt: this.now().toISOString(),
+      ownerId,
+      events: [],
+    };
+    this.threads.set(thread.threadId, thread);
+    return toInfo(thread);
+  }
+
+  has(threadId: string): boolean {
+    return this.threads.has(threadId);
+  }
+
+  list(ownerId: number): ScratchThreadInfo[] {
+    return [...this.threads.values()]
+      .filter((thread) => thread.ownerId === ownerId)
+      .map((thread) => toInfo(thread));
+  }
+
+  /**
+   * Buffers events that belong to scratch threads and returns the rest, which
+   * callers should persist as usual.
+   */
+  captureEvents(events: TranscriptBatchInput[]): TranscriptBatchInput[] {
+    const remaining: TranscriptBatchInput[] = [];
+    for (const event of events) {
+      const thread = this.threads.get(event.threadId);
+      if (!thread) {
+        remaining.push(event);
+        continue;
+      }
+      thread.events.push({ ...event });
+    }
+    return remaining;
+  }
+
+  readTranscript(threadId: string): TranscriptEvent[] | null {
+    const thread = this.threads.get(threadId);
+    return thread ? [...thread.events] : null;
+  }
+
+  /** Removes the scratch thread and hands back its buffered transcript. */
+  take(
+    ownerId: number,
+    threadId: string,
+  ): { thread: ScratchThreadInfo; events: TranscriptEvent[] } {
+    const thread = this.threads.get(threadId);
+    if (!thread) {
+      throw new Error(`Unknown scratch thread: ${threadId}`);
+    }
+    if (thread.ownerId !== ownerId) {
+      throw new Error("Scratch thread belongs to another window");
+    }
+    this.threads.delete(threadId);
+    return { thread: toInfo(thread), events: thread.events };
+  }
+
+  /** Puts a taken thread back, e.g. when promotion fails part-way. */
+  restore(ownerId: number, thread: ScratchThreadInfo, events: TranscriptEvent[]): void {
+    this.threads.set(thread.threadId, { ...thread, ownerId, events });
+  }
+
+  discard(threadId: string): boolean {
+    return this.threads.delete(threadId);
+  }
+
+  releaseOwner(ownerId: number): number {
+    let released = 0;
+    for (const [threadId, thread] of this.threads) {
+      if (thread.ownerId === ownerId) {
+        this.threads.delete(threadId);
+        released += 1;
+      }
+    }
+    return released;
+  }
+}
+
+function toInfo(thread: ScratchThreadEntry): ScratchThreadInfo {
+  return {
+    threadId: thread.threadId,
+    workspaceId: thread.workspaceId,
+    createdAt: thread.createdAt,
+  };
+}
@@ -1,9 +1,9 @@
 import { type ChildProcessByStdio, spawn, spawnSync } from "node:child_process";
-import { createHash, randomUUID } from "node:crypto";
+import { createHash, randomBytes, randomUUID } from "node:crypto";
 import fs from "node:fs";
 import path from "node:path";
 import readline from "node:readline";
-import type { Readable } from "node:stream";
+import type { Readable, Writable } from "node:stream";
 import { app } from "electron";
 import { z } from "zod";
 import {
@@ -17,6 +17,7 @@ import {
   WINDOWS_SANDBOX_HELPER_NAME,
   WINDOWS_SANDBOX_SETUP_NAME,
 } from "../../../../src/platform/sandbox/windows";
+import { HANDOFF_QUERY_PARAM, signHandoffToken } from "../../../../src/server/handoff";
 import {
   COWORK_RUNTIME_STARTUP_COMPONENT,
   SERVER_STARTUP_PROGRESS_TYPE,
@@ -27,38 +28,95 @@ import {
   resolveCrashReportingConfig,
 } from "../../../../src/telemetry/crashReporting";
 import { captureProductEvent } from "../../../../src/telemetry/productAnalytics";
+import { parseSshWorkspaceUrl, type SshWorkspaceTarget } from "../../src/app/remoteWorkspaces";
 import type {
   normalizePrivacyTelemetrySettings,
   PersistedPrivacyTelemetrySettings,
   PersistedProductAnalyticsState,
+  WorkspaceContainerLaunch,
+  WorkspaceLaunchProfile,
+  WorkspaceResourceLimits,
 } from "../../src/app/types";
+import {
+  type HandoffAccess,
+  type HandoffLink,
+  type InstallationIntegrityReport,
+  SERVER_STARTUP_FAILED_ERROR_CODE,
+  type WorkspaceProxyConnectionEvent,
+  type WorkspaceResourceLimitKind,
+  type WorkspaceServerStartupDiagnostics,
+  type WorkspaceServerStartupStage,
+  type WorkspaceServerStats,
+  type WorkspaceServerVersionInfo,
+} from "../../src/lib/desktopApi";
+import {
+  CONTAINER_SERVER_PORT,
+  containerNameForServer,
+  containerPathPolicy,
+  type DockerWorkspaces,
+  resolveContainerLaunch,
+} from "./containerWorkspaces";
 import { resolvePackagedBuiltinDistDir } from "./desktopBuiltinPaths";
+import { verifyInstallation } from "./installationIntegrity";
 import { flushLocalLogWrites, getLocalLogPath, writeLocalLog } from "./localLogs";
+import { forwardedServerUrl } from "./loopbackPorts";
 import type {
   MobileRelayTrustedDevicePermissionKey,
   MobileRelayTrustedPhoneDevice,
 } from "./mobileRelayTypes";
+import type { SpawnedServerLedger } from "./orphanedServers";
+import { ProcessStatsSampler, type ProcessUsageReader, readProcessUsage } from "./processStats";
 import { buildDesktopProductAnalyticsEnv } from "./productAnalytics";
+import { applyProcessNiceness, watchResourceLimits } from "./resourceLimits";
 import {
   buildSourceEnvForAttempt,
   getServerTerminationSignal,
   getSourceStartupAttemptCount,
 } from "./serverPlatform";
+import { ServerOutputReaders } from "./serverOutputReaders";
+import {
+  MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+  resolveServerVersionInfo,
+  ServerUpgradeRequiredError,
+} from "./serverVersion";
+import {
+  AdoptedSidecarProcess,
+  isProcessAlive,
+  SIDECAR_ADOPTABLE_ENV,
+  type SidecarRegistry,
+  type SidecarRegistryEntry,
+} from "./sidecarAdoption";
 import {
   FOUNDATION_MODELS_SDK_DIR_NAME,
   findPackagedSidecarLaunchCommand,
   hasPackagedFoundationModelsSdk,
   hasPackagedWindowsAiElectronPackage,
+  type SidecarLaunchCommand,
+  verifySidecarLaunchCommand,
   WINDOWS_AI_ELECTRON_DIR_NAME,
 } from "./sidecar";
+import { SleepClock, setAwakeTimeout } from "./sleepClock";
+import type { SshRemoteWorkspaces, SshTunnel } from "./sshRemoteWorkspaces";
 import { assertSafeId, assertWorkspaceDirectory } from "./validation";
 import { writeWindowsSandboxReadiness } from "./windowsSandboxReadiness";
+import { WorkspaceSocketProxy } from "./workspaceSocketProxy";
 
 const DEFAULT_SERVER_STARTUP_TIMEOUT_MS = 120_000;
 const PACKAGED_SERVER_STARTUP_TIMEOUT_MS = 300_000;
 const MIN_SERVER_STARTUP_TIMEOUT_MS = 5_000;
 const MAX_SERVER_STARTUP_TIMEOUT_MS = 300_000;
 const SERVER_HEALTH_TIMEOUT_MS = 1_500;
+const SERVER_SHUTDOWN_REQUEST_TIMEOUT_MS = 1_000;
+const SERVER_TITLE_TIMEOUT_MS = 20_000;
+const SERVER_HANDOFF_TIMEOUT_MS = 5_000;
+const SERVER_FLUSH_TIMEOUT_MS = 10_000;
+const GRACEFUL_SHUTDOWN_TIMEOUT_MS = 3_000;
+const SERVER_SHUTDOWN_TOKEN_ENV = "COWORK_SHUTDOWN_TOKEN";
+const SERVER_AUTH_TOKEN_ENV = "COWORK_SERVER_AUTH_TOKEN";
+const SERVER_HEALTH_POLL_INTERVAL_MS = 250;
+const MIN_SERVER_HEALTH_WAIT_MS = 5_000;
+/** How long after a wake a failed status check keeps re-handshaking before giving up. */
+const POST_WAKE_HEALTH_GRACE_MS = 15_000;
 const WINDOWS_SANDBOX_PROBE_TIMEOUT_MS = 15_000;
 const WINDOWS_SANDBOX_SETUP_TIMEOUT_MS = 60_000;
 const STDERR_TAIL_LIMIT = 16_384;
@@ -81,18 +139,48 @@ const TELEMETRY_CONSENT_ENV_KEYS = [
 ] as const;
 
 type ServerHandle = {
-  child: ServerChildProcess;
+  child: ManagedServerProcess;
   url: string;
+  /** Bearer token the server requires from local clients without a browser origin. */
+  authToken: string;
   mobileH3: ServerListening["mobileH3"];
+  serverVersion: WorkspaceServerVersionInfo;
+  /** Epoch ms the process started; for adopted sidecars, when the previous instance launched it. */
+  startedAt: number;
+  /** Identifies the launch profile args and env it was spawned with; null when none or adopted. */
+  launchProfileKey: string | null;
+  /** Set when the server runs in a Docker container; the child is the attached `docker run`. */
+  container?: { name: string; image: string };
+  /** Start options of a server run from `src/server`; a source change restarts it with them. */
+  sourceStartOptions?: StartWorkspaceServerOptions;
+  /** Lets the process keep running after this app instance exits. */
+  release: () => void;
   cleanup: () => void;
 };
 
+type StartedWorkspaceServer = {
+  url: string;
+  authToken: string;
+  mobileH3: ServerListening["mobileH3"];
+};
+
 type PendingServerHandle = {
   child: ServerChildProcess;
   cleanup: () => void;
 };
 
-type ServerChildProcess = ChildProcessByStdio<null, Readable, Readable>;
+/** Remote servers keep stdin open; see `SshRemoteWorkspaces.launchServer`. */
+type ServerChildProcess = ChildProcessByStdio<Writable | null, Readable, Readable>;
+
+/** Either a spawned child or a sidecar adopted from a previous app instance. */
+type ManagedServerProcess = {
+  readonly pid?: number;
+  readonly exitCode: number | null;
+  readonly signalCode: NodeJS.Signals | null;
+  kill(signal?: NodeJS.Signals | number): boolean;
+  once(event: "exit", listener: () => void): unknown;
+  off(event: "exit", listener: () => void): unknown;
+};
 
 type ServerOutputSource = "stdout" | "stderr";
 
@@ -107,14 +195,29 @@ type WaitForServerListeningOptions = {
   bootstrapTimeoutMs?: number;
   onStdoutLine?: (line: string) => void;
   onCoworkRuntimeBootstrapProgress?: (progress: CoworkRuntimeBootstrapProgress) => void;
+  /** Receives a stop for the stdout reader, which otherwise runs until the child exits. */
+  trackReader?: (stop: () => void) => void;
+  /** Time asleep does not count toward the timeout. */
+  sleepClock?: SleepClock;
+};
+
+type WaitForServerHealthyOptions = {
+  fetch: typeof fetch;
+  /** Awake time to wait; time asleep does not count. */
+  timeoutMs: number;
+  pollIntervalMs?: number;
+  sleepClock?: SleepClock;
 };
 
 type ServerListening = {
   type: "server_listening";
+  version?: string;
+  protocolVersion?: string;
   url: string;
   port: number;
   cwd: string;
   browserAccessToken?: string | null;
+  authToken?: string | null;
   mobileH3?: {
     url: string;
     port: number;
@@ -138,7 +241,7 @@ type ServerListening = {
   } | null;
 };
 
-type StartWorkspaceServerOptions = {
+export type StartWorkspaceServerOptions = {
   workspaceId: string;
   workspacePath: string;
   yolo: boolean;
@@ -148,17 +251,56 @@ type StartWorkspaceServerOptions = {
   productAnalyticsState?: PersistedProductAnalyticsState | null;
   mobileH3?: boolean;
   rotateMobileH3Tls?: boolean;
+  resourceLimits?: WorkspaceResourceLimits;
+  /** Extra roots of a multi-root workspace, passed to the sidecar as `--root`. */
+  additionalRoots?: string[];
+  /** Allowlisted extra sidecar flags and env, already validated by the caller. */
+  launchProfile?: WorkspaceLaunchProfile;
+  /** The workspace's path policy as JSON, passed to the sidecar as `AGENT_PATH_POLICY`. */
+  pathPolicy?: string;
+  /** Runs the sidecar in a Docker container instead of as a local process. */
+  container?: WorkspaceContainerLaunch;
   onCoworkRuntimeBootstrapProgress?: (progress: CoworkRuntimeBootstrapProgress) => void;
 };
 
+type SidecarAdoptionOptions = {
+  registry: SidecarRegistry;
+  isProcessAlive?: (pid: number) => boolean;
+  signalProcess?: (pid: number, signal?: NodeJS.Signals | number) => void;
+};
+
 type ServerManagerOptions = {
   getProductAnalyticsState?: () => PersistedProductAnalyticsState | null | undefined;
+  /** Proxy and CA variables layered over the inherited environment on each spawn. */
+  getNetworkEnv?: () => Promise<Record<string, string>>;
   fetch?: typeof fetch;
+  /** When set, sidecars are registered for adoption by the next app instance. */
+  sidecarAdoption?: SidecarAdoptionOptions | null;
+  /** Records spawned servers so a later launch can find ones orphaned by a crash. */
+  spawnLedger?: SpawnedServerLedger | null;
+  /** Supplies a downloaded server binary that replaces the bundled one; see `SidecarUpdater`. */
+  sidecarUpdates?: { prepareLaunch(): Promise<SidecarLaunchCommand | null> } | null;
+  /** Runs servers for `ssh://` workspaces; without it they cannot be started. */
+  remoteWorkspaces?: SshRemoteWorkspaces | null;
+  /** Runs servers for workspaces with a container launch; without it they cannot be started. */
+  containerWorkspaces?: DockerWorkspaces | null;
+  /** Runs before spawning a server with `--yolo`; a rejection aborts the start. */
+  beforeYoloStart?: (workspaceId: string) => Promise<void>;
+  /** Keeps startup timeouts and health checks from counting time the machine slept. */
+  sleepClock?: SleepClock;
+  /**
+   * Held while a server starts so macOS App Nap does not throttle the app's timers
+   * and stdout reads mid-startup; returns a release function.
+   */
+  preventAppSuspension?: () => () => void;
+  readProcessUsage?: ProcessUsageReader;
+  onProxyConnectionStateChanged?: (event: WorkspaceProxyConnectionEvent) => void;
   onWorkspaceServerExited?: (event: {
     workspaceId: string;
     url: string | null;
     code: number | null;
     signal: string | null;
+    limitExceeded?: WorkspaceResourceLimitKind;
   }) => void;
 };
 
@@ -208,17 +350,45 @@ const trustedDeviceSchema = z.object({
   permissions: trustedDevicePermissionSchema,
 });
 
+const sidecarHandshakeSchema = z
+  .object({
+    version: z.string().min(1).optional(),
+    protocolVersion: z.string().min(1).optional(),
+    pid: z.number().int().positive().optional(),
+    cwd: z.string().min(1),
+  })
+  .passthrough();
+
 const trustedDevicesResponseSchema = z.object({
   trustedDevices: z.array(trustedDeviceSchema).optional().default([]),
 });
 
+const handoffRegistrationResponseSchema = z.object({
+  port: z.number().int().positive(),
+  hostHints: z.array(z.string().min(1)).min(1),
+});
+
+const handoffRevocationResponseSchema = z.object({
+  removed: z.boolean(),
+});
+
+const generatedTitleResponseSchema = z.object({
+  title: z.string().trim().min(1),
+  source: z.enum(["default", "model", "heuristic"]),
+});
+
+type GeneratedThreadTitle = z.infer<typeof generatedTitleResponseSchema>;
+
 const serverListeningSchema = z
   .object({
     type: z.literal("server_listening"),
+    version: z.string().min(1).optional(),
+    protocolVersion: z.string().min(1).optional(),
     url: z.string().min(1),
     port: z.number(),
     cwd: z.string().min(1),
     browserAccessToken: z.string().min(1).nullable().optional(),
+    authToken: z.string().min(1).nullable().optional(),
     mobileH3: z
       .object({
         url: z.string().min(1),
@@ -307,6 +477,22 @@ function resolveRepoRoot(): string {
   throw new Error("Unable to locate repository root for development server startup");
 }
 
+function isSourceServerMode(): boolean {
+  return !app.isPackaged || process.env.COWORK_DESKTOP_USE_SOURCE === "1";
+}
+
+/** The `src/server` directory servers run from in source mode; null when they run packaged. */
+export function resolveSourceServerDir(): string | null {
+  if (!isSourceServerMode()) {
+    return null;
+  }
+  try {
+    return path.join(resolveRepoRoot(), "src", "server");
+  } catch {
+    return null;
+  }
+}
+
 function getSidecarSearchDirs(): string[] {
   const fromEnv = process.env.COWORK_DESKTOP_SIDECAR_PATH;
   if (fromEnv && fs.existsSync(fromEnv)) {
@@ -593,7 +779,7 @@ function findBundledWindowsAiElectronDir(): string | null {
   return null;
 }
 
-function waitForExit(child: ServerChildProcess, timeoutMs: number): Promise<boolean> {
+function waitForExit(child: ManagedServerProcess, timeoutMs: number): Promise<boolean> {
   if (child.exitCode !== null || child.signalCode !== null) {
     return Promise.resolve(true);
   }
@@ -613,23 +799,68 @@ function waitForExit(child: ServerChildProcess, timeoutMs: number): Promise<bool
   });
 }
 
-async function gracefulKill(child: ServerChildProcess): Promise<void> {
+type ServerShutdownEndpoint = {
+  url: string;
+  token: string;
+};
+
+/**
+ * Control endpoints of servers we spawned, used to ask them to stop where
+ * there is no signal to send. Adopted sidecars never have one: the token is
+ * not persisted, so they are terminated outright.
+ */
+const serverShutdownEndpoints = new WeakMap<ManagedServerProcess, ServerShutdownEndpoint>();
+
+async function requestServerShutdown(
+  endpoint: ServerShutdownEndpoint,
+  fetchImpl: typeof fetch,
+): Promise<boolean> {
+  const controller = new AbortController();
+  const timeout = setTimeout(() => controller.abort(), SERVER_SHUTDOWN_REQUEST_TIMEOUT_MS);
+  try {
+    const response = await fetchImpl(toHttpServerRequestUrl(endpoint.url, "/cowork/shutdown"), {
+      method: "POST",
+      headers: { "x-cowork-shutdown-token": endpoint.token },
+      signal: controller.signal,
+    });
+    return response.status === 202;
+  } catch {
+    return false;
+  } finally {
+    clearTimeout(timeout);
+  }
+}
+
+/**
+ * Asks the server to stop and escalates to a hard kill after three seconds.
+ * Windows has no SIGTERM, so there the request goes over the server's
+ * shutdown endpoint and `kill()` (TerminateProcess) is the fallback.
+ */
+async function gracefulKill(
+  child: ManagedServerProcess,
+  deps: { platform?: NodeJS.Platform; fetch?: typeof fetch } = {},
+): Promise<void> {
   if (child.exitCode !== null || child.signalCode !== null) {
     return;
   }
 
-  try {
-    const signal = getServerTerminationSignal();
-    if (signal) {
-      child.kill(signal);
-    } else {
-      child.kill();
+  const signal = getServerTerminationSignal(deps.platform);
+  const endpoint = serverShutdownEndpoints.get(child);
+  const requested =
+    !signal && endpoint ? await requestServerShutdown(endpoint, deps.fetch ?? fetch) : false;
+  if (!requested) {
+    try {
+      if (signal) {
+        child.kill(signal);
+      } else {
+        child.kill();
+      }
+    } catch {
+      // ignore; process may already be gone
     }
-  } catch {
-    // ignore; process may already be gone
   }
 
-  const exited = await waitForExit(child, 3_000);
+  const exited = await waitForExit(child, GRACEFUL_SHUTDOWN_TIMEOUT_MS);
   if (exited) {
     return;
   }
@@ -729,13 +960,14 @@ function waitForServerListening(
 ): Promise<ServerListening> {
   const timeoutMs = opts.timeoutMs ?? getServerStartupTimeoutMs();
   const bootstrapTimeoutMs = opts.bootstrapTimeoutMs ?? PACKAGED_SERVER_STARTUP_TIMEOUT_MS;
+  const sleepClock = opts.sleepClock ?? new SleepClock();
   return new Promise((resolve, reject) => {
     const rl = readline.createInterface({ input: child.stdout });
     const recentLines: string[] = [];
     let readySeen = false;
     let finished = false;
     let readySettled = false;
-    let timeout: ReturnType<typeof setTimeout>;
+    let cancelTimeout = () => {};
     let activeTimeoutMs = timeoutMs;
 
     const settleReadyResolve = (value: ServerListening) => {
@@ -785,9 +1017,9 @@ function waitForServerListening(
       if (readySeen || finished) {
         return;
       }
-      clearTimeout(timeout);
+      cancelTimeout();
       activeTimeoutMs = delayMs;
-      timeout = setTimeout(onTimeout, delayMs);
+      cancelTimeout = setAwakeTimeout(sleepClock, onTimeout, delayMs);
     };
 
     const onError = (error: Error) => {
@@ -802,9 +1034,7 @@ function waitForServerListening(
       if (!readySeen) {
         settleReadyReject(
           new Error(
-            withRecentOutput(
-              `Server exited before startup JSON (code=${code ?? "null"}, signal=${signal ?? "null"})`,
-            ),
+            withRecentOutput(`Server exited before startup JSON (${describeExit(code, signal)})`),
           ),
         );
       }
@@ -815,7 +1045,7 @@ function waitForServerListening(
         return;
       }
       finished = true;
-      clearTimeout(timeout);
+      cancelTimeout();
       rl.off("line", onLine);
       rl.close();
       child.off("error", onError);
@@ -843,7 +1073,7 @@ function waitForServerListening(
         const listening = serverListeningSchema.safeParse(payload);
         if (listening.success && !readySeen) {
           readySeen = true;
-          clearTimeout(timeout);
+          cancelTimeout();
           settleReadyResolve(listening.data);
           return;
         }
@@ -854,24 +1084,113 @@ function waitForServerListening(
       opts.onStdoutLine?.(trimmed);
     };
 
-    timeout = setTimeout(onTimeout, timeoutMs);
+    cancelTimeout = setAwakeTimeout(sleepClock, onTimeout, timeoutMs);
     rl.on("line", onLine);
     child.once("error", onError);
     child.once("exit", onExit);
+    opts.trackReader?.(() => onError(new Error("Server startup was cancelled")));
   });
 }
 
-function buildSpawnArgs(workspacePath: string, yolo: boolean, mobileH3 = false): string[] {
+function describeExit(code: number | null, signal: NodeJS.Signals | null): string {
+  return `code=${code ?? "null"}, signal=${signal ?? "null"}`;
+}
+
+/**
+ * `server_listening` only means the socket is bound; poll `/cowork/health` until the server
+ * actually answers, failing fast if the process exits in the meantime. A probe that spans a
+ * sleep is retried without counting as the last failure, and the sleep does not use up the
+ * wait.
+ */
+async function waitForServerHealthy(
+  child: ServerChildProcess,
+  url: string,
+  opts: WaitForServerHealthyOptions,
+): Promise<void> {
+  const healthUrl = toHttpServerRequestUrl(url, "/cowork/health");
+  const pollIntervalMs = opts.pollIntervalMs ?? SERVER_HEALTH_POLL_INTERVAL_MS;
+  const sleepClock = opts.sleepClock ?? new SleepClock();
+  const startedAt = sleepClock.currentTime();
+  const remainingMs = () => opts.timeoutMs - sleepClock.awakeSince(startedAt);
+  let exitDescription =
+    child.exitCode !== null || child.signalCode !== null
+      ? describeExit(child.exitCode, child.signalCode)
+      : null;
+  let wakeOnExit = () => {};
+  const onExit = (code: number | null, signal: NodeJS.Signals | null) => {
+    exitDescription = describeExit(code, signal);
+    wakeOnExit();
+  };
+  child.once("exit", onExit);
+  let lastFailure = "no response";
+  try {
+    while (exitDescription === null) {
+      const attemptStartedAt = sleepClock.currentTime();
+      let failure: string;
+      try {
+        const response = await fetchWithTimeout(
+          opts.fetch,
+          healthUrl,
+          Math.min(SERVER_HEALTH_TIMEOUT_MS, Math.max(1, remainingMs())),
+        );
+        if (response.ok) {
+          return;
+        }
+        failure = `HTTP ${response.status}`;
+      } catch (error) {
+        failure = toErrorMessage(error);
+      }
+      if (sleepClock.sleptSince(attemptStartedAt) === 0) {
+        lastFailure = failure;
+      }
+      if (exitDescription !== null) {
+        break;
+      }
+      if (pollIntervalMs >= remainingMs()) {
+        throw new Error(
+          `Server health check did not pass within ${opts.timeoutMs / 1000} seconds ` +
+            `(last=${lastFailure})`,
+        );
+      }
+      await new Promise<void>((resolve) => {
+        const timer = setTimeout(resolve, pollIntervalMs);
+        wakeOnExit = () => {
+          clearTimeout(timer);
+          resolve();
+        };
+      });
+    }
+    throw new Error(`Server exited before passing health check (${exitDescription})`);
+  } finally {
+    child.off("exit", onExit);
+  }
+}
+
+function buildSpawnArgs(
+  workspacePath: string,
+  yolo: boolean,
+  mobileH3 = false,
+  additionalRoots: string[] = [],
+  extraArgs: string[] = [],
+): string[] {
   const args = ["--dir", workspacePath, "--port", "0", "--json"];
+  for (const root of additionalRoots) {
+    args.push("--root", root);
+  }
   if (mobileH3) {
     args.push("--mobile-h3");
   }
   if (yolo) {
     args.push("--yolo");
   }
+  args.push(...extraArgs);
   return args;
 }
 
+function launchProfileKey(profile: WorkspaceLaunchProfile | undefined): string | null {
+  return profile ? JSON.stringify([profile.args, profile.env]) : null;
+}
+
 function resolveSourceStartup(
   useSource: boolean,
   resolveRepoRootImpl: () => string = resolveRepoRoot,
@@ -892,6 +1211,7 @@ function resolveSourceStartup(
 function buildServerEnv(
   featureFlags?: { openAiNativeConnectors?: boolean; tasks?: boolean },
   opts: {
+    adoptable?: boolean;
     includeBundledFoundationModelsSdk?: boolean;
     includeBundledWindowsAiElectron?: boolean;
     rotateMobileH3Tls?: boolean;
@@ -924,6 +1244,7 @@ function buildServerEnv(
   delete processEnv.COWORK_SKIP_DEFAULT_SKILLS_BOOTSTRAP;
   delete processEnv.COWORK_ENABLE_TASKS;
   delete processEnv.COWORK_EXPERIMENTAL_OPENAI_NATIVE_CONNECTORS;
+  delete processEnv[SIDECAR_ADOPTABLE_ENV];
   return {
     ...processEnv,
     COWORK_WEB_DESKTOP_SERVICE: "1",
@@ -955,6 +1276,7 @@ function buildServerEnv(
       : {}),
     ...(featureFlags?.tasks ? { COWORK_ENABLE_TASKS: "1" } : {}),
     ...(opts.rotateMobileH3Tls ? { COWORK_H3_ROTATE_TLS: "1" } : {}),
+    ...(opts.adoptable ? { [SIDECAR_ADOPTABLE_ENV]: "1" } : {}),
     ...buildHarnessTerminalLogsEnv(processEnv),
     ...buildDesktopObservabilityEnv(privacyTelemetrySettings),
     ...buildDesktopCrashReportingEnv(privacyTelemetrySettings),
@@ -1142,15 +1464,106 @@ function withStderrTail(message: string, stderrTail: string): string {
   return summary ? `${message}; stderr=${summary}` : message;
 }
 
+type ServerLaunch = {
+  mode: WorkspaceServerStartupDiagnostics["mode"];
+  command: string;
+  args: string[];
+  cwd: string;
+};
+
+/**
+ * What differs between running a sidecar locally, over ssh, or in a container;
+ * `ServerManager.runWorkspaceServerStart` does everything else.
+ */
+type WorkspaceServerTransport = {
+  /** Prefix of the start's log events, e.g. "remote workspace server". */
+  logLabel: string;
+  /** Added to the listening and failure log events. */
+  logFields?: Record<string, unknown>;
+  /** Spawns the sidecar with `tokenEnv`, its shutdown and auth tokens, in its environment. */
+  launch: (tokenEnv: Record<string, string>) => ServerChildProcess;
+  onSpawned?: (child: ServerChildProcess, spawnedAt: number) => void;
+  /** Where this process reaches the sidecar; defaults to the URL it reported. */
+  reachableUrl?: (
+    listening: ServerListening,
+    child: ServerChildProcess,
+  ) => Promise<string> | string;
+  /** Teardown beyond releasing the output readers; runs once. */
+  cleanup?: () => void;
+  onStderr?: (text: string) => void;
+  onStdoutLine?: (line: string) => void;
+  /** Runs once the child has exited or been killed. */
+  onSettled?: () => void;
+  /** Extra handle fields; `release` defaults to the start's cleanup. */
+  handle?: (
+    child: ServerChildProcess,
+  ) => Partial<Pick<ServerHandle, "container" | "sourceStartOptions" | "release">>;
+  /** Runs once the server is registered; a throw fails the start. */
+  afterRegister?: (started: {
+    child: ServerChildProcess;
+    listening: ServerListening;
+    url: string;
+    authToken: string;
+    serverVersion: WorkspaceServerVersionInfo;
+    spawnedAt: number;
+  }) => Promise<void>;
+};
+
+/** A start that failed after its sidecar was spawned; the child is already gone. */
+class WorkspaceServerStartFailure extends Error {
+  constructor(
+    readonly error: unknown,
+    readonly stage: WorkspaceServerStartupStage,
+    readonly stderrTail: string,
+    readonly exitCode: number | null,
+    readonly signal: NodeJS.Signals | null,
+  ) {
+    super(toErrorMessage(error));
+    this.name = "WorkspaceServerStartFailure";
+  }
+}
+
+/** Spawn failures surface as `error` events whose syscall is `spawn <command>`. */
+function isSpawnFailure(error: unknown): error is NodeJS.ErrnoException {
+  const syscall = (error as NodeJS.ErrnoException | null)?.syscall;
+  return typeof syscall === "string" && syscall.startsWith("spawn");
+}
+
+function buildStartupDiagnostics(input: {
+  workspaceId: string;
+  stage: WorkspaceServerStartupStage;
+  error: unknown;
+  launch: ServerLaunch;
+  stderrTail: string;
+  exitCode: number | null;
+  signal: NodeJS.Signals | null;
+  failedAt?: Date;
+}): WorkspaceServerStartupDiagnostics {
+  const spawnFailed = isSpawnFailure(input.error);
+  const code = spawnFailed ? (input.error as NodeJS.ErrnoException).code : undefined;
+  return {
+    workspaceId: input.workspaceId,
+    stage: spawnFailed ? "spawn" : input.stage,
+    message: toErrorMessage(input.error),
+    ...input.launch,
+    launcherFound: !(code === "ENOENT" || code === "EACCES"),
+    stderrTail: input.stderrTail,
+    exitCode: input.exitCode,
+    signal: input.signal,
+    failedAt: (input.failedAt ?? new Date()).toISOString(),
+  };
+}
+
 async function fetchWithTimeout(
   fetchImpl: typeof fetch,
   url: string,
   timeoutMs: number,
+  init: RequestInit = {},
 ): Promise<Response> {
   const controller = new AbortController();
   const timeout = setTimeout(() => controller.abort(), timeoutMs);
   try {
-    return await fetchImpl(url, { signal: controller.signal });
+    return await fetchImpl(url, { ...init, signal: controller.signal });
   } finally {
     clearTimeout(timeout);
   }
@@ -1182,9 +1595,24 @@ function shouldReplaceForMobileH3Request(
 }
 
 function shouldReuseExistingWorkspaceServer(
-  opts: Pick<StartWorkspaceServerOptions, "forceRestart" | "mobileH3">,
+  opts: Pick<
+    StartWorkspaceServerOptions,
+    "forceRestart" | "mobileH3" | "launchProfile" | "container"
+  >,
   existing: ServerHandle,
 ): boolean {
+  // Switching between a container and a local process, or to another image, needs a new server.
+  if (Boolean(opts.container) !== Boolean(existing.container)) {
+    return false;
+  }
+  if (opts.container?.image && opts.container.image !== existing.container?.image) {
+    return false;
+  }
+  // Starting without a profile attaches to whatever is running; naming one
+  // restarts the server unless it was already spawned with the same args and env.
+  if (opts.launchProfile && launchProfileKey(opts.launchProfile) !== existing.launchProfileKey) {
+    return false;
+  }
   return (
     opts.forceRestart !== true && !shouldReplaceForMobileH3Request(opts.mobileH3, existing.mobileH3)
   );
@@ -1193,18 +1621,39 @@ function shouldReuseExistingWorkspaceServer(
 export class ServerManager {
   private readonly servers = new Map<string, ServerHandle>();
   private readonly pendingStarts = new Map<string, PendingServerHandle>();
-  private readonly suppressedExitNotifications = new WeakSet<ServerChildProcess>();
+  private readonly outputReaders = new ServerOutputReaders<ServerChildProcess>();
+  private readonly suppressedExitNotifications = new WeakSet<ManagedServerProcess>();
+  private readonly limitBreaches = new WeakMap<ManagedServerProcess, WorkspaceResourceLimitKind>();
   private readonly startCountsByWorkspace = new Map<string, number>();
+  /** Server each handoff link was registered with, so it can be revoked by token id. */
+  private readonly handoffServers = new Map<string, { serverId: string; expiresAt: number }>();
+  private readonly crashCountsByWorkspace = new Map<string, number>();
   private readonly lastExitByWorkspace = new Map<
     string,
     ServerManagerDiagnostics["workspaces"][number]["lastChildExit"]
   >();
-
-  constructor(private readonly options: ServerManagerOptions = {}) {}
+  private readonly lastVersionByWorkspace = new Map<string, WorkspaceServerVersionInfo>();
+  /** The last failed local start per workspace, cleared once one succeeds. */
+  private readonly startupFailures = new Map<string, WorkspaceServerStartupDiagnostics>();
+  private readonly processStats: ProcessStatsSampler;
+  private readonly socketProxy: WorkspaceSocketProxy;
+  private readonly sleepClock: SleepClock;
+
+  constructor(private readonly options: ServerManagerOptions = {}) {
+    this.processStats = new ProcessStatsSampler(options.readProcessUsage);
+    this.sleepClock = options.sleepClock ?? new SleepClock();
+    this.socketProxy = new WorkspaceSocketProxy({
+      resolveUpstream: (workspaceId) => {
+        const handle = this.servers.get(workspaceId);
+        return handle ? { url: handle.url, authToken: handle.authToken } : null;
+      },
+      onConnectionStateChanged: options.onProxyConnectionStateChanged,
+    });
+  }
 
   private finishWorkspaceServerExit(
     workspaceId: string,
-    child: ServerChildProcess,
+    child: ManagedServerProcess,
     url: string | null,
     cleanup: () => void,
     code: number | null,
@@ -1219,20 +1668,69 @@ export class ServerManager {
     if (handle?.child === child) {
       this.servers.delete(workspaceId);
     }
+    this.forgetSidecar(workspaceId, child.pid);
+    this.forgetSpawnedServer(workspaceId, child.pid);
+    if (child.pid) {
+      this.processStats.forget(child.pid);
+    }
     if (!this.suppressedExitNotifications.has(child)) {
+      this.crashCountsByWorkspace.set(
+        workspaceId,
+        (this.crashCountsByWorkspace.get(workspaceId) ?? 0) + 1,
+      );
       this.lastExitByWorkspace.set(workspaceId, {
         url: stripUrlSecrets(url),
         code,
         signal,
         exitedAt: new Date().toISOString(),
       });
+      const limitExceeded = this.limitBreaches.get(child);
       this.options.onWorkspaceServerExited?.({
         workspaceId,
         url,
         code,
         signal,
+        ...(limitExceeded ? { limitExceeded } : {}),
+      });
+    }
+  }
+
+  /** Applies niceness now and watches memory/lifetime; returns a function that stops watching. */
+  private enforceResourceLimits(
+    workspaceId: string,
+    child: ManagedServerProcess,
+    startedAt: number,
+    limits: WorkspaceResourceLimits | undefined,
+  ): () => void {
+    const pid = child.pid;
+    if (!limits || pid === undefined) {
+      return () => {};
+    }
+    if (limits.niceness !== undefined && !applyProcessNiceness(pid, limits.niceness)) {
+      logServerManagerEvent("workspace server niceness not applied", {
+        workspaceId,
+        pid,
+        niceness: limits.niceness,
       });
     }
+    if (limits.maxMemoryMb === undefined && limits.maxLifetimeMinutes === undefined) {
+      return () => {};
+    }
+    return watchResourceLimits({
+      pid,
+      startedAt,
+      limits,
+      readUsage: this.options.readProcessUsage ?? readProcessUsage,
+      onExceeded: (breach) => {
+        logServerManagerEvent("workspace server resource limit exceeded", {
+          workspaceId,
+          pid,
+          ...breach,
+        });
+        this.limitBreaches.set(child, breach.limit);
+        void gracefulKill(child);
+      },
+    });
   }
 
   async getWorkspaceServerStatus(workspaceId: string): Promise<WorkspaceServerStatus> {
@@ -1258,233 +1756,618 @@ export class ServerManager {
       return { workspaceId, running: false, url: handle.url, reason: "exited" };
     }
 
-    try {
-      const response = await fetchWithTimeout(
-        this.options.fetch ?? fetch,
-        toHttpServerRequestUrl(handle.url, "/cowork/health"),
-        SERVER_HEALTH_TIMEOUT_MS,
-      );
-      if (!response.ok) {
-        return {
-          workspaceId,
-          running: false,
-          url: handle.url,
-          reason: "health_failed",
-          error: `HTTP ${response.status}`,
-        };
+    const probe = async (): Promise<string | null> => {
+      try {
+        const response = await fetchWithTimeout(
+          this.options.fetch ?? fetch,
+          toHttpServerRequestUrl(handle.url, "/cowork/health"),
+          SERVER_HEALTH_TIMEOUT_MS,
+        );
+        return response.ok ? null : `HTTP ${response.status}`;
+      } catch (error) {
+        return toErrorMessage(error);
       }
-      return { workspaceId, running: true, url: handle.url, reason: "running" };
-    } catch (error) {
+    };
+    let failure = await probe();
+    // A sidecar that slept with the machine can take a moment to answer again;
+    // keep re-handshaking for a while after a wake before reporting it dead.
+    while (
+      failure !== null &&
+      this.sleepClock.wokeRecently(POST_WAKE_HEALTH_GRACE_MS) &&
+      this.servers.get(workspaceId) === handle &&
+      handle.child.exitCode === null &&
+      handle.child.signalCode === null
+    ) {
+      await new Promise((resolve) => setTimeout(resolve, SERVER_HEALTH_POLL_INTERVAL_MS));
+      failure = await probe();
+    }
+    if (failure !== null) {
       return {
         workspaceId,
         running: false,
         url: handle.url,
         reason: "health_failed",
-        error: toErrorMessage(error),
+        error: failure,
       };
     }
+    return { workspaceId, running: true, url: handle.url, reason: "running" };
   }
 
-  async startWorkspaceServer(
-    opts: StartWorkspaceServerOptions,
-  ): Promise<{ url: string; mobileH3: ServerListening["mobileH3"] }> {
-    const { workspaceId, workspacePath, yolo } = opts;
+  /** Checks the bundled sidecar and helper binaries against their shipped digests. */
+  async verifyInstallation(): Promise<InstallationIntegrityReport> {
+    return await verifyInstallation({
+      searchDirs: getSidecarSearchDirs(),
+      isPackaged: app.isPackaged,
+      explicitSidecarPath: process.env.COWORK_DESKTOP_SIDECAR_PATH,
+    });
+  }
 
+  getServerVersion(workspaceId: string): WorkspaceServerVersionInfo {
     assertSafeId(workspaceId, "workspaceId");
-    await assertWorkspaceDirectory(workspacePath);
-    const startedAt = Date.now();
-    const productAnalyticsState =
-      opts.productAnalyticsState ?? this.options.getProductAnalyticsState?.() ?? null;
-
-    const existing = this.servers.get(workspaceId);
-    if (existing) {
-      if (existing.child.exitCode === null && existing.child.signalCode === null) {
-        if (!shouldReuseExistingWorkspaceServer(opts, existing)) {
-          const replacementPending = { child: existing.child, cleanup: existing.cleanup };
-          this.pendingStarts.set(workspaceId, replacementPending);
-          this.servers.delete(workspaceId);
-          this.suppressedExitNotifications.add(existing.child);
-          await gracefulKill(existing.child);
-          if (this.pendingStarts.get(workspaceId) === replacementPending) {
-            this.pendingStarts.delete(workspaceId);
-          }
-          existing.cleanup();
-        } else {
-          captureProductEvent("workspace_server_started", {
-            eventSource: "main",
-            status: "reused",
-            durationMs: Date.now() - startedAt,
-            yoloEnabled: yolo,
-          });
-          return { url: existing.url, mobileH3: existing.mobileH3 };
-        }
-      }
-      if (this.servers.get(workspaceId) === existing) {
-        this.servers.delete(workspaceId);
-        existing.cleanup();
-      }
+    const handle = this.servers.get(workspaceId);
+    if (handle && handle.child.exitCode === null && handle.child.signalCode === null) {
+      return { ...handle.serverVersion, running: true };
     }
-
-    const pending = this.pendingStarts.get(workspaceId);
-    if (pending) {
-      if (pending.child.exitCode === null && pending.child.signalCode === null) {
-        throw new Error("Workspace server startup already in progress");
-      }
-      this.pendingStarts.delete(workspaceId);
-      pending.cleanup();
+    const lastKnown = this.lastVersionByWorkspace.get(workspaceId);
+    if (lastKnown) {
+      return { ...lastKnown, running: false };
     }
-
-    this.startCountsByWorkspace.set(
+    return {
       workspaceId,
-      (this.startCountsByWorkspace.get(workspaceId) ?? 0) + 1,
-    );
+      running: false,
+      version: null,
+      protocolVersion: null,
+      minProtocolVersion: MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+      compatible: null,
+    };
+  }
 
-    const useSource = !app.isPackaged || process.env.COWORK_DESKTOP_USE_SOURCE === "1";
-    const spawnArgs = buildSpawnArgs(workspacePath, yolo, opts.mobileH3 === true);
-    const { repoRoot, sourceEntry } = resolveSourceStartup(useSource);
+  getStartupDiagnostics(workspaceId: string): WorkspaceServerStartupDiagnostics | null {
+    assertSafeId(workspaceId, "workspaceId");
+    return this.startupFailures.get(workspaceId) ?? null;
+  }
 
-    const sidecar = !useSource ? findSidecarLaunchCommand() : null;
-    const builtInDir = !useSource ? resolvePackagedBuiltinDistDir() : null;
-    if (!useSource && !builtInDir) {
-      throw new Error(
-        `Bundled dist directory not found: ${path.join(process.resourcesPath, "dist")}`,
-      );
+  async getServerStats(workspaceId: string): Promise<WorkspaceServerStats> {
+    assertSafeId(workspaceId, "workspaceId");
+    const handle = this.servers.get(workspaceId);
+    const pid = handle?.child.pid;
+    const sample =
+      handle && pid && handle.child.exitCode === null && handle.child.signalCode === null
+        ? await this.processStats.sample(pid, handle.startedAt)
+        : null;
+    if (!handle || !pid || !sample) {
+      return {
+        workspaceId,
+        running: false,
+        pid: null,
+        cpuPercent: null,
+        rssBytes: null,
+        uptimeMs: null,
+        sampledAt: new Date().toISOString(),
+      };
     }
-
-    logServerManagerEvent("workspace server start requested", {
+    return {
       workspaceId,
-      mode: useSource ? "source" : "packaged",
-      workspacePath,
-      yolo,
-    });
+      running: true,
+      pid,
+      cpuPercent: sample.cpuPercent,
+      rssBytes: sample.rssBytes,
+      uptimeMs: Math.max(0, sample.sampledAt - handle.startedAt),
+      sampledAt: new Date(sample.sampledAt).toISOString(),
+    };
+  }
 
-    await ensureWindowsSandboxReady(workspacePath);
+  /** Start attempts and exits nobody asked for, counted since this app instance launched. */
+  getStabilityStats(workspaceId: string): { starts: number; crashes: number } {
+    return {
+      starts: this.startCountsByWorkspace.get(workspaceId) ?? 0,
+      crashes: this.crashCountsByWorkspace.get(workspaceId) ?? 0,
+    };
+  }
 
-    const attemptCount = getSourceStartupAttemptCount(useSource);
-    let previousError: unknown = null;
-    const outputMirror = shouldMirrorServerOutput() ? createServerOutputMirror() : null;
+  /** Samples every running workspace server; servers that vanish mid-sample are omitted. */
+  async getAllServerStats(): Promise<WorkspaceServerStats[]> {
+    const stats = await Promise.all(
+      [...this.servers.keys()].map((workspaceId) => this.getServerStats(workspaceId)),
+    );
+    return stats.filter((entry) => entry.running);
+  }
 
-    for (let attempt = 1; attempt <= attemptCount; attempt += 1) {
-      const serverEnv = buildServerEnv(opts.featureFlags, {
-        includeBundledFoundationModelsSdk: !useSource,
-        includeBundledWindowsAiElectron: !useSource,
-        rotateMobileH3Tls: opts.rotateMobileH3Tls === true,
-        privacyTelemetrySettings: opts.privacyTelemetrySettings,
-        productAnalyticsState,
+  private async rememberSidecar(entry: SidecarRegistryEntry): Promise<void> {
+    try {
+      await this.options.sidecarAdoption?.registry.record(entry);
+    } catch (error) {
+      logServerManagerEvent("workspace server registry write failed", {
+        workspaceId: entry.workspaceId,
+        error: toErrorMessage(error),
       });
-      const sourceEnvForAttempt = useSource ? buildSourceEnvForAttempt(serverEnv, attempt) : null;
-      const cleanup = sourceEnvForAttempt?.cleanup ?? (() => {});
-
-      let child: ServerChildProcess;
-      let spawnDescription: string;
-      if (useSource) {
-        if (!sourceEntry || !repoRoot || !sourceEnvForAttempt) {
-          throw new Error("Source server startup configuration is incomplete.");
-        }
-        child = spawn("bun", [sourceEntry, ...spawnArgs], {
-          cwd: repoRoot,
-          stdio: ["ignore", "pipe", "pipe"],
-          env: sourceEnvForAttempt.env,
-        });
-        spawnDescription = "bun";
-      } else {
-        if (!sidecar || !builtInDir) {
-          throw new Error("Packaged server startup configuration is incomplete.");
-        }
-        child = spawn(sidecar.command, [...sidecar.args, ...spawnArgs], {
-          cwd: process.resourcesPath,
-          stdio: ["ignore", "pipe", "pipe"],
-          env: {
-            ...serverEnv,
-            COWORK_BUILTIN_DIR: builtInDir,
-            COWORK_DESKTOP_BUNDLE: "1",
-          },
-        });
-        spawnDescription = `${path.basename(sidecar.command)} ${sidecar.args.join(" ")}`.trim();
-      }
+    }
+  }
 
-      logServerManagerEvent("workspace server spawn attempt", {
+  private recordSpawnedServer(
+    workspaceId: string,
+    child: ManagedServerProcess,
+    startedAt: number,
+  ): void {
+    const ledger = this.options.spawnLedger;
+    const pid = child.pid;
+    if (!ledger || pid === undefined) {
+      return;
+    }
+    ledger.record({ pid, workspaceId, startedAt }).catch((error) => {
+      logServerManagerEvent("spawned server ledger write failed", {
         workspaceId,
-        attempt,
-        attemptCount,
-        spawn: spawnDescription,
+        error: toErrorMessage(error),
       });
+    });
+    child.once("exit", () => this.forgetSpawnedServer(workspaceId, pid));
+  }
 
-      let cleaned = false;
-      const cleanupOnce = () => {
-        if (cleaned) {
-          return;
-        }
-        cleaned = true;
-        cleanup();
-      };
-
-      this.pendingStarts.set(workspaceId, { child, cleanup: cleanupOnce });
+  private forgetSpawnedServer(workspaceId: string, pid: number | undefined): void {
+    const ledger = this.options.spawnLedger;
+    if (!ledger || pid === undefined) {
+      return;
+    }
+    ledger.remove([pid]).catch((error) => {
+      logServerManagerEvent("spawned server ledger write failed", {
+        workspaceId,
+        error: toErrorMessage(error),
+      });
+    });
+  }
 
-      let stderrTail = "";
-      child.stderr.on("data", (chunk) => {
-        const text = chunk.toString();
-        stderrTail += text;
-        if (stderrTail.length > STDERR_TAIL_LIMIT) {
-          stderrTail = stderrTail.slice(-STDERR_TAIL_LIMIT);
-        }
-        outputMirror?.writeChunk("stderr", text);
-        const sidecarDiagnosticPreview = outputMirror ? summarizeLogChunk(text) : "";
-        logServerManagerEvent("workspace server stderr emitted", {
-          workspaceId,
-          bytes: Buffer.byteLength(text),
-          bunCrash: isLikelyBunSegfault(text),
-          ...(sidecarDiagnosticPreview ? { sidecarDiagnosticPreview } : {}),
+  private forgetSidecar(workspaceId: string, pid: number | undefined): void {
+    const registry = this.options.sidecarAdoption?.registry;
+    if (!registry || pid === undefined) {
+      return;
+    }
+    registry.remove(workspaceId, pid).catch((error) => {
+      logServerManagerEvent("workspace server registry write failed", {
+        workspaceId,
+        error: toErrorMessage(error),
+      });
+    });
+  }
+
+  /**
+   * Confirms a registered sidecar is still the process we launched (pid and
+   * cwd echoed back by its health endpoint) and speaks a compatible protocol.
+   * `owned` is true once the pid is confirmed, so mismatches can be stopped
+   * rather than leaked.
+   */
+  private async verifyRegisteredSidecar(
+    entry: SidecarRegistryEntry,
+    opts: StartWorkspaceServerOptions,
+    adoption: SidecarAdoptionOptions,
+  ): Promise<
+    | { adoptable: true; serverVersion: WorkspaceServerVersionInfo; authToken: string }
+    | { adoptable: false; owned: boolean; reason: string }
+  > {
+    if (!(adoption.isProcessAlive ?? isProcessAlive)(entry.pid)) {
+      return { adoptable: false, owned: false, reason: "process_exited" };
+    }
+    let handshake: z.infer<typeof sidecarHandshakeSchema>;
+    try {
+      const response = await fetchWithTimeout(
+        this.options.fetch ?? fetch,
+        toHttpServerRequestUrl(entry.url, "/cowork/health"),
+        SERVER_HEALTH_TIMEOUT_MS,
+      );
+      if (!response.ok) {
+        return { adoptable: false, owned: false, reason: `health_http_${response.status}` };
+      }
+      handshake = sidecarHandshakeSchema.parse(await response.json());
+    } catch {
+      return { adoptable: false, owned: false, reason: "health_unreachable" };
+    }
+    if (handshake.pid !== entry.pid) {
+      return { adoptable: false, owned: false, reason: "pid_mismatch" };
+    }
+    if (!entry.authToken) {
+      // Registered before servers required an auth token; we cannot hand one to clients.
+      return { adoptable: false, owned: true, reason: "auth_token_missing" };
+    }
+    if (handshake.cwd !== entry.cwd || entry.workspacePath !== path.resolve(opts.workspacePath)) {
+      return { adoptable: false, owned: true, reason: "workspace_mismatch" };
+    }
+    if (entry.yolo !== opts.yolo) {
+      return { adoptable: false, owned: true, reason: "yolo_mismatch" };
+    }
+    const serverVersion = resolveServerVersionInfo(opts.workspaceId, handshake, true);
+    if (!serverVersion.compatible) {
+      return { adoptable: false, owned: true, reason: "protocol_incompatible" };
+    }
+    return { adoptable: true, serverVersion, authToken: entry.authToken };
+  }
+
+  private async resolveSidecarLaunchCommand(): Promise<SidecarLaunchCommand> {
+    // An explicit sidecar path is a developer override and beats installed updates.
+    const explicitOverride = Boolean(process.env.COWORK_DESKTOP_SIDECAR_PATH);
+    if (!explicitOverride && this.options.sidecarUpdates) {
+      const updated = await this.options.sidecarUpdates
+        .prepareLaunch()
+        .then(async (launch) => {
+          if (launch) {
+            await verifySidecarLaunchCommand(launch, { requireDigests: true });
+          }
+          return launch;
+        })
+        .catch((error) => {
+          logServerManagerEvent("sidecar update launch failed", { error: String(error) });
+          return null;
         });
+      if (updated) {
+        return updated;
+      }
+    }
+    const bundled = findSidecarLaunchCommand();
+    // Unpackaged builds and developer overrides may run a sidecar without digests.
+    await verifySidecarLaunchCommand(bundled, {
+      requireDigests: app.isPackaged && !explicitOverride,
+    });
+    return bundled;
+  }
+
+  private async tryAdoptWorkspaceServer(
+    opts: StartWorkspaceServerOptions,
+  ): Promise<StartedWorkspaceServer | null> {
+    const adoption = this.options.sidecarAdoption;
+    // The registry does not record launch profiles, so a profiled start always spawns.
+    if (!adoption || opts.forceRestart === true || opts.mobileH3 === true || opts.launchProfile) {
+      return null;
+    }
+    const { workspaceId } = opts;
+    const entry = await adoption.registry.get(workspaceId).catch(() => null);
+    if (!entry) {
+      return null;
+    }
+
+    const verdict = await this.verifyRegisteredSidecar(entry, opts, adoption);
+    if (!verdict.adoptable) {
+      logServerManagerEvent("workspace server adoption skipped", {
+        workspaceId,
+        pid: entry.pid,
+        fromAppVersion: entry.appVersion,
+        reason: verdict.reason,
+      });
+      if (verdict.owned) {
+        const stale = new AdoptedSidecarProcess(entry.pid, adoption);
+        await gracefulKill(stale);
+        stale.release();
+      }
+      this.forgetSidecar(workspaceId, entry.pid);
+      return null;
+    }
+
+    const child = new AdoptedSidecarProcess(entry.pid, adoption);
+    const startedAt = Date.parse(entry.startedAt) || Date.now();
+    const stopLimitWatch = this.enforceResourceLimits(
+      workspaceId,
+      child,
+      startedAt,
+      opts.resourceLimits,
+    );
+    const cleanup = () => {
+      stopLimitWatch();
+      child.release();
+    };
+    this.lastVersionByWorkspace.set(workspaceId, verdict.serverVersion);
+    this.servers.set(workspaceId, {
+      child,
+      url: entry.url,
+      authToken: verdict.authToken,
+      mobileH3: null,
+      serverVersion: verdict.serverVersion,
+      startedAt,
+      launchProfileKey: null,
+      release: cleanup,
+      cleanup,
+    });
+    child.once("exit", (code: number | null, signal: NodeJS.Signals | null) => {
+      this.finishWorkspaceServerExit(workspaceId, child, entry.url, cleanup, code, signal);
+    });
+    logServerManagerEvent("workspace server adopted", {
+      workspaceId,
+      pid: entry.pid,
+      fromAppVersion: entry.appVersion,
+      version: verdict.serverVersion.version,
+      protocolVersion: verdict.serverVersion.protocolVersion,
+    });
+    return { url: entry.url, authToken: verdict.authToken, mobileH3: null };
+  }
+
+  async startWorkspaceServer(
+    opts: StartWorkspaceServerOptions,
+  ): Promise<StartedWorkspaceServer> {
+    const releaseAppSuspension = this.options.preventAppSuspension?.();
+    try {
+      return await this.startWorkspaceServerUnguarded(opts);
+    } finally {
+      releaseAppSuspension?.();
+    }
+  }
+
+  private async startWorkspaceServerUnguarded(
+    opts: StartWorkspaceServerOptions,
+  ): Promise<StartedWorkspaceServer> {
+    const { workspaceId, workspacePath, yolo } = opts;
+
+    assertSafeId(workspaceId, "workspaceId");
+    const remoteTarget = parseSshWorkspaceUrl(workspacePath);
+    if (!remoteTarget) {
+      await assertWorkspaceDirectory(workspacePath);
+    }
+    const startedAt = Date.now();
+    const startupTimeoutSeconds = opts.resourceLimits?.startupTimeoutSeconds;
+    const startupTimeoutMs =
+      startupTimeoutSeconds !== undefined
+        ? startupTimeoutSeconds * 1000
+        : getServerStartupTimeoutMs();
+    const productAnalyticsState =
+      opts.productAnalyticsState ?? this.options.getProductAnalyticsState?.() ?? null;
+
+    const existing = this.servers.get(workspaceId);
+    if (existing) {
+      if (existing.child.exitCode === null && existing.child.signalCode === null) {
+        if (!shouldReuseExistingWorkspaceServer(opts, existing)) {
+          const replacementPending = { child: existing.child, cleanup: existing.cleanup };
+          this.pendingStarts.set(workspaceId, replacementPending);
+          this.servers.delete(workspaceId);
+          this.suppressedExitNotifications.add(existing.child);
+          await gracefulKill(existing.child);
+          if (this.pendingStarts.get(workspaceId) === replacementPending) {
+            this.pendingStarts.delete(workspaceId);
+          }
+          existing.cleanup();
+        } else {
+          captureProductEvent("workspace_server_started", {
+            eventSource: "main",
+            status: "reused",
+            durationMs: Date.now() - startedAt,
+            yoloEnabled: yolo,
+          });
+          return { url: existing.url, authToken: existing.authToken, mobileH3: existing.mobileH3 };
+        }
+      }
+      if (this.servers.get(workspaceId) === existing) {
+        this.servers.delete(workspaceId);
+        existing.cleanup();
+      }
+    }
+
+    const pending = this.pendingStarts.get(workspaceId);
+    if (pending) {
+      if (pending.child.exitCode === null && pending.child.signalCode === null) {
+        throw new Error("Workspace server startup already in progress");
+      }
+      this.pendingStarts.delete(workspaceId);
+      pending.cleanup();
+    }
+
+    if (remoteTarget) {
+      return await this.startRemoteWorkspaceServer(opts, remoteTarget, startedAt, startupTimeoutMs);
+    }
+    if (opts.container) {
+      return await this.startContainerWorkspaceServer(
+        opts,
+        opts.container,
+        startedAt,
+        startupTimeoutMs,
+      );
+    }
+
+    const adopted = await this.tryAdoptWorkspaceServer(opts);
+    if (adopted) {
+      captureProductEvent("workspace_server_started", {
+        eventSource: "main",
+        status: "adopted",
+        durationMs: Date.now() - startedAt,
+        yoloEnabled: yolo,
       });
+      return adopted;
+    }
+
+    if (yolo) {
+      await this.options.beforeYoloStart?.(workspaceId);
+    }
+
+    this.startCountsByWorkspace.set(
+      workspaceId,
+      (this.startCountsByWorkspace.get(workspaceId) ?? 0) + 1,
+    );
+
+    const useSource = isSourceServerMode();
+    const spawnArgs = buildSpawnArgs(
+      workspacePath,
+      yolo,
+      opts.mobileH3 === true,
+      opts.additionalRoots,
+      opts.launchProfile?.args,
+    );
+    const { repoRoot, sourceEntry } = resolveSourceStartup(useSource);
+
+    const sidecar = !useSource ? await this.resolveSidecarLaunchCommand() : null;
+    const builtInDir = !useSource ? resolvePackagedBuiltinDistDir() : null;
+    if (!useSource && !builtInDir) {
+      throw new Error(
+        `Bundled dist directory not found: ${path.join(process.resourcesPath, "dist")}`,
+      );
+    }
+
+    logServerManagerEvent("workspace server start requested", {
+      workspaceId,
+      mode: useSource ? "source" : "packaged",
+      workspacePath,
+      yolo,
+      ...(opts.launchProfile ? { launchProfile: opts.launchProfile.name } : {}),
+    });
+
+    await ensureWindowsSandboxReady(workspacePath);
+
+    const attemptCount = getSourceStartupAttemptCount(useSource);
+    let previousError: unknown = null;
+    const networkEnv = (await this.options.getNetworkEnv?.()) ?? {};
+    const outputMirror = shouldMirrorServerOutput() ? createServerOutputMirror() : null;
+    // Adoptable sidecars get their own process group so they survive the app
+    // exiting; mobile H3 servers hold secrets we never persist, so they don't.
+    const adoptable = Boolean(this.options.sidecarAdoption) && opts.mobileH3 !== true;
+    const detached = adoptable && process.platform !== "win32";
+
+    let launch: ServerLaunch;
+    let spawnDescription: string;
+    let packagedEnv: Record<string, string> = {};
+    if (useSource) {
+      if (!sourceEntry || !repoRoot) {
+        throw new Error("Source server startup configuration is incomplete.");
+      }
+      launch = {
+        mode: "source",
+        command: "bun",
+        args: [sourceEntry, ...spawnArgs],
+        cwd: repoRoot,
+      };
+      spawnDescription = "bun";
+    } else {
+      if (!sidecar || !builtInDir) {
+        throw new Error("Packaged server startup configuration is incomplete.");
+      }
+      launch = {
+        mode: "packaged",
+        command: sidecar.command,
+        args: [...sidecar.args, ...spawnArgs],
+        cwd: process.resourcesPath,
+      };
+      spawnDescription = `${path.basename(sidecar.command)} ${sidecar.args.join(" ")}`.trim();
+      packagedEnv = { COWORK_BUILTIN_DIR: builtInDir, COWORK_DESKTOP_BUNDLE: "1" };
+    }
 
+    for (let attempt = 1; attempt <= attemptCount; attempt += 1) {
+      let cleanup = () => {};
+      let stopLimitWatch = () => {};
       try {
-        const listening = await waitForServerListening(child, {
-          onCoworkRuntimeBootstrapProgress: opts.onCoworkRuntimeBootstrapProgress,
+        return await this.runWorkspaceServerStart(opts, startedAt, startupTimeoutMs, {
+          logLabel: "workspace server",
+          launch: (tokenEnv) => {
+            const serverEnv = {
+              ...buildServerEnv(opts.featureFlags, {
+                adoptable,
+                includeBundledFoundationModelsSdk: !useSource,
+                includeBundledWindowsAiElectron: !useSource,
+                rotateMobileH3Tls: opts.rotateMobileH3Tls === true,
+                privacyTelemetrySettings: opts.privacyTelemetrySettings,
+                productAnalyticsState,
+              }),
+              ...networkEnv,
+              ...opts.launchProfile?.env,
+              ...(opts.pathPolicy ? { AGENT_PATH_POLICY: opts.pathPolicy } : {}),
+              ...tokenEnv,
+            };
+            let env: NodeJS.ProcessEnv = { ...serverEnv, ...packagedEnv };
+            if (useSource) {
+              const sourceEnvForAttempt = buildSourceEnvForAttempt(serverEnv, attempt);
+              cleanup = sourceEnvForAttempt.cleanup;
+              env = sourceEnvForAttempt.env;
+            }
+            return spawn(launch.command, launch.args, {
+              cwd: launch.cwd,
+              detached,
+              stdio: ["ignore", "pipe", "pipe"],
+              env,
+            });
+          },
+          onSpawned: (spawned, spawnedAt) => {
+            this.recordSpawnedServer(workspaceId, spawned, spawnedAt);
+            logServerManagerEvent("workspace server spawn attempt", {
+              workspaceId,
+              attempt,
+              attemptCount,
+              spawn: spawnDescription,
+            });
+          },
+          cleanup: () => {
+            stopLimitWatch();
+            cleanup();
+          },
+          onStderr: (text) => {
+            outputMirror?.writeChunk("stderr", text);
+            const sidecarDiagnosticPreview = outputMirror ? summarizeLogChunk(text) : "";
+            logServerManagerEvent("workspace server stderr emitted", {
+              workspaceId,
+              bytes: Buffer.byteLength(text),
+              bunCrash: isLikelyBunSegfault(text),
+              ...(sidecarDiagnosticPreview ? { sidecarDiagnosticPreview } : {}),
+            });
+          },
           onStdoutLine: outputMirror
             ? (line) => {
                 outputMirror.writeLine("stdout", line);
               }
             : undefined,
+          onSettled: () => outputMirror?.flush(),
+          handle: (spawned) => ({
+            ...(useSource ? { sourceStartOptions: opts } : {}),
+            release: () => {
+              stopLimitWatch();
+              this.outputReaders.release(spawned);
+              spawned.unref();
+            },
+          }),
+          afterRegister: async (started) => {
+            stopLimitWatch = this.enforceResourceLimits(
+              workspaceId,
+              started.child,
+              started.spawnedAt,
+              opts.resourceLimits,
+            );
+            if (adoptable && !started.listening.mobileH3 && started.child.pid !== undefined) {
+              await this.rememberSidecar({
+                workspaceId,
+                workspacePath: path.resolve(workspacePath),
+                cwd: started.listening.cwd,
+                pid: started.child.pid,
+                url: started.url,
+                authToken: started.authToken,
+                yolo,
+                version: started.serverVersion.version,
+                protocolVersion: started.serverVersion.protocolVersion,
+                appVersion: resolveAppRelease(),
+                startedAt: new Date().toISOString(),
+              });
+            }
+          },
         });
-        const url = appendBrowserAccessToken(listening.url, listening.browserAccessToken);
-        logServerManagerEvent("workspace server listening", {
-          workspaceId,
-          url: listening.url,
-        });
-        const pendingHandle = this.pendingStarts.get(workspaceId);
-        if (pendingHandle?.child === child) {
-          this.pendingStarts.delete(workspaceId);
+      } catch (caught) {
+        if (!(caught instanceof WorkspaceServerStartFailure)) {
+          throw caught;
         }
+        const failure = caught;
+        const { error, stderrTail } = failure;
+        const diagnostics = () =>
+          buildStartupDiagnostics({
+            workspaceId,
+            stage: failure.stage,
+            error,
+            launch,
+            stderrTail,
+            exitCode: failure.exitCode,
+            signal: failure.signal,
+          });
 
-        this.servers.set(workspaceId, {
-          child,
-          url,
-          mobileH3: listening.mobileH3 ?? null,
-          cleanup: cleanupOnce,
-        });
-
-        captureProductEvent("workspace_server_started", {
-          eventSource: "main",
-          status: "started",
-          durationMs: Date.now() - startedAt,
-          yoloEnabled: yolo,
-        });
-
-        child.once("exit", (code, signal) => {
-          outputMirror?.flush();
-          this.finishWorkspaceServerExit(workspaceId, child, url, cleanupOnce, code, signal);
-        });
-
-        return { url, mobileH3: listening.mobileH3 ?? null };
-      } catch (error) {
-        await gracefulKill(child);
-        outputMirror?.flush();
-        const pendingHandle = this.pendingStarts.get(workspaceId);
-        if (pendingHandle?.child === child) {
-          this.pendingStarts.delete(workspaceId);
+        if (error instanceof ServerUpgradeRequiredError) {
+          logServerManagerEvent("workspace server rejected as incompatible", {
+            workspaceId,
+            version: error.details.version,
+            protocolVersion: error.details.protocolVersion,
+            minProtocolVersion: error.details.minProtocolVersion,
+          });
+          captureProductEvent("workspace_server_failed", {
+            eventSource: "main",
+            status: "failed",
+            errorCategory: "upgrade_required",
+            durationMs: Date.now() - startedAt,
+          });
+          throw error;
         }
-        cleanupOnce();
 
         const shouldRetry =
           useSource &&
@@ -1506,13 +2389,14 @@ export class ServerManager {
           continue;
         }
 
+        captureWorkspaceServerStartupFailure({
+          error,
+          workspaceId,
+          mode: useSource ? "source" : "packaged",
+          attempt,
+        });
+
         if (isLikelyBunSegfault(stderrTail)) {
-          captureWorkspaceServerStartupFailure({
-            error,
-            workspaceId,
-            mode: useSource ? "source" : "packaged",
-            attempt,
-          });
           logServerManagerEvent("workspace server Bun crash", {
             workspaceId,
             error: toErrorMessage(error),
@@ -1524,43 +2408,566 @@ export class ServerManager {
             errorCategory: "bun_crash",
             durationMs: Date.now() - startedAt,
           });
+          this.startupFailures.set(workspaceId, diagnostics());
           throw new Error(
             withStderrTail(
-              `Cowork server crashed inside Bun while starting: ${toErrorMessage(error)}. ` +
-                "Try upgrading Bun and retrying.",
+              `${SERVER_STARTUP_FAILED_ERROR_CODE}: Cowork server crashed inside Bun while ` +
+                `starting: ${toErrorMessage(error)}. Try upgrading Bun and retrying.`,
               stderrTail,
             ),
           );
         }
 
-        captureWorkspaceServerStartupFailure({
-          error,
+        this.startupFailures.set(workspaceId, diagnostics());
+        throw this.workspaceServerStartFailed(
           workspaceId,
-          mode: useSource ? "source" : "packaged",
-          attempt,
-        });
-        logServerManagerEvent("workspace server start failed", {
-          workspaceId,
-          error: toErrorMessage(error),
-          stderrBytes: Buffer.byteLength(stderrTail),
-        });
-        captureProductEvent("workspace_server_failed", {
-          eventSource: "main",
-          status: "failed",
-          errorCategory: "startup_failed",
-          durationMs: Date.now() - startedAt,
-        });
-        const message = withStderrTail(toErrorMessage(error), stderrTail);
-        throw error instanceof Error ? new Error(message, { cause: error }) : new Error(message);
+          { logLabel: "workspace server" },
+          failure,
+          startedAt,
+          `${SERVER_STARTUP_FAILED_ERROR_CODE}: `,
+        );
       }
     }
 
     throw (previousError as Error) ?? new Error("Failed to start workspace server");
   }
 
+  /**
+   * The start sequence every transport shares, from spawning the sidecar to
+   * registering it: generates its tokens, tracks it as pending, keeps a stderr
+   * tail, waits for it to listen and echo its auth token, checks its protocol
+   * version, waits for it to answer health checks at the URL
+   * `transport.reachableUrl` maps it to, then registers it and watches for its
+   * exit. On failure the child is killed and cleaned up, and a
+   * `WorkspaceServerStartFailure` says what went wrong.
+   */
+  private async runWorkspaceServerStart(
+    opts: StartWorkspaceServerOptions,
+    startedAt: number,
+    startupTimeoutMs: number,
+    transport: WorkspaceServerTransport,
+  ): Promise<StartedWorkspaceServer> {
+    const { workspaceId, yolo } = opts;
+    const shutdownToken = randomUUID();
+    const authToken = randomBytes(32).toString("hex");
+    const child = transport.launch({
+      [SERVER_SHUTDOWN_TOKEN_ENV]: shutdownToken,
+      [SERVER_AUTH_TOKEN_ENV]: authToken,
+    });
+    const spawnedAt = Date.now();
+    transport.onSpawned?.(child, spawnedAt);
+
+    let cleaned = false;
+    const cleanupOnce = () => {
+      if (cleaned) {
+        return;
+      }
+      cleaned = true;
+      this.outputReaders.release(child);
+      transport.cleanup?.();
+    };
+
+    this.pendingStarts.set(workspaceId, { child, cleanup: cleanupOnce });
+
+    let stderrTail = "";
+    const onStderr = (chunk: Buffer | string) => {
+      const text = chunk.toString();
+      stderrTail += text;
+      if (stderrTail.length > STDERR_TAIL_LIMIT) {
+        stderrTail = stderrTail.slice(-STDERR_TAIL_LIMIT);
+      }
+      transport.onStderr?.(text);
+    };
+    child.stderr.on("data", onStderr);
+    this.outputReaders.track(child, () => child.stderr.off("data", onStderr));
+    // Released readers also close the pipes so nothing keeps buffering output.
+    this.outputReaders.track(child, () => {
+      child.stdout.destroy();
+      child.stderr.destroy();
+    });
+
+    let stage: WorkspaceServerStartupStage = "startup";
+    try {
+      const listening = await waitForServerListening(child, {
+        timeoutMs: startupTimeoutMs,
+        onCoworkRuntimeBootstrapProgress: opts.onCoworkRuntimeBootstrapProgress,
+        trackReader: (stop) => this.outputReaders.track(child, stop),
+        sleepClock: this.sleepClock,
+        onStdoutLine: transport.onStdoutLine,
+      });
+      if (listening.authToken !== authToken) {
+        throw new Error("Workspace server did not echo its auth token; refusing to register it");
+      }
+      const serverVersion = resolveServerVersionInfo(workspaceId, listening, true);
+      this.lastVersionByWorkspace.set(workspaceId, serverVersion);
+      if (!serverVersion.compatible) {
+        throw new ServerUpgradeRequiredError(serverVersion);
+      }
+
+      const reachableUrl = transport.reachableUrl
+        ? await transport.reachableUrl(listening, child)
+        : listening.url;
+      const url = appendBrowserAccessToken(reachableUrl, listening.browserAccessToken);
+      serverShutdownEndpoints.set(child, { url, token: shutdownToken });
+      stage = "health_check";
+      await waitForServerHealthy(child, url, {
+        fetch: this.options.fetch ?? fetch,
+        timeoutMs: Math.max(
+          MIN_SERVER_HEALTH_WAIT_MS,
+          startupTimeoutMs - this.sleepClock.awakeSince(spawnedAt),
+        ),
+        sleepClock: this.sleepClock,
+      });
+      logServerManagerEvent(`${transport.logLabel} listening`, {
+        workspaceId,
+        ...transport.logFields,
+        url: reachableUrl,
+        version: serverVersion.version,
+        protocolVersion: serverVersion.protocolVersion,
+      });
+      const pendingHandle = this.pendingStarts.get(workspaceId);
+      if (pendingHandle?.child === child) {
+        this.pendingStarts.delete(workspaceId);
+      }
+
+      this.servers.set(workspaceId, {
+        child,
+        url,
+        authToken,
+        mobileH3: listening.mobileH3 ?? null,
+        serverVersion,
+        startedAt: spawnedAt,
+        launchProfileKey: launchProfileKey(opts.launchProfile),
+        release: cleanupOnce,
+        cleanup: cleanupOnce,
+        ...transport.handle?.(child),
+      });
+
+      captureProductEvent("workspace_server_started", {
+        eventSource: "main",
+        status: "started",
+        durationMs: Date.now() - startedAt,
+        yoloEnabled: yolo,
+      });
+
+      child.once("exit", (code, signal) => {
+        transport.onSettled?.();
+        this.finishWorkspaceServerExit(workspaceId, child, url, cleanupOnce, code, signal);
+      });
+
+      await transport.afterRegister?.({
+        child,
+        listening,
+        url,
+        authToken,
+        serverVersion,
+        spawnedAt,
+      });
+
+      this.startupFailures.delete(workspaceId);
+      return { url, authToken, mobileH3: listening.mobileH3 ?? null };
+    } catch (error) {
+      // Read before the kill so only an exit the server made on its own is reported.
+      const exitCode = child.exitCode;
+      const signal = child.signalCode;
+      await gracefulKill(child);
+      transport.onSettled?.();
+      const pendingHandle = this.pendingStarts.get(workspaceId);
+      if (pendingHandle?.child === child) {
+        this.pendingStarts.delete(workspaceId);
+      }
+      cleanupOnce();
+      throw new WorkspaceServerStartFailure(error, stage, stderrTail, exitCode, signal);
+    }
+  }
+
+  /**
+   * Logs and counts a failed start and returns the error to throw: the
+   * upgrade error as is, anything else with the sidecar's stderr tail.
+   */
+  private workspaceServerStartFailed(
+    workspaceId: string,
+    transport: Pick<WorkspaceServerTransport, "logLabel" | "logFields">,
+    failure: WorkspaceServerStartFailure,
+    startedAt: number,
+    messagePrefix = "",
+  ): Error {
+    const { error, stderrTail } = failure;
+    const upgradeRequired = error instanceof ServerUpgradeRequiredError;
+    logServerManagerEvent(`${transport.logLabel} start failed`, {
+      workspaceId,
+      ...transport.logFields,
+      error: toErrorMessage(error),
+      stderrBytes: Buffer.byteLength(stderrTail),
+    });
+    captureProductEvent("workspace_server_failed", {
+      eventSource: "main",
+      status: "failed",
+      errorCategory: upgradeRequired ? "upgrade_required" : "startup_failed",
+      durationMs: Date.now() - startedAt,
+    });
+    if (upgradeRequired) {
+      return error;
+    }
+    const message = withStderrTail(`${messagePrefix}${toErrorMessage(error)}`, stderrTail);
+    return error instanceof Error ? new Error(message, { cause: error }) : new Error(message);
+  }
+
+  /**
+   * Starts the sidecar for an `ssh://` workspace on its host and forwards a
+   * local port to it. The handle's process is the ssh session, so stopping it
+   * stops the sidecar and closes the forward; a forward that drops on its own
+   * ends the session, which is then reported like any other server exit.
+   */
+  private async startRemoteWorkspaceServer(
+    opts: StartWorkspaceServerOptions,
+    target: SshWorkspaceTarget,
+    startedAt: number,
+    startupTimeoutMs: number,
+  ): Promise<StartedWorkspaceServer> {
+    const { workspaceId, yolo } = opts;
+    const remote = this.options.remoteWorkspaces;
+    if (!remote) {
+      throw new Error("Remote workspaces are not available in this build.");
+    }
+    if (opts.mobileH3) {
+      throw new Error("The mobile relay is not available for remote workspaces.");
+    }
+
+    this.startCountsByWorkspace.set(
+      workspaceId,
+      (this.startCountsByWorkspace.get(workspaceId) ?? 0) + 1,
+    );
+
+    logServerManagerEvent("remote workspace server start requested", {
+      workspaceId,
+      host: target.host,
+      yolo,
+    });
+
+    // Extra roots are local folders, so the remote sidecar serves only its own path.
+    const spawnArgs = buildSpawnArgs(target.path, yolo, false, [], opts.launchProfile?.args);
+    let tunnel: SshTunnel | null = null;
+    let stopped = false;
+    // Resource limits sample local process usage, so they are not applied to
+    // the ssh session; remote sidecars are never registered for adoption.
+    const transport: WorkspaceServerTransport = {
+      logLabel: "remote workspace server",
+      logFields: { host: target.host },
+      launch: (tokenEnv) =>
+        remote.launchServer(target, {
+          args: spawnArgs,
+          env: {
+            ...opts.launchProfile?.env,
+            ...(opts.pathPolicy ? { AGENT_PATH_POLICY: opts.pathPolicy } : {}),
+            ...tokenEnv,
+          },
+        }),
+      reachableUrl: async (listening, child) => {
+        const opened = await remote.openTunnel(target, listening.url);
+        tunnel = opened;
+        if (stopped) {
+          opened.close();
+          throw new Error("Workspace server was stopped while starting");
+        }
+        void opened.closed.then(() => {
+          if (child.exitCode === null && child.signalCode === null) {
+            child.kill();
+          }
+        });
+        return opened.url;
+      },
+      cleanup: () => {
+        stopped = true;
+        tunnel?.close();
+      },
+    };
+    try {
+      return await this.runWorkspaceServerStart(opts, startedAt, startupTimeoutMs, transport);
+    } catch (error) {
+      if (!(error instanceof WorkspaceServerStartFailure)) {
+        throw error;
+      }
+      throw this.workspaceServerStartFailed(workspaceId, transport, error, startedAt);
+    }
+  }
+
+  /**
+   * Starts the sidecar in a Docker container with the workspace folder
+   * mounted and its port published on a free loopback port. The handle's
+   * process is the attached `docker run`, so stopping it stops the container
+   * and health checks, exits, and crash reporting work as for a local server.
+   * Cleanup removes the container by name in case it outlived the client.
+   */
+  private async startContainerWorkspaceServer(
+    opts: StartWorkspaceServerOptions,
+    container: WorkspaceContainerLaunch,
+    startedAt: number,
+    startupTimeoutMs: number,
+  ): Promise<StartedWorkspaceServer> {
+    const { workspaceId, workspacePath, yolo } = opts;
+    const docker = this.options.containerWorkspaces;
+    if (!docker) {
+      throw new Error("Container workspaces are not available in this build.");
+    }
+    if (opts.mobileH3) {
+      throw new Error("The mobile relay is not available for container workspaces.");
+    }
+    const launch = await resolveContainerLaunch(workspacePath, container);
+    // Policy paths name host folders; the sidecar only sees the mounted copy.
+    const pathPolicy = opts.pathPolicy
+      ? containerPathPolicy(opts.pathPolicy, workspacePath, launch.workspaceFolder)
+      : null;
+    const containerName = containerNameForServer(workspaceId);
+    // A container left behind by a crash would hold the name.
+    await docker.removeContainer(containerName);
+
+    if (yolo) {
+      await this.options.beforeYoloStart?.(workspaceId);
+    }
+
+    this.startCountsByWorkspace.set(
+      workspaceId,
+      (this.startCountsByWorkspace.get(workspaceId) ?? 0) + 1,
+    );
+
+    logServerManagerEvent("container workspace server start requested", {
+      workspaceId,
+      image: launch.image,
+      yolo,
+    });
+
+    const hostPort = await docker.allocateHostPort();
+    // Extra roots are not mounted, so the sidecar serves only the workspace folder.
+    const spawnArgs = [
+      ...buildSpawnArgs(launch.workspaceFolder, yolo, false, [], opts.launchProfile?.args),
+      "--host",
+      "0.0.0.0",
+      "--port",
+      String(CONTAINER_SERVER_PORT),
+    ];
+    // Resource limits sample local process usage, which here is only the
+    // docker client; container sidecars are never registered for adoption.
+    const transport: WorkspaceServerTransport = {
+      logLabel: "container workspace server",
+      logFields: { container: containerName, image: launch.image },
+      launch: (tokenEnv) =>
+        docker.launchServer({
+          containerName,
+          serverId: workspaceId,
+          workspacePath,
+          launch,
+          hostPort,
+          args: spawnArgs,
+          env: {
+            ...opts.launchProfile?.env,
+            ...(pathPolicy ? { AGENT_PATH_POLICY: pathPolicy } : {}),
+            ...tokenEnv,
+          },
+        }),
+      reachableUrl: (listening) => forwardedServerUrl(listening.url, hostPort),
+      cleanup: () => {
+        void docker.removeContainer(containerName);
+      },
+      handle: () => ({ container: { name: containerName, image: launch.image } }),
+    };
+    try {
+      return await this.runWorkspaceServerStart(opts, startedAt, startupTimeoutMs, transport);
+    } catch (error) {
+      if (!(error instanceof WorkspaceServerStartFailure)) {
+        throw error;
+      }
+      throw this.workspaceServerStartFailed(workspaceId, transport, error, startedAt);
+    }
+  }
+
+  /**
+   * Loopback URL that proxies to the workspace's sidecar and rides out its
+   * restarts; see `WorkspaceSocketProxy`.
+   */
+  async getProxyUrl(workspaceId: string): Promise<{ url: string }> {
+    assertSafeId(workspaceId, "workspaceId");
+    if (!this.servers.has(workspaceId) && !this.pendingStarts.has(workspaceId)) {
+      throw new Error("Workspace server is not running");
+    }
+    return { url: await this.socketProxy.getUrl(workspaceId) };
+  }
+
+  /**
+   * Asks a running server to write out its session state and thread journal,
+   * ahead of a planned stop. Servers from before the flush endpoint answer 404
+   * and are left to flush as they shut down.
+   */
+  async flushWorkspaceServer(serverId: string): Promise<void> {
+    assertSafeId(serverId, "serverId");
+    const handle = this.servers.get(serverId);
+    if (!handle) {
+      return;
+    }
+    const response = await fetchWithTimeout(
+      this.options.fetch ?? fetch,
+      toHttpServerRequestUrl(handle.url, "/cowork/flush"),
+      SERVER_FLUSH_TIMEOUT_MS,
+      {
+        method: "POST",
+        headers: {
+          authorization: `Bearer ${handle.authToken}`,
+        },
+      },
+    );
+    if (!response.ok && response.status !== 404) {
+      throw new Error(`Failed to flush workspace server state: HTTP ${response.status}.`);
+    }
+  }
+
+  /**
+   * Mints a handoff token for a thread on a running server, registers it with
+   * that server, and returns the LAN URL another client opens to attach. The
+   * token is signed with the server's auth token, so the server only accepts
+   * links this app minted for it.
+   */
+  async createHandoffLink(
+    serverId: string,
+    opts: { workspaceId: string; threadId: string; access: HandoffAccess; ttlSeconds: number },
+  ): Promise<HandoffLink> {
+    assertSafeId(serverId, "serverId");
+    assertSafeId(opts.threadId, "threadId");
+    const handle = this.servers.get(serverId);
+    if (!handle) {
+      throw new Error("Workspace server is not running");
+    }
+    if (handle.container) {
+      throw new Error("Handoff links are not available for workspaces that run in a container.");
+    }
+    const now = Date.now();
+    for (const [tokenId, registered] of this.handoffServers) {
+      if (registered.expiresAt <= now) {
+        this.handoffServers.delete(tokenId);
+      }
+    }
+    const tokenId = randomUUID();
+    const expiresAt = now + opts.ttlSeconds * 1000;
+    const token = signHandoffToken(
+      { tokenId, threadId: opts.threadId, access: opts.access, expiresAt },
+      handle.authToken,
+    );
+    const response = await fetchWithTimeout(
+      this.options.fetch ?? fetch,
+      toHttpServerRequestUrl(handle.url, "/cowork/handoffs"),
+      SERVER_HANDOFF_TIMEOUT_MS,
+      {
+        method: "POST",
+        headers: {
+          authorization: `Bearer ${handle.authToken}`,
+          "content-type": "application/json",
+        },
+        body: JSON.stringify({ token }),
+      },
+    );
+    if (!response.ok) {
+      // Servers from before handoff links answer 404.
+      throw new Error(
+        response.status === 404
+          ? "This workspace server does not support handoff links. Restart it and try again."
+          : `Failed to register handoff link: HTTP ${response.status}.`,
+      );
+    }
+    const registered = handoffRegistrationResponseSchema.parse(await response.json());
+    this.handoffServers.set(tokenId, { serverId, expiresAt });
+    const host = registered.hostHints[0] ?? "127.0.0.1";
+    const url = new URL(`ws://${host.includes(":") ? `[${host}]` : host}:${registered.port}/ws`);
+    url.searchParams.set(HANDOFF_QUERY_PARAM, token);
+    logServerManagerEvent("handoff link created", {
+      workspaceId: opts.workspaceId,
+      serverId,
+      tokenId,
+      access: opts.access,
+    });
+    return {
+      tokenId,
+      workspaceId: opts.workspaceId,
+      threadId: opts.threadId,
+      access: opts.access,
+      url: url.toString(),
+      expiresAt: new Date(expiresAt).toISOString(),
+    };
+  }
+
+  /** Revokes a handoff link and disconnects clients attached through it. */
+  async revokeHandoff(tokenId: string): Promise<boolean> {
+    assertSafeId(tokenId, "tokenId");
+    const registered = this.handoffServers.get(tokenId);
+    this.handoffServers.delete(tokenId);
+    const handle = registered ? this.servers.get(registered.serverId) : undefined;
+    if (!handle) {
+      return false;
+    }
+    const response = await fetchWithTimeout(
+      this.options.fetch ?? fetch,
+      toHttpServerRequestUrl(handle.url, `/cowork/handoffs/${encodeURIComponent(tokenId)}`),
+      SERVER_HANDOFF_TIMEOUT_MS,
+      {
+        method: "DELETE",
+        headers: {
+          authorization: `Bearer ${handle.authToken}`,
+        },
+      },
+    );
+    if (!response.ok) {
+      throw new Error(`Failed to revoke handoff link: HTTP ${response.status}.`);
+    }
+    return handoffRevocationResponseSchema.parse(await response.json()).removed;
+  }
+
+  /**
+   * Asks the workspace's running server to title `query` with its configured
+   * model. Resolves null when no server is running or the request fails, so
+   * callers can fall back to a local title.
+   */
+  async generateThreadTitle(
+    workspaceId: string,
+    query: string,
+  ): Promise<GeneratedThreadTitle | null> {
+    assertSafeId(workspaceId, "workspaceId");
+    const handle = this.servers.get(workspaceId);
+    if (!handle) {
+      return null;
+    }
+    try {
+      const response = await fetchWithTimeout(
+        this.options.fetch ?? fetch,
+        toHttpServerRequestUrl(handle.url, "/cowork/title"),
+        SERVER_TITLE_TIMEOUT_MS,
+        {
+          method: "POST",
+          headers: {
+            authorization: `Bearer ${handle.authToken}`,
+            "content-type": "application/json",
+          },
+          body: JSON.stringify({ query }),
+        },
+      );
+      if (!response.ok) {
+        // Servers from before the title endpoint answer 404.
+        return null;
+      }
+      return generatedTitleResponseSchema.parse(await response.json());
+    } catch (error) {
+      logServerManagerEvent("thread title generation failed", {
+        workspaceId,
+        error: toErrorMessage(error),
+      });
+      return null;
+    }
+  }
+
   async stopWorkspaceServer(workspaceId: string): Promise<void> {
     assertSafeId(workspaceId, "workspaceId");
+    await this.stopServerProcess(workspaceId);
+    this.socketProxy.closeWorkspace(workspaceId);
+  }
 
+  private async stopServerProcess(workspaceId: string): Promise<void> {
     const pending = this.pendingStarts.get(workspaceId);
     if (pending) {
       this.pendingStarts.delete(workspaceId);
@@ -1582,11 +2989,48 @@ export class ServerManager {
 
   async restartWorkspaceServer(
     opts: StartWorkspaceServerOptions,
-  ): Promise<{ url: string; mobileH3: ServerListening["mobileH3"] }> {
-    await this.stopWorkspaceServer(opts.workspaceId);
+  ): Promise<StartedWorkspaceServer> {
+    assertSafeId(opts.workspaceId, "workspaceId");
+    // Proxied clients stay attached and reconnect once the new server is up.
+    await this.stopServerProcess(opts.workspaceId);
     return await this.startWorkspaceServer(opts);
   }
 
+  /** Ids of running servers started from `src/server` rather than a packaged sidecar. */
+  getSourceServerIds(): string[] {
+    return [...this.servers.entries()]
+      .filter(([, handle]) => handle.sourceStartOptions)
+      .map(([workspaceId]) => workspaceId);
+  }
+
+  /**
+   * Restarts a source-mode server with the options it was started with, so it
+   * picks up edited server code. Returns false when it is no longer running.
+   * A server that fails to come back is reported as exited before the error
+   * is rethrown.
+   */
+  async reloadSourceServer(workspaceId: string): Promise<boolean> {
+    assertSafeId(workspaceId, "workspaceId");
+    const handle = this.servers.get(workspaceId);
+    const opts = handle?.sourceStartOptions;
+    if (!handle || !opts) {
+      return false;
+    }
+    logServerManagerEvent("workspace server reloading after source change", { workspaceId });
+    try {
+      await this.restartWorkspaceServer({ ...opts, rotateMobileH3Tls: false });
+    } catch (error) {
+      this.options.onWorkspaceServerExited?.({
+        workspaceId,
+        url: handle.url,
+        code: null,
+        signal: null,
+      });
+      throw error;
+    }
+    return true;
+  }
+
   async listMobileH3TrustedDevices(workspaceId: string): Promise<MobileRelayTrustedPhoneDevice[]> {
     assertSafeId(workspaceId, "workspaceId");
     const handle = this.servers.get(workspaceId);
@@ -1704,6 +3148,7 @@ export class ServerManager {
   }
 
   async stopAll(): Promise<void> {
+    await this.socketProxy.dispose();
     const entries = [...this.servers.entries()];
     this.servers.clear();
     const pendingEntries = [...this.pendingStarts.entries()];
@@ -1723,6 +3168,72 @@ export class ServerManager {
     ];
 
     await Promise.all(killPromises);
+    this.sweepOutputReaders();
+    await this.options.sidecarAdoption?.registry.flush();
+  }
+
+  /**
+   * Used when quitting to install an update: registered sidecars are left
+   * running for the next app instance to adopt, everything else is stopped as
+   * in `stopAll`.
+   */
+  async handOffAll(): Promise<void> {
+    const registry = this.options.sidecarAdoption?.registry;
+    if (!registry) {
+      await this.stopAll();
+      return;
+    }
+    await this.socketProxy.dispose();
+    const registered = new Set(
+      (await registry.list()).map((entry) => `${entry.workspaceId}:${entry.pid}`),
+    );
+    const entries = [...this.servers.entries()];
+    this.servers.clear();
+    const pendingEntries = [...this.pendingStarts.entries()];
+    this.pendingStarts.clear();
+
+    await Promise.all([
+      ...entries.map(async ([workspaceId, handle]) => {
+        this.suppressedExitNotifications.add(handle.child);
+        const alive = handle.child.exitCode === null && handle.child.signalCode === null;
+        if (alive && registered.has(`${workspaceId}:${handle.child.pid}`)) {
+          handle.release();
+          logServerManagerEvent("workspace server handed off", {
+            workspaceId,
+            pid: handle.child.pid,
+          });
+          return;
+        }
+        await gracefulKill(handle.child);
+        handle.cleanup();
+      }),
+      ...pendingEntries.map(async ([, handle]) => {
+        this.suppressedExitNotifications.add(handle.child);
+        await gracefulKill(handle.child);
+        handle.cleanup();
+      }),
+    ]);
+    this.sweepOutputReaders();
+    await registry.flush();
+  }
+
+  private sweepOutputReaders(): void {
+    const leaked = this.outputReaders.releaseAll();
+    if (leaked > 0) {
+      logServerManagerEvent("workspace server output readers swept", { readers: leaked });
+    }
+  }
+
+  /** Pids of servers this instance is running or still starting. */
+  getManagedPids(): number[] {
+    return [...this.servers.values(), ...this.pendingStarts.values()].flatMap((handle) =>
+      handle.child.pid === undefined ? [] : [handle.child.pid],
+    );
+  }
+
+  /** Ids of servers that finished starting; pending starts are left out. */
+  getRunningServerIds(): string[] {
+    return [...this.servers.keys()];
   }
 
   getDiagnostics(): ServerManagerDiagnostics {
@@ -1764,6 +3275,10 @@ export const __internal = {
   findSidecarLaunchCommand,
   getServerTerminationSignal,
   getServerLogPath,
+  gracefulKill,
+  registerServerShutdownEndpoint: (child: ManagedServerProcess, url: string, token: string) => {
+    serverShutdownEndpoints.set(child, { url, token });
+  },
   getServerStartupTimeoutMs,
   appendBrowserAccessToken,
   buildHarnessTerminalLogsEnv,
@@ -1779,11 +3294,15 @@ export const __internal = {
   logServerManagerEvent,
   flushServerManagerLogWrites,
   resolveSourceStartup,
+  buildSpawnArgs,
+  buildStartupDiagnostics,
+  launchProfileKey,
   shouldMirrorServerOutput,
   shouldReplaceForMobileH3Request,
   shouldReuseExistingWorkspaceServer,
   summarizeLogChunk,
   withStderrTail,
+  waitForServerHealthy,
   waitForServerListening,
   withoutInheritedCrashReportingEnv,
   withoutInheritedObservabilityEnv,
@@ -0,0 +1,51 @@
+/**
+ * Readers attached to each spawned server's stdout and stderr. Every stop,
+ * exit, and failed start releases the child's readers, so listeners and pipes
+ * do not pile up over the app's lifetime; `releaseAll` sweeps any a path missed.
+ */
+export class ServerOutputReaders<Child extends object> {
+  private readonly readers = new Map<Child, Array<() => void>>();
+
+  /** Registers `stop`, which detaches one reader; it runs at most once. */
+  track(child: Child, stop: () => void): void {
+    const stops = this.readers.get(child);
+    if (stops) {
+      stops.push(stop);
+    } else {
+      this.readers.set(child, [stop]);
+    }
+  }
+
+  /** Stops the child's readers in registration order; returns how many ran. */
+  release(child: Child): number {
+    const stops = this.readers.get(child);
+    if (!stops) {
+      return 0;
+    }
+    this.readers.delete(child);
+    for (const stop of stops) {
+      try {
+        stop();
+      } catch {
+        // A reader whose stream is already gone has nothing left to detach.
+      }
+    }
+    return stops.length;
+  }
+
+  releaseAll(): number {
+    let released = 0;
+    for (const child of [...this.readers.keys()]) {
+      released += this.release(child);
+    }
+    return released;
+  }
+
+  get activeCount(): number {
+    let count = 0;
+    for (const stops of this.readers.values()) {
+      count += stops.length;
+    }
+    return count;
+  }
+}
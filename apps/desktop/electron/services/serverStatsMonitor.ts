@@ -0,0 +1,80 @@
+import type { ServerStatsEvent, WorkspaceServerStats } from "../../src/lib/desktopApi";
+
+const DEFAULT_SERVER_STATS_INTERVAL_MS = 5_000;
+
+type ServerStatsMonitorOptions = {
+  sample: () => Promise<WorkspaceServerStats[]>;
+  emit: (event: ServerStatsEvent) => void;
+  intervalMs?: number;
+  onError?: (error: unknown) => void;
+  setInterval?: (callback: () => void, ms: number) => ReturnType<typeof setInterval>;
+  clearInterval?: (handle: ReturnType<typeof setInterval>) => void;
+};
+
+/**
+ * Periodically samples workspace server resource usage and broadcasts it.
+ * Stays quiet while no servers run, apart from one empty event after the
+ * last one stops so the UI can clear stale numbers.
+ */
+export class ServerStatsMonitor {
+  private readonly sample: () => Promise<WorkspaceServerStats[]>;
+  private readonly emit: (event: ServerStatsEvent) => void;
+  private readonly intervalMs: number;
+  private readonly onError: (error: unknown) => void;
+  private readonly setIntervalImpl: NonNullable<ServerStatsMonitorOptions["setInterval"]>;
+  private readonly clearIntervalImpl: NonNullable<ServerStatsMonitorOptions["clearInterval"]>;
+  private timer: ReturnType<typeof setInterval> | null = null;
+  private inFlight: Promise<void> | null = null;
+  private lastEmittedEmpty = true;
+
+  constructor(options: ServerStatsMonitorOptions) {
+    this.sample = options.sample;
+    this.emit = options.emit;
+    this.intervalMs = Math.max(1_000, options.intervalMs ?? DEFAULT_SERVER_STATS_INTERVAL_MS);
+    this.onError = options.onError ?? (() => {});
+    this.setIntervalImpl = options.setInterval ?? setInterval;
+    this.clearIntervalImpl = options.clearInterval ?? clearInterval;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+    this.timer = this.setIntervalImpl(() => {
+      void this.runOnce();
+    }, this.intervalMs);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  async runOnce(): Promise<void> {
+    if (this.inFlight) {
+      return await this.inFlight;
+    }
+    this.inFlight = this.sampleSafely();
+    try {
+      await this.inFlight;
+    } finally {
+      this.inFlight = null;
+    }
+  }
+
+  private async sampleSafely(): Promise<void> {
+    try {
+      const workspaces = await this.sample();
+      if (workspaces.length === 0 && this.lastEmittedEmpty) {
+        return;
+      }
+      this.lastEmittedEmpty = workspaces.length === 0;
+      this.emit({ workspaces });
+    } catch (error) {
+      this.onError(error);
+    }
+  }
+
+  dispose(): void {
+    if (this.timer) {
+      this.clearIntervalImpl(this.timer);
+      this.timer = null;
+    }
+  }
+}
@@ -0,0 +1,75 @@
+import { JSONRPC_PROTOCOL_VERSION } from "../../../../src/server/jsonrpc/protocol";
+import {
+  SERVER_UPGRADE_REQUIRED_ERROR_CODE,
+  type WorkspaceServerVersionInfo,
+} from "../../src/lib/desktopApi";
+
+/** Oldest sidecar protocol this build of the desktop app can talk to. */
+export const MIN_SUPPORTED_SERVER_PROTOCOL_VERSION = JSONRPC_PROTOCOL_VERSION;
+
+/**
+ * Sidecars that predate the version fields in `server_listening` spoke the
+ * first JSON-RPC protocol revision.
+ */
+const LEGACY_SERVER_PROTOCOL_VERSION = "0.1";
+
+type ServerVersionFields = {
+  version?: string | null;
+  protocolVersion?: string | null;
+};
+
+function parseProtocolVersion(value: string): number[] | null {
+  if (!/^\d+(?:\.\d+)*$/.test(value)) {
+    return null;
+  }
+  return value.split(".").map((part) => Number(part));
+}
+
+/** Returns a negative number when `left` is older than `right`; null when either is malformed. */
+export function compareProtocolVersions(left: string, right: string): number | null {
+  const leftParts = parseProtocolVersion(left);
+  const rightParts = parseProtocolVersion(right);
+  if (!leftParts || !rightParts) {
+    return null;
+  }
+  const length = Math.max(leftParts.length, rightParts.length);
+  for (let index = 0; index < length; index += 1) {
+    const diff = (leftParts[index] ?? 0) - (rightParts[index] ?? 0);
+    if (diff !== 0) {
+      return diff;
+    }
+  }
+  return 0;
+}
+
+export function resolveServerVersionInfo(
+  workspaceId: string,
+  fields: ServerVersionFields,
+  running: boolean,
+  minProtocolVersion = MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+): WorkspaceServerVersionInfo {
+  const protocolVersion = fields.protocolVersion?.trim() || LEGACY_SERVER_PROTOCOL_VERSION;
+  const comparison = compareProtocolVersions(protocolVersion, minProtocolVersion);
+  return {
+    workspaceId,
+    running,
+    version: fields.version?.trim() || null,
+    protocolVersion,
+    minProtocolVersion,
+    compatible: comparison !== null && comparison >= 0,
+  };
+}
+
+export class ServerUpgradeRequiredError extends Error {
+  readonly code = SERVER_UPGRADE_REQUIRED_ERROR_CODE;
+
+  constructor(readonly details: WorkspaceServerVersionInfo) {
+    const serverLabel = details.version ?? "(unknown version)";
+    super(
+      `${SERVER_UPGRADE_REQUIRED_ERROR_CODE}: Cowork server ${serverLabel} speaks protocol ` +
+        `${details.protocolVersion ?? "unknown"}, but this app requires ` +
+        `${details.minProtocolVersion} or newer. Update the Cowork server and try again.`,
+    );
+    this.name = "ServerUpgradeRequiredError";
+  }
+}
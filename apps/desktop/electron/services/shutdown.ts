@@ -2,18 +2,127 @@ type QuitEvent = {
   preventDefault(): void;
 };
 
+type ShutdownStep = () => Promise<void> | void;
+
+export type ShutdownPhaseName =
+  | "stop-schedulers"
+  | "drain-run-queue"
+  | "flush-transcripts"
+  | "persist-state"
+  | "stop-sidecars"
+  | "stop-watchers";
+
+type ShutdownLogLevel = "info" | "warn";
+
 type ShutdownDeps = {
   unregisterAppearanceListener?: () => void;
   stopUpdater?: () => void;
   stopQuickChat?: () => void;
+  stopSchedulers?: ShutdownStep;
+  drainRunQueue?: ShutdownStep;
+  flushTranscripts?: ShutdownStep;
+  persistState?: ShutdownStep;
   stopProductAnalytics?: () => Promise<void> | void;
   stopCloudSync?: () => Promise<void> | void;
   stopAllServers: () => Promise<void>;
   stopMobileRelayBridge?: () => Promise<void>;
+  stopWatchers?: ShutdownStep;
+  phaseTimeoutsMs?: Partial<Record<ShutdownPhaseName, number>>;
+  log?: (level: ShutdownLogLevel, message: string, meta: Record<string, unknown>) => void;
+  now?: () => number;
   quit: () => void;
-  onError?: (error: unknown) => void;
+  onError?: (error: unknown, phase?: ShutdownPhaseName) => void;
+};
+
+type ShutdownPhase = {
+  name: ShutdownPhaseName;
+  steps: ShutdownStep[];
+};
+
+export const DEFAULT_SHUTDOWN_PHASE_TIMEOUTS_MS: Readonly<Record<ShutdownPhaseName, number>> = {
+  "stop-schedulers": 2_000,
+  "drain-run-queue": 10_000,
+  "flush-transcripts": 5_000,
+  "persist-state": 5_000,
+  "stop-sidecars": 15_000,
+  "stop-watchers": 2_000,
 };
 
+function compactSteps(...steps: Array<ShutdownStep | undefined>): ShutdownStep[] {
+  return steps.filter((step): step is ShutdownStep => typeof step === "function");
+}
+
+/**
+ * Phases run strictly in order so each one can rely on the previous one having
+ * settled: nothing new is scheduled once drains start, transcripts and state
+ * are on disk before sidecars are killed, and watchers go last so late writes
+ * are still observed.
+ */
+function buildShutdownPhases(deps: ShutdownDeps): ShutdownPhase[] {
+  const phases: ShutdownPhase[] = [
+    {
+      name: "stop-schedulers",
+      steps: compactSteps(deps.stopSchedulers, deps.stopUpdater),
+    },
+    { name: "drain-run-queue", steps: compactSteps(deps.drainRunQueue) },
+    { name: "flush-transcripts", steps: compactSteps(deps.flushTranscripts) },
+    {
+      name: "persist-state",
+      steps: compactSteps(deps.persistState, deps.stopProductAnalytics, deps.stopCloudSync),
+    },
+    {
+      // Clear mobile relay bridge state before killing managed workspace servers.
+      name: "stop-sidecars",
+      steps: compactSteps(deps.stopMobileRelayBridge, deps.stopAllServers),
+    },
+    {
+      name: "stop-watchers",
+      steps: compactSteps(deps.unregisterAppearanceListener, deps.stopQuickChat, deps.stopWatchers),
+    },
+  ];
+  return phases.filter((phase) => phase.steps.length > 0);
+}
+
+async function runPhaseSteps(phase: ShutdownPhase, deps: ShutdownDeps): Promise<number> {
+  let failures = 0;
+  for (const step of phase.steps) {
+    try {
+      await step();
+    } catch (error) {
+      failures += 1;
+      deps.onError?.(error, phase.name);
+    }
+  }
+  return failures;
+}
+
+async function runPhase(phase: ShutdownPhase, deps: ShutdownDeps): Promise<void> {
+  const now = deps.now ?? Date.now;
+  const timeoutMs =
+    deps.phaseTimeoutsMs?.[phase.name] ?? DEFAULT_SHUTDOWN_PHASE_TIMEOUTS_MS[phase.name];
+  const startedAt = now();
+  let timer: ReturnType<typeof setTimeout> | undefined;
+  const timeout = new Promise<"timeout">((resolve) => {
+    timer = setTimeout(() => resolve("timeout"), timeoutMs);
+  });
+
+  try {
+    const outcome = await Promise.race([runPhaseSteps(phase, deps), timeout]);
+    const durationMs = now() - startedAt;
+    if (outcome === "timeout") {
+      deps.log?.("warn", "Shutdown phase timed out", { phase: phase.name, timeoutMs, durationMs });
+      return;
+    }
+    deps.log?.(outcome > 0 ? "warn" : "info", "Shutdown phase finished", {
+      phase: phase.name,
+      durationMs,
+      failures: outcome,
+    });
+  } finally {
+    clearTimeout(timer);
+  }
+}
+
 export function createBeforeQuitHandler(deps: ShutdownDeps): (event: QuitEvent) => void {
   let shutdownStarted = false;
   let shutdownFinished = false;
@@ -32,36 +141,11 @@ export function createBeforeQuitHandler(deps: ShutdownDeps): (event: QuitEvent)
     event.preventDefault();
 
     void (async () => {
-      // Clear mobile relay bridge state before killing managed workspace servers.
-      if (deps.stopMobileRelayBridge) {
-        try {
-          await deps.stopMobileRelayBridge();
-        } catch (error) {
-          deps.onError?.(error);
-        }
-      }
-
-      try {
-        await deps.stopAllServers();
-      } catch (error) {
-        deps.onError?.(error);
-      }
-
       // Keep IPC handlers live until process exit. The renderer may still make
       // recovery calls while quit is in flight, and Electron clears ipcMain
       // handlers when the app process exits.
-      deps.unregisterAppearanceListener?.();
-      deps.stopUpdater?.();
-      deps.stopQuickChat?.();
-      try {
-        await deps.stopProductAnalytics?.();
-      } catch (error) {
-        deps.onError?.(error);
-      }
-      try {
-        await deps.stopCloudSync?.();
-      } catch (error) {
-        deps.onError?.(error);
+      for (const phase of buildShutdownPhases(deps)) {
+        await runPhase(phase, deps);
       }
       shutdownFinished = true;
       deps.quit();
@@ -1,7 +1,8 @@
+import { createHash } from "node:crypto";
 import fs from "node:fs";
 import path from "node:path";
 
-const SIDECAR_BASE_NAME = "cowork-server";
+export const SIDECAR_BASE_NAME = "cowork-server";
 export const SIDECAR_MANIFEST_NAME = "cowork-server-manifest.json";
 export const SIDECAR_BUN_EXECUTABLE_NAME = "bun.exe";
 export const SIDECAR_BUN_ENTRYPOINT_PATH = "server/index.js";
@@ -29,6 +30,8 @@ export type SidecarManifest = {
   platform: NodeJS.Platform;
   arch: string;
   launch: SidecarLaunchSpec;
+  /** SHA-256 of every launch file, keyed by its path in `launch`. Older bundles have none. */
+  sha256?: Record<string, string>;
 };
 
 type LegacySidecarManifest = {
@@ -38,6 +41,11 @@ type LegacySidecarManifest = {
   arch: string;
 };
 
+export type SidecarFileDigest = {
+  path: string;
+  sha256: string;
+};
+
 export type SidecarLaunchCommand = {
   command: string;
   args: string[];
@@ -45,8 +53,21 @@ export type SidecarLaunchCommand = {
   platform: NodeJS.Platform;
   arch: string;
   manifestPath?: string;
+  /** Expected digests of the files the command runs; absent when the source ships none. */
+  integrity?: SidecarFileDigest[];
 };
 
+export class SidecarIntegrityError extends Error {
+  readonly code = "sidecar_integrity_failed";
+
+  constructor(message: string) {
+    super(message);
+    this.name = "SidecarIntegrityError";
+  }
+}
+
+const SHA256_PATTERN = /^[a-f0-9]{64}$/;
+
 export function resolveDesktopTargetTriple(
   platform: NodeJS.Platform = process.platform,
   arch: string = process.arch,
@@ -178,6 +199,47 @@ export function buildSidecarManifest(
   };
 }
 
+/** Files the manifest's launch spec runs, relative to the manifest's directory. */
+export function listSidecarLaunchFiles(manifest: SidecarManifest): string[] {
+  return manifest.launch.kind === "executable"
+    ? [manifest.launch.path]
+    : [manifest.launch.runtime, manifest.launch.entrypoint];
+}
+
+export async function hashFileSha256(filePath: string): Promise<string> {
+  const hash = createHash("sha256");
+  for await (const chunk of fs.createReadStream(filePath)) {
+    hash.update(chunk as Buffer);
+  }
+  return hash.digest("hex");
+}
+
+/**
+ * Rejects a launch whose files do not match their expected digests. With
+ * `requireDigests`, a launch that carries none is rejected too, so a binary
+ * dropped next to the app without a manifest never runs.
+ */
+export async function verifySidecarLaunchCommand(
+  launch: SidecarLaunchCommand,
+  options: { requireDigests: boolean; hashFile?: (filePath: string) => Promise<string> },
+): Promise<void> {
+  if (!launch.integrity || launch.integrity.length === 0) {
+    if (options.requireDigests) {
+      throw new SidecarIntegrityError(
+        `Server sidecar has no expected digests: ${launch.manifestPath ?? launch.command}`,
+      );
+    }
+    return;
+  }
+  const hashFile = options.hashFile ?? hashFileSha256;
+  for (const file of launch.integrity) {
+    const actual = await hashFile(file.path).catch(() => null);
+    if (actual !== file.sha256) {
+      throw new SidecarIntegrityError(`Server sidecar failed integrity verification: ${file.path}`);
+    }
+  }
+}
+
 function isStringArray(value: unknown): value is string[] {
   return Array.isArray(value) && value.every((entry) => typeof entry === "string");
 }
@@ -229,10 +291,40 @@ function isSidecarManifest(value: unknown): value is SidecarManifest {
     candidate.platform.length > 0 &&
     typeof candidate.arch === "string" &&
     candidate.arch.length > 0 &&
-    isSidecarLaunchSpec(candidate.launch)
+    isSidecarLaunchSpec(candidate.launch) &&
+    (candidate.sha256 === undefined ||
+      hasLaunchFileDigests(candidate as SidecarManifest, candidate.sha256))
   );
 }
 
+// Digests cover every launch file or the manifest is not trusted at all.
+function hasLaunchFileDigests(manifest: SidecarManifest, digests: unknown): boolean {
+  if (!digests || typeof digests !== "object" || Array.isArray(digests)) {
+    return false;
+  }
+  const record = digests as Record<string, unknown>;
+  return listSidecarLaunchFiles(manifest).every((file) => {
+    const digest = record[file];
+    return typeof digest === "string" && SHA256_PATTERN.test(digest);
+  });
+}
+
+function manifestIntegrity(
+  dir: string,
+  manifest: SidecarManifest,
+): Pick<SidecarLaunchCommand, "integrity"> {
+  const digests = manifest.sha256;
+  if (!digests) {
+    return {};
+  }
+  return {
+    integrity: listSidecarLaunchFiles(manifest).map((file) => ({
+      path: resolveRelativeManifestPath(dir, file),
+      sha256: digests[file] ?? "",
+    })),
+  };
+}
+
 function isLegacySidecarManifest(value: unknown): value is LegacySidecarManifest {
   if (!value || typeof value !== "object") {
     return false;
@@ -302,6 +394,7 @@ function buildSidecarLaunchCommand(
       platform: manifest.platform,
       arch: manifest.arch,
       manifestPath,
+      ...manifestIntegrity(dir, manifest),
     };
   }
 
@@ -326,6 +419,7 @@ function buildSidecarLaunchCommand(
     platform: manifest.platform,
     arch: manifest.arch,
     manifestPath,
+    ...manifestIntegrity(dir, manifest),
   };
 }
 
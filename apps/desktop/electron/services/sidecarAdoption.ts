@@ -0,0 +1,207 @@
+import { EventEmitter } from "node:events";
+import fs from "node:fs/promises";
+import path from "node:path";
+import { z } from "zod";
+
+export const SIDECAR_REGISTRY_FILE = "sidecars.json";
+export const SIDECAR_ADOPTION_ENV = "COWORK_DESKTOP_ADOPT_SIDECARS";
+export const SIDECAR_ADOPTABLE_ENV = "COWORK_DESKTOP_SIDECAR_ADOPTABLE";
+
+const ADOPTED_SIDECAR_POLL_INTERVAL_MS = 2_000;
+
+const sidecarRegistryEntrySchema = z.object({
+  workspaceId: z.string().min(1),
+  workspacePath: z.string().min(1),
+  cwd: z.string().min(1),
+  pid: z.number().int().positive(),
+  url: z.string().min(1),
+  /** Absent for sidecars registered before servers required an auth token. */
+  authToken: z.string().min(1).optional(),
+  yolo: z.boolean(),
+  version: z.string().nullable(),
+  protocolVersion: z.string().nullable(),
+  appVersion: z.string(),
+  startedAt: z.string(),
+});
+
+const sidecarRegistryFileSchema = z.object({
+  schemaVersion: z.literal(1),
+  sidecars: z.array(sidecarRegistryEntrySchema),
+});
+
+export type SidecarRegistryEntry = z.infer<typeof sidecarRegistryEntrySchema>;
+
+/**
+ * Packaged builds adopt sidecars across restarts by default. Dev builds opt in
+ * with `COWORK_DESKTOP_ADOPT_SIDECARS=1`, since detached sidecars would
+ * otherwise outlive a Ctrl-C in the dev terminal; `=0` disables adoption.
+ */
+export function isSidecarAdoptionEnabled(
+  isPackaged: boolean,
+  env: Record<string, string | undefined> = process.env,
+): boolean {
+  const override = env[SIDECAR_ADOPTION_ENV]?.trim();
+  if (override === "1") {
+    return true;
+  }
+  if (override === "0") {
+    return false;
+  }
+  return isPackaged;
+}
+
+export function isProcessAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch {
+    // EPERM means the pid exists but belongs to someone else, which is never
+    // one of our sidecars.
+    return false;
+  }
+}
+
+/**
+ * Records the workspace servers this app instance launched so the next
+ * instance (typically the one installed by an update) can find and adopt
+ * them instead of starting over. Entries hold browser access tokens, so the
+ * file is private to the user.
+ */
+export class SidecarRegistry {
+  private writeChain: Promise<void> = Promise.resolve();
+
+  constructor(private readonly userDataDir: string) {}
+
+  get filePath(): string {
+    return path.join(this.userDataDir, SIDECAR_REGISTRY_FILE);
+  }
+
+  async list(): Promise<SidecarRegistryEntry[]> {
+    await this.writeChain;
+    return await this.readEntries();
+  }
+
+  async get(workspaceId: string): Promise<SidecarRegistryEntry | null> {
+    const entries = await this.list();
+    return entries.find((entry) => entry.workspaceId === workspaceId) ?? null;
+  }
+
+  async record(entry: SidecarRegistryEntry): Promise<void> {
+    await this.update((entries) => [
+      ...entries.filter((existing) => existing.workspaceId !== entry.workspaceId),
+      entry,
+    ]);
+  }
+
+  /** Removes the workspace entry, optionally only when it still points at `pid`. */
+  async remove(workspaceId: string, pid?: number): Promise<void> {
+    await this.update((entries) =>
+      entries.filter(
+        (entry) => entry.workspaceId !== workspaceId || (pid !== undefined && entry.pid !== pid),
+      ),
+    );
+  }
+
+  async flush(): Promise<void> {
+    await this.writeChain;
+  }
+
+  private update(
+    transform: (entries: SidecarRegistryEntry[]) => SidecarRegistryEntry[],
+  ): Promise<void> {
+    const next = this.writeChain.then(async () => {
+      await this.writeEntries(transform(await this.readEntries()));
+    });
+    this.writeChain = next.catch(() => {});
+    return next;
+  }
+
+  private async readEntries(): Promise<SidecarRegistryEntry[]> {
+    try {
+      const parsed = sidecarRegistryFileSchema.safeParse(
+        JSON.parse(await fs.readFile(this.filePath, "utf8")),
+      );
+      return parsed.success ? parsed.data.sidecars : [];
+    } catch {
+      return [];
+    }
+  }
+
+  private async writeEntries(entries: SidecarRegistryEntry[]): Promise<void> {
+    if (entries.length === 0) {
+      await fs.rm(this.filePath, { force: true });
+      return;
+    }
+    await fs.mkdir(this.userDataDir, { recursive: true });
+    const temporary = `${this.filePath}.tmp-${process.pid}`;
+    await fs.writeFile(
+      temporary,
+      `${JSON.stringify({ schemaVersion: 1, sidecars: entries }, null, 2)}\n`,
+      { encoding: "utf8", mode: 0o600 },
+    );
+    await fs.rename(temporary, this.filePath);
+  }
+}
+
+type AdoptedSidecarProcessOptions = {
+  isProcessAlive?: (pid: number) => boolean;
+  signalProcess?: (pid: number, signal?: NodeJS.Signals | number) => void;
+  pollIntervalMs?: number;
+};
+
+/**
+ * Stands in for the `ChildProcess` of a sidecar launched by a previous app
+ * instance. There is no exit event for a non-child process, so liveness is
+ * polled and an `exit` with unknown code/signal is emitted once it is gone.
+ */
+export class AdoptedSidecarProcess extends EventEmitter {
+  exitCode: number | null = null;
+  signalCode: NodeJS.Signals | null = null;
+  private readonly isAlive: (pid: number) => boolean;
+  private readonly signalProcess: (pid: number, signal?: NodeJS.Signals | number) => void;
+  private timer: ReturnType<typeof setInterval> | null = null;
+
+  constructor(readonly pid: number, options: AdoptedSidecarProcessOptions = {}) {
+    super();
+    this.isAlive = options.isProcessAlive ?? isProcessAlive;
+    this.signalProcess =
+      options.signalProcess ?? ((target, signal) => void process.kill(target, signal));
+    this.timer = setInterval(
+      () => this.poll(),
+      options.pollIntervalMs ?? ADOPTED_SIDECAR_POLL_INTERVAL_MS,
+    );
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  kill(signal?: NodeJS.Signals | number): boolean {
+    if (this.exitCode !== null || this.signalCode !== null) {
+      return false;
+    }
+    try {
+      this.signalProcess(this.pid, signal ?? "SIGTERM");
+    } catch {
+      this.poll();
+      return false;
+    }
+    queueMicrotask(() => this.poll());
+    return true;
+  }
+
+  /** Stops watching the process without signalling it. */
+  release(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = null;
+    }
+  }
+
+  private poll(): void {
+    if (this.exitCode !== null || this.signalCode !== null || this.isAlive(this.pid)) {
+      return;
+    }
+    this.release();
+    // The real exit status belongs to whichever process reaped the sidecar.
+    this.exitCode = -1;
+    this.emit("exit", null, null);
+  }
+}
@@ -0,0 +1,489 @@
+import { createHash, createPublicKey, type KeyObject, randomUUID, verify } from "node:crypto";
+import fs from "node:fs/promises";
+import path from "node:path";
+import { z } from "zod";
+
+import type { SidecarUpdateState, UpdaterProgress } from "../../src/lib/desktopApi";
+import { writeLocalLog } from "./localLogs";
+import { compareProtocolVersions, MIN_SUPPORTED_SERVER_PROTOCOL_VERSION } from "./serverVersion";
+import {
+  resolveDesktopTargetTriple,
+  resolvePackagedSidecarFilename,
+  type SidecarLaunchCommand,
+  shouldUseBundledBunRuntime,
+} from "./sidecar";
+
+declare global {
+  // Defined by electron-vite from COWORK_SIDECAR_UPDATE_PUBLIC_KEY at build time.
+  var __COWORK_SIDECAR_UPDATE_PUBLIC_KEY__: string | undefined;
+}
+
+export const SIDECAR_UPDATE_MANIFEST_URL =
+  "https://github.com/mweinbach/agent-coworker/releases/latest/download/cowork-server-update.json";
+
+const STAGED_DIR_NAME = "staged";
+const CURRENT_DIR_NAME = "current";
+const RECORD_FILE_NAME = "update.json";
+const PROGRESS_INTERVAL_MS = 250;
+
+const versionSchema = z.string().regex(/^\d+(?:\.\d+)*$/);
+
+const sidecarUpdateManifestSchema = z.object({
+  version: versionSchema,
+  protocolVersion: versionSchema,
+  assets: z.record(
+    z.string(),
+    z.object({
+      url: z.string().url().startsWith("https://"),
+      sha256: z.string().regex(/^[a-f0-9]{64}$/),
+      size: z.number().int().positive(),
+      /** Base64 Ed25519 signature over `sidecarUpdateSignedMessage(...)`. */
+      signature: z.string().min(1),
+    }),
+  ),
+});
+
+type SidecarUpdateAsset = z.infer<typeof sidecarUpdateManifestSchema>["assets"][string];
+
+type SidecarUpdateOffer = {
+  version: string;
+  protocolVersion: string;
+  asset: SidecarUpdateAsset;
+};
+
+const installedRecordSchema = z.object({
+  version: versionSchema,
+  protocolVersion: versionSchema,
+  targetTriple: z.string().min(1),
+  filename: z.string().min(1),
+  sha256: z.string().regex(/^[a-f0-9]{64}$/),
+});
+
+type InstalledRecord = z.infer<typeof installedRecordSchema>;
+
+type SidecarUpdaterOptions = {
+  /** Holds `staged/` (verified, waiting for a server start) and `current/` (in use). */
+  rootDir: string;
+  /** Version of the server binary bundled with this app build. */
+  bundledVersion: string;
+  /** PEM Ed25519 key release manifests are signed with; updates stay off without one. */
+  publicKey: string | null;
+  /** False in dev builds and when a sidecar path override is set. */
+  enabled: boolean;
+  manifestUrl?: string;
+  fetch?: typeof fetch;
+  platform?: NodeJS.Platform;
+  arch?: string;
+  now?: () => number;
+  onStateChange?: (state: SidecarUpdateState) => void;
+};
+
+/** What the signature covers: the asset hash bound to the version and platform it was built for. */
+export function sidecarUpdateSignedMessage(
+  version: string,
+  targetTriple: string,
+  sha256: string,
+): string {
+  return `cowork-server ${version} ${targetTriple} ${sha256}`;
+}
+
+function toMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+function logSidecarUpdater(level: "info" | "warn" | "error", message: string, meta?: unknown) {
+  writeLocalLog("updater.log", level, "sidecar-updater", message, meta);
+}
+
+function isNewerVersion(candidate: string, current: string): boolean {
+  return (compareProtocolVersions(candidate, current) ?? 0) > 0;
+}
+
+function parsePublicKey(pem: string | null): KeyObject | null {
+  if (!pem?.trim()) {
+    return null;
+  }
+  try {
+    const key = createPublicKey(pem);
+    return key.asymmetricKeyType === "ed25519" ? key : null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Downloads newer `cowork-server` binaries published alongside app releases.
+ * A verified download waits in `staged/` and is moved into `current/` by the
+ * next workspace server start, so running servers keep their binary.
+ */
+export class SidecarUpdater {
+  private readonly rootDir: string;
+  private readonly bundledVersion: string;
+  private readonly publicKey: KeyObject | null;
+  private readonly enabled: boolean;
+  private readonly manifestUrl: string;
+  private readonly fetchImpl: typeof fetch;
+  private readonly platform: NodeJS.Platform;
+  private readonly arch: string;
+  private readonly now: () => number;
+  private readonly onStateChange?: (state: SidecarUpdateState) => void;
+
+  private state: SidecarUpdateState;
+  private offer: SidecarUpdateOffer | null = null;
+  private loaded: Promise<void> | null = null;
+  private checking: Promise<SidecarUpdateState> | null = null;
+  private applying: Promise<SidecarUpdateState> | null = null;
+  private promoting: Promise<SidecarLaunchCommand | null> | null = null;
+
+  constructor(options: SidecarUpdaterOptions) {
+    this.rootDir = options.rootDir;
+    this.bundledVersion = options.bundledVersion;
+    this.publicKey = parsePublicKey(options.publicKey);
+    this.manifestUrl = options.manifestUrl ?? SIDECAR_UPDATE_MANIFEST_URL;
+    this.fetchImpl = options.fetch ?? fetch;
+    this.platform = options.platform ?? process.platform;
+    this.arch = options.arch ?? process.arch;
+    this.now = options.now ?? Date.now;
+    this.onStateChange = options.onStateChange;
+    // Bun-runtime sidecars ship as a directory tree, not a single binary.
+    this.enabled =
+      options.enabled &&
+      this.publicKey !== null &&
+      !shouldUseBundledBunRuntime(this.platform, this.arch);
+    this.state = {
+      phase: this.enabled ? "idle" : "disabled",
+      currentVersion: this.bundledVersion,
+      availableVersion: null,
+      stagedVersion: null,
+      progress: null,
+      message: this.enabled
+        ? "Server updates are ready to check."
+        : "Server updates are unavailable in this build.",
+      error: null,
+    };
+  }
+
+  async getState(): Promise<SidecarUpdateState> {
+    await this.load();
+    return this.snapshot();
+  }
+
+  /** Looks for a newer server in the release manifest; failures land in the state, not a throw. */
+  async check(): Promise<SidecarUpdateState> {
+    if (!this.enabled) {
+      return this.snapshot();
+    }
+    this.checking ??= this.runCheck().finally(() => {
+      this.checking = null;
+    });
+    return await this.checking;
+  }
+
+  /** Downloads and verifies the offered update into `staged/` for the next server start. */
+  async apply(): Promise<SidecarUpdateState> {
+    if (!this.enabled) {
+      throw new Error("Server updates are unavailable in this build.");
+    }
+    this.applying ??= this.runApply().finally(() => {
+      this.applying = null;
+    });
+    return await this.applying;
+  }
+
+  /**
+   * Called before spawning a workspace server: promotes a staged update if
+   * there is one and returns the installed binary, or null for the bundled one.
+   */
+  async prepareLaunch(): Promise<SidecarLaunchCommand | null> {
+    if (!this.enabled) {
+      return null;
+    }
+    this.promoting ??= this.runPrepareLaunch().finally(() => {
+      this.promoting = null;
+    });
+    return await this.promoting;
+  }
+
+  private async runCheck(): Promise<SidecarUpdateState> {
+    await this.load();
+    this.setState({ phase: "checking", message: "Checking for server updates…", error: null });
+    try {
+      const offer = await this.fetchOffer();
+      const installedVersion = this.state.stagedVersion ?? this.state.currentVersion;
+      if (!offer || !isNewerVersion(offer.version, installedVersion)) {
+        this.offer = null;
+        this.setState({
+          phase: this.state.stagedVersion ? "staged" : "up-to-date",
+          availableVersion: null,
+          message: this.state.stagedVersion
+            ? `Server ${this.state.stagedVersion} starts with the next workspace server.`
+            : "The Cowork server is up to date.",
+        });
+      } else {
+        this.offer = offer;
+        this.setState({
+          phase: "available",
+          availableVersion: offer.version,
+          message: `Server ${offer.version} is available.`,
+        });
+      }
+    } catch (error) {
+      logSidecarUpdater("warn", "server update check failed", { error: toMessage(error) });
+      this.setState({
+        phase: "error",
+        message: "Unable to check for server updates.",
+        error: toMessage(error),
+      });
+    }
+    return this.snapshot();
+  }
+
+  private async runApply(): Promise<SidecarUpdateState> {
+    if (!this.offer) {
+      await this.check();
+    }
+    const offer = this.offer;
+    if (!offer) {
+      return this.snapshot();
+    }
+    const targetTriple = resolveDesktopTargetTriple(this.platform, this.arch);
+    const filename = resolvePackagedSidecarFilename(this.platform, this.arch);
+    const incomingDir = path.join(this.rootDir, `incoming-${randomUUID()}`);
+    try {
+      this.verifySignature(offer, targetTriple);
+      await fs.mkdir(incomingDir, { recursive: true });
+      const sha256 = await this.download(offer, path.join(incomingDir, filename));
+      if (sha256 !== offer.asset.sha256) {
+        throw new Error("Downloaded server binary does not match the signed checksum.");
+      }
+      const record: InstalledRecord = {
+        version: offer.version,
+        protocolVersion: offer.protocolVersion,
+        targetTriple,
+        filename,
+        sha256,
+      };
+      await fs.writeFile(path.join(incomingDir, RECORD_FILE_NAME), JSON.stringify(record), "utf8");
+      const stagedDir = path.join(this.rootDir, STAGED_DIR_NAME);
+      await fs.rm(stagedDir, { recursive: true, force: true });
+      await fs.rename(incomingDir, stagedDir);
+      this.offer = null;
+      logSidecarUpdater("info", "server update staged", { version: offer.version });
+      this.setState({
+        phase: "staged",
+        availableVersion: null,
+        stagedVersion: offer.version,
+        progress: null,
+        message: `Server ${offer.version} starts with the next workspace server.`,
+        error: null,
+      });
+    } catch (error) {
+      await fs.rm(incomingDir, { recursive: true, force: true }).catch(() => {});
+      logSidecarUpdater("error", "server update failed", {
+        version: offer.version,
+        error: toMessage(error),
+      });
+      this.setState({
+        phase: "error",
+        progress: null,
+        message: "Unable to install the server update.",
+        error: toMessage(error),
+      });
+      throw error;
+    }
+    return this.snapshot();
+  }
+
+  private async runPrepareLaunch(): Promise<SidecarLaunchCommand | null> {
+    await this.load();
+    const stagedDir = path.join(this.rootDir, STAGED_DIR_NAME);
+    const currentDir = path.join(this.rootDir, CURRENT_DIR_NAME);
+    const staged = await this.readRecord(stagedDir);
+    if (staged) {
+      const retiredDir = path.join(this.rootDir, `retired-${randomUUID()}`);
+      try {
+        await fs.rename(currentDir, retiredDir).catch((error: NodeJS.ErrnoException) => {
+          if (error.code !== "ENOENT") throw error;
+        });
+        await fs.rename(stagedDir, currentDir);
+        this.setState({
+          phase: "up-to-date",
+          currentVersion: staged.version,
+          stagedVersion: null,
+          message: `Cowork server ${staged.version} is installed.`,
+        });
+        logSidecarUpdater("info", "server update installed", { version: staged.version });
+        await fs.rm(retiredDir, { recursive: true, force: true }).catch(() => {});
+      } catch (error) {
+        // Windows refuses to move a directory while a server runs from it; try again next start.
+        logSidecarUpdater("warn", "server update swap deferred", { error: toMessage(error) });
+        await fs.rename(retiredDir, currentDir).catch(() => {});
+      }
+    }
+
+    const current = await this.readRecord(currentDir);
+    if (!current) {
+      return null;
+    }
+    const command = path.join(currentDir, current.filename);
+    try {
+      await fs.access(command);
+    } catch {
+      return null;
+    }
+    return {
+      command,
+      args: [],
+      targetTriple: current.targetTriple,
+      platform: this.platform,
+      arch: this.arch,
+      integrity: [{ path: command, sha256: current.sha256 }],
+    };
+  }
+
+  private async load(): Promise<void> {
+    if (!this.enabled) {
+      return;
+    }
+    this.loaded ??= (async () => {
+      // Leftovers from a download or swap interrupted by a crash.
+      const entries = await fs.readdir(this.rootDir).catch(() => [] as string[]);
+      await Promise.all(
+        entries
+          .filter((entry) => entry.startsWith("incoming-") || entry.startsWith("retired-"))
+          .map((entry) => fs.rm(path.join(this.rootDir, entry), { recursive: true, force: true })),
+      );
+      // Drop installs the bundled server has caught up with, e.g. after an app update.
+      const current = await this.readRecord(path.join(this.rootDir, CURRENT_DIR_NAME));
+      const staged = await this.readRecord(path.join(this.rootDir, STAGED_DIR_NAME));
+      this.setState(
+        {
+          currentVersion: current?.version ?? this.bundledVersion,
+          stagedVersion: staged?.version ?? null,
+          ...(staged
+            ? {
+                phase: "staged" as const,
+                message: `Server ${staged.version} starts with the next workspace server.`,
+              }
+            : {}),
+        },
+        { silent: true },
+      );
+    })();
+    await this.loaded;
+  }
+
+  /** Reads an install record, removing the directory when it is unusable or outdated. */
+  private async readRecord(dir: string): Promise<InstalledRecord | null> {
+    let raw: string;
+    try {
+      raw = await fs.readFile(path.join(dir, RECORD_FILE_NAME), "utf8");
+    } catch {
+      return null;
+    }
+    let record: InstalledRecord | null = null;
+    try {
+      record = installedRecordSchema.parse(JSON.parse(raw));
+    } catch {
+      record = null;
+    }
+    const targetTriple = resolveDesktopTargetTriple(this.platform, this.arch);
+    if (
+      !record ||
+      record.targetTriple !== targetTriple ||
+      !isNewerVersion(record.version, this.bundledVersion)
+    ) {
+      await fs.rm(dir, { recursive: true, force: true }).catch(() => {});
+      return null;
+    }
+    return record;
+  }
+
+  private async fetchOffer(): Promise<SidecarUpdateOffer | null> {
+    const response = await this.fetchImpl(this.manifestUrl);
+    if (response.status === 404) {
+      return null;
+    }
+    if (!response.ok) {
+      throw new Error(`Server update manifest request failed with HTTP ${response.status}.`);
+    }
+    const manifest = sidecarUpdateManifestSchema.parse(await response.json());
+    const asset = manifest.assets[resolveDesktopTargetTriple(this.platform, this.arch)];
+    const protocolComparison = compareProtocolVersions(
+      manifest.protocolVersion,
+      MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+    );
+    if (!asset || protocolComparison === null || protocolComparison < 0) {
+      return null;
+    }
+    return { version: manifest.version, protocolVersion: manifest.protocolVersion, asset };
+  }
+
+  private verifySignature(offer: SidecarUpdateOffer, targetTriple: string): void {
+    const message = sidecarUpdateSignedMessage(offer.version, targetTriple, offer.asset.sha256);
+    const signature = Buffer.from(offer.asset.signature, "base64");
+    if (!this.publicKey || !verify(null, Buffer.from(message), this.publicKey, signature)) {
+      throw new Error("Server update signature is invalid.");
+    }
+  }
+
+  /** Streams the asset to `target`, returning its sha256. */
+  private async download(offer: SidecarUpdateOffer, target: string): Promise<string> {
+    const response = await this.fetchImpl(offer.asset.url);
+    if (!response.ok || !response.body) {
+      throw new Error(`Server update download failed with HTTP ${response.status}.`);
+    }
+    const total = offer.asset.size;
+    const hash = createHash("sha256");
+    const file = await fs.open(target, "w", 0o755);
+    const startedAt = this.now();
+    let transferred = 0;
+    let lastProgressAt = 0;
+    try {
+      for await (const chunk of response.body as unknown as AsyncIterable<Uint8Array>) {
+        transferred += chunk.byteLength;
+        if (transferred > total) {
+          throw new Error("Server update download is larger than the manifest says.");
+        }
+        hash.update(chunk);
+        await file.write(chunk);
+        if (this.now() - lastProgressAt >= PROGRESS_INTERVAL_MS || transferred === total) {
+          lastProgressAt = this.now();
+          this.setState({
+            phase: "downloading",
+            message: `Downloading server ${offer.version}…`,
+            progress: this.progress(transferred, total, startedAt),
+          });
+        }
+      }
+      await file.sync();
+    } finally {
+      await file.close();
+    }
+    if (transferred !== total) {
+      throw new Error("Server update download ended early.");
+    }
+    return hash.digest("hex");
+  }
+
+  private progress(transferred: number, total: number, startedAt: number): UpdaterProgress {
+    const elapsedSeconds = Math.max(0.001, (this.now() - startedAt) / 1000);
+    return {
+      percent: Math.min(100, (transferred / total) * 100),
+      transferred,
+      total,
+      bytesPerSecond: Math.round(transferred / elapsedSeconds),
+    };
+  }
+
+  private snapshot(): SidecarUpdateState {
+    return { ...this.state, progress: this.state.progress ? { ...this.state.progress } : null };
+  }
+
+  private setState(patch: Partial<SidecarUpdateState>, opts: { silent?: boolean } = {}): void {
+    this.state = { ...this.state, ...patch };
+    if (!opts.silent) {
+      this.onStateChange?.(this.snapshot());
+    }
+  }
+}
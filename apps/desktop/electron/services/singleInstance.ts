@@ -0,0 +1,111 @@
+import path from "node:path";
+
+import { DEEP_LINK_SCHEME, findDeepLinkArg } from "./deepLinks";
+import { PROFILE_FLAG } from "./profiles";
+import { isTranscriptExportPath } from "./transcriptViewer";
+
+/** Sent with the single-instance lock so the primary sees the launch exactly as it was made. */
+export type SingleInstanceLaunchData = {
+  args: string[];
+  workingDirectory: string;
+};
+
+type LaunchArgsOptions = {
+  /** True when running under the `electron` binary, where argv[1] is the app path. */
+  defaultApp?: boolean;
+};
+
+/** Drops the executable (and, in development, the app path) from a process argv. */
+export function launchArgs(argv: readonly string[], options: LaunchArgsOptions = {}): string[] {
+  return argv.slice(options.defaultApp ? 2 : 1);
+}
+
+export function createSingleInstanceLaunchData(
+  argv: readonly string[],
+  workingDirectory: string,
+  options: LaunchArgsOptions = {},
+): SingleInstanceLaunchData {
+  return { args: launchArgs(argv, options), workingDirectory };
+}
+
+function isLaunchData(value: unknown): value is SingleInstanceLaunchData {
+  if (!value || typeof value !== "object") {
+    return false;
+  }
+  const candidate = value as Partial<SingleInstanceLaunchData>;
+  return (
+    Array.isArray(candidate.args) &&
+    candidate.args.every((arg) => typeof arg === "string") &&
+    typeof candidate.workingDirectory === "string"
+  );
+}
+
+export function openPathDeepLink(directory: string): string {
+  return `${DEEP_LINK_SCHEME}://open?path=${encodeURIComponent(directory)}`;
+}
+
+/** Switches whose value may follow as a separate argument (`--profile work`). */
+const VALUE_SWITCHES = new Set([PROFILE_FLAG]);
+
+/**
+ * The last bare (non-switch) argument, resolved against the launch directory.
+ * Values of switches like `--profile work` are not bare arguments.
+ */
+function launchPathArg(launch: SingleInstanceLaunchData): string | null {
+  let target: string | undefined;
+  for (let index = 0; index < launch.args.length; index += 1) {
+    const arg = launch.args[index] ?? "";
+    if (VALUE_SWITCHES.has(arg)) {
+      index += 1;
+    } else if (arg.length > 0 && !arg.startsWith("-")) {
+      target = arg;
+    }
+  }
+  return target ? path.resolve(launch.workingDirectory, target) : null;
+}
+
+/**
+ * Turns a launch into the deep link it should open, or null when it only
+ * needs the app focused. A bare folder argument (`cowork ~/project`) becomes
+ * `cowork://open?path=...` so it goes through the same confirmation as links.
+ * Exported threads are not links; see `resolveLaunchTranscriptFile`.
+ */
+export function resolveLaunchDeepLink(launch: SingleInstanceLaunchData): string | null {
+  const deepLinkUrl = findDeepLinkArg(launch.args);
+  if (deepLinkUrl) {
+    return deepLinkUrl;
+  }
+  const target = launchPathArg(launch);
+  if (!target || isTranscriptExportPath(target)) {
+    return null;
+  }
+  return openPathDeepLink(target);
+}
+
+/**
+ * Returns the exported thread a launch asks to open, as Windows and Linux
+ * pass "open with" files on the command line. Deep links take precedence.
+ */
+export function resolveLaunchTranscriptFile(launch: SingleInstanceLaunchData): string | null {
+  if (findDeepLinkArg(launch.args)) {
+    return null;
+  }
+  const target = launchPathArg(launch);
+  return target && isTranscriptExportPath(target) ? target : null;
+}
+
+/**
+ * Chromium can add or reorder switches in the argv it hands to
+ * `second-instance`, so prefer the launch data the second instance sent with
+ * its lock request and fall back to the raw argv only when it is missing.
+ */
+export function resolveSecondInstanceLaunch(
+  argv: readonly string[],
+  workingDirectory: string,
+  additionalData: unknown,
+  options: LaunchArgsOptions = {},
+): SingleInstanceLaunchData {
+  return isLaunchData(additionalData)
+    ? additionalData
+    : createSingleInstanceLaunchData(argv, workingDirectory, options);
+}
@@ -0,0 +1,153 @@
+import type { PowerMonitor } from "electron";
+
+/** Wall-clock time that outruns the monotonic clock by more than this was spent asleep. */
+const DEFAULT_SLEEP_DRIFT_THRESHOLD_MS = 2_000;
+const MAX_RECORDED_SLEEPS = 32;
+
+type PowerSource = Pick<PowerMonitor, "on" | "off">;
+
+type SleepInterval = { start: number; end: number };
+
+type SleepClockOptions = {
+  now?: () => number;
+  /**
+   * A clock that stops while the machine sleeps. `performance.now()` is backed by
+   * `mach_absolute_time` on macOS and `CLOCK_MONOTONIC` on Linux, both of which do.
+   */
+  monotonicNow?: () => number;
+  driftThresholdMs?: number;
+};
+
+/**
+ * Tells time the machine was awake apart from time it spent asleep, so startup
+ * timeouts and health checks do not fail because the lid was closed. Sleep is
+ * noticed from `suspend`/`resume` power events when attached, and otherwise
+ * from the wall clock jumping ahead of the monotonic clock between samples,
+ * which also covers platforms or sleeps that never emit the events.
+ */
+export class SleepClock {
+  private readonly sleeps: SleepInterval[] = [];
+  private readonly now: () => number;
+  private readonly monotonicNow: () => number;
+  private readonly driftThresholdMs: number;
+  private lastWall: number;
+  private lastMonotonic: number;
+  private asleepSince: number | null = null;
+  private detachPowerSource: (() => void) | null = null;
+
+  constructor(options: SleepClockOptions = {}) {
+    this.now = options.now ?? Date.now;
+    this.monotonicNow = options.monotonicNow ?? (() => performance.now());
+    this.driftThresholdMs = options.driftThresholdMs ?? DEFAULT_SLEEP_DRIFT_THRESHOLD_MS;
+    this.lastWall = this.now();
+    this.lastMonotonic = this.monotonicNow();
+  }
+
+  /** Follows `suspend`/`resume` from `power` in addition to clock drift. */
+  attach(power: PowerSource): void {
+    if (this.detachPowerSource) {
+      return;
+    }
+    const onSuspend = () => this.markSuspended();
+    const onResume = () => this.markResumed();
+    power.on("suspend", onSuspend);
+    power.on("resume", onResume);
+    this.detachPowerSource = () => {
+      power.off("suspend", onSuspend);
+      power.off("resume", onResume);
+    };
+  }
+
+  dispose(): void {
+    this.detachPowerSource?.();
+    this.detachPowerSource = null;
+  }
+
+  markSuspended(): void {
+    this.asleepSince ??= this.now();
+  }
+
+  markResumed(): void {
+    // Sampled while still marked asleep, so the drift is not counted a second time.
+    this.sample();
+    const since = this.asleepSince;
+    this.asleepSince = null;
+    if (since !== null) {
+      this.recordSleep(since, this.now());
+    }
+  }
+
+  /** Milliseconds spent asleep after wall-clock time `since`. */
+  sleptSince(since: number): number {
+    this.sample();
+    let slept = 0;
+    for (const sleep of this.sleeps) {
+      const start = Math.max(sleep.start, since);
+      if (sleep.end > start) {
+        slept += sleep.end - start;
+      }
+    }
+    return slept;
+  }
+
+  /** Milliseconds the machine was awake since wall-clock time `since`. */
+  awakeSince(since: number): number {
+    return Math.max(0, this.now() - since - this.sleptSince(since));
+  }
+
+  /** Whether the machine woke from sleep within the last `withinMs` milliseconds. */
+  wokeRecently(withinMs: number): boolean {
+    this.sample();
+    const last = this.sleeps.at(-1);
+    return last !== undefined && this.now() - last.end < withinMs;
+  }
+
+  currentTime(): number {
+    return this.now();
+  }
+
+  private sample(): void {
+    const wall = this.now();
+    const monotonic = this.monotonicNow();
+    const drift = wall - this.lastWall - (monotonic - this.lastMonotonic);
+    this.lastWall = wall;
+    this.lastMonotonic = monotonic;
+    if (drift > this.driftThresholdMs && this.asleepSince === null) {
+      this.recordSleep(wall - drift, wall);
+    }
+  }
+
+  private recordSleep(start: number, end: number): void {
+    const last = this.sleeps.at(-1);
+    if (last && start <= last.end) {
+      last.end = Math.max(last.end, end);
+    } else {
+      this.sleeps.push({ start, end });
+    }
+    if (this.sleeps.length > MAX_RECORDED_SLEEPS) {
+      this.sleeps.shift();
+    }
+  }
+}
+
+/**
+ * Like `setTimeout`, but time spent asleep does not count toward `ms`: a timer
+ * that comes due after a sleep re-arms for the awake time it still owes.
+ * Returns a function that cancels it.
+ */
+export function setAwakeTimeout(clock: SleepClock, callback: () => void, ms: number): () => void {
+  const startedAt = clock.currentTime();
+  let timer: ReturnType<typeof setTimeout>;
+  const arm = (delayMs: number) => {
+    timer = setTimeout(() => {
+      const remainingMs = ms - clock.awakeSince(startedAt);
+      if (remainingMs > 0) {
+        arm(remainingMs);
+        return;
+      }
+      callback();
+    }, delayMs);
+  };
+  arm(ms);
+  return () => clearTimeout(timer);
+}
@@ -0,0 +1,145 @@
+import { type FSWatcher, watch as watchFileSystem } from "node:fs";
+import path from "node:path";
+
+const DEFAULT_DEBOUNCE_MS = 300;
+/** Files whose edits change what the server runs; editor swap files and the like are skipped. */
+const SOURCE_EXTENSIONS = new Set([".ts", ".tsx", ".js", ".mjs", ".json"]);
+const MAX_REASON_PATHS = 3;
+
+type WatchFactory = (
+  rootPath: string,
+  listener: (eventType: string, filename: string | Buffer | null) => void,
+) => Pick<FSWatcher, "close">;
+
+export type SourceReloadEvent = {
+  serverId: string;
+  /** Human-readable cause, e.g. `src/server/index.ts changed`. */
+  reason: string;
+  /** Changed files relative to the watched directory, sorted. */
+  changedPaths: string[];
+};
+
+type SourceReloadWatcherOptions = {
+  /** The `src/server` directory source-mode servers run from. */
+  sourceDir: string;
+  /** Ids of the running servers that were started from `sourceDir`. */
+  getServerIds: () => string[];
+  /** Restarts one server; resolves false when it was no longer running. */
+  reloadServer: (serverId: string) => Promise<boolean>;
+  onReloaded?: (event: SourceReloadEvent) => void;
+  onError?: (error: unknown, serverId: string | null) => void;
+  debounceMs?: number;
+  watch?: WatchFactory;
+};
+
+function defaultWatchFactory(
+  rootPath: string,
+  listener: Parameters<WatchFactory>[1],
+): Pick<FSWatcher, "close"> {
+  return watchFileSystem(rootPath, { recursive: true }, listener);
+}
+
+function describeChanges(sourceDir: string, changedPaths: string[]): string {
+  const base = path.basename(sourceDir);
+  const named = changedPaths
+    .slice(0, MAX_REASON_PATHS)
+    .map((changed) => path.posix.join("src", base, changed.split(path.sep).join("/")));
+  const more = changedPaths.length - named.length;
+  return `${named.join(", ")}${more > 0 ? ` and ${more} more` : ""} changed`;
+}
+
+/**
+ * Dev-mode hot reload: watches the server source and, once edits settle for
+ * `debounceMs`, restarts every server running from it. Edits that land while
+ * a reload is in progress are batched into one more pass afterwards, so a
+ * burst of saves never restarts a server twice at once.
+ */
+export class SourceReloadWatcher {
+  private readonly debounceMs: number;
+  private readonly watchFactory: WatchFactory;
+  private readonly pendingPaths = new Set<string>();
+  private watcher: Pick<FSWatcher, "close"> | null = null;
+  private debounceTimer: ReturnType<typeof setTimeout> | null = null;
+  private inFlight: Promise<void> | null = null;
+
+  constructor(private readonly options: SourceReloadWatcherOptions) {
+    this.debounceMs = options.debounceMs ?? DEFAULT_DEBOUNCE_MS;
+    this.watchFactory = options.watch ?? defaultWatchFactory;
+  }
+
+  /** Starts watching; returns false when the directory cannot be watched. */
+  start(): boolean {
+    if (this.watcher) {
+      return true;
+    }
+    try {
+      this.watcher = this.watchFactory(this.options.sourceDir, (_eventType, filename) => {
+        this.queueChange(filename?.toString() ?? "");
+      });
+    } catch (error) {
+      this.options.onError?.(error, null);
+      return false;
+    }
+    return true;
+  }
+
+  dispose(): void {
+    if (this.debounceTimer) {
+      clearTimeout(this.debounceTimer);
+      this.debounceTimer = null;
+    }
+    this.pendingPaths.clear();
+    this.watcher?.close();
+    this.watcher = null;
+  }
+
+  /** Reloads servers for the changes seen so far; concurrent calls wait for the current pass. */
+  async flush(): Promise<void> {
+    while (this.inFlight) {
+      await this.inFlight;
+    }
+    const changedPaths = [...this.pendingPaths].sort();
+    this.pendingPaths.clear();
+    if (changedPaths.length === 0) {
+      return;
+    }
+    this.inFlight = this.reloadAll(changedPaths).finally(() => {
+      this.inFlight = null;
+    });
+    await this.inFlight;
+  }
+
+  private queueChange(filename: string): void {
+    const relativePath = path.normalize(filename.trim());
+    if (
+      !relativePath ||
+      relativePath.startsWith("..") ||
+      !SOURCE_EXTENSIONS.has(path.extname(relativePath))
+    ) {
+      return;
+    }
+    this.pendingPaths.add(relativePath);
+    if (this.debounceTimer) {
+      clearTimeout(this.debounceTimer);
+    }
+    this.debounceTimer = setTimeout(() => {
+      this.debounceTimer = null;
+      void this.flush();
+    }, this.debounceMs);
+  }
+
+  private async reloadAll(changedPaths: string[]): Promise<void> {
+    const reason = describeChanges(this.options.sourceDir, changedPaths);
+    await Promise.all(
+      this.options.getServerIds().map(async (serverId) => {
+        try {
+          if (await this.options.reloadServer(serverId)) {
+            this.options.onReloaded?.({ serverId, reason, changedPaths });
+          }
+        } catch (error) {
+          this.options.onError?.(error, serverId);
+        }
+      }),
+    );
+  }
+}
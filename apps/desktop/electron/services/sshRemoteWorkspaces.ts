@@ -0,0 +1,209 @@
+import { type ChildProcessByStdio, spawn } from "node:child_process";
+import type { Readable, Writable } from "node:stream";
+
+import type { SshWorkspaceTarget } from "../../src/app/remoteWorkspaces";
+import { allocateLoopbackPort, forwardedServerUrl } from "./loopbackPorts";
+
+const DEFAULT_SSH_COMMAND = "ssh";
+const DEFAULT_REMOTE_SERVER_COMMAND = "cowork-server";
+const REMOTE_SERVER_COMMAND_ENV = "COWORK_DESKTOP_REMOTE_SERVER_COMMAND";
+const DEFAULT_CONNECT_TIMEOUT_SECONDS = 15;
+const PROBE_TIMEOUT_MS = 30_000;
+const ENV_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+
+export type RemoteServerSession = ChildProcessByStdio<Writable, Readable, Readable>;
+
+/** A local port forwarded to the remote server; closing it ends the ssh process. */
+export type SshTunnel = {
+  url: string;
+  localPort: number;
+  /** Resolves once the forward is gone, whether closed here or dropped. */
+  closed: Promise<void>;
+  close: () => void;
+};
+
+type SshRemoteWorkspacesOptions = {
+  sshCommand?: string;
+  /** The sidecar on the remote host's PATH; `COWORK_DESKTOP_REMOTE_SERVER_COMMAND` overrides it. */
+  serverCommand?: string;
+  connectTimeoutSeconds?: number;
+  spawn?: typeof spawn;
+  allocateLocalPort?: () => Promise<number>;
+};
+
+/** Quotes `value` as one word for a POSIX shell. */
+export function shellQuote(value: string): string {
+  return `'${value.replace(/'/g, `'\\''`)}'`;
+}
+
+/**
+ * Script run with `sh -c` on the remote host, whatever its login shell.
+ * Environment values arrive on stdin, one per line, so tokens never show up in
+ * the remote process list. The sidecar is stopped when stdin closes, which
+ * happens once the local ssh process exits, so a dropped connection never
+ * leaves it running.
+ */
+export function buildRemoteServerScript(
+  serverCommand: string,
+  workspacePath: string,
+  args: readonly string[],
+  envNames: readonly string[],
+): string {
+  const lines: string[] = [];
+  for (const name of envNames) {
+    if (!ENV_NAME_PATTERN.test(name)) {
+      throw new Error(`Invalid environment variable name: ${name}`);
+    }
+    lines.push(`IFS= read -r ${name} || exit 70`, `export ${name}`);
+  }
+  lines.push(
+    `cd ${shellQuote(workspacePath)} || exit 66`,
+    `${[serverCommand, ...args].map(shellQuote).join(" ")} </dev/null &`,
+    "pid=$!",
+    `(cat >/dev/null; kill "$pid" 2>/dev/null) >/dev/null 2>&1 &`,
+    `wait "$pid"`,
+  );
+  return lines.join("\n");
+}
+
+function unbracketHost(host: string): string {
+  return host.startsWith("[") && host.endsWith("]") ? host.slice(1, -1) : host;
+}
+
+/**
+ * Runs workspace servers on other machines over the system `ssh` client,
+ * which keeps the user's keys, agent, and `~/.ssh/config` in play. A server
+ * is one ssh session running the sidecar plus one port forward to the port it
+ * reports; `ServerManager` owns both alongside its local server handles.
+ */
+export class SshRemoteWorkspaces {
+  private readonly sshCommand: string;
+  private readonly serverCommand: string;
+  private readonly connectTimeoutSeconds: number;
+  private readonly spawnImpl: typeof spawn;
+  private readonly allocateLocalPort: () => Promise<number>;
+
+  constructor(options: SshRemoteWorkspacesOptions = {}) {
+    this.sshCommand = options.sshCommand ?? DEFAULT_SSH_COMMAND;
+    this.serverCommand =
+      options.serverCommand ??
+      (process.env[REMOTE_SERVER_COMMAND_ENV]?.trim() || DEFAULT_REMOTE_SERVER_COMMAND);
+    this.connectTimeoutSeconds = options.connectTimeoutSeconds ?? DEFAULT_CONNECT_TIMEOUT_SECONDS;
+    this.spawnImpl = options.spawn ?? spawn;
+    this.allocateLocalPort = options.allocateLocalPort ?? allocateLoopbackPort;
+  }
+
+  /** Arguments selecting `target`, with `extra` options placed before the destination. */
+  sshArgs(target: SshWorkspaceTarget, extra: readonly string[] = []): string[] {
+    return [
+      "-o",
+      "BatchMode=yes",
+      "-o",
+      `ConnectTimeout=${this.connectTimeoutSeconds}`,
+      "-o",
+      "ServerAliveInterval=15",
+      "-o",
+      "ServerAliveCountMax=3",
+      ...(target.port !== null ? ["-p", String(target.port)] : []),
+      ...(target.user ? ["-l", target.user] : []),
+      ...extra,
+      "--",
+      unbracketHost(target.host),
+    ];
+  }
+
+  /** Fails unless the host is reachable without a prompt and the path is a directory. */
+  async probe(target: SshWorkspaceTarget): Promise<void> {
+    const child = this.spawnImpl(
+      this.sshCommand,
+      [...this.sshArgs(target, ["-T"]), `test -d ${shellQuote(target.path)}`],
+      { stdio: ["ignore", "ignore", "pipe"], windowsHide: true },
+    );
+    let stderr = "";
+    child.stderr.on("data", (chunk: Buffer | string) => {
+      stderr = `${stderr}${chunk.toString()}`.slice(-2_048);
+    });
+    const code = await new Promise<number | null>((resolve, reject) => {
+      const timer = setTimeout(() => {
+        child.kill();
+        reject(new Error(`Timed out connecting to ${target.host}`));
+      }, PROBE_TIMEOUT_MS);
+      child.once("error", (error) => {
+        clearTimeout(timer);
+        reject(error);
+      });
+      child.once("exit", (exitCode) => {
+        clearTimeout(timer);
+        resolve(exitCode);
+      });
+    });
+    if (code === 1) {
+      throw new Error(`Remote path is not a directory: ${target.path}`);
+    }
+    if (code !== 0) {
+      const detail = stderr.trim();
+      throw new Error(
+        `Could not connect to ${target.host} over SSH${detail ? `: ${detail}` : ""}`,
+      );
+    }
+  }
+
+  /** Starts the sidecar on the remote host; its stdout and stderr come back over the session. */
+  launchServer(
+    target: SshWorkspaceTarget,
+    opts: { args: readonly string[]; env: Readonly<Record<string, string>> },
+  ): RemoteServerSession {
+    const entries = Object.entries(opts.env);
+    for (const [name, value] of entries) {
+      if (/[\r\n]/.test(value)) {
+        throw new Error(`Environment value for ${name} cannot span lines`);
+      }
+    }
+    const script = buildRemoteServerScript(
+      this.serverCommand,
+      target.path,
+      opts.args,
+      entries.map(([name]) => name),
+    );
+    const command = `sh -c ${shellQuote(script)}`;
+    const child = this.spawnImpl(this.sshCommand, [...this.sshArgs(target, ["-T"]), command], {
+      stdio: ["pipe", "pipe", "pipe"],
+      windowsHide: true,
+    });
+    // Left open: the remote side stops the sidecar when it reaches end of input.
+    child.stdin.on("error", () => {});
+    child.stdin.write(entries.map(([, value]) => `${value}\n`).join(""));
+    return child;
+  }
+
+  /** Forwards a free loopback port to the host and port in `remoteUrl`. */
+  async openTunnel(target: SshWorkspaceTarget, remoteUrl: string): Promise<SshTunnel> {
+    const remote = new URL(remoteUrl);
+    const remotePort = Number(remote.port);
+    if (!Number.isInteger(remotePort) || remotePort <= 0) {
+      throw new Error(`Remote server reported no port: ${remoteUrl}`);
+    }
+    const localPort = await this.allocateLocalPort();
+    const forward = `127.0.0.1:${localPort}:${unbracketHost(remote.hostname)}:${remotePort}`;
+    const child = this.spawnImpl(
+      this.sshCommand,
+      this.sshArgs(target, ["-N", "-T", "-o", "ExitOnForwardFailure=yes", "-L", forward]),
+      { stdio: "ignore", windowsHide: true },
+    );
+    // A missing ssh binary surfaces as "error" with no "exit"; either ends the forward.
+    const closed = new Promise<void>((resolve) => {
+      child.once("error", () => resolve());
+      child.once("exit", () => resolve());
+    });
+    return {
+      url: forwardedServerUrl(remoteUrl, localPort),
+      localPort,
+      closed,
+      close: () => {
+        if (child.exitCode === null && child.signalCode === null) {
+          child.kill();
+        }
+      },
+    };
+  }
+}
@@ -0,0 +1,112 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+const DEFAULT_ACQUIRE_TIMEOUT_MS = 10_000;
+const DEFAULT_RETRY_DELAY_MS = 25;
+const SQLITE_BUSY = 5;
+
+/** The subset of `node:sqlite` (and `bun:sqlite`) the lock needs. */
+export type StateLockDatabase = {
+  exec(sql: string): unknown;
+  close(): unknown;
+};
+
+type StateFileLockOptions = {
+  lockPath: string;
+  openDatabase: (lockPath: string) => StateLockDatabase;
+  acquireTimeoutMs?: number;
+  retryDelayMs?: number;
+  now?: () => number;
+  sleep?: (ms: number) => Promise<void>;
+};
+
+function defaultSleep(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+// node:sqlite reports contention through `errcode`, bun:sqlite through `code`.
+function isBusy(error: unknown): boolean {
+  if (typeof error !== "object" || error === null) {
+    return false;
+  }
+  const { code, errcode } = error as { code?: unknown; errcode?: unknown };
+  if (typeof errcode === "number") {
+    return (errcode & 0xff) === SQLITE_BUSY;
+  }
+  return typeof code === "string" && code.startsWith("SQLITE_BUSY");
+}
+
+function closeQuietly(database: StateLockDatabase): void {
+  try {
+    database.close();
+  } catch {
+    // Closing releases the transaction natively; nothing else to undo.
+  }
+}
+
+/**
+ * Cross-process lock around state.json read-modify-write cycles, so a second
+ * app instance or the CLI cannot interleave its writes with ours.
+ *
+ * Same approach as `src/utils/fileLock`, which cannot load in the Electron
+ * main process: an open `BEGIN IMMEDIATE` transaction on a private SQLite file
+ * is the mutex, and the OS releases it if the holder exits.
+ */
+export class StateFileLock {
+  private readonly acquireTimeoutMs: number;
+  private readonly retryDelayMs: number;
+  private readonly now: () => number;
+  private readonly sleep: (ms: number) => Promise<void>;
+
+  constructor(private readonly options: StateFileLockOptions) {
+    this.acquireTimeoutMs = options.acquireTimeoutMs ?? DEFAULT_ACQUIRE_TIMEOUT_MS;
+    this.retryDelayMs = options.retryDelayMs ?? DEFAULT_RETRY_DELAY_MS;
+    this.now = options.now ?? Date.now;
+    this.sleep = options.sleep ?? defaultSleep;
+  }
+
+  async run<T>(fn: () => Promise<T>): Promise<T> {
+    const database = await this.acquire();
+    try {
+      return await fn();
+    } finally {
+      try {
+        database.exec("ROLLBACK");
+      } finally {
+        closeQuietly(database);
+      }
+    }
+  }
+
+  private async acquire(): Promise<StateLockDatabase> {
+    const { lockPath } = this.options;
+    await fs.mkdir(path.dirname(lockPath), { recursive: true, mode: 0o700 });
+    await (await fs.open(lockPath, "a", 0o600)).close();
+
+    const startedAt = this.now();
+    const database = this.options.openDatabase(lockPath);
+    try {
+      database.exec("PRAGMA busy_timeout = 0");
+      while (true) {
+        try {
+          database.exec("BEGIN IMMEDIATE");
+          return database;
+        } catch (error) {
+          if (!isBusy(error)) {
+            throw error;
+          }
+        }
+        const waitedMs = this.now() - startedAt;
+        if (waitedMs >= this.acquireTimeoutMs) {
+          throw new Error(
+            `Timed out acquiring state lock at ${lockPath} after ${Math.round(waitedMs)}ms`,
+          );
+        }
+        await this.sleep(Math.min(this.retryDelayMs, this.acquireTimeoutMs - waitedMs));
+      }
+    } catch (error) {
+      closeQuietly(database);
+      throw error;
+    }
+  }
+}
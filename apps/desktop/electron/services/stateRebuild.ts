@@ -0,0 +1,335 @@
+import crypto from "node:crypto";
+import os from "node:os";
+import path from "node:path";
+
+import type {
+  ThreadRecord,
+  ThreadTitleSource,
+  TranscriptEvent,
+  WorkspaceRecord,
+} from "../../src/app/types";
+import type {
+  StateRebuildReport,
+  StateRebuildThread,
+  StateRebuildWorkspace,
+} from "../../src/lib/desktopApi";
+
+const SERVER_START_LOG_MESSAGE = "workspace server start requested";
+const REDACTED_HOME_PREFIX = "[home]";
+const REDACTED_PATH_MARKER = /\[(?:local-path|workspace-path|local-user|redacted[^\]]*)\]/;
+const HEURISTIC_TITLE_MAX_LENGTH = 60;
+const FALLBACK_THREAD_TITLE = "Recovered thread";
+
+export type TranscriptInference = {
+  threadId: string;
+  workingDirectory: string | null;
+  sessionId: string | null;
+  title: string;
+  titleSource: ThreadTitleSource;
+  titleFrom: StateRebuildThread["titleFrom"];
+  createdAt: string;
+  lastMessageAt: string;
+  messageCount: number;
+};
+
+type RebuildInput = {
+  transcripts: Array<{ threadId: string; events: TranscriptEvent[] }>;
+  /** Workspace ids recovered from server.log, keyed by resolved workspace path. */
+  loggedWorkspaceIds: Map<string, string>;
+  existingWorkspaces: WorkspaceRecord[];
+  existingThreadIds: Set<string>;
+  /** Resolves a transcript working directory to the on-disk folder, or null when it is gone. */
+  resolveWorkspacePath: (workspacePath: string) => Promise<string | null>;
+  createId?: () => string;
+  now?: () => Date;
+};
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+function asNonEmptyString(value: unknown): string | null {
+  return typeof value === "string" && value.trim() ? value.trim() : null;
+}
+
+/**
+ * Maps workspace paths to the ids they were started under. server.log
+ * redacts the home directory to `[home]`, which is expanded again; paths that
+ * were redacted any further cannot be recovered and are ignored.
+ */
+export function parseServerLogWorkspaceIds(
+  raw: string,
+  homeDir: string = os.homedir(),
+): Map<string, string> {
+  const workspaceIds = new Map<string, string>();
+  for (const line of raw.split(/\r?\n/)) {
+    if (!line.includes(SERVER_START_LOG_MESSAGE)) {
+      continue;
+    }
+    let entry: unknown;
+    try {
+      entry = JSON.parse(line);
+    } catch {
+      continue;
+    }
+    if (!isRecord(entry) || entry.message !== SERVER_START_LOG_MESSAGE || !isRecord(entry.meta)) {
+      continue;
+    }
+    const workspaceId = asNonEmptyString(entry.meta.workspaceId);
+    let workspacePath = asNonEmptyString(entry.meta.workspacePath);
+    if (!workspaceId || !workspacePath) {
+      continue;
+    }
+    if (workspacePath.startsWith(REDACTED_HOME_PREFIX)) {
+      workspacePath = path.join(homeDir, workspacePath.slice(REDACTED_HOME_PREFIX.length));
+    }
+    if (REDACTED_PATH_MARKER.test(workspacePath) || !path.isAbsolute(workspacePath)) {
+      continue;
+    }
+    // Later starts win, matching the id the workspace had most recently.
+    workspaceIds.set(path.resolve(workspacePath), workspaceId);
+  }
+  return workspaceIds;
+}
+
+/** Collapses `text` onto one line, shortened to fit a sidebar row. */
+export function heuristicThreadTitle(text: string): string {
+  const singleLine = text.replace(/\s+/g, " ").trim();
+  return singleLine.length > HEURISTIC_TITLE_MAX_LENGTH
+    ? `${singleLine.slice(0, HEURISTIC_TITLE_MAX_LENGTH - 1).trimEnd()}…`
+    : singleLine;
+}
+
+/** Replays one transcript and infers the thread record fields it implies. */
+export function inferThreadFromTranscript(
+  threadId: string,
+  events: TranscriptEvent[],
+): TranscriptInference | null {
+  const first = events[0];
+  const last = events.at(-1);
+  if (!first || !last) {
+    return null;
+  }
+
+  let workingDirectory: string | null = null;
+  let sessionId: string | null = null;
+  let sessionInfo: { title: string; titleSource: ThreadTitleSource; createdAt: string } | null =
+    null;
+  let firstUserMessage: string | null = null;
+  let clientMessages = 0;
+  let serverMessages = 0;
+  for (const event of events) {
+    const payload = event.payload;
+    if (!isRecord(payload)) {
+      continue;
+    }
+    sessionId = asNonEmptyString(payload.sessionId) ?? sessionId;
+    if (payload.type === "user_message") {
+      const text = asNonEmptyString(payload.text);
+      if (event.direction === "client") {
+        clientMessages += 1;
+      } else {
+        serverMessages += 1;
+      }
+      firstUserMessage ??= text;
+      continue;
+    }
+    if (event.direction !== "server") {
+      continue;
+    }
+    if (
+      (payload.type === "server_hello" || payload.type === "config_updated") &&
+      isRecord(payload.config)
+    ) {
+      workingDirectory = asNonEmptyString(payload.config.workingDirectory) ?? workingDirectory;
+    } else if (payload.type === "session_info") {
+      const title = asNonEmptyString(payload.title);
+      if (title) {
+        const titleSource = payload.titleSource;
+        sessionInfo = {
+          title,
+          titleSource:
+            titleSource === "default" ||
+            titleSource === "model" ||
+            titleSource === "heuristic" ||
+            titleSource === "manual"
+              ? titleSource
+              : "default",
+          createdAt: asNonEmptyString(payload.createdAt) ?? sessionInfo?.createdAt ?? first.ts,
+        };
+      }
+    }
+  }
+
+  let title: Pick<TranscriptInference, "title" | "titleSource" | "titleFrom">;
+  if (sessionInfo) {
+    title = {
+      title: sessionInfo.title,
+      titleSource: sessionInfo.titleSource,
+      titleFrom: "sessionInfo",
+    };
+  } else if (firstUserMessage) {
+    title = {
+      title: heuristicThreadTitle(firstUserMessage),
+      titleSource: "heuristic",
+      titleFrom: "firstMessage",
+    };
+  } else {
+    title = { title: FALLBACK_THREAD_TITLE, titleSource: "default", titleFrom: "fallback" };
+  }
+  const createdAt = sessionInfo?.createdAt ?? first.ts;
+  return {
+    threadId,
+    workingDirectory,
+    sessionId,
+    ...title,
+    createdAt: Number.isNaN(Date.parse(createdAt)) ? first.ts : createdAt,
+    lastMessageAt: last.ts,
+    // The server echoes client messages; prefer the client side to avoid double counting.
+    messageCount: clientMessages || serverMessages,
+  };
+}
+
+function buildWorkspaceRecord(
+  id: string,
+  workspacePath: string,
+  threads: TranscriptInference[],
+): WorkspaceRecord {
+  const createdAt = threads.map((thread) => thread.createdAt).sort()[0] ?? "";
+  const lastOpenedAt = threads.map((thread) => thread.lastMessageAt).sort().at(-1) ?? createdAt;
+  return {
+    id,
+    name: path.basename(workspacePath) || workspacePath,
+    path: workspacePath,
+    workspaceKind: "project",
+    createdAt,
+    lastOpenedAt,
+    wsProtocol: "jsonrpc",
+    defaultEnableMcp: true,
+    defaultBackupsEnabled: false,
+    yolo: false,
+  };
+}
+
+/**
+ * Reconstructs workspace and thread records from transcripts. Existing records
+ * are never modified; threads already in state are counted but left alone, and
+ * every other transcript is either recovered or listed with the reason it was
+ * skipped.
+ */
+export async function rebuildRecordsFromTranscripts(input: RebuildInput): Promise<{
+  workspaces: WorkspaceRecord[];
+  threads: ThreadRecord[];
+  report: Omit<StateRebuildReport, "dryRun" | "applied" | "snapshotName">;
+}> {
+  const createId = input.createId ?? (() => crypto.randomUUID());
+  const now = input.now ?? (() => new Date());
+  const existingByPath = new Map(
+    input.existingWorkspaces.map((workspace) => [path.resolve(workspace.path), workspace]),
+  );
+  const skipped: StateRebuildReport["skipped"] = [];
+  const groups = new Map<
+    string,
+    { source: StateRebuildWorkspace["source"]; id: string; threads: TranscriptInference[] }
+  >();
+  const takenWorkspaceIds = new Set(input.existingWorkspaces.map((workspace) => workspace.id));
+  let alreadyPresent = 0;
+
+  for (const { threadId, events } of input.transcripts) {
+    if (input.existingThreadIds.has(threadId)) {
+      alreadyPresent += 1;
+      continue;
+    }
+    const inferred = inferThreadFromTranscript(threadId, events);
+    if (!inferred) {
+      skipped.push({ threadId, reason: "The transcript has no readable events." });
+      continue;
+    }
+    if (!inferred.workingDirectory) {
+      skipped.push({ threadId, reason: "The transcript never reported a working directory." });
+      continue;
+    }
+    const workspacePath = await input.resolveWorkspacePath(inferred.workingDirectory);
+    if (!workspacePath) {
+      skipped.push({
+        threadId,
+        reason: `The workspace folder no longer exists: ${inferred.workingDirectory}`,
+      });
+      continue;
+    }
+
+    let group = groups.get(workspacePath);
+    if (!group) {
+      const existing =
+        existingByPath.get(workspacePath) ??
+        existingByPath.get(path.resolve(inferred.workingDirectory));
+      const loggedId =
+        input.loggedWorkspaceIds.get(workspacePath) ??
+        input.loggedWorkspaceIds.get(path.resolve(inferred.workingDirectory));
+      group = existing
+        ? { source: "existing", id: existing.id, threads: [] }
+        : loggedId && !takenWorkspaceIds.has(loggedId)
+          ? { source: "serverLog", id: loggedId, threads: [] }
+          : { source: "generated", id: createId(), threads: [] };
+      takenWorkspaceIds.add(group.id);
+      groups.set(workspacePath, group);
+    }
+    group.threads.push(inferred);
+  }
+
+  const workspaces: WorkspaceRecord[] = [];
+  const threads: ThreadRecord[] = [];
+  const reportWorkspaces: StateRebuildWorkspace[] = [];
+  const reportThreads: StateRebuildThread[] = [];
+  for (const [workspacePath, group] of groups) {
+    const existing = input.existingWorkspaces.find((workspace) => workspace.id === group.id);
+    const workspace = existing ?? buildWorkspaceRecord(group.id, workspacePath, group.threads);
+    if (!existing) {
+      workspaces.push(workspace);
+    }
+    reportWorkspaces.push({
+      id: workspace.id,
+      name: workspace.name,
+      path: workspace.path,
+      source: group.source,
+      threadIds: group.threads.map((thread) => thread.threadId),
+    });
+    for (const inferred of group.threads) {
+      threads.push({
+        id: inferred.threadId,
+        workspaceId: group.id,
+        title: inferred.title,
+        titleSource: inferred.titleSource,
+        createdAt: inferred.createdAt,
+        lastMessageAt: inferred.lastMessageAt,
+        status: "disconnected",
+        sessionId: inferred.sessionId,
+        messageCount: inferred.messageCount,
+        lastEventSeq: 0,
+      });
+      reportThreads.push({
+        id: inferred.threadId,
+        workspaceId: group.id,
+        title: inferred.title,
+        titleFrom: inferred.titleFrom,
+        sessionId: inferred.sessionId,
+        messageCount: inferred.messageCount,
+        createdAt: inferred.createdAt,
+        lastMessageAt: inferred.lastMessageAt,
+      });
+    }
+  }
+
+  return {
+    workspaces,
+    threads,
+    report: {
+      scannedTranscripts: input.transcripts.length,
+      alreadyPresent,
+      workspaces: reportWorkspaces,
+      threads: reportThreads,
+      skipped,
+      generatedAt: now().toISOString(),
+    },
+  };
+}
@@ -0,0 +1,71 @@
+import type { StateSnapshotInfo } from "../../src/lib/desktopApi";
+
+const DEFAULT_STATE_SNAPSHOT_INTERVAL_MS = 15 * 60_000;
+
+type StateSnapshotSchedulerOptions = {
+  capture: () => Promise<StateSnapshotInfo | null>;
+  intervalMs?: number;
+  onError?: (error: unknown) => void;
+  setInterval?: (callback: () => void, ms: number) => ReturnType<typeof setInterval>;
+  clearInterval?: (handle: ReturnType<typeof setInterval>) => void;
+};
+
+/**
+ * Periodically snapshots state.json in the background. Runs one capture on
+ * start so the state loaded at launch is always recoverable.
+ */
+export class StateSnapshotScheduler {
+  private readonly capture: () => Promise<StateSnapshotInfo | null>;
+  private readonly intervalMs: number;
+  private readonly onError: (error: unknown) => void;
+  private readonly setIntervalImpl: NonNullable<StateSnapshotSchedulerOptions["setInterval"]>;
+  private readonly clearIntervalImpl: NonNullable<StateSnapshotSchedulerOptions["clearInterval"]>;
+  private timer: ReturnType<typeof setInterval> | null = null;
+  private inFlight: Promise<void> | null = null;
+
+  constructor(options: StateSnapshotSchedulerOptions) {
+    this.capture = options.capture;
+    this.intervalMs = Math.max(1_000, options.intervalMs ?? DEFAULT_STATE_SNAPSHOT_INTERVAL_MS);
+    this.onError = options.onError ?? (() => {});
+    this.setIntervalImpl = options.setInterval ?? setInterval;
+    this.clearIntervalImpl = options.clearInterval ?? clearInterval;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+    void this.runOnce();
+    this.timer = this.setIntervalImpl(() => {
+      void this.runOnce();
+    }, this.intervalMs);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  async runOnce(): Promise<void> {
+    if (this.inFlight) {
+      return await this.inFlight;
+    }
+    this.inFlight = this.captureSafely();
+    try {
+      await this.inFlight;
+    } finally {
+      this.inFlight = null;
+    }
+  }
+
+  private async captureSafely(): Promise<void> {
+    try {
+      await this.capture();
+    } catch (error) {
+      this.onError(error);
+    }
+  }
+
+  dispose(): void {
+    if (this.timer) {
+      this.clearIntervalImpl(this.timer);
+      this.timer = null;
+    }
+  }
+}
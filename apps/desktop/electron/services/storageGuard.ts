@@ -0,0 +1,101 @@
+import fs from "node:fs/promises";
+
+const BYTES_PER_MB = 1024 * 1024;
+// Transcript appends ask on every batch; a few seconds of staleness keeps
+// statfs off the hot path without letting a filling disk go unnoticed.
+const DEFAULT_CACHE_MS = 5_000;
+
+export type StorageWriteOperation = "save_state" | "transcript_append" | "export";
+
+export type DiskSpaceCheck = {
+  /** Null when the platform cannot report free space; the guard then lets writes through. */
+  freeBytes: number | null;
+  minFreeBytes: number;
+  low: boolean;
+};
+
+type StatFs = (target: string) => Promise<{ bavail: number; bsize: number }>;
+
+type StorageGuardOptions = {
+  /** Current `minFreeDiskMb` setting; 0 turns the guard off. */
+  getMinFreeMb: () => Promise<number>;
+  statfs?: StatFs;
+  now?: () => number;
+  cacheMs?: number;
+};
+
+const OPERATION_LABELS: Record<StorageWriteOperation, string> = {
+  save_state: "save app state",
+  transcript_append: "write transcripts",
+  export: "export the file",
+};
+
+function formatMb(bytes: number): string {
+  return `${Math.floor(bytes / BYTES_PER_MB)} MB`;
+}
+
+export class LowDiskSpaceError extends Error {
+  readonly code = "low_disk_space";
+
+  constructor(
+    readonly operation: StorageWriteOperation,
+    readonly check: DiskSpaceCheck,
+  ) {
+    super(
+      `Not enough disk space to ${OPERATION_LABELS[operation]}: ` +
+        `${formatMb(check.freeBytes ?? 0)} free, ${formatMb(check.minFreeBytes)} required`,
+    );
+    this.name = "LowDiskSpaceError";
+  }
+}
+
+/** Checks free space on a volume against the `minFreeDiskMb` setting before large writes. */
+export class StorageGuard {
+  private readonly getMinFreeMb: () => Promise<number>;
+  private readonly statfs: StatFs;
+  private readonly now: () => number;
+  private readonly cacheMs: number;
+  private readonly cache = new Map<string, { checkedAt: number; freeBytes: number | null }>();
+
+  constructor(options: StorageGuardOptions) {
+    this.getMinFreeMb = options.getMinFreeMb;
+    this.statfs = options.statfs ?? (async (target) => await fs.statfs(target));
+    this.now = options.now ?? Date.now;
+    this.cacheMs = options.cacheMs ?? DEFAULT_CACHE_MS;
+  }
+
+  /** Free space on the volume holding `targetPath`, cached unless `refresh` is set. */
+  async check(targetPath: string, opts: { refresh?: boolean } = {}): Promise<DiskSpaceCheck> {
+    const minFreeBytes = Math.max(0, await this.getMinFreeMb()) * BYTES_PER_MB;
+    if (minFreeBytes === 0) {
+      return { freeBytes: null, minFreeBytes, low: false };
+    }
+    const freeBytes = await this.readFreeBytes(targetPath, opts.refresh === true);
+    return { freeBytes, minFreeBytes, low: freeBytes !== null && freeBytes < minFreeBytes };
+  }
+
+  /** Throws `LowDiskSpaceError` when the volume holding `targetPath` is below the threshold. */
+  async assertWritable(operation: StorageWriteOperation, targetPath: string): Promise<void> {
+    const check = await this.check(targetPath, { refresh: true });
+    if (check.low) {
+      throw new LowDiskSpaceError(operation, check);
+    }
+  }
+
+  private async readFreeBytes(targetPath: string, refresh: boolean): Promise<number | null> {
+    const cached = this.cache.get(targetPath);
+    if (!refresh && cached && this.now() - cached.checkedAt < this.cacheMs) {
+      return cached.freeBytes;
+    }
+    let freeBytes: number | null;
+    try {
+      const stats = await this.statfs(targetPath);
+      freeBytes = stats.bavail * stats.bsize;
+    } catch {
+      // Missing directories and platforms without statfs should not block writes.
+      freeBytes = null;
+    }
+    this.cache.set(targetPath, { checkedAt: this.now(), freeBytes });
+    return freeBytes;
+  }
+}
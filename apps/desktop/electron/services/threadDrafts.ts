@@ -0,0 +1,154 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type {
+  SaveThreadDraftInput,
+  ThreadDraft,
+  ThreadDraftAttachment,
+} from "../../src/lib/desktopApi";
+import { assertSafeId } from "./validation";
+
+const DEFAULT_DRAFT_DEBOUNCE_MS = 500;
+
+type DraftFile = {
+  version: 1;
+  draft: ThreadDraft;
+};
+
+type ThreadDraftStoreOptions = {
+  directory: string;
+  debounceMs?: number;
+  now?: () => Date;
+  onError?: (threadId: string, error: unknown) => void;
+};
+
+type PendingDraft = {
+  draft: ThreadDraft | null;
+  timer: ReturnType<typeof setTimeout>;
+};
+
+function parseDraftFile(value: unknown, threadId: string): ThreadDraft | null {
+  const draft = (value as { draft?: Partial<ThreadDraft> } | null)?.draft;
+  if (
+    draft?.threadId !== threadId ||
+    typeof draft.content !== "string" ||
+    typeof draft.updatedAt !== "string"
+  ) {
+    return null;
+  }
+  const attachments = Array.isArray(draft.attachments)
+    ? draft.attachments.filter(
+        (attachment): attachment is ThreadDraftAttachment =>
+          typeof attachment?.name === "string",
+      )
+    : [];
+  return { threadId, content: draft.content, attachments, updatedAt: draft.updatedAt };
+}
+
+/**
+ * Half-written prompts, one file per thread, so they survive restarts and
+ * window switches without going through `state.json`. Saves are debounced per
+ * thread while the user types; until a save reaches disk, `load` answers from
+ * memory so a window that switches back straight away still sees it.
+ */
+export class ThreadDraftStore {
+  private readonly pending = new Map<string, PendingDraft>();
+  private readonly writes = new Map<string, Promise<void>>();
+  private readonly debounceMs: number;
+
+  constructor(private readonly options: ThreadDraftStoreOptions) {
+    this.debounceMs = options.debounceMs ?? DEFAULT_DRAFT_DEBOUNCE_MS;
+  }
+
+  /** Empty content with no attachments clears the draft. Returns what will be written. */
+  save(input: SaveThreadDraftInput): ThreadDraft | null {
+    assertSafeId(input.threadId, "threadId");
+    const attachments = input.attachments ?? [];
+    const draft =
+      input.content.trim() || attachments.length > 0
+        ? {
+            threadId: input.threadId,
+            content: input.content,
+            attachments,
+            updatedAt: (this.options.now?.() ?? new Date()).toISOString(),
+          }
+        : null;
+    const existing = this.pending.get(input.threadId);
+    if (existing) {
+      clearTimeout(existing.timer);
+    }
+    this.pending.set(input.threadId, {
+      draft,
+      timer: setTimeout(() => this.write(input.threadId), this.debounceMs),
+    });
+    return draft;
+  }
+
+  async load(threadId: string): Promise<ThreadDraft | null> {
+    assertSafeId(threadId, "threadId");
+    const pending = this.pending.get(threadId);
+    if (pending) {
+      return pending.draft;
+    }
+    await this.writes.get(threadId);
+    let raw: string;
+    try {
+      raw = await fs.readFile(this.filePath(threadId), "utf8");
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+        return null;
+      }
+      throw error;
+    }
+    try {
+      return parseDraftFile(JSON.parse(raw), threadId);
+    } catch {
+      return null;
+    }
+  }
+
+  /** Writes every pending draft now; used before quitting. */
+  async flush(): Promise<void> {
+    for (const [threadId, { timer }] of this.pending) {
+      clearTimeout(timer);
+      this.write(threadId);
+    }
+    await Promise.all([...this.writes.values()]);
+  }
+
+  private filePath(threadId: string): string {
+    return path.join(this.options.directory, `${threadId}.json`);
+  }
+
+  /** Chains onto the thread's previous write so an older draft never lands last. */
+  private write(threadId: string): void {
+    const pending = this.pending.get(threadId);
+    if (!pending) {
+      return;
+    }
+    this.pending.delete(threadId);
+    const previous = this.writes.get(threadId) ?? Promise.resolve();
+    const next = previous
+      .then(() => this.persist(threadId, pending.draft))
+      .catch((error: unknown) => {
+        this.options.onError?.(threadId, error);
+      })
+      .finally(() => {
+        if (this.writes.get(threadId) === next) {
+          this.writes.delete(threadId);
+        }
+      });
+    this.writes.set(threadId, next);
+  }
+
+  private async persist(threadId: string, draft: ThreadDraft | null): Promise<void> {
+    if (!draft) {
+      await fs.rm(this.filePath(threadId), { force: true });
+      return;
+    }
+    await fs.mkdir(this.options.directory, { recursive: true });
+    const file: DraftFile = { version: 1, draft };
+    await writeFileAtomic(this.filePath(threadId), `${JSON.stringify(file)}\n`);
+  }
+}
@@ -0,0 +1,206 @@
+import { createHash } from "node:crypto";
+import path from "node:path";
+
+import type { FeedItem } from "../../src/app/types";
+import type { ThreadFileChange, ThreadFileChanges } from "../../src/lib/desktopApi";
+
+type ToolFeedItem = Extract<FeedItem, { kind: "tool" }>;
+
+type FileChangeDelta = {
+  path: string;
+  hunkCount: number;
+  linesAdded: number;
+  linesRemoved: number;
+  /** Set by whole-file writes; any other change leaves the final content unknown. */
+  contentHash?: string;
+};
+
+function asRecord(value: unknown): Record<string, unknown> | null {
+  return typeof value === "object" && value !== null && !Array.isArray(value)
+    ? (value as Record<string, unknown>)
+    : null;
+}
+
+function asNonEmptyString(value: unknown): string | null {
+  return typeof value === "string" && value.trim() ? value : null;
+}
+
+function countLines(text: string): number {
+  if (!text) {
+    return 0;
+  }
+  const lines = text.split(/\r?\n/);
+  return text.endsWith("\n") ? lines.length - 1 : lines.length;
+}
+
+function sha256(text: string): string {
+  return createHash("sha256").update(text, "utf8").digest("hex");
+}
+
+function diffHeaderPath(line: string): string | null {
+  const raw = line.slice(4).split("\t")[0]?.trim() ?? "";
+  if (!raw || raw === "/dev/null") {
+    return null;
+  }
+  return raw.replace(/^[ab]\//, "");
+}
+
+/** Splits a unified diff into per-file hunk and line counts. */
+export function parseUnifiedDiff(
+  diff: string,
+  fallbackPath: string | null = null,
+): FileChangeDelta[] {
+  const deltas: FileChangeDelta[] = [];
+  let current: FileChangeDelta | null = null;
+  let removedPath: string | null = null;
+  const start = (filePath: string | null): FileChangeDelta | null => {
+    if (!filePath) {
+      return null;
+    }
+    const delta: FileChangeDelta = { path: filePath, hunkCount: 0, linesAdded: 0, linesRemoved: 0 };
+    deltas.push(delta);
+    return delta;
+  };
+
+  for (const line of diff.split(/\r?\n/)) {
+    if (line.startsWith("--- ")) {
+      removedPath = diffHeaderPath(line);
+      continue;
+    }
+    if (line.startsWith("+++ ")) {
+      current = start(diffHeaderPath(line) ?? removedPath);
+      continue;
+    }
+    if (line.startsWith("@@")) {
+      current ??= start(fallbackPath);
+      if (current) {
+        current.hunkCount += 1;
+      }
+      continue;
+    }
+    if (!current) {
+      continue;
+    }
+    if (line.startsWith("+")) {
+      current.linesAdded += 1;
+    } else if (line.startsWith("-")) {
+      current.linesRemoved += 1;
+    }
+  }
+  return deltas;
+}
+
+/** Codex reports file changes as a patch string, or a list of per-file entries. */
+function fileChangeToolDeltas(item: ToolFeedItem): FileChangeDelta[] {
+  const result = item.result;
+  if (typeof result === "string") {
+    return parseUnifiedDiff(result);
+  }
+  const entries = Array.isArray(result) ? result : (asRecord(result)?.changes ?? null);
+  if (Array.isArray(entries)) {
+    return entries.flatMap((entry) => {
+      const record = asRecord(entry);
+      const filePath = asNonEmptyString(record?.path);
+      const diff = asNonEmptyString(record?.diff) ?? asNonEmptyString(record?.patch);
+      if (diff) {
+        return parseUnifiedDiff(diff, filePath);
+      }
+      return filePath ? [{ path: filePath, hunkCount: 0, linesAdded: 0, linesRemoved: 0 }] : [];
+    });
+  }
+  const paths = asRecord(item.args)?.paths;
+  return (Array.isArray(paths) ? paths : [paths])
+    .map(asNonEmptyString)
+    .filter((filePath): filePath is string => filePath !== null)
+    .map((filePath) => ({ path: filePath, hunkCount: 0, linesAdded: 0, linesRemoved: 0 }));
+}
+
+function toolDeltas(item: ToolFeedItem): FileChangeDelta[] {
+  const args = asRecord(item.args);
+  const filePath = asNonEmptyString(args?.filePath) ?? asNonEmptyString(args?.path);
+  switch (item.name.toLowerCase()) {
+    case "write": {
+      const content = typeof args?.content === "string" ? args.content : null;
+      if (!filePath || content === null) {
+        return [];
+      }
+      return [
+        {
+          path: filePath,
+          hunkCount: 1,
+          linesAdded: countLines(content),
+          linesRemoved: 0,
+          ...(args?.mode === "append" ? {} : { contentHash: sha256(content) }),
+        },
+      ];
+    }
+    case "edit": {
+      if (!filePath || typeof args?.oldString !== "string") {
+        return [];
+      }
+      return [
+        {
+          path: filePath,
+          hunkCount: 1,
+          linesAdded: countLines(typeof args.newString === "string" ? args.newString : ""),
+          linesRemoved: countLines(args.oldString),
+        },
+      ];
+    }
+    case "filechange":
+      return fileChangeToolDeltas(item);
+    default:
+      return [];
+  }
+}
+
+/**
+ * Rolls the successful file-editing tool calls in a thread's feed up into one
+ * entry per file, resolving relative paths against the workspace.
+ */
+export function summarizeThreadFileChanges(
+  threadId: string,
+  feed: FeedItem[],
+  workspacePath: string | null,
+): ThreadFileChanges {
+  const byPath = new Map<string, ThreadFileChange>();
+  for (const item of feed) {
+    if (item.kind !== "tool" || item.state !== "output-available") {
+      continue;
+    }
+    const cwd = asNonEmptyString(asRecord(item.args)?.cwd) ?? workspacePath;
+    for (const delta of toolDeltas(item)) {
+      const absolutePath =
+        path.isAbsolute(delta.path) || !cwd
+          ? path.normalize(delta.path)
+          : path.resolve(cwd, delta.path);
+      const relative = workspacePath ? path.relative(workspacePath, absolutePath) : "";
+      const entry = byPath.get(absolutePath) ?? {
+        path: absolutePath,
+        displayPath:
+          relative && !relative.startsWith("..") && !path.isAbsolute(relative)
+            ? relative
+            : absolutePath,
+        changeCount: 0,
+        hunkCount: 0,
+        linesAdded: 0,
+        linesRemoved: 0,
+        finalContentHash: null,
+        lastChangedAt: item.ts,
+      };
+      entry.changeCount += 1;
+      entry.hunkCount += delta.hunkCount;
+      entry.linesAdded += delta.linesAdded;
+      entry.linesRemoved += delta.linesRemoved;
+      entry.finalContentHash = delta.contentHash ?? null;
+      entry.lastChangedAt = item.completedAt ?? item.ts;
+      byPath.set(absolutePath, entry);
+    }
+  }
+  const files = [...byPath.values()].sort(
+    (left, right) =>
+      right.lastChangedAt.localeCompare(left.lastChangedAt) ||
+      left.displayPath.localeCompare(right.displayPath),
+  );
+  return { threadId, files };
+}
@@ -0,0 +1,201 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type { TranscriptEvent } from "../../src/app/types";
+import type { AnnotateEventInput, TranscriptAnnotation } from "../../src/lib/desktopApi";
+import { assertSafeId } from "./validation";
+
+const MAX_NOTE_LENGTH = 4_000;
+const MAX_TAGS = 20;
+
+type AnnotationsFile = {
+  version: 1;
+  annotations: TranscriptAnnotation[];
+};
+
+type TranscriptAnnotationStoreOptions = {
+  directory: string;
+  readTranscript: (threadId: string) => Promise<TranscriptEvent[]>;
+  now?: () => Date;
+};
+
+function normalizeTags(tags: string[] = []): string[] {
+  const normalized = new Set<string>();
+  for (const tag of tags) {
+    const trimmed = tag.trim().toLowerCase();
+    if (trimmed) {
+      normalized.add(trimmed);
+    }
+  }
+  if (normalized.size > MAX_TAGS) {
+    throw new Error(`An annotation can have at most ${MAX_TAGS} tags`);
+  }
+  return [...normalized];
+}
+
+function parseAnnotationsFile(value: unknown, threadId: string): TranscriptAnnotation[] {
+  const annotations = (value as { annotations?: unknown } | null)?.annotations;
+  if (!Array.isArray(annotations)) {
+    return [];
+  }
+  return annotations.filter(
+    (annotation): annotation is TranscriptAnnotation =>
+      annotation?.threadId === threadId &&
+      Number.isInteger(annotation.eventIndex) &&
+      typeof annotation.note === "string" &&
+      Array.isArray(annotation.tags),
+  );
+}
+
+/**
+ * Notes and tags on transcript events, kept in one file per thread beside the
+ * transcripts so the JSONL itself is never rewritten. Events are addressed by
+ * their index in the transcript, which only ever grows. Annotations outlive a
+ * trashed thread so restoring it brings them back.
+ */
+export class TranscriptAnnotationStore {
+  private readonly locks = new Map<string, Promise<unknown>>();
+
+  constructor(private readonly options: TranscriptAnnotationStoreOptions) {}
+
+  async list(threadId: string): Promise<TranscriptAnnotation[]> {
+    return (await this.read(threadId)).sort((left, right) => left.eventIndex - right.eventIndex);
+  }
+
+  /** Sets the event's note and tags; an empty note with no tags removes the annotation. */
+  async annotate(input: AnnotateEventInput): Promise<TranscriptAnnotation | null> {
+    const note = input.note.trim();
+    if (note.length > MAX_NOTE_LENGTH) {
+      throw new Error(`Annotation notes are limited to ${MAX_NOTE_LENGTH} characters`);
+    }
+    const tags = normalizeTags(input.tags);
+    return await this.withLock(input.threadId, async () => {
+      const annotations = await this.read(input.threadId);
+      const index = annotations.findIndex((entry) => entry.eventIndex === input.eventIndex);
+      const existing = index >= 0 ? annotations[index] : undefined;
+      if (!note && tags.length === 0) {
+        if (existing) {
+          annotations.splice(index, 1);
+          await this.write(input.threadId, annotations);
+        }
+        return null;
+      }
+
+      let eventTs = existing?.eventTs;
+      if (!eventTs) {
+        const events = await this.options.readTranscript(input.threadId);
+        const event = events[input.eventIndex];
+        if (!event) {
+          throw new Error(
+            `Thread ${input.threadId} has no event ${input.eventIndex} (it has ${events.length})`,
+          );
+        }
+        eventTs = event.ts;
+      }
+      const now = (this.options.now?.() ?? new Date()).toISOString();
+      const annotation: TranscriptAnnotation = {
+        threadId: input.threadId,
+        eventIndex: input.eventIndex,
+        eventTs,
+        note,
+        tags,
+        createdAt: existing?.createdAt ?? now,
+        updatedAt: now,
+      };
+      if (existing) {
+        annotations[index] = annotation;
+      } else {
+        annotations.push(annotation);
+      }
+      await this.write(input.threadId, annotations);
+      return annotation;
+    });
+  }
+
+  /** Overwrites a thread's annotations, dropping malformed entries; used by app data import. */
+  async replace(threadId: string, annotations: TranscriptAnnotation[]): Promise<void> {
+    const valid = parseAnnotationsFile({ annotations }, threadId);
+    await this.withLock(threadId, async () => {
+      await this.write(threadId, valid);
+    });
+  }
+
+  /**
+   * Re-addresses the target's annotations after a merge rewrote its transcript
+   * and copies the source's over, using the merge's old-to-new index maps. The
+   * source keeps its own copy in case it is restored from the trash.
+   */
+  async merge(
+    sourceThreadId: string,
+    targetThreadId: string,
+    eventIndexes: { target: number[]; source: number[] },
+  ): Promise<void> {
+    const moved = (annotations: TranscriptAnnotation[], indexes: number[]) =>
+      annotations.flatMap((annotation) => {
+        const eventIndex = indexes[annotation.eventIndex];
+        return eventIndex === undefined
+          ? []
+          : [{ ...annotation, threadId: targetThreadId, eventIndex }];
+      });
+    const source = await this.withLock(sourceThreadId, () => this.read(sourceThreadId));
+    await this.withLock(targetThreadId, async () => {
+      const annotations = [
+        ...moved(await this.read(targetThreadId), eventIndexes.target),
+        ...moved(source, eventIndexes.source),
+      ];
+      await this.write(targetThreadId, annotations);
+    });
+  }
+
+  async delete(threadId: string): Promise<void> {
+    await this.withLock(threadId, async () => {
+      await fs.rm(this.filePath(threadId), { force: true });
+    });
+  }
+
+  private filePath(threadId: string): string {
+    assertSafeId(threadId, "threadId");
+    return path.join(this.options.directory, `${threadId}.json`);
+  }
+
+  private async read(threadId: string): Promise<TranscriptAnnotation[]> {
+    let raw: string;
+    try {
+      raw = await fs.readFile(this.filePath(threadId), "utf8");
+    } catch (error) {
+      if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+        return [];
+      }
+      throw error;
+    }
+    try {
+      return parseAnnotationsFile(JSON.parse(raw), threadId);
+    } catch {
+      return [];
+    }
+  }
+
+  private async write(threadId: string, annotations: TranscriptAnnotation[]): Promise<void> {
+    if (annotations.length === 0) {
+      await fs.rm(this.filePath(threadId), { force: true });
+      return;
+    }
+    await fs.mkdir(this.options.directory, { recursive: true });
+    const file: AnnotationsFile = { version: 1, annotations };
+    await writeFileAtomic(this.filePath(threadId), `${JSON.stringify(file, null, 2)}\n`);
+  }
+
+  private async withLock<T>(threadId: string, fn: () => Promise<T>): Promise<T> {
+    const previous = this.locks.get(threadId) ?? Promise.resolve();
+    const next = previous.catch(() => {}).then(fn);
+    this.locks.set(threadId, next);
+    try {
+      return await next;
+    } finally {
+      if (this.locks.get(threadId) === next) {
+        this.locks.delete(threadId);
+      }
+    }
+  }
+}
@@ -0,0 +1,410 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import zlib from "node:zlib";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type {
+  BackupCredentials,
+  BackupSyncConfig,
+  BackupSyncResult,
+  BackupTarget,
+  ConfigureBackupSyncInput,
+  OperationProgress,
+  SaveBackupCredentialsResult,
+} from "../../src/lib/desktopApi";
+import { type BackupStore, createBackupStore } from "./backupStores";
+import type { PersistenceService } from "./persistence";
+import type { TranscriptKeychain } from "./transcriptEncryption";
+
+const PRIVATE_FILE_MODE = 0o600;
+/** Chunks close at the first line boundary past this many bytes of JSONL. */
+const CHUNK_TARGET_BYTES = 512 * 1024;
+/** How many fresh generations a sync tries when a segment key is already taken. */
+const MAX_GENERATION_ATTEMPTS = 3;
+const SEGMENT_OFFSET_DIGITS = 10;
+
+type SyncedTranscript = {
+  /** Bumped whenever the local transcript no longer starts with what was uploaded. */
+  generation: number;
+  /** Lines of the current generation that are already in the backup. */
+  lines: number;
+  /** sha256 over those lines, to notice a transcript rewritten under them. */
+  prefixHash: string;
+  /** The file as last synced, so unchanged transcripts are skipped without being read. */
+  sizeBytes: number;
+  modifiedAtMs: number;
+};
+
+type BackupSyncStateFile = {
+  version: 1;
+  deviceId: string;
+  target: BackupTarget | null;
+  credentialsRef: string | null;
+  lastSyncedAt: string | null;
+  /** Hash of the last uploaded state snapshot. */
+  stateHash: string | null;
+  transcripts: Record<string, SyncedTranscript>;
+};
+
+type BackupCredentialsFile = {
+  version: 1;
+  /** Credentials by reference, each wrapped by the OS keychain via `safeStorage`. */
+  entries: Record<string, string>;
+};
+
+type TranscriptBackupServiceOptions = {
+  stateFilePath: string;
+  credentialsFilePath: string;
+  keychain: TranscriptKeychain;
+  persistence: Pick<PersistenceService, "loadState" | "listTranscriptFiles" | "readTranscript">;
+  createStore?: (
+    target: BackupTarget,
+    credentials: BackupCredentials,
+    signal?: AbortSignal,
+  ) => BackupStore;
+  now?: () => Date;
+  createId?: () => string;
+};
+
+type SyncOptions = {
+  signal?: AbortSignal;
+  onProgress?: (progress: OperationProgress) => void;
+};
+
+type ChunkRef = { sha256: string; lines: number; bytes: number };
+
+function sha256Hex(data: string | Uint8Array): string {
+  return crypto.createHash("sha256").update(data).digest("hex");
+}
+
+function hashLines(lines: string[], count = lines.length): string {
+  const hash = crypto.createHash("sha256");
+  for (let index = 0; index < count; index += 1) {
+    hash.update(lines[index] as string).update("\n");
+  }
+  return hash.digest("hex");
+}
+
+function splitIntoChunks(lines: string[]): string[][] {
+  const chunks: string[][] = [];
+  let current: string[] = [];
+  let bytes = 0;
+  for (const line of lines) {
+    current.push(line);
+    bytes += Buffer.byteLength(line) + 1;
+    if (bytes >= CHUNK_TARGET_BYTES) {
+      chunks.push(current);
+      current = [];
+      bytes = 0;
+    }
+  }
+  if (current.length > 0) {
+    chunks.push(current);
+  }
+  return chunks;
+}
+
+function isNotFound(error: unknown): boolean {
+  return (error as NodeJS.ErrnoException | null)?.code === "ENOENT";
+}
+
+function sameTarget(left: BackupTarget | null, right: BackupTarget | null): boolean {
+  return JSON.stringify(left) === JSON.stringify(right);
+}
+
+/**
+ * Backs transcripts and app state up to an S3-compatible bucket or WebDAV
+ * folder the user runs. Transcript lines are uploaded as gzipped JSONL chunks
+ * named by their sha256, so a chunk already in the backup is never sent again,
+ * and each sync adds a small segment manifest listing the chunks it appended.
+ * Local progress is saved after every transcript, so an interrupted sync picks
+ * up where it stopped. Nothing in the backup is overwritten: a transcript that
+ * was rewritten locally (merged or redacted) starts a new generation beside
+ * the old one, and the state snapshot is only uploaded when it changed.
+ */
+export class TranscriptBackupService {
+  private state: BackupSyncStateFile | null = null;
+  private running: Promise<BackupSyncResult> | null = null;
+  private readonly now: () => Date;
+  private readonly createId: () => string;
+
+  constructor(private readonly options: TranscriptBackupServiceOptions) {
+    this.now = options.now ?? (() => new Date());
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+  }
+
+  /** Stores credentials in the keychain-wrapped vault and returns the reference to configure. */
+  async saveCredentials(credentials: BackupCredentials): Promise<SaveBackupCredentialsResult> {
+    if (!this.options.keychain.isEncryptionAvailable()) {
+      throw new Error("Backup credentials need the system keychain, which is unavailable.");
+    }
+    const vault = await this.readCredentialsFile();
+    const credentialsRef = `backup-${this.createId()}`;
+    vault.entries[credentialsRef] = this.options.keychain
+      .encryptString(JSON.stringify(credentials))
+      .toString("base64");
+    await this.writeCredentialsFile(vault);
+    return { credentialsRef };
+  }
+
+  /**
+   * Points backups at `target`, or turns them off when it is null. Moving to a
+   * different target forgets what was synced, since the new one starts empty.
+   * Saved credentials other than the configured ones are dropped.
+   */
+  async configure(input: ConfigureBackupSyncInput): Promise<BackupSyncConfig> {
+    const state = await this.loadSyncState();
+    const vault = await this.readCredentialsFile();
+    if (input.target) {
+      const credentials = this.unwrapCredentials(vault, input.credentialsRef);
+      if (credentials.kind !== input.target.kind) {
+        throw new Error(`Saved credentials are for ${credentials.kind}, not ${input.target.kind}.`);
+      }
+    }
+    if (!sameTarget(state.target, input.target)) {
+      state.transcripts = {};
+      state.stateHash = null;
+      state.lastSyncedAt = null;
+    }
+    state.target = input.target;
+    state.credentialsRef = input.target ? input.credentialsRef : null;
+    await this.saveSyncState();
+    const kept = state.credentialsRef;
+    if (Object.keys(vault.entries).some((ref) => ref !== kept)) {
+      vault.entries = kept && vault.entries[kept] ? { [kept]: vault.entries[kept] } : {};
+      await this.writeCredentialsFile(vault);
+    }
+    return this.toConfig(state);
+  }
+
+  async getConfig(): Promise<BackupSyncConfig> {
+    return this.toConfig(await this.loadSyncState());
+  }
+
+  /** Runs one sync; a call made while one is running waits for that one instead. */
+  async syncNow(options: SyncOptions = {}): Promise<BackupSyncResult> {
+    this.running ??= this.sync(options).finally(() => {
+      this.running = null;
+    });
+    return await this.running;
+  }
+
+  private async sync({ signal, onProgress }: SyncOptions): Promise<BackupSyncResult> {
+    const state = await this.loadSyncState();
+    const target = state.target;
+    if (!target) {
+      throw new Error("Backup sync is not configured.");
+    }
+    const credentials = this.unwrapCredentials(
+      await this.readCredentialsFile(),
+      state.credentialsRef,
+    );
+    const store = this.options.createStore
+      ? this.options.createStore(target, credentials, signal)
+      : createBackupStore(target, credentials, {}, signal);
+    const assertUnchanged = () => {
+      signal?.throwIfAborted();
+      if (!sameTarget(state.target, target)) {
+        throw new Error("The backup target changed during the sync.");
+      }
+    };
+
+    const result: BackupSyncResult = {
+      transcripts: 0,
+      segments: 0,
+      uploadedChunks: 0,
+      reusedChunks: 0,
+      uploadedBytes: 0,
+      stateUploaded: false,
+      syncedAt: "",
+    };
+    const putChunk = async (text: string): Promise<string> => {
+      const sha256 = sha256Hex(text);
+      const key = `chunks/${sha256}`;
+      if (await store.has(key)) {
+        result.reusedChunks += 1;
+        return sha256;
+      }
+      const body = zlib.gzipSync(text);
+      if ((await store.putIfAbsent(key, body, "application/gzip")) === "created") {
+        result.uploadedChunks += 1;
+        result.uploadedBytes += body.length;
+      } else {
+        result.reusedChunks += 1;
+      }
+      return sha256;
+    };
+    const putManifest = async (key: string, manifest: unknown) => {
+      const body = Buffer.from(JSON.stringify(manifest));
+      const outcome = await store.putIfAbsent(key, body, "application/json");
+      if (outcome === "created") {
+        result.uploadedBytes += body.length;
+      }
+      return outcome;
+    };
+
+    const files = await this.options.persistence.listTranscriptFiles();
+    const total = files.length + 1;
+    onProgress?.({ completed: 0, total, message: "Backing up transcripts" });
+    for (const [index, file] of files.entries()) {
+      assertUnchanged();
+      const synced = state.transcripts[file.transcriptId];
+      if (synced?.sizeBytes === file.sizeBytes && synced.modifiedAtMs === file.modifiedAtMs) {
+        continue;
+      }
+      const lines = (await this.options.persistence.readTranscript(file.transcriptId)).map(
+        (event) => JSON.stringify(event),
+      );
+      const continues =
+        synced !== undefined &&
+        lines.length >= synced.lines &&
+        hashLines(lines, synced.lines) === synced.prefixHash;
+      let generation = synced ? synced.generation + (continues ? 0 : 1) : 0;
+      let fromLine = continues ? synced.lines : 0;
+
+      for (let attempt = 0; fromLine < lines.length; attempt += 1) {
+        const chunks: ChunkRef[] = [];
+        for (const chunkLines of splitIntoChunks(lines.slice(fromLine))) {
+          assertUnchanged();
+          const text = `${chunkLines.join("\n")}\n`;
+          chunks.push({
+            sha256: await putChunk(text),
+            lines: chunkLines.length,
+            bytes: Buffer.byteLength(text),
+          });
+        }
+        const segmentKey =
+          `devices/${state.deviceId}/transcripts/${file.transcriptId}/g${generation}/` +
+          `${String(fromLine).padStart(SEGMENT_OFFSET_DIGITS, "0")}.json`;
+        const outcome = await putManifest(segmentKey, {
+          version: 1,
+          deviceId: state.deviceId,
+          transcriptId: file.transcriptId,
+          generation,
+          fromLine,
+          toLine: lines.length,
+          chunks,
+          createdAt: this.now().toISOString(),
+        });
+        if (outcome === "created") {
+          result.segments += 1;
+          break;
+        }
+        // A sync that stopped before saving its progress left this key behind, and
+        // what it holds may not match. Uploading the whole transcript as a new
+        // generation is cheap, as its chunks are already in the backup.
+        if (attempt + 1 >= MAX_GENERATION_ATTEMPTS) {
+          throw new Error(`Backup segment ${segmentKey} already exists.`);
+        }
+        generation += 1;
+        fromLine = 0;
+      }
+
+      assertUnchanged();
+      state.transcripts[file.transcriptId] = {
+        generation,
+        lines: lines.length,
+        prefixHash: hashLines(lines),
+        sizeBytes: file.sizeBytes,
+        modifiedAtMs: file.modifiedAtMs,
+      };
+      await this.saveSyncState();
+      result.transcripts += 1;
+      onProgress?.({ completed: index + 1, total, message: "Backing up transcripts" });
+    }
+
+    assertUnchanged();
+    onProgress?.({ completed: files.length, total, message: "Backing up app state" });
+    const snapshot = JSON.stringify(await this.options.persistence.loadState());
+    const stateHash = sha256Hex(snapshot);
+    const syncedAt = this.now().toISOString();
+    if (stateHash !== state.stateHash) {
+      const chunk = await putChunk(snapshot);
+      const snapshotName = `${syncedAt.replace(/[-:.]/g, "")}-${stateHash.slice(0, 12)}`;
+      await putManifest(`devices/${state.deviceId}/state/${snapshotName}.json`, {
+        version: 1,
+        deviceId: state.deviceId,
+        chunk,
+        createdAt: syncedAt,
+      });
+      result.stateUploaded = true;
+    }
+
+    assertUnchanged();
+    state.stateHash = stateHash;
+    state.lastSyncedAt = syncedAt;
+    await this.saveSyncState();
+    onProgress?.({ completed: total, total, message: null });
+    return { ...result, syncedAt };
+  }
+
+  private toConfig(state: BackupSyncStateFile): BackupSyncConfig {
+    return {
+      target: state.target,
+      credentialsRef: state.credentialsRef,
+      deviceId: state.deviceId,
+      lastSyncedAt: state.lastSyncedAt,
+    };
+  }
+
+  private unwrapCredentials(
+    vault: BackupCredentialsFile,
+    credentialsRef: string | null | undefined,
+  ): BackupCredentials {
+    const wrapped = credentialsRef ? vault.entries[credentialsRef] : undefined;
+    if (!wrapped) {
+      throw new Error("The backup credentials were not found; save them again.");
+    }
+    return JSON.parse(
+      this.options.keychain.decryptString(Buffer.from(wrapped, "base64")),
+    ) as BackupCredentials;
+  }
+
+  private async loadSyncState(): Promise<BackupSyncStateFile> {
+    if (this.state) {
+      return this.state;
+    }
+    let stored: Partial<BackupSyncStateFile> = {};
+    try {
+      stored = JSON.parse(await fs.readFile(this.options.stateFilePath, "utf8"));
+    } catch (error) {
+      if (!isNotFound(error) && !(error instanceof SyntaxError)) {
+        throw error;
+      }
+    }
+    this.state = {
+      version: 1,
+      deviceId: typeof stored.deviceId === "string" ? stored.deviceId : this.createId(),
+      target: stored.target ?? null,
+      credentialsRef: stored.credentialsRef ?? null,
+      lastSyncedAt: stored.lastSyncedAt ?? null,
+      stateHash: stored.stateHash ?? null,
+      transcripts: stored.transcripts ?? {},
+    };
+    return this.state;
+  }
+
+  private async saveSyncState(): Promise<void> {
+    await writeFileAtomic(this.options.stateFilePath, `${JSON.stringify(this.state, null, 2)}\n`, {
+      mode: PRIVATE_FILE_MODE,
+    });
+  }
+
+  private async readCredentialsFile(): Promise<BackupCredentialsFile> {
+    try {
+      const parsed = JSON.parse(await fs.readFile(this.options.credentialsFilePath, "utf8"));
+      return { version: 1, entries: { ...parsed?.entries } };
+    } catch (error) {
+      if (isNotFound(error)) {
+        return { version: 1, entries: {} };
+      }
+      throw error;
+    }
+  }
+
+  private async writeCredentialsFile(vault: BackupCredentialsFile): Promise<void> {
+    await writeFileAtomic(this.options.credentialsFilePath, `${JSON.stringify(vault)}\n`, {
+      mode: PRIVATE_FILE_MODE,
+    });
+  }
+}
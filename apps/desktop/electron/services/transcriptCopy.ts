@@ -0,0 +1,114 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { redactSecrets } from "../../../../src/diagnostics/sensitiveText";
+import type { FeedItem } from "../../src/app/types";
+
+// Short values such as ports or feature flags are configuration, not secrets,
+// and redacting them would scramble ordinary prose.
+const MIN_ENV_SECRET_LENGTH = 8;
+const ENV_FILE_PATTERN = /^\.env(?:\..+)?$/;
+const ENV_TEMPLATE_SUFFIXES = [".example", ".sample", ".template"];
+const ENV_LINE_PATTERN = /^\s*(?:export\s+)?([A-Za-z_][A-Za-z0-9_.-]*)\s*=\s*(.*)$/;
+
+export type EnvSecret = {
+  name: string;
+  value: string;
+};
+
+/** Reads `NAME=value` pairs worth redacting from dotenv-formatted text. */
+export function parseEnvSecrets(raw: string): EnvSecret[] {
+  const secrets: EnvSecret[] = [];
+  for (const line of raw.split(/\r?\n/)) {
+    const match = ENV_LINE_PATTERN.exec(line);
+    const name = match?.[1];
+    let value = match?.[2]?.trim() ?? "";
+    if (!name) {
+      continue;
+    }
+    if (/^(["']).*\1$/.test(value)) {
+      value = value.slice(1, -1);
+    } else {
+      value = value.replace(/\s+#.*$/, "");
+    }
+    if (value.length >= MIN_ENV_SECRET_LENGTH) {
+      secrets.push({ name, value });
+    }
+  }
+  return secrets;
+}
+
+/** Values assigned in the `.env*` files at the workspace root, skipping templates. */
+export async function readWorkspaceEnvSecrets(workspacePath: string): Promise<EnvSecret[]> {
+  let entries: string[];
+  try {
+    entries = await fs.readdir(workspacePath);
+  } catch {
+    return [];
+  }
+  const secrets: EnvSecret[] = [];
+  for (const entry of entries.sort()) {
+    if (
+      !ENV_FILE_PATTERN.test(entry) ||
+      ENV_TEMPLATE_SUFFIXES.some((suffix) => entry.endsWith(suffix))
+    ) {
+      continue;
+    }
+    try {
+      secrets.push(...parseEnvSecrets(await fs.readFile(path.join(workspacePath, entry), "utf8")));
+    } catch {
+      // Directories named like env files, or files we cannot read, hold nothing to redact.
+    }
+  }
+  return secrets;
+}
+
+function renderFeedItem(item: FeedItem): string | null {
+  switch (item.kind) {
+    case "message": {
+      const text = item.text.trim();
+      return text ? `**${item.role === "user" ? "User" : "Assistant"}:**\n\n${text}` : null;
+    }
+    case "tool":
+      return `_Tool call: \`${item.name}\`${item.state === "output-error" ? " (failed)" : ""}_`;
+    case "todos":
+      return item.todos
+        .map((todo) => `- [${todo.status === "completed" ? "x" : " "}] ${todo.content}`)
+        .join("\n");
+    case "error":
+      return `**Error:** ${item.message}`;
+    default:
+      // Reasoning, logs, and system lines are internal to the session.
+      return null;
+  }
+}
+
+/** Renders feed items as Markdown for sharing outside the app, skipping internal items. */
+export function renderTranscriptMarkdown(items: readonly FeedItem[]): {
+  markdown: string;
+  itemCount: number;
+} {
+  const blocks = items.map(renderFeedItem).filter((block): block is string => block !== null);
+  return { markdown: blocks.join("\n\n"), itemCount: blocks.length };
+}
+
+/**
+ * Replaces anything that looks like a credential, then any remaining workspace
+ * `.env` values by name. Longer values go first so a value containing another
+ * is replaced whole.
+ */
+export function redactTranscriptText(
+  text: string,
+  envSecrets: readonly EnvSecret[],
+): { text: string; redactionCount: number } {
+  const scanned = redactSecrets(text);
+  let redacted = scanned.text;
+  let redactionCount = scanned.redactionCount;
+  const byLength = [...envSecrets].sort((a, b) => b.value.length - a.value.length);
+  for (const secret of byLength) {
+    const parts = redacted.split(secret.value);
+    redactionCount += parts.length - 1;
+    redacted = parts.join(`[redacted:${secret.name}]`);
+  }
+  return { text: redacted, redactionCount };
+}
@@ -0,0 +1,143 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import { safeStorage } from "electron";
+
+import type { TranscriptEncryptionStatus } from "../../src/lib/desktopApi";
+
+const PRIVATE_FILE_MODE = 0o600;
+const KEY_BYTES = 32;
+const IV_BYTES = 12;
+const AUTH_TAG_BYTES = 16;
+
+/** Marks a JSONL line whose payload is `base64(iv | auth tag | AES-256-GCM ciphertext)`. */
+export const ENCRYPTED_TRANSCRIPT_LINE_PREFIX = "enc:v1:";
+
+export type TranscriptKeychain = Pick<
+  typeof safeStorage,
+  "isEncryptionAvailable" | "encryptString" | "decryptString"
+>;
+
+type StoredTranscriptEncryption = {
+  enabled: boolean;
+  /** The transcript key, wrapped by the OS keychain via `safeStorage`. */
+  wrappedKey?: string;
+};
+
+export function isEncryptedTranscriptLine(line: string): boolean {
+  return line.startsWith(ENCRYPTED_TRANSCRIPT_LINE_PREFIX);
+}
+
+export function encryptTranscriptLine(key: Buffer, line: string): string {
+  const iv = crypto.randomBytes(IV_BYTES);
+  const cipher = crypto.createCipheriv("aes-256-gcm", key, iv);
+  const ciphertext = Buffer.concat([cipher.update(line, "utf8"), cipher.final()]);
+  const sealed = Buffer.concat([iv, cipher.getAuthTag(), ciphertext]);
+  return `${ENCRYPTED_TRANSCRIPT_LINE_PREFIX}${sealed.toString("base64")}`;
+}
+
+/** Throws when the line was not sealed with `key` or has been tampered with. */
+export function decryptTranscriptLine(key: Buffer, line: string): string {
+  const sealed = Buffer.from(line.slice(ENCRYPTED_TRANSCRIPT_LINE_PREFIX.length), "base64");
+  if (sealed.length < IV_BYTES + AUTH_TAG_BYTES) {
+    throw new Error("Encrypted transcript line is truncated.");
+  }
+  const decipher = crypto.createDecipheriv("aes-256-gcm", key, sealed.subarray(0, IV_BYTES));
+  decipher.setAuthTag(sealed.subarray(IV_BYTES, IV_BYTES + AUTH_TAG_BYTES));
+  return Buffer.concat([
+    decipher.update(sealed.subarray(IV_BYTES + AUTH_TAG_BYTES)),
+    decipher.final(),
+  ]).toString("utf8");
+}
+
+function isNotFound(error: unknown): boolean {
+  return (error as NodeJS.ErrnoException | null)?.code === "ENOENT";
+}
+
+/**
+ * Owns the opt-in transcript encryption setting and its AES key. The key is
+ * generated once, stored only in keychain-wrapped form, and kept after
+ * encryption is turned off so previously encrypted lines stay readable.
+ */
+export class TranscriptEncryption {
+  private stored: StoredTranscriptEncryption | null = null;
+  private key: Buffer | null = null;
+
+  constructor(
+    private readonly configPath: string,
+    private readonly keychain: TranscriptKeychain = safeStorage,
+  ) {}
+
+  async getStatus(): Promise<TranscriptEncryptionStatus> {
+    const stored = await this.load();
+    return { enabled: stored.enabled, available: this.keychain.isEncryptionAvailable() };
+  }
+
+  async setEnabled(enabled: boolean): Promise<TranscriptEncryptionStatus> {
+    const next: StoredTranscriptEncryption = { ...(await this.load()), enabled };
+    let key: Buffer | null = null;
+    if (enabled && !next.wrappedKey) {
+      if (!this.keychain.isEncryptionAvailable()) {
+        throw new Error("The OS keychain is not available, so transcripts cannot be encrypted.");
+      }
+      key = crypto.randomBytes(KEY_BYTES);
+      next.wrappedKey = this.keychain.encryptString(key.toString("base64")).toString("base64");
+    }
+    await this.save(next);
+    this.key = key ?? this.key;
+    return await this.getStatus();
+  }
+
+  /** The key for new lines, or null when encryption is off. */
+  async getWriteKey(): Promise<Buffer | null> {
+    return (await this.load()).enabled ? await this.getReadKey() : null;
+  }
+
+  /** The key for existing encrypted lines, or null when none was ever created. */
+  async getReadKey(): Promise<Buffer | null> {
+    if (this.key) {
+      return this.key;
+    }
+    const { wrappedKey } = await this.load();
+    if (!wrappedKey) {
+      return null;
+    }
+    const key = Buffer.from(
+      this.keychain.decryptString(Buffer.from(wrappedKey, "base64")),
+      "base64",
+    );
+    if (key.length !== KEY_BYTES) {
+      throw new Error("The stored transcript key is invalid.");
+    }
+    this.key = key;
+    return key;
+  }
+
+  private async load(): Promise<StoredTranscriptEncryption> {
+    if (this.stored) {
+      return this.stored;
+    }
+    let parsed: Partial<StoredTranscriptEncryption> = {};
+    try {
+      parsed = JSON.parse(await fs.readFile(this.configPath, "utf8"));
+    } catch (error) {
+      if (!isNotFound(error)) {
+        throw new Error(`Failed to read transcript encryption settings: ${String(error)}`);
+      }
+    }
+    this.stored = {
+      enabled: parsed.enabled === true,
+      ...(typeof parsed.wrappedKey === "string" ? { wrappedKey: parsed.wrappedKey } : {}),
+    };
+    return this.stored;
+  }
+
+  private async save(stored: StoredTranscriptEncryption): Promise<void> {
+    const tempPath = `${this.configPath}.tmp`;
+    await fs.writeFile(tempPath, `${JSON.stringify(stored, null, 2)}\n`, {
+      encoding: "utf8",
+      mode: PRIVATE_FILE_MODE,
+    });
+    await fs.rename(tempPath, this.configPath);
+    this.stored = stored;
+  }
+}
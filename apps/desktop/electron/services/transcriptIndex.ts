@@ -0,0 +1,231 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type { TranscriptEvent } from "../../src/app/types";
+import type { ThreadPreview, ThreadPreviewMessage } from "../../src/lib/desktopApi";
+import { assertSafeId } from "./validation";
+
+/** Every this many lines of the live file, the index keeps the line's byte offset. */
+export const TRANSCRIPT_INDEX_STRIDE = 256;
+const SNIPPET_MAX_CHARS = 200;
+
+/**
+ * What `{transcriptId}.json` in the index directory holds. `liveBytes` and
+ * `sqliteLines` describe the live JSONL file and SQLite rows the index was
+ * last brought up to date with; when either differs, the transcript was
+ * rewritten, compacted, or migrated since, and the index must be rebuilt.
+ */
+export type TranscriptIndexFile = {
+  version: 1;
+  liveBytes: number;
+  /** Lines in the live file, including ones that do not decode. */
+  liveLines: number;
+  /** Byte offsets of live lines 0, stride, 2 × stride, and so on. */
+  lineOffsets: number[];
+  /** Rows in the SQLite store, including ones that do not decode. */
+  sqliteLines: number;
+  /** Decoded events across the archive, the live file, and SQLite. */
+  eventCount: number;
+  lastEventAt: string | null;
+  lastMessage: ThreadPreviewMessage | null;
+};
+
+/**
+ * One line of the live file: its bytes including the newline, the event it
+ * decoded to (null when it did not), and whether it was sealed on disk.
+ */
+export type IndexedTranscriptLine = {
+  bytes: number;
+  event: TranscriptEvent | null;
+  sealed: boolean;
+};
+
+function snippet(text: string): string {
+  const collapsed = text.replace(/\s+/g, " ").trim();
+  return collapsed.length > SNIPPET_MAX_CHARS
+    ? `${collapsed.slice(0, SNIPPET_MAX_CHARS - 1)}…`
+    : collapsed;
+}
+
+/**
+ * The sidebar preview of a user or assistant message, or null for events that
+ * are not messages. Sealed lines keep no text, since index files are not encrypted.
+ */
+export function summarizeTranscriptMessage(
+  event: TranscriptEvent,
+  sealed: boolean,
+): ThreadPreviewMessage | null {
+  const payload = event.payload as { type?: unknown; text?: unknown; filename?: unknown };
+  let role: ThreadPreviewMessage["role"];
+  let text: unknown;
+  if (payload.type === "user_message" || payload.type === "steer_message") {
+    role = "user";
+    text = payload.text;
+  } else if (payload.type === "assistant_message" && event.direction === "server") {
+    role = "assistant";
+    text = payload.text;
+  } else if (payload.type === "attachment" && event.direction === "client") {
+    role = "user";
+    text = payload.filename;
+  } else {
+    return null;
+  }
+  if (typeof text !== "string") {
+    return null;
+  }
+  return { role, ts: event.ts, text: sealed ? null : snippet(text) };
+}
+
+export function emptyTranscriptIndex(): TranscriptIndexFile {
+  return {
+    version: 1,
+    liveBytes: 0,
+    liveLines: 0,
+    lineOffsets: [],
+    sqliteLines: 0,
+    eventCount: 0,
+    lastEventAt: null,
+    lastMessage: null,
+  };
+}
+
+/** Counts an event from the archive or SQLite, which have no live offsets to track. */
+export function recordIndexedEvent(
+  index: TranscriptIndexFile,
+  event: TranscriptEvent,
+  sealed: boolean,
+): TranscriptIndexFile {
+  return {
+    ...index,
+    eventCount: index.eventCount + 1,
+    lastEventAt: event.ts,
+    lastMessage: summarizeTranscriptMessage(event, sealed) ?? index.lastMessage,
+  };
+}
+
+/** Folds rows appended to the SQLite store into `index`. */
+export function advanceSqliteTranscriptIndex(
+  index: TranscriptIndexFile,
+  lines: IndexedTranscriptLine[],
+): TranscriptIndexFile {
+  let next = { ...index, sqliteLines: index.sqliteLines + lines.length };
+  for (const line of lines) {
+    if (line.event) {
+      next = recordIndexedEvent(next, line.event, line.sealed);
+    }
+  }
+  return next;
+}
+
+/** Folds lines appended to the live file into `index`. */
+export function advanceTranscriptIndex(
+  index: TranscriptIndexFile,
+  lines: IndexedTranscriptLine[],
+): TranscriptIndexFile {
+  let next = { ...index, lineOffsets: [...index.lineOffsets] };
+  for (const line of lines) {
+    if (next.liveLines % TRANSCRIPT_INDEX_STRIDE === 0) {
+      next.lineOffsets.push(next.liveBytes);
+    }
+    next.liveLines += 1;
+    next.liveBytes += line.bytes;
+    if (line.event) {
+      next = recordIndexedEvent(next, line.event, line.sealed);
+    }
+  }
+  return next;
+}
+
+function parseIndexFile(value: unknown): TranscriptIndexFile | null {
+  const file = value as Partial<TranscriptIndexFile> | null;
+  if (
+    file?.version !== 1 ||
+    typeof file.liveBytes !== "number" ||
+    typeof file.liveLines !== "number" ||
+    !Array.isArray(file.lineOffsets) ||
+    typeof file.eventCount !== "number"
+  ) {
+    return null;
+  }
+  return {
+    version: 1,
+    liveBytes: file.liveBytes,
+    liveLines: file.liveLines,
+    lineOffsets: file.lineOffsets.filter(
+      (offset): offset is number => typeof offset === "number",
+    ),
+    // Indexes written before SQLite rows were tracked count none, so they are
+    // rebuilt for any transcript that has some.
+    sqliteLines: typeof file.sqliteLines === "number" ? file.sqliteLines : 0,
+    eventCount: file.eventCount,
+    lastEventAt: typeof file.lastEventAt === "string" ? file.lastEventAt : null,
+    lastMessage: file.lastMessage ?? null,
+  };
+}
+
+/**
+ * Small per-transcript summaries kept beside the transcripts, so listing
+ * threads reads a few hundred bytes each instead of every transcript.
+ * `PersistenceService` decides when an index is stale; this only stores them.
+ */
+export class TranscriptIndexStore {
+  private readonly cache = new Map<string, TranscriptIndexFile>();
+
+  constructor(private readonly directory: string) {}
+
+  /** Null when there is no index or it cannot be read. */
+  async read(transcriptId: string): Promise<TranscriptIndexFile | null> {
+    const cached = this.cache.get(transcriptId);
+    if (cached) {
+      return cached;
+    }
+    let raw: string;
+    try {
+      raw = await fs.readFile(this.filePath(transcriptId), "utf8");
+    } catch {
+      return null;
+    }
+    try {
+      const parsed = parseIndexFile(JSON.parse(raw));
+      if (parsed) {
+        this.cache.set(transcriptId, parsed);
+      }
+      return parsed;
+    } catch {
+      return null;
+    }
+  }
+
+  async write(transcriptId: string, index: TranscriptIndexFile): Promise<void> {
+    this.cache.set(transcriptId, index);
+    await writeFileAtomic(this.filePath(transcriptId), `${JSON.stringify(index)}\n`, {
+      mode: 0o600,
+    });
+  }
+
+  async delete(transcriptId: string): Promise<void> {
+    this.cache.delete(transcriptId);
+    await fs.rm(this.filePath(transcriptId), { force: true });
+  }
+
+  /** Drops every index, e.g. once their plaintext snippets must not outlive encryption. */
+  async clear(): Promise<void> {
+    this.cache.clear();
+    await fs.rm(this.directory, { recursive: true, force: true });
+  }
+
+  private filePath(transcriptId: string): string {
+    assertSafeId(transcriptId, "threadId");
+    return path.join(this.directory, `${transcriptId}.json`);
+  }
+}
+
+export function toThreadPreview(threadId: string, index: TranscriptIndexFile): ThreadPreview {
+  return {
+    threadId,
+    eventCount: index.eventCount,
+    lastEventAt: index.lastEventAt,
+    lastMessage: index.lastMessage,
+  };
+}
@@ -0,0 +1,158 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { hydrateTranscriptSnapshot } from "../../src/app/transcriptHydration";
+import type {
+  PersistedState,
+  ThreadRecord,
+  TranscriptEvent,
+  WorkspaceRecord,
+} from "../../src/app/types";
+import { transcriptMirrorApprovalPath } from "../../src/app/workspaceTranscriptMirror";
+
+const DEFAULT_MIRROR_DEBOUNCE_MS = 1_000;
+
+type TranscriptMirrorServiceOptions = {
+  loadState: () => Promise<PersistedState>;
+  /** Must return decrypted events so the mirror is readable outside the app. */
+  readTranscript: (transcriptId: string) => Promise<TranscriptEvent[]>;
+  debounceMs?: number;
+  onError?: (transcriptId: string, error: unknown) => void;
+};
+
+function formatToolArgs(args: unknown): string {
+  if (args === undefined) {
+    return "";
+  }
+  const text = typeof args === "string" ? args : JSON.stringify(args);
+  return text.length > 200 ? `${text.slice(0, 200)}…` : text;
+}
+
+/** Renders the readable parts of a transcript; reasoning and raw log lines are left out. */
+export function renderTranscriptMarkdown(title: string, events: TranscriptEvent[]): string {
+  const sections = [`# ${title}`];
+  for (const item of hydrateTranscriptSnapshot(events).feed) {
+    switch (item.kind) {
+      case "message":
+        sections.push(`## ${item.role === "user" ? "User" : "Assistant"}\n\n${item.text.trim()}`);
+        break;
+      case "tool": {
+        const args = formatToolArgs(item.args);
+        sections.push(`> Tool \`${item.name}\` (${item.state})${args ? `: ${args}` : ""}`);
+        break;
+      }
+      case "error":
+        sections.push(`> Error: ${item.message}`);
+        break;
+      case "system":
+        sections.push(`> ${item.line}`);
+        break;
+      default:
+        break;
+    }
+  }
+  return `${sections.join("\n\n")}\n`;
+}
+
+function isInsideDirectory(parent: string, candidate: string): boolean {
+  const relative = path.relative(parent, candidate);
+  return relative === "" || (!relative.startsWith("..") && !path.isAbsolute(relative));
+}
+
+/** Relative mirror folders resolve inside the workspace and may not climb out of it. */
+export function resolveTranscriptMirrorDirectory(workspace: WorkspaceRecord): string | null {
+  const mirror = workspace.transcriptMirror;
+  if (!mirror) {
+    return null;
+  }
+  const absolute = transcriptMirrorApprovalPath(workspace);
+  if (absolute) {
+    return absolute;
+  }
+  const resolved = path.resolve(workspace.path, mirror.directory);
+  return isInsideDirectory(path.resolve(workspace.path), resolved) ? resolved : null;
+}
+
+/**
+ * Keeps a plain copy of each transcript in the folder its workspace chose, so
+ * other tools can follow a conversation as it happens. Appends are debounced
+ * per transcript and each mirror file is rewritten whole, atomically.
+ */
+export class TranscriptMirrorService {
+  private readonly pending = new Map<string, ReturnType<typeof setTimeout>>();
+  private readonly inFlight = new Set<Promise<void>>();
+  private readonly debounceMs: number;
+
+  constructor(private readonly options: TranscriptMirrorServiceOptions) {
+    this.debounceMs = options.debounceMs ?? DEFAULT_MIRROR_DEBOUNCE_MS;
+  }
+
+  handleAppended(events: TranscriptEvent[]): void {
+    for (const transcriptId of new Set(events.map((event) => event.threadId))) {
+      const existing = this.pending.get(transcriptId);
+      if (existing) {
+        clearTimeout(existing);
+      }
+      this.pending.set(
+        transcriptId,
+        setTimeout(() => {
+          this.pending.delete(transcriptId);
+          this.track(transcriptId);
+        }, this.debounceMs),
+      );
+    }
+  }
+
+  /** Writes every pending mirror now; used before quitting. */
+  async flush(): Promise<void> {
+    for (const [transcriptId, timer] of this.pending) {
+      clearTimeout(timer);
+      this.track(transcriptId);
+    }
+    this.pending.clear();
+    await Promise.all([...this.inFlight]);
+  }
+
+  async mirrorTranscript(transcriptId: string): Promise<string | null> {
+    const state = await this.options.loadState();
+    const thread = state.threads.find(
+      (candidate: ThreadRecord) =>
+        candidate.id === transcriptId ||
+        candidate.sessionId === transcriptId ||
+        candidate.legacyTranscriptId === transcriptId,
+    );
+    const workspace = thread
+      ? state.workspaces.find((candidate) => candidate.id === thread.workspaceId)
+      : undefined;
+    const mirror = workspace?.transcriptMirror;
+    const directory = workspace ? resolveTranscriptMirrorDirectory(workspace) : null;
+    if (!thread || !mirror || !directory) {
+      return null;
+    }
+
+    const events = await this.options.readTranscript(transcriptId);
+    const contents =
+      mirror.format === "markdown"
+        ? renderTranscriptMarkdown(thread.title, events)
+        : events.map((event) => `${JSON.stringify(event)}\n`).join("");
+    const extension = mirror.format === "markdown" ? "md" : "jsonl";
+    const targetPath = path.join(directory, `${transcriptId}.${extension}`);
+    const tempPath = `${targetPath}.tmp`;
+    await fs.mkdir(directory, { recursive: true });
+    await fs.writeFile(tempPath, contents, "utf8");
+    await fs.rename(tempPath, targetPath);
+    return targetPath;
+  }
+
+  private track(transcriptId: string): void {
+    const write = this.mirrorTranscript(transcriptId)
+      .then(() => undefined)
+      .catch((error: unknown) => {
+        this.options.onError?.(transcriptId, error);
+      })
+      .finally(() => {
+        this.inFlight.delete(write);
+      });
+    this.inFlight.add(write);
+  }
+}
@@ -0,0 +1,133 @@
+import type { TranscriptEvent } from "../../src/app/types";
+import type { TranscriptPage } from "../../src/lib/desktopApi";
+
+export const DEFAULT_TRANSCRIPT_PAGE_MAX_EVENTS = 5_000;
+export const DEFAULT_TRANSCRIPT_PAGE_MAX_BYTES = 16 * 1024 * 1024;
+
+/**
+ * Where a page stopped: a byte offset into the decompressed archive or the
+ * live JSONL file, a sequence number in the SQLite store, or an index into a
+ * transcript held in memory.
+ */
+export type TranscriptCursor = {
+  source: "archived" | "live" | "sqlite" | "memory";
+  offset: number;
+};
+
+export type TranscriptPageLimits = {
+  maxEvents: number;
+  maxBytes: number;
+};
+
+export type TranscriptLine = {
+  text: string;
+  /** Bytes the line takes on disk, without its newline. */
+  bytes: number;
+  /** Offset just past the line's newline, where the next line starts. */
+  end: number;
+};
+
+const CURSOR_PATTERN = /^(archived|live|sqlite|memory):(\d{1,15})$/;
+
+export function encodeTranscriptCursor(cursor: TranscriptCursor): string {
+  return `${cursor.source}:${cursor.offset}`;
+}
+
+export function decodeTranscriptCursor(raw: string): TranscriptCursor {
+  const match = CURSOR_PATTERN.exec(raw);
+  if (!match) {
+    throw new Error("Invalid transcript cursor.");
+  }
+  return { source: match[1] as TranscriptCursor["source"], offset: Number(match[2]) };
+}
+
+export function resolveTranscriptPageLimits(options: {
+  maxEvents?: number;
+  maxBytes?: number;
+}): TranscriptPageLimits {
+  return {
+    maxEvents: options.maxEvents ?? DEFAULT_TRANSCRIPT_PAGE_MAX_EVENTS,
+    maxBytes: options.maxBytes ?? DEFAULT_TRANSCRIPT_PAGE_MAX_BYTES,
+  };
+}
+
+/**
+ * True when a page that already holds `events` events and `bytes` bytes
+ * must stop before taking a line of `lineBytes`. A page always takes its
+ * first line, however large, so reading can make progress.
+ */
+export function isTranscriptPageFull(
+  limits: TranscriptPageLimits,
+  events: number,
+  bytes: number,
+  lineBytes: number,
+): boolean {
+  return events >= limits.maxEvents || (events > 0 && bytes + lineBytes > limits.maxBytes);
+}
+
+/**
+ * Splits a byte stream into lines without holding more than the current line.
+ * `offset` is the position of the stream's first byte; the first `skip` bytes
+ * of the stream are dropped, for streams that cannot seek.
+ */
+export async function* readTranscriptLines(
+  chunks: AsyncIterable<Buffer>,
+  options: { offset?: number; skip?: number } = {},
+): AsyncGenerator<TranscriptLine> {
+  let position = options.offset ?? 0;
+  const skipUntil = position + (options.skip ?? 0);
+  let parts: Buffer[] = [];
+  for await (let chunk of chunks) {
+    if (position < skipUntil) {
+      const dropped = Math.min(skipUntil - position, chunk.length);
+      position += dropped;
+      chunk = chunk.subarray(dropped);
+    }
+    let from = 0;
+    while (from < chunk.length) {
+      const newline = chunk.indexOf(0x0a, from);
+      if (newline === -1) {
+        parts.push(chunk.subarray(from));
+        break;
+      }
+      const piece = chunk.subarray(from, newline);
+      const line = parts.length > 0 ? Buffer.concat([...parts, piece]) : piece;
+      parts = [];
+      position += line.length + 1;
+      yield { text: line.toString("utf8"), bytes: line.length, end: position };
+      from = newline + 1;
+    }
+  }
+  if (parts.length > 0) {
+    const line = Buffer.concat(parts);
+    position += line.length;
+    yield { text: line.toString("utf8"), bytes: line.length, end: position };
+  }
+}
+
+/** Pages a transcript that is already in memory, such as a viewer or scratch thread. */
+export function pageTranscriptEvents(
+  events: readonly TranscriptEvent[],
+  cursor: TranscriptCursor | null,
+  limits: TranscriptPageLimits,
+): TranscriptPage {
+  if (cursor && cursor.source !== "memory") {
+    throw new Error("Invalid transcript cursor.");
+  }
+  const page: TranscriptEvent[] = [];
+  let bytes = 0;
+  for (let index = cursor?.offset ?? 0; index < events.length; index += 1) {
+    const event = events[index]!;
+    const eventBytes = Buffer.byteLength(JSON.stringify(event));
+    if (isTranscriptPageFull(limits, page.length, bytes, eventBytes)) {
+      return {
+        events: page,
+        nextCursor: encodeTranscriptCursor({ source: "memory", offset: index }),
+        truncated: true,
+      };
+    }
+    page.push(event);
+    bytes += eventBytes;
+  }
+  return { events: page, nextCursor: null, truncated: false };
+}
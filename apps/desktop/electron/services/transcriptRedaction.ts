@@ -0,0 +1,116 @@
+import crypto from "node:crypto";
+
+import { findSecretSpans } from "../../../../src/diagnostics/sensitiveText";
+import type { TranscriptEvent } from "../../src/app/types";
+import type { TranscriptRedactionMatch } from "../../src/lib/desktopApi";
+
+const HASH_LENGTH = 12;
+
+type RedactionSpan = { start: number; end: number; rule: string };
+
+/** `[REDACTED:<hash>]`; the same secret always gets the same hash, so repeats stay traceable. */
+export function redactionPlaceholder(secret: string): string {
+  const hash = crypto.createHash("sha256").update(secret).digest("hex").slice(0, HASH_LENGTH);
+  return `[REDACTED:${hash}]`;
+}
+
+/**
+ * Rewrites the strings in transcript event payloads, replacing credentials
+ * found by the diagnostics secret patterns and matches of `patterns` with a
+ * hashed placeholder, and counts what it replaced per rule. Placeholders
+ * written by an earlier pass are left alone, so redacting twice is a no-op.
+ */
+export class TranscriptRedactor {
+  private readonly patterns: Array<{ rule: string; pattern: RegExp }>;
+  private readonly counts = new Map<string, number>();
+
+  constructor(patterns: readonly string[] = []) {
+    this.patterns = [...new Set(patterns)].map((source) => ({
+      rule: source,
+      pattern: new RegExp(source, "g"),
+    }));
+  }
+
+  get matches(): TranscriptRedactionMatch[] {
+    return [...this.counts].map(([rule, count]) => ({ rule, count }));
+  }
+
+  get redactionCount(): number {
+    let total = 0;
+    for (const count of this.counts.values()) {
+      total += count;
+    }
+    return total;
+  }
+
+  /** The event with its payload redacted, or null when nothing in it matched. */
+  redactEvent(event: TranscriptEvent): TranscriptEvent | null {
+    let changed = false;
+    const payload = this.redactValue(event.payload, () => {
+      changed = true;
+    });
+    return changed ? { ...event, payload } : null;
+  }
+
+  private redactValue(value: unknown, onChange: () => void): unknown {
+    if (typeof value === "string") {
+      const redacted = this.redactText(value);
+      if (redacted !== value) {
+        onChange();
+      }
+      return redacted;
+    }
+    if (Array.isArray(value)) {
+      return value.map((entry) => this.redactValue(entry, onChange));
+    }
+    if (value && typeof value === "object") {
+      return Object.fromEntries(
+        Object.entries(value).map(([key, entry]) => [key, this.redactValue(entry, onChange)]),
+      );
+    }
+    return value;
+  }
+
+  private redactText(text: string): string {
+    const spans: RedactionSpan[] = findSecretSpans(text).map(({ start, end, kind }) => ({
+      start,
+      end,
+      rule: kind,
+    }));
+    for (const { rule, pattern } of this.patterns) {
+      for (const match of text.matchAll(pattern)) {
+        const start = match.index ?? 0;
+        if (match[0].length > 0) {
+          spans.push({ start, end: start + match[0].length, rule });
+        }
+      }
+    }
+    for (const placeholder of text.matchAll(/\[REDACTED:[0-9a-f]+\]/g)) {
+      const start = placeholder.index ?? 0;
+      spans.push({ start, end: start + placeholder[0].length, rule: "" });
+    }
+    if (spans.length === 0) {
+      return text;
+    }
+
+    // Earlier and longer spans win; a span overlapping one already kept is dropped.
+    spans.sort((left, right) => left.start - right.start || right.end - left.end);
+    let output = "";
+    let cursor = 0;
+    for (const span of spans) {
+      if (span.start < cursor) {
+        continue;
+      }
+      output += text.slice(cursor, span.start);
+      const secret = text.slice(span.start, span.end);
+      if (span.rule) {
+        output += redactionPlaceholder(secret);
+        this.counts.set(span.rule, (this.counts.get(span.rule) ?? 0) + 1);
+      } else {
+        output += secret;
+      }
+      cursor = span.end;
+    }
+    return output + text.slice(cursor);
+  }
+}
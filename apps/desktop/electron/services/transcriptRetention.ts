@@ -0,0 +1,265 @@
+import type { PersistedState } from "../../src/app/types";
+import type {
+  AppSettings,
+  PreviewTranscriptRetentionInput,
+  TranscriptRetentionCandidate,
+  TranscriptRetentionPreview,
+  TranscriptRetentionSummary,
+} from "../../src/lib/desktopApi";
+import type { TranscriptFileInfo } from "./persistence";
+
+const DEFAULT_RETENTION_INTERVAL_MS = 6 * 60 * 60_000;
+const DAY_MS = 24 * 60 * 60_000;
+const BYTES_PER_MB = 1024 * 1024;
+/** Transcripts written this recently are never pruned, whatever the limits say. */
+export const MIN_TRANSCRIPT_RETENTION_AGE_MS = DAY_MS;
+
+export type TranscriptRetentionPolicy = {
+  maxAgeDays: number;
+  maxTotalMb: number;
+};
+
+type TranscriptRetentionServiceOptions = {
+  listTranscriptFiles: () => Promise<TranscriptFileInfo[]>;
+  deleteTranscript: (transcriptId: string) => Promise<void>;
+  loadState: () => Promise<PersistedState>;
+  getSettings: () => Promise<AppSettings>;
+  emit: (summary: TranscriptRetentionSummary) => void;
+  now?: () => number;
+  intervalMs?: number;
+  onError?: (error: unknown) => void;
+  setInterval?: (callback: () => void, ms: number) => ReturnType<typeof setInterval>;
+  clearInterval?: (handle: ReturnType<typeof setInterval>) => void;
+};
+
+type TranscriptGroup = {
+  transcriptId: string;
+  sizeBytes: number;
+  modifiedAtMs: number;
+};
+
+function groupByTranscript(files: TranscriptFileInfo[]): TranscriptGroup[] {
+  const groups = new Map<string, TranscriptGroup>();
+  for (const file of files) {
+    const group = groups.get(file.transcriptId);
+    if (group) {
+      group.sizeBytes += file.sizeBytes;
+      group.modifiedAtMs = Math.max(group.modifiedAtMs, file.modifiedAtMs);
+    } else {
+      groups.set(file.transcriptId, {
+        transcriptId: file.transcriptId,
+        sizeBytes: file.sizeBytes,
+        modifiedAtMs: file.modifiedAtMs,
+      });
+    }
+  }
+  return [...groups.values()].sort(
+    (left, right) =>
+      left.modifiedAtMs - right.modifiedAtMs || left.transcriptId.localeCompare(right.transcriptId),
+  );
+}
+
+export function retentionPolicyFromSettings(settings: AppSettings): TranscriptRetentionPolicy {
+  return {
+    maxAgeDays: settings.transcriptRetentionDays,
+    maxTotalMb: settings.transcriptRetentionMaxTotalMb,
+  };
+}
+
+/**
+ * Picks transcripts to prune: everything untouched for longer than the max
+ * age, then the oldest of the rest until the total fits under the disk cap.
+ * A transcript's live and archived copies are pruned together.
+ */
+export function planTranscriptRetention(
+  files: TranscriptFileInfo[],
+  policy: TranscriptRetentionPolicy,
+  now: number,
+  threadIdByTranscriptId: ReadonlyMap<string, string> = new Map(),
+): TranscriptRetentionPreview {
+  const groups = groupByTranscript(files);
+  const totalBytes = groups.reduce((sum, group) => sum + group.sizeBytes, 0);
+  const enabled = policy.maxAgeDays > 0 || policy.maxTotalMb > 0;
+  const candidates: TranscriptRetentionCandidate[] = [];
+  const addCandidate = (
+    group: TranscriptGroup,
+    reason: TranscriptRetentionCandidate["reason"],
+  ): void => {
+    candidates.push({
+      transcriptId: group.transcriptId,
+      threadId: threadIdByTranscriptId.get(group.transcriptId) ?? null,
+      sizeBytes: group.sizeBytes,
+      lastModifiedAt: new Date(group.modifiedAtMs).toISOString(),
+      reason,
+    });
+  };
+
+  const maxAgeMs = Math.max(MIN_TRANSCRIPT_RETENTION_AGE_MS, policy.maxAgeDays * DAY_MS);
+  const remaining: TranscriptGroup[] = [];
+  for (const group of groups) {
+    if (policy.maxAgeDays > 0 && now - group.modifiedAtMs > maxAgeMs) {
+      addCandidate(group, "age");
+    } else {
+      remaining.push(group);
+    }
+  }
+
+  if (policy.maxTotalMb > 0) {
+    const capBytes = policy.maxTotalMb * BYTES_PER_MB;
+    let keptBytes = remaining.reduce((sum, group) => sum + group.sizeBytes, 0);
+    for (const group of remaining) {
+      if (keptBytes <= capBytes) {
+        break;
+      }
+      if (now - group.modifiedAtMs < MIN_TRANSCRIPT_RETENTION_AGE_MS) {
+        continue;
+      }
+      addCandidate(group, "diskUsage");
+      keptBytes -= group.sizeBytes;
+    }
+  }
+
+  return {
+    maxAgeDays: policy.maxAgeDays,
+    maxTotalMb: policy.maxTotalMb,
+    enabled,
+    totalBytes,
+    reclaimableBytes: candidates.reduce((sum, candidate) => sum + candidate.sizeBytes, 0),
+    candidates,
+  };
+}
+
+/** Transcripts are keyed by the thread id, its session id, or a legacy id. */
+function buildThreadIdIndex(state: PersistedState): Map<string, string> {
+  const index = new Map<string, string>();
+  for (const thread of state.threads) {
+    for (const transcriptId of [thread.legacyTranscriptId, thread.sessionId, thread.id]) {
+      if (transcriptId) {
+        index.set(transcriptId, thread.id);
+      }
+    }
+  }
+  return index;
+}
+
+/**
+ * Applies the transcript retention settings in the background: once on start,
+ * on an interval, and whenever the limits change. Each pass that prunes
+ * anything emits a summary.
+ */
+export class TranscriptRetentionService {
+  private readonly intervalMs: number;
+  private readonly now: () => number;
+  private readonly onError: (error: unknown) => void;
+  private readonly setIntervalImpl: NonNullable<TranscriptRetentionServiceOptions["setInterval"]>;
+  private readonly clearIntervalImpl: NonNullable<
+    TranscriptRetentionServiceOptions["clearInterval"]
+  >;
+  private timer: ReturnType<typeof setInterval> | null = null;
+  private lastPolicy: TranscriptRetentionPolicy | null = null;
+  private inFlight: Promise<TranscriptRetentionSummary | null> | null = null;
+
+  constructor(private readonly options: TranscriptRetentionServiceOptions) {
+    this.intervalMs = Math.max(60_000, options.intervalMs ?? DEFAULT_RETENTION_INTERVAL_MS);
+    this.now = options.now ?? Date.now;
+    this.onError = options.onError ?? (() => {});
+    this.setIntervalImpl = options.setInterval ?? setInterval;
+    this.clearIntervalImpl = options.clearInterval ?? clearInterval;
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+    void this.runSafely();
+    this.timer = this.setIntervalImpl(() => {
+      void this.runSafely();
+    }, this.intervalMs);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  /** Re-runs right away when the limits change while the schedule is running. */
+  handleSettingsChanged(settings: AppSettings): void {
+    const policy = retentionPolicyFromSettings(settings);
+    if (
+      !this.timer ||
+      (policy.maxAgeDays === this.lastPolicy?.maxAgeDays &&
+        policy.maxTotalMb === this.lastPolicy.maxTotalMb)
+    ) {
+      return;
+    }
+    void this.runSafely();
+  }
+
+  async preview(input: PreviewTranscriptRetentionInput = {}): Promise<TranscriptRetentionPreview> {
+    const saved = retentionPolicyFromSettings(await this.options.getSettings());
+    return await this.plan({
+      maxAgeDays: input.maxAgeDays ?? saved.maxAgeDays,
+      maxTotalMb: input.maxTotalMb ?? saved.maxTotalMb,
+    });
+  }
+
+  async runOnce(): Promise<TranscriptRetentionSummary | null> {
+    if (this.inFlight) {
+      return await this.inFlight;
+    }
+    this.inFlight = this.prune();
+    try {
+      return await this.inFlight;
+    } finally {
+      this.inFlight = null;
+    }
+  }
+
+  dispose(): void {
+    if (this.timer) {
+      this.clearIntervalImpl(this.timer);
+      this.timer = null;
+    }
+  }
+
+  private async runSafely(): Promise<void> {
+    try {
+      await this.runOnce();
+    } catch (error) {
+      this.onError(error);
+    }
+  }
+
+  private async plan(policy: TranscriptRetentionPolicy): Promise<TranscriptRetentionPreview> {
+    const [files, state] = await Promise.all([
+      this.options.listTranscriptFiles(),
+      this.options.loadState(),
+    ]);
+    return planTranscriptRetention(files, policy, this.now(), buildThreadIdIndex(state));
+  }
+
+  private async prune(): Promise<TranscriptRetentionSummary | null> {
+    const policy = retentionPolicyFromSettings(await this.options.getSettings());
+    this.lastPolicy = policy;
+    const preview = await this.plan(policy);
+    if (!preview.enabled || preview.candidates.length === 0) {
+      return null;
+    }
+    const summary: TranscriptRetentionSummary = {
+      ranAt: new Date(this.now()).toISOString(),
+      deletedCount: 0,
+      reclaimedBytes: 0,
+      failures: [],
+    };
+    for (const candidate of preview.candidates) {
+      try {
+        await this.options.deleteTranscript(candidate.transcriptId);
+        summary.deletedCount += 1;
+        summary.reclaimedBytes += candidate.sizeBytes;
+      } catch (error) {
+        summary.failures.push({
+          transcriptId: candidate.transcriptId,
+          message: error instanceof Error ? error.message : String(error),
+        });
+      }
+    }
+    this.options.emit(summary);
+    return summary;
+  }
+}
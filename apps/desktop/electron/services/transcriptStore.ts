@@ -0,0 +1,324 @@
+import fs, { type FileHandle } from "node:fs/promises";
+import path from "node:path";
+
+import type { TranscriptStoreKind } from "../../src/lib/desktopApi";
+import { readTranscriptLines, type TranscriptLine } from "./transcriptPages";
+
+const PRIVATE_FILE_MODE = 0o600;
+const PRIVATE_DIR_MODE = 0o700;
+const JSONL_EXTENSION = ".jsonl";
+/** Rows fetched per query while streaming a thread out of SQLite. */
+const SQLITE_READ_BATCH = 500;
+
+/** One encoded transcript line: the event's JSON, sealed when encryption is on. */
+export type StoredTranscriptLine = {
+  ts: string;
+  text: string;
+};
+
+export type TranscriptLineSource = {
+  lines: AsyncIterable<TranscriptLine>;
+  close: () => Promise<void>;
+};
+
+/**
+ * Append log behind a thread's live transcript. Stores hold encoded lines, so
+ * decoding, encryption, and validation stay in `PersistenceService` and both
+ * backends read back exactly what was written. Offsets are opaque to callers:
+ * bytes into the JSONL file, or sequence numbers in SQLite.
+ */
+export interface TranscriptStore {
+  readonly kind: TranscriptStoreKind;
+  append(threadId: string, lines: StoredTranscriptLine[]): Promise<void>;
+  /** Replaces everything stored for the thread in one step. */
+  replace(threadId: string, lines: StoredTranscriptLine[]): Promise<void>;
+  /** Makes appended lines durable. */
+  sync(threadId: string): Promise<void>;
+  /** Streams lines from `offset` in append order; null when nothing is stored for the thread. */
+  openLines(threadId: string, offset: number): Promise<TranscriptLineSource | null>;
+  listTranscriptIds(): Promise<string[]>;
+  delete(threadId: string): Promise<void>;
+}
+
+function isNotFound(error: unknown): boolean {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    "code" in error &&
+    (error as NodeJS.ErrnoException).code === "ENOENT"
+  );
+}
+
+function joinLines(lines: StoredTranscriptLine[]): string {
+  return lines.length > 0 ? `${lines.map((line) => line.text).join("\n")}\n` : "";
+}
+
+/** One `{threadId}.jsonl` file per thread, appended in place. */
+export class JsonlTranscriptStore implements TranscriptStore {
+  readonly kind = "jsonl";
+
+  constructor(
+    private readonly options: {
+      dir: string;
+      /** Resolves and validates the thread's file inside `dir`. */
+      filePath: (threadId: string) => string;
+    },
+  ) {}
+
+  async append(threadId: string, lines: StoredTranscriptLine[]): Promise<void> {
+    const filePath = this.options.filePath(threadId);
+    await fs.mkdir(this.options.dir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    await fs.appendFile(filePath, joinLines(lines), {
+      encoding: "utf8",
+      mode: PRIVATE_FILE_MODE,
+    });
+    await fs.chmod(filePath, PRIVATE_FILE_MODE);
+  }
+
+  async replace(threadId: string, lines: StoredTranscriptLine[]): Promise<void> {
+    const filePath = this.options.filePath(threadId);
+    await fs.mkdir(this.options.dir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    const tempPath = `${filePath}.tmp`;
+    await fs.writeFile(tempPath, joinLines(lines), { encoding: "utf8", mode: PRIVATE_FILE_MODE });
+    await fs.rename(tempPath, filePath);
+    await fs.chmod(filePath, PRIVATE_FILE_MODE);
+  }
+
+  async sync(threadId: string): Promise<void> {
+    let handle: FileHandle;
+    try {
+      // r+ rather than a so a transcript deleted since the append is not recreated.
+      handle = await fs.open(this.options.filePath(threadId), "r+");
+    } catch (error) {
+      if (isNotFound(error)) {
+        return;
+      }
+      throw error;
+    }
+    try {
+      await handle.sync();
+    } finally {
+      await handle.close();
+    }
+  }
+
+  async openLines(threadId: string, offset: number): Promise<TranscriptLineSource | null> {
+    let handle: FileHandle;
+    try {
+      handle = await fs.open(this.options.filePath(threadId), "r");
+    } catch (error) {
+      if (isNotFound(error)) {
+        return null;
+      }
+      throw new Error(`Failed to read transcript: ${String(error)}`);
+    }
+    const stream = handle.createReadStream({ start: offset });
+    return {
+      lines: readTranscriptLines(stream, { offset }),
+      close: async () => {
+        stream.destroy();
+        await handle.close().catch(() => {});
+      },
+    };
+  }
+
+  async listTranscriptIds(): Promise<string[]> {
+    let names: string[];
+    try {
+      names = await fs.readdir(this.options.dir);
+    } catch (error) {
+      if (isNotFound(error)) {
+        return [];
+      }
+      throw new Error(`Failed to list transcripts: ${String(error)}`);
+    }
+    return names
+      .filter((name) => name.endsWith(JSONL_EXTENSION))
+      .map((name) => name.slice(0, -JSONL_EXTENSION.length))
+      .sort();
+  }
+
+  async delete(threadId: string): Promise<void> {
+    await fs.rm(this.options.filePath(threadId), { force: true });
+  }
+}
+
+type SqlValue = string | number | null;
+
+/** The subset of `node:sqlite` (and `bun:sqlite`) the SQLite store needs. */
+export type TranscriptDatabase = {
+  exec(sql: string): unknown;
+  prepare(sql: string): {
+    run(...params: SqlValue[]): unknown;
+    all(...params: SqlValue[]): unknown[];
+  };
+  close(): unknown;
+};
+
+const SCHEMA_SQL = [
+  "PRAGMA journal_mode = WAL;",
+  "PRAGMA synchronous = FULL;",
+  "CREATE TABLE IF NOT EXISTS transcript_events (",
+  "  thread_id TEXT NOT NULL,",
+  "  seq INTEGER NOT NULL,",
+  "  ts TEXT NOT NULL,",
+  "  payload TEXT NOT NULL,",
+  "  PRIMARY KEY (thread_id, seq)",
+  ") WITHOUT ROWID;",
+].join("\n");
+
+/**
+ * Every thread's events in one `transcript_events` table keyed by thread and
+ * sequence number. `payload` holds the encoded line, so sealed transcripts
+ * stay sealed; `ts` is kept in the clear for ordering and queries.
+ */
+export class SqliteTranscriptStore implements TranscriptStore {
+  readonly kind = "sqlite";
+  private database: TranscriptDatabase | null = null;
+
+  constructor(
+    private readonly options: {
+      filePath: string;
+      openDatabase: (filePath: string) => TranscriptDatabase;
+    },
+  ) {}
+
+  async append(threadId: string, lines: StoredTranscriptLine[]): Promise<void> {
+    const database = await this.openOrCreate();
+    this.transaction(database, () => this.insert(database, threadId, lines));
+  }
+
+  async replace(threadId: string, lines: StoredTranscriptLine[]): Promise<void> {
+    const database = lines.length > 0 ? await this.openOrCreate() : await this.openExisting();
+    if (!database) {
+      return;
+    }
+    this.transaction(database, () => {
+      database.prepare("DELETE FROM transcript_events WHERE thread_id = ?").run(threadId);
+      this.insert(database, threadId, lines);
+    });
+  }
+
+  /** Commits are durable already; see `synchronous = FULL`. */
+  async sync(): Promise<void> {}
+
+  async openLines(threadId: string, offset: number): Promise<TranscriptLineSource | null> {
+    const database = await this.openExisting();
+    if (!database) {
+      return null;
+    }
+    const select = database.prepare(
+      [
+        "SELECT seq, payload FROM transcript_events",
+        "WHERE thread_id = ? AND seq >= ? ORDER BY seq LIMIT ?",
+      ].join(" "),
+    );
+    const first = select.all(threadId, offset, SQLITE_READ_BATCH) as Array<{
+      seq: number;
+      payload: string;
+    }>;
+    if (first.length === 0) {
+      return null;
+    }
+    async function* lines(): AsyncGenerator<TranscriptLine> {
+      let rows = first;
+      while (rows.length > 0) {
+        for (const row of rows) {
+          yield { text: row.payload, bytes: Buffer.byteLength(row.payload), end: row.seq + 1 };
+        }
+        if (rows.length < SQLITE_READ_BATCH) {
+          return;
+        }
+        rows = select.all(threadId, rows.at(-1)!.seq + 1, SQLITE_READ_BATCH) as typeof first;
+      }
+    }
+    return { lines: lines(), close: async () => {} };
+  }
+
+  async listTranscriptIds(): Promise<string[]> {
+    const database = await this.openExisting();
+    if (!database) {
+      return [];
+    }
+    const rows = database
+      .prepare("SELECT DISTINCT thread_id FROM transcript_events ORDER BY thread_id")
+      .all() as Array<{ thread_id: string }>;
+    return rows.map((row) => row.thread_id);
+  }
+
+  /** Rows stored for the thread; one indexed lookup, so cheap enough to check per preview. */
+  async countLines(threadId: string): Promise<number> {
+    const database = await this.openExisting();
+    if (!database) {
+      return 0;
+    }
+    const [{ count }] = database
+      .prepare("SELECT COUNT(*) AS count FROM transcript_events WHERE thread_id = ?")
+      .all(threadId) as [{ count: number }];
+    return count;
+  }
+
+  async delete(threadId: string): Promise<void> {
+    const database = await this.openExisting();
+    database?.prepare("DELETE FROM transcript_events WHERE thread_id = ?").run(threadId);
+  }
+
+  close(): void {
+    this.database?.close();
+    this.database = null;
+  }
+
+  /** Reads and deletes leave a missing database uncreated. */
+  private async openExisting(): Promise<TranscriptDatabase | null> {
+    if (this.database) {
+      return this.database;
+    }
+    try {
+      await fs.access(this.options.filePath);
+    } catch (error) {
+      if (isNotFound(error)) {
+        return null;
+      }
+      throw error;
+    }
+    return await this.openOrCreate();
+  }
+
+  private async openOrCreate(): Promise<TranscriptDatabase> {
+    if (this.database) {
+      return this.database;
+    }
+    const { filePath } = this.options;
+    await fs.mkdir(path.dirname(filePath), { recursive: true, mode: PRIVATE_DIR_MODE });
+    const database = this.options.openDatabase(filePath);
+    database.exec(SCHEMA_SQL);
+    await fs.chmod(filePath, PRIVATE_FILE_MODE);
+    this.database = database;
+    return database;
+  }
+
+  private insert(database: TranscriptDatabase, threadId: string, lines: StoredTranscriptLine[]) {
+    const [{ next }] = database
+      .prepare(
+        "SELECT COALESCE(MAX(seq), -1) + 1 AS next FROM transcript_events WHERE thread_id = ?",
+      )
+      .all(threadId) as [{ next: number }];
+    const insert = database.prepare(
+      "INSERT INTO transcript_events (thread_id, seq, ts, payload) VALUES (?, ?, ?, ?)",
+    );
+    lines.forEach((line, index) => {
+      insert.run(threadId, next + index, line.ts, line.text);
+    });
+  }
+
+  private transaction(database: TranscriptDatabase, fn: () => void): void {
+    database.exec("BEGIN IMMEDIATE");
+    try {
+      fn();
+      database.exec("COMMIT");
+    } catch (error) {
+      database.exec("ROLLBACK");
+      throw error;
+    }
+  }
+}
@@ -0,0 +1,81 @@
+import type { TranscriptEvent } from "../../src/app/types";
+import { DESKTOP_EVENT_CHANNELS, type TranscriptAppendedEvent } from "../../src/lib/desktopApi";
+
+/** The slice of `WebContents` needed to follow transcripts. */
+export type TranscriptSubscriber = {
+  id: number;
+  isDestroyed(): boolean;
+  send(channel: string, payload: TranscriptAppendedEvent): void;
+  once(event: "destroyed", listener: () => void): unknown;
+};
+
+type SubscriberEntry = {
+  subscriber: TranscriptSubscriber;
+  threadIds: Set<string>;
+};
+
+/**
+ * Pushes transcript appends to the renderers following each thread, so a
+ * secondary window can tail a thread without re-reading its JSONL file.
+ * Subscriptions are dropped with their `WebContents`.
+ */
+export class TranscriptSubscriptions {
+  private readonly entries = new Map<number, SubscriberEntry>();
+
+  subscribe(subscriber: TranscriptSubscriber, threadId: string): void {
+    let entry = this.entries.get(subscriber.id);
+    if (!entry) {
+      entry = { subscriber, threadIds: new Set() };
+      this.entries.set(subscriber.id, entry);
+      subscriber.once("destroyed", () => {
+        this.entries.delete(subscriber.id);
+      });
+    }
+    entry.threadIds.add(threadId);
+  }
+
+  /** Returns false when the subscriber was not following the thread. */
+  unsubscribe(subscriber: TranscriptSubscriber, threadId: string): boolean {
+    const entry = this.entries.get(subscriber.id);
+    if (!entry?.threadIds.delete(threadId)) {
+      return false;
+    }
+    // Keep the entry so the destroyed listener is not registered twice.
+    return true;
+  }
+
+  isSubscribed(subscriberId: number, threadId: string): boolean {
+    return this.entries.get(subscriberId)?.threadIds.has(threadId) ?? false;
+  }
+
+  publish(events: TranscriptEvent[]): void {
+    if (this.entries.size === 0 || events.length === 0) {
+      return;
+    }
+
+    const byThread = new Map<string, TranscriptEvent[]>();
+    for (const event of events) {
+      const bucket = byThread.get(event.threadId);
+      if (bucket) {
+        bucket.push(event);
+      } else {
+        byThread.set(event.threadId, [event]);
+      }
+    }
+
+    for (const { subscriber, threadIds } of this.entries.values()) {
+      if (subscriber.isDestroyed()) {
+        this.entries.delete(subscriber.id);
+        continue;
+      }
+      for (const [threadId, threadEvents] of byThread) {
+        if (threadIds.has(threadId)) {
+          subscriber.send(DESKTOP_EVENT_CHANNELS.transcriptAppended, {
+            threadId,
+            events: threadEvents,
+          });
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,194 @@
+import type { TranscriptBatchInput, TranscriptThrottledEvent } from "../../src/lib/desktopApi";
+
+const DEFAULT_MAX_PAYLOAD_BYTES = 1024 * 1024;
+const DEFAULT_BURST_EVENTS = 1_000;
+const DEFAULT_EVENTS_PER_SECOND = 100;
+const DEFAULT_BURST_BYTES = 16 * 1024 * 1024;
+const DEFAULT_BYTES_PER_SECOND = 2 * 1024 * 1024;
+const DEFAULT_REPORT_INTERVAL_MS = 5_000;
+const TRUNCATED_PREVIEW_CHARS = 4_096;
+/** Buckets are dropped past this many threads once they have refilled. */
+const MAX_TRACKED_THREADS = 256;
+
+export const TRUNCATED_TRANSCRIPT_PAYLOAD_TYPE = "transcript_truncated";
+
+type TimerHandle = ReturnType<typeof setTimeout>;
+
+type TranscriptThrottleOptions = {
+  /** Largest serialized payload written as-is; bigger ones are replaced by a marker. */
+  maxPayloadBytes?: number;
+  burstEvents?: number;
+  eventsPerSecond?: number;
+  burstBytes?: number;
+  bytesPerSecond?: number;
+  /** Minimum time between warnings for one thread; counts in between are combined. */
+  reportIntervalMs?: number;
+  onThrottled?: (event: TranscriptThrottledEvent) => void;
+  now?: () => number;
+  setTimeout?: (callback: () => void, ms: number) => TimerHandle;
+  clearTimeout?: (handle: TimerHandle) => void;
+};
+
+type ThreadBudget = {
+  events: number;
+  bytes: number;
+  refilledAt: number;
+};
+
+type PendingReport = {
+  droppedEvents: number;
+  droppedBytes: number;
+  truncatedEvents: number;
+  timer: TimerHandle | null;
+};
+
+function payloadType(payload: unknown): string {
+  const type = (payload as { type?: unknown } | null)?.type;
+  return typeof type === "string" && type ? type : "untyped";
+}
+
+/**
+ * Keeps a runaway sidecar from filling the disk through transcript appends.
+ * Each thread has token buckets for events and bytes; appends past either
+ * budget are dropped, and payloads over the size cap are replaced by a
+ * `transcript_truncated` marker with a short preview. Dropped and truncated
+ * events are reported through `onThrottled`, at most once per interval per
+ * thread.
+ */
+export class TranscriptThrottle {
+  private readonly budgets = new Map<string, ThreadBudget>();
+  private readonly reports = new Map<string, PendingReport>();
+  private readonly maxPayloadBytes: number;
+  private readonly burstEvents: number;
+  private readonly eventsPerMs: number;
+  private readonly burstBytes: number;
+  private readonly bytesPerMs: number;
+  private readonly reportIntervalMs: number;
+  private readonly now: () => number;
+  private readonly setTimeoutImpl: NonNullable<TranscriptThrottleOptions["setTimeout"]>;
+  private readonly clearTimeoutImpl: NonNullable<TranscriptThrottleOptions["clearTimeout"]>;
+
+  constructor(private readonly options: TranscriptThrottleOptions = {}) {
+    this.maxPayloadBytes = options.maxPayloadBytes ?? DEFAULT_MAX_PAYLOAD_BYTES;
+    this.burstEvents = options.burstEvents ?? DEFAULT_BURST_EVENTS;
+    this.eventsPerMs = (options.eventsPerSecond ?? DEFAULT_EVENTS_PER_SECOND) / 1_000;
+    this.burstBytes = Math.max(this.maxPayloadBytes, options.burstBytes ?? DEFAULT_BURST_BYTES);
+    this.bytesPerMs = (options.bytesPerSecond ?? DEFAULT_BYTES_PER_SECOND) / 1_000;
+    this.reportIntervalMs = options.reportIntervalMs ?? DEFAULT_REPORT_INTERVAL_MS;
+    this.now = options.now ?? Date.now;
+    this.setTimeoutImpl = options.setTimeout ?? setTimeout;
+    this.clearTimeoutImpl = options.clearTimeout ?? clearTimeout;
+  }
+
+  /** Returns the events to write, in order, with oversized payloads truncated. */
+  admit(events: TranscriptBatchInput[]): TranscriptBatchInput[] {
+    const now = this.now();
+    const admitted: TranscriptBatchInput[] = [];
+    for (const event of events) {
+      const budget = this.refill(event.threadId, now);
+      const serialized = JSON.stringify(event.payload) ?? "null";
+      const bytes = Buffer.byteLength(serialized, "utf8");
+      const truncated = bytes > this.maxPayloadBytes;
+      const payload = truncated
+        ? {
+            type: TRUNCATED_TRANSCRIPT_PAYLOAD_TYPE,
+            originalType: payloadType(event.payload),
+            originalBytes: bytes,
+            preview: serialized.slice(0, TRUNCATED_PREVIEW_CHARS),
+          }
+        : event.payload;
+      const cost = truncated ? Buffer.byteLength(JSON.stringify(payload), "utf8") : bytes;
+      if (budget.events < 1 || budget.bytes < cost) {
+        this.record(event.threadId, { droppedEvents: 1, droppedBytes: bytes });
+        continue;
+      }
+      budget.events -= 1;
+      budget.bytes -= cost;
+      if (truncated) {
+        this.record(event.threadId, { truncatedEvents: 1 });
+        admitted.push({ ...event, payload });
+      } else {
+        admitted.push(event);
+      }
+    }
+    this.pruneBudgets(now);
+    return admitted;
+  }
+
+  dispose(): void {
+    for (const report of this.reports.values()) {
+      if (report.timer) {
+        this.clearTimeoutImpl(report.timer);
+      }
+    }
+    this.reports.clear();
+  }
+
+  private refill(threadId: string, now: number): ThreadBudget {
+    const budget = this.budgets.get(threadId);
+    if (!budget) {
+      const fresh = { events: this.burstEvents, bytes: this.burstBytes, refilledAt: now };
+      this.budgets.set(threadId, fresh);
+      return fresh;
+    }
+    const elapsed = Math.max(0, now - budget.refilledAt);
+    budget.events = Math.min(this.burstEvents, budget.events + elapsed * this.eventsPerMs);
+    budget.bytes = Math.min(this.burstBytes, budget.bytes + elapsed * this.bytesPerMs);
+    budget.refilledAt = now;
+    return budget;
+  }
+
+  private pruneBudgets(now: number): void {
+    if (this.budgets.size <= MAX_TRACKED_THREADS) {
+      return;
+    }
+    for (const [threadId, budget] of this.budgets) {
+      const elapsed = now - budget.refilledAt;
+      const eventsFull = budget.events + elapsed * this.eventsPerMs >= this.burstEvents;
+      const bytesFull = budget.bytes + elapsed * this.bytesPerMs >= this.burstBytes;
+      if (eventsFull && bytesFull) {
+        this.budgets.delete(threadId);
+      }
+    }
+  }
+
+  /** Adds to the thread's pending warning, sending it now if none went out recently. */
+  private record(
+    threadId: string,
+    counts: Partial<Pick<PendingReport, "droppedEvents" | "droppedBytes" | "truncatedEvents">>,
+  ): void {
+    let report = this.reports.get(threadId);
+    if (!report) {
+      report = { droppedEvents: 0, droppedBytes: 0, truncatedEvents: 0, timer: null };
+      this.reports.set(threadId, report);
+    }
+    report.droppedEvents += counts.droppedEvents ?? 0;
+    report.droppedBytes += counts.droppedBytes ?? 0;
+    report.truncatedEvents += counts.truncatedEvents ?? 0;
+    if (!report.timer) {
+      this.flush(threadId, report);
+    }
+  }
+
+  private flush(threadId: string, report: PendingReport): void {
+    if (report.droppedEvents === 0 && report.truncatedEvents === 0) {
+      this.reports.delete(threadId);
+      return;
+    }
+    this.options.onThrottled?.({
+      threadId,
+      droppedEvents: report.droppedEvents,
+      droppedBytes: report.droppedBytes,
+      truncatedEvents: report.truncatedEvents,
+      at: new Date(this.now()).toISOString(),
+    });
+    report.droppedEvents = 0;
+    report.droppedBytes = 0;
+    report.truncatedEvents = 0;
+    report.timer = this.setTimeoutImpl(() => {
+      report.timer = null;
+      this.flush(threadId, report);
+    }, this.reportIntervalMs);
+    (report.timer as { unref?: () => void }).unref?.();
+  }
+}
@@ -0,0 +1,73 @@
+const RFC3339_TIMESTAMP =
+  /^(\d{4})-(\d{2})-(\d{2})[Tt](\d{2}):(\d{2}):(\d{2})(\.\d+)?([Zz]|[+-]\d{2}:\d{2})$/;
+const EPOCH_MILLISECONDS = /^\d{12,14}$/;
+
+export class InvalidTranscriptTimestampError extends Error {
+  readonly code = "invalid_transcript_timestamp";
+
+  constructor(
+    readonly threadId: string,
+    readonly ts: string,
+  ) {
+    super(`Rejected transcript event for ${threadId}: ts "${ts}" is not an RFC 3339 timestamp.`);
+    this.name = "InvalidTranscriptTimestampError";
+  }
+}
+
+function fieldsInRange(match: RegExpExecArray): boolean {
+  const [year, month, day, hour, minute, second] = match.slice(1, 7).map(Number);
+  const offset = match[8];
+  const daysInMonth = new Date(Date.UTC(year, month, 0)).getUTCDate();
+  if (month < 1 || month > 12 || day < 1 || day > daysInMonth) {
+    return false;
+  }
+  // Leap seconds (":60") are valid RFC 3339 but cannot be represented by `Date`.
+  if (hour > 23 || minute > 59 || second > 59) {
+    return false;
+  }
+  if (offset.length === 6) {
+    const offsetHours = Number(offset.slice(1, 3));
+    const offsetMinutes = Number(offset.slice(4, 6));
+    return offsetHours <= 23 && offsetMinutes <= 59;
+  }
+  return true;
+}
+
+/**
+ * Parses a strict RFC 3339 date-time and returns it as a UTC ISO string with
+ * millisecond precision, so lexical order is chronological order. Returns null
+ * for anything else, including dates that do not exist.
+ */
+export function parseRfc3339Timestamp(ts: string): string | null {
+  const match = RFC3339_TIMESTAMP.exec(ts);
+  if (!match || !fieldsInRange(match)) {
+    return null;
+  }
+  const ms = Date.parse(ts.toUpperCase());
+  return Number.isNaN(ms) ? null : new Date(ms).toISOString();
+}
+
+/** Normalizes an appended event's `ts`; throws unless it is strict RFC 3339. */
+export function normalizeTranscriptTimestamp(threadId: string, ts: string): string {
+  const normalized = parseRfc3339Timestamp(ts);
+  if (normalized === null) {
+    throw new InvalidTranscriptTimestampError(threadId, ts);
+  }
+  return normalized;
+}
+
+/**
+ * Best-effort reading of a `ts` written before timestamps were validated:
+ * RFC 3339, epoch milliseconds, or anything `Date` can parse. Strings without
+ * a zone, such as `toLocaleString()` output, are read in this machine's time
+ * zone, which is where they were written. Returns null when nothing fits.
+ */
+export function coerceLegacyTranscriptTimestamp(ts: string): string | null {
+  const strict = parseRfc3339Timestamp(ts);
+  if (strict !== null) {
+    return strict;
+  }
+  const trimmed = ts.trim();
+  const ms = EPOCH_MILLISECONDS.test(trimmed) ? Number(trimmed) : Date.parse(trimmed);
+  return Number.isNaN(ms) ? null : new Date(ms).toISOString();
+}
@@ -0,0 +1,155 @@
+import { z } from "zod";
+
+import type { TranscriptEvent } from "../../src/app/types";
+import type { TranscriptBatchInput, TranscriptValidationMode } from "../../src/lib/desktopApi";
+
+const MAX_REPORTED_ISSUES = 5;
+
+// Client payloads may be recorded before the session exists, so the id is only
+// required on server events.
+const optionalSessionId = z.string().min(1).nullish();
+const sessionId = z.string().min(1);
+
+const clientPayloadSchemas: Record<string, z.ZodType> = {
+  user_message: z.object({ sessionId: optionalSessionId, text: z.string() }).passthrough(),
+  steer_message: z.object({ sessionId: optionalSessionId, text: z.string() }).passthrough(),
+  ask_response: z
+    .object({ sessionId: optionalSessionId, requestId: z.string().min(1), answer: z.string() })
+    .passthrough(),
+  approval_response: z
+    .object({ sessionId: optionalSessionId, requestId: z.string().min(1), approved: z.boolean() })
+    .passthrough(),
+  attachment: z
+    .object({
+      hash: z.string().regex(/^[a-f0-9]{64}$/),
+      filename: z.string().min(1),
+      mimeType: z.string().min(1),
+      sizeBytes: z.number().int().nonnegative(),
+    })
+    .passthrough(),
+};
+
+const serverPayloadSchemas: Record<string, z.ZodType> = {
+  user_message: z.object({ sessionId, text: z.string() }).passthrough(),
+  assistant_message: z.object({ sessionId, text: z.string() }).passthrough(),
+  reasoning: z
+    .object({ sessionId, kind: z.enum(["reasoning", "summary"]), text: z.string() })
+    .passthrough(),
+  session_busy: z.object({ sessionId, busy: z.boolean() }).passthrough(),
+  steer_accepted: z
+    .object({ sessionId, turnId: z.string().min(1), text: z.string() })
+    .passthrough(),
+  model_stream_chunk: z
+    .object({
+      sessionId,
+      turnId: z.string().min(1),
+      index: z.number().int().nonnegative(),
+      provider: z.string().min(1),
+      model: z.string(),
+      partType: z.string().min(1),
+      part: z.record(z.string(), z.unknown()),
+    })
+    .passthrough(),
+  ask: z
+    .object({
+      sessionId,
+      requestId: z.string().min(1),
+      question: z.string(),
+      options: z.array(z.string()).optional(),
+    })
+    .passthrough(),
+  approval: z
+    .object({
+      sessionId,
+      requestId: z.string().min(1),
+      command: z.string(),
+      dangerous: z.boolean(),
+    })
+    .passthrough(),
+  todos: z.object({ sessionId, todos: z.array(z.unknown()) }).passthrough(),
+  turn_usage: z.object({ sessionId, turnId: z.string().min(1) }).passthrough(),
+  error: z
+    .object({ sessionId, message: z.string(), code: z.string().min(1), source: z.string() })
+    .passthrough(),
+};
+
+const PAYLOAD_SCHEMAS: Record<TranscriptEvent["direction"], Record<string, z.ZodType>> = {
+  client: clientPayloadSchemas,
+  server: serverPayloadSchemas,
+};
+
+function formatIssue(issue: { path: PropertyKey[]; message: string }): string {
+  const location = issue.path.map(String).join(".");
+  return location ? `${location}: ${issue.message}` : issue.message;
+}
+
+/**
+ * Checks a transcript payload against the schema registered for its `type`.
+ * Returns the problems found, or an empty list when it matches. Types without
+ * a schema pass, so events added by newer servers are not flagged; extra keys
+ * pass for the same reason.
+ */
+export function validateTranscriptPayload(
+  direction: TranscriptEvent["direction"],
+  payload: unknown,
+): string[] {
+  if (typeof payload !== "object" || payload === null || Array.isArray(payload)) {
+    return ["payload: expected an object"];
+  }
+  const type = (payload as { type?: unknown }).type;
+  if (typeof type !== "string" || !type) {
+    return ["type: expected a non-empty string"];
+  }
+  const schema = PAYLOAD_SCHEMAS[direction][type];
+  if (!schema) {
+    return [];
+  }
+  const result = schema.safeParse(payload);
+  return result.success ? [] : result.error.issues.map(formatIssue);
+}
+
+export class InvalidTranscriptPayloadError extends Error {
+  readonly code = "invalid_transcript_payload";
+
+  constructor(
+    readonly threadId: string,
+    readonly payloadType: string,
+    readonly issues: string[],
+  ) {
+    super(
+      `Rejected ${payloadType} transcript event for ${threadId}: ` +
+        issues.slice(0, MAX_REPORTED_ISSUES).join("; "),
+    );
+    this.name = "InvalidTranscriptPayloadError";
+  }
+}
+
+/**
+ * Applies the `transcriptValidation` setting to a batch before it is written.
+ * "annotate" copies each mismatch onto its event as `validationIssues`, and
+ * "strict" throws for the first malformed event so nothing in the batch is
+ * written.
+ */
+export function applyTranscriptValidation(
+  events: TranscriptBatchInput[],
+  mode: TranscriptValidationMode,
+): Array<TranscriptBatchInput & Pick<TranscriptEvent, "validationIssues">> {
+  if (mode === "off") {
+    return events;
+  }
+  return events.map((event) => {
+    const issues = validateTranscriptPayload(event.direction, event.payload);
+    if (issues.length === 0) {
+      return event;
+    }
+    if (mode === "strict") {
+      const type = (event.payload as { type?: unknown } | null)?.type;
+      throw new InvalidTranscriptPayloadError(
+        event.threadId,
+        typeof type === "string" && type ? type : "untyped",
+        issues,
+      );
+    }
+    return { ...event, validationIssues: issues.slice(0, MAX_REPORTED_ISSUES) };
+  });
+}
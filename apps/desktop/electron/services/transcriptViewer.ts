@@ -0,0 +1,172 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import type { TranscriptEvent } from "../../src/app/types";
+import type { OpenedTranscriptFile } from "../../src/lib/desktopApi";
+import { inferThreadFromTranscript } from "./stateRebuild";
+import { isEncryptedTranscriptLine } from "./transcriptEncryption";
+import { validateTranscriptPayload } from "./transcriptValidation";
+
+export const VIEWER_THREAD_ID_PREFIX = "viewer-";
+/** Extension of exported threads, which the OS opens with the app. */
+export const TRANSCRIPT_EXPORT_EXTENSION = ".cowork-thread";
+const DEFAULT_MAX_FILE_BYTES = 50 * 1024 * 1024;
+const DEFAULT_MAX_OPEN_TRANSCRIPTS = 20;
+
+type TranscriptViewerRegistryOptions = {
+  createId?: () => string;
+  maxFileBytes?: number;
+  maxOpenTranscripts?: number;
+};
+
+export function isViewerThreadId(threadId: string): boolean {
+  return threadId.startsWith(VIEWER_THREAD_ID_PREFIX);
+}
+
+export function isTranscriptExportPath(filePath: string): boolean {
+  return filePath.toLowerCase().endsWith(TRANSCRIPT_EXPORT_EXTENSION);
+}
+
+/** Writes events as the JSONL that `parseTranscriptFile` reads back. */
+export function renderTranscriptExport(events: readonly TranscriptEvent[]): string {
+  return events
+    .map(({ ts, threadId, direction, payload }) =>
+      JSON.stringify({ ts, threadId, direction, payload }),
+    )
+    .join("\n")
+    .concat("\n");
+}
+
+export class ReadOnlyThreadError extends Error {
+  readonly code = "read_only_thread";
+
+  constructor(readonly threadId: string) {
+    super(`Thread ${threadId} is a read-only transcript viewer`);
+    this.name = "ReadOnlyThreadError";
+  }
+}
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+/**
+ * Parses an exported JSONL transcript into events for `threadId`. Every line
+ * must be a plaintext transcript event; the first one that is not fails the
+ * whole file. Payloads that do not match their schema are kept and annotated,
+ * as with the "annotate" validation mode.
+ */
+export function parseTranscriptFile(raw: string, threadId: string): TranscriptEvent[] {
+  const events: TranscriptEvent[] = [];
+  for (const [index, line] of raw.split(/\r?\n/).entries()) {
+    if (!line.trim()) {
+      continue;
+    }
+    const lineNumber = index + 1;
+    if (isEncryptedTranscriptLine(line)) {
+      throw new Error(`Line ${lineNumber} is encrypted with another installation's key`);
+    }
+    let parsed: unknown;
+    try {
+      parsed = JSON.parse(line);
+    } catch {
+      throw new Error(`Line ${lineNumber} is not valid JSON`);
+    }
+    if (
+      !isRecord(parsed) ||
+      typeof parsed.ts !== "string" ||
+      Number.isNaN(Date.parse(parsed.ts)) ||
+      (parsed.direction !== "client" && parsed.direction !== "server") ||
+      !isRecord(parsed.payload)
+    ) {
+      throw new Error(`Line ${lineNumber} is not a transcript event`);
+    }
+    const issues = validateTranscriptPayload(parsed.direction, parsed.payload);
+    events.push({
+      ts: parsed.ts,
+      threadId,
+      direction: parsed.direction,
+      payload: parsed.payload,
+      ...(issues.length > 0 ? { validationIssues: issues } : {}),
+    });
+  }
+  if (events.length === 0) {
+    throw new Error("The file contains no transcript events");
+  }
+  return events;
+}
+
+/**
+ * Transcripts opened from outside app data for review. Each one gets a
+ * synthetic `viewer-` thread id that is never persisted and that the append
+ * handlers refuse, so the loaded session cannot be extended. The oldest are
+ * dropped once too many are open.
+ */
+export class TranscriptViewerRegistry {
+  private readonly transcripts = new Map<string, TranscriptEvent[]>();
+  private readonly createId: () => string;
+  private readonly maxFileBytes: number;
+  private readonly maxOpenTranscripts: number;
+
+  constructor(options: TranscriptViewerRegistryOptions = {}) {
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+    this.maxFileBytes = options.maxFileBytes ?? DEFAULT_MAX_FILE_BYTES;
+    this.maxOpenTranscripts = Math.max(
+      1,
+      options.maxOpenTranscripts ?? DEFAULT_MAX_OPEN_TRANSCRIPTS,
+    );
+  }
+
+  async open(filePath: string): Promise<OpenedTranscriptFile> {
+    if (!path.isAbsolute(filePath)) {
+      throw new Error(`Transcript path must be absolute: ${filePath}`);
+    }
+    const stat = await fs.stat(filePath);
+    if (!stat.isFile()) {
+      throw new Error(`Not a file: ${filePath}`);
+    }
+    if (stat.size > this.maxFileBytes) {
+      throw new Error(
+        `Transcript is ${stat.size} bytes; files over ${this.maxFileBytes} bytes cannot be opened`,
+      );
+    }
+
+    const threadId = `${VIEWER_THREAD_ID_PREFIX}${this.createId()}`;
+    let events: TranscriptEvent[];
+    try {
+      events = parseTranscriptFile(await fs.readFile(filePath, "utf8"), threadId);
+    } catch (error) {
+      throw new Error(
+        `Cannot open ${path.basename(filePath)}: ${error instanceof Error ? error.message : error}`,
+      );
+    }
+
+    this.transcripts.set(threadId, events);
+    for (const staleId of this.transcripts.keys()) {
+      if (this.transcripts.size <= this.maxOpenTranscripts) {
+        break;
+      }
+      this.transcripts.delete(staleId);
+    }
+
+    const inferred = inferThreadFromTranscript(threadId, events);
+    return {
+      threadId,
+      title:
+        inferred && inferred.titleFrom !== "fallback" ? inferred.title : path.basename(filePath),
+      sourcePath: filePath,
+      readOnly: true,
+      events: [...events],
+    };
+  }
+
+  readTranscript(threadId: string): TranscriptEvent[] | null {
+    const events = this.transcripts.get(threadId);
+    return events ? [...events] : null;
+  }
+
+  close(threadId: string): boolean {
+    return this.transcripts.delete(threadId);
+  }
+}
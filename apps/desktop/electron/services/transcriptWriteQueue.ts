@@ -0,0 +1,152 @@
+const DEFAULT_MAX_PENDING = 1_000;
+const DEFAULT_SYNC_INTERVAL_MS = 1_000;
+
+type TranscriptWriteQueueOptions<T> = {
+  /** Appends one coalesced chunk; never called twice at once for the same thread. */
+  write: (threadId: string, items: T[]) => Promise<void>;
+  /** Forces everything appended for the thread so far onto stable storage. */
+  sync: (threadId: string) => Promise<void>;
+  /** Items a thread may hold unwritten before `enqueue` waits for its writer to catch up. */
+  maxPendingPerThread?: number;
+  syncIntervalMs?: number;
+  onSyncError?: (threadId: string, error: unknown) => void;
+};
+
+type PendingChunk<T> = {
+  items: T[];
+  resolve: () => void;
+  reject: (error: unknown) => void;
+};
+
+type ThreadWriter<T> = {
+  pending: PendingChunk<T>[];
+  /** Queued plus in-flight items, for backpressure. */
+  unwritten: number;
+  draining: Promise<void> | null;
+  dirty: boolean;
+  syncTimer: ReturnType<typeof setTimeout> | null;
+};
+
+/**
+ * One writer per thread: chunks queued while a write is in flight are
+ * coalesced into the next append, in the order they were queued. Appended
+ * data is synced on an interval and on `flush`.
+ */
+export class TranscriptWriteQueue<T> {
+  private readonly threads = new Map<string, ThreadWriter<T>>();
+  private readonly maxPendingPerThread: number;
+  private readonly syncIntervalMs: number;
+
+  constructor(private readonly options: TranscriptWriteQueueOptions<T>) {
+    this.maxPendingPerThread = Math.max(1, options.maxPendingPerThread ?? DEFAULT_MAX_PENDING);
+    this.syncIntervalMs = options.syncIntervalMs ?? DEFAULT_SYNC_INTERVAL_MS;
+  }
+
+  /** Resolves once the items have been appended, not necessarily synced. */
+  async enqueue(threadId: string, items: T[]): Promise<void> {
+    if (items.length === 0) {
+      return;
+    }
+    let writer = this.writerFor(threadId);
+    while (writer.draining && writer.unwritten + items.length > this.maxPendingPerThread) {
+      await writer.draining;
+      // An idle writer may have been dropped while this call waited.
+      writer = this.writerFor(threadId);
+    }
+    const written = new Promise<void>((resolve, reject) => {
+      writer.pending.push({ items, resolve, reject });
+    });
+    writer.unwritten += items.length;
+    this.startDrain(threadId, writer);
+    await written;
+  }
+
+  /** Waits for every queued chunk to be appended, then syncs the threads that were written. */
+  async flush(): Promise<void> {
+    for (const writer of this.threads.values()) {
+      while (writer.draining) {
+        await writer.draining;
+      }
+    }
+    await Promise.all([...this.threads.keys()].map((threadId) => this.syncThread(threadId)));
+  }
+
+  private writerFor(threadId: string): ThreadWriter<T> {
+    let writer = this.threads.get(threadId);
+    if (!writer) {
+      writer = { pending: [], unwritten: 0, draining: null, dirty: false, syncTimer: null };
+      this.threads.set(threadId, writer);
+    }
+    return writer;
+  }
+
+  private startDrain(threadId: string, writer: ThreadWriter<T>): void {
+    if (writer.draining) {
+      return;
+    }
+    writer.draining = (async () => {
+      while (writer.pending.length > 0) {
+        const chunks = writer.pending.splice(0);
+        const items = chunks.flatMap((chunk) => chunk.items);
+        try {
+          await this.options.write(threadId, items);
+          writer.dirty = true;
+          for (const chunk of chunks) {
+            chunk.resolve();
+          }
+        } catch (error) {
+          for (const chunk of chunks) {
+            chunk.reject(error);
+          }
+        } finally {
+          writer.unwritten -= items.length;
+        }
+      }
+      writer.draining = null;
+      if (!writer.dirty && !writer.syncTimer) {
+        // Every write failed, so no sync will come along to drop the idle writer.
+        this.dropIdleWriter(threadId, writer);
+        return;
+      }
+      this.scheduleSync(threadId, writer);
+    })();
+  }
+
+  private scheduleSync(threadId: string, writer: ThreadWriter<T>): void {
+    if (!writer.dirty || writer.syncTimer) {
+      return;
+    }
+    writer.syncTimer = setTimeout(() => {
+      void this.syncThread(threadId);
+    }, this.syncIntervalMs);
+    (writer.syncTimer as { unref?: () => void }).unref?.();
+  }
+
+  private async syncThread(threadId: string): Promise<void> {
+    const writer = this.threads.get(threadId);
+    if (!writer) {
+      return;
+    }
+    if (writer.syncTimer) {
+      clearTimeout(writer.syncTimer);
+      writer.syncTimer = null;
+    }
+    if (writer.dirty) {
+      writer.dirty = false;
+      try {
+        await this.options.sync(threadId);
+      } catch (error) {
+        this.options.onSyncError?.(threadId, error);
+      }
+    }
+    if (!writer.dirty) {
+      this.dropIdleWriter(threadId, writer);
+    }
+  }
+
+  private dropIdleWriter(threadId: string, writer: ThreadWriter<T>): void {
+    if (!writer.draining && writer.pending.length === 0 && this.threads.get(threadId) === writer) {
+      this.threads.delete(threadId);
+    }
+  }
+}
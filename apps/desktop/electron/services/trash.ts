@@ -0,0 +1,323 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import type { ThreadRecord, WorkspaceRecord } from "../../src/app/types";
+import type { TrashEntry, TrashEntryKind } from "../../src/lib/desktopApi";
+import { isPathEqualOrInside } from "./pathBoundary";
+
+const DEFAULT_PURGE_INTERVAL_MS = 6 * 60 * 60_000;
+const DAY_MS = 24 * 60 * 60_000;
+const MANIFEST_FILE_NAME = "manifest.json";
+const PRIVATE_DIR_MODE = 0o700;
+const PRIVATE_FILE_MODE = 0o600;
+
+export type TrashedRecords = {
+  workspaces: WorkspaceRecord[];
+  threads: ThreadRecord[];
+};
+
+type TrashManifestEntry = {
+  id: string;
+  kind: TrashEntryKind;
+  label: string;
+  deletedAt: string;
+  sizeBytes: number;
+  /** Original locations relative to the data directory; stored as `<id>/<index>`. */
+  files: string[];
+  records: TrashedRecords;
+};
+
+type TrashManifest = {
+  version: 1;
+  entries: TrashManifestEntry[];
+};
+
+type TrashServiceOptions = {
+  /** App data directory; only files inside it can be trashed or restored. */
+  dataDir: string;
+  /** Current `trashRetentionDays` setting; 0 turns the automatic purge off. */
+  getRetentionDays: () => Promise<number>;
+  now?: () => Date;
+  createId?: () => string;
+  intervalMs?: number;
+  onError?: (error: unknown) => void;
+};
+
+function isNotFound(error: unknown): boolean {
+  return (error as NodeJS.ErrnoException | null)?.code === "ENOENT";
+}
+
+async function pathExists(target: string): Promise<boolean> {
+  try {
+    await fs.lstat(target);
+    return true;
+  } catch (error) {
+    if (isNotFound(error)) {
+      return false;
+    }
+    throw error;
+  }
+}
+
+function parseManifest(value: unknown): TrashManifest {
+  const entries = (value as { entries?: unknown } | null)?.entries;
+  if (!Array.isArray(entries)) {
+    return { version: 1, entries: [] };
+  }
+  return {
+    version: 1,
+    entries: entries.filter(
+      (entry): entry is TrashManifestEntry =>
+        typeof entry?.id === "string" &&
+        typeof entry.deletedAt === "string" &&
+        Array.isArray(entry.files) &&
+        Array.isArray(entry.records?.workspaces) &&
+        Array.isArray(entry.records?.threads),
+    ),
+  };
+}
+
+/**
+ * Holds deleted workspace records, thread records, and transcript files in
+ * `trash/` under the app data directory so deletions can be undone. Entries
+ * are purged once they are older than the `trashRetentionDays` setting.
+ */
+export class TrashService {
+  private readonly now: () => Date;
+  private readonly createId: () => string;
+  private readonly intervalMs: number;
+  private readonly onError: (error: unknown) => void;
+  private timer: ReturnType<typeof setInterval> | null = null;
+  private pending: Promise<unknown> = Promise.resolve();
+
+  constructor(private readonly options: TrashServiceOptions) {
+    this.now = options.now ?? (() => new Date());
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+    this.intervalMs = Math.max(60_000, options.intervalMs ?? DEFAULT_PURGE_INTERVAL_MS);
+    this.onError = options.onError ?? (() => {});
+  }
+
+  private get rootDir(): string {
+    return path.join(this.options.dataDir, "trash");
+  }
+
+  private get manifestPath(): string {
+    return path.join(this.rootDir, MANIFEST_FILE_NAME);
+  }
+
+  start(): void {
+    if (this.timer) {
+      return;
+    }
+    void this.purgeExpired().catch(this.onError);
+    this.timer = setInterval(() => {
+      void this.purgeExpired().catch(this.onError);
+    }, this.intervalMs);
+    (this.timer as { unref?: () => void }).unref?.();
+  }
+
+  dispose(): void {
+    if (this.timer) {
+      clearInterval(this.timer);
+      this.timer = null;
+    }
+  }
+
+  /**
+   * Moves `files` into a new trash entry alongside the records they belonged
+   * to. Files that no longer exist are skipped; if a move fails, the ones
+   * already moved are put back.
+   */
+  async put(input: {
+    kind: TrashEntryKind;
+    label: string;
+    files: string[];
+    records: TrashedRecords;
+  }): Promise<TrashEntry> {
+    return await this.run(async () => {
+      const manifest = await this.readManifest();
+      const id = this.createId();
+      const entryDir = path.join(this.rootDir, id);
+      await fs.mkdir(entryDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+      const files: string[] = [];
+      let sizeBytes = 0;
+      try {
+        for (const filePath of input.files) {
+          const relative = this.relativeDataPath(filePath);
+          const stat = await fs.stat(filePath).catch((error: unknown) => {
+            if (isNotFound(error)) {
+              return null;
+            }
+            throw error;
+          });
+          if (!stat) {
+            continue;
+          }
+          await fs.rename(filePath, path.join(entryDir, String(files.length)));
+          files.push(relative);
+          sizeBytes += stat.size;
+        }
+      } catch (error) {
+        await this.moveBack(entryDir, files);
+        await fs.rm(entryDir, { recursive: true, force: true });
+        throw error;
+      }
+      const entry: TrashManifestEntry = {
+        id,
+        kind: input.kind,
+        label: input.label,
+        deletedAt: this.now().toISOString(),
+        sizeBytes,
+        files,
+        records: input.records,
+      };
+      await this.writeManifest({ ...manifest, entries: [...manifest.entries, entry] });
+      return this.toTrashEntry(entry, await this.options.getRetentionDays());
+    });
+  }
+
+  /** Entries newest first. */
+  async list(): Promise<TrashEntry[]> {
+    const [manifest, retentionDays] = await Promise.all([
+      this.run(() => this.readManifest()),
+      this.options.getRetentionDays(),
+    ]);
+    return manifest.entries
+      .map((entry) => this.toTrashEntry(entry, retentionDays))
+      .sort((left, right) => right.deletedAt.localeCompare(left.deletedAt));
+  }
+
+  /**
+   * Moves an entry's files back to where they came from, then hands its
+   * records to `apply`. Nothing is restored when a file's original location
+   * is taken, and the files go back into the trash if `apply` throws.
+   */
+  async restore<T>(entryId: string, apply: (records: TrashedRecords) => Promise<T>): Promise<T> {
+    return await this.run(async () => {
+      const manifest = await this.readManifest();
+      const entry = manifest.entries.find((candidate) => candidate.id === entryId);
+      if (!entry) {
+        throw new Error(`Unknown trash entry: ${entryId}`);
+      }
+      for (const relative of entry.files) {
+        if (await pathExists(this.absoluteDataPath(relative))) {
+          throw new Error(`Cannot restore over an existing file: ${relative}`);
+        }
+      }
+      const entryDir = path.join(this.rootDir, entry.id);
+      await this.moveBack(entryDir, entry.files);
+      let result: T;
+      try {
+        result = await apply(entry.records);
+      } catch (error) {
+        for (const [index, relative] of entry.files.entries()) {
+          await fs.rename(this.absoluteDataPath(relative), path.join(entryDir, String(index)));
+        }
+        throw error;
+      }
+      await this.writeManifest({
+        ...manifest,
+        entries: manifest.entries.filter((candidate) => candidate.id !== entryId),
+      });
+      await fs.rm(entryDir, { recursive: true, force: true });
+      return result;
+    });
+  }
+
+  /** Deletes entries older than the retention setting; returns how many were removed. */
+  async purgeExpired(): Promise<number> {
+    const retentionDays = await this.options.getRetentionDays();
+    if (retentionDays <= 0) {
+      return 0;
+    }
+    const cutoff = this.now().getTime() - retentionDays * DAY_MS;
+    return await this.run(async () => {
+      const manifest = await this.readManifest();
+      const expired = manifest.entries.filter((entry) => Date.parse(entry.deletedAt) <= cutoff);
+      if (expired.length === 0) {
+        return 0;
+      }
+      const expiredIds = new Set(expired.map((entry) => entry.id));
+      await this.writeManifest({
+        ...manifest,
+        entries: manifest.entries.filter((entry) => !expiredIds.has(entry.id)),
+      });
+      for (const entry of expired) {
+        await fs.rm(path.join(this.rootDir, entry.id), { recursive: true, force: true });
+      }
+      return expired.length;
+    });
+  }
+
+  private async run<T>(fn: () => Promise<T>): Promise<T> {
+    const next = this.pending.then(fn);
+    this.pending = next.catch(() => {});
+    return await next;
+  }
+
+  private toTrashEntry(entry: TrashManifestEntry, retentionDays: number): TrashEntry {
+    return {
+      id: entry.id,
+      kind: entry.kind,
+      label: entry.label,
+      deletedAt: entry.deletedAt,
+      expiresAt:
+        retentionDays > 0
+          ? new Date(Date.parse(entry.deletedAt) + retentionDays * DAY_MS).toISOString()
+          : null,
+      sizeBytes: entry.sizeBytes,
+      workspaceCount: entry.records.workspaces.length,
+      threadCount: entry.records.threads.length,
+    };
+  }
+
+  private async moveBack(entryDir: string, files: string[]): Promise<void> {
+    for (const [index, relative] of files.entries()) {
+      const target = this.absoluteDataPath(relative);
+      await fs.mkdir(path.dirname(target), { recursive: true, mode: PRIVATE_DIR_MODE });
+      await fs.rename(path.join(entryDir, String(index)), target);
+    }
+  }
+
+  private relativeDataPath(filePath: string): string {
+    const relative = path.relative(this.options.dataDir, filePath);
+    if (!isPathEqualOrInside(this.options.dataDir, filePath) || relative === "") {
+      throw new Error(`Only app data files can be moved to the trash: ${filePath}`);
+    }
+    if (isPathEqualOrInside(this.rootDir, filePath)) {
+      throw new Error(`File is already in the trash: ${filePath}`);
+    }
+    return relative;
+  }
+
+  private absoluteDataPath(relative: string): string {
+    const target = path.resolve(this.options.dataDir, relative);
+    if (
+      !isPathEqualOrInside(this.options.dataDir, target) ||
+      isPathEqualOrInside(this.rootDir, target)
+    ) {
+      throw new Error(`Trash entry points outside the app data directory: ${relative}`);
+    }
+    return target;
+  }
+
+  private async readManifest(): Promise<TrashManifest> {
+    try {
+      return parseManifest(JSON.parse(await fs.readFile(this.manifestPath, "utf8")));
+    } catch (error) {
+      if (isNotFound(error)) {
+        return { version: 1, entries: [] };
+      }
+      throw error;
+    }
+  }
+
+  private async writeManifest(manifest: TrashManifest): Promise<void> {
+    await fs.mkdir(this.rootDir, { recursive: true, mode: PRIVATE_DIR_MODE });
+    await writeFileAtomic(this.manifestPath, `${JSON.stringify(manifest, null, 2)}\n`, {
+      mode: PRIVATE_FILE_MODE,
+    });
+  }
+}
@@ -192,6 +192,7 @@ export class DesktopUpdaterService {
   private intervalHandle: ReturnType<typeof setInterval> | null = null;
   private startupHandle: ReturnType<typeof setTimeout> | null = null;
   private started = false;
+  private installRequested = false;
   private state: UpdaterState;
   private checkStartedAtMs: number | null = null;
 
@@ -338,9 +339,15 @@ export class DesktopUpdaterService {
       eventSource: "main",
       status: "started",
     });
+    this.installRequested = true;
     this.updater.quitAndInstall(false, true);
   }
 
+  /** True once the app is quitting so the downloaded update can be installed. */
+  isInstallingUpdate(): boolean {
+    return this.installRequested;
+  }
+
   private registerListeners(): void {
     this.updater.on("checking-for-update", () => {
       logUpdater("info", "checking for update");
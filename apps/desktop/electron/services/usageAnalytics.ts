@@ -0,0 +1,381 @@
+import { calculateTokenCost, resolveModelPricing } from "../../../../src/session/pricing";
+import { isProviderName } from "../../../../src/types";
+import type { PersistedState, ThreadRecord, TranscriptEvent } from "../../src/app/types";
+import type {
+  GetUsageSummaryInput,
+  UsageSummary,
+  UsageSummaryRange,
+  UsageTotals,
+} from "../../src/lib/desktopApi";
+
+const THREAD_SUMMARY_LIMIT = 50;
+const RANGE_DAYS: Record<Exclude<UsageSummaryRange, "all">, number> = {
+  today: 1,
+  "7d": 7,
+  "30d": 30,
+};
+
+type SqlValue = string | number | null;
+
+/** The subset of `node:sqlite` (and `bun:sqlite`) this service needs. */
+export type UsageDatabase = {
+  exec(sql: string): unknown;
+  prepare(sql: string): {
+    run(...params: SqlValue[]): unknown;
+    all(...params: SqlValue[]): unknown[];
+  };
+  close(): unknown;
+};
+
+type UsageAnalyticsServiceOptions = {
+  openDatabase: () => UsageDatabase;
+  loadState: () => Promise<PersistedState>;
+  onError?: (error: unknown) => void;
+};
+
+/** One `turn_usage` event, priced and attributed to its thread. */
+export type UsageTurnRecord = {
+  threadId: string;
+  turnId: string;
+  recordedAtMs: number;
+  provider: string | null;
+  model: string | null;
+  promptTokens: number;
+  completionTokens: number;
+  cachedPromptTokens: number;
+  cacheWritePromptTokens: number;
+  reasoningOutputTokens: number;
+  totalTokens: number;
+  estimatedCostUsd: number | null;
+};
+
+type TotalsRow = {
+  turns: number;
+  prompt_tokens: number | null;
+  completion_tokens: number | null;
+  cached_prompt_tokens: number | null;
+  reasoning_output_tokens: number | null;
+  total_tokens: number | null;
+  estimated_cost_usd: number | null;
+  unpriced_turns: number | null;
+};
+
+const SCHEMA_SQL = [
+  "CREATE TABLE IF NOT EXISTS usage_turns (",
+  "  thread_id TEXT NOT NULL,",
+  "  turn_id TEXT NOT NULL,",
+  "  workspace_id TEXT,",
+  "  day TEXT NOT NULL,",
+  "  recorded_at_ms INTEGER NOT NULL,",
+  "  provider TEXT,",
+  "  model TEXT,",
+  "  prompt_tokens INTEGER NOT NULL,",
+  "  completion_tokens INTEGER NOT NULL,",
+  "  cached_prompt_tokens INTEGER NOT NULL,",
+  "  cache_write_prompt_tokens INTEGER NOT NULL,",
+  "  reasoning_output_tokens INTEGER NOT NULL,",
+  "  total_tokens INTEGER NOT NULL,",
+  "  estimated_cost_usd REAL,",
+  "  PRIMARY KEY (thread_id, turn_id)",
+  ");",
+  "CREATE INDEX IF NOT EXISTS usage_turns_recorded_at ON usage_turns(recorded_at_ms);",
+  "CREATE INDEX IF NOT EXISTS usage_turns_workspace ON usage_turns(workspace_id, recorded_at_ms);",
+].join("\n");
+
+const TOTALS_COLUMNS = [
+  "COUNT(*) AS turns",
+  "SUM(prompt_tokens) AS prompt_tokens",
+  "SUM(completion_tokens) AS completion_tokens",
+  "SUM(cached_prompt_tokens) AS cached_prompt_tokens",
+  "SUM(reasoning_output_tokens) AS reasoning_output_tokens",
+  "SUM(total_tokens) AS total_tokens",
+  "SUM(estimated_cost_usd) AS estimated_cost_usd",
+  "SUM(CASE WHEN estimated_cost_usd IS NULL THEN 1 ELSE 0 END) AS unpriced_turns",
+].join(", ");
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+function tokenCount(value: unknown): number {
+  return typeof value === "number" && Number.isFinite(value) && value > 0 ? Math.round(value) : 0;
+}
+
+/** `YYYY-MM-DD` in the machine's local time zone, matching how users think about "today". */
+export function localDayKey(ms: number): string {
+  const date = new Date(ms);
+  const month = String(date.getMonth() + 1).padStart(2, "0");
+  const day = String(date.getDate()).padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+export function usageRangeStart(range: UsageSummaryRange, now: number): number | null {
+  if (range === "all") {
+    return null;
+  }
+  const start = new Date(now);
+  start.setHours(0, 0, 0, 0);
+  start.setDate(start.getDate() - (RANGE_DAYS[range] - 1));
+  return start.getTime();
+}
+
+/**
+ * Pulls per-turn usage out of transcript events. The model for a turn comes from the latest
+ * `server_hello`, `session_info`, or `config_updated` event seen for the same thread, which is
+ * only needed to price turns the server did not already price.
+ */
+export class UsageEventParser {
+  private readonly modelByThread = new Map<string, { provider: string; model: string }>();
+
+  parse(event: TranscriptEvent): UsageTurnRecord | null {
+    const payload = event.payload;
+    if (event.direction !== "server" || !isRecord(payload)) {
+      return null;
+    }
+    this.trackModel(event.threadId, payload);
+    if (payload.type !== "turn_usage" || typeof payload.turnId !== "string") {
+      return null;
+    }
+    const usage = payload.usage;
+    if (!isRecord(usage)) {
+      return null;
+    }
+
+    const recordedAtMs = Date.parse(event.ts);
+    const promptTokens = tokenCount(usage.promptTokens);
+    const completionTokens = tokenCount(usage.completionTokens);
+    const cachedPromptTokens = tokenCount(usage.cachedPromptTokens);
+    const cacheWritePromptTokens = tokenCount(usage.cacheWritePromptTokens);
+    const current = this.modelByThread.get(event.threadId) ?? null;
+    let estimatedCostUsd =
+      typeof usage.estimatedCostUsd === "number" && Number.isFinite(usage.estimatedCostUsd)
+        ? usage.estimatedCostUsd
+        : null;
+    if (estimatedCostUsd === null && current && isProviderName(current.provider)) {
+      const pricing = resolveModelPricing(current.provider, current.model);
+      estimatedCostUsd = pricing
+        ? calculateTokenCost(
+            promptTokens,
+            completionTokens,
+            pricing,
+            cachedPromptTokens,
+            cacheWritePromptTokens,
+          )
+        : null;
+    }
+
+    return {
+      threadId: event.threadId,
+      turnId: payload.turnId,
+      recordedAtMs: Number.isFinite(recordedAtMs) ? recordedAtMs : Date.now(),
+      provider: current?.provider ?? null,
+      model: current?.model ?? null,
+      promptTokens,
+      completionTokens,
+      cachedPromptTokens,
+      cacheWritePromptTokens,
+      reasoningOutputTokens: tokenCount(usage.reasoningOutputTokens),
+      totalTokens: tokenCount(usage.totalTokens) || promptTokens + completionTokens,
+      estimatedCostUsd,
+    };
+  }
+
+  private trackModel(threadId: string, payload: Record<string, unknown>): void {
+    const source =
+      payload.type === "session_info"
+        ? payload
+        : payload.type === "server_hello" || payload.type === "config_updated"
+          ? payload.config
+          : null;
+    if (!isRecord(source)) {
+      return;
+    }
+    const model =
+      payload.type === "server_hello" && typeof payload.effectiveModel === "string"
+        ? payload.effectiveModel
+        : source.model;
+    if (typeof source.provider === "string" && typeof model === "string" && model.trim()) {
+      this.modelByThread.set(threadId, { provider: source.provider, model });
+    }
+  }
+}
+
+function toTotals(row: TotalsRow | undefined): UsageTotals {
+  return {
+    turns: row?.turns ?? 0,
+    promptTokens: row?.prompt_tokens ?? 0,
+    completionTokens: row?.completion_tokens ?? 0,
+    cachedPromptTokens: row?.cached_prompt_tokens ?? 0,
+    reasoningOutputTokens: row?.reasoning_output_tokens ?? 0,
+    totalTokens: row?.total_tokens ?? 0,
+    estimatedCostUsd: row?.estimated_cost_usd ?? null,
+    unpricedTurns: row?.unpriced_turns ?? 0,
+  };
+}
+
+function threadMatches(thread: ThreadRecord, transcriptId: string): boolean {
+  return (
+    thread.id === transcriptId ||
+    thread.sessionId === transcriptId ||
+    thread.legacyTranscriptId === transcriptId
+  );
+}
+
+/**
+ * Keeps a per-turn token and cost ledger in SQLite as transcripts are appended, so usage outlives
+ * the sessions that produced it. Summaries are grouped per day, workspace, and thread on read.
+ */
+export class UsageAnalyticsService {
+  private database: UsageDatabase | null = null;
+  private disabled = false;
+  private readonly parser = new UsageEventParser();
+  private readonly workspaceByThread = new Map<string, string | null>();
+  private queue: Promise<void> = Promise.resolve();
+
+  constructor(private readonly options: UsageAnalyticsServiceOptions) {}
+
+  handleAppended(events: TranscriptEvent[]): void {
+    const turns = events.flatMap((event) => this.parser.parse(event) ?? []);
+    if (turns.length === 0) {
+      return;
+    }
+    this.queue = this.queue
+      .then(() => this.recordTurns(turns))
+      .catch((error: unknown) => {
+        this.options.onError?.(error);
+      });
+  }
+
+  /** Waits for appended events that are still being written. */
+  async flush(): Promise<void> {
+    await this.queue;
+  }
+
+  async recordTurns(turns: UsageTurnRecord[]): Promise<void> {
+    const database = this.open();
+    if (!database) {
+      return;
+    }
+    const insert = database.prepare(
+      [
+        "INSERT OR REPLACE INTO usage_turns (",
+        "  thread_id, turn_id, workspace_id, day, recorded_at_ms, provider, model,",
+        "  prompt_tokens, completion_tokens, cached_prompt_tokens, cache_write_prompt_tokens,",
+        "  reasoning_output_tokens, total_tokens, estimated_cost_usd",
+        ") VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+      ].join("\n"),
+    );
+    for (const turn of turns) {
+      insert.run(
+        turn.threadId,
+        turn.turnId,
+        await this.resolveWorkspaceId(turn.threadId),
+        localDayKey(turn.recordedAtMs),
+        turn.recordedAtMs,
+        turn.provider,
+        turn.model,
+        turn.promptTokens,
+        turn.completionTokens,
+        turn.cachedPromptTokens,
+        turn.cacheWritePromptTokens,
+        turn.reasoningOutputTokens,
+        turn.totalTokens,
+        turn.estimatedCostUsd,
+      );
+    }
+  }
+
+  async getSummary(input: GetUsageSummaryInput, now = Date.now()): Promise<UsageSummary> {
+    await this.flush();
+    const since = usageRangeStart(input.range, now);
+    const empty: UsageSummary = {
+      workspaceId: input.workspaceId ?? null,
+      range: input.range,
+      since: since === null ? null : new Date(since).toISOString(),
+      totals: toTotals(undefined),
+      byDay: [],
+      byWorkspace: [],
+      byThread: [],
+    };
+    const database = this.open();
+    if (!database) {
+      return empty;
+    }
+
+    const conditions: string[] = [];
+    const params: SqlValue[] = [];
+    if (since !== null) {
+      conditions.push("recorded_at_ms >= ?");
+      params.push(since);
+    }
+    if (input.workspaceId) {
+      conditions.push("workspace_id = ?");
+      params.push(input.workspaceId);
+    }
+    const where = conditions.length > 0 ? `WHERE ${conditions.join(" AND ")}` : "";
+    const select = (extraColumns: string, tail: string) =>
+      database
+        .prepare(`SELECT ${extraColumns}${TOTALS_COLUMNS} FROM usage_turns ${where} ${tail}`)
+        .all(...params) as Array<TotalsRow & Record<string, unknown>>;
+
+    const [totalsRow] = select("", "");
+    return {
+      ...empty,
+      totals: toTotals(totalsRow),
+      byDay: select("day, ", "GROUP BY day ORDER BY day DESC").map((row) => ({
+        day: String(row.day),
+        ...toTotals(row),
+      })),
+      byWorkspace: select(
+        "workspace_id, ",
+        "GROUP BY workspace_id ORDER BY estimated_cost_usd DESC, total_tokens DESC",
+      ).map((row) => ({
+        workspaceId: typeof row.workspace_id === "string" ? row.workspace_id : null,
+        ...toTotals(row),
+      })),
+      byThread: select(
+        "thread_id, MAX(workspace_id) AS workspace_id, ",
+        `GROUP BY thread_id ORDER BY MAX(recorded_at_ms) DESC LIMIT ${THREAD_SUMMARY_LIMIT}`,
+      ).map((row) => ({
+        threadId: String(row.thread_id),
+        workspaceId: typeof row.workspace_id === "string" ? row.workspace_id : null,
+        ...toTotals(row),
+      })),
+    };
+  }
+
+  dispose(): void {
+    this.database?.close();
+    this.database = null;
+  }
+
+  private open(): UsageDatabase | null {
+    if (this.database || this.disabled) {
+      return this.database;
+    }
+    try {
+      const database = this.options.openDatabase();
+      database.exec(SCHEMA_SQL);
+      this.database = database;
+    } catch (error) {
+      // Usage history is best-effort; a broken database must not break transcript writes.
+      this.disabled = true;
+      this.options.onError?.(error);
+    }
+    return this.database;
+  }
+
+  private async resolveWorkspaceId(threadId: string): Promise<string | null> {
+    const known = this.workspaceByThread.get(threadId);
+    if (known !== undefined) {
+      return known;
+    }
+    const state = await this.options.loadState();
+    const thread = state.threads.find((candidate) => threadMatches(candidate, threadId));
+    const workspaceId = thread?.workspaceId ?? null;
+    if (workspaceId) {
+      this.workspaceByThread.set(threadId, workspaceId);
+    }
+    return workspaceId;
+  }
+}
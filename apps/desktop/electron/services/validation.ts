@@ -2,19 +2,19 @@ import fs from "node:fs";
 import fsp from "node:fs/promises";
 import path from "node:path";
 
-import { isPathEqualOrInside } from "./pathBoundary";
+import { fromFileSystemPath, isPathEqualOrInside, toFileSystemPath } from "./pathBoundary";
 
 const SAFE_ID = /^[A-Za-z0-9_-]{1,256}$/;
 
 function normalizeBoundaryPath(targetPath: string): string {
-  const resolved = path.resolve(targetPath);
+  const resolved = path.resolve(fromFileSystemPath(targetPath));
   try {
-    return fs.realpathSync(resolved);
+    return fromFileSystemPath(fs.realpathSync(toFileSystemPath(resolved)));
   } catch {
     // If file doesn't exist, try to get realpath of the parent directory
     try {
       const parent = path.dirname(resolved);
-      const parentRealpath = fs.realpathSync(parent);
+      const parentRealpath = fromFileSystemPath(fs.realpathSync(toFileSystemPath(parent)));
       return path.join(parentRealpath, path.basename(resolved));
     } catch {
       return resolved;
@@ -48,7 +48,7 @@ export async function assertWorkspaceDirectory(workspacePath: string): Promise<v
 
   let stat: fs.Stats;
   try {
-    stat = await fsp.stat(workspacePath);
+    stat = await fsp.stat(toFileSystemPath(workspacePath));
   } catch {
     throw new Error(`Workspace path does not exist: ${workspacePath}`);
   }
@@ -0,0 +1,218 @@
+import crypto from "node:crypto";
+
+import {
+  type ConnectionStore,
+  getAiCoworkerPaths,
+  readConnectionStore,
+} from "../../../../src/store/connections";
+import type {
+  AppendVoiceAudioInput,
+  AppSettings,
+  StartVoiceCaptureInput,
+  StartVoiceCaptureResult,
+  StopVoiceCaptureInput,
+  VoiceTranscriptEvent,
+} from "../../src/lib/desktopApi";
+import { resolveProviderEnvApiKey } from "./providerCredentials";
+
+const OPENAI_BASE_URL = "https://api.openai.com/v1";
+const DEFAULT_TRANSCRIPTION_MODEL = "whisper-1";
+/** OpenAI's transcription endpoint rejects larger uploads. */
+export const MAX_VOICE_CAPTURE_BYTES = 25 * 1024 * 1024;
+const DEFAULT_INTERIM_INTERVAL_MS = 3_000;
+const DEFAULT_TIMEOUT_MS = 60_000;
+
+type FetchLike = (url: string, init: RequestInit) => Promise<Response>;
+
+export type VoiceAudio = { data: Buffer; mimeType: string };
+
+export type VoiceTranscriber = (audio: VoiceAudio) => Promise<string>;
+
+type VoiceTranscriberOptions = {
+  getSettings: () => Promise<
+    Pick<AppSettings, "voiceTranscriptionBaseUrl" | "voiceTranscriptionModel">
+  >;
+  fetch?: FetchLike;
+  readStore?: () => Promise<ConnectionStore>;
+  env?: NodeJS.ProcessEnv;
+  timeoutMs?: number;
+};
+
+type VoiceCaptureServiceOptions = {
+  transcribe: VoiceTranscriber;
+  emit: (event: VoiceTranscriptEvent) => void;
+  /** Interim transcriptions that fail are reported here and otherwise skipped. */
+  onInterimError?: (error: unknown) => void;
+  createId?: () => string;
+  interimIntervalMs?: number;
+};
+
+type ActiveCapture = {
+  id: string;
+  threadId: string;
+  mimeType: string;
+  chunks: Buffer[];
+  bytes: number;
+  /** Bytes covered by the last interim transcription. */
+  interimBytes: number;
+  interim: Promise<void> | null;
+  timer: ReturnType<typeof setInterval>;
+};
+
+function fileNameFor(mimeType: string): string {
+  const subtype = mimeType.split(";")[0]?.split("/")[1]?.trim().toLowerCase();
+  const extension = subtype === "mpeg" ? "mp3" : subtype === "x-wav" ? "wav" : subtype;
+  return `dictation.${extension || "webm"}`;
+}
+
+/**
+ * Transcribes audio with an OpenAI-compatible `/audio/transcriptions`
+ * endpoint. The OpenAI key comes from the saved connection or the
+ * environment, like the workspace servers'; a custom endpoint gets no key.
+ */
+export function createVoiceTranscriber(options: VoiceTranscriberOptions): VoiceTranscriber {
+  const fetchImpl = options.fetch ?? ((url, init) => fetch(url, init));
+  const readStore = options.readStore ?? (() => readConnectionStore(getAiCoworkerPaths()));
+  const env = options.env ?? process.env;
+  const timeoutMs = options.timeoutMs ?? DEFAULT_TIMEOUT_MS;
+
+  return async ({ data, mimeType }) => {
+    const settings = await options.getSettings();
+    const headers: Record<string, string> = { accept: "application/json" };
+    if (!settings.voiceTranscriptionBaseUrl) {
+      const saved = (await readStore()).services.openai;
+      const apiKey =
+        (saved?.mode === "api_key" ? saved.apiKey?.trim() : undefined) ||
+        resolveProviderEnvApiKey("openai", env);
+      if (!apiKey) {
+        throw new Error(
+          "Dictation needs an OpenAI API key or a transcription endpoint in settings.",
+        );
+      }
+      headers.authorization = `Bearer ${apiKey}`;
+    }
+    const baseUrl = (settings.voiceTranscriptionBaseUrl ?? OPENAI_BASE_URL).replace(/\/+$/, "");
+    const form = new FormData();
+    form.append("model", settings.voiceTranscriptionModel ?? DEFAULT_TRANSCRIPTION_MODEL);
+    const file = new Blob([new Uint8Array(data)], { type: mimeType });
+    form.append("file", file, fileNameFor(mimeType));
+
+    const response = await fetchImpl(`${baseUrl}/audio/transcriptions`, {
+      method: "POST",
+      headers,
+      body: form,
+      signal: AbortSignal.timeout(timeoutMs),
+    });
+    if (!response.ok) {
+      throw new Error(`Transcription failed with HTTP ${response.status}`);
+    }
+    const body = (await response.json()) as { text?: unknown } | null;
+    return typeof body?.text === "string" ? body.text.trim() : "";
+  };
+}
+
+/**
+ * Collects dictation audio the renderer records and turns it into text. The
+ * renderer owns the microphone, since only Chromium can open it without a
+ * native module, and streams `MediaRecorder` chunks here. While recording,
+ * the audio so far is transcribed every few seconds and sent as interim text;
+ * stopping transcribes the whole capture once more for the final text. One
+ * capture runs at a time.
+ */
+export class VoiceCaptureService {
+  private active: ActiveCapture | null = null;
+  private readonly createId: () => string;
+  private readonly interimIntervalMs: number;
+
+  constructor(private readonly options: VoiceCaptureServiceOptions) {
+    this.createId = options.createId ?? (() => crypto.randomUUID());
+    this.interimIntervalMs = options.interimIntervalMs ?? DEFAULT_INTERIM_INTERVAL_MS;
+  }
+
+  start(input: StartVoiceCaptureInput): StartVoiceCaptureResult {
+    this.discardActive();
+    const capture: ActiveCapture = {
+      id: this.createId(),
+      threadId: input.threadId,
+      mimeType: input.mimeType,
+      chunks: [],
+      bytes: 0,
+      interimBytes: 0,
+      interim: null,
+      timer: setInterval(() => this.transcribeInterim(capture), this.interimIntervalMs),
+    };
+    capture.timer.unref?.();
+    this.active = capture;
+    return { captureId: capture.id };
+  }
+
+  append(input: AppendVoiceAudioInput): void {
+    const capture = this.active;
+    if (capture?.id !== input.captureId) {
+      throw new Error("That voice capture is no longer running.");
+    }
+    if (capture.bytes + input.chunk.byteLength > MAX_VOICE_CAPTURE_BYTES) {
+      throw new Error("Dictation is limited to 25 MB of audio; stop and start a new one.");
+    }
+    capture.chunks.push(Buffer.from(input.chunk));
+    capture.bytes += input.chunk.byteLength;
+  }
+
+  /** Ends the capture and resolves its final transcript, which is also emitted. */
+  async stop(input: StopVoiceCaptureInput = {}): Promise<VoiceTranscriptEvent | null> {
+    const capture = this.active;
+    if (!capture) {
+      return null;
+    }
+    this.active = null;
+    clearInterval(capture.timer);
+    if (input.discard) {
+      return null;
+    }
+    // Let a running interim finish first so the final text is the last event.
+    await capture.interim;
+    const text = capture.bytes > 0 ? await this.options.transcribe(this.audioOf(capture)) : "";
+    const event: VoiceTranscriptEvent = {
+      captureId: capture.id,
+      threadId: capture.threadId,
+      text,
+      final: true,
+    };
+    this.options.emit(event);
+    return event;
+  }
+
+  dispose(): void {
+    this.discardActive();
+  }
+
+  private discardActive(): void {
+    if (this.active) {
+      clearInterval(this.active.timer);
+      this.active = null;
+    }
+  }
+
+  private audioOf(capture: ActiveCapture): VoiceAudio {
+    return { data: Buffer.concat(capture.chunks), mimeType: capture.mimeType };
+  }
+
+  private transcribeInterim(capture: ActiveCapture): void {
+    if (this.active !== capture || capture.interim || capture.bytes === capture.interimBytes) {
+      return;
+    }
+    capture.interimBytes = capture.bytes;
+    capture.interim = this.options
+      .transcribe(this.audioOf(capture))
+      .then((text) => {
+        if (this.active === capture) {
+          const { id: captureId, threadId } = capture;
+          this.options.emit({ captureId, threadId, text, final: false });
+        }
+      })
+      .catch((error: unknown) => this.options.onInterimError?.(error))
+      .finally(() => {
+        capture.interim = null;
+      });
+  }
+}
@@ -0,0 +1,339 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { promisify } from "node:util";
+
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import type {
+  RestoreWorkspaceCheckpointInput,
+  RestoreWorkspaceCheckpointResult,
+  WorkspaceCheckpoint,
+  WorkspaceCheckpointList,
+  WorkspaceCheckpointReason,
+} from "../../src/lib/desktopApi";
+
+const execFileAsync = promisify(execFile);
+
+export const WORKSPACE_CHECKPOINT_REF = "refs/cowork-checkpoints";
+
+// Staging a large working tree hashes every changed file, so allow far longer than status.
+const GIT_COMMAND_TIMEOUT_MS = 120_000;
+const DEFAULT_LIST_LIMIT = 50;
+const FIELD_SEPARATOR = "\x1f";
+const RECORD_SEPARATOR = "\x1e";
+const CHECKPOINT_LOG_FORMAT = `%H${FIELD_SEPARATOR}%cI${FIELD_SEPARATOR}%B${RECORD_SEPARATOR}`;
+
+const CHECKPOINT_IDENTITY = {
+  GIT_AUTHOR_NAME: "Cowork",
+  GIT_AUTHOR_EMAIL: "cowork@localhost",
+  GIT_COMMITTER_NAME: "Cowork",
+  GIT_COMMITTER_EMAIL: "cowork@localhost",
+};
+
+export type GitRunner = (
+  cwd: string,
+  args: string[],
+  env?: Record<string, string>,
+) => Promise<string>;
+
+export const runGit: GitRunner = async (cwd, args, env) => {
+  const { stdout } = await execFileAsync("git", ["-C", cwd, ...args], {
+    env: env ? { ...process.env, ...env } : process.env,
+    timeout: GIT_COMMAND_TIMEOUT_MS,
+    maxBuffer: 64 * 1024 * 1024,
+    windowsHide: true,
+  });
+  return stdout;
+};
+
+function parseTrailers(body: string): Map<string, string> {
+  const trailers = new Map<string, string>();
+  for (const line of body.split(/\r?\n/)) {
+    const match = /^(Cowork-[A-Za-z]+): (.*)$/.exec(line.trim());
+    if (match?.[1] && match[2] !== undefined) {
+      trailers.set(match[1], match[2].trim());
+    }
+  }
+  return trailers;
+}
+
+/** Parses `git log --format=%H%x1f%cI%x1f%B%x1e` over the checkpoint ref. */
+export function parseCheckpointLog(stdout: string): WorkspaceCheckpoint[] {
+  const checkpoints: WorkspaceCheckpoint[] = [];
+  for (const record of stdout.split(RECORD_SEPARATOR)) {
+    const [id, createdAt, body] = record.replace(/^\s+/, "").split(FIELD_SEPARATOR);
+    if (!id || !createdAt) {
+      continue;
+    }
+    const trailers = parseTrailers(body ?? "");
+    const reason = trailers.get("Cowork-Reason");
+    checkpoints.push({
+      id,
+      createdAt,
+      reason: reason === "before-restore" ? "before-restore" : "yolo-start",
+      head: trailers.get("Cowork-Head") || null,
+    });
+  }
+  return checkpoints;
+}
+
+export function formatCheckpointMessage(input: {
+  reason: WorkspaceCheckpointReason;
+  workspaceId: string;
+  head: string | null;
+}): string {
+  return [
+    `Cowork checkpoint (${input.reason})`,
+    "",
+    `Cowork-Reason: ${input.reason}`,
+    `Cowork-Workspace: ${input.workspaceId}`,
+    ...(input.head ? [`Cowork-Head: ${input.head}`] : []),
+    "",
+  ].join("\n");
+}
+
+async function revParse(git: GitRunner, repoRoot: string, rev: string): Promise<string | null> {
+  try {
+    return (await git(repoRoot, ["rev-parse", "--verify", "--quiet", rev])).trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+/**
+ * Writes the whole working tree, untracked files included and ignored files
+ * excluded, as a tree object. Staging happens in a throwaway copy of the
+ * index so the user's staged changes are left alone.
+ */
+async function writeWorkingTree(git: GitRunner, repoRoot: string): Promise<string> {
+  const indexDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-checkpoint-"));
+  try {
+    const env = { GIT_INDEX_FILE: path.join(indexDir, "index") };
+    const userIndex = path.resolve(
+      repoRoot,
+      (await git(repoRoot, ["rev-parse", "--git-path", "index"])).trim(),
+    );
+    // Starting from the real index reuses its stat cache, so unchanged files are not re-hashed.
+    await fs.copyFile(userIndex, env.GIT_INDEX_FILE).catch(() => {});
+    await git(repoRoot, ["add", "--all", "--", "."], env);
+    return (await git(repoRoot, ["write-tree"], env)).trim();
+  } finally {
+    await fs.rm(indexDir, { recursive: true, force: true });
+  }
+}
+
+async function commitCheckpoint(
+  git: GitRunner,
+  repoRoot: string,
+  tree: string,
+  input: { reason: WorkspaceCheckpointReason; workspaceId: string },
+): Promise<WorkspaceCheckpoint | null> {
+  const previous = await revParse(git, repoRoot, `${WORKSPACE_CHECKPOINT_REF}^{commit}`);
+  if (previous && (await revParse(git, repoRoot, `${previous}^{tree}`)) === tree) {
+    return null;
+  }
+  const head = await revParse(git, repoRoot, "HEAD^{commit}");
+  const message = formatCheckpointMessage({ ...input, head });
+  const commit = (
+    await git(
+      repoRoot,
+      ["commit-tree", tree, ...(previous ? ["-p", previous] : []), "-m", message],
+      CHECKPOINT_IDENTITY,
+    )
+  ).trim();
+  // Passing the old value makes a concurrent checkpoint in the same repository fail
+  // instead of silently dropping one of them from the chain.
+  await git(repoRoot, [
+    "update-ref",
+    "-m",
+    `cowork checkpoint: ${input.reason}`,
+    WORKSPACE_CHECKPOINT_REF,
+    commit,
+    ...(previous ? [previous] : []),
+  ]);
+  const [checkpoint] = parseCheckpointLog(
+    await git(repoRoot, ["log", "-1", `--format=${CHECKPOINT_LOG_FORMAT}`, commit]),
+  );
+  return checkpoint ?? null;
+}
+
+/** The top of the repository containing `workspacePath`, or null outside a repository. */
+export async function resolveRepositoryRoot(
+  git: GitRunner,
+  workspacePath: string,
+): Promise<string | null> {
+  try {
+    return (await git(workspacePath, ["rev-parse", "--show-toplevel"])).trim() || null;
+  } catch {
+    return null;
+  }
+}
+
+/** Returns null when nothing changed since the newest checkpoint. */
+export async function createWorkspaceCheckpoint(
+  git: GitRunner,
+  repoRoot: string,
+  input: { reason: WorkspaceCheckpointReason; workspaceId: string },
+): Promise<WorkspaceCheckpoint | null> {
+  const tree = await writeWorkingTree(git, repoRoot);
+  return await commitCheckpoint(git, repoRoot, tree, input);
+}
+
+export async function listWorkspaceCheckpoints(
+  git: GitRunner,
+  repoRoot: string,
+  limit = DEFAULT_LIST_LIMIT,
+): Promise<WorkspaceCheckpoint[]> {
+  if (!(await revParse(git, repoRoot, `${WORKSPACE_CHECKPOINT_REF}^{commit}`))) {
+    return [];
+  }
+  const stdout = await git(repoRoot, [
+    "log",
+    `--max-count=${limit}`,
+    `--format=${CHECKPOINT_LOG_FORMAT}`,
+    WORKSPACE_CHECKPOINT_REF,
+  ]);
+  return parseCheckpointLog(stdout);
+}
+
+/**
+ * Makes the working tree match a checkpoint: files it recorded are rewritten,
+ * and files created since are deleted. The current state is checkpointed
+ * first so the restore can itself be undone. The user's index and HEAD are
+ * not touched.
+ */
+export async function restoreWorkspaceCheckpoint(
+  git: GitRunner,
+  repoRoot: string,
+  input: { workspaceId: string; checkpointId: string },
+): Promise<Omit<RestoreWorkspaceCheckpointResult, "workspaceId" | "checkpointId">> {
+  const target = await revParse(git, repoRoot, `${input.checkpointId}^{commit}`);
+  const isCheckpoint =
+    target !== null &&
+    (await git(repoRoot, ["merge-base", "--is-ancestor", target, WORKSPACE_CHECKPOINT_REF]).then(
+      () => true,
+      () => false,
+    ));
+  if (!target || !isCheckpoint) {
+    throw new Error(`Unknown checkpoint: ${input.checkpointId}`);
+  }
+
+  const currentTree = await writeWorkingTree(git, repoRoot);
+  const safetyCheckpoint = await commitCheckpoint(git, repoRoot, currentTree, {
+    reason: "before-restore",
+    workspaceId: input.workspaceId,
+  });
+
+  const added = (
+    await git(repoRoot, [
+      "diff-tree",
+      "-r",
+      "-z",
+      "--name-only",
+      "--no-renames",
+      "--diff-filter=A",
+      target,
+      currentTree,
+    ])
+  )
+    .split("\0")
+    .filter(Boolean);
+  for (const relativePath of added) {
+    const absolutePath = path.resolve(repoRoot, relativePath);
+    if (!absolutePath.startsWith(`${path.resolve(repoRoot)}${path.sep}`)) {
+      continue;
+    }
+    await fs.rm(absolutePath, { force: true });
+  }
+
+  const indexDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-checkpoint-"));
+  try {
+    const env = { GIT_INDEX_FILE: path.join(indexDir, "index") };
+    await git(repoRoot, ["read-tree", target], env);
+    await git(repoRoot, ["checkout-index", "--all", "--force"], env);
+  } finally {
+    await fs.rm(indexDir, { recursive: true, force: true });
+  }
+
+  return { safetyCheckpoint, removedFileCount: added.length };
+}
+
+type WorkspaceCheckpointServiceOptions = {
+  loadState: () => Promise<PersistedState>;
+  git?: GitRunner;
+};
+
+/**
+ * Optional safety net for YOLO mode: before a workspace server that may run
+ * commands without approval starts, the repository's working tree is
+ * committed to `refs/cowork-checkpoints`, outside every branch. Checkpoints
+ * cover the whole repository containing the workspace.
+ */
+export class WorkspaceCheckpointService {
+  private readonly git: GitRunner;
+
+  constructor(private readonly options: WorkspaceCheckpointServiceOptions) {
+    this.git = options.git ?? runGit;
+  }
+
+  /**
+   * Called before a YOLO server spawns. Does nothing when the workspace has
+   * not opted in or is not inside a repository. Otherwise a failed checkpoint
+   * blocks the start, since running unguarded is what the setting prevents.
+   */
+  async checkpointBeforeYoloStart(workspaceId: string): Promise<WorkspaceCheckpoint | null> {
+    const workspace = await this.findWorkspace(workspaceId);
+    if (workspace.yoloCheckpoints !== true) {
+      return null;
+    }
+    const repoRoot = await resolveRepositoryRoot(this.git, workspace.path);
+    if (!repoRoot) {
+      return null;
+    }
+    try {
+      return await createWorkspaceCheckpoint(this.git, repoRoot, {
+        reason: "yolo-start",
+        workspaceId,
+      });
+    } catch (error) {
+      throw new Error(
+        `Could not create a checkpoint before starting in YOLO mode: ${
+          error instanceof Error ? error.message : String(error)
+        }`,
+      );
+    }
+  }
+
+  async list(workspaceId: string): Promise<WorkspaceCheckpointList> {
+    const workspace = await this.findWorkspace(workspaceId);
+    const repoRoot = await resolveRepositoryRoot(this.git, workspace.path);
+    if (!repoRoot) {
+      return { workspaceId, isRepository: false, checkpoints: [] };
+    }
+    return {
+      workspaceId,
+      isRepository: true,
+      checkpoints: await listWorkspaceCheckpoints(this.git, repoRoot),
+    };
+  }
+
+  async restore(input: RestoreWorkspaceCheckpointInput): Promise<RestoreWorkspaceCheckpointResult> {
+    const workspace = await this.findWorkspace(input.workspaceId);
+    const repoRoot = await resolveRepositoryRoot(this.git, workspace.path);
+    if (!repoRoot) {
+      throw new Error(`Workspace is not inside a git repository: ${input.workspaceId}`);
+    }
+    const result = await restoreWorkspaceCheckpoint(this.git, repoRoot, input);
+    return { workspaceId: input.workspaceId, checkpointId: input.checkpointId, ...result };
+  }
+
+  private async findWorkspace(workspaceId: string): Promise<WorkspaceRecord> {
+    const state = await this.options.loadState();
+    const workspace = state.workspaces.find((candidate) => candidate.id === workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    return workspace;
+  }
+}
@@ -0,0 +1,232 @@
+import { type Stats, unwatchFile, watchFile as watchFileSystem } from "node:fs";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { writeFileAtomic } from "../../../../src/platform/fs";
+import { isProviderName } from "../../../../src/types";
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import type {
+  WorkspaceConfig,
+  WorkspaceConfigSnapshot,
+  WriteWorkspaceConfigInput,
+} from "../../src/lib/desktopApi";
+
+export const WORKSPACE_CONFIG_DIR_NAME = ".cowork";
+export const WORKSPACE_CONFIG_FILE_NAME = "config.json";
+
+const DEFAULT_POLL_INTERVAL_MS = 1_000;
+
+type WatchFile = (filePath: string, onChange: () => void) => () => void;
+
+type WorkspaceConfigServiceOptions = {
+  loadState: () => Promise<PersistedState>;
+  emit: (snapshot: WorkspaceConfigSnapshot) => void;
+  watchFile?: WatchFile;
+  onError?: (workspaceId: string, error: unknown) => void;
+};
+
+type WatchedConfig = {
+  filePath: string;
+  /** Last contents seen or written by the app; `null` when the file did not exist. */
+  lastContents: string | null;
+  stop: () => void;
+};
+
+type RawConfigFile = {
+  exists: boolean;
+  contents: string | null;
+  values: Record<string, unknown>;
+  error: string | null;
+};
+
+/**
+ * Polls with `fs.watchFile` because `.cowork/` may not exist yet and editors
+ * often replace the file rather than writing in place, which drops `fs.watch`.
+ */
+function defaultWatchFile(filePath: string, onChange: () => void): () => void {
+  const listener = (current: Stats, previous: Stats) => {
+    if (current.mtimeMs !== previous.mtimeMs || current.size !== previous.size) {
+      onChange();
+    }
+  };
+  watchFileSystem(filePath, { interval: DEFAULT_POLL_INTERVAL_MS, persistent: false }, listener);
+  return () => unwatchFile(filePath, listener);
+}
+
+export function resolveWorkspaceConfigPath(workspacePath: string): string {
+  return path.join(workspacePath, WORKSPACE_CONFIG_DIR_NAME, WORKSPACE_CONFIG_FILE_NAME);
+}
+
+function isPlainObject(value: unknown): value is Record<string, unknown> {
+  return typeof value === "object" && value !== null && !Array.isArray(value);
+}
+
+async function readRawConfigFile(filePath: string): Promise<RawConfigFile> {
+  let contents: string;
+  try {
+    contents = await fs.readFile(filePath, "utf8");
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code === "ENOENT") {
+      return { exists: false, contents: null, values: {}, error: null };
+    }
+    throw error;
+  }
+  try {
+    const parsed: unknown = JSON.parse(contents);
+    if (!isPlainObject(parsed)) {
+      return { exists: true, contents, values: {}, error: "Expected a JSON object." };
+    }
+    return { exists: true, contents, values: parsed, error: null };
+  } catch (error) {
+    return {
+      exists: true,
+      contents,
+      values: {},
+      error: `Invalid JSON: ${error instanceof Error ? error.message : String(error)}`,
+    };
+  }
+}
+
+/** Picks the desktop-managed keys, dropping values of the wrong type. */
+export function pickWorkspaceConfig(values: Record<string, unknown>): WorkspaceConfig {
+  const config: WorkspaceConfig = {};
+  if (isProviderName(values.provider)) {
+    config.provider = values.provider;
+  }
+  if (typeof values.model === "string" && values.model.trim()) {
+    config.model = values.model.trim();
+  }
+  if (typeof values.enableMcp === "boolean") {
+    config.enableMcp = values.enableMcp;
+  }
+  if (typeof values.yolo === "boolean") {
+    config.yolo = values.yolo;
+  }
+  return config;
+}
+
+export function resolveEffectiveWorkspaceConfig(
+  config: WorkspaceConfig,
+  workspace: WorkspaceRecord,
+): WorkspaceConfigSnapshot["effective"] {
+  return {
+    provider: config.provider ?? workspace.defaultProvider ?? null,
+    model: config.model ?? workspace.defaultModel ?? null,
+    enableMcp: config.enableMcp ?? workspace.defaultEnableMcp,
+    yolo: config.yolo ?? workspace.yolo,
+  };
+}
+
+/**
+ * Reads and writes the desktop-managed keys of `<workspace>/.cowork/config.json`
+ * and reports edits made outside the app. Keys the desktop does not manage are
+ * preserved on write, since the same file configures the workspace server.
+ */
+export class WorkspaceConfigService {
+  private readonly watched = new Map<string, WatchedConfig>();
+  private readonly watchFile: WatchFile;
+
+  constructor(private readonly options: WorkspaceConfigServiceOptions) {
+    this.watchFile = options.watchFile ?? defaultWatchFile;
+  }
+
+  async read(workspaceId: string): Promise<WorkspaceConfigSnapshot> {
+    const workspace = await this.findWorkspace(workspaceId);
+    const filePath = resolveWorkspaceConfigPath(workspace.path);
+    const raw = await readRawConfigFile(filePath);
+    this.watch(workspaceId, filePath, raw.contents);
+    return buildSnapshot(workspace, filePath, raw);
+  }
+
+  async write(input: WriteWorkspaceConfigInput): Promise<WorkspaceConfigSnapshot> {
+    const workspace = await this.findWorkspace(input.workspaceId);
+    const filePath = resolveWorkspaceConfigPath(workspace.path);
+    const current = await readRawConfigFile(filePath);
+    if (current.error) {
+      throw new Error(`Cannot update ${filePath}: ${current.error}`);
+    }
+    const next: Record<string, unknown> = { ...current.values };
+    for (const [key, value] of Object.entries(input.config)) {
+      if (value === null) {
+        delete next[key];
+      } else if (value !== undefined) {
+        next[key] = value;
+      }
+    }
+    const contents = `${JSON.stringify(next, null, 2)}\n`;
+    await writeFileAtomic(filePath, contents);
+    // Record the write before watching so the poll that sees it is not reported as external.
+    this.watch(input.workspaceId, filePath, contents);
+    return buildSnapshot(workspace, filePath, {
+      exists: true,
+      contents,
+      values: next,
+      error: null,
+    });
+  }
+
+  unwatch(workspaceId: string): void {
+    this.watched.get(workspaceId)?.stop();
+    this.watched.delete(workspaceId);
+  }
+
+  dispose(): void {
+    for (const workspaceId of [...this.watched.keys()]) {
+      this.unwatch(workspaceId);
+    }
+  }
+
+  private watch(workspaceId: string, filePath: string, contents: string | null): void {
+    const existing = this.watched.get(workspaceId);
+    if (existing?.filePath === filePath) {
+      existing.lastContents = contents;
+      return;
+    }
+    existing?.stop();
+    const entry: WatchedConfig = {
+      filePath,
+      lastContents: contents,
+      stop: this.watchFile(filePath, () => {
+        void this.handleFileChanged(workspaceId, entry).catch((error) => {
+          this.options.onError?.(workspaceId, error);
+        });
+      }),
+    };
+    this.watched.set(workspaceId, entry);
+  }
+
+  private async handleFileChanged(workspaceId: string, entry: WatchedConfig): Promise<void> {
+    const raw = await readRawConfigFile(entry.filePath);
+    if (this.watched.get(workspaceId) !== entry || raw.contents === entry.lastContents) {
+      return;
+    }
+    entry.lastContents = raw.contents;
+    const workspace = await this.findWorkspace(workspaceId);
+    this.options.emit(buildSnapshot(workspace, entry.filePath, raw));
+  }
+
+  private async findWorkspace(workspaceId: string): Promise<WorkspaceRecord> {
+    const state = await this.options.loadState();
+    const workspace = state.workspaces.find((candidate) => candidate.id === workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    return workspace;
+  }
+}
+
+function buildSnapshot(
+  workspace: WorkspaceRecord,
+  filePath: string,
+  raw: RawConfigFile,
+): WorkspaceConfigSnapshot {
+  const config = pickWorkspaceConfig(raw.values);
+  return {
+    workspaceId: workspace.id,
+    path: filePath,
+    exists: raw.exists,
+    error: raw.error,
+    config,
+    effective: resolveEffectiveWorkspaceConfig(config, workspace),
+  };
+}
@@ -1,4 +1,4 @@
-import { type FSWatcher, watch as watchFileSystem } from "node:fs";
+import { type Dirent, type FSWatcher, statfsSync, watch as watchFileSystem } from "node:fs";
 import fs from "node:fs/promises";
 import path from "node:path";
 
@@ -8,6 +8,10 @@ import {
   type WorkspaceFileChangeKind,
 } from "../../../../src/filesystem/workspaceFileEvents";
 import { isPathInside } from "../../../../src/utils/paths";
+import type {
+  WorkspaceDirectoryWatchStatus,
+  WorkspaceWatcherStatus,
+} from "../../src/lib/desktopApi";
 
 export type WorkspaceDirectoryWatchScope = {
   workspaceId: string;
@@ -19,12 +23,22 @@ export type DirectoryWatchListener = (event: WorkspaceFileChangeEvent) => void;
 type WatchFactory = (
   rootPath: string,
   listener: (eventType: "rename" | "change", filename: string | Buffer | null) => void,
+  onError?: (error: Error) => void,
 ) => Pick<FSWatcher, "close">;
 
+/** Path to a signature that changes whenever the entry does. */
+export type DirectorySnapshot = Map<string, string>;
+
 export type WorkspaceDirectoryWatcherOptions = {
   debounceMs?: number;
   pathExists?: (candidatePath: string) => Promise<boolean>;
   watch?: WatchFactory;
+  /** Roots on these filesystems are polled from the start. */
+  isNetworkFilesystem?: (rootPath: string) => boolean;
+  scan?: (rootPath: string) => Promise<DirectorySnapshot>;
+  minPollIntervalMs?: number;
+  maxPollIntervalMs?: number;
+  now?: () => number;
 };
 
 type PendingWatchEvent = {
@@ -32,16 +46,39 @@ type PendingWatchEvent = {
   path: string;
 };
 
+type PollState = {
+  intervalMs: number;
+  snapshot: DirectorySnapshot | null;
+  timer: ReturnType<typeof setTimeout> | null;
+};
+
 type ActiveWatch = {
+  closed: boolean;
   debounceTimer: ReturnType<typeof setTimeout> | null;
+  downgradedAt: number | null;
+  lastError: string | null;
+  lastEventAt: number | null;
+  mode: WorkspaceDirectoryWatchStatus["mode"];
   pendingByPath: Map<string, PendingWatchEvent>;
+  poll: PollState | null;
+  reason: string | null;
   rootPath: string;
   subscribers: Map<string, DirectoryWatchListener>;
-  watcher: Pick<FSWatcher, "close">;
+  watcher: Pick<FSWatcher, "close"> | null;
   workspaceId: string;
 };
 
 const DEFAULT_WATCH_DEBOUNCE_MS = 40;
+const DEFAULT_MIN_POLL_INTERVAL_MS = 1_000;
+const DEFAULT_MAX_POLL_INTERVAL_MS = 10_000;
+const POLL_BACKOFF_FACTOR = 1.5;
+/** A scan may take at most this share of the time between scans. */
+const POLL_SCAN_BUDGET_RATIO = 4;
+const MAX_POLLED_ENTRIES = 50_000;
+const POLL_SKIPPED_DIR_NAMES = new Set(["node_modules"]);
+
+/** statfs magic numbers for NFS, SMB, CIFS, SMB2, and FUSE (sshfs and friends). */
+const NETWORK_FILESYSTEM_TYPES = new Set([0x6969, 0x517b, 0xff534d42, 0xfe534d42, 0x65735546]);
 
 async function defaultPathExists(candidatePath: string): Promise<boolean> {
   try {
@@ -55,24 +92,116 @@ async function defaultPathExists(candidatePath: string): Promise<boolean> {
 function defaultWatchFactory(
   rootPath: string,
   listener: Parameters<WatchFactory>[1],
+  onError?: (error: Error) => void,
 ): Pick<FSWatcher, "close"> {
-  return watchFileSystem(rootPath, { recursive: true }, listener);
+  const watcher = watchFileSystem(rootPath, { recursive: true }, listener);
+  if (onError) {
+    watcher.on("error", onError);
+  }
+  return watcher;
+}
+
+/**
+ * Native change events only cover writes made by this machine on network and
+ * FUSE mounts, so those roots are polled. Detection is Linux statfs plus UNC
+ * paths on Windows; other mounts fall back to polling only once watching fails.
+ */
+function defaultIsNetworkFilesystem(rootPath: string): boolean {
+  if (process.platform === "win32") {
+    return rootPath.startsWith("\\\\");
+  }
+  if (process.platform !== "linux") {
+    return false;
+  }
+  try {
+    return NETWORK_FILESYSTEM_TYPES.has(Number(statfsSync(rootPath).type));
+  } catch {
+    return false;
+  }
+}
+
+/** Walks the tree under `rootPath`, skipping dependency directories, up to a fixed entry cap. */
+async function scanDirectoryTree(rootPath: string): Promise<DirectorySnapshot> {
+  const snapshot: DirectorySnapshot = new Map();
+  const pending = [rootPath];
+  while (pending.length > 0 && snapshot.size < MAX_POLLED_ENTRIES) {
+    const directory = pending.pop() ?? rootPath;
+    let children: Dirent[];
+    try {
+      children = await fs.readdir(directory, { withFileTypes: true });
+    } catch (error) {
+      if (directory === rootPath) {
+        throw error;
+      }
+      continue;
+    }
+    const files: string[] = [];
+    for (const child of children.slice(0, MAX_POLLED_ENTRIES - snapshot.size)) {
+      const childPath = path.join(directory, child.name);
+      if (child.isDirectory()) {
+        snapshot.set(childPath, "dir");
+        if (!POLL_SKIPPED_DIR_NAMES.has(child.name)) {
+          pending.push(childPath);
+        }
+      } else {
+        files.push(childPath);
+      }
+    }
+    const stats = await Promise.all(files.map((file) => fs.lstat(file).catch(() => null)));
+    stats.forEach((stat, index) => {
+      if (stat) {
+        snapshot.set(files[index] ?? "", `${stat.mtimeMs}:${stat.size}`);
+      }
+    });
+  }
+  return snapshot;
+}
+
+function errorMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+function errorCode(error: unknown): string | undefined {
+  return (error as NodeJS.ErrnoException | null)?.code;
+}
+
+function toIsoOrNull(timestamp: number | null): string | null {
+  return timestamp === null ? null : new Date(timestamp).toISOString();
 }
 
 function watchScopeKey(scope: WorkspaceDirectoryWatchScope): string {
   return `${scope.workspaceId}\0${path.resolve(scope.rootPath)}`;
 }
 
+/**
+ * Shares one recursive watcher per workspace root between subscribers. Roots
+ * where native events are unreliable, or whose native watcher fails, are
+ * polled instead: a scan that finds changes brings the next one forward, and
+ * quiet scans back off toward the maximum interval.
+ */
 export class WorkspaceDirectoryWatcher {
   private readonly activeByScope = new Map<string, ActiveWatch>();
   private readonly debounceMs: number;
   private readonly pathExists: (candidatePath: string) => Promise<boolean>;
   private readonly watchFactory: WatchFactory;
+  private readonly isNetworkFilesystem: (rootPath: string) => boolean;
+  private readonly scan: (rootPath: string) => Promise<DirectorySnapshot>;
+  private readonly minPollIntervalMs: number;
+  private readonly maxPollIntervalMs: number;
+  private readonly now: () => number;
 
   constructor(options: WorkspaceDirectoryWatcherOptions = {}) {
     this.debounceMs = options.debounceMs ?? DEFAULT_WATCH_DEBOUNCE_MS;
     this.pathExists = options.pathExists ?? defaultPathExists;
     this.watchFactory = options.watch ?? defaultWatchFactory;
+    this.isNetworkFilesystem = options.isNetworkFilesystem ?? defaultIsNetworkFilesystem;
+    this.scan = options.scan ?? scanDirectoryTree;
+    this.minPollIntervalMs = options.minPollIntervalMs ?? DEFAULT_MIN_POLL_INTERVAL_MS;
+    this.maxPollIntervalMs = Math.max(
+      this.minPollIntervalMs,
+      options.maxPollIntervalMs ?? DEFAULT_MAX_POLL_INTERVAL_MS,
+    );
+    this.now = options.now ?? Date.now;
   }
 
   watch(
@@ -88,28 +217,63 @@ export class WorkspaceDirectoryWatcher {
     }
 
     const rootPath = path.resolve(scope.rootPath);
-    let active: ActiveWatch | null = null;
-    try {
-      const watcher = this.watchFactory(rootPath, (eventType, filename) => {
-        if (active) {
-          this.queueRawEvent(active, eventType, filename);
+    const active: ActiveWatch = {
+      closed: false,
+      debounceTimer: null,
+      downgradedAt: null,
+      lastError: null,
+      lastEventAt: null,
+      mode: "native",
+      pendingByPath: new Map(),
+      poll: null,
+      reason: null,
+      rootPath,
+      subscribers: new Map([[subscriberId, listener]]),
+      watcher: null,
+      workspaceId: scope.workspaceId,
+    };
+    if (this.isNetworkFilesystem(rootPath)) {
+      this.startPolling(active, "Network filesystem; native change events are unreliable here.");
+    } else {
+      try {
+        active.watcher = this.watchFactory(
+          rootPath,
+          (eventType, filename) => this.queueRawEvent(active, eventType, filename),
+          (error) => this.downgradeToPolling(active, error),
+        );
+      } catch (error) {
+        const code = errorCode(error);
+        if (code === "ENOENT" || code === "ENOTDIR") {
+          return false;
         }
-      });
-      active = {
-        debounceTimer: null,
-        pendingByPath: new Map(),
-        rootPath,
-        subscribers: new Map([[subscriberId, listener]]),
-        watcher,
-        workspaceId: scope.workspaceId,
-      };
-    } catch {
-      return false;
+        this.downgradeToPolling(active, error);
+      }
     }
     this.activeByScope.set(key, active);
     return true;
   }
 
+  getStatus(workspaceId: string): WorkspaceWatcherStatus {
+    const watches: WorkspaceDirectoryWatchStatus[] = [];
+    for (const active of this.activeByScope.values()) {
+      if (active.workspaceId !== workspaceId) {
+        continue;
+      }
+      watches.push({
+        rootPath: active.rootPath,
+        mode: active.mode,
+        healthy: active.lastError === null,
+        reason: active.reason,
+        pollIntervalMs: active.poll?.intervalMs ?? null,
+        subscriberCount: active.subscribers.size,
+        lastEventAt: toIsoOrNull(active.lastEventAt),
+        lastError: active.lastError,
+        downgradedAt: toIsoOrNull(active.downgradedAt),
+      });
+    }
+    return { workspaceId, watches };
+  }
+
   unwatch(scope: WorkspaceDirectoryWatchScope, subscriberId: string): void {
     const key = watchScopeKey(scope);
     const active = this.activeByScope.get(key);
@@ -202,7 +366,91 @@ export class WorkspaceDirectoryWatcher {
     }
   }
 
+  private downgradeToPolling(active: ActiveWatch, error: unknown): void {
+    if (active.closed || active.mode === "polling") {
+      return;
+    }
+    try {
+      active.watcher?.close();
+    } catch {
+      // The native watcher is already broken; polling replaces it either way.
+    }
+    active.watcher = null;
+    active.downgradedAt = this.now();
+    this.startPolling(active, `Native watching failed: ${errorMessage(error)}`);
+  }
+
+  private startPolling(active: ActiveWatch, reason: string): void {
+    active.mode = "polling";
+    active.reason = reason;
+    active.poll = { intervalMs: this.minPollIntervalMs, snapshot: null, timer: null };
+    void this.pollOnce(active);
+  }
+
+  private async pollOnce(active: ActiveWatch): Promise<void> {
+    const poll = active.poll;
+    if (!poll || active.closed) {
+      return;
+    }
+    const startedAt = this.now();
+    try {
+      const snapshot = await this.scan(active.rootPath);
+      if (active.closed) {
+        return;
+      }
+      const changed =
+        poll.snapshot !== null && this.emitSnapshotChanges(active, poll.snapshot, snapshot);
+      poll.snapshot = snapshot;
+      active.lastError = null;
+      const nextIntervalMs = changed
+        ? this.minPollIntervalMs
+        : Math.min(this.maxPollIntervalMs, Math.round(poll.intervalMs * POLL_BACKOFF_FACTOR));
+      poll.intervalMs = Math.max(nextIntervalMs, (this.now() - startedAt) * POLL_SCAN_BUDGET_RATIO);
+    } catch (error) {
+      if (active.closed) {
+        return;
+      }
+      active.lastError = errorMessage(error);
+      poll.intervalMs = this.maxPollIntervalMs;
+    }
+    poll.timer = setTimeout(() => {
+      poll.timer = null;
+      void this.pollOnce(active);
+    }, poll.intervalMs);
+  }
+
+  private emitSnapshotChanges(
+    active: ActiveWatch,
+    previous: DirectorySnapshot,
+    next: DirectorySnapshot,
+  ): boolean {
+    const added: string[] = [];
+    const modified: string[] = [];
+    for (const [entryPath, signature] of next) {
+      const before = previous.get(entryPath);
+      if (before === undefined) {
+        added.push(entryPath);
+      } else if (before !== signature) {
+        modified.push(entryPath);
+      }
+    }
+    const removed = [...previous.keys()].filter((entryPath) => !next.has(entryPath));
+    if (active.subscribers.size > 0) {
+      if (modified.length > 0) {
+        this.emit(active, "modify", modified);
+      }
+      if (added.length > 0) {
+        this.emit(active, "add", added);
+      }
+      if (removed.length > 0) {
+        this.emit(active, "remove", removed);
+      }
+    }
+    return added.length + modified.length + removed.length > 0;
+  }
+
   private emit(active: ActiveWatch, kind: WorkspaceFileChangeKind, changedPaths: string[]): void {
+    active.lastEventAt = this.now();
     const event = createWorkspaceFileChangeEvent({
       workspaceId: active.workspaceId,
       rootPath: active.rootPath,
@@ -215,10 +463,14 @@ export class WorkspaceDirectoryWatcher {
   }
 
   private closeWatch(key: string, active: ActiveWatch): void {
+    active.closed = true;
     if (active.debounceTimer) {
       clearTimeout(active.debounceTimer);
     }
-    active.watcher.close();
+    if (active.poll?.timer) {
+      clearTimeout(active.poll.timer);
+    }
+    active.watcher?.close();
     this.activeByScope.delete(key);
   }
 }
@@ -0,0 +1,224 @@
+import type { Dirent } from "node:fs";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import type { WorkspaceRecord } from "../../src/app/types";
+import type {
+  ScanForWorkspacesResult,
+  WorkspaceCandidate,
+  WorkspaceCandidateMarker,
+  WorkspaceGitCommit,
+} from "../../src/lib/desktopApi";
+import {
+  canonicalLocalPath,
+  isPathEqualOrInside,
+  pathIdentityKey,
+  toFileSystemPath,
+} from "./pathBoundary";
+import { readGitLastCommit } from "./workspaceGit";
+
+const DEFAULT_SCAN_DEPTH = 3;
+const MAX_SCANNED_DIRECTORIES = 5_000;
+const MAX_CANDIDATES = 500;
+const MAX_SIZED_ENTRIES = 20_000;
+const DESCRIBE_CONCURRENCY = 8;
+
+/** Dependency and build output folders: never projects themselves, and often huge. */
+const SKIPPED_DIRECTORIES = new Set([
+  "node_modules",
+  "bower_components",
+  "vendor",
+  "target",
+  "dist",
+  "build",
+  "out",
+  "__pycache__",
+  "venv",
+]);
+
+/** The first manifest found decides the language, so more specific ones come first. */
+const LANGUAGE_MANIFESTS: Array<[string, string]> = [
+  ["tsconfig.json", "TypeScript"],
+  ["package.json", "JavaScript"],
+  ["Cargo.toml", "Rust"],
+  ["go.mod", "Go"],
+  ["pyproject.toml", "Python"],
+  ["setup.py", "Python"],
+  ["requirements.txt", "Python"],
+  ["Gemfile", "Ruby"],
+  ["build.gradle.kts", "Kotlin"],
+  ["pom.xml", "Java"],
+  ["build.gradle", "Java"],
+  ["Package.swift", "Swift"],
+  ["composer.json", "PHP"],
+  ["mix.exs", "Elixir"],
+  ["pubspec.yaml", "Dart"],
+  ["CMakeLists.txt", "C++"],
+];
+
+type WorkspaceDiscoveryOptions = {
+  /** The app's own data directories; nothing inside them is offered. */
+  appDataPaths: string[];
+  /** Registered workspaces, which are left out of the results. */
+  workspaces: Array<Pick<WorkspaceRecord, "path">>;
+  readLastCommit?: (folderPath: string) => Promise<WorkspaceGitCommit | null>;
+};
+
+type FoundFolder = {
+  path: string;
+  entries: Dirent[];
+  markers: WorkspaceCandidateMarker[];
+};
+
+async function readEntries(folderPath: string): Promise<Dirent[] | null> {
+  try {
+    return await fs.readdir(toFileSystemPath(folderPath), { withFileTypes: true });
+  } catch {
+    return null;
+  }
+}
+
+function markersOf(entries: Dirent[]): WorkspaceCandidateMarker[] {
+  const markers: WorkspaceCandidateMarker[] = [];
+  // Worktrees and submodules have a `.git` file rather than a folder.
+  if (entries.some((entry) => entry.name === ".git")) {
+    markers.push("git");
+  }
+  if (entries.some((entry) => entry.name === ".cowork" && entry.isDirectory())) {
+    markers.push("cowork");
+  }
+  return markers;
+}
+
+export function detectWorkspaceLanguage(fileNames: Iterable<string>): string | null {
+  const names = new Set(fileNames);
+  if ([...names].some((name) => name.endsWith(".csproj") || name.endsWith(".sln"))) {
+    return "C#";
+  }
+  return LANGUAGE_MANIFESTS.find(([manifest]) => names.has(manifest))?.[1] ?? null;
+}
+
+function isDescendable(entry: Dirent): boolean {
+  // Dirent.isDirectory() is false for symlinks, so links out of the root are not followed.
+  return entry.isDirectory() && !entry.name.startsWith(".") && !SKIPPED_DIRECTORIES.has(entry.name);
+}
+
+/** Bytes under `folderPath` outside `.git` and dependency folders, stopping at a cap. */
+async function measureFolder(folderPath: string): Promise<{ bytes: number; complete: boolean }> {
+  let bytes = 0;
+  let visited = 0;
+  const pending = [folderPath];
+  while (pending.length > 0) {
+    const current = pending.pop() as string;
+    for (const entry of (await readEntries(current)) ?? []) {
+      if (++visited > MAX_SIZED_ENTRIES) {
+        return { bytes, complete: false };
+      }
+      const entryPath = path.join(current, entry.name);
+      if (entry.isDirectory()) {
+        if (entry.name !== ".git" && !SKIPPED_DIRECTORIES.has(entry.name)) {
+          pending.push(entryPath);
+        }
+      } else if (entry.isFile()) {
+        bytes += await fs
+          .lstat(toFileSystemPath(entryPath))
+          .then((stats) => stats.size)
+          .catch(() => 0);
+      }
+    }
+  }
+  return { bytes, complete: true };
+}
+
+/**
+ * Looks under `rootPaths`, up to `maxDepth` folder levels down, for folders
+ * that are git repositories or carry a `.cowork` folder. The search does not
+ * descend into a match, hidden folders, or dependency folders, and skips
+ * folders that are already workspaces or hold the app's data. Results are
+ * sorted by path and capped; `truncated` says whether a cap was hit.
+ */
+export async function scanForWorkspaces(
+  rootPaths: string[],
+  maxDepth: number = DEFAULT_SCAN_DEPTH,
+  options: WorkspaceDiscoveryOptions,
+): Promise<ScanForWorkspacesResult> {
+  const registered = new Set(
+    await Promise.all(
+      options.workspaces.map(async (workspace) =>
+        pathIdentityKey(await canonicalLocalPath(workspace.path)),
+      ),
+    ),
+  );
+  const appDataPaths = await Promise.all(options.appDataPaths.map(canonicalLocalPath));
+  const visited = new Set<string>();
+  const found: FoundFolder[] = [];
+  const queue = (await Promise.all(rootPaths.map(canonicalLocalPath))).map((folderPath) => ({
+    folderPath,
+    depth: 0,
+  }));
+  let truncated = false;
+
+  while (queue.length > 0) {
+    const { folderPath, depth } = queue.shift() as (typeof queue)[number];
+    const key = pathIdentityKey(folderPath);
+    if (visited.has(key)) {
+      continue;
+    }
+    if (visited.size >= MAX_SCANNED_DIRECTORIES) {
+      truncated = true;
+      break;
+    }
+    visited.add(key);
+    if (appDataPaths.some((appDataPath) => isPathEqualOrInside(appDataPath, folderPath))) {
+      continue;
+    }
+    const entries = await readEntries(folderPath);
+    if (!entries) {
+      continue;
+    }
+    const markers = markersOf(entries);
+    if (markers.length > 0) {
+      if (!registered.has(key)) {
+        found.push({ path: folderPath, entries, markers });
+      }
+      if (found.length >= MAX_CANDIDATES) {
+        truncated = true;
+        break;
+      }
+      continue;
+    }
+    if (depth >= maxDepth) {
+      continue;
+    }
+    for (const entry of entries) {
+      if (isDescendable(entry)) {
+        queue.push({ folderPath: path.join(folderPath, entry.name), depth: depth + 1 });
+      }
+    }
+  }
+
+  const readLastCommit = options.readLastCommit ?? readGitLastCommit;
+  const describe = async (folder: FoundFolder): Promise<WorkspaceCandidate> => {
+    const [size, lastCommit] = await Promise.all([
+      measureFolder(folder.path),
+      folder.markers.includes("git") ? readLastCommit(folder.path) : Promise.resolve(null),
+    ]);
+    return {
+      path: folder.path,
+      name: path.basename(folder.path),
+      markers: folder.markers,
+      language: detectWorkspaceLanguage(folder.entries.map((entry) => entry.name)),
+      sizeBytes: size.bytes,
+      sizeComplete: size.complete,
+      lastCommit,
+    };
+  };
+  const candidates: WorkspaceCandidate[] = [];
+  for (let index = 0; index < found.length; index += DESCRIBE_CONCURRENCY) {
+    candidates.push(
+      ...(await Promise.all(found.slice(index, index + DESCRIBE_CONCURRENCY).map(describe))),
+    );
+  }
+  candidates.sort((left, right) => left.path.localeCompare(right.path));
+  return { candidates, truncated };
+}
@@ -0,0 +1,52 @@
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { isPathEqualOrInside } from "./pathBoundary";
+
+async function pathExists(target: string): Promise<boolean> {
+  try {
+    await fs.lstat(target);
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * Copies a workspace folder to `target` for a duplicate workspace. Files are
+ * cloned copy-on-write where the filesystem supports it instead of being
+ * hard-linked, so edits made in the copy never reach the original. The copy
+ * is staged next to `target` and renamed into place once complete.
+ */
+export async function copyWorkspaceDirectory(source: string, target: string): Promise<void> {
+  if (!path.isAbsolute(target)) {
+    throw new Error(`Workspace copy path must be absolute: ${target}`);
+  }
+  if (isPathEqualOrInside(source, target) || isPathEqualOrInside(target, source)) {
+    throw new Error("Workspace copy path cannot overlap the original workspace");
+  }
+  if (await pathExists(target)) {
+    throw new Error(`Workspace copy path already exists: ${target}`);
+  }
+  const parent = path.dirname(target);
+  const parentStat = await fs.stat(parent).catch(() => null);
+  if (!parentStat?.isDirectory()) {
+    throw new Error(`Parent folder does not exist: ${parent}`);
+  }
+
+  const staging = path.join(parent, `.${path.basename(target)}.copying-${crypto.randomUUID()}`);
+  try {
+    await fs.cp(source, staging, {
+      recursive: true,
+      errorOnExist: true,
+      force: false,
+      mode: fs.constants.COPYFILE_FICLONE,
+      verbatimSymlinks: true,
+    });
+    await fs.rename(staging, target);
+  } catch (error) {
+    await fs.rm(staging, { recursive: true, force: true });
+    throw error;
+  }
+}
@@ -0,0 +1,224 @@
+import { execFile } from "node:child_process";
+import { promisify } from "node:util";
+
+import type { WorkspaceFileChangeEvent } from "../../../../src/filesystem/workspaceFileEvents";
+import type { PersistedState } from "../../src/app/types";
+import type { WorkspaceGitCommit, WorkspaceGitStatus } from "../../src/lib/desktopApi";
+import type { WorkspaceDirectoryWatcher } from "./workspaceDirectoryWatcher";
+
+const execFileAsync = promisify(execFile);
+
+const GIT_COMMAND_TIMEOUT_MS = 5_000;
+const DEFAULT_REFRESH_DEBOUNCE_MS = 500;
+const WATCH_SUBSCRIBER_ID = "workspace-git";
+const FIELD_SEPARATOR = "\x1f";
+
+type GitStatusSummary = Omit<WorkspaceGitStatus, "workspaceId" | "lastCommit"> & {
+  headOid: string | null;
+};
+
+/** Parses `git status --porcelain=v2 --branch`. */
+export function parseGitStatusPorcelainV2(stdout: string): GitStatusSummary {
+  const summary: GitStatusSummary = {
+    isRepository: true,
+    branch: null,
+    upstream: null,
+    ahead: null,
+    behind: null,
+    dirtyFileCount: 0,
+    headOid: null,
+  };
+  for (const line of stdout.split(/\r?\n/)) {
+    if (line.startsWith("# branch.oid ")) {
+      const oid = line.slice("# branch.oid ".length).trim();
+      summary.headOid = oid === "(initial)" ? null : oid;
+    } else if (line.startsWith("# branch.head ")) {
+      const head = line.slice("# branch.head ".length).trim();
+      summary.branch = head === "(detached)" ? null : head;
+    } else if (line.startsWith("# branch.upstream ")) {
+      summary.upstream = line.slice("# branch.upstream ".length).trim();
+    } else if (line.startsWith("# branch.ab ")) {
+      const match = /^# branch\.ab \+(\d+) -(\d+)/.exec(line);
+      if (match) {
+        summary.ahead = Number(match[1]);
+        summary.behind = Number(match[2]);
+      }
+    } else if (/^[12u?] /.test(line)) {
+      summary.dirtyFileCount += 1;
+    }
+  }
+  return summary;
+}
+
+export function parseGitLastCommit(stdout: string): WorkspaceGitCommit | null {
+  const [sha, summary, author, committedAt] = stdout.trim().split(FIELD_SEPARATOR);
+  if (!sha || committedAt === undefined) {
+    return null;
+  }
+  return { sha, summary: summary ?? "", author: author ?? "", committedAt };
+}
+
+async function runGit(workspacePath: string, args: string[]): Promise<string> {
+  const { stdout } = await execFileAsync(
+    "git",
+    // Keep status from rewriting the index, which the watcher would report as another change.
+    ["--no-optional-locks", "-C", workspacePath, ...args],
+    { timeout: GIT_COMMAND_TIMEOUT_MS, maxBuffer: 16 * 1024 * 1024, windowsHide: true },
+  );
+  return stdout;
+}
+
+export async function readWorkspaceGitStatus(
+  workspaceId: string,
+  workspacePath: string,
+): Promise<WorkspaceGitStatus> {
+  let summary: GitStatusSummary;
+  try {
+    summary = parseGitStatusPorcelainV2(
+      await runGit(workspacePath, ["status", "--porcelain=v2", "--branch"]),
+    );
+  } catch {
+    // Not a repository (exit 128) or git is missing; both mean there is nothing to show.
+    return {
+      workspaceId,
+      isRepository: false,
+      branch: null,
+      upstream: null,
+      ahead: null,
+      behind: null,
+      dirtyFileCount: 0,
+      lastCommit: null,
+    };
+  }
+  const { headOid, ...status } = summary;
+  const lastCommit = headOid ? await readGitLastCommit(workspacePath) : null;
+  return { workspaceId, ...status, lastCommit };
+}
+
+/** Null when the folder is not a repository, has no commits, or git is missing. */
+export async function readGitLastCommit(workspacePath: string): Promise<WorkspaceGitCommit | null> {
+  return await runGit(workspacePath, [
+    "log",
+    "-1",
+    `--format=%H${FIELD_SEPARATOR}%s${FIELD_SEPARATOR}%an${FIELD_SEPARATOR}%cI`,
+  ])
+    .then(parseGitLastCommit)
+    .catch(() => null);
+}
+
+function sameStatus(left: WorkspaceGitStatus, right: WorkspaceGitStatus): boolean {
+  return JSON.stringify(left) === JSON.stringify(right);
+}
+
+/** Object files churn on every fetch and gc without changing anything the status reports. */
+function isRelevantChange(event: WorkspaceFileChangeEvent): boolean {
+  // Event paths are normalized to forward slashes on every platform.
+  const objectsDir = `${event.rootPath}/.git/objects`;
+  return event.changedPaths.some(
+    (changedPath) => changedPath !== objectsDir && !changedPath.startsWith(`${objectsDir}/`),
+  );
+}
+
+type WorkspaceGitServiceOptions = {
+  loadState: () => Promise<PersistedState>;
+  emit: (status: WorkspaceGitStatus) => void;
+  directoryWatcher: Pick<WorkspaceDirectoryWatcher, "watch" | "unwatch">;
+  readStatus?: (workspaceId: string, workspacePath: string) => Promise<WorkspaceGitStatus>;
+  debounceMs?: number;
+  onError?: (workspaceId: string, error: unknown) => void;
+};
+
+type TrackedWorkspace = {
+  rootPath: string;
+  last: WorkspaceGitStatus;
+  timer: ReturnType<typeof setTimeout> | null;
+};
+
+/**
+ * Reports branch, dirty state, and last commit for workspace folders. Once a
+ * workspace has been queried, file changes under it (including `.git/`)
+ * trigger a debounced re-read, and a change event is emitted only when the
+ * status actually differs.
+ */
+export class WorkspaceGitService {
+  private readonly tracked = new Map<string, TrackedWorkspace>();
+  private readonly readStatus: NonNullable<WorkspaceGitServiceOptions["readStatus"]>;
+  private readonly debounceMs: number;
+
+  constructor(private readonly options: WorkspaceGitServiceOptions) {
+    this.readStatus = options.readStatus ?? readWorkspaceGitStatus;
+    this.debounceMs = options.debounceMs ?? DEFAULT_REFRESH_DEBOUNCE_MS;
+  }
+
+  async getStatus(workspaceId: string): Promise<WorkspaceGitStatus> {
+    const state = await this.options.loadState();
+    const workspace = state.workspaces.find((candidate) => candidate.id === workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    const status = await this.readStatus(workspaceId, workspace.path);
+    this.track(workspaceId, workspace.path, status);
+    return status;
+  }
+
+  unwatch(workspaceId: string): void {
+    const entry = this.tracked.get(workspaceId);
+    if (!entry) {
+      return;
+    }
+    if (entry.timer) {
+      clearTimeout(entry.timer);
+    }
+    this.options.directoryWatcher.unwatch(
+      { workspaceId, rootPath: entry.rootPath },
+      WATCH_SUBSCRIBER_ID,
+    );
+    this.tracked.delete(workspaceId);
+  }
+
+  dispose(): void {
+    for (const workspaceId of [...this.tracked.keys()]) {
+      this.unwatch(workspaceId);
+    }
+  }
+
+  private track(workspaceId: string, rootPath: string, status: WorkspaceGitStatus): void {
+    const existing = this.tracked.get(workspaceId);
+    if (existing?.rootPath === rootPath) {
+      existing.last = status;
+      return;
+    }
+    if (existing) {
+      this.unwatch(workspaceId);
+    }
+    const entry: TrackedWorkspace = { rootPath, last: status, timer: null };
+    this.tracked.set(workspaceId, entry);
+    const onChange = (event: WorkspaceFileChangeEvent) => {
+      if (isRelevantChange(event)) {
+        this.scheduleRefresh(workspaceId, entry);
+      }
+    };
+    this.options.directoryWatcher.watch({ workspaceId, rootPath }, WATCH_SUBSCRIBER_ID, onChange);
+  }
+
+  private scheduleRefresh(workspaceId: string, entry: TrackedWorkspace): void {
+    if (entry.timer) {
+      clearTimeout(entry.timer);
+    }
+    entry.timer = setTimeout(() => {
+      entry.timer = null;
+      void this.refresh(workspaceId, entry).catch((error) => {
+        this.options.onError?.(workspaceId, error);
+      });
+    }, this.debounceMs);
+  }
+
+  private async refresh(workspaceId: string, entry: TrackedWorkspace): Promise<void> {
+    const status = await this.readStatus(workspaceId, entry.rootPath);
+    if (this.tracked.get(workspaceId) !== entry || sameStatus(entry.last, status)) {
+      return;
+    }
+    entry.last = status;
+    this.options.emit(status);
+  }
+}
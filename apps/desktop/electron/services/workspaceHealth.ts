@@ -0,0 +1,256 @@
+import { execFile } from "node:child_process";
+import fs from "node:fs/promises";
+import { promisify } from "node:util";
+
+import type { PersistedState, WorkspaceRecord } from "../../src/app/types";
+import type {
+  WorkspaceHealthCheck,
+  WorkspaceHealthReport,
+  WorkspaceHealthStatus,
+} from "../../src/lib/desktopApi";
+import { assertSafeId } from "./validation";
+
+const execFileAsync = promisify(execFile);
+
+const GIT_CHECK_IGNORE_TIMEOUT_MS = 5_000;
+const MB = 1024 * 1024;
+const TRANSCRIPT_TOTAL_WARNING_BYTES = 100 * MB;
+const TRANSCRIPT_TOTAL_CRITICAL_BYTES = 500 * MB;
+const TRANSCRIPT_SINGLE_WARNING_BYTES = 25 * MB;
+const SERVER_CRASH_WARNING_COUNT = 1;
+const SERVER_CRASH_CRITICAL_COUNT = 3;
+const STATUS_PENALTIES: Record<WorkspaceHealthStatus, number> = {
+  ok: 0,
+  unknown: 0,
+  warning: 15,
+  critical: 35,
+};
+
+const SECRET_FILE_PATTERNS = [
+  /^\.env(?:\..+)?$/,
+  /\.(?:pem|key|p12|pfx)$/,
+  /^id_(?:rsa|dsa|ecdsa|ed25519)$/,
+  /^(?:credentials|service-account)[\w.-]*\.json$/,
+];
+const SECRET_TEMPLATE_SUFFIX = /\.(?:example|sample|template|dist)$/;
+
+type TranscriptStats = { fileCount: number; totalBytes: number; largestBytes: number };
+
+type WorkspaceHealthServiceOptions = {
+  persistence: {
+    loadState(): Promise<PersistedState>;
+    getTranscriptStats(threadIds: string[]): Promise<TranscriptStats>;
+  };
+  serverManager: {
+    getStabilityStats(workspaceId: string): { starts: number; crashes: number };
+  };
+  /** Returns null when the workspace cannot be scanned. */
+  findExposedSecretFiles?: (workspacePath: string) => Promise<string[] | null>;
+  now?: () => Date;
+};
+
+function formatMegabytes(bytes: number): string {
+  return `${(bytes / MB).toFixed(1)} MB`;
+}
+
+/** Lists secret-looking files in the workspace root that git does not ignore. */
+export async function findExposedSecretFiles(workspacePath: string): Promise<string[] | null> {
+  let names: string[];
+  try {
+    const entries = await fs.readdir(workspacePath, { withFileTypes: true });
+    names = entries
+      .filter((entry) => entry.isFile())
+      .map((entry) => entry.name)
+      .filter(
+        (name) =>
+          !SECRET_TEMPLATE_SUFFIX.test(name) &&
+          SECRET_FILE_PATTERNS.some((pattern) => pattern.test(name)),
+      )
+      .sort();
+  } catch {
+    return null;
+  }
+  if (names.length === 0) {
+    return [];
+  }
+
+  let ignored: Set<string>;
+  try {
+    const { stdout } = await execFileAsync(
+      "git",
+      ["-C", workspacePath, "check-ignore", "--", ...names],
+      { timeout: GIT_CHECK_IGNORE_TIMEOUT_MS },
+    );
+    ignored = new Set(stdout.split(/\r?\n/).filter(Boolean));
+  } catch (error) {
+    // Exit code 1 means none of the paths are ignored. Anything else (not a
+    // repository, git missing) leaves nothing to protect the files either.
+    const stdout = (error as { stdout?: string }).stdout ?? "";
+    ignored = new Set(stdout.split(/\r?\n/).filter(Boolean));
+  }
+  return names.filter((name) => !ignored.has(name));
+}
+
+function checkServerStability(stats: { starts: number; crashes: number }): WorkspaceHealthCheck {
+  if (stats.starts === 0) {
+    return {
+      id: "serverStability",
+      status: "unknown",
+      summary: "The workspace server has not run since the app started.",
+      recommendation: null,
+    };
+  }
+  if (stats.crashes < SERVER_CRASH_WARNING_COUNT) {
+    return {
+      id: "serverStability",
+      status: "ok",
+      summary: `No unexpected server exits across ${stats.starts} start(s).`,
+      recommendation: null,
+    };
+  }
+  return {
+    id: "serverStability",
+    status:
+      stats.crashes >= SERVER_CRASH_CRITICAL_COUNT || stats.crashes >= stats.starts
+        ? "critical"
+        : "warning",
+    summary:
+      `The workspace server exited unexpectedly ${stats.crashes} time(s) ` +
+      `across ${stats.starts} start(s).`,
+    recommendation:
+      "Check server.log in the logs folder for the exit reason, then restart the workspace server.",
+  };
+}
+
+function checkTranscriptSize(stats: TranscriptStats): WorkspaceHealthCheck {
+  const summary =
+    `${stats.fileCount} transcript(s) use ${formatMegabytes(stats.totalBytes)}; ` +
+    `the largest is ${formatMegabytes(stats.largestBytes)}.`;
+  const status: WorkspaceHealthStatus =
+    stats.totalBytes >= TRANSCRIPT_TOTAL_CRITICAL_BYTES
+      ? "critical"
+      : stats.totalBytes >= TRANSCRIPT_TOTAL_WARNING_BYTES ||
+          stats.largestBytes >= TRANSCRIPT_SINGLE_WARNING_BYTES
+        ? "warning"
+        : "ok";
+  return {
+    id: "transcriptSize",
+    status,
+    summary,
+    recommendation:
+      status === "ok"
+        ? null
+        : "Delete threads you no longer need; large transcripts slow down thread loading.",
+  };
+}
+
+function checkIndexFreshness(): WorkspaceHealthCheck {
+  return {
+    id: "indexFreshness",
+    status: "unknown",
+    summary: "This build does not maintain a workspace index.",
+    recommendation: null,
+  };
+}
+
+function checkGuardrails(workspace: WorkspaceRecord): WorkspaceHealthCheck {
+  if (workspace.yolo) {
+    return {
+      id: "guardrails",
+      status: "critical",
+      summary: "YOLO mode is on, so tool calls run without approval.",
+      recommendation: "Turn off YOLO mode in the workspace settings to review tool calls.",
+    };
+  }
+  return {
+    id: "guardrails",
+    status: "ok",
+    summary: "Tool calls that need approval are confirmed before they run.",
+    recommendation: null,
+  };
+}
+
+function checkSecretsExposure(files: string[] | null): WorkspaceHealthCheck {
+  if (files === null) {
+    return {
+      id: "secretsExposure",
+      status: "unknown",
+      summary: "The workspace folder could not be scanned.",
+      recommendation: null,
+    };
+  }
+  if (files.length === 0) {
+    return {
+      id: "secretsExposure",
+      status: "ok",
+      summary: "No unignored secret files were found in the workspace root.",
+      recommendation: null,
+    };
+  }
+  return {
+    id: "secretsExposure",
+    status: "warning",
+    summary: `Secret files are not ignored by git: ${files.join(", ")}.`,
+    recommendation:
+      "Add these files to .gitignore or move them out of the workspace so they are not committed.",
+  };
+}
+
+export function summarizeWorkspaceHealth(
+  workspaceId: string,
+  checks: WorkspaceHealthCheck[],
+  generatedAt: Date,
+): WorkspaceHealthReport {
+  const score = Math.max(
+    0,
+    checks.reduce((total, check) => total - STATUS_PENALTIES[check.status], 100),
+  );
+  const status = checks.some((check) => check.status === "critical")
+    ? "critical"
+    : checks.some((check) => check.status === "warning")
+      ? "warning"
+      : "ok";
+  return { workspaceId, score, status, checks, generatedAt: generatedAt.toISOString() };
+}
+
+/**
+ * Folds server stability, transcript storage, and security signals into one
+ * score with a recommendation for every check that needs attention.
+ */
+export class WorkspaceHealthService {
+  private readonly findSecretFiles: (workspacePath: string) => Promise<string[] | null>;
+  private readonly now: () => Date;
+
+  constructor(private readonly options: WorkspaceHealthServiceOptions) {
+    this.findSecretFiles = options.findExposedSecretFiles ?? findExposedSecretFiles;
+    this.now = options.now ?? (() => new Date());
+  }
+
+  async getHealth(workspaceId: string): Promise<WorkspaceHealthReport> {
+    assertSafeId(workspaceId, "workspaceId");
+    const state = await this.options.persistence.loadState();
+    const workspace = state.workspaces.find((candidate) => candidate.id === workspaceId);
+    if (!workspace) {
+      throw new Error(`Unknown workspace: ${workspaceId}`);
+    }
+    const threadIds = state.threads
+      .filter((thread) => thread.workspaceId === workspaceId)
+      .map((thread) => thread.id);
+    const [transcripts, secretFiles] = await Promise.all([
+      this.options.persistence.getTranscriptStats(threadIds),
+      this.findSecretFiles(workspace.path),
+    ]);
+
+    return summarizeWorkspaceHealth(
+      workspaceId,
+      [
+        checkServerStability(this.options.serverManager.getStabilityStats(workspaceId)),
+        checkTranscriptSize(transcripts),
+        checkIndexFreshness(),
+        checkGuardrails(workspace),
+        checkSecretsExposure(secretFiles),
+      ],
+      this.now(),
+    );
+  }
+}
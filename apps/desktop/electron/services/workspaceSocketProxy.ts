@@ -0,0 +1,397 @@
+import { randomBytes, timingSafeEqual } from "node:crypto";
+import type { IncomingMessage } from "node:http";
+
+import { type RawData, WebSocket, WebSocketServer } from "ws";
+
+import type {
+  WorkspaceProxyConnectionEvent,
+  WorkspaceProxyConnectionState,
+} from "../../src/lib/desktopApi";
+
+const DEFAULT_RECONNECT_WINDOW_MS = 15_000;
+const DEFAULT_RETRY_DELAY_MS = 250;
+const DEFAULT_MAX_BUFFERED_BYTES = 4 * 1024 * 1024;
+const PROXY_PATH_PATTERN = /^\/workspaces\/([A-Za-z0-9_-]{1,256})\/ws$/;
+/** Ids of `initialize` requests the proxy replays itself; their responses are not forwarded. */
+const REPLAYED_INITIALIZE_ID_PREFIX = "cowork-proxy-initialize-";
+
+type TimerHandle = ReturnType<typeof setTimeout>;
+
+type UpstreamEndpoint = {
+  url: string;
+  /** Sent as `Authorization: Bearer` on the sidecar handshake when set. */
+  authToken: string | null;
+};
+
+type WorkspaceSocketProxyOptions = {
+  /** Current sidecar endpoint for a workspace, or null while none is running. */
+  resolveUpstream: (workspaceId: string) => UpstreamEndpoint | null;
+  onConnectionStateChanged?: (event: WorkspaceProxyConnectionEvent) => void;
+  /** How long a client waits for its sidecar to come back before it is disconnected. */
+  reconnectWindowMs?: number;
+  retryDelayMs?: number;
+  /** Per-client cap on messages held while the sidecar is unreachable. */
+  maxBufferedBytes?: number;
+  createToken?: () => string;
+  now?: () => number;
+  setTimeout?: (callback: () => void, ms: number) => TimerHandle;
+  clearTimeout?: (handle: TimerHandle) => void;
+};
+
+type BufferedMessage = {
+  data: RawData;
+  isBinary: boolean;
+  bytes: number;
+};
+
+type ClientConnectionState = "connecting" | "connected" | "reconnecting";
+
+type ProxiedClient = {
+  workspaceId: string;
+  socket: WebSocket;
+  protocols: string[];
+  state: ClientConnectionState;
+  upstream: WebSocket | null;
+  buffer: BufferedMessage[];
+  bufferedBytes: number;
+  /** The client's `initialize` request, replayed to each replacement sidecar connection. */
+  initializeRequest: Record<string, unknown> | null;
+  initializedNotification: string | null;
+  reconnectDeadline: number | null;
+  retryTimer: TimerHandle | null;
+  closed: boolean;
+};
+
+function rawDataBytes(data: RawData): number {
+  if (Array.isArray(data)) {
+    return data.reduce((sum, chunk) => sum + chunk.byteLength, 0);
+  }
+  return data.byteLength;
+}
+
+function parseJsonRecord(data: RawData, isBinary: boolean): Record<string, unknown> | null {
+  if (isBinary) {
+    return null;
+  }
+  try {
+    const value: unknown = JSON.parse(data.toString());
+    return typeof value === "object" && value !== null && !Array.isArray(value)
+      ? (value as Record<string, unknown>)
+      : null;
+  } catch {
+    return null;
+  }
+}
+
+function requestedProtocols(request: IncomingMessage): string[] {
+  const header = request.headers["sec-websocket-protocol"];
+  return (Array.isArray(header) ? header.join(",") : (header ?? ""))
+    .split(",")
+    .map((protocol) => protocol.trim())
+    .filter(Boolean);
+}
+
+function sameToken(expected: string, actual: string | null): boolean {
+  if (!actual) {
+    return false;
+  }
+  const left = Buffer.from(expected);
+  const right = Buffer.from(actual);
+  return left.length === right.length && timingSafeEqual(left, right);
+}
+
+/**
+ * Loopback WebSocket proxy between renderers and workspace sidecars. Every
+ * workspace is served on one listener under its own path; each client gets its
+ * own sidecar connection. When a sidecar drops (a crash or restart), clients
+ * stay connected: their messages are held, the proxy reconnects to whatever
+ * URL the workspace serves next, replays the client's `initialize`, and then
+ * flushes what it held. Thread subscriptions are not replayed, so clients
+ * should resume threads when a workspace returns to "connected".
+ */
+export class WorkspaceSocketProxy {
+  private server: WebSocketServer | null = null;
+  private listening: Promise<number> | null = null;
+  private readonly token: string;
+  private readonly clients = new Set<ProxiedClient>();
+  private readonly lastStateByWorkspace = new Map<string, string>();
+  private readonly reconnectWindowMs: number;
+  private readonly retryDelayMs: number;
+  private readonly maxBufferedBytes: number;
+  private readonly now: () => number;
+  private readonly setTimeoutImpl: NonNullable<WorkspaceSocketProxyOptions["setTimeout"]>;
+  private readonly clearTimeoutImpl: NonNullable<WorkspaceSocketProxyOptions["clearTimeout"]>;
+  private replayedInitializeCount = 0;
+
+  constructor(private readonly options: WorkspaceSocketProxyOptions) {
+    this.token = (options.createToken ?? (() => randomBytes(32).toString("hex")))();
+    this.reconnectWindowMs = options.reconnectWindowMs ?? DEFAULT_RECONNECT_WINDOW_MS;
+    this.retryDelayMs = options.retryDelayMs ?? DEFAULT_RETRY_DELAY_MS;
+    this.maxBufferedBytes = options.maxBufferedBytes ?? DEFAULT_MAX_BUFFERED_BYTES;
+    this.now = options.now ?? Date.now;
+    this.setTimeoutImpl = options.setTimeout ?? setTimeout;
+    this.clearTimeoutImpl = options.clearTimeout ?? clearTimeout;
+  }
+
+  /** Starts the listener on first use and returns the workspace's proxy URL. */
+  async getUrl(workspaceId: string): Promise<string> {
+    const port = await this.listen();
+    return `ws://127.0.0.1:${port}/workspaces/${workspaceId}/ws?token=${this.token}`;
+  }
+
+  /** Disconnects a workspace's clients, e.g. after its server was stopped on purpose. */
+  closeWorkspace(workspaceId: string, reason = "Workspace server stopped"): void {
+    for (const client of this.clients) {
+      if (client.workspaceId === workspaceId) {
+        this.closeClient(client, 1001, reason);
+      }
+    }
+  }
+
+  async dispose(): Promise<void> {
+    for (const client of [...this.clients]) {
+      this.closeClient(client, 1001, "Desktop app is shutting down");
+    }
+    const server = this.server;
+    this.server = null;
+    this.listening = null;
+    if (server) {
+      await new Promise<void>((resolve) => {
+        server.close(() => resolve());
+      });
+    }
+  }
+
+  private listen(): Promise<number> {
+    this.listening ??= new Promise<number>((resolve, reject) => {
+      const server = new WebSocketServer({
+        host: "127.0.0.1",
+        port: 0,
+        verifyClient: ({ req }) => this.workspaceIdForRequest(req) !== null,
+        handleProtocols: (protocols) => protocols.values().next().value ?? false,
+      });
+      server.once("listening", () => {
+        const address = server.address();
+        if (!address || typeof address === "string") {
+          reject(new Error("Workspace socket proxy did not expose a TCP address"));
+          return;
+        }
+        resolve(address.port);
+      });
+      server.once("error", reject);
+      server.on("connection", (socket, request) => {
+        const workspaceId = this.workspaceIdForRequest(request);
+        if (workspaceId) {
+          this.attachClient(workspaceId, socket, requestedProtocols(request));
+        } else {
+          socket.close(1008, "Unauthorized");
+        }
+      });
+      this.server = server;
+    });
+    this.listening.catch(() => {
+      this.listening = null;
+    });
+    return this.listening;
+  }
+
+  private workspaceIdForRequest(request: IncomingMessage): string | null {
+    const url = new URL(request.url ?? "/", "ws://127.0.0.1");
+    const match = PROXY_PATH_PATTERN.exec(url.pathname);
+    if (!match?.[1] || !sameToken(this.token, url.searchParams.get("token"))) {
+      return null;
+    }
+    return match[1];
+  }
+
+  private attachClient(workspaceId: string, socket: WebSocket, protocols: string[]): void {
+    const client: ProxiedClient = {
+      workspaceId,
+      socket,
+      protocols,
+      state: "connecting",
+      upstream: null,
+      buffer: [],
+      bufferedBytes: 0,
+      initializeRequest: null,
+      initializedNotification: null,
+      reconnectDeadline: this.now() + this.reconnectWindowMs,
+      retryTimer: null,
+      closed: false,
+    };
+    this.clients.add(client);
+    socket.on("message", (data, isBinary) => this.handleClientMessage(client, data, isBinary));
+    socket.on("close", () => this.closeClient(client));
+    socket.on("error", () => this.closeClient(client));
+    this.connectUpstream(client);
+  }
+
+  private handleClientMessage(client: ProxiedClient, data: RawData, isBinary: boolean): void {
+    if (client.state === "connected" && client.upstream?.readyState === WebSocket.OPEN) {
+      this.forward(client, client.upstream, data, isBinary);
+      return;
+    }
+    const bytes = rawDataBytes(data);
+    if (client.bufferedBytes + bytes > this.maxBufferedBytes) {
+      this.closeClient(client, 1013, "Workspace server unavailable; too many queued messages");
+      return;
+    }
+    client.buffer.push({ data, isBinary, bytes });
+    client.bufferedBytes += bytes;
+  }
+
+  private connectUpstream(client: ProxiedClient): void {
+    if (client.closed) {
+      return;
+    }
+    const endpoint = this.options.resolveUpstream(client.workspaceId);
+    if (!endpoint) {
+      this.scheduleReconnect(client);
+      return;
+    }
+    const isReconnect = client.state === "reconnecting";
+    const upstream = new WebSocket(endpoint.url, client.protocols, {
+      headers: endpoint.authToken ? { authorization: `Bearer ${endpoint.authToken}` } : {},
+    });
+    client.upstream = upstream;
+    upstream.on("open", () => {
+      if (client.closed) {
+        upstream.close();
+        return;
+      }
+      if (isReconnect) {
+        this.replayHandshake(client, upstream);
+      }
+      client.state = "connected";
+      client.reconnectDeadline = null;
+      for (const message of client.buffer.splice(0)) {
+        this.forward(client, upstream, message.data, message.isBinary);
+      }
+      client.bufferedBytes = 0;
+      this.emitState(client.workspaceId);
+    });
+    upstream.on("message", (data, isBinary) => {
+      const message = parseJsonRecord(data, isBinary);
+      if (
+        typeof message?.id === "string" &&
+        message.id.startsWith(REPLAYED_INITIALIZE_ID_PREFIX)
+      ) {
+        return;
+      }
+      if (client.socket.readyState === WebSocket.OPEN) {
+        client.socket.send(data, { binary: isBinary });
+      }
+    });
+    upstream.on("error", () => {
+      // A close event always follows; reconnecting is handled there.
+    });
+    upstream.on("close", () => {
+      if (client.upstream !== upstream || client.closed) {
+        return;
+      }
+      client.upstream = null;
+      if (client.state === "connected") {
+        client.state = "reconnecting";
+        client.reconnectDeadline = this.now() + this.reconnectWindowMs;
+        this.emitState(client.workspaceId, "Workspace server connection lost");
+      }
+      this.scheduleReconnect(client);
+    });
+  }
+
+  /** Sends a client message upstream, remembering the handshake so it can be replayed. */
+  private forward(
+    client: ProxiedClient,
+    upstream: WebSocket,
+    data: RawData,
+    isBinary: boolean,
+  ): void {
+    const message = parseJsonRecord(data, isBinary);
+    if (message?.method === "initialize" && message.id !== undefined) {
+      client.initializeRequest = message;
+    } else if (message?.method === "initialized" && message.id === undefined) {
+      client.initializedNotification = data.toString();
+    }
+    upstream.send(data, { binary: isBinary });
+  }
+
+  /** Re-runs the client's handshake on a fresh sidecar connection before its queued messages. */
+  private replayHandshake(client: ProxiedClient, upstream: WebSocket): void {
+    if (client.initializeRequest) {
+      this.replayedInitializeCount += 1;
+      upstream.send(
+        JSON.stringify({
+          ...client.initializeRequest,
+          id: `${REPLAYED_INITIALIZE_ID_PREFIX}${this.replayedInitializeCount}`,
+        }),
+      );
+    }
+    if (client.initializedNotification) {
+      upstream.send(client.initializedNotification);
+    }
+  }
+
+  private scheduleReconnect(client: ProxiedClient): void {
+    if (client.closed) {
+      return;
+    }
+    if (client.reconnectDeadline !== null && this.now() >= client.reconnectDeadline) {
+      this.closeClient(client, 1011, "Workspace server did not come back");
+      return;
+    }
+    client.retryTimer = this.setTimeoutImpl(() => {
+      client.retryTimer = null;
+      this.connectUpstream(client);
+    }, this.retryDelayMs);
+  }
+
+  private closeClient(client: ProxiedClient, code?: number, reason?: string): void {
+    if (client.closed) {
+      return;
+    }
+    client.closed = true;
+    this.clients.delete(client);
+    if (client.retryTimer) {
+      this.clearTimeoutImpl(client.retryTimer);
+      client.retryTimer = null;
+    }
+    client.buffer = [];
+    client.bufferedBytes = 0;
+    const upstream = client.upstream;
+    client.upstream = null;
+    if (upstream && upstream.readyState !== WebSocket.CLOSED) {
+      upstream.terminate();
+    }
+    if (
+      code !== undefined &&
+      (client.socket.readyState === WebSocket.OPEN ||
+        client.socket.readyState === WebSocket.CONNECTING)
+    ) {
+      client.socket.close(code, reason);
+    }
+    this.emitState(client.workspaceId, reason);
+  }
+
+  private emitState(workspaceId: string, reason?: string): void {
+    const clients = [...this.clients].filter((client) => client.workspaceId === workspaceId);
+    let state: WorkspaceProxyConnectionState = "disconnected";
+    if (clients.some((client) => client.state !== "connected")) {
+      state = "reconnecting";
+    } else if (clients.length > 0) {
+      state = "connected";
+    }
+    const event: WorkspaceProxyConnectionEvent = {
+      workspaceId,
+      state,
+      clientCount: clients.length,
+      bufferedMessages: clients.reduce((sum, client) => sum + client.buffer.length, 0),
+      ...(reason ? { reason } : {}),
+    };
+    const key = `${event.state}:${event.clientCount}`;
+    if (this.lastStateByWorkspace.get(workspaceId) === key) {
+      return;
+    }
+    this.lastStateByWorkspace.set(workspaceId, key);
+    this.options.onConnectionStateChanged?.(event);
+  }
+}
@@ -0,0 +1,86 @@
+import type { YoloGrant } from "../../src/lib/desktopApi";
+import { ConfirmationTokens } from "./destructiveConfirmations";
+
+type ActiveGrant = {
+  expiresAtMs: number;
+  timer: ReturnType<typeof setTimeout>;
+};
+
+type YoloGrantsOptions = {
+  /** How long yolo stays on once a token is redeemed. */
+  getDurationMs: () => Promise<number>;
+  /** Runs when an active grant runs out, so its servers can be stopped. */
+  onExpire?: (workspaceId: string) => void;
+  now?: () => number;
+  tokenTtlMs?: number;
+  createToken?: () => string;
+};
+
+/**
+ * Yolo mode is only honoured while main holds a grant for the workspace, not
+ * because the persisted state says `yolo: true`. The renderer asks for a
+ * short-lived token after the user confirms, redeems it when it starts the
+ * server, and the grant then lapses on its own after the configured duration.
+ */
+export class YoloGrants {
+  private readonly tokens: ConfirmationTokens<"yolo">;
+  private readonly active = new Map<string, ActiveGrant>();
+  private readonly now: () => number;
+
+  constructor(private readonly options: YoloGrantsOptions) {
+    this.now = options.now ?? Date.now;
+    this.tokens = new ConfirmationTokens({
+      now: this.now,
+      ttlMs: options.tokenTtlMs,
+      createToken: options.createToken,
+    });
+  }
+
+  issue(workspaceId: string, ownerId: number): YoloGrant {
+    const { token, expiresAt } = this.tokens.issue("yolo", workspaceId, ownerId);
+    return { workspaceId, token, expiresAt: expiresAt.toISOString() };
+  }
+
+  /**
+   * Consumes `token` and turns yolo on for the workspace; throws unless it was
+   * issued to this window for this workspace and has not expired. Returns when
+   * the grant lapses.
+   */
+  async redeem(workspaceId: string, ownerId: number, token: string): Promise<Date> {
+    switch (this.tokens.consume("yolo", workspaceId, ownerId, token)) {
+      case "invalid":
+        throw new Error("Invalid yolo confirmation token.");
+      case "expired":
+        throw new Error("Yolo confirmation expired; request a new one.");
+    }
+    const durationMs = await this.options.getDurationMs();
+    this.revoke(workspaceId);
+    const expiresAtMs = this.now() + durationMs;
+    const timer = setTimeout(() => {
+      if (this.active.get(workspaceId)?.timer === timer) {
+        this.active.delete(workspaceId);
+        this.options.onExpire?.(workspaceId);
+      }
+    }, durationMs);
+    timer.unref?.();
+    this.active.set(workspaceId, { expiresAtMs, timer });
+    return new Date(expiresAtMs);
+  }
+
+  /** When the workspace's grant lapses, or null when yolo is not granted. */
+  activeUntil(workspaceId: string): Date | null {
+    const grant = this.active.get(workspaceId);
+    if (!grant || grant.expiresAtMs <= this.now()) {
+      return null;
+    }
+    return new Date(grant.expiresAtMs);
+  }
+
+  revoke(workspaceId: string): void {
+    const grant = this.active.get(workspaceId);
+    if (grant) {
+      clearTimeout(grant.timer);
+      this.active.delete(workspaceId);
+    }
+  }
+}
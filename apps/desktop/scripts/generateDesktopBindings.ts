@@ -0,0 +1,19 @@
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import {
+  buildDesktopBindingsArtifact,
+  DESKTOP_BINDINGS_ARTIFACT_PATH,
+} from "../src/lib/desktopBindingsCodegen";
+
+const DESKTOP_ROOT = path.resolve(import.meta.dir, "..");
+
+async function main() {
+  await fs.writeFile(
+    path.join(DESKTOP_ROOT, DESKTOP_BINDINGS_ARTIFACT_PATH),
+    buildDesktopBindingsArtifact(),
+    "utf-8",
+  );
+}
+
+void main();
@@ -13,8 +13,10 @@ import type { DesktopMenuCommand, SystemAppearance } from "./lib/desktopApi";
 import {
   getPlatformChrome,
   getSystemAppearance,
+  onDeepLinkNavigation,
   onMenuCommand,
   onPreviewFileChanged,
+  onServerSuspended,
   onSystemAppearanceChanged,
   onUpdateStateChanged,
   onWindowCloseRequested,
@@ -36,6 +38,7 @@ import { applySystemAppearanceToDocument, readBootstrappedThemeSource } from "./
 import { useAdaptiveLayout } from "./lib/useAdaptiveLayout";
 import { cn } from "./lib/utils";
 import { getDesktopWindowMode } from "./lib/windowMode";
+import { dataTransferHasFiles, droppedFolderFiles } from "./lib/workspaceFolderDrop";
 import { Canvas } from "./ui/Canvas";
 import { CommandPalette } from "./ui/CommandPalette";
 import { ConnectionRecoveryBanner } from "./ui/ConnectionRecoveryBanner";
@@ -665,7 +668,9 @@ function AppContent() {
   const notifications = useAppStore((s) => s.notifications);
   const setUpdateState = useAppStore((s) => s.setUpdateState);
   const handleWorkspaceServerExited = useAppStore((s) => s.handleWorkspaceServerExited);
+  const handleServerSuspended = useAppStore((s) => s.handleServerSuspended);
   const setWorkspaceServerStartupProgress = useAppStore((s) => s.setWorkspaceServerStartupProgress);
+  const addDroppedWorkspaceFolders = useAppStore((s) => s.addDroppedWorkspaceFolders);
   const seenNotificationIds = useRef(new Set<string>());
   const [commandPaletteOpen, setCommandPaletteOpen] = useState(false);
 
@@ -703,6 +708,47 @@ function AppContent() {
     });
   }, [handleWorkspaceServerExited, windowMode]);
 
+  useEffect(() => {
+    if (windowMode !== "main") return;
+    return onServerSuspended((event) => {
+      void writeRendererLog({
+        category: "sidecar",
+        message: "workspace server suspended while idle",
+        meta: {
+          workspaceId: event.workspaceId,
+          idleMinutes: event.idleMinutes,
+          onBattery: event.onBattery,
+        },
+      }).catch(() => {});
+      handleServerSuspended(event);
+    });
+  }, [handleServerSuspended, windowMode]);
+
+  // Folders dropped anywhere outside a drop target (the composer takes files)
+  // become workspaces.
+  useEffect(() => {
+    if (windowMode !== "main" || !ready) return;
+    const handleDragOver = (event: DragEvent) => {
+      if (!event.dataTransfer || !dataTransferHasFiles(event.dataTransfer)) return;
+      event.preventDefault();
+      event.dataTransfer.dropEffect = "copy";
+    };
+    const handleDrop = (event: DragEvent) => {
+      if (!event.dataTransfer || !dataTransferHasFiles(event.dataTransfer)) return;
+      event.preventDefault();
+      const folders = droppedFolderFiles(event.dataTransfer);
+      if (folders.length > 0) {
+        void addDroppedWorkspaceFolders(folders);
+      }
+    };
+    window.addEventListener("dragover", handleDragOver);
+    window.addEventListener("drop", handleDrop);
+    return () => {
+      window.removeEventListener("dragover", handleDragOver);
+      window.removeEventListener("drop", handleDrop);
+    };
+  }, [addDroppedWorkspaceFolders, ready, windowMode]);
+
   useEffect(
     () =>
       onWindowCloseRequested((request) => {
@@ -850,6 +896,29 @@ function AppContent() {
     return unsubscribe;
   }, []);
 
+  // cowork:// links are validated in the main process, which has already
+  // started the workspace server by the time they arrive here.
+  useEffect(() => {
+    if (windowMode !== "main") return;
+    return onDeepLinkNavigation((navigation) => {
+      const state = useAppStore.getState();
+      if (navigation.kind === "openPath") {
+        void state.addWorkspace({ path: navigation.path });
+        return;
+      }
+      if (navigation.kind === "transcriptFile") {
+        // The viewer thread is already loaded in the main process and reads like
+        // any other transcript; there is no workspace to select for it.
+        return;
+      }
+      if (navigation.kind === "thread") {
+        void state.selectThread(navigation.threadId);
+        return;
+      }
+      void state.selectWorkspace(navigation.workspaceId);
+    });
+  }, [windowMode]);
+
   useEffect(() => {
     return onPreviewFileChanged((event) => {
       workspaceFileChangeEvents.publish(event);
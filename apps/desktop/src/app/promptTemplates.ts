@@ -0,0 +1,31 @@
+import { PROMPT_VARIABLES_MISSING_ERROR_CODE } from "../lib/desktopApi";
+
+const VARIABLE_PATTERN = /\{\{\s*([A-Za-z_][\w.-]*)\s*\}\}/g;
+
+/** Placeholder names in the order they first appear; `{{ name }}` and `{{name}}` are the same. */
+export function extractPromptVariables(body: string): string[] {
+  const names = new Set<string>();
+  for (const match of body.matchAll(VARIABLE_PATTERN)) {
+    names.add(match[1] as string);
+  }
+  return [...names];
+}
+
+/** Variables the body uses that `vars` has no value for. */
+export function findMissingPromptVariables(body: string, vars: Record<string, string>): string[] {
+  return extractPromptVariables(body).filter((name) => !Object.hasOwn(vars, name));
+}
+
+/**
+ * Substitutes every placeholder in one pass, so values containing `{{...}}`
+ * are inserted literally. Throws when any variable is missing rather than
+ * sending a half-filled prompt; extra values are ignored.
+ */
+export function renderPromptTemplate(body: string, vars: Record<string, string>): string {
+  const missing = findMissingPromptVariables(body, vars);
+  if (missing.length > 0) {
+    const placeholders = missing.map((name) => `{{${name}}}`).join(", ");
+    throw new Error(`${PROMPT_VARIABLES_MISSING_ERROR_CODE}: Missing values for ${placeholders}`);
+  }
+  return body.replace(VARIABLE_PATTERN, (_match, name: string) => vars[name] as string);
+}
@@ -0,0 +1,61 @@
+export const SSH_WORKSPACE_URL_PREFIX = "ssh://";
+
+/** Where a remote workspace lives; `path` is absolute on the remote host. */
+export type SshWorkspaceTarget = {
+  user: string | null;
+  host: string;
+  port: number | null;
+  path: string;
+};
+
+// Both end up as separate ssh arguments, so neither may look like an option.
+const SSH_USER_PATTERN = /^[A-Za-z0-9_][A-Za-z0-9._-]*$/;
+const SSH_HOST_PATTERN = /^(?:[A-Za-z0-9][A-Za-z0-9.-]*|\[[0-9A-Fa-f:.]+\])$/;
+
+export function isSshWorkspacePath(value: string): boolean {
+  return value.trim().toLowerCase().startsWith(SSH_WORKSPACE_URL_PREFIX);
+}
+
+/**
+ * Parses `ssh://[user@]host[:port]/absolute/path`. Returns null for local
+ * paths and throws for an `ssh://` URL that cannot be used.
+ */
+export function parseSshWorkspaceUrl(value: string): SshWorkspaceTarget | null {
+  if (!isSshWorkspacePath(value)) {
+    return null;
+  }
+  let url: URL;
+  try {
+    url = new URL(value.trim());
+  } catch {
+    throw new Error(`Invalid remote workspace URL: ${value}`);
+  }
+  if (url.password || url.search || url.hash) {
+    throw new Error("Remote workspace URLs take no password, query, or fragment");
+  }
+  const user = url.username ? decodeURIComponent(url.username) : null;
+  if (user !== null && !SSH_USER_PATTERN.test(user)) {
+    throw new Error(`Invalid SSH user: ${user}`);
+  }
+  if (!SSH_HOST_PATTERN.test(url.hostname)) {
+    throw new Error(`Invalid SSH host: ${url.hostname || "(empty)"}`);
+  }
+  const remotePath = decodeURIComponent(url.pathname).replace(/(?<=.)\/+$/, "");
+  if (!remotePath.startsWith("/") || /[\0\r\n]/.test(remotePath)) {
+    throw new Error("Remote workspace URLs need an absolute path, e.g. ssh://host/home/me/project");
+  }
+  return {
+    user,
+    host: url.hostname,
+    port: url.port ? Number(url.port) : null,
+    path: remotePath,
+  };
+}
+
+/** The canonical URL for `target`, used as the workspace's `path`. */
+export function formatSshWorkspaceUrl(target: SshWorkspaceTarget): string {
+  const user = target.user ? `${encodeURIComponent(target.user)}@` : "";
+  const port = target.port !== null ? `:${target.port}` : "";
+  const remotePath = target.path.split("/").map(encodeURIComponent).join("/");
+  return `${SSH_WORKSPACE_URL_PREFIX}${user}${target.host}${port}${remotePath}`;
+}
@@ -0,0 +1,90 @@
+import type { WorkspaceServerStartupDiagnostics } from "../lib/desktopApi";
+
+export type ServerStartupGuidance = {
+  summary: string;
+  steps: string[];
+};
+
+function quoteArg(arg: string): string {
+  return /^[\w@%+=:,./-]+$/.test(arg) ? arg : `"${arg.replace(/(["\\$`])/g, "\\$1")}"`;
+}
+
+/** The command a failed start ran, quoted so it can be pasted into a shell. */
+export function formatStartupCommandLine(diagnostics: WorkspaceServerStartupDiagnostics): string {
+  return [diagnostics.command, ...diagnostics.args].map(quoteArg).join(" ");
+}
+
+function describeExit(diagnostics: WorkspaceServerStartupDiagnostics): string | null {
+  if (diagnostics.signal) {
+    return `signal ${diagnostics.signal}`;
+  }
+  return diagnostics.exitCode !== null ? `exit code ${diagnostics.exitCode}` : null;
+}
+
+/** Turns startup diagnostics into a one-line cause and the steps most likely to fix it. */
+export function describeServerStartupFailure(
+  diagnostics: WorkspaceServerStartupDiagnostics,
+): ServerStartupGuidance {
+  const source = diagnostics.mode === "source";
+  if (!diagnostics.launcherFound) {
+    return source
+      ? {
+          summary: "Bun could not be found, so the workspace server never started.",
+          steps: [
+            "Install Bun from https://bun.sh.",
+            "Make sure `bun` is on the PATH Cowork is launched with, then restart Cowork.",
+          ],
+        }
+      : {
+          summary: "The bundled Cowork server is missing or cannot be executed.",
+          steps: [
+            "Reinstall Cowork to restore the server.",
+            "If security software quarantined it, allow it and try again.",
+          ],
+        };
+  }
+  if (diagnostics.stage === "spawn") {
+    return {
+      summary: `The workspace server could not be launched: ${diagnostics.message}`,
+      steps: [`Check that ${diagnostics.cwd || "the server directory"} exists and is readable.`],
+    };
+  }
+  if (diagnostics.stage === "health_check") {
+    return {
+      summary: "The workspace server started but did not answer its health check.",
+      steps: [
+        "Make sure nothing blocks connections to 127.0.0.1, such as a proxy or firewall.",
+        "Restart the workspace.",
+      ],
+    };
+  }
+
+  const stderr = diagnostics.stderrTail;
+  const steps: string[] = [];
+  if (/EACCES|EPERM|permission denied/i.test(stderr)) {
+    steps.push("Check that you can read and write the workspace folder.");
+  }
+  if (source && /Cannot find (module|package)|Could not resolve/i.test(stderr)) {
+    steps.push("Run `bun install` in the repository, then restart the workspace.");
+  }
+  const exit = describeExit(diagnostics);
+  if (exit) {
+    steps.push("Review the server output below for the first error.");
+    return {
+      summary: `The workspace server exited during startup (${exit}).`,
+      steps,
+    };
+  }
+  if (/timed out/i.test(diagnostics.message)) {
+    steps.push("Restart the workspace; the first start can be slow while runtimes install.");
+    return {
+      summary: "The workspace server did not finish starting in time.",
+      steps,
+    };
+  }
+  steps.push("Review the server output below, then restart the workspace.");
+  return {
+    summary: `The workspace server failed to start: ${diagnostics.message}`,
+    steps,
+  };
+}
@@ -52,8 +52,10 @@ import {
   RUNTIME,
   type StoreGet,
   type StoreSet,
+  setForeignStateRecordsHandler,
   syncDesktopStateCache,
   syncDesktopStateCacheNow,
+  trackPersistedStateGeneration,
 } from "../store.helpers";
 import {
   type BootstrapRunContext,
@@ -62,6 +64,7 @@ import {
 import { invalidateNavigationIntent } from "../store.helpers/operationIntent";
 import { waitForNextPaintOrTimeout } from "../store.helpers/paintScheduling";
 import { isStandardChatThread } from "../threadFilters";
+import { normalizeThreadRouting } from "../threadRouting";
 import { getThreadSelectionContext, getThreadSelectionIntent } from "../threadSelectionContext";
 import {
   type CachedDesktopUiState,
@@ -79,6 +82,14 @@ import {
   type ThreadRecord,
   type WorkspaceRecord,
 } from "../types";
+import { normalizeWorkspaceContainerLaunch } from "../workspaceContainer";
+import { normalizeWorkspaceIdlePolicy } from "../workspaceIdlePolicy";
+import { normalizeWorkspaceLaunchProfiles } from "../workspaceLaunchProfiles";
+import { normalizeWorkspacePathPolicy } from "../workspacePathPolicy";
+import { normalizeWorkspaceResourceLimits } from "../workspaceResourceLimits";
+import { normalizeAdditionalRoots } from "../workspaceRootPaths";
+import { normalizeWorkspaceTags } from "../workspaceTags";
+import { normalizeWorkspaceTranscriptMirror } from "../workspaceTranscriptMirror";
 import { DEFAULT_ONBOARDING_STATE, resolveStartupOnboarding } from "./onboarding";
 
 const optionalStringWithContentSchema = z.preprocess(
@@ -268,7 +279,18 @@ const persistedWorkspaceSchema = z
     defaultSkillImprovementModel: z.string().optional(),
     defaultSkillImprovementScope: z.enum(["user", "all"]).optional(),
     defaultSkillImprovementExcludedSkills: z.array(z.string()).optional(),
+    additionalRoots: z.unknown().optional(),
+    resourceLimits: z.unknown().transform(normalizeWorkspaceResourceLimits),
+    idlePolicy: z.unknown().transform(normalizeWorkspaceIdlePolicy),
+    container: z.unknown().transform(normalizeWorkspaceContainerLaunch),
+    transcriptMirror: z.unknown().transform(normalizeWorkspaceTranscriptMirror),
     yolo: z.preprocess((value) => (typeof value === "boolean" ? value : false), z.boolean()),
+    yoloCheckpoints: z.boolean().optional(),
+    pinned: z.boolean().optional(),
+    sortOrder: z.number().int().nonnegative().optional(),
+    tags: z.unknown().transform(normalizeWorkspaceTags),
+    launchProfiles: z.unknown().transform(normalizeWorkspaceLaunchProfiles),
+    pathPolicy: z.unknown().transform(normalizeWorkspacePathPolicy),
   })
   .passthrough()
   .transform((workspace): WorkspaceRecord => {
@@ -321,7 +343,18 @@ const persistedWorkspaceSchema = z
       defaultSkillImprovementModel: workspace.defaultSkillImprovementModel,
       defaultSkillImprovementScope: workspace.defaultSkillImprovementScope,
       defaultSkillImprovementExcludedSkills: workspace.defaultSkillImprovementExcludedSkills,
+      additionalRoots: normalizeAdditionalRoots(workspace.additionalRoots, workspace.path),
+      resourceLimits: workspace.resourceLimits,
+      idlePolicy: workspace.idlePolicy,
+      container: workspace.container,
+      transcriptMirror: workspace.transcriptMirror,
       yolo: workspace.yolo,
+      yoloCheckpoints: workspace.yoloCheckpoints,
+      pinned: workspace.pinned,
+      sortOrder: workspace.sortOrder,
+      tags: workspace.tags,
+      launchProfiles: workspace.launchProfiles,
+      pathPolicy: workspace.pathPolicy,
     };
   });
 
@@ -347,12 +380,15 @@ const persistedThreadSchema = z
       .preprocess((value) => (typeof value === "boolean" ? value : false), z.boolean())
       .optional(),
     archivedAt: z.string().optional(),
+    mergedIntoThreadId: optionalSafeIdSchema,
+    mergedAt: z.string().optional(),
     reasoningEffort: z
       .preprocess(
         (value) => (isReasoningEffortValue(value) ? value : undefined),
         z.custom<NonNullable<ThreadRecord["reasoningEffort"]>>().optional(),
       )
       .optional(),
+    routing: z.unknown().transform(normalizeThreadRouting),
   })
   .passthrough()
   .transform((thread): ThreadRecord => {
@@ -374,7 +410,11 @@ const persistedThreadSchema = z
       draft: thread.draft ?? false,
       archived: thread.archived ?? false,
       archivedAt: thread.archivedAt,
+      ...(thread.mergedIntoThreadId
+        ? { mergedIntoThreadId: thread.mergedIntoThreadId, mergedAt: thread.mergedAt }
+        : {}),
       ...(thread.reasoningEffort ? { reasoningEffort: thread.reasoningEffort } : {}),
+      ...(thread.routing ? { routing: thread.routing } : {}),
     };
   });
 
@@ -1001,7 +1041,21 @@ export function createBootstrapActions(
               return;
             }
           }
-          const state = hydratePersistedDesktopState(await loadState());
+          setForeignStateRecordsHandler(({ workspaces, threads }) => {
+            set((s) => ({
+              workspaces: [
+                ...s.workspaces,
+                ...workspaces.filter((w) => !s.workspaces.some((known) => known.id === w.id)),
+              ],
+              threads: [
+                ...s.threads,
+                ...threads.filter((t) => !s.threads.some((known) => known.id === t.id)),
+              ],
+            }));
+          });
+          const persistedState = await loadState();
+          trackPersistedStateGeneration(persistedState.generation);
+          const state = hydratePersistedDesktopState(persistedState);
           if (!isCurrent()) {
             return;
           }
@@ -1076,7 +1130,8 @@ export function createBootstrapActions(
                       continue;
                     }
                     try {
-                      await deleteTranscript({ threadId: transcriptId });
+                      // Turning on auto-delete in settings is the user's confirmation.
+                      await deleteTranscript({ threadId: transcriptId }, () => true);
                       deletedArchivedTranscriptIds.add(transcriptId);
                     } catch {
                       // ignore
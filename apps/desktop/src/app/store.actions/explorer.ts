@@ -13,6 +13,7 @@ import {
 import { isCanvasSupportedFile } from "../../lib/filePreviewKind";
 
 import type { AppStoreActions, StoreGet, StoreSet } from "../store.helpers";
+import { containingWorkspaceRoot, workspaceRootPaths } from "../workspaceRootPaths";
 
 let nextExplorerRequestId = 0;
 
@@ -24,6 +25,7 @@ export function createExplorerActions(
   | "refreshWorkspaceFiles"
   | "navigateWorkspaceFiles"
   | "navigateWorkspaceFilesUp"
+  | "setWorkspaceExplorerRoot"
   | "selectWorkspaceFile"
   | "openWorkspaceFile"
   | "revealWorkspaceFile"
@@ -185,8 +187,9 @@ export function createExplorerActions(
       const currentPath = state.workspaceExplorerById[workspaceId]?.currentPath;
       if (!ws || !currentPath) return;
 
-      // don't navigate above workspace root
-      const normalizedRoot = ws.path.replace(/\\/g, "/").replace(/\/$/, "");
+      // don't navigate above the workspace root that contains the current folder
+      const root = containingWorkspaceRoot(ws, currentPath) ?? ws.path;
+      const normalizedRoot = root.replace(/\\/g, "/").replace(/\/$/, "");
       const normalizedCurrent = currentPath.replace(/\\/g, "/").replace(/\/$/, "");
 
       if (
@@ -202,6 +205,26 @@ export function createExplorerActions(
       await get().navigateWorkspaceFiles(workspaceId, parent);
     },
 
+    setWorkspaceExplorerRoot: async (workspaceId: string, rootPath: string) => {
+      const ws = get().workspaces.find((w) => w.id === workspaceId);
+      if (!ws || !workspaceRootPaths(ws).includes(rootPath)) return;
+      set((s) => ({
+        workspaceExplorerById: {
+          ...s.workspaceExplorerById,
+          [workspaceId]: {
+            rootPath,
+            currentPath: rootPath,
+            entries: [],
+            selectedPath: null,
+            loading: false,
+            error: null,
+            requestId: s.workspaceExplorerById[workspaceId]?.requestId ?? 0,
+          },
+        },
+      }));
+      await get().navigateWorkspaceFiles(workspaceId, rootPath);
+    },
+
     selectWorkspaceFile: (workspaceId: string, path: string | null) => {
       set((s) => {
         const current = s.workspaceExplorerById[workspaceId];
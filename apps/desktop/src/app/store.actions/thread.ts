@@ -97,6 +97,7 @@ import {
   type SessionSnapshotFingerprint,
   type ThreadBusyPolicy,
   type ThreadRecord,
+  type ThreadRouting,
   type TranscriptEvent,
 } from "../types";
 
@@ -692,6 +693,9 @@ export function createThreadActions(
   | "removeThread"
   | "archiveThread"
   | "restoreThread"
+  | "forkThread"
+  | "mergeThreads"
+  | "suggestThreadTitle"
   | "deleteThreadHistory"
   | "renameThread"
   | "newThread"
@@ -711,6 +715,7 @@ export function createThreadActions(
   | "cancelThread"
   | "clearThreadUsageHardCap"
   | "setThreadModel"
+  | "updateThreadRouting"
   | "setThreadReasoningEffort"
   | "setComposerText"
   | "addComposerAttachments"
@@ -1039,6 +1044,14 @@ export function createThreadActions(
         selectedThreadId: s.selectedThreadId === threadId ? null : s.selectedThreadId,
       }));
       await persistNow(get);
+      if (typeof desktopCommands.archiveThread === "function") {
+        try {
+          // Moves the transcript to compressed cold storage; it stays readable.
+          await desktopCommands.archiveThread({ threadId });
+        } catch {
+          // The thread is archived either way; its transcript just stays hot.
+        }
+      }
     },
 
     restoreThread: async (threadId: string) => {
@@ -1048,9 +1061,104 @@ export function createThreadActions(
         ),
       }));
       await persistNow(get);
+      if (typeof desktopCommands.unarchiveThread === "function") {
+        try {
+          await desktopCommands.unarchiveThread({ threadId });
+        } catch {
+          // readTranscript still falls back to the archive.
+        }
+      }
+    },
+
+    forkThread: async (threadId: string) => {
+      let forkId: string;
+      try {
+        // Save first so main sees the source thread as the renderer does.
+        await persistNow(get);
+        const fork = await desktopCommands.forkThread({ threadId });
+        if (!fork) return null;
+        set((s) => ({
+          threads: [...s.threads.filter((t) => t.id !== fork.id), fork],
+        }));
+        await persistNow(get);
+        forkId = fork.id;
+      } catch (error) {
+        set((s) => ({
+          notifications: pushNotification(s.notifications, {
+            id: makeId(),
+            ts: nowIso(),
+            kind: "error",
+            title: "Unable to fork chat",
+            detail: error instanceof Error ? error.message : String(error),
+          }),
+        }));
+        return null;
+      }
+      await get().selectThread(forkId);
+      return forkId;
+    },
+
+    mergeThreads: async (sourceThreadId, targetThreadId, options) => {
+      let result: Awaited<ReturnType<typeof desktopCommands.mergeThreads>>;
+      try {
+        // Save first so main merges the threads as the renderer knows them.
+        await persistNow(get);
+        result = await desktopCommands.mergeThreads({
+          sourceThreadId,
+          targetThreadId,
+          ...(options?.trashSource ? { trashSource: true } : {}),
+        });
+      } catch (error) {
+        set((s) => ({
+          notifications: pushNotification(s.notifications, {
+            id: makeId(),
+            ts: nowIso(),
+            kind: "error",
+            title: "Unable to merge chats",
+            detail: error instanceof Error ? error.message : String(error),
+          }),
+        }));
+        return null;
+      }
+      if (!result) return null;
+      const { thread, source } = result;
+      set((s) => ({
+        threads: s.threads.flatMap((t) => {
+          if (t.id === thread.id) return [thread];
+          if (t.id === sourceThreadId) return source ? [source] : [];
+          return [t];
+        }),
+      }));
+      await persistNow(get);
+      if (get().selectedThreadId === sourceThreadId) {
+        await get().selectThread(thread.id);
+      }
+      return result.mergedEventCount;
+    },
+
+    suggestThreadTitle: async (threadId: string) => {
+      let thread: ThreadRecord | null;
+      try {
+        // Save first so main titles the thread the renderer knows about.
+        await persistNow(get);
+        thread = await desktopCommands.suggestThreadTitle({ threadId });
+      } catch {
+        // The current title stays; a later server title can still replace it.
+        return;
+      }
+      if (!thread) return;
+      const { title, titleSource } = thread;
+      set((s) => ({
+        threads: s.threads.map((t) =>
+          t.id === threadId && t.titleSource !== "manual" ? { ...t, title, titleSource } : t,
+        ),
+      }));
     },
 
-    removeThread: async (threadId: string) => {
+    removeThread: async (
+      threadId: string,
+      confirmTranscriptDeletion?: desktopCommands.ConfirmDestructiveCommand,
+    ) => {
       const thread = get().threads.find((t) => t.id === threadId);
       get().discardComposerDraft(composerDraftKeyForThread(threadId));
       const runtimeSessionId = get().threadRuntimeById[threadId]?.sessionId ?? null;
@@ -1117,10 +1225,13 @@ export function createThreadActions(
         await cleanupRemovedWorkspaceRuntime(workspaceIdToRemove);
       }
 
-      if (thread) {
+      if (thread && confirmTranscriptDeletion) {
         for (const transcriptId of transcriptIdsForThread(thread)) {
           try {
-            await desktopCommands.deleteTranscript({ threadId: transcriptId });
+            await desktopCommands.deleteTranscript(
+              { threadId: transcriptId },
+              confirmTranscriptDeletion,
+            );
           } catch {
             // ignore
           }
@@ -1130,7 +1241,10 @@ export function createThreadActions(
       await persistNow(get);
     },
 
-    deleteThreadHistory: async (threadId: string) => {
+    deleteThreadHistory: async (
+      threadId: string,
+      confirm: desktopCommands.ConfirmDestructiveCommand,
+    ) => {
       const thread = get().threads.find((t) => t.id === threadId);
       if (!thread) return;
       const targetSessionId = get().threadRuntimeById[threadId]?.sessionId ?? thread.sessionId;
@@ -1151,7 +1265,7 @@ export function createThreadActions(
         );
       }
 
-      await get().removeThread(threadId);
+      await get().removeThread(threadId, confirm);
 
       if (!targetSessionId) return;
 
@@ -2091,41 +2205,66 @@ export function createThreadActions(
       }
 
       const rt = get().threadRuntimeById[threadId];
-      if (!rt?.sessionId) return;
-      set((state) => ({
-        threads: state.threads.map((candidate) =>
-          candidate.id === threadId ? { ...candidate, reasoningEffort: undefined } : candidate,
-        ),
-        threadRuntimeById: {
-          ...state.threadRuntimeById,
-          [threadId]: {
-            ...state.threadRuntimeById[threadId],
-            composerReasoningEffort: null,
+      if (rt?.sessionId) {
+        set((state) => ({
+          threads: state.threads.map((candidate) =>
+            candidate.id === threadId ? { ...candidate, reasoningEffort: undefined } : candidate,
+          ),
+          threadRuntimeById: {
+            ...state.threadRuntimeById,
+            [threadId]: {
+              ...state.threadRuntimeById[threadId],
+              composerReasoningEffort: null,
+            },
           },
-        },
-      }));
-      persist(get);
-      const pendingApply = RUNTIME.pendingWorkspaceDefaultApplyByThread.get(threadId);
-      if (pendingApply?.draftModelSelection) {
-        RUNTIME.pendingWorkspaceDefaultApplyByThread.set(threadId, {
-          ...pendingApply,
-          draftModelSelection: null,
-        });
+        }));
+        persist(get);
+        const pendingApply = RUNTIME.pendingWorkspaceDefaultApplyByThread.get(threadId);
+        if (pendingApply?.draftModelSelection) {
+          RUNTIME.pendingWorkspaceDefaultApplyByThread.set(threadId, {
+            ...pendingApply,
+            draftModelSelection: null,
+          });
+        }
       }
-      const ok = sendThread(get, threadId, (sessionId) => ({
+      get().updateThreadRouting(threadId, provider, model);
+    },
+
+    updateThreadRouting: (threadId, provider, model) => {
+      const thread = get().threads.find((candidate) => candidate.id === threadId);
+      if (!thread) return;
+      const previous = thread.routing;
+      const trimmedModel = model?.trim() ?? "";
+      const next: ThreadRouting | undefined =
+        provider && trimmedModel
+          ? { provider, model: trimmedModel, updatedAt: nowIso() }
+          : undefined;
+      if (previous?.provider !== next?.provider || previous?.model !== next?.model) {
+        set((state) => ({
+          threads: state.threads.map((candidate) =>
+            candidate.id === threadId ? { ...candidate, routing: next } : candidate,
+          ),
+        }));
+        persist(get);
+        if (!thread.draft) {
+          appendThreadTranscript(threadId, "client", {
+            type: "thread_routing_updated",
+            sessionId: get().threadRuntimeById[threadId]?.sessionId ?? null,
+            previous: previous ? { provider: previous.provider, model: previous.model } : null,
+            next: next ? { provider: next.provider, model: next.model } : null,
+          });
+        }
+      }
+
+      // Clearing the override leaves the live session on its current model;
+      // new sessions pick up the workspace defaults again.
+      if (!next || thread.draft || !get().threadRuntimeById[threadId]?.sessionId) return;
+      sendThread(get, threadId, (sessionId) => ({
         type: "set_model",
         sessionId,
-        provider,
-        model,
+        provider: next.provider,
+        model: next.model,
       }));
-      if (ok) {
-        appendThreadTranscript(threadId, "client", {
-          type: "set_model",
-          sessionId: rt.sessionId,
-          provider,
-          model,
-        });
-      }
     },
 
     setThreadReasoningEffort: (threadId, provider, effort) => {
@@ -2489,7 +2628,7 @@ export function createThreadActions(
       return true;
     },
 
-    answerApproval: (threadId, requestId, approved) => {
+    answerApproval: (threadId, requestId, approved, opts) => {
       const interaction = get().interactionsByThread[threadId]?.find(
         (candidate) => candidate.requestId === requestId,
       );
@@ -2524,6 +2663,15 @@ export function createThreadActions(
         requestId,
         approved,
       });
+      void desktopCommands
+        .resolveApproval({
+          id: requestId,
+          decision: approved ? "approve" : "deny",
+          ...(opts?.rememberRule && approved ? { rememberRule: true } : {}),
+        })
+        .catch(() => {
+          // The queue only mirrors what the server is waiting on; the decision was sent.
+        });
       return true;
     },
 
@@ -2,7 +2,17 @@ import { defaultModelForProvider } from "@cowork/providers/catalog";
 import { sameWorkspacePath } from "@cowork/utils/workspacePath";
 
 import { captureProductEvent } from "../../lib/analytics";
-import { pickWorkspaceDirectory, stopWorkspaceServer } from "../../lib/desktopCommands";
+import {
+  duplicateWorkspace,
+  pickWorkspaceDirectory,
+  registerRemoteWorkspace,
+  resolveDroppedWorkspaceFolder,
+  restoreFromTrash,
+  setWorkspaceLaunchProfiles,
+  setWorkspacePinned,
+  setWorkspaceTags,
+  stopWorkspaceServer,
+} from "../../lib/desktopCommands";
 import { getDesktopPlatformInfo } from "../../lib/desktopPlatform";
 import { applyWorkspaceOrder, reorderSidebarItemsById } from "../../ui/sidebarHelpers";
 import {
@@ -21,6 +31,7 @@ import {
   markWorkspaceServerStale,
   nowIso,
   persistNow,
+  pushNotification,
   RUNTIME,
   requestWorkspaceSessions,
   type StoreGet,
@@ -35,7 +46,9 @@ import {
 } from "../store.helpers/operationIntent";
 import { isStandardChatThread } from "../threadFilters";
 import { getThreadSelectionIntent } from "../threadSelectionContext";
-import type { WorkspaceRecord } from "../types";
+import { isOneOffChatWorkspace, type WorkspaceRecord } from "../types";
+import { workspaceRootPaths } from "../workspaceRootPaths";
+import { normalizeWorkspaceTags } from "../workspaceTags";
 import { hydrateThreadSelection } from "./thread";
 
 export function createWorkspaceActions(
@@ -44,10 +57,21 @@ export function createWorkspaceActions(
 ): Pick<
   AppStoreActions,
   | "addWorkspace"
+  | "addDroppedWorkspaceFolders"
+  | "addRemoteWorkspace"
   | "removeWorkspace"
   | "selectWorkspace"
   | "reorderWorkspaces"
   | "setWorkspacesOrder"
+  | "setWorkspacePinned"
+  | "setWorkspaceTags"
+  | "setWorkspaceLaunchProfiles"
+  | "duplicateWorkspace"
+  | "restoreFromTrash"
+  | "addWorkspaceRoot"
+  | "removeWorkspaceRoot"
+  | "setWorkspaceTranscriptMirror"
+  | "chooseWorkspaceTranscriptMirrorFolder"
   | "restartWorkspaceServer"
   | "handleWorkspaceServerExited"
   | "setWorkspaceServerStartupProgress"
@@ -56,6 +80,25 @@ export function createWorkspaceActions(
     sendThread(get, threadId, (sessionId) => ({ type: "session_close", sessionId }));
   };
 
+  // Saves the renderer's state first so main edits the record the user sees,
+  // then adopts main's normalized copy; builds without the command update locally.
+  const updateWorkspaceOrganization = async (
+    workspaceId: string,
+    patch: Pick<WorkspaceRecord, "pinned" | "tags">,
+    command: () => Promise<WorkspaceRecord | null>,
+  ) => {
+    if (!get().workspaces.some((w) => w.id === workspaceId)) return;
+    await persistNow(get);
+    const updated = await command();
+    const next = updated ? { pinned: updated.pinned, tags: updated.tags } : patch;
+    set((s) => ({
+      workspaces: s.workspaces.map((w) => (w.id === workspaceId ? { ...w, ...next } : w)),
+    }));
+    if (!updated) {
+      await persistNow(get);
+    }
+  };
+
   const preferredThreadIdForWorkspace = (workspaceId: string): string | null => {
     const state = get();
     const currentThreadId = state.selectedThreadId;
@@ -121,6 +164,28 @@ export function createWorkspaceActions(
 
   const isWorkspaceLifecycleEnabled = () => get().desktopFeatureFlags.workspaceLifecycle !== false;
 
+  /** Persists new extra roots and restarts the sidecar so its tools pick them up. */
+  const applyWorkspaceRoots = async (workspaceId: string, additionalRoots: string[]) => {
+    set((s) => {
+      const workspaces = s.workspaces.map((w) =>
+        w.id === workspaceId
+          ? { ...w, additionalRoots: additionalRoots.length > 0 ? additionalRoots : undefined }
+          : w,
+      );
+      const explorerRoot = s.workspaceExplorerById[workspaceId]?.rootPath;
+      const workspace = workspaces.find((w) => w.id === workspaceId);
+      if (!explorerRoot || !workspace || workspaceRootPaths(workspace).includes(explorerRoot)) {
+        return { workspaces };
+      }
+      // The explorer was showing a root that no longer exists; reset it to the primary root.
+      const workspaceExplorerById = { ...s.workspaceExplorerById };
+      delete workspaceExplorerById[workspaceId];
+      return { workspaces, workspaceExplorerById };
+    });
+    await persistNow(get);
+    await get().restartWorkspaceServer(workspaceId);
+  };
+
   return {
     addWorkspace: async (options = {}) => {
       if (!isWorkspaceLifecycleEnabled()) return;
@@ -130,9 +195,9 @@ export function createWorkspaceActions(
         !options.intent || isCreationNavigationIntentCurrent(options.intent);
       RUNTIME.workspacePickerOpen = true;
 
-      let dir: string | null = null;
+      let dir: string | null = options.path ?? null;
       try {
-        dir = await pickWorkspaceDirectory();
+        dir ??= await pickWorkspaceDirectory();
       } finally {
         RUNTIME.workspacePickerOpen = false;
       }
@@ -202,6 +267,50 @@ export function createWorkspaceActions(
       void requestWorkspaceSessions(get, set, ws.id);
     },
 
+    addDroppedWorkspaceFolders: async (files) => {
+      if (!isWorkspaceLifecycleEnabled()) return;
+      for (const file of files) {
+        try {
+          const folder = await resolveDroppedWorkspaceFolder(file);
+          if (!folder) continue;
+          const existing = get().workspaces.find((w) => w.id === folder.existingWorkspaceId);
+          if (existing) {
+            await get().selectWorkspace(existing.id);
+          } else {
+            await get().addWorkspace({ path: folder.path });
+          }
+        } catch (error) {
+          set((s) => ({
+            notifications: pushNotification(s.notifications, {
+              id: makeId(),
+              ts: nowIso(),
+              kind: "error",
+              title: `Could not add ${file.name || "folder"}`,
+              detail: error instanceof Error ? error.message : String(error),
+            }),
+          }));
+        }
+      }
+    },
+
+    addRemoteWorkspace: async (url) => {
+      if (!isWorkspaceLifecycleEnabled()) return;
+      try {
+        const path = await registerRemoteWorkspace({ url: url.trim() });
+        await get().addWorkspace({ path });
+      } catch (error) {
+        set((s) => ({
+          notifications: pushNotification(s.notifications, {
+            id: makeId(),
+            ts: nowIso(),
+            kind: "error",
+            title: "Could not add remote workspace",
+            detail: error instanceof Error ? error.message : String(error),
+          }),
+        }));
+      }
+    },
+
     removeWorkspace: async (workspaceId: string) => {
       if (!isWorkspaceLifecycleEnabled()) return;
       bumpWorkspaceStartGeneration(workspaceId);
@@ -399,6 +508,148 @@ export function createWorkspaceActions(
       await persistNow(get);
     },
 
+    setWorkspacePinned: async (workspaceId, pinned) => {
+      await updateWorkspaceOrganization(workspaceId, { pinned: pinned ? true : undefined }, () =>
+        setWorkspacePinned({ workspaceId, pinned }),
+      );
+    },
+
+    setWorkspaceTags: async (workspaceId, tags) => {
+      await updateWorkspaceOrganization(workspaceId, { tags: normalizeWorkspaceTags(tags) }, () =>
+        setWorkspaceTags({ workspaceId, tags }),
+      );
+    },
+
+    setWorkspaceLaunchProfiles: async (workspaceId, profiles) => {
+      if (!get().workspaces.some((w) => w.id === workspaceId)) return;
+      await persistNow(get);
+      const updated = await setWorkspaceLaunchProfiles({ workspaceId, profiles });
+      set((s) => ({
+        workspaces: s.workspaces.map((w) =>
+          w.id === workspaceId ? { ...w, launchProfiles: updated.launchProfiles } : w,
+        ),
+      }));
+    },
+
+    duplicateWorkspace: async (workspaceId, options) => {
+      if (!get().workspaces.some((w) => w.id === workspaceId)) return null;
+      let copyId: string;
+      try {
+        // Save first so main copies the workspace and threads the renderer knows about.
+        await persistNow(get);
+        const { workspace, threads } = await duplicateWorkspace({ workspaceId, ...options });
+        set((s) => ({
+          workspaces: [...s.workspaces.filter((w) => w.id !== workspace.id), workspace],
+          threads: [...s.threads.filter((t) => !threads.some((c) => c.id === t.id)), ...threads],
+        }));
+        await persistNow(get);
+        copyId = workspace.id;
+      } catch (error) {
+        set((s) => ({
+          notifications: pushNotification(s.notifications, {
+            id: makeId(),
+            ts: nowIso(),
+            kind: "error",
+            title: "Unable to duplicate workspace",
+            detail: error instanceof Error ? error.message : String(error),
+          }),
+        }));
+        return null;
+      }
+      await get().selectWorkspace(copyId);
+      return copyId;
+    },
+
+    restoreFromTrash: async (entryId) => {
+      try {
+        const { workspaces, threads } = await restoreFromTrash({ entryId });
+        set((s) => ({
+          workspaces: [
+            ...s.workspaces.filter((w) => !workspaces.some((r) => r.id === w.id)),
+            ...workspaces,
+          ],
+          threads: [...s.threads.filter((t) => !threads.some((r) => r.id === t.id)), ...threads],
+        }));
+        await persistNow(get);
+        return true;
+      } catch (error) {
+        set((s) => ({
+          notifications: pushNotification(s.notifications, {
+            id: makeId(),
+            ts: nowIso(),
+            kind: "error",
+            title: "Unable to restore from trash",
+            detail: error instanceof Error ? error.message : String(error),
+          }),
+        }));
+        return false;
+      }
+    },
+
+    addWorkspaceRoot: async (workspaceId: string) => {
+      if (!isWorkspaceLifecycleEnabled()) return;
+      if (RUNTIME.workspacePickerOpen) return;
+      const workspace = get().workspaces.find((w) => w.id === workspaceId);
+      if (!workspace || isOneOffChatWorkspace(workspace)) return;
+      RUNTIME.workspacePickerOpen = true;
+
+      let dir: string | null = null;
+      try {
+        dir = await pickWorkspaceDirectory();
+      } finally {
+        RUNTIME.workspacePickerOpen = false;
+      }
+      const current = get().workspaces.find((w) => w.id === workspaceId);
+      if (!dir || !current || workspaceRootPaths(current).includes(dir)) return;
+
+      await applyWorkspaceRoots(workspaceId, [...(current.additionalRoots ?? []), dir]);
+    },
+
+    removeWorkspaceRoot: async (workspaceId: string, rootPath: string) => {
+      if (!isWorkspaceLifecycleEnabled()) return;
+      const workspace = get().workspaces.find((w) => w.id === workspaceId);
+      if (!workspace?.additionalRoots?.includes(rootPath)) return;
+
+      await applyWorkspaceRoots(
+        workspaceId,
+        workspace.additionalRoots.filter((root) => root !== rootPath),
+      );
+    },
+
+    setWorkspaceTranscriptMirror: async (workspaceId, mirror) => {
+      const workspace = get().workspaces.find((w) => w.id === workspaceId);
+      if (!workspace || isOneOffChatWorkspace(workspace)) return;
+
+      set((s) => ({
+        workspaces: s.workspaces.map((w) =>
+          w.id === workspaceId ? { ...w, transcriptMirror: mirror ?? undefined } : w,
+        ),
+      }));
+      await persistNow(get);
+    },
+
+    chooseWorkspaceTranscriptMirrorFolder: async (workspaceId: string) => {
+      if (RUNTIME.workspacePickerOpen) return;
+      const workspace = get().workspaces.find((w) => w.id === workspaceId);
+      if (!workspace || isOneOffChatWorkspace(workspace)) return;
+      RUNTIME.workspacePickerOpen = true;
+
+      // Picking through the native dialog is what approves a folder outside the workspace.
+      let dir: string | null = null;
+      try {
+        dir = await pickWorkspaceDirectory();
+      } finally {
+        RUNTIME.workspacePickerOpen = false;
+      }
+      if (!dir) return;
+
+      const format = get().workspaces.find((w) => w.id === workspaceId)?.transcriptMirror?.format;
+      await get().setWorkspaceTranscriptMirror(workspaceId, {
+        directory: dir,
+        format: format ?? "markdown",
+      });
+    },
+
     setWorkspaceServerStartupProgress: ({ workspaceId, progress }) => {
       set((state) => {
         const runtime = state.workspaceRuntimeById[workspaceId];
@@ -481,5 +732,15 @@ export function createWorkspaceActions(
         void requestWorkspaceSessions(get, set, workspaceId);
       })();
     },
+
+    // Unlike an exit, a suspension is left alone: the next action that needs
+    // the server starts it again.
+    handleServerSuspended: (event) => {
+      if (!isWorkspaceLifecycleEnabled()) return;
+      const { workspaceId } = event;
+      if (!get().workspaces.some((workspace) => workspace.id === workspaceId)) return;
+      bumpWorkspaceStartGeneration(workspaceId);
+      markWorkspaceServerStale(get, set, workspaceId);
+    },
   };
 }
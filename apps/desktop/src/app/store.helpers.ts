@@ -31,15 +31,22 @@ import type {
 import type { TaskCreationInput } from "../../../../src/shared/tasks";
 import {
   createDefaultUpdaterState,
+  type DuplicateWorkspaceInput,
   type MobileRelayBridgeState,
   type MobileRelayForgetTrustedPhoneInput,
   type MobileRelayUpdateTrustedPhonePermissionsInput,
+  type ServerSuspendedEvent,
   type UpdaterState,
   type WorkspaceServerExitedEvent,
   type WorkspaceServerStartupProgress,
 } from "../lib/desktopApi";
 import {
+  type ConfirmDestructiveCommand,
+  getServerStartupDiagnostics,
+  getWorkspaceProxyUrl,
   getWorkspaceServerStatus,
+  isNetworkUnavailableError,
+  isServerStartupFailedError,
   startWorkspaceServer,
   stopWorkspaceServer,
 } from "../lib/desktopCommands";
@@ -89,8 +96,10 @@ import { operationError, operationKey, runAcknowledgedOperation } from "./store.
 import {
   persist,
   persistNow,
+  setForeignStateRecordsHandler,
   syncDesktopStateCache,
   syncDesktopStateCacheNow,
+  trackPersistedStateGeneration,
 } from "./store.helpers/persistence";
 import {
   beginThreadSelectionRequest,
@@ -148,8 +157,10 @@ import type {
   ViewId,
   WorkspaceDefaultsPatch,
   WorkspaceExplorerState,
+  WorkspaceLaunchProfile,
   WorkspaceRecord,
   WorkspaceRuntime,
+  WorkspaceTranscriptMirror,
 } from "./types";
 
 function nowIso() {
@@ -341,7 +352,12 @@ export type AppStoreState = {
   closeSettings: () => void;
   setSettingsPage: (page: SettingsPageId) => void;
 
-  addWorkspace: (options?: { intent?: CreationOperationIntent }) => Promise<void>;
+  /** `path` skips the folder picker; it must already be an approved workspace root. */
+  addWorkspace: (options?: { intent?: CreationOperationIntent; path?: string }) => Promise<void>;
+  /** Adds (or selects, when already registered) each folder dropped onto the window. */
+  addDroppedWorkspaceFolders: (files: File[]) => Promise<void>;
+  /** Checks an `ssh://user@host/path` workspace over SSH, then adds (or selects) it. */
+  addRemoteWorkspace: (url: string) => Promise<void>;
   removeWorkspace: (workspaceId: string) => Promise<void>;
   selectWorkspace: (
     workspaceId: string,
@@ -349,6 +365,31 @@ export type AppStoreState = {
   ) => Promise<void>;
   reorderWorkspaces: (sourceWorkspaceId: string, targetWorkspaceId: string) => Promise<void>;
   setWorkspacesOrder: (orderedIds: string[]) => Promise<void>;
+  setWorkspacePinned: (workspaceId: string, pinned: boolean) => Promise<void>;
+  /** Replaces the workspace's tags; an empty list clears them. */
+  setWorkspaceTags: (workspaceId: string, tags: string[]) => Promise<void>;
+  /** Replaces the workspace's launch profiles; rejects profiles main refuses to save. */
+  setWorkspaceLaunchProfiles: (
+    workspaceId: string,
+    profiles: WorkspaceLaunchProfile[],
+  ) => Promise<void>;
+  /** Records a copy of the workspace and opens it; resolves to its id, or null on failure. */
+  duplicateWorkspace: (
+    workspaceId: string,
+    options: Omit<DuplicateWorkspaceInput, "workspaceId">,
+  ) => Promise<string | null>;
+  /** Brings back a trashed workspace or thread; resolves false and notifies on failure. */
+  restoreFromTrash: (entryId: string) => Promise<boolean>;
+  /** Picks a folder and adds it as an extra root of a project workspace. */
+  addWorkspaceRoot: (workspaceId: string) => Promise<void>;
+  removeWorkspaceRoot: (workspaceId: string, rootPath: string) => Promise<void>;
+  /** Pass null to stop mirroring the workspace's transcripts. */
+  setWorkspaceTranscriptMirror: (
+    workspaceId: string,
+    mirror: WorkspaceTranscriptMirror | null,
+  ) => Promise<void>;
+  /** Picks a folder outside the workspace to mirror transcripts into. */
+  chooseWorkspaceTranscriptMirrorFolder: (workspaceId: string) => Promise<void>;
 
   newThread: (
     opts?: {
@@ -379,10 +420,28 @@ export type AppStoreState = {
   ) => Promise<CreationPreflightResult>;
   repairCreationReadiness: (action: CreationRepairAction, workspaceId?: string) => Promise<void>;
   releasePreparedQuickChatWorkspace: () => Promise<void>;
-  removeThread: (threadId: string) => Promise<void>;
+  /** Keeps the thread's transcripts on disk unless `confirmTranscriptDeletion` accepts. */
+  removeThread: (
+    threadId: string,
+    confirmTranscriptDeletion?: ConfirmDestructiveCommand,
+  ) => Promise<void>;
   archiveThread: (threadId: string) => Promise<void>;
   restoreThread: (threadId: string) => Promise<void>;
-  deleteThreadHistory: (threadId: string) => Promise<void>;
+  /** Branches the thread into a new one and selects it; resolves the new id. */
+  forkThread: (threadId: string) => Promise<string | null>;
+  /**
+   * Merges the source thread's history into the target and hides or trashes
+   * the source; resolves the number of events moved.
+   */
+  mergeThreads: (
+    sourceThreadId: string,
+    targetThreadId: string,
+    options?: { trashSource?: boolean },
+  ) => Promise<number | null>;
+  /** Retitles the thread from its first message unless the user named it. */
+  suggestThreadTitle: (threadId: string) => Promise<void>;
+  /** `confirm` is the user's answer to deleting the thread's local transcripts. */
+  deleteThreadHistory: (threadId: string, confirm: ConfirmDestructiveCommand) => Promise<void>;
   selectThread: (threadId: string, options?: AbortableActionOptions) => Promise<void>;
   reconnectThread: (
     threadId: string,
@@ -426,6 +485,12 @@ export type AppStoreState = {
   cancelThread: (threadId: string, opts?: { includeSubagents?: boolean }) => boolean;
   clearThreadUsageHardCap: (threadId: string) => void;
   setThreadModel: (threadId: string, provider: ProviderName, model: string) => void;
+  /** Pins a thread to `provider` and `model`, or clears the pin when either is null. */
+  updateThreadRouting: (
+    threadId: string,
+    provider: ProviderName | null,
+    model: string | null,
+  ) => void;
   setThreadReasoningEffort: (
     threadId: string,
     provider: ProviderName,
@@ -704,6 +769,7 @@ export type AppStoreState = {
   ) => Promise<OperationResult>;
   restartWorkspaceServer: (workspaceId: string) => Promise<void>;
   handleWorkspaceServerExited: (event: WorkspaceServerExitedEvent) => void;
+  handleServerSuspended: (event: ServerSuspendedEvent) => void;
   setWorkspaceServerStartupProgress: (event: WorkspaceServerStartupProgress) => void;
   requestWorkspaceMcpServers: (workspaceId: string) => Promise<void>;
   upsertWorkspaceMcpServer: (
@@ -933,7 +999,12 @@ export type AppStoreState = {
   loadAllThreadUsage: () => Promise<void>;
 
   answerAsk: (threadId: string, requestId: string, answer: string) => boolean;
-  answerApproval: (threadId: string, requestId: string, approved: boolean) => boolean;
+  answerApproval: (
+    threadId: string,
+    requestId: string,
+    approved: boolean,
+    opts?: { rememberRule?: boolean },
+  ) => boolean;
   dismissPrompt: () => void;
   retryInteractionResponse: (threadId: string, requestId: string) => boolean;
 
@@ -951,6 +1022,8 @@ export type AppStoreState = {
   refreshWorkspaceFiles: (workspaceId: string) => Promise<void>;
   navigateWorkspaceFiles: (workspaceId: string, path: string) => Promise<void>;
   navigateWorkspaceFilesUp: (workspaceId: string) => Promise<void>;
+  /** Switches the explorer to another root of a multi-root workspace. */
+  setWorkspaceExplorerRoot: (workspaceId: string, rootPath: string) => Promise<void>;
   selectWorkspaceFile: (workspaceId: string, path: string | null) => void;
   openWorkspaceFile: (workspaceId: string, path: string, isDirectory: boolean) => Promise<void>;
   revealWorkspaceFile: (path: string) => Promise<void>;
@@ -1124,6 +1197,16 @@ function markWorkspaceServerStale(
   });
 }
 
+/**
+ * URL the renderer's sockets use for a workspace: main's loopback proxy, which
+ * authenticates to the sidecar and rides out its restarts, or the sidecar
+ * itself in builds without the proxy.
+ */
+async function resolveWorkspaceSocketUrl(workspaceId: string, serverUrl: string): Promise<string> {
+  const proxy = await getWorkspaceProxyUrl({ workspaceId }).catch(() => null);
+  return proxy?.url ?? serverUrl;
+}
+
 function syncWorkspaceServerRunningUrl(
   get: StoreGet,
   set: StoreSet,
@@ -1259,7 +1342,11 @@ async function ensureServerRunning(
     );
     if (!isCurrent()) return;
     if (status.running) {
-      syncWorkspaceServerRunningUrl(get, set, workspaceId, status.url);
+      const socketUrl = status.url
+        ? await waitForOperation(resolveWorkspaceSocketUrl(workspaceId, status.url), options.signal)
+        : null;
+      if (!isCurrent()) return;
+      syncWorkspaceServerRunningUrl(get, set, workspaceId, socketUrl);
       if (!isCurrent()) return;
       scheduleWorkspaceServerRestartBackoffReset(workspaceId);
       return;
@@ -1307,6 +1394,7 @@ async function ensureServerRunning(
         starting: true,
         startupProgress: null,
         error: null,
+        startupDiagnostics: null,
       },
     },
   }));
@@ -1314,15 +1402,19 @@ async function ensureServerRunning(
   const startPromise = (async () => {
     try {
       if (!isCurrent()) return;
+      const yoloToken = RUNTIME.pendingYoloTokens.get(workspaceId);
+      RUNTIME.pendingYoloTokens.delete(workspaceId);
       const res = await startWorkspaceServer({
         workspaceId,
         workspacePath: ws.path,
         yolo: ws.yolo,
+        ...(ws.yolo && yoloToken ? { yoloToken } : {}),
         forceRestart,
         preserveMobileRelay: true,
         featureFlags: get().desktopFeatureFlags,
         privacyTelemetrySettings: get().privacyTelemetrySettings,
       });
+      const socketUrl = await resolveWorkspaceSocketUrl(workspaceId, res.url);
       if (!isCurrent() || getWorkspaceStartGeneration(workspaceId) !== generation) {
         return;
       }
@@ -1331,7 +1423,7 @@ async function ensureServerRunning(
           ...s.workspaceRuntimeById,
           [workspaceId]: {
             ...s.workspaceRuntimeById[workspaceId],
-            serverUrl: res.url,
+            serverUrl: socketUrl,
             starting: false,
             startupProgress: null,
             error: null,
@@ -1345,12 +1437,20 @@ async function ensureServerRunning(
         return;
       }
       const message = err instanceof Error ? err.message : String(err);
+      const startupDiagnostics = isServerStartupFailedError(err)
+        ? await getServerStartupDiagnostics({ workspaceId }).catch(() => null)
+        : null;
+      if (!isCurrent() || getWorkspaceStartGeneration(workspaceId) !== generation) {
+        return;
+      }
       set((s) => ({
         notifications: pushNotification(s.notifications, {
           id: makeId(),
           ts: nowIso(),
           kind: "error",
-          title: "Workspace server unavailable",
+          title: isNetworkUnavailableError(err)
+            ? "Workspace server unavailable while offline"
+            : "Workspace server unavailable",
           detail: message,
         }),
         workspaceRuntimeById: {
@@ -1360,6 +1460,7 @@ async function ensureServerRunning(
             starting: false,
             startupProgress: null,
             error: message,
+            startupDiagnostics,
           },
         },
       }));
@@ -1424,11 +1525,13 @@ export {
   runAcknowledgedOperation,
   sendThread,
   sendUserMessageToThread,
+  setForeignStateRecordsHandler,
   shiftPendingThreadAttachments,
   shiftPendingThreadMessage,
   shiftPendingThreadReferences,
   syncDesktopStateCache,
   syncDesktopStateCacheNow,
+  trackPersistedStateGeneration,
   truncateTitle,
   waitForControlSession,
   waitForWorkspaceServerRestartBackoff,
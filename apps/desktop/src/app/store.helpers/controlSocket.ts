@@ -170,6 +170,9 @@ export function createControlSocketHelpers(
         draft: false,
         archived: existing?.archived ?? false,
         archivedAt: existing?.archivedAt,
+        ...(existing?.mergedIntoThreadId
+          ? { mergedIntoThreadId: existing.mergedIntoThreadId, mergedAt: existing.mergedAt }
+          : {}),
         legacyTranscriptId,
         ...(existing?.taskId ? { taskId: existing.taskId } : {}),
         ...(existing?.taskThreadId ? { taskThreadId: existing.taskThreadId } : {}),
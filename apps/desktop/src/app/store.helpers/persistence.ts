@@ -15,6 +15,8 @@ import {
   normalizeCloudSyncSettings,
   normalizePrivacyTelemetrySettings,
   type PersistedState,
+  type ThreadRecord,
+  type WorkspaceRecord,
 } from "../types";
 import { getEffectiveThreadLastEventSeq, RUNTIME } from "./runtimeState";
 
@@ -23,6 +25,11 @@ const DESKTOP_CACHE_DEBOUNCE_MS = 120;
 
 let _persistTimer: ReturnType<typeof setTimeout> | null = null;
 let _desktopCacheTimer: ReturnType<typeof setTimeout> | null = null;
+/** Generation of state.json the store last loaded or saved; sent so stale saves are caught. */
+let _stateGeneration: number | undefined;
+let _foreignRecordsHandler:
+  | ((records: { workspaces: WorkspaceRecord[]; threads: ThreadRecord[] }) => void)
+  | null = null;
 
 /**
  * Filters out draft threads from persistence.
@@ -149,12 +156,46 @@ export function syncDesktopStateCacheNow(get: () => AppStoreState) {
   return syncDesktopStateCacheState(get());
 }
 
+export function trackPersistedStateGeneration(generation: number | undefined) {
+  _stateGeneration = generation;
+}
+
+/** Receives records another app instance added to state.json so they can be merged. */
+export function setForeignStateRecordsHandler(handler: typeof _foreignRecordsHandler) {
+  _foreignRecordsHandler = handler;
+}
+
+async function saveStateWithGeneration(state: PersistedState) {
+  const result = await saveState(
+    _stateGeneration === undefined ? state : { ...state, generation: _stateGeneration },
+  );
+  if (result?.status === "saved") {
+    _stateGeneration = result.generation;
+  } else if (result?.status === "mergeRequired") {
+    _stateGeneration = result.currentGeneration;
+  }
+  return result;
+}
+
+/**
+ * Saves the store, and when another process wrote state.json since our last
+ * load or save, merges the records it added and saves once more. Fields on
+ * records we already have keep the store's values.
+ */
+async function saveStoreState(get: () => AppStoreState) {
+  const result = await saveStateWithGeneration(syncDesktopStateCacheNow(get));
+  if (result?.status !== "mergeRequired") {
+    return;
+  }
+  _foreignRecordsHandler?.({ workspaces: result.workspaces, threads: result.threads });
+  await saveStateWithGeneration(syncDesktopStateCacheNow(get));
+}
+
 export function persist(get: () => AppStoreState) {
   if (_persistTimer) clearTimeout(_persistTimer);
   _persistTimer = setTimeout(() => {
     _persistTimer = null;
-    const state = syncDesktopStateCacheNow(get);
-    void saveState(state);
+    void saveStoreState(get);
   }, PERSIST_DEBOUNCE_MS);
 }
 
@@ -163,8 +204,7 @@ export async function persistNow(get: () => AppStoreState) {
     clearTimeout(_persistTimer);
     _persistTimer = null;
   }
-  const state = syncDesktopStateCacheNow(get);
-  await saveState(state);
+  await saveStoreState(get);
 }
 
 export const __internal = {
@@ -71,6 +71,8 @@ export type RuntimeMaps = {
   pendingWorkspaceDefaultApplyByThread: Map<string, PendingWorkspaceDefaultApply>;
   workspaceStartPromises: Map<string, { generation: number; promise: Promise<void> }>;
   workspaceStartGenerations: Map<string, number>;
+  /** Yolo confirmation tokens waiting to be redeemed by the workspace's next server start. */
+  pendingYoloTokens: Map<string, string>;
   workspaceServerRestartAttempts: Map<string, number>;
   workspaceServerRestartStabilityTimers: Map<string, ReturnType<typeof setTimeout>>;
   modelStreamByThread: Map<string, ThreadModelStreamRuntime>;
@@ -101,6 +103,7 @@ export const RUNTIME: RuntimeMaps = {
   pendingWorkspaceDefaultApplyByThread: new Map(),
   workspaceStartPromises: new Map(),
   workspaceStartGenerations: new Map(),
+  pendingYoloTokens: new Map(),
   workspaceServerRestartAttempts: new Map(),
   workspaceServerRestartStabilityTimers: new Map(),
   modelStreamByThread: new Map(),
@@ -317,6 +320,7 @@ export function defaultWorkspaceRuntime(): WorkspaceRuntime {
     starting: false,
     startupProgress: null,
     error: null,
+    startupDiagnostics: null,
     controlSessionId: null,
     controlConfig: null,
     controlSessionConfig: null,
@@ -462,9 +466,15 @@ export function bumpWorkspaceStartGeneration(workspaceId: string): number {
   return next;
 }
 
+/** Hands a yolo confirmation token to the workspace's next server start. */
+export function setPendingYoloToken(workspaceId: string, token: string): void {
+  RUNTIME.pendingYoloTokens.set(workspaceId, token);
+}
+
 export function clearWorkspaceStartState(workspaceId: string): void {
   RUNTIME.workspaceStartPromises.delete(workspaceId);
   RUNTIME.workspaceStartGenerations.delete(workspaceId);
+  RUNTIME.pendingYoloTokens.delete(workspaceId);
   clearWorkspaceServerRestartBackoffState(workspaceId);
 }
 
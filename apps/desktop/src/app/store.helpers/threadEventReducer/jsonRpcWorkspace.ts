@@ -1,3 +1,4 @@
+import { recordPendingApproval } from "../../../lib/desktopCommands";
 import type { SessionEvent } from "../../../lib/wsProtocol";
 import { composerDraftKeyForThread } from "../../composerDrafts";
 import type { StoreGet, StoreSet } from "../../store.helpers";
@@ -115,16 +116,39 @@ export function createJsonRpcWorkspaceModule(
             requestParams.category === "filesystem" || requestParams.category === "network"
               ? requestParams.category
               : undefined;
+          const requestId = String(message.id);
+          const command = String(requestParams.command ?? "");
+          const dangerous = requestParams.dangerous === true;
+          const reason = requestParams.reason ?? "requires_manual_review";
+          const detail =
+            typeof requestParams.detail === "string" ? requestParams.detail : undefined;
           handleThreadEvent(get, set, threadId, {
             type: "approval",
             sessionId,
-            requestId: String(message.id),
-            command: String(requestParams.command ?? ""),
-            dangerous: requestParams.dangerous === true,
-            reasonCode: requestParams.reason ?? "requires_manual_review",
-            ...(typeof requestParams.detail === "string" ? { detail: requestParams.detail } : {}),
+            requestId,
+            command,
+            dangerous,
+            reasonCode: reason,
+            ...(detail !== undefined ? { detail } : {}),
             ...(category ? { category } : {}),
           } as SessionEvent);
+          // Queued in the main process; a remembered allow-rule answers it right away.
+          void recordPendingApproval({
+            id: requestId,
+            workspaceId,
+            threadId,
+            command,
+            dangerous,
+            reason,
+            ...(detail !== undefined ? { detail } : {}),
+            ...(category ? { category } : {}),
+          })
+            .then((result) => {
+              if (result.autoApproved && !isWorkspaceDisposed(workspaceId)) {
+                get().answerApproval(threadId, requestId, true);
+              }
+            })
+            .catch(() => {});
         }
         return;
       }
@@ -4,6 +4,7 @@ import { buildAttachmentSignature, buildUserInputDisplayText } from "../../attac
 import { type ComposerDraftRevision, composerDraftKeyForThread } from "../../composerDrafts";
 import { findComposerSubmissionById } from "../../composerSubmission";
 import type { StoreGet, StoreSet } from "../../store.helpers";
+import { threadRoutingDiffers } from "../../threadRouting";
 import type { FeedItem, ThreadBusyPolicy } from "../../types";
 import {
   ensureWorkspaceJsonRpcSocket,
@@ -558,20 +559,70 @@ export function createMessagingModule(
       clientMessageId,
     });
 
-    dispatchJsonRpcTurnStart(
+    const sessionId = rt.sessionId;
+    applyThreadRoutingBeforeTurn(get, set, threadId, () =>
+      dispatchJsonRpcTurnStart(
+        get,
+        set,
+        workspaceId,
+        sessionId,
+        trimmed,
+        threadId,
+        clientMessageId,
+        attachments,
+        references,
+        draftSubmission,
+        retryToolItemIds,
+      ),
+    );
+    return true;
+  }
+
+  /**
+   * Moves the session onto the thread's pinned provider and model when it is
+   * running something else, such as after a resume or a workspace default
+   * change, then starts the turn once the server has answered.
+   */
+  function applyThreadRoutingBeforeTurn(
+    get: StoreGet,
+    set: StoreSet,
+    threadId: string,
+    startTurn: () => void,
+  ): void {
+    const routing = get().threads.find((thread) => thread.id === threadId)?.routing;
+    if (!threadRoutingDiffers(routing, get().threadRuntimeById[threadId]?.config)) {
+      startTurn();
+      return;
+    }
+    const sent = sendThread(
       get,
-      set,
-      workspaceId,
-      rt.sessionId,
-      trimmed,
       threadId,
-      clientMessageId,
-      attachments,
-      references,
-      draftSubmission,
-      retryToolItemIds,
+      (sessionId) => ({
+        type: "set_model",
+        sessionId,
+        provider: routing.provider,
+        model: routing.model,
+      }),
+      {
+        onSettled: (error) => {
+          if (error) {
+            set((s) => ({
+              notifications: ctx.deps.pushNotification(s.notifications, {
+                id: ctx.deps.makeId(),
+                ts: ctx.deps.nowIso(),
+                kind: "error",
+                title: "Thread model not applied",
+                detail: `Could not switch to ${routing.model}; sending with the current model.`,
+              }),
+            }));
+          }
+          startTurn();
+        },
+      },
     );
-    return true;
+    if (!sent) {
+      startTurn();
+    }
   }
 
   function flushOneQueuedThreadMessage(get: StoreGet, set: StoreSet, threadId: string) {
@@ -3,10 +3,17 @@ export function isTaskOwnedThread(thread: { taskId?: string | null }): boolean {
 }
 
 export function isStandardChatThread(
-  thread: { taskId?: string | null; draft?: boolean; archived?: boolean },
+  thread: {
+    taskId?: string | null;
+    draft?: boolean;
+    archived?: boolean;
+    mergedIntoThreadId?: string;
+  },
   options: { includeDrafts?: boolean; includeArchived?: boolean } = {},
 ): boolean {
   if (isTaskOwnedThread(thread)) return false;
+  // A merged thread's history now lives in the thread it was merged into.
+  if (thread.mergedIntoThreadId) return false;
   if (!options.includeDrafts && thread.draft) return false;
   if (!options.includeArchived && thread.archived) return false;
   return true;
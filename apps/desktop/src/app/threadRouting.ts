@@ -0,0 +1,24 @@
+import { isProviderNameString } from "../lib/providerDisplayNames";
+import type { ThreadRouting } from "./types";
+
+/** Drops routing without a known provider or a model. */
+export function normalizeThreadRouting(value: unknown): ThreadRouting | undefined {
+  if (!value || typeof value !== "object" || Array.isArray(value)) return undefined;
+  const { provider, model, updatedAt } = value as Record<string, unknown>;
+  if (typeof provider !== "string" || !isProviderNameString(provider)) return undefined;
+  if (typeof model !== "string" || !model.trim()) return undefined;
+  return {
+    provider,
+    model: model.trim(),
+    updatedAt: typeof updatedAt === "string" ? updatedAt : new Date(0).toISOString(),
+  };
+}
+
+/** True when a session configured with `config` is not running the pinned provider and model. */
+export function threadRoutingDiffers(
+  routing: ThreadRouting | undefined,
+  config: { provider?: unknown; model?: unknown } | null | undefined,
+): routing is ThreadRouting {
+  if (!routing) return false;
+  return config?.provider !== routing.provider || config?.model !== routing.model;
+}
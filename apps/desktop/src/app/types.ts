@@ -25,6 +25,7 @@ import {
   type PersistedPrivacyTelemetrySettings,
   type PrivacyTelemetrySettings,
 } from "../../../../src/telemetry/config";
+import type { WorkspaceServerStartupDiagnostics } from "../lib/desktopApi";
 import type { NewChatLandingTarget } from "../lib/newChatLanding";
 import { normalizeQuickChatShortcutAccelerator } from "../lib/quickChatShortcut";
 import type {
@@ -96,10 +97,77 @@ export function isOneOffChatWorkspace(
   return workspace?.workspaceKind === "oneOffChat";
 }
 
+export type WorkspaceResourceLimits = {
+  /** Resident memory ceiling for the workspace server, in MiB. */
+  maxMemoryMb?: number;
+  /** Unix nice value (0-19) applied at spawn; Windows maps it to a priority class. */
+  niceness?: number;
+  /** The workspace server is stopped once it has run this long. */
+  maxLifetimeMinutes?: number;
+  /** How long to wait for the workspace server to report ready; overrides the app default. */
+  startupTimeoutSeconds?: number;
+};
+
+/** Overrides the app's idle server settings for one workspace's server. */
+export type WorkspaceIdlePolicy = {
+  /** `never` keeps the server up; `onBattery` only stops it while off AC power. */
+  suspend: "never" | "onBattery" | "always";
+  /** Minutes without transcript traffic before the server is stopped. */
+  timeoutMinutes: number;
+};
+
+/** Runs the workspace server in a Docker container with the workspace mounted. */
+export type WorkspaceContainerLaunch = {
+  /** Image to run; when unset, the `image` from the workspace's devcontainer.json is used. */
+  image?: string;
+};
+
+/** Named extra sidecar flags and environment for starting a workspace server. */
+export type WorkspaceLaunchProfile = {
+  name: string;
+  /** Flags from `LAUNCH_PROFILE_FLAGS`, each followed by its value where it takes one. */
+  args: string[];
+  env: Record<string, string>;
+};
+
+/** One path on a workspace's allow or deny list. */
+export type WorkspacePathRule = {
+  /** Absolute path; it must sit inside a workspace root unless `external` is set. */
+  path: string;
+  /** Marks a path deliberately outside every workspace root. */
+  external?: boolean;
+};
+
+/** Paths the agent's file tools may (allow) or may not (deny) touch; see `workspacePathPolicy`. */
+export type WorkspacePathPolicy = {
+  allow: WorkspacePathRule[];
+  deny: WorkspacePathRule[];
+};
+
+/** How a workspace's path policy treats one path, with the entry that decided it. */
+export type PathPolicyPreview = {
+  path: string;
+  decision: "allow" | "deny";
+  /** The policy entry that decided, or null when the workspace roots alone did. */
+  rule: { list: "allow" | "deny"; path: string } | null;
+  reason: string;
+};
+
+export type TranscriptMirrorFormat = "jsonl" | "markdown";
+
+export type WorkspaceTranscriptMirror = {
+  /** Relative paths resolve inside the workspace; absolute paths must be picked by the user. */
+  directory: string;
+  format: TranscriptMirrorFormat;
+};
+
 export type WorkspaceRecord = {
   id: string;
   name: string;
+  /** Primary root; the workspace server runs here. */
   path: string;
+  /** Further folders (e.g. a backend repo next to the frontend) the workspace spans. */
+  additionalRoots?: string[];
   workspaceKind?: WorkspaceKind;
   createdAt: string;
   lastOpenedAt: string;
@@ -122,7 +190,21 @@ export type WorkspaceRecord = {
   defaultSkillImprovementModel?: string;
   defaultSkillImprovementScope?: "user" | "all";
   defaultSkillImprovementExcludedSkills?: string[];
+  resourceLimits?: WorkspaceResourceLimits;
+  idlePolicy?: WorkspaceIdlePolicy;
+  /** Set to start the workspace server in a container instead of as a local process. */
+  container?: WorkspaceContainerLaunch;
+  transcriptMirror?: WorkspaceTranscriptMirror;
   yolo: boolean;
+  /** Snapshot the repository to `refs/cowork-checkpoints` before a YOLO server starts. */
+  yoloCheckpoints?: boolean;
+  pinned?: boolean;
+  /** Position in the workspace list; persistence rewrites it from the list order on every load. */
+  sortOrder?: number;
+  /** Free-form labels for grouping projects; see `normalizeWorkspaceTags`. */
+  tags?: string[];
+  launchProfiles?: WorkspaceLaunchProfile[];
+  pathPolicy?: WorkspacePathPolicy;
 };
 
 export type WorkspaceDefaultsPatch = Partial<Omit<WorkspaceRecord, "userProfile">> & {
@@ -150,9 +232,20 @@ export type ThreadRecord = {
   draft?: boolean;
   archived?: boolean;
   archivedAt?: string;
+  /** Set once the thread's transcript was merged into another thread; it is hidden from lists. */
+  mergedIntoThreadId?: string;
+  mergedAt?: string;
   taskId?: string;
   taskThreadId?: string;
   reasoningEffort?: ReasoningEffortValue;
+  /** Provider and model pinned for this thread, which override the workspace defaults. */
+  routing?: ThreadRouting;
+};
+
+export type ThreadRouting = {
+  provider: ProviderName;
+  model: string;
+  updatedAt: string;
 };
 
 export type ThreadPendingSteer = {
@@ -384,6 +477,8 @@ export const DEFAULT_RESEARCH_SETTINGS: ResearchSettingsState = {
 
 export type PersistedState = {
   version: number;
+  /** Bumped on every write to state.json; saves based on an older generation are rejected. */
+  generation?: number;
   workspaces: WorkspaceRecord[];
   threads: ThreadRecord[];
   developerMode?: boolean;
@@ -408,6 +503,8 @@ export type TranscriptEvent = {
   threadId: string;
   direction: TranscriptDirection;
   payload: unknown;
+  /** Set when the payload did not match the schema for its `type` at append time. */
+  validationIssues?: string[];
 };
 
 export type ToolFeedState =
@@ -501,6 +598,8 @@ export type WorkspaceRuntime = {
   starting: boolean;
   startupProgress: CoworkRuntimeBootstrapProgress | null;
   error: string | null;
+  /** Details of the last failed server start, kept until a start succeeds. */
+  startupDiagnostics: WorkspaceServerStartupDiagnostics | null;
   controlSessionId: string | null;
   controlConfig: ConfigSubset | null;
   controlSessionConfig: SessionConfigSubset | null;
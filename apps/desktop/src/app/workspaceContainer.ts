@@ -0,0 +1,23 @@
+import type { WorkspaceContainerLaunch } from "./types";
+
+export const MAX_CONTAINER_IMAGE_LENGTH = 256;
+
+// Registry host, path, and an optional tag or digest; never starts with "-".
+const CONTAINER_IMAGE_PATTERN = /^[A-Za-z0-9][A-Za-z0-9._\-/:@]*$/;
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+export function isValidContainerImage(image: string): boolean {
+  return image.length <= MAX_CONTAINER_IMAGE_LENGTH && CONTAINER_IMAGE_PATTERN.test(image);
+}
+
+/** Keeps a valid image name; an empty record still opts the workspace into containers. */
+export function normalizeWorkspaceContainerLaunch(
+  value: unknown,
+): WorkspaceContainerLaunch | undefined {
+  if (!isRecord(value)) return undefined;
+  const image = typeof value.image === "string" ? value.image.trim() : "";
+  return image && isValidContainerImage(image) ? { image } : {};
+}
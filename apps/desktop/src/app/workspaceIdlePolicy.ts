@@ -0,0 +1,18 @@
+import type { WorkspaceIdlePolicy } from "./types";
+
+export const MAX_WORKSPACE_IDLE_TIMEOUT_MINUTES = 24 * 60;
+
+/** Drops malformed policies and clamps the timeout to 1 minute through a day. */
+export function normalizeWorkspaceIdlePolicy(value: unknown): WorkspaceIdlePolicy | undefined {
+  if (!value || typeof value !== "object" || Array.isArray(value)) return undefined;
+  const { suspend, timeoutMinutes } = value as Record<string, unknown>;
+  if (suspend !== "never" && suspend !== "onBattery" && suspend !== "always") return undefined;
+  if (typeof timeoutMinutes !== "number" || !Number.isFinite(timeoutMinutes)) return undefined;
+  return {
+    suspend,
+    timeoutMinutes: Math.min(
+      MAX_WORKSPACE_IDLE_TIMEOUT_MINUTES,
+      Math.max(1, Math.floor(timeoutMinutes)),
+    ),
+  };
+}
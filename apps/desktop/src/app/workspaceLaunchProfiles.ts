@@ -0,0 +1,123 @@
+import type { WorkspaceLaunchProfile } from "./types";
+
+export const MAX_WORKSPACE_LAUNCH_PROFILES = 16;
+export const MAX_LAUNCH_PROFILE_NAME_LENGTH = 60;
+export const MAX_LAUNCH_PROFILE_ARGS = 32;
+export const MAX_LAUNCH_PROFILE_ENV_VARS = 32;
+export const MAX_LAUNCH_PROFILE_VALUE_LENGTH = 4096;
+
+/**
+ * Sidecar flags a launch profile may pass, mapped to whether each takes a
+ * value. Flags the app sets itself (`--dir`, `--port`, `--yolo`, ...) are
+ * deliberately absent.
+ */
+export const LAUNCH_PROFILE_FLAGS: ReadonlyMap<string, boolean> = new Map([
+  ["--provider", true],
+  ["--model", true],
+]);
+
+const ENV_NAME_PATTERN = /^[A-Za-z_][A-Za-z0-9_]*$/;
+// Set by the app on every spawn, including the server's auth and shutdown tokens.
+const RESERVED_ENV_PREFIX = "COWORK_";
+const RESERVED_ENV_NAMES = new Set([
+  "AGENT_WORKING_DIR",
+  "AGENT_ADDITIONAL_WORKING_DIRS",
+  "AGENT_PATH_POLICY",
+]);
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+/** Why `args` cannot be passed to the sidecar, or null when every flag is allowed. */
+export function launchProfileArgsError(args: readonly string[]): string | null {
+  if (args.length > MAX_LAUNCH_PROFILE_ARGS) {
+    return `Launch profiles take at most ${MAX_LAUNCH_PROFILE_ARGS} arguments`;
+  }
+  for (let index = 0; index < args.length; index += 1) {
+    const flag = args[index] ?? "";
+    const takesValue = LAUNCH_PROFILE_FLAGS.get(flag);
+    if (takesValue === undefined) {
+      return `Launch profile flag is not allowed: ${flag}`;
+    }
+    if (takesValue) {
+      const value = args[index + 1];
+      if (!value?.trim() || value.startsWith("-")) {
+        return `Missing value for ${flag}`;
+      }
+      if (value.length > MAX_LAUNCH_PROFILE_VALUE_LENGTH) {
+        return `Value for ${flag} is too long`;
+      }
+      index += 1;
+    }
+  }
+  return null;
+}
+
+/** Why `env` cannot be layered onto the sidecar environment, or null when it can. */
+export function launchProfileEnvError(env: Readonly<Record<string, string>>): string | null {
+  const entries = Object.entries(env);
+  if (entries.length > MAX_LAUNCH_PROFILE_ENV_VARS) {
+    return `Launch profiles set at most ${MAX_LAUNCH_PROFILE_ENV_VARS} environment variables`;
+  }
+  for (const [name, value] of entries) {
+    if (!ENV_NAME_PATTERN.test(name)) {
+      return `Invalid environment variable name: ${name}`;
+    }
+    const upper = name.toUpperCase();
+    if (upper.startsWith(RESERVED_ENV_PREFIX) || RESERVED_ENV_NAMES.has(upper)) {
+      return `Environment variable is managed by the app: ${name}`;
+    }
+    if (value.length > MAX_LAUNCH_PROFILE_VALUE_LENGTH) {
+      return `Value for ${name} is too long`;
+    }
+  }
+  return null;
+}
+
+/** Why `profiles` cannot be saved, or null when they are valid. */
+export function launchProfilesError(profiles: readonly WorkspaceLaunchProfile[]): string | null {
+  if (profiles.length > MAX_WORKSPACE_LAUNCH_PROFILES) {
+    return `Workspaces hold at most ${MAX_WORKSPACE_LAUNCH_PROFILES} launch profiles`;
+  }
+  const names = new Set<string>();
+  for (const profile of profiles) {
+    const name = profile.name.trim();
+    if (!name || name.length > MAX_LAUNCH_PROFILE_NAME_LENGTH) {
+      return `Launch profile names must be 1-${MAX_LAUNCH_PROFILE_NAME_LENGTH} characters`;
+    }
+    if (names.has(name)) {
+      return `Duplicate launch profile: ${name}`;
+    }
+    names.add(name);
+    const error = launchProfileArgsError(profile.args) ?? launchProfileEnvError(profile.env);
+    if (error) {
+      return `${name}: ${error}`;
+    }
+  }
+  return null;
+}
+
+/**
+ * Keeps persisted profiles that would still pass validation, trimming names
+ * and dropping later duplicates. Returns undefined when nothing is left.
+ */
+export function normalizeWorkspaceLaunchProfiles(
+  value: unknown,
+): WorkspaceLaunchProfile[] | undefined {
+  if (!Array.isArray(value)) return undefined;
+  const profiles: WorkspaceLaunchProfile[] = [];
+  for (const entry of value) {
+    if (!isRecord(entry) || typeof entry.name !== "string") continue;
+    const args = Array.isArray(entry.args) ? entry.args : [];
+    if (!args.every((arg): arg is string => typeof arg === "string")) continue;
+    const env = isRecord(entry.env) ? entry.env : {};
+    if (!Object.values(env).every((item) => typeof item === "string")) continue;
+    const profile = { name: entry.name.trim(), args, env: env as Record<string, string> };
+    if (!launchProfilesError([...profiles, profile])) {
+      profiles.push(profile);
+    }
+    if (profiles.length === MAX_WORKSPACE_LAUNCH_PROFILES) break;
+  }
+  return profiles.length > 0 ? profiles : undefined;
+}
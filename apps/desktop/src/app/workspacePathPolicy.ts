@@ -0,0 +1,168 @@
+import type {
+  PathPolicyPreview,
+  WorkspacePathPolicy,
+  WorkspacePathRule,
+  WorkspaceRecord,
+} from "./types";
+import { containingWorkspaceRoot } from "./workspaceRootPaths";
+
+export const MAX_PATH_POLICY_ENTRIES = 64;
+
+type PolicyWorkspace = Pick<WorkspaceRecord, "path" | "additionalRoots" | "pathPolicy">;
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+/** Accepts POSIX, drive-letter, and UNC paths. */
+function isAbsolutePathLike(value: string): boolean {
+  return value.startsWith("/") || /^[A-Za-z]:[\\/]/.test(value) || value.startsWith("\\\\");
+}
+
+function comparable(value: string): string {
+  return value.replace(/\\/g, "/").replace(/\/+$/, "");
+}
+
+function isInside(parent: string, target: string): boolean {
+  const normalizedParent = comparable(parent);
+  const normalizedTarget = comparable(target);
+  return (
+    normalizedTarget === normalizedParent || normalizedTarget.startsWith(`${normalizedParent}/`)
+  );
+}
+
+/**
+ * Why `policy` cannot be saved for `workspace`, or null when it can. Entries
+ * must be absolute and listed once; each must resolve inside a workspace root
+ * unless marked `external`, and an `external` entry must really be outside.
+ */
+export function pathPolicyError(
+  workspace: Pick<WorkspaceRecord, "path" | "additionalRoots">,
+  policy: WorkspacePathPolicy,
+): string | null {
+  if (policy.allow.length + policy.deny.length > MAX_PATH_POLICY_ENTRIES) {
+    return `Path policies hold at most ${MAX_PATH_POLICY_ENTRIES} entries`;
+  }
+  const seen = new Set<string>();
+  for (const rule of [...policy.allow, ...policy.deny]) {
+    if (!isAbsolutePathLike(rule.path)) {
+      return `Path policy entries must be absolute: ${rule.path}`;
+    }
+    const key = comparable(rule.path);
+    if (seen.has(key)) {
+      return `Path is listed more than once: ${rule.path}`;
+    }
+    seen.add(key);
+    const inside = containingWorkspaceRoot(workspace, rule.path) !== null;
+    if (!inside && !rule.external) {
+      return `${rule.path} is outside the workspace; mark it external to list it`;
+    }
+    if (inside && rule.external) {
+      return `${rule.path} is inside the workspace, so it cannot be marked external`;
+    }
+  }
+  return null;
+}
+
+function normalizeRules(value: unknown): WorkspacePathRule[] {
+  if (!Array.isArray(value)) return [];
+  const rules: WorkspacePathRule[] = [];
+  for (const entry of value) {
+    if (!isRecord(entry) || typeof entry.path !== "string") continue;
+    const rulePath = entry.path.trim();
+    if (!isAbsolutePathLike(rulePath)) continue;
+    rules.push(entry.external === true ? { path: rulePath, external: true } : { path: rulePath });
+  }
+  return rules;
+}
+
+/**
+ * Keeps well-formed persisted entries. Containment is not re-checked here,
+ * since roots can change after a policy is saved; the sidecar only ever sees
+ * absolute paths. Returns undefined when both lists are empty.
+ */
+export function normalizeWorkspacePathPolicy(value: unknown): WorkspacePathPolicy | undefined {
+  if (!isRecord(value)) return undefined;
+  const allow = normalizeRules(value.allow).slice(0, MAX_PATH_POLICY_ENTRIES);
+  const deny = normalizeRules(value.deny).slice(0, MAX_PATH_POLICY_ENTRIES - allow.length);
+  return allow.length > 0 || deny.length > 0 ? { allow, deny } : undefined;
+}
+
+/**
+ * How the sidecar's file tools treat `targetPath` (already absolute). The most
+ * specific matching entry decides and a deny beats an allow for the same path,
+ * mirroring the server's check; with no match, paths inside a workspace root
+ * are allowed and everything else is denied.
+ */
+export function evaluatePathPolicy(
+  workspace: PolicyWorkspace,
+  targetPath: string,
+): PathPolicyPreview {
+  let rule: PathPolicyPreview["rule"] = null;
+  for (const [list, rules] of [
+    ["allow", workspace.pathPolicy?.allow ?? []],
+    ["deny", workspace.pathPolicy?.deny ?? []],
+  ] as const) {
+    for (const entry of rules) {
+      if (!isInside(entry.path, targetPath)) continue;
+      const length = comparable(entry.path).length;
+      const bestLength = rule ? comparable(rule.path).length : -1;
+      if (length > bestLength || (length === bestLength && list === "deny")) {
+        rule = { list, path: entry.path };
+      }
+    }
+  }
+
+  if (rule?.list === "deny") {
+    return {
+      path: targetPath,
+      decision: "deny",
+      rule,
+      reason: `Denied by the workspace path policy entry ${rule.path}.`,
+    };
+  }
+  if (rule) {
+    return {
+      path: targetPath,
+      decision: "allow",
+      rule,
+      reason: `Allowed by the workspace path policy entry ${rule.path}.`,
+    };
+  }
+  const root = containingWorkspaceRoot(workspace, targetPath);
+  return root
+    ? {
+        path: targetPath,
+        decision: "allow",
+        rule: null,
+        reason: `Inside the workspace root ${root}.`,
+      }
+    : {
+        path: targetPath,
+        decision: "deny",
+        rule: null,
+        reason: "Outside every workspace root and not on the allow list.",
+      };
+}
+
+/**
+ * The workspace's policy as the sidecar's `AGENT_PATH_POLICY` env value, or
+ * null when there is none. Allow entries whose place no longer matches their
+ * `external` flag (say a root was removed since) are left out; deny entries
+ * always go through, since they only narrow access.
+ */
+export function pathPolicyEnvValue(workspace: PolicyWorkspace): string | null {
+  const policy = workspace.pathPolicy;
+  const allow = (policy?.allow ?? []).filter((rule) => {
+    const external = containingWorkspaceRoot(workspace, rule.path) === null;
+    return external === (rule.external === true);
+  });
+  const deny = policy?.deny ?? [];
+  if (allow.length === 0 && deny.length === 0) {
+    return null;
+  }
+  return JSON.stringify({
+    allow: allow.map((rule) => rule.path),
+    deny: deny.map((rule) => rule.path),
+  });
+}
@@ -0,0 +1,46 @@
+import type { WorkspaceResourceLimits } from "./types";
+
+export const MIN_WORKSPACE_MEMORY_LIMIT_MB = 256;
+export const MAX_WORKSPACE_MEMORY_LIMIT_MB = 1024 * 1024;
+export const MAX_WORKSPACE_NICENESS = 19;
+export const MAX_WORKSPACE_LIFETIME_MINUTES = 7 * 24 * 60;
+export const MIN_WORKSPACE_STARTUP_TIMEOUT_SECONDS = 5;
+export const MAX_WORKSPACE_STARTUP_TIMEOUT_SECONDS = 300;
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+function asBoundedInteger(value: unknown, min: number, max: number): number | undefined {
+  if (typeof value !== "number" || !Number.isFinite(value)) return undefined;
+  return Math.min(max, Math.max(min, Math.floor(value)));
+}
+
+/** Clamps persisted limits into range and drops the record when nothing is limited. */
+export function normalizeWorkspaceResourceLimits(
+  value: unknown,
+): WorkspaceResourceLimits | undefined {
+  if (!isRecord(value)) return undefined;
+  const limits: WorkspaceResourceLimits = {};
+  const maxMemoryMb = asBoundedInteger(
+    value.maxMemoryMb,
+    MIN_WORKSPACE_MEMORY_LIMIT_MB,
+    MAX_WORKSPACE_MEMORY_LIMIT_MB,
+  );
+  const niceness = asBoundedInteger(value.niceness, 0, MAX_WORKSPACE_NICENESS);
+  const maxLifetimeMinutes = asBoundedInteger(
+    value.maxLifetimeMinutes,
+    1,
+    MAX_WORKSPACE_LIFETIME_MINUTES,
+  );
+  const startupTimeoutSeconds = asBoundedInteger(
+    value.startupTimeoutSeconds,
+    MIN_WORKSPACE_STARTUP_TIMEOUT_SECONDS,
+    MAX_WORKSPACE_STARTUP_TIMEOUT_SECONDS,
+  );
+  if (maxMemoryMb !== undefined) limits.maxMemoryMb = maxMemoryMb;
+  if (niceness !== undefined) limits.niceness = niceness;
+  if (maxLifetimeMinutes !== undefined) limits.maxLifetimeMinutes = maxLifetimeMinutes;
+  if (startupTimeoutSeconds !== undefined) limits.startupTimeoutSeconds = startupTimeoutSeconds;
+  return Object.keys(limits).length > 0 ? limits : undefined;
+}
@@ -0,0 +1,42 @@
+import type { WorkspaceRecord } from "./types";
+
+/** Every root of a workspace, primary `path` first. */
+export function workspaceRootPaths(
+  workspace: Pick<WorkspaceRecord, "path" | "additionalRoots">,
+): string[] {
+  return [workspace.path, ...(workspace.additionalRoots ?? [])];
+}
+
+/** Keeps unique, non-empty extra roots other than the primary path; undefined when none remain. */
+export function normalizeAdditionalRoots(
+  value: unknown,
+  primaryPath: string,
+): string[] | undefined {
+  if (!Array.isArray(value)) return undefined;
+  const roots: string[] = [];
+  for (const entry of value) {
+    if (typeof entry !== "string") continue;
+    const root = entry.trim();
+    if (!root || root === primaryPath || roots.includes(root)) continue;
+    roots.push(root);
+  }
+  return roots.length > 0 ? roots : undefined;
+}
+
+/** The workspace root containing `targetPath`, preferring the deepest match for nested roots. */
+export function containingWorkspaceRoot(
+  workspace: Pick<WorkspaceRecord, "path" | "additionalRoots">,
+  targetPath: string,
+): string | null {
+  const normalize = (value: string) => value.replace(/\\/g, "/").replace(/\/+$/, "");
+  const target = normalize(targetPath);
+  let best: string | null = null;
+  for (const root of workspaceRootPaths(workspace)) {
+    const normalizedRoot = normalize(root);
+    if (target !== normalizedRoot && !target.startsWith(`${normalizedRoot}/`)) continue;
+    if (!best || normalizedRoot.length > normalize(best).length) {
+      best = root;
+    }
+  }
+  return best;
+}
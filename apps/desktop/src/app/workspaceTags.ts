@@ -0,0 +1,22 @@
+export const MAX_WORKSPACE_TAG_COUNT = 16;
+export const MAX_WORKSPACE_TAG_LENGTH = 40;
+
+/**
+ * Trims tags, drops blank or over-long ones, and removes case-insensitive
+ * duplicates (the first spelling wins). Returns undefined when nothing is left.
+ */
+export function normalizeWorkspaceTags(value: unknown): string[] | undefined {
+  if (!Array.isArray(value)) return undefined;
+  const tags: string[] = [];
+  const seen = new Set<string>();
+  for (const entry of value) {
+    if (typeof entry !== "string") continue;
+    const tag = entry.trim();
+    const key = tag.toLowerCase();
+    if (!tag || tag.length > MAX_WORKSPACE_TAG_LENGTH || seen.has(key)) continue;
+    seen.add(key);
+    tags.push(tag);
+    if (tags.length === MAX_WORKSPACE_TAG_COUNT) break;
+  }
+  return tags.length > 0 ? tags : undefined;
+}
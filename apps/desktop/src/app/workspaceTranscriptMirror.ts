@@ -0,0 +1,37 @@
+import type { TranscriptMirrorFormat, WorkspaceRecord, WorkspaceTranscriptMirror } from "./types";
+
+export const DEFAULT_TRANSCRIPT_MIRROR_DIRECTORY = ".cowork/transcripts";
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return Boolean(value) && typeof value === "object" && !Array.isArray(value);
+}
+
+function isAbsoluteMirrorDirectory(directory: string): boolean {
+  return /^(?:[\\/]|[A-Za-z]:[\\/])/.test(directory);
+}
+
+function asMirrorFormat(value: unknown): TranscriptMirrorFormat | undefined {
+  return value === "jsonl" || value === "markdown" ? value : undefined;
+}
+
+/** Drops mirrors with an unknown format or a relative directory that leaves the workspace. */
+export function normalizeWorkspaceTranscriptMirror(
+  value: unknown,
+): WorkspaceTranscriptMirror | undefined {
+  if (!isRecord(value)) return undefined;
+  const directory = typeof value.directory === "string" ? value.directory.trim() : "";
+  const format = asMirrorFormat(value.format);
+  if (!directory || !format) return undefined;
+  if (!isAbsoluteMirrorDirectory(directory) && directory.split(/[\\/]+/).includes("..")) {
+    return undefined;
+  }
+  return { directory, format };
+}
+
+/** An absolute mirror directory needs the same user approval as a workspace root. */
+export function transcriptMirrorApprovalPath(
+  workspace: Pick<WorkspaceRecord, "transcriptMirror">,
+): string | null {
+  const directory = workspace.transcriptMirror?.directory;
+  return directory && isAbsoluteMirrorDirectory(directory) ? directory : null;
+}
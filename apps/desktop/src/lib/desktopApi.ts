@@ -16,156 +16,1670 @@ import type {
 import desktopPackage from "../../package.json";
 import type {
   HydratedTranscriptSnapshot,
+  PathPolicyPreview,
+  PersistedOnboardingState,
   PersistedPrivacyTelemetrySettings,
   PersistedState,
+  ThreadRecord,
   TranscriptEvent,
+  WorkspaceIdlePolicy,
+  WorkspaceLaunchProfile,
+  WorkspacePathPolicy,
+  WorkspaceRecord,
 } from "../app/types";
+import type { ProviderName } from "./wsProtocol";
 
 export type StartWorkspaceServerInput = {
   workspaceId: string;
   workspacePath: string;
   yolo: boolean;
+  /**
+   * From `requestYoloGrant`. Without one, `yolo` only takes effect while an
+   * earlier grant for the workspace is still active; otherwise the server
+   * starts sandboxed.
+   */
+  yoloToken?: string;
   forceRestart?: boolean;
   preserveMobileRelay?: boolean;
   featureFlags?: DesktopFeatureFlagOverrides;
   privacyTelemetrySettings?: PersistedPrivacyTelemetrySettings;
+  /** Name of one of the workspace's `launchProfiles` to add to the sidecar's args and env. */
+  launchProfile?: string;
+  /** With isolated sessions on, starts or reuses a server for just this thread. */
+  threadId?: string;
+};
+
+export type StartWorkspaceServerResult = {
+  url: string;
+  /**
+   * Bearer token the server requires from local clients that send no browser
+   * origin. Null when the app did not generate one (e.g. the web build).
+   */
+  authToken: string | null;
+  /** When the server's yolo grant lapses; null when it started sandboxed. */
+  yoloExpiresAt?: string | null;
+};
+
+export type RequestYoloGrantInput = {
+  workspaceId: string;
+};
+
+/** A single-use token that lets the requesting window start the workspace in yolo mode. */
+export type YoloGrant = {
+  workspaceId: string;
+  token: string;
+  /** The token must be redeemed by then; the grant it starts lasts `yoloGrantMinutes`. */
+  expiresAt: string;
+};
+
+export type WorkspaceServerStartupProgress = {
+  workspaceId: string;
+  progress: CoworkRuntimeBootstrapProgress;
+};
+
+export type PrewarmWorkspaceServersInput = {
+  workspaceIds: string[];
+  /** How many servers may start at once. */
+  concurrency?: number;
+  featureFlags?: DesktopFeatureFlagOverrides;
+  privacyTelemetrySettings?: PersistedPrivacyTelemetrySettings;
+};
+
+export type WorkspaceServerPrewarmStatus = "queued" | "starting" | "ready" | "failed";
+
+export type WorkspaceServerPrewarmProgress = {
+  workspaceId: string;
+  status: WorkspaceServerPrewarmStatus;
+  url?: string;
+  error?: string;
+};
+
+export type WorkspaceServerPrewarmResult =
+  | { status: "ready"; url: string }
+  | { status: "failed"; error: string };
+
+export type PrewarmWorkspaceServersResult = {
+  /** Keyed by workspace id. */
+  results: Record<string, WorkspaceServerPrewarmResult>;
+};
+
+export type WorkspaceServerStatusReason =
+  | "running"
+  | "starting"
+  | "not_found"
+  | "exited"
+  | "health_failed";
+
+export type WorkspaceServerStatus = {
+  workspaceId: string;
+  running: boolean;
+  url: string | null;
+  reason: WorkspaceServerStatusReason;
+  error?: string;
+};
+
+export const SERVER_UPGRADE_REQUIRED_ERROR_CODE = "server_upgrade_required";
+export const NETWORK_UNAVAILABLE_ERROR_CODE = "network_unavailable";
+export const SERVER_STARTUP_FAILED_ERROR_CODE = "server_startup_failed";
+
+export type WorkspaceServerVersionInfo = {
+  workspaceId: string;
+  running: boolean;
+  version: string | null;
+  protocolVersion: string | null;
+  minProtocolVersion: string;
+  compatible: boolean | null;
+};
+
+/** Where a workspace server start gave up: launching it, reading its startup JSON, or health. */
+export type WorkspaceServerStartupStage = "spawn" | "startup" | "health_check";
+
+/** What the app knew when the last start of a workspace server failed. */
+export type WorkspaceServerStartupDiagnostics = {
+  workspaceId: string;
+  stage: WorkspaceServerStartupStage;
+  message: string;
+  mode: "source" | "packaged";
+  /** `bun` in source mode, the sidecar executable otherwise. */
+  command: string;
+  args: string[];
+  cwd: string;
+  /** False when `command` could not be found or executed. */
+  launcherFound: boolean;
+  stderrTail: string;
+  /** Set when the server exited on its own before it was stopped. */
+  exitCode: number | null;
+  signal: string | null;
+  failedAt: string;
+};
+
+export type WorkspaceServerStats = {
+  workspaceId: string;
+  running: boolean;
+  pid: number | null;
+  /** 100 is one fully busy core, so multi-threaded servers can report more. */
+  cpuPercent: number | null;
+  rssBytes: number | null;
+  uptimeMs: number | null;
+  sampledAt: string;
+};
+
+export type ServerStatsEvent = {
+  workspaces: WorkspaceServerStats[];
+};
+
+export type WorkspaceHealthCheckId =
+  | "serverStability"
+  | "transcriptSize"
+  | "indexFreshness"
+  | "guardrails"
+  | "secretsExposure";
+
+export type WorkspaceHealthStatus = "ok" | "warning" | "critical" | "unknown";
+
+export type WorkspaceHealthCheck = {
+  id: WorkspaceHealthCheckId;
+  status: WorkspaceHealthStatus;
+  summary: string;
+  recommendation: string | null;
+};
+
+export type WorkspaceHealthReport = {
+  workspaceId: string;
+  /** 0-100; checks that cannot be evaluated do not count against it. */
+  score: number;
+  status: Exclude<WorkspaceHealthStatus, "unknown">;
+  checks: WorkspaceHealthCheck[];
+  generatedAt: string;
+};
+
+/** A workspace server process still running from an earlier launch of the app. */
+export type OrphanedServer = {
+  pid: number;
+  /** Null when the process was found by binary name rather than the spawn ledger. */
+  workspaceId: string | null;
+  startedAt: string | null;
+  command: string;
+};
+
+export type OrphanedServerCleanupResult = {
+  stopped: number[];
+  failed: Array<{ pid: number; error: string }>;
+};
+
+/** The desktop-managed keys of a workspace's `.cowork/config.json`. */
+export type WorkspaceConfig = {
+  provider?: ProviderName;
+  model?: string;
+  enableMcp?: boolean;
+  yolo?: boolean;
+};
+
+export type WorkspaceConfigSnapshot = {
+  workspaceId: string;
+  path: string;
+  exists: boolean;
+  /** Set when the file exists but is not a JSON object; `config` is then empty. */
+  error: string | null;
+  /** Values present in the file. */
+  config: WorkspaceConfig;
+  /** The file merged over the workspace's desktop defaults. */
+  effective: {
+    provider: ProviderName | null;
+    model: string | null;
+    enableMcp: boolean;
+    yolo: boolean;
+  };
+};
+
+export type ReadWorkspaceConfigInput = {
+  workspaceId: string;
+};
+
+export type WriteWorkspaceConfigInput = {
+  workspaceId: string;
+  /** Keys to set; null removes the key so the desktop default applies again. */
+  config: { [K in keyof WorkspaceConfig]?: WorkspaceConfig[K] | null };
+};
+
+export type ModelPricingTier = "free" | "low" | "standard" | "premium";
+
+export type AvailableModel = {
+  id: string;
+  displayName: string;
+  description: string | null;
+  /** Tokens; null when the provider does not report it. */
+  contextWindow: number | null;
+  modalities: Array<"text" | "image">;
+  /** From local pricing data; null for models without a known price. */
+  pricingTier: ModelPricingTier | null;
+  supportsReasoning: boolean;
+  isDefault: boolean;
+};
+
+export type ListAvailableModelsInput = {
+  provider: ProviderName;
+  /** Workspace whose server answers a cache miss; defaults to the first workspace. */
+  workspaceId?: string;
+  /** Skips the cache and asks the server to rediscover models. */
+  refresh?: boolean;
+};
+
+export type AvailableModelsResult = {
+  provider: ProviderName;
+  models: AvailableModel[];
+  defaultModel: string | null;
+  state: "ready" | "empty" | "unreachable";
+  fetchedAt: string;
+  expiresAt: string;
+  /** True when the refresh failed and the expired cache was returned instead. */
+  stale: boolean;
+};
+
+export type WorkspaceGitCommit = {
+  sha: string;
+  summary: string;
+  author: string;
+  committedAt: string;
+};
+
+export type WorkspaceGitStatus = {
+  workspaceId: string;
+  /** False when the folder is not inside a repository or git is not installed. */
+  isRepository: boolean;
+  /** Null on a detached HEAD. */
+  branch: string | null;
+  upstream: string | null;
+  /** Null without an upstream to compare against. */
+  ahead: number | null;
+  behind: number | null;
+  dirtyFileCount: number;
+  lastCommit: WorkspaceGitCommit | null;
+};
+
+/** `before-restore` checkpoints make a restore itself undoable. */
+export type WorkspaceCheckpointReason = "yolo-start" | "before-restore";
+
+export type WorkspaceCheckpoint = {
+  /** Commit on the repository's `refs/cowork-checkpoints` ref. */
+  id: string;
+  createdAt: string;
+  reason: WorkspaceCheckpointReason;
+  /** HEAD when the checkpoint was taken; null before the first commit. */
+  head: string | null;
+};
+
+export type WorkspaceCheckpointList = {
+  workspaceId: string;
+  /** False when the folder is not inside a repository, so no checkpoints can be taken. */
+  isRepository: boolean;
+  /** Newest first. */
+  checkpoints: WorkspaceCheckpoint[];
+};
+
+export type RestoreWorkspaceCheckpointInput = {
+  workspaceId: string;
+  checkpointId: string;
+};
+
+export type RestoreWorkspaceCheckpointResult = {
+  workspaceId: string;
+  checkpointId: string;
+  /** Null when the working tree already matched the newest checkpoint. */
+  safetyCheckpoint: WorkspaceCheckpoint | null;
+  removedFileCount: number;
+};
+
+export type ValidateProviderCredentialsInput = {
+  provider: ProviderName;
+  /** Checks this key instead of the saved one, e.g. before saving it. */
+  apiKey?: string;
+  /** Skips the cached result. */
+  force?: boolean;
+};
+
+export type ProviderCredentialStatus =
+  | "valid"
+  | "invalid"
+  | "unreachable"
+  | "missing"
+  | "unsupported";
+
+/** Rate-limit headers reported by the provider on the check request. */
+export type ProviderCredentialQuota = {
+  requestsLimit: number | null;
+  requestsRemaining: number | null;
+  tokensLimit: number | null;
+  tokensRemaining: number | null;
+  resetAt: string | null;
+};
+
+export type ProviderCredentialValidation = {
+  provider: ProviderName;
+  status: ProviderCredentialStatus;
+  keySource: "input" | "saved" | "environment" | null;
+  keyMask: string | null;
+  httpStatus: number | null;
+  latencyMs: number | null;
+  quota: ProviderCredentialQuota | null;
+  message: string;
+  checkedAt: string;
+  /** True when this result was served from the cache rather than a new request. */
+  cached: boolean;
+};
+
+/** Local calendar days counted back from today, or everything recorded. */
+export type UsageSummaryRange = "today" | "7d" | "30d" | "all";
+
+export type GetUsageSummaryInput = {
+  /** Omit to sum across every workspace. */
+  workspaceId?: string;
+  range: UsageSummaryRange;
+};
+
+export type UsageTotals = {
+  turns: number;
+  promptTokens: number;
+  completionTokens: number;
+  cachedPromptTokens: number;
+  reasoningOutputTokens: number;
+  totalTokens: number;
+  /** Null when none of the turns could be priced. */
+  estimatedCostUsd: number | null;
+  /** Turns whose model had no known pricing; their tokens are counted but not their cost. */
+  unpricedTurns: number;
+};
+
+export type UsageSummary = {
+  workspaceId: string | null;
+  range: UsageSummaryRange;
+  /** Start of the range as an ISO timestamp; null for `all`. */
+  since: string | null;
+  totals: UsageTotals;
+  /** Local `YYYY-MM-DD` days, newest first. */
+  byDay: Array<UsageTotals & { day: string }>;
+  byWorkspace: Array<UsageTotals & { workspaceId: string | null }>;
+  /** The most recently active threads, capped at 50. */
+  byThread: Array<UsageTotals & { threadId: string; workspaceId: string | null }>;
+};
+
+/** What the main window shows when the app launches. */
+export type AppStartupBehavior = "restoreLastThread" | "newChat";
+
+/**
+ * How transcript payloads are checked against their `type` schema on append:
+ * "annotate" records mismatches on the event, "strict" rejects the batch.
+ */
+export type TranscriptValidationMode = "off" | "annotate" | "strict";
+
+/**
+ * Where new transcript events are appended: a JSONL file per thread, or one
+ * SQLite database for the installation. Reads cover both.
+ */
+export type TranscriptStoreKind = "jsonl" | "sqlite";
+
+/** When idle workspace servers are stopped to save power; "onBattery" only off AC power. */
+export type IdleServerSuspendMode = "never" | "onBattery" | "always";
+
+/**
+ * App-wide preferences stored in `settings.json`. Theme and telemetry consent
+ * keep their existing homes (`appearance.json` and the persisted privacy settings).
+ */
+export type AppSettings = {
+  /** Provider preselected for new workspaces; null keeps the built-in default. */
+  defaultProvider: ProviderName | null;
+  startupBehavior: AppStartupBehavior;
+  /** Days to keep thread transcripts; 0 keeps them forever. */
+  transcriptRetentionDays: number;
+  /** Cap on disk used by all transcripts, pruned oldest first; 0 means no cap. */
+  transcriptRetentionMaxTotalMb: number;
+  /** Free space to keep on the app data volume before writes pause; 0 turns the check off. */
+  minFreeDiskMb: number;
+  /** Days deleted workspaces and transcripts stay in the trash; 0 keeps them until restored. */
+  trashRetentionDays: number;
+  transcriptValidation: TranscriptValidationMode;
+  /** Desktop notifications are dropped while this is off. */
+  notificationsEnabled: boolean;
+  /** Proxy URL for plain HTTP requests from workspace servers; null inherits the environment. */
+  httpProxy: string | null;
+  /** Proxy URL for HTTPS requests from workspace servers; null inherits the environment. */
+  httpsProxy: string | null;
+  /** Comma-separated hosts that bypass the proxy, in `NO_PROXY` syntax. */
+  noProxy: string | null;
+  /** Absolute path to a PEM bundle trusted on top of the system roots. */
+  caBundlePath: string | null;
+  /** Overrides the OS light/dark appearance; "system" follows it. */
+  themeSource: ThemeSource;
+  /**
+   * Gives each thread its own workspace server instead of sharing one per
+   * workspace, so a runaway thread cannot stall the others.
+   */
+  isolatedSessions: boolean;
+  /** Cap on isolated servers running at once across all workspaces. */
+  maxIsolatedSidecars: number;
+  /** Language of main-process errors, notifications, and tray labels; null follows the OS. */
+  locale: string | null;
+  idleServerSuspend: IdleServerSuspendMode;
+  /** Minutes without transcript activity before a workspace server counts as idle. */
+  idleServerTimeoutMinutes: number;
+  /** Minutes a confirmed yolo grant lasts before the workspace's servers are stopped. */
+  yoloGrantMinutes: number;
+  /**
+   * OpenAI-compatible endpoint dictation is transcribed with, such as a local
+   * Whisper server; null uses OpenAI with the saved key. A custom endpoint is
+   * never sent that key.
+   */
+  voiceTranscriptionBaseUrl: string | null;
+  /** Transcription model; null uses `whisper-1`. */
+  voiceTranscriptionModel: string | null;
+  /** Changes only through `migrateTranscriptStore`, which moves stored transcripts over. */
+  transcriptStore: TranscriptStoreKind;
+};
+
+export type UpdateAppSettingsInput = Partial<Omit<AppSettings, "transcriptStore">>;
+
+/** A separate app-data directory: its own state, transcripts, settings, and secrets. */
+export type DesktopProfile = {
+  id: string;
+  name: string;
+  createdAt: string;
+};
+
+export type DesktopProfileList = {
+  /** The profile this app instance runs in. */
+  currentProfileId: string;
+  /** Opened by a launch without `--profile`. */
+  lastUsedProfileId: string;
+  profiles: DesktopProfile[];
+};
+
+export type CreateProfileInput = {
+  name: string;
+};
+
+export type SwitchProfileInput = {
+  profileId: string;
+};
+
+export type TestProxyConnectivityInput = {
+  url: string;
+};
+
+export type ProxyConnectivityResult = {
+  url: string;
+  /** Proxy the request went through, without credentials; null when sent directly. */
+  proxy: string | null;
+  /** True once the target answered with any HTTP status. */
+  ok: boolean;
+  httpStatus: number | null;
+  latencyMs: number;
+  message: string;
+};
+
+/** Limits to preview; each falls back to the saved app setting when omitted. */
+export type PreviewTranscriptRetentionInput = {
+  maxAgeDays?: number;
+  maxTotalMb?: number;
+};
+
+export type TranscriptRetentionCandidate = {
+  transcriptId: string;
+  /** Thread whose history the transcript holds, when one is still recorded. */
+  threadId: string | null;
+  /** Live and archived copies combined. */
+  sizeBytes: number;
+  lastModifiedAt: string;
+  reason: "age" | "diskUsage";
+};
+
+export type TranscriptRetentionPreview = {
+  maxAgeDays: number;
+  maxTotalMb: number;
+  /** False when neither limit is set, so nothing is pruned. */
+  enabled: boolean;
+  totalBytes: number;
+  reclaimableBytes: number;
+  /** Oldest first. */
+  candidates: TranscriptRetentionCandidate[];
+};
+
+export type TranscriptRetentionSummary = {
+  ranAt: string;
+  deletedCount: number;
+  reclaimedBytes: number;
+  failures: Array<{ transcriptId: string; message: string }>;
+};
+
+/** Free space on the app data volume, sent as `lowDiskSpace` when it crosses the threshold. */
+export type DiskSpaceStatus = {
+  /** Null when the platform cannot report free space. */
+  freeBytes: number | null;
+  /** Threshold from the `minFreeDiskMb` setting; 0 when the check is off. */
+  minFreeBytes: number;
+  low: boolean;
+  /** Transcript events held in memory until space returns or they are flushed. */
+  bufferedTranscriptEvents: number;
+};
+
+/**
+ * Sent as `transcriptThrottled` when a thread's transcript appends exceed its
+ * rate or size budget; counts cover the time since the previous warning.
+ */
+export type TranscriptThrottledEvent = {
+  threadId: string;
+  /** Events not written because the thread was over its event or byte budget. */
+  droppedEvents: number;
+  droppedBytes: number;
+  /** Events written with an oversized payload replaced by a `transcript_truncated` marker. */
+  truncatedEvents: number;
+  at: string;
+};
+
+export type ConnectivityState = "online" | "degraded" | "offline";
+
+/** Reachability of provider endpoints, sent as `connectivityChanged` when it changes. */
+export type ConnectivityStatus = {
+  state: ConnectivityState;
+  /** Provider hosts that did not answer; empty when the OS reports no network at all. */
+  unreachableHosts: string[];
+  /** Null until the first check has finished. */
+  checkedAt: string | null;
+  message: string;
+};
+
+export type InstallationResourceIntegrity = {
+  /** File or bundled package name, e.g. the sidecar binary's filename. */
+  name: string;
+  /** Null when the resource could not be found. */
+  path: string | null;
+  /** `unverified` resources ship without digests, so only their presence is known. */
+  status: "verified" | "mismatch" | "missing" | "unverified";
+  expectedSha256: string | null;
+  actualSha256: string | null;
+};
+
+export type InstallationIntegrityReport = {
+  /** `failed` when any resource is missing or altered. */
+  status: "ok" | "failed" | "unverified";
+  checkedAt: string;
+  /** Unpackaged builds run the server from source and have no bundled resources. */
+  packaged: boolean;
+  resources: InstallationResourceIntegrity[];
+};
+
+export type EnvironmentCheckId = "bun" | "git" | "sidecar" | "keychain" | "writePermissions";
+
+export type EnvironmentCheckStatus = "pass" | "warn" | "fail";
+
+export type EnvironmentCheck = {
+  id: EnvironmentCheckId;
+  label: string;
+  status: EnvironmentCheckStatus;
+  summary: string;
+  recommendation: string | null;
+};
+
+export type EnvironmentCheckReport = {
+  /** The worst status among the checks. */
+  status: EnvironmentCheckStatus;
+  checks: EnvironmentCheck[];
+  checkedAt: string;
+};
+
+export type DesktopOnboardingStatus = {
+  /** True until the first workspace is added. */
+  firstRun: boolean;
+  workspaceCount: number;
+  /** The setup flow's saved progress; null before it was first saved. */
+  onboarding: PersistedOnboardingState | null;
+  /** The latest environment checks since the app started, or null before any ran. */
+  environment: EnvironmentCheckReport | null;
+};
+
+export type WorkspaceResourceLimitKind = "memory" | "lifetime";
+
+export type WorkspaceServerExitedEvent = {
+  workspaceId: string;
+  url: string | null;
+  code: number | null;
+  signal: string | null;
+  /** Set when the desktop app stopped the server for exceeding a workspace resource limit. */
+  limitExceeded?: WorkspaceResourceLimitKind;
+  /** Set when the server was this thread's isolated server; other threads are unaffected. */
+  threadId?: string;
+};
+
+/** Sent as `serverSuspended` when an idle workspace server is stopped to save power. */
+export type ServerSuspendedEvent = {
+  workspaceId: string;
+  /** Minutes since the workspace's last transcript activity, sleep excluded. */
+  idleMinutes: number;
+  onBattery: boolean;
+  suspendedAt: string;
+};
+
+/** Sent as `serverResumed` when a suspended workspace server is started again on demand. */
+export type ServerResumedEvent = {
+  workspaceId: string;
+  suspendedAt: string;
+  resumedAt: string;
+};
+
+/** Sent as `serverReloaded` when a source-mode server restarts after `src/server` changed. */
+export type ServerReloadedEvent = {
+  workspaceId: string;
+  /** Present when the server belonged to an isolated session for this thread. */
+  threadId?: string;
+  /** Why it restarted, e.g. `src/server/index.ts changed`. */
+  reason: string;
+  /** Changed files relative to `src/server`. */
+  changedPaths: string[];
+  reloadedAt: string;
+};
+
+export type WorkspaceProxyConnectionState = "connected" | "reconnecting" | "disconnected";
+
+/** Emitted when the proxied connections for a workspace change state. */
+export type WorkspaceProxyConnectionEvent = {
+  workspaceId: string;
+  /** "reconnecting" while any client waits on the sidecar; "disconnected" when none remain. */
+  state: WorkspaceProxyConnectionState;
+  clientCount: number;
+  /** Client messages held until the sidecar is reachable again. */
+  bufferedMessages: number;
+  reason?: string;
+};
+
+export type CreateOneOffChatWorkspaceInput = {
+  titleHint?: string;
+};
+
+export type CreateOneOffChatWorkspaceOutput = {
+  name: string;
+  path: string;
+};
+
+export type ResolveDroppedWorkspaceFolderInput = {
+  path: string;
+};
+
+export type RegisterRemoteWorkspaceInput = {
+  /** `ssh://[user@]host[:port]/absolute/path` */
+  url: string;
+};
+
+export type DroppedWorkspaceFolder = {
+  /** Canonical folder path, already approved as a workspace root. */
+  path: string;
+  /** Set when a workspace already uses this folder. */
+  existingWorkspaceId: string | null;
+};
+
+export type ScanForWorkspacesInput = {
+  /** Folders to search, each already approved through the workspace picker. */
+  rootPaths: string[];
+  /** Folder levels below each root to search; defaults to 3. */
+  maxDepth?: number;
+};
+
+export type WorkspaceCandidateMarker = "git" | "cowork";
+
+export type WorkspaceCandidate = {
+  /** Canonical folder path, already approved as a workspace root. */
+  path: string;
+  name: string;
+  markers: WorkspaceCandidateMarker[];
+  /** Guessed from manifests such as `package.json` or `Cargo.toml`. */
+  language: string | null;
+  /** Bytes outside `.git` and dependency folders. */
+  sizeBytes: number;
+  /** False when measuring stopped early, so `sizeBytes` is a lower bound. */
+  sizeComplete: boolean;
+  lastCommit: WorkspaceGitCommit | null;
+};
+
+export type ScanForWorkspacesResult = {
+  candidates: WorkspaceCandidate[];
+  /** Set when the scan stopped at its folder or candidate limit. */
+  truncated: boolean;
+};
+
+export type StopWorkspaceServerInput = {
+  workspaceId: string;
+};
+
+export type ReleaseThreadServerInput = {
+  workspaceId: string;
+  threadId: string;
+};
+
+/** `read` can follow the thread; `readWrite` can also run turns and answer prompts in it. */
+export type HandoffAccess = "read" | "readWrite";
+
+export type GenerateHandoffLinkInput = {
+  workspaceId: string;
+  threadId: string;
+  /** Defaults to 15 minutes; at most a day. */
+  ttlSeconds?: number;
+  /** Defaults to `read`. */
+  access?: HandoffAccess;
+};
+
+/** A link another client on the LAN opens to attach to a running thread. */
+export type HandoffLink = {
+  tokenId: string;
+  workspaceId: string;
+  threadId: string;
+  access: HandoffAccess;
+  /** `ws://` URL on the workspace server's LAN listener, with the token in its query. */
+  url: string;
+  expiresAt: string;
+};
+
+export type RevokeHandoffInput = {
+  tokenId: string;
+};
+
+export type RendererLogInput = {
+  level?: "info" | "warn" | "error";
+  category: string;
+  message: string;
+  meta?: Record<string, string | number | boolean | null>;
+};
+
+export type AppLogLevel = "debug" | "info" | "warn" | "error";
+
+export type AppLogFileName = "desktop-main.log" | "server.log" | "renderer.log" | "updater.log";
+
+export type SetLogLevelInput = {
+  level: AppLogLevel;
+};
+
+export type ReadAppLogsInput = {
+  /** Most recent entries to return; defaults to 200. */
+  tail?: number;
+  /** Minimum level to include; defaults to every level. */
+  levelFilter?: AppLogLevel;
+  file?: AppLogFileName;
+};
+
+export type AppLogEntry = {
+  ts: string;
+  level: AppLogLevel;
+  category: string;
+  message: string;
+  meta?: unknown;
+  /** Live or rotated file the entry was read from. */
+  file: string;
+};
+
+export type AppLogsResult = {
+  /** Level currently being written. */
+  level: AppLogLevel;
+  entries: AppLogEntry[];
+  /** True when older matching entries exist beyond those returned. */
+  truncated: boolean;
+};
+
+export type MobileRelayStartInput = {
+  workspaceId: string;
+  workspacePath: string;
+  yolo: boolean;
+  featureFlags?: DesktopFeatureFlagOverrides;
+};
+
+const MOBILE_RELAY_TRUSTED_DEVICE_PERMISSION_KEYS = [
+  "turns",
+  "serverRequests",
+  "providerAuth",
+  "mcpAuth",
+  "workspaceSettings",
+  "backups",
+  "conversations",
+] as const;
+
+export type MobileRelayTrustedDevicePermissionKey =
+  (typeof MOBILE_RELAY_TRUSTED_DEVICE_PERMISSION_KEYS)[number];
+
+type MobileRelayTrustedDevicePermissions = Record<MobileRelayTrustedDevicePermissionKey, boolean>;
+
+export type MobileRelayTrustedPhoneDevice = {
+  deviceId: string;
+  fingerprint: string;
+  displayName: string | null;
+  lastPairedAt: string | null;
+  lastConnectedAt: string | null;
+  permissions: MobileRelayTrustedDevicePermissions;
+};
+
+export type MobileRelayForgetTrustedPhoneInput =
+  | {
+      workspaceId: string;
+      scope: "device";
+      deviceId: string;
+    }
+  | {
+      workspaceId: string;
+      scope: "all";
+      expectedDeviceIds: string[];
+    };
+
+export type MobileRelayUpdateTrustedPhonePermissionsInput = {
+  workspaceId: string;
+  deviceId: string;
+  permissions: Partial<Record<MobileRelayTrustedDevicePermissionKey, boolean>>;
+};
+
+export type MobileRelayBridgeState = {
+  status: "idle" | "starting" | "pairing" | "connected" | "reconnecting" | "error";
+  workspaceId: string | null;
+  workspacePath: string | null;
+  relaySource: "direct" | "managed" | "remodex" | "override" | "unavailable";
+  relaySourceMessage: string | null;
+  relayServiceStatus: "unknown" | "running" | "not-running" | "unavailable";
+  relayServiceMessage: string | null;
+  relayServiceUpdatedAt: string | null;
+  relayUrl: string | null;
+  sessionId: string | null;
+  pairingPayload: {
+    v: 1;
+    scheme: "h3";
+    hosts: string[];
+    port: number;
+    certSha256: string;
+    spkiSha256: string;
+    identityPub: string;
+    nonce: string;
+    expiresAt: number;
+  } | null;
+  trustedPhoneDeviceId: string | null;
+  trustedPhoneFingerprint: string | null;
+  trustedPhoneDevices: MobileRelayTrustedPhoneDevice[];
+  directUrl: string | null;
+  ticketUrl: string | null;
+  certSha256: string | null;
+  spkiSha256: string | null;
+  hostHints: string[];
+  lastError: string | null;
+};
+
+/**
+ * Outcome of `saveState`. `mergeRequired` means another process wrote
+ * state.json after the save's base generation; `workspaces` and `threads` are
+ * the records it added, to be merged before saving again.
+ */
+export type SaveStateResult =
+  | { status: "saved"; generation: number }
+  | {
+      status: "mergeRequired";
+      baseGeneration: number;
+      currentGeneration: number;
+      workspaces: WorkspaceRecord[];
+      threads: ThreadRecord[];
+    };
+
+export type StateSnapshotInfo = {
+  name: string;
+  createdAt: string;
+  sizeBytes: number;
+};
+
+export type RestoreStateSnapshotInput = {
+  name: string;
+};
+
+export type RebuildStateInput = {
+  /** Report what would be recovered without writing state.json. */
+  dryRun: boolean;
+};
+
+export type StateRebuildWorkspace = {
+  id: string;
+  name: string;
+  path: string;
+  /** Where the id came from: the current state, a server.log start entry, or a new id. */
+  source: "existing" | "serverLog" | "generated";
+  threadIds: string[];
+};
+
+export type StateRebuildThread = {
+  id: string;
+  workspaceId: string;
+  title: string;
+  titleFrom: "sessionInfo" | "firstMessage" | "fallback";
+  sessionId: string | null;
+  messageCount: number;
+  createdAt: string;
+  lastMessageAt: string;
+};
+
+export type StateRebuildReport = {
+  dryRun: boolean;
+  applied: boolean;
+  /** Snapshot taken before applying; null if state.json was missing or already snapshotted. */
+  snapshotName: string | null;
+  scannedTranscripts: number;
+  alreadyPresent: number;
+  workspaces: StateRebuildWorkspace[];
+  threads: StateRebuildThread[];
+  skipped: Array<{ threadId: string; reason: string }>;
+  generatedAt: string;
+};
+
+/**
+ * How an app data archive is applied. `merge` adds the workspaces and threads
+ * this machine does not have and keeps its settings; `replace` swaps in the
+ * archived state and settings.
+ */
+export type AppDataMergeStrategy = "merge" | "replace";
+
+export type AppDataArchiveManifest = {
+  format: "cowork-app-data";
+  formatVersion: number;
+  appVersion: string;
+  createdAt: string;
+  includesTranscripts: boolean;
+  workspaceCount: number;
+  threadCount: number;
+};
+
+export type ExportAppDataInput = {
+  /** Path the save dialog suggests; the user picks the final location. */
+  destPath?: string;
+  includeTranscripts: boolean;
+};
+
+export type ExportAppDataResult = {
+  path: string;
+  sizeBytes: number;
+  manifest: AppDataArchiveManifest;
+};
+
+export type ImportAppDataInput = {
+  /** Archive the open dialog starts from; the user picks the final file. */
+  archivePath?: string;
+  mergeStrategy: AppDataMergeStrategy;
+};
+
+export type ImportAppDataResult = {
+  manifest: AppDataArchiveManifest;
+  mergeStrategy: AppDataMergeStrategy;
+  /** Snapshot of the previous state.json, taken before importing. */
+  snapshotName: string | null;
+  workspacesImported: number;
+  threadsImported: number;
+  /** Archived threads left alone because this machine already has them. */
+  threadsSkipped: number;
+  transcriptsImported: number;
+  /** Archived transcript events dropped because their `ts` or direction could not be read. */
+  transcriptEventsSkipped: number;
+  /** False when the archived settings were not applied, e.g. a CA bundle path missing here. */
+  settingsApplied: boolean;
+  state: PersistedState;
+};
+
+export type CreateScratchThreadInput = {
+  workspaceId: string;
+};
+
+export type ScratchThreadInfo = {
+  threadId: string;
+  workspaceId: string;
+  createdAt: string;
+};
+
+export type PersistScratchThreadInput = {
+  threadId: string;
+  title?: string;
+  sessionId?: string | null;
+  messageCount?: number;
+  lastEventSeq?: number;
+};
+
+export type ReadTranscriptInput = {
+  threadId: string;
+};
+
+export type ReadTranscriptPageInput = {
+  threadId: string;
+  /** `nextCursor` from the previous page; omit to start at the beginning. */
+  cursor?: string;
+  maxEvents?: number;
+  /** Caps the raw JSONL bytes behind a page; a page always holds at least one event. */
+  maxBytes?: number;
+};
+
+export type GetThreadPreviewsInput = {
+  threadIds: string[];
+};
+
+/** The latest user or assistant message of a thread, as the sidebar shows it. */
+export type ThreadPreviewMessage = {
+  role: "user" | "assistant";
+  ts: string;
+  /** Clipped to a short snippet; null when the message is only stored encrypted. */
+  text: string | null;
+};
+
+/** Sidebar summary of a transcript, read from its index rather than the transcript. */
+export type ThreadPreview = {
+  threadId: string;
+  eventCount: number;
+  lastEventAt: string | null;
+  lastMessage: ThreadPreviewMessage | null;
+};
+
+/** A slice of a transcript, read without loading the whole file. */
+export type TranscriptPage = {
+  events: TranscriptEvent[];
+  /** Where the next page starts, or null when this page reached the end. */
+  nextCursor: string | null;
+  /** True when the page stopped at a limit and more events may follow. */
+  truncated: boolean;
+};
+
+export type TranscriptCorruptedLine = {
+  /** Archived history is read first, then the live JSONL file, then SQLite rows. */
+  source: "archived" | "live" | "sqlite";
+  /** 1-based line number within that file, or row number within the thread's rows. */
+  line: number;
+  reason: "unparseable" | "invalid_event";
+};
+
+/** Lines `readTranscript` skipped because they could not be decoded. */
+export type TranscriptInspection = {
+  threadId: string;
+  eventCount: number;
+  corruptedLines: TranscriptCorruptedLine[];
+};
+
+export type TranscriptTimestampNormalizationResult = {
+  threadId: string;
+  /** Events whose `ts` was rewritten as a UTC RFC 3339 timestamp. */
+  normalizedCount: number;
+  /** Events whose `ts` could not be read as a time at all; they are left as they were. */
+  unparseableCount: number;
+};
+
+export type TranscriptRepairResult = {
+  threadId: string;
+  quarantinedLineCount: number;
+  /** `{threadId}.corrupt` beside the transcript; null when nothing needed repair. */
+  quarantinePath: string | null;
+};
+
+export type RedactTranscriptInput = {
+  threadId: string;
+  /** Extra regular expressions to redact, on top of the built-in credential detection. */
+  patterns?: string[];
+  /** Count what would be redacted without rewriting anything. */
+  dryRun: boolean;
+};
+
+export type TranscriptRedactionMatch = {
+  /** A built-in credential kind, or the user-supplied pattern that matched. */
+  rule: string;
+  count: number;
+};
+
+export type TranscriptRedactionResult = {
+  threadId: string;
+  dryRun: boolean;
+  redactionCount: number;
+  redactedEventCount: number;
+  matches: TranscriptRedactionMatch[];
+  /** Encrypted lines whose key is unavailable on this device; they were not scanned. */
+  sealedLineCount: number;
+};
+
+export type GetThreadFileChangesInput = {
+  threadId: string;
+};
+
+export type ThreadFileChange = {
+  /** Absolute path of the changed file. */
+  path: string;
+  /** Path relative to the workspace when the file is inside it, otherwise the absolute path. */
+  displayPath: string;
+  /** Successful tool calls that changed the file. */
+  changeCount: number;
+  /** Edited regions; a whole-file write counts as one. */
+  hunkCount: number;
+  linesAdded: number;
+  linesRemoved: number;
+  /** sha256 of the last whole-file write, or null when later edits make the result unknown. */
+  finalContentHash: string | null;
+  lastChangedAt: string;
+};
+
+export type ThreadFileChanges = {
+  threadId: string;
+  /** Most recently changed first. */
+  files: ThreadFileChange[];
+};
+
+export type StartReplayInput = {
+  threadId: string;
+  /** Playback rate relative to the recording; 2 plays twice as fast. Defaults to 1. */
+  speed?: number;
+};
+
+export type ReplaySession = {
+  replayId: string;
+  threadId: string;
+  /** Id the replayed thread is served under; the recording's server session id. */
+  sessionId: string;
+  /** Workspace server WebSocket URL that re-emits the recording to each client that resumes it. */
+  url: string;
+  speed: number;
+  /** Server events in the recording. */
+  eventCount: number;
+  /** Playback length at the requested speed, after long pauses are shortened. */
+  durationMs: number;
+};
+
+export type StopReplayInput = {
+  replayId: string;
+};
+
+export type ArchiveThreadInput = {
+  threadId: string;
+};
+
+export type SuggestThreadTitleInput = {
+  threadId: string;
+};
+
+export type CopyTranscriptSelectionInput = {
+  threadId: string;
+  /** Inclusive indices into the hydrated transcript feed. */
+  fromIndex: number;
+  toIndex: number;
+  /** Defaults to true: credentials and workspace `.env` values are replaced before copying. */
+  redactSecrets?: boolean;
+};
+
+export type CopyTranscriptSelectionResult = {
+  /** Feed items that rendered to text; reasoning and log lines are left out. */
+  itemCount: number;
+  redactionCount: number;
+};
+
+export type ForkThreadInput = {
+  threadId: string;
+  /** Defaults to the source title with " (fork)" appended. */
+  title?: string;
+  /** Zero-based index of the last transcript event to copy; copies everything when omitted. */
+  throughEventIndex?: number;
+};
+
+export type MergeThreadsInput = {
+  sourceThreadId: string;
+  targetThreadId: string;
+  /** Moves the source thread to the trash instead of keeping it marked as merged. */
+  trashSource?: boolean;
+};
+
+export type MergeThreadsResult = {
+  thread: ThreadRecord;
+  /** The source thread marked as merged; null when it was moved to the trash. */
+  source: ThreadRecord | null;
+  /** Source events added to the target's transcript. */
+  mergedEventCount: number;
+};
+
+export type TranscriptEncryptionStatus = {
+  enabled: boolean;
+  /** False when the OS keychain cannot wrap the transcript key. */
+  available: boolean;
+};
+
+export type SetTranscriptEncryptionInput = {
+  enabled: boolean;
+};
+
+export type TranscriptEncryptionMigrationResult = {
+  encryptedFiles: number;
+  unchangedFiles: number;
+};
+
+export type MigrateTranscriptStoreInput = {
+  target: TranscriptStoreKind;
+};
+
+export type TranscriptStoreMigrationResult = {
+  target: TranscriptStoreKind;
+  /** Threads whose transcripts moved into the target store. */
+  migratedTranscripts: number;
+  migratedLines: number;
+};
+
+export type DeleteTranscriptInput = {
+  threadId: string;
+  confirmationToken?: string;
+};
+
+export type PurgeThreadsInput = {
+  threadIds: string[];
+  confirmationToken?: string;
+};
+
+export type DeleteWorkspaceInput = {
+  workspaceId: string;
+  confirmationToken?: string;
+};
+
+export type SetWorkspacePinnedInput = {
+  workspaceId: string;
+  pinned: boolean;
+};
+
+export type SetWorkspaceTagsInput = {
+  workspaceId: string;
+  /** Replaces the workspace's tags; an empty list clears them. */
+  tags: string[];
+};
+
+export type SetWorkspaceLaunchProfilesInput = {
+  workspaceId: string;
+  /** Replaces the workspace's launch profiles; an empty list clears them. */
+  profiles: WorkspaceLaunchProfile[];
+};
+
+export type SetWorkspaceIdlePolicyInput = {
+  workspaceId: string;
+  /** Replaces the workspace's idle policy; null follows the app's idle settings again. */
+  policy: WorkspaceIdlePolicy | null;
+};
+
+export type SetWorkspacePathPolicyInput = {
+  workspaceId: string;
+  /** Replaces the workspace's path policy; two empty lists clear it. */
+  policy: WorkspacePathPolicy;
+};
+
+export type PreviewPathPolicyInput = {
+  workspaceId: string;
+  /** Absolute, or relative to the workspace's primary root. */
+  path: string;
+};
+
+export type DuplicateWorkspaceInput = {
+  workspaceId: string;
+  /** Absolute folder to copy the directory into; without it the copy shares the original. */
+  newPath?: string;
+  copyThreads: boolean;
+  /** Threads to copy when `copyThreads` is set; defaults to every unarchived chat thread. */
+  threadIds?: string[];
+};
+
+export type DuplicateWorkspaceResult = {
+  workspace: WorkspaceRecord;
+  threads: ThreadRecord[];
+};
+
+export type ReorderWorkspacesInput = {
+  /** Every workspace id, exactly once, in the new order. */
+  workspaceIds: string[];
+};
+
+export type GlobalSearchInput = {
+  query: string;
+  /** Defaults to 20. */
+  limit?: number;
+};
+
+type GlobalSearchResultBase = {
+  /** Higher ranks first; names and titles outrank message text. */
+  score: number;
+  workspaceId: string;
+  /** Workspace name, or the title of the thread the result belongs to. */
+  title: string;
+  /** Last opened, last message, or when the matching event was recorded. */
+  timestamp: string;
+};
+
+export type GlobalSearchResult =
+  | (GlobalSearchResultBase & { kind: "workspace"; path: string })
+  | (GlobalSearchResultBase & { kind: "thread"; threadId: string })
+  | (GlobalSearchResultBase & { kind: "event"; threadId: string; snippet: string })
+  | (GlobalSearchResultBase & {
+      kind: "annotation";
+      threadId: string;
+      eventIndex: number;
+      snippet: string;
+      tags: string[];
+    })
+  | (Omit<GlobalSearchResultBase, "workspaceId"> & {
+      kind: "prompt";
+      templateId: string;
+      scope: PromptTemplateScope;
+      /** Set for workspace templates. */
+      workspaceId?: string;
+      snippet: string;
+      tags: string[];
+    });
+
+/** A note and tags on one transcript event, stored apart from the transcript. */
+export type TranscriptAnnotation = {
+  threadId: string;
+  /** Position of the event in the thread's transcript. */
+  eventIndex: number;
+  /** When the annotated event was recorded. */
+  eventTs: string;
+  note: string;
+  /** Lowercased and de-duplicated. */
+  tags: string[];
+  createdAt: string;
+  updatedAt: string;
+};
+
+export type AnnotateEventInput = {
+  threadId: string;
+  eventIndex: number;
+  /** An empty note with no tags removes the annotation. */
+  note: string;
+  tags?: string[];
+};
+
+export type ListAnnotationsInput = {
+  threadId: string;
+};
+
+/** A file attached to an unsent prompt, as the composer described it; the file is not copied. */
+export type ThreadDraftAttachment = {
+  name: string;
+  path?: string;
+  mimeType?: string;
+  sizeBytes?: number;
+};
+
+/** A half-written prompt, stored apart from `state.json`. */
+export type ThreadDraft = {
+  threadId: string;
+  content: string;
+  attachments: ThreadDraftAttachment[];
+  updatedAt: string;
+};
+
+export type SaveThreadDraftInput = {
+  threadId: string;
+  /** Empty content with no attachments clears the draft. */
+  content: string;
+  attachments?: ThreadDraftAttachment[];
 };
 
-export type WorkspaceServerStartupProgress = {
-  workspaceId: string;
-  progress: CoworkRuntimeBootstrapProgress;
+export type LoadThreadDraftInput = {
+  threadId: string;
 };
 
-export type WorkspaceServerStatusReason =
-  | "running"
-  | "starting"
-  | "not_found"
-  | "exited"
-  | "health_failed";
+export const PROMPT_VARIABLES_MISSING_ERROR_CODE = "prompt_variables_missing";
 
-export type WorkspaceServerStatus = {
-  workspaceId: string;
-  running: boolean;
-  url: string | null;
-  reason: WorkspaceServerStatusReason;
-  error?: string;
-};
+/** Global templates live in app data; workspace ones in the workspace's `.cowork/prompts.json`. */
+export type PromptTemplateScope = "global" | "workspace";
 
-export type WorkspaceServerExitedEvent = {
-  workspaceId: string;
-  url: string | null;
-  code: number | null;
-  signal: string | null;
+/** A named, reusable prompt with `{{variable}}` placeholders. */
+export type PromptTemplate = {
+  id: string;
+  scope: PromptTemplateScope;
+  /** Set for workspace templates. */
+  workspaceId?: string;
+  name: string;
+  description: string;
+  body: string;
+  /** Placeholder names in the order they first appear in the body. */
+  variables: string[];
+  /** Lowercased and de-duplicated. */
+  tags: string[];
+  createdAt: string;
+  updatedAt: string;
 };
 
-export type CreateOneOffChatWorkspaceInput = {
-  titleHint?: string;
+export type ListPromptTemplatesInput = {
+  /** Adds that workspace's templates to the global ones. */
+  workspaceId?: string;
 };
 
-export type CreateOneOffChatWorkspaceOutput = {
+export type SavePromptTemplateInput = {
+  /** Updates the template with this id; omit to create one. */
+  id?: string;
+  /** Saves into the workspace's library; omit for the global library. */
+  workspaceId?: string;
   name: string;
-  path: string;
+  description?: string;
+  body: string;
+  tags?: string[];
 };
 
-export type StopWorkspaceServerInput = {
-  workspaceId: string;
+export type DeletePromptTemplateInput = {
+  id: string;
+  workspaceId?: string;
 };
 
-export type RendererLogInput = {
-  level?: "info" | "warn" | "error";
-  category: string;
-  message: string;
-  meta?: Record<string, string | number | boolean | null>;
+export type RenderPromptInput = {
+  templateId: string;
+  /** Looks in this workspace's library before the global one. */
+  workspaceId?: string;
+  vars: Record<string, string>;
 };
 
-export type MobileRelayStartInput = {
+export type RenderPromptResult = {
+  template: PromptTemplate;
+  text: string;
+};
+
+export type DestructiveCommand = "deleteTranscript" | "purgeThreads" | "deleteWorkspace";
+
+export type DestructiveCommandImpact = {
+  workspaceCount: number;
+  threadCount: number;
+  transcriptCount: number;
+  transcriptBytes: number;
+};
+
+/** Issued by the main process; the command only runs when re-invoked with `token`. */
+export type DestructiveConfirmationChallenge = {
+  command: DestructiveCommand;
+  token: string;
+  summary: string;
+  impact: DestructiveCommandImpact;
+  expiresAt: string;
+};
+
+export type DestructiveCommandResult =
+  | { status: "confirmationRequired"; challenge: DestructiveConfirmationChallenge }
+  | { status: "completed" };
+
+/** Bytes on disk that belong to a workspace's threads. */
+export type WorkspaceStorageReport = {
   workspaceId: string;
-  workspacePath: string;
-  yolo: boolean;
-  featureFlags?: DesktopFeatureFlagOverrides;
+  threadCount: number;
+  /** Live JSONL transcripts. */
+  transcriptBytes: number;
+  /** zstd-compressed transcripts of archived or compacted threads. */
+  archiveBytes: number;
+  /** Lines `repairTranscript` moved aside because they could not be decoded. */
+  quarantineBytes: number;
+  totalBytes: number;
 };
 
-const MOBILE_RELAY_TRUSTED_DEVICE_PERMISSION_KEYS = [
-  "turns",
-  "serverRequests",
-  "providerAuth",
-  "mcpAuth",
-  "workspaceSettings",
-  "backups",
-  "conversations",
-] as const;
+export type GetWorkspaceStorageReportInput = {
+  workspaceId: string;
+};
 
-export type MobileRelayTrustedDevicePermissionKey =
-  (typeof MOBILE_RELAY_TRUSTED_DEVICE_PERMISSION_KEYS)[number];
+/**
+ * - `compactTranscripts` compresses the live transcripts of threads idle for a day.
+ * - `purgeArchivedThreads` deletes archived threads; it needs a confirmation.
+ * - `clearQuarantine` deletes quarantined transcript lines.
+ */
+export type WorkspaceStorageCleanupTarget =
+  | "compactTranscripts"
+  | "purgeArchivedThreads"
+  | "clearQuarantine";
 
-type MobileRelayTrustedDevicePermissions = Record<MobileRelayTrustedDevicePermissionKey, boolean>;
+export type CleanupWorkspaceStorageInput = {
+  workspaceId: string;
+  targets: WorkspaceStorageCleanupTarget[];
+  /** Reports what each target would touch without changing anything. */
+  dryRun?: boolean;
+  confirmationToken?: string;
+};
 
-export type MobileRelayTrustedPhoneDevice = {
-  deviceId: string;
-  fingerprint: string;
-  displayName: string | null;
-  lastPairedAt: string | null;
-  lastConnectedAt: string | null;
-  permissions: MobileRelayTrustedDevicePermissions;
+export type WorkspaceStorageCleanupAction = {
+  target: WorkspaceStorageCleanupTarget;
+  threadIds: string[];
+  /** Bytes deleted, or for compaction the live bytes that get compressed. */
+  bytes: number;
 };
 
-export type MobileRelayForgetTrustedPhoneInput =
-  | {
-      workspaceId: string;
-      scope: "device";
-      deviceId: string;
-    }
+export type CleanupWorkspaceStorageResult =
+  | { status: "confirmationRequired"; challenge: DestructiveConfirmationChallenge }
   | {
-      workspaceId: string;
-      scope: "all";
-      expectedDeviceIds: string[];
+      status: "completed";
+      dryRun: boolean;
+      actions: WorkspaceStorageCleanupAction[];
+      /** Usage after the cleanup, or the current usage for a dry run. */
+      report: WorkspaceStorageReport;
     };
 
-export type MobileRelayUpdateTrustedPhonePermissionsInput = {
+export type TrashEntryKind = "thread" | "threads" | "workspace";
+
+export type TrashEntry = {
+  id: string;
+  kind: TrashEntryKind;
+  /** Workspace name or thread title shown in the trash list. */
+  label: string;
+  deletedAt: string;
+  /** When the entry is purged; null while `trashRetentionDays` is 0. */
+  expiresAt: string | null;
+  sizeBytes: number;
+  workspaceCount: number;
+  threadCount: number;
+};
+
+export type RestoreFromTrashInput = {
+  entryId: string;
+};
+
+export type RestoreFromTrashResult = {
+  workspaces: WorkspaceRecord[];
+  threads: ThreadRecord[];
+};
+
+export type ScheduledTaskRun = {
+  taskId: string;
+  taskName: string;
   workspaceId: string;
-  deviceId: string;
-  permissions: Partial<Record<MobileRelayTrustedDevicePermissionKey, boolean>>;
+  startedAt: string;
+  finishedAt: string;
+  status: "completed" | "failed" | "interrupted";
+  /** Thread the run was recorded as; null when it failed before the prompt was sent. */
+  threadId: string | null;
+  error: string | null;
 };
 
-export type MobileRelayBridgeState = {
-  status: "idle" | "starting" | "pairing" | "connected" | "reconnecting" | "error";
-  workspaceId: string | null;
-  workspacePath: string | null;
-  relaySource: "direct" | "managed" | "remodex" | "override" | "unavailable";
-  relaySourceMessage: string | null;
-  relayServiceStatus: "unknown" | "running" | "not-running" | "unavailable";
-  relayServiceMessage: string | null;
-  relayServiceUpdatedAt: string | null;
-  relayUrl: string | null;
-  sessionId: string | null;
-  pairingPayload: {
-    v: 1;
-    scheme: "h3";
-    hosts: string[];
-    port: number;
-    certSha256: string;
-    spkiSha256: string;
-    identityPub: string;
-    nonce: string;
-    expiresAt: number;
-  } | null;
-  trustedPhoneDeviceId: string | null;
-  trustedPhoneFingerprint: string | null;
-  trustedPhoneDevices: MobileRelayTrustedPhoneDevice[];
-  directUrl: string | null;
-  ticketUrl: string | null;
-  certSha256: string | null;
-  spkiSha256: string | null;
-  hostHints: string[];
-  lastError: string | null;
+export type ScheduledTask = {
+  id: string;
+  workspaceId: string;
+  name: string;
+  /** Five-field cron expression or macro such as `@daily`, in local time. */
+  cronExpr: string;
+  prompt: string;
+  enabled: boolean;
+  /** A run still going after this long is interrupted. */
+  timeoutMinutes: number;
+  createdAt: string;
+  /** Null while disabled or when the expression never matches again. */
+  nextRunAt: string | null;
+  lastRun: ScheduledTaskRun | null;
 };
 
-export type ReadTranscriptInput = {
+export type CreateScheduledTaskInput = {
+  workspaceId: string;
+  cronExpr: string;
+  prompt: string;
+  options?: {
+    /** Defaults to the first line of the prompt. */
+    name?: string;
+    enabled?: boolean;
+    /** Defaults to 30. */
+    timeoutMinutes?: number;
+  };
+};
+
+export type SetScheduledTaskEnabledInput = {
+  taskId: string;
+  enabled: boolean;
+};
+
+export type DeleteScheduledTaskInput = {
+  taskId: string;
+};
+
+/** A tool call a workspace server is waiting on the user to approve. */
+export type PendingApproval = {
+  /** The server's request id; decisions are sent back under it. */
+  id: string;
+  workspaceId: string;
+  threadId: string;
+  command: string;
+  dangerous: boolean;
+  reason: string;
+  detail?: string;
+  category?: "filesystem" | "network";
+  receivedAt: string;
+};
+
+export type ApprovalDecision = "approve" | "deny";
+
+export type RecordPendingApprovalInput = Omit<PendingApproval, "receivedAt">;
+
+export type RecordPendingApprovalResult = {
+  /** Set when a remembered allow-rule matched; the approval was not queued. */
+  autoApproved: boolean;
+};
+
+export type ListPendingApprovalsInput = {
+  workspaceId: string;
+};
+
+export type ResolveApprovalInput = {
+  id: string;
+  decision: ApprovalDecision;
+  /** Approves this exact command in the workspace from now on; ignored for denials. */
+  rememberRule?: boolean;
+};
+
+export type StartVoiceCaptureInput = {
+  /** The thread whose composer the dictation goes into. */
   threadId: string;
+  /** Container of the recorded chunks, as reported by `MediaRecorder`. */
+  mimeType: string;
 };
 
-export type DeleteTranscriptInput = {
+export type StartVoiceCaptureResult = {
+  captureId: string;
+};
+
+export type AppendVoiceAudioInput = {
+  captureId: string;
+  chunk: Uint8Array;
+};
+
+export type StopVoiceCaptureInput = {
+  /** Drops the recording without transcribing it. */
+  discard?: boolean;
+};
+
+/**
+ * Sent as `voiceTranscript` while dictating: interim text covers the audio so
+ * far and is replaced by later events; the final text covers the whole capture.
+ */
+export type VoiceTranscriptEvent = {
+  captureId: string;
   threadId: string;
+  text: string;
+  final: boolean;
 };
 
 export type TranscriptBatchInput = {
@@ -175,6 +1689,28 @@ export type TranscriptBatchInput = {
   payload: unknown;
 };
 
+export type OpenTranscriptFileInput = {
+  path: string;
+};
+
+export type ShareTranscriptInput = {
+  threadId: string;
+};
+
+/** An exported transcript loaded under a synthetic thread id that refuses appends. */
+export type OpenedTranscriptFile = {
+  threadId: string;
+  title: string;
+  sourcePath: string;
+  readOnly: true;
+  events: TranscriptEvent[];
+};
+
+export type TranscriptAppendedEvent = {
+  threadId: string;
+  events: TranscriptEvent[];
+};
+
 export type TranscriptCaptureResult =
   | {
       accepted: true;
@@ -257,6 +1793,28 @@ export type WatchWorkspaceDirectoryInput = {
   rootPath: string;
 };
 
+export type WorkspaceDirectoryWatchStatus = {
+  rootPath: string;
+  /** `polling` on network filesystems and after native watching fails. */
+  mode: "native" | "polling";
+  /** False while the last poll of the root failed. */
+  healthy: boolean;
+  /** Why the watch polls; null for native watches. */
+  reason: string | null;
+  /** Current adaptive poll interval; null for native watches. */
+  pollIntervalMs: number | null;
+  subscriberCount: number;
+  lastEventAt: string | null;
+  lastError: string | null;
+  /** When a native watch fell back to polling. */
+  downgradedAt: string | null;
+};
+
+export type WorkspaceWatcherStatus = {
+  workspaceId: string;
+  watches: WorkspaceDirectoryWatchStatus[];
+};
+
 export type OpenPathInput = {
   path: string;
 };
@@ -336,6 +1894,61 @@ export type AuthorizeUploadSourceInput = {
   sourcePath: string;
 };
 
+/**
+ * A file picked through `getPathForFile`, or bytes the renderer already holds
+ * such as a pasted screenshot.
+ */
+export type StoreAttachmentInput =
+  | { threadId: string; sourcePath: string; filename?: string }
+  | { threadId: string; bytes: Uint8Array; filename: string };
+
+/** A file in the attachment store, addressed by the sha256 of its contents. */
+export type StoredAttachment = {
+  hash: string;
+  sizeBytes: number;
+  /** Sniffed from the contents; `application/octet-stream` when unrecognised. */
+  mimeType: string;
+};
+
+export type StoreAttachmentResult = StoredAttachment & {
+  filename: string;
+  /** The same contents were already stored, so nothing new was written. */
+  deduplicated: boolean;
+};
+
+/** The transcript event recorded for a stored attachment, in place of its contents. */
+export type AttachmentReferencePayload = {
+  type: "attachment";
+  hash: string;
+  filename: string;
+  mimeType: string;
+  sizeBytes: number;
+};
+
+export type GetAttachmentInput = {
+  hash: string;
+};
+
+export type AttachmentContents = StoredAttachment & {
+  bytes: Uint8Array;
+};
+
+export type GetThumbnailInput = {
+  hash: string;
+  /** The longest edge of the preview, in pixels. Smaller images are not upscaled. */
+  maxDim: number;
+};
+
+/** A PNG preview of an image attachment, cached on disk by hash and size. */
+export type AttachmentThumbnail = {
+  hash: string;
+  maxDim: number;
+  width: number;
+  height: number;
+  path: string;
+  bytes: Uint8Array;
+};
+
 export type CreateDirectoryInput = {
   parentPath: string;
   name: string;
@@ -359,6 +1972,15 @@ export type ExplorerEntry = {
   modifiedAtMs: number | null;
 };
 
+/** A validated `cowork://` link, delivered once its workspace server is running. */
+export type DesktopDeepLinkNavigation =
+  | { kind: "workspace"; workspaceId: string }
+  | { kind: "thread"; workspaceId: string; threadId: string }
+  /** A folder the user agreed to open as a new workspace; it is already approved. */
+  | { kind: "openPath"; path: string }
+  /** An exported thread the OS asked the app to open, loaded into a read-only viewer thread. */
+  | { kind: "transcriptFile"; threadId: string; title: string; sourcePath: string };
+
 export type DesktopMenuCommand =
   | "newThread"
   | "toggleSidebar"
@@ -373,6 +1995,8 @@ export type ThemeSource = "system" | "light" | "dark";
 
 export type WindowsBackgroundMaterial = "auto" | "none" | "mica" | "acrylic" | "tabbed";
 
+export type SystemColorScheme = "dark" | "light" | "high-contrast";
+
 export type SystemAppearance = {
   platform: string;
   themeSource: ThemeSource;
@@ -382,6 +2006,27 @@ export type SystemAppearance = {
   shouldUseInvertedColorScheme: boolean;
   prefersReducedTransparency: boolean;
   inForcedColorsMode: boolean;
+  /** The scheme to render with once the theme source is applied; high contrast wins. */
+  colorScheme?: SystemColorScheme;
+  /** The OS accent color as `#rrggbb`; null where the platform does not expose one. */
+  accentColor?: string | null;
+};
+
+/** Locales with a bundled message catalog for main-process strings. */
+export type MessageLocale = "en" | "es" | "fr" | "de";
+
+export type SetLocaleInput = {
+  /** A BCP 47 tag such as `es-MX`; null follows the OS language. */
+  locale: string | null;
+};
+
+export type LocaleInfo = {
+  /** The saved override, or null when following the OS. */
+  requested: string | null;
+  systemLocale: string;
+  /** The bundle in use; tags without one fall back to English. */
+  resolved: MessageLocale;
+  supported: MessageLocale[];
 };
 
 export type ConfirmActionInput = {
@@ -438,6 +2083,29 @@ export type UpdaterState = {
   release: UpdaterReleaseInfo | null;
 };
 
+type SidecarUpdatePhase =
+  | "disabled"
+  | "idle"
+  | "checking"
+  | "available"
+  | "downloading"
+  | "staged"
+  | "up-to-date"
+  | "error";
+
+/** Updates to the bundled `cowork-server` binary, shipped separately from app updates. */
+export type SidecarUpdateState = {
+  phase: SidecarUpdatePhase;
+  /** Version new workspace servers launch with. */
+  currentVersion: string;
+  availableVersion: string | null;
+  /** Verified download that replaces the current binary on the next server start. */
+  stagedVersion: string | null;
+  progress: UpdaterProgress | null;
+  message: string | null;
+  error: string | null;
+};
+
 const desktopAppVersion = desktopPackage.version;
 
 export type DesktopCrashReportingConfig = {
@@ -533,6 +2201,98 @@ export type UploadDiagnosticsBundleOutput = {
   message: string;
 };
 
+/**
+ * Commands that can take many seconds, started in the background instead of
+ * held open on an invoke. `importAppData` still asks for the archive in a
+ * dialog and succeeds with a null result when it is canceled.
+ */
+export type StartOperationInput =
+  | { kind: "createDiagnosticsBundle" }
+  | ({ kind: "uploadDiagnosticsBundle" } & UploadDiagnosticsBundleInput)
+  | ({ kind: "prewarmWorkspaceServers" } & PrewarmWorkspaceServersInput)
+  | ({ kind: "importAppData" } & ImportAppDataInput)
+  | { kind: "encryptExistingTranscripts" }
+  | ({ kind: "migrateTranscriptStore" } & MigrateTranscriptStoreInput);
+
+/** Exports run one at a time on a queue, so a large archive never blocks the command that asked. */
+export type EnqueueExportInput =
+  | { kind: "appData"; includeTranscripts: boolean; destPath?: string }
+  | { kind: "diagnosticsBundle" };
+
+export type OperationKind = StartOperationInput["kind"] | "exportAppData" | "syncBackup";
+
+export type OperationStatus = "queued" | "running" | "succeeded" | "failed" | "cancelled";
+
+export type OperationProgress = {
+  completed: number;
+  total: number | null;
+  message: string | null;
+};
+
+/**
+ * `result` is only set for `succeeded` operations and `error` only for
+ * `failed` ones; a `cancelled` operation never carries either. `startedAt` is
+ * when a `queued` operation was accepted until it starts running.
+ */
+export type OperationSnapshot = {
+  operationId: string;
+  kind: OperationKind;
+  status: OperationStatus;
+  progress: OperationProgress | null;
+  result: unknown;
+  error: string | null;
+  startedAt: string;
+  finishedAt: string | null;
+};
+
+export type OperationIdInput = {
+  operationId: string;
+};
+
+/** An S3-compatible bucket (addressed path-style) or a WebDAV folder the user runs. */
+export type BackupTarget =
+  | { kind: "s3"; endpoint: string; bucket: string; region: string; prefix?: string }
+  | { kind: "webdav"; url: string };
+
+export type BackupCredentials =
+  | { kind: "s3"; accessKeyId: string; secretAccessKey: string }
+  | { kind: "webdav"; username: string; password: string };
+
+export type SaveBackupCredentialsInput = {
+  credentials: BackupCredentials;
+};
+
+/** Credentials stay in the main process; the renderer only holds this reference. */
+export type SaveBackupCredentialsResult = {
+  credentialsRef: string;
+};
+
+/** A null `target` turns backups off. */
+export type ConfigureBackupSyncInput =
+  | { target: BackupTarget; credentialsRef: string }
+  | { target: null };
+
+export type BackupSyncConfig = {
+  target: BackupTarget | null;
+  credentialsRef: string | null;
+  /** Names this installation's folder in the backup. */
+  deviceId: string;
+  lastSyncedAt: string | null;
+};
+
+/** The result of a `syncBackup` operation. */
+export type BackupSyncResult = {
+  /** Transcripts that had new lines to back up. */
+  transcripts: number;
+  segments: number;
+  uploadedChunks: number;
+  /** Chunks that were already in the backup and were not sent again. */
+  reusedChunks: number;
+  uploadedBytes: number;
+  stateUploaded: boolean;
+  syncedAt: string;
+};
+
 export function createDefaultUpdaterState(
   currentVersion = desktopAppVersion,
   packaged = false,
@@ -573,6 +2333,24 @@ export type PlatformChromeInfo = {
   disableCssBlur: boolean;
 };
 
+/**
+ * What the main process can serve, built from the IPC contract so a renderer
+ * bundle can degrade against an older or newer backend.
+ */
+export type DesktopBackendCapabilities = {
+  /** Bumped when a change to the contract cannot be detected from the lists below. */
+  contractVersion: number;
+  /** `DesktopApi` methods the main process handles. */
+  commands: string[];
+  /** Event channel names the main process may emit. */
+  events: string[];
+  /** Payload schema versions, listed only for commands and events past version 1. */
+  schemaVersions: {
+    commands: Record<string, number>;
+    events: Record<string, number>;
+  };
+};
+
 export interface DesktopApi {
   readonly features: DesktopFeatureFlags;
   readonly isPackaged?: boolean;
@@ -584,9 +2362,68 @@ export interface DesktopApi {
   createOneOffChatWorkspace(
     opts?: CreateOneOffChatWorkspaceInput,
   ): Promise<CreateOneOffChatWorkspaceOutput>;
-  startWorkspaceServer(opts: StartWorkspaceServerInput): Promise<{ url: string }>;
+  startWorkspaceServer(opts: StartWorkspaceServerInput): Promise<StartWorkspaceServerResult>;
+  /**
+   * Asks the user in a native dialog and resolves null when they decline;
+   * pass the token to `startWorkspaceServer`.
+   */
+  requestYoloGrant?(opts: RequestYoloGrantInput): Promise<YoloGrant | null>;
+  prewarmWorkspaceServers?(
+    opts: PrewarmWorkspaceServersInput,
+  ): Promise<PrewarmWorkspaceServersResult>;
   getWorkspaceServerStatus(opts: StopWorkspaceServerInput): Promise<WorkspaceServerStatus>;
+  getWorkspaceProxyUrl?(opts: StopWorkspaceServerInput): Promise<{ url: string }>;
+  generateHandoffLink?(opts: GenerateHandoffLinkInput): Promise<HandoffLink>;
+  /** Resolves false when the link was unknown, had expired, or its server has stopped. */
+  revokeHandoff?(opts: RevokeHandoffInput): Promise<boolean>;
   stopWorkspaceServer(opts: StopWorkspaceServerInput): Promise<void>;
+  releaseThreadServer?(opts: ReleaseThreadServerInput): Promise<void>;
+  getServerVersion?(opts: StopWorkspaceServerInput): Promise<WorkspaceServerVersionInfo>;
+  getServerStartupDiagnostics?(
+    opts: StopWorkspaceServerInput,
+  ): Promise<WorkspaceServerStartupDiagnostics | null>;
+  getServerStats?(opts: StopWorkspaceServerInput): Promise<WorkspaceServerStats>;
+  getWorkspaceHealth?(opts: StopWorkspaceServerInput): Promise<WorkspaceHealthReport>;
+  listOrphanedServers?(): Promise<OrphanedServer[]>;
+  cleanupOrphanedServers?(): Promise<OrphanedServerCleanupResult>;
+  readWorkspaceConfig?(opts: ReadWorkspaceConfigInput): Promise<WorkspaceConfigSnapshot>;
+  writeWorkspaceConfig?(opts: WriteWorkspaceConfigInput): Promise<WorkspaceConfigSnapshot>;
+  listAvailableModels?(opts: ListAvailableModelsInput): Promise<AvailableModelsResult>;
+  onWorkspaceConfigChanged?(listener: (event: WorkspaceConfigSnapshot) => void): () => void;
+  getWorkspaceGitStatus?(opts: StopWorkspaceServerInput): Promise<WorkspaceGitStatus>;
+  onWorkspaceGitChanged?(listener: (status: WorkspaceGitStatus) => void): () => void;
+  listWorkspaceCheckpoints?(opts: StopWorkspaceServerInput): Promise<WorkspaceCheckpointList>;
+  restoreWorkspaceCheckpoint?(
+    opts: RestoreWorkspaceCheckpointInput,
+  ): Promise<RestoreWorkspaceCheckpointResult>;
+  validateProviderCredentials?(
+    opts: ValidateProviderCredentialsInput,
+  ): Promise<ProviderCredentialValidation>;
+  getUsageSummary?(opts: GetUsageSummaryInput): Promise<UsageSummary>;
+  getAppSettings?(): Promise<AppSettings>;
+  updateAppSettings?(patch: UpdateAppSettingsInput): Promise<AppSettings>;
+  listProfiles?(): Promise<DesktopProfileList>;
+  createProfile?(opts: CreateProfileInput): Promise<DesktopProfile>;
+  /** Relaunches the app into the profile; resolves just before this instance quits. */
+  switchProfile?(opts: SwitchProfileInput): Promise<void>;
+  onAppSettingsChanged?(listener: (settings: AppSettings) => void): () => void;
+  previewTranscriptRetention?(
+    opts?: PreviewTranscriptRetentionInput,
+  ): Promise<TranscriptRetentionPreview>;
+  testProxyConnectivity?(opts: TestProxyConnectivityInput): Promise<ProxyConnectivityResult>;
+  onTranscriptRetentionCompleted?(
+    listener: (summary: TranscriptRetentionSummary) => void,
+  ): () => void;
+  flushBufferedTranscripts?(): Promise<DiskSpaceStatus>;
+  onLowDiskSpace?(listener: (status: DiskSpaceStatus) => void): () => void;
+  onTranscriptThrottled?(listener: (event: TranscriptThrottledEvent) => void): () => void;
+  onVoiceTranscript?(listener: (event: VoiceTranscriptEvent) => void): () => void;
+  getConnectivityStatus?(): Promise<ConnectivityStatus>;
+  verifyInstallation?(): Promise<InstallationIntegrityReport>;
+  getOnboardingStatus?(): Promise<DesktopOnboardingStatus>;
+  runEnvironmentChecks?(): Promise<EnvironmentCheckReport>;
+  onConnectivityChanged?(listener: (status: ConnectivityStatus) => void): () => void;
+  onScheduledTaskRunCompleted?(listener: (run: ScheduledTaskRun) => void): () => void;
   startMobileRelay(opts: MobileRelayStartInput): Promise<MobileRelayBridgeState>;
   stopMobileRelay(): Promise<MobileRelayBridgeState>;
   getMobileRelayState(): Promise<MobileRelayBridgeState>;
@@ -599,18 +2436,116 @@ export interface DesktopApi {
     opts: MobileRelayUpdateTrustedPhonePermissionsInput,
   ): Promise<MobileRelayBridgeState>;
   loadState(): Promise<PersistedState>;
-  saveState(state: PersistedState): Promise<void>;
+  /** Resolves void in builds without state generation checks. */
+  saveState(state: PersistedState): Promise<SaveStateResult | void>;
+  listStateSnapshots?(): Promise<StateSnapshotInfo[]>;
+  restoreStateSnapshot?(opts: RestoreStateSnapshotInput): Promise<PersistedState>;
+  rebuildStateFromTranscripts?(opts: RebuildStateInput): Promise<StateRebuildReport>;
+  /** Resolves null when the save dialog is canceled. */
+  exportAppData?(opts: ExportAppDataInput): Promise<ExportAppDataResult | null>;
+  /** Resolves null when the open dialog is canceled. */
+  importAppData?(opts: ImportAppDataInput): Promise<ImportAppDataResult | null>;
   captureProductEvent(input: CaptureProductEventInput): Promise<void>;
+  createScratchThread?(opts: CreateScratchThreadInput): Promise<ScratchThreadInfo>;
+  persistScratchThread?(opts: PersistScratchThreadInput): Promise<ThreadRecord>;
   readTranscript(opts: ReadTranscriptInput): Promise<TranscriptEvent[]>;
+  readTranscriptPage?(opts: ReadTranscriptPageInput): Promise<TranscriptPage>;
+  getThreadPreviews?(opts: GetThreadPreviewsInput): Promise<ThreadPreview[]>;
+  inspectTranscript?(opts: ReadTranscriptInput): Promise<TranscriptInspection>;
+  repairTranscript?(opts: ReadTranscriptInput): Promise<TranscriptRepairResult>;
+  normalizeTranscriptTimestamps?(
+    opts: ReadTranscriptInput,
+  ): Promise<TranscriptTimestampNormalizationResult>;
+  redactTranscript?(opts: RedactTranscriptInput): Promise<TranscriptRedactionResult>;
+  archiveThread?(opts: ArchiveThreadInput): Promise<ThreadRecord>;
+  unarchiveThread?(opts: ArchiveThreadInput): Promise<ThreadRecord>;
+  forkThread?(opts: ForkThreadInput): Promise<ThreadRecord>;
+  mergeThreads?(opts: MergeThreadsInput): Promise<MergeThreadsResult>;
+  suggestThreadTitle?(opts: SuggestThreadTitleInput): Promise<ThreadRecord>;
+  copyTranscriptSelection?(
+    opts: CopyTranscriptSelectionInput,
+  ): Promise<CopyTranscriptSelectionResult>;
+  getThreadFileChanges?(opts: GetThreadFileChangesInput): Promise<ThreadFileChanges>;
+  startReplay?(opts: StartReplayInput): Promise<ReplaySession>;
+  stopReplay?(opts: StopReplayInput): Promise<void>;
+  getTranscriptEncryption?(): Promise<TranscriptEncryptionStatus>;
+  setTranscriptEncryption?(opts: SetTranscriptEncryptionInput): Promise<TranscriptEncryptionStatus>;
+  encryptExistingTranscripts?(): Promise<TranscriptEncryptionMigrationResult>;
+  /** Moves every live transcript into `target` and makes it the store new events go to. */
+  migrateTranscriptStore?(
+    opts: MigrateTranscriptStoreInput,
+  ): Promise<TranscriptStoreMigrationResult>;
+  openTranscriptFile?(opts: OpenTranscriptFileInput): Promise<OpenedTranscriptFile>;
+  /** Exports the thread and offers it through the native share sheet (macOS only). */
+  shareTranscript?(opts: ShareTranscriptInput): Promise<void>;
   hydrateTranscript(opts: ReadTranscriptInput): Promise<HydratedTranscriptSnapshot>;
   appendTranscriptEvent(opts: TranscriptBatchInput): Promise<void>;
   captureTranscriptEvent?(event: TranscriptBatchInput): Promise<TranscriptCaptureResult>;
   appendTranscriptBatch(events: TranscriptBatchInput[]): Promise<void>;
+  subscribeTranscript?(opts: ReadTranscriptInput): Promise<void>;
+  unsubscribeTranscript?(opts: ReadTranscriptInput): Promise<void>;
   onTranscriptDeliveryFailure?(listener: (failure: TranscriptDeliveryFailure) => void): () => void;
   retryTranscriptDelivery?(batchId?: string): Promise<void>;
   discardTranscriptBatch?(batchId: string): Promise<void>;
-  deleteTranscript(opts: DeleteTranscriptInput): Promise<void>;
+  /** Resolves void in builds without confirmation challenges. */
+  deleteTranscript(opts: DeleteTranscriptInput): Promise<DestructiveCommandResult | void>;
+  purgeThreads?(opts: PurgeThreadsInput): Promise<DestructiveCommandResult>;
+  deleteWorkspace?(opts: DeleteWorkspaceInput): Promise<DestructiveCommandResult>;
+  getWorkspaceStorageReport?(opts: GetWorkspaceStorageReportInput): Promise<WorkspaceStorageReport>;
+  cleanupWorkspaceStorage?(
+    opts: CleanupWorkspaceStorageInput,
+  ): Promise<CleanupWorkspaceStorageResult>;
+  /** Newest first. */
+  listTrash?(): Promise<TrashEntry[]>;
+  restoreFromTrash?(opts: RestoreFromTrashInput): Promise<RestoreFromTrashResult>;
+  setWorkspacePinned?(opts: SetWorkspacePinnedInput): Promise<WorkspaceRecord>;
+  setWorkspaceTags?(opts: SetWorkspaceTagsInput): Promise<WorkspaceRecord>;
+  setWorkspaceLaunchProfiles?(opts: SetWorkspaceLaunchProfilesInput): Promise<WorkspaceRecord>;
+  setWorkspaceIdlePolicy?(opts: SetWorkspaceIdlePolicyInput): Promise<WorkspaceRecord>;
+  setWorkspacePathPolicy?(opts: SetWorkspacePathPolicyInput): Promise<WorkspaceRecord>;
+  previewPathPolicy?(opts: PreviewPathPolicyInput): Promise<PathPolicyPreview>;
+  duplicateWorkspace?(opts: DuplicateWorkspaceInput): Promise<DuplicateWorkspaceResult>;
+  /** Resolves the workspaces in their new order. */
+  reorderWorkspaces?(opts: ReorderWorkspacesInput): Promise<WorkspaceRecord[]>;
+  /** Workspaces, threads, prompt templates, and messages matching the query, best match first. */
+  globalSearch?(opts: GlobalSearchInput): Promise<GlobalSearchResult[]>;
+  /** Resolves null when the annotation was removed. */
+  annotateEvent?(opts: AnnotateEventInput): Promise<TranscriptAnnotation | null>;
+  /** Ordered by event index. */
+  listAnnotations?(opts: ListAnnotationsInput): Promise<TranscriptAnnotation[]>;
+  /** Writes are debounced; resolves with the draft to be written, or null when it was cleared. */
+  saveThreadDraft?(opts: SaveThreadDraftInput): Promise<ThreadDraft | null>;
+  /** Resolves null when the thread has no draft. */
+  loadThreadDraft?(opts: LoadThreadDraftInput): Promise<ThreadDraft | null>;
+  /** Global templates plus the workspace's, ordered by name. */
+  listPromptTemplates?(opts: ListPromptTemplatesInput): Promise<PromptTemplate[]>;
+  savePromptTemplate?(opts: SavePromptTemplateInput): Promise<PromptTemplate>;
+  deletePromptTemplate?(opts: DeletePromptTemplateInput): Promise<void>;
+  /** Rejects with a `prompt_variables_missing` error naming any variable without a value. */
+  renderPrompt?(opts: RenderPromptInput): Promise<RenderPromptResult>;
+  /** Newest first. */
+  listScheduledTasks?(): Promise<ScheduledTask[]>;
+  createScheduledTask?(opts: CreateScheduledTaskInput): Promise<ScheduledTask>;
+  setScheduledTaskEnabled?(opts: SetScheduledTaskEnabledInput): Promise<ScheduledTask>;
+  deleteScheduledTask?(opts: DeleteScheduledTaskInput): Promise<void>;
+  /** Queues an approval the renderer received, unless an allow-rule already covers it. */
+  recordPendingApproval?(opts: RecordPendingApprovalInput): Promise<RecordPendingApprovalResult>;
+  /** Oldest first. */
+  listPendingApprovals?(opts: ListPendingApprovalsInput): Promise<PendingApproval[]>;
+  /** Dequeues the approval; resolves null when it was no longer pending. */
+  resolveApproval?(opts: ResolveApprovalInput): Promise<PendingApproval | null>;
+  /** Starts a dictation capture, ending any other one without transcribing it. */
+  startVoiceCapture?(opts: StartVoiceCaptureInput): Promise<StartVoiceCaptureResult>;
+  appendVoiceAudio?(opts: AppendVoiceAudioInput): Promise<void>;
+  /** Resolves the final transcript; null when nothing was recording or it was discarded. */
+  stopVoiceCapture?(opts: StopVoiceCaptureInput): Promise<VoiceTranscriptEvent | null>;
   pickWorkspaceDirectory(): Promise<string | null>;
+  /** Resolves a folder dropped onto the window; null when `file` is not a dropped OS file. */
+  resolveDroppedWorkspaceFolder?(file: unknown): Promise<DroppedWorkspaceFolder | null>;
+  /** Checks an `ssh://` workspace over SSH and approves it; resolves its canonical URL. */
+  registerRemoteWorkspace?(opts: RegisterRemoteWorkspaceInput): Promise<string>;
+  /** Finds git repositories and `.cowork` folders that are not yet workspaces. */
+  scanForWorkspaces?(opts: ScanForWorkspacesInput): Promise<ScanForWorkspacesResult>;
   pickDirectory(opts?: PickDirectoryInput): Promise<string | null>;
   showContextMenu(opts: ShowContextMenuInput): Promise<string | null>;
   windowMinimize(): Promise<void>;
@@ -628,6 +2563,7 @@ export interface DesktopApi {
   listDirectory(opts: ListDirectoryInput): Promise<ExplorerEntry[]>;
   watchWorkspaceDirectory(opts: WatchWorkspaceDirectoryInput): Promise<boolean>;
   unwatchWorkspaceDirectory(opts: WatchWorkspaceDirectoryInput): Promise<void>;
+  getWatcherStatus?(opts: StopWorkspaceServerInput): Promise<WorkspaceWatcherStatus>;
   readFile(opts: ReadFileInput): Promise<ReadFileOutput>;
   writeFile(opts: WriteFileInput): Promise<void>;
   readFileForPreview(opts: ReadFileForPreviewInput): Promise<ReadFileForPreviewOutput>;
@@ -644,43 +2580,118 @@ export interface DesktopApi {
   copyFileToWorkspaceUploads?(
     opts: CopyFileToWorkspaceUploadsInput,
   ): Promise<CopyFileToWorkspaceUploadsOutput>;
+  /** Stores the file by content hash and records a reference in the thread's transcript. */
+  storeAttachment?(opts: StoreAttachmentInput): Promise<StoreAttachmentResult>;
+  /** Resolves null when no attachment has this hash. */
+  getAttachment?(opts: GetAttachmentInput): Promise<AttachmentContents | null>;
+  /** Resolves null for unknown hashes and attachments that are not decodable images. */
+  getThumbnail?(opts: GetThumbnailInput): Promise<AttachmentThumbnail | null>;
   createDirectory(opts: CreateDirectoryInput): Promise<void>;
   renamePath(opts: RenamePathInput): Promise<void>;
   trashPath(opts: TrashPathInput): Promise<void>;
   confirmAction(opts: ConfirmActionInput): Promise<boolean>;
   showNotification(opts: DesktopNotificationInput): Promise<boolean>;
   writeRendererLog(opts: RendererLogInput): Promise<void>;
+  setLogLevel?(opts: SetLogLevelInput): Promise<void>;
+  readAppLogs?(opts?: ReadAppLogsInput): Promise<AppLogsResult>;
   createDiagnosticsBundle(): Promise<CreateDiagnosticsBundleOutput>;
   revealDiagnosticsBundle(opts: DiagnosticsBundlePathInput): Promise<void>;
   openLogsFolder(): Promise<void>;
   uploadDiagnosticsBundle(
     opts: UploadDiagnosticsBundleInput,
   ): Promise<UploadDiagnosticsBundleOutput>;
+  startOperation?(opts: StartOperationInput): Promise<OperationSnapshot>;
+  getOperation?(opts: OperationIdInput): Promise<OperationSnapshot | null>;
+  /** Unfinished operations and the most recently finished ones, in the order they started. */
+  listOperations?(): Promise<OperationSnapshot[]>;
+  cancelOperation?(opts: OperationIdInput): Promise<boolean>;
+  /** Resolves null when the save dialog is canceled; follow the job through export events. */
+  enqueueExport?(opts: EnqueueExportInput): Promise<OperationSnapshot | null>;
+  saveBackupCredentials?(opts: SaveBackupCredentialsInput): Promise<SaveBackupCredentialsResult>;
+  configureBackupSync?(opts: ConfigureBackupSyncInput): Promise<BackupSyncConfig>;
+  getBackupSyncConfig?(): Promise<BackupSyncConfig>;
+  /** Starts a `syncBackup` operation; one already running is joined rather than repeated. */
+  syncBackupNow?(): Promise<OperationSnapshot>;
   getTelemetryStatus(opts?: TelemetryStatusInput): Promise<TelemetryStatusSnapshot>;
   getUpdateState(): Promise<UpdaterState>;
   checkForUpdates(): Promise<void>;
   quitAndInstallUpdate(): Promise<void>;
+  getSidecarUpdateState?(): Promise<SidecarUpdateState>;
+  applySidecarUpdate?(): Promise<SidecarUpdateState>;
   getSystemAppearance(): Promise<SystemAppearance>;
   getPlatformChrome(): Promise<PlatformChromeInfo>;
   setWindowAppearance(opts: SetWindowAppearanceInput): Promise<SystemAppearance>;
+  setLocale?(opts: SetLocaleInput): Promise<LocaleInfo>;
+  /** Missing on backends that predate capability reporting. */
+  getBackendCapabilities?(): Promise<DesktopBackendCapabilities>;
   onUpdateStateChanged(listener: (state: UpdaterState) => void): () => void;
+  onSidecarUpdateStateChanged?(listener: (state: SidecarUpdateState) => void): () => void;
   onWorkspaceServerStartupProgress(
     listener: (event: WorkspaceServerStartupProgress) => void,
   ): () => void;
+  onWorkspaceServerPrewarmProgress?(
+    listener: (event: WorkspaceServerPrewarmProgress) => void,
+  ): () => void;
   onWorkspaceServerExited(listener: (event: WorkspaceServerExitedEvent) => void): () => void;
+  onServerSuspended?(listener: (event: ServerSuspendedEvent) => void): () => void;
+  onServerResumed?(listener: (event: ServerResumedEvent) => void): () => void;
+  onServerReloaded?(listener: (event: ServerReloadedEvent) => void): () => void;
+  onWorkspaceProxyConnectionChanged?(
+    listener: (event: WorkspaceProxyConnectionEvent) => void,
+  ): () => void;
   onWindowCloseRequested?(listener: (request: WindowCloseRequest) => void): () => void;
   onPreviewFileChanged?(listener: (event: PreviewFileChangeEvent) => void): () => void;
   onSystemAppearanceChanged(listener: (appearance: SystemAppearance) => void): () => void;
   onMenuCommand(listener: (command: DesktopMenuCommand) => void): () => void;
+  onDeepLinkNavigation?(listener: (navigation: DesktopDeepLinkNavigation) => void): () => void;
   onMobileRelayStateChanged(listener: (state: MobileRelayBridgeState) => void): () => void;
   onWorkspaceFileChanged(listener: (event: WorkspaceFileChangeEvent) => void): () => void;
+  onOperationUpdated?(listener: (snapshot: OperationSnapshot) => void): () => void;
+  /** Export jobs while they are queued or running. */
+  onExportProgress?(listener: (snapshot: OperationSnapshot) => void): () => void;
+  /** Export jobs once they succeed, fail, or are cancelled. */
+  onExportComplete?(listener: (snapshot: OperationSnapshot) => void): () => void;
+  onTranscriptAppended?(listener: (event: TranscriptAppendedEvent) => void): () => void;
+  onServerStats?(listener: (event: ServerStatsEvent) => void): () => void;
 }
 
 export const DESKTOP_IPC_CHANNELS = {
   createOneOffChatWorkspace: "desktop:createOneOffChatWorkspace",
   startWorkspaceServer: "desktop:startWorkspaceServer",
+  requestYoloGrant: "desktop:requestYoloGrant",
+  prewarmWorkspaceServers: "desktop:prewarmWorkspaceServers",
   getWorkspaceServerStatus: "desktop:getWorkspaceServerStatus",
+  getWorkspaceProxyUrl: "desktop:getWorkspaceProxyUrl",
+  generateHandoffLink: "desktop:generateHandoffLink",
+  revokeHandoff: "desktop:revokeHandoff",
   stopWorkspaceServer: "desktop:stopWorkspaceServer",
+  releaseThreadServer: "desktop:releaseThreadServer",
+  getServerVersion: "desktop:getServerVersion",
+  getServerStartupDiagnostics: "desktop:getServerStartupDiagnostics",
+  getServerStats: "desktop:getServerStats",
+  getWorkspaceHealth: "desktop:getWorkspaceHealth",
+  listOrphanedServers: "desktop:listOrphanedServers",
+  cleanupOrphanedServers: "desktop:cleanupOrphanedServers",
+  readWorkspaceConfig: "desktop:readWorkspaceConfig",
+  writeWorkspaceConfig: "desktop:writeWorkspaceConfig",
+  listAvailableModels: "desktop:listAvailableModels",
+  getWorkspaceGitStatus: "desktop:getWorkspaceGitStatus",
+  listWorkspaceCheckpoints: "desktop:listWorkspaceCheckpoints",
+  restoreWorkspaceCheckpoint: "desktop:restoreWorkspaceCheckpoint",
+  validateProviderCredentials: "desktop:validateProviderCredentials",
+  getUsageSummary: "desktop:getUsageSummary",
+  getAppSettings: "desktop:getAppSettings",
+  updateAppSettings: "desktop:updateAppSettings",
+  listProfiles: "desktop:listProfiles",
+  createProfile: "desktop:createProfile",
+  switchProfile: "desktop:switchProfile",
+  previewTranscriptRetention: "desktop:previewTranscriptRetention",
+  testProxyConnectivity: "desktop:testProxyConnectivity",
+  flushBufferedTranscripts: "desktop:flushBufferedTranscripts",
+  getConnectivityStatus: "desktop:getConnectivityStatus",
+  verifyInstallation: "desktop:verifyInstallation",
+  getOnboardingStatus: "desktop:getOnboardingStatus",
+  runEnvironmentChecks: "desktop:runEnvironmentChecks",
   mobileRelayStart: "desktop:mobileRelayStart",
   mobileRelayStop: "desktop:mobileRelayStop",
   mobileRelayGetState: "desktop:mobileRelayGetState",
@@ -690,13 +2701,79 @@ export const DESKTOP_IPC_CHANNELS = {
   mobileRelayUpdateTrustedPhonePermissions: "desktop:mobileRelayUpdateTrustedPhonePermissions",
   loadState: "desktop:loadState",
   saveState: "desktop:saveState",
+  listStateSnapshots: "desktop:listStateSnapshots",
+  restoreStateSnapshot: "desktop:restoreStateSnapshot",
+  rebuildStateFromTranscripts: "desktop:rebuildStateFromTranscripts",
+  exportAppData: "desktop:exportAppData",
+  importAppData: "desktop:importAppData",
   captureProductEvent: "desktop:captureProductEvent",
+  createScratchThread: "desktop:createScratchThread",
+  persistScratchThread: "desktop:persistScratchThread",
   readTranscript: "desktop:readTranscript",
+  readTranscriptPage: "desktop:readTranscriptPage",
+  getThreadPreviews: "desktop:getThreadPreviews",
+  inspectTranscript: "desktop:inspectTranscript",
+  repairTranscript: "desktop:repairTranscript",
+  normalizeTranscriptTimestamps: "desktop:normalizeTranscriptTimestamps",
+  redactTranscript: "desktop:redactTranscript",
+  archiveThread: "desktop:archiveThread",
+  unarchiveThread: "desktop:unarchiveThread",
+  forkThread: "desktop:forkThread",
+  mergeThreads: "desktop:mergeThreads",
+  suggestThreadTitle: "desktop:suggestThreadTitle",
+  copyTranscriptSelection: "desktop:copyTranscriptSelection",
+  getThreadFileChanges: "desktop:getThreadFileChanges",
+  startReplay: "desktop:startReplay",
+  stopReplay: "desktop:stopReplay",
+  getTranscriptEncryption: "desktop:getTranscriptEncryption",
+  setTranscriptEncryption: "desktop:setTranscriptEncryption",
+  encryptExistingTranscripts: "desktop:encryptExistingTranscripts",
+  migrateTranscriptStore: "desktop:migrateTranscriptStore",
+  openTranscriptFile: "desktop:openTranscriptFile",
+  shareTranscript: "desktop:shareTranscript",
   hydrateTranscript: "desktop:hydrateTranscript",
   appendTranscriptEvent: "desktop:appendTranscriptEvent",
   appendTranscriptBatch: "desktop:appendTranscriptBatch",
+  subscribeTranscript: "desktop:subscribeTranscript",
+  unsubscribeTranscript: "desktop:unsubscribeTranscript",
   deleteTranscript: "desktop:deleteTranscript",
+  purgeThreads: "desktop:purgeThreads",
+  deleteWorkspace: "desktop:deleteWorkspace",
+  getWorkspaceStorageReport: "desktop:getWorkspaceStorageReport",
+  cleanupWorkspaceStorage: "desktop:cleanupWorkspaceStorage",
+  listTrash: "desktop:listTrash",
+  restoreFromTrash: "desktop:restoreFromTrash",
+  setWorkspacePinned: "desktop:setWorkspacePinned",
+  setWorkspaceTags: "desktop:setWorkspaceTags",
+  setWorkspaceLaunchProfiles: "desktop:setWorkspaceLaunchProfiles",
+  setWorkspaceIdlePolicy: "desktop:setWorkspaceIdlePolicy",
+  setWorkspacePathPolicy: "desktop:setWorkspacePathPolicy",
+  previewPathPolicy: "desktop:previewPathPolicy",
+  duplicateWorkspace: "desktop:duplicateWorkspace",
+  reorderWorkspaces: "desktop:reorderWorkspaces",
+  globalSearch: "desktop:globalSearch",
+  annotateEvent: "desktop:annotateEvent",
+  listAnnotations: "desktop:listAnnotations",
+  saveThreadDraft: "desktop:saveThreadDraft",
+  loadThreadDraft: "desktop:loadThreadDraft",
+  listPromptTemplates: "desktop:listPromptTemplates",
+  savePromptTemplate: "desktop:savePromptTemplate",
+  deletePromptTemplate: "desktop:deletePromptTemplate",
+  renderPrompt: "desktop:renderPrompt",
+  listScheduledTasks: "desktop:listScheduledTasks",
+  createScheduledTask: "desktop:createScheduledTask",
+  setScheduledTaskEnabled: "desktop:setScheduledTaskEnabled",
+  deleteScheduledTask: "desktop:deleteScheduledTask",
+  recordPendingApproval: "desktop:recordPendingApproval",
+  listPendingApprovals: "desktop:listPendingApprovals",
+  resolveApproval: "desktop:resolveApproval",
+  startVoiceCapture: "desktop:startVoiceCapture",
+  appendVoiceAudio: "desktop:appendVoiceAudio",
+  stopVoiceCapture: "desktop:stopVoiceCapture",
   pickWorkspaceDirectory: "desktop:pickWorkspaceDirectory",
+  resolveDroppedWorkspaceFolder: "desktop:resolveDroppedWorkspaceFolder",
+  registerRemoteWorkspace: "desktop:registerRemoteWorkspace",
+  scanForWorkspaces: "desktop:scanForWorkspaces",
   pickDirectory: "desktop:pickDirectory",
   showContextMenu: "desktop:showContextMenu",
   windowMinimize: "desktop:windowMinimize",
@@ -709,12 +2786,14 @@ export const DESKTOP_IPC_CHANNELS = {
   getPlatform: "desktop:getPlatform",
   showMainWindow: "desktop:showMainWindow",
   consumePendingMenuCommands: "desktop:consumePendingMenuCommands",
+  consumePendingDeepLinks: "desktop:consumePendingDeepLinks",
   showQuickChatWindow: "desktop:showQuickChatWindow",
   showCanvasWindow: "desktop:showCanvasWindow",
 
   listDirectory: "desktop:listDirectory",
   watchWorkspaceDirectory: "desktop:watchWorkspaceDirectory",
   unwatchWorkspaceDirectory: "desktop:unwatchWorkspaceDirectory",
+  getWatcherStatus: "desktop:getWatcherStatus",
   readFile: "desktop:readFile",
   writeFile: "desktop:writeFile",
   readFileForPreview: "desktop:readFileForPreview",
@@ -729,33 +2808,72 @@ export const DESKTOP_IPC_CHANNELS = {
   copyText: "desktop:copyText",
   copyFileToWorkspaceUploads: "desktop:copyFileToWorkspaceUploads",
   authorizeUploadSource: "desktop:authorizeUploadSource",
+  storeAttachment: "desktop:storeAttachment",
+  getAttachment: "desktop:getAttachment",
+  getThumbnail: "desktop:getThumbnail",
   createDirectory: "desktop:createDirectory",
   renamePath: "desktop:renamePath",
   trashPath: "desktop:trashPath",
   confirmAction: "desktop:confirmAction",
   showNotification: "desktop:showNotification",
   writeRendererLog: "desktop:writeRendererLog",
+  setLogLevel: "desktop:setLogLevel",
+  readAppLogs: "desktop:readAppLogs",
   createDiagnosticsBundle: "desktop:createDiagnosticsBundle",
   revealDiagnosticsBundle: "desktop:revealDiagnosticsBundle",
   openLogsFolder: "desktop:openLogsFolder",
   uploadDiagnosticsBundle: "desktop:uploadDiagnosticsBundle",
+  startOperation: "desktop:startOperation",
+  getOperation: "desktop:getOperation",
+  listOperations: "desktop:listOperations",
+  cancelOperation: "desktop:cancelOperation",
+  enqueueExport: "desktop:enqueueExport",
+  saveBackupCredentials: "desktop:saveBackupCredentials",
+  configureBackupSync: "desktop:configureBackupSync",
+  getBackupSyncConfig: "desktop:getBackupSyncConfig",
+  syncBackupNow: "desktop:syncBackupNow",
   getTelemetryStatus: "desktop:getTelemetryStatus",
   getUpdateState: "desktop:getUpdateState",
   checkForUpdates: "desktop:checkForUpdates",
   quitAndInstallUpdate: "desktop:quitAndInstallUpdate",
+  getSidecarUpdateState: "desktop:getSidecarUpdateState",
+  applySidecarUpdate: "desktop:applySidecarUpdate",
   getSystemAppearance: "desktop:getSystemAppearance",
   getPlatformChrome: "desktop:getPlatformChrome",
   setWindowAppearance: "desktop:setWindowAppearance",
+  setLocale: "desktop:setLocale",
+  getBackendCapabilities: "desktop:getBackendCapabilities",
 } as const;
 
 export const DESKTOP_EVENT_CHANNELS = {
   menuCommand: "desktop:event:menuCommand",
+  deepLinkNavigation: "desktop:event:deepLinkNavigation",
   updateStateChanged: "desktop:event:updateState",
+  sidecarUpdateStateChanged: "desktop:event:sidecarUpdateState",
   workspaceServerStartupProgress: "desktop:event:workspaceServerStartupProgress",
+  workspaceServerPrewarmProgress: "desktop:event:workspaceServerPrewarmProgress",
   workspaceServerExited: "desktop:event:workspaceServerExited",
+  workspaceProxyConnectionChanged: "desktop:event:workspaceProxyConnectionChanged",
   windowCloseRequested: "desktop:event:windowCloseRequested",
   previewFileChanged: "desktop:event:previewFileChanged",
   systemAppearanceChanged: "desktop:event:systemAppearanceChanged",
   mobileRelayStateChanged: "desktop:event:mobileRelayStateChanged",
   workspaceFileChanged: "desktop:event:workspaceFileChanged",
+  operationUpdated: "desktop:event:operationUpdated",
+  exportProgress: "desktop:event:exportProgress",
+  exportComplete: "desktop:event:exportComplete",
+  transcriptAppended: "desktop:event:transcriptAppended",
+  serverStats: "desktop:event:serverStats",
+  workspaceConfigChanged: "desktop:event:workspaceConfigChanged",
+  workspaceGitChanged: "desktop:event:workspaceGitChanged",
+  appSettingsChanged: "desktop:event:appSettingsChanged",
+  transcriptRetentionCompleted: "desktop:event:transcriptRetentionCompleted",
+  lowDiskSpace: "desktop:event:lowDiskSpace",
+  connectivityChanged: "desktop:event:connectivityChanged",
+  scheduledTaskRunCompleted: "desktop:event:scheduledTaskRunCompleted",
+  serverSuspended: "desktop:event:serverSuspended",
+  serverResumed: "desktop:event:serverResumed",
+  serverReloaded: "desktop:event:serverReloaded",
+  transcriptThrottled: "desktop:event:transcriptThrottled",
+  voiceTranscript: "desktop:event:voiceTranscript",
 } as const;
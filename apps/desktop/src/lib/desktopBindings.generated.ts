@@ -0,0 +1,444 @@
+// Generated by apps/desktop/scripts/generateDesktopBindings.ts. Do not edit by hand.
+
+import { DESKTOP_EVENT_CHANNELS, DESKTOP_IPC_CHANNELS } from "./desktopApi";
+import type { DesktopEventPayload, DesktopIpcArgs, DesktopIpcResult } from "./desktopIpcContract";
+
+export type DesktopIpcInvoke = (channel: string, ...args: unknown[]) => Promise<unknown>;
+export type DesktopEventSubscribe = (
+  channel: string,
+  listener: (payload: unknown) => void,
+) => () => void;
+
+export function createDesktopIpcBindings(invoke: DesktopIpcInvoke) {
+  return {
+    createOneOffChatWorkspace: (...args: DesktopIpcArgs<"createOneOffChatWorkspace">) =>
+      invoke(DESKTOP_IPC_CHANNELS.createOneOffChatWorkspace, ...args) as Promise<DesktopIpcResult<"createOneOffChatWorkspace">>,
+    startWorkspaceServer: (...args: DesktopIpcArgs<"startWorkspaceServer">) =>
+      invoke(DESKTOP_IPC_CHANNELS.startWorkspaceServer, ...args) as Promise<DesktopIpcResult<"startWorkspaceServer">>,
+    requestYoloGrant: (...args: DesktopIpcArgs<"requestYoloGrant">) =>
+      invoke(DESKTOP_IPC_CHANNELS.requestYoloGrant, ...args) as Promise<DesktopIpcResult<"requestYoloGrant">>,
+    prewarmWorkspaceServers: (...args: DesktopIpcArgs<"prewarmWorkspaceServers">) =>
+      invoke(DESKTOP_IPC_CHANNELS.prewarmWorkspaceServers, ...args) as Promise<DesktopIpcResult<"prewarmWorkspaceServers">>,
+    getWorkspaceServerStatus: (...args: DesktopIpcArgs<"getWorkspaceServerStatus">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getWorkspaceServerStatus, ...args) as Promise<DesktopIpcResult<"getWorkspaceServerStatus">>,
+    getWorkspaceProxyUrl: (...args: DesktopIpcArgs<"getWorkspaceProxyUrl">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getWorkspaceProxyUrl, ...args) as Promise<DesktopIpcResult<"getWorkspaceProxyUrl">>,
+    generateHandoffLink: (...args: DesktopIpcArgs<"generateHandoffLink">) =>
+      invoke(DESKTOP_IPC_CHANNELS.generateHandoffLink, ...args) as Promise<DesktopIpcResult<"generateHandoffLink">>,
+    revokeHandoff: (...args: DesktopIpcArgs<"revokeHandoff">) =>
+      invoke(DESKTOP_IPC_CHANNELS.revokeHandoff, ...args) as Promise<DesktopIpcResult<"revokeHandoff">>,
+    stopWorkspaceServer: (...args: DesktopIpcArgs<"stopWorkspaceServer">) =>
+      invoke(DESKTOP_IPC_CHANNELS.stopWorkspaceServer, ...args) as Promise<DesktopIpcResult<"stopWorkspaceServer">>,
+    releaseThreadServer: (...args: DesktopIpcArgs<"releaseThreadServer">) =>
+      invoke(DESKTOP_IPC_CHANNELS.releaseThreadServer, ...args) as Promise<DesktopIpcResult<"releaseThreadServer">>,
+    getServerVersion: (...args: DesktopIpcArgs<"getServerVersion">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getServerVersion, ...args) as Promise<DesktopIpcResult<"getServerVersion">>,
+    getServerStartupDiagnostics: (...args: DesktopIpcArgs<"getServerStartupDiagnostics">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getServerStartupDiagnostics, ...args) as Promise<DesktopIpcResult<"getServerStartupDiagnostics">>,
+    getServerStats: (...args: DesktopIpcArgs<"getServerStats">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getServerStats, ...args) as Promise<DesktopIpcResult<"getServerStats">>,
+    getWorkspaceHealth: (...args: DesktopIpcArgs<"getWorkspaceHealth">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getWorkspaceHealth, ...args) as Promise<DesktopIpcResult<"getWorkspaceHealth">>,
+    listOrphanedServers: (...args: DesktopIpcArgs<"listOrphanedServers">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listOrphanedServers, ...args) as Promise<DesktopIpcResult<"listOrphanedServers">>,
+    cleanupOrphanedServers: (...args: DesktopIpcArgs<"cleanupOrphanedServers">) =>
+      invoke(DESKTOP_IPC_CHANNELS.cleanupOrphanedServers, ...args) as Promise<DesktopIpcResult<"cleanupOrphanedServers">>,
+    readWorkspaceConfig: (...args: DesktopIpcArgs<"readWorkspaceConfig">) =>
+      invoke(DESKTOP_IPC_CHANNELS.readWorkspaceConfig, ...args) as Promise<DesktopIpcResult<"readWorkspaceConfig">>,
+    writeWorkspaceConfig: (...args: DesktopIpcArgs<"writeWorkspaceConfig">) =>
+      invoke(DESKTOP_IPC_CHANNELS.writeWorkspaceConfig, ...args) as Promise<DesktopIpcResult<"writeWorkspaceConfig">>,
+    listAvailableModels: (...args: DesktopIpcArgs<"listAvailableModels">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listAvailableModels, ...args) as Promise<DesktopIpcResult<"listAvailableModels">>,
+    getWorkspaceGitStatus: (...args: DesktopIpcArgs<"getWorkspaceGitStatus">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getWorkspaceGitStatus, ...args) as Promise<DesktopIpcResult<"getWorkspaceGitStatus">>,
+    listWorkspaceCheckpoints: (...args: DesktopIpcArgs<"listWorkspaceCheckpoints">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listWorkspaceCheckpoints, ...args) as Promise<DesktopIpcResult<"listWorkspaceCheckpoints">>,
+    restoreWorkspaceCheckpoint: (...args: DesktopIpcArgs<"restoreWorkspaceCheckpoint">) =>
+      invoke(DESKTOP_IPC_CHANNELS.restoreWorkspaceCheckpoint, ...args) as Promise<DesktopIpcResult<"restoreWorkspaceCheckpoint">>,
+    validateProviderCredentials: (...args: DesktopIpcArgs<"validateProviderCredentials">) =>
+      invoke(DESKTOP_IPC_CHANNELS.validateProviderCredentials, ...args) as Promise<DesktopIpcResult<"validateProviderCredentials">>,
+    getUsageSummary: (...args: DesktopIpcArgs<"getUsageSummary">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getUsageSummary, ...args) as Promise<DesktopIpcResult<"getUsageSummary">>,
+    getAppSettings: (...args: DesktopIpcArgs<"getAppSettings">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getAppSettings, ...args) as Promise<DesktopIpcResult<"getAppSettings">>,
+    updateAppSettings: (...args: DesktopIpcArgs<"updateAppSettings">) =>
+      invoke(DESKTOP_IPC_CHANNELS.updateAppSettings, ...args) as Promise<DesktopIpcResult<"updateAppSettings">>,
+    listProfiles: (...args: DesktopIpcArgs<"listProfiles">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listProfiles, ...args) as Promise<DesktopIpcResult<"listProfiles">>,
+    createProfile: (...args: DesktopIpcArgs<"createProfile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.createProfile, ...args) as Promise<DesktopIpcResult<"createProfile">>,
+    switchProfile: (...args: DesktopIpcArgs<"switchProfile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.switchProfile, ...args) as Promise<DesktopIpcResult<"switchProfile">>,
+    previewTranscriptRetention: (...args: DesktopIpcArgs<"previewTranscriptRetention">) =>
+      invoke(DESKTOP_IPC_CHANNELS.previewTranscriptRetention, ...args) as Promise<DesktopIpcResult<"previewTranscriptRetention">>,
+    testProxyConnectivity: (...args: DesktopIpcArgs<"testProxyConnectivity">) =>
+      invoke(DESKTOP_IPC_CHANNELS.testProxyConnectivity, ...args) as Promise<DesktopIpcResult<"testProxyConnectivity">>,
+    flushBufferedTranscripts: (...args: DesktopIpcArgs<"flushBufferedTranscripts">) =>
+      invoke(DESKTOP_IPC_CHANNELS.flushBufferedTranscripts, ...args) as Promise<DesktopIpcResult<"flushBufferedTranscripts">>,
+    getConnectivityStatus: (...args: DesktopIpcArgs<"getConnectivityStatus">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getConnectivityStatus, ...args) as Promise<DesktopIpcResult<"getConnectivityStatus">>,
+    verifyInstallation: (...args: DesktopIpcArgs<"verifyInstallation">) =>
+      invoke(DESKTOP_IPC_CHANNELS.verifyInstallation, ...args) as Promise<DesktopIpcResult<"verifyInstallation">>,
+    getOnboardingStatus: (...args: DesktopIpcArgs<"getOnboardingStatus">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getOnboardingStatus, ...args) as Promise<DesktopIpcResult<"getOnboardingStatus">>,
+    runEnvironmentChecks: (...args: DesktopIpcArgs<"runEnvironmentChecks">) =>
+      invoke(DESKTOP_IPC_CHANNELS.runEnvironmentChecks, ...args) as Promise<DesktopIpcResult<"runEnvironmentChecks">>,
+    startMobileRelay: (...args: DesktopIpcArgs<"mobileRelayStart">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayStart, ...args) as Promise<DesktopIpcResult<"mobileRelayStart">>,
+    stopMobileRelay: (...args: DesktopIpcArgs<"mobileRelayStop">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayStop, ...args) as Promise<DesktopIpcResult<"mobileRelayStop">>,
+    getMobileRelayState: (...args: DesktopIpcArgs<"mobileRelayGetState">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayGetState, ...args) as Promise<DesktopIpcResult<"mobileRelayGetState">>,
+    refreshMobileRelayTrustedPhones: (...args: DesktopIpcArgs<"mobileRelayRefreshTrustedPhones">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayRefreshTrustedPhones, ...args) as Promise<DesktopIpcResult<"mobileRelayRefreshTrustedPhones">>,
+    rotateMobileRelaySession: (...args: DesktopIpcArgs<"mobileRelayRotateSession">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayRotateSession, ...args) as Promise<DesktopIpcResult<"mobileRelayRotateSession">>,
+    forgetMobileRelayTrustedPhone: (...args: DesktopIpcArgs<"mobileRelayForgetTrustedPhone">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayForgetTrustedPhone, ...args) as Promise<DesktopIpcResult<"mobileRelayForgetTrustedPhone">>,
+    updateMobileRelayTrustedPhonePermissions: (...args: DesktopIpcArgs<"mobileRelayUpdateTrustedPhonePermissions">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mobileRelayUpdateTrustedPhonePermissions, ...args) as Promise<DesktopIpcResult<"mobileRelayUpdateTrustedPhonePermissions">>,
+    loadState: (...args: DesktopIpcArgs<"loadState">) =>
+      invoke(DESKTOP_IPC_CHANNELS.loadState, ...args) as Promise<DesktopIpcResult<"loadState">>,
+    saveState: (...args: DesktopIpcArgs<"saveState">) =>
+      invoke(DESKTOP_IPC_CHANNELS.saveState, ...args) as Promise<DesktopIpcResult<"saveState">>,
+    listStateSnapshots: (...args: DesktopIpcArgs<"listStateSnapshots">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listStateSnapshots, ...args) as Promise<DesktopIpcResult<"listStateSnapshots">>,
+    restoreStateSnapshot: (...args: DesktopIpcArgs<"restoreStateSnapshot">) =>
+      invoke(DESKTOP_IPC_CHANNELS.restoreStateSnapshot, ...args) as Promise<DesktopIpcResult<"restoreStateSnapshot">>,
+    rebuildStateFromTranscripts: (...args: DesktopIpcArgs<"rebuildStateFromTranscripts">) =>
+      invoke(DESKTOP_IPC_CHANNELS.rebuildStateFromTranscripts, ...args) as Promise<DesktopIpcResult<"rebuildStateFromTranscripts">>,
+    exportAppData: (...args: DesktopIpcArgs<"exportAppData">) =>
+      invoke(DESKTOP_IPC_CHANNELS.exportAppData, ...args) as Promise<DesktopIpcResult<"exportAppData">>,
+    importAppData: (...args: DesktopIpcArgs<"importAppData">) =>
+      invoke(DESKTOP_IPC_CHANNELS.importAppData, ...args) as Promise<DesktopIpcResult<"importAppData">>,
+    captureProductEvent: (...args: DesktopIpcArgs<"captureProductEvent">) =>
+      invoke(DESKTOP_IPC_CHANNELS.captureProductEvent, ...args) as Promise<DesktopIpcResult<"captureProductEvent">>,
+    createScratchThread: (...args: DesktopIpcArgs<"createScratchThread">) =>
+      invoke(DESKTOP_IPC_CHANNELS.createScratchThread, ...args) as Promise<DesktopIpcResult<"createScratchThread">>,
+    persistScratchThread: (...args: DesktopIpcArgs<"persistScratchThread">) =>
+      invoke(DESKTOP_IPC_CHANNELS.persistScratchThread, ...args) as Promise<DesktopIpcResult<"persistScratchThread">>,
+    readTranscript: (...args: DesktopIpcArgs<"readTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.readTranscript, ...args) as Promise<DesktopIpcResult<"readTranscript">>,
+    readTranscriptPage: (...args: DesktopIpcArgs<"readTranscriptPage">) =>
+      invoke(DESKTOP_IPC_CHANNELS.readTranscriptPage, ...args) as Promise<DesktopIpcResult<"readTranscriptPage">>,
+    getThreadPreviews: (...args: DesktopIpcArgs<"getThreadPreviews">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getThreadPreviews, ...args) as Promise<DesktopIpcResult<"getThreadPreviews">>,
+    inspectTranscript: (...args: DesktopIpcArgs<"inspectTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.inspectTranscript, ...args) as Promise<DesktopIpcResult<"inspectTranscript">>,
+    repairTranscript: (...args: DesktopIpcArgs<"repairTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.repairTranscript, ...args) as Promise<DesktopIpcResult<"repairTranscript">>,
+    normalizeTranscriptTimestamps: (...args: DesktopIpcArgs<"normalizeTranscriptTimestamps">) =>
+      invoke(DESKTOP_IPC_CHANNELS.normalizeTranscriptTimestamps, ...args) as Promise<DesktopIpcResult<"normalizeTranscriptTimestamps">>,
+    redactTranscript: (...args: DesktopIpcArgs<"redactTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.redactTranscript, ...args) as Promise<DesktopIpcResult<"redactTranscript">>,
+    archiveThread: (...args: DesktopIpcArgs<"archiveThread">) =>
+      invoke(DESKTOP_IPC_CHANNELS.archiveThread, ...args) as Promise<DesktopIpcResult<"archiveThread">>,
+    unarchiveThread: (...args: DesktopIpcArgs<"unarchiveThread">) =>
+      invoke(DESKTOP_IPC_CHANNELS.unarchiveThread, ...args) as Promise<DesktopIpcResult<"unarchiveThread">>,
+    forkThread: (...args: DesktopIpcArgs<"forkThread">) =>
+      invoke(DESKTOP_IPC_CHANNELS.forkThread, ...args) as Promise<DesktopIpcResult<"forkThread">>,
+    mergeThreads: (...args: DesktopIpcArgs<"mergeThreads">) =>
+      invoke(DESKTOP_IPC_CHANNELS.mergeThreads, ...args) as Promise<DesktopIpcResult<"mergeThreads">>,
+    suggestThreadTitle: (...args: DesktopIpcArgs<"suggestThreadTitle">) =>
+      invoke(DESKTOP_IPC_CHANNELS.suggestThreadTitle, ...args) as Promise<DesktopIpcResult<"suggestThreadTitle">>,
+    copyTranscriptSelection: (...args: DesktopIpcArgs<"copyTranscriptSelection">) =>
+      invoke(DESKTOP_IPC_CHANNELS.copyTranscriptSelection, ...args) as Promise<DesktopIpcResult<"copyTranscriptSelection">>,
+    getThreadFileChanges: (...args: DesktopIpcArgs<"getThreadFileChanges">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getThreadFileChanges, ...args) as Promise<DesktopIpcResult<"getThreadFileChanges">>,
+    startReplay: (...args: DesktopIpcArgs<"startReplay">) =>
+      invoke(DESKTOP_IPC_CHANNELS.startReplay, ...args) as Promise<DesktopIpcResult<"startReplay">>,
+    stopReplay: (...args: DesktopIpcArgs<"stopReplay">) =>
+      invoke(DESKTOP_IPC_CHANNELS.stopReplay, ...args) as Promise<DesktopIpcResult<"stopReplay">>,
+    getTranscriptEncryption: (...args: DesktopIpcArgs<"getTranscriptEncryption">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getTranscriptEncryption, ...args) as Promise<DesktopIpcResult<"getTranscriptEncryption">>,
+    setTranscriptEncryption: (...args: DesktopIpcArgs<"setTranscriptEncryption">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setTranscriptEncryption, ...args) as Promise<DesktopIpcResult<"setTranscriptEncryption">>,
+    encryptExistingTranscripts: (...args: DesktopIpcArgs<"encryptExistingTranscripts">) =>
+      invoke(DESKTOP_IPC_CHANNELS.encryptExistingTranscripts, ...args) as Promise<DesktopIpcResult<"encryptExistingTranscripts">>,
+    migrateTranscriptStore: (...args: DesktopIpcArgs<"migrateTranscriptStore">) =>
+      invoke(DESKTOP_IPC_CHANNELS.migrateTranscriptStore, ...args) as Promise<DesktopIpcResult<"migrateTranscriptStore">>,
+    openTranscriptFile: (...args: DesktopIpcArgs<"openTranscriptFile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.openTranscriptFile, ...args) as Promise<DesktopIpcResult<"openTranscriptFile">>,
+    shareTranscript: (...args: DesktopIpcArgs<"shareTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.shareTranscript, ...args) as Promise<DesktopIpcResult<"shareTranscript">>,
+    hydrateTranscript: (...args: DesktopIpcArgs<"hydrateTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.hydrateTranscript, ...args) as Promise<DesktopIpcResult<"hydrateTranscript">>,
+    appendTranscriptEvent: (...args: DesktopIpcArgs<"appendTranscriptEvent">) =>
+      invoke(DESKTOP_IPC_CHANNELS.appendTranscriptEvent, ...args) as Promise<DesktopIpcResult<"appendTranscriptEvent">>,
+    appendTranscriptBatch: (...args: DesktopIpcArgs<"appendTranscriptBatch">) =>
+      invoke(DESKTOP_IPC_CHANNELS.appendTranscriptBatch, ...args) as Promise<DesktopIpcResult<"appendTranscriptBatch">>,
+    subscribeTranscript: (...args: DesktopIpcArgs<"subscribeTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.subscribeTranscript, ...args) as Promise<DesktopIpcResult<"subscribeTranscript">>,
+    unsubscribeTranscript: (...args: DesktopIpcArgs<"unsubscribeTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.unsubscribeTranscript, ...args) as Promise<DesktopIpcResult<"unsubscribeTranscript">>,
+    deleteTranscript: (...args: DesktopIpcArgs<"deleteTranscript">) =>
+      invoke(DESKTOP_IPC_CHANNELS.deleteTranscript, ...args) as Promise<DesktopIpcResult<"deleteTranscript">>,
+    purgeThreads: (...args: DesktopIpcArgs<"purgeThreads">) =>
+      invoke(DESKTOP_IPC_CHANNELS.purgeThreads, ...args) as Promise<DesktopIpcResult<"purgeThreads">>,
+    deleteWorkspace: (...args: DesktopIpcArgs<"deleteWorkspace">) =>
+      invoke(DESKTOP_IPC_CHANNELS.deleteWorkspace, ...args) as Promise<DesktopIpcResult<"deleteWorkspace">>,
+    getWorkspaceStorageReport: (...args: DesktopIpcArgs<"getWorkspaceStorageReport">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getWorkspaceStorageReport, ...args) as Promise<DesktopIpcResult<"getWorkspaceStorageReport">>,
+    cleanupWorkspaceStorage: (...args: DesktopIpcArgs<"cleanupWorkspaceStorage">) =>
+      invoke(DESKTOP_IPC_CHANNELS.cleanupWorkspaceStorage, ...args) as Promise<DesktopIpcResult<"cleanupWorkspaceStorage">>,
+    listTrash: (...args: DesktopIpcArgs<"listTrash">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listTrash, ...args) as Promise<DesktopIpcResult<"listTrash">>,
+    restoreFromTrash: (...args: DesktopIpcArgs<"restoreFromTrash">) =>
+      invoke(DESKTOP_IPC_CHANNELS.restoreFromTrash, ...args) as Promise<DesktopIpcResult<"restoreFromTrash">>,
+    setWorkspacePinned: (...args: DesktopIpcArgs<"setWorkspacePinned">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setWorkspacePinned, ...args) as Promise<DesktopIpcResult<"setWorkspacePinned">>,
+    setWorkspaceTags: (...args: DesktopIpcArgs<"setWorkspaceTags">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setWorkspaceTags, ...args) as Promise<DesktopIpcResult<"setWorkspaceTags">>,
+    setWorkspaceLaunchProfiles: (...args: DesktopIpcArgs<"setWorkspaceLaunchProfiles">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setWorkspaceLaunchProfiles, ...args) as Promise<DesktopIpcResult<"setWorkspaceLaunchProfiles">>,
+    setWorkspaceIdlePolicy: (...args: DesktopIpcArgs<"setWorkspaceIdlePolicy">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setWorkspaceIdlePolicy, ...args) as Promise<DesktopIpcResult<"setWorkspaceIdlePolicy">>,
+    setWorkspacePathPolicy: (...args: DesktopIpcArgs<"setWorkspacePathPolicy">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setWorkspacePathPolicy, ...args) as Promise<DesktopIpcResult<"setWorkspacePathPolicy">>,
+    previewPathPolicy: (...args: DesktopIpcArgs<"previewPathPolicy">) =>
+      invoke(DESKTOP_IPC_CHANNELS.previewPathPolicy, ...args) as Promise<DesktopIpcResult<"previewPathPolicy">>,
+    duplicateWorkspace: (...args: DesktopIpcArgs<"duplicateWorkspace">) =>
+      invoke(DESKTOP_IPC_CHANNELS.duplicateWorkspace, ...args) as Promise<DesktopIpcResult<"duplicateWorkspace">>,
+    reorderWorkspaces: (...args: DesktopIpcArgs<"reorderWorkspaces">) =>
+      invoke(DESKTOP_IPC_CHANNELS.reorderWorkspaces, ...args) as Promise<DesktopIpcResult<"reorderWorkspaces">>,
+    globalSearch: (...args: DesktopIpcArgs<"globalSearch">) =>
+      invoke(DESKTOP_IPC_CHANNELS.globalSearch, ...args) as Promise<DesktopIpcResult<"globalSearch">>,
+    annotateEvent: (...args: DesktopIpcArgs<"annotateEvent">) =>
+      invoke(DESKTOP_IPC_CHANNELS.annotateEvent, ...args) as Promise<DesktopIpcResult<"annotateEvent">>,
+    listAnnotations: (...args: DesktopIpcArgs<"listAnnotations">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listAnnotations, ...args) as Promise<DesktopIpcResult<"listAnnotations">>,
+    saveThreadDraft: (...args: DesktopIpcArgs<"saveThreadDraft">) =>
+      invoke(DESKTOP_IPC_CHANNELS.saveThreadDraft, ...args) as Promise<DesktopIpcResult<"saveThreadDraft">>,
+    loadThreadDraft: (...args: DesktopIpcArgs<"loadThreadDraft">) =>
+      invoke(DESKTOP_IPC_CHANNELS.loadThreadDraft, ...args) as Promise<DesktopIpcResult<"loadThreadDraft">>,
+    listPromptTemplates: (...args: DesktopIpcArgs<"listPromptTemplates">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listPromptTemplates, ...args) as Promise<DesktopIpcResult<"listPromptTemplates">>,
+    savePromptTemplate: (...args: DesktopIpcArgs<"savePromptTemplate">) =>
+      invoke(DESKTOP_IPC_CHANNELS.savePromptTemplate, ...args) as Promise<DesktopIpcResult<"savePromptTemplate">>,
+    deletePromptTemplate: (...args: DesktopIpcArgs<"deletePromptTemplate">) =>
+      invoke(DESKTOP_IPC_CHANNELS.deletePromptTemplate, ...args) as Promise<DesktopIpcResult<"deletePromptTemplate">>,
+    renderPrompt: (...args: DesktopIpcArgs<"renderPrompt">) =>
+      invoke(DESKTOP_IPC_CHANNELS.renderPrompt, ...args) as Promise<DesktopIpcResult<"renderPrompt">>,
+    listScheduledTasks: (...args: DesktopIpcArgs<"listScheduledTasks">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listScheduledTasks, ...args) as Promise<DesktopIpcResult<"listScheduledTasks">>,
+    createScheduledTask: (...args: DesktopIpcArgs<"createScheduledTask">) =>
+      invoke(DESKTOP_IPC_CHANNELS.createScheduledTask, ...args) as Promise<DesktopIpcResult<"createScheduledTask">>,
+    setScheduledTaskEnabled: (...args: DesktopIpcArgs<"setScheduledTaskEnabled">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setScheduledTaskEnabled, ...args) as Promise<DesktopIpcResult<"setScheduledTaskEnabled">>,
+    deleteScheduledTask: (...args: DesktopIpcArgs<"deleteScheduledTask">) =>
+      invoke(DESKTOP_IPC_CHANNELS.deleteScheduledTask, ...args) as Promise<DesktopIpcResult<"deleteScheduledTask">>,
+    recordPendingApproval: (...args: DesktopIpcArgs<"recordPendingApproval">) =>
+      invoke(DESKTOP_IPC_CHANNELS.recordPendingApproval, ...args) as Promise<DesktopIpcResult<"recordPendingApproval">>,
+    listPendingApprovals: (...args: DesktopIpcArgs<"listPendingApprovals">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listPendingApprovals, ...args) as Promise<DesktopIpcResult<"listPendingApprovals">>,
+    resolveApproval: (...args: DesktopIpcArgs<"resolveApproval">) =>
+      invoke(DESKTOP_IPC_CHANNELS.resolveApproval, ...args) as Promise<DesktopIpcResult<"resolveApproval">>,
+    startVoiceCapture: (...args: DesktopIpcArgs<"startVoiceCapture">) =>
+      invoke(DESKTOP_IPC_CHANNELS.startVoiceCapture, ...args) as Promise<DesktopIpcResult<"startVoiceCapture">>,
+    appendVoiceAudio: (...args: DesktopIpcArgs<"appendVoiceAudio">) =>
+      invoke(DESKTOP_IPC_CHANNELS.appendVoiceAudio, ...args) as Promise<DesktopIpcResult<"appendVoiceAudio">>,
+    stopVoiceCapture: (...args: DesktopIpcArgs<"stopVoiceCapture">) =>
+      invoke(DESKTOP_IPC_CHANNELS.stopVoiceCapture, ...args) as Promise<DesktopIpcResult<"stopVoiceCapture">>,
+    pickWorkspaceDirectory: (...args: DesktopIpcArgs<"pickWorkspaceDirectory">) =>
+      invoke(DESKTOP_IPC_CHANNELS.pickWorkspaceDirectory, ...args) as Promise<DesktopIpcResult<"pickWorkspaceDirectory">>,
+    registerRemoteWorkspace: (...args: DesktopIpcArgs<"registerRemoteWorkspace">) =>
+      invoke(DESKTOP_IPC_CHANNELS.registerRemoteWorkspace, ...args) as Promise<DesktopIpcResult<"registerRemoteWorkspace">>,
+    scanForWorkspaces: (...args: DesktopIpcArgs<"scanForWorkspaces">) =>
+      invoke(DESKTOP_IPC_CHANNELS.scanForWorkspaces, ...args) as Promise<DesktopIpcResult<"scanForWorkspaces">>,
+    pickDirectory: (...args: DesktopIpcArgs<"pickDirectory">) =>
+      invoke(DESKTOP_IPC_CHANNELS.pickDirectory, ...args) as Promise<DesktopIpcResult<"pickDirectory">>,
+    showContextMenu: (...args: DesktopIpcArgs<"showContextMenu">) =>
+      invoke(DESKTOP_IPC_CHANNELS.showContextMenu, ...args) as Promise<DesktopIpcResult<"showContextMenu">>,
+    windowMinimize: (...args: DesktopIpcArgs<"windowMinimize">) =>
+      invoke(DESKTOP_IPC_CHANNELS.windowMinimize, ...args) as Promise<DesktopIpcResult<"windowMinimize">>,
+    windowMaximize: (...args: DesktopIpcArgs<"windowMaximize">) =>
+      invoke(DESKTOP_IPC_CHANNELS.windowMaximize, ...args) as Promise<DesktopIpcResult<"windowMaximize">>,
+    windowClose: (...args: DesktopIpcArgs<"windowClose">) =>
+      invoke(DESKTOP_IPC_CHANNELS.windowClose, ...args) as Promise<DesktopIpcResult<"windowClose">>,
+    resolveWindowCloseRequest: (...args: DesktopIpcArgs<"resolveWindowCloseRequest">) =>
+      invoke(DESKTOP_IPC_CHANNELS.resolveWindowCloseRequest, ...args) as Promise<DesktopIpcResult<"resolveWindowCloseRequest">>,
+    windowDragStart: (...args: DesktopIpcArgs<"windowDragStart">) =>
+      invoke(DESKTOP_IPC_CHANNELS.windowDragStart, ...args) as Promise<DesktopIpcResult<"windowDragStart">>,
+    windowDragMove: (...args: DesktopIpcArgs<"windowDragMove">) =>
+      invoke(DESKTOP_IPC_CHANNELS.windowDragMove, ...args) as Promise<DesktopIpcResult<"windowDragMove">>,
+    windowDragEnd: (...args: DesktopIpcArgs<"windowDragEnd">) =>
+      invoke(DESKTOP_IPC_CHANNELS.windowDragEnd, ...args) as Promise<DesktopIpcResult<"windowDragEnd">>,
+    getPlatform: (...args: DesktopIpcArgs<"getPlatform">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getPlatform, ...args) as Promise<DesktopIpcResult<"getPlatform">>,
+    showMainWindow: (...args: DesktopIpcArgs<"showMainWindow">) =>
+      invoke(DESKTOP_IPC_CHANNELS.showMainWindow, ...args) as Promise<DesktopIpcResult<"showMainWindow">>,
+    showQuickChatWindow: (...args: DesktopIpcArgs<"showQuickChatWindow">) =>
+      invoke(DESKTOP_IPC_CHANNELS.showQuickChatWindow, ...args) as Promise<DesktopIpcResult<"showQuickChatWindow">>,
+    showCanvasWindow: (...args: DesktopIpcArgs<"showCanvasWindow">) =>
+      invoke(DESKTOP_IPC_CHANNELS.showCanvasWindow, ...args) as Promise<DesktopIpcResult<"showCanvasWindow">>,
+    listDirectory: (...args: DesktopIpcArgs<"listDirectory">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listDirectory, ...args) as Promise<DesktopIpcResult<"listDirectory">>,
+    watchWorkspaceDirectory: (...args: DesktopIpcArgs<"watchWorkspaceDirectory">) =>
+      invoke(DESKTOP_IPC_CHANNELS.watchWorkspaceDirectory, ...args) as Promise<DesktopIpcResult<"watchWorkspaceDirectory">>,
+    unwatchWorkspaceDirectory: (...args: DesktopIpcArgs<"unwatchWorkspaceDirectory">) =>
+      invoke(DESKTOP_IPC_CHANNELS.unwatchWorkspaceDirectory, ...args) as Promise<DesktopIpcResult<"unwatchWorkspaceDirectory">>,
+    getWatcherStatus: (...args: DesktopIpcArgs<"getWatcherStatus">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getWatcherStatus, ...args) as Promise<DesktopIpcResult<"getWatcherStatus">>,
+    readFile: (...args: DesktopIpcArgs<"readFile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.readFile, ...args) as Promise<DesktopIpcResult<"readFile">>,
+    writeFile: (...args: DesktopIpcArgs<"writeFile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.writeFile, ...args) as Promise<DesktopIpcResult<"writeFile">>,
+    readFileForPreview: (...args: DesktopIpcArgs<"readFileForPreview">) =>
+      invoke(DESKTOP_IPC_CHANNELS.readFileForPreview, ...args) as Promise<DesktopIpcResult<"readFileForPreview">>,
+    getPreferredFileApp: (...args: DesktopIpcArgs<"getPreferredFileApp">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getPreferredFileApp, ...args) as Promise<DesktopIpcResult<"getPreferredFileApp">>,
+    previewOSFile: (...args: DesktopIpcArgs<"previewOSFile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.previewOSFile, ...args) as Promise<DesktopIpcResult<"previewOSFile">>,
+    openPath: (...args: DesktopIpcArgs<"openPath">) =>
+      invoke(DESKTOP_IPC_CHANNELS.openPath, ...args) as Promise<DesktopIpcResult<"openPath">>,
+    saveExportedFile: (...args: DesktopIpcArgs<"saveExportedFile">) =>
+      invoke(DESKTOP_IPC_CHANNELS.saveExportedFile, ...args) as Promise<DesktopIpcResult<"saveExportedFile">>,
+    pickCanvasSavePath: (...args: DesktopIpcArgs<"pickCanvasSavePath">) =>
+      invoke(DESKTOP_IPC_CHANNELS.pickCanvasSavePath, ...args) as Promise<DesktopIpcResult<"pickCanvasSavePath">>,
+    openExternalUrl: (...args: DesktopIpcArgs<"openExternalUrl">) =>
+      invoke(DESKTOP_IPC_CHANNELS.openExternalUrl, ...args) as Promise<DesktopIpcResult<"openExternalUrl">>,
+    revealPath: (...args: DesktopIpcArgs<"revealPath">) =>
+      invoke(DESKTOP_IPC_CHANNELS.revealPath, ...args) as Promise<DesktopIpcResult<"revealPath">>,
+    copyPath: (...args: DesktopIpcArgs<"copyPath">) =>
+      invoke(DESKTOP_IPC_CHANNELS.copyPath, ...args) as Promise<DesktopIpcResult<"copyPath">>,
+    copyText: (...args: DesktopIpcArgs<"copyText">) =>
+      invoke(DESKTOP_IPC_CHANNELS.copyText, ...args) as Promise<DesktopIpcResult<"copyText">>,
+    copyFileToWorkspaceUploads: (...args: DesktopIpcArgs<"copyFileToWorkspaceUploads">) =>
+      invoke(DESKTOP_IPC_CHANNELS.copyFileToWorkspaceUploads, ...args) as Promise<DesktopIpcResult<"copyFileToWorkspaceUploads">>,
+    storeAttachment: (...args: DesktopIpcArgs<"storeAttachment">) =>
+      invoke(DESKTOP_IPC_CHANNELS.storeAttachment, ...args) as Promise<DesktopIpcResult<"storeAttachment">>,
+    getAttachment: (...args: DesktopIpcArgs<"getAttachment">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getAttachment, ...args) as Promise<DesktopIpcResult<"getAttachment">>,
+    getThumbnail: (...args: DesktopIpcArgs<"getThumbnail">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getThumbnail, ...args) as Promise<DesktopIpcResult<"getThumbnail">>,
+    createDirectory: (...args: DesktopIpcArgs<"createDirectory">) =>
+      invoke(DESKTOP_IPC_CHANNELS.createDirectory, ...args) as Promise<DesktopIpcResult<"createDirectory">>,
+    renamePath: (...args: DesktopIpcArgs<"renamePath">) =>
+      invoke(DESKTOP_IPC_CHANNELS.renamePath, ...args) as Promise<DesktopIpcResult<"renamePath">>,
+    trashPath: (...args: DesktopIpcArgs<"trashPath">) =>
+      invoke(DESKTOP_IPC_CHANNELS.trashPath, ...args) as Promise<DesktopIpcResult<"trashPath">>,
+    confirmAction: (...args: DesktopIpcArgs<"confirmAction">) =>
+      invoke(DESKTOP_IPC_CHANNELS.confirmAction, ...args) as Promise<DesktopIpcResult<"confirmAction">>,
+    showNotification: (...args: DesktopIpcArgs<"showNotification">) =>
+      invoke(DESKTOP_IPC_CHANNELS.showNotification, ...args) as Promise<DesktopIpcResult<"showNotification">>,
+    writeRendererLog: (...args: DesktopIpcArgs<"writeRendererLog">) =>
+      invoke(DESKTOP_IPC_CHANNELS.writeRendererLog, ...args) as Promise<DesktopIpcResult<"writeRendererLog">>,
+    setLogLevel: (...args: DesktopIpcArgs<"setLogLevel">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setLogLevel, ...args) as Promise<DesktopIpcResult<"setLogLevel">>,
+    readAppLogs: (...args: DesktopIpcArgs<"readAppLogs">) =>
+      invoke(DESKTOP_IPC_CHANNELS.readAppLogs, ...args) as Promise<DesktopIpcResult<"readAppLogs">>,
+    createDiagnosticsBundle: (...args: DesktopIpcArgs<"createDiagnosticsBundle">) =>
+      invoke(DESKTOP_IPC_CHANNELS.createDiagnosticsBundle, ...args) as Promise<DesktopIpcResult<"createDiagnosticsBundle">>,
+    revealDiagnosticsBundle: (...args: DesktopIpcArgs<"revealDiagnosticsBundle">) =>
+      invoke(DESKTOP_IPC_CHANNELS.revealDiagnosticsBundle, ...args) as Promise<DesktopIpcResult<"revealDiagnosticsBundle">>,
+    openLogsFolder: (...args: DesktopIpcArgs<"openLogsFolder">) =>
+      invoke(DESKTOP_IPC_CHANNELS.openLogsFolder, ...args) as Promise<DesktopIpcResult<"openLogsFolder">>,
+    uploadDiagnosticsBundle: (...args: DesktopIpcArgs<"uploadDiagnosticsBundle">) =>
+      invoke(DESKTOP_IPC_CHANNELS.uploadDiagnosticsBundle, ...args) as Promise<DesktopIpcResult<"uploadDiagnosticsBundle">>,
+    startOperation: (...args: DesktopIpcArgs<"startOperation">) =>
+      invoke(DESKTOP_IPC_CHANNELS.startOperation, ...args) as Promise<DesktopIpcResult<"startOperation">>,
+    getOperation: (...args: DesktopIpcArgs<"getOperation">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getOperation, ...args) as Promise<DesktopIpcResult<"getOperation">>,
+    listOperations: (...args: DesktopIpcArgs<"listOperations">) =>
+      invoke(DESKTOP_IPC_CHANNELS.listOperations, ...args) as Promise<DesktopIpcResult<"listOperations">>,
+    cancelOperation: (...args: DesktopIpcArgs<"cancelOperation">) =>
+      invoke(DESKTOP_IPC_CHANNELS.cancelOperation, ...args) as Promise<DesktopIpcResult<"cancelOperation">>,
+    enqueueExport: (...args: DesktopIpcArgs<"enqueueExport">) =>
+      invoke(DESKTOP_IPC_CHANNELS.enqueueExport, ...args) as Promise<DesktopIpcResult<"enqueueExport">>,
+    saveBackupCredentials: (...args: DesktopIpcArgs<"saveBackupCredentials">) =>
+      invoke(DESKTOP_IPC_CHANNELS.saveBackupCredentials, ...args) as Promise<DesktopIpcResult<"saveBackupCredentials">>,
+    configureBackupSync: (...args: DesktopIpcArgs<"configureBackupSync">) =>
+      invoke(DESKTOP_IPC_CHANNELS.configureBackupSync, ...args) as Promise<DesktopIpcResult<"configureBackupSync">>,
+    getBackupSyncConfig: (...args: DesktopIpcArgs<"getBackupSyncConfig">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getBackupSyncConfig, ...args) as Promise<DesktopIpcResult<"getBackupSyncConfig">>,
+    syncBackupNow: (...args: DesktopIpcArgs<"syncBackupNow">) =>
+      invoke(DESKTOP_IPC_CHANNELS.syncBackupNow, ...args) as Promise<DesktopIpcResult<"syncBackupNow">>,
+    getTelemetryStatus: (...args: DesktopIpcArgs<"getTelemetryStatus">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getTelemetryStatus, ...args) as Promise<DesktopIpcResult<"getTelemetryStatus">>,
+    getUpdateState: (...args: DesktopIpcArgs<"getUpdateState">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getUpdateState, ...args) as Promise<DesktopIpcResult<"getUpdateState">>,
+    checkForUpdates: (...args: DesktopIpcArgs<"checkForUpdates">) =>
+      invoke(DESKTOP_IPC_CHANNELS.checkForUpdates, ...args) as Promise<DesktopIpcResult<"checkForUpdates">>,
+    quitAndInstallUpdate: (...args: DesktopIpcArgs<"quitAndInstallUpdate">) =>
+      invoke(DESKTOP_IPC_CHANNELS.quitAndInstallUpdate, ...args) as Promise<DesktopIpcResult<"quitAndInstallUpdate">>,
+    getSidecarUpdateState: (...args: DesktopIpcArgs<"getSidecarUpdateState">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getSidecarUpdateState, ...args) as Promise<DesktopIpcResult<"getSidecarUpdateState">>,
+    applySidecarUpdate: (...args: DesktopIpcArgs<"applySidecarUpdate">) =>
+      invoke(DESKTOP_IPC_CHANNELS.applySidecarUpdate, ...args) as Promise<DesktopIpcResult<"applySidecarUpdate">>,
+    getSystemAppearance: (...args: DesktopIpcArgs<"getSystemAppearance">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getSystemAppearance, ...args) as Promise<DesktopIpcResult<"getSystemAppearance">>,
+    getPlatformChrome: (...args: DesktopIpcArgs<"getPlatformChrome">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getPlatformChrome, ...args) as Promise<DesktopIpcResult<"getPlatformChrome">>,
+    setWindowAppearance: (...args: DesktopIpcArgs<"setWindowAppearance">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setWindowAppearance, ...args) as Promise<DesktopIpcResult<"setWindowAppearance">>,
+    setLocale: (...args: DesktopIpcArgs<"setLocale">) =>
+      invoke(DESKTOP_IPC_CHANNELS.setLocale, ...args) as Promise<DesktopIpcResult<"setLocale">>,
+    getBackendCapabilities: (...args: DesktopIpcArgs<"getBackendCapabilities">) =>
+      invoke(DESKTOP_IPC_CHANNELS.getBackendCapabilities, ...args) as Promise<DesktopIpcResult<"getBackendCapabilities">>,
+  };
+}
+
+export function createDesktopEventBindings(subscribe: DesktopEventSubscribe) {
+  return {
+    onMenuCommand: (listener: (payload: DesktopEventPayload<"menuCommand">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.menuCommand, listener as (payload: unknown) => void),
+    onDeepLinkNavigation: (listener: (payload: DesktopEventPayload<"deepLinkNavigation">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.deepLinkNavigation, listener as (payload: unknown) => void),
+    onUpdateStateChanged: (listener: (payload: DesktopEventPayload<"updateStateChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.updateStateChanged, listener as (payload: unknown) => void),
+    onSidecarUpdateStateChanged: (listener: (payload: DesktopEventPayload<"sidecarUpdateStateChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.sidecarUpdateStateChanged, listener as (payload: unknown) => void),
+    onWorkspaceServerStartupProgress: (listener: (payload: DesktopEventPayload<"workspaceServerStartupProgress">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceServerStartupProgress, listener as (payload: unknown) => void),
+    onWorkspaceServerPrewarmProgress: (listener: (payload: DesktopEventPayload<"workspaceServerPrewarmProgress">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceServerPrewarmProgress, listener as (payload: unknown) => void),
+    onWorkspaceServerExited: (listener: (payload: DesktopEventPayload<"workspaceServerExited">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceServerExited, listener as (payload: unknown) => void),
+    onWorkspaceProxyConnectionChanged: (listener: (payload: DesktopEventPayload<"workspaceProxyConnectionChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceProxyConnectionChanged, listener as (payload: unknown) => void),
+    onWindowCloseRequested: (listener: (payload: DesktopEventPayload<"windowCloseRequested">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.windowCloseRequested, listener as (payload: unknown) => void),
+    onPreviewFileChanged: (listener: (payload: DesktopEventPayload<"previewFileChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.previewFileChanged, listener as (payload: unknown) => void),
+    onSystemAppearanceChanged: (listener: (payload: DesktopEventPayload<"systemAppearanceChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.systemAppearanceChanged, listener as (payload: unknown) => void),
+    onMobileRelayStateChanged: (listener: (payload: DesktopEventPayload<"mobileRelayStateChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.mobileRelayStateChanged, listener as (payload: unknown) => void),
+    onWorkspaceFileChanged: (listener: (payload: DesktopEventPayload<"workspaceFileChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceFileChanged, listener as (payload: unknown) => void),
+    onOperationUpdated: (listener: (payload: DesktopEventPayload<"operationUpdated">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.operationUpdated, listener as (payload: unknown) => void),
+    onExportProgress: (listener: (payload: DesktopEventPayload<"exportProgress">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.exportProgress, listener as (payload: unknown) => void),
+    onExportComplete: (listener: (payload: DesktopEventPayload<"exportComplete">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.exportComplete, listener as (payload: unknown) => void),
+    onTranscriptAppended: (listener: (payload: DesktopEventPayload<"transcriptAppended">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.transcriptAppended, listener as (payload: unknown) => void),
+    onServerStats: (listener: (payload: DesktopEventPayload<"serverStats">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.serverStats, listener as (payload: unknown) => void),
+    onWorkspaceConfigChanged: (listener: (payload: DesktopEventPayload<"workspaceConfigChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceConfigChanged, listener as (payload: unknown) => void),
+    onWorkspaceGitChanged: (listener: (payload: DesktopEventPayload<"workspaceGitChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.workspaceGitChanged, listener as (payload: unknown) => void),
+    onAppSettingsChanged: (listener: (payload: DesktopEventPayload<"appSettingsChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.appSettingsChanged, listener as (payload: unknown) => void),
+    onTranscriptRetentionCompleted: (listener: (payload: DesktopEventPayload<"transcriptRetentionCompleted">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.transcriptRetentionCompleted, listener as (payload: unknown) => void),
+    onLowDiskSpace: (listener: (payload: DesktopEventPayload<"lowDiskSpace">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.lowDiskSpace, listener as (payload: unknown) => void),
+    onConnectivityChanged: (listener: (payload: DesktopEventPayload<"connectivityChanged">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.connectivityChanged, listener as (payload: unknown) => void),
+    onScheduledTaskRunCompleted: (listener: (payload: DesktopEventPayload<"scheduledTaskRunCompleted">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.scheduledTaskRunCompleted, listener as (payload: unknown) => void),
+    onServerSuspended: (listener: (payload: DesktopEventPayload<"serverSuspended">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.serverSuspended, listener as (payload: unknown) => void),
+    onServerResumed: (listener: (payload: DesktopEventPayload<"serverResumed">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.serverResumed, listener as (payload: unknown) => void),
+    onServerReloaded: (listener: (payload: DesktopEventPayload<"serverReloaded">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.serverReloaded, listener as (payload: unknown) => void),
+    onTranscriptThrottled: (listener: (payload: DesktopEventPayload<"transcriptThrottled">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.transcriptThrottled, listener as (payload: unknown) => void),
+    onVoiceTranscript: (listener: (payload: DesktopEventPayload<"voiceTranscript">) => void) =>
+      subscribe(DESKTOP_EVENT_CHANNELS.voiceTranscript, listener as (payload: unknown) => void),
+  };
+}
@@ -0,0 +1,57 @@
+import { DESKTOP_EVENT_LISTENERS, DESKTOP_IPC_COMMANDS } from "./desktopIpcContract";
+
+export const DESKTOP_BINDINGS_ARTIFACT_PATH = "src/lib/desktopBindings.generated.ts";
+
+function renderCommand(channelKey: string, method: string): string[] {
+  return [
+    `    ${method}: (...args: DesktopIpcArgs<"${channelKey}">) =>`,
+    `      invoke(DESKTOP_IPC_CHANNELS.${channelKey}, ...args) as Promise<DesktopIpcResult<"${channelKey}">>,`,
+  ];
+}
+
+function renderListener(channelKey: string, method: string): string[] {
+  return [
+    `    ${method}: (listener: (payload: DesktopEventPayload<"${channelKey}">) => void) =>`,
+    `      subscribe(DESKTOP_EVENT_CHANNELS.${channelKey}, listener as (payload: unknown) => void),`,
+  ];
+}
+
+/**
+ * Renders typed wrappers for every public IPC command and event channel. The
+ * wrappers take the transport as an argument, so the same file serves the
+ * preload, tests, and any out-of-process client driving the desktop surface.
+ */
+export function buildDesktopBindingsArtifact(): string {
+  const commands = Object.entries(DESKTOP_IPC_COMMANDS).flatMap(([channelKey, method]) =>
+    method ? renderCommand(channelKey, method) : [],
+  );
+  const listeners = Object.entries(DESKTOP_EVENT_LISTENERS).flatMap(([channelKey, method]) =>
+    renderListener(channelKey, method),
+  );
+
+  return [
+    "// Generated by apps/desktop/scripts/generateDesktopBindings.ts. Do not edit by hand.",
+    "",
+    'import { DESKTOP_EVENT_CHANNELS, DESKTOP_IPC_CHANNELS } from "./desktopApi";',
+    'import type { DesktopEventPayload, DesktopIpcArgs, DesktopIpcResult } from "./desktopIpcContract";',
+    "",
+    "export type DesktopIpcInvoke = (channel: string, ...args: unknown[]) => Promise<unknown>;",
+    "export type DesktopEventSubscribe = (",
+    "  channel: string,",
+    "  listener: (payload: unknown) => void,",
+    ") => () => void;",
+    "",
+    "export function createDesktopIpcBindings(invoke: DesktopIpcInvoke) {",
+    "  return {",
+    ...commands,
+    "  };",
+    "}",
+    "",
+    "export function createDesktopEventBindings(subscribe: DesktopEventSubscribe) {",
+    "  return {",
+    ...listeners,
+    "  };",
+    "}",
+    "",
+  ].join("\n");
+}
@@ -12,38 +12,205 @@ import { resolveFeatureFlags } from "../../../../src/shared/featureFlags";
 import type { WorkspaceFileChangeEvent as PreviewFileChangeEvent } from "../../../../src/shared/fileVersion";
 import type {
   HydratedTranscriptSnapshot,
+  PathPolicyPreview,
   PersistedPrivacyTelemetrySettings,
   PersistedState,
+  ThreadRecord,
   TranscriptEvent,
+  WorkspaceRecord,
 } from "../app/types";
 import type {
+  AnnotateEventInput,
+  AppendVoiceAudioInput,
+  AppLogsResult,
+  AppSettings,
+  AttachmentContents,
+  AttachmentThumbnail,
+  AvailableModelsResult,
+  BackupSyncConfig,
   CaptureProductEventInput,
+  CleanupWorkspaceStorageInput,
+  CleanupWorkspaceStorageResult,
+  ConfigureBackupSyncInput,
   ConfirmActionInput,
+  ConnectivityStatus,
+  CopyTranscriptSelectionInput,
+  CopyTranscriptSelectionResult,
   CreateDiagnosticsBundleOutput,
   CreateOneOffChatWorkspaceInput,
   CreateOneOffChatWorkspaceOutput,
+  CreateProfileInput,
+  CreateScheduledTaskInput,
+  CreateScratchThreadInput,
+  DeletePromptTemplateInput,
+  DeleteScheduledTaskInput,
   DesktopApi,
+  DesktopBackendCapabilities,
+  DesktopDeepLinkNavigation,
   DesktopMenuCommand,
   DesktopNotificationInput,
+  DesktopOnboardingStatus,
+  DesktopProfile,
+  DesktopProfileList,
+  DestructiveCommandResult,
+  DestructiveConfirmationChallenge,
+  DiskSpaceStatus,
+  DroppedWorkspaceFolder,
+  DuplicateWorkspaceInput,
+  DuplicateWorkspaceResult,
+  EnqueueExportInput,
+  EnvironmentCheckReport,
   ExplorerEntry,
+  ExportAppDataInput,
+  ExportAppDataResult,
+  ForkThreadInput,
+  GenerateHandoffLinkInput,
+  GetAttachmentInput,
+  GetThumbnailInput,
+  GetThreadFileChangesInput,
+  GetThreadPreviewsInput,
+  GetUsageSummaryInput,
+  GetWorkspaceStorageReportInput,
+  GlobalSearchInput,
+  GlobalSearchResult,
+  HandoffLink,
+  ImportAppDataInput,
+  ImportAppDataResult,
+  InstallationIntegrityReport,
+  ListAnnotationsInput,
+  ListAvailableModelsInput,
+  ListPendingApprovalsInput,
+  ListPromptTemplatesInput,
+  LoadThreadDraftInput,
+  LocaleInfo,
+  MergeThreadsInput,
+  MergeThreadsResult,
+  MigrateTranscriptStoreInput,
+  OpenedTranscriptFile,
+  OperationSnapshot,
+  OrphanedServer,
+  OrphanedServerCleanupResult,
+  PendingApproval,
+  PersistScratchThreadInput,
   PickCanvasSavePathInput,
   PlatformChromeInfo,
+  PreviewPathPolicyInput,
+  PreviewTranscriptRetentionInput,
+  PrewarmWorkspaceServersInput,
+  PrewarmWorkspaceServersResult,
+  PromptTemplate,
+  ProviderCredentialValidation,
+  ProxyConnectivityResult,
+  ReadAppLogsInput,
   ReadFileForPreviewOutput,
+  ReadTranscriptPageInput,
+  RecordPendingApprovalInput,
+  RecordPendingApprovalResult,
+  RedactTranscriptInput,
+  RegisterRemoteWorkspaceInput,
+  ReleaseThreadServerInput,
+  RenderPromptInput,
+  RenderPromptResult,
+  ReorderWorkspacesInput,
+  ReplaySession,
+  RequestYoloGrantInput,
+  ResolveApprovalInput,
+  RestoreFromTrashResult,
+  RestoreWorkspaceCheckpointInput,
+  RestoreWorkspaceCheckpointResult,
+  RevokeHandoffInput,
+  SaveBackupCredentialsInput,
+  SaveBackupCredentialsResult,
+  SavePromptTemplateInput,
+  SaveStateResult,
+  SaveThreadDraftInput,
+  ScanForWorkspacesInput,
+  ScanForWorkspacesResult,
+  ScheduledTask,
+  ScheduledTaskRun,
+  ScratchThreadInfo,
+  ServerReloadedEvent,
+  ServerResumedEvent,
+  ServerStatsEvent,
+  ServerSuspendedEvent,
+  SetLocaleInput,
+  SetLogLevelInput,
+  SetScheduledTaskEnabledInput,
   SetWindowAppearanceInput,
+  SetWorkspaceIdlePolicyInput,
+  SetWorkspaceLaunchProfilesInput,
+  SetWorkspacePathPolicyInput,
+  SetWorkspacePinnedInput,
+  SetWorkspaceTagsInput,
+  ShareTranscriptInput,
   ShowQuickChatWindowInput,
+  SidecarUpdateState,
+  StartOperationInput,
+  StartReplayInput,
+  StartVoiceCaptureInput,
+  StartVoiceCaptureResult,
+  StartWorkspaceServerResult,
+  StateRebuildReport,
+  StateSnapshotInfo,
+  StopReplayInput,
+  StopVoiceCaptureInput,
+  StoreAttachmentInput,
+  StoreAttachmentResult,
+  SwitchProfileInput,
   SystemAppearance,
   TelemetryStatusInput,
   TelemetryStatusSnapshot,
+  TestProxyConnectivityInput,
+  ThreadDraft,
+  ThreadFileChanges,
+  ThreadPreview,
+  TranscriptAnnotation,
+  TranscriptAppendedEvent,
   TranscriptBatchInput,
   TranscriptCaptureResult,
   TranscriptDeliveryFailure,
+  TranscriptEncryptionMigrationResult,
+  TranscriptEncryptionStatus,
+  TranscriptInspection,
+  TranscriptPage,
+  TranscriptRedactionResult,
+  TranscriptRepairResult,
+  TranscriptRetentionPreview,
+  TranscriptRetentionSummary,
+  TranscriptStoreMigrationResult,
+  TranscriptThrottledEvent,
+  TranscriptTimestampNormalizationResult,
+  TrashEntry,
+  UpdateAppSettingsInput,
   UpdaterState,
   UploadDiagnosticsBundleOutput,
+  UsageSummary,
+  ValidateProviderCredentialsInput,
+  VoiceTranscriptEvent,
   WindowCloseRequest,
   WindowCloseResponseInput,
+  WorkspaceCheckpointList,
+  WorkspaceConfigSnapshot,
+  WorkspaceGitStatus,
+  WorkspaceHealthReport,
+  WorkspaceProxyConnectionEvent,
   WorkspaceServerExitedEvent,
+  WorkspaceServerPrewarmProgress,
+  WorkspaceServerStartupDiagnostics,
   WorkspaceServerStartupProgress,
+  WorkspaceServerStats,
   WorkspaceServerStatus,
+  WorkspaceServerVersionInfo,
+  WorkspaceStorageReport,
+  WorkspaceWatcherStatus,
+  WriteWorkspaceConfigInput,
+  YoloGrant,
+} from "./desktopApi";
+import {
+  NETWORK_UNAVAILABLE_ERROR_CODE,
+  PROMPT_VARIABLES_MISSING_ERROR_CODE,
+  SERVER_STARTUP_FAILED_ERROR_CODE,
+  SERVER_UPGRADE_REQUIRED_ERROR_CODE,
 } from "./desktopApi";
 import { DESKTOP_API_OVERRIDE_KEY } from "./desktopApiOverride";
 
@@ -125,22 +292,48 @@ export function isDesktopDemoMode(): boolean {
   return api?.demoMode === true;
 }
 
+/** Whether main gates yolo mode behind `requestYoloGrant`. */
+export function hasYoloGrants(): boolean {
+  return typeof getDesktopApi()?.requestYoloGrant === "function";
+}
+
 export async function startWorkspaceServer(opts: {
   workspaceId: string;
   workspacePath: string;
   yolo: boolean;
+  yoloToken?: string;
   forceRestart?: boolean;
   preserveMobileRelay?: boolean;
   featureFlags?: DesktopFeatureFlagOverrides;
   privacyTelemetrySettings?: PersistedPrivacyTelemetrySettings;
-}): Promise<{ url: string }> {
+  launchProfile?: string;
+  threadId?: string;
+}): Promise<StartWorkspaceServerResult> {
   return await requireDesktopApi().startWorkspaceServer(opts);
 }
 
+/**
+ * Main asks the user before issuing a grant. Resolves null when they decline,
+ * or in builds where yolo is not gated by the main process.
+ */
+export async function requestYoloGrant(opts: RequestYoloGrantInput): Promise<YoloGrant | null> {
+  return (await getDesktopApi()?.requestYoloGrant?.(opts)) ?? null;
+}
+
+export async function prewarmWorkspaceServers(
+  opts: PrewarmWorkspaceServersInput,
+): Promise<PrewarmWorkspaceServersResult | null> {
+  return (await getDesktopApi()?.prewarmWorkspaceServers?.(opts)) ?? null;
+}
+
 export async function stopWorkspaceServer(opts: { workspaceId: string }): Promise<void> {
   await requireDesktopApi().stopWorkspaceServer(opts);
 }
 
+export async function releaseThreadServer(opts: ReleaseThreadServerInput): Promise<void> {
+  await getDesktopApi()?.releaseThreadServer?.(opts);
+}
+
 export async function getWorkspaceServerStatus(opts: {
   workspaceId: string;
 }): Promise<WorkspaceServerStatus> {
@@ -156,6 +349,216 @@ export async function getWorkspaceServerStatus(opts: {
   return await api.getWorkspaceServerStatus(opts);
 }
 
+/** Loopback URL that survives sidecar restarts; null where the proxy is unavailable. */
+export async function getWorkspaceProxyUrl(opts: {
+  workspaceId: string;
+}): Promise<{ url: string } | null> {
+  return (await getDesktopApi()?.getWorkspaceProxyUrl?.(opts)) ?? null;
+}
+
+export async function generateHandoffLink(opts: GenerateHandoffLinkInput): Promise<HandoffLink> {
+  const api = requireDesktopApi();
+  if (!api.generateHandoffLink) {
+    throw new Error("Handoff links are unavailable in this build.");
+  }
+  return await api.generateHandoffLink(opts);
+}
+
+export async function revokeHandoff(opts: RevokeHandoffInput): Promise<boolean> {
+  const api = requireDesktopApi();
+  if (!api.revokeHandoff) {
+    throw new Error("Handoff links are unavailable in this build.");
+  }
+  return await api.revokeHandoff(opts);
+}
+
+export async function getServerVersion(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceServerVersionInfo | null> {
+  return (await getDesktopApi()?.getServerVersion?.(opts)) ?? null;
+}
+
+export async function getServerStartupDiagnostics(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceServerStartupDiagnostics | null> {
+  return (await getDesktopApi()?.getServerStartupDiagnostics?.(opts)) ?? null;
+}
+
+export async function getWorkspaceHealth(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceHealthReport | null> {
+  return (await getDesktopApi()?.getWorkspaceHealth?.(opts)) ?? null;
+}
+
+export async function listOrphanedServers(): Promise<OrphanedServer[]> {
+  return (await getDesktopApi()?.listOrphanedServers?.()) ?? [];
+}
+
+export async function getWorkspaceGitStatus(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceGitStatus | null> {
+  return (await getDesktopApi()?.getWorkspaceGitStatus?.(opts)) ?? null;
+}
+
+export async function listWorkspaceCheckpoints(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceCheckpointList | null> {
+  return (await getDesktopApi()?.listWorkspaceCheckpoints?.(opts)) ?? null;
+}
+
+export async function readWorkspaceConfig(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceConfigSnapshot | null> {
+  return (await getDesktopApi()?.readWorkspaceConfig?.(opts)) ?? null;
+}
+
+export async function writeWorkspaceConfig(
+  opts: WriteWorkspaceConfigInput,
+): Promise<WorkspaceConfigSnapshot> {
+  const api = requireDesktopApi();
+  if (!api.writeWorkspaceConfig) {
+    throw new Error("Workspace config files are unavailable in this build.");
+  }
+  return await api.writeWorkspaceConfig(opts);
+}
+
+/** Cached for a few hours; null without the desktop bridge. */
+export async function listAvailableModels(
+  opts: ListAvailableModelsInput,
+): Promise<AvailableModelsResult | null> {
+  return (await getDesktopApi()?.listAvailableModels?.(opts)) ?? null;
+}
+
+export async function restoreWorkspaceCheckpoint(
+  opts: RestoreWorkspaceCheckpointInput,
+): Promise<RestoreWorkspaceCheckpointResult> {
+  const api = requireDesktopApi();
+  if (!api.restoreWorkspaceCheckpoint) {
+    throw new Error("Workspace checkpoints are unavailable in this build.");
+  }
+  return await api.restoreWorkspaceCheckpoint(opts);
+}
+
+export async function validateProviderCredentials(
+  opts: ValidateProviderCredentialsInput,
+): Promise<ProviderCredentialValidation | null> {
+  return (await getDesktopApi()?.validateProviderCredentials?.(opts)) ?? null;
+}
+
+export async function getUsageSummary(opts: GetUsageSummaryInput): Promise<UsageSummary | null> {
+  return (await getDesktopApi()?.getUsageSummary?.(opts)) ?? null;
+}
+
+export async function getAppSettings(): Promise<AppSettings | null> {
+  return (await getDesktopApi()?.getAppSettings?.()) ?? null;
+}
+
+export async function updateAppSettings(patch: UpdateAppSettingsInput): Promise<AppSettings> {
+  const api = requireDesktopApi();
+  if (!api.updateAppSettings) {
+    throw new Error("App settings are unavailable in this build.");
+  }
+  return await api.updateAppSettings(patch);
+}
+
+export async function listProfiles(): Promise<DesktopProfileList | null> {
+  return (await getDesktopApi()?.listProfiles?.()) ?? null;
+}
+
+export async function createProfile(opts: CreateProfileInput): Promise<DesktopProfile> {
+  const api = requireDesktopApi();
+  if (!api.createProfile) {
+    throw new Error("Profiles are unavailable in this build.");
+  }
+  return await api.createProfile(opts);
+}
+
+/** Relaunches the app into another profile; this window closes with the old instance. */
+export async function switchProfile(opts: SwitchProfileInput): Promise<void> {
+  const api = requireDesktopApi();
+  if (!api.switchProfile) {
+    throw new Error("Profiles are unavailable in this build.");
+  }
+  await api.switchProfile(opts);
+}
+
+export async function previewTranscriptRetention(
+  opts: PreviewTranscriptRetentionInput = {},
+): Promise<TranscriptRetentionPreview | null> {
+  return (await getDesktopApi()?.previewTranscriptRetention?.(opts)) ?? null;
+}
+
+export async function testProxyConnectivity(
+  opts: TestProxyConnectivityInput,
+): Promise<ProxyConnectivityResult> {
+  const api = requireDesktopApi();
+  if (!api.testProxyConnectivity) {
+    throw new Error("Proxy diagnostics are unavailable in this build.");
+  }
+  return await api.testProxyConnectivity(opts);
+}
+
+export async function flushBufferedTranscripts(): Promise<DiskSpaceStatus> {
+  const api = requireDesktopApi();
+  if (!api.flushBufferedTranscripts) {
+    throw new Error("Transcript flushing is unavailable in this build.");
+  }
+  return await api.flushBufferedTranscripts();
+}
+
+export async function getConnectivityStatus(): Promise<ConnectivityStatus | null> {
+  return (await getDesktopApi()?.getConnectivityStatus?.()) ?? null;
+}
+
+/** Resolves null in builds that cannot check their bundled resources. */
+export async function verifyInstallation(): Promise<InstallationIntegrityReport | null> {
+  return (await getDesktopApi()?.verifyInstallation?.()) ?? null;
+}
+
+/** Resolves null in builds without the first-run setup checks. */
+export async function getOnboardingStatus(): Promise<DesktopOnboardingStatus | null> {
+  return (await getDesktopApi()?.getOnboardingStatus?.()) ?? null;
+}
+
+export async function runEnvironmentChecks(): Promise<EnvironmentCheckReport> {
+  const api = requireDesktopApi();
+  if (!api.runEnvironmentChecks) {
+    throw new Error("Setup checks are unavailable in this build.");
+  }
+  return await api.runEnvironmentChecks();
+}
+
+export async function cleanupOrphanedServers(): Promise<OrphanedServerCleanupResult> {
+  const api = requireDesktopApi();
+  if (!api.cleanupOrphanedServers) {
+    throw new Error("Orphaned server cleanup is unavailable in this build.");
+  }
+  return await api.cleanupOrphanedServers();
+}
+
+export async function getServerStats(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceServerStats | null> {
+  return (await getDesktopApi()?.getServerStats?.(opts)) ?? null;
+}
+
+export function isServerUpgradeRequiredError(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error);
+  return message.includes(`${SERVER_UPGRADE_REQUIRED_ERROR_CODE}:`);
+}
+
+/** True when starting a local workspace server failed and left startup diagnostics behind. */
+export function isServerStartupFailedError(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error);
+  return message.includes(`${SERVER_STARTUP_FAILED_ERROR_CODE}:`);
+}
+
+/** True when a workspace server failed to start while the network was offline or degraded. */
+export function isNetworkUnavailableError(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error);
+  return message.includes(`${NETWORK_UNAVAILABLE_ERROR_CODE}:`);
+}
+
 export async function createOneOffChatWorkspace(
   opts?: CreateOneOffChatWorkspaceInput,
 ): Promise<CreateOneOffChatWorkspaceOutput> {
@@ -166,18 +569,242 @@ export async function loadState(): Promise<PersistedState> {
   return await requireDesktopApi().loadState();
 }
 
-export async function saveState(state: PersistedState): Promise<void> {
-  await requireDesktopApi().saveState(state);
+export async function saveState(state: PersistedState): Promise<SaveStateResult | null> {
+  return (await requireDesktopApi().saveState(state)) ?? null;
+}
+
+export async function listStateSnapshots(): Promise<StateSnapshotInfo[]> {
+  return (await getDesktopApi()?.listStateSnapshots?.()) ?? [];
+}
+
+export async function restoreStateSnapshot(opts: { name: string }): Promise<PersistedState> {
+  const api = requireDesktopApi();
+  if (!api.restoreStateSnapshot) {
+    throw new Error("State snapshots are unavailable in this build.");
+  }
+  return await api.restoreStateSnapshot(opts);
+}
+
+export async function rebuildStateFromTranscripts(opts: {
+  dryRun: boolean;
+}): Promise<StateRebuildReport> {
+  const api = requireDesktopApi();
+  if (!api.rebuildStateFromTranscripts) {
+    throw new Error("State recovery is unavailable in this build.");
+  }
+  return await api.rebuildStateFromTranscripts(opts);
+}
+
+export async function exportAppData(opts: ExportAppDataInput): Promise<ExportAppDataResult | null> {
+  const api = requireDesktopApi();
+  if (!api.exportAppData) {
+    throw new Error("App data export is unavailable in this build.");
+  }
+  return await api.exportAppData(opts);
+}
+
+export async function importAppData(opts: ImportAppDataInput): Promise<ImportAppDataResult | null> {
+  const api = requireDesktopApi();
+  if (!api.importAppData) {
+    throw new Error("App data import is unavailable in this build.");
+  }
+  return await api.importAppData(opts);
 }
 
 export async function captureProductEvent(input: CaptureProductEventInput): Promise<void> {
   await requireDesktopApi().captureProductEvent(input);
 }
 
+export async function createScratchThread(
+  opts: CreateScratchThreadInput,
+): Promise<ScratchThreadInfo> {
+  const api = requireDesktopApi();
+  if (!api.createScratchThread) {
+    throw new Error("Scratch threads are unavailable in this build.");
+  }
+  return await api.createScratchThread(opts);
+}
+
+export async function persistScratchThread(opts: PersistScratchThreadInput): Promise<ThreadRecord> {
+  const api = requireDesktopApi();
+  if (!api.persistScratchThread) {
+    throw new Error("Scratch threads are unavailable in this build.");
+  }
+  return await api.persistScratchThread(opts);
+}
+
 export async function readTranscript(opts: { threadId: string }): Promise<TranscriptEvent[]> {
   return await requireDesktopApi().readTranscript(opts);
 }
 
+/** Reads a transcript one bounded page at a time; follow `nextCursor` for the rest. */
+export async function readTranscriptPage(opts: ReadTranscriptPageInput): Promise<TranscriptPage> {
+  const api = requireDesktopApi();
+  if (!api.readTranscriptPage) {
+    const events = await api.readTranscript({ threadId: opts.threadId });
+    return { events, nextCursor: null, truncated: false };
+  }
+  return await api.readTranscriptPage(opts);
+}
+
+/** Sidebar previews for the given threads; resolves an empty list where unsupported. */
+export async function getThreadPreviews(opts: GetThreadPreviewsInput): Promise<ThreadPreview[]> {
+  return (await getDesktopApi()?.getThreadPreviews?.(opts)) ?? [];
+}
+
+/** Reports transcript lines that reads skip as undecodable; resolves null where unsupported. */
+export async function inspectTranscript(opts: {
+  threadId: string;
+}): Promise<TranscriptInspection | null> {
+  return (await getDesktopApi()?.inspectTranscript?.(opts)) ?? null;
+}
+
+/** Quarantines undecodable transcript lines and rewrites the clean transcript. */
+export async function repairTranscript(opts: {
+  threadId: string;
+}): Promise<TranscriptRepairResult> {
+  const api = requireDesktopApi();
+  if (!api.repairTranscript) {
+    throw new Error("Transcript repair is unavailable in this build.");
+  }
+  return await api.repairTranscript(opts);
+}
+
+/** Rewrites legacy transcript timestamps as UTC RFC 3339 so the transcript sorts by time. */
+export async function normalizeTranscriptTimestamps(opts: {
+  threadId: string;
+}): Promise<TranscriptTimestampNormalizationResult> {
+  const api = requireDesktopApi();
+  if (!api.normalizeTranscriptTimestamps) {
+    throw new Error("Transcript timestamp normalization is unavailable in this build.");
+  }
+  return await api.normalizeTranscriptTimestamps(opts);
+}
+
+/**
+ * Replaces credentials and `patterns` matches already written to a transcript
+ * with `[REDACTED:<hash>]`; with `dryRun` it only counts them.
+ */
+export async function redactTranscript(
+  opts: RedactTranscriptInput,
+): Promise<TranscriptRedactionResult> {
+  const api = requireDesktopApi();
+  if (!api.redactTranscript) {
+    throw new Error("Transcript redaction is unavailable in this build.");
+  }
+  return await api.redactTranscript(opts);
+}
+
+/** Resolves null in builds without transcript cold storage. */
+export async function archiveThread(opts: { threadId: string }): Promise<ThreadRecord | null> {
+  return (await getDesktopApi()?.archiveThread?.(opts)) ?? null;
+}
+
+export async function unarchiveThread(opts: { threadId: string }): Promise<ThreadRecord | null> {
+  return (await getDesktopApi()?.unarchiveThread?.(opts)) ?? null;
+}
+
+/**
+ * Titles a thread from its first user message, using the workspace server's model when one is
+ * running. Manual titles are kept. Resolves null where unsupported.
+ */
+export async function suggestThreadTitle(opts: { threadId: string }): Promise<ThreadRecord | null> {
+  return (await getDesktopApi()?.suggestThreadTitle?.(opts)) ?? null;
+}
+
+/**
+ * Copies the inclusive feed range to the clipboard as Markdown, redacting secrets unless told
+ * otherwise. Resolves null where unsupported.
+ */
+export async function copyTranscriptSelection(
+  opts: CopyTranscriptSelectionInput,
+): Promise<CopyTranscriptSelectionResult | null> {
+  return (await getDesktopApi()?.copyTranscriptSelection?.(opts)) ?? null;
+}
+
+/** Copies a thread's transcript into a new thread; resolves null where forking is unsupported. */
+export async function forkThread(opts: ForkThreadInput): Promise<ThreadRecord | null> {
+  return (await getDesktopApi()?.forkThread?.(opts)) ?? null;
+}
+
+/** Merges one thread's transcript into another; resolves null where merging is unsupported. */
+export async function mergeThreads(opts: MergeThreadsInput): Promise<MergeThreadsResult | null> {
+  return (await getDesktopApi()?.mergeThreads?.(opts)) ?? null;
+}
+
+/** Files the thread's tool calls edited, most recent first; null where unsupported. */
+export async function getThreadFileChanges(
+  opts: GetThreadFileChangesInput,
+): Promise<ThreadFileChanges | null> {
+  return (await getDesktopApi()?.getThreadFileChanges?.(opts)) ?? null;
+}
+
+/**
+ * Serves a thread's recording on a local workspace server URL for debugging
+ * rendering; resolves null where replay is unsupported.
+ */
+export async function startReplay(opts: StartReplayInput): Promise<ReplaySession | null> {
+  return (await getDesktopApi()?.startReplay?.(opts)) ?? null;
+}
+
+export async function stopReplay(opts: StopReplayInput): Promise<void> {
+  await getDesktopApi()?.stopReplay?.(opts);
+}
+
+export async function getTranscriptEncryption(): Promise<TranscriptEncryptionStatus> {
+  return (
+    (await getDesktopApi()?.getTranscriptEncryption?.()) ?? { enabled: false, available: false }
+  );
+}
+
+export async function setTranscriptEncryption(opts: {
+  enabled: boolean;
+}): Promise<TranscriptEncryptionStatus> {
+  const api = requireDesktopApi();
+  if (!api.setTranscriptEncryption) {
+    throw new Error("Transcript encryption is unavailable in this build.");
+  }
+  return await api.setTranscriptEncryption(opts);
+}
+
+export async function encryptExistingTranscripts(): Promise<TranscriptEncryptionMigrationResult> {
+  const api = requireDesktopApi();
+  if (!api.encryptExistingTranscripts) {
+    throw new Error("Transcript encryption is unavailable in this build.");
+  }
+  return await api.encryptExistingTranscripts();
+}
+
+export async function migrateTranscriptStore(
+  opts: MigrateTranscriptStoreInput,
+): Promise<TranscriptStoreMigrationResult> {
+  const api = requireDesktopApi();
+  if (!api.migrateTranscriptStore) {
+    throw new Error("Switching the transcript store is unavailable in this build.");
+  }
+  return await api.migrateTranscriptStore(opts);
+}
+
+/** Loads an exported transcript from anywhere on disk into a read-only viewer thread. */
+export async function openTranscriptFile(opts: {
+  path: string;
+}): Promise<OpenedTranscriptFile> {
+  const api = requireDesktopApi();
+  if (!api.openTranscriptFile) {
+    throw new Error("Transcript viewer is unavailable in this build.");
+  }
+  return await api.openTranscriptFile(opts);
+}
+
+/** Offers an exported copy of the thread through the macOS share sheet. */
+export async function shareTranscript(opts: ShareTranscriptInput): Promise<void> {
+  const api = requireDesktopApi();
+  if (!api.shareTranscript) {
+    throw new Error("Sharing transcripts is unavailable in this build.");
+  }
+  await api.shareTranscript(opts);
+}
+
 export async function hydrateTranscript(opts: {
   threadId: string;
 }): Promise<HydratedTranscriptSnapshot> {
@@ -195,6 +822,21 @@ export async function appendTranscriptBatch(
   await requireDesktopApi().appendTranscriptBatch(events);
 }
 
+/** Starts pushing appends for `threadId` to `onTranscriptAppended`; a no-op without the bridge. */
+export async function subscribeTranscript(opts: { threadId: string }): Promise<void> {
+  await getDesktopApi()?.subscribeTranscript?.(opts);
+}
+
+export async function unsubscribeTranscript(opts: { threadId: string }): Promise<void> {
+  await getDesktopApi()?.unsubscribeTranscript?.(opts);
+}
+
+export function onTranscriptAppended(
+  listener: (event: TranscriptAppendedEvent) => void,
+): () => void {
+  return getDesktopApi()?.onTranscriptAppended?.(listener) ?? noopUnsubscribe;
+}
+
 export function captureTranscriptEvent(
   event: TranscriptBatchInput,
 ): Promise<TranscriptCaptureResult> | null {
@@ -219,14 +861,352 @@ export async function discardTranscriptBatch(batchId: string): Promise<void> {
   await getDesktopApi()?.discardTranscriptBatch?.(batchId);
 }
 
-export async function deleteTranscript(opts: { threadId: string }): Promise<void> {
-  await requireDesktopApi().deleteTranscript(opts);
+export type ConfirmDestructiveCommand = (
+  challenge: DestructiveConfirmationChallenge,
+) => boolean | Promise<boolean>;
+
+/**
+ * Runs a destructive command through its confirmation challenge. `confirm`
+ * sees the impact reported by the main process and can still decline.
+ */
+async function runConfirmedCommand(
+  invoke: (confirmationToken?: string) => Promise<DestructiveCommandResult | void>,
+  confirm: ConfirmDestructiveCommand,
+): Promise<boolean> {
+  const first = await invoke();
+  if (!first || first.status === "completed") {
+    return true;
+  }
+  if (!(await confirm(first.challenge))) {
+    return false;
+  }
+  const result = await invoke(first.challenge.token);
+  if (result && result.status !== "completed") {
+    throw new Error(`${first.challenge.command} was not confirmed.`);
+  }
+  return true;
+}
+
+/** Resolves false when `confirm` declines and the transcript was kept. */
+export async function deleteTranscript(
+  opts: { threadId: string },
+  confirm: ConfirmDestructiveCommand,
+): Promise<boolean> {
+  const api = requireDesktopApi();
+  return await runConfirmedCommand(
+    (confirmationToken) =>
+      api.deleteTranscript({ ...opts, ...(confirmationToken ? { confirmationToken } : {}) }),
+    confirm,
+  );
+}
+
+/** Resolves false when `confirm` declines; reload state afterwards to pick up the removal. */
+export async function purgeThreads(
+  opts: { threadIds: string[] },
+  confirm: ConfirmDestructiveCommand,
+): Promise<boolean> {
+  const api = requireDesktopApi();
+  const purge = api.purgeThreads;
+  if (!purge) {
+    throw new Error("Thread purging is unavailable in this build.");
+  }
+  return await runConfirmedCommand(
+    (confirmationToken) => purge({ ...opts, ...(confirmationToken ? { confirmationToken } : {}) }),
+    confirm,
+  );
+}
+
+/** Resolves false when `confirm` declines; reload state afterwards to pick up the removal. */
+export async function deleteWorkspace(
+  opts: { workspaceId: string },
+  confirm: ConfirmDestructiveCommand,
+): Promise<boolean> {
+  const api = requireDesktopApi();
+  const remove = api.deleteWorkspace;
+  if (!remove) {
+    throw new Error("Workspace deletion is unavailable in this build.");
+  }
+  return await runConfirmedCommand(
+    (confirmationToken) => remove({ ...opts, ...(confirmationToken ? { confirmationToken } : {}) }),
+    confirm,
+  );
+}
+
+export async function getWorkspaceStorageReport(
+  opts: GetWorkspaceStorageReportInput,
+): Promise<WorkspaceStorageReport> {
+  const api = requireDesktopApi();
+  if (!api.getWorkspaceStorageReport) {
+    throw new Error("Workspace storage reports are unavailable in this build.");
+  }
+  return await api.getWorkspaceStorageReport(opts);
+}
+
+/**
+ * Resolves null when `confirm` declines purging archived threads; dry runs
+ * never ask. Reload state afterwards when threads were purged.
+ */
+export async function cleanupWorkspaceStorage(
+  opts: Omit<CleanupWorkspaceStorageInput, "confirmationToken">,
+  confirm: ConfirmDestructiveCommand,
+): Promise<Extract<CleanupWorkspaceStorageResult, { status: "completed" }> | null> {
+  const api = requireDesktopApi();
+  const cleanup = api.cleanupWorkspaceStorage;
+  if (!cleanup) {
+    throw new Error("Workspace storage cleanup is unavailable in this build.");
+  }
+  const first = await cleanup(opts);
+  if (first.status === "completed") {
+    return first;
+  }
+  if (!(await confirm(first.challenge))) {
+    return null;
+  }
+  const result = await cleanup({ ...opts, confirmationToken: first.challenge.token });
+  if (result.status !== "completed") {
+    throw new Error(`${first.challenge.command} was not confirmed.`);
+  }
+  return result;
+}
+
+export async function setWorkspacePinned(
+  opts: SetWorkspacePinnedInput,
+): Promise<WorkspaceRecord | null> {
+  return (await getDesktopApi()?.setWorkspacePinned?.(opts)) ?? null;
+}
+
+export async function setWorkspaceTags(
+  opts: SetWorkspaceTagsInput,
+): Promise<WorkspaceRecord | null> {
+  return (await getDesktopApi()?.setWorkspaceTags?.(opts)) ?? null;
+}
+
+export async function setWorkspaceLaunchProfiles(
+  opts: SetWorkspaceLaunchProfilesInput,
+): Promise<WorkspaceRecord> {
+  const api = requireDesktopApi();
+  if (!api.setWorkspaceLaunchProfiles) {
+    throw new Error("Launch profiles are unavailable in this build.");
+  }
+  return await api.setWorkspaceLaunchProfiles(opts);
+}
+
+export async function setWorkspaceIdlePolicy(
+  opts: SetWorkspaceIdlePolicyInput,
+): Promise<WorkspaceRecord> {
+  const api = requireDesktopApi();
+  if (!api.setWorkspaceIdlePolicy) {
+    throw new Error("Workspace idle policies are unavailable in this build.");
+  }
+  return await api.setWorkspaceIdlePolicy(opts);
+}
+
+export async function setWorkspacePathPolicy(
+  opts: SetWorkspacePathPolicyInput,
+): Promise<WorkspaceRecord> {
+  const api = requireDesktopApi();
+  if (!api.setWorkspacePathPolicy) {
+    throw new Error("Workspace path policies are unavailable in this build.");
+  }
+  return await api.setWorkspacePathPolicy(opts);
+}
+
+export async function previewPathPolicy(
+  opts: PreviewPathPolicyInput,
+): Promise<PathPolicyPreview | null> {
+  return (await getDesktopApi()?.previewPathPolicy?.(opts)) ?? null;
+}
+
+export async function duplicateWorkspace(
+  opts: DuplicateWorkspaceInput,
+): Promise<DuplicateWorkspaceResult> {
+  const api = requireDesktopApi();
+  if (!api.duplicateWorkspace) {
+    throw new Error("Workspace duplication is unavailable in this build.");
+  }
+  return await api.duplicateWorkspace(opts);
+}
+
+export async function listTrash(): Promise<TrashEntry[]> {
+  return (await getDesktopApi()?.listTrash?.()) ?? [];
+}
+
+export async function restoreFromTrash(opts: { entryId: string }): Promise<RestoreFromTrashResult> {
+  const api = requireDesktopApi();
+  if (!api.restoreFromTrash) {
+    throw new Error("Trash is unavailable in this build.");
+  }
+  return await api.restoreFromTrash(opts);
+}
+
+/** Resolves null where main-process reordering is unsupported. */
+export async function reorderWorkspaces(
+  opts: ReorderWorkspacesInput,
+): Promise<WorkspaceRecord[] | null> {
+  return (await getDesktopApi()?.reorderWorkspaces?.(opts)) ?? null;
+}
+
+export async function globalSearch(opts: GlobalSearchInput): Promise<GlobalSearchResult[]> {
+  return (await getDesktopApi()?.globalSearch?.(opts)) ?? [];
+}
+
+export async function annotateEvent(
+  opts: AnnotateEventInput,
+): Promise<TranscriptAnnotation | null> {
+  const api = requireDesktopApi();
+  if (!api.annotateEvent) {
+    throw new Error("Transcript annotations are unavailable in this build.");
+  }
+  return await api.annotateEvent(opts);
+}
+
+export async function listAnnotations(opts: ListAnnotationsInput): Promise<TranscriptAnnotation[]> {
+  return (await getDesktopApi()?.listAnnotations?.(opts)) ?? [];
+}
+
+/** Resolves null when the draft was cleared, or when this build cannot keep drafts. */
+export async function saveThreadDraft(opts: SaveThreadDraftInput): Promise<ThreadDraft | null> {
+  return (await getDesktopApi()?.saveThreadDraft?.(opts)) ?? null;
+}
+
+export async function loadThreadDraft(opts: LoadThreadDraftInput): Promise<ThreadDraft | null> {
+  return (await getDesktopApi()?.loadThreadDraft?.(opts)) ?? null;
+}
+
+export async function listPromptTemplates(
+  opts: ListPromptTemplatesInput = {},
+): Promise<PromptTemplate[]> {
+  return (await getDesktopApi()?.listPromptTemplates?.(opts)) ?? [];
+}
+
+export async function savePromptTemplate(opts: SavePromptTemplateInput): Promise<PromptTemplate> {
+  const api = requireDesktopApi();
+  if (!api.savePromptTemplate) {
+    throw new Error("The prompt library is unavailable in this build.");
+  }
+  return await api.savePromptTemplate(opts);
+}
+
+export async function deletePromptTemplate(opts: DeletePromptTemplateInput): Promise<void> {
+  const api = requireDesktopApi();
+  if (!api.deletePromptTemplate) {
+    throw new Error("The prompt library is unavailable in this build.");
+  }
+  await api.deletePromptTemplate(opts);
+}
+
+export async function renderPrompt(opts: RenderPromptInput): Promise<RenderPromptResult> {
+  const api = requireDesktopApi();
+  if (!api.renderPrompt) {
+    throw new Error("The prompt library is unavailable in this build.");
+  }
+  return await api.renderPrompt(opts);
+}
+
+/** True when a prompt could not be rendered because variables had no values. */
+export function isPromptVariablesMissingError(error: unknown): boolean {
+  const message = error instanceof Error ? error.message : String(error);
+  return message.includes(`${PROMPT_VARIABLES_MISSING_ERROR_CODE}:`);
+}
+
+export async function listScheduledTasks(): Promise<ScheduledTask[]> {
+  return (await getDesktopApi()?.listScheduledTasks?.()) ?? [];
+}
+
+export async function createScheduledTask(opts: CreateScheduledTaskInput): Promise<ScheduledTask> {
+  const api = requireDesktopApi();
+  if (!api.createScheduledTask) {
+    throw new Error("Scheduled tasks are unavailable in this build.");
+  }
+  return await api.createScheduledTask(opts);
+}
+
+export async function setScheduledTaskEnabled(
+  opts: SetScheduledTaskEnabledInput,
+): Promise<ScheduledTask> {
+  const api = requireDesktopApi();
+  if (!api.setScheduledTaskEnabled) {
+    throw new Error("Scheduled tasks are unavailable in this build.");
+  }
+  return await api.setScheduledTaskEnabled(opts);
+}
+
+export async function deleteScheduledTask(opts: DeleteScheduledTaskInput): Promise<void> {
+  const api = requireDesktopApi();
+  if (!api.deleteScheduledTask) {
+    throw new Error("Scheduled tasks are unavailable in this build.");
+  }
+  await api.deleteScheduledTask(opts);
+}
+
+export async function recordPendingApproval(
+  opts: RecordPendingApprovalInput,
+): Promise<RecordPendingApprovalResult> {
+  return (await getDesktopApi()?.recordPendingApproval?.(opts)) ?? { autoApproved: false };
+}
+
+export async function listPendingApprovals(
+  opts: ListPendingApprovalsInput,
+): Promise<PendingApproval[]> {
+  return (await getDesktopApi()?.listPendingApprovals?.(opts)) ?? [];
+}
+
+export async function resolveApproval(opts: ResolveApprovalInput): Promise<PendingApproval | null> {
+  return (await getDesktopApi()?.resolveApproval?.(opts)) ?? null;
+}
+
+export async function startVoiceCapture(
+  opts: StartVoiceCaptureInput,
+): Promise<StartVoiceCaptureResult> {
+  const api = requireDesktopApi();
+  if (!api.startVoiceCapture) {
+    throw new Error("Dictation is unavailable in this build.");
+  }
+  return await api.startVoiceCapture(opts);
+}
+
+export async function appendVoiceAudio(opts: AppendVoiceAudioInput): Promise<void> {
+  const api = requireDesktopApi();
+  if (!api.appendVoiceAudio) {
+    throw new Error("Dictation is unavailable in this build.");
+  }
+  await api.appendVoiceAudio(opts);
+}
+
+export async function stopVoiceCapture(
+  opts: StopVoiceCaptureInput = {},
+): Promise<VoiceTranscriptEvent | null> {
+  return (await getDesktopApi()?.stopVoiceCapture?.(opts)) ?? null;
 }
 
 export async function pickWorkspaceDirectory(): Promise<string | null> {
   return await requireDesktopApi().pickWorkspaceDirectory();
 }
 
+export async function resolveDroppedWorkspaceFolder(
+  file: File,
+): Promise<DroppedWorkspaceFolder | null> {
+  return (await getDesktopApi()?.resolveDroppedWorkspaceFolder?.(file)) ?? null;
+}
+
+export async function scanForWorkspaces(
+  opts: ScanForWorkspacesInput,
+): Promise<ScanForWorkspacesResult> {
+  const api = requireDesktopApi();
+  if (!api.scanForWorkspaces) {
+    throw new Error("Scanning for workspaces is unavailable in this build.");
+  }
+  return await api.scanForWorkspaces(opts);
+}
+
+export async function registerRemoteWorkspace(opts: RegisterRemoteWorkspaceInput): Promise<string> {
+  const api = requireDesktopApi();
+  if (!api.registerRemoteWorkspace) {
+    throw new Error("Remote workspaces are unavailable in this build.");
+  }
+  return await api.registerRemoteWorkspace(opts);
+}
+
 export async function pickDirectory(opts?: { title?: string }): Promise<string | null> {
   return await requireDesktopApi().pickDirectory(opts);
 }
@@ -320,6 +1300,12 @@ export async function unwatchWorkspaceDirectory(opts: {
   await getDesktopApi()?.unwatchWorkspaceDirectory(opts);
 }
 
+export async function getWatcherStatus(opts: {
+  workspaceId: string;
+}): Promise<WorkspaceWatcherStatus | null> {
+  return (await getDesktopApi()?.getWatcherStatus?.(opts)) ?? null;
+}
+
 export function onWorkspaceFileChanged(
   listener: (event: WorkspaceFileChangeEvent) => void,
 ): () => void {
@@ -384,6 +1370,22 @@ export async function copyText(text: string): Promise<void> {
   await requireDesktopApi().copyText(text);
 }
 
+export async function storeAttachment(opts: StoreAttachmentInput): Promise<StoreAttachmentResult> {
+  const api = requireDesktopApi();
+  if (!api.storeAttachment) {
+    throw new Error("Attachment storage is unavailable in this build.");
+  }
+  return await api.storeAttachment(opts);
+}
+
+export async function getAttachment(opts: GetAttachmentInput): Promise<AttachmentContents | null> {
+  return (await getDesktopApi()?.getAttachment?.(opts)) ?? null;
+}
+
+export async function getThumbnail(opts: GetThumbnailInput): Promise<AttachmentThumbnail | null> {
+  return (await getDesktopApi()?.getThumbnail?.(opts)) ?? null;
+}
+
 export async function createDirectory(opts: { parentPath: string; name: string }): Promise<void> {
   await requireDesktopApi().createDirectory(opts);
 }
@@ -423,6 +1425,70 @@ export async function uploadDiagnosticsBundle(opts: {
   return await requireDesktopApi().uploadDiagnosticsBundle(opts);
 }
 
+export async function startOperation(input: StartOperationInput): Promise<OperationSnapshot> {
+  const api = requireDesktopApi();
+  if (!api.startOperation) {
+    throw new Error("Background operations are unavailable in this build.");
+  }
+  return await api.startOperation(input);
+}
+
+export async function listOperations(): Promise<OperationSnapshot[]> {
+  return (await getDesktopApi()?.listOperations?.()) ?? [];
+}
+
+export async function getOperation(opts: {
+  operationId: string;
+}): Promise<OperationSnapshot | null> {
+  return (await getDesktopApi()?.getOperation?.(opts)) ?? null;
+}
+
+export async function cancelOperation(opts: { operationId: string }): Promise<boolean> {
+  return (await getDesktopApi()?.cancelOperation?.(opts)) ?? false;
+}
+
+/** Resolves null when the save dialog is canceled. */
+export async function enqueueExport(opts: EnqueueExportInput): Promise<OperationSnapshot | null> {
+  const api = requireDesktopApi();
+  if (!api.enqueueExport) {
+    throw new Error("Background exports are unavailable in this build.");
+  }
+  return await api.enqueueExport(opts);
+}
+
+export async function saveBackupCredentials(
+  opts: SaveBackupCredentialsInput,
+): Promise<SaveBackupCredentialsResult> {
+  const api = requireDesktopApi();
+  if (!api.saveBackupCredentials) {
+    throw new Error("Backup sync is unavailable in this build.");
+  }
+  return await api.saveBackupCredentials(opts);
+}
+
+export async function configureBackupSync(
+  opts: ConfigureBackupSyncInput,
+): Promise<BackupSyncConfig> {
+  const api = requireDesktopApi();
+  if (!api.configureBackupSync) {
+    throw new Error("Backup sync is unavailable in this build.");
+  }
+  return await api.configureBackupSync(opts);
+}
+
+export async function getBackupSyncConfig(): Promise<BackupSyncConfig | null> {
+  return (await getDesktopApi()?.getBackupSyncConfig?.()) ?? null;
+}
+
+/** Follow the returned `syncBackup` operation through operation events. */
+export async function syncBackupNow(): Promise<OperationSnapshot> {
+  const api = requireDesktopApi();
+  if (!api.syncBackupNow) {
+    throw new Error("Backup sync is unavailable in this build.");
+  }
+  return await api.syncBackupNow();
+}
+
 export async function getTelemetryStatus(
   opts?: TelemetryStatusInput,
 ): Promise<TelemetryStatusSnapshot> {
@@ -441,6 +1507,18 @@ export async function quitAndInstallUpdate(): Promise<void> {
   await requireDesktopApi().quitAndInstallUpdate();
 }
 
+export async function getSidecarUpdateState(): Promise<SidecarUpdateState | null> {
+  return (await getDesktopApi()?.getSidecarUpdateState?.()) ?? null;
+}
+
+export async function applySidecarUpdate(): Promise<SidecarUpdateState> {
+  const api = requireDesktopApi();
+  if (!api.applySidecarUpdate) {
+    throw new Error("Server updates are unavailable in this build.");
+  }
+  return await api.applySidecarUpdate();
+}
+
 export async function getSystemAppearance(): Promise<SystemAppearance> {
   return await requireDesktopApi().getSystemAppearance();
 }
@@ -455,6 +1533,19 @@ export async function setWindowAppearance(
   return await requireDesktopApi().setWindowAppearance(opts);
 }
 
+/** Null when not running in the desktop app or the backend predates capability reporting. */
+export async function getBackendCapabilities(): Promise<DesktopBackendCapabilities | null> {
+  return (await getDesktopApi()?.getBackendCapabilities?.()) ?? null;
+}
+
+export async function setLocale(opts: SetLocaleInput): Promise<LocaleInfo> {
+  const api = requireDesktopApi();
+  if (!api.setLocale) {
+    throw new Error("Changing the app language is unavailable in this build.");
+  }
+  return await api.setLocale(opts);
+}
+
 export async function startMobileRelay(opts: {
   workspaceId: string;
   workspacePath: string;
@@ -514,18 +1605,108 @@ export function onUpdateStateChanged(listener: (state: UpdaterState) => void): (
   return getDesktopApi()?.onUpdateStateChanged(listener) ?? noopUnsubscribe;
 }
 
+export function onSidecarUpdateStateChanged(
+  listener: (state: SidecarUpdateState) => void,
+): () => void {
+  return getDesktopApi()?.onSidecarUpdateStateChanged?.(listener) ?? noopUnsubscribe;
+}
+
 export function onWorkspaceServerStartupProgress(
   listener: (event: WorkspaceServerStartupProgress) => void,
 ): () => void {
   return getDesktopApi()?.onWorkspaceServerStartupProgress(listener) ?? noopUnsubscribe;
 }
 
+export function onWorkspaceServerPrewarmProgress(
+  listener: (event: WorkspaceServerPrewarmProgress) => void,
+): () => void {
+  return getDesktopApi()?.onWorkspaceServerPrewarmProgress?.(listener) ?? noopUnsubscribe;
+}
+
 export function onWorkspaceServerExited(
   listener: (event: WorkspaceServerExitedEvent) => void,
 ): () => void {
   return getDesktopApi()?.onWorkspaceServerExited(listener) ?? noopUnsubscribe;
 }
 
+export function onServerSuspended(listener: (event: ServerSuspendedEvent) => void): () => void {
+  return getDesktopApi()?.onServerSuspended?.(listener) ?? noopUnsubscribe;
+}
+
+export function onServerResumed(listener: (event: ServerResumedEvent) => void): () => void {
+  return getDesktopApi()?.onServerResumed?.(listener) ?? noopUnsubscribe;
+}
+
+export function onServerReloaded(listener: (event: ServerReloadedEvent) => void): () => void {
+  return getDesktopApi()?.onServerReloaded?.(listener) ?? noopUnsubscribe;
+}
+
+export function onWorkspaceProxyConnectionChanged(
+  listener: (event: WorkspaceProxyConnectionEvent) => void,
+): () => void {
+  return getDesktopApi()?.onWorkspaceProxyConnectionChanged?.(listener) ?? noopUnsubscribe;
+}
+
+export function onOperationUpdated(
+  listener: (snapshot: OperationSnapshot) => void,
+): () => void {
+  return getDesktopApi()?.onOperationUpdated?.(listener) ?? noopUnsubscribe;
+}
+
+export function onExportProgress(listener: (snapshot: OperationSnapshot) => void): () => void {
+  return getDesktopApi()?.onExportProgress?.(listener) ?? noopUnsubscribe;
+}
+
+export function onExportComplete(listener: (snapshot: OperationSnapshot) => void): () => void {
+  return getDesktopApi()?.onExportComplete?.(listener) ?? noopUnsubscribe;
+}
+
+export function onServerStats(listener: (event: ServerStatsEvent) => void): () => void {
+  return getDesktopApi()?.onServerStats?.(listener) ?? noopUnsubscribe;
+}
+
+export function onWorkspaceConfigChanged(
+  listener: (snapshot: WorkspaceConfigSnapshot) => void,
+): () => void {
+  return getDesktopApi()?.onWorkspaceConfigChanged?.(listener) ?? noopUnsubscribe;
+}
+
+export function onWorkspaceGitChanged(listener: (status: WorkspaceGitStatus) => void): () => void {
+  return getDesktopApi()?.onWorkspaceGitChanged?.(listener) ?? noopUnsubscribe;
+}
+
+export function onAppSettingsChanged(listener: (settings: AppSettings) => void): () => void {
+  return getDesktopApi()?.onAppSettingsChanged?.(listener) ?? noopUnsubscribe;
+}
+
+export function onTranscriptRetentionCompleted(
+  listener: (summary: TranscriptRetentionSummary) => void,
+): () => void {
+  return getDesktopApi()?.onTranscriptRetentionCompleted?.(listener) ?? noopUnsubscribe;
+}
+
+export function onLowDiskSpace(listener: (status: DiskSpaceStatus) => void): () => void {
+  return getDesktopApi()?.onLowDiskSpace?.(listener) ?? noopUnsubscribe;
+}
+
+export function onTranscriptThrottled(
+  listener: (event: TranscriptThrottledEvent) => void,
+): () => void {
+  return getDesktopApi()?.onTranscriptThrottled?.(listener) ?? noopUnsubscribe;
+}
+
+export function onVoiceTranscript(listener: (event: VoiceTranscriptEvent) => void): () => void {
+  return getDesktopApi()?.onVoiceTranscript?.(listener) ?? noopUnsubscribe;
+}
+
+export function onConnectivityChanged(listener: (status: ConnectivityStatus) => void): () => void {
+  return getDesktopApi()?.onConnectivityChanged?.(listener) ?? noopUnsubscribe;
+}
+
+export function onScheduledTaskRunCompleted(listener: (run: ScheduledTaskRun) => void): () => void {
+  return getDesktopApi()?.onScheduledTaskRunCompleted?.(listener) ?? noopUnsubscribe;
+}
+
 export function onWindowCloseRequested(
   listener: (request: WindowCloseRequest) => void,
 ): () => void {
@@ -538,10 +1719,28 @@ export async function writeRendererLog(
   await getDesktopApi()?.writeRendererLog(opts);
 }
 
+export async function setLogLevel(opts: SetLogLevelInput): Promise<void> {
+  const api = requireDesktopApi();
+  if (!api.setLogLevel) {
+    throw new Error("Log level control is unavailable in this build.");
+  }
+  await api.setLogLevel(opts);
+}
+
+export async function readAppLogs(opts: ReadAppLogsInput = {}): Promise<AppLogsResult | null> {
+  return (await getDesktopApi()?.readAppLogs?.(opts)) ?? null;
+}
+
 export function onMenuCommand(listener: (command: DesktopMenuCommand) => void): () => void {
   return getDesktopApi()?.onMenuCommand(listener) ?? noopUnsubscribe;
 }
 
+export function onDeepLinkNavigation(
+  listener: (navigation: DesktopDeepLinkNavigation) => void,
+): () => void {
+  return getDesktopApi()?.onDeepLinkNavigation?.(listener) ?? noopUnsubscribe;
+}
+
 export function onMobileRelayStateChanged(
   listener: (state: import("./desktopApi").MobileRelayBridgeState) => void,
 ): () => void {
@@ -0,0 +1,311 @@
+import type {
+  DESKTOP_EVENT_CHANNELS,
+  DESKTOP_IPC_CHANNELS,
+  DesktopApi,
+  DesktopBackendCapabilities,
+} from "./desktopApi";
+
+export type DesktopIpcChannelKey = keyof typeof DESKTOP_IPC_CHANNELS;
+export type DesktopEventChannelKey = keyof typeof DESKTOP_EVENT_CHANNELS;
+
+export type DesktopApiCommand = {
+  [M in keyof DesktopApi]-?: NonNullable<DesktopApi[M]> extends (
+    ...args: never[]
+  ) => Promise<unknown>
+    ? M
+    : never;
+}[keyof DesktopApi];
+
+type DesktopApiListener = Extract<keyof DesktopApi, `on${string}`>;
+
+/**
+ * The `DesktopApi` method behind each IPC channel, or null for channels the
+ * preload only uses internally. Typed over every channel so adding one to
+ * `DESKTOP_IPC_CHANNELS` fails typecheck until it is listed here.
+ */
+export const DESKTOP_IPC_COMMANDS = {
+  createOneOffChatWorkspace: "createOneOffChatWorkspace",
+  startWorkspaceServer: "startWorkspaceServer",
+  requestYoloGrant: "requestYoloGrant",
+  prewarmWorkspaceServers: "prewarmWorkspaceServers",
+  getWorkspaceServerStatus: "getWorkspaceServerStatus",
+  getWorkspaceProxyUrl: "getWorkspaceProxyUrl",
+  generateHandoffLink: "generateHandoffLink",
+  revokeHandoff: "revokeHandoff",
+  stopWorkspaceServer: "stopWorkspaceServer",
+  releaseThreadServer: "releaseThreadServer",
+  getServerVersion: "getServerVersion",
+  getServerStartupDiagnostics: "getServerStartupDiagnostics",
+  getServerStats: "getServerStats",
+  getWorkspaceHealth: "getWorkspaceHealth",
+  listOrphanedServers: "listOrphanedServers",
+  cleanupOrphanedServers: "cleanupOrphanedServers",
+  readWorkspaceConfig: "readWorkspaceConfig",
+  writeWorkspaceConfig: "writeWorkspaceConfig",
+  listAvailableModels: "listAvailableModels",
+  getWorkspaceGitStatus: "getWorkspaceGitStatus",
+  listWorkspaceCheckpoints: "listWorkspaceCheckpoints",
+  restoreWorkspaceCheckpoint: "restoreWorkspaceCheckpoint",
+  validateProviderCredentials: "validateProviderCredentials",
+  getUsageSummary: "getUsageSummary",
+  getAppSettings: "getAppSettings",
+  updateAppSettings: "updateAppSettings",
+  listProfiles: "listProfiles",
+  createProfile: "createProfile",
+  switchProfile: "switchProfile",
+  previewTranscriptRetention: "previewTranscriptRetention",
+  testProxyConnectivity: "testProxyConnectivity",
+  flushBufferedTranscripts: "flushBufferedTranscripts",
+  getConnectivityStatus: "getConnectivityStatus",
+  verifyInstallation: "verifyInstallation",
+  getOnboardingStatus: "getOnboardingStatus",
+  runEnvironmentChecks: "runEnvironmentChecks",
+  mobileRelayStart: "startMobileRelay",
+  mobileRelayStop: "stopMobileRelay",
+  mobileRelayGetState: "getMobileRelayState",
+  mobileRelayRefreshTrustedPhones: "refreshMobileRelayTrustedPhones",
+  mobileRelayRotateSession: "rotateMobileRelaySession",
+  mobileRelayForgetTrustedPhone: "forgetMobileRelayTrustedPhone",
+  mobileRelayUpdateTrustedPhonePermissions: "updateMobileRelayTrustedPhonePermissions",
+  loadState: "loadState",
+  saveState: "saveState",
+  listStateSnapshots: "listStateSnapshots",
+  restoreStateSnapshot: "restoreStateSnapshot",
+  rebuildStateFromTranscripts: "rebuildStateFromTranscripts",
+  exportAppData: "exportAppData",
+  importAppData: "importAppData",
+  captureProductEvent: "captureProductEvent",
+  createScratchThread: "createScratchThread",
+  persistScratchThread: "persistScratchThread",
+  readTranscript: "readTranscript",
+  readTranscriptPage: "readTranscriptPage",
+  getThreadPreviews: "getThreadPreviews",
+  inspectTranscript: "inspectTranscript",
+  repairTranscript: "repairTranscript",
+  normalizeTranscriptTimestamps: "normalizeTranscriptTimestamps",
+  redactTranscript: "redactTranscript",
+  archiveThread: "archiveThread",
+  unarchiveThread: "unarchiveThread",
+  forkThread: "forkThread",
+  mergeThreads: "mergeThreads",
+  suggestThreadTitle: "suggestThreadTitle",
+  copyTranscriptSelection: "copyTranscriptSelection",
+  getThreadFileChanges: "getThreadFileChanges",
+  startReplay: "startReplay",
+  stopReplay: "stopReplay",
+  getTranscriptEncryption: "getTranscriptEncryption",
+  setTranscriptEncryption: "setTranscriptEncryption",
+  encryptExistingTranscripts: "encryptExistingTranscripts",
+  migrateTranscriptStore: "migrateTranscriptStore",
+  openTranscriptFile: "openTranscriptFile",
+  shareTranscript: "shareTranscript",
+  hydrateTranscript: "hydrateTranscript",
+  appendTranscriptEvent: "appendTranscriptEvent",
+  appendTranscriptBatch: "appendTranscriptBatch",
+  subscribeTranscript: "subscribeTranscript",
+  unsubscribeTranscript: "unsubscribeTranscript",
+  deleteTranscript: "deleteTranscript",
+  purgeThreads: "purgeThreads",
+  deleteWorkspace: "deleteWorkspace",
+  getWorkspaceStorageReport: "getWorkspaceStorageReport",
+  cleanupWorkspaceStorage: "cleanupWorkspaceStorage",
+  listTrash: "listTrash",
+  restoreFromTrash: "restoreFromTrash",
+  setWorkspacePinned: "setWorkspacePinned",
+  setWorkspaceTags: "setWorkspaceTags",
+  setWorkspaceLaunchProfiles: "setWorkspaceLaunchProfiles",
+  setWorkspaceIdlePolicy: "setWorkspaceIdlePolicy",
+  setWorkspacePathPolicy: "setWorkspacePathPolicy",
+  previewPathPolicy: "previewPathPolicy",
+  duplicateWorkspace: "duplicateWorkspace",
+  reorderWorkspaces: "reorderWorkspaces",
+  globalSearch: "globalSearch",
+  annotateEvent: "annotateEvent",
+  listAnnotations: "listAnnotations",
+  saveThreadDraft: "saveThreadDraft",
+  loadThreadDraft: "loadThreadDraft",
+  listPromptTemplates: "listPromptTemplates",
+  savePromptTemplate: "savePromptTemplate",
+  deletePromptTemplate: "deletePromptTemplate",
+  renderPrompt: "renderPrompt",
+  listScheduledTasks: "listScheduledTasks",
+  createScheduledTask: "createScheduledTask",
+  setScheduledTaskEnabled: "setScheduledTaskEnabled",
+  deleteScheduledTask: "deleteScheduledTask",
+  recordPendingApproval: "recordPendingApproval",
+  listPendingApprovals: "listPendingApprovals",
+  resolveApproval: "resolveApproval",
+  startVoiceCapture: "startVoiceCapture",
+  appendVoiceAudio: "appendVoiceAudio",
+  stopVoiceCapture: "stopVoiceCapture",
+  pickWorkspaceDirectory: "pickWorkspaceDirectory",
+  resolveDroppedWorkspaceFolder: null,
+  registerRemoteWorkspace: "registerRemoteWorkspace",
+  scanForWorkspaces: "scanForWorkspaces",
+  pickDirectory: "pickDirectory",
+  showContextMenu: "showContextMenu",
+  windowMinimize: "windowMinimize",
+  windowMaximize: "windowMaximize",
+  windowClose: "windowClose",
+  resolveWindowCloseRequest: "resolveWindowCloseRequest",
+  windowDragStart: "windowDragStart",
+  windowDragMove: "windowDragMove",
+  windowDragEnd: "windowDragEnd",
+  getPlatform: "getPlatform",
+  showMainWindow: "showMainWindow",
+  consumePendingMenuCommands: null,
+  consumePendingDeepLinks: null,
+  showQuickChatWindow: "showQuickChatWindow",
+  showCanvasWindow: "showCanvasWindow",
+  listDirectory: "listDirectory",
+  watchWorkspaceDirectory: "watchWorkspaceDirectory",
+  unwatchWorkspaceDirectory: "unwatchWorkspaceDirectory",
+  getWatcherStatus: "getWatcherStatus",
+  readFile: "readFile",
+  writeFile: "writeFile",
+  readFileForPreview: "readFileForPreview",
+  getPreferredFileApp: "getPreferredFileApp",
+  previewOSFile: "previewOSFile",
+  openPath: "openPath",
+  saveExportedFile: "saveExportedFile",
+  pickCanvasSavePath: "pickCanvasSavePath",
+  openExternalUrl: "openExternalUrl",
+  revealPath: "revealPath",
+  copyPath: "copyPath",
+  copyText: "copyText",
+  copyFileToWorkspaceUploads: "copyFileToWorkspaceUploads",
+  authorizeUploadSource: null,
+  storeAttachment: "storeAttachment",
+  getAttachment: "getAttachment",
+  getThumbnail: "getThumbnail",
+  createDirectory: "createDirectory",
+  renamePath: "renamePath",
+  trashPath: "trashPath",
+  confirmAction: "confirmAction",
+  showNotification: "showNotification",
+  writeRendererLog: "writeRendererLog",
+  setLogLevel: "setLogLevel",
+  readAppLogs: "readAppLogs",
+  createDiagnosticsBundle: "createDiagnosticsBundle",
+  revealDiagnosticsBundle: "revealDiagnosticsBundle",
+  openLogsFolder: "openLogsFolder",
+  uploadDiagnosticsBundle: "uploadDiagnosticsBundle",
+  startOperation: "startOperation",
+  getOperation: "getOperation",
+  listOperations: "listOperations",
+  cancelOperation: "cancelOperation",
+  enqueueExport: "enqueueExport",
+  saveBackupCredentials: "saveBackupCredentials",
+  configureBackupSync: "configureBackupSync",
+  getBackupSyncConfig: "getBackupSyncConfig",
+  syncBackupNow: "syncBackupNow",
+  getTelemetryStatus: "getTelemetryStatus",
+  getUpdateState: "getUpdateState",
+  checkForUpdates: "checkForUpdates",
+  quitAndInstallUpdate: "quitAndInstallUpdate",
+  getSidecarUpdateState: "getSidecarUpdateState",
+  applySidecarUpdate: "applySidecarUpdate",
+  getSystemAppearance: "getSystemAppearance",
+  getPlatformChrome: "getPlatformChrome",
+  setWindowAppearance: "setWindowAppearance",
+  setLocale: "setLocale",
+  getBackendCapabilities: "getBackendCapabilities",
+} as const satisfies Record<DesktopIpcChannelKey, DesktopApiCommand | null>;
+
+/** The `DesktopApi` subscription method behind each event channel. */
+export const DESKTOP_EVENT_LISTENERS = {
+  menuCommand: "onMenuCommand",
+  deepLinkNavigation: "onDeepLinkNavigation",
+  updateStateChanged: "onUpdateStateChanged",
+  sidecarUpdateStateChanged: "onSidecarUpdateStateChanged",
+  workspaceServerStartupProgress: "onWorkspaceServerStartupProgress",
+  workspaceServerPrewarmProgress: "onWorkspaceServerPrewarmProgress",
+  workspaceServerExited: "onWorkspaceServerExited",
+  workspaceProxyConnectionChanged: "onWorkspaceProxyConnectionChanged",
+  windowCloseRequested: "onWindowCloseRequested",
+  previewFileChanged: "onPreviewFileChanged",
+  systemAppearanceChanged: "onSystemAppearanceChanged",
+  mobileRelayStateChanged: "onMobileRelayStateChanged",
+  workspaceFileChanged: "onWorkspaceFileChanged",
+  operationUpdated: "onOperationUpdated",
+  exportProgress: "onExportProgress",
+  exportComplete: "onExportComplete",
+  transcriptAppended: "onTranscriptAppended",
+  serverStats: "onServerStats",
+  workspaceConfigChanged: "onWorkspaceConfigChanged",
+  workspaceGitChanged: "onWorkspaceGitChanged",
+  appSettingsChanged: "onAppSettingsChanged",
+  transcriptRetentionCompleted: "onTranscriptRetentionCompleted",
+  lowDiskSpace: "onLowDiskSpace",
+  connectivityChanged: "onConnectivityChanged",
+  scheduledTaskRunCompleted: "onScheduledTaskRunCompleted",
+  serverSuspended: "onServerSuspended",
+  serverResumed: "onServerResumed",
+  serverReloaded: "onServerReloaded",
+  transcriptThrottled: "onTranscriptThrottled",
+  voiceTranscript: "onVoiceTranscript",
+} as const satisfies Record<DesktopEventChannelKey, DesktopApiListener>;
+
+/**
+ * Bumped when a change cannot be seen from the command and event lists, such
+ * as a channel changing what it means without changing its name.
+ */
+export const DESKTOP_IPC_CONTRACT_VERSION = 1;
+
+/**
+ * Payload schema version for each command (by `DesktopApi` method) or event
+ * whose arguments, result or payload changed incompatibly; unlisted ones are at 1.
+ */
+export const DESKTOP_IPC_SCHEMA_VERSIONS: {
+  commands: Partial<Record<DesktopApiCommand, number>>;
+  events: Partial<Record<DesktopEventChannelKey, number>>;
+} = {
+  commands: {},
+  events: {},
+};
+
+export function describeDesktopBackendCapabilities(): DesktopBackendCapabilities {
+  return {
+    contractVersion: DESKTOP_IPC_CONTRACT_VERSION,
+    commands: Object.values(DESKTOP_IPC_COMMANDS)
+      .filter((command): command is DesktopApiCommand => command !== null)
+      .sort(),
+    events: Object.keys(DESKTOP_EVENT_LISTENERS).sort(),
+    schemaVersions: {
+      commands: { ...DESKTOP_IPC_SCHEMA_VERSIONS.commands },
+      events: { ...DESKTOP_IPC_SCHEMA_VERSIONS.events },
+    },
+  };
+}
+
+/**
+ * Whether a backend reporting `capabilities` serves `command` at `minSchemaVersion`
+ * or later. Backends without capability reporting are taken at their preload's word.
+ */
+export function backendSupportsCommand(
+  capabilities: DesktopBackendCapabilities | null,
+  command: DesktopApiCommand,
+  minSchemaVersion = 1,
+): boolean {
+  if (!capabilities) {
+    return minSchemaVersion <= 1;
+  }
+  return (
+    capabilities.commands.includes(command) &&
+    (capabilities.schemaVersions.commands[command] ?? 1) >= minSchemaVersion
+  );
+}
+
+type PublicIpcChannelKey = {
+  [K in DesktopIpcChannelKey]: (typeof DESKTOP_IPC_COMMANDS)[K] extends null ? never : K;
+}[DesktopIpcChannelKey];
+
+type CommandFor<K extends PublicIpcChannelKey> = NonNullable<
+  DesktopApi[NonNullable<(typeof DESKTOP_IPC_COMMANDS)[K]>]
+>;
+
+export type DesktopIpcArgs<K extends PublicIpcChannelKey> = Parameters<CommandFor<K>>;
+export type DesktopIpcResult<K extends PublicIpcChannelKey> = Awaited<ReturnType<CommandFor<K>>>;
+export type DesktopEventPayload<K extends DesktopEventChannelKey> = Parameters<
+  Parameters<NonNullable<DesktopApi[(typeof DESKTOP_EVENT_LISTENERS)[K]]>>[0]
+>[0];
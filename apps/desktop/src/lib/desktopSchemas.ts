@@ -1,5 +1,6 @@
 import { z } from "zod";
 import type { WorkspaceFileChangeEvent } from "../../../../src/filesystem/workspaceFileEvents";
+import { MAX_ATTACHMENT_UPLOAD_BYTE_SIZE } from "../../../../src/shared/attachments";
 import type {
   FileChangeVersion,
   WorkspaceFileChangeEvent as PreviewFileChangeEvent,
@@ -18,6 +19,8 @@ import {
   PRODUCT_ANALYTICS_EVENT_NAMES,
   type ProductAnalyticsProperties,
 } from "../../../../src/telemetry/productAnalytics";
+import { PROVIDER_NAMES } from "../../../../src/types";
+import { isSshWorkspacePath } from "../app/remoteWorkspaces";
 import {
   normalizeCloudSyncSettings,
   normalizePersistedProductAnalyticsState,
@@ -27,64 +30,216 @@ import {
   type PersistedProductAnalyticsState,
   type PersistedState,
 } from "../app/types";
+import { normalizeWorkspaceContainerLaunch } from "../app/workspaceContainer";
+import {
+  MAX_WORKSPACE_IDLE_TIMEOUT_MINUTES,
+  normalizeWorkspaceIdlePolicy,
+} from "../app/workspaceIdlePolicy";
+import {
+  launchProfilesError,
+  MAX_LAUNCH_PROFILE_NAME_LENGTH,
+  normalizeWorkspaceLaunchProfiles,
+} from "../app/workspaceLaunchProfiles";
+import { MAX_PATH_POLICY_ENTRIES, normalizeWorkspacePathPolicy } from "../app/workspacePathPolicy";
+import { normalizeWorkspaceResourceLimits } from "../app/workspaceResourceLimits";
+import {
+  MAX_WORKSPACE_TAG_COUNT,
+  MAX_WORKSPACE_TAG_LENGTH,
+  normalizeWorkspaceTags,
+} from "../app/workspaceTags";
+import { normalizeWorkspaceTranscriptMirror } from "../app/workspaceTranscriptMirror";
 import type {
+  AnnotateEventInput,
+  AppendVoiceAudioInput,
+  AppLogsResult,
+  AppSettings,
+  ArchiveThreadInput,
   AuthorizeUploadSourceInput,
+  BackupSyncConfig,
+  BackupTarget,
   CaptureProductEventInput,
+  CleanupWorkspaceStorageInput,
+  ConfigureBackupSyncInput,
   ConfirmActionInput,
+  ConnectivityStatus,
   ContextMenuItem,
   CopyFileToWorkspaceUploadsInput,
   CopyPathInput,
+  CopyTranscriptSelectionInput,
+  CopyTranscriptSelectionResult,
   CreateDirectoryInput,
   CreateOneOffChatWorkspaceInput,
+  CreateProfileInput,
+  CreateScheduledTaskInput,
+  CreateScratchThreadInput,
+  DeletePromptTemplateInput,
+  DeleteScheduledTaskInput,
   DeleteTranscriptInput,
+  DeleteWorkspaceInput,
+  DesktopBackendCapabilities,
+  DesktopDeepLinkNavigation,
   DesktopMenuCommand,
   DesktopNotificationInput,
+  DesktopProfile,
+  DesktopProfileList,
   DiagnosticsBundlePathInput,
+  DiskSpaceStatus,
+  DroppedWorkspaceFolder,
+  DuplicateWorkspaceInput,
+  EnqueueExportInput,
+  ExportAppDataInput,
+  ForkThreadInput,
+  GenerateHandoffLinkInput,
+  GetAttachmentInput,
+  GetThumbnailInput,
+  GetThreadFileChangesInput,
+  GetThreadPreviewsInput,
+  GetUsageSummaryInput,
+  GetWorkspaceStorageReportInput,
+  GlobalSearchInput,
+  ImportAppDataInput,
+  ListAnnotationsInput,
+  ListAvailableModelsInput,
   ListDirectoryInput,
+  ListPendingApprovalsInput,
+  ListPromptTemplatesInput,
+  LoadThreadDraftInput,
+  MergeThreadsInput,
+  MigrateTranscriptStoreInput,
   MobileRelayForgetTrustedPhoneInput,
   MobileRelayStartInput,
   MobileRelayUpdateTrustedPhonePermissionsInput,
   OpenExternalUrlInput,
   OpenPathInput,
+  OpenTranscriptFileInput,
+  OperationIdInput,
+  OperationSnapshot,
+  OrphanedServer,
+  OrphanedServerCleanupResult,
+  PersistScratchThreadInput,
   PickCanvasSavePathInput,
   PickDirectoryInput,
   PlatformChromeInfo,
   PreferredFileAppInput,
   PreviewOSFileInput,
+  PreviewPathPolicyInput,
+  PreviewTranscriptRetentionInput,
+  PrewarmWorkspaceServersInput,
+  PrewarmWorkspaceServersResult,
+  ProviderCredentialValidation,
+  ProxyConnectivityResult,
+  PurgeThreadsInput,
+  ReadAppLogsInput,
   ReadFileForPreviewInput,
   ReadFileInput,
   ReadTranscriptInput,
+  ReadTranscriptPageInput,
+  ReadWorkspaceConfigInput,
+  RebuildStateInput,
+  RecordPendingApprovalInput,
+  RedactTranscriptInput,
+  RegisterRemoteWorkspaceInput,
+  ReleaseThreadServerInput,
   RenamePathInput,
   RendererLogInput,
+  RenderPromptInput,
+  ReorderWorkspacesInput,
+  ReplaySession,
+  RequestYoloGrantInput,
+  ResolveApprovalInput,
+  ResolveDroppedWorkspaceFolderInput,
+  RestoreFromTrashInput,
+  RestoreStateSnapshotInput,
+  RestoreWorkspaceCheckpointInput,
+  RestoreWorkspaceCheckpointResult,
   RevealPathInput,
+  RevokeHandoffInput,
+  SaveBackupCredentialsInput,
+  SaveBackupCredentialsResult,
   SaveExportedFileInput,
+  SavePromptTemplateInput,
+  SaveThreadDraftInput,
+  ScanForWorkspacesInput,
+  ScanForWorkspacesResult,
+  ScheduledTaskRun,
+  ServerReloadedEvent,
+  ServerResumedEvent,
+  ServerStatsEvent,
+  ServerSuspendedEvent,
+  SetLocaleInput,
+  SetLogLevelInput,
+  SetScheduledTaskEnabledInput,
+  SetTranscriptEncryptionInput,
   SetWindowAppearanceInput,
+  SetWorkspaceIdlePolicyInput,
+  SetWorkspaceLaunchProfilesInput,
+  SetWorkspacePathPolicyInput,
+  SetWorkspacePinnedInput,
+  SetWorkspaceTagsInput,
+  ShareTranscriptInput,
   ShowCanvasWindowInput,
   ShowContextMenuInput,
   ShowQuickChatWindowInput,
+  SidecarUpdateState,
+  StartOperationInput,
+  StartReplayInput,
+  StartVoiceCaptureInput,
   StartWorkspaceServerInput,
+  StopReplayInput,
+  StopVoiceCaptureInput,
   StopWorkspaceServerInput,
+  StoreAttachmentInput,
+  SuggestThreadTitleInput,
+  SwitchProfileInput,
   SystemAppearance,
   TelemetryStatusInput,
   TelemetryStatusSnapshot,
+  TestProxyConnectivityInput,
+  ThreadFileChanges,
+  TranscriptAppendedEvent,
   TranscriptBatchInput,
+  TranscriptInspection,
+  TranscriptRedactionResult,
+  TranscriptRepairResult,
+  TranscriptRetentionPreview,
+  TranscriptRetentionSummary,
+  TranscriptThrottledEvent,
+  TranscriptTimestampNormalizationResult,
   TrashPathInput,
+  UpdateAppSettingsInput,
   UpdaterProgress,
   UpdaterReleaseInfo,
   UpdaterState,
   UploadDiagnosticsBundleInput,
+  UsageSummary,
+  ValidateProviderCredentialsInput,
+  VoiceTranscriptEvent,
   WatchWorkspaceDirectoryInput,
   WindowCloseRequest,
   WindowCloseResponseInput,
   WindowDragPointInput,
+  WorkspaceCheckpoint,
+  WorkspaceCheckpointList,
+  WorkspaceConfigSnapshot,
+  WorkspaceGitCommit,
+  WorkspaceGitStatus,
+  WorkspaceHealthReport,
+  WorkspaceProxyConnectionEvent,
   WorkspaceServerExitedEvent,
+  WorkspaceServerPrewarmProgress,
+  WorkspaceServerStartupDiagnostics,
   WorkspaceServerStartupProgress,
+  WorkspaceServerStats,
   WorkspaceServerStatus,
+  WorkspaceServerVersionInfo,
+  WorkspaceWatcherStatus,
   WriteFileInput,
+  WriteWorkspaceConfigInput,
 } from "./desktopApi";
 import { normalizeQuickChatShortcutAccelerator } from "./quickChatShortcut";
 
 const SAFE_ID = /^[A-Za-z0-9_-]{1,256}$/;
+const STATE_SNAPSHOT_NAME = /^state-\d{8}T\d{9}Z\.json$/;
 const invalidPathSegmentPattern = /[/\\\0]/;
 
 const nonEmptyStringSchema = z.string().trim().min(1);
@@ -277,155 +432,1244 @@ const persistedCloudSyncSettingsSchema = z.preprocess(
   }),
 );
 
-const telemetryStatusEntrySchema = z
+const telemetryStatusEntrySchema = z
+  .object({
+    label: z.enum([
+      "Disabled",
+      "Not configured",
+      "Enabled",
+      "Metadata only",
+      "Full payload",
+      "Local only",
+      "Upload configured",
+      "Connected",
+      "Error",
+    ]),
+    status: z.enum([
+      "disabled",
+      "not_configured",
+      "enabled",
+      "metadata_only",
+      "full_payload",
+      "local_only",
+      "upload_configured",
+      "connected",
+      "error",
+    ]),
+    configured: z.boolean(),
+    enabled: z.boolean(),
+    message: z.string().optional(),
+  })
+  .strict();
+
+export const telemetryStatusSnapshotSchema: z.ZodType<TelemetryStatusSnapshot> = z
+  .object({
+    globalKillSwitchActive: z.boolean(),
+    crashReports: telemetryStatusEntrySchema,
+    productAnalytics: telemetryStatusEntrySchema,
+    aiTraces: telemetryStatusEntrySchema,
+    diagnosticsUpload: telemetryStatusEntrySchema,
+    cloudSync: telemetryStatusEntrySchema,
+  })
+  .strict();
+
+export const telemetryStatusInputSchema: z.ZodType<TelemetryStatusInput> = z
+  .object({
+    privacyTelemetrySettings: persistedPrivacyTelemetrySettingsSchema.optional(),
+  })
+  .strict();
+
+const persistedProductAnalyticsStateSchema = z.preprocess(
+  (value) =>
+    normalizePersistedProductAnalyticsState(
+      value && typeof value === "object" && !Array.isArray(value)
+        ? (value as PersistedProductAnalyticsState)
+        : undefined,
+    ),
+  z
+    .object({
+      anonymousInstallationId: z.string().trim().min(16).max(128).optional(),
+      lastAppVersion: z.string().trim().min(1).nullable().optional(),
+    })
+    .optional(),
+);
+
+export const startWorkspaceServerInputSchema: z.ZodType<StartWorkspaceServerInput> = z.object({
+  workspaceId: safeIdSchema,
+  workspacePath: nonEmptyStringSchema,
+  yolo: z.boolean(),
+  yoloToken: nonEmptyStringSchema.max(256).optional(),
+  forceRestart: z.boolean().optional(),
+  preserveMobileRelay: z.boolean().optional(),
+  featureFlags: desktopFeatureFlagOverridesSchema.optional(),
+  privacyTelemetrySettings: persistedPrivacyTelemetrySettingsSchema.optional(),
+  launchProfile: z.string().trim().min(1).max(MAX_LAUNCH_PROFILE_NAME_LENGTH).optional(),
+  threadId: safeIdSchema.optional(),
+});
+
+export const requestYoloGrantInputSchema: z.ZodType<RequestYoloGrantInput> = z
+  .object({ workspaceId: safeIdSchema })
+  .strict();
+
+export const MAX_WORKSPACE_SERVER_PREWARM_CONCURRENCY = 8;
+
+const prewarmWorkspaceServersInputShape = {
+  workspaceIds: z.array(safeIdSchema).max(64),
+  concurrency: z.number().int().min(1).max(MAX_WORKSPACE_SERVER_PREWARM_CONCURRENCY).optional(),
+  featureFlags: desktopFeatureFlagOverridesSchema.optional(),
+  privacyTelemetrySettings: persistedPrivacyTelemetrySettingsSchema.optional(),
+};
+
+export const prewarmWorkspaceServersInputSchema: z.ZodType<PrewarmWorkspaceServersInput> = z
+  .object(prewarmWorkspaceServersInputShape)
+  .strict();
+
+export const workspaceServerPrewarmProgressSchema: z.ZodType<WorkspaceServerPrewarmProgress> = z
+  .object({
+    workspaceId: safeIdSchema,
+    status: z.enum(["queued", "starting", "ready", "failed"]),
+    url: z.string().min(1).optional(),
+    error: z.string().optional(),
+  })
+  .strict();
+
+export const prewarmWorkspaceServersResultSchema: z.ZodType<PrewarmWorkspaceServersResult> = z
+  .object({
+    results: z.record(
+      safeIdSchema,
+      z.discriminatedUnion("status", [
+        z.object({ status: z.literal("ready"), url: z.string().min(1) }).strict(),
+        z.object({ status: z.literal("failed"), error: z.string() }).strict(),
+      ]),
+    ),
+  })
+  .strict();
+
+export const workspaceServerStartupProgressSchema: z.ZodType<WorkspaceServerStartupProgress> = z
+  .object({
+    workspaceId: safeIdSchema,
+    progress: z
+      .object({
+        phase: z.enum(["waiting", "downloading", "installing", "ready"]),
+        version: z
+          .string()
+          .trim()
+          .regex(/^\d{4}-\d{2}-\d{2}$/),
+        transferredBytes: z.number().finite().nonnegative().nullable(),
+        totalBytes: z.number().finite().nonnegative().nullable(),
+        percent: z.number().finite().min(0).max(100).nullable(),
+      })
+      .strict(),
+  })
+  .strict();
+
+export const workspaceServerStatusSchema: z.ZodType<WorkspaceServerStatus> = z
+  .object({
+    workspaceId: safeIdSchema,
+    running: z.boolean(),
+    url: z.string().min(1).nullable(),
+    reason: z.enum(["running", "starting", "not_found", "exited", "health_failed"]),
+    error: z.string().min(1).optional(),
+  })
+  .strict();
+
+export const workspaceServerVersionInfoSchema: z.ZodType<WorkspaceServerVersionInfo> = z
+  .object({
+    workspaceId: safeIdSchema,
+    running: z.boolean(),
+    version: z.string().min(1).nullable(),
+    protocolVersion: z.string().min(1).nullable(),
+    minProtocolVersion: z.string().min(1),
+    compatible: z.boolean().nullable(),
+  })
+  .strict();
+
+export const workspaceServerStartupDiagnosticsSchema: z.ZodType<WorkspaceServerStartupDiagnostics> =
+  z
+    .object({
+      workspaceId: safeIdSchema,
+      stage: z.enum(["spawn", "startup", "health_check"]),
+      message: z.string(),
+      mode: z.enum(["source", "packaged"]),
+      command: z.string().min(1),
+      args: z.array(z.string()),
+      cwd: z.string(),
+      launcherFound: z.boolean(),
+      stderrTail: z.string(),
+      exitCode: z.number().int().nullable(),
+      signal: z.string().min(1).nullable(),
+      failedAt: z.string().min(1),
+    })
+    .strict();
+
+export const workspaceServerStatsSchema: z.ZodType<WorkspaceServerStats> = z
+  .object({
+    workspaceId: safeIdSchema,
+    running: z.boolean(),
+    pid: z.number().int().positive().nullable(),
+    cpuPercent: z.number().finite().nonnegative().nullable(),
+    rssBytes: z.number().finite().nonnegative().nullable(),
+    uptimeMs: z.number().finite().nonnegative().nullable(),
+    sampledAt: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const serverStatsEventSchema: z.ZodType<ServerStatsEvent> = z.object({
+  workspaces: z.array(workspaceServerStatsSchema),
+});
+
+const workspaceHealthStatusSchema = z.enum(["ok", "warning", "critical", "unknown"]);
+
+export const workspaceHealthReportSchema: z.ZodType<WorkspaceHealthReport> = z.object({
+  workspaceId: safeIdSchema,
+  score: z.number().finite().min(0).max(100),
+  status: z.enum(["ok", "warning", "critical"]),
+  checks: z.array(
+    z.object({
+      id: z.enum([
+        "serverStability",
+        "transcriptSize",
+        "indexFreshness",
+        "guardrails",
+        "secretsExposure",
+      ]),
+      status: workspaceHealthStatusSchema,
+      summary: nonEmptyStringSchema,
+      recommendation: nonEmptyStringSchema.nullable(),
+    }),
+  ),
+  generatedAt: nonEmptyStringSchema,
+});
+
+export const orphanedServerListSchema: z.ZodType<OrphanedServer[]> = z.array(
+  z.object({
+    pid: z.number().int().positive(),
+    workspaceId: safeIdSchema.nullable(),
+    startedAt: nonEmptyStringSchema.nullable(),
+    command: z.string(),
+  }),
+);
+
+export const orphanedServerCleanupResultSchema: z.ZodType<OrphanedServerCleanupResult> =
+  z.object({
+    stopped: z.array(z.number().int().positive()),
+    failed: z.array(z.object({ pid: z.number().int().positive(), error: z.string() })),
+  });
+
+const workspaceConfigSchema = z
+  .object({
+    provider: z.enum(PROVIDER_NAMES).optional(),
+    model: nonEmptyStringSchema.optional(),
+    enableMcp: z.boolean().optional(),
+    yolo: z.boolean().optional(),
+  })
+  .strict();
+
+export const readWorkspaceConfigInputSchema: z.ZodType<ReadWorkspaceConfigInput> = z.object({
+  workspaceId: safeIdSchema,
+});
+
+export const writeWorkspaceConfigInputSchema: z.ZodType<WriteWorkspaceConfigInput> = z.object({
+  workspaceId: safeIdSchema,
+  config: z
+    .object({
+      provider: z.enum(PROVIDER_NAMES).nullable().optional(),
+      model: nonEmptyStringSchema.nullable().optional(),
+      enableMcp: z.boolean().nullable().optional(),
+      yolo: z.boolean().nullable().optional(),
+    })
+    .strict(),
+});
+
+export const listAvailableModelsInputSchema: z.ZodType<ListAvailableModelsInput> = z
+  .object({
+    provider: z.enum(PROVIDER_NAMES),
+    workspaceId: safeIdSchema.optional(),
+    refresh: z.boolean().optional(),
+  })
+  .strict();
+
+export const workspaceConfigSnapshotSchema: z.ZodType<WorkspaceConfigSnapshot> = z.object({
+  workspaceId: safeIdSchema,
+  path: nonEmptyStringSchema,
+  exists: z.boolean(),
+  error: z.string().nullable(),
+  config: workspaceConfigSchema,
+  effective: z.object({
+    provider: z.enum(PROVIDER_NAMES).nullable(),
+    model: nonEmptyStringSchema.nullable(),
+    enableMcp: z.boolean(),
+    yolo: z.boolean(),
+  }),
+});
+
+const workspaceGitCommitSchema: z.ZodType<WorkspaceGitCommit> = z.object({
+  sha: nonEmptyStringSchema,
+  summary: z.string(),
+  author: z.string(),
+  committedAt: nonEmptyStringSchema,
+});
+
+export const workspaceGitStatusSchema: z.ZodType<WorkspaceGitStatus> = z.object({
+  workspaceId: safeIdSchema,
+  isRepository: z.boolean(),
+  branch: nonEmptyStringSchema.nullable(),
+  upstream: nonEmptyStringSchema.nullable(),
+  ahead: z.number().int().nonnegative().nullable(),
+  behind: z.number().int().nonnegative().nullable(),
+  dirtyFileCount: z.number().int().nonnegative(),
+  lastCommit: workspaceGitCommitSchema.nullable(),
+});
+
+const gitObjectIdSchema = z.string().regex(/^[0-9a-f]{7,64}$/, "Invalid git object id");
+
+const workspaceCheckpointSchema: z.ZodType<WorkspaceCheckpoint> = z.object({
+  id: gitObjectIdSchema,
+  createdAt: nonEmptyStringSchema,
+  reason: z.enum(["yolo-start", "before-restore"]),
+  head: gitObjectIdSchema.nullable(),
+});
+
+export const workspaceCheckpointListSchema: z.ZodType<WorkspaceCheckpointList> = z.object({
+  workspaceId: safeIdSchema,
+  isRepository: z.boolean(),
+  checkpoints: z.array(workspaceCheckpointSchema),
+});
+
+export const restoreWorkspaceCheckpointInputSchema: z.ZodType<RestoreWorkspaceCheckpointInput> =
+  z.object({
+    workspaceId: safeIdSchema,
+    checkpointId: gitObjectIdSchema,
+  });
+
+export const restoreWorkspaceCheckpointResultSchema: z.ZodType<RestoreWorkspaceCheckpointResult> =
+  z.object({
+    workspaceId: safeIdSchema,
+    checkpointId: gitObjectIdSchema,
+    safetyCheckpoint: workspaceCheckpointSchema.nullable(),
+    removedFileCount: z.number().int().nonnegative(),
+  });
+
+export const validateProviderCredentialsInputSchema: z.ZodType<ValidateProviderCredentialsInput> =
+  z.object({
+    provider: z.enum(PROVIDER_NAMES),
+    apiKey: z.string().trim().min(1).max(4096).optional(),
+    force: z.boolean().optional(),
+  });
+
+const quotaCountSchema = z.number().nonnegative().nullable();
+
+export const providerCredentialValidationSchema: z.ZodType<ProviderCredentialValidation> =
+  z.object({
+    provider: z.enum(PROVIDER_NAMES),
+    status: z.enum(["valid", "invalid", "unreachable", "missing", "unsupported"]),
+    keySource: z.enum(["input", "saved", "environment"]).nullable(),
+    keyMask: z.string().nullable(),
+    httpStatus: z.number().int().nullable(),
+    latencyMs: z.number().nonnegative().nullable(),
+    quota: z
+      .object({
+        requestsLimit: quotaCountSchema,
+        requestsRemaining: quotaCountSchema,
+        tokensLimit: quotaCountSchema,
+        tokensRemaining: quotaCountSchema,
+        resetAt: z.string().nullable(),
+      })
+      .nullable(),
+    message: z.string(),
+    checkedAt: nonEmptyStringSchema,
+    cached: z.boolean(),
+  });
+
+const usageSummaryRangeSchema = z.enum(["today", "7d", "30d", "all"]);
+
+export const getUsageSummaryInputSchema: z.ZodType<GetUsageSummaryInput> = z.object({
+  workspaceId: safeIdSchema.optional(),
+  range: usageSummaryRangeSchema,
+});
+
+const usageCountSchema = z.number().int().nonnegative();
+
+const usageTotalsSchema = z.object({
+  turns: usageCountSchema,
+  promptTokens: usageCountSchema,
+  completionTokens: usageCountSchema,
+  cachedPromptTokens: usageCountSchema,
+  reasoningOutputTokens: usageCountSchema,
+  totalTokens: usageCountSchema,
+  estimatedCostUsd: z.number().nonnegative().nullable(),
+  unpricedTurns: usageCountSchema,
+});
+
+export const usageSummarySchema: z.ZodType<UsageSummary> = z.object({
+  workspaceId: safeIdSchema.nullable(),
+  range: usageSummaryRangeSchema,
+  since: nonEmptyStringSchema.nullable(),
+  totals: usageTotalsSchema,
+  byDay: z.array(usageTotalsSchema.extend({ day: nonEmptyStringSchema })),
+  byWorkspace: z.array(usageTotalsSchema.extend({ workspaceId: safeIdSchema.nullable() })),
+  byThread: z.array(
+    usageTotalsSchema.extend({
+      threadId: nonEmptyStringSchema,
+      workspaceId: safeIdSchema.nullable(),
+    }),
+  ),
+});
+
+export const MAX_TRANSCRIPT_RETENTION_DAYS = 3650;
+export const MAX_TRANSCRIPT_RETENTION_TOTAL_MB = 1_000_000;
+export const MAX_MIN_FREE_DISK_MB = 100_000;
+export const MAX_TRASH_RETENTION_DAYS = 365;
+export const MAX_ISOLATED_SIDECARS = 16;
+export const MAX_IDLE_SERVER_TIMEOUT_MINUTES = 1440;
+export const MAX_YOLO_GRANT_MINUTES = 1440;
+/** A language subtag with optional region/script subtags, e.g. `en`, `pt-BR`, `zh_Hant_TW`. */
+export const LOCALE_TAG_PATTERN = /^[A-Za-z]{2,3}(?:[-_][A-Za-z0-9]{2,8})*$/;
+
+function isHttpUrl(value: string): boolean {
+  try {
+    const parsed = new URL(value);
+    return parsed.protocol === "http:" || parsed.protocol === "https:";
+  } catch {
+    return false;
+  }
+}
+
+/** Accepts POSIX, drive-letter, and UNC paths; the main process re-checks the file itself. */
+function isAbsolutePathLike(value: string): boolean {
+  return value.startsWith("/") || /^[A-Za-z]:[\\/]/.test(value) || value.startsWith("\\\\");
+}
+
+const proxyUrlSchema = nonEmptyStringSchema
+  .max(2048)
+  .refine(isHttpUrl, "Proxy URL must use the http: or https: scheme");
+
+const endpointUrlSchema = nonEmptyStringSchema
+  .max(2048)
+  .refine(isHttpUrl, "Endpoint URL must use the http: or https: scheme");
+
+const appSettingsShape = {
+  defaultProvider: z.enum(PROVIDER_NAMES).nullable(),
+  startupBehavior: z.enum(["restoreLastThread", "newChat"]),
+  transcriptRetentionDays: z.number().int().min(0).max(MAX_TRANSCRIPT_RETENTION_DAYS),
+  transcriptRetentionMaxTotalMb: z.number().int().min(0).max(MAX_TRANSCRIPT_RETENTION_TOTAL_MB),
+  minFreeDiskMb: z.number().int().min(0).max(MAX_MIN_FREE_DISK_MB),
+  trashRetentionDays: z.number().int().min(0).max(MAX_TRASH_RETENTION_DAYS),
+  transcriptValidation: z.enum(["off", "annotate", "strict"]),
+  notificationsEnabled: z.boolean(),
+  httpProxy: proxyUrlSchema.nullable(),
+  httpsProxy: proxyUrlSchema.nullable(),
+  noProxy: nonEmptyStringSchema.max(4096).nullable(),
+  caBundlePath: nonEmptyStringSchema
+    .max(4096)
+    .refine(isAbsolutePathLike, "CA bundle path must be absolute")
+    .nullable(),
+  themeSource: z.enum(["system", "light", "dark"]),
+  isolatedSessions: z.boolean(),
+  maxIsolatedSidecars: z.number().int().min(1).max(MAX_ISOLATED_SIDECARS),
+  locale: z.string().max(35).regex(LOCALE_TAG_PATTERN, "must be a locale tag").nullable(),
+  idleServerSuspend: z.enum(["never", "onBattery", "always"]),
+  idleServerTimeoutMinutes: z.number().int().min(1).max(MAX_IDLE_SERVER_TIMEOUT_MINUTES),
+  yoloGrantMinutes: z.number().int().min(1).max(MAX_YOLO_GRANT_MINUTES),
+  voiceTranscriptionBaseUrl: endpointUrlSchema.nullable(),
+  voiceTranscriptionModel: nonEmptyStringSchema.max(200).nullable(),
+  transcriptStore: z.enum(["jsonl", "sqlite"]),
+};
+
+export const appSettingsSchema: z.ZodType<AppSettings> = z.object(appSettingsShape);
+
+/** Lowercase letters, digits, and inner dashes; also names the profile's directory. */
+export const profileIdSchema = z.string().regex(/^[a-z0-9](?:[a-z0-9-]{0,38}[a-z0-9])?$/);
+
+export const desktopProfileSchema: z.ZodType<DesktopProfile> = z.object({
+  id: profileIdSchema,
+  name: nonEmptyStringSchema,
+  createdAt: z.string(),
+});
+
+export const desktopProfileListSchema: z.ZodType<DesktopProfileList> = z.object({
+  currentProfileId: profileIdSchema,
+  lastUsedProfileId: profileIdSchema,
+  profiles: z.array(desktopProfileSchema),
+});
+
+export const createProfileInputSchema: z.ZodType<CreateProfileInput> = z
+  .object({
+    name: z.string().trim().min(1).max(80),
+  })
+  .strict();
+
+export const switchProfileInputSchema: z.ZodType<SwitchProfileInput> = z
+  .object({
+    profileId: profileIdSchema,
+  })
+  .strict();
+
+export const updateAppSettingsInputSchema: z.ZodType<UpdateAppSettingsInput> = z
+  .object(appSettingsShape)
+  .omit({ transcriptStore: true })
+  .partial()
+  .strict();
+
+export const previewTranscriptRetentionInputSchema: z.ZodType<PreviewTranscriptRetentionInput> = z
+  .object({
+    maxAgeDays: appSettingsShape.transcriptRetentionDays.optional(),
+    maxTotalMb: appSettingsShape.transcriptRetentionMaxTotalMb.optional(),
+  })
+  .strict();
+
+export const setLocaleInputSchema: z.ZodType<SetLocaleInput> = z
+  .object({ locale: appSettingsShape.locale })
+  .strict();
+
+export const testProxyConnectivityInputSchema: z.ZodType<TestProxyConnectivityInput> = z
+  .object({ url: nonEmptyStringSchema.max(2048).refine(isHttpUrl, "URL must use http: or https:") })
+  .strict();
+
+export const proxyConnectivityResultSchema: z.ZodType<ProxyConnectivityResult> = z.object({
+  url: nonEmptyStringSchema,
+  proxy: nonEmptyStringSchema.nullable(),
+  ok: z.boolean(),
+  httpStatus: z.number().int().min(100).max(599).nullable(),
+  latencyMs: z.number().nonnegative(),
+  message: z.string(),
+});
+
+const byteCountSchema = z.number().int().nonnegative();
+
+export const transcriptRetentionPreviewSchema: z.ZodType<TranscriptRetentionPreview> = z.object({
+  maxAgeDays: z.number().int().nonnegative(),
+  maxTotalMb: z.number().int().nonnegative(),
+  enabled: z.boolean(),
+  totalBytes: byteCountSchema,
+  reclaimableBytes: byteCountSchema,
+  candidates: z.array(
+    z.object({
+      transcriptId: nonEmptyStringSchema,
+      threadId: safeIdSchema.nullable(),
+      sizeBytes: byteCountSchema,
+      lastModifiedAt: nonEmptyStringSchema,
+      reason: z.enum(["age", "diskUsage"]),
+    }),
+  ),
+});
+
+export const transcriptRetentionSummarySchema: z.ZodType<TranscriptRetentionSummary> = z.object({
+  ranAt: nonEmptyStringSchema,
+  deletedCount: z.number().int().nonnegative(),
+  reclaimedBytes: byteCountSchema,
+  failures: z.array(z.object({ transcriptId: nonEmptyStringSchema, message: z.string() })),
+});
+
+export const diskSpaceStatusSchema: z.ZodType<DiskSpaceStatus> = z.object({
+  freeBytes: byteCountSchema.nullable(),
+  minFreeBytes: byteCountSchema,
+  low: z.boolean(),
+  bufferedTranscriptEvents: z.number().int().nonnegative(),
+});
+
+export const transcriptThrottledEventSchema: z.ZodType<TranscriptThrottledEvent> = z
+  .object({
+    threadId: safeIdSchema,
+    droppedEvents: z.number().int().nonnegative(),
+    droppedBytes: z.number().int().nonnegative(),
+    truncatedEvents: z.number().int().nonnegative(),
+    at: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const voiceTranscriptEventSchema: z.ZodType<VoiceTranscriptEvent> = z
+  .object({
+    captureId: safeIdSchema,
+    threadId: safeIdSchema,
+    text: z.string(),
+    final: z.boolean(),
+  })
+  .strict();
+
+export const connectivityStatusSchema: z.ZodType<ConnectivityStatus> = z.object({
+  state: z.enum(["online", "degraded", "offline"]),
+  unreachableHosts: z.array(z.string().min(1)),
+  checkedAt: z.string().min(1).nullable(),
+  message: z.string(),
+});
+
+export const workspaceServerExitedEventSchema: z.ZodType<WorkspaceServerExitedEvent> = z
+  .object({
+    workspaceId: safeIdSchema,
+    url: z.string().min(1).nullable(),
+    code: z.number().int().nullable(),
+    signal: z.string().min(1).nullable(),
+    limitExceeded: z.enum(["memory", "lifetime"]).optional(),
+    threadId: nonEmptyStringSchema.optional(),
+  })
+  .strict();
+
+export const serverSuspendedEventSchema: z.ZodType<ServerSuspendedEvent> = z
+  .object({
+    workspaceId: safeIdSchema,
+    idleMinutes: z.number().int().nonnegative(),
+    onBattery: z.boolean(),
+    suspendedAt: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const serverResumedEventSchema: z.ZodType<ServerResumedEvent> = z
+  .object({
+    workspaceId: safeIdSchema,
+    suspendedAt: nonEmptyStringSchema,
+    resumedAt: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const serverReloadedEventSchema: z.ZodType<ServerReloadedEvent> = z
+  .object({
+    workspaceId: safeIdSchema,
+    threadId: nonEmptyStringSchema.optional(),
+    reason: nonEmptyStringSchema,
+    changedPaths: z.array(nonEmptyStringSchema),
+    reloadedAt: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const workspaceProxyConnectionEventSchema: z.ZodType<WorkspaceProxyConnectionEvent> = z
+  .object({
+    workspaceId: safeIdSchema,
+    state: z.enum(["connected", "reconnecting", "disconnected"]),
+    clientCount: z.number().int().nonnegative(),
+    bufferedMessages: z.number().int().nonnegative(),
+    reason: z.string().min(1).optional(),
+  })
+  .strict();
+
+export const createOneOffChatWorkspaceInputSchema: z.ZodType<CreateOneOffChatWorkspaceInput> =
+  z.object({
+    titleHint: z.string().trim().optional(),
+  });
+
+export const stopWorkspaceServerInputSchema: z.ZodType<StopWorkspaceServerInput> = z.object({
+  workspaceId: safeIdSchema,
+});
+
+export const releaseThreadServerInputSchema: z.ZodType<ReleaseThreadServerInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    threadId: safeIdSchema,
+  })
+  .strict();
+
+export const generateHandoffLinkInputSchema: z.ZodType<GenerateHandoffLinkInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    threadId: safeIdSchema,
+    ttlSeconds: z.number().int().min(60).max(86_400).optional(),
+    access: z.enum(["read", "readWrite"]).optional(),
+  })
+  .strict();
+
+export const revokeHandoffInputSchema: z.ZodType<RevokeHandoffInput> = z
+  .object({ tokenId: safeIdSchema })
+  .strict();
+
+const rendererLogMetaValueSchema = z.union([z.string(), z.number(), z.boolean(), z.null()]);
+export const rendererLogInputSchema: z.ZodType<RendererLogInput> = z
+  .object({
+    level: z.enum(["info", "warn", "error"]).optional(),
+    category: z.string().trim().min(1).max(80),
+    message: z.string().trim().min(1).max(200),
+    meta: z.record(z.string(), rendererLogMetaValueSchema).optional(),
+  })
+  .strict();
+
+const appLogLevelSchema = z.enum(["debug", "info", "warn", "error"]);
+
+export const setLogLevelInputSchema: z.ZodType<SetLogLevelInput> = z
+  .object({ level: appLogLevelSchema })
+  .strict();
+
+export const readAppLogsInputSchema: z.ZodType<ReadAppLogsInput> = z
+  .object({
+    tail: z.number().int().min(1).max(2000).optional(),
+    levelFilter: appLogLevelSchema.optional(),
+    file: z.enum(["desktop-main.log", "server.log", "renderer.log", "updater.log"]).optional(),
+  })
+  .strict();
+
+export const appLogsResultSchema: z.ZodType<AppLogsResult> = z.object({
+  level: appLogLevelSchema,
+  entries: z.array(
+    z.object({
+      ts: z.string(),
+      level: appLogLevelSchema,
+      category: z.string(),
+      message: z.string(),
+      meta: z.unknown().optional(),
+      file: nonEmptyStringSchema,
+    }),
+  ),
+  truncated: z.boolean(),
+});
+
+const productAnalyticsPropertyValueSchema = z.union([
+  z.string(),
+  z.number(),
+  z.boolean(),
+  z.null(),
+]);
+
+export const captureProductEventInputSchema: z.ZodType<CaptureProductEventInput> = z.object({
+  name: z.enum(PRODUCT_ANALYTICS_EVENT_NAMES),
+  properties: z
+    .record(z.string(), productAnalyticsPropertyValueSchema)
+    .optional()
+    .transform((value) => value as ProductAnalyticsProperties | undefined),
+});
+
+export const restoreStateSnapshotInputSchema: z.ZodType<RestoreStateSnapshotInput> = z.object({
+  name: nonEmptyStringSchema.regex(STATE_SNAPSHOT_NAME, "is not a valid snapshot name"),
+});
+
+export const rebuildStateInputSchema: z.ZodType<RebuildStateInput> = z.object({
+  dryRun: z.boolean(),
+});
+
+const appDataArchivePathSchema = nonEmptyStringSchema
+  .max(4096)
+  .refine(isAbsolutePathLike, "Archive path must be absolute");
+
+export const exportAppDataInputSchema: z.ZodType<ExportAppDataInput> = z
+  .object({
+    destPath: appDataArchivePathSchema.optional(),
+    includeTranscripts: z.boolean(),
+  })
+  .strict();
+
+const importAppDataInputShape = {
+  archivePath: appDataArchivePathSchema.optional(),
+  mergeStrategy: z.enum(["merge", "replace"]),
+};
+
+export const importAppDataInputSchema: z.ZodType<ImportAppDataInput> = z
+  .object(importAppDataInputShape)
+  .strict();
+
+export const createScratchThreadInputSchema: z.ZodType<CreateScratchThreadInput> = z.object({
+  workspaceId: safeIdSchema,
+});
+
+export const persistScratchThreadInputSchema: z.ZodType<PersistScratchThreadInput> = z.object({
+  threadId: safeIdSchema,
+  title: z.string().trim().min(1).max(500).optional(),
+  sessionId: nonEmptyStringSchema.nullable().optional(),
+  messageCount: z.number().int().nonnegative().optional(),
+  lastEventSeq: z.number().int().nonnegative().optional(),
+});
+
+export const readTranscriptInputSchema: z.ZodType<ReadTranscriptInput> = z.object({
+  threadId: safeIdSchema,
+});
+
+export const readTranscriptPageInputSchema: z.ZodType<ReadTranscriptPageInput> = z
+  .object({
+    threadId: safeIdSchema,
+    cursor: z
+      .string()
+      .regex(/^(archived|live|sqlite|memory):\d{1,15}$/, "is not a transcript cursor")
+      .optional(),
+    maxEvents: z.number().int().min(1).max(100_000).optional(),
+    maxBytes: z
+      .number()
+      .int()
+      .min(1)
+      .max(256 * 1024 * 1024)
+      .optional(),
+  })
+  .strict();
+
+export const getThreadPreviewsInputSchema: z.ZodType<GetThreadPreviewsInput> = z
+  .object({
+    threadIds: z.array(safeIdSchema).max(500),
+  })
+  .strict();
+
+export const openTranscriptFileInputSchema: z.ZodType<OpenTranscriptFileInput> = z
+  .object({
+    path: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const shareTranscriptInputSchema: z.ZodType<ShareTranscriptInput> = z
+  .object({
+    threadId: safeIdSchema,
+  })
+  .strict();
+
+export const transcriptInspectionSchema: z.ZodType<TranscriptInspection> = z.object({
+  threadId: safeIdSchema,
+  eventCount: z.number().int().nonnegative(),
+  corruptedLines: z.array(
+    z.object({
+      source: z.enum(["archived", "live"]),
+      line: z.number().int().positive(),
+      reason: z.enum(["unparseable", "invalid_event"]),
+    }),
+  ),
+});
+
+export const transcriptRepairResultSchema: z.ZodType<TranscriptRepairResult> = z.object({
+  threadId: safeIdSchema,
+  quarantinedLineCount: z.number().int().nonnegative(),
+  quarantinePath: nonEmptyStringSchema.nullable(),
+});
+
+export const transcriptTimestampNormalizationResultSchema: z.ZodType<TranscriptTimestampNormalizationResult> =
+  z.object({
+    threadId: safeIdSchema,
+    normalizedCount: z.number().int().nonnegative(),
+    unparseableCount: z.number().int().nonnegative(),
+  });
+
+const MAX_REDACTION_PATTERNS = 20;
+const MAX_REDACTION_PATTERN_LENGTH = 500;
+
+function isValidRegExp(source: string): boolean {
+  try {
+    new RegExp(source, "g");
+    return true;
+  } catch {
+    return false;
+  }
+}
+
+export const redactTranscriptInputSchema: z.ZodType<RedactTranscriptInput> = z.object({
+  threadId: safeIdSchema,
+  patterns: z
+    .array(
+      z
+        .string()
+        .min(1)
+        .max(MAX_REDACTION_PATTERN_LENGTH)
+        .refine(isValidRegExp, "Redaction patterns must be valid regular expressions."),
+    )
+    .max(MAX_REDACTION_PATTERNS)
+    .optional(),
+  dryRun: z.boolean(),
+});
+
+export const transcriptRedactionResultSchema: z.ZodType<TranscriptRedactionResult> = z.object({
+  threadId: safeIdSchema,
+  dryRun: z.boolean(),
+  redactionCount: z.number().int().nonnegative(),
+  redactedEventCount: z.number().int().nonnegative(),
+  matches: z.array(
+    z.object({
+      rule: nonEmptyStringSchema,
+      count: z.number().int().positive(),
+    }),
+  ),
+  sealedLineCount: z.number().int().nonnegative(),
+});
+
+export const getThreadFileChangesInputSchema: z.ZodType<GetThreadFileChangesInput> = z.object({
+  threadId: safeIdSchema,
+});
+
+export const threadFileChangesSchema: z.ZodType<ThreadFileChanges> = z.object({
+  threadId: safeIdSchema,
+  files: z.array(
+    z.object({
+      path: nonEmptyStringSchema,
+      displayPath: nonEmptyStringSchema,
+      changeCount: z.number().int().positive(),
+      hunkCount: z.number().int().nonnegative(),
+      linesAdded: z.number().int().nonnegative(),
+      linesRemoved: z.number().int().nonnegative(),
+      finalContentHash: z
+        .string()
+        .regex(/^[0-9a-f]{64}$/)
+        .nullable(),
+      lastChangedAt: nonEmptyStringSchema,
+    }),
+  ),
+});
+
+export const MIN_REPLAY_SPEED = 0.1;
+export const MAX_REPLAY_SPEED = 100;
+
+export const startReplayInputSchema: z.ZodType<StartReplayInput> = z.object({
+  threadId: safeIdSchema,
+  speed: z.number().min(MIN_REPLAY_SPEED).max(MAX_REPLAY_SPEED).optional(),
+});
+
+export const replaySessionSchema: z.ZodType<ReplaySession> = z.object({
+  replayId: safeIdSchema,
+  threadId: safeIdSchema,
+  sessionId: nonEmptyStringSchema,
+  url: nonEmptyStringSchema,
+  speed: z.number().min(MIN_REPLAY_SPEED).max(MAX_REPLAY_SPEED),
+  eventCount: z.number().int().nonnegative(),
+  durationMs: z.number().nonnegative(),
+});
+
+export const stopReplayInputSchema: z.ZodType<StopReplayInput> = z.object({
+  replayId: safeIdSchema,
+});
+
+export const archiveThreadInputSchema: z.ZodType<ArchiveThreadInput> = z.object({
+  threadId: safeIdSchema,
+});
+
+export const suggestThreadTitleInputSchema: z.ZodType<SuggestThreadTitleInput> = z.object({
+  threadId: safeIdSchema,
+});
+
+export const copyTranscriptSelectionInputSchema: z.ZodType<CopyTranscriptSelectionInput> = z
+  .object({
+    threadId: safeIdSchema,
+    fromIndex: z.number().int().nonnegative(),
+    toIndex: z.number().int().nonnegative(),
+    redactSecrets: z.boolean().optional(),
+  })
+  .refine((input) => input.fromIndex <= input.toIndex, {
+    message: "fromIndex must not be after toIndex",
+  });
+
+export const copyTranscriptSelectionResultSchema: z.ZodType<CopyTranscriptSelectionResult> =
+  z.object({
+    itemCount: z.number().int().nonnegative(),
+    redactionCount: z.number().int().nonnegative(),
+  });
+
+export const forkThreadInputSchema: z.ZodType<ForkThreadInput> = z.object({
+  threadId: safeIdSchema,
+  title: nonEmptyStringSchema.optional(),
+  throughEventIndex: z.number().int().nonnegative().optional(),
+});
+
+export const mergeThreadsInputSchema: z.ZodType<MergeThreadsInput> = z
+  .object({
+    sourceThreadId: safeIdSchema,
+    targetThreadId: safeIdSchema,
+    trashSource: z.boolean().optional(),
+  })
+  .strict();
+
+export const setTranscriptEncryptionInputSchema: z.ZodType<SetTranscriptEncryptionInput> =
+  z.object({
+    enabled: z.boolean(),
+  });
+
+const migrateTranscriptStoreInputShape = { target: appSettingsShape.transcriptStore };
+
+export const migrateTranscriptStoreInputSchema: z.ZodType<MigrateTranscriptStoreInput> = z
+  .object(migrateTranscriptStoreInputShape)
+  .strict();
+
+const confirmationTokenSchema = nonEmptyStringSchema.max(128);
+
+export const deleteTranscriptInputSchema: z.ZodType<DeleteTranscriptInput> = z.object({
+  threadId: safeIdSchema,
+  confirmationToken: confirmationTokenSchema.optional(),
+});
+
+export const purgeThreadsInputSchema: z.ZodType<PurgeThreadsInput> = z.object({
+  threadIds: z.array(safeIdSchema).min(1).max(1000),
+  confirmationToken: confirmationTokenSchema.optional(),
+});
+
+export const deleteWorkspaceInputSchema: z.ZodType<DeleteWorkspaceInput> = z.object({
+  workspaceId: safeIdSchema,
+  confirmationToken: confirmationTokenSchema.optional(),
+});
+
+export const getWorkspaceStorageReportInputSchema: z.ZodType<GetWorkspaceStorageReportInput> =
+  z.object({ workspaceId: safeIdSchema }).strict();
+
+export const cleanupWorkspaceStorageInputSchema: z.ZodType<CleanupWorkspaceStorageInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    targets: z
+      .array(z.enum(["compactTranscripts", "purgeArchivedThreads", "clearQuarantine"]))
+      .min(1)
+      .max(3),
+    dryRun: z.boolean().optional(),
+    confirmationToken: confirmationTokenSchema.optional(),
+  })
+  .strict();
+
+export const restoreFromTrashInputSchema: z.ZodType<RestoreFromTrashInput> = z
+  .object({
+    entryId: safeIdSchema,
+  })
+  .strict();
+
+export const setWorkspacePinnedInputSchema: z.ZodType<SetWorkspacePinnedInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    pinned: z.boolean(),
+  })
+  .strict();
+
+export const setWorkspaceTagsInputSchema: z.ZodType<SetWorkspaceTagsInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    tags: z
+      .array(z.string().trim().min(1).max(MAX_WORKSPACE_TAG_LENGTH))
+      .max(MAX_WORKSPACE_TAG_COUNT),
+  })
+  .strict();
+
+const workspaceLaunchProfileSchema = z
+  .object({
+    name: z.string().trim().min(1).max(MAX_LAUNCH_PROFILE_NAME_LENGTH),
+    args: z.array(z.string()),
+    env: z.record(z.string(), z.string()),
+  })
+  .strict();
+
+export const setWorkspaceLaunchProfilesInputSchema: z.ZodType<SetWorkspaceLaunchProfilesInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    profiles: z.array(workspaceLaunchProfileSchema),
+  })
+  .strict()
+  .superRefine((input, refinement) => {
+    const error = launchProfilesError(input.profiles);
+    if (error) {
+      refinement.addIssue({ code: "custom", path: ["profiles"], message: error });
+    }
+  });
+
+const workspacePathRuleSchema = z
+  .object({
+    path: nonEmptyStringSchema.refine(isAbsolutePathLike, "must be an absolute path"),
+    external: z.boolean().optional(),
+  })
+  .strict();
+
+const workspaceIdlePolicySchema = z
+  .object({
+    suspend: z.enum(["never", "onBattery", "always"]),
+    timeoutMinutes: z.number().int().min(1).max(MAX_WORKSPACE_IDLE_TIMEOUT_MINUTES),
+  })
+  .strict();
+
+const workspacePathPolicySchema = z
+  .object({
+    allow: z.array(workspacePathRuleSchema).max(MAX_PATH_POLICY_ENTRIES),
+    deny: z.array(workspacePathRuleSchema).max(MAX_PATH_POLICY_ENTRIES),
+  })
+  .strict();
+
+export const setWorkspaceIdlePolicyInputSchema: z.ZodType<SetWorkspaceIdlePolicyInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    policy: workspaceIdlePolicySchema.nullable(),
+  })
+  .strict();
+
+export const setWorkspacePathPolicyInputSchema: z.ZodType<SetWorkspacePathPolicyInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    policy: workspacePathPolicySchema,
+  })
+  .strict();
+
+export const previewPathPolicyInputSchema: z.ZodType<PreviewPathPolicyInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    path: nonEmptyStringSchema,
+  })
+  .strict();
+
+export const duplicateWorkspaceInputSchema: z.ZodType<DuplicateWorkspaceInput> = z
+  .object({
+    workspaceId: safeIdSchema,
+    newPath: nonEmptyStringSchema.refine(isAbsolutePathLike, "must be an absolute path").optional(),
+    copyThreads: z.boolean(),
+    threadIds: z.array(safeIdSchema).max(10_000).optional(),
+  })
+  .strict();
+
+export const reorderWorkspacesInputSchema: z.ZodType<ReorderWorkspacesInput> = z
+  .object({
+    workspaceIds: z
+      .array(safeIdSchema)
+      .max(1000)
+      .refine((ids) => new Set(ids).size === ids.length, "contains duplicate workspace ids"),
+  })
+  .strict();
+
+export const globalSearchInputSchema: z.ZodType<GlobalSearchInput> = z
+  .object({
+    query: z.string().max(500),
+    limit: z.number().int().min(1).max(100).optional(),
+  })
+  .strict();
+
+export const annotateEventInputSchema: z.ZodType<AnnotateEventInput> = z
+  .object({
+    threadId: safeIdSchema,
+    eventIndex: z.number().int().min(0),
+    note: z.string().max(4_000),
+    tags: z.array(z.string().max(64)).max(20).optional(),
+  })
+  .strict();
+
+export const listAnnotationsInputSchema: z.ZodType<ListAnnotationsInput> = z
+  .object({
+    threadId: safeIdSchema,
+  })
+  .strict();
+
+const threadDraftAttachmentSchema = z
+  .object({
+    name: nonEmptyStringSchema.max(1_024),
+    path: nonEmptyStringSchema.max(4_096).optional(),
+    mimeType: nonEmptyStringSchema.max(255).optional(),
+    sizeBytes: z.number().int().nonnegative().optional(),
+  })
+  .strict();
+
+export const saveThreadDraftInputSchema: z.ZodType<SaveThreadDraftInput> = z
+  .object({
+    threadId: safeIdSchema,
+    content: z.string().max(1_000_000),
+    attachments: z.array(threadDraftAttachmentSchema).max(100).optional(),
+  })
+  .strict();
+
+export const loadThreadDraftInputSchema: z.ZodType<LoadThreadDraftInput> = z
   .object({
-    label: z.enum([
-      "Disabled",
-      "Not configured",
-      "Enabled",
-      "Metadata only",
-      "Full payload",
-      "Local only",
-      "Upload configured",
-      "Connected",
-      "Error",
-    ]),
-    status: z.enum([
-      "disabled",
-      "not_configured",
-      "enabled",
-      "metadata_only",
-      "full_payload",
-      "local_only",
-      "upload_configured",
-      "connected",
-      "error",
-    ]),
-    configured: z.boolean(),
-    enabled: z.boolean(),
-    message: z.string().optional(),
+    threadId: safeIdSchema,
   })
   .strict();
 
-export const telemetryStatusSnapshotSchema: z.ZodType<TelemetryStatusSnapshot> = z
+export const listPromptTemplatesInputSchema: z.ZodType<ListPromptTemplatesInput> = z
   .object({
-    globalKillSwitchActive: z.boolean(),
-    crashReports: telemetryStatusEntrySchema,
-    productAnalytics: telemetryStatusEntrySchema,
-    aiTraces: telemetryStatusEntrySchema,
-    diagnosticsUpload: telemetryStatusEntrySchema,
-    cloudSync: telemetryStatusEntrySchema,
+    workspaceId: safeIdSchema.optional(),
   })
   .strict();
 
-export const telemetryStatusInputSchema: z.ZodType<TelemetryStatusInput> = z
+export const savePromptTemplateInputSchema: z.ZodType<SavePromptTemplateInput> = z
   .object({
-    privacyTelemetrySettings: persistedPrivacyTelemetrySettingsSchema.optional(),
+    id: safeIdSchema.optional(),
+    workspaceId: safeIdSchema.optional(),
+    name: nonEmptyStringSchema.max(200),
+    description: z.string().max(2_000).optional(),
+    body: nonEmptyStringSchema.max(100_000),
+    tags: z.array(z.string().max(64)).max(20).optional(),
   })
   .strict();
 
-const persistedProductAnalyticsStateSchema = z.preprocess(
-  (value) =>
-    normalizePersistedProductAnalyticsState(
-      value && typeof value === "object" && !Array.isArray(value)
-        ? (value as PersistedProductAnalyticsState)
-        : undefined,
-    ),
-  z
-    .object({
-      anonymousInstallationId: z.string().trim().min(16).max(128).optional(),
-      lastAppVersion: z.string().trim().min(1).nullable().optional(),
-    })
-    .optional(),
-);
+export const deletePromptTemplateInputSchema: z.ZodType<DeletePromptTemplateInput> = z
+  .object({
+    id: safeIdSchema,
+    workspaceId: safeIdSchema.optional(),
+  })
+  .strict();
 
-export const startWorkspaceServerInputSchema: z.ZodType<StartWorkspaceServerInput> = z.object({
-  workspaceId: safeIdSchema,
-  workspacePath: nonEmptyStringSchema,
-  yolo: z.boolean(),
-  forceRestart: z.boolean().optional(),
-  preserveMobileRelay: z.boolean().optional(),
-  featureFlags: desktopFeatureFlagOverridesSchema.optional(),
-  privacyTelemetrySettings: persistedPrivacyTelemetrySettingsSchema.optional(),
-});
+export const renderPromptInputSchema: z.ZodType<RenderPromptInput> = z
+  .object({
+    templateId: safeIdSchema,
+    workspaceId: safeIdSchema.optional(),
+    vars: z.record(z.string().max(200), z.string().max(100_000)),
+  })
+  .strict();
 
-export const workspaceServerStartupProgressSchema: z.ZodType<WorkspaceServerStartupProgress> = z
+export const createScheduledTaskInputSchema: z.ZodType<CreateScheduledTaskInput> = z
   .object({
     workspaceId: safeIdSchema,
-    progress: z
+    cronExpr: nonEmptyStringSchema.max(200),
+    prompt: nonEmptyStringSchema.max(100_000),
+    options: z
       .object({
-        phase: z.enum(["waiting", "downloading", "installing", "ready"]),
-        version: z
-          .string()
-          .trim()
-          .regex(/^\d{4}-\d{2}-\d{2}$/),
-        transferredBytes: z.number().finite().nonnegative().nullable(),
-        totalBytes: z.number().finite().nonnegative().nullable(),
-        percent: z.number().finite().min(0).max(100).nullable(),
+        name: z.string().max(200).optional(),
+        enabled: z.boolean().optional(),
+        timeoutMinutes: z.number().int().min(1).max(24 * 60).optional(),
       })
-      .strict(),
+      .strict()
+      .optional(),
   })
   .strict();
 
-export const workspaceServerStatusSchema: z.ZodType<WorkspaceServerStatus> = z
+export const setScheduledTaskEnabledInputSchema: z.ZodType<SetScheduledTaskEnabledInput> = z
+  .object({
+    taskId: safeIdSchema,
+    enabled: z.boolean(),
+  })
+  .strict();
+
+export const deleteScheduledTaskInputSchema: z.ZodType<DeleteScheduledTaskInput> = z
+  .object({
+    taskId: safeIdSchema,
+  })
+  .strict();
+
+export const recordPendingApprovalInputSchema: z.ZodType<RecordPendingApprovalInput> = z
   .object({
+    id: safeIdSchema,
     workspaceId: safeIdSchema,
-    running: z.boolean(),
-    url: z.string().min(1).nullable(),
-    reason: z.enum(["running", "starting", "not_found", "exited", "health_failed"]),
-    error: z.string().min(1).optional(),
+    threadId: safeIdSchema,
+    command: z.string().max(100_000),
+    dangerous: z.boolean(),
+    reason: z.string().max(200),
+    detail: z.string().max(10_000).optional(),
+    category: z.enum(["filesystem", "network"]).optional(),
   })
   .strict();
 
-export const workspaceServerExitedEventSchema: z.ZodType<WorkspaceServerExitedEvent> = z
+export const listPendingApprovalsInputSchema: z.ZodType<ListPendingApprovalsInput> = z
   .object({
     workspaceId: safeIdSchema,
-    url: z.string().min(1).nullable(),
-    code: z.number().int().nullable(),
-    signal: z.string().min(1).nullable(),
   })
   .strict();
 
-export const createOneOffChatWorkspaceInputSchema: z.ZodType<CreateOneOffChatWorkspaceInput> =
-  z.object({
-    titleHint: z.string().trim().optional(),
-  });
+export const resolveApprovalInputSchema: z.ZodType<ResolveApprovalInput> = z
+  .object({
+    id: safeIdSchema,
+    decision: z.enum(["approve", "deny"]),
+    rememberRule: z.boolean().optional(),
+  })
+  .strict();
 
-export const stopWorkspaceServerInputSchema: z.ZodType<StopWorkspaceServerInput> = z.object({
-  workspaceId: safeIdSchema,
-});
+/** MediaRecorder emits about a second of compressed audio per chunk; this leaves ample room. */
+const MAX_VOICE_CHUNK_BYTES = 4 * 1024 * 1024;
 
-const rendererLogMetaValueSchema = z.union([z.string(), z.number(), z.boolean(), z.null()]);
-export const rendererLogInputSchema: z.ZodType<RendererLogInput> = z
+export const startVoiceCaptureInputSchema: z.ZodType<StartVoiceCaptureInput> = z
   .object({
-    level: z.enum(["info", "warn", "error"]).optional(),
-    category: z.string().trim().min(1).max(80),
-    message: z.string().trim().min(1).max(200),
-    meta: z.record(z.string(), rendererLogMetaValueSchema).optional(),
+    threadId: safeIdSchema,
+    mimeType: z
+      .string()
+      .max(200)
+      .regex(/^audio\/[a-z0-9.+-]+(;\s*codecs=[\w.,"-]+)?$/i, "must be an audio MIME type"),
   })
   .strict();
 
-const productAnalyticsPropertyValueSchema = z.union([
-  z.string(),
-  z.number(),
-  z.boolean(),
-  z.null(),
-]);
-
-export const captureProductEventInputSchema: z.ZodType<CaptureProductEventInput> = z.object({
-  name: z.enum(PRODUCT_ANALYTICS_EVENT_NAMES),
-  properties: z
-    .record(z.string(), productAnalyticsPropertyValueSchema)
-    .optional()
-    .transform((value) => value as ProductAnalyticsProperties | undefined),
-});
+export const appendVoiceAudioInputSchema: z.ZodType<AppendVoiceAudioInput> = z
+  .object({
+    captureId: safeIdSchema,
+    chunk: z
+      .instanceof(Uint8Array)
+      .refine((chunk) => chunk.byteLength <= MAX_VOICE_CHUNK_BYTES, "chunk is too large"),
+  })
+  .strict();
 
-export const readTranscriptInputSchema: z.ZodType<ReadTranscriptInput> = z.object({
-  threadId: safeIdSchema,
-});
+export const stopVoiceCaptureInputSchema: z.ZodType<StopVoiceCaptureInput> = z
+  .object({
+    discard: z.boolean().optional(),
+  })
+  .strict();
 
-export const deleteTranscriptInputSchema: z.ZodType<DeleteTranscriptInput> = z.object({
-  threadId: safeIdSchema,
+export const scheduledTaskRunSchema: z.ZodType<ScheduledTaskRun> = z.object({
+  taskId: safeIdSchema,
+  taskName: z.string(),
+  workspaceId: safeIdSchema,
+  startedAt: nonEmptyStringSchema,
+  finishedAt: nonEmptyStringSchema,
+  status: z.enum(["completed", "failed", "interrupted"]),
+  threadId: nonEmptyStringSchema.nullable(),
+  error: z.string().nullable(),
 });
 
 export const transcriptBatchInputSchema: z.ZodType<TranscriptBatchInput> = z.object({
@@ -435,6 +1679,11 @@ export const transcriptBatchInputSchema: z.ZodType<TranscriptBatchInput> = z.obj
   payload: z.unknown(),
 }) as z.ZodType<TranscriptBatchInput>;
 
+export const transcriptAppendedEventSchema: z.ZodType<TranscriptAppendedEvent> = z.object({
+  threadId: safeIdSchema,
+  events: z.array(transcriptBatchInputSchema),
+}) as z.ZodType<TranscriptAppendedEvent>;
+
 export const showContextMenuInputSchema: z.ZodType<ShowContextMenuInput> = z.object({
   items: z.array(contextMenuItemSchema),
 });
@@ -479,6 +1728,27 @@ export const watchWorkspaceDirectoryInputSchema: z.ZodType<WatchWorkspaceDirecto
   })
   .strict();
 
+export const workspaceWatcherStatusSchema: z.ZodType<WorkspaceWatcherStatus> = z
+  .object({
+    workspaceId: safeIdSchema,
+    watches: z.array(
+      z
+        .object({
+          rootPath: nonEmptyStringSchema,
+          mode: z.enum(["native", "polling"]),
+          healthy: z.boolean(),
+          reason: z.string().nullable(),
+          pollIntervalMs: z.number().int().nonnegative().nullable(),
+          subscriberCount: z.number().int().nonnegative(),
+          lastEventAt: z.string().nullable(),
+          lastError: z.string().nullable(),
+          downgradedAt: z.string().nullable(),
+        })
+        .strict(),
+    ),
+  })
+  .strict();
+
 export const workspaceFileChangeEventSchema: z.ZodType<WorkspaceFileChangeEvent> = z
   .object({
     workspaceId: safeIdSchema,
@@ -575,6 +1845,71 @@ export const authorizeUploadSourceInputSchema: z.ZodType<AuthorizeUploadSourceIn
     sourcePath: nonEmptyStringSchema,
   })
   .strict();
+export const storeAttachmentInputSchema: z.ZodType<StoreAttachmentInput> = z.union([
+  z
+    .object({
+      threadId: safeIdSchema,
+      sourcePath: nonEmptyStringSchema,
+      filename: validatedSegmentSchema.optional(),
+    })
+    .strict(),
+  z
+    .object({
+      threadId: safeIdSchema,
+      bytes: z
+        .instanceof(Uint8Array)
+        .refine(
+          (bytes) => bytes.byteLength <= MAX_ATTACHMENT_UPLOAD_BYTE_SIZE,
+          "attachment is too large",
+        ),
+      filename: validatedSegmentSchema,
+    })
+    .strict(),
+]);
+export const getAttachmentInputSchema: z.ZodType<GetAttachmentInput> = z
+  .object({
+    hash: z.string().regex(/^[a-f0-9]{64}$/, "must be a sha256 hex digest"),
+  })
+  .strict();
+export const getThumbnailInputSchema: z.ZodType<GetThumbnailInput> = z
+  .object({
+    hash: z.string().regex(/^[a-f0-9]{64}$/, "must be a sha256 hex digest"),
+    maxDim: z.number().int().min(16).max(2048),
+  })
+  .strict();
+export const resolveDroppedWorkspaceFolderInputSchema: z.ZodType<ResolveDroppedWorkspaceFolderInput> =
+  z.object({ path: nonEmptyStringSchema }).strict();
+export const registerRemoteWorkspaceInputSchema: z.ZodType<RegisterRemoteWorkspaceInput> = z
+  .object({
+    url: nonEmptyStringSchema.refine(isSshWorkspacePath, "must be an ssh:// URL"),
+  })
+  .strict();
+export const droppedWorkspaceFolderSchema: z.ZodType<DroppedWorkspaceFolder> = z.object({
+  path: nonEmptyStringSchema,
+  existingWorkspaceId: safeIdSchema.nullable(),
+});
+export const MAX_WORKSPACE_SCAN_DEPTH = 6;
+
+export const scanForWorkspacesInputSchema: z.ZodType<ScanForWorkspacesInput> = z
+  .object({
+    rootPaths: z.array(nonEmptyStringSchema).min(1).max(16),
+    maxDepth: z.number().int().min(0).max(MAX_WORKSPACE_SCAN_DEPTH).optional(),
+  })
+  .strict();
+export const scanForWorkspacesResultSchema: z.ZodType<ScanForWorkspacesResult> = z.object({
+  candidates: z.array(
+    z.object({
+      path: nonEmptyStringSchema,
+      name: z.string(),
+      markers: z.array(z.enum(["git", "cowork"])).min(1),
+      language: nonEmptyStringSchema.nullable(),
+      sizeBytes: z.number().int().nonnegative(),
+      sizeComplete: z.boolean(),
+      lastCommit: workspaceGitCommitSchema.nullable(),
+    }),
+  ),
+  truncated: z.boolean(),
+});
 export const trashPathInputSchema: z.ZodType<TrashPathInput> = sharedPathSchema;
 
 export const createDirectoryInputSchema: z.ZodType<CreateDirectoryInput> = z.object({
@@ -592,6 +1927,7 @@ const persistedWorkspaceSchema = z
     id: safeIdSchema,
     name: nonEmptyStringSchema,
     path: nonEmptyStringSchema,
+    additionalRoots: z.array(nonEmptyStringSchema).optional(),
     workspaceKind: z
       .preprocess(
         (value) => (value === "oneOffChat" ? "oneOffChat" : "project"),
@@ -639,7 +1975,41 @@ const persistedWorkspaceSchema = z
       (value) => (typeof value === "boolean" ? value : false),
       z.boolean(),
     ),
+    resourceLimits: z.preprocess(
+      normalizeWorkspaceResourceLimits,
+      z
+        .object({
+          maxMemoryMb: z.number().int().positive().optional(),
+          niceness: z.number().int().nonnegative().optional(),
+          maxLifetimeMinutes: z.number().int().positive().optional(),
+          startupTimeoutSeconds: z.number().int().positive().optional(),
+        })
+        .optional(),
+    ),
+    idlePolicy: z.preprocess(normalizeWorkspaceIdlePolicy, workspaceIdlePolicySchema.optional()),
+    container: z.preprocess(
+      normalizeWorkspaceContainerLaunch,
+      z.object({ image: nonEmptyStringSchema.optional() }).strict().optional(),
+    ),
+    transcriptMirror: z.preprocess(
+      normalizeWorkspaceTranscriptMirror,
+      z
+        .object({
+          directory: nonEmptyStringSchema,
+          format: z.enum(["jsonl", "markdown"]),
+        })
+        .optional(),
+    ),
     yolo: z.preprocess((value) => (typeof value === "boolean" ? value : false), z.boolean()),
+    yoloCheckpoints: z.boolean().optional(),
+    pinned: z.boolean().optional(),
+    sortOrder: z.number().int().nonnegative().optional(),
+    tags: z.preprocess(normalizeWorkspaceTags, z.array(nonEmptyStringSchema).optional()),
+    launchProfiles: z.preprocess(
+      normalizeWorkspaceLaunchProfiles,
+      z.array(workspaceLaunchProfileSchema).optional(),
+    ),
+    pathPolicy: z.preprocess(normalizeWorkspacePathPolicy, workspacePathPolicySchema.optional()),
   })
   .passthrough();
 
@@ -764,6 +2134,7 @@ const persistedDesktopSettingsSchema = z
 
 export const persistedStateInputSchema: z.ZodType<PersistedState> = z
   .object({
+    generation: z.number().int().nonnegative().optional(),
     workspaces: z.array(persistedWorkspaceSchema),
     threads: z.array(persistedThreadSchema),
     developerMode: z.preprocess(
@@ -816,6 +2187,130 @@ export const uploadDiagnosticsBundleInputSchema: z.ZodType<UploadDiagnosticsBund
     confirmed: z.boolean(),
   });
 
+export const startOperationInputSchema: z.ZodType<StartOperationInput> = z.discriminatedUnion(
+  "kind",
+  [
+    z.object({ kind: z.literal("createDiagnosticsBundle") }),
+    sharedPathSchema.extend({
+      kind: z.literal("uploadDiagnosticsBundle"),
+      confirmed: z.boolean(),
+    }),
+    z
+      .object({ kind: z.literal("prewarmWorkspaceServers"), ...prewarmWorkspaceServersInputShape })
+      .strict(),
+    z.object({ kind: z.literal("importAppData"), ...importAppDataInputShape }).strict(),
+    z.object({ kind: z.literal("encryptExistingTranscripts") }),
+    z
+      .object({ kind: z.literal("migrateTranscriptStore"), ...migrateTranscriptStoreInputShape })
+      .strict(),
+  ],
+);
+
+export const enqueueExportInputSchema: z.ZodType<EnqueueExportInput> = z.discriminatedUnion(
+  "kind",
+  [
+    z
+      .object({
+        kind: z.literal("appData"),
+        includeTranscripts: z.boolean(),
+        destPath: appDataArchivePathSchema.optional(),
+      })
+      .strict(),
+    z.object({ kind: z.literal("diagnosticsBundle") }).strict(),
+  ],
+);
+
+export const operationIdInputSchema: z.ZodType<OperationIdInput> = z.object({
+  operationId: safeIdSchema,
+});
+
+/** S3 bucket naming rules, which S3-compatible services follow too. */
+const S3_BUCKET_NAME = /^[a-z0-9][a-z0-9.-]{1,61}[a-z0-9]$/;
+const BACKUP_PREFIX = /^[A-Za-z0-9_-][A-Za-z0-9._-]*(?:\/[A-Za-z0-9_-][A-Za-z0-9._-]*)*$/;
+
+const backupServiceUrlSchema = nonEmptyStringSchema
+  .max(2048)
+  .refine(isHttpUrl, "Backup URL must use the http: or https: scheme")
+  .refine((value) => !/[?#]/.test(value), "Backup URL must not have a query or fragment");
+
+const backupTargetSchema: z.ZodType<BackupTarget> = z.discriminatedUnion("kind", [
+  z
+    .object({
+      kind: z.literal("s3"),
+      endpoint: backupServiceUrlSchema,
+      bucket: z.string().regex(S3_BUCKET_NAME, "is not a valid bucket name"),
+      region: z.string().regex(/^[a-z0-9-]{1,64}$/, "is not a valid region"),
+      prefix: z.string().max(512).regex(BACKUP_PREFIX, "contains invalid characters").optional(),
+    })
+    .strict(),
+  z.object({ kind: z.literal("webdav"), url: backupServiceUrlSchema }).strict(),
+]);
+
+export const saveBackupCredentialsInputSchema: z.ZodType<SaveBackupCredentialsInput> = z
+  .object({
+    credentials: z.discriminatedUnion("kind", [
+      z
+        .object({
+          kind: z.literal("s3"),
+          accessKeyId: nonEmptyStringSchema.max(256),
+          secretAccessKey: nonEmptyStringSchema.max(1024),
+        })
+        .strict(),
+      z
+        .object({
+          kind: z.literal("webdav"),
+          username: nonEmptyStringSchema.max(256).refine((value) => !value.includes(":"), {
+            message: "must not contain a colon",
+          }),
+          password: nonEmptyStringSchema.max(1024),
+        })
+        .strict(),
+    ]),
+  })
+  .strict();
+
+export const saveBackupCredentialsResultSchema: z.ZodType<SaveBackupCredentialsResult> = z.object({
+  credentialsRef: safeIdSchema,
+});
+
+export const configureBackupSyncInputSchema: z.ZodType<ConfigureBackupSyncInput> = z.union([
+  z.object({ target: backupTargetSchema, credentialsRef: safeIdSchema }).strict(),
+  z.object({ target: z.null() }).strict(),
+]);
+
+export const backupSyncConfigSchema: z.ZodType<BackupSyncConfig> = z.object({
+  target: backupTargetSchema.nullable(),
+  credentialsRef: safeIdSchema.nullable(),
+  deviceId: nonEmptyStringSchema,
+  lastSyncedAt: nonEmptyStringSchema.nullable(),
+});
+
+const operationProgressSchema = z.object({
+  completed: z.number().finite().nonnegative(),
+  total: z.number().finite().nonnegative().nullable(),
+  message: z.string().nullable(),
+});
+
+export const operationSnapshotSchema: z.ZodType<OperationSnapshot> = z.object({
+  operationId: safeIdSchema,
+  kind: z.enum([
+    "createDiagnosticsBundle",
+    "uploadDiagnosticsBundle",
+    "prewarmWorkspaceServers",
+    "importAppData",
+    "encryptExistingTranscripts",
+    "migrateTranscriptStore",
+    "exportAppData",
+    "syncBackup",
+  ]),
+  status: z.enum(["queued", "running", "succeeded", "failed", "cancelled"]),
+  progress: operationProgressSchema.nullable(),
+  result: z.unknown(),
+  error: z.string().nullable(),
+  startedAt: nonEmptyStringSchema,
+  finishedAt: nonEmptyStringSchema.nullable(),
+}) as z.ZodType<OperationSnapshot>;
+
 const updaterProgressSchema: z.ZodType<UpdaterProgress> = z.object({
   percent: z.number().finite(),
   transferred: z.number().finite(),
@@ -853,6 +2348,25 @@ export const updaterStateSchema: z.ZodType<UpdaterState> = z.object({
   release: updaterReleaseInfoSchema.nullable(),
 });
 
+export const sidecarUpdateStateSchema: z.ZodType<SidecarUpdateState> = z.object({
+  phase: z.enum([
+    "disabled",
+    "idle",
+    "checking",
+    "available",
+    "downloading",
+    "staged",
+    "up-to-date",
+    "error",
+  ]),
+  currentVersion: nonEmptyStringSchema,
+  availableVersion: z.string().nullable(),
+  stagedVersion: z.string().nullable(),
+  progress: updaterProgressSchema.nullable(),
+  message: z.string().nullable(),
+  error: z.string().nullable(),
+});
+
 export const setWindowAppearanceInputSchema: z.ZodType<SetWindowAppearanceInput> = z.object({
   themeSource: z.enum(["system", "light", "dark"]).optional(),
   backgroundMaterial: z.enum(["auto", "none", "mica", "acrylic", "tabbed"]).optional(),
@@ -869,6 +2383,23 @@ export const desktopMenuCommandSchema: z.ZodType<DesktopMenuCommand> = z.enum([
   "openCommandPalette",
 ]);
 
+export const desktopDeepLinkNavigationSchema: z.ZodType<DesktopDeepLinkNavigation> =
+  z.discriminatedUnion("kind", [
+    z.object({ kind: z.literal("workspace"), workspaceId: safeIdSchema }).strict(),
+    z
+      .object({ kind: z.literal("thread"), workspaceId: safeIdSchema, threadId: safeIdSchema })
+      .strict(),
+    z.object({ kind: z.literal("openPath"), path: nonEmptyStringSchema }).strict(),
+    z
+      .object({
+        kind: z.literal("transcriptFile"),
+        threadId: safeIdSchema,
+        title: z.string(),
+        sourcePath: nonEmptyStringSchema,
+      })
+      .strict(),
+  ]);
+
 export const systemAppearanceSchema: z.ZodType<SystemAppearance> = z.object({
   platform: z.enum(["darwin", "linux", "win32", "aix", "freebsd", "openbsd", "sunos", "android"]),
   themeSource: z.enum(["system", "light", "dark"]),
@@ -878,6 +2409,12 @@ export const systemAppearanceSchema: z.ZodType<SystemAppearance> = z.object({
   shouldUseInvertedColorScheme: z.boolean(),
   prefersReducedTransparency: z.boolean(),
   inForcedColorsMode: z.boolean(),
+  colorScheme: z.enum(["dark", "light", "high-contrast"]).optional(),
+  accentColor: z
+    .string()
+    .regex(/^#[0-9a-f]{6}$/)
+    .nullable()
+    .optional(),
 });
 
 export const platformChromeInfoSchema: z.ZodType<PlatformChromeInfo> = z.object({
@@ -895,6 +2432,18 @@ export const platformChromeInfoSchema: z.ZodType<PlatformChromeInfo> = z.object(
   disableCssBlur: z.boolean(),
 });
 
+const schemaVersionMapSchema = z.record(nonEmptyStringSchema, z.number().int().min(1));
+
+export const desktopBackendCapabilitiesSchema: z.ZodType<DesktopBackendCapabilities> = z.object({
+  contractVersion: z.number().int().min(1),
+  commands: z.array(nonEmptyStringSchema),
+  events: z.array(nonEmptyStringSchema),
+  schemaVersions: z.object({
+    commands: schemaVersionMapSchema,
+    events: schemaVersionMapSchema,
+  }),
+});
+
 const h3MobileRelayPairingPayloadSchema = z.object({
   v: z.literal(1),
   scheme: z.literal("h3"),
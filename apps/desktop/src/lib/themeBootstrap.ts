@@ -36,6 +36,11 @@ export function applySystemAppearanceToDocument(
         : theme;
   root.classList.toggle("dark", theme === "dark");
   root.classList.toggle("light", theme === "light");
+  if (appearance.accentColor) {
+    root.style.setProperty("--system-accent-color", appearance.accentColor);
+  } else {
+    root.style.removeProperty("--system-accent-color");
+  }
 
   if (!storage) {
     return;
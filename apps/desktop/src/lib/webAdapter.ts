@@ -13,6 +13,7 @@ import type {
   MobileRelayBridgeState,
   ReadFileForPreviewOutput,
   ShowQuickChatWindowInput,
+  StartWorkspaceServerResult,
   SystemAppearance,
   TelemetryStatusSnapshot,
   TranscriptCaptureResult,
@@ -473,6 +474,8 @@ function buildSystemAppearance(): SystemAppearance {
     shouldUseInvertedColorScheme: invMql.matches,
     prefersReducedTransparency: transMql.matches,
     inForcedColorsMode: hcMql.matches,
+    colorScheme: hcMql.matches ? "high-contrast" : mql.matches ? "dark" : "light",
+    accentColor: null,
   };
 }
 
@@ -564,15 +567,15 @@ export function createWebAdapter(): DesktopApi {
     },
     resolveDesktopFeatureFlags: (overrides) => resolveWebDesktopFeatureFlags(overrides),
 
-    async startWorkspaceServer(opts): Promise<{ url: string }> {
+    async startWorkspaceServer(opts): Promise<StartWorkspaceServerResult> {
       const started = await maybePostWebJson<{ url: string }>(
         "/cowork/desktop/workspace/start",
         opts,
       );
       if (started) {
-        return started;
+        return { url: started.url, authToken: null };
       }
-      return { url: getServerUrl() };
+      return { url: getServerUrl(), authToken: null };
     },
 
     async stopWorkspaceServer(opts): Promise<void> {
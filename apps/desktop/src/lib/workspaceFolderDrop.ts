@@ -0,0 +1,21 @@
+type DroppedItems = Pick<DataTransfer, "items" | "types">;
+
+/** True while a drag carries files from the OS rather than in-app content. */
+export function dataTransferHasFiles(dataTransfer: Pick<DataTransfer, "types">): boolean {
+  return Array.from(dataTransfer.types).includes("Files");
+}
+
+/** The dropped entries Chromium reports as directories; plain files are left out. */
+export function droppedFolderFiles(dataTransfer: DroppedItems): File[] {
+  const folders: File[] = [];
+  for (const item of Array.from(dataTransfer.items)) {
+    if (item.kind !== "file" || item.webkitGetAsEntry()?.isDirectory !== true) {
+      continue;
+    }
+    const file = item.getAsFile();
+    if (file) {
+      folders.push(file);
+    }
+  }
+  return folders;
+}
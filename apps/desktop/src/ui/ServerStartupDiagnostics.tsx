@@ -0,0 +1,65 @@
+import { useState } from "react";
+
+import {
+  describeServerStartupFailure,
+  formatStartupCommandLine,
+} from "../app/serverStartupGuidance";
+import { Collapsible, CollapsibleContent, CollapsibleTrigger } from "../components/ui/collapsible";
+import type { WorkspaceServerStartupDiagnostics } from "../lib/desktopApi";
+
+function DetailRow({ label, value }: { label: string; value: string }) {
+  return (
+    <div className="grid grid-cols-[6rem_minmax(0,1fr)] gap-2">
+      <dt className="text-muted-foreground">{label}</dt>
+      <dd className="break-all font-mono">{value}</dd>
+    </div>
+  );
+}
+
+export function ServerStartupDiagnostics({
+  diagnostics,
+}: {
+  diagnostics: WorkspaceServerStartupDiagnostics;
+}) {
+  const [open, setOpen] = useState(false);
+  const guidance = describeServerStartupFailure(diagnostics);
+  const exit = diagnostics.signal
+    ? `signal ${diagnostics.signal}`
+    : diagnostics.exitCode !== null
+      ? String(diagnostics.exitCode)
+      : "still running when stopped";
+
+  return (
+    <div className="mt-2 space-y-2 text-xs">
+      <div className="font-medium text-foreground">{guidance.summary}</div>
+      {guidance.steps.length > 0 ? (
+        <ul className="list-disc space-y-1 pl-4 text-muted-foreground">
+          {guidance.steps.map((step) => (
+            <li key={step}>{step}</li>
+          ))}
+        </ul>
+      ) : null}
+      <Collapsible open={open} onOpenChange={setOpen}>
+        <CollapsibleTrigger className="text-muted-foreground underline-offset-2 hover:underline">
+          {open ? "Hide startup details" : "Show startup details"}
+        </CollapsibleTrigger>
+        <CollapsibleContent>
+          <dl className="mt-2 space-y-1">
+            <DetailRow label="Command" value={formatStartupCommandLine(diagnostics)} />
+            <DetailRow label="Directory" value={diagnostics.cwd} />
+            <DetailRow
+              label={diagnostics.mode === "source" ? "Bun" : "Sidecar"}
+              value={diagnostics.launcherFound ? "found" : "not found"}
+            />
+            <DetailRow label="Exit" value={exit} />
+          </dl>
+          {diagnostics.stderrTail.trim() ? (
+            <pre className="mt-2 max-h-48 overflow-auto whitespace-pre-wrap break-all rounded-md bg-muted p-2 font-mono">
+              {diagnostics.stderrTail.trim()}
+            </pre>
+          ) : null}
+        </CollapsibleContent>
+      </Collapsible>
+    </div>
+  );
+}
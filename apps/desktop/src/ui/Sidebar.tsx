@@ -78,6 +78,7 @@ export const Sidebar = memo(function Sidebar() {
   const generateAdvancedMemoryForThread = useAppStore((s) => s.generateAdvancedMemoryForThread);
   const selectThread = useAppStore((s) => s.selectThread);
   const renameThread = useAppStore((s) => s.renameThread);
+  const forkThread = useAppStore((s) => s.forkThread);
   const archiveThread = useAppStore((s) => s.archiveThread);
   const restoreThread = useAppStore((s) => s.restoreThread);
   const openSkills = useAppStore((s) => s.openSkills);
@@ -450,7 +451,8 @@ export const Sidebar = memo(function Sidebar() {
         defaultAction: "cancel",
       });
       if (confirmed) {
-        void deleteThreadHistory(tId);
+        // This dialog already covers the local transcripts, so reuse its answer.
+        void deleteThreadHistory(tId, () => confirmed);
       }
     },
     [deleteThreadHistory],
@@ -485,6 +487,7 @@ export const Sidebar = memo(function Sidebar() {
 
       const result = await showContextMenu([
         { id: "rename", label: "Rename" },
+        { id: "fork", label: "Fork chat" },
         { id: "archive", label: "Archive" },
         ...(!isPackagedDesktopApp()
           ? [
@@ -500,6 +503,8 @@ export const Sidebar = memo(function Sidebar() {
 
       if (result === "rename") {
         startEditing(tId, tTitle);
+      } else if (result === "fork") {
+        void forkThread(tId);
       } else if (result === "archive") {
         void archiveThreadWithConfirm(tId, tTitle);
       } else if (result === "generate_memory") {
@@ -512,6 +517,7 @@ export const Sidebar = memo(function Sidebar() {
       archiveThreadWithConfirm,
       canGenerateMemoryForThread,
       deleteThreadHistoryWithConfirm,
+      forkThread,
       generateMemoryForThread,
       startEditing,
     ],
@@ -27,6 +27,7 @@ import {
   MessageComposerTools,
 } from "../composer/MessageComposer";
 import { MessageBarResizer } from "../layout/MessageBarResizer";
+import { ComposerDictationButton } from "./ComposerDictationButton";
 import { ComposerMentionInput } from "./ComposerMentionInput";
 import { ComposerReasoningSelector } from "./ComposerReasoningToggle";
 import type { getComposerSubmitState } from "./chatLogic";
@@ -208,6 +209,12 @@ export function ChatComposer(props: {
                 >
                   <PaperclipIcon />
                 </Button>
+                <ComposerDictationButton
+                  threadId={selectedThreadId}
+                  composerText={composerText}
+                  setComposerText={setComposerText}
+                  disabled={inputDisabled || transcriptOnly}
+                />
                 {threadModelConfig ? (
                   <ThreadModelSelector
                     threadId={selectedThreadId}
@@ -597,7 +597,12 @@ export const ChatFeed = memo(function ChatFeed(props: {
   bottomOffset: number;
   interactions: VisibleInteraction[];
   onAnswerAsk: (threadId: string, requestId: string, answer: string) => boolean;
-  onAnswerApproval: (threadId: string, requestId: string, approved: boolean) => boolean;
+  onAnswerApproval: (
+    threadId: string,
+    requestId: string,
+    approved: boolean,
+    opts?: { rememberRule?: boolean },
+  ) => boolean;
   onRetryInteraction: (threadId: string, requestId: string) => boolean;
   selectedThreadId?: string | null;
   threadTitleById?: ReadonlyMap<string, string>;
@@ -0,0 +1,152 @@
+import { Loader2Icon, MicIcon } from "lucide-react";
+import { useEffect, useRef, useState } from "react";
+import { Button } from "../../components/ui/button";
+import {
+  appendVoiceAudio,
+  onVoiceTranscript,
+  startVoiceCapture,
+  stopVoiceCapture,
+} from "../../lib/desktopCommands";
+import { cn } from "../../lib/utils";
+
+/** How much audio MediaRecorder buffers before handing over a chunk. */
+const RECORDER_TIMESLICE_MS = 1_000;
+const PREFERRED_MIME_TYPES = ["audio/webm;codecs=opus", "audio/webm", "audio/mp4"];
+
+type DictationSession = {
+  captureId: string;
+  recorder: MediaRecorder;
+  stream: MediaStream;
+  unsubscribe: () => void;
+  /** Chunks still on their way to the main process. */
+  uploads: Promise<unknown>;
+};
+
+type DictationState = "idle" | "starting" | "recording" | "finishing";
+
+function pickMimeType(): string {
+  return PREFERRED_MIME_TYPES.find((type) => MediaRecorder.isTypeSupported(type)) ?? "";
+}
+
+function releaseSession(session: DictationSession): void {
+  session.unsubscribe();
+  for (const track of session.stream.getTracks()) {
+    track.stop();
+  }
+}
+
+/**
+ * Records from the microphone and types what it hears into the composer. The
+ * transcript replaces whatever dictation added so far, after the text that was
+ * already there when recording started.
+ */
+export function ComposerDictationButton(props: {
+  threadId: string;
+  composerText: string;
+  setComposerText: (text: string) => void;
+  disabled: boolean;
+}) {
+  const { threadId, composerText, setComposerText, disabled } = props;
+  const [state, setState] = useState<DictationState>("idle");
+  const [error, setError] = useState<string | null>(null);
+  const sessionRef = useRef<DictationSession | null>(null);
+  const setTextRef = useRef(setComposerText);
+  setTextRef.current = setComposerText;
+
+  useEffect(() => {
+    return () => {
+      const session = sessionRef.current;
+      if (!session) return;
+      sessionRef.current = null;
+      session.recorder.stop();
+      releaseSession(session);
+      void stopVoiceCapture({ discard: true });
+    };
+  }, []);
+
+  const start = async () => {
+    setError(null);
+    setState("starting");
+    let stream: MediaStream | null = null;
+    try {
+      stream = await navigator.mediaDevices.getUserMedia({ audio: true });
+      const mimeType = pickMimeType();
+      const recorder = new MediaRecorder(stream, mimeType ? { mimeType } : undefined);
+      const prefix = composerText.trim() ? `${composerText.trimEnd()} ` : "";
+      const { captureId } = await startVoiceCapture({
+        threadId,
+        mimeType: recorder.mimeType || mimeType || "audio/webm",
+      });
+      const session: DictationSession = {
+        captureId,
+        recorder,
+        stream,
+        uploads: Promise.resolve(),
+        unsubscribe: onVoiceTranscript((event) => {
+          if (event.captureId === captureId && event.text) {
+            setTextRef.current(`${prefix}${event.text}`);
+          }
+        }),
+      };
+      recorder.ondataavailable = (event) => {
+        if (event.data.size === 0) return;
+        session.uploads = session.uploads.then(async () => {
+          const chunk = new Uint8Array(await event.data.arrayBuffer());
+          await appendVoiceAudio({ captureId, chunk });
+        });
+      };
+      sessionRef.current = session;
+      recorder.start(RECORDER_TIMESLICE_MS);
+      setState("recording");
+    } catch (err) {
+      for (const track of stream?.getTracks() ?? []) {
+        track.stop();
+      }
+      setError(err instanceof Error ? err.message : String(err));
+      setState("idle");
+    }
+  };
+
+  const finish = async () => {
+    const session = sessionRef.current;
+    if (!session) return;
+    sessionRef.current = null;
+    setState("finishing");
+    try {
+      // The recorder hands over its last chunk before `stop` fires.
+      await new Promise<void>((resolve) => {
+        session.recorder.addEventListener("stop", () => resolve(), { once: true });
+        session.recorder.stop();
+      });
+      await session.uploads;
+      await stopVoiceCapture();
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+      void stopVoiceCapture({ discard: true });
+    } finally {
+      releaseSession(session);
+      setState("idle");
+    }
+  };
+
+  const recording = state === "recording";
+  const label = recording ? "Stop dictation" : "Dictate";
+  return (
+    <Button
+      type="button"
+      variant="ghost"
+      size="icon"
+      onClick={() => void (recording ? finish() : start())}
+      disabled={state === "starting" || state === "finishing" || (disabled && !recording)}
+      className={cn(
+        "rounded-full text-muted-foreground hover:bg-muted/45 hover:text-foreground",
+        recording && "bg-destructive/10 text-destructive hover:bg-destructive/15",
+      )}
+      aria-label={label}
+      aria-pressed={recording}
+      title={error ? `${label}: ${error}` : label}
+    >
+      {state === "finishing" ? <Loader2Icon className="animate-spin" /> : <MicIcon />}
+    </Button>
+  );
+}
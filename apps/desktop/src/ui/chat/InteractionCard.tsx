@@ -52,7 +52,12 @@ export function InteractionCard(props: {
   position: number;
   total: number;
   onAnswerAsk: (threadId: string, requestId: string, answer: string) => boolean;
-  onAnswerApproval: (threadId: string, requestId: string, approved: boolean) => boolean;
+  onAnswerApproval: (
+    threadId: string,
+    requestId: string,
+    approved: boolean,
+    opts?: { rememberRule?: boolean },
+  ) => boolean;
   onRetry: (threadId: string, requestId: string) => boolean;
   selectedThreadId?: string | null;
   threadTitle?: string | null;
@@ -248,6 +253,22 @@ export function InteractionCard(props: {
             ) : null}
             {sandbox ? "Keep blocked" : "Deny"}
           </Button>
+          {!sandbox && !interaction.dangerous ? (
+            <Button
+              type="button"
+              size="sm"
+              variant="ghost"
+              disabled={busy}
+              title="Approve this exact command in this workspace from now on"
+              onClick={() =>
+                props.onAnswerApproval(props.threadId, interaction.requestId, true, {
+                  rememberRule: true,
+                })
+              }
+            >
+              Always allow
+            </Button>
+          ) : null}
           <Button
             type="button"
             size="sm"
@@ -10,7 +10,9 @@ import { memo, useCallback, useEffect, useMemo, useRef, useState } from "react";
 import { publishForegroundNotification, useAppStore } from "../../app/store";
 import type { ExplorerEntry } from "../../app/types";
 import { isOneOffChatWorkspace } from "../../app/types";
+import { workspaceRootPaths } from "../../app/workspaceRootPaths";
 import { AccessibleIconButton, Button } from "../../components/ui/button";
+import { NativeSelect, NativeSelectOption } from "../../components/ui/native-select";
 import {
   clearDirectoryListingScope,
   confirmAction,
@@ -531,6 +533,11 @@ export const WorkspaceFileExplorer = memo(function WorkspaceFileExplorer({
   const revealFile = useAppStore((s) => s.revealWorkspaceFile);
   const copyPath = useAppStore((s) => s.copyWorkspaceFilePath);
   const trashPath = useAppStore((s) => s.trashWorkspacePath);
+  const setExplorerRoot = useAppStore((s) => s.setWorkspaceExplorerRoot);
+  const workspaceRoots = useMemo(
+    () => (workspace ? workspaceRootPaths(workspace) : []),
+    [workspace],
+  );
 
   const [directoryByPath, setDirectoryByPath] = useState<Record<string, DirectorySnapshot>>({});
   const [expandedPaths, setExpandedPaths] = useState<Set<string>>(new Set());
@@ -1274,6 +1281,24 @@ export const WorkspaceFileExplorer = memo(function WorkspaceFileExplorer({
               >
                 {rootLabel}
               </Button>
+              {workspaceRoots.length > 1 ? (
+                <NativeSelect
+                  size="sm"
+                  aria-label="Workspace folder"
+                  className="h-6 min-w-0 max-w-32 text-[11px]"
+                  data-file-explorer-control="true"
+                  value={explorer?.rootPath ?? workspacePath ?? ""}
+                  onChange={(event) =>
+                    void setExplorerRoot(workspaceId, event.currentTarget.value).catch(() => {})
+                  }
+                >
+                  {workspaceRoots.map((root) => (
+                    <NativeSelectOption key={root} value={root}>
+                      {formatPathLabel(root)}
+                    </NativeSelectOption>
+                  ))}
+                </NativeSelect>
+              ) : null}
             </>
           )}
         </div>
@@ -44,6 +44,7 @@ import { cn } from "../../lib/utils";
 import type { ProviderName, SessionEvent } from "../../lib/wsProtocol";
 import { PROVIDER_NAMES } from "../../lib/wsProtocol";
 import { OperationFeedback } from "../OperationFeedback";
+import { ServerStartupDiagnostics } from "../ServerStartupDiagnostics";
 import { WorkspaceRuntimeProgress } from "../WorkspaceRuntimeProgress";
 
 const PROVIDER_STATUS_POLL_MS = 4000;
@@ -177,6 +178,7 @@ function WorkspaceStep({ onContinue, onBack }: { onContinue: () => void; onBack:
   const starting = runtime?.starting === true;
   const startupProgress = starting && !serverReady ? (runtime?.startupProgress ?? null) : null;
   const serverError = runtime?.error ?? null;
+  const startupDiagnostics = serverError ? (runtime?.startupDiagnostics ?? null) : null;
   const hasWorkspace = workspace !== null;
   const hasMultipleWorkspaces = workspaceTargets.length > 1;
   const handleWorkspaceTargetChange = (targetId: string) => {
@@ -272,6 +274,9 @@ function WorkspaceStep({ onContinue, onBack }: { onContinue: () => void; onBack:
             {serverError ? (
               <div className="mt-2 text-xs text-destructive">{serverError}</div>
             ) : null}
+            {startupDiagnostics ? (
+              <ServerStartupDiagnostics diagnostics={startupDiagnostics} />
+            ) : null}
             {timedOut && starting ? (
               <div className="mt-2 text-xs text-muted-foreground">
                 The workspace server is taking longer than expected. You can continue waiting or try
@@ -69,7 +69,8 @@ export function ArchivedChatsPage() {
       defaultAction: "cancel",
     });
     if (confirmed) {
-      void deleteThreadHistory(threadId);
+      // This dialog already covers the local transcripts, so reuse its answer.
+      void deleteThreadHistory(threadId, () => confirmed);
     }
   };
 
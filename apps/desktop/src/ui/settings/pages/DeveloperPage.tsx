@@ -23,11 +23,13 @@ import {
 } from "../../../components/ui/select";
 import { Spinner } from "../../../components/ui/spinner";
 import { Switch } from "../../../components/ui/switch";
-import type { CreateDiagnosticsBundleOutput } from "../../../lib/desktopApi";
+import type { CreateDiagnosticsBundleOutput, OrphanedServer } from "../../../lib/desktopApi";
 import {
+  cleanupOrphanedServers,
   confirmAction,
   copyText,
   createDiagnosticsBundle,
+  listOrphanedServers,
   openLogsFolder,
   revealDiagnosticsBundle,
   uploadDiagnosticsBundle,
@@ -68,6 +70,118 @@ function smokeSummary(status: LibreOfficeRuntimeDiagnostic | null): string {
   return `${size} in ${status.smoke.durationMs.toLocaleString()}ms`;
 }
 
+function describeOrphanedServer(server: OrphanedServer): string {
+  return server.workspaceId ? `PID ${server.pid} (${server.workspaceId})` : `PID ${server.pid}`;
+}
+
+function OrphanedServersSection() {
+  const [servers, setServers] = useState<OrphanedServer[] | null>(null);
+  const [pending, setPending] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  const refresh = async () => {
+    setPending(true);
+    setMessage(null);
+    try {
+      setServers(await listOrphanedServers());
+    } catch (error) {
+      setMessage(error instanceof Error ? error.message : String(error));
+    } finally {
+      setPending(false);
+    }
+  };
+
+  useEffect(() => {
+    let cancelled = false;
+    void listOrphanedServers()
+      .then((next) => {
+        if (!cancelled) {
+          setServers(next);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  const stopAll = async () => {
+    const confirmed = await confirmAction({
+      title: "Stop orphaned servers?",
+      message: `Stop ${servers?.length ?? 0} workspace server process(es)?`,
+      detail: "These were left running by an earlier launch of the app.",
+      kind: "warning",
+      confirmLabel: "Stop",
+      cancelLabel: "Cancel",
+    });
+    if (!confirmed) return;
+    setPending(true);
+    setMessage(null);
+    try {
+      const result = await cleanupOrphanedServers();
+      const failedPids = result.failed.map((entry) => entry.pid).join(", ");
+      setMessage(
+        failedPids
+          ? `Stopped ${result.stopped.length}; could not stop ${failedPids}.`
+          : `Stopped ${result.stopped.length} server process(es).`,
+      );
+      setServers(await listOrphanedServers());
+    } catch (error) {
+      setMessage(error instanceof Error ? error.message : String(error));
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <SettingsSection
+      title="Orphaned servers"
+      description="Workspace servers left running after the app quit unexpectedly keep holding ports."
+    >
+      <SettingsRow
+        title={
+          servers && servers.length > 0
+            ? `${servers.length} orphaned server${servers.length === 1 ? "" : "s"}`
+            : "No orphaned servers"
+        }
+        description={
+          servers && servers.length > 0 ? (
+            <span className="break-all">
+              {servers.map(describeOrphanedServer).join(", ")}
+            </span>
+          ) : (
+            "Checked when the app starts and whenever this page opens."
+          )
+        }
+        meta={message}
+        control={
+          <div className="flex flex-wrap gap-2">
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              disabled={pending}
+              onClick={() => void refresh()}
+            >
+              <RefreshCwIcon data-icon="inline-start" />
+              Rescan
+            </Button>
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              disabled={pending || !servers || servers.length === 0}
+              onClick={() => void stopAll()}
+            >
+              Stop all
+            </Button>
+          </div>
+        }
+      />
+    </SettingsSection>
+  );
+}
+
 export function DeveloperPage() {
   const privacyTelemetrySettings = useAppStore((s) => s.privacyTelemetrySettings);
   const desktopFeatures = useAppStore((s) => s.desktopFeatureFlags);
@@ -342,6 +456,8 @@ export function DeveloperPage() {
         />
       </SettingsSection>
 
+      <OrphanedServersSection />
+
       <SettingsSection
         title="File Explorer"
         description="Configure how files are displayed in the workspace."
@@ -15,9 +15,19 @@ import {
   AlertDialogTrigger,
 } from "../../../components/ui/alert-dialog";
 import { Badge } from "../../../components/ui/badge";
+import { Button } from "../../../components/ui/button";
 import { Switch } from "../../../components/ui/switch";
-import type { TelemetryStatusEntry, TelemetryStatusSnapshot } from "../../../lib/desktopApi";
-import { getTelemetryStatus } from "../../../lib/desktopCommands";
+import type {
+  TelemetryStatusEntry,
+  TelemetryStatusSnapshot,
+  TranscriptEncryptionStatus,
+} from "../../../lib/desktopApi";
+import {
+  encryptExistingTranscripts,
+  getTelemetryStatus,
+  getTranscriptEncryption,
+  setTranscriptEncryption,
+} from "../../../lib/desktopCommands";
 import { OperationFeedback } from "../../OperationFeedback";
 import { SettingsPage, SettingsRow, SettingsSection } from "../SettingsPrimitives";
 
@@ -80,6 +90,91 @@ function fallbackTelemetryStatus(settings: PrivacyTelemetrySettings): TelemetryS
   };
 }
 
+function TranscriptEncryptionSection() {
+  const [status, setStatus] = useState<TranscriptEncryptionStatus | null>(null);
+  const [pending, setPending] = useState(false);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    void getTranscriptEncryption()
+      .then((next) => {
+        if (!cancelled) {
+          setStatus(next);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, []);
+
+  const run = async (action: () => Promise<string | null>) => {
+    setPending(true);
+    setMessage(null);
+    try {
+      setMessage(await action());
+    } catch (error) {
+      setMessage(error instanceof Error ? error.message : String(error));
+    } finally {
+      setPending(false);
+    }
+  };
+
+  return (
+    <SettingsSection
+      title="Local data"
+      description="Thread transcripts are stored on this device in the Cowork data folder."
+    >
+      <SettingsRow
+        title="Encrypt transcripts at rest"
+        description={
+          status && !status.available && !status.enabled
+            ? "The system keychain is unavailable, so transcripts cannot be encrypted."
+            : "Encrypts new transcript lines with a key kept in the system keychain. Existing transcripts stay readable either way."
+        }
+        control={
+          <Switch
+            checked={status?.enabled ?? false}
+            disabled={!status || pending || (!status.available && !status.enabled)}
+            aria-label="Encrypt transcripts at rest"
+            onCheckedChange={(enabled) => {
+              void run(async () => {
+                setStatus(await setTranscriptEncryption({ enabled }));
+                return null;
+              });
+            }}
+          />
+        }
+      >
+        {message ? <div className="text-xs text-muted-foreground">{message}</div> : null}
+      </SettingsRow>
+      {status?.enabled ? (
+        <SettingsRow
+          title="Encrypt existing transcripts"
+          description="Rewrites transcripts saved before encryption was turned on, including archived threads."
+          control={
+            <Button
+              type="button"
+              variant="outline"
+              size="sm"
+              disabled={pending}
+              onClick={() => {
+                void run(async () => {
+                  const result = await encryptExistingTranscripts();
+                  return `Encrypted ${result.encryptedFiles} transcript file(s); ${result.unchangedFiles} were already encrypted.`;
+                });
+              }}
+            >
+              Encrypt now
+            </Button>
+          }
+        />
+      ) : null}
+    </SettingsSection>
+  );
+}
+
 export function PrivacyTelemetryPage() {
   const settings = useAppStore((s) => s.privacyTelemetrySettings);
   const operationsByKey = useAppStore((s) => s.operationsByKey);
@@ -278,6 +373,7 @@ export function PrivacyTelemetryPage() {
           description="COWORK_DISABLE_NETWORK_TELEMETRY disables crash reports, product analytics, AI traces, diagnostics uploads, and cloud sync in every process. Local logs and local diagnostics bundle creation remain available."
         />
       </SettingsSection>
+      <TranscriptEncryptionSection />
     </SettingsPage>
   );
 }
@@ -32,6 +32,8 @@ import {
 } from "../../../components/ui/dialog";
 import { Input } from "../../../components/ui/input";
 import { InputGroupButton } from "../../../components/ui/input-group";
+import type { ProviderCredentialValidation } from "../../../lib/desktopApi";
+import { validateProviderCredentials } from "../../../lib/desktopCommands";
 import {
   isCatalogModelEnabled,
   isUiDisabledProvider,
@@ -59,6 +61,7 @@ import {
   fallbackExaAuthMethod,
   fallbackParallelAuthMethod,
   formatAccount,
+  formatCredentialCheck,
   formatCreditsSummary,
   formatRateLimitName,
   formatWindowMeta,
@@ -154,6 +157,9 @@ export function ProvidersPage({
     Record<string, Record<string, string>>
   >({});
   const [oauthCodesByMethod, setOauthCodesByMethod] = useState<Record<string, string>>({});
+  const [credentialChecksByMethod, setCredentialChecksByMethod] = useState<
+    Record<string, ProviderCredentialValidation | "pending">
+  >({});
   const [expandedSectionId, setExpandedSectionId] = useState<string | null>(
     initialExpandedSectionId,
   );
@@ -327,6 +333,22 @@ export function ProvidersPage({
     })();
   };
 
+  const testApiKey = async (provider: ProviderName, stateKey: string, apiKey?: string) => {
+    setCredentialChecksByMethod((s) => ({ ...s, [stateKey]: "pending" }));
+    try {
+      const result = await validateProviderCredentials({ provider, apiKey, force: true });
+      setCredentialChecksByMethod((s) => {
+        const { [stateKey]: _pending, ...rest } = s;
+        return result ? { ...rest, [stateKey]: result } : rest;
+      });
+    } catch {
+      setCredentialChecksByMethod((s) => {
+        const { [stateKey]: _pending, ...rest } = s;
+        return rest;
+      });
+    }
+  };
+
   const renderAuthMethod = (opts: {
     provider: ProviderName;
     providerDisplayName: string;
@@ -393,6 +415,11 @@ export function ProvidersPage({
       typeof siblingSavedApiKeyMask === "string" &&
       siblingSavedApiKeyMask.trim().length > 0 &&
       !hasSavedApiKey;
+    const credentialCheck = credentialChecksByMethod[stateKey];
+    const canTestApiKey =
+      opts.method.type === "api" &&
+      opts.method.id === "api_key" &&
+      (isEditingApiKey ? apiKeyValue.trim().length > 0 : hasSavedApiKey);
     const canSaveStructuredMethod = (opts.method.fields ?? []).every(
       (field) => !field.required || (credentialValues[field.id] ?? "").trim().length > 0,
     );
@@ -508,6 +535,9 @@ export function ProvidersPage({
                     if (!isEditingApiKey) return;
                     const nextValue = e.currentTarget.value;
                     setApiKeysByMethod((s) => ({ ...s, [stateKey]: nextValue }));
+                    if (credentialCheck) {
+                      setCredentialChecksByMethod(({ [stateKey]: _stale, ...rest }) => rest);
+                    }
                   }}
                   placeholder={
                     isEditingApiKey
@@ -576,6 +606,23 @@ export function ProvidersPage({
                   Save
                 </Button>
               ) : null}
+              {canTestApiKey ? (
+                <Button
+                  variant="outline"
+                  type="button"
+                  disabled={methodPending || credentialCheck === "pending"}
+                  title="Make one small request to the provider with this key"
+                  onClick={() => {
+                    void testApiKey(
+                      opts.provider,
+                      stateKey,
+                      isEditingApiKey ? apiKeyValue.trim() : undefined,
+                    );
+                  }}
+                >
+                  {credentialCheck === "pending" ? "Testing…" : "Test key"}
+                </Button>
+              ) : null}
               {canCopySiblingApiKey && siblingProvider && siblingDisplayName ? (
                 <Button
                   variant="outline"
@@ -634,6 +681,18 @@ export function ProvidersPage({
 
         <OperationFeedback operation={methodOperation} />
 
+        {credentialCheck && credentialCheck !== "pending" ? (
+          <div
+            className={cn(
+              "text-xs",
+              credentialCheck.status === "valid" ? "text-muted-foreground" : "text-destructive",
+            )}
+            role="status"
+          >
+            {formatCredentialCheck(credentialCheck)}
+          </div>
+        ) : null}
+
         {challengeMatch ? (
           <div className="text-xs text-muted-foreground">
             {challengeMatch.challenge.instructions}
@@ -15,6 +15,8 @@ import {
   DialogHeader,
   DialogTitle,
 } from "../../../components/ui/dialog";
+import type { UsageSummary, UsageSummaryRange, UsageTotals } from "../../../lib/desktopApi";
+import { getUsageSummary } from "../../../lib/desktopCommands";
 import { useOptionalSettingsChrome } from "../SettingsChromeContext";
 import {
   SettingsEmptyState,
@@ -194,6 +196,97 @@ function formatEstimatedCost(value: number | null, available: boolean): string {
   return "—";
 }
 
+const USAGE_HISTORY_RANGES: Array<{ id: UsageSummaryRange; label: string }> = [
+  { id: "today", label: "Today" },
+  { id: "7d", label: "7 days" },
+  { id: "30d", label: "30 days" },
+  { id: "all", label: "All time" },
+];
+
+export function formatUsageTotals(totals: UsageTotals): string {
+  const cost =
+    totals.estimatedCostUsd === null ? "no pricing" : formatCost(totals.estimatedCostUsd);
+  const unpriced = totals.unpricedTurns > 0 ? ` (${totals.unpricedTurns} unpriced)` : "";
+  const turns = `${totals.turns} turn${totals.turns === 1 ? "" : "s"}`;
+  return `${formatTokenCount(totals.totalTokens)} tokens · ${turns} · ${cost}${unpriced}`;
+}
+
+/** Recorded usage from the local ledger, which keeps turns from sessions no longer loaded. */
+function UsageHistorySection() {
+  const workspaces = useAppStore((s) => s.workspaces);
+  const [range, setRange] = useState<UsageSummaryRange>("7d");
+  const [summary, setSummary] = useState<UsageSummary | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    void getUsageSummary({ range })
+      .then((next) => {
+        if (!cancelled) setSummary(next);
+      })
+      .catch(() => {
+        if (!cancelled) setSummary(null);
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [range]);
+
+  const workspaceName = (workspaceId: string | null) =>
+    workspaces.find((workspace) => workspace.id === workspaceId)?.name ?? "Removed workspace";
+
+  return (
+    <SettingsSection
+      title="History"
+      description="Tokens and estimated cost recorded on this computer, per day and workspace."
+    >
+      <div className="flex flex-wrap gap-2 px-4 pt-3.5">
+        {USAGE_HISTORY_RANGES.map((option) => (
+          <Button
+            key={option.id}
+            type="button"
+            size="sm"
+            variant={option.id === range ? "secondary" : "ghost"}
+            aria-pressed={option.id === range}
+            onClick={() => setRange(option.id)}
+          >
+            {option.label}
+          </Button>
+        ))}
+      </div>
+      {summary && summary.totals.turns > 0 ? (
+        <div className="divide-y divide-border/45 text-sm">
+          <div className="px-4 py-3 font-medium text-foreground">
+            {formatUsageTotals(summary.totals)}
+          </div>
+          {summary.byWorkspace.map((entry) => (
+            <div
+              key={entry.workspaceId ?? "unknown"}
+              className="flex items-center justify-between gap-4 px-4 py-2.5"
+            >
+              <span className="truncate text-foreground">{workspaceName(entry.workspaceId)}</span>
+              <span className="shrink-0 text-xs text-muted-foreground">
+                {formatUsageTotals(entry)}
+              </span>
+            </div>
+          ))}
+          {summary.byDay.map((entry) => (
+            <div key={entry.day} className="flex items-center justify-between gap-4 px-4 py-2.5">
+              <span className="tabular-nums text-muted-foreground">{entry.day}</span>
+              <span className="shrink-0 text-xs text-muted-foreground">
+                {formatUsageTotals(entry)}
+              </span>
+            </div>
+          ))}
+        </div>
+      ) : (
+        <div className="px-4 py-3.5 text-sm text-muted-foreground">
+          No usage recorded in this period.
+        </div>
+      )}
+    </SettingsSection>
+  );
+}
+
 // ── Component ────────────────────────────────────────────────────────
 
 export type UsagePageProps = {
@@ -465,6 +558,8 @@ export function UsagePage(props: UsagePageProps = {}) {
         />
       )}
 
+      <UsageHistorySection />
+
       {!settingsChrome ? (
         <div className="flex justify-end">
           <Button
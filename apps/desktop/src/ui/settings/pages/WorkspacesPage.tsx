@@ -35,6 +35,7 @@ import {
 } from "../../../app/openaiCompatibleProviderOptions";
 import { useAppStore } from "../../../app/store";
 import { operationKey } from "../../../app/store.helpers/operations";
+import { setPendingYoloToken } from "../../../app/store.helpers/runtimeState";
 import type { PersistedProviderStatus } from "../../../app/types";
 import {
   isOneOffChatWorkspace,
@@ -43,6 +44,7 @@ import {
   type WorkspaceUserProfile,
 } from "../../../app/types";
 import { resolveWorkspaceDisplayTargets } from "../../../app/workspaceDisplayTargets";
+import { DEFAULT_TRANSCRIPT_MIRROR_DIRECTORY } from "../../../app/workspaceTranscriptMirror";
 import { Badge } from "../../../components/ui/badge";
 import { Button } from "../../../components/ui/button";
 import { Checkbox } from "../../../components/ui/checkbox";
@@ -62,7 +64,14 @@ import {
 } from "../../../components/ui/select";
 import { Switch } from "../../../components/ui/switch";
 import { Textarea } from "../../../components/ui/textarea";
-import { confirmAction } from "../../../lib/desktopCommands";
+import type { WorkspaceCheckpoint, WorkspaceCheckpointList } from "../../../lib/desktopApi";
+import {
+  confirmAction,
+  hasYoloGrants,
+  listWorkspaceCheckpoints,
+  requestYoloGrant,
+  restoreWorkspaceCheckpoint,
+} from "../../../lib/desktopCommands";
 import {
   type CatalogVisibilityOptions,
   isUiDisabledProvider,
@@ -78,7 +87,7 @@ import { cn } from "../../../lib/utils";
 import type { ProviderName } from "../../../lib/wsProtocol";
 import { PROVIDER_NAMES } from "../../../lib/wsProtocol";
 import { OperationFeedback } from "../../OperationFeedback";
-import { SettingsEmptyState, SettingsSection } from "../SettingsPrimitives";
+import { SettingsEmptyState, SettingsRow, SettingsSection } from "../SettingsPrimitives";
 
 function ToggleChip({
   pressed,
@@ -935,6 +944,148 @@ export function WorkspaceUserProfileCard({
 
 type WorkspacesPageSurface = "defaults" | "models" | "profile";
 
+function describeCheckpointReason(checkpoint: WorkspaceCheckpoint): string {
+  return checkpoint.reason === "before-restore"
+    ? "Taken before restoring another checkpoint"
+    : "Taken before the YOLO server started";
+}
+
+function YoloCheckpointsSection({ workspaceId }: { workspaceId: string }) {
+  const [list, setList] = useState<WorkspaceCheckpointList | null>(null);
+  const [pendingId, setPendingId] = useState<string | null>(null);
+  const [message, setMessage] = useState<string | null>(null);
+
+  useEffect(() => {
+    let cancelled = false;
+    setList(null);
+    setMessage(null);
+    void listWorkspaceCheckpoints({ workspaceId })
+      .then((next) => {
+        if (!cancelled) {
+          setList(next);
+        }
+      })
+      .catch(() => {});
+    return () => {
+      cancelled = true;
+    };
+  }, [workspaceId]);
+
+  const restore = async (checkpoint: WorkspaceCheckpoint) => {
+    const createdAt = new Date(checkpoint.createdAt).toLocaleString();
+    const confirmed = await confirmAction({
+      title: "Restore checkpoint?",
+      message: `Restore the repository files to the checkpoint from ${createdAt}?`,
+      detail:
+        "Files are rewritten to their checkpointed contents and files created since are deleted. The current files are checkpointed first, so the restore can be undone.",
+      kind: "warning",
+      confirmLabel: "Restore",
+      cancelLabel: "Cancel",
+      defaultAction: "cancel",
+    });
+    if (!confirmed) return;
+    setPendingId(checkpoint.id);
+    setMessage(null);
+    try {
+      const result = await restoreWorkspaceCheckpoint({
+        workspaceId,
+        checkpointId: checkpoint.id,
+      });
+      setMessage(
+        `Restored ${checkpoint.id.slice(0, 7)} and removed ${result.removedFileCount} file(s).`,
+      );
+      setList(await listWorkspaceCheckpoints({ workspaceId }));
+    } catch (error) {
+      setMessage(error instanceof Error ? error.message : String(error));
+    } finally {
+      setPendingId(null);
+    }
+  };
+
+  return (
+    <SettingsSection
+      title="YOLO checkpoints"
+      description="Snapshots of the repository taken before YOLO runs, kept on refs/cowork-checkpoints outside your branches."
+    >
+      {message ? <div className="px-4 pt-3 text-xs text-muted-foreground">{message}</div> : null}
+      {list && !list.isRepository ? (
+        <SettingsEmptyState
+          title="Not a git repository"
+          description="Checkpoints need the folder to be inside a git repository, so YOLO runs here start without one."
+        />
+      ) : list && list.checkpoints.length === 0 ? (
+        <SettingsEmptyState
+          title="No checkpoints yet"
+          description="One is taken the next time the YOLO server starts with changes since the last checkpoint."
+        />
+      ) : (
+        list?.checkpoints.map((checkpoint) => (
+          <SettingsRow
+            key={checkpoint.id}
+            title={new Date(checkpoint.createdAt).toLocaleString()}
+            description={describeCheckpointReason(checkpoint)}
+            meta={<span className="font-mono">{checkpoint.id.slice(0, 7)}</span>}
+            control={
+              <Button
+                type="button"
+                variant="outline"
+                size="sm"
+                disabled={pendingId !== null}
+                onClick={() => void restore(checkpoint)}
+              >
+                Restore
+              </Button>
+            }
+          />
+        ))
+      )}
+    </SettingsSection>
+  );
+}
+
+function RemoteWorkspaceSection() {
+  const addRemoteWorkspace = useAppStore((s) => s.addRemoteWorkspace);
+  const [url, setUrl] = useState("");
+  const [adding, setAdding] = useState(false);
+
+  const submit = async () => {
+    setAdding(true);
+    try {
+      await addRemoteWorkspace(url);
+    } finally {
+      setAdding(false);
+    }
+  };
+
+  return (
+    <SettingsSection
+      title="Remote workspace"
+      description="Run the agent on another machine over SSH. Its key must work without a prompt, and cowork-server must be on its PATH."
+    >
+      <form
+        className="flex items-center gap-2 px-4 py-4"
+        onSubmit={(event) => {
+          event.preventDefault();
+          void submit();
+        }}
+      >
+        <Input
+          aria-label="Remote workspace URL"
+          autoComplete="off"
+          spellCheck={false}
+          placeholder="ssh://user@host/home/user/project"
+          value={url}
+          disabled={adding}
+          onChange={(event) => setUrl(event.target.value)}
+        />
+        <Button type="submit" variant="outline" size="sm" disabled={adding || !url.trim()}>
+          {adding ? "Connecting..." : "Add"}
+        </Button>
+      </form>
+    </SettingsSection>
+  );
+}
+
 export function WorkspacesPage({ surface = "defaults" }: { surface?: WorkspacesPageSurface } = {}) {
   const desktopFeatures = useAppStore((s) => s.desktopFeatureFlags);
   const workspacePickerEnabled = desktopFeatures.workspacePicker !== false;
@@ -957,6 +1108,12 @@ export function WorkspacesPage({ surface = "defaults" }: { surface?: WorkspacesP
   const selectWorkspace = useAppStore((s) => s.selectWorkspace);
   const updateWorkspaceDefaults = useSharedUpdateWorkspaceDefaults();
   const restartWorkspaceServer = useAppStore((s) => s.restartWorkspaceServer);
+  const addWorkspaceRoot = useAppStore((s) => s.addWorkspaceRoot);
+  const removeWorkspaceRoot = useAppStore((s) => s.removeWorkspaceRoot);
+  const setWorkspaceTranscriptMirror = useAppStore((s) => s.setWorkspaceTranscriptMirror);
+  const chooseWorkspaceTranscriptMirrorFolder = useAppStore(
+    (s) => s.chooseWorkspaceTranscriptMirrorFolder,
+  );
   const projectWorkspaces = useMemo(
     () => workspaces.filter((workspace) => !isOneOffChatWorkspace(workspace)),
     [workspaces],
@@ -1203,6 +1360,112 @@ export function WorkspacesPage({ surface = "defaults" }: { surface?: WorkspacesP
               </SettingsSection>
             )}
 
+            {workspaceLifecycleEnabled && !isOneOffChatWorkspace(ws) ? (
+              <SettingsSection
+                title="Folders"
+                description="The agent can read and edit files in every folder of this workspace."
+                action={
+                  <Button
+                    variant="outline"
+                    size="sm"
+                    type="button"
+                    onClick={() => void addWorkspaceRoot(ws.id)}
+                  >
+                    Add folder
+                  </Button>
+                }
+              >
+                <div className="space-y-3 px-4 py-4">
+                  <div className="flex items-center justify-between gap-3">
+                    <div className="min-w-0">
+                      <div className="truncate text-sm font-medium text-foreground">{ws.path}</div>
+                      <div className="text-xs text-muted-foreground">
+                        Primary folder; the agent server runs here.
+                      </div>
+                    </div>
+                  </div>
+                  {(ws.additionalRoots ?? []).map((rootPath) => (
+                    <div key={rootPath} className="flex items-center justify-between gap-3">
+                      <div className="min-w-0 truncate text-sm text-foreground">{rootPath}</div>
+                      <Button
+                        variant="ghost"
+                        size="sm"
+                        type="button"
+                        onClick={() => void removeWorkspaceRoot(ws.id, rootPath)}
+                      >
+                        Remove
+                      </Button>
+                    </div>
+                  ))}
+                </div>
+              </SettingsSection>
+            ) : null}
+
+            {workspaceLifecycleEnabled ? <RemoteWorkspaceSection /> : null}
+
+            {workspaceLifecycleEnabled && !isOneOffChatWorkspace(ws) ? (
+              <SettingsSection
+                title="Transcript mirror"
+                description="Keep a plain copy of each chat in a folder, updated as the chat runs."
+                action={
+                  <ToggleChip
+                    id="transcript-mirror-toggle"
+                    pressed={Boolean(ws.transcriptMirror)}
+                    aria-label="Mirror transcripts"
+                    onPressedChange={(next) => {
+                      void setWorkspaceTranscriptMirror(
+                        ws.id,
+                        next
+                          ? { directory: DEFAULT_TRANSCRIPT_MIRROR_DIRECTORY, format: "markdown" }
+                          : null,
+                      );
+                    }}
+                  />
+                }
+              >
+                {ws.transcriptMirror ? (
+                  <div className="space-y-3 px-4 py-4">
+                    <div className="flex items-center justify-between gap-3">
+                      <div className="min-w-0">
+                        <div className="truncate text-sm font-medium text-foreground">
+                          {ws.transcriptMirror.directory}
+                        </div>
+                        <div className="text-xs text-muted-foreground">
+                          Relative folders are inside this workspace.
+                        </div>
+                      </div>
+                      <Button
+                        variant="outline"
+                        size="sm"
+                        type="button"
+                        onClick={() => void chooseWorkspaceTranscriptMirrorFolder(ws.id)}
+                      >
+                        Choose folder
+                      </Button>
+                    </div>
+                    <Select
+                      value={ws.transcriptMirror.format}
+                      onValueChange={(value) => {
+                        if (!ws.transcriptMirror) return;
+                        void setWorkspaceTranscriptMirror(ws.id, {
+                          ...ws.transcriptMirror,
+                          format: value === "jsonl" ? "jsonl" : "markdown",
+                        });
+                      }}
+                    >
+                      <SelectTrigger aria-label="Transcript mirror format" size="sm">
+                        <SelectValue />
+                      </SelectTrigger>
+                      <SelectContent>
+                        <SelectItem value="markdown">Markdown</SelectItem>
+                        <SelectItem value="jsonl">JSONL</SelectItem>
+                      </SelectContent>
+                    </Select>
+                  </div>
+                ) : null}
+              </SettingsSection>
+            ) : null}
+
             <SettingsSection description="Execution and visibility options for all folders and chats.">
               <div className="space-y-4 px-4 py-4">
                 <div className="flex items-start justify-between gap-4 max-[960px]:flex-col">
@@ -1249,32 +1512,68 @@ export function WorkspacesPage({ surface = "defaults" }: { surface?: WorkspacesP
                     aria-label="YOLO mode"
                     onPressedChange={async (next) => {
                       if (!ws) return;
-                      const confirmed = await confirmAction({
-                        title: next ? "Enable YOLO mode" : "Disable YOLO mode",
-                        message: next
-                          ? "Enable YOLO mode? The agent will run shell commands on your machine without asking for approval and without the OS sandbox."
-                          : "Disable YOLO mode?",
-                        detail: next
-                          ? "This is a high-risk setting. Commands get full file and network access. The server will restart to apply this change."
-                          : undefined,
-                        confirmLabel: next ? "Enable" : "Disable",
-                        cancelLabel: "Cancel",
-                        kind: "warning",
-                        defaultAction: "cancel",
-                      });
-                      if (confirmed) {
-                        void updateWorkspaceDefaults(ws.id, { yolo: next }).then(() => {
-                          if (workspaceLifecycleEnabled) {
-                            return restartWorkspaceServer(ws.id);
-                          }
+                      if (next && hasYoloGrants()) {
+                        // Main asks the user itself before it issues a grant, and only
+                        // honours yolo with one, so the restart redeems it.
+                        const grant = await requestYoloGrant({ workspaceId: ws.id });
+                        if (!grant) return;
+                        setPendingYoloToken(ws.id, grant.token);
+                      } else {
+                        const confirmed = await confirmAction({
+                          title: next ? "Enable YOLO mode" : "Disable YOLO mode",
+                          message: next
+                            ? "Enable YOLO mode? The agent will run shell commands on your machine without asking for approval and without the OS sandbox."
+                            : "Disable YOLO mode?",
+                          detail: next
+                            ? "This is a high-risk setting. Commands get full file and network access. The server will restart to apply this change."
+                            : undefined,
+                          confirmLabel: next ? "Enable" : "Disable",
+                          cancelLabel: "Cancel",
+                          kind: "warning",
+                          defaultAction: "cancel",
                         });
+                        if (!confirmed) return;
                       }
+                      void updateWorkspaceDefaults(ws.id, { yolo: next }).then(() => {
+                        if (workspaceLifecycleEnabled) {
+                          return restartWorkspaceServer(ws.id);
+                        }
+                      });
                     }}
                   />
                 </div>
+
+                {!isOneOffChatWorkspace(ws) ? (
+                  <div className="flex items-start justify-between gap-4 max-[960px]:flex-col">
+                    <div className="grid gap-1.5">
+                      <Label
+                        htmlFor="yolo-checkpoints-toggle"
+                        className="text-sm font-medium leading-none cursor-pointer"
+                      >
+                        Checkpoint before YOLO runs
+                      </Label>
+                      <div className="text-xs text-muted-foreground">
+                        Snapshot the repository before the server starts in YOLO mode so agent edits
+                        can be rolled back. Your branches and staged changes are not touched.
+                      </div>
+                    </div>
+                    <ToggleChip
+                      id="yolo-checkpoints-toggle"
+                      pressed={ws.yoloCheckpoints === true}
+                      aria-label="Checkpoint before YOLO runs"
+                      onPressedChange={(next) => {
+                        void updateWorkspaceDefaults(ws.id, { yoloCheckpoints: next });
+                      }}
+                    />
+                  </div>
+                ) : null}
               </div>
             </SettingsSection>
 
+            {ws.yoloCheckpoints && !isOneOffChatWorkspace(ws) ? (
+              <YoloCheckpointsSection workspaceId={ws.id} />
+            ) : null}
+
             {surface === "defaults" ? (
               <Collapsible>
                 <SettingsSection>
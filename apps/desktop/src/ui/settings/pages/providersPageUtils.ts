@@ -1,3 +1,4 @@
+import type { ProviderCredentialValidation } from "../../../lib/desktopApi";
 import type { ProviderName, SessionEvent } from "../../../lib/wsProtocol";
 
 export type ProviderAuthMethod = Extract<
@@ -252,6 +253,22 @@ export function providerSectionId(provider: ProviderName): string {
   return `provider:${provider}`;
 }
 
+/** One line for a "Test key" result: the verdict, latency, and any quota the provider reported. */
+export function formatCredentialCheck(result: ProviderCredentialValidation): string {
+  const parts = [result.message];
+  if (result.latencyMs !== null) {
+    parts.push(`${Math.round(result.latencyMs)} ms`);
+  }
+  const { requestsLimit, requestsRemaining, tokensLimit, tokensRemaining } = result.quota ?? {};
+  if (typeof requestsRemaining === "number" && typeof requestsLimit === "number") {
+    parts.push(`${requestsRemaining} of ${requestsLimit} requests left`);
+  }
+  if (typeof tokensRemaining === "number" && typeof tokensLimit === "number") {
+    parts.push(`${tokensRemaining} of ${tokensLimit} tokens left`);
+  }
+  return parts.join(" \u2022 ");
+}
+
 export function toolProviderConnectionSummary(label: string, hasSavedApiKey: boolean): string {
   return hasSavedApiKey
     ? "Web search API key saved"
@@ -24,6 +24,7 @@ import { usePrefersReducedMotion } from "../../lib/usePrefersReducedMotion";
 import { cn } from "../../lib/utils";
 import { MAX_VISIBLE_SIDEBAR_ITEMS } from "../sidebarHelpers";
 import { SidebarThreadItem } from "./SidebarThreadItem";
+import { WorkspaceGitBadge } from "./WorkspaceGitBadge";
 
 /** @deprecated Prefer MAX_VISIBLE_SIDEBAR_ITEMS */
 export const MAX_VISIBLE_THREADS = MAX_VISIBLE_SIDEBAR_ITEMS;
@@ -279,6 +280,7 @@ export const SidebarWorkspaceItem = memo(function SidebarWorkspaceItem({
           <span className="block min-w-0 flex-1 truncate text-[13px] font-medium tracking-[-0.015em]">
             {workspace.name}
           </span>
+          <WorkspaceGitBadge workspaceId={workspace.id} workspacePath={workspace.path} />
         </Button>
         <Button
           aria-label={`New chat in ${workspace.name}`}
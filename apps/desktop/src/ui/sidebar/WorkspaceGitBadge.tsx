@@ -0,0 +1,73 @@
+import { GitBranchIcon } from "lucide-react";
+import { memo, useEffect, useState } from "react";
+
+import type { WorkspaceGitStatus } from "../../lib/desktopApi";
+import { getWorkspaceGitStatus, onWorkspaceGitChanged } from "../../lib/desktopCommands";
+
+export function formatWorkspaceGitTitle(status: WorkspaceGitStatus): string {
+  const lines = [
+    status.branch ? `Branch: ${status.branch}` : "Detached HEAD",
+    status.dirtyFileCount === 0
+      ? "No uncommitted changes"
+      : `${status.dirtyFileCount} changed file${status.dirtyFileCount === 1 ? "" : "s"}`,
+  ];
+  if (status.upstream && status.ahead !== null && status.behind !== null) {
+    lines.push(`${status.ahead} ahead, ${status.behind} behind ${status.upstream}`);
+  }
+  if (status.lastCommit) {
+    lines.push(`Last commit: ${status.lastCommit.summary} (${status.lastCommit.sha.slice(0, 7)})`);
+  }
+  return lines.join("\n");
+}
+
+/** Branch name and dirty/ahead/behind counts for a workspace folder that is a git repository. */
+export const WorkspaceGitBadge = memo(function WorkspaceGitBadge({
+  workspaceId,
+  workspacePath,
+}: {
+  workspaceId: string;
+  workspacePath: string;
+}) {
+  const [status, setStatus] = useState<WorkspaceGitStatus | null>(null);
+
+  // biome-ignore lint/correctness/useExhaustiveDependencies: re-read when the folder moves.
+  useEffect(() => {
+    let cancelled = false;
+    void getWorkspaceGitStatus({ workspaceId })
+      .then((next) => {
+        if (!cancelled) {
+          setStatus(next);
+        }
+      })
+      .catch(() => {});
+    const unsubscribe = onWorkspaceGitChanged((next) => {
+      if (next.workspaceId === workspaceId) {
+        setStatus(next);
+      }
+    });
+    return () => {
+      cancelled = true;
+      unsubscribe();
+    };
+  }, [workspaceId, workspacePath]);
+
+  if (!status?.isRepository) {
+    return null;
+  }
+
+  const label = status.branch ?? status.lastCommit?.sha.slice(0, 7) ?? "HEAD";
+  return (
+    <span
+      className="flex min-w-0 max-w-[45%] shrink items-center gap-1 text-[11px] text-muted-foreground"
+      title={formatWorkspaceGitTitle(status)}
+    >
+      <GitBranchIcon className="h-3 w-3 shrink-0" />
+      <span className="truncate">{label}</span>
+      {status.dirtyFileCount > 0 ? (
+        <span className="shrink-0 tabular-nums">•{status.dirtyFileCount}</span>
+      ) : null}
+      {status.ahead ? <span className="shrink-0 tabular-nums">↑{status.ahead}</span> : null}
+      {status.behind ? <span className="shrink-0 tabular-nums">↓{status.behind}</span> : null}
+    </span>
+  );
+});
@@ -0,0 +1,272 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+
+import {
+  AppDataArchiveError,
+  AppDataTransferService,
+} from "../electron/services/appDataTransfer";
+import { DEFAULT_APP_SETTINGS } from "../electron/services/appSettings";
+import { normalizeTranscriptTimestamp } from "../electron/services/transcriptTimestamps";
+import type {
+  PersistedState,
+  ThreadRecord,
+  TranscriptEvent,
+  WorkspaceRecord,
+} from "../src/app/types";
+import type { AppSettings, TranscriptAnnotation } from "../src/lib/desktopApi";
+
+const TS = "2026-03-01T00:00:00.000Z";
+
+function workspace(id: string): WorkspaceRecord {
+  return { id, name: id, path: `/projects/${id}` } as WorkspaceRecord;
+}
+
+function thread(id: string, workspaceId: string): ThreadRecord {
+  return { id, workspaceId, title: id } as ThreadRecord;
+}
+
+function event(threadId: string, type: string): TranscriptEvent {
+  return { ts: TS, threadId, direction: "server", payload: { type } };
+}
+
+/** In-memory stand-ins for the stores one machine's app data lives in. */
+function createMachine(state: PersistedState, transcripts: Record<string, TranscriptEvent[]> = {}) {
+  const machine = {
+    state,
+    transcripts: new Map(Object.entries(transcripts)),
+    annotations: new Map<string, TranscriptAnnotation[]>(),
+    settings: { ...DEFAULT_APP_SETTINGS } as AppSettings,
+    snapshots: 0,
+  };
+  const service = new AppDataTransferService({
+    appVersion: "1.4.0",
+    now: () => new Date(TS),
+    persistence: {
+      loadState: async () => machine.state,
+      saveState: async (next) => {
+        machine.state = next;
+        return 1;
+      },
+      captureStateSnapshot: async () => {
+        machine.snapshots += 1;
+        return { name: `state-${machine.snapshots}.json`, createdAt: TS, sizeBytes: 1 };
+      },
+      readTranscript: async (threadId) => machine.transcripts.get(threadId) ?? [],
+      deleteTranscript: async (threadId) => {
+        machine.transcripts.delete(threadId);
+      },
+      appendTranscriptBatch: async (events) => {
+        // Rejects the batch like `PersistenceService` does.
+        for (const entry of events) {
+          normalizeTranscriptTimestamp(entry.threadId, entry.ts);
+        }
+        for (const entry of events) {
+          const existing = machine.transcripts.get(entry.threadId) ?? [];
+          machine.transcripts.set(entry.threadId, [...existing, entry]);
+        }
+      },
+    },
+    settings: {
+      get: async () => machine.settings,
+      update: async (patch) => {
+        machine.settings = { ...machine.settings, ...patch };
+        return machine.settings;
+      },
+    },
+    annotations: {
+      list: async (threadId) => machine.annotations.get(threadId) ?? [],
+      replace: async (threadId, annotations) => {
+        machine.annotations.set(threadId, annotations);
+      },
+    },
+  });
+  return { machine, service };
+}
+
+describe("app data export and import", () => {
+  let root = "";
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "app-data-transfer-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  const oldLaptop = () => {
+    const source = createMachine(
+      {
+        version: 2,
+        generation: 7,
+        workspaces: [workspace("ws-1")],
+        threads: [thread("t-1", "ws-1"), thread("t-2", "ws-1")],
+      },
+      { "t-1": [event("t-1", "turn_start"), event("t-1", "turn_end")], "t-2": [] },
+    );
+    source.machine.settings = { ...source.machine.settings, trashRetentionDays: 7 };
+    source.machine.annotations.set("t-1", [
+      {
+        threadId: "t-1",
+        eventIndex: 1,
+        eventTs: TS,
+        note: "good answer",
+        tags: ["keep"],
+        createdAt: TS,
+        updatedAt: TS,
+      },
+    ]);
+    return source;
+  };
+
+  test("moves state, settings, transcripts, and annotations to a new machine", async () => {
+    const archivePath = path.join(root, "cowork-data.coworkdata");
+    const exported = await oldLaptop().service.exportTo(archivePath, true);
+
+    expect(exported.manifest).toEqual({
+      format: "cowork-app-data",
+      formatVersion: 1,
+      appVersion: "1.4.0",
+      createdAt: TS,
+      includesTranscripts: true,
+      workspaceCount: 1,
+      threadCount: 2,
+    });
+    expect(exported.sizeBytes).toBeGreaterThan(0);
+    expect((await fs.stat(archivePath)).mode & 0o777).toBe(0o600);
+
+    const { machine, service } = createMachine({ version: 2, workspaces: [], threads: [] });
+    const result = await service.importFrom(archivePath, "replace");
+
+    expect(result).toMatchObject({
+      mergeStrategy: "replace",
+      snapshotName: "state-1.json",
+      workspacesImported: 1,
+      threadsImported: 2,
+      threadsSkipped: 0,
+      transcriptsImported: 2,
+      settingsApplied: true,
+    });
+    expect(machine.state.generation).toBeUndefined();
+    expect(machine.state.threads.map((entry) => entry.id)).toEqual(["t-1", "t-2"]);
+    expect(machine.transcripts.get("t-1")?.map((entry) => entry.payload)).toEqual([
+      { type: "turn_start" },
+      { type: "turn_end" },
+    ]);
+    expect(machine.annotations.get("t-1")?.[0]?.note).toBe("good answer");
+    expect(machine.settings.trashRetentionDays).toBe(7);
+  });
+
+  test("reports progress per transcript and leaves nothing behind when cancelled", async () => {
+    const archivePath = path.join(root, "cowork-data.coworkdata");
+    const progress: Array<[number, number | null]> = [];
+    await oldLaptop().service.exportTo(archivePath, true, {
+      onProgress: ({ completed, total }) => progress.push([completed, total]),
+    });
+    expect(progress).toEqual([
+      [0, 2],
+      [1, 2],
+    ]);
+
+    const cancelledPath = path.join(root, "cancelled.coworkdata");
+    const controller = new AbortController();
+    const cancelled = oldLaptop().service.exportTo(cancelledPath, true, {
+      signal: controller.signal,
+      onProgress: () => controller.abort(),
+    });
+    await expect(cancelled).rejects.toThrow();
+    expect(await fs.readdir(root)).toEqual(["cowork-data.coworkdata"]);
+  });
+
+  test("merges only the threads this machine lacks and keeps its settings", async () => {
+    const archivePath = path.join(root, "cowork-data.coworkdata");
+    await oldLaptop().service.exportTo(archivePath, true);
+    const { machine, service } = createMachine(
+      { version: 2, workspaces: [workspace("ws-1")], threads: [thread("t-1", "ws-1")] },
+      { "t-1": [event("t-1", "local")] },
+    );
+
+    const result = await service.importFrom(archivePath, "merge");
+
+    expect(result).toMatchObject({
+      workspacesImported: 0,
+      threadsImported: 1,
+      threadsSkipped: 1,
+      transcriptsImported: 1,
+      settingsApplied: false,
+    });
+    expect(machine.state.threads.map((entry) => entry.id)).toEqual(["t-1", "t-2"]);
+    expect(machine.transcripts.get("t-1")?.map((entry) => entry.payload)).toEqual([
+      { type: "local" },
+    ]);
+    expect(machine.settings.trashRetentionDays).toBe(DEFAULT_APP_SETTINGS.trashRetentionDays);
+  });
+
+  test("fixes legacy transcript timestamps on import and skips ones it cannot read", async () => {
+    const archivePath = path.join(root, "cowork-data.coworkdata");
+    const legacy = (ts: string, type: string): TranscriptEvent => ({ ...event("t-1", type), ts });
+    await createMachine(
+      { version: 2, workspaces: [workspace("ws-1")], threads: [thread("t-1", "ws-1")] },
+      {
+        "t-1": [
+          legacy("Sun, 01 Mar 2026 00:00:00 GMT", "turn_start"),
+          legacy("someday", "lost"),
+          legacy("1772323200000", "turn_end"),
+        ],
+      },
+    ).service.exportTo(archivePath, true);
+    const { machine, service } = createMachine(
+      { version: 2, workspaces: [workspace("ws-1")], threads: [thread("t-1", "ws-1")] },
+      { "t-1": [event("t-1", "local")] },
+    );
+
+    const result = await service.importFrom(archivePath, "replace");
+
+    expect(result).toMatchObject({ transcriptsImported: 1, transcriptEventsSkipped: 1 });
+    expect(machine.transcripts.get("t-1")?.map((entry) => [entry.ts, entry.payload])).toEqual([
+      [TS, { type: "turn_start" }],
+      [TS, { type: "turn_end" }],
+    ]);
+  });
+
+  test("rejects newer, foreign, and truncated archives before writing anything", async () => {
+    const write = async (name: string, lines: unknown[]) => {
+      const filePath = path.join(root, name);
+      const body = lines.map((line) => `${JSON.stringify(line)}\n`).join("");
+      await fs.writeFile(filePath, zlib.gzipSync(body));
+      return filePath;
+    };
+    const manifest = {
+      format: "cowork-app-data",
+      formatVersion: 1,
+      appVersion: "1.4.0",
+      createdAt: TS,
+      includesTranscripts: false,
+      workspaceCount: 0,
+      threadCount: 0,
+    };
+    const state = { version: 2, workspaces: [], threads: [] };
+    const newer = await write("newer.coworkdata", [
+      { kind: "manifest", manifest: { ...manifest, formatVersion: 2, appVersion: "9.0.0" } },
+      { kind: "state", state },
+      { kind: "end" },
+    ]);
+    const truncated = await write("truncated.coworkdata", [
+      { kind: "manifest", manifest },
+      { kind: "state", state },
+    ]);
+    const plainText = path.join(root, "notes.coworkdata");
+    await fs.writeFile(plainText, "not an archive");
+    const { machine, service } = createMachine({ version: 2, workspaces: [], threads: [] });
+
+    await expect(service.importFrom(newer, "replace")).rejects.toThrow("newer version");
+    await expect(service.importFrom(truncated, "replace")).rejects.toThrow("incomplete");
+    await expect(service.importFrom(plainText, "replace")).rejects.toBeInstanceOf(
+      AppDataArchiveError,
+    );
+    expect(machine.snapshots).toBe(0);
+  });
+});
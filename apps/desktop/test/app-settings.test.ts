@@ -0,0 +1,116 @@
+import { afterEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  AppSettingsStore,
+  DEFAULT_APP_SETTINGS,
+  normalizeAppSettings,
+} from "../electron/services/appSettings";
+import type { AppSettings } from "../src/lib/desktopApi";
+import { updateAppSettingsInputSchema } from "../src/lib/desktopSchemas";
+
+const temporaryDirectories: string[] = [];
+
+async function createStore(
+  validate?: (next: AppSettings, current: AppSettings) => Promise<void>,
+): Promise<{
+  directory: string;
+  emitted: AppSettings[];
+  store: AppSettingsStore;
+}> {
+  const directory = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-app-settings-"));
+  temporaryDirectories.push(directory);
+  const emitted: AppSettings[] = [];
+  return {
+    directory,
+    emitted,
+    store: new AppSettingsStore(
+      { getPath: () => directory },
+      {
+        emit: (settings) => emitted.push(settings),
+        validate,
+        hardenPrivateDir: async () => {},
+        hardenPrivateFile: async () => {},
+      },
+    ),
+  };
+}
+
+afterEach(async () => {
+  await Promise.all(
+    temporaryDirectories.splice(0).map(async (directory) => {
+      await fs.rm(directory, { force: true, recursive: true });
+    }),
+  );
+});
+
+describe("app settings", () => {
+  test("fills missing and malformed keys from the defaults", () => {
+    expect(normalizeAppSettings(null)).toEqual(DEFAULT_APP_SETTINGS);
+    expect(
+      normalizeAppSettings({
+        defaultProvider: "not-a-provider",
+        startupBehavior: "newChat",
+        transcriptRetentionDays: 90.7,
+        notificationsEnabled: "yes",
+      }),
+    ).toEqual({ ...DEFAULT_APP_SETTINGS, startupBehavior: "newChat", transcriptRetentionDays: 90 });
+    expect(normalizeAppSettings({ themeSource: "dark" }).themeSource).toBe("dark");
+    expect(normalizeAppSettings({ themeSource: "sepia" }).themeSource).toBe("system");
+  });
+
+  test("merges patches into settings.json and reports each change once", async () => {
+    const { directory, emitted, store } = await createStore();
+    const seen: AppSettings[] = [];
+    const unsubscribe = store.subscribe((settings) => seen.push(settings));
+
+    expect(await store.get()).toEqual(DEFAULT_APP_SETTINGS);
+    await store.update({ defaultProvider: "anthropic" });
+    const updated = await store.update({ notificationsEnabled: false });
+    await store.update({ notificationsEnabled: false });
+    unsubscribe();
+
+    expect(updated).toEqual({
+      ...DEFAULT_APP_SETTINGS,
+      defaultProvider: "anthropic",
+      notificationsEnabled: false,
+    });
+    expect(JSON.parse(await fs.readFile(path.join(directory, "settings.json"), "utf8"))).toEqual(
+      updated,
+    );
+    expect(emitted).toHaveLength(2);
+    expect(seen).toEqual(emitted);
+  });
+
+  test("keeps defaults when the file is malformed and rejects unknown patch keys", async () => {
+    const { directory, store } = await createStore();
+    await fs.writeFile(path.join(directory, "settings.json"), "{not json", "utf8");
+
+    expect(await store.get()).toEqual(DEFAULT_APP_SETTINGS);
+    expect(updateAppSettingsInputSchema.safeParse({ theme: "dark" }).success).toBe(false);
+    expect(updateAppSettingsInputSchema.safeParse({ transcriptRetentionDays: -1 }).success).toBe(
+      false,
+    );
+  });
+
+  test("leaves settings untouched when validation rejects a change", async () => {
+    const { directory, emitted, store } = await createStore(async (next) => {
+      if (next.caBundlePath) {
+        throw new Error("CA bundle /missing.pem could not be read");
+      }
+    });
+
+    await expect(store.update({ caBundlePath: "/missing.pem" })).rejects.toThrow(
+      "could not be read",
+    );
+    const updated = await store.update({ httpsProxy: " http://proxy.corp:3128 " });
+
+    expect(updated).toEqual({ ...DEFAULT_APP_SETTINGS, httpsProxy: "http://proxy.corp:3128" });
+    expect(JSON.parse(await fs.readFile(path.join(directory, "settings.json"), "utf8"))).toEqual(
+      updated,
+    );
+    expect(emitted).toEqual([updated]);
+  });
+});
@@ -0,0 +1,86 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { ApprovalQueue } from "../electron/services/approvalQueue";
+
+const request = {
+  id: "req-1",
+  workspaceId: "ws-1",
+  threadId: "thread-1",
+  command: "npm test",
+  dangerous: false,
+  reason: "requires_manual_review",
+};
+
+describe("ApprovalQueue", () => {
+  let dir: string;
+  let filePath: string;
+
+  beforeEach(async () => {
+    dir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-approvals-"));
+    filePath = path.join(dir, "approvals.json");
+  });
+
+  afterEach(async () => {
+    await fs.rm(dir, { recursive: true, force: true });
+  });
+
+  function createQueue() {
+    return new ApprovalQueue({ filePath, now: () => new Date("2026-03-01T12:00:00.000Z") });
+  }
+
+  test("queues approvals per workspace and keeps them across instances", async () => {
+    const queue = createQueue();
+    expect(await queue.record(request)).toEqual({ autoApproved: false });
+    expect(await queue.record(request)).toEqual({ autoApproved: false });
+    await queue.record({ ...request, id: "req-2", workspaceId: "ws-2" });
+
+    expect(await createQueue().list("ws-1")).toEqual([
+      { ...request, receivedAt: "2026-03-01T12:00:00.000Z" },
+    ]);
+  });
+
+  test("remembers approved commands and answers matching requests", async () => {
+    const queue = createQueue();
+    await queue.record(request);
+    const resolved = await queue.resolve({ id: "req-1", decision: "approve", rememberRule: true });
+
+    expect(resolved?.command).toBe("npm test");
+    expect(await queue.list("ws-1")).toEqual([]);
+    expect(await queue.resolve({ id: "req-1", decision: "approve" })).toBeNull();
+
+    const reloaded = createQueue();
+    expect(await reloaded.record({ ...request, id: "req-3" })).toEqual({ autoApproved: true });
+    expect(await reloaded.record({ ...request, id: "req-4", workspaceId: "ws-2" })).toEqual({
+      autoApproved: false,
+    });
+    expect(await reloaded.record({ ...request, id: "req-5", command: "npm test -- -u" })).toEqual({
+      autoApproved: false,
+    });
+  });
+
+  test("never remembers denials or dangerous commands", async () => {
+    const queue = createQueue();
+    await queue.record(request);
+    await queue.resolve({ id: "req-1", decision: "deny", rememberRule: true });
+    expect(await queue.record({ ...request, id: "req-2" })).toEqual({ autoApproved: false });
+
+    await queue.record({ ...request, id: "req-3", dangerous: true });
+    await expect(
+      queue.resolve({ id: "req-3", decision: "approve", rememberRule: true }),
+    ).rejects.toThrow("cannot be approved automatically");
+    expect((await queue.list("ws-1")).map((entry) => entry.id)).toEqual(["req-2", "req-3"]);
+  });
+
+  test("clears a workspace's pending approvals", async () => {
+    const queue = createQueue();
+    await queue.record(request);
+    await queue.record({ ...request, id: "req-2", workspaceId: "ws-2" });
+    await queue.clearPending("ws-1");
+
+    expect(await queue.list("ws-1")).toEqual([]);
+    expect(await queue.list("ws-2")).toHaveLength(1);
+  });
+});
@@ -0,0 +1,57 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { AttachmentStore, sniffMimeType } from "../electron/services/attachmentStore";
+
+const PNG_HEADER = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a, 0x00, 0x00];
+
+describe("attachment store", () => {
+  let directory = "";
+
+  beforeEach(async () => {
+    directory = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-attachment-store-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(directory, { recursive: true, force: true });
+  });
+
+  test("stores identical bytes once under their sha256", async () => {
+    const store = new AttachmentStore({ directory });
+    const bytes = Uint8Array.from(PNG_HEADER);
+
+    const first = await store.store(bytes);
+    const second = await store.store(Uint8Array.from(PNG_HEADER));
+
+    expect(first).toMatchObject({ sizeBytes: 10, mimeType: "image/png", deduplicated: false });
+    expect(second).toEqual({ ...first, deduplicated: true });
+    expect(await fs.readdir(path.join(directory, first.hash.slice(0, 2)))).toEqual([first.hash]);
+    expect(await store.stat(first.hash)).toEqual({
+      hash: first.hash,
+      sizeBytes: 10,
+      mimeType: "image/png",
+    });
+    expect(Array.from((await store.read(first.hash))?.bytes ?? [])).toEqual(PNG_HEADER);
+  });
+
+  test("enforces the size limit and rejects malformed hashes", async () => {
+    const store = new AttachmentStore({ directory, maxBytes: 4 });
+
+    await expect(store.store(new Uint8Array(5))).rejects.toThrow("Attachments are limited to");
+    expect(await store.read("a".repeat(64))).toBeNull();
+    expect(await store.stat("a".repeat(64))).toBeNull();
+    expect(() => store.pathFor("../state.json")).toThrow("lowercase sha256 hex digest");
+  });
+
+  test("sniffs content rather than trusting names", () => {
+    const encoder = new TextEncoder();
+    expect(sniffMimeType(encoder.encode("%PDF-1.7\n"))).toBe("application/pdf");
+    expect(sniffMimeType(encoder.encode('<svg xmlns="http://www.w3.org/2000/svg"/>'))).toBe(
+      "image/svg+xml",
+    );
+    expect(sniffMimeType(encoder.encode("plain notes"))).toBe("text/plain");
+    expect(sniffMimeType(Uint8Array.from([0x00, 0x01, 0x02]))).toBe("application/octet-stream");
+  });
+});
@@ -0,0 +1,85 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { AttachmentStore } from "../electron/services/attachmentStore";
+import {
+  AttachmentThumbnailer,
+  type DecodedImage,
+} from "../electron/services/attachmentThumbnails";
+import { getThumbnailInputSchema } from "../src/lib/desktopSchemas";
+
+const PNG_SIGNATURE = [0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a, 0x1a, 0x0a];
+
+/** A PNG header with just enough IHDR for the size to be read back. */
+function pngHeader(width: number, height: number): Buffer {
+  const bytes = Buffer.alloc(24);
+  Buffer.from(PNG_SIGNATURE).copy(bytes);
+  bytes.writeUInt32BE(width, 16);
+  bytes.writeUInt32BE(height, 20);
+  return bytes;
+}
+
+function fakeImage(width: number, height: number, resizes: string[]): DecodedImage {
+  return {
+    isEmpty: () => false,
+    getSize: () => ({ width, height }),
+    resize: (size) => {
+      resizes.push(`${size.width}x${size.height}`);
+      return fakeImage(size.width, size.height, resizes);
+    },
+    toPNG: () => pngHeader(width, height),
+  };
+}
+
+describe("attachment thumbnails", () => {
+  let root = "";
+  let attachments: AttachmentStore;
+  let resizes: string[];
+
+  const createThumbnailer = () =>
+    new AttachmentThumbnailer({
+      attachments,
+      directory: path.join(root, "thumbnails"),
+      decodeImage: (bytes) => fakeImage(Buffer.from(bytes).readUInt32BE(16), 1500, resizes),
+    });
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-thumbnails-"));
+    attachments = new AttachmentStore({ directory: path.join(root, "attachments") });
+    resizes = [];
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  test("downscales once and serves later requests from the cache", async () => {
+    const { hash } = await attachments.store(pngHeader(3000, 1500));
+
+    const [first, concurrent] = await Promise.all([
+      createThumbnailer().get(hash, 200),
+      createThumbnailer().get(hash, 400),
+    ]);
+    const cached = await createThumbnailer().get(hash, 200);
+
+    expect(first).toMatchObject({ hash, maxDim: 200, width: 200, height: 100 });
+    expect(concurrent).toMatchObject({ width: 400, height: 200 });
+    expect(cached).toMatchObject({ width: 200, height: 100, path: first?.path });
+    expect(resizes.sort()).toEqual(["200x100", "400x200"]);
+  });
+
+  test("never upscales and skips attachments that are not images", async () => {
+    const small = await attachments.store(pngHeader(64, 1500));
+    const text = await attachments.store(new TextEncoder().encode("not an image"));
+    const thumbnailer = createThumbnailer();
+
+    expect(await thumbnailer.get(small.hash, 2048)).toMatchObject({ width: 64, height: 1500 });
+    expect(await thumbnailer.get(text.hash, 200)).toBeNull();
+    expect(await thumbnailer.get("a".repeat(64), 200)).toBeNull();
+    expect(getThumbnailInputSchema.safeParse({ hash: small.hash, maxDim: 4096 }).success).toBe(
+      false,
+    );
+  });
+});
@@ -690,6 +690,30 @@ describe("desktop bootstrap cache", () => {
     expect(seed?.threadRuntimeById?.["thread-cached"]?.hydrating).toBeUndefined();
   });
 
+  test("buildCachedDesktopStateSeed keeps workspace roots, limits, and transcript mirror", () => {
+    const [workspace] = cachedState.persistedState.workspaces;
+    const seed = buildCachedDesktopStateSeed({
+      ...cachedState,
+      persistedState: {
+        ...cachedState.persistedState,
+        workspaces: [
+          {
+            ...workspace,
+            additionalRoots: ["/tmp/workspace-lib", "/tmp/workspace-cached"],
+            resourceLimits: { maxMemoryMb: 2048 },
+            transcriptMirror: { directory: "transcripts", format: "jsonl" },
+          },
+        ],
+      },
+    });
+
+    expect(seed?.workspaces?.[0]).toMatchObject({
+      additionalRoots: ["/tmp/workspace-lib"],
+      resourceLimits: { maxMemoryMb: 2048 },
+      transcriptMirror: { directory: "transcripts", format: "jsonl" },
+    });
+  });
+
   test("buildCachedDesktopStateSeed restores a complete New Chat target draft", async () => {
     const key = composerDraftKeyForNewChatTarget({
       kind: "project",
@@ -353,4 +353,15 @@ describe("Canvas first paint and live theme changes", () => {
     expect(root.dataset.theme).toBe("dark");
     expect(root.style.colorScheme).toBe("light");
   });
+
+  test("exposes the OS accent color as a CSS variable until it goes away", () => {
+    const dom = new JSDOM("<!doctype html><html></html>");
+    const root = dom.window.document.documentElement;
+
+    applySystemAppearanceToDocument(appearance({ accentColor: "#0a84ff" }), dom.window.document);
+    expect(root.style.getPropertyValue("--system-accent-color")).toBe("#0a84ff");
+
+    applySystemAppearanceToDocument(appearance({ accentColor: null }), dom.window.document);
+    expect(root.style.getPropertyValue("--system-accent-color")).toBe("");
+  });
 });
@@ -0,0 +1,87 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  ConnectivityMonitor,
+  withConnectivityContext,
+} from "../electron/services/connectivityMonitor";
+import type { ConnectivityStatus } from "../src/lib/desktopApi";
+import { isNetworkUnavailableError } from "../src/lib/desktopCommands";
+
+const ENDPOINTS = ["https://api.openai.com", "https://api.anthropic.com"];
+
+function createMonitor(reachable: Set<string>, online = { value: true }) {
+  const changes: ConnectivityStatus[] = [];
+  const monitor = new ConnectivityMonitor({
+    isOnline: () => online.value,
+    probe: async (url) => {
+      if (url.includes("anthropic") && !reachable.has(url)) {
+        throw new Error("ECONNRESET");
+      }
+      return reachable.has(url);
+    },
+    endpoints: ENDPOINTS,
+    now: () => Date.parse("2026-10-15T12:00:00.000Z"),
+    onChange: (status) => changes.push(status),
+  });
+  return { monitor, changes, online };
+}
+
+describe("connectivity monitor", () => {
+  test("reports degraded when only some provider endpoints answer", async () => {
+    const { monitor } = createMonitor(new Set([ENDPOINTS[0] ?? ""]));
+
+    expect(await monitor.check()).toEqual({
+      state: "degraded",
+      unreachableHosts: ["api.anthropic.com"],
+      checkedAt: "2026-10-15T12:00:00.000Z",
+      message: "Could not reach api.anthropic.com.",
+    });
+  });
+
+  test("reports offline without probing when the OS has no network", async () => {
+    let probes = 0;
+    const monitor = new ConnectivityMonitor({
+      isOnline: () => false,
+      probe: async () => {
+        probes += 1;
+        return true;
+      },
+    });
+
+    expect(await monitor.check()).toMatchObject({ state: "offline", unreachableHosts: [] });
+    expect(probes).toBe(0);
+  });
+
+  test("emits only when the state or unreachable hosts change", async () => {
+    const reachable = new Set(ENDPOINTS);
+    const { monitor, changes, online } = createMonitor(reachable);
+
+    await monitor.check();
+    await monitor.check();
+    online.value = false;
+    await monitor.check();
+    online.value = true;
+    reachable.clear();
+    await monitor.check();
+
+    expect(changes.map((status) => status.state)).toEqual(["offline", "offline"]);
+    expect(changes[1]?.unreachableHosts).toEqual(["api.openai.com", "api.anthropic.com"]);
+    expect(monitor.getStatus().message).toBe("No provider endpoint could be reached.");
+  });
+
+  test("tags start failures only when the network is not online", async () => {
+    const failure = new Error("Server exited before reporting a listening URL");
+    const { monitor } = createMonitor(new Set(ENDPOINTS));
+    const online = await monitor.check();
+
+    expect(withConnectivityContext(failure, online)).toBe(failure);
+    expect(isNetworkUnavailableError(failure)).toBe(false);
+
+    const offline: ConnectivityStatus = { ...online, state: "offline", message: "Offline." };
+    const tagged = withConnectivityContext(failure, offline);
+    expect(isNetworkUnavailableError(tagged)).toBe(true);
+    expect((tagged as Error).message).toBe(
+      "network_unavailable: Offline. Server exited before reporting a listening URL",
+    );
+  });
+});
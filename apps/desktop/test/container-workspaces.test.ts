@@ -0,0 +1,145 @@
+import { describe, expect, test } from "bun:test";
+import { EventEmitter } from "node:events";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import { PassThrough } from "node:stream";
+
+import {
+  containerNameForServer,
+  containerPathPolicy,
+  DockerWorkspaces,
+  resolveContainerLaunch,
+  stripJsonComments,
+} from "../electron/services/containerWorkspaces";
+import { normalizeWorkspaceContainerLaunch } from "../src/app/workspaceContainer";
+
+async function withWorkspace(run: (dir: string) => Promise<void>): Promise<void> {
+  const dir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-container-"));
+  try {
+    await run(dir);
+  } finally {
+    await fs.rm(dir, { recursive: true, force: true });
+  }
+}
+
+describe("container workspace launch", () => {
+  test("normalizes the persisted container setting", () => {
+    expect(normalizeWorkspaceContainerLaunch(undefined)).toBeUndefined();
+    expect(normalizeWorkspaceContainerLaunch({})).toEqual({});
+    expect(normalizeWorkspaceContainerLaunch({ image: " ghcr.io/acme/dev:1.2 " })).toEqual({
+      image: "ghcr.io/acme/dev:1.2",
+    });
+    expect(normalizeWorkspaceContainerLaunch({ image: "--privileged" })).toEqual({});
+  });
+
+  test("strips comments and trailing commas but not string contents", () => {
+    const text = `{
+      // the image
+      "image": "node:22", /* pinned */
+      "note": "http://example.com/*x*/",
+    }`;
+    expect(JSON.parse(stripJsonComments(text))).toEqual({
+      image: "node:22",
+      note: "http://example.com/*x*/",
+    });
+  });
+
+  test("reads the image and folder from devcontainer.json", async () => {
+    await withWorkspace(async (dir) => {
+      await fs.mkdir(path.join(dir, ".devcontainer"));
+      await fs.writeFile(
+        path.join(dir, ".devcontainer", "devcontainer.json"),
+        `{ "image": "mcr.microsoft.com/devcontainers/base:ubuntu", "workspaceFolder": "/code", }`,
+      );
+      expect(await resolveContainerLaunch(dir, {})).toEqual({
+        image: "mcr.microsoft.com/devcontainers/base:ubuntu",
+        workspaceFolder: "/code",
+        configPath: path.join(dir, ".devcontainer", "devcontainer.json"),
+      });
+      expect(await resolveContainerLaunch(dir, { image: "node:22" })).toMatchObject({
+        image: "node:22",
+        workspaceFolder: "/code",
+      });
+    });
+  });
+
+  test("needs an image when there is none to read", async () => {
+    await withWorkspace(async (dir) => {
+      await expect(resolveContainerLaunch(dir, {})).rejects.toThrow("no devcontainer.json");
+      expect(await resolveContainerLaunch(dir, { image: "node:22" })).toEqual({
+        image: "node:22",
+        workspaceFolder: `/workspaces/${path.basename(dir)}`,
+        configPath: null,
+      });
+
+      await fs.writeFile(
+        path.join(dir, ".devcontainer.json"),
+        `{ "build": { "dockerfile": "Dockerfile" } }`,
+      );
+      await expect(resolveContainerLaunch(dir, {})).rejects.toThrow("builds its own image");
+    });
+  });
+
+  test("maps path policy entries onto the mounted workspace folder", () => {
+    const policy = JSON.stringify({
+      allow: ["/projects/app/vendor"],
+      deny: ["/projects/app", "/projects/app/.env", "/home/me/.ssh"],
+    });
+    expect(JSON.parse(containerPathPolicy(policy, "/projects/app", "/workspaces/app"))).toEqual({
+      allow: ["/workspaces/app/vendor"],
+      deny: ["/workspaces/app", "/workspaces/app/.env"],
+    });
+    const windows = JSON.stringify({ allow: [], deny: ["C:\\src\\app\\secrets", "C:\\src\\apps"] });
+    expect(JSON.parse(containerPathPolicy(windows, "C:\\src\\app", "/w"))).toEqual({
+      allow: [],
+      deny: ["/w/secrets"],
+    });
+
+    const external = JSON.stringify({ allow: ["/projects/shared"], deny: [] });
+    expect(() => containerPathPolicy(external, "/projects/app", "/workspaces/app")).toThrow(
+      "not mounted in the container",
+    );
+  });
+
+  test("runs the sidecar with env values outside the docker command line", () => {
+    const calls: Array<{ command: string; args: string[]; env: NodeJS.ProcessEnv }> = [];
+    const spawn = (command: string, args: string[], options: { env: NodeJS.ProcessEnv }) => {
+      calls.push({ command, args, env: options.env });
+      return Object.assign(new EventEmitter(), {
+        stdout: new PassThrough(),
+        stderr: new PassThrough(),
+      });
+    };
+    const docker = new DockerWorkspaces({ spawn: spawn as never, serverCommand: "cowork-server" });
+    const containerName = containerNameForServer("ws-1");
+    docker.launchServer({
+      containerName,
+      serverId: "ws-1",
+      workspacePath: "/Users/me/project",
+      launch: { image: "node:22", workspaceFolder: "/workspaces/project", configPath: null },
+      hostPort: 50200,
+      args: ["--dir", "/workspaces/project", "--json"],
+      env: { COWORK_SERVER_AUTH_TOKEN: "secret-token" },
+    });
+
+    const call = calls[0]!;
+    expect(call.command).toBe("docker");
+    expect(call.args).toEqual(
+      expect.arrayContaining([
+        "--rm",
+        "--name",
+        "cowork-workspace-ws-1",
+        "-v",
+        "/Users/me/project:/workspaces/project",
+        "-p",
+        "127.0.0.1:50200:7337",
+        "-e",
+        "COWORK_SERVER_AUTH_TOKEN",
+      ]),
+    );
+    expect(call.args.slice(call.args.indexOf("--") + 1, -3)).toEqual(["node:22", "cowork-server"]);
+    expect(call.args.join(" ")).not.toContain("secret-token");
+    expect(call.env.COWORK_SERVER_AUTH_TOKEN).toBe("secret-token");
+  });
+});
@@ -0,0 +1,36 @@
+import { describe, expect, test } from "bun:test";
+
+import { nextCronOccurrence, parseCronExpression } from "../electron/services/cron";
+
+function next(expression: string, after: Date): string | undefined {
+  return nextCronOccurrence(parseCronExpression(expression), after)?.toString();
+}
+
+describe("cron expressions", () => {
+  test("parses lists, ranges, steps, names, and macros", () => {
+    const schedule = parseCronExpression("*/15 9-17 * jan,JUL mon-fri");
+    expect([...schedule.minutes]).toEqual([0, 15, 30, 45]);
+    expect([...schedule.hours]).toEqual([9, 10, 11, 12, 13, 14, 15, 16, 17]);
+    expect([...schedule.months]).toEqual([1, 7]);
+    expect([...schedule.daysOfWeek]).toEqual([1, 2, 3, 4, 5]);
+    expect([...parseCronExpression("0 0 * * 7").daysOfWeek]).toEqual([0]);
+    expect(parseCronExpression("@daily")).toEqual(parseCronExpression("0 0 * * *"));
+  });
+
+  test("rejects malformed expressions", () => {
+    expect(() => parseCronExpression("* * * *")).toThrow("expected 5 fields");
+    expect(() => parseCronExpression("60 * * * *")).toThrow("60 is out of range");
+    expect(() => parseCronExpression("*/0 * * * *")).toThrow("bad step");
+    expect(() => parseCronExpression("0 5-3 * * *")).toThrow("5-3 is backwards");
+  });
+
+  test("finds the next matching minute in local time", () => {
+    const after = new Date(2026, 0, 1, 8, 59, 30);
+    expect(next("*/15 9-17 * * *", after)).toBe(new Date(2026, 0, 1, 9, 0).toString());
+    expect(next("30 8 * * *", after)).toBe(new Date(2026, 0, 2, 8, 30).toString());
+    // 2026-01-01 is a Thursday; either day field may match when both are set.
+    expect(next("0 0 15 * fri", after)).toBe(new Date(2026, 0, 2, 0, 0).toString());
+    expect(next("0 0 29 2 *", after)).toBe(new Date(2028, 1, 29, 0, 0).toString());
+    expect(next("0 0 30 2 *", after)).toBeUndefined();
+  });
+});
@@ -0,0 +1,194 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import type { PersistedState, WorkspaceRecord } from "../src/app/types";
+import type { DesktopDeepLinkNavigation } from "../src/lib/desktopApi";
+import { DeepLinkService, findDeepLinkArg, parseDeepLink } from "../electron/services/deepLinks";
+
+function workspaceRecord(id: string, workspacePath: string): WorkspaceRecord {
+  return {
+    id,
+    name: path.basename(workspacePath),
+    path: workspacePath,
+    createdAt: "2026-01-01T00:00:00.000Z",
+    lastOpenedAt: "2026-01-01T00:00:00.000Z",
+    defaultEnableMcp: true,
+    defaultBackupsEnabled: false,
+    yolo: false,
+  };
+}
+
+describe("cowork:// deep links", () => {
+  let root = "";
+  let workspacePath = "";
+  let state: PersistedState;
+
+  beforeEach(async () => {
+    root = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), "cowork-deep-links-")));
+    workspacePath = path.join(root, "project");
+    await fs.mkdir(workspacePath, { recursive: true });
+    state = {
+      version: 2,
+      workspaces: [workspaceRecord("ws-1", workspacePath)],
+      threads: [
+        {
+          id: "thread-1",
+          workspaceId: "ws-1",
+          title: "Thread",
+          createdAt: "2026-01-01T00:00:00.000Z",
+          lastMessageAt: "2026-01-01T00:00:00.000Z",
+          status: "disconnected",
+          sessionId: null,
+          messageCount: 0,
+          lastEventSeq: 0,
+        },
+      ],
+    };
+  });
+
+  afterEach(async () => {
+    if (root) {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+    root = "";
+  });
+
+  function createService(overrides: { confirm?: boolean; listening?: boolean } = {}) {
+    const started: string[] = [];
+    const delivered: DesktopDeepLinkNavigation[] = [];
+    const approved: string[] = [];
+    const service = new DeepLinkService({
+      loadState: async () => state,
+      startWorkspaceServer: async (workspace) => {
+        started.push(workspace.id);
+      },
+      confirmOpenPath: async () => overrides.confirm ?? true,
+      approveWorkspacePath: async (directory) => {
+        approved.push(directory);
+        return directory;
+      },
+      openTranscriptFile: async (filePath) => ({
+        threadId: "viewer-1",
+        title: "Exported",
+        sourcePath: filePath,
+        readOnly: true,
+        events: [],
+      }),
+      deliver: (navigation) => {
+        if (overrides.listening === false) {
+          return false;
+        }
+        delivered.push(navigation);
+        return true;
+      },
+    });
+    return { service, started, delivered, approved };
+  }
+
+  test("parses workspace, thread, and open links", () => {
+    expect(parseDeepLink("cowork://workspace/ws-1")).toEqual({
+      kind: "workspace",
+      workspaceId: "ws-1",
+    });
+    expect(parseDeepLink("cowork://workspace/ws-1/thread/thread-1/")).toEqual({
+      kind: "thread",
+      workspaceId: "ws-1",
+      threadId: "thread-1",
+    });
+    expect(parseDeepLink("cowork://open?path=%2Fsrv%2Fapp")).toEqual({
+      kind: "openPath",
+      path: path.resolve("/srv/app"),
+    });
+  });
+
+  test("rejects other schemes, unknown actions, unsafe ids, and relative paths", () => {
+    expect(() => parseDeepLink("https://workspace/ws-1")).toThrow("cowork:// scheme");
+    expect(() => parseDeepLink("cowork://settings")).toThrow("Unsupported deep link");
+    expect(() => parseDeepLink("cowork://workspace/ws-1/thread")).toThrow("Unsupported deep link");
+    expect(() => parseDeepLink("cowork://workspace/ws%2E1")).toThrow("invalid characters");
+    expect(() => parseDeepLink("cowork://open?path=src")).toThrow("absolute path");
+    expect(() => parseDeepLink("not a url")).toThrow("not a valid URL");
+  });
+
+  test("finds the link among second-instance arguments", () => {
+    expect(findDeepLinkArg(["Cowork.exe", "--flag", "COWORK://workspace/ws-1"])).toBe(
+      "COWORK://workspace/ws-1",
+    );
+    expect(findDeepLinkArg(["Cowork.exe", "--flag"])).toBeNull();
+  });
+
+  test("starts the workspace server before navigating to a thread", async () => {
+    const { service, started, delivered } = createService();
+
+    await service.handle("cowork://workspace/ws-1/thread/thread-1");
+
+    expect(started).toEqual(["ws-1"]);
+    expect(delivered).toEqual([{ kind: "thread", workspaceId: "ws-1", threadId: "thread-1" }]);
+    await expect(service.handle("cowork://workspace/ws-2")).rejects.toThrow(
+      "Unknown workspace: ws-2",
+    );
+    await expect(service.handle("cowork://workspace/ws-1/thread/other")).rejects.toThrow(
+      "Unknown thread in workspace ws-1: other",
+    );
+    expect(started).toEqual(["ws-1"]);
+  });
+
+  test("opening a known folder navigates to its workspace", async () => {
+    const { service, started, delivered, approved } = createService();
+
+    await service.handle(`cowork://open?path=${encodeURIComponent(`${workspacePath}/`)}`);
+
+    expect(started).toEqual(["ws-1"]);
+    expect(approved).toEqual([]);
+    expect(delivered).toEqual([{ kind: "workspace", workspaceId: "ws-1" }]);
+  });
+
+  test("opening a new folder requires confirmation before it is approved", async () => {
+    const otherPath = path.join(root, "other");
+    await fs.mkdir(otherPath);
+    const declined = createService({ confirm: false });
+
+    expect(await declined.service.handle(`cowork://open?path=${otherPath}`)).toBeNull();
+    expect(declined.approved).toEqual([]);
+    expect(declined.delivered).toEqual([]);
+
+    const accepted = createService();
+    await accepted.service.handle(`cowork://open?path=${otherPath}`);
+    expect(accepted.approved).toEqual([otherPath]);
+    expect(accepted.delivered).toEqual([{ kind: "openPath", path: otherPath }]);
+
+    await expect(
+      accepted.service.handle(`cowork://open?path=${path.join(root, "missing")}`),
+    ).rejects.toThrow("does not exist");
+  });
+
+  test("queues navigation until the main window is listening", async () => {
+    const { service, delivered } = createService({ listening: false });
+
+    await service.handle("cowork://workspace/ws-1");
+
+    expect(delivered).toEqual([]);
+    expect(service.drainPending()).toEqual([{ kind: "workspace", workspaceId: "ws-1" }]);
+    expect(service.drainPending()).toEqual([]);
+  });
+
+  test("opens exported threads from the OS in the viewer", async () => {
+    const filePath = path.join(root, "notes.cowork-thread");
+    const navigation = {
+      kind: "transcriptFile",
+      threadId: "viewer-1",
+      title: "Exported",
+      sourcePath: filePath,
+    };
+    const listening = createService();
+    await listening.service.openTranscriptFile(filePath);
+    expect(listening.delivered).toEqual([navigation]);
+    expect(listening.started).toEqual([]);
+
+    const waiting = createService({ listening: false });
+    await waiting.service.openTranscriptFile(filePath);
+    expect(waiting.service.drainPending()).toEqual([navigation]);
+  });
+});
@@ -0,0 +1,98 @@
+import { describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import path from "node:path";
+
+import { DESKTOP_EVENT_CHANNELS, DESKTOP_IPC_CHANNELS } from "../src/lib/desktopApi";
+import {
+  createDesktopEventBindings,
+  createDesktopIpcBindings,
+} from "../src/lib/desktopBindings.generated";
+import {
+  buildDesktopBindingsArtifact,
+  DESKTOP_BINDINGS_ARTIFACT_PATH,
+} from "../src/lib/desktopBindingsCodegen";
+import {
+  backendSupportsCommand,
+  DESKTOP_EVENT_LISTENERS,
+  DESKTOP_IPC_COMMANDS,
+  describeDesktopBackendCapabilities,
+} from "../src/lib/desktopIpcContract";
+import { desktopBackendCapabilitiesSchema } from "../src/lib/desktopSchemas";
+
+function normalizeTypeScriptArtifact(value: string): string {
+  return value.replace(/\r\n/g, "\n").replace(/\s+/g, "");
+}
+
+describe("generated desktop bindings", () => {
+  test("the checked-in bindings are up to date", async () => {
+    const generated = await fs.readFile(
+      path.join(import.meta.dir, "..", DESKTOP_BINDINGS_ARTIFACT_PATH),
+      "utf-8",
+    );
+
+    expect(normalizeTypeScriptArtifact(generated)).toBe(
+      normalizeTypeScriptArtifact(buildDesktopBindingsArtifact()),
+    );
+  });
+
+  test("the contract covers every channel", () => {
+    expect(Object.keys(DESKTOP_IPC_COMMANDS).sort()).toEqual(
+      Object.keys(DESKTOP_IPC_CHANNELS).sort(),
+    );
+    expect(Object.keys(DESKTOP_EVENT_LISTENERS).sort()).toEqual(
+      Object.keys(DESKTOP_EVENT_CHANNELS).sort(),
+    );
+  });
+
+  test("command wrappers invoke the mapped channel with their arguments", async () => {
+    const calls: unknown[][] = [];
+    const bindings = createDesktopIpcBindings(async (...args) => {
+      calls.push(args);
+      return { status: "ok" };
+    });
+
+    await expect(bindings.startMobileRelay({ workspaceId: "ws-1" } as never)).resolves.toEqual({
+      status: "ok",
+    });
+    expect(calls).toEqual([[DESKTOP_IPC_CHANNELS.mobileRelayStart, { workspaceId: "ws-1" }]]);
+    expect("authorizeUploadSource" in bindings).toBe(false);
+  });
+
+  test("event wrappers subscribe to the mapped channel", () => {
+    const channels: string[] = [];
+    const bindings = createDesktopEventBindings((channel) => {
+      channels.push(channel);
+      return () => {};
+    });
+
+    bindings.onServerStats(() => {});
+
+    expect(channels).toEqual([DESKTOP_EVENT_CHANNELS.serverStats]);
+  });
+
+  test("backend capabilities list the public commands and every event", () => {
+    const capabilities = describeDesktopBackendCapabilities();
+
+    expect(desktopBackendCapabilitiesSchema.safeParse(capabilities).success).toBe(true);
+    expect(capabilities.commands).toContain("getBackendCapabilities");
+    expect(capabilities.commands).toContain("startMobileRelay");
+    expect(capabilities.commands).not.toContain("mobileRelayStart");
+    expect(capabilities.commands).not.toContain("authorizeUploadSource");
+    expect(capabilities.events).toEqual(Object.keys(DESKTOP_EVENT_CHANNELS).sort());
+  });
+
+  test("renderers degrade against backends missing a command or schema version", () => {
+    const capabilities = {
+      ...describeDesktopBackendCapabilities(),
+      commands: ["listOperations"],
+      schemaVersions: { commands: { listOperations: 2 }, events: {} },
+    };
+
+    expect(backendSupportsCommand(capabilities, "listOperations", 2)).toBe(true);
+    expect(backendSupportsCommand(capabilities, "listOperations", 3)).toBe(false);
+    expect(backendSupportsCommand(capabilities, "enqueueExport")).toBe(false);
+    // Backends without capability reporting only promise the original schemas.
+    expect(backendSupportsCommand(null, "enqueueExport")).toBe(true);
+    expect(backendSupportsCommand(null, "enqueueExport", 2)).toBe(false);
+  });
+});
@@ -184,6 +184,32 @@ describe("desktop diagnostics service", () => {
     }
   });
 
+  test("reports bundle progress and stops writing once the signal is aborted", async () => {
+    const userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-diagnostics-"));
+    try {
+      const service = await createService({ userDataDir, uploadEnabled: false });
+      const controller = new AbortController();
+      const messages: Array<string | null> = [];
+
+      await expect(
+        service.createBundle({
+          signal: controller.signal,
+          onProgress: (progress: { message: string | null }) => {
+            messages.push(progress.message);
+            if (progress.message === "Collecting logs") {
+              controller.abort();
+            }
+          },
+        }),
+      ).rejects.toThrow();
+
+      expect(messages).toEqual(["Reading app state", "Collecting logs"]);
+      await expect(fs.readdir(path.join(userDataDir, "diagnostics"))).rejects.toThrow();
+    } finally {
+      await fs.rm(userDataDir, { recursive: true, force: true });
+    }
+  });
+
   test("includes sanitized sidecar lifecycle diagnostics", async () => {
     const userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-diagnostics-"));
     try {
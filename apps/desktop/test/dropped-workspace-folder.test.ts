@@ -0,0 +1,85 @@
+import { afterEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { resolveDroppedWorkspaceFolder } from "../electron/services/droppedWorkspaceFolder";
+import { dataTransferHasFiles, droppedFolderFiles } from "../src/lib/workspaceFolderDrop";
+
+const temporaryDirectories: string[] = [];
+
+async function createRoot(): Promise<string> {
+  const root = await fs.realpath(
+    await fs.mkdtemp(path.join(os.tmpdir(), "cowork-dropped-folder-")),
+  );
+  temporaryDirectories.push(root);
+  await fs.mkdir(path.join(root, "project"));
+  await fs.mkdir(path.join(root, "userData", "workspaces"), { recursive: true });
+  await fs.writeFile(path.join(root, "notes.txt"), "hello", "utf8");
+  return root;
+}
+
+afterEach(async () => {
+  await Promise.all(
+    temporaryDirectories.splice(0).map(async (directory) => {
+      await fs.rm(directory, { force: true, recursive: true });
+    }),
+  );
+});
+
+describe("dropped workspace folders", () => {
+  test("accepts a new folder and reports one that is already registered", async () => {
+    const root = await createRoot();
+    const projectPath = path.join(root, "project");
+    const appDataPaths = [path.join(root, "userData")];
+
+    expect(
+      await resolveDroppedWorkspaceFolder(`${projectPath}${path.sep}`, {
+        appDataPaths,
+        workspaces: [],
+      }),
+    ).toEqual({ path: projectPath, existingWorkspaceId: null });
+    expect(
+      await resolveDroppedWorkspaceFolder(projectPath, {
+        appDataPaths,
+        workspaces: [{ id: "ws-1", path: projectPath }],
+      }),
+    ).toEqual({ path: projectPath, existingWorkspaceId: "ws-1" });
+  });
+
+  test("rejects missing paths, plain files, and the app's own data", async () => {
+    const root = await createRoot();
+    const options = { appDataPaths: [path.join(root, "userData")], workspaces: [] };
+
+    await expect(
+      resolveDroppedWorkspaceFolder(path.join(root, "missing"), options),
+    ).rejects.toThrow("no longer exists");
+    await expect(
+      resolveDroppedWorkspaceFolder(path.join(root, "notes.txt"), options),
+    ).rejects.toThrow("Only folders");
+    await expect(
+      resolveDroppedWorkspaceFolder(path.join(root, "userData", "workspaces"), options),
+    ).rejects.toThrow("app's data directory");
+  });
+
+  test("keeps only directory entries from a drop", () => {
+    const folder = new File([], "project");
+    const item = (isDirectory: boolean, file: File | null) =>
+      ({
+        kind: "file",
+        getAsFile: () => file,
+        webkitGetAsEntry: () => ({ isDirectory }),
+      }) as unknown as DataTransferItem;
+    const dataTransfer = {
+      types: ["Files"],
+      items: [
+        item(true, folder),
+        item(false, new File(["x"], "notes.txt")),
+      ] as unknown as DataTransferItemList,
+    };
+
+    expect(dataTransferHasFiles(dataTransfer)).toBe(true);
+    expect(dataTransferHasFiles({ types: ["text/plain"] })).toBe(false);
+    expect(droppedFolderFiles(dataTransfer)).toEqual([folder]);
+  });
+});
@@ -119,4 +119,95 @@ describe("desktop shutdown handler", () => {
     expect(calls.indexOf("stop:start")).toBeLessThan(calls.indexOf("error:boom"));
     expect(calls.indexOf("relay:stop")).toBeLessThan(calls.indexOf("quit"));
   });
+
+  test("runs shutdown phases in dependency order", async () => {
+    const calls: string[] = [];
+    const logged: Array<{ level: string; phase: unknown }> = [];
+    let quitResolve!: () => void;
+    const quitPromise = new Promise<void>((resolve) => {
+      quitResolve = resolve;
+    });
+
+    const beforeQuit = createBeforeQuitHandler({
+      stopWatchers: () => {
+        calls.push("watchers");
+      },
+      stopAllServers: async () => {
+        calls.push("servers");
+      },
+      persistState: async () => {
+        calls.push("state");
+      },
+      flushTranscripts: async () => {
+        calls.push("transcripts");
+      },
+      drainRunQueue: async () => {
+        calls.push("drain");
+      },
+      stopSchedulers: () => {
+        calls.push("schedulers");
+      },
+      log: (level, _message, meta) => {
+        logged.push({ level, phase: meta.phase });
+      },
+      quit: () => {
+        calls.push("quit");
+        quitResolve();
+      },
+    });
+
+    beforeQuit({ preventDefault: () => {} });
+    await quitPromise;
+
+    expect(calls).toEqual([
+      "schedulers",
+      "drain",
+      "transcripts",
+      "state",
+      "servers",
+      "watchers",
+      "quit",
+    ]);
+    expect(logged.map((entry) => entry.phase)).toEqual([
+      "stop-schedulers",
+      "drain-run-queue",
+      "flush-transcripts",
+      "persist-state",
+      "stop-sidecars",
+      "stop-watchers",
+    ]);
+    expect(logged.every((entry) => entry.level === "info")).toBe(true);
+  });
+
+  test("moves on to the next phase when one exceeds its timeout", async () => {
+    const calls: string[] = [];
+    const warnings: unknown[] = [];
+    let quitResolve!: () => void;
+    const quitPromise = new Promise<void>((resolve) => {
+      quitResolve = resolve;
+    });
+
+    const beforeQuit = createBeforeQuitHandler({
+      drainRunQueue: () => new Promise<void>(() => {}),
+      stopAllServers: async () => {
+        calls.push("servers");
+      },
+      phaseTimeoutsMs: { "drain-run-queue": 5 },
+      log: (level, message, meta) => {
+        if (level === "warn") {
+          warnings.push({ message, phase: meta.phase });
+        }
+      },
+      quit: () => {
+        calls.push("quit");
+        quitResolve();
+      },
+    });
+
+    beforeQuit({ preventDefault: () => {} });
+    await quitPromise;
+
+    expect(calls).toEqual(["servers", "quit"]);
+    expect(warnings).toEqual([{ message: "Shutdown phase timed out", phase: "drain-run-queue" }]);
+  });
 });
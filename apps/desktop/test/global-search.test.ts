@@ -0,0 +1,189 @@
+import { describe, expect, test } from "bun:test";
+
+import { globalSearch } from "../electron/services/globalSearch";
+import type { PersistedState, TranscriptEvent } from "../src/app/types";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+function workspace(id: string, name: string) {
+  return {
+    id,
+    name,
+    path: `/work/${id}`,
+    createdAt: TS,
+    lastOpenedAt: TS,
+    defaultEnableMcp: true,
+    defaultBackupsEnabled: true,
+    yolo: false,
+  };
+}
+
+function thread(id: string, workspaceId: string, title: string, lastMessageAt = TS) {
+  return {
+    id,
+    workspaceId,
+    title,
+    createdAt: TS,
+    lastMessageAt,
+    status: "active" as const,
+    sessionId: null,
+    lastEventSeq: 0,
+  };
+}
+
+function message(threadId: string, type: string, text: string, ts = TS): TranscriptEvent {
+  return { ts, threadId, direction: "server", payload: { type, sessionId: "s-1", text } };
+}
+
+const state: PersistedState = {
+  version: 2,
+  workspaces: [workspace("ws-1", "Billing"), workspace("ws-2", "Docs site")],
+  threads: [
+    thread("thread-1", "ws-1", "Fix billing rounding", "2026-01-03T00:00:00.000Z"),
+    thread("thread-2", "ws-2", "Landing page copy", "2026-01-02T00:00:00.000Z"),
+    thread("thread-orphan", "ws-gone", "Billing leftovers"),
+  ],
+};
+
+const transcripts: Record<string, TranscriptEvent[]> = {
+  "thread-1": [message("thread-1", "user_message", "Invoices are off by a cent")],
+  "thread-2": [
+    message("thread-2", "assistant_message", "Updated the billing FAQ section."),
+    message("thread-2", "tool_output", "billing"),
+  ],
+};
+
+const readTranscript = async (threadId: string) => transcripts[threadId] ?? [];
+
+describe("global search", () => {
+  test("ranks workspaces, thread titles, and transcript messages in one list", async () => {
+    const results = await globalSearch(state, { query: "Billing" }, { readTranscript });
+
+    expect(results.map((result) => [result.kind, result.title])).toEqual([
+      ["workspace", "Billing"],
+      ["thread", "Fix billing rounding"],
+      ["event", "Landing page copy"],
+    ]);
+    expect(results[2]).toMatchObject({
+      threadId: "thread-2",
+      snippet: "Updated the billing FAQ section.",
+    });
+  });
+
+  test("applies the limit and skips unreadable transcripts", async () => {
+    const results = await globalSearch(
+      state,
+      { query: "cent", limit: 1 },
+      {
+        readTranscript: async (threadId) => {
+          if (threadId === "thread-2") {
+            throw new Error("corrupt");
+          }
+          return await readTranscript(threadId);
+        },
+      },
+    );
+
+    expect(results).toEqual([expect.objectContaining({ kind: "event", threadId: "thread-1" })]);
+    expect(await globalSearch(state, { query: "   " }, { readTranscript })).toEqual([]);
+  });
+
+  test("matches annotation tags exactly and notes like titles", async () => {
+    const readAnnotations = async (threadId: string) =>
+      threadId === "thread-2"
+        ? [
+            {
+              threadId,
+              eventIndex: 1,
+              eventTs: "2026-01-02T00:00:00.000Z",
+              note: "Good summary of the rounding fix",
+              tags: ["decision"],
+              createdAt: TS,
+              updatedAt: TS,
+            },
+          ]
+        : [];
+
+    const byTag = await globalSearch(
+      state,
+      { query: "Decision" },
+      { readTranscript, readAnnotations },
+    );
+    expect(byTag).toEqual([
+      {
+        kind: "annotation",
+        score: 0.7,
+        workspaceId: "ws-2",
+        threadId: "thread-2",
+        eventIndex: 1,
+        title: "Landing page copy",
+        snippet: "Good summary of the rounding fix",
+        tags: ["decision"],
+        timestamp: "2026-01-02T00:00:00.000Z",
+      },
+    ]);
+
+    const byNote = await globalSearch(
+      state,
+      { query: "rounding" },
+      { readTranscript, readAnnotations },
+    );
+    expect(byNote.map((result) => result.kind)).toEqual(["thread", "annotation"]);
+  });
+
+  test("matches prompt templates by name and tag above message text", async () => {
+    const readPromptTemplates = async () => [
+      {
+        id: "prompt-1",
+        scope: "workspace" as const,
+        workspaceId: "ws-1",
+        name: "Rounding review",
+        description: "Checks currency math",
+        body: "Review {{file}} for rounding errors.",
+        variables: ["file"],
+        tags: ["billing"],
+        createdAt: TS,
+        updatedAt: "2026-01-03T00:00:00.000Z",
+      },
+    ];
+
+    const byTag = await globalSearch(
+      state,
+      { query: "billing" },
+      { readTranscript, readPromptTemplates },
+    );
+    expect(byTag.map((result) => result.kind)).toEqual(["workspace", "prompt", "thread", "event"]);
+    expect(byTag[1]).toEqual({
+      kind: "prompt",
+      score: 0.8,
+      templateId: "prompt-1",
+      scope: "workspace",
+      workspaceId: "ws-1",
+      title: "Rounding review",
+      snippet: "Checks currency math",
+      tags: ["billing"],
+      timestamp: "2026-01-03T00:00:00.000Z",
+    });
+
+    const byBody = await globalSearch(
+      state,
+      { query: "errors" },
+      { readTranscript, readPromptTemplates },
+    );
+    expect(byBody).toEqual([
+      expect.objectContaining({ kind: "prompt", snippet: "Review {{file}} for rounding errors." }),
+    ]);
+
+    const unreadable = await globalSearch(
+      state,
+      { query: "billing" },
+      {
+        readTranscript,
+        readPromptTemplates: async () => {
+          throw new Error("EACCES");
+        },
+      },
+    );
+    expect(unreadable.map((result) => result.kind)).toEqual(["workspace", "thread", "event"]);
+  });
+});
@@ -0,0 +1,165 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  HEADLESS_WORKSPACE_ID,
+  HeadlessArgsError,
+  type HeadlessOptions,
+  parseHeadlessArgs,
+  runHeadless,
+} from "../electron/services/headless";
+import type { TranscriptEvent, WorkspaceRecord } from "../src/app/types";
+
+function transcriptEvent(threadId: string, type: string): TranscriptEvent {
+  return { ts: "2026-01-01T00:00:00.000Z", threadId, direction: "server", payload: { type } };
+}
+
+const workspace = {
+  id: "ws-1",
+  name: "Project",
+  path: "/projects/app",
+  resourceLimits: { maxMemoryMb: 2048 },
+} as WorkspaceRecord;
+
+function createDeps(transcripts: Record<string, TranscriptEvent[]>) {
+  const lines: string[] = [];
+  const errors: string[] = [];
+  const started: unknown[] = [];
+  const stopped: string[] = [];
+  const abort = new AbortController();
+  return {
+    lines,
+    errors,
+    started,
+    stopped,
+    abort,
+    deps: {
+      loadState: async () => ({ workspaces: [workspace] }),
+      readTranscript: async (threadId: string) => transcripts[threadId] ?? [],
+      startServer: async (opts: unknown) => {
+        started.push(opts);
+        return "ws://127.0.0.1:7000/ws";
+      },
+      stopServer: async (workspaceId: string) => {
+        stopped.push(workspaceId);
+      },
+      write: (line: string) => lines.push(line),
+      writeError: (line: string) => errors.push(line),
+      signal: abort.signal,
+      cwd: "/projects",
+      pollIntervalMs: 1,
+    },
+  };
+}
+
+const options = (overrides: Partial<HeadlessOptions>): HeadlessOptions => ({
+  workspace: null,
+  threadIds: [],
+  follow: false,
+  yolo: false,
+  ...overrides,
+});
+
+describe("headless launch", () => {
+  test("parses only launches that ask for headless mode", () => {
+    expect(parseHeadlessArgs(["/projects/app"])).toBeNull();
+    expect(
+      parseHeadlessArgs([
+        "--headless",
+        "--no-sandbox",
+        "--workspace",
+        "ws-1",
+        "--tail=thread-1",
+        "--tail",
+        "thread-2",
+        "--follow",
+      ]),
+    ).toEqual({
+      workspace: "ws-1",
+      threadIds: ["thread-1", "thread-2"],
+      follow: true,
+      yolo: false,
+    });
+    expect(() => parseHeadlessArgs(["--headless"])).toThrow(HeadlessArgsError);
+    expect(() => parseHeadlessArgs(["--headless", "--tail", "../escape"])).toThrow(
+      HeadlessArgsError,
+    );
+    expect(() => parseHeadlessArgs(["--headless", "--workspace", "--follow"])).toThrow(
+      "--workspace needs a value",
+    );
+  });
+
+  test("prints a thread's transcript and exits without starting a server", async () => {
+    const { deps, lines, started } = createDeps({
+      "thread-1": [
+        transcriptEvent("thread-1", "turn_start"),
+        transcriptEvent("thread-1", "turn_end"),
+      ],
+    });
+
+    expect(await runHeadless(options({ threadIds: ["thread-1"] }), deps)).toBe(0);
+
+    expect(started).toEqual([]);
+    expect(lines.map((line) => JSON.parse(line).event.payload.type)).toEqual([
+      "turn_start",
+      "turn_end",
+    ]);
+  });
+
+  test("starts a saved workspace's server by path and stops it when interrupted", async () => {
+    const { deps, lines, started, stopped, abort } = createDeps({});
+    const run = runHeadless(options({ workspace: "app", yolo: true }), deps);
+
+    await Bun.sleep(5);
+    abort.abort();
+
+    expect(await run).toBe(0);
+    expect(started).toEqual([
+      {
+        workspaceId: "ws-1",
+        workspacePath: "/projects/app",
+        yolo: true,
+        resourceLimits: { maxMemoryMb: 2048 },
+      },
+    ]);
+    expect(JSON.parse(lines[0] ?? "")).toEqual({
+      type: "server_listening",
+      workspaceId: "ws-1",
+      url: "ws://127.0.0.1:7000/ws",
+    });
+    expect(stopped).toEqual(["ws-1"]);
+  });
+
+  test("follows new events and reports failures on stderr", async () => {
+    const events = [transcriptEvent("thread-1", "turn_start")];
+    const { deps, lines, errors, abort } = createDeps({ "thread-1": events });
+    const run = runHeadless(options({ threadIds: ["thread-1"], follow: true }), {
+      ...deps,
+      write: (line) => {
+        lines.push(line);
+        if (lines.length === 1) {
+          events.push(transcriptEvent("thread-1", "turn_end"));
+        } else {
+          abort.abort();
+        }
+      },
+    });
+
+    expect(await run).toBe(0);
+    expect(lines).toHaveLength(2);
+
+    const failing = createDeps({});
+    const exitCode = await runHeadless(options({ workspace: "/elsewhere" }), {
+      ...failing.deps,
+      startServer: async (opts) => {
+        failing.started.push(opts);
+        throw new Error("spawn failed");
+      },
+    });
+    expect(exitCode).toBe(1);
+    expect(failing.started).toEqual([
+      expect.objectContaining({ workspaceId: HEADLESS_WORKSPACE_ID, workspacePath: "/elsewhere" }),
+    ]);
+    expect(JSON.parse(failing.errors[0] ?? "")).toEqual({ type: "error", message: "spawn failed" });
+    expect(errors).toEqual([]);
+  });
+});
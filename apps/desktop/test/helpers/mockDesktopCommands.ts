@@ -136,18 +136,199 @@ export function createDesktopCommandsMock(
     isPackagedDesktopApp: () => false,
     isDesktopDemoMode: () => false,
     createOneOffChatWorkspace: async () => ({ name: "New chat", path: "/tmp/cowork-chat" }),
-    startWorkspaceServer: async () => ({ url: "ws://mock" }),
+    startWorkspaceServer: async () => ({ url: "ws://mock", authToken: null }),
+    hasYoloGrants: () => false,
+    requestYoloGrant: async () => null,
+    prewarmWorkspaceServers: async () => null,
     getWorkspaceServerStatus: async ({ workspaceId }) => ({
       workspaceId,
       running: true,
       url: "ws://mock",
       reason: "running",
     }),
+    getWorkspaceProxyUrl: async () => null,
+    generateHandoffLink: async () => {
+      throw new Error("Handoff links are unavailable in this build.");
+    },
+    revokeHandoff: async () => false,
     stopWorkspaceServer: async () => {},
+    releaseThreadServer: async () => {},
+    getServerVersion: async () => null,
+    getServerStartupDiagnostics: async () => null,
+    getServerStats: async () => null,
+    getWorkspaceHealth: async () => null,
+    listOrphanedServers: async () => [],
+    cleanupOrphanedServers: async () => ({ stopped: [], failed: [] }),
+    readWorkspaceConfig: async () => null,
+    getWorkspaceGitStatus: async () => null,
+    listWorkspaceCheckpoints: async () => null,
+    restoreWorkspaceCheckpoint: async ({
+      workspaceId,
+      checkpointId,
+    }: {
+      workspaceId: string;
+      checkpointId: string;
+    }) => ({ workspaceId, checkpointId, safetyCheckpoint: null, removedFileCount: 0 }),
+    validateProviderCredentials: async () => null,
+    getUsageSummary: async () => null,
+    getAppSettings: async () => null,
+    listProfiles: async () => null,
+    createProfile: async ({ name }: { name: string }) => ({
+      id: name.toLowerCase(),
+      name,
+      createdAt: "2026-01-01T00:00:00.000Z",
+    }),
+    switchProfile: async () => {},
+    previewTranscriptRetention: async () => null,
+    testProxyConnectivity: async ({ url }: { url: string }) => ({
+      url,
+      proxy: null,
+      ok: true,
+      httpStatus: 200,
+      latencyMs: 0,
+      message: "Reached directly",
+    }),
+    flushBufferedTranscripts: async () => ({
+      freeBytes: null,
+      minFreeBytes: 0,
+      low: false,
+      bufferedTranscriptEvents: 0,
+    }),
+    getConnectivityStatus: async () => null,
+    verifyInstallation: async () => null,
+    getOnboardingStatus: async () => null,
+    runEnvironmentChecks: async () => ({ status: "pass", checks: [], checkedAt: "" }),
+    updateAppSettings: async (patch: Record<string, unknown>) => ({
+      defaultProvider: null,
+      startupBehavior: "restoreLastThread",
+      transcriptRetentionDays: 0,
+      transcriptRetentionMaxTotalMb: 0,
+      minFreeDiskMb: 256,
+      trashRetentionDays: 30,
+      transcriptValidation: "annotate",
+      notificationsEnabled: true,
+      httpProxy: null,
+      httpsProxy: null,
+      noProxy: null,
+      caBundlePath: null,
+      themeSource: "system",
+      isolatedSessions: false,
+      maxIsolatedSidecars: 4,
+      locale: null,
+      idleServerSuspend: "onBattery",
+      idleServerTimeoutMinutes: 30,
+      yoloGrantMinutes: 60,
+      voiceTranscriptionBaseUrl: null,
+      voiceTranscriptionModel: null,
+      transcriptStore: "jsonl",
+      ...patch,
+    }),
+    writeWorkspaceConfig: async ({ workspaceId }: { workspaceId: string }) => ({
+      workspaceId,
+      path: `/tmp/${workspaceId}/.cowork/config.json`,
+      exists: true,
+      error: null,
+      config: {},
+      effective: { provider: null, model: null, enableMcp: true, yolo: false },
+    }),
+    listAvailableModels: async () => null,
+    isServerUpgradeRequiredError: () => false,
+    isNetworkUnavailableError: () => false,
+    isServerStartupFailedError: () => false,
     loadState: async () => ({ version: 2, workspaces: [], threads: [] }),
-    saveState: async () => {},
+    saveState: async () => null,
+    listStateSnapshots: async () => [],
+    restoreStateSnapshot: async () => ({ version: 2, workspaces: [], threads: [] }),
+    purgeThreads: async () => true,
+    deleteWorkspace: async () => true,
+    getWorkspaceStorageReport: async ({ workspaceId }: { workspaceId: string }) => ({
+      workspaceId,
+      threadCount: 0,
+      transcriptBytes: 0,
+      archiveBytes: 0,
+      quarantineBytes: 0,
+      totalBytes: 0,
+    }),
+    cleanupWorkspaceStorage: async () => null,
+    rebuildStateFromTranscripts: async ({ dryRun }: { dryRun: boolean }) => ({
+      dryRun,
+      applied: false,
+      snapshotName: null,
+      scannedTranscripts: 0,
+      alreadyPresent: 0,
+      workspaces: [],
+      threads: [],
+      skipped: [],
+      generatedAt: new Date(0).toISOString(),
+    }),
+    exportAppData: async () => null,
+    importAppData: async () => null,
     captureProductEvent: async () => {},
+    createScratchThread: async ({ workspaceId }) => ({
+      threadId: "scratch-mock",
+      workspaceId,
+      createdAt: "2026-06-01T00:00:00.000Z",
+    }),
+    persistScratchThread: async ({ threadId }) => ({
+      id: threadId,
+      workspaceId: "ws-mock",
+      title: "New thread",
+      titleSource: "default",
+      createdAt: "2026-06-01T00:00:00.000Z",
+      lastMessageAt: "2026-06-01T00:00:00.000Z",
+      status: "disconnected",
+      sessionId: null,
+      messageCount: 0,
+      lastEventSeq: 0,
+    }),
     readTranscript: async () => [],
+    readTranscriptPage: async () => ({ events: [], nextCursor: null, truncated: false }),
+    getThreadPreviews: async () => [],
+    archiveThread: async () => null,
+    unarchiveThread: async () => null,
+    forkThread: async () => null,
+    mergeThreads: async () => null,
+    suggestThreadTitle: async () => null,
+    copyTranscriptSelection: async () => null,
+    inspectTranscript: async () => null,
+    repairTranscript: async ({ threadId }: { threadId: string }) => ({
+      threadId,
+      quarantinedLineCount: 0,
+      quarantinePath: null,
+    }),
+    normalizeTranscriptTimestamps: async ({ threadId }: { threadId: string }) => ({
+      threadId,
+      normalizedCount: 0,
+      unparseableCount: 0,
+    }),
+    redactTranscript: async ({ threadId, dryRun }: { threadId: string; dryRun: boolean }) => ({
+      threadId,
+      dryRun,
+      redactionCount: 0,
+      redactedEventCount: 0,
+      matches: [],
+      sealedLineCount: 0,
+    }),
+    getThreadFileChanges: async () => null,
+    startReplay: async () => null,
+    stopReplay: async () => {},
+    getTranscriptEncryption: async () => ({ enabled: false, available: false }),
+    setTranscriptEncryption: async ({ enabled }: { enabled: boolean }) => ({
+      enabled,
+      available: true,
+    }),
+    encryptExistingTranscripts: async () => ({ encryptedFiles: 0, unchangedFiles: 0 }),
+    migrateTranscriptStore: async ({ target }: { target: "jsonl" | "sqlite" }) => ({
+      target,
+      migratedTranscripts: 0,
+      migratedLines: 0,
+    }),
+    openTranscriptFile: async () => {
+      throw new Error("Transcript viewer is unavailable in this build.");
+    },
+    shareTranscript: async () => {
+      throw new Error("Sharing transcripts is unavailable in this build.");
+    },
     hydrateTranscript: async () => ({
       feed: [],
       agents: [],
@@ -156,12 +337,70 @@ export function createDesktopCommandsMock(
     }),
     appendTranscriptEvent: async () => {},
     appendTranscriptBatch: async () => {},
+    subscribeTranscript: async () => {},
+    unsubscribeTranscript: async () => {},
+    onTranscriptAppended: () => () => {},
     captureTranscriptEvent: () => null,
     onTranscriptDeliveryFailure: () => () => {},
     retryTranscriptDelivery: async () => {},
     discardTranscriptBatch: async () => {},
-    deleteTranscript: async () => {},
+    deleteTranscript: async () => true,
+    setWorkspacePinned: async () => null,
+    setWorkspaceTags: async () => null,
+    setWorkspaceLaunchProfiles: async () => {
+      throw new Error("Launch profiles are unavailable in this build.");
+    },
+    setWorkspaceIdlePolicy: async () => {
+      throw new Error("Workspace idle policies are unavailable in this build.");
+    },
+    setWorkspacePathPolicy: async () => {
+      throw new Error("Workspace path policies are unavailable in this build.");
+    },
+    previewPathPolicy: async () => null,
+    duplicateWorkspace: async () => {
+      throw new Error("Workspace duplication is unavailable in this build.");
+    },
+    listTrash: async () => [],
+    restoreFromTrash: async () => {
+      throw new Error("Trash is unavailable in this build.");
+    },
+    reorderWorkspaces: async () => null,
+    globalSearch: async () => [],
+    annotateEvent: async () => null,
+    listAnnotations: async () => [],
+    saveThreadDraft: async () => null,
+    loadThreadDraft: async () => null,
+    listPromptTemplates: async () => [],
+    savePromptTemplate: async () => {
+      throw new Error("The prompt library is unavailable in this build.");
+    },
+    deletePromptTemplate: async () => {},
+    renderPrompt: async () => {
+      throw new Error("The prompt library is unavailable in this build.");
+    },
+    isPromptVariablesMissingError: () => false,
+    listScheduledTasks: async () => [],
+    createScheduledTask: async () => {
+      throw new Error("Scheduled tasks are unavailable in this build.");
+    },
+    setScheduledTaskEnabled: async () => {
+      throw new Error("Scheduled tasks are unavailable in this build.");
+    },
+    deleteScheduledTask: async () => {},
+    recordPendingApproval: async () => ({ autoApproved: false }),
+    listPendingApprovals: async () => [],
+    resolveApproval: async () => null,
+    startVoiceCapture: async () => {
+      throw new Error("Dictation is unavailable in this build.");
+    },
+    appendVoiceAudio: async () => {},
+    stopVoiceCapture: async () => null,
     pickWorkspaceDirectory: async () => null,
+    resolveDroppedWorkspaceFolder: async () => null,
+    registerRemoteWorkspace: async () => {
+      throw new Error("Remote workspaces are unavailable in this build.");
+    },
+    scanForWorkspaces: async () => ({ candidates: [], truncated: false }),
     pickDirectory: async () => null,
     showContextMenu: async () => null,
     windowMinimize: async () => {},
@@ -173,6 +412,7 @@ export function createDesktopCommandsMock(
     windowDragEnd: async () => {},
     getPlatform: async () => "linux",
     getPlatformChrome: async () => DEFAULT_PLATFORM_CHROME,
+    getBackendCapabilities: async () => null,
     showMainWindow: async () => {},
     showCanvasWindow: async () => {},
     showQuickChatWindow: async (_opts?: { threadId?: string; newThread?: boolean }) => {},
@@ -183,6 +423,7 @@ export function createDesktopCommandsMock(
     isStaleDirectoryListingError: () => false,
     watchWorkspaceDirectory: async () => false,
     unwatchWorkspaceDirectory: async () => {},
+    getWatcherStatus: async () => null,
     onWorkspaceFileChanged: () => () => {},
     onPreviewFileChanged: () => () => {},
     readFile: async () => "",
@@ -208,12 +449,19 @@ export function createDesktopCommandsMock(
     revealPath: async () => {},
     copyPath: async () => {},
     copyText: async () => {},
+    storeAttachment: async () => {
+      throw new Error("Attachment storage is unavailable in this build.");
+    },
+    getAttachment: async () => null,
+    getThumbnail: async () => null,
     createDirectory: async () => {},
     renamePath: async () => {},
     trashPath: async () => {},
     confirmAction: async () => true,
     showNotification: async () => true,
     writeRendererLog: async () => {},
+    setLogLevel: async () => {},
+    readAppLogs: async () => null,
     createDiagnosticsBundle: async () => ({
       path: "/tmp/cowork-diagnostics.json",
       createdAt: "2026-06-01T00:00:00.000Z",
@@ -230,12 +478,45 @@ export function createDesktopCommandsMock(
       url: null,
       message: "No diagnostics upload endpoint is configured. The local bundle is ready.",
     }),
+    startOperation: async ({ kind }) => ({
+      operationId: "op-mock",
+      kind,
+      status: "running",
+      progress: null,
+      result: null,
+      error: null,
+      startedAt: "2026-06-01T00:00:00.000Z",
+      finishedAt: null,
+    }),
+    listOperations: async () => [],
+    getOperation: async () => null,
+    cancelOperation: async () => false,
+    enqueueExport: async () => {
+      throw new Error("Background exports are unavailable in this build.");
+    },
+    saveBackupCredentials: async () => {
+      throw new Error("Backup sync is unavailable in this build.");
+    },
+    configureBackupSync: async () => {
+      throw new Error("Backup sync is unavailable in this build.");
+    },
+    getBackupSyncConfig: async () => null,
+    syncBackupNow: async () => {
+      throw new Error("Backup sync is unavailable in this build.");
+    },
     getTelemetryStatus: async () => DEFAULT_TELEMETRY_STATUS,
     getUpdateState: async () => DEFAULT_UPDATE_STATE,
     checkForUpdates: async () => {},
     quitAndInstallUpdate: async () => {},
+    getSidecarUpdateState: async () => null,
+    applySidecarUpdate: async () => {
+      throw new Error("Server updates are unavailable in this build.");
+    },
     getSystemAppearance: async () => DEFAULT_SYSTEM_APPEARANCE,
     setWindowAppearance: async () => DEFAULT_SYSTEM_APPEARANCE,
+    setLocale: async () => {
+      throw new Error("Changing the app language is unavailable in this build.");
+    },
     startMobileRelay: async () => DEFAULT_MOBILE_RELAY_STATE,
     stopMobileRelay: async () => DEFAULT_MOBILE_RELAY_STATE,
     getMobileRelayState: async () => DEFAULT_MOBILE_RELAY_STATE,
@@ -246,9 +527,29 @@ export function createDesktopCommandsMock(
     onSystemAppearanceChanged: () => () => {},
     onUpdateStateChanged: () => () => {},
     onWorkspaceServerStartupProgress: () => () => {},
+    onWorkspaceServerPrewarmProgress: () => () => {},
     onWorkspaceServerExited: () => () => {},
+    onServerSuspended: () => () => {},
+    onServerResumed: () => () => {},
+    onServerReloaded: () => () => {},
+    onWorkspaceProxyConnectionChanged: () => () => {},
+    onOperationUpdated: () => () => {},
+    onExportProgress: () => () => {},
+    onExportComplete: () => () => {},
+    onServerStats: () => () => {},
+    onWorkspaceConfigChanged: () => () => {},
+    onWorkspaceGitChanged: () => () => {},
+    onAppSettingsChanged: () => () => {},
+    onTranscriptRetentionCompleted: () => () => {},
+    onLowDiskSpace: () => () => {},
+    onTranscriptThrottled: () => () => {},
+    onVoiceTranscript: () => () => {},
+    onConnectivityChanged: () => () => {},
+    onScheduledTaskRunCompleted: () => () => {},
+    onSidecarUpdateStateChanged: () => () => {},
     onWindowCloseRequested: () => () => {},
     onMenuCommand: () => () => {},
+    onDeepLinkNavigation: () => () => {},
     onMobileRelayStateChanged: () => () => {},
     ...overrides,
   };
@@ -287,10 +588,12 @@ export function createDesktopCommandsBridgeMock(): DesktopCommandsModule {
         fallback.stopWorkspaceServer(options)),
     loadState: async () => await (getActiveDesktopApi()?.loadState() ?? fallback.loadState()),
     saveState: async (state) =>
-      await (getActiveDesktopApi()?.saveState(state) ?? fallback.saveState(state)),
-    deleteTranscript: async (options) =>
+      (await (getActiveDesktopApi()?.saveState(state) ?? fallback.saveState(state))) ?? null,
+    deleteTranscript: async (options, confirm) => {
       await (getActiveDesktopApi()?.deleteTranscript(options) ??
-        fallback.deleteTranscript(options)),
+        fallback.deleteTranscript(options, confirm));
+      return true;
+    },
     getUpdateState: async () =>
       await (getActiveDesktopApi()?.getUpdateState() ?? fallback.getUpdateState()),
     onWorkspaceServerExited: (listener) =>
@@ -94,6 +94,20 @@ const defaultNativeTheme = {
   off() {},
 };
 
+const defaultSystemPreferences = {
+  getAccentColor: () => "0a84ffff",
+  on() {},
+  off() {},
+  subscribeNotification: () => 0,
+  unsubscribeNotification() {},
+};
+
+const defaultSafeStorage = {
+  isEncryptionAvailable: () => false,
+  encryptString: (plainText: string) => Buffer.from(plainText, "utf8"),
+  decryptString: (encrypted: Buffer) => encrypted.toString("utf8"),
+};
+
 class DefaultTray {
   setToolTip() {}
 
@@ -164,6 +178,12 @@ const electronMock = {
   get shell() {
     return mergeMock(defaultShell, "shell");
   },
+  get safeStorage() {
+    return mergeMock(defaultSafeStorage, "safeStorage");
+  },
+  get systemPreferences() {
+    return mergeMock(defaultSystemPreferences, "systemPreferences");
+  },
 };
 
 export function createElectronMock(overrides?: ElectronMockShape) {
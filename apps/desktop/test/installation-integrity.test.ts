@@ -0,0 +1,109 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import { createHash } from "node:crypto";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { verifyInstallation } from "../electron/services/installationIntegrity";
+import {
+  findPackagedSidecarLaunchCommand,
+  resolvePackagedSidecarFilename,
+  SIDECAR_MANIFEST_NAME,
+  SidecarIntegrityError,
+  verifySidecarLaunchCommand,
+} from "../electron/services/sidecar";
+
+const BINARY_NAME = resolvePackagedSidecarFilename("linux", "x64");
+
+function sha256(content: string): string {
+  return createHash("sha256").update(content).digest("hex");
+}
+
+describe("installation integrity", () => {
+  let dir = "";
+
+  const writeBundle = async (digests?: Record<string, string>) => {
+    await fs.writeFile(path.join(dir, BINARY_NAME), "server build");
+    await fs.writeFile(
+      path.join(dir, SIDECAR_MANIFEST_NAME),
+      JSON.stringify({
+        targetTriple: "x86_64-unknown-linux-gnu",
+        platform: "linux",
+        arch: "x64",
+        launch: { kind: "executable", path: BINARY_NAME },
+        ...(digests ? { sha256: digests } : {}),
+      }),
+    );
+  };
+
+  const findLaunch = () =>
+    findPackagedSidecarLaunchCommand([dir], { platform: "linux", arch: "x64" });
+
+  beforeEach(async () => {
+    dir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-installation-integrity-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(dir, { recursive: true, force: true });
+  });
+
+  test("verifies the sidecar against the digest in its manifest before launch", async () => {
+    await writeBundle({ [BINARY_NAME]: sha256("server build") });
+    const launch = findLaunch();
+    expect(launch.integrity).toEqual([
+      { path: path.join(dir, BINARY_NAME), sha256: sha256("server build") },
+    ]);
+    await verifySidecarLaunchCommand(launch, { requireDigests: true });
+
+    await fs.writeFile(path.join(dir, BINARY_NAME), "tampered build");
+    const rejected = verifySidecarLaunchCommand(launch, { requireDigests: true });
+    await expect(rejected).rejects.toBeInstanceOf(SidecarIntegrityError);
+    await expect(rejected).rejects.toThrow("failed integrity verification");
+  });
+
+  test("refuses a sidecar without digests only when they are required", async () => {
+    await writeBundle();
+    const launch = findLaunch();
+    await verifySidecarLaunchCommand(launch, { requireDigests: false });
+    await expect(verifySidecarLaunchCommand(launch, { requireDigests: true })).rejects.toThrow(
+      "has no expected digests",
+    );
+
+    await writeBundle({ "some-other-file": sha256("server build") });
+    expect(findLaunch).toThrow("Bundled sidecar manifest is invalid");
+  });
+
+  test("reports each bundled resource and an overall status", async () => {
+    const options = {
+      searchDirs: [dir],
+      isPackaged: true,
+      platform: "linux" as const,
+      arch: "x64",
+    };
+    await writeBundle({ [BINARY_NAME]: sha256("server build") });
+    expect(await verifyInstallation(options)).toMatchObject({
+      status: "ok",
+      packaged: true,
+      resources: [{ name: BINARY_NAME, status: "verified", actualSha256: sha256("server build") }],
+    });
+
+    await fs.writeFile(path.join(dir, BINARY_NAME), "tampered build");
+    expect(await verifyInstallation(options)).toMatchObject({
+      status: "failed",
+      resources: [{ name: BINARY_NAME, status: "mismatch" }],
+    });
+
+    await writeBundle();
+    expect(await verifyInstallation(options)).toMatchObject({
+      status: "unverified",
+      resources: [{ name: BINARY_NAME, status: "unverified", expectedSha256: null }],
+    });
+
+    await fs.rm(path.join(dir, SIDECAR_MANIFEST_NAME));
+    await fs.rm(path.join(dir, BINARY_NAME));
+    expect(await verifyInstallation(options)).toMatchObject({
+      status: "failed",
+      resources: [{ name: "cowork-server", path: null, status: "missing" }],
+    });
+  });
+});
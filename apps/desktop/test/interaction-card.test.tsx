@@ -67,5 +67,22 @@ describe("InteractionCard", () => {
     expect(html).toContain("Re-run with full access?");
     expect(html).toContain("Keep blocked");
     expect(html).toContain("Run with full access");
+    expect(html).not.toContain("Always allow");
+  });
+
+  test("offers to remember manual approvals unless the command is dangerous", () => {
+    const approval: ChatInteraction = {
+      kind: "approval",
+      approvalKind: "manual",
+      requestId: "approval-2",
+      receivedSequence: 3,
+      status: "pending",
+      command: "npm test",
+      dangerous: false,
+      reasonCode: "requires_manual_review",
+    };
+
+    expect(renderInteraction(approval)).toContain("Always allow");
+    expect(renderInteraction({ ...approval, dangerous: true })).not.toContain("Always allow");
   });
 });
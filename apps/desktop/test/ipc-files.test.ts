@@ -482,6 +482,103 @@ describe("files IPC", () => {
     await fs.rm(sourceDir, { recursive: true, force: true });
   });
 
+  test("storeAttachment stores picked files once and records a reference in the transcript", async () => {
+    const registerFilesIpc = await loadRegisterFilesIpc();
+    const { AttachmentStore } = await import("../electron/services/attachmentStore");
+    const dataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-attachments-"));
+    const sourcePath = path.join(dataDir, "notes.txt");
+    await fs.writeFile(sourcePath, "attachment payload", "utf-8");
+    const appended: unknown[] = [];
+
+    const handlers = new Map<
+      string,
+      (event: unknown, args?: unknown) => Promise<unknown> | unknown
+    >();
+    registerFilesIpc(
+      {
+        deps: {
+          attachments: new AttachmentStore({ directory: path.join(dataDir, "attachments") }),
+        } as never,
+        workspaceRoots: {
+          async ensureApprovedWorkspaceRoots() {},
+          async refreshApprovedWorkspaceRootsFromState() {},
+          async assertApprovedWorkspacePath(workspacePath: string) {
+            return workspacePath;
+          },
+          async addApprovedWorkspacePath(workspacePath: string) {
+            return workspacePath;
+          },
+          setApprovedWorkspaceRoots() {},
+          getApprovedWorkspaceRoots() {
+            return [];
+          },
+        },
+        handleDesktopInvoke(channel, handler) {
+          handlers.set(channel, handler as never);
+        },
+        parseWithSchema(schema, value, label) {
+          const parsed = schema.safeParse(value);
+          if (parsed.success) {
+            return parsed.data as never;
+          }
+          throw new Error(`${label} ${parsed.error.issues[0]?.message ?? "is invalid"}`);
+        },
+      },
+      async (events) => {
+        appended.push(...events);
+      },
+    );
+    const storeHandler = handlers.get(DESKTOP_IPC_CHANNELS.storeAttachment);
+    expect(storeHandler).toBeDefined();
+
+    await expect(
+      storeHandler?.({ sender: {} }, { threadId: "thread-1", sourcePath }),
+    ).rejects.toThrow("Upload source path is not authorized");
+
+    await handlers.get(DESKTOP_IPC_CHANNELS.authorizeUploadSource)?.(
+      { sender: {} },
+      { sourcePath },
+    );
+    const stored = (await storeHandler?.(
+      { sender: {} },
+      { threadId: "thread-1", sourcePath },
+    )) as { hash: string };
+    const pasted = await storeHandler?.(
+      { sender: {} },
+      { threadId: "thread-2", bytes: Buffer.from("attachment payload"), filename: "paste.txt" },
+    );
+
+    expect(stored).toMatchObject({
+      filename: "notes.txt",
+      mimeType: "text/plain",
+      sizeBytes: 18,
+      deduplicated: false,
+    });
+    expect(pasted).toMatchObject({ hash: stored.hash, filename: "paste.txt", deduplicated: true });
+    expect(appended).toEqual([
+      expect.objectContaining({
+        threadId: "thread-1",
+        direction: "client",
+        payload: {
+          type: "attachment",
+          hash: stored.hash,
+          filename: "notes.txt",
+          mimeType: "text/plain",
+          sizeBytes: 18,
+        },
+      }),
+      expect.objectContaining({ threadId: "thread-2" }),
+    ]);
+
+    const contents = (await handlers.get(DESKTOP_IPC_CHANNELS.getAttachment)?.(
+      { sender: {} },
+      { hash: stored.hash },
+    )) as { bytes: Uint8Array };
+    expect(Buffer.from(contents.bytes).toString("utf-8")).toBe("attachment payload");
+
+    await fs.rm(dataDir, { recursive: true, force: true });
+  });
+
   test("copyFileToWorkspaceUploads scopes authorized source paths to the sender and consumes them", async () => {
     const registerFilesIpc = await loadRegisterFilesIpc();
     const tempWorkspaceRaw = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-upload-owner-ws-"));
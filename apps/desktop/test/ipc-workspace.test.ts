@@ -1,5 +1,6 @@
 import { beforeEach, describe, expect, mock, test } from "bun:test";
 
+import { YoloGrants } from "../electron/services/yoloGrants";
 import { DESKTOP_EVENT_CHANNELS, DESKTOP_IPC_CHANNELS } from "../src/lib/desktopApi";
 import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
 
@@ -42,7 +43,11 @@ describe("workspace IPC", () => {
     registerWorkspaceIpc({
       deps: {
         mobileRelayBridge: { isActiveForWorkspace: () => true },
-        persistence: {},
+        persistence: {
+          async loadState() {
+            return { workspaces: [{ id: "ws-1", resourceLimits: { maxMemoryMb: 2048 } }] };
+          },
+        },
         serverManager: {
           async startWorkspaceServer(opts: unknown) {
             managerStartOptions = opts;
@@ -133,6 +138,7 @@ describe("workspace IPC", () => {
       yolo: false,
       forceRestart: true,
       mobileH3: true,
+      resourceLimits: { maxMemoryMb: 2048 },
       privacyTelemetrySettings: {
         aiTraceTelemetryEnabled: true,
         aiTracePayloadsEnabled: false,
@@ -165,6 +171,106 @@ describe("workspace IPC", () => {
     expect(managerStatusWorkspaceId).toBe("ws-1");
   });
 
+  test("prewarmWorkspaceServers starts workspaces with bounded parallelism", async () => {
+    const handlers = new Map<
+      string,
+      (event: unknown, args?: unknown) => Promise<unknown> | unknown
+    >();
+    const sentEvents: Array<{ channel: string; payload: unknown }> = [];
+    const releases: Array<() => void> = [];
+    let inFlight = 0;
+    let maxInFlight = 0;
+
+    registerWorkspaceIpc({
+      deps: {
+        mobileRelayBridge: { isActiveForWorkspace: () => false },
+        persistence: {
+          async loadState() {
+            return {
+              workspaces: ["ws-1", "ws-2", "ws-3", "ws-bad"].map((id) => ({
+                id,
+                path: `/tmp/${id}`,
+                yolo: id === "ws-2",
+              })),
+            };
+          },
+        },
+        serverManager: {
+          async startWorkspaceServer(opts: { workspaceId: string; yolo: boolean }) {
+            inFlight += 1;
+            maxInFlight = Math.max(maxInFlight, inFlight);
+            await new Promise<void>((resolve) => releases.push(resolve));
+            inFlight -= 1;
+            if (opts.workspaceId === "ws-bad") {
+              throw new Error("Server exited before startup JSON (code=1, signal=null)");
+            }
+            return { url: `ws://127.0.0.1/${opts.workspaceId}${opts.yolo ? "?yolo" : ""}` };
+          },
+        },
+        updater: {} as never,
+      } as never,
+      workspaceRoots: {
+        async ensureApprovedWorkspaceRoots() {},
+        async refreshApprovedWorkspaceRootsFromState() {},
+        async assertApprovedWorkspacePath(workspacePath: string) {
+          return workspacePath;
+        },
+        async addApprovedWorkspacePath(workspacePath: string) {
+          return workspacePath;
+        },
+        setApprovedWorkspaceRoots() {},
+        getApprovedWorkspaceRoots() {
+          return [];
+        },
+      },
+      handleDesktopInvoke(channel, handler) {
+        handlers.set(channel, handler as never);
+      },
+      parseWithSchema(_schema, value) {
+        return value as never;
+      },
+    });
+
+    const prewarmHandler = handlers.get(DESKTOP_IPC_CHANNELS.prewarmWorkspaceServers);
+    const resultPromise = prewarmHandler?.(
+      {
+        sender: {
+          isDestroyed: () => false,
+          send: (channel: string, payload: unknown) => sentEvents.push({ channel, payload }),
+        },
+      },
+      { workspaceIds: ["ws-1", "ws-2", "ws-bad", "ws-missing", "ws-1"], concurrency: 2 },
+    );
+    let settled = false;
+    void Promise.resolve(resultPromise).then(() => {
+      settled = true;
+    });
+    while (!settled) {
+      await Bun.sleep(1);
+      releases.shift()?.();
+    }
+
+    await expect(resultPromise).resolves.toEqual({
+      results: {
+        "ws-1": { status: "ready", url: "ws://127.0.0.1/ws-1" },
+        "ws-2": { status: "ready", url: "ws://127.0.0.1/ws-2?yolo" },
+        "ws-bad": {
+          status: "failed",
+          error: "Server exited before startup JSON (code=1, signal=null)",
+        },
+        "ws-missing": { status: "failed", error: "Unknown workspace: ws-missing" },
+      },
+    });
+    expect(maxInFlight).toBe(2);
+    const progress = sentEvents
+      .filter((event) => event.channel === DESKTOP_EVENT_CHANNELS.workspaceServerPrewarmProgress)
+      .map((event) => event.payload as { workspaceId: string; status: string });
+    expect(progress.filter((event) => event.status === "queued")).toHaveLength(4);
+    expect(
+      progress.filter((event) => event.workspaceId === "ws-bad").map((event) => event.status),
+    ).toEqual(["queued", "starting", "failed"]);
+  });
+
   test("updates approved roots after saving workspace state", async () => {
     const handlers = new Map<
       string,
@@ -1212,4 +1318,321 @@ describe("workspace IPC", () => {
       "ws-main",
     ]);
   });
+
+  test("scratch threads stay in memory until promoted and are dropped with their window", async () => {
+    const handlers = new Map<
+      string,
+      (event: unknown, args?: unknown) => Promise<unknown> | unknown
+    >();
+    const appendedBatches: unknown[][] = [];
+    const savedStates: Array<{ threads: Array<{ id: string }> }> = [];
+    let destroyListener: (() => void) | null = null;
+    const workspace = {
+      id: "ws-1",
+      name: "Workspace",
+      path: "/tmp/ws-1",
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastOpenedAt: "2026-01-01T00:00:00.000Z",
+      yolo: false,
+    };
+
+    registerWorkspaceIpc({
+      deps: {
+        mobileRelayBridge: { isActiveForWorkspace: () => false },
+        persistence: {
+          async loadState() {
+            return { version: 2, workspaces: [workspace], threads: [] };
+          },
+          async saveState(state: { threads: Array<{ id: string }> }) {
+            savedStates.push(state);
+          },
+          async readTranscript() {
+            throw new Error("scratch transcripts must not hit disk");
+          },
+          async appendTranscriptBatch(events: unknown[]) {
+            appendedBatches.push(events);
+          },
+          async deleteTranscript() {},
+        },
+        serverManager: {},
+        updater: {} as never,
+      } as never,
+      workspaceRoots: {
+        async ensureApprovedWorkspaceRoots() {},
+        async refreshApprovedWorkspaceRootsFromState() {},
+        async assertApprovedWorkspacePath(workspacePath: string) {
+          return workspacePath;
+        },
+        async addApprovedWorkspacePath(workspacePath: string) {
+          return workspacePath;
+        },
+        setApprovedWorkspaceRoots() {},
+        getApprovedWorkspaceRoots() {
+          return [];
+        },
+      },
+      handleDesktopInvoke(channel, handler) {
+        handlers.set(channel, handler as never);
+      },
+      parseWithSchema(_schema, value) {
+        return value as never;
+      },
+    });
+
+    const windowEvent = {
+      sender: {
+        id: 7,
+        once(_event: string, listener: () => void) {
+          destroyListener = listener;
+        },
+      },
+    };
+    const create = handlers.get(DESKTOP_IPC_CHANNELS.createScratchThread);
+    const first = (await create?.(windowEvent, { workspaceId: "ws-1" })) as { threadId: string };
+    const second = (await create?.(windowEvent, { workspaceId: "ws-1" })) as { threadId: string };
+    await expect(create?.(windowEvent, { workspaceId: "ws-missing" })).rejects.toThrow(
+      "Unknown workspace: ws-missing",
+    );
+
+    const event = {
+      ts: "2026-01-02T00:00:00.000Z",
+      threadId: first.threadId,
+      direction: "client",
+      payload: { text: "hi" },
+    };
+    await handlers.get(DESKTOP_IPC_CHANNELS.appendTranscriptBatch)?.(windowEvent, [event]);
+    expect(appendedBatches).toEqual([[]]);
+    expect(
+      await handlers.get(DESKTOP_IPC_CHANNELS.readTranscript)?.(windowEvent, {
+        threadId: first.threadId,
+      }),
+    ).toEqual([event]);
+
+    await handlers.get(DESKTOP_IPC_CHANNELS.saveState)?.(windowEvent, {
+      version: 2,
+      workspaces: [workspace],
+      threads: [{ id: second.threadId, workspaceId: "ws-1" }],
+    });
+    expect(savedStates.at(-1)?.threads).toEqual([]);
+
+    await expect(
+      handlers.get(DESKTOP_IPC_CHANNELS.persistScratchThread)?.(
+        { sender: { id: 8 } },
+        { threadId: first.threadId },
+      ),
+    ).rejects.toThrow("Scratch thread belongs to another window");
+
+    const record = await handlers.get(DESKTOP_IPC_CHANNELS.persistScratchThread)?.(windowEvent, {
+      threadId: first.threadId,
+      title: "Quick question",
+    });
+    expect(record).toMatchObject({
+      id: first.threadId,
+      workspaceId: "ws-1",
+      title: "Quick question",
+      titleSource: "manual",
+      lastMessageAt: "2026-01-02T00:00:00.000Z",
+    });
+    expect(appendedBatches.at(-1)).toEqual([event]);
+    expect(savedStates.at(-1)?.threads.map((thread) => thread.id)).toEqual([first.threadId]);
+
+    (destroyListener as (() => void) | null)?.();
+    await expect(
+      handlers.get(DESKTOP_IPC_CHANNELS.persistScratchThread)?.(windowEvent, {
+        threadId: second.threadId,
+      }),
+    ).rejects.toThrow(`Unknown scratch thread: ${second.threadId}`);
+  });
+
+  test("deleteTranscript only runs with a token issued for that thread and window", async () => {
+    const handlers = new Map<
+      string,
+      (event: unknown, args?: unknown) => Promise<unknown> | unknown
+    >();
+    const deleted: string[] = [];
+
+    registerWorkspaceIpc({
+      deps: {
+        persistence: {
+          async getTranscriptStats() {
+            return { fileCount: 1, totalBytes: 2048, largestBytes: 2048 };
+          },
+          async trashTranscript(threadId: string) {
+            deleted.push(threadId);
+          },
+        },
+      } as never,
+      workspaceRoots: {} as never,
+      handleDesktopInvoke(channel, handler) {
+        handlers.set(channel, handler as never);
+      },
+      parseWithSchema(_schema, value) {
+        return value as never;
+      },
+    });
+
+    const deleteTranscript = handlers.get(DESKTOP_IPC_CHANNELS.deleteTranscript);
+    const mainWindow = { sender: { id: 1 } };
+    const challengeResult = (await deleteTranscript?.(mainWindow, { threadId: "thread-1" })) as {
+      status: string;
+      challenge: { token: string; impact: unknown; summary: string };
+    };
+
+    expect(challengeResult.status).toBe("confirmationRequired");
+    expect(challengeResult.challenge.impact).toEqual({
+      workspaceCount: 0,
+      threadCount: 0,
+      transcriptCount: 1,
+      transcriptBytes: 2048,
+    });
+    expect(challengeResult.challenge.summary).toContain("2.0 KB");
+    expect(deleted).toEqual([]);
+
+    const { token } = challengeResult.challenge;
+    await expect(
+      deleteTranscript?.({ sender: { id: 2 } }, { threadId: "thread-1", confirmationToken: token }),
+    ).rejects.toThrow("Invalid confirmation token");
+    await expect(
+      deleteTranscript?.(mainWindow, { threadId: "thread-1", confirmationToken: token }),
+    ).rejects.toThrow("Invalid confirmation token");
+    expect(deleted).toEqual([]);
+
+    const retry = (await deleteTranscript?.(mainWindow, { threadId: "thread-1" })) as {
+      challenge: { token: string };
+    };
+    await expect(
+      deleteTranscript?.(mainWindow, {
+        threadId: "thread-2",
+        confirmationToken: retry.challenge.token,
+      }),
+    ).rejects.toThrow("Invalid confirmation token");
+
+    const confirmed = (await deleteTranscript?.(mainWindow, { threadId: "thread-1" })) as {
+      challenge: { token: string };
+    };
+    await expect(
+      deleteTranscript?.(mainWindow, {
+        threadId: "thread-1",
+        confirmationToken: confirmed.challenge.token,
+      }),
+    ).resolves.toEqual({ status: "completed" });
+    expect(deleted).toEqual(["thread-1"]);
+  });
+
+  test("requestYoloGrant issues a grant only after the user confirms in main", async () => {
+    const handlers = new Map<
+      string,
+      (event: unknown, args?: unknown) => Promise<unknown> | unknown
+    >();
+    const answers = ["Cancel", "Enable"];
+    const prompts: string[] = [];
+    setElectronMockOverrides({
+      ...electronMockOverrides,
+      dialog: {
+        ...electronMockOverrides.dialog,
+        async showMessageBox(options: { message: string; buttons: string[] }) {
+          prompts.push(options.message);
+          return { response: options.buttons.indexOf(answers.shift() ?? "Cancel") };
+        },
+      },
+    });
+
+    registerWorkspaceIpc({
+      deps: {
+        persistence: {
+          async loadState() {
+            return { workspaces: [{ id: "ws-1", name: "Project" }] };
+          },
+        },
+        yoloGrants: new YoloGrants({
+          getDurationMs: async () => 60_000,
+          createToken: () => "token-1",
+        }),
+      } as never,
+      workspaceRoots: {} as never,
+      handleDesktopInvoke(channel, handler) {
+        handlers.set(channel, handler as never);
+      },
+      parseWithSchema(_schema, value) {
+        return value as never;
+      },
+    });
+
+    const requestYoloGrant = handlers.get(DESKTOP_IPC_CHANNELS.requestYoloGrant);
+    const mainWindow = { sender: { id: 1 } };
+    expect(await requestYoloGrant?.(mainWindow, { workspaceId: "ws-1" })).toBeNull();
+    expect(await requestYoloGrant?.(mainWindow, { workspaceId: "ws-1" })).toMatchObject({
+      workspaceId: "ws-1",
+      token: "token-1",
+    });
+    expect(prompts).toEqual(Array(2).fill('Enable YOLO mode for "Project"?'));
+  });
+
+  test("deleteWorkspace stops the server only after a valid confirmation", async () => {
+    const handlers = new Map<
+      string,
+      (event: unknown, args?: unknown) => Promise<unknown> | unknown
+    >();
+    const calls: string[] = [];
+    const remainingState = { version: 2, workspaces: [], threads: [] };
+
+    registerWorkspaceIpc({
+      deps: {
+        persistence: {
+          async measureRecordRemoval(target: unknown) {
+            calls.push(`measure:${JSON.stringify(target)}`);
+            return { workspaceCount: 1, threadCount: 3, transcriptCount: 2, transcriptBytes: 10 };
+          },
+          async loadState() {
+            return remainingState;
+          },
+          async removeRecords(target: unknown) {
+            calls.push(`remove:${JSON.stringify(target)}`);
+            return remainingState;
+          },
+        },
+        serverManager: {
+          async stopWorkspaceServer(workspaceId: string) {
+            calls.push(`stop:${workspaceId}`);
+          },
+        },
+        applyPersistedState() {
+          calls.push("apply");
+        },
+      } as never,
+      workspaceRoots: {
+        setApprovedWorkspaceRoots() {
+          calls.push("roots");
+        },
+      } as never,
+      handleDesktopInvoke(channel, handler) {
+        handlers.set(channel, handler as never);
+      },
+      parseWithSchema(_schema, value) {
+        return value as never;
+      },
+    });
+
+    const deleteWorkspace = handlers.get(DESKTOP_IPC_CHANNELS.deleteWorkspace);
+    const event = { sender: { id: 7 } };
+    const first = (await deleteWorkspace?.(event, { workspaceId: "ws-1" })) as {
+      challenge: { token: string; summary: string };
+    };
+    expect(first.challenge.summary).toContain("its 3 thread(s)");
+
+    await expect(
+      deleteWorkspace?.(event, { workspaceId: "ws-1", confirmationToken: "forged" }),
+    ).rejects.toThrow("Invalid confirmation token");
+    expect(calls).toEqual(['measure:{"workspaceIds":["ws-1"]}']);
+
+    await expect(
+      deleteWorkspace?.(event, { workspaceId: "ws-1", confirmationToken: first.challenge.token }),
+    ).resolves.toEqual({ status: "completed" });
+    expect(calls.slice(1)).toEqual([
+      "stop:ws-1",
+      'remove:{"workspaceIds":["ws-1"]}',
+      "roots",
+      "apply",
+    ]);
+  });
 });
@@ -0,0 +1,80 @@
+import { describe, expect, test } from "bun:test";
+
+import { IsolatedSessionPool, SidecarLimitError } from "../electron/services/isolatedSessions";
+
+function createPool(max: number) {
+  const stopped: string[] = [];
+  let clock = 0;
+  const pool = new IsolatedSessionPool({
+    getMaxSidecars: async () => max,
+    stopServer: async (serverId) => {
+      stopped.push(serverId);
+    },
+    now: () => ++clock,
+  });
+  return { pool, stopped };
+}
+
+describe("isolated session pool", () => {
+  test("gives each thread its own server and hands it back on repeat starts", async () => {
+    const { pool } = createPool(4);
+
+    const first = await pool.acquire("ws", "thread-a");
+    const second = await pool.acquire("ws", "thread-b");
+
+    expect(first).toEqual({ serverId: "ws--iso1", reused: false });
+    expect(second).toEqual({ serverId: "ws--iso2", reused: false });
+    expect(await pool.acquire("ws", "thread-a")).toEqual({ serverId: "ws--iso1", reused: true });
+    expect(pool.resolve("ws--iso2")).toEqual({ workspaceId: "ws", threadId: "thread-b" });
+    expect(pool.resolve("ws")).toBeNull();
+  });
+
+  test("reuses a released server in the same workspace before starting another", async () => {
+    const { pool, stopped } = createPool(4);
+    await pool.acquire("ws", "thread-a");
+    pool.release("ws", "thread-a");
+
+    expect(await pool.acquire("other", "thread-b")).toEqual({
+      serverId: "other--iso1",
+      reused: false,
+    });
+    expect(await pool.acquire("ws", "thread-c")).toEqual({ serverId: "ws--iso1", reused: true });
+    expect(stopped).toEqual([]);
+  });
+
+  test("stops the least recently used idle server once the cap is reached", async () => {
+    const { pool, stopped } = createPool(2);
+    await pool.acquire("ws", "thread-a");
+    await pool.acquire("other", "thread-b");
+    pool.release("ws", "thread-a");
+    pool.release("other", "thread-b");
+
+    const lease = await pool.acquire("third", "thread-c");
+
+    expect(stopped).toEqual(["ws--iso1"]);
+    expect(lease).toEqual({ serverId: "third--iso1", reused: false });
+    expect(pool.resolve("ws--iso1")).toBeNull();
+  });
+
+  test("rejects a new thread when every server is busy", async () => {
+    const { pool } = createPool(1);
+    await pool.acquire("ws", "thread-a");
+
+    const error = await pool.acquire("ws", "thread-b").catch((caught: unknown) => caught);
+
+    expect(error).toBeInstanceOf(SidecarLimitError);
+    expect((error as SidecarLimitError).code).toBe("sidecar_limit_reached");
+    pool.forget("ws--iso1");
+    expect(await pool.acquire("ws", "thread-b")).toEqual({ serverId: "ws--iso1", reused: false });
+  });
+
+  test("drains every server of a removed workspace", async () => {
+    const { pool } = createPool(4);
+    await pool.acquire("ws", "thread-a");
+    await pool.acquire("ws", "thread-b");
+    await pool.acquire("other", "thread-c");
+
+    expect(pool.drainWorkspace("ws")).toEqual(["ws--iso1", "ws--iso2"]);
+    expect(pool.resolve("other--iso1")).toEqual({ workspaceId: "other", threadId: "thread-c" });
+  });
+});
@@ -0,0 +1,98 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { appLogsResultSchema } from "../src/lib/desktopSchemas";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+setElectronMockOverrides({
+  app: {
+    getPath: (name: string) => (name === "userData" ? userDataDir : process.cwd()),
+  },
+});
+
+mock.module("electron", () => createElectronMock());
+
+const {
+  flushLocalLogWrites,
+  LOG_RETENTION_DAYS,
+  logInfo,
+  logWarn,
+  readLocalLogs,
+  setLocalLogLevel,
+  writeLocalLog,
+} = await import("../electron/services/localLogs");
+
+function dayKey(daysAgo: number): string {
+  const date = new Date(Date.now() - daysAgo * 24 * 60 * 60_000);
+  const month = String(date.getMonth() + 1).padStart(2, "0");
+  const day = String(date.getDate()).padStart(2, "0");
+  return `${date.getFullYear()}-${month}-${day}`;
+}
+
+beforeEach(async () => {
+  userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-local-logs-"));
+});
+
+afterEach(async () => {
+  setLocalLogLevel("info");
+  await flushLocalLogWrites();
+  await fs.rm(userDataDir, { recursive: true, force: true });
+});
+
+describe("local logs", () => {
+  test("drops entries below the runtime level and filters on read", async () => {
+    logInfo("startup", "kept at info");
+    setLocalLogLevel("warn");
+    logInfo("startup", "dropped at warn");
+    logWarn("startup", "kept at warn");
+    writeLocalLog("desktop-main.log", "error", "startup", "kept error");
+
+    const all = await readLocalLogs();
+    const errorsOnly = await readLocalLogs({ levelFilter: "error" });
+    const newest = await readLocalLogs({ tail: 2 });
+
+    expect(appLogsResultSchema.safeParse(all).success).toBe(true);
+    expect(all.level).toBe("warn");
+    expect(all.entries.map((entry) => entry.message)).toEqual([
+      "kept at info",
+      "kept at warn",
+      "kept error",
+    ]);
+    expect(errorsOnly.entries.map((entry) => entry.level)).toEqual(["error"]);
+    expect(newest.entries.map((entry) => entry.message)).toEqual(["kept at warn", "kept error"]);
+    expect(newest.truncated).toBe(true);
+  });
+
+  test("rotates a previous day's log and keeps a week of rotated files", async () => {
+    const logsDir = path.join(userDataDir, "logs");
+    await fs.mkdir(logsDir, { recursive: true });
+    for (let daysAgo = 2; daysAgo < LOG_RETENTION_DAYS + 4; daysAgo += 1) {
+      await fs.writeFile(path.join(logsDir, `updater.${dayKey(daysAgo)}.log`), "", "utf8");
+    }
+    const livePath = path.join(logsDir, "updater.log");
+    await fs.writeFile(
+      livePath,
+      `${JSON.stringify({ ts: "t0", level: "info", category: "updater", message: "old" })}\n`,
+      "utf8",
+    );
+    const yesterday = new Date(Date.now() - 24 * 60 * 60_000);
+    await fs.utimes(livePath, yesterday, yesterday);
+
+    writeLocalLog("updater.log", "info", "updater", "new");
+    await flushLocalLogWrites("updater.log");
+
+    const rotated = (await fs.readdir(logsDir)).filter((name) => /^updater\..+\.log$/.test(name));
+    const logs = await readLocalLogs({ file: "updater.log" });
+
+    expect(rotated).toHaveLength(LOG_RETENTION_DAYS);
+    expect(rotated).toContain(`updater.${dayKey(1)}.log`);
+    expect(logs.entries.map((entry) => [entry.message, entry.file])).toEqual([
+      ["old", `updater.${dayKey(1)}.log`],
+      ["new", "updater.log"],
+    ]);
+  });
+});
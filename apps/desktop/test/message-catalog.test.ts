@@ -0,0 +1,57 @@
+import { describe, expect, test } from "bun:test";
+
+import { SidecarLimitError } from "../electron/services/isolatedSessions";
+import {
+  describeLocale,
+  MessageCatalog,
+  resolveMessageLocale,
+} from "../electron/services/messageCatalog";
+
+describe("message catalog", () => {
+  test("resolves region tags to the bundled language", () => {
+    expect(resolveMessageLocale("es-MX")).toBe("es");
+    expect(resolveMessageLocale("de_AT")).toBe("de");
+    expect(resolveMessageLocale("FR")).toBe("fr");
+    expect(resolveMessageLocale("pt-BR")).toBe("en");
+    expect(resolveMessageLocale(null)).toBe("en");
+  });
+
+  test("translates and interpolates, falling back to English", () => {
+    const messages = new MessageCatalog();
+    expect(messages.t("tray.quit", { appName: "Cowork" })).toBe("Quit Cowork");
+    expect(messages.setLocale("fr-CA")).toBe("fr");
+    expect(messages.t("tray.quit", { appName: "Cowork" })).toBe("Quitter Cowork");
+    expect(messages.t("tray.openQuickChat", { shortcut: "" })).toBe("Ouvrir le chat rapide");
+    expect(messages.t("tray.openApp")).toBe("Ouvrir {appName}");
+  });
+
+  test("localizes coded errors and keeps their name and code", () => {
+    const messages = new MessageCatalog();
+    const error = new SidecarLimitError(4);
+    expect(messages.localizeError(error)).toBe(error);
+
+    messages.setLocale("de");
+    const localized = messages.localizeError(error);
+    expect(localized.message).toBe(
+      "Alle 4 isolierten Workspace-Server sind belegt. " +
+        "Schließe einen Thread oder erhöhe das Limit.",
+    );
+    expect(localized.name).toBe("SidecarLimitError");
+    expect((localized as Error & { code?: string }).code).toBe("sidecar_limit_reached");
+
+    const uncoded = new Error("Workspace not found");
+    expect(messages.localizeError(uncoded)).toBe(uncoded);
+    const untranslated = Object.assign(new Error("boom"), { code: "something_else" });
+    expect(messages.localizeError(untranslated)).toBe(untranslated);
+  });
+
+  test("describes the saved override and the OS locale", () => {
+    expect(describeLocale(null, "es-ES")).toEqual({
+      requested: null,
+      systemLocale: "es-ES",
+      resolved: "es",
+      supported: ["en", "es", "fr", "de"],
+    });
+    expect(describeLocale("ja", "de-DE").resolved).toBe("en");
+  });
+});
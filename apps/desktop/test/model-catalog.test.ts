@@ -0,0 +1,176 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  ModelCatalogService,
+  pricingTierFor,
+  UnknownModelError,
+} from "../electron/services/modelCatalog";
+import type { PersistedState } from "../src/app/types";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+const state: PersistedState = {
+  version: 2,
+  workspaces: [
+    {
+      id: "ws-1",
+      name: "Project",
+      path: "/work/project",
+      createdAt: TS,
+      lastOpenedAt: TS,
+      defaultEnableMcp: true,
+      defaultBackupsEnabled: true,
+      yolo: false,
+    },
+  ],
+  threads: [],
+};
+
+const catalogEvent = {
+  type: "provider_catalog",
+  all: [
+    {
+      id: "openai",
+      name: "OpenAI",
+      defaultModel: "gpt-big",
+      models: [
+        {
+          id: "gpt-big",
+          displayName: "GPT Big",
+          knowledgeCutoff: "2025",
+          supportsImageInput: true,
+          reasoning: { defaultEffort: "medium", availableEfforts: ["low", "medium"] },
+          runtimeOptions: { contextWindow: 400_000 },
+        },
+        {
+          id: "gpt-small",
+          displayName: "GPT Small",
+          knowledgeCutoff: "2025",
+          supportsImageInput: false,
+        },
+      ],
+    },
+  ],
+  default: { openai: "gpt-big" },
+  connected: ["openai"],
+};
+
+describe("model catalog", () => {
+  let root = "";
+  let clock = new Date(TS);
+  let requests: Array<{ method: string; params?: unknown }> = [];
+  let failRequests = false;
+
+  const createService = () =>
+    new ModelCatalogService({
+      filePath: path.join(root, "model-catalog.json"),
+      clientVersion: "1.0.0",
+      loadState: async () => state,
+      startWorkspaceServer: async () => ({ url: "ws://127.0.0.1:1/ws", authToken: "token" }),
+      resolvePricing: (_provider, model) =>
+        model === "gpt-big" ? { inputPerMillion: 5, outputPerMillion: 30 } : null,
+      connectJsonRpc: async () => ({
+        sendRequest: async (method, params) => {
+          requests.push({ method, params });
+          if (failRequests) {
+            throw new Error("socket closed");
+          }
+          return { result: { event: catalogEvent } };
+        },
+        waitFor: async () => {
+          throw new Error("unused");
+        },
+        close: () => {},
+      }),
+      ttlMs: 60_000,
+      now: () => clock,
+    });
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "model-catalog-"));
+    clock = new Date(TS);
+    requests = [];
+    failRequests = false;
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  test("maps the server catalog to model metadata and caches it on disk", async () => {
+    const result = await createService().list({ provider: "openai" });
+
+    expect(requests).toEqual([
+      { method: "cowork/provider/catalog/read", params: { cwd: "/work/project" } },
+    ]);
+    expect(result).toMatchObject({
+      provider: "openai",
+      defaultModel: "gpt-big",
+      state: "ready",
+      fetchedAt: TS,
+      expiresAt: "2026-01-01T00:01:00.000Z",
+      stale: false,
+    });
+    expect(result.models).toEqual([
+      {
+        id: "gpt-big",
+        displayName: "GPT Big",
+        description: null,
+        contextWindow: 400_000,
+        modalities: ["text", "image"],
+        pricingTier: "premium",
+        supportsReasoning: true,
+        isDefault: true,
+      },
+      {
+        id: "gpt-small",
+        displayName: "GPT Small",
+        description: null,
+        contextWindow: null,
+        modalities: ["text"],
+        pricingTier: null,
+        supportsReasoning: false,
+        isDefault: false,
+      },
+    ]);
+
+    // A new service reads the cache file instead of asking the server again.
+    await createService().list({ provider: "openai" });
+    expect(requests).toHaveLength(1);
+  });
+
+  test("refetches after the TTL and serves the expired cache when that fails", async () => {
+    const service = createService();
+    await service.list({ provider: "openai" });
+
+    clock = new Date(Date.parse(TS) + 120_000);
+    failRequests = true;
+    const stale = await service.list({ provider: "openai" });
+    expect(requests).toHaveLength(2);
+    expect(stale).toMatchObject({ stale: true, fetchedAt: TS });
+
+    await expect(service.list({ provider: "anthropic" })).rejects.toThrow("socket closed");
+  });
+
+  test("rejects models missing from a cached catalog", async () => {
+    const service = createService();
+    await service.assertKnownModel("openai", "anything");
+
+    await service.list({ provider: "openai" });
+    await service.assertKnownModel("openai", "gpt-small");
+    await expect(service.assertKnownModel("openai", "gpt-typo")).rejects.toBeInstanceOf(
+      UnknownModelError,
+    );
+  });
+
+  test("buckets pricing by output cost", () => {
+    expect(pricingTierFor(null)).toBeNull();
+    expect(pricingTierFor({ inputPerMillion: 0, outputPerMillion: 0 })).toBe("free");
+    expect(pricingTierFor({ inputPerMillion: 0.1, outputPerMillion: 0.4 })).toBe("low");
+    expect(pricingTierFor({ inputPerMillion: 1.25, outputPerMillion: 10 })).toBe("standard");
+    expect(pricingTierFor({ inputPerMillion: 15, outputPerMillion: 75 })).toBe("premium");
+  });
+});
@@ -0,0 +1,140 @@
+import { afterEach, describe, expect, test } from "bun:test";
+import http from "node:http";
+import type { AddressInfo } from "node:net";
+
+import { DEFAULT_APP_SETTINGS } from "../electron/services/appSettings";
+import {
+  proxyEnvFromSettings,
+  resolveProxyForUrl,
+  shouldBypassProxy,
+  testProxyConnectivity,
+  validateNetworkSettings,
+} from "../electron/services/networkProxy";
+import {
+  proxyConnectivityResultSchema,
+  updateAppSettingsInputSchema,
+} from "../src/lib/desktopSchemas";
+
+const servers: http.Server[] = [];
+
+async function listen(handler: http.RequestListener): Promise<string> {
+  const server = http.createServer(handler);
+  servers.push(server);
+  await new Promise<void>((resolve) => server.listen(0, "127.0.0.1", resolve));
+  return `http://127.0.0.1:${(server.address() as AddressInfo).port}`;
+}
+
+afterEach(async () => {
+  await Promise.all(
+    servers.splice(0).map(
+      (server) => new Promise<void>((resolve) => server.close(() => resolve())),
+    ),
+  );
+});
+
+describe("network proxy settings", () => {
+  test("maps configured values onto the proxy environment variables", () => {
+    expect(proxyEnvFromSettings(DEFAULT_APP_SETTINGS)).toEqual({});
+    expect(
+      proxyEnvFromSettings({
+        ...DEFAULT_APP_SETTINGS,
+        httpsProxy: "http://proxy.corp:3128",
+        noProxy: "localhost,.internal",
+        caBundlePath: "/etc/corp/ca.pem",
+      }),
+    ).toEqual({
+      HTTPS_PROXY: "http://proxy.corp:3128",
+      https_proxy: "http://proxy.corp:3128",
+      NO_PROXY: "localhost,.internal",
+      no_proxy: "localhost,.internal",
+      NODE_EXTRA_CA_CERTS: "/etc/corp/ca.pem",
+    });
+  });
+
+  test("honors NO_PROXY hosts, suffixes, ports, and wildcards", () => {
+    const target = new URL("https://api.example.com/v1");
+
+    expect(shouldBypassProxy(target, "example.com")).toBe(true);
+    expect(shouldBypassProxy(target, ".example.com")).toBe(true);
+    expect(shouldBypassProxy(target, "*.example.com")).toBe(true);
+    expect(shouldBypassProxy(target, "example.com:8443")).toBe(false);
+    expect(shouldBypassProxy(target, "example.com:443")).toBe(true);
+    expect(shouldBypassProxy(target, "ample.com, other.org")).toBe(false);
+    expect(shouldBypassProxy(target, "*")).toBe(true);
+    expect(shouldBypassProxy(new URL("http://[::1]:8080/"), "[::1]")).toBe(true);
+    expect(
+      resolveProxyForUrl(target, { HTTPS_PROXY: "proxy.corp:3128", HTTP_PROXY: "http://other" })
+        ?.href,
+    ).toBe("http://proxy.corp:3128/");
+  });
+
+  test("rejects proxy URLs without an http scheme and unreadable CA bundles", async () => {
+    const socksProxy = updateAppSettingsInputSchema.safeParse({ httpsProxy: "socks5://proxy:1" });
+    const relativeCa = updateAppSettingsInputSchema.safeParse({ caBundlePath: "certs/ca.pem" });
+    expect(socksProxy.success).toBe(false);
+    expect(relativeCa.success).toBe(false);
+
+    const current = DEFAULT_APP_SETTINGS;
+    await expect(
+      validateNetworkSettings({ ...current, caBundlePath: "/missing.pem" }, current, async () => {
+        throw new Error("ENOENT");
+      }),
+    ).rejects.toThrow("could not be read: ENOENT");
+    await expect(
+      validateNetworkSettings({ ...current, caBundlePath: "/ca.pem" }, current, async () => "x"),
+    ).rejects.toThrow("does not contain a PEM certificate");
+    await expect(
+      validateNetworkSettings({ ...current, noProxy: "localhost" }, current),
+    ).resolves.toBeUndefined();
+  });
+});
+
+describe("proxy connectivity diagnostics", () => {
+  test("sends plain HTTP through the configured proxy with its credentials", async () => {
+    const seen: Array<{ url?: string; authorization?: string }> = [];
+    const proxyUrl = await listen((request, response) => {
+      seen.push({ url: request.url, authorization: request.headers["proxy-authorization"] });
+      response.writeHead(204).end();
+    });
+    const proxy = new URL(proxyUrl);
+    proxy.username = "alice";
+    proxy.password = "s3cret";
+
+    const result = await testProxyConnectivity(
+      { url: "http://updates.example.test/ping" },
+      { ...DEFAULT_APP_SETTINGS, httpProxy: proxy.href },
+      { env: {} },
+    );
+
+    expect(proxyConnectivityResultSchema.safeParse(result).success).toBe(true);
+    expect(result).toMatchObject({ ok: true, httpStatus: 204, proxy: proxyUrl });
+    expect(result.message).not.toContain("s3cret");
+    expect(seen).toEqual([
+      {
+        url: "http://updates.example.test/ping",
+        authorization: `Basic ${Buffer.from("alice:s3cret").toString("base64")}`,
+      },
+    ]);
+  });
+
+  test("goes direct for NO_PROXY hosts and reports failures without throwing", async () => {
+    const targetUrl = await listen((_request, response) => {
+      response.writeHead(200).end();
+    });
+
+    const direct = await testProxyConnectivity(
+      { url: targetUrl },
+      { ...DEFAULT_APP_SETTINGS, httpProxy: "http://127.0.0.1:9", noProxy: "127.0.0.1" },
+      { env: {} },
+    );
+    const refused = await testProxyConnectivity(
+      { url: "http://updates.example.test/" },
+      { ...DEFAULT_APP_SETTINGS, httpProxy: "http://127.0.0.1:9" },
+      { env: {}, timeoutMs: 2_000 },
+    );
+
+    expect(direct).toMatchObject({ ok: true, httpStatus: 200, proxy: null });
+    expect(refused).toMatchObject({ ok: false, httpStatus: null, proxy: "http://127.0.0.1:9" });
+    expect(refused.message.length).toBeGreaterThan(0);
+  });
+});
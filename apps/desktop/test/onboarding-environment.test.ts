@@ -0,0 +1,138 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { type OnboardingKeychain, OnboardingService } from "../electron/services/onboarding";
+import type { PersistedState } from "../src/app/types";
+import type { InstallationIntegrityReport } from "../src/lib/desktopApi";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+const emptyState: PersistedState = { version: 2, workspaces: [], threads: [] };
+
+const workingKeychain: OnboardingKeychain = {
+  isEncryptionAvailable: () => true,
+  encryptString: (value) => Buffer.from(value, "utf8"),
+  decryptString: (value) => value.toString("utf8"),
+  getSelectedStorageBackend: () => "gnome_libsecret",
+};
+
+const verifiedInstall: InstallationIntegrityReport = {
+  status: "ok",
+  checkedAt: TS,
+  packaged: true,
+  resources: [],
+};
+
+const missingSidecar: InstallationIntegrityReport = {
+  ...verifiedInstall,
+  status: "failed",
+  resources: [
+    {
+      name: "cowork-server",
+      path: null,
+      status: "missing",
+      expectedSha256: null,
+      actualSha256: null,
+    },
+  ],
+};
+
+describe("onboarding environment checks", () => {
+  let root = "";
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "onboarding-environment-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  const createService = (overrides: Partial<ConstructorParameters<typeof OnboardingService>[0]>) =>
+    new OnboardingService({
+      loadState: async () => emptyState,
+      writableDirs: [path.join(root, "userData")],
+      isPackaged: true,
+      verifyInstallation: async () => verifiedInstall,
+      keychain: workingKeychain,
+      runCommand: async (command) => (command === "git" ? "git version 2.45.1\n" : "1.2.3\n"),
+      platform: "linux",
+      now: () => new Date(TS),
+      ...overrides,
+    });
+
+  test("passes every check on a healthy machine and remembers the report", async () => {
+    const service = createService({});
+    expect(await service.getStatus()).toEqual({
+      firstRun: true,
+      workspaceCount: 0,
+      onboarding: null,
+      environment: null,
+    });
+
+    const report = await service.runEnvironmentChecks();
+
+    expect(report.status).toBe("pass");
+    expect(report.checks.map((check) => [check.id, check.status, check.summary])).toEqual([
+      ["bun", "pass", "Bun 1.2.3 is installed."],
+      ["git", "pass", "Git 2.45.1 is installed."],
+      ["sidecar", "pass", "The bundled agent server matches its recorded checksums."],
+      ["keychain", "pass", "The OS keychain is available for protecting secrets."],
+      ["writePermissions", "pass", "The app can write to its data folders."],
+    ]);
+    expect(await fs.readdir(path.join(root, "userData"))).toEqual([]);
+    expect((await service.getStatus()).environment).toBe(report);
+  });
+
+  test("reports missing tools, an unwritable folder, and an unprotected keyring", async () => {
+    const blocked = path.join(root, "blocked");
+    await fs.writeFile(blocked, "not a directory");
+    const service = createService({
+      isPackaged: false,
+      writableDirs: [blocked],
+      runCommand: async () => {
+        throw Object.assign(new Error("spawn ENOENT"), { code: "ENOENT" });
+      },
+      verifyInstallation: async () => ({ ...missingSidecar, packaged: false }),
+      keychain: { ...workingKeychain, getSelectedStorageBackend: () => "basic_text" },
+    });
+
+    const report = await service.runEnvironmentChecks();
+
+    expect(report.status).toBe("fail");
+    expect(Object.fromEntries(report.checks.map((check) => [check.id, check.status]))).toEqual({
+      bun: "fail",
+      git: "warn",
+      // Unpackaged builds run the server from source, so there is no bundle to check.
+      sidecar: "pass",
+      keychain: "warn",
+      writePermissions: "fail",
+    });
+    expect(report.checks.find((check) => check.id === "keychain")?.recommendation).toContain(
+      "Secret Service",
+    );
+  });
+
+  test("fails a packaged install whose sidecar is missing but does not need Bun", async () => {
+    const service = createService({
+      runCommand: async () => {
+        throw new Error("not found");
+      },
+      verifyInstallation: async () => missingSidecar,
+    });
+
+    const report = await service.runEnvironmentChecks();
+
+    expect(report.checks.slice(0, 3)).toEqual([
+      expect.objectContaining({ id: "bun", status: "pass" }),
+      expect.objectContaining({ id: "git", status: "warn" }),
+      expect.objectContaining({
+        id: "sidecar",
+        status: "fail",
+        summary: "The bundled agent server is missing or altered: cowork-server.",
+      }),
+    ]);
+  });
+});
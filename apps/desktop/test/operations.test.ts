@@ -0,0 +1,245 @@
+import { describe, expect, test } from "bun:test";
+
+import { OperationManager } from "../electron/services/operations";
+import type { OperationSnapshot } from "../src/lib/desktopApi";
+import {
+  enqueueExportInputSchema,
+  operationSnapshotSchema,
+  startOperationInputSchema,
+} from "../src/lib/desktopSchemas";
+
+function createManager(retainFinished?: number) {
+  const events: OperationSnapshot[] = [];
+  let nextId = 0;
+  const manager = new OperationManager({
+    emit: (snapshot) => events.push(snapshot),
+    now: () => new Date("2026-06-01T12:00:00.000Z"),
+    createId: () => `op-${++nextId}`,
+    retainFinished,
+  });
+  return { manager, events };
+}
+
+function deferred<T>() {
+  let resolve!: (value: T) => void;
+  let reject!: (error: unknown) => void;
+  const promise = new Promise<T>((res, rej) => {
+    resolve = res;
+    reject = rej;
+  });
+  return { promise, resolve, reject };
+}
+
+describe("desktop background operations", () => {
+  test("returns a running snapshot immediately and streams progress until success", async () => {
+    const { manager, events } = createManager();
+    const gate = deferred<void>();
+
+    const started = manager.start("createDiagnosticsBundle", async ({ reportProgress }) => {
+      reportProgress({ completed: 1, total: 2, message: "Collecting logs" });
+      await gate.promise;
+      return { path: "/tmp/bundle.json" };
+    });
+
+    expect(started).toMatchObject({ operationId: "op-1", status: "running", finishedAt: null });
+    expect(manager.get("op-1")?.progress).toEqual({
+      completed: 1,
+      total: 2,
+      message: "Collecting logs",
+    });
+
+    gate.resolve();
+    await manager.drain();
+
+    expect(manager.get("op-1")).toMatchObject({
+      status: "succeeded",
+      result: { path: "/tmp/bundle.json" },
+      error: null,
+      finishedAt: "2026-06-01T12:00:00.000Z",
+    });
+    expect(events.map((event) => event.status)).toEqual(["running", "running", "succeeded"]);
+    for (const event of events) {
+      expect(operationSnapshotSchema.safeParse(event).success).toBe(true);
+    }
+  });
+
+  test("records task errors as failed operations", async () => {
+    const { manager } = createManager();
+
+    manager.start("uploadDiagnosticsBundle", async () => {
+      throw new Error("upload endpoint rejected the bundle");
+    });
+    await manager.drain();
+
+    expect(manager.get("op-1")).toMatchObject({
+      status: "failed",
+      result: null,
+      error: "upload endpoint rejected the bundle",
+    });
+  });
+
+  test("cancellation aborts the task and wins over its eventual result", async () => {
+    const { manager, events } = createManager();
+    const gate = deferred<void>();
+    let aborted = false;
+
+    manager.start("uploadDiagnosticsBundle", async ({ signal, reportProgress }) => {
+      signal.addEventListener("abort", () => {
+        aborted = true;
+      });
+      await gate.promise;
+      reportProgress({ completed: 1, total: 1, message: "late progress" });
+      return { uploaded: true };
+    });
+
+    expect(manager.cancel("op-1")).toBe(true);
+    expect(aborted).toBe(true);
+    expect(manager.cancel("op-1")).toBe(false);
+    expect(manager.cancel("op-unknown")).toBe(false);
+
+    gate.resolve();
+    await manager.drain();
+
+    expect(manager.get("op-1")).toMatchObject({
+      status: "cancelled",
+      result: null,
+      error: null,
+      progress: null,
+    });
+    expect(events.map((event) => event.status)).toEqual(["running", "cancelled"]);
+  });
+
+  test("cancelAll stops running operations and drain waits for them to unwind", async () => {
+    const { manager } = createManager();
+    const unwound: string[] = [];
+
+    for (const kind of ["createDiagnosticsBundle", "uploadDiagnosticsBundle"] as const) {
+      manager.start(kind, ({ operationId, signal }) => {
+        return new Promise((_resolve, reject) => {
+          signal.addEventListener("abort", () => {
+            setTimeout(() => {
+              unwound.push(operationId);
+              reject(signal.reason);
+            }, 5);
+          });
+        });
+      });
+    }
+
+    manager.cancelAll();
+    await manager.drain();
+
+    expect(unwound.sort()).toEqual(["op-1", "op-2"]);
+    expect(manager.list().map((snapshot) => snapshot.status)).toEqual(["cancelled", "cancelled"]);
+  });
+
+  test("runs one operation per queue at a time and never runs a cancelled queued one", async () => {
+    const { manager, events } = createManager();
+    const first = deferred<void>();
+    const ran: string[] = [];
+    const task = async ({ operationId }: { operationId: string }) => {
+      ran.push(operationId);
+      if (operationId === "op-1") {
+        await first.promise;
+      }
+      return operationId;
+    };
+
+    manager.start("exportAppData", task, { queue: "export" });
+    const second = manager.start("createDiagnosticsBundle", task, { queue: "export" });
+    manager.start("exportAppData", task, { queue: "export" });
+    manager.start("uploadDiagnosticsBundle", task);
+
+    expect(second.status).toBe("queued");
+    expect(ran).toEqual(["op-1", "op-4"]);
+    expect(manager.cancel("op-2")).toBe(true);
+
+    first.resolve();
+    await manager.drain();
+    await manager.drain();
+
+    expect(ran).toEqual(["op-1", "op-4", "op-3"]);
+    expect(manager.list().map((snapshot) => [snapshot.operationId, snapshot.status])).toEqual([
+      ["op-1", "succeeded"],
+      ["op-2", "cancelled"],
+      ["op-3", "succeeded"],
+      ["op-4", "succeeded"],
+    ]);
+    expect(
+      events.filter((event) => event.operationId === "op-3").map((event) => event.status),
+    ).toEqual(["queued", "running", "succeeded"]);
+    for (const event of events) {
+      expect(operationSnapshotSchema.safeParse(event).success).toBe(true);
+    }
+  });
+
+  test("keeps only the most recent finished operations", async () => {
+    const { manager } = createManager(2);
+
+    for (let index = 0; index < 4; index += 1) {
+      manager.start("createDiagnosticsBundle", async () => index);
+      await manager.drain();
+    }
+
+    expect(manager.list().map((snapshot) => snapshot.operationId)).toEqual(["op-3", "op-4"]);
+    expect(manager.get("op-1")).toBeNull();
+  });
+
+  test("start input requires a known operation kind and its arguments", () => {
+    expect(startOperationInputSchema.safeParse({ kind: "createDiagnosticsBundle" }).success).toBe(
+      true,
+    );
+    expect(
+      startOperationInputSchema.safeParse({
+        kind: "uploadDiagnosticsBundle",
+        path: "/tmp/cowork-diagnostics.json",
+        confirmed: true,
+      }).success,
+    ).toBe(true);
+    expect(startOperationInputSchema.safeParse({ kind: "uploadDiagnosticsBundle" }).success).toBe(
+      false,
+    );
+    expect(startOperationInputSchema.safeParse({ kind: "rm -rf" }).success).toBe(false);
+  });
+
+  test("start input accepts the long-running desktop commands", () => {
+    expect(
+      startOperationInputSchema.safeParse({
+        kind: "prewarmWorkspaceServers",
+        workspaceIds: ["ws-1", "ws-2"],
+        concurrency: 2,
+      }).success,
+    ).toBe(true);
+    expect(
+      startOperationInputSchema.safeParse({ kind: "importAppData", mergeStrategy: "merge" })
+        .success,
+    ).toBe(true);
+    expect(
+      startOperationInputSchema.safeParse({ kind: "encryptExistingTranscripts" }).success,
+    ).toBe(true);
+    expect(
+      startOperationInputSchema.safeParse({ kind: "migrateTranscriptStore", target: "sqlite" })
+        .success,
+    ).toBe(true);
+    expect(
+      startOperationInputSchema.safeParse({ kind: "migrateTranscriptStore", target: "s3" }).success,
+    ).toBe(false);
+    expect(
+      startOperationInputSchema.safeParse({
+        kind: "encryptExistingTranscripts",
+        workspaceIds: ["ws-1"],
+      }).success,
+    ).toBe(false);
+  });
+
+  test("export input names a supported export and its options", () => {
+    expect(
+      enqueueExportInputSchema.safeParse({ kind: "appData", includeTranscripts: true }).success,
+    ).toBe(true);
+    expect(enqueueExportInputSchema.safeParse({ kind: "diagnosticsBundle" }).success).toBe(true);
+    expect(enqueueExportInputSchema.safeParse({ kind: "appData" }).success).toBe(false);
+    expect(
+      enqueueExportInputSchema.safeParse({ kind: "diagnosticsBundle", path: "/tmp/x" }).success,
+    ).toBe(false);
+  });
+});
@@ -0,0 +1,175 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  findOrphanedServers,
+  OrphanedServerService,
+  parsePsProcessList,
+  parseWindowsProcessList,
+  type RunningProcess,
+  SpawnedServerLedger,
+} from "../electron/services/orphanedServers";
+import { orphanedServerListSchema } from "../src/lib/desktopSchemas";
+
+const STARTED_AT = Date.parse("2026-03-02T10:15:00.000Z");
+
+describe("process list parsing", () => {
+  test("parses ps output with fixed-width start times", () => {
+    const stdout = [
+      "  501 Mon Mar  2 10:15:00 2026     /Applications/Cowork.app/Contents/Resources/cowork-server --port 0",
+      "77 Tue Mar 10 08:00:05 2026 /bin/zsh -l",
+      "garbage",
+      "",
+    ].join("\n");
+
+    const processes = parsePsProcessList(stdout);
+
+    expect(processes.map((entry) => entry.pid)).toEqual([501, 77]);
+    expect(processes[0]?.command).toBe(
+      "/Applications/Cowork.app/Contents/Resources/cowork-server --port 0",
+    );
+    expect(processes[0]?.startedAt).toBe(new Date(2026, 2, 2, 10, 15, 0).getTime());
+    expect(processes[1]?.command).toBe("/bin/zsh -l");
+  });
+
+  test("parses single and multiple Windows rows", () => {
+    const single = parseWindowsProcessList(
+      JSON.stringify({ ProcessId: 12, CommandLine: "cowork-server.exe", StartedAt: STARTED_AT }),
+    );
+    const multiple = parseWindowsProcessList(
+      JSON.stringify([
+        { ProcessId: 12, CommandLine: null, StartedAt: null },
+        { ProcessId: "bad" },
+      ]),
+    );
+
+    expect(single).toEqual([{ pid: 12, startedAt: STARTED_AT, command: "cowork-server.exe" }]);
+    expect(multiple).toEqual([{ pid: 12, startedAt: null, command: "" }]);
+    expect(parseWindowsProcessList("  ")).toEqual([]);
+  });
+});
+
+describe("findOrphanedServers", () => {
+  const ledgerEntry = {
+    pid: 4100,
+    workspaceId: "ws-1",
+    sessionId: "previous",
+    startedAt: STARTED_AT,
+  };
+
+  function find(processes: RunningProcess[], overrides: { protectedPids?: number[] } = {}) {
+    return findOrphanedServers({
+      ledger: [ledgerEntry, { ...ledgerEntry, pid: 4200, sessionId: "current" }],
+      processes,
+      sessionId: "current",
+      protectedPids: new Set(overrides.protectedPids ?? []),
+      selfPid: 1,
+    });
+  }
+
+  test("reports servers recorded by an earlier launch that are still running", () => {
+    const orphans = find([
+      { pid: 4100, startedAt: STARTED_AT + 2_000, command: "bun run src/server/index.ts" },
+      { pid: 4200, startedAt: STARTED_AT, command: "bun run src/server/index.ts" },
+    ]);
+
+    expect(orphans).toEqual([
+      {
+        pid: 4100,
+        workspaceId: "ws-1",
+        startedAt: new Date(STARTED_AT + 2_000).toISOString(),
+        command: "bun run src/server/index.ts",
+      },
+    ]);
+    expect(orphanedServerListSchema.safeParse(orphans).success).toBe(true);
+  });
+
+  test("ignores a reused pid whose start time does not match the ledger", () => {
+    expect(
+      find([{ pid: 4100, startedAt: STARTED_AT + 3_600_000, command: "/usr/bin/vim notes" }]),
+    ).toEqual([]);
+  });
+
+  test("finds unrecorded sidecar binaries but skips protected pids", () => {
+    const orphans = find(
+      [
+        {
+          pid: 9000,
+          startedAt: null,
+          command: '"C:\\Program Files\\Cowork\\cowork-server.exe" --x',
+        },
+        { pid: 9001, startedAt: STARTED_AT, command: "/opt/cowork/cowork-server --port 0" },
+        { pid: 9002, startedAt: STARTED_AT, command: "/usr/bin/tail -f cowork-server.log" },
+        { pid: 4100, startedAt: STARTED_AT, command: "bun run src/server/index.ts" },
+      ],
+      { protectedPids: [9001, 4100] },
+    );
+
+    expect(orphans).toEqual([
+      {
+        pid: 9000,
+        workspaceId: null,
+        startedAt: null,
+        command: '"C:\\Program Files\\Cowork\\cowork-server.exe" --x',
+      },
+    ]);
+  });
+});
+
+describe("orphaned server cleanup", () => {
+  let userDataDir = "";
+
+  beforeEach(async () => {
+    userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-orphans-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(userDataDir, { recursive: true, force: true });
+  });
+
+  test("the ledger records and forgets spawned servers", async () => {
+    const ledger = new SpawnedServerLedger(userDataDir, "session-a");
+
+    await Promise.all([
+      ledger.record({ pid: 10, workspaceId: "ws-1", startedAt: STARTED_AT }),
+      ledger.record({ pid: 11, workspaceId: "ws-2", startedAt: STARTED_AT }),
+    ]);
+    expect((await ledger.list()).map((entry) => entry.pid)).toEqual([10, 11]);
+
+    await ledger.remove([10, 11]);
+    expect(await ledger.list()).toEqual([]);
+    await expect(fs.access(ledger.filePath)).rejects.toThrow();
+  });
+
+  test("stops orphans from an earlier session and prunes dead entries", async () => {
+    const previous = new SpawnedServerLedger(userDataDir, "session-a");
+    await previous.record({ pid: 10, workspaceId: "ws-1", startedAt: STARTED_AT });
+    await previous.record({ pid: 11, workspaceId: "ws-2", startedAt: STARTED_AT });
+    await previous.record({ pid: 12, workspaceId: "ws-3", startedAt: STARTED_AT });
+
+    const ledger = new SpawnedServerLedger(userDataDir, "session-b");
+    const signals: Array<[number, NodeJS.Signals]> = [];
+    const service = new OrphanedServerService({
+      ledger,
+      getProtectedPids: () => [],
+      listProcesses: async () => [
+        { pid: 10, startedAt: STARTED_AT, command: "cowork-server" },
+        { pid: 11, startedAt: STARTED_AT, command: "cowork-server" },
+      ],
+      signalProcess: (pid, signal) => {
+        if (pid === 11) {
+          throw new Error("EPERM");
+        }
+        signals.push([pid, signal]);
+      },
+    });
+
+    const result = await service.cleanup();
+
+    expect(result).toEqual({ stopped: [10], failed: [{ pid: 11, error: "EPERM" }] });
+    expect(signals).toEqual([[10, "SIGTERM"]]);
+    expect((await ledger.list()).map((entry) => entry.pid)).toEqual([11]);
+  });
+});
@@ -0,0 +1,50 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  fromFileSystemPath,
+  isPathEqualOrInside,
+  pathIdentityKey,
+  toFileSystemPath,
+} from "../electron/services/pathBoundary";
+
+const ONE_DRIVE = "C:\\Users\\me\\OneDrive - Contoso\\Documents";
+const SHARE = "\\\\fileserver\\team";
+
+describe("pathBoundary", () => {
+  test("containment ignores long-path prefixes and case", () => {
+    const prefixedRepo = "\\\\?\\c:\\users\\me\\onedrive - contoso\\documents\\repo";
+    expect(isPathEqualOrInside(ONE_DRIVE, prefixedRepo, "win32")).toBe(true);
+    expect(isPathEqualOrInside(SHARE, "\\\\?\\UNC\\FileServer\\Team\\repo\\src", "win32")).toBe(
+      true,
+    );
+    expect(isPathEqualOrInside(SHARE, "\\\\fileserver\\team-archive", "win32")).toBe(false);
+    expect(isPathEqualOrInside(SHARE, "C:\\team", "win32")).toBe(false);
+  });
+
+  test("POSIX containment stays case-sensitive", () => {
+    expect(isPathEqualOrInside("/home/me/repo", "/home/me/repo/src", "linux")).toBe(true);
+    expect(isPathEqualOrInside("/home/me/repo", "/home/me/Repo/src", "linux")).toBe(false);
+  });
+
+  test("spellings of one network or OneDrive folder share an identity key", () => {
+    expect(pathIdentityKey(`${ONE_DRIVE}\\repo`, "win32")).toBe(
+      pathIdentityKey("\\\\?\\C:\\USERS\\me\\OneDrive - Contoso\\Documents\\repo\\", "win32"),
+    );
+    expect(pathIdentityKey(`${SHARE}\\repo`, "win32")).toBe(
+      pathIdentityKey("//FileServer/Team/repo", "win32"),
+    );
+    expect(pathIdentityKey("/home/me/Repo", "linux")).not.toBe(
+      pathIdentityKey("/home/me/repo", "linux"),
+    );
+  });
+
+  test("fs paths get a prefix past MAX_PATH and lose it when stored", () => {
+    const deep = `${SHARE}\\${"nested-folder\\".repeat(20)}repo`;
+    const fsPath = toFileSystemPath(deep, "win32");
+
+    expect(fsPath).toBe(`\\\\?\\UNC\\${deep.slice(2)}`);
+    expect(fromFileSystemPath(fsPath, "win32")).toBe(deep);
+    expect(toFileSystemPath(`${ONE_DRIVE}\\repo`, "win32")).toBe(`${ONE_DRIVE}\\repo`);
+    expect(toFileSystemPath("/home/me/repo", "linux")).toBe("/home/me/repo");
+  });
+});
@@ -286,6 +286,61 @@ describe("desktop persistence state validation", () => {
     ]);
   });
 
+  test("saveState preserves thread model routing and drops malformed routing", async () => {
+    const persistence = new PersistenceService();
+    const validWorkspace = path.join(userDataDir, "workspace-valid");
+    await fs.mkdir(validWorkspace, { recursive: true });
+    const thread = {
+      workspaceId: "ws_valid",
+      title: "Routed thread",
+      titleSource: "manual",
+      createdAt: TS,
+      lastMessageAt: TS,
+      status: "active",
+      messageCount: 1,
+      lastEventSeq: 1,
+    } as const;
+
+    await persistence.saveState({
+      version: 2,
+      workspaces: [
+        {
+          id: "ws_valid",
+          name: "Valid workspace",
+          path: validWorkspace,
+          createdAt: TS,
+          lastOpenedAt: TS,
+          defaultEnableMcp: true,
+          defaultBackupsEnabled: false,
+          yolo: false,
+        },
+      ],
+      threads: [
+        {
+          ...thread,
+          id: "thread_routed",
+          sessionId: "session_routed",
+          routing: { provider: "openai", model: "gpt-5.4", updatedAt: TS },
+        },
+        {
+          ...thread,
+          id: "thread_bad_routing",
+          sessionId: "session_bad_routing",
+          routing: { provider: "nope", model: "gpt-5.4", updatedAt: TS } as never,
+        },
+      ],
+    });
+
+    const loaded = await persistence.loadState();
+    expect(loaded.threads).toEqual([
+      expect.objectContaining({
+        id: "thread_routed",
+        routing: { provider: "openai", model: "gpt-5.4", updatedAt: TS },
+      }),
+      expect.not.objectContaining({ routing: expect.anything() }),
+    ]);
+  });
+
   test("saveState preserves yolo configuration", async () => {
     const persistence = new PersistenceService();
     const workspaceYoloTrue = path.join(userDataDir, "workspace-yolo-true");
@@ -0,0 +1,168 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+let appDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? appDataDir : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { StateSnapshotScheduler } = await import("../electron/services/stateSnapshots");
+
+function createClock(start = Date.parse("2026-01-01T00:00:00.000Z")) {
+  let current = start;
+  return {
+    now: () => new Date(current),
+    advance: (ms: number) => {
+      current += ms;
+    },
+  };
+}
+
+async function writeRawState(value: unknown): Promise<void> {
+  await fs.writeFile(path.join(userDataDir, "state.json"), JSON.stringify(value), "utf8");
+}
+
+describe("desktop persistence state snapshots", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    appDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-desktop-snapshots-"));
+    userDataDir = path.join(appDataDir, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+  });
+
+  afterEach(async () => {
+    if (appDataDir) {
+      await fs.rm(appDataDir, { recursive: true, force: true });
+    }
+    userDataDir = "";
+    appDataDir = "";
+  });
+
+  test("captures timestamped snapshots and skips unchanged state", async () => {
+    const clock = createClock();
+    const persistence = new PersistenceService({ now: clock.now });
+
+    expect(await persistence.captureStateSnapshot()).toBeNull();
+
+    await writeRawState({ version: 2, workspaces: [], threads: [], developerMode: true });
+    const first = await persistence.captureStateSnapshot();
+    expect(first?.name).toBe("state-20260101T000000000Z.json");
+    expect(first?.createdAt).toBe("2026-01-01T00:00:00.000Z");
+
+    clock.advance(1_000);
+    expect(await persistence.captureStateSnapshot()).toBeNull();
+
+    await writeRawState({ version: 2, workspaces: [], threads: [], developerMode: false });
+    const second = await persistence.captureStateSnapshot();
+    expect(second?.name).toBe("state-20260101T000001000Z.json");
+
+    const snapshots = await persistence.listStateSnapshots();
+    expect(snapshots.map((snapshot) => snapshot.name)).toEqual([
+      "state-20260101T000001000Z.json",
+      "state-20260101T000000000Z.json",
+    ]);
+    expect(snapshots[0]?.sizeBytes).toBeGreaterThan(0);
+  });
+
+  test("prunes snapshots beyond the retention limit", async () => {
+    const clock = createClock();
+    const persistence = new PersistenceService({ now: clock.now, maxStateSnapshots: 3 });
+
+    for (let index = 0; index < 5; index += 1) {
+      await writeRawState({ version: 2, workspaces: [], threads: [], marker: index });
+      await persistence.captureStateSnapshot();
+      clock.advance(60_000);
+    }
+
+    const snapshots = await persistence.listStateSnapshots();
+    expect(snapshots).toHaveLength(3);
+    expect(snapshots.map((snapshot) => snapshot.createdAt)).toEqual([
+      "2026-01-01T00:04:00.000Z",
+      "2026-01-01T00:03:00.000Z",
+      "2026-01-01T00:02:00.000Z",
+    ]);
+  });
+
+  test("restores a snapshot after the state file is wiped", async () => {
+    const clock = createClock();
+    const persistence = new PersistenceService({ now: clock.now });
+
+    await writeRawState({ version: 2, workspaces: [], threads: [], showHiddenFiles: true });
+    const snapshot = await persistence.captureStateSnapshot();
+    expect(snapshot).not.toBeNull();
+
+    clock.advance(1_000);
+    await fs.writeFile(path.join(userDataDir, "state.json"), "{corrupt", "utf8");
+    expect((await persistence.loadState()).showHiddenFiles).toBe(false);
+
+    const restored = await persistence.restoreStateSnapshot(snapshot?.name ?? "");
+    expect(restored.showHiddenFiles).toBe(true);
+    expect((await persistence.loadState()).showHiddenFiles).toBe(true);
+
+    // The corrupted state was itself snapshotted before being replaced.
+    const names = (await persistence.listStateSnapshots()).map((entry) => entry.name);
+    expect(names).toEqual(["state-20260101T000001000Z.json", snapshot?.name ?? ""]);
+  });
+
+  test("rejects invalid or missing snapshot names", async () => {
+    const persistence = new PersistenceService();
+
+    await expect(persistence.restoreStateSnapshot("../state.json")).rejects.toThrow(
+      "snapshot name is invalid",
+    );
+    await expect(
+      persistence.restoreStateSnapshot("state-20260101T000000000Z.json"),
+    ).rejects.toThrow("State snapshot not found");
+  });
+
+  test("scheduler captures on start and reports capture failures", async () => {
+    let captures = 0;
+    const errors: unknown[] = [];
+    let tick: (() => void) | null = null;
+    const scheduler = new StateSnapshotScheduler({
+      capture: async () => {
+        captures += 1;
+        if (captures === 2) {
+          throw new Error("disk full");
+        }
+        return null;
+      },
+      onError: (error) => errors.push(error),
+      setInterval: (callback) => {
+        tick = callback;
+        return 1 as unknown as ReturnType<typeof setInterval>;
+      },
+      clearInterval: () => {
+        tick = null;
+      },
+    });
+
+    scheduler.start();
+    await scheduler.runOnce();
+    expect(captures).toBe(1);
+
+    (tick as (() => void) | null)?.();
+    await scheduler.runOnce();
+    expect(captures).toBe(2);
+    expect(errors).toHaveLength(1);
+
+    await scheduler.runOnce();
+    expect(captures).toBe(3);
+
+    scheduler.dispose();
+    expect(tick).toBeNull();
+  });
+});
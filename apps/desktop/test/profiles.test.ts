@@ -0,0 +1,91 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  applyLaunchProfile,
+  DEFAULT_PROFILE_ID,
+  ProfileArgsError,
+  ProfileStore,
+  parseProfileArg,
+  relaunchArgsForProfile,
+} from "../electron/services/profiles";
+
+function createApp(userData: string) {
+  const paths = new Map([["userData", userData]]);
+  return {
+    getPath: (name: string) => paths.get(name) ?? "",
+    setPath: (name: string, value: string) => {
+      paths.set(name, value);
+    },
+  } as unknown as Parameters<typeof applyLaunchProfile>[0];
+}
+
+describe("profiles", () => {
+  let rootDir = "";
+
+  beforeEach(async () => {
+    rootDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-profiles-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(rootDir, { recursive: true, force: true });
+  });
+
+  test("parses --profile in both forms and rejects unusable ids", () => {
+    expect(parseProfileArg(["--profile", "Client-A"])).toBe("client-a");
+    expect(parseProfileArg(["--inspect", "--profile=work"])).toBe("work");
+    expect(parseProfileArg(["cowork://open"])).toBeNull();
+    expect(() => parseProfileArg(["--profile"])).toThrow(ProfileArgsError);
+    expect(() => parseProfileArg(["--profile", "../etc"])).toThrow(ProfileArgsError);
+  });
+
+  test("creates profiles with unique ids in their own directories", async () => {
+    const store = new ProfileStore({
+      rootDir,
+      now: () => new Date("2026-03-01T00:00:00.000Z"),
+    });
+
+    const acme = store.create("Client: ACME");
+    const again = store.create("client acme");
+
+    expect(acme).toEqual({
+      id: "client-acme",
+      name: "Client: ACME",
+      createdAt: "2026-03-01T00:00:00.000Z",
+    });
+    expect(again.id).toBe("client-acme-2");
+    expect(store.profileDir(acme.id)).toBe(path.join(rootDir, "profiles", "client-acme"));
+    expect(store.profileDir(DEFAULT_PROFILE_ID)).toBe(rootDir);
+    expect((await fs.stat(store.profileDir(again.id))).isDirectory()).toBe(true);
+    expect(store.list(DEFAULT_PROFILE_ID).profiles.map((profile) => profile.id)).toEqual([
+      "default",
+      "client-acme",
+      "client-acme-2",
+    ]);
+  });
+
+  test("launches into the requested profile, then the last one used", () => {
+    const first = createApp(rootDir);
+    const work = applyLaunchProfile(first, ["--profile", "work"]);
+
+    expect(work.profileId).toBe("work");
+    expect(first.getPath("userData")).toBe(path.join(rootDir, "profiles", "work"));
+
+    const second = createApp(rootDir);
+    expect(applyLaunchProfile(second, []).profileId).toBe("work");
+    expect(second.getPath("userData")).toBe(path.join(rootDir, "profiles", "work"));
+
+    const third = createApp(rootDir);
+    expect(applyLaunchProfile(third, ["--profile=default"]).profileId).toBe("default");
+    expect(third.getPath("userData")).toBe(rootDir);
+  });
+
+  test("relaunch args replace the profile and keep everything else", () => {
+    expect(relaunchArgsForProfile(["--profile", "work", "--inspect"], "personal")).toEqual([
+      "--inspect",
+      "--profile=personal",
+    ]);
+  });
+});
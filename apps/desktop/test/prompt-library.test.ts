@@ -0,0 +1,191 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  PromptLibraryStore,
+  resolveWorkspacePromptsPath,
+} from "../electron/services/promptLibrary";
+import { extractPromptVariables, renderPromptTemplate } from "../src/app/promptTemplates";
+import type { PersistedState } from "../src/app/types";
+import { PROMPT_VARIABLES_MISSING_ERROR_CODE } from "../src/lib/desktopApi";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+describe("prompt templates", () => {
+  test("lists each variable once in order of first use", () => {
+    const body = "Fix {{ issue }} in {{file}}, then re-run {{issue}}. {{}}";
+    expect(extractPromptVariables(body)).toEqual(["issue", "file"]);
+  });
+
+  test("fills variables in one pass and names every missing one", () => {
+    expect(
+      renderPromptTemplate("Review {{file}} for {{focus}}.", {
+        file: "{{focus}}.ts",
+        focus: "races",
+        unused: "ignored",
+      }),
+    ).toBe("Review {{focus}}.ts for races.");
+    expect(() => renderPromptTemplate("{{a}} {{b}} {{c}}", { b: "" })).toThrow(
+      `${PROMPT_VARIABLES_MISSING_ERROR_CODE}: Missing values for {{a}}, {{c}}`,
+    );
+  });
+});
+
+describe("prompt library", () => {
+  let root = "";
+  let workspacePath = "";
+  let clock = new Date(TS);
+  let nextId = 0;
+
+  const state = (): PersistedState => ({
+    version: 2,
+    workspaces: [
+      {
+        id: "ws-1",
+        name: "Billing",
+        path: workspacePath,
+        createdAt: TS,
+        lastOpenedAt: TS,
+        defaultEnableMcp: true,
+        defaultBackupsEnabled: true,
+        yolo: false,
+      },
+    ],
+    threads: [],
+  });
+
+  const createStore = () =>
+    new PromptLibraryStore({
+      globalFilePath: path.join(root, "userData", "prompt-library.json"),
+      loadState: async () => state(),
+      now: () => clock,
+      createId: () => `prompt-${++nextId}`,
+    });
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "prompt-library-"));
+    workspacePath = path.join(root, "workspace");
+    await fs.mkdir(workspacePath, { recursive: true });
+    clock = new Date(TS);
+    nextId = 0;
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  test("keeps global and workspace templates apart and lists both for a workspace", async () => {
+    const store = createStore();
+    const global = await store.save({
+      name: "Write tests",
+      body: "Add tests for {{module}}.",
+      tags: ["Testing", "testing "],
+    });
+    const local = await store.save({
+      workspaceId: "ws-1",
+      name: "Release notes",
+      description: "  For the changelog  ",
+      body: "Summarize changes since {{tag}}.",
+    });
+
+    expect(global).toEqual({
+      id: "prompt-1",
+      scope: "global",
+      name: "Write tests",
+      description: "",
+      body: "Add tests for {{module}}.",
+      variables: ["module"],
+      tags: ["testing"],
+      createdAt: TS,
+      updatedAt: TS,
+    });
+    expect(local).toMatchObject({
+      id: "prompt-2",
+      scope: "workspace",
+      workspaceId: "ws-1",
+      description: "For the changelog",
+      variables: ["tag"],
+    });
+    expect((await store.list()).map((template) => template.id)).toEqual(["prompt-1"]);
+    expect((await store.list("ws-1")).map((template) => template.name)).toEqual([
+      "Release notes",
+      "Write tests",
+    ]);
+
+    const file = JSON.parse(await fs.readFile(resolveWorkspacePromptsPath(workspacePath), "utf8"));
+    expect(file.prompts).toEqual([
+      {
+        id: "prompt-2",
+        name: "Release notes",
+        description: "For the changelog",
+        body: "Summarize changes since {{tag}}.",
+        tags: [],
+        createdAt: TS,
+        updatedAt: TS,
+      },
+    ]);
+  });
+
+  test("updates in place, rejects duplicate names, and deletes", async () => {
+    const store = createStore();
+    const first = await store.save({ name: "Triage", body: "Triage {{issue}}." });
+    await store.save({ name: "Plan", body: "Plan {{feature}}." });
+
+    clock = new Date("2026-01-02T00:00:00.000Z");
+    const updated = await store.save({ id: first.id, name: "Triage", body: "Triage {{ticket}}." });
+    expect(updated).toMatchObject({
+      id: first.id,
+      variables: ["ticket"],
+      createdAt: TS,
+      updatedAt: "2026-01-02T00:00:00.000Z",
+    });
+
+    await expect(store.save({ name: "plan", body: "Again" })).rejects.toThrow(
+      'A prompt template named "Plan" already exists',
+    );
+    await expect(store.save({ id: "missing", name: "New", body: "x" })).rejects.toThrow(
+      "Unknown prompt template: missing",
+    );
+
+    await store.delete({ id: first.id });
+    expect((await store.list()).map((template) => template.name)).toEqual(["Plan"]);
+    await expect(store.delete({ id: first.id })).rejects.toThrow(
+      `Unknown prompt template: ${first.id}`,
+    );
+  });
+
+  test("renders workspace and global templates and reports missing variables", async () => {
+    const store = createStore();
+    const global = await store.save({ name: "Explain", body: "Explain {{symbol}}." });
+    const local = await store.save({ workspaceId: "ws-1", name: "Deploy", body: "Ship {{env}}." });
+
+    expect(
+      await store.render({ templateId: global.id, workspaceId: "ws-1", vars: { symbol: "Foo" } }),
+    ).toEqual({ template: global, text: "Explain Foo." });
+    expect(
+      (await store.render({ templateId: local.id, workspaceId: "ws-1", vars: { env: "prod" } }))
+        .text,
+    ).toBe("Ship prod.");
+    await expect(store.render({ templateId: local.id, vars: { env: "prod" } })).rejects.toThrow(
+      `Unknown prompt template: ${local.id}`,
+    );
+    await expect(store.render({ templateId: global.id, vars: {} })).rejects.toThrow(
+      `${PROMPT_VARIABLES_MISSING_ERROR_CODE}: Missing values for {{symbol}}`,
+    );
+  });
+
+  test("leaves a workspace file it cannot parse untouched", async () => {
+    const filePath = resolveWorkspacePromptsPath(workspacePath);
+    await fs.mkdir(path.dirname(filePath), { recursive: true });
+    await fs.writeFile(filePath, "{ not json", "utf8");
+    const store = createStore();
+
+    expect(await store.list("ws-1")).toEqual([]);
+    await expect(store.save({ workspaceId: "ws-1", name: "Anything", body: "x" })).rejects.toThrow(
+      `Cannot update ${filePath}: Invalid JSON`,
+    );
+    expect(await fs.readFile(filePath, "utf8")).toBe("{ not json");
+  });
+});
@@ -0,0 +1,119 @@
+import { afterEach, describe, expect, test } from "bun:test";
+
+import { WebSocket } from "ws";
+
+import { buildReplayTimeline, ProtocolReplayService } from "../electron/services/protocolReplay";
+import type { TranscriptEvent } from "../src/app/types";
+
+function serverEvent(ts: string, payload: Record<string, unknown>): TranscriptEvent {
+  return { ts, threadId: "thread-1", direction: "server", payload };
+}
+
+const transcript: TranscriptEvent[] = [
+  serverEvent("2026-01-01T00:00:00.000Z", { type: "session_info", sessionId: "sess-1" }),
+  {
+    ts: "2026-01-01T00:00:00.500Z",
+    threadId: "thread-1",
+    direction: "client",
+    payload: { type: "user_message", sessionId: "sess-1", text: "hi" },
+  },
+  serverEvent("2026-01-01T00:00:01.000Z", { type: "session_usage", sessionId: "sess-1" }),
+  serverEvent("2026-01-01T01:00:00.000Z", { type: "turn_usage", sessionId: "sess-1" }),
+];
+
+describe("buildReplayTimeline", () => {
+  test("keeps server events, scales gaps by speed, and caps long pauses", () => {
+    const timeline = buildReplayTimeline(transcript, 2, 5_000);
+
+    expect(timeline.map((entry) => [entry.event.type, entry.offsetMs])).toEqual([
+      ["session_info", 0],
+      ["session_usage", 500],
+      ["turn_usage", 3_000],
+    ]);
+  });
+});
+
+describe("ProtocolReplayService", () => {
+  let service: ProtocolReplayService | null = null;
+
+  afterEach(async () => {
+    await service?.dispose();
+    service = null;
+  });
+
+  test("re-emits the recording to a client that resumes the thread", async () => {
+    service = new ProtocolReplayService({ maxGapMs: 10 });
+    const session = await service.start(
+      {
+        threadId: "thread-1",
+        title: "Recorded thread",
+        createdAt: "2026-01-01T00:00:00.000Z",
+        workspacePath: "/work/project",
+        transcript,
+      },
+      10,
+    );
+    expect(session).toMatchObject({ threadId: "thread-1", sessionId: "sess-1", eventCount: 3 });
+    expect(session.url).toMatch(/^ws:\/\/127\.0\.0\.1:\d+\/ws$/);
+
+    const socket = new WebSocket(session.url, "cowork.jsonrpc.v1");
+    const messages: Array<Record<string, unknown>> = [];
+    const done = new Promise<void>((resolve) => {
+      socket.on("message", (raw) => {
+        const message = JSON.parse(raw.toString()) as Record<string, unknown>;
+        messages.push(message);
+        if (message.method === "cowork/session/turnUsage") {
+          resolve();
+        }
+      });
+    });
+    await new Promise((resolve) => socket.once("open", resolve));
+    socket.send(JSON.stringify({ id: 1, method: "initialize", params: {} }));
+    socket.send(JSON.stringify({ id: 2, method: "thread/resume", params: { threadId: "sess-1" } }));
+    socket.send(JSON.stringify({ id: 3, method: "turn/start", params: {} }));
+    await done;
+    socket.close();
+
+    expect(messages.find((message) => message.id === 2)).toMatchObject({
+      result: { thread: { id: "sess-1", title: "Recorded thread", cwd: "/work/project" } },
+    });
+    expect(messages.find((message) => message.id === 3)).toMatchObject({
+      error: { code: -32601 },
+    });
+    expect(
+      messages.map((message) => message.method).filter((method) => method !== undefined),
+    ).toEqual([
+      "thread/started",
+      "cowork/session/info",
+      "cowork/session/usage",
+      "cowork/session/turnUsage",
+    ]);
+  });
+
+  test("refuses recordings without server events", async () => {
+    service = new ProtocolReplayService();
+    await expect(
+      service.start({
+        threadId: "thread-1",
+        title: "Empty",
+        createdAt: "2026-01-01T00:00:00.000Z",
+        workspacePath: null,
+        transcript: [],
+      }),
+    ).rejects.toThrow("no recorded server events");
+  });
+
+  test("stop closes the replay server", async () => {
+    service = new ProtocolReplayService();
+    const session = await service.start({
+      threadId: "thread-1",
+      title: "Recorded thread",
+      createdAt: "2026-01-01T00:00:00.000Z",
+      workspacePath: null,
+      transcript,
+    });
+
+    expect(await service.stop(session.replayId)).toBe(true);
+    expect(await service.stop(session.replayId)).toBe(false);
+  });
+});
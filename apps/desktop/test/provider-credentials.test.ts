@@ -0,0 +1,168 @@
+import { describe, expect, test } from "bun:test";
+
+import type { ConnectionStore } from "../../../src/store/connections";
+import {
+  buildProviderProbe,
+  parseProviderQuota,
+  ProviderCredentialValidator,
+} from "../electron/services/providerCredentials";
+import { providerCredentialValidationSchema } from "../src/lib/desktopSchemas";
+import { formatCredentialCheck } from "../src/ui/settings/pages/providersPageUtils";
+
+const NOW = Date.parse("2026-05-01T10:00:00Z");
+
+function connectionStore(services: ConnectionStore["services"] = {}): ConnectionStore {
+  return { version: 1, updatedAt: "2026-05-01T09:00:00Z", services };
+}
+
+function createValidator(
+  respond: (url: string, init: RequestInit) => Promise<Response>,
+  opts: { store?: ConnectionStore; env?: NodeJS.ProcessEnv } = {},
+) {
+  const requests: Array<{ url: string; headers: Record<string, string> }> = [];
+  let clock = NOW;
+  const validator = new ProviderCredentialValidator({
+    fetch: async (url, init) => {
+      requests.push({ url, headers: init.headers as Record<string, string> });
+      clock += 120;
+      return await respond(url, init);
+    },
+    readStore: async () => opts.store ?? connectionStore(),
+    env: opts.env ?? {},
+    now: () => clock,
+  });
+  return {
+    validator,
+    requests,
+    advance: (ms: number) => {
+      clock += ms;
+    },
+  };
+}
+
+describe("provider probes", () => {
+  test("use each provider's own auth header", () => {
+    expect(buildProviderProbe("anthropic", "sk-ant")?.headers["x-api-key"]).toBe("sk-ant");
+    expect(buildProviderProbe("baseten", "bt")?.headers.authorization).toBe("Api-Key bt");
+    expect(buildProviderProbe("together", "tg")).toEqual({
+      url: "https://api.together.xyz/v1/models",
+      headers: { authorization: "Bearer tg" },
+    });
+    expect(buildProviderProbe("bedrock", "key")).toBeNull();
+    expect(buildProviderProbe("codex-cli", "key")).toBeNull();
+  });
+
+  test("read OpenAI and Anthropic rate-limit headers", () => {
+    const openai = parseProviderQuota(
+      new Headers({
+        "x-ratelimit-limit-requests": "5000",
+        "x-ratelimit-remaining-requests": "4999",
+        "x-ratelimit-reset-requests": "1m30s",
+      }),
+      NOW,
+    );
+    const anthropic = parseProviderQuota(
+      new Headers({
+        "anthropic-ratelimit-tokens-limit": "80000",
+        "anthropic-ratelimit-tokens-remaining": "79000",
+        "anthropic-ratelimit-requests-reset": "2026-05-01T10:01:00Z",
+      }),
+      NOW,
+    );
+
+    expect(openai).toEqual({
+      requestsLimit: 5000,
+      requestsRemaining: 4999,
+      tokensLimit: null,
+      tokensRemaining: null,
+      resetAt: "2026-05-01T10:01:30.000Z",
+    });
+    expect(anthropic?.tokensRemaining).toBe(79000);
+    expect(anthropic?.resetAt).toBe("2026-05-01T10:01:00.000Z");
+    expect(parseProviderQuota(new Headers(), NOW)).toBeNull();
+  });
+});
+
+describe("provider credential validator", () => {
+  test("checks the saved key and caches the verdict", async () => {
+    const { validator, requests, advance } = createValidator(
+      async () =>
+        new Response("{}", {
+          status: 200,
+          headers: {
+            "x-ratelimit-limit-requests": "100",
+            "x-ratelimit-remaining-requests": "99",
+          },
+        }),
+      {
+        store: connectionStore({
+          openai: {
+            service: "openai",
+            mode: "api_key",
+            apiKey: "sk-saved-1234567890",
+            updatedAt: "2026-05-01T09:00:00Z",
+          },
+        }),
+        env: { OPENAI_API_KEY: "sk-env" },
+      },
+    );
+
+    const first = await validator.validate({ provider: "openai" });
+    const second = await validator.validate({ provider: "openai" });
+    advance(10 * 60_000);
+    const third = await validator.validate({ provider: "openai" });
+
+    expect(first).toMatchObject({
+      status: "valid",
+      keySource: "saved",
+      keyMask: "sk-s...7890",
+      httpStatus: 200,
+      latencyMs: 120,
+      cached: false,
+    });
+    expect(providerCredentialValidationSchema.safeParse(first).success).toBe(true);
+    expect(second.cached).toBe(true);
+    expect(third.cached).toBe(false);
+    expect(requests).toHaveLength(2);
+    expect(requests[0]?.headers.authorization).toBe("Bearer sk-saved-1234567890");
+    expect(formatCredentialCheck(first)).toBe("Key accepted. • 120 ms • 99 of 100 requests left");
+  });
+
+  test("reports rejected keys and does not cache network failures", async () => {
+    let online = true;
+    const { validator, requests } = createValidator(async () => {
+      if (!online) throw new Error("getaddrinfo ENOTFOUND");
+      return new Response("{}", { status: 401 });
+    });
+
+    const rejected = await validator.validate({ provider: "anthropic", apiKey: "sk-bad" });
+    online = false;
+    const offline = await validator.validate({ provider: "anthropic", apiKey: "sk-new" });
+    const retry = await validator.validate({ provider: "anthropic", apiKey: "sk-new" });
+
+    expect(rejected).toMatchObject({ status: "invalid", keySource: "input", httpStatus: 401 });
+    expect(offline.status).toBe("unreachable");
+    expect(offline.message).toContain("ENOTFOUND");
+    expect(retry.cached).toBe(false);
+    expect(requests).toHaveLength(3);
+  });
+
+  test("falls back to the environment and explains missing or unsupported keys", async () => {
+    const { validator, requests } = createValidator(async () => new Response("{}"), {
+      env: { GEMINI_API_KEY: "gem-key" },
+    });
+
+    await expect(validator.validate({ provider: "google" })).resolves.toMatchObject({
+      status: "valid",
+      keySource: "environment",
+    });
+    await expect(validator.validate({ provider: "nvidia" })).resolves.toMatchObject({
+      status: "missing",
+      keySource: null,
+    });
+    await expect(validator.validate({ provider: "lmstudio" })).resolves.toMatchObject({
+      status: "unsupported",
+    });
+    expect(requests.map((request) => request.headers["x-goog-api-key"])).toEqual(["gem-key"]);
+  });
+});
@@ -0,0 +1,122 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  applyProcessNiceness,
+  type ResourceLimitBreach,
+  watchResourceLimits,
+} from "../electron/services/resourceLimits";
+import { normalizeWorkspaceResourceLimits } from "../src/app/workspaceResourceLimits";
+
+const MB = 1024 * 1024;
+
+function waitForBreach(): {
+  promise: Promise<ResourceLimitBreach>;
+  resolve: (breach: ResourceLimitBreach) => void;
+} {
+  let resolve: (breach: ResourceLimitBreach) => void = () => {};
+  const promise = new Promise<ResourceLimitBreach>((done) => {
+    resolve = done;
+  });
+  return { promise, resolve };
+}
+
+describe("workspace resource limits", () => {
+  test("normalizes persisted limits into range and drops empty records", () => {
+    expect(
+      normalizeWorkspaceResourceLimits({ maxMemoryMb: 64, niceness: 42.7, maxLifetimeMinutes: 0 }),
+    ).toEqual({ maxMemoryMb: 256, niceness: 19, maxLifetimeMinutes: 1 });
+    expect(normalizeWorkspaceResourceLimits({ maxMemoryMb: 2048.9 })).toEqual({
+      maxMemoryMb: 2048,
+    });
+    expect(normalizeWorkspaceResourceLimits({ maxMemoryMb: "2048", niceness: null })).toBe(
+      undefined,
+    );
+    expect(normalizeWorkspaceResourceLimits(null)).toBe(undefined);
+    expect(normalizeWorkspaceResourceLimits({ startupTimeoutSeconds: 1 })).toEqual({
+      startupTimeoutSeconds: 5,
+    });
+    expect(normalizeWorkspaceResourceLimits({ startupTimeoutSeconds: 900 })).toEqual({
+      startupTimeoutSeconds: 300,
+    });
+  });
+
+  test("applies niceness and reports platforms that refuse it", () => {
+    const calls: Array<[number, number]> = [];
+    expect(applyProcessNiceness(123, 10, (pid, priority) => calls.push([pid, priority]))).toBe(
+      true,
+    );
+    expect(calls).toEqual([[123, 10]]);
+    expect(
+      applyProcessNiceness(123, 10, () => {
+        throw new Error("EACCES");
+      }),
+    ).toBe(false);
+  });
+
+  test("reports the first memory breach and stops polling", async () => {
+    const breach = waitForBreach();
+    const breaches: ResourceLimitBreach[] = [];
+    let reads = 0;
+    const stop = watchResourceLimits({
+      pid: 123,
+      startedAt: Date.now(),
+      limits: { maxMemoryMb: 256 },
+      pollIntervalMs: 5,
+      readUsage: async () => {
+        reads += 1;
+        return { cpuTimeMs: 0, rssBytes: reads >= 2 ? 512 * MB : 128 * MB };
+      },
+      onExceeded: (value) => {
+        breaches.push(value);
+        breach.resolve(value);
+      },
+    });
+
+    await expect(breach.promise).resolves.toEqual({
+      limit: "memory",
+      rssBytes: 512 * MB,
+      maxBytes: 256 * MB,
+    });
+    const readsAtBreach = reads;
+    await new Promise((resolve) => setTimeout(resolve, 30));
+    stop();
+
+    expect(breaches).toHaveLength(1);
+    expect(reads).toBe(readsAtBreach);
+  });
+
+  test("reports a lifetime breach once the sidecar has run past its limit", async () => {
+    const breach = waitForBreach();
+    const startedAt = 1_000;
+    watchResourceLimits({
+      pid: 123,
+      startedAt,
+      limits: { maxLifetimeMinutes: 1 },
+      now: () => startedAt + 90_000,
+      readUsage: async () => null,
+      onExceeded: breach.resolve,
+    });
+
+    await expect(breach.promise).resolves.toEqual({
+      limit: "lifetime",
+      uptimeMs: 90_000,
+      maxMs: 60_000,
+    });
+  });
+
+  test("stopping the watch cancels pending limits", async () => {
+    const breaches: ResourceLimitBreach[] = [];
+    const stop = watchResourceLimits({
+      pid: 123,
+      startedAt: Date.now(),
+      limits: { maxMemoryMb: 256, maxLifetimeMinutes: 1 },
+      pollIntervalMs: 5,
+      readUsage: async () => ({ cpuTimeMs: 0, rssBytes: 1024 * MB }),
+      onExceeded: (value) => breaches.push(value),
+    });
+    stop();
+    await new Promise((resolve) => setTimeout(resolve, 30));
+
+    expect(breaches).toEqual([]);
+  });
+});
@@ -0,0 +1,175 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  type RecordScheduledRunInput,
+  ScheduledTaskService,
+} from "../electron/services/scheduledTasks";
+import type { PersistedState, ThreadRecord } from "../src/app/types";
+import type { ScheduledTaskRun } from "../src/lib/desktopApi";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+const state: PersistedState = {
+  version: 2,
+  workspaces: [
+    {
+      id: "ws-1",
+      name: "Project",
+      path: "/work/project",
+      createdAt: TS,
+      lastOpenedAt: TS,
+      defaultEnableMcp: true,
+      defaultBackupsEnabled: true,
+      yolo: true,
+    },
+  ],
+  threads: [],
+};
+
+const notifications: Array<{ method: string; params: unknown }> = [
+  {
+    method: "item/completed",
+    params: { turnId: "turn-1", item: { type: "agentMessage", text: "All tests pass." } },
+  },
+  { method: "item/completed", params: { turnId: "turn-1", item: { type: "reasoning" } } },
+  { method: "turn/completed", params: { turn: { id: "turn-1", status: "completed" } } },
+];
+
+describe("scheduled tasks", () => {
+  let root = "";
+  let clock = new Date(2026, 0, 1, 8, 59);
+  let requests: Array<{ method: string; params?: unknown }> = [];
+  let recorded: RecordScheduledRunInput[] = [];
+
+  const createService = (overrides: {
+    startWorkspaceServer?: () => Promise<{ url: string; authToken: string | null }>;
+    onRunCompleted?: (run: ScheduledTaskRun) => void;
+  }) =>
+    new ScheduledTaskService({
+      filePath: path.join(root, "scheduled-tasks.json"),
+      clientVersion: "1.0.0",
+      loadState: async () => state,
+      startWorkspaceServer:
+        overrides.startWorkspaceServer ??
+        (async () => ({ url: "ws://127.0.0.1:1/ws", authToken: "token" })),
+      recordRun: async (input) => {
+        recorded.push(input);
+        return { id: "thread-1", workspaceId: "ws-1", title: input.title } as ThreadRecord;
+      },
+      onRunCompleted: overrides.onRunCompleted,
+      connectJsonRpc: async (options) => {
+        expect(options).toMatchObject({ clientName: "desktop-scheduler", authToken: "token" });
+        return {
+          sendRequest: async (method, params) => {
+            requests.push({ method, params });
+            return method === "thread/start"
+              ? { result: { thread: { id: "session-1" } } }
+              : { result: { turn: { id: "turn-1" } } };
+          },
+          waitFor: async (predicate) => {
+            const match = notifications.find(predicate);
+            if (!match) {
+              throw new Error("no match");
+            }
+            return match;
+          },
+          close: () => {},
+        };
+      },
+      now: () => clock,
+      createId: () => "task-1",
+    });
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-scheduled-tasks-"));
+    clock = new Date(2026, 0, 1, 8, 59);
+    requests = [];
+    recorded = [];
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  test("runs a due task as a new thread and schedules the next run", async () => {
+    let resolveRun: (run: ScheduledTaskRun) => void = () => {};
+    const completed = new Promise<ScheduledTaskRun>((resolve) => {
+      resolveRun = resolve;
+    });
+    const service = createService({ onRunCompleted: (run) => resolveRun(run) });
+    const threads: string[] = [];
+    service.onThreadRecorded((thread) => threads.push(thread.id));
+
+    await expect(
+      service.create({ workspaceId: "ws-1", cronExpr: "daily", prompt: "Run the tests" }),
+    ).rejects.toThrow("expected 5 fields");
+    const task = await service.create({
+      workspaceId: "ws-1",
+      cronExpr: "0 9 * * *",
+      prompt: "Run the tests\nand summarize failures",
+    });
+    expect(task).toMatchObject({
+      name: "Run the tests",
+      enabled: true,
+      nextRunAt: new Date(2026, 0, 1, 9, 0).toISOString(),
+    });
+    expect(await service.runDueTasks()).toBe(0);
+
+    clock = new Date(2026, 0, 1, 9, 0, 5);
+    expect(await service.runDueTasks()).toBe(1);
+    const run = await completed;
+
+    expect(run).toMatchObject({ taskId: "task-1", status: "completed", threadId: "thread-1" });
+    expect(threads).toEqual(["thread-1"]);
+    expect(requests).toEqual([
+      { method: "thread/start", params: { cwd: "/work/project" } },
+      {
+        method: "turn/start",
+        params: { threadId: "session-1", input: "Run the tests\nand summarize failures" },
+      },
+    ]);
+    expect(recorded[0]?.events.map((event) => [event.direction, event.payload])).toEqual([
+      ["client", { type: "user_message", sessionId: "session-1", text: task.prompt }],
+      ["server", { type: "user_message", sessionId: "session-1", text: task.prompt }],
+      ["server", { type: "assistant_message", sessionId: "session-1", text: "All tests pass." }],
+    ]);
+    const [listed] = await service.list();
+    expect(listed?.lastRun).toEqual(run);
+    expect(listed?.nextRunAt).toBe(new Date(2026, 0, 2, 9, 0).toISOString());
+  });
+
+  test("reports a failed run and skips disabled tasks", async () => {
+    const runs: ScheduledTaskRun[] = [];
+    let resolveRun: () => void = () => {};
+    const completed = new Promise<void>((resolve) => {
+      resolveRun = resolve;
+    });
+    const service = createService({
+      startWorkspaceServer: async () => {
+        throw new Error("sidecar missing");
+      },
+      onRunCompleted: (run) => {
+        runs.push(run);
+        resolveRun();
+      },
+    });
+    await service.create({ workspaceId: "ws-1", cronExpr: "@hourly", prompt: "Check CI" });
+    await service.setEnabled("task-1", false);
+    clock = new Date(2026, 0, 1, 10, 0);
+    expect(await service.runDueTasks()).toBe(0);
+    expect((await service.list())[0]?.nextRunAt).toBeNull();
+
+    await service.setEnabled("task-1", true);
+    clock = new Date(2026, 0, 1, 11, 0);
+    expect(await service.runDueTasks()).toBe(1);
+    await completed;
+
+    expect(runs).toEqual([
+      expect.objectContaining({ status: "failed", threadId: null, error: "sidecar missing" }),
+    ]);
+    expect(recorded).toEqual([]);
+  });
+});
@@ -13,6 +13,8 @@ import {
   WINDOWS_SANDBOX_SETUP_NAME,
 } from "../../../src/platform/sandbox/windows";
 
+import { ServerOutputReaders } from "../electron/services/serverOutputReaders";
+import { MIN_SUPPORTED_SERVER_PROTOCOL_VERSION } from "../electron/services/serverVersion";
 import {
   resolvePackagedSidecarFilename,
   resolveWindowsAiElectronPrebuildTriplet,
@@ -376,6 +378,137 @@ describe("desktop server manager startup parsing", () => {
     child.emit("exit", 0, null);
   });
 
+  test("waitForServerListening hands out a stop that cancels startup and detaches stdout", async () => {
+    const child = createFakeChild();
+    const readers = new ServerOutputReaders<FakeChild>();
+    const stdoutLines: string[] = [];
+    const waitPromise = __internal.waitForServerListening(child as any, {
+      onStdoutLine: (line: string) => {
+        stdoutLines.push(line);
+      },
+      trackReader: (stop: () => void) => readers.track(child, stop),
+    });
+    expect(readers.activeCount).toBe(1);
+
+    expect(readers.release(child)).toBe(1);
+    await expect(waitPromise).rejects.toThrow("Server startup was cancelled");
+    child.stdout.write("late output\n");
+    await Bun.sleep(0);
+
+    expect(stdoutLines).toEqual([]);
+    expect(child.listenerCount("exit")).toBe(0);
+    expect(readers.activeCount).toBe(0);
+  });
+
+  test("server output readers stop each child's readers once and sweep leftovers", () => {
+    const readers = new ServerOutputReaders<object>();
+    const first = {};
+    const second = {};
+    const stopped: string[] = [];
+    readers.track(first, () => stopped.push("first:stdout"));
+    readers.track(first, () => {
+      throw new Error("stream already destroyed");
+    });
+    readers.track(second, () => stopped.push("second:stdout"));
+
+    expect(readers.release(first)).toBe(2);
+    expect(readers.release(first)).toBe(0);
+    expect(readers.releaseAll()).toBe(1);
+    expect(stopped).toEqual(["first:stdout", "second:stdout"]);
+    expect(readers.activeCount).toBe(0);
+  });
+
+  test("waitForServerHealthy polls /cowork/health until the server answers", async () => {
+    const child = createFakeChild();
+    const urls: string[] = [];
+    const statuses = [503, 503, 200];
+
+    await __internal.waitForServerHealthy(child as any, "ws://127.0.0.1:1234/ws?token=abc", {
+      fetch: (async (url: string | URL | Request) => {
+        urls.push(String(url));
+        return new Response("", { status: statuses.shift() ?? 200 });
+      }) as typeof fetch,
+      timeoutMs: 1_000,
+      pollIntervalMs: 1,
+    });
+
+    expect(urls).toHaveLength(3);
+    expect(new URL(urls[0] ?? "").pathname).toBe("/cowork/health");
+  });
+
+  test("waitForServerHealthy reports the exit code or the last failure", async () => {
+    const exiting = createFakeChild();
+    const exitPromise = __internal.waitForServerHealthy(exiting as any, "ws://127.0.0.1:1/ws", {
+      fetch: (async () => {
+        throw new Error("ECONNREFUSED");
+      }) as unknown as typeof fetch,
+      timeoutMs: 5_000,
+      pollIntervalMs: 1_000,
+    });
+    await Bun.sleep(0);
+    exiting.emit("exit", 3, null);
+
+    await expect(exitPromise).rejects.toThrow(
+      "Server exited before passing health check (code=3, signal=null)",
+    );
+    await expect(
+      __internal.waitForServerHealthy(createFakeChild() as any, "ws://127.0.0.1:1/ws", {
+        fetch: (async () => new Response("", { status: 503 })) as unknown as typeof fetch,
+        timeoutMs: 20,
+        pollIntervalMs: 5,
+      }),
+    ).rejects.toThrow("(last=HTTP 503)");
+  });
+
+  test("gracefulKill asks Windows servers to stop over the shutdown endpoint", async () => {
+    const child = createFakeChild();
+    const kills: Array<NodeJS.Signals | number | undefined> = [];
+    child.kill = (signal) => {
+      kills.push(signal);
+      return true;
+    };
+    const requests: Array<{ url: string; token: string | null }> = [];
+    __internal.registerServerShutdownEndpoint(child as any, "ws://127.0.0.1:1234/ws", "tok");
+
+    await __internal.gracefulKill(child as any, {
+      platform: "win32",
+      fetch: (async (url: string | URL | Request, init?: RequestInit) => {
+        requests.push({
+          url: String(url),
+          token: new Headers(init?.headers).get("x-cowork-shutdown-token"),
+        });
+        queueMicrotask(() => {
+          child.exitCode = 0;
+          child.emit("exit", 0, null);
+        });
+        return new Response(null, { status: 202 });
+      }) as typeof fetch,
+    });
+
+    expect(requests).toEqual([{ url: "http://127.0.0.1:1234/cowork/shutdown", token: "tok" }]);
+    expect(kills).toEqual([]);
+  });
+
+  test("gracefulKill terminates Windows servers when the shutdown request fails", async () => {
+    const child = createFakeChild();
+    const kill = child.kill;
+    const kills: Array<NodeJS.Signals | number | undefined> = [];
+    child.kill = (signal) => {
+      kills.push(signal);
+      return kill(signal);
+    };
+    __internal.registerServerShutdownEndpoint(child as any, "ws://127.0.0.1:1/ws", "tok");
+
+    await __internal.gracefulKill(child as any, {
+      platform: "win32",
+      fetch: (async () => {
+        throw new Error("ECONNREFUSED");
+      }) as unknown as typeof fetch,
+    });
+
+    expect(kills).toEqual([undefined]);
+  });
+
   test("withStderrTail appends compact server stderr diagnostics", () => {
     expect(__internal.withStderrTail("startup failed", " first line\n\nsecond\tline ")).toBe(
       "startup failed; stderr=first line second line",
@@ -383,6 +516,53 @@ describe("desktop server manager startup parsing", () => {
     expect(__internal.withStderrTail("startup failed", " \n ")).toBe("startup failed");
   });
 
+  test("startup diagnostics report a missing launcher as a spawn failure", () => {
+    const launch = {
+      mode: "source" as const,
+      command: "bun",
+      args: ["/repo/src/server/index.ts", "--dir", "/work", "--json"],
+      cwd: "/repo",
+    };
+    const missing = Object.assign(new Error("spawn bun ENOENT"), {
+      code: "ENOENT",
+      syscall: "spawn bun",
+    });
+
+    expect(
+      __internal.buildStartupDiagnostics({
+        workspaceId: "ws-1",
+        stage: "startup",
+        error: missing,
+        launch,
+        stderrTail: "",
+        exitCode: null,
+        signal: null,
+        failedAt: new Date("2026-01-01T00:00:00.000Z"),
+      }),
+    ).toEqual({
+      workspaceId: "ws-1",
+      stage: "spawn",
+      message: "spawn bun ENOENT",
+      ...launch,
+      launcherFound: false,
+      stderrTail: "",
+      exitCode: null,
+      signal: null,
+      failedAt: "2026-01-01T00:00:00.000Z",
+    });
+
+    const exited = __internal.buildStartupDiagnostics({
+      workspaceId: "ws-1",
+      stage: "startup",
+      error: new Error("Server exited before startup JSON (code=1, signal=null)"),
+      launch,
+      stderrTail: "error: boom",
+      exitCode: 1,
+      signal: null,
+    });
+    expect(exited).toMatchObject({ stage: "startup", launcherFound: true, exitCode: 1 });
+  });
+
   test("server output mirror prefixes complete lines and flushes partial chunks", () => {
     const stdoutWrites: string[] = [];
     const stderrWrites: string[] = [];
@@ -455,6 +635,19 @@ describe("desktop server manager startup mode", () => {
     }
   });
 
+  test("buildSpawnArgs passes each additional workspace root as --root", () => {
+    const baseArgs = ["--dir", "/work/app", "--port", "0", "--json"];
+    expect(__internal.buildSpawnArgs("/work/app", false)).toEqual(baseArgs);
+    expect(
+      __internal.buildSpawnArgs("/work/app", false, false, ["/work/lib", "/work/docs"]),
+    ).toEqual([...baseArgs, "--root", "/work/lib", "--root", "/work/docs"]);
+  });
+
+  test("buildSpawnArgs appends launch profile args after the app's own flags", () => {
+    const args = __internal.buildSpawnArgs("/work/app", true, false, [], ["--model", "o3"]);
+    expect(args.slice(-3)).toEqual(["--yolo", "--model", "o3"]);
+  });
+
   test("buildServerEnv enables independent marketplace and runtime downloads by default", () => {
     const env = __internal.buildServerEnv();
     expect(env).not.toBe(process.env);
@@ -1251,6 +1444,31 @@ describe("desktop server manager bun crash detection", () => {
     ).toBe(false);
   });
 
+  test("naming a different launch profile replaces the running workspace server", () => {
+    const profile = { name: "Staging", args: ["--model", "o3"], env: {} };
+    const existing = {
+      child: createFakeChild(),
+      url: "ws://127.0.0.1:7337/ws",
+      mobileH3: null,
+      launchProfileKey: __internal.launchProfileKey(profile),
+      cleanup: () => {},
+    };
+
+    expect(__internal.shouldReuseExistingWorkspaceServer({}, existing as never)).toBe(true);
+    expect(
+      __internal.shouldReuseExistingWorkspaceServer(
+        { launchProfile: { ...profile, name: "Renamed" } },
+        existing as never,
+      ),
+    ).toBe(true);
+    expect(
+      __internal.shouldReuseExistingWorkspaceServer(
+        { launchProfile: { ...profile, args: ["--model", "o4-mini"] } },
+        existing as never,
+      ),
+    ).toBe(false);
+  });
+
   test("reusing a healthy workspace server does not increment restart diagnostics", async () => {
     const workspacePath = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-server-reuse-"));
     try {
@@ -1324,6 +1542,165 @@ describe("desktop server manager bun crash detection", () => {
     ]);
   });
 
+  test("remote workspace servers run over ssh and end when their tunnel drops", async () => {
+    const session = Object.assign(createFakeChild(), { stdin: new PassThrough() });
+    const launches: Array<{ target: unknown; args: readonly string[] }> = [];
+    let tunnelClosed = false;
+    let dropTunnel = () => {};
+    const remoteWorkspaces = {
+      launchServer: (
+        target: unknown,
+        opts: { args: readonly string[]; env: Record<string, string> },
+      ) => {
+        launches.push({ target, args: opts.args });
+        setTimeout(() => {
+          session.stdout.write(
+            `${JSON.stringify({
+              type: "server_listening",
+              protocolVersion: MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+              url: "ws://127.0.0.1:41000/ws",
+              port: 41000,
+              cwd: "/srv/project",
+              authToken: opts.env.COWORK_SERVER_AUTH_TOKEN,
+            })}\n`,
+          );
+        }, 0);
+        return session;
+      },
+      openTunnel: async (_target: unknown, remoteUrl: string) => {
+        expect(remoteUrl).toBe("ws://127.0.0.1:41000/ws");
+        return {
+          url: "ws://127.0.0.1:50123/ws",
+          localPort: 50123,
+          closed: new Promise<void>((resolve) => {
+            dropTunnel = resolve;
+          }),
+          close: () => {
+            tunnelClosed = true;
+          },
+        };
+      },
+    };
+    const healthChecks: string[] = [];
+    const exits: unknown[] = [];
+    const manager = new ServerManager({
+      remoteWorkspaces: remoteWorkspaces as never,
+      fetch: (async (url: string | URL | Request) => {
+        healthChecks.push(String(url));
+        return new Response("{}", { status: 200 });
+      }) as unknown as typeof fetch,
+      onWorkspaceServerExited: (event) => exits.push(event),
+    });
+
+    const started = await manager.startWorkspaceServer({
+      workspaceId: "ws-remote",
+      workspacePath: "ssh://dev@build.example.com/srv/project",
+      yolo: false,
+    });
+
+    expect(started.url).toBe("ws://127.0.0.1:50123/ws");
+    expect(launches[0]?.target).toEqual({
+      user: "dev",
+      host: "build.example.com",
+      port: null,
+      path: "/srv/project",
+    });
+    expect(launches[0]?.args.slice(0, 2)).toEqual(["--dir", "/srv/project"]);
+    expect(healthChecks).toEqual(["http://127.0.0.1:50123/cowork/health"]);
+
+    dropTunnel();
+    await new Promise((resolve) => setTimeout(resolve, 0));
+
+    expect(tunnelClosed).toBe(true);
+    expect(manager.getRunningServerIds()).toEqual([]);
+    expect(exits).toEqual([expect.objectContaining({ workspaceId: "ws-remote", code: 0 })]);
+  });
+
+  test("container workspace servers publish a port and remove the container on stop", async () => {
+    const workspacePath = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-container-ws-"));
+    const child = createFakeChild();
+    const launches: Array<{
+      containerName: string;
+      image: string;
+      hostPort: number;
+      args: readonly string[];
+    }> = [];
+    const removed: string[] = [];
+    const containerWorkspaces = {
+      allocateHostPort: async () => 50200,
+      removeContainer: async (name: string) => {
+        removed.push(name);
+      },
+      launchServer: (opts: {
+        containerName: string;
+        launch: { image: string };
+        hostPort: number;
+        args: readonly string[];
+        env: Record<string, string>;
+      }) => {
+        launches.push({
+          containerName: opts.containerName,
+          image: opts.launch.image,
+          hostPort: opts.hostPort,
+          args: opts.args,
+        });
+        setTimeout(() => {
+          child.stdout.write(
+            `${JSON.stringify({
+              type: "server_listening",
+              protocolVersion: MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+              url: "ws://0.0.0.0:7337/ws",
+              port: 7337,
+              cwd: "/workspaces/project",
+              authToken: opts.env.COWORK_SERVER_AUTH_TOKEN,
+            })}\n`,
+          );
+        }, 0);
+        return child;
+      },
+    };
+    const healthChecks: string[] = [];
+    const manager = new ServerManager({
+      containerWorkspaces: containerWorkspaces as never,
+      fetch: (async (url: string | URL | Request) => {
+        healthChecks.push(String(url));
+        return new Response("{}", { status: 200 });
+      }) as unknown as typeof fetch,
+    });
+
+    try {
+      const started = await manager.startWorkspaceServer({
+        workspaceId: "ws-container",
+        workspacePath,
+        yolo: false,
+        container: { image: "node:22" },
+      });
+
+      expect(started.url).toBe("ws://127.0.0.1:50200/ws");
+      expect(launches).toHaveLength(1);
+      expect(launches[0]).toMatchObject({
+        containerName: "cowork-workspace-ws-container",
+        image: "node:22",
+        hostPort: 50200,
+      });
+      expect(launches[0]?.args.slice(0, 2)).toEqual([
+        "--dir",
+        `/workspaces/${path.basename(workspacePath)}`,
+      ]);
+      expect(launches[0]?.args.slice(-4)).toEqual(["--host", "0.0.0.0", "--port", "7337"]);
+      expect(healthChecks).toEqual(["http://127.0.0.1:50200/cowork/health"]);
+      expect(removed).toEqual(["cowork-workspace-ws-container"]);
+
+      await manager.stopWorkspaceServer("ws-container");
+
+      expect(child.exitCode).toBe(0);
+      expect(manager.getRunningServerIds()).toEqual([]);
+      expect(removed).toEqual(["cowork-workspace-ws-container", "cowork-workspace-ws-container"]);
+    } finally {
+      await fs.rm(workspacePath, { recursive: true, force: true });
+    }
+  });
+
   test("stopWorkspaceServer suppresses active server exit notifications", async () => {
     const child = createFakeChild();
     const exits: unknown[] = [];
@@ -0,0 +1,217 @@
+import { describe, expect, test } from "bun:test";
+import { EventEmitter } from "node:events";
+
+import { normalizeAppSettings } from "../electron/services/appSettings";
+import { ServerPowerManager } from "../electron/services/powerManagement";
+import type { WorkspaceIdlePolicy } from "../src/app/types";
+import { normalizeWorkspaceIdlePolicy } from "../src/app/workspaceIdlePolicy";
+import type {
+  IdleServerSuspendMode,
+  ServerResumedEvent,
+  ServerSuspendedEvent,
+} from "../src/lib/desktopApi";
+
+const MINUTE_MS = 60_000;
+
+function createPowerSource(onBattery: boolean) {
+  const emitter = new EventEmitter();
+  return Object.assign(emitter, { isOnBatteryPower: () => onBattery });
+}
+
+function createHarness(
+  options: {
+    mode?: IdleServerSuspendMode;
+    onBattery?: boolean;
+    idlePolicies?: Record<string, WorkspaceIdlePolicy>;
+    failFlush?: boolean;
+  } = {},
+) {
+  let now = Date.parse("2026-03-01T09:00:00.000Z");
+  const running = new Set(["ws-alpha", "ws-beta"]);
+  const stopped: string[] = [];
+  const flushed: string[] = [];
+  const suspended: ServerSuspendedEvent[] = [];
+  const resumed: ServerResumedEvent[] = [];
+  const power = createPowerSource(options.onBattery ?? true);
+  const manager = new ServerPowerManager({
+    getSettings: async () => ({
+      idleServerSuspend: options.mode ?? "onBattery",
+      idleServerTimeoutMinutes: 30,
+    }),
+    getRunningServerIds: () => [...running],
+    canSuspend: (serverId) => serverId !== "ws-relay",
+    loadThreads: async () => [
+      { id: "thread-a", workspaceId: "ws-alpha" },
+      { id: "thread-b", workspaceId: "ws-beta" },
+    ],
+    loadIdlePolicies: async () =>
+      Object.entries(options.idlePolicies ?? {}).map(([id, idlePolicy]) => ({ id, idlePolicy })),
+    flushServer: async (serverId) => {
+      if (options.failFlush) {
+        throw new Error("flush timed out");
+      }
+      flushed.push(serverId);
+    },
+    stopServer: async (serverId) => {
+      running.delete(serverId);
+      stopped.push(serverId);
+    },
+    onSuspended: (event) => suspended.push(event),
+    onResumed: (event) => resumed.push(event),
+    now: () => now,
+    setInterval: () => 0 as unknown as ReturnType<typeof setInterval>,
+    clearInterval: () => {},
+  });
+  manager.start(power as unknown as Parameters<ServerPowerManager["start"]>[0]);
+  return {
+    manager,
+    power,
+    running,
+    stopped,
+    flushed,
+    suspended,
+    resumed,
+    advance: (ms: number) => {
+      now += ms;
+    },
+  };
+}
+
+describe("server power management", () => {
+  test("stops servers without transcript activity past the timeout", async () => {
+    const harness = createHarness();
+    harness.running.add("ws-relay");
+    for (const serverId of harness.running) {
+      harness.manager.recordServerStarted(serverId);
+    }
+
+    harness.advance(20 * MINUTE_MS);
+    harness.manager.recordTranscriptActivity(["thread-b", "thread-unsaved"]);
+    harness.advance(15 * MINUTE_MS);
+
+    expect(await harness.manager.sweep()).toEqual(["ws-alpha"]);
+    expect(harness.suspended).toEqual([
+      {
+        workspaceId: "ws-alpha",
+        idleMinutes: 35,
+        onBattery: true,
+        suspendedAt: "2026-03-01T09:35:00.000Z",
+      },
+    ]);
+    expect(harness.manager.isSuspended("ws-alpha")).toBe(true);
+    expect([...harness.running].sort()).toEqual(["ws-beta", "ws-relay"]);
+  });
+
+  test("reports a resume when a suspended server is started again", async () => {
+    const harness = createHarness();
+    harness.manager.recordServerStarted("ws-alpha");
+    harness.manager.recordServerStarted("ws-beta");
+    harness.advance(31 * MINUTE_MS);
+    await harness.manager.sweep();
+
+    harness.advance(5 * MINUTE_MS);
+    harness.manager.recordServerStarted("ws-alpha");
+    expect(harness.resumed).toEqual([
+      {
+        workspaceId: "ws-alpha",
+        suspendedAt: "2026-03-01T09:31:00.000Z",
+        resumedAt: "2026-03-01T09:36:00.000Z",
+      },
+    ]);
+    expect(harness.manager.isSuspended("ws-alpha")).toBe(false);
+    harness.manager.recordServerStarted("ws-alpha");
+    expect(harness.resumed).toHaveLength(1);
+  });
+
+  test("time asleep does not count toward the idle timeout", async () => {
+    const harness = createHarness();
+    harness.manager.recordServerStarted("ws-alpha");
+    harness.manager.recordServerStarted("ws-beta");
+    harness.advance(10 * MINUTE_MS);
+    harness.power.emit("suspend");
+    harness.advance(8 * 60 * MINUTE_MS);
+    expect(await harness.manager.sweep()).toEqual([]);
+    harness.power.emit("resume");
+    harness.advance(10 * MINUTE_MS);
+
+    expect(await harness.manager.sweep()).toEqual([]);
+    harness.advance(10 * MINUTE_MS);
+    expect((await harness.manager.sweep()).sort()).toEqual(["ws-alpha", "ws-beta"]);
+  });
+
+  test("the onBattery mode only suspends servers while off AC power", async () => {
+    const harness = createHarness({ onBattery: false });
+    harness.manager.recordServerStarted("ws-alpha");
+    harness.manager.recordServerStarted("ws-beta");
+    harness.advance(45 * MINUTE_MS);
+    expect(await harness.manager.sweep()).toEqual([]);
+
+    harness.power.emit("on-battery");
+    await harness.manager.sweep();
+    expect(harness.stopped.sort()).toEqual(["ws-alpha", "ws-beta"]);
+
+    const never = createHarness({ mode: "never" });
+    never.manager.recordServerStarted("ws-alpha");
+    never.advance(24 * 60 * MINUTE_MS);
+    expect(await never.manager.sweep()).toEqual([]);
+    never.manager.dispose();
+    expect(never.power.listenerCount("suspend")).toBe(0);
+  });
+
+  test("a workspace's idle policy overrides the app settings", async () => {
+    const harness = createHarness({
+      mode: "never",
+      onBattery: false,
+      idlePolicies: { "ws-beta": { suspend: "always", timeoutMinutes: 5 } },
+    });
+    harness.manager.recordServerStarted("ws-alpha");
+    harness.manager.recordServerStarted("ws-beta");
+    harness.advance(4 * MINUTE_MS);
+    expect(await harness.manager.sweep()).toEqual([]);
+
+    harness.advance(2 * MINUTE_MS);
+    expect(await harness.manager.sweep()).toEqual(["ws-beta"]);
+    expect(harness.flushed).toEqual(["ws-beta"]);
+    expect(harness.suspended[0]).toMatchObject({ workspaceId: "ws-beta", idleMinutes: 6 });
+
+    const pinned = createHarness({
+      mode: "always",
+      idlePolicies: { "ws-alpha": { suspend: "never", timeoutMinutes: 1 } },
+    });
+    pinned.manager.recordServerStarted("ws-alpha");
+    pinned.manager.recordServerStarted("ws-beta");
+    pinned.advance(60 * MINUTE_MS);
+    expect(await pinned.manager.sweep()).toEqual(["ws-beta"]);
+  });
+
+  test("a failed flush still stops the idle server", async () => {
+    const harness = createHarness({ mode: "always", failFlush: true });
+    harness.manager.recordServerStarted("ws-alpha");
+    harness.advance(31 * MINUTE_MS);
+
+    expect(await harness.manager.sweep()).toEqual(["ws-alpha"]);
+    expect(harness.stopped).toEqual(["ws-alpha"]);
+    expect(harness.suspended.map((event) => event.workspaceId)).toEqual(["ws-alpha"]);
+  });
+
+  test("workspace idle policies clamp their timeout and drop unknown modes", () => {
+    expect(normalizeWorkspaceIdlePolicy({ suspend: "always", timeoutMinutes: 0 })).toEqual({
+      suspend: "always",
+      timeoutMinutes: 1,
+    });
+    expect(normalizeWorkspaceIdlePolicy({ suspend: "never", timeoutMinutes: 1e9 })).toEqual({
+      suspend: "never",
+      timeoutMinutes: 24 * 60,
+    });
+    expect(normalizeWorkspaceIdlePolicy({ suspend: "sometimes", timeoutMinutes: 5 })).toBe(
+      undefined,
+    );
+  });
+
+  test("settings fall back to suspending on battery after at least a minute", () => {
+    expect(
+      normalizeAppSettings({ idleServerSuspend: "sometimes", idleServerTimeoutMinutes: 0 }),
+    ).toMatchObject({ idleServerSuspend: "onBattery", idleServerTimeoutMinutes: 1 });
+    expect(normalizeAppSettings({ idleServerSuspend: "always" }).idleServerSuspend).toBe("always");
+  });
+});
@@ -0,0 +1,71 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  describeServerStartupFailure,
+  formatStartupCommandLine,
+} from "../src/app/serverStartupGuidance";
+import type { WorkspaceServerStartupDiagnostics } from "../src/lib/desktopApi";
+
+function diagnostics(
+  overrides: Partial<WorkspaceServerStartupDiagnostics> = {},
+): WorkspaceServerStartupDiagnostics {
+  return {
+    workspaceId: "ws-1",
+    stage: "startup",
+    message: "Server exited before startup JSON (code=1, signal=null)",
+    mode: "source",
+    command: "bun",
+    args: ["/repo/src/server/index.ts", "--dir", "/Users/me/My Project", "--json"],
+    cwd: "/repo",
+    launcherFound: true,
+    stderrTail: "",
+    exitCode: 1,
+    signal: null,
+    failedAt: "2026-01-01T00:00:00.000Z",
+    ...overrides,
+  };
+}
+
+describe("server startup guidance", () => {
+  test("quotes arguments that would not survive a shell", () => {
+    expect(formatStartupCommandLine(diagnostics())).toBe(
+      'bun /repo/src/server/index.ts --dir "/Users/me/My Project" --json',
+    );
+  });
+
+  test("points at installing Bun or reinstalling when the launcher is missing", () => {
+    const missingBun = describeServerStartupFailure(
+      diagnostics({ stage: "spawn", launcherFound: false, exitCode: null }),
+    );
+    expect(missingBun.summary).toContain("Bun could not be found");
+    expect(missingBun.steps[0]).toContain("https://bun.sh");
+
+    const missingSidecar = describeServerStartupFailure(
+      diagnostics({ mode: "packaged", stage: "spawn", launcherFound: false, exitCode: null }),
+    );
+    expect(missingSidecar.steps[0]).toBe("Reinstall Cowork to restore the server.");
+  });
+
+  test("reads the stderr tail for known causes when the server exits", () => {
+    const guidance = describeServerStartupFailure(
+      diagnostics({ stderrTail: "error: Cannot find module 'zod' from '/repo/src/server'" }),
+    );
+
+    expect(guidance.summary).toBe("The workspace server exited during startup (exit code 1).");
+    expect(guidance.steps).toEqual([
+      "Run `bun install` in the repository, then restart the workspace.",
+      "Review the server output below for the first error.",
+    ]);
+  });
+
+  test("distinguishes timeouts and failed health checks", () => {
+    expect(
+      describeServerStartupFailure(
+        diagnostics({ message: "Server startup timed out after 30 seconds", exitCode: null }),
+      ).summary,
+    ).toBe("The workspace server did not finish starting in time.");
+    expect(
+      describeServerStartupFailure(diagnostics({ stage: "health_check", exitCode: null })).summary,
+    ).toBe("The workspace server started but did not answer its health check.");
+  });
+});
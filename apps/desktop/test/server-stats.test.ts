@@ -0,0 +1,135 @@
+import { describe, expect, mock, test } from "bun:test";
+
+import {
+  parseLinuxProcStatCpuTimeMs,
+  parseLinuxProcStatusRssBytes,
+  parsePsCpuTimeMs,
+  ProcessStatsSampler,
+} from "../electron/services/processStats";
+import { ServerStatsMonitor } from "../electron/services/serverStatsMonitor";
+import type { ServerStatsEvent, WorkspaceServerStats } from "../src/lib/desktopApi";
+import { workspaceServerStatsSchema } from "../src/lib/desktopSchemas";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+setElectronMockOverrides({
+  app: {
+    getPath: () => process.cwd(),
+    getAppPath: () => process.cwd(),
+    getVersion: () => "1.2.3",
+    isPackaged: false,
+  },
+});
+
+mock.module("electron", () => createElectronMock());
+
+const { ServerManager } = await import("../electron/services/serverManager");
+
+function createClock(start: number) {
+  let current = start;
+  return {
+    now: () => current,
+    advance: (ms: number) => {
+      current += ms;
+    },
+  };
+}
+
+function runningStats(workspaceId: string): WorkspaceServerStats {
+  return {
+    workspaceId,
+    running: true,
+    pid: 4242,
+    cpuPercent: 12.5,
+    rssBytes: 64 * 1024 * 1024,
+    uptimeMs: 1_000,
+    sampledAt: "2026-01-01T00:00:01.000Z",
+  };
+}
+
+describe("desktop server stats", () => {
+  test("parses per-platform process usage output", () => {
+    expect(
+      parseLinuxProcStatCpuTimeMs(
+        "4242 (cowork server) S 1 4242 4242 0 -1 4194560 100 0 0 0 250 50 0 0 20 0 12 0",
+      ),
+    ).toBe(3_000);
+    expect(parseLinuxProcStatusRssBytes("Name:\tbun\nVmRSS:\t  2048 kB\nThreads:\t12\n")).toBe(
+      2 * 1024 * 1024,
+    );
+    expect(parseLinuxProcStatusRssBytes("Name:\tzombie\n")).toBeNull();
+    expect(parsePsCpuTimeMs("0:01.23")).toBe(1_230);
+    expect(parsePsCpuTimeMs("02:03:04")).toBe(7_384_000);
+    expect(parsePsCpuTimeMs("1-00:00:00")).toBe(86_400_000);
+    expect(parsePsCpuTimeMs("not-a-time")).toBeNull();
+  });
+
+  test("sampler averages over the lifetime first and then over each interval", async () => {
+    const clock = createClock(10_000);
+    let cpuTimeMs = 2_000;
+    const sampler = new ProcessStatsSampler(
+      async () => ({ cpuTimeMs, rssBytes: 1_024 }),
+      clock.now,
+    );
+
+    await expect(sampler.sample(4242, 6_000)).resolves.toEqual({
+      cpuPercent: 50,
+      rssBytes: 1_024,
+      sampledAt: 10_000,
+    });
+
+    clock.advance(1_000);
+    cpuTimeMs += 1_500;
+    expect((await sampler.sample(4242, 6_000))?.cpuPercent).toBe(150);
+
+    sampler.forget(4242);
+    clock.advance(1_000);
+    expect((await sampler.sample(4242, 6_000))?.cpuPercent).toBe(58.3);
+  });
+
+  test("ServerManager reports stats for running servers and an idle row otherwise", async () => {
+    const readProcessUsage = mock(async () => ({ cpuTimeMs: 500, rssBytes: 4_096 }));
+    const manager = new ServerManager({ readProcessUsage });
+    const startedAt = Date.now() - 1_000;
+    (manager as any).servers.set("ws-stats", {
+      child: { pid: 4242, exitCode: null, signalCode: null },
+      url: "ws://127.0.0.1:7337/ws",
+      mobileH3: null,
+      startedAt,
+      cleanup: () => {},
+    });
+
+    const stats = await manager.getServerStats("ws-stats");
+    expect(workspaceServerStatsSchema.safeParse(stats).success).toBe(true);
+    expect(stats).toMatchObject({ workspaceId: "ws-stats", running: true, pid: 4242 });
+    expect(stats.rssBytes).toBe(4_096);
+    expect(stats.uptimeMs).toBeGreaterThanOrEqual(1_000);
+    expect(readProcessUsage).toHaveBeenCalledWith(4242);
+
+    await expect(manager.getServerStats("ws-missing")).resolves.toMatchObject({
+      running: false,
+      pid: null,
+      cpuPercent: null,
+      rssBytes: null,
+      uptimeMs: null,
+    });
+    await expect(manager.getAllServerStats()).resolves.toHaveLength(1);
+  });
+
+  test("monitor stays quiet while idle and clears once after the last server stops", async () => {
+    let current: WorkspaceServerStats[] = [];
+    const events: ServerStatsEvent[] = [];
+    const monitor = new ServerStatsMonitor({
+      sample: async () => current,
+      emit: (event) => events.push(event),
+    });
+
+    await monitor.runOnce();
+    current = [runningStats("ws-1")];
+    await monitor.runOnce();
+    current = [];
+    await monitor.runOnce();
+    await monitor.runOnce();
+
+    expect(events).toEqual([{ workspaces: [runningStats("ws-1")] }, { workspaces: [] }]);
+  });
+});
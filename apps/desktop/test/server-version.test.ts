@@ -0,0 +1,52 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  compareProtocolVersions,
+  MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+  resolveServerVersionInfo,
+  ServerUpgradeRequiredError,
+} from "../electron/services/serverVersion";
+import { isServerUpgradeRequiredError } from "../src/lib/desktopCommands";
+
+describe("server version compatibility", () => {
+  test("compares dotted protocol versions numerically", () => {
+    expect(compareProtocolVersions("0.1", "0.1")).toBe(0);
+    expect(compareProtocolVersions("0.10", "0.9")).toBeGreaterThan(0);
+    expect(compareProtocolVersions("1", "1.0.1")).toBeLessThan(0);
+    expect(compareProtocolVersions("v1", "1")).toBeNull();
+  });
+
+  test("treats sidecars without version fields as the legacy protocol", () => {
+    expect(resolveServerVersionInfo("ws-1", {}, true)).toEqual({
+      workspaceId: "ws-1",
+      running: true,
+      version: null,
+      protocolVersion: "0.1",
+      minProtocolVersion: MIN_SUPPORTED_SERVER_PROTOCOL_VERSION,
+      compatible: true,
+    });
+  });
+
+  test("flags sidecars older than the supported minimum", () => {
+    const info = resolveServerVersionInfo(
+      "ws-1",
+      { version: "1.0.0", protocolVersion: "0.1" },
+      true,
+      "0.2",
+    );
+    expect(info.compatible).toBe(false);
+
+    const error = new ServerUpgradeRequiredError(info);
+    expect(error.details).toEqual(info);
+    expect(error.message).toContain("requires 0.2 or newer");
+    const remoteError = new Error(`Error invoking remote method: ${error.message}`);
+    expect(isServerUpgradeRequiredError(remoteError)).toBe(true);
+    expect(isServerUpgradeRequiredError(new Error("boom"))).toBe(false);
+  });
+
+  test("rejects malformed protocol versions", () => {
+    expect(resolveServerVersionInfo("ws-1", { protocolVersion: "latest" }, true).compatible).toBe(
+      false,
+    );
+  });
+});
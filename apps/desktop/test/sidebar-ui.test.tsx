@@ -545,11 +545,12 @@ describe("desktop sidebar", () => {
 
       expect(lastContextMenuItems.map((item) => item.id)).toEqual([
         "rename",
+        "fork",
         "archive",
         "generate_memory",
         "delete_history",
       ]);
-      expect(lastContextMenuItems[2]).toEqual({
+      expect(lastContextMenuItems[3]).toEqual({
         id: "generate_memory",
         label: "Generate memory from conversation",
         enabled: true,
@@ -601,6 +602,7 @@ describe("desktop sidebar", () => {
 
       expect(lastContextMenuItems.map((item) => item.id)).toEqual([
         "rename",
+        "fork",
         "archive",
         "delete_history",
       ]);
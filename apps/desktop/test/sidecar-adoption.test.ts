@@ -0,0 +1,245 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { JSONRPC_PROTOCOL_VERSION } from "../../../src/server/jsonrpc/protocol";
+import type { SidecarRegistryEntry } from "../electron/services/sidecarAdoption";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = process.cwd();
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "userData" ? userDataDir : process.cwd()),
+    getAppPath: () => process.cwd(),
+    getVersion: () => "1.3.0",
+    isPackaged: false,
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { ServerManager } = await import("../electron/services/serverManager");
+const { isSidecarAdoptionEnabled, SidecarRegistry } = await import(
+  "../electron/services/sidecarAdoption"
+);
+
+const SIDECAR_PID = 424_242;
+const SIDECAR_URL = "ws://127.0.0.1:7337/ws?coworkBrowserToken=token";
+const SIDECAR_AUTH_TOKEN = "sidecar-auth-token";
+
+type FakeSidecar = {
+  alive: boolean;
+  signals: Array<NodeJS.Signals | number | undefined>;
+};
+
+function createFakeSidecar(): FakeSidecar {
+  return { alive: true, signals: [] };
+}
+
+function createAdoptionOptions(
+  registry: InstanceType<typeof SidecarRegistry>,
+  sidecar: FakeSidecar,
+) {
+  return {
+    registry,
+    isProcessAlive: (pid: number) => pid === SIDECAR_PID && sidecar.alive,
+    signalProcess: (_pid: number, signal?: NodeJS.Signals | number) => {
+      sidecar.signals.push(signal);
+      sidecar.alive = false;
+    },
+  };
+}
+
+function createHealthFetch(body: Record<string, unknown>): typeof fetch {
+  return mock(async () => new Response(JSON.stringify(body), { status: 200 })) as typeof fetch;
+}
+
+describe("desktop sidecar adoption", () => {
+  let workspacePath = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-sidecar-registry-"));
+    workspacePath = path.join(userDataDir, "workspace");
+    await fs.mkdir(workspacePath);
+  });
+
+  afterEach(async () => {
+    await fs.rm(userDataDir, { recursive: true, force: true });
+    userDataDir = process.cwd();
+  });
+
+  function registryEntry(overrides: Partial<SidecarRegistryEntry> = {}): SidecarRegistryEntry {
+    return {
+      workspaceId: "ws-adopt",
+      workspacePath: path.resolve(workspacePath),
+      cwd: workspacePath,
+      pid: SIDECAR_PID,
+      url: SIDECAR_URL,
+      authToken: SIDECAR_AUTH_TOKEN,
+      yolo: false,
+      version: "1.2.0",
+      protocolVersion: JSONRPC_PROTOCOL_VERSION,
+      appVersion: "1.2.0",
+      startedAt: "2026-01-01T00:00:00.000Z",
+      ...overrides,
+    };
+  }
+
+  test("adoption defaults to packaged builds and honors the env override", () => {
+    expect(isSidecarAdoptionEnabled(true, {})).toBe(true);
+    expect(isSidecarAdoptionEnabled(false, {})).toBe(false);
+    expect(isSidecarAdoptionEnabled(true, { COWORK_DESKTOP_ADOPT_SIDECARS: "0" })).toBe(false);
+    expect(isSidecarAdoptionEnabled(false, { COWORK_DESKTOP_ADOPT_SIDECARS: "1" })).toBe(true);
+  });
+
+  test("registry replaces entries per workspace and only removes matching pids", async () => {
+    const registry = new SidecarRegistry(userDataDir);
+    await registry.record(registryEntry());
+    await registry.record(registryEntry({ pid: SIDECAR_PID + 1 }));
+
+    expect((await registry.list()).map((entry) => entry.pid)).toEqual([SIDECAR_PID + 1]);
+
+    await registry.remove("ws-adopt", SIDECAR_PID);
+    expect(await registry.get("ws-adopt")).not.toBeNull();
+
+    await registry.remove("ws-adopt", SIDECAR_PID + 1);
+    expect(await registry.list()).toEqual([]);
+    await expect(fs.stat(registry.filePath)).rejects.toThrow();
+  });
+
+  test("adopts a registered sidecar that passes the version handshake", async () => {
+    const registry = new SidecarRegistry(userDataDir);
+    await registry.record(registryEntry());
+    const sidecar = createFakeSidecar();
+    const exits: string[] = [];
+    const manager = new ServerManager({
+      fetch: createHealthFetch({
+        ok: true,
+        version: "1.2.0",
+        protocolVersion: JSONRPC_PROTOCOL_VERSION,
+        pid: SIDECAR_PID,
+        cwd: workspacePath,
+      }),
+      sidecarAdoption: createAdoptionOptions(registry, sidecar),
+      onWorkspaceServerExited: (event) => exits.push(event.workspaceId),
+    });
+
+    await expect(
+      manager.startWorkspaceServer({ workspaceId: "ws-adopt", workspacePath, yolo: false }),
+    ).resolves.toEqual({ url: SIDECAR_URL, authToken: SIDECAR_AUTH_TOKEN, mobileH3: null });
+    expect(manager.getServerVersion("ws-adopt")).toMatchObject({
+      running: true,
+      version: "1.2.0",
+      compatible: true,
+    });
+    expect(manager.getDiagnostics().workspaces[0]?.restartCount).toBe(0);
+
+    await manager.stopWorkspaceServer("ws-adopt");
+    await registry.flush();
+
+    expect(sidecar.signals).toHaveLength(1);
+    expect(exits).toEqual([]);
+    expect(await registry.list()).toEqual([]);
+  });
+
+  test("stops registered sidecars that fail the handshake instead of adopting them", async () => {
+    const registry = new SidecarRegistry(userDataDir);
+    await registry.record(registryEntry());
+    const sidecar = createFakeSidecar();
+    const manager = new ServerManager({
+      fetch: createHealthFetch({
+        ok: true,
+        version: "0.9.0",
+        protocolVersion: "0.0",
+        pid: SIDECAR_PID,
+        cwd: workspacePath,
+      }),
+      sidecarAdoption: createAdoptionOptions(registry, sidecar),
+    });
+
+    const adopted = await (manager as any).tryAdoptWorkspaceServer({
+      workspaceId: "ws-adopt",
+      workspacePath,
+      yolo: false,
+    });
+    await registry.flush();
+
+    expect(adopted).toBeNull();
+    expect(sidecar.signals).toHaveLength(1);
+    expect(await registry.list()).toEqual([]);
+  });
+
+  test("stops sidecars registered without an auth token instead of adopting them", async () => {
+    const registry = new SidecarRegistry(userDataDir);
+    await registry.record(registryEntry({ authToken: undefined }));
+    const sidecar = createFakeSidecar();
+    const manager = new ServerManager({
+      fetch: createHealthFetch({
+        ok: true,
+        protocolVersion: JSONRPC_PROTOCOL_VERSION,
+        pid: SIDECAR_PID,
+        cwd: workspacePath,
+      }),
+      sidecarAdoption: createAdoptionOptions(registry, sidecar),
+    });
+
+    const adopted = await (manager as any).tryAdoptWorkspaceServer({
+      workspaceId: "ws-adopt",
+      workspacePath,
+      yolo: false,
+    });
+    await registry.flush();
+
+    expect(adopted).toBeNull();
+    expect(sidecar.signals).toHaveLength(1);
+    expect(await registry.list()).toEqual([]);
+  });
+
+  test("leaves unverifiable pids alone and forgets the entry", async () => {
+    const registry = new SidecarRegistry(userDataDir);
+    await registry.record(registryEntry());
+    const sidecar = createFakeSidecar();
+    const manager = new ServerManager({
+      fetch: createHealthFetch({ ok: true, pid: SIDECAR_PID + 7, cwd: workspacePath }),
+      sidecarAdoption: createAdoptionOptions(registry, sidecar),
+    });
+
+    const adopted = await (manager as any).tryAdoptWorkspaceServer({
+      workspaceId: "ws-adopt",
+      workspacePath,
+      yolo: false,
+    });
+    await registry.flush();
+
+    expect(adopted).toBeNull();
+    expect(sidecar.signals).toEqual([]);
+    expect(await registry.list()).toEqual([]);
+  });
+
+  test("handOffAll keeps registered sidecars running for the next instance", async () => {
+    const registry = new SidecarRegistry(userDataDir);
+    await registry.record(registryEntry());
+    const sidecar = createFakeSidecar();
+    const manager = new ServerManager({
+      fetch: createHealthFetch({
+        ok: true,
+        protocolVersion: JSONRPC_PROTOCOL_VERSION,
+        pid: SIDECAR_PID,
+        cwd: workspacePath,
+      }),
+      sidecarAdoption: createAdoptionOptions(registry, sidecar),
+    });
+    await manager.startWorkspaceServer({ workspaceId: "ws-adopt", workspacePath, yolo: false });
+
+    await manager.handOffAll();
+
+    expect(sidecar.signals).toEqual([]);
+    expect((await registry.list()).map((entry) => entry.pid)).toEqual([SIDECAR_PID]);
+    expect(manager.getServerVersion("ws-adopt").running).toBe(false);
+  });
+});
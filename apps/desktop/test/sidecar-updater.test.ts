@@ -0,0 +1,166 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import { createHash, generateKeyPairSync, sign } from "node:crypto";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import type { SidecarUpdateState } from "../src/lib/desktopApi";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+setElectronMockOverrides({
+  app: {
+    getPath: (name: string) => (name === "userData" ? userDataDir : process.cwd()),
+  },
+});
+
+mock.module("electron", () => createElectronMock());
+
+const { SidecarUpdater, sidecarUpdateSignedMessage } = await import(
+  "../electron/services/sidecarUpdater"
+);
+const { flushLocalLogWrites } = await import("../electron/services/localLogs");
+
+const TRIPLE = "x86_64-unknown-linux-gnu";
+const BINARY_NAME = `cowork-server-${TRIPLE}`;
+const MANIFEST_URL = "https://updates.example.com/cowork-server-update.json";
+const BINARY_URL = "https://updates.example.com/cowork-server";
+
+const { publicKey, privateKey } = generateKeyPairSync("ed25519");
+const publicKeyPem = publicKey.export({ type: "spki", format: "pem" }).toString();
+
+function releaseManifest(version: string, binary: Buffer, signedWith = privateKey) {
+  const sha256 = createHash("sha256").update(binary).digest("hex");
+  const message = sidecarUpdateSignedMessage(version, TRIPLE, sha256);
+  return {
+    version,
+    protocolVersion: "99.0",
+    assets: {
+      [TRIPLE]: {
+        url: BINARY_URL,
+        sha256,
+        size: binary.byteLength,
+        signature: sign(null, Buffer.from(message), signedWith).toString("base64"),
+      },
+    },
+  };
+}
+
+function fakeFetch(manifest: unknown, binary: Buffer): typeof fetch {
+  return (async (input: string | URL | Request) => {
+    const url = String(input);
+    if (url === MANIFEST_URL) {
+      return new Response(JSON.stringify(manifest), { status: 200 });
+    }
+    if (url === BINARY_URL) {
+      return new Response(binary, { status: 200 });
+    }
+    return new Response("not found", { status: 404 });
+  }) as typeof fetch;
+}
+
+describe("sidecar updater", () => {
+  let rootDir = "";
+
+  beforeEach(async () => {
+    userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-sidecar-updater-"));
+    rootDir = path.join(userDataDir, "sidecar-updates");
+  });
+
+  afterEach(async () => {
+    await flushLocalLogWrites("updater.log");
+    await fs.rm(userDataDir, { recursive: true, force: true });
+    userDataDir = "";
+  });
+
+  function createUpdater(fetchImpl: typeof fetch, events: SidecarUpdateState[] = []) {
+    return new SidecarUpdater({
+      rootDir,
+      bundledVersion: "1.2.0",
+      publicKey: publicKeyPem,
+      enabled: true,
+      manifestUrl: MANIFEST_URL,
+      fetch: fetchImpl,
+      platform: "linux",
+      arch: "x64",
+      onStateChange: (state) => events.push(state),
+    });
+  }
+
+  test("stages a verified download and swaps it in on the next launch", async () => {
+    const binary = Buffer.from("#!/bin/sh\necho new server\n");
+    const events: SidecarUpdateState[] = [];
+    const updater = createUpdater(fakeFetch(releaseManifest("1.3.0", binary), binary), events);
+
+    expect(await updater.check()).toMatchObject({ phase: "available", availableVersion: "1.3.0" });
+    expect(await updater.apply()).toMatchObject({ phase: "staged", stagedVersion: "1.3.0" });
+    expect(events.some((event) => event.phase === "downloading")).toBe(true);
+    expect(await fs.readFile(path.join(rootDir, "staged", BINARY_NAME))).toEqual(binary);
+
+    const launch = await updater.prepareLaunch();
+    const command = path.join(rootDir, "current", BINARY_NAME);
+    expect(launch).toMatchObject({
+      command,
+      args: [],
+      integrity: [{ path: command, sha256: createHash("sha256").update(binary).digest("hex") }],
+    });
+    expect(await updater.getState()).toMatchObject({
+      phase: "up-to-date",
+      currentVersion: "1.3.0",
+      stagedVersion: null,
+    });
+    await expect(fs.stat(path.join(rootDir, "staged"))).rejects.toThrow();
+
+    // A later app update that bundles a newer server retires the download.
+    const upgraded = new SidecarUpdater({
+      rootDir,
+      bundledVersion: "1.4.0",
+      publicKey: publicKeyPem,
+      enabled: true,
+      platform: "linux",
+      arch: "x64",
+    });
+    expect(await upgraded.prepareLaunch()).toBeNull();
+    expect((await upgraded.getState()).currentVersion).toBe("1.4.0");
+  });
+
+  test("rejects a release signed with another key and stages nothing", async () => {
+    const binary = Buffer.from("tampered");
+    const otherKey = generateKeyPairSync("ed25519").privateKey;
+    const manifest = releaseManifest("1.3.0", binary, otherKey);
+    const updater = createUpdater(fakeFetch(manifest, binary));
+
+    await expect(updater.apply()).rejects.toThrow("Server update signature is invalid.");
+    expect((await updater.getState()).phase).toBe("error");
+    expect(await updater.prepareLaunch()).toBeNull();
+    expect(await fs.readdir(rootDir).catch(() => [])).toEqual([]);
+  });
+
+  test("rejects a download that does not match the signed checksum", async () => {
+    const manifest = releaseManifest("1.3.0", Buffer.from("expected"));
+    const updater = createUpdater(fakeFetch(manifest, Buffer.from("modified")));
+
+    await expect(updater.apply()).rejects.toThrow("does not match the signed checksum");
+    expect(await updater.prepareLaunch()).toBeNull();
+  });
+
+  test("reports up to date when the manifest is not newer", async () => {
+    const binary = Buffer.from("same");
+    const updater = createUpdater(fakeFetch(releaseManifest("1.2.0", binary), binary));
+
+    expect(await updater.check()).toMatchObject({ phase: "up-to-date", availableVersion: null });
+  });
+
+  test("stays off without a signing key", async () => {
+    const updater = new SidecarUpdater({
+      rootDir,
+      bundledVersion: "1.2.0",
+      publicKey: null,
+      enabled: true,
+    });
+
+    expect((await updater.check()).phase).toBe("disabled");
+    await expect(updater.apply()).rejects.toThrow("Server updates are unavailable in this build.");
+  });
+});
@@ -0,0 +1,84 @@
+import { describe, expect, test } from "bun:test";
+import path from "node:path";
+
+import { parseDeepLink } from "../electron/services/deepLinks";
+import {
+  createSingleInstanceLaunchData,
+  launchArgs,
+  resolveLaunchDeepLink,
+  resolveLaunchTranscriptFile,
+  resolveSecondInstanceLaunch,
+} from "../electron/services/singleInstance";
+
+describe("single-instance launch forwarding", () => {
+  test("strips the executable and the development app path", () => {
+    expect(launchArgs(["/Applications/Cowork", "--flag", "proj"])).toEqual(["--flag", "proj"]);
+    expect(launchArgs(["electron", ".", "proj"], { defaultApp: true })).toEqual(["proj"]);
+  });
+
+  test("forwards deep links unchanged", () => {
+    const launch = createSingleInstanceLaunchData(
+      ["/usr/bin/cowork", "--no-sandbox", "cowork://workspace/ws-1"],
+      "/home/me",
+    );
+
+    expect(resolveLaunchDeepLink(launch)).toBe("cowork://workspace/ws-1");
+  });
+
+  test("turns a folder argument into an open link relative to the launch directory", () => {
+    const deepLinkUrl = resolveLaunchDeepLink({
+      args: ["--verbose", "proj"],
+      workingDirectory: "/home/me",
+    });
+
+    expect(deepLinkUrl).not.toBeNull();
+    expect(parseDeepLink(deepLinkUrl ?? "")).toEqual({
+      kind: "openPath",
+      path: path.resolve("/home/me", "proj"),
+    });
+  });
+
+  test("does not mistake a profile id for a folder", () => {
+    expect(
+      resolveLaunchDeepLink({ args: ["--profile", "work"], workingDirectory: "/home/me" }),
+    ).toBeNull();
+    expect(
+      resolveLaunchDeepLink({ args: ["--profile=work"], workingDirectory: "/home/me" }),
+    ).toBeNull();
+
+    const deepLinkUrl = resolveLaunchDeepLink({
+      args: ["--profile", "work", "proj"],
+      workingDirectory: "/home/me",
+    });
+    expect(parseDeepLink(deepLinkUrl ?? "")).toEqual({
+      kind: "openPath",
+      path: path.resolve("/home/me", "proj"),
+    });
+  });
+
+  test("opens exported thread arguments in the viewer instead of as folders", () => {
+    const launch = { args: ["--flag", "Shared Notes.COWORK-THREAD"], workingDirectory: "/home/me" };
+
+    expect(resolveLaunchDeepLink(launch)).toBeNull();
+    expect(resolveLaunchTranscriptFile(launch)).toBe(
+      path.resolve("/home/me", "Shared Notes.COWORK-THREAD"),
+    );
+    expect(
+      resolveLaunchTranscriptFile({ args: ["proj"], workingDirectory: "/home/me" }),
+    ).toBeNull();
+  });
+
+  test("only focuses when there is nothing to open", () => {
+    expect(resolveLaunchDeepLink({ args: ["--hidden"], workingDirectory: "/home/me" })).toBeNull();
+  });
+
+  test("prefers launch data sent with the lock over the raw argv", () => {
+    const sent = { args: ["cowork://workspace/ws-2"], workingDirectory: "/tmp" };
+
+    expect(resolveSecondInstanceLaunch(["cowork", "--reordered"], "/", sent)).toEqual(sent);
+    expect(resolveSecondInstanceLaunch(["cowork", "proj"], "/work", { args: "bad" })).toEqual({
+      args: ["proj"],
+      workingDirectory: "/work",
+    });
+  });
+});
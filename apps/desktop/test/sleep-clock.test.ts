@@ -0,0 +1,93 @@
+import { describe, expect, test } from "bun:test";
+import { EventEmitter } from "node:events";
+
+import { SleepClock, setAwakeTimeout } from "../electron/services/sleepClock";
+
+function createClocks() {
+  const clocks = { wall: 1_000_000, monotonic: 0 };
+  return {
+    clocks,
+    /** Both clocks move, as they do while the machine is awake. */
+    advance(ms: number) {
+      clocks.wall += ms;
+      clocks.monotonic += ms;
+    },
+    /** Only the wall clock moves, as it does while the machine is asleep. */
+    sleep(ms: number) {
+      clocks.wall += ms;
+    },
+    clock: new SleepClock({ now: () => clocks.wall, monotonicNow: () => clocks.monotonic }),
+  };
+}
+
+describe("SleepClock", () => {
+  test("notices sleep from the wall clock outrunning the monotonic clock", () => {
+    const { clock, clocks, advance, sleep } = createClocks();
+    const startedAt = clocks.wall;
+
+    advance(1_000);
+    sleep(60_000);
+    advance(500);
+
+    expect(clock.sleptSince(startedAt)).toBe(60_000);
+    expect(clock.awakeSince(startedAt)).toBe(1_500);
+    expect(clock.wokeRecently(1_000)).toBe(true);
+    advance(2_000);
+    expect(clock.wokeRecently(1_000)).toBe(false);
+  });
+
+  test("ignores drift below the threshold and sleeps before the window", () => {
+    const { clock, clocks, advance, sleep } = createClocks();
+
+    sleep(1_000);
+    expect(clock.sleptSince(0)).toBe(0);
+
+    sleep(30_000);
+    advance(5_000);
+    expect(clock.sleptSince(0)).toBe(30_000);
+    const afterWake = clocks.wall;
+    advance(1_000);
+    expect(clock.sleptSince(afterWake)).toBe(0);
+    expect(clock.awakeSince(afterWake)).toBe(1_000);
+  });
+
+  test("counts a sleep reported by power events once", () => {
+    const { clock, clocks, advance, sleep } = createClocks();
+    const power = new EventEmitter();
+    clock.attach(power as never);
+    const startedAt = clocks.wall;
+
+    advance(1_000);
+    power.emit("suspend");
+    sleep(20_000);
+    power.emit("resume");
+    advance(1_000);
+
+    expect(clock.sleptSince(startedAt)).toBe(20_000);
+    clock.dispose();
+    expect(power.listenerCount("resume")).toBe(0);
+  });
+
+  test("awake timeouts re-arm for the awake time they still owe after a sleep", async () => {
+    let lag = 0;
+    const clock = new SleepClock({
+      monotonicNow: () => performance.now() - lag,
+      driftThresholdMs: 10,
+    });
+    let fired = false;
+    setAwakeTimeout(clock, () => (fired = true), 60);
+    // Looks like 50ms of the first 60 were spent asleep.
+    lag = 50;
+
+    await new Promise((resolve) => setTimeout(resolve, 90));
+    expect(fired).toBe(false);
+    await new Promise((resolve) => setTimeout(resolve, 70));
+    expect(fired).toBe(true);
+
+    fired = false;
+    const cancel = setAwakeTimeout(clock, () => (fired = true), 20);
+    cancel();
+    await new Promise((resolve) => setTimeout(resolve, 40));
+    expect(fired).toBe(false);
+  });
+});
@@ -0,0 +1,113 @@
+import { describe, expect, test } from "bun:test";
+import path from "node:path";
+
+import { type SourceReloadEvent, SourceReloadWatcher } from "../electron/services/sourceReload";
+
+function createHarness(options: { failFor?: string } = {}) {
+  let emitChange: ((eventType: string, filename: string | null) => void) | null = null;
+  let closed = false;
+  const running = new Set(["ws-alpha", "ws-beta"]);
+  const reloads: string[] = [];
+  const reloaded: SourceReloadEvent[] = [];
+  const errors: Array<string | null> = [];
+  let release: (() => void) | null = null;
+  let holdReloads = false;
+  const watcher = new SourceReloadWatcher({
+    sourceDir: path.join("/repo", "src", "server"),
+    getServerIds: () => [...running],
+    reloadServer: async (serverId) => {
+      reloads.push(serverId);
+      if (holdReloads) {
+        await new Promise<void>((resolve) => {
+          release = resolve;
+        });
+      }
+      if (serverId === options.failFor) {
+        throw new Error("listen failed");
+      }
+      return running.has(serverId);
+    },
+    onReloaded: (event) => reloaded.push(event),
+    onError: (_error, serverId) => errors.push(serverId),
+    debounceMs: 5,
+    watch: (_rootPath, listener) => {
+      emitChange = listener;
+      return {
+        close: () => {
+          closed = true;
+        },
+      };
+    },
+  });
+  expect(watcher.start()).toBe(true);
+  return {
+    watcher,
+    running,
+    reloads,
+    reloaded,
+    errors,
+    change: (filename: string) => emitChange?.("change", filename),
+    holdReloads: () => {
+      holdReloads = true;
+    },
+    release: () => {
+      holdReloads = false;
+      release?.();
+    },
+    isClosed: () => closed,
+  };
+}
+
+describe("SourceReloadWatcher", () => {
+  test("debounces a burst of edits into one reload per server with its reason", async () => {
+    const harness = createHarness();
+    harness.change("index.ts");
+    harness.change("session.ts");
+    harness.change("index.ts");
+    harness.change("notes.md");
+    await Bun.sleep(30);
+
+    expect(harness.reloads.sort()).toEqual(["ws-alpha", "ws-beta"]);
+    expect(harness.reloaded).toContainEqual({
+      serverId: "ws-alpha",
+      reason: "src/server/index.ts, src/server/session.ts changed",
+      changedPaths: ["index.ts", "session.ts"],
+    });
+    harness.watcher.dispose();
+    expect(harness.isClosed()).toBe(true);
+  });
+
+  test("batches edits made during a reload into one more pass", async () => {
+    const harness = createHarness();
+    harness.running.delete("ws-beta");
+    harness.holdReloads();
+    harness.change("index.ts");
+    await Bun.sleep(20);
+    harness.change("a.ts");
+    harness.change("b.ts");
+    await Bun.sleep(20);
+    expect(harness.reloads).toEqual(["ws-alpha"]);
+
+    harness.release();
+    await harness.watcher.flush();
+    await Bun.sleep(20);
+
+    expect(harness.reloaded.map((event) => event.changedPaths)).toEqual([
+      ["index.ts"],
+      ["a.ts", "b.ts"],
+    ]);
+    expect(harness.reloaded[1]?.reason).toBe("src/server/a.ts, src/server/b.ts changed");
+    harness.watcher.dispose();
+  });
+
+  test("reports a server that fails to restart without blocking the others", async () => {
+    const harness = createHarness({ failFor: "ws-beta" });
+    harness.change("index.ts");
+    await harness.watcher.flush();
+    await Bun.sleep(20);
+
+    expect(harness.reloaded.map((event) => event.serverId)).toEqual(["ws-alpha"]);
+    expect(harness.errors).toEqual(["ws-beta"]);
+    harness.watcher.dispose();
+  });
+});
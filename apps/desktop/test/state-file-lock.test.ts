@@ -0,0 +1,122 @@
+import { Database } from "bun:sqlite";
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService, StateMergeRequiredError } = await import(
+  "../electron/services/persistence"
+);
+const { StateFileLock } = await import("../electron/services/stateFileLock");
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+function thread(id: string) {
+  return { id, workspaceId: "ws-1", title: `Thread ${id}`, createdAt: TS, lastMessageAt: TS };
+}
+
+describe("state file lock", () => {
+  let root = "";
+
+  const createLock = (acquireTimeoutMs?: number) =>
+    new StateFileLock({
+      lockPath: path.join(userDataDir, "state.lock"),
+      openDatabase: (lockPath) => new Database(lockPath),
+      acquireTimeoutMs,
+      retryDelayMs: 1,
+    });
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-state-lock-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        generation: 1,
+        workspaces: [
+          {
+            id: "ws-1",
+            name: "Project",
+            path: root,
+            createdAt: TS,
+            lastOpenedAt: TS,
+            defaultProvider: "openai",
+            defaultModel: "o3",
+            yolo: false,
+          },
+        ],
+        threads: [thread("thread-1")],
+      }),
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (root) {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+    root = "";
+    userDataDir = "";
+  });
+
+  test("blocks a second holder until the first releases the lock", async () => {
+    const first = createLock();
+    const second = createLock(20);
+
+    await first.run(async () => {
+      await expect(second.run(async () => "acquired")).rejects.toThrow(
+        "Timed out acquiring state lock",
+      );
+    });
+    expect(await second.run(async () => "acquired")).toBe("acquired");
+  });
+
+  test("rejects a save based on a generation another process has replaced", async () => {
+    const ours = new PersistenceService({ stateFileLock: createLock() });
+    const theirs = new PersistenceService({ stateFileLock: createLock() });
+
+    const base = await ours.loadState();
+    expect(base.generation).toBe(1);
+    // Our own writes never conflict, even when the renderer's base lags behind them.
+    expect(await ours.saveState(base)).toBe(2);
+    expect(await ours.saveState(base)).toBe(3);
+
+    const theirState = await theirs.loadState();
+    const theirThreads = [...theirState.threads, thread("thread-2")];
+    expect(await theirs.saveState({ ...theirState, threads: theirThreads })).toBe(4);
+
+    const rejected = ours.saveState({ ...base, generation: 3 });
+    await expect(rejected).rejects.toBeInstanceOf(StateMergeRequiredError);
+    await expect(rejected).rejects.toMatchObject({
+      code: "state_merge_required",
+      baseGeneration: 3,
+      currentGeneration: 4,
+      workspaces: [],
+      threads: [expect.objectContaining({ id: "thread-2" })],
+    });
+
+    const merged = { ...base, generation: 4, threads: [...base.threads, thread("thread-2")] };
+    expect(await ours.saveState(merged)).toBe(5);
+    expect((await theirs.loadState()).threads.map((record) => record.id)).toEqual([
+      "thread-1",
+      "thread-2",
+    ]);
+  });
+});
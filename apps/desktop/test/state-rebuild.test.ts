@@ -0,0 +1,268 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { inferThreadFromTranscript, parseServerLogWorkspaceIds } = await import(
+  "../electron/services/stateRebuild"
+);
+
+function transcriptLine(threadId: string, ts: string, direction: string, payload: unknown) {
+  return JSON.stringify({ ts, threadId, direction, payload });
+}
+
+async function writeTranscript(threadId: string, lines: string[]): Promise<void> {
+  const dir = path.join(userDataDir, "transcripts");
+  await fs.mkdir(dir, { recursive: true });
+  await fs.writeFile(path.join(dir, `${threadId}.jsonl`), `${lines.join("\n")}\n`, "utf8");
+}
+
+async function writeServerLog(entries: unknown[]): Promise<void> {
+  const dir = path.join(userDataDir, "logs");
+  await fs.mkdir(dir, { recursive: true });
+  await fs.writeFile(
+    path.join(dir, "server.log"),
+    `${entries.map((entry) => JSON.stringify(entry)).join("\n")}\n`,
+    "utf8",
+  );
+}
+
+function helloLine(threadId: string, ts: string, workingDirectory: string): string {
+  return transcriptLine(threadId, ts, "server", {
+    type: "server_hello",
+    sessionId: `session-${threadId}`,
+    config: { provider: "openai", model: "gpt-5", workingDirectory },
+  });
+}
+
+describe("desktop state rebuild from transcripts", () => {
+  let workspacePath = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-state-rebuild-"));
+    userDataDir = path.join(root, "Cowork");
+    workspacePath = path.join(root, "project");
+    await fs.mkdir(userDataDir, { recursive: true });
+    await fs.mkdir(workspacePath, { recursive: true });
+    workspacePath = await fs.realpath(workspacePath);
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("reads workspace ids from server.log and expands the redacted home directory", () => {
+    const raw = [
+      JSON.stringify({
+        message: "workspace server start requested",
+        meta: { workspaceId: "ws-home", workspacePath: "[home]/code/app" },
+      }),
+      JSON.stringify({
+        message: "workspace server start requested",
+        meta: { workspaceId: "ws-redacted", workspacePath: "[local-path]" },
+      }),
+      JSON.stringify({
+        message: "workspace server start requested",
+        meta: { workspaceId: "ws-abs", workspacePath: "/srv/app" },
+      }),
+      JSON.stringify({
+        message: "workspace server start requested",
+        meta: { workspaceId: "ws-abs-2", workspacePath: "/srv/app" },
+      }),
+      "not json workspace server start requested",
+    ].join("\n");
+
+    expect([...parseServerLogWorkspaceIds(raw, "/home/tester").entries()]).toEqual([
+      [path.resolve("/home/tester/code/app"), "ws-home"],
+      [path.resolve("/srv/app"), "ws-abs-2"],
+    ]);
+  });
+
+  test("infers titles, message counts, and timestamps from transcript events", () => {
+    const event = (ts: string, direction: "server" | "client", payload: unknown) => ({
+      ts,
+      threadId: "t1",
+      direction,
+      payload,
+    });
+    const events = [
+      event("2026-01-01T00:00:00.000Z", "server", {
+        type: "server_hello",
+        sessionId: "s1",
+        config: { workingDirectory: "/srv/app" },
+      }),
+      event("2026-01-01T00:00:01.000Z", "client", {
+        type: "user_message",
+        sessionId: "s1",
+        text: "  Fix the\nflaky login test  ",
+      }),
+      event("2026-01-01T00:00:02.000Z", "server", {
+        type: "user_message",
+        sessionId: "s1",
+        text: "Fix the flaky login test",
+      }),
+    ];
+
+    expect(inferThreadFromTranscript("t1", events)).toEqual({
+      threadId: "t1",
+      workingDirectory: "/srv/app",
+      sessionId: "s1",
+      title: "Fix the flaky login test",
+      titleSource: "heuristic",
+      titleFrom: "firstMessage",
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastMessageAt: "2026-01-01T00:00:02.000Z",
+      messageCount: 1,
+    });
+
+    const withInfo = inferThreadFromTranscript("t1", [
+      ...events,
+      event("2026-01-01T00:00:03.000Z", "server", {
+        type: "session_info",
+        sessionId: "s1",
+        title: "Flaky login test",
+        titleSource: "model",
+        createdAt: "2025-12-31T23:59:59.000Z",
+      }),
+    ]);
+    expect(withInfo).toMatchObject({
+      title: "Flaky login test",
+      titleSource: "model",
+      titleFrom: "sessionInfo",
+      createdAt: "2025-12-31T23:59:59.000Z",
+    });
+    expect(inferThreadFromTranscript("t1", [])).toBeNull();
+  });
+
+  test("dry run reports recovered records without touching state.json", async () => {
+    await fs.writeFile(path.join(userDataDir, "state.json"), "{not json", "utf8");
+    await writeServerLog([
+      {
+        message: "workspace server start requested",
+        meta: { workspaceId: "ws-logged", workspacePath },
+      },
+    ]);
+    await writeTranscript("thread-a", [
+      helloLine("thread-a", "2026-02-01T10:00:00.000Z", workspacePath),
+      transcriptLine("thread-a", "2026-02-01T10:00:05.000Z", "client", {
+        type: "user_message",
+        text: "Summarize the README",
+      }),
+    ]);
+    await writeTranscript("thread-gone", [
+      helloLine("thread-gone", "2026-02-01T11:00:00.000Z", path.join(workspacePath, "missing")),
+    ]);
+    await writeTranscript("thread-unknown", [
+      transcriptLine("thread-unknown", "2026-02-01T12:00:00.000Z", "client", {
+        type: "user_message",
+        text: "hello",
+      }),
+    ]);
+
+    const persistence = new PersistenceService({
+      now: () => new Date("2026-03-01T00:00:00.000Z"),
+    });
+    const { report, state } = await persistence.rebuildStateFromTranscripts({ dryRun: true });
+
+    expect(report).toMatchObject({
+      dryRun: true,
+      applied: false,
+      snapshotName: null,
+      scannedTranscripts: 3,
+      alreadyPresent: 0,
+      generatedAt: "2026-03-01T00:00:00.000Z",
+      workspaces: [
+        {
+          id: "ws-logged",
+          name: path.basename(workspacePath),
+          path: workspacePath,
+          source: "serverLog",
+          threadIds: ["thread-a"],
+        },
+      ],
+      threads: [
+        {
+          id: "thread-a",
+          workspaceId: "ws-logged",
+          title: "Summarize the README",
+          titleFrom: "firstMessage",
+          messageCount: 1,
+        },
+      ],
+    });
+    expect(report.skipped.map((entry) => entry.threadId)).toEqual([
+      "thread-gone",
+      "thread-unknown",
+    ]);
+    expect(state.workspaces).toEqual([]);
+    expect(await fs.readFile(path.join(userDataDir, "state.json"), "utf8")).toBe("{not json");
+  });
+
+  test("applying merges recovered threads into the existing state after a snapshot", async () => {
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-existing",
+            name: "Project",
+            path: workspacePath,
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastOpenedAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+        threads: [
+          {
+            id: "thread-known",
+            workspaceId: "ws-existing",
+            title: "Known",
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastMessageAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+      }),
+      "utf8",
+    );
+    await writeTranscript("thread-known", [
+      helloLine("thread-known", "2026-01-01T00:00:00.000Z", workspacePath),
+    ]);
+    await writeTranscript("thread-lost", [
+      helloLine("thread-lost", "2026-02-01T00:00:00.000Z", workspacePath),
+    ]);
+
+    const persistence = new PersistenceService();
+    const { report, state } = await persistence.rebuildStateFromTranscripts({ dryRun: false });
+
+    expect(report.applied).toBe(true);
+    expect(report.snapshotName).not.toBeNull();
+    expect(report.alreadyPresent).toBe(1);
+    expect(report.workspaces).toMatchObject([{ id: "ws-existing", source: "existing" }]);
+    expect(state.workspaces.map((workspace) => workspace.id)).toEqual(["ws-existing"]);
+    expect(state.threads.map((thread) => [thread.id, thread.workspaceId, thread.title])).toEqual([
+      ["thread-known", "ws-existing", "Known"],
+      ["thread-lost", "ws-existing", "Recovered thread"],
+    ]);
+    expect((await persistence.loadState()).threads).toHaveLength(2);
+    expect(await persistence.listStateSnapshots()).toHaveLength(1);
+  });
+});
@@ -0,0 +1,146 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import type { DiskSpaceStatus } from "../src/lib/desktopApi";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { LowDiskSpaceError, StorageGuard } = await import("../electron/services/storageGuard");
+
+const MB = 1024 * 1024;
+
+function fakeDisk(freeMb: number) {
+  const disk = { freeMb, statCalls: 0 };
+  const statfs = async () => {
+    disk.statCalls += 1;
+    return { bavail: disk.freeMb * 256, bsize: 4096 };
+  };
+  return { disk, statfs };
+}
+
+function transcriptEvent(threadId: string, seq: number) {
+  return {
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId,
+    direction: "server" as const,
+    payload: { type: "agent_text_delta", seq },
+  };
+}
+
+describe("storage guard", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-storage-guard-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("compares free space to the threshold and caches between checks", async () => {
+    const { disk, statfs } = fakeDisk(100);
+    let now = 0;
+    let minFreeMb = 256;
+    const guard = new StorageGuard({ getMinFreeMb: async () => minFreeMb, statfs, now: () => now });
+
+    expect(await guard.check("/data")).toEqual({
+      freeBytes: 100 * MB,
+      minFreeBytes: 256 * MB,
+      low: true,
+    });
+    disk.freeMb = 1024;
+    expect((await guard.check("/data")).low).toBe(true);
+    now = 10_000;
+    expect((await guard.check("/data")).low).toBe(false);
+    expect(disk.statCalls).toBe(2);
+
+    disk.freeMb = 10;
+    await expect(guard.assertWritable("export", "/data")).rejects.toBeInstanceOf(LowDiskSpaceError);
+    minFreeMb = 0;
+    await guard.assertWritable("export", "/data");
+  });
+
+  test("lets writes through when free space cannot be read", async () => {
+    const guard = new StorageGuard({
+      getMinFreeMb: async () => 256,
+      statfs: async () => {
+        throw new Error("ENOSYS");
+      },
+    });
+
+    expect(await guard.check("/data")).toEqual({
+      freeBytes: null,
+      minFreeBytes: 256 * MB,
+      low: false,
+    });
+  });
+
+  test("buffers transcript appends while low and writes them in order once flushed", async () => {
+    const { disk, statfs } = fakeDisk(100);
+    const events: DiskSpaceStatus[] = [];
+    const persistence = new PersistenceService({
+      storageGuard: new StorageGuard({ getMinFreeMb: async () => 256, statfs, cacheMs: 0 }),
+      onDiskSpaceChanged: (status) => events.push(status),
+      maxBufferedTranscriptEvents: 3,
+    });
+
+    await persistence.appendTranscriptBatch([transcriptEvent("thread-1", 1)]);
+    await persistence.appendTranscriptBatch([
+      transcriptEvent("thread-1", 2),
+      transcriptEvent("thread-2", 1),
+    ]);
+    await expect(
+      persistence.appendTranscriptBatch([transcriptEvent("thread-1", 3)]),
+    ).rejects.toThrow("Not enough disk space to write transcripts: 100 MB free, 256 MB required");
+
+    expect(await persistence.readTranscript("thread-1")).toEqual([]);
+    expect(events).toEqual([
+      { freeBytes: 100 * MB, minFreeBytes: 256 * MB, low: true, bufferedTranscriptEvents: 0 },
+    ]);
+    await expect(persistence.flushBufferedTranscripts()).rejects.toBeInstanceOf(LowDiskSpaceError);
+
+    disk.freeMb = 1024;
+    expect(await persistence.flushBufferedTranscripts()).toMatchObject({
+      low: false,
+      bufferedTranscriptEvents: 0,
+    });
+    expect(events.map((event) => [event.low, event.bufferedTranscriptEvents])).toEqual([
+      [true, 0],
+      [false, 3],
+    ]);
+    const thread1 = await persistence.readTranscript("thread-1");
+    expect(thread1.map((event) => (event.payload as { seq: number }).seq)).toEqual([1, 2]);
+    expect(await persistence.readTranscript("thread-2")).toHaveLength(1);
+  });
+
+  test("refuses to save state below the threshold", async () => {
+    const { statfs } = fakeDisk(100);
+    const persistence = new PersistenceService({
+      storageGuard: new StorageGuard({ getMinFreeMb: async () => 256, statfs }),
+    });
+    const state = await persistence.loadState();
+
+    await expect(persistence.saveState(state)).rejects.toThrow(
+      "Not enough disk space to save app state",
+    );
+  });
+});
@@ -218,6 +218,27 @@ describe("store explorer actions", () => {
     expect(useAppStore.getState().workspaceExplorerById[wsId]?.currentPath).toBe(rootPath);
   });
 
+  test("setWorkspaceExplorerRoot switches between roots of a multi-root workspace", async () => {
+    const extraRoot = "/tmp/shared-lib";
+    useAppStore.setState((state) => ({
+      workspaces: state.workspaces.map((ws) => ({ ...ws, additionalRoots: [extraRoot] })),
+    }));
+
+    await useAppStore.getState().setWorkspaceExplorerRoot(wsId, extraRoot);
+    expect(useAppStore.getState().workspaceExplorerById[wsId]).toMatchObject({
+      rootPath: extraRoot,
+      currentPath: extraRoot,
+    });
+
+    await useAppStore.getState().navigateWorkspaceFiles(wsId, `${extraRoot}/dir1`);
+    await useAppStore.getState().navigateWorkspaceFilesUp(wsId);
+    await useAppStore.getState().navigateWorkspaceFilesUp(wsId);
+    expect(useAppStore.getState().workspaceExplorerById[wsId]?.currentPath).toBe(extraRoot);
+
+    await useAppStore.getState().setWorkspaceExplorerRoot(wsId, "/tmp/elsewhere");
+    expect(useAppStore.getState().workspaceExplorerById[wsId]?.rootPath).toBe(extraRoot);
+  });
+
   test("selectWorkspaceFile sets selectedPath", async () => {
     await useAppStore.getState().navigateWorkspaceFiles(wsId, rootPath);
     useAppStore.getState().selectWorkspaceFile(wsId, `${rootPath}/file1.txt`);
@@ -0,0 +1,64 @@
+import { describe, expect, mock, test } from "bun:test";
+
+import type { SystemAppearance } from "../src/lib/desktopApi";
+import { createElectronMock } from "./helpers/mockElectron";
+
+let updated: (() => void) | null = null;
+const nativeTheme = {
+  themeSource: "system",
+  shouldUseDarkColors: false,
+  shouldUseDarkColorsForSystemIntegratedUI: false,
+  shouldUseHighContrastColors: false,
+  shouldUseInvertedColorScheme: false,
+  prefersReducedTransparency: false,
+  inForcedColorsMode: false,
+  on: (_event: string, handler: () => void) => {
+    updated = handler;
+  },
+  off: () => {
+    updated = null;
+  },
+};
+
+mock.module("electron", () => ({ ...createElectronMock(), nativeTheme }));
+
+const { normalizeAccentColor, registerSystemAppearanceListener, resolveSystemColorScheme } =
+  await import("../electron/services/appearance");
+
+describe("system appearance", () => {
+  test("resolves the color scheme with high contrast taking precedence", () => {
+    const base = {
+      shouldUseDarkColors: false,
+      shouldUseHighContrastColors: false,
+      inForcedColorsMode: false,
+    };
+    expect(resolveSystemColorScheme(base)).toBe("light");
+    expect(resolveSystemColorScheme({ ...base, shouldUseDarkColors: true })).toBe("dark");
+    expect(
+      resolveSystemColorScheme({ ...base, shouldUseDarkColors: true, inForcedColorsMode: true }),
+    ).toBe("high-contrast");
+  });
+
+  test("normalizes the platform accent color to #rrggbb", () => {
+    expect(normalizeAccentColor("0A84FFFF")).toBe("#0a84ff");
+    expect(normalizeAccentColor("#34c759")).toBe("#34c759");
+    expect(normalizeAccentColor("")).toBeNull();
+    expect(normalizeAccentColor("blue")).toBeNull();
+    expect(normalizeAccentColor(undefined)).toBeNull();
+  });
+
+  test("only sends snapshots that changed and detaches on dispose", () => {
+    const sent: SystemAppearance[] = [];
+
+    const dispose = registerSystemAppearanceListener((appearance) => sent.push(appearance));
+    updated?.();
+    nativeTheme.shouldUseDarkColors = true;
+    updated?.();
+    updated?.();
+
+    expect(sent).toHaveLength(1);
+    expect(sent[0]).toMatchObject({ shouldUseDarkColors: true, colorScheme: "dark" });
+    dispose();
+    expect(updated).toBeNull();
+  });
+});
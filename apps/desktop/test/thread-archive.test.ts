@@ -0,0 +1,229 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function transcriptLine(threadId: string, ts: string, text: string): string {
+  return JSON.stringify({
+    ts,
+    threadId,
+    direction: "client",
+    payload: { type: "user_message", text },
+  });
+}
+
+async function pathExists(filePath: string): Promise<boolean> {
+  return await fs
+    .access(filePath)
+    .then(() => true)
+    .catch(() => false);
+}
+
+describe("thread transcript archiving", () => {
+  let livePath = "";
+  let archivePath = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-thread-archive-"));
+    userDataDir = path.join(root, "Cowork");
+    livePath = path.join(userDataDir, "transcripts", "thread-1.jsonl");
+    archivePath = path.join(userDataDir, "transcripts-archive", "thread-1.jsonl.zst");
+    const workspacePath = path.join(root, "project");
+    await fs.mkdir(path.dirname(livePath), { recursive: true });
+    await fs.mkdir(workspacePath, { recursive: true });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-1",
+            name: "Project",
+            path: workspacePath,
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastOpenedAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+        threads: [
+          {
+            id: "thread-1",
+            workspaceId: "ws-1",
+            title: "Archived",
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastMessageAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+      }),
+      "utf8",
+    );
+    await fs.writeFile(
+      livePath,
+      `${transcriptLine("thread-1", "2026-01-01T00:00:00.000Z", "first")}\n`,
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("archiving compresses the transcript and keeps it readable", async () => {
+    const persistence = new PersistenceService({
+      now: () => new Date("2026-02-01T00:00:00.000Z"),
+    });
+
+    const thread = await persistence.archiveThread("thread-1");
+
+    expect(thread).toMatchObject({ archived: true, archivedAt: "2026-02-01T00:00:00.000Z" });
+    expect(await pathExists(livePath)).toBe(false);
+    expect(await pathExists(archivePath)).toBe(true);
+    expect((await persistence.loadState()).threads[0]?.archived).toBe(true);
+
+    await fs.writeFile(
+      livePath,
+      `${transcriptLine("thread-1", "2026-02-02T00:00:00.000Z", "second")}\n`,
+      "utf8",
+    );
+    const events = await persistence.readTranscript("thread-1");
+    expect(events.map((event) => (event.payload as { text: string }).text)).toEqual([
+      "first",
+      "second",
+    ]);
+  });
+
+  test("unarchiving restores the JSONL transcript and clears the flag", async () => {
+    const persistence = new PersistenceService();
+    await persistence.archiveThread("thread-1");
+
+    const thread = await persistence.unarchiveThread("thread-1");
+
+    expect(thread.archived).toBe(false);
+    expect(thread.archivedAt).toBeUndefined();
+    expect(await pathExists(archivePath)).toBe(false);
+    expect(await fs.readFile(livePath, "utf8")).toContain('"first"');
+  });
+
+  test("deleting a transcript also removes its archive", async () => {
+    const persistence = new PersistenceService();
+    await persistence.archiveThread("thread-1");
+
+    await persistence.deleteTranscript("thread-1");
+
+    expect(await pathExists(archivePath)).toBe(false);
+    expect(await persistence.readTranscript("thread-1")).toEqual([]);
+    await expect(persistence.archiveThread("missing")).rejects.toThrow("Unknown thread: missing");
+  });
+
+  test("forking copies the transcript prefix into a new thread", async () => {
+    await fs.appendFile(
+      livePath,
+      `${transcriptLine("thread-1", "2026-01-02T00:00:00.000Z", "second")}\n`,
+      "utf8",
+    );
+    const persistence = new PersistenceService({
+      now: () => new Date("2026-02-01T00:00:00.000Z"),
+      createId: () => "fork-1",
+    });
+
+    const fork = await persistence.forkThread({ threadId: "thread-1", throughEventIndex: 0 });
+
+    expect(fork).toMatchObject({
+      id: "fork-1",
+      workspaceId: "ws-1",
+      title: "Archived (fork)",
+      status: "disconnected",
+      sessionId: null,
+      createdAt: "2026-02-01T00:00:00.000Z",
+    });
+    const events = await persistence.readTranscript("fork-1");
+    expect(events).toHaveLength(1);
+    expect(events[0]).toMatchObject({ threadId: "fork-1", payload: { text: "first" } });
+    expect((await persistence.loadState()).threads.map((thread) => thread.id)).toEqual([
+      "thread-1",
+      "fork-1",
+    ]);
+    expect(await persistence.readTranscript("thread-1")).toHaveLength(2);
+    await expect(persistence.forkThread({ threadId: "missing" })).rejects.toThrow(
+      "Unknown thread: missing",
+    );
+    await expect(
+      persistence.forkThread({ threadId: "thread-1", throughEventIndex: 5 }),
+    ).rejects.toThrow("Event index 5 is past the end of the transcript");
+  });
+
+  test("merging interleaves the source transcript and marks the source merged", async () => {
+    const sourcePath = path.join(userDataDir, "transcripts", "thread-2.jsonl");
+    await fs.appendFile(
+      livePath,
+      `${transcriptLine("thread-1", "2026-01-03T00:00:00.000Z", "third")}\n`,
+      "utf8",
+    );
+    await fs.writeFile(
+      sourcePath,
+      `${transcriptLine("thread-2", "2026-01-02T00:00:00.000Z", "second")}\n`,
+      "utf8",
+    );
+    const statePath = path.join(userDataDir, "state.json");
+    const state = JSON.parse(await fs.readFile(statePath, "utf8"));
+    state.threads.push({
+      id: "thread-2",
+      workspaceId: "ws-1",
+      title: "Fragment",
+      createdAt: "2026-01-02T00:00:00.000Z",
+      lastMessageAt: "2026-01-02T00:00:00.000Z",
+      messageCount: 1,
+    });
+    await fs.writeFile(statePath, JSON.stringify(state), "utf8");
+    const persistence = new PersistenceService({
+      now: () => new Date("2026-02-01T00:00:00.000Z"),
+    });
+
+    const result = await persistence.mergeThreads({
+      sourceThreadId: "thread-2",
+      targetThreadId: "thread-1",
+    });
+
+    expect(result.mergedEventCount).toBe(1);
+    expect(result.eventIndexes).toEqual({ target: [0, 2], source: [1] });
+    expect(result.thread).toMatchObject({ id: "thread-1", messageCount: 1 });
+    expect(result.source).toMatchObject({
+      id: "thread-2",
+      mergedIntoThreadId: "thread-1",
+      mergedAt: "2026-02-01T00:00:00.000Z",
+    });
+    const events = await persistence.readTranscript("thread-1");
+    expect(events.map((event) => (event.payload as { text: string }).text)).toEqual([
+      "first",
+      "second",
+      "third",
+    ]);
+    expect(events.every((event) => event.threadId === "thread-1")).toBe(true);
+    expect((await persistence.loadState()).threads[1]?.mergedIntoThreadId).toBe("thread-1");
+    await expect(
+      persistence.mergeThreads({ sourceThreadId: "thread-2", targetThreadId: "thread-1" }),
+    ).rejects.toThrow("Thread thread-2 was already merged into thread-1");
+    await expect(
+      persistence.mergeThreads({ sourceThreadId: "thread-1", targetThreadId: "thread-1" }),
+    ).rejects.toThrow("cannot be merged into itself");
+  });
+});
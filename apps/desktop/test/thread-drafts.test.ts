@@ -0,0 +1,81 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { ThreadDraftStore } from "../electron/services/threadDrafts";
+import { saveThreadDraftInputSchema } from "../src/lib/desktopSchemas";
+
+const NOW = new Date("2026-06-01T00:00:00.000Z");
+
+describe("thread drafts", () => {
+  let directory = "";
+
+  const createStore = (debounceMs = 60_000) =>
+    new ThreadDraftStore({ directory, debounceMs, now: () => NOW });
+
+  beforeEach(async () => {
+    directory = path.join(await fs.mkdtemp(path.join(os.tmpdir(), "cowork-drafts-")), "drafts");
+  });
+
+  afterEach(async () => {
+    await fs.rm(path.dirname(directory), { recursive: true, force: true });
+  });
+
+  test("answers from memory until the debounced write lands, then survives a restart", async () => {
+    const store = createStore();
+    store.save({ threadId: "thread-1", content: "Half a th" });
+    store.save({
+      threadId: "thread-1",
+      content: "Half a thought",
+      attachments: [{ name: "notes.md", path: "/tmp/notes.md", sizeBytes: 12 }],
+    });
+
+    expect(await fs.readdir(directory).catch(() => [])).toEqual([]);
+    expect((await store.load("thread-1"))?.content).toBe("Half a thought");
+
+    await store.flush();
+
+    expect(await createStore().load("thread-1")).toEqual({
+      threadId: "thread-1",
+      content: "Half a thought",
+      attachments: [{ name: "notes.md", path: "/tmp/notes.md", sizeBytes: 12 }],
+      updatedAt: NOW.toISOString(),
+    });
+  });
+
+  test("writes on its own once typing pauses", async () => {
+    const store = createStore(5);
+    store.save({ threadId: "thread-1", content: "Draft" });
+
+    await Bun.sleep(30);
+
+    expect(await fs.readdir(directory)).toEqual(["thread-1.json"]);
+  });
+
+  test("clearing a draft removes its file", async () => {
+    const store = createStore();
+    store.save({ threadId: "thread-1", content: "Draft" });
+    await store.flush();
+
+    expect(store.save({ threadId: "thread-1", content: "   " })).toBeNull();
+    expect(await store.load("thread-1")).toBeNull();
+    await store.flush();
+
+    expect(await fs.readdir(directory)).toEqual([]);
+    expect(await createStore().load("thread-1")).toBeNull();
+  });
+
+  test("rejects unsafe thread ids and oversized attachment lists", () => {
+    expect(() => createStore().save({ threadId: "../state", content: "x" })).toThrow(
+      "threadId contains invalid characters",
+    );
+    expect(
+      saveThreadDraftInputSchema.safeParse({
+        threadId: "thread-1",
+        content: "x",
+        attachments: Array.from({ length: 101 }, (_, index) => ({ name: `file-${index}` })),
+      }).success,
+    ).toBe(false);
+  });
+});
@@ -0,0 +1,146 @@
+import { describe, expect, test } from "bun:test";
+import { createHash } from "node:crypto";
+import path from "node:path";
+
+import {
+  parseUnifiedDiff,
+  summarizeThreadFileChanges,
+} from "../electron/services/threadFileChanges";
+import type { FeedItem } from "../src/app/types";
+
+const WORKSPACE = path.resolve("/work/project");
+const START = "2026-01-01T00:00:00.000Z";
+
+let nextId = 0;
+function toolItem(
+  name: string,
+  args: unknown,
+  ts: string,
+  overrides: Partial<Extract<FeedItem, { kind: "tool" }>> = {},
+): FeedItem {
+  nextId += 1;
+  return {
+    id: `tool-${nextId}`,
+    kind: "tool",
+    ts,
+    name,
+    state: "output-available",
+    args,
+    ...overrides,
+  };
+}
+
+describe("parseUnifiedDiff", () => {
+  test("counts hunks and lines per file", () => {
+    const diff = [
+      "diff --git a/src/a.ts b/src/a.ts",
+      "--- a/src/a.ts",
+      "+++ b/src/a.ts",
+      "@@ -1,2 +1,2 @@",
+      "-old",
+      "+new",
+      "@@ -10,1 +10,2 @@",
+      " context",
+      "+added",
+      "--- a/src/gone.ts",
+      "+++ /dev/null",
+      "@@ -1,1 +0,0 @@",
+      "-bye",
+    ].join("\n");
+
+    expect(parseUnifiedDiff(diff)).toEqual([
+      { path: "src/a.ts", hunkCount: 2, linesAdded: 2, linesRemoved: 1 },
+      { path: "src/gone.ts", hunkCount: 1, linesAdded: 0, linesRemoved: 1 },
+    ]);
+  });
+
+  test("attributes headerless hunks to the fallback path", () => {
+    expect(parseUnifiedDiff("@@ -1 +1 @@\n-a\n+b\n", "notes.md")).toEqual([
+      { path: "notes.md", hunkCount: 1, linesAdded: 1, linesRemoved: 1 },
+    ]);
+  });
+});
+
+describe("summarizeThreadFileChanges", () => {
+  test("aggregates edits and writes per file, most recent first", () => {
+    const feed: FeedItem[] = [
+      toolItem("write", { filePath: "src/app.ts", content: "a\nb\n" }, START),
+      toolItem(
+        "edit",
+        { filePath: path.join(WORKSPACE, "src/app.ts"), oldString: "a", newString: "x\ny" },
+        "2026-01-01T00:01:00.000Z",
+      ),
+      toolItem("write", { filePath: "README.md", content: "hi" }, "2026-01-01T00:02:00.000Z"),
+      toolItem("read", { filePath: "src/other.ts" }, "2026-01-01T00:03:00.000Z"),
+    ];
+
+    const summary = summarizeThreadFileChanges("thread-1", feed, WORKSPACE);
+
+    expect(summary.threadId).toBe("thread-1");
+    expect(summary.files).toEqual([
+      {
+        path: path.join(WORKSPACE, "README.md"),
+        displayPath: "README.md",
+        changeCount: 1,
+        hunkCount: 1,
+        linesAdded: 1,
+        linesRemoved: 0,
+        finalContentHash: createHash("sha256").update("hi").digest("hex"),
+        lastChangedAt: "2026-01-01T00:02:00.000Z",
+      },
+      {
+        path: path.join(WORKSPACE, "src/app.ts"),
+        displayPath: path.join("src", "app.ts"),
+        changeCount: 2,
+        hunkCount: 2,
+        linesAdded: 4,
+        linesRemoved: 1,
+        finalContentHash: null,
+        lastChangedAt: "2026-01-01T00:01:00.000Z",
+      },
+    ]);
+  });
+
+  test("skips tool calls that did not complete", () => {
+    const feed: FeedItem[] = [
+      toolItem("write", { filePath: "a.txt", content: "x" }, START, { state: "output-error" }),
+      toolItem("edit", { filePath: "b.txt", oldString: "x", newString: "y" }, START, {
+        state: "output-denied",
+      }),
+    ];
+
+    expect(summarizeThreadFileChanges("thread-1", feed, WORKSPACE).files).toEqual([]);
+  });
+
+  test("reads Codex file changes from the patch result against its cwd", () => {
+    const cwd = path.join(WORKSPACE, "packages/core");
+    const feed: FeedItem[] = [
+      toolItem("fileChange", { cwd, paths: ["index.ts"] }, START, {
+        result: "--- a/index.ts\n+++ b/index.ts\n@@ -1 +1 @@\n-a\n+b\n",
+        completedAt: "2026-01-01T00:00:05.000Z",
+      }),
+    ];
+
+    expect(summarizeThreadFileChanges("thread-1", feed, WORKSPACE).files).toEqual([
+      {
+        path: path.join(cwd, "index.ts"),
+        displayPath: path.join("packages", "core", "index.ts"),
+        changeCount: 1,
+        hunkCount: 1,
+        linesAdded: 1,
+        linesRemoved: 1,
+        finalContentHash: null,
+        lastChangedAt: "2026-01-01T00:00:05.000Z",
+      },
+    ]);
+  });
+
+  test("keeps files outside the workspace as absolute display paths", () => {
+    const outside = path.resolve("/tmp/scratch.txt");
+    const feed = [toolItem("write", { filePath: outside, content: "x", mode: "append" }, START)];
+
+    const [file] = summarizeThreadFileChanges("thread-1", feed, WORKSPACE).files;
+    expect(file?.displayPath).toBe(outside);
+    expect(file?.finalContentHash).toBeNull();
+  });
+});
@@ -0,0 +1,30 @@
+import { describe, expect, test } from "bun:test";
+
+import { normalizeThreadRouting, threadRoutingDiffers } from "../src/app/threadRouting";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+describe("thread routing", () => {
+  test("keeps known providers with a model and drops everything else", () => {
+    expect(
+      normalizeThreadRouting({ provider: "openai", model: " gpt-5.4 ", updatedAt: TS }),
+    ).toEqual({ provider: "openai", model: "gpt-5.4", updatedAt: TS });
+    expect(normalizeThreadRouting({ provider: "openai", model: "gpt-5.4" })?.updatedAt).toBe(
+      new Date(0).toISOString(),
+    );
+    expect(normalizeThreadRouting({ provider: "not-a-provider", model: "x" })).toBeUndefined();
+    expect(normalizeThreadRouting({ provider: "openai", model: "  " })).toBeUndefined();
+    expect(normalizeThreadRouting(["openai", "gpt-5.4"])).toBeUndefined();
+    expect(normalizeThreadRouting(null)).toBeUndefined();
+  });
+
+  test("reports a difference only when the session runs another provider or model", () => {
+    const routing = { provider: "openai" as const, model: "gpt-5.4", updatedAt: TS };
+
+    expect(threadRoutingDiffers(undefined, { provider: "anthropic", model: "x" })).toBe(false);
+    expect(threadRoutingDiffers(routing, { provider: "openai", model: "gpt-5.4" })).toBe(false);
+    expect(threadRoutingDiffers(routing, { provider: "openai", model: "gpt-5.4-mini" })).toBe(true);
+    expect(threadRoutingDiffers(routing, { provider: "anthropic", model: "gpt-5.4" })).toBe(true);
+    expect(threadRoutingDiffers(routing, null)).toBe(true);
+  });
+});
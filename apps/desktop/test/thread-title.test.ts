@@ -0,0 +1,99 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { heuristicThreadTitle } = await import("../electron/services/stateRebuild");
+
+function threadRecord(id: string, title: string, titleSource: string) {
+  return {
+    id,
+    workspaceId: "ws-1",
+    title,
+    titleSource,
+    createdAt: "2026-01-01T00:00:00.000Z",
+    lastMessageAt: "2026-01-01T00:00:00.000Z",
+  };
+}
+
+describe("thread titles", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-thread-title-"));
+    userDataDir = path.join(root, "Cowork");
+    const workspacePath = path.join(root, "project");
+    await fs.mkdir(userDataDir, { recursive: true });
+    await fs.mkdir(workspacePath, { recursive: true });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-1",
+            name: "Project",
+            path: workspacePath,
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastOpenedAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+        threads: [
+          threadRecord("thread-new", "New thread", "default"),
+          threadRecord("thread-named", "My notes", "manual"),
+        ],
+      }),
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("setThreadTitle replaces generated titles and keeps manual ones", async () => {
+    const persistence = new PersistenceService();
+
+    const titled = await persistence.setThreadTitle("thread-new", "  Fix login  ", "model");
+    const kept = await persistence.setThreadTitle("thread-named", "Fix login", "heuristic");
+    const renamed = await persistence.setThreadTitle("thread-named", "Login work", "manual");
+
+    expect(titled).toMatchObject({ title: "Fix login", titleSource: "model" });
+    expect(kept).toMatchObject({ title: "My notes", titleSource: "manual" });
+    expect(renamed).toMatchObject({ title: "Login work", titleSource: "manual" });
+    expect((await persistence.loadState()).threads.map((thread) => thread.title)).toEqual([
+      "Fix login",
+      "Login work",
+    ]);
+    await expect(persistence.setThreadTitle("thread-new", " ", "model")).rejects.toThrow(
+      "Thread title must not be empty",
+    );
+    await expect(persistence.setThreadTitle("missing", "Title", "model")).rejects.toThrow(
+      "Unknown thread: missing",
+    );
+  });
+
+  test("heuristic titles collapse whitespace and fit a sidebar row", () => {
+    expect(heuristicThreadTitle("  fix the\n  login bug ")).toBe("fix the login bug");
+    const long = heuristicThreadTitle("word ".repeat(40));
+    expect(long).toHaveLength(60);
+    expect(long.endsWith("…")).toBe(true);
+  });
+});
@@ -0,0 +1,122 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { TranscriptAnnotationStore } from "../electron/services/transcriptAnnotations";
+import type { TranscriptEvent } from "../src/app/types";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+function event(ts: string): TranscriptEvent {
+  return {
+    ts,
+    threadId: "thread-1",
+    direction: "server",
+    payload: { type: "assistant_message", sessionId: "s-1", text: "done" },
+  };
+}
+
+describe("transcript annotations", () => {
+  let root = "";
+  let clock = new Date(TS);
+
+  const createStore = () =>
+    new TranscriptAnnotationStore({
+      directory: path.join(root, "annotations"),
+      readTranscript: async (threadId) =>
+        threadId === "thread-1" ? [event(TS), event("2026-01-01T00:00:05.000Z")] : [],
+      now: () => clock,
+    });
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "transcript-annotations-"));
+    clock = new Date(TS);
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  test("upserts by event index and keeps the original creation time", async () => {
+    const store = createStore();
+    await store.annotate({
+      threadId: "thread-1",
+      eventIndex: 1,
+      note: "  Root cause  ",
+      tags: ["Bug", "bug ", " "],
+    });
+    await store.annotate({ threadId: "thread-1", eventIndex: 0, note: "", tags: ["start"] });
+
+    clock = new Date("2026-01-02T00:00:00.000Z");
+    const updated = await store.annotate({
+      threadId: "thread-1",
+      eventIndex: 1,
+      note: "Root cause, confirmed",
+      tags: ["bug"],
+    });
+
+    expect(updated).toEqual({
+      threadId: "thread-1",
+      eventIndex: 1,
+      eventTs: "2026-01-01T00:00:05.000Z",
+      note: "Root cause, confirmed",
+      tags: ["bug"],
+      createdAt: TS,
+      updatedAt: "2026-01-02T00:00:00.000Z",
+    });
+    // A fresh store reads the same annotations back from disk, ordered by event.
+    expect((await createStore().list("thread-1")).map((entry) => entry.eventIndex)).toEqual([
+      0, 1,
+    ]);
+  });
+
+  test("removes an annotation cleared of its note and tags", async () => {
+    const store = createStore();
+    await store.annotate({ threadId: "thread-1", eventIndex: 0, note: "Keep?" });
+
+    expect(await store.annotate({ threadId: "thread-1", eventIndex: 0, note: " " })).toBeNull();
+    expect(await store.list("thread-1")).toEqual([]);
+    expect(await fs.readdir(path.join(root, "annotations"))).toEqual([]);
+  });
+
+  test("rejects events outside the transcript and unsafe thread ids", async () => {
+    const store = createStore();
+
+    await expect(
+      store.annotate({ threadId: "thread-1", eventIndex: 2, note: "Too far" }),
+    ).rejects.toThrow("Thread thread-1 has no event 2 (it has 2)");
+    await expect(
+      store.annotate({ threadId: "../thread-1", eventIndex: 0, note: "Escape" }),
+    ).rejects.toThrow();
+    expect(await store.list("thread-1")).toEqual([]);
+  });
+
+  test("moves annotations to their merged events and copies the source's", async () => {
+    const store = createStore();
+    await store.annotate({ threadId: "thread-1", eventIndex: 0, note: "Start" });
+    await store.annotate({ threadId: "thread-1", eventIndex: 1, note: "Done" });
+    await store.replace("thread-2", [
+      {
+        threadId: "thread-2",
+        eventIndex: 0,
+        eventTs: "2026-01-01T00:00:02.000Z",
+        note: "Side quest",
+        tags: [],
+        createdAt: TS,
+        updatedAt: TS,
+      },
+    ]);
+
+    await store.merge("thread-2", "thread-1", { target: [0, 2], source: [1] });
+
+    expect(
+      (await store.list("thread-1")).map((entry) => [entry.eventIndex, entry.note, entry.threadId]),
+    ).toEqual([
+      [0, "Start", "thread-1"],
+      [1, "Side quest", "thread-1"],
+      [2, "Done", "thread-1"],
+    ]);
+    expect(await store.list("thread-2")).toHaveLength(1);
+  });
+});
@@ -0,0 +1,272 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+
+import { type BackupStore, createBackupStore } from "../electron/services/backupStores";
+import type { TranscriptFileInfo } from "../electron/services/persistence";
+import { TranscriptBackupService } from "../electron/services/transcriptBackup";
+import type { PersistedState, TranscriptEvent } from "../src/app/types";
+import type { BackupTarget } from "../src/lib/desktopApi";
+import { backupSyncConfigSchema, configureBackupSyncInputSchema } from "../src/lib/desktopSchemas";
+
+const fakeKeychain = {
+  isEncryptionAvailable: () => true,
+  encryptString: (plainText: string) => Buffer.from(plainText, "utf8").reverse(),
+  decryptString: (encrypted: Buffer) => Buffer.from(encrypted).reverse().toString("utf8"),
+};
+
+const S3_TARGET: Extract<BackupTarget, { kind: "s3" }> = {
+  kind: "s3",
+  endpoint: "https://backup.example.test",
+  bucket: "cowork-backups",
+  region: "us-east-1",
+};
+
+class MemoryBackupStore implements BackupStore {
+  readonly objects = new Map<string, Buffer>();
+  puts = 0;
+
+  async has(key: string): Promise<boolean> {
+    return this.objects.has(key);
+  }
+
+  async putIfAbsent(key: string, body: Uint8Array): Promise<"created" | "exists"> {
+    if (this.objects.has(key)) {
+      return "exists";
+    }
+    this.puts += 1;
+    this.objects.set(key, Buffer.from(body));
+    return "created";
+  }
+
+  keys(prefix: string): string[] {
+    return [...this.objects.keys()].filter((key) => key.startsWith(prefix)).sort();
+  }
+
+  json(key: string) {
+    return JSON.parse((this.objects.get(key) as Buffer).toString("utf8"));
+  }
+}
+
+function event(text: string): TranscriptEvent {
+  return {
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId: "thread-1",
+    direction: "client",
+    payload: { type: "user_message", text },
+  };
+}
+
+describe("transcript backup sync", () => {
+  let root = "";
+  let transcripts: Map<string, TranscriptEvent[]>;
+  let state: PersistedState;
+  let store: MemoryBackupStore;
+
+  const fileInfo = (transcriptId: string): TranscriptFileInfo => ({
+    transcriptId,
+    archived: false,
+    sizeBytes: JSON.stringify(transcripts.get(transcriptId)).length,
+    modifiedAtMs: (transcripts.get(transcriptId) ?? []).length,
+  });
+
+  const createService = () =>
+    new TranscriptBackupService({
+      stateFilePath: path.join(root, "backup-sync.json"),
+      credentialsFilePath: path.join(root, "backup-credentials.json"),
+      keychain: fakeKeychain,
+      persistence: {
+        loadState: async () => state,
+        listTranscriptFiles: async () => [...transcripts.keys()].map(fileInfo),
+        readTranscript: async (threadId) => transcripts.get(threadId) ?? [],
+      },
+      createStore: () => store,
+    });
+
+  const configure = async (service: TranscriptBackupService) => {
+    const { credentialsRef } = await service.saveCredentials({
+      kind: "s3",
+      accessKeyId: "AKIDEXAMPLE",
+      secretAccessKey: "secret",
+    });
+    return await service.configure({ target: S3_TARGET, credentialsRef });
+  };
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-backup-"));
+    transcripts = new Map([["thread-1", [event("one"), event("two")]]]);
+    state = { version: 2, workspaces: [], threads: [] };
+    store = new MemoryBackupStore();
+  });
+
+  afterEach(async () => {
+    await fs.rm(root, { recursive: true, force: true });
+  });
+
+  test("uploads only the lines added since the last sync", async () => {
+    const service = createService();
+    const config = await configure(service);
+    expect(backupSyncConfigSchema.safeParse(config).success).toBe(true);
+
+    const first = await service.syncNow();
+    expect(first).toMatchObject({ transcripts: 1, segments: 1, stateUploaded: true });
+
+    transcripts.get("thread-1")?.push(event("three"));
+    const second = await createService().syncNow();
+    expect(second).toMatchObject({ transcripts: 1, segments: 1, stateUploaded: false });
+
+    const segments = store.keys(`devices/${config.deviceId}/transcripts/thread-1/`);
+    expect(segments).toEqual([
+      `devices/${config.deviceId}/transcripts/thread-1/g0/0000000000.json`,
+      `devices/${config.deviceId}/transcripts/thread-1/g0/0000000002.json`,
+    ]);
+    const appended = store.json(segments[1] as string);
+    expect(appended).toMatchObject({ fromLine: 2, toLine: 3 });
+    const chunk = store.objects.get(`chunks/${appended.chunks[0].sha256}`) as Buffer;
+    expect(zlib.gunzipSync(chunk).toString("utf8")).toBe(`${JSON.stringify(event("three"))}\n`);
+
+    const putsBefore = store.puts;
+    const third = await createService().syncNow();
+    expect(third).toMatchObject({ transcripts: 0, segments: 0, uploadedChunks: 0 });
+    expect(store.puts).toBe(putsBefore);
+  });
+
+  test("starts a new generation for a rewritten transcript and reuses its chunks", async () => {
+    const service = createService();
+    const config = await configure(service);
+    await service.syncNow();
+
+    transcripts.set("thread-1", [event("two")]);
+    await service.syncNow();
+    transcripts.set("thread-1", [event("one"), event("two")]);
+    const result = await service.syncNow();
+
+    expect(store.keys(`devices/${config.deviceId}/transcripts/thread-1/`)).toEqual([
+      `devices/${config.deviceId}/transcripts/thread-1/g0/0000000000.json`,
+      `devices/${config.deviceId}/transcripts/thread-1/g1/0000000000.json`,
+      `devices/${config.deviceId}/transcripts/thread-1/g2/0000000000.json`,
+    ]);
+    expect(result).toMatchObject({ uploadedChunks: 0, reusedChunks: 1 });
+  });
+
+  test("moves to a new generation when an interrupted sync left a segment behind", async () => {
+    const service = createService();
+    const config = await configure(service);
+    const segmentKey = `devices/${config.deviceId}/transcripts/thread-1/g0/0000000000.json`;
+    await store.putIfAbsent(segmentKey, Buffer.from("{}"));
+
+    await service.syncNow();
+
+    expect(store.json(segmentKey)).toEqual({});
+    expect(store.keys(`devices/${config.deviceId}/transcripts/thread-1/g1/`)).toHaveLength(1);
+  });
+
+  test("keeps credentials sealed and rejects ones for another kind of target", async () => {
+    const service = createService();
+    const { credentialsRef } = await service.saveCredentials({
+      kind: "webdav",
+      username: "cowork",
+      password: "hunter2",
+    });
+
+    await expect(service.configure({ target: S3_TARGET, credentialsRef })).rejects.toThrow(
+      "Saved credentials are for webdav, not s3.",
+    );
+    const vault = await fs.readFile(path.join(root, "backup-credentials.json"), "utf8");
+    expect(vault).not.toContain("hunter2");
+    await expect(service.syncNow()).rejects.toThrow("Backup sync is not configured.");
+
+    const config = await service.configure({
+      target: { kind: "webdav", url: "https://dav.example.test/cowork" },
+      credentialsRef,
+    });
+    expect(config.credentialsRef).toBe(credentialsRef);
+    expect((await service.configure({ target: null })).credentialsRef).toBeNull();
+  });
+
+  test("rejects targets that are not plain http(s) URLs", () => {
+    expect(
+      configureBackupSyncInputSchema.safeParse({
+        target: { kind: "webdav", url: "file:///tmp/backup" },
+        credentialsRef: "backup-1",
+      }).success,
+    ).toBe(false);
+    expect(
+      configureBackupSyncInputSchema.safeParse({
+        target: { ...S3_TARGET, bucket: "Not_A_Bucket" },
+        credentialsRef: "backup-1",
+      }).success,
+    ).toBe(false);
+  });
+});
+
+describe("backup stores", () => {
+  test("WebDAV creates missing collections and treats 412 as already stored", async () => {
+    const requests: string[] = [];
+    const collections = new Set<string>();
+    const stored = new Set<string>();
+    const webdav = createBackupStore(
+      { kind: "webdav", url: "https://dav.example.test/cowork/" },
+      { kind: "webdav", username: "cowork", password: "hunter2" },
+      {
+        fetch: async (url, init) => {
+          requests.push(`${init.method} ${url}`);
+          const key = url.replace("https://dav.example.test/cowork/", "");
+          if (init.method === "MKCOL") {
+            collections.add(key);
+            return new Response(null, { status: 201 });
+          }
+          if (stored.has(key)) {
+            return new Response(null, { status: 412 });
+          }
+          if (!collections.has("chunks/")) {
+            return new Response(null, { status: 409 });
+          }
+          stored.add(key);
+          return new Response(null, { status: 201 });
+        },
+      },
+    );
+
+    expect(await webdav.putIfAbsent("chunks/abc", Buffer.from("x"), "application/gzip")).toBe(
+      "created",
+    );
+    expect(await webdav.putIfAbsent("chunks/abc", Buffer.from("x"), "application/gzip")).toBe(
+      "exists",
+    );
+    expect(requests).toEqual([
+      "PUT https://dav.example.test/cowork/chunks/abc",
+      "MKCOL https://dav.example.test/cowork/chunks/",
+      "PUT https://dav.example.test/cowork/chunks/abc",
+      "PUT https://dav.example.test/cowork/chunks/abc",
+    ]);
+  });
+
+  test("S3 requests are signed path-style and conditional", async () => {
+    const seen: Array<{ url: string; headers: Record<string, string> }> = [];
+    const s3 = createBackupStore(
+      { ...S3_TARGET, prefix: "laptop" },
+      { kind: "s3", accessKeyId: "AKIDEXAMPLE", secretAccessKey: "secret" },
+      {
+        now: () => new Date("2026-01-01T00:00:00.000Z"),
+        fetch: async (url, init) => {
+          seen.push({ url, headers: init.headers as Record<string, string> });
+          return new Response(null, { status: 200 });
+        },
+      },
+    );
+
+    await s3.putIfAbsent("chunks/abc", Buffer.from("x"), "application/gzip");
+
+    expect(seen).toHaveLength(1);
+    const [request] = seen;
+    expect(request?.url).toBe("https://backup.example.test/cowork-backups/laptop/chunks/abc");
+    expect(request?.headers["if-none-match"]).toBe("*");
+    expect(request?.headers["x-amz-date"]).toBe("20260101T000000Z");
+    expect(request?.headers.authorization).toStartWith(
+      "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260101/us-east-1/s3/aws4_request, SignedHeaders=",
+    );
+  });
+});
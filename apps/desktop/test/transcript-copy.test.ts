@@ -0,0 +1,91 @@
+import { afterEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  parseEnvSecrets,
+  readWorkspaceEnvSecrets,
+  redactTranscriptText,
+  renderTranscriptMarkdown,
+} from "../electron/services/transcriptCopy";
+import type { FeedItem } from "../src/app/types";
+
+const TS = "2026-01-01T00:00:00.000Z";
+
+describe("transcript copy", () => {
+  let workspacePath = "";
+
+  afterEach(async () => {
+    if (workspacePath) {
+      await fs.rm(workspacePath, { recursive: true, force: true });
+    }
+    workspacePath = "";
+  });
+
+  test("renders messages, tool calls, todos, and errors but skips internal items", () => {
+    const feed: FeedItem[] = [
+      { id: "1", kind: "message", role: "user", ts: TS, text: "Deploy it\n\nplease" },
+      { id: "2", kind: "reasoning", mode: "reasoning", ts: TS, text: "thinking" },
+      { id: "3", kind: "tool", ts: TS, name: "bash", state: "output-error" },
+      {
+        id: "4",
+        kind: "todos",
+        ts: TS,
+        todos: [
+          { content: "Build", status: "completed", activeForm: "Building" },
+          { content: "Ship", status: "pending", activeForm: "Shipping" },
+        ],
+      },
+      { id: "5", kind: "log", ts: TS, line: "debug" },
+      { id: "6", kind: "message", role: "assistant", ts: TS, text: "Done." },
+    ];
+
+    const { markdown, itemCount } = renderTranscriptMarkdown(feed);
+
+    expect(itemCount).toBe(4);
+    expect(markdown).toBe(
+      [
+        "**User:**\n\nDeploy it\n\nplease",
+        "_Tool call: `bash` (failed)_",
+        "- [x] Build\n- [ ] Ship",
+        "**Assistant:**\n\nDone.",
+      ].join("\n\n"),
+    );
+  });
+
+  test("reads .env values from the workspace root but not templates or short values", async () => {
+    workspacePath = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-copy-"));
+    await fs.writeFile(
+      path.join(workspacePath, ".env"),
+      [
+        "# comment",
+        "PORT=3000",
+        'export DB_PASSWORD="hunter2hunter2"',
+        "TOKEN=abcdefgh123 # ci",
+      ].join("\n"),
+    );
+    await fs.writeFile(path.join(workspacePath, ".env.example"), "DB_PASSWORD=placeholder-value");
+    await fs.writeFile(path.join(workspacePath, ".env.local"), "LOCAL_SECRET='local-secret-1'");
+
+    expect(await readWorkspaceEnvSecrets(workspacePath)).toEqual([
+      { name: "DB_PASSWORD", value: "hunter2hunter2" },
+      { name: "TOKEN", value: "abcdefgh123" },
+      { name: "LOCAL_SECRET", value: "local-secret-1" },
+    ]);
+    expect(await readWorkspaceEnvSecrets(path.join(workspacePath, "missing"))).toEqual([]);
+    expect(parseEnvSecrets("not an assignment")).toEqual([]);
+  });
+
+  test("redacts credentials and workspace env values while keeping prose", () => {
+    const { text, redactionCount } = redactTranscriptText(
+      "Use sk-abcdefghijklmnopqrstuvwx and the password hunter2hunter2 for alice@example.com",
+      [{ name: "DB_PASSWORD", value: "hunter2hunter2" }],
+    );
+
+    expect(text).toBe(
+      "Use [redacted-secret] and the password [redacted:DB_PASSWORD] for alice@example.com",
+    );
+    expect(redactionCount).toBe(2);
+  });
+});
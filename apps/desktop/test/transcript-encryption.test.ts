@@ -0,0 +1,164 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import crypto from "node:crypto";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { decryptTranscriptLine, encryptTranscriptLine, isEncryptedTranscriptLine } = await import(
+  "../electron/services/transcriptEncryption"
+);
+
+/** Reversible stand-in for `safeStorage` that still hides the key from the settings file. */
+const fakeKeychain = {
+  isEncryptionAvailable: () => true,
+  encryptString: (plainText: string) => Buffer.from(plainText, "utf8").reverse(),
+  decryptString: (encrypted: Buffer) => Buffer.from(encrypted).reverse().toString("utf8"),
+};
+
+function transcriptEvent(text: string) {
+  return {
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId: "thread-1",
+    direction: "client" as const,
+    payload: { type: "user_message", text },
+  };
+}
+
+describe("transcript encryption at rest", () => {
+  let transcriptPath = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-encryption-"));
+    userDataDir = path.join(root, "Cowork");
+    transcriptPath = path.join(userDataDir, "transcripts", "thread-1.jsonl");
+    await fs.mkdir(userDataDir, { recursive: true });
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("sealed lines round-trip and reject the wrong key", () => {
+    const key = crypto.randomBytes(32);
+    const sealed = encryptTranscriptLine(key, '{"hello":"world"}');
+
+    expect(isEncryptedTranscriptLine(sealed)).toBe(true);
+    expect(sealed).not.toContain("world");
+    expect(decryptTranscriptLine(key, sealed)).toBe('{"hello":"world"}');
+    expect(() => decryptTranscriptLine(crypto.randomBytes(32), sealed)).toThrow();
+  });
+
+  test("new lines are encrypted once enabled and read back transparently", async () => {
+    const persistence = new PersistenceService({ transcriptKeychain: fakeKeychain });
+    await persistence.appendTranscriptEvent(transcriptEvent("plain"));
+
+    expect(await persistence.setTranscriptEncryption(true)).toEqual({
+      enabled: true,
+      available: true,
+    });
+    await persistence.appendTranscriptEvent(transcriptEvent("secret"));
+
+    const lines = (await fs.readFile(transcriptPath, "utf8")).trim().split("\n");
+    expect(lines[0]).toContain("plain");
+    expect(isEncryptedTranscriptLine(lines[1] ?? "")).toBe(true);
+    expect(await fs.readFile(transcriptPath, "utf8")).not.toContain("secret");
+
+    const reopened = new PersistenceService({ transcriptKeychain: fakeKeychain });
+    const texts = (await reopened.readTranscript("thread-1")).map(
+      (event) => (event.payload as { text: string }).text,
+    );
+    expect(texts).toEqual(["plain", "secret"]);
+
+    await reopened.setTranscriptEncryption(false);
+    expect(await reopened.readTranscript("thread-1")).toHaveLength(2);
+  });
+
+  test("the migration encrypts existing live and archived transcripts", async () => {
+    const workspacePath = path.join(path.dirname(userDataDir), "project");
+    await fs.mkdir(workspacePath, { recursive: true });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-1",
+            name: "Project",
+            path: workspacePath,
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastOpenedAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+        threads: [
+          {
+            id: "thread-2",
+            workspaceId: "ws-1",
+            title: "Archived",
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastMessageAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+      }),
+      "utf8",
+    );
+    const persistence = new PersistenceService({ transcriptKeychain: fakeKeychain });
+    await persistence.appendTranscriptEvent(transcriptEvent("live"));
+    await persistence.appendTranscriptEvent({
+      ...transcriptEvent("archived"),
+      threadId: "thread-2",
+    });
+    await persistence.archiveThread("thread-2");
+
+    await expect(persistence.encryptExistingTranscripts()).rejects.toThrow(
+      "Turn on transcript encryption",
+    );
+    await persistence.setTranscriptEncryption(true);
+
+    expect(await persistence.encryptExistingTranscripts()).toEqual({
+      encryptedFiles: 2,
+      unchangedFiles: 0,
+    });
+    expect(await persistence.encryptExistingTranscripts()).toEqual({
+      encryptedFiles: 0,
+      unchangedFiles: 2,
+    });
+    expect(await fs.readFile(transcriptPath, "utf8")).not.toContain("live");
+    expect((await persistence.readTranscript("thread-1"))[0]?.payload).toMatchObject({
+      text: "live",
+    });
+    expect((await persistence.readTranscript("thread-2"))[0]?.payload).toMatchObject({
+      text: "archived",
+    });
+  });
+
+  test("enabling fails when the keychain is unavailable", async () => {
+    const persistence = new PersistenceService({
+      transcriptKeychain: { ...fakeKeychain, isEncryptionAvailable: () => false },
+    });
+
+    await expect(persistence.setTranscriptEncryption(true)).rejects.toThrow("keychain");
+    expect(await persistence.getTranscriptEncryption()).toEqual({
+      enabled: false,
+      available: false,
+    });
+  });
+});
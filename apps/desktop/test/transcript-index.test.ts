@@ -0,0 +1,165 @@
+import { Database } from "bun:sqlite";
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  advanceTranscriptIndex,
+  emptyTranscriptIndex,
+  summarizeTranscriptMessage,
+  TRANSCRIPT_INDEX_STRIDE,
+} from "../electron/services/transcriptIndex";
+import { SqliteTranscriptStore } from "../electron/services/transcriptStore";
+import type { TranscriptEvent } from "../src/app/types";
+import { getThreadPreviewsInputSchema } from "../src/lib/desktopSchemas";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function message(
+  seq: number,
+  direction: TranscriptEvent["direction"],
+  type: string,
+  text = `message ${seq}`,
+): TranscriptEvent {
+  return {
+    ts: `2026-01-01T00:00:${String(seq).padStart(2, "0")}.000Z`,
+    threadId: "thread-1",
+    direction,
+    payload: { type, text },
+  };
+}
+
+describe("transcript index", () => {
+  test("keeps the last user or assistant message and skips other events", () => {
+    const index = advanceTranscriptIndex(emptyTranscriptIndex(), [
+      { bytes: 10, event: message(1, "client", "user_message"), sealed: false },
+      { bytes: 10, event: message(2, "server", "assistant_message", "a\n\n reply"), sealed: false },
+      { bytes: 10, event: message(3, "server", "agent_text_delta"), sealed: false },
+      { bytes: 5, event: null, sealed: false },
+    ]);
+
+    expect(index).toMatchObject({
+      liveBytes: 35,
+      liveLines: 4,
+      lineOffsets: [0],
+      eventCount: 3,
+      lastEventAt: "2026-01-01T00:00:03.000Z",
+      lastMessage: { role: "assistant", ts: "2026-01-01T00:00:02.000Z", text: "a reply" },
+    });
+  });
+
+  test("records an offset every stride lines and clips long or sealed text", () => {
+    const lines = Array.from({ length: TRANSCRIPT_INDEX_STRIDE * 2 + 1 }, () => ({
+      bytes: 3,
+      event: null,
+      sealed: false,
+    }));
+    const offsets = advanceTranscriptIndex(emptyTranscriptIndex(), lines).lineOffsets;
+
+    expect(offsets).toEqual([0, TRANSCRIPT_INDEX_STRIDE * 3, TRANSCRIPT_INDEX_STRIDE * 6]);
+    const long = summarizeTranscriptMessage(
+      message(1, "client", "user_message", "x".repeat(500)),
+      false,
+    );
+    expect(long?.text).toHaveLength(200);
+    expect(summarizeTranscriptMessage(message(1, "client", "user_message"), true)?.text).toBeNull();
+    expect(
+      getThreadPreviewsInputSchema.safeParse({ threadIds: Array(501).fill("thread-1") }).success,
+    ).toBe(false);
+  });
+});
+
+describe("PersistenceService.getThreadPreviews", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-index-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(path.join(userDataDir, "transcripts"), { recursive: true });
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("updates the index on append and rebuilds it once the transcript changes", async () => {
+    const persistence = new PersistenceService();
+    await persistence.appendTranscriptBatch([message(1, "client", "user_message")]);
+    await persistence.appendTranscriptBatch([message(2, "server", "assistant_message")]);
+    const indexPath = path.join(userDataDir, "transcript-index", "thread-1.json");
+
+    expect(JSON.parse(await fs.readFile(indexPath, "utf8"))).toMatchObject({ eventCount: 2 });
+    expect(await persistence.getThreadPreviews(["thread-1", "thread-2"])).toEqual([
+      {
+        threadId: "thread-1",
+        eventCount: 2,
+        lastEventAt: "2026-01-01T00:00:02.000Z",
+        lastMessage: { role: "assistant", ts: "2026-01-01T00:00:02.000Z", text: "message 2" },
+      },
+      { threadId: "thread-2", eventCount: 0, lastEventAt: null, lastMessage: null },
+    ]);
+
+    // Written behind the service's back, so the cached index no longer matches the file.
+    await fs.appendFile(
+      path.join(userDataDir, "transcripts", "thread-1.jsonl"),
+      `${JSON.stringify(message(3, "client", "user_message"))}\n`,
+    );
+    const [rebuilt] = await persistence.getThreadPreviews(["thread-1"]);
+    expect(rebuilt).toMatchObject({ eventCount: 3, lastMessage: { text: "message 3" } });
+
+    await persistence.deleteTranscript("thread-1");
+    expect(await fs.stat(indexPath).catch(() => null)).toBeNull();
+  });
+
+  test("keeps the index for the SQLite store and rebuilds it when rows change", async () => {
+    const persistence = new PersistenceService({
+      getTranscriptStoreKind: async () => "sqlite",
+      openTranscriptDatabase: (file) => new Database(file),
+    });
+    await persistence.appendTranscriptBatch([message(1, "client", "user_message")]);
+    await persistence.appendTranscriptBatch([message(2, "server", "assistant_message")]);
+    const indexPath = path.join(userDataDir, "transcript-index", "thread-1.json");
+
+    expect(JSON.parse(await fs.readFile(indexPath, "utf8"))).toMatchObject({
+      liveBytes: 0,
+      sqliteLines: 2,
+      eventCount: 2,
+    });
+    expect(await persistence.getThreadPreviews(["thread-1"])).toEqual([
+      {
+        threadId: "thread-1",
+        eventCount: 2,
+        lastEventAt: "2026-01-01T00:00:02.000Z",
+        lastMessage: { role: "assistant", ts: "2026-01-01T00:00:02.000Z", text: "message 2" },
+      },
+    ]);
+
+    // Inserted through another connection, so the cached index no longer matches the rows.
+    const other = new SqliteTranscriptStore({
+      filePath: path.join(userDataDir, "transcripts.sqlite"),
+      openDatabase: (file) => new Database(file),
+    });
+    const event = message(3, "client", "user_message");
+    await other.append("thread-1", [{ ts: event.ts, text: JSON.stringify(event) }]);
+    other.close();
+    const [rebuilt] = await persistence.getThreadPreviews(["thread-1"]);
+    expect(rebuilt).toMatchObject({ eventCount: 3, lastMessage: { text: "message 3" } });
+    persistence.closeTranscriptDatabase();
+  });
+});
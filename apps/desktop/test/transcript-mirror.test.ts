@@ -0,0 +1,145 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  renderTranscriptMarkdown,
+  resolveTranscriptMirrorDirectory,
+  TranscriptMirrorService,
+} from "../electron/services/transcriptMirror";
+import type { PersistedState, TranscriptEvent, WorkspaceRecord } from "../src/app/types";
+import { normalizeWorkspaceTranscriptMirror } from "../src/app/workspaceTranscriptMirror";
+
+function workspace(overrides: Partial<WorkspaceRecord>): WorkspaceRecord {
+  return {
+    id: "ws-1",
+    name: "Project",
+    path: "/work/app",
+    createdAt: "2026-01-01T00:00:00.000Z",
+    lastOpenedAt: "2026-01-01T00:00:00.000Z",
+    defaultEnableMcp: true,
+    defaultBackupsEnabled: true,
+    yolo: false,
+    ...overrides,
+  } as WorkspaceRecord;
+}
+
+function userMessage(text: string): TranscriptEvent {
+  return {
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId: "thread-1",
+    direction: "client",
+    payload: { type: "user_message", sessionId: "thread-1", text },
+  };
+}
+
+describe("transcript mirror settings", () => {
+  test("drops unknown formats and relative folders that leave the workspace", () => {
+    expect(normalizeWorkspaceTranscriptMirror({ directory: " notes ", format: "jsonl" })).toEqual({
+      directory: "notes",
+      format: "jsonl",
+    });
+    expect(
+      normalizeWorkspaceTranscriptMirror({ directory: "notes", format: "html" }),
+    ).toBeUndefined();
+    expect(
+      normalizeWorkspaceTranscriptMirror({ directory: "../outside", format: "markdown" }),
+    ).toBeUndefined();
+    expect(
+      normalizeWorkspaceTranscriptMirror({ directory: "/mirror/../x", format: "markdown" }),
+    ).toEqual({ directory: "/mirror/../x", format: "markdown" });
+  });
+
+  test("resolves relative folders inside the workspace", () => {
+    expect(
+      resolveTranscriptMirrorDirectory(
+        workspace({ transcriptMirror: { directory: ".cowork/transcripts", format: "jsonl" } }),
+      ),
+    ).toBe(path.resolve("/work/app", ".cowork/transcripts"));
+    expect(
+      resolveTranscriptMirrorDirectory(
+        workspace({ transcriptMirror: { directory: "a/../../b", format: "jsonl" } }),
+      ),
+    ).toBeNull();
+    expect(resolveTranscriptMirrorDirectory(workspace({}))).toBeNull();
+  });
+
+  test("renders messages as markdown sections", () => {
+    const markdown = renderTranscriptMarkdown("Fix the build", [userMessage("Why is CI red?")]);
+
+    expect(markdown).toStartWith("# Fix the build\n\n## User\n\nWhy is CI red?");
+  });
+});
+
+describe("transcript mirror service", () => {
+  let root = "";
+  let state: PersistedState;
+  let transcript: TranscriptEvent[] = [];
+
+  beforeEach(async () => {
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-mirror-"));
+    transcript = [];
+    state = {
+      version: 2,
+      workspaces: [
+        workspace({
+          path: root,
+          transcriptMirror: { directory: ".cowork/transcripts", format: "jsonl" },
+        }),
+      ],
+      threads: [
+        {
+          id: "thread-1",
+          workspaceId: "ws-1",
+          title: "Mirrored",
+          createdAt: "2026-01-01T00:00:00.000Z",
+          lastMessageAt: "2026-01-01T00:00:00.000Z",
+          status: "active",
+          sessionId: null,
+          messageCount: 0,
+          lastEventSeq: 0,
+        },
+      ],
+    } as PersistedState;
+  });
+
+  afterEach(async () => {
+    if (root) {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+    root = "";
+  });
+
+  function createService() {
+    return new TranscriptMirrorService({
+      loadState: async () => state,
+      readTranscript: async () => transcript,
+      debounceMs: 60_000,
+    });
+  }
+
+  test("debounces appends and writes the whole transcript on flush", async () => {
+    const service = createService();
+    const mirrorPath = path.join(root, ".cowork", "transcripts", "thread-1.jsonl");
+
+    transcript = [userMessage("one")];
+    service.handleAppended(transcript);
+    transcript = [userMessage("one"), userMessage("two")];
+    service.handleAppended(transcript.slice(1));
+    await expect(fs.stat(mirrorPath)).rejects.toThrow();
+
+    await service.flush();
+
+    const lines = (await fs.readFile(mirrorPath, "utf8")).trim().split("\n");
+    expect(lines.map((line) => JSON.parse(line).payload.text)).toEqual(["one", "two"]);
+  });
+
+  test("skips threads whose workspace does not mirror", async () => {
+    state = { ...state, workspaces: [workspace({ path: root })] };
+    transcript = [userMessage("one")];
+
+    expect(await createService().mirrorTranscript("thread-1")).toBeNull();
+    await expect(fs.stat(path.join(root, ".cowork"))).rejects.toThrow();
+  });
+});
@@ -0,0 +1,154 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+
+import {
+  decodeTranscriptCursor,
+  pageTranscriptEvents,
+  readTranscriptLines,
+} from "../electron/services/transcriptPages";
+import type { TranscriptEvent } from "../src/app/types";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function event(seq: number): TranscriptEvent {
+  return {
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId: "thread-1",
+    direction: "server",
+    payload: { type: "agent_text_delta", seq },
+  };
+}
+
+function seqs(events: TranscriptEvent[]): number[] {
+  return events.map((entry) => (entry.payload as { seq: number }).seq);
+}
+
+async function* chunks(...parts: string[]): AsyncGenerator<Buffer> {
+  for (const part of parts) {
+    yield Buffer.from(part);
+  }
+}
+
+async function collect<T>(items: AsyncIterable<T>): Promise<T[]> {
+  const collected: T[] = [];
+  for await (const item of items) {
+    collected.push(item);
+  }
+  return collected;
+}
+
+describe("transcript line streaming", () => {
+  test("joins lines split across chunks and tracks where each ends", async () => {
+    const lines = await collect(readTranscriptLines(chunks("ab", "c\r\nd", "e\n\nf")));
+
+    expect(lines).toEqual([
+      { text: "abc\r", bytes: 4, end: 5 },
+      { text: "de", bytes: 2, end: 8 },
+      { text: "", bytes: 0, end: 9 },
+      { text: "f", bytes: 1, end: 10 },
+    ]);
+  });
+
+  test("starts at an offset or skips ahead in streams that cannot seek", async () => {
+    expect(await collect(readTranscriptLines(chunks("cd\n"), { offset: 3 }))).toEqual([
+      { text: "cd", bytes: 2, end: 6 },
+    ]);
+    expect(await collect(readTranscriptLines(chunks("a", "b\ncd\n"), { skip: 3 }))).toEqual([
+      { text: "cd", bytes: 2, end: 6 },
+    ]);
+  });
+
+  test("pages in-memory transcripts and always returns at least one event", () => {
+    const events = [event(1), event(2), event(3)];
+    const first = pageTranscriptEvents(events, null, { maxEvents: 2, maxBytes: 1 });
+
+    expect(seqs(first.events)).toEqual([1]);
+    expect(first.truncated).toBe(true);
+    const rest = pageTranscriptEvents(events, decodeTranscriptCursor(first.nextCursor!), {
+      maxEvents: 2,
+      maxBytes: 10_000,
+    });
+    expect(seqs(rest.events)).toEqual([2, 3]);
+    expect(rest).toMatchObject({ nextCursor: null, truncated: false });
+    expect(() => decodeTranscriptCursor("live:-1")).toThrow("Invalid transcript cursor");
+  });
+});
+
+describe("PersistenceService.readTranscriptPage", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-pages-"));
+    userDataDir = path.join(root, "Cowork");
+    const archiveDir = path.join(userDataDir, "transcripts-archive");
+    await fs.mkdir(path.join(userDataDir, "transcripts"), { recursive: true });
+    await fs.mkdir(archiveDir, { recursive: true });
+    const lines = (from: number, to: number) =>
+      Array.from({ length: to - from + 1 }, (_, index) => JSON.stringify(event(from + index)))
+        .map((line) => `${line}\n`)
+        .join("");
+    await fs.writeFile(
+      path.join(archiveDir, "thread-1.jsonl.zst"),
+      zlib.zstdCompressSync(Buffer.from(lines(1, 3))),
+    );
+    await fs.writeFile(
+      path.join(userDataDir, "transcripts", "thread-1.jsonl"),
+      `${lines(4, 4)}not json\n${lines(5, 6)}`,
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("walks archived then live history with a cursor", async () => {
+    const persistence = new PersistenceService();
+    const pages: number[][] = [];
+    let cursor: string | undefined;
+    do {
+      const page = await persistence.readTranscriptPage("thread-1", { cursor, maxEvents: 2 });
+      pages.push(seqs(page.events));
+      expect(page.truncated).toBe(page.nextCursor !== null);
+      cursor = page.nextCursor ?? undefined;
+    } while (cursor);
+
+    expect(pages).toEqual([
+      [1, 2],
+      [3, 4],
+      [5, 6],
+    ]);
+    expect(seqs(await persistence.readTranscript("thread-1"))).toEqual([1, 2, 3, 4, 5, 6]);
+  });
+
+  test("stops at the byte limit and reads a thread without transcripts as empty", async () => {
+    const persistence = new PersistenceService();
+    const page = await persistence.readTranscriptPage("thread-1", { maxBytes: 10 });
+
+    expect(seqs(page.events)).toEqual([1]);
+    expect(page.nextCursor).toStartWith("archived:");
+    expect(await persistence.readTranscriptPage("thread-2")).toEqual({
+      events: [],
+      nextCursor: null,
+      truncated: false,
+    });
+  });
+});
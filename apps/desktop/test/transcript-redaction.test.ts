@@ -0,0 +1,113 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { redactionPlaceholder } = await import("../electron/services/transcriptRedaction");
+
+const API_KEY = "sk-abcdefghijklmnopqrstuvwx";
+
+function eventLine(text: string): string {
+  return JSON.stringify({
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId: "thread-1",
+    direction: "server",
+    payload: { type: "user_message", text },
+  });
+}
+
+describe("transcript redaction", () => {
+  let transcriptPath = "";
+  let original = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-redaction-"));
+    userDataDir = path.join(root, "Cowork");
+    const transcriptsDir = path.join(userDataDir, "transcripts");
+    await fs.mkdir(transcriptsDir, { recursive: true });
+    transcriptPath = path.join(transcriptsDir, "thread-1.jsonl");
+    original = [
+      eventLine(`use ${API_KEY} for the deploy`),
+      eventLine("nothing to see here"),
+      eventLine("the staging id is ACME-4471"),
+    ]
+      .map((line) => `${line}\n`)
+      .join("");
+    await fs.writeFile(transcriptPath, original, "utf8");
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("a dry run counts matches without touching the transcript", async () => {
+    const persistence = new PersistenceService();
+
+    const result = await persistence.redactTranscript({
+      threadId: "thread-1",
+      patterns: ["ACME-\\d+"],
+      dryRun: true,
+    });
+
+    expect(result).toEqual({
+      threadId: "thread-1",
+      dryRun: true,
+      redactionCount: 2,
+      redactedEventCount: 2,
+      matches: [
+        { rule: "token", count: 1 },
+        { rule: "ACME-\\d+", count: 1 },
+      ],
+      sealedLineCount: 0,
+    });
+    expect(await fs.readFile(transcriptPath, "utf8")).toBe(original);
+  });
+
+  test("rewrites matched spans with hashed placeholders", async () => {
+    const persistence = new PersistenceService();
+
+    const result = await persistence.redactTranscript({
+      threadId: "thread-1",
+      patterns: ["ACME-\\d+"],
+      dryRun: false,
+    });
+
+    expect(result.redactionCount).toBe(2);
+    const texts = (await persistence.readTranscript("thread-1")).map(
+      (event) => (event.payload as { text: string }).text,
+    );
+    expect(texts).toEqual([
+      `use ${redactionPlaceholder(API_KEY)} for the deploy`,
+      "nothing to see here",
+      `the staging id is ${redactionPlaceholder("ACME-4471")}`,
+    ]);
+    expect(await fs.readFile(transcriptPath, "utf8")).not.toContain(API_KEY);
+
+    const again = await persistence.redactTranscript({
+      threadId: "thread-1",
+      patterns: ["ACME-\\d+"],
+      dryRun: false,
+    });
+    expect(again.redactionCount).toBe(0);
+    expect(again.matches).toEqual([]);
+  });
+});
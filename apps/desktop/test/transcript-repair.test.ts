@@ -0,0 +1,93 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function eventLine(seq: number): string {
+  return JSON.stringify({
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId: "thread-1",
+    direction: "server",
+    payload: { type: "agent_text_delta", seq },
+  });
+}
+
+describe("transcript repair", () => {
+  let transcriptPath = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-repair-"));
+    userDataDir = path.join(root, "Cowork");
+    const transcriptsDir = path.join(userDataDir, "transcripts");
+    await fs.mkdir(transcriptsDir, { recursive: true });
+    transcriptPath = path.join(transcriptsDir, "thread-1.jsonl");
+    await fs.writeFile(
+      transcriptPath,
+      [eventLine(1), '{"ts":"2026-01-01T00:00', eventLine(2), '{"hello":"world"}', eventLine(3)]
+        .map((line) => `${line}\n`)
+        .join(""),
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("reads past bad lines and reports where they were", async () => {
+    const persistence = new PersistenceService();
+
+    const { events, corruptedLines } = await persistence.readTranscriptWithReport("thread-1");
+
+    expect(events.map((event) => (event.payload as { seq: number }).seq)).toEqual([1, 2, 3]);
+    expect(corruptedLines).toEqual([
+      { source: "live", line: 2, reason: "unparseable" },
+      { source: "live", line: 4, reason: "invalid_event" },
+    ]);
+    expect(await persistence.readTranscript("thread-1")).toHaveLength(3);
+  });
+
+  test("quarantines bad lines and rewrites a clean transcript", async () => {
+    const persistence = new PersistenceService();
+
+    const result = await persistence.repairTranscript("thread-1");
+
+    const quarantinePath = path.join(path.dirname(transcriptPath), "thread-1.corrupt");
+    expect(result).toEqual({ threadId: "thread-1", quarantinedLineCount: 2, quarantinePath });
+    expect(await fs.readFile(quarantinePath, "utf8")).toBe(
+      '{"ts":"2026-01-01T00:00\n{"hello":"world"}\n',
+    );
+    expect(await fs.readFile(transcriptPath, "utf8")).toBe(
+      `${[eventLine(1), eventLine(2), eventLine(3)].join("\n")}\n`,
+    );
+    expect((await persistence.readTranscriptWithReport("thread-1")).corruptedLines).toEqual([]);
+    expect(await persistence.repairTranscript("thread-1")).toEqual({
+      threadId: "thread-1",
+      quarantinedLineCount: 0,
+      quarantinePath: null,
+    });
+
+    await persistence.deleteTranscript("thread-1");
+    await expect(fs.stat(quarantinePath)).rejects.toThrow();
+  });
+});
@@ -0,0 +1,142 @@
+import { describe, expect, test } from "bun:test";
+
+import type { TranscriptFileInfo } from "../electron/services/persistence";
+import {
+  planTranscriptRetention,
+  TranscriptRetentionService,
+} from "../electron/services/transcriptRetention";
+import type { PersistedState } from "../src/app/types";
+import type { AppSettings, TranscriptRetentionSummary } from "../src/lib/desktopApi";
+import { transcriptRetentionPreviewSchema } from "../src/lib/desktopSchemas";
+
+const NOW = Date.parse("2026-06-01T00:00:00.000Z");
+const DAY_MS = 24 * 60 * 60_000;
+const MB = 1024 * 1024;
+
+function file(
+  transcriptId: string,
+  daysOld: number,
+  sizeMb: number,
+  archived = false,
+): TranscriptFileInfo {
+  return {
+    transcriptId,
+    archived,
+    sizeBytes: sizeMb * MB,
+    modifiedAtMs: NOW - daysOld * DAY_MS,
+  };
+}
+
+function settings(overrides: Partial<AppSettings> = {}): AppSettings {
+  return {
+    defaultProvider: null,
+    startupBehavior: "restoreLastThread",
+    transcriptRetentionDays: 0,
+    transcriptRetentionMaxTotalMb: 0,
+    minFreeDiskMb: 256,
+    trashRetentionDays: 30,
+    transcriptValidation: "annotate",
+    notificationsEnabled: true,
+    httpProxy: null,
+    httpsProxy: null,
+    noProxy: null,
+    caBundlePath: null,
+    themeSource: "system",
+    isolatedSessions: false,
+    maxIsolatedSidecars: 4,
+    locale: null,
+    idleServerSuspend: "onBattery",
+    idleServerTimeoutMinutes: 30,
+    yoloGrantMinutes: 60,
+    voiceTranscriptionBaseUrl: null,
+    voiceTranscriptionModel: null,
+    transcriptStore: "jsonl",
+    ...overrides,
+  };
+}
+
+describe("transcript retention planning", () => {
+  test("prunes by age first, then the oldest remaining until under the disk cap", () => {
+    const preview = planTranscriptRetention(
+      [
+        file("ancient", 90, 1),
+        file("ancient", 40, 2, true),
+        file("old", 20, 3),
+        file("older", 25, 3),
+        file("today", 0, 10),
+      ],
+      { maxAgeDays: 30, maxTotalMb: 13 },
+      NOW,
+      new Map([["ancient", "thread-1"]]),
+    );
+
+    expect(transcriptRetentionPreviewSchema.safeParse(preview).success).toBe(true);
+    expect(preview.totalBytes).toBe(19 * MB);
+    expect(preview.candidates.map(({ transcriptId, reason }) => [transcriptId, reason])).toEqual([
+      ["ancient", "age"],
+      ["older", "diskUsage"],
+    ]);
+    expect(preview.candidates[0]).toMatchObject({ threadId: "thread-1", sizeBytes: 3 * MB });
+    expect(preview.reclaimableBytes).toBe(6 * MB);
+  });
+
+  test("never prunes recent transcripts or anything when both limits are off", () => {
+    const files = [file("busy", 0.5, 50), file("idle", 3, 1)];
+
+    const capped = planTranscriptRetention(files, { maxAgeDays: 0, maxTotalMb: 1 }, NOW);
+    const disabled = planTranscriptRetention(files, { maxAgeDays: 0, maxTotalMb: 0 }, NOW);
+
+    expect(capped.candidates.map((candidate) => candidate.transcriptId)).toEqual(["idle"]);
+    expect(disabled).toMatchObject({ enabled: false, candidates: [] });
+  });
+});
+
+describe("transcript retention service", () => {
+  function createService(files: TranscriptFileInfo[], current: AppSettings) {
+    const deleted: string[] = [];
+    const emitted: TranscriptRetentionSummary[] = [];
+    const service = new TranscriptRetentionService({
+      listTranscriptFiles: async () =>
+        files.filter((entry) => !deleted.includes(entry.transcriptId)),
+      deleteTranscript: async (transcriptId) => {
+        if (transcriptId === "locked") {
+          throw new Error("EBUSY");
+        }
+        deleted.push(transcriptId);
+      },
+      loadState: async () => ({ version: 2, workspaces: [], threads: [] }) as PersistedState,
+      getSettings: async () => current,
+      emit: (summary) => emitted.push(summary),
+      now: () => NOW,
+    });
+    return { service, deleted, emitted };
+  }
+
+  test("previews proposed limits without deleting anything", async () => {
+    const { service, deleted } = createService([file("a", 60, 1)], settings());
+
+    const preview = await service.preview({ maxAgeDays: 30 });
+
+    expect(preview.candidates.map((candidate) => candidate.transcriptId)).toEqual(["a"]);
+    expect(deleted).toEqual([]);
+    expect(await service.runOnce()).toBeNull();
+  });
+
+  test("deletes candidates, reports failures, and emits a summary", async () => {
+    const { service, deleted, emitted } = createService(
+      [file("a", 60, 1), file("locked", 45, 2), file("b", 1.5, 1)],
+      settings({ transcriptRetentionDays: 30 }),
+    );
+
+    const summary = await service.runOnce();
+
+    expect(deleted).toEqual(["a"]);
+    expect(summary).toEqual({
+      ranAt: new Date(NOW).toISOString(),
+      deletedCount: 1,
+      reclaimedBytes: MB,
+      failures: [{ transcriptId: "locked", message: "EBUSY" }],
+    });
+    expect(emitted).toEqual([summary as TranscriptRetentionSummary]);
+  });
+});
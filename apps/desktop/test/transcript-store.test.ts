@@ -0,0 +1,192 @@
+import { Database } from "bun:sqlite";
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { SqliteTranscriptStore } from "../electron/services/transcriptStore";
+import type { TranscriptEvent } from "../src/app/types";
+import type { TranscriptStoreKind } from "../src/lib/desktopApi";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function event(seq: number): TranscriptEvent {
+  return {
+    ts: `2026-01-01T00:00:0${seq}.000Z`,
+    threadId: "thread-1",
+    direction: "client",
+    payload: { type: "user_message", text: String(seq) },
+  };
+}
+
+function seqs(events: TranscriptEvent[]): number[] {
+  return events.map((entry) => Number((entry.payload as { text: string }).text));
+}
+
+async function collect(store: SqliteTranscriptStore, offset: number) {
+  const source = await store.openLines("thread-1", offset);
+  const lines: Array<{ text: string; end: number }> = [];
+  for await (const { text, end } of source?.lines ?? []) {
+    lines.push({ text, end });
+  }
+  return lines;
+}
+
+async function pathExists(filePath: string): Promise<boolean> {
+  return await fs
+    .access(filePath)
+    .then(() => true)
+    .catch(() => false);
+}
+
+beforeEach(async () => {
+  setElectronMockOverrides(electronMockOverrides);
+  const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-store-"));
+  userDataDir = path.join(root, "Cowork");
+  await fs.mkdir(path.join(userDataDir, "transcripts"), { recursive: true });
+});
+
+afterEach(async () => {
+  if (userDataDir) {
+    await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+  }
+  userDataDir = "";
+});
+
+describe("SqliteTranscriptStore", () => {
+  test("appends in sequence and streams from an offset", async () => {
+    const filePath = path.join(userDataDir, "transcripts.sqlite");
+    const store = new SqliteTranscriptStore({
+      filePath,
+      openDatabase: (file) => new Database(file),
+    });
+
+    expect(await store.openLines("thread-1", 0)).toBeNull();
+    expect(await pathExists(filePath)).toBe(false);
+
+    await store.append("thread-1", [
+      { ts: "a", text: "one" },
+      { ts: "b", text: "two" },
+    ]);
+    await store.append("thread-1", [{ ts: "c", text: "three" }]);
+    expect(await collect(store, 1)).toEqual([
+      { text: "two", end: 2 },
+      { text: "three", end: 3 },
+    ]);
+
+    await store.replace("thread-1", [{ ts: "d", text: "four" }]);
+    expect(await collect(store, 0)).toEqual([{ text: "four", end: 1 }]);
+    expect(await store.listTranscriptIds()).toEqual(["thread-1"]);
+    await store.delete("thread-1");
+    expect(await store.listTranscriptIds()).toEqual([]);
+    store.close();
+  });
+});
+
+describe("PersistenceService transcript stores", () => {
+  let kind: TranscriptStoreKind = "jsonl";
+
+  function createPersistence() {
+    return new PersistenceService({
+      getTranscriptStoreKind: async () => kind,
+      setTranscriptStoreKind: async (next) => {
+        kind = next;
+      },
+      openTranscriptDatabase: (file) => new Database(file),
+    });
+  }
+
+  beforeEach(() => {
+    kind = "jsonl";
+  });
+
+  test("reads JSONL history before events appended to SQLite", async () => {
+    const persistence = createPersistence();
+    const livePath = path.join(userDataDir, "transcripts", "thread-1.jsonl");
+    await persistence.appendTranscriptBatch([event(1), event(2)]);
+    await persistence.flushTranscriptWrites();
+    const liveBytes = (await fs.stat(livePath)).size;
+
+    kind = "sqlite";
+    await persistence.appendTranscriptBatch([event(3)]);
+    await persistence.flushTranscriptWrites();
+
+    expect((await fs.stat(livePath)).size).toBe(liveBytes);
+    expect(seqs(await persistence.readTranscript("thread-1"))).toEqual([1, 2, 3]);
+    const first = await persistence.readTranscriptPage("thread-1", { maxEvents: 2 });
+    expect(seqs(first.events)).toEqual([1, 2]);
+    const rest = await persistence.readTranscriptPage("thread-1", {
+      cursor: first.nextCursor ?? undefined,
+    });
+    expect(seqs(rest.events)).toEqual([3]);
+    expect(rest.nextCursor).toBeNull();
+    persistence.closeTranscriptDatabase();
+  });
+
+  test("migrates transcripts between stores and switches the setting", async () => {
+    const persistence = createPersistence();
+    const livePath = path.join(userDataDir, "transcripts", "thread-1.jsonl");
+    await persistence.appendTranscriptBatch([event(1), event(2)]);
+    await persistence.flushTranscriptWrites();
+
+    expect(await persistence.migrateTranscriptStore("sqlite")).toEqual({
+      target: "sqlite",
+      migratedTranscripts: 1,
+      migratedLines: 2,
+    });
+    expect(kind).toBe("sqlite");
+    expect(await pathExists(livePath)).toBe(false);
+    await persistence.appendTranscriptBatch([event(3)]);
+    expect(seqs(await persistence.readTranscript("thread-1"))).toEqual([1, 2, 3]);
+
+    expect(await persistence.migrateTranscriptStore("jsonl")).toMatchObject({
+      migratedTranscripts: 1,
+      migratedLines: 3,
+    });
+    expect(kind).toBe("jsonl");
+    expect((await fs.readFile(livePath, "utf8")).trim().split("\n")).toHaveLength(3);
+    expect(seqs(await persistence.readTranscript("thread-1"))).toEqual([1, 2, 3]);
+    persistence.closeTranscriptDatabase();
+  });
+
+  test("rebuilding state scans transcripts that only live in SQLite", async () => {
+    kind = "sqlite";
+    const persistence = createPersistence();
+    await persistence.appendTranscriptBatch([event(1)]);
+    await persistence.flushTranscriptWrites();
+
+    const { report } = await persistence.rebuildStateFromTranscripts({ dryRun: true });
+
+    // The event names no working directory, so the thread is found but cannot be placed.
+    expect(report.scannedTranscripts).toBe(1);
+    expect(report.skipped).toEqual([
+      { threadId: "thread-1", reason: "The transcript never reported a working directory." },
+    ]);
+    persistence.closeTranscriptDatabase();
+  });
+
+  test("deleting a transcript removes its SQLite rows", async () => {
+    kind = "sqlite";
+    const persistence = createPersistence();
+    await persistence.appendTranscriptBatch([event(1)]);
+    await persistence.flushTranscriptWrites();
+
+    await persistence.deleteTranscript("thread-1");
+
+    expect(await persistence.readTranscript("thread-1")).toEqual([]);
+    persistence.closeTranscriptDatabase();
+  });
+});
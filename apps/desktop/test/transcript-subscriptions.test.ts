@@ -0,0 +1,128 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import { EventEmitter } from "node:events";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import type { TranscriptAppendedEvent } from "../src/lib/desktopApi";
+import { DESKTOP_EVENT_CHANNELS } from "../src/lib/desktopApi";
+import { transcriptAppendedEventSchema } from "../src/lib/desktopSchemas";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: () => userDataDir,
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { TranscriptSubscriptions } = await import("../electron/services/transcriptSubscriptions");
+
+class FakeWebContents extends EventEmitter {
+  readonly sent: Array<{ channel: string; payload: TranscriptAppendedEvent }> = [];
+  destroyed = false;
+
+  constructor(readonly id: number) {
+    super();
+  }
+
+  isDestroyed(): boolean {
+    return this.destroyed;
+  }
+
+  send(channel: string, payload: TranscriptAppendedEvent): void {
+    this.sent.push({ channel, payload });
+  }
+
+  destroy(): void {
+    this.destroyed = true;
+    this.emit("destroyed");
+  }
+}
+
+function transcriptEvent(threadId: string, seq: number) {
+  return {
+    ts: `2026-01-01T00:00:0${seq}.000Z`,
+    threadId,
+    direction: "server" as const,
+    payload: { type: "agent_text_delta", seq },
+  };
+}
+
+describe("desktop transcript subscriptions", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    userDataDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-tail-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(userDataDir, { recursive: true, force: true });
+    userDataDir = "";
+  });
+
+  test("pushes appended events to windows following the thread", async () => {
+    const persistence = new PersistenceService();
+    const subscriptions = new TranscriptSubscriptions();
+    persistence.onTranscriptAppended((events) => subscriptions.publish(events));
+    const follower = new FakeWebContents(1);
+    const bystander = new FakeWebContents(2);
+    subscriptions.subscribe(follower, "thread-1");
+    subscriptions.subscribe(bystander, "thread-2");
+
+    await persistence.appendTranscriptBatch([
+      transcriptEvent("thread-1", 1),
+      transcriptEvent("thread-3", 2),
+      transcriptEvent("thread-1", 3),
+    ]);
+
+    expect(follower.sent).toHaveLength(1);
+    expect(follower.sent[0]?.channel).toBe(DESKTOP_EVENT_CHANNELS.transcriptAppended);
+    expect(follower.sent[0]?.payload).toEqual({
+      threadId: "thread-1",
+      events: [transcriptEvent("thread-1", 1), transcriptEvent("thread-1", 3)],
+    });
+    expect(transcriptAppendedEventSchema.safeParse(follower.sent[0]?.payload).success).toBe(true);
+    expect(bystander.sent).toEqual([]);
+    expect(await persistence.readTranscript("thread-1")).toHaveLength(2);
+  });
+
+  test("stops pushing after unsubscribe or once the window is destroyed", () => {
+    const subscriptions = new TranscriptSubscriptions();
+    const first = new FakeWebContents(1);
+    const second = new FakeWebContents(2);
+    subscriptions.subscribe(first, "thread-1");
+    subscriptions.subscribe(first, "thread-1");
+    subscriptions.subscribe(second, "thread-1");
+
+    expect(subscriptions.unsubscribe(first, "thread-1")).toBe(true);
+    expect(subscriptions.unsubscribe(first, "thread-1")).toBe(false);
+    second.destroy();
+
+    subscriptions.publish([transcriptEvent("thread-1", 1)]);
+
+    expect(first.sent).toEqual([]);
+    expect(second.sent).toEqual([]);
+    expect(subscriptions.isSubscribed(2, "thread-1")).toBe(false);
+    expect(first.listenerCount("destroyed")).toBe(1);
+  });
+
+  test("removing the persistence listener detaches the tail", async () => {
+    const persistence = new PersistenceService();
+    const received: string[] = [];
+    const dispose = persistence.onTranscriptAppended((events) => {
+      received.push(...events.map((event) => event.threadId));
+    });
+
+    await persistence.appendTranscriptEvent(transcriptEvent("thread-1", 1));
+    dispose();
+    await persistence.appendTranscriptEvent(transcriptEvent("thread-1", 2));
+
+    expect(received).toEqual(["thread-1"]);
+  });
+});
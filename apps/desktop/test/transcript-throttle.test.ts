@@ -0,0 +1,109 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  TRUNCATED_TRANSCRIPT_PAYLOAD_TYPE,
+  TranscriptThrottle,
+} from "../electron/services/transcriptThrottle";
+import type { TranscriptBatchInput, TranscriptThrottledEvent } from "../src/lib/desktopApi";
+import { transcriptThrottledEventSchema } from "../src/lib/desktopSchemas";
+
+function delta(threadId: string, text = "x"): TranscriptBatchInput {
+  return {
+    ts: "2026-04-01T10:00:00.000Z",
+    threadId,
+    direction: "server",
+    payload: { type: "assistant_message", sessionId: "session-1", text },
+  };
+}
+
+function createHarness(options: ConstructorParameters<typeof TranscriptThrottle>[0] = {}) {
+  let now = Date.parse("2026-04-01T10:00:00.000Z");
+  const warnings: TranscriptThrottledEvent[] = [];
+  const timers: Array<() => void> = [];
+  const throttle = new TranscriptThrottle({
+    burstEvents: 5,
+    eventsPerSecond: 2,
+    reportIntervalMs: 5_000,
+    ...options,
+    onThrottled: (event) => warnings.push(event),
+    now: () => now,
+    setTimeout: (callback) => {
+      timers.push(callback);
+      return 0 as unknown as ReturnType<typeof setTimeout>;
+    },
+    clearTimeout: () => {},
+  });
+  return {
+    throttle,
+    warnings,
+    advance: (ms: number) => {
+      now += ms;
+    },
+    fireTimers: () => {
+      for (const callback of timers.splice(0)) callback();
+    },
+  };
+}
+
+describe("transcript throttle", () => {
+  test("drops appends past a thread's event budget and refills over time", () => {
+    const harness = createHarness();
+    const storm = Array.from({ length: 8 }, () => delta("thread-a"));
+    expect(harness.throttle.admit(storm)).toHaveLength(5);
+    expect(harness.throttle.admit([delta("thread-b")])).toHaveLength(1);
+
+    harness.advance(1_000);
+    expect(harness.throttle.admit(storm)).toHaveLength(2);
+
+    expect(harness.warnings).toHaveLength(1);
+    expect(harness.warnings[0]).toMatchObject({
+      threadId: "thread-a",
+      droppedEvents: 1,
+      truncatedEvents: 0,
+    });
+    harness.fireTimers();
+    expect(harness.warnings).toHaveLength(2);
+    expect(harness.warnings[1]).toMatchObject({ threadId: "thread-a", droppedEvents: 8 });
+    expect(transcriptThrottledEventSchema.safeParse(harness.warnings[1]).success).toBe(true);
+
+    harness.fireTimers();
+    expect(harness.warnings).toHaveLength(2);
+  });
+
+  test("replaces oversized payloads with a truncation marker", () => {
+    const harness = createHarness({ maxPayloadBytes: 1_024 });
+    const [small, large] = harness.throttle.admit([
+      delta("thread-a", "hello"),
+      delta("thread-a", "y".repeat(10_000)),
+    ]);
+    expect(small?.payload).toEqual({
+      type: "assistant_message",
+      sessionId: "session-1",
+      text: "hello",
+    });
+    expect(large?.payload).toMatchObject({
+      type: TRUNCATED_TRANSCRIPT_PAYLOAD_TYPE,
+      originalType: "assistant_message",
+    });
+    const marker = large?.payload as { originalBytes: number; preview: string };
+    expect(marker.originalBytes).toBeGreaterThan(10_000);
+    expect(marker.preview.length).toBeLessThanOrEqual(4_096);
+    expect(harness.warnings).toEqual([
+      expect.objectContaining({ threadId: "thread-a", droppedEvents: 0, truncatedEvents: 1 }),
+    ]);
+  });
+
+  test("drops appends past a thread's byte budget", () => {
+    const harness = createHarness({
+      burstEvents: 100,
+      maxPayloadBytes: 2_048,
+      burstBytes: 4_096,
+      bytesPerSecond: 1_024,
+    });
+    const chunk = () => delta("thread-a", "z".repeat(1_500));
+    expect(harness.throttle.admit([chunk(), chunk(), chunk()])).toHaveLength(2);
+    harness.advance(2_000);
+    expect(harness.throttle.admit([chunk()])).toHaveLength(1);
+    expect(harness.warnings[0]?.droppedBytes).toBeGreaterThan(1_500);
+  });
+});
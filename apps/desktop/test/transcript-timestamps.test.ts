@@ -0,0 +1,163 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+
+import {
+  coerceLegacyTranscriptTimestamp,
+  InvalidTranscriptTimestampError,
+  parseRfc3339Timestamp,
+} from "../electron/services/transcriptTimestamps";
+import type { TranscriptBatchInput } from "../src/lib/desktopApi";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function event(ts: string, text: string): TranscriptBatchInput {
+  return { ts, threadId: "thread-1", direction: "client", payload: { type: "user_message", text } };
+}
+
+describe("transcript timestamps", () => {
+  test("accepts strict RFC 3339 and normalizes it to UTC", () => {
+    expect(parseRfc3339Timestamp("2026-10-15T09:30:00+02:00")).toBe("2026-10-15T07:30:00.000Z");
+    expect(parseRfc3339Timestamp("2026-10-15t07:30:00.5z")).toBe("2026-10-15T07:30:00.500Z");
+    expect(parseRfc3339Timestamp("2026-10-15T07:30:00.000Z")).toBe("2026-10-15T07:30:00.000Z");
+
+    for (const invalid of [
+      "2026-10-15",
+      "2026-10-15 07:30:00Z",
+      "2026-10-15T07:30:00",
+      "2026-02-30T00:00:00Z",
+      "2026-10-15T24:00:00Z",
+      "1760513400000",
+    ]) {
+      expect(parseRfc3339Timestamp(invalid)).toBeNull();
+    }
+  });
+
+  test("reads legacy timestamps leniently and gives up on nonsense", () => {
+    expect(coerceLegacyTranscriptTimestamp("Thu, 15 Oct 2026 07:30:00 GMT")).toBe(
+      "2026-10-15T07:30:00.000Z",
+    );
+    expect(coerceLegacyTranscriptTimestamp("1760513400000")).toBe("2025-10-15T07:30:00.000Z");
+    expect(coerceLegacyTranscriptTimestamp("yesterday")).toBeNull();
+  });
+});
+
+describe("PersistenceService transcript timestamps", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-transcript-timestamps-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(path.join(userDataDir, "transcripts"), { recursive: true });
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("normalizes appended timestamps and rejects ones that are not RFC 3339", async () => {
+    const persistence = new PersistenceService();
+    await persistence.appendTranscriptBatch([event("2026-10-15T09:30:00+02:00", "one")]);
+
+    await expect(
+      persistence.appendTranscriptBatch([event("10/15/2026, 9:30:00 AM", "two")]),
+    ).rejects.toThrow(InvalidTranscriptTimestampError);
+    const events = await persistence.readTranscript("thread-1");
+    expect(events.map((entry) => entry.ts)).toEqual(["2026-10-15T07:30:00.000Z"]);
+  });
+
+  test("rewrites legacy timestamps on request", async () => {
+    await fs.writeFile(
+      path.join(userDataDir, "transcripts", "thread-1.jsonl"),
+      [
+        event("2026-10-15T07:30:00.000Z", "current"),
+        event("1760513400000", "epoch"),
+        event("not a time", "broken"),
+      ]
+        .map((entry) => `${JSON.stringify(entry)}\n`)
+        .join(""),
+    );
+    const persistence = new PersistenceService();
+
+    expect(await persistence.normalizeTranscriptTimestamps("thread-1")).toEqual({
+      threadId: "thread-1",
+      normalizedCount: 1,
+      unparseableCount: 1,
+    });
+    const events = await persistence.readTranscript("thread-1");
+    expect(events.map((entry) => entry.ts)).toEqual([
+      "2026-10-15T07:30:00.000Z",
+      "2025-10-15T07:30:00.000Z",
+      "not a time",
+    ]);
+    expect(await persistence.normalizeTranscriptTimestamps("thread-1")).toMatchObject({
+      normalizedCount: 0,
+    });
+  });
+
+  test("compaction fixes legacy timestamps in the archive and the live file", async () => {
+    const root = path.dirname(userDataDir);
+    await fs.mkdir(path.join(root, "ws-1"));
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-1",
+            name: "ws-1",
+            path: path.join(root, "ws-1"),
+            createdAt: "2026-10-15T00:00:00.000Z",
+            lastOpenedAt: "2026-10-15T00:00:00.000Z",
+          },
+        ],
+        threads: [
+          {
+            id: "thread-1",
+            workspaceId: "ws-1",
+            title: "thread-1",
+            createdAt: "2026-10-15T00:00:00.000Z",
+            lastMessageAt: "2026-10-15T00:00:00.000Z",
+          },
+        ],
+      }),
+      "utf8",
+    );
+    const archiveDir = path.join(userDataDir, "transcripts-archive");
+    await fs.mkdir(archiveDir, { recursive: true });
+    await fs.writeFile(
+      path.join(archiveDir, "thread-1.jsonl.zst"),
+      zlib.zstdCompressSync(Buffer.from(`${JSON.stringify(event("1760513400000", "old"))}\n`)),
+    );
+    await fs.writeFile(
+      path.join(userDataDir, "transcripts", "thread-1.jsonl"),
+      `${JSON.stringify(event("Thu, 15 Oct 2026 07:30:00 GMT", "new"))}\n`,
+    );
+    const persistence = new PersistenceService();
+
+    await persistence.compactTranscripts(["thread-1"]);
+
+    const events = await persistence.readTranscript("thread-1");
+    expect(events.map((entry) => entry.ts)).toEqual([
+      "2025-10-15T07:30:00.000Z",
+      "2026-10-15T07:30:00.000Z",
+    ]);
+  });
+});
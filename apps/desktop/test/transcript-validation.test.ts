@@ -0,0 +1,62 @@
+import { describe, expect, test } from "bun:test";
+
+import { normalizeAppSettings } from "../electron/services/appSettings";
+import {
+  applyTranscriptValidation,
+  InvalidTranscriptPayloadError,
+  validateTranscriptPayload,
+} from "../electron/services/transcriptValidation";
+import type { TranscriptBatchInput } from "../src/lib/desktopApi";
+
+function serverEvent(payload: unknown): TranscriptBatchInput {
+  return { ts: "2026-10-15T00:00:00.000Z", threadId: "thread-1", direction: "server", payload };
+}
+
+describe("transcript payload validation", () => {
+  test("checks known payload types and lets unknown types and extra keys through", () => {
+    expect(
+      validateTranscriptPayload("server", {
+        type: "assistant_message",
+        sessionId: "s-1",
+        text: "hi",
+        addedLater: true,
+      }),
+    ).toEqual([]);
+    expect(validateTranscriptPayload("server", { type: "new_event_type", anything: 1 })).toEqual(
+      [],
+    );
+    expect(validateTranscriptPayload("server", { type: "assistant_message", text: 42 })).toEqual([
+      expect.stringContaining("sessionId"),
+      expect.stringContaining("text"),
+    ]);
+    expect(validateTranscriptPayload("client", { text: "hi" })).toEqual([
+      "type: expected a non-empty string",
+    ]);
+    expect(validateTranscriptPayload("client", { type: "user_message", text: "hi" })).toEqual([]);
+  });
+
+  test("annotates malformed events by default and rejects them in strict mode", () => {
+    const valid = serverEvent({ type: "session_busy", sessionId: "s-1", busy: false });
+    const malformed = serverEvent({ type: "session_busy", sessionId: "s-1", busy: "no" });
+
+    expect(applyTranscriptValidation([valid, malformed], "annotate")).toEqual([
+      valid,
+      { ...malformed, validationIssues: [expect.stringContaining("busy")] },
+    ]);
+    expect(applyTranscriptValidation([malformed], "off")).toEqual([malformed]);
+    expect(() => applyTranscriptValidation([valid, malformed], "strict")).toThrow(
+      InvalidTranscriptPayloadError,
+    );
+    expect(() => applyTranscriptValidation([valid], "strict")).not.toThrow();
+  });
+
+  test("defaults the setting to annotate", () => {
+    expect(normalizeAppSettings({}).transcriptValidation).toBe("annotate");
+    expect(normalizeAppSettings({ transcriptValidation: "strict" }).transcriptValidation).toBe(
+      "strict",
+    );
+    expect(normalizeAppSettings({ transcriptValidation: "loud" }).transcriptValidation).toBe(
+      "annotate",
+    );
+  });
+});
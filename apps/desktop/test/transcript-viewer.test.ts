@@ -0,0 +1,107 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import { randomBytes } from "node:crypto";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { encryptTranscriptLine } from "../electron/services/transcriptEncryption";
+import {
+  isViewerThreadId,
+  parseTranscriptFile,
+  renderTranscriptExport,
+  TranscriptViewerRegistry,
+} from "../electron/services/transcriptViewer";
+
+function line(direction: "client" | "server", payload: unknown): string {
+  return JSON.stringify({ ts: "2026-10-15T00:00:00.000Z", threadId: "orig", direction, payload });
+}
+
+describe("transcript viewer", () => {
+  let dir = "";
+
+  beforeEach(async () => {
+    dir = await fs.mkdtemp(path.join(os.tmpdir(), "transcript-viewer-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(dir, { recursive: true, force: true });
+  });
+
+  test("rebinds events to the viewer thread and annotates malformed payloads", () => {
+    const events = parseTranscriptFile(
+      [
+        line("client", { type: "user_message", text: "hi" }),
+        "",
+        line("server", { type: "session_busy", sessionId: "s-1", busy: "no" }),
+      ].join("\n"),
+      "viewer-1",
+    );
+
+    expect(events.map((event) => event.threadId)).toEqual(["viewer-1", "viewer-1"]);
+    expect(events[0]?.validationIssues).toBeUndefined();
+    expect(events[1]?.validationIssues?.length).toBeGreaterThan(0);
+  });
+
+  test("rejects files that are not plaintext transcripts", () => {
+    expect(() => parseTranscriptFile("\n\n", "viewer-1")).toThrow("no transcript events");
+    expect(() => parseTranscriptFile("{not json", "viewer-1")).toThrow("Line 1 is not valid JSON");
+    expect(() =>
+      parseTranscriptFile(
+        [line("client", { type: "user_message" }), JSON.stringify({ ts: "x" })].join("\n"),
+        "viewer-1",
+      ),
+    ).toThrow("Line 2 is not a transcript event");
+    const encrypted = encryptTranscriptLine(randomBytes(32), line("client", { type: "ping" }));
+    expect(() => parseTranscriptFile(encrypted, "viewer-1")).toThrow("Line 1 is encrypted");
+  });
+
+  test("exports events in the format it opens", () => {
+    const events = parseTranscriptFile(
+      line("server", { type: "session_busy", sessionId: "s-1", busy: "no" }),
+      "thread-1",
+    );
+    const exported = renderTranscriptExport(events);
+
+    expect(exported).not.toContain("validationIssues");
+    expect(parseTranscriptFile(exported, "viewer-1")).toEqual(
+      events.map((event) => ({ ...event, threadId: "viewer-1" })),
+    );
+  });
+
+  test("opens a file under a synthetic read-only thread id", async () => {
+    const filePath = path.join(dir, "shared.jsonl");
+    await fs.writeFile(filePath, `${line("client", { type: "user_message", text: "hi" })}\n`);
+    const registry = new TranscriptViewerRegistry({ createId: () => "abc" });
+
+    const opened = await registry.open(filePath);
+
+    expect(opened).toMatchObject({
+      threadId: "viewer-abc",
+      sourcePath: filePath,
+      readOnly: true,
+    });
+    expect(isViewerThreadId(opened.threadId)).toBe(true);
+    expect(registry.readTranscript("viewer-abc")).toEqual(opened.events);
+    expect(registry.close("viewer-abc")).toBe(true);
+    expect(registry.readTranscript("viewer-abc")).toBeNull();
+  });
+
+  test("refuses relative paths and oversized files, and evicts the oldest transcript", async () => {
+    const filePath = path.join(dir, "shared.jsonl");
+    await fs.writeFile(filePath, `${line("client", { type: "user_message", text: "hi" })}\n`);
+    let nextId = 0;
+    const registry = new TranscriptViewerRegistry({
+      createId: () => String(++nextId),
+      maxOpenTranscripts: 1,
+    });
+
+    await expect(registry.open("shared.jsonl")).rejects.toThrow("must be absolute");
+    const small = new TranscriptViewerRegistry({ maxFileBytes: 4 });
+    await expect(small.open(filePath)).rejects.toThrow("cannot be opened");
+
+    await registry.open(filePath);
+    await registry.open(filePath);
+    expect(registry.readTranscript("viewer-1")).toBeNull();
+    expect(registry.readTranscript("viewer-2")).not.toBeNull();
+  });
+});
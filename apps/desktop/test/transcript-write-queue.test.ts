@@ -0,0 +1,102 @@
+import { describe, expect, test } from "bun:test";
+
+import { TranscriptWriteQueue } from "../electron/services/transcriptWriteQueue";
+
+function deferred() {
+  let resolve = () => {};
+  const promise = new Promise<void>((done) => {
+    resolve = done;
+  });
+  return { promise, resolve };
+}
+
+describe("transcript write queue", () => {
+  test("coalesces chunks queued behind an in-flight write and keeps their order", async () => {
+    const writes: Array<[string, number[]]> = [];
+    const firstWrite = deferred();
+    const queue = new TranscriptWriteQueue<number>({
+      write: async (threadId, items) => {
+        writes.push([threadId, items]);
+        if (writes.length === 1) {
+          await firstWrite.promise;
+        }
+      },
+      sync: async () => {},
+    });
+
+    const appends = [
+      queue.enqueue("thread-1", [1]),
+      queue.enqueue("thread-1", [2, 3]),
+      queue.enqueue("thread-1", [4]),
+      queue.enqueue("thread-2", [1]),
+    ];
+    firstWrite.resolve();
+    await Promise.all(appends);
+
+    expect(writes).toEqual([
+      ["thread-1", [1]],
+      ["thread-2", [1]],
+      ["thread-1", [2, 3, 4]],
+    ]);
+  });
+
+  test("flush waits for queued writes and syncs each written thread once", async () => {
+    const synced: string[] = [];
+    const queue = new TranscriptWriteQueue<number>({
+      write: async () => {},
+      sync: async (threadId) => {
+        synced.push(threadId);
+      },
+      syncIntervalMs: 60_000,
+    });
+
+    const append = queue.enqueue("thread-1", [1]);
+    void queue.enqueue("thread-2", [1]);
+    await queue.flush();
+    await append;
+    await queue.flush();
+
+    expect(synced.sort()).toEqual(["thread-1", "thread-2"]);
+  });
+
+  test("rejects the callers of a failed write without blocking later ones", async () => {
+    let fail = true;
+    const queue = new TranscriptWriteQueue<number>({
+      write: async () => {
+        if (fail) {
+          fail = false;
+          throw new Error("disk full");
+        }
+      },
+      sync: async () => {},
+    });
+
+    await expect(queue.enqueue("thread-1", [1])).rejects.toThrow("disk full");
+    expect((queue as unknown as { threads: Map<string, unknown> }).threads.size).toBe(0);
+    await queue.enqueue("thread-1", [2]);
+  });
+
+  test("makes callers wait once a thread has too many unwritten items", async () => {
+    const firstWrite = deferred();
+    const writes: number[][] = [];
+    const queue = new TranscriptWriteQueue<number>({
+      write: async (_threadId, items) => {
+        writes.push(items);
+        if (writes.length === 1) {
+          await firstWrite.promise;
+        }
+      },
+      sync: async () => {},
+      maxPendingPerThread: 2,
+    });
+
+    const first = queue.enqueue("thread-1", [1, 2]);
+    const second = queue.enqueue("thread-1", [3]);
+    await Promise.resolve();
+    expect(writes).toEqual([[1, 2]]);
+
+    firstWrite.resolve();
+    await Promise.all([first, second]);
+    expect(writes).toEqual([[1, 2], [3]]);
+  });
+});
@@ -0,0 +1,192 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { TrashService } = await import("../electron/services/trash");
+
+const DELETED_AT = "2026-10-01T00:00:00.000Z";
+
+function transcriptLine(threadId: string, text: string): string {
+  return JSON.stringify({
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId,
+    direction: "client",
+    payload: { type: "user_message", text },
+  });
+}
+
+describe("trash", () => {
+  let root = "";
+  let clock = new Date(DELETED_AT);
+  let retentionDays = 30;
+
+  const createTrash = () => {
+    const ids = ["entry-1", "entry-2", "entry-3"];
+    return new TrashService({
+      dataDir: userDataDir,
+      getRetentionDays: async () => retentionDays,
+      now: () => clock,
+      createId: () => ids.shift() ?? "unexpected",
+    });
+  };
+
+  const transcriptPath = (threadId: string) =>
+    path.join(userDataDir, "transcripts", `${threadId}.jsonl`);
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-trash-"));
+    userDataDir = path.join(root, "Cowork");
+    clock = new Date(DELETED_AT);
+    retentionDays = 30;
+    await fs.mkdir(path.join(userDataDir, "transcripts"), { recursive: true });
+    const workspace = {
+      id: "ws-1",
+      name: "Project",
+      path: root,
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastOpenedAt: "2026-01-01T00:00:00.000Z",
+      defaultProvider: "openai",
+      defaultModel: "o3",
+      yolo: false,
+    };
+    const thread = (id: string) => ({
+      id,
+      workspaceId: "ws-1",
+      title: `Thread ${id}`,
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastMessageAt: "2026-01-01T00:00:00.000Z",
+    });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [workspace],
+        threads: [thread("thread-1"), thread("thread-2")],
+      }),
+      "utf8",
+    );
+    for (const threadId of ["thread-1", "thread-2"]) {
+      await fs.writeFile(transcriptPath(threadId), `${transcriptLine(threadId, "hi")}\n`, "utf8");
+    }
+  });
+
+  afterEach(async () => {
+    if (root) {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+    root = "";
+    userDataDir = "";
+  });
+
+  test("moves a removed workspace and its transcripts to the trash and restores them", async () => {
+    const trash = createTrash();
+    const persistence = new PersistenceService({ trash });
+
+    const state = await persistence.removeRecords({ workspaceIds: ["ws-1"] });
+
+    expect(state.workspaces).toEqual([]);
+    expect(state.threads).toEqual([]);
+    await expect(fs.access(transcriptPath("thread-1"))).rejects.toThrow();
+    expect(await trash.list()).toEqual([
+      expect.objectContaining({
+        id: "entry-1",
+        kind: "workspace",
+        label: "Project",
+        deletedAt: DELETED_AT,
+        expiresAt: "2026-10-31T00:00:00.000Z",
+        workspaceCount: 1,
+        threadCount: 2,
+      }),
+    ]);
+
+    const restored = await persistence.restoreFromTrash("entry-1");
+
+    expect(restored.workspaces.map((workspace) => workspace.id)).toEqual(["ws-1"]);
+    expect(restored.threads.map((thread) => thread.id)).toEqual(["thread-1", "thread-2"]);
+    expect(await persistence.readTranscript("thread-1")).toEqual([
+      expect.objectContaining({ threadId: "thread-1" }),
+    ]);
+    expect((await persistence.loadState()).threads).toHaveLength(2);
+    expect(await trash.list()).toEqual([]);
+  });
+
+  test("keeps a deleted transcript with its thread record", async () => {
+    const trash = createTrash();
+    const persistence = new PersistenceService({ trash });
+
+    await persistence.trashTranscript("thread-2");
+    await persistence.trashTranscript("thread-2");
+
+    expect(await trash.list()).toEqual([
+      expect.objectContaining({ kind: "thread", label: "Thread thread-2", threadCount: 1 }),
+    ]);
+    await persistence.removeRecords({ threadIds: ["thread-2"] });
+    await persistence.restoreFromTrash("entry-1");
+
+    expect((await persistence.loadState()).threads.map((thread) => thread.id)).toContain(
+      "thread-2",
+    );
+    expect(await persistence.readTranscript("thread-2")).toHaveLength(1);
+  });
+
+  test("refuses to restore over a file that was recreated", async () => {
+    const trash = createTrash();
+    await trash.put({
+      kind: "thread",
+      label: "Thread thread-1",
+      files: [transcriptPath("thread-1")],
+      records: { workspaces: [], threads: [] },
+    });
+    await fs.writeFile(transcriptPath("thread-1"), "new\n", "utf8");
+
+    await expect(trash.restore("entry-1", async () => {})).rejects.toThrow(
+      "Cannot restore over an existing file",
+    );
+    expect(await fs.readFile(transcriptPath("thread-1"), "utf8")).toBe("new\n");
+    expect(await trash.list()).toHaveLength(1);
+  });
+
+  test("purges entries older than the retention setting", async () => {
+    const trash = createTrash();
+    await trash.put({
+      kind: "thread",
+      label: "old",
+      files: [transcriptPath("thread-1")],
+      records: { workspaces: [], threads: [] },
+    });
+    clock = new Date("2026-10-20T00:00:00.000Z");
+    await trash.put({
+      kind: "thread",
+      label: "recent",
+      files: [transcriptPath("thread-2")],
+      records: { workspaces: [], threads: [] },
+    });
+    clock = new Date("2026-11-05T00:00:00.000Z");
+
+    retentionDays = 0;
+    expect(await trash.purgeExpired()).toBe(0);
+    expect((await trash.list()).map((entry) => entry.expiresAt)).toEqual([null, null]);
+
+    retentionDays = 30;
+    expect(await trash.purgeExpired()).toBe(1);
+    expect((await trash.list()).map((entry) => entry.label)).toEqual(["recent"]);
+    await expect(fs.access(path.join(userDataDir, "trash", "entry-1"))).rejects.toThrow();
+  });
+});
@@ -0,0 +1,171 @@
+import { Database } from "bun:sqlite";
+import { describe, expect, test } from "bun:test";
+
+import {
+  localDayKey,
+  UsageAnalyticsService,
+  UsageEventParser,
+  usageRangeStart,
+} from "../electron/services/usageAnalytics";
+import type { PersistedState, ThreadRecord, TranscriptEvent } from "../src/app/types";
+import { usageSummarySchema } from "../src/lib/desktopSchemas";
+import { formatUsageTotals } from "../src/ui/settings/pages/UsagePage";
+
+const NOW = new Date(2026, 4, 10, 15, 0, 0).getTime();
+
+function daysAgo(days: number): string {
+  return new Date(NOW - days * 24 * 60 * 60 * 1000).toISOString();
+}
+
+function serverEvent(threadId: string, ts: string, payload: unknown): TranscriptEvent {
+  return { ts, threadId, direction: "server", payload };
+}
+
+function turnUsage(
+  threadId: string,
+  turnId: string,
+  ts: string,
+  usage: Record<string, number>,
+): TranscriptEvent {
+  return serverEvent(threadId, ts, { type: "turn_usage", sessionId: "s", turnId, usage });
+}
+
+function createService() {
+  const state = {
+    version: 2,
+    workspaces: [],
+    threads: [
+      { id: "thread-a", workspaceId: "ws-1" },
+      { id: "thread-b", workspaceId: "ws-2", sessionId: "session-b" },
+    ] as ThreadRecord[],
+  } as unknown as PersistedState;
+  const errors: unknown[] = [];
+  const service = new UsageAnalyticsService({
+    openDatabase: () => new Database(":memory:"),
+    loadState: async () => state,
+    onError: (error) => errors.push(error),
+  });
+  return { service, errors };
+}
+
+describe("usage event parsing", () => {
+  test("prices turns from the thread's latest model when the server did not", () => {
+    const parser = new UsageEventParser();
+    const hello = serverEvent("t", daysAgo(0), {
+      type: "server_hello",
+      sessionId: "s",
+      config: { provider: "openai", model: "gpt-5.2", workingDirectory: "/w" },
+    });
+
+    expect(parser.parse(hello)).toBeNull();
+    const priced = parser.parse(
+      turnUsage("t", "turn-1", daysAgo(0), {
+        promptTokens: 1_000_000,
+        completionTokens: 0,
+        totalTokens: 1_000_000,
+      }),
+    );
+    const reported = parser.parse(
+      turnUsage("t", "turn-2", daysAgo(0), {
+        promptTokens: 10,
+        completionTokens: 5,
+        totalTokens: 15,
+        estimatedCostUsd: 0.25,
+      }),
+    );
+
+    expect(priced).toMatchObject({ provider: "openai", model: "gpt-5.2", turnId: "turn-1" });
+    expect(priced?.estimatedCostUsd).toBeGreaterThan(0);
+    expect(reported?.estimatedCostUsd).toBe(0.25);
+    expect(
+      parser.parse({ ...turnUsage("t", "turn-3", daysAgo(0), {}), direction: "client" }),
+    ).toBeNull();
+  });
+
+  test("ranges start at local midnight", () => {
+    expect(usageRangeStart("all", NOW)).toBeNull();
+    expect(localDayKey(usageRangeStart("today", NOW) ?? 0)).toBe("2026-05-10");
+    expect(localDayKey(usageRangeStart("7d", NOW) ?? 0)).toBe("2026-05-04");
+  });
+});
+
+describe("usage analytics service", () => {
+  test("aggregates per day, workspace, and thread and replaces replayed turns", async () => {
+    const { service, errors } = createService();
+
+    service.handleAppended([
+      turnUsage("thread-a", "turn-1", daysAgo(0), {
+        promptTokens: 100,
+        completionTokens: 50,
+        totalTokens: 150,
+        estimatedCostUsd: 0.5,
+      }),
+      turnUsage("thread-a", "turn-1", daysAgo(0), {
+        promptTokens: 120,
+        completionTokens: 60,
+        totalTokens: 180,
+        estimatedCostUsd: 0.6,
+      }),
+      turnUsage("session-b", "turn-1", daysAgo(2), {
+        promptTokens: 10,
+        completionTokens: 10,
+        totalTokens: 20,
+      }),
+      turnUsage("thread-a", "turn-old", daysAgo(20), {
+        promptTokens: 1,
+        completionTokens: 1,
+        totalTokens: 2,
+        estimatedCostUsd: 1,
+      }),
+    ]);
+
+    const week = await service.getSummary({ range: "7d" }, NOW);
+    const workspace = await service.getSummary({ range: "all", workspaceId: "ws-1" }, NOW);
+
+    expect(errors).toEqual([]);
+    expect(usageSummarySchema.safeParse(week).success).toBe(true);
+    expect(week.totals).toEqual({
+      turns: 2,
+      promptTokens: 130,
+      completionTokens: 70,
+      cachedPromptTokens: 0,
+      reasoningOutputTokens: 0,
+      totalTokens: 200,
+      estimatedCostUsd: 0.6,
+      unpricedTurns: 1,
+    });
+    expect(week.byDay.map((entry) => [entry.day, entry.totalTokens])).toEqual([
+      [localDayKey(NOW), 180],
+      [localDayKey(Date.parse(daysAgo(2))), 20],
+    ]);
+    expect(week.byWorkspace.map((entry) => entry.workspaceId)).toEqual(["ws-1", "ws-2"]);
+    expect(week.byThread.map((entry) => [entry.threadId, entry.workspaceId])).toEqual([
+      ["thread-a", "ws-1"],
+      ["session-b", "ws-2"],
+    ]);
+    expect(workspace.totals.turns).toBe(2);
+    expect(workspace.totals.estimatedCostUsd).toBeCloseTo(1.6);
+    expect(formatUsageTotals(week.totals)).toBe("200 tokens · 2 turns · $0.60 (1 unpriced)");
+    service.dispose();
+  });
+
+  test("returns an empty summary when the database cannot be opened", async () => {
+    const errors: unknown[] = [];
+    const service = new UsageAnalyticsService({
+      openDatabase: () => {
+        throw new Error("disk I/O error");
+      },
+      loadState: async () => ({ version: 2, workspaces: [], threads: [] }) as PersistedState,
+      onError: (error) => errors.push(error),
+    });
+
+    service.handleAppended([
+      turnUsage("t", "turn-1", daysAgo(0), { promptTokens: 1, completionTokens: 1 }),
+    ]);
+    const summary = await service.getSummary({ range: "today" }, NOW);
+
+    expect(summary.totals.turns).toBe(0);
+    expect(summary.byDay).toEqual([]);
+    expect(errors).toHaveLength(1);
+  });
+});
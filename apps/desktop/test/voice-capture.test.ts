@@ -0,0 +1,115 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  createVoiceTranscriber,
+  MAX_VOICE_CAPTURE_BYTES,
+  type VoiceAudio,
+  VoiceCaptureService,
+} from "../electron/services/voiceCapture";
+import type { VoiceTranscriptEvent } from "../src/lib/desktopApi";
+
+function createService(transcribe: (audio: VoiceAudio) => Promise<string>) {
+  const events: VoiceTranscriptEvent[] = [];
+  const service = new VoiceCaptureService({
+    transcribe,
+    emit: (event) => events.push(event),
+    createId: () => "capture-1",
+    interimIntervalMs: 10,
+  });
+  return { service, events };
+}
+
+describe("VoiceCaptureService", () => {
+  test("emits interim text while recording and the final text on stop", async () => {
+    const { service, events } = createService(async ({ data }) => `${data.byteLength} bytes`);
+    expect(service.start({ threadId: "thread-1", mimeType: "audio/webm" })).toEqual({
+      captureId: "capture-1",
+    });
+    service.append({ captureId: "capture-1", chunk: new Uint8Array(3) });
+    await Bun.sleep(30);
+    service.append({ captureId: "capture-1", chunk: new Uint8Array(2) });
+
+    const final = await service.stop();
+    expect(final).toEqual({
+      captureId: "capture-1",
+      threadId: "thread-1",
+      text: "5 bytes",
+      final: true,
+    });
+    expect(events[0]).toEqual({ ...final!, text: "3 bytes", final: false });
+    expect(events.at(-1)).toEqual(final!);
+    expect(await service.stop()).toBeNull();
+  });
+
+  test("rejects stale captures and audio over the size limit", async () => {
+    const { service, events } = createService(async () => "never");
+    service.start({ threadId: "thread-1", mimeType: "audio/webm" });
+    expect(() => service.append({ captureId: "other", chunk: new Uint8Array(1) })).toThrow(
+      "no longer running",
+    );
+    const oversized = new Uint8Array(MAX_VOICE_CAPTURE_BYTES + 1);
+    expect(() => service.append({ captureId: "capture-1", chunk: oversized })).toThrow(
+      "limited to 25 MB",
+    );
+
+    expect(await service.stop({ discard: true })).toBeNull();
+    expect(events).toEqual([]);
+  });
+});
+
+describe("createVoiceTranscriber", () => {
+  const audio = { data: Buffer.from("abc"), mimeType: "audio/webm;codecs=opus" };
+
+  test("sends the saved OpenAI key to OpenAI", async () => {
+    const requests: Array<{ url: string; init: RequestInit }> = [];
+    const transcribe = createVoiceTranscriber({
+      getSettings: async () => ({ voiceTranscriptionBaseUrl: null, voiceTranscriptionModel: null }),
+      readStore: async () =>
+        ({ services: { openai: { mode: "api_key", apiKey: "sk-test" } } }) as never,
+      env: {},
+      fetch: async (url, init) => {
+        requests.push({ url, init });
+        return Response.json({ text: " hello there " });
+      },
+    });
+
+    expect(await transcribe(audio)).toBe("hello there");
+    expect(requests[0]!.url).toBe("https://api.openai.com/v1/audio/transcriptions");
+    expect(requests[0]!.init.headers).toMatchObject({ authorization: "Bearer sk-test" });
+    const form = requests[0]!.init.body as FormData;
+    expect(form.get("model")).toBe("whisper-1");
+    expect((form.get("file") as File).name).toBe("dictation.webm");
+  });
+
+  test("never sends the OpenAI key to a custom endpoint", async () => {
+    const requests: Array<{ url: string; init: RequestInit }> = [];
+    const transcribe = createVoiceTranscriber({
+      getSettings: async () => ({
+        voiceTranscriptionBaseUrl: "http://127.0.0.1:8080/v1/",
+        voiceTranscriptionModel: "large-v3",
+      }),
+      readStore: async () =>
+        ({ services: { openai: { mode: "api_key", apiKey: "sk-test" } } }) as never,
+      env: { OPENAI_API_KEY: "sk-env" },
+      fetch: async (url, init) => {
+        requests.push({ url, init });
+        return Response.json({ text: "local" });
+      },
+    });
+
+    expect(await transcribe(audio)).toBe("local");
+    expect(requests[0]!.url).toBe("http://127.0.0.1:8080/v1/audio/transcriptions");
+    expect(requests[0]!.init.headers).not.toHaveProperty("authorization");
+    expect((requests[0]!.init.body as FormData).get("model")).toBe("large-v3");
+  });
+
+  test("needs a key or an endpoint", async () => {
+    const transcribe = createVoiceTranscriber({
+      getSettings: async () => ({ voiceTranscriptionBaseUrl: null, voiceTranscriptionModel: null }),
+      readStore: async () => ({ services: {} }) as never,
+      env: {},
+      fetch: async () => Response.json({ text: "" }),
+    });
+    await expect(transcribe(audio)).rejects.toThrow("needs an OpenAI API key");
+  });
+});
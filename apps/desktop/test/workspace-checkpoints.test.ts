@@ -0,0 +1,172 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  createWorkspaceCheckpoint,
+  formatCheckpointMessage,
+  listWorkspaceCheckpoints,
+  parseCheckpointLog,
+  restoreWorkspaceCheckpoint,
+  runGit,
+  WORKSPACE_CHECKPOINT_REF,
+  WorkspaceCheckpointService,
+} from "../electron/services/workspaceCheckpoints";
+import type { PersistedState, WorkspaceRecord } from "../src/app/types";
+import { workspaceCheckpointListSchema } from "../src/lib/desktopSchemas";
+
+const IDENTITY = {
+  GIT_AUTHOR_NAME: "Test",
+  GIT_AUTHOR_EMAIL: "test@example.com",
+  GIT_COMMITTER_NAME: "Test",
+  GIT_COMMITTER_EMAIL: "test@example.com",
+};
+
+let repoDir = "";
+
+async function git(...args: string[]): Promise<string> {
+  return (await runGit(repoDir, args, IDENTITY)).trim();
+}
+
+async function readRepoFile(relativePath: string): Promise<string | null> {
+  return await fs.readFile(path.join(repoDir, relativePath), "utf8").catch(() => null);
+}
+
+function createService(overrides: Partial<WorkspaceRecord> = {}) {
+  const workspace = { id: "ws-1", path: repoDir, yolo: true, ...overrides } as WorkspaceRecord;
+  return new WorkspaceCheckpointService({
+    loadState: async () => ({ version: 2, workspaces: [workspace], threads: [] }) as PersistedState,
+  });
+}
+
+beforeEach(async () => {
+  repoDir = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), "cowork-checkpoints-")));
+  await git("init", "--quiet");
+  await fs.writeFile(path.join(repoDir, ".gitignore"), "build/\n");
+  await fs.writeFile(path.join(repoDir, "app.ts"), "one\n");
+  await git("add", "--all");
+  await git("commit", "--quiet", "-m", "init");
+});
+
+afterEach(async () => {
+  await fs.rm(repoDir, { recursive: true, force: true });
+});
+
+describe("checkpoint commit messages", () => {
+  test("round-trip reason and HEAD through trailers", () => {
+    const message = formatCheckpointMessage({
+      reason: "before-restore",
+      workspaceId: "ws-1",
+      head: "abc1234",
+    });
+    const stdout = `\n${["def5678", "2026-05-01T10:00:00Z", message].join("\x1f")}\x1e\n`;
+
+    expect(parseCheckpointLog(stdout)).toEqual([
+      {
+        id: "def5678",
+        createdAt: "2026-05-01T10:00:00Z",
+        reason: "before-restore",
+        head: "abc1234",
+      },
+    ]);
+    expect(parseCheckpointLog("")).toEqual([]);
+  });
+});
+
+describe("git checkpoints", () => {
+  test("snapshot untracked files without touching the index or branch", async () => {
+    const head = await git("rev-parse", "HEAD");
+    await fs.writeFile(path.join(repoDir, "app.ts"), "two\n");
+    await fs.writeFile(path.join(repoDir, "notes.md"), "draft\n");
+    await fs.mkdir(path.join(repoDir, "build"));
+    await fs.writeFile(path.join(repoDir, "build", "out.js"), "ignored\n");
+    await git("add", "notes.md");
+
+    const checkpoint = await createWorkspaceCheckpoint(runGit, repoDir, {
+      reason: "yolo-start",
+      workspaceId: "ws-1",
+    });
+
+    expect(checkpoint).toMatchObject({ reason: "yolo-start", head });
+    expect(await git("rev-parse", "HEAD")).toBe(head);
+    expect(await git("diff", "--cached", "--name-only")).toBe("notes.md");
+    expect(await git("ls-tree", "-r", "--name-only", WORKSPACE_CHECKPOINT_REF)).toBe(
+      [".gitignore", "app.ts", "notes.md"].join("\n"),
+    );
+    await expect(
+      createWorkspaceCheckpoint(runGit, repoDir, { reason: "yolo-start", workspaceId: "ws-1" }),
+    ).resolves.toBeNull();
+    expect(await listWorkspaceCheckpoints(runGit, repoDir)).toEqual([checkpoint]);
+  });
+
+  test("restore rewrites files, removes new ones, and checkpoints first", async () => {
+    const checkpoint = await createWorkspaceCheckpoint(runGit, repoDir, {
+      reason: "yolo-start",
+      workspaceId: "ws-1",
+    });
+    if (!checkpoint) {
+      throw new Error("Expected an initial checkpoint");
+    }
+    await fs.writeFile(path.join(repoDir, "app.ts"), "broken\n");
+    await fs.mkdir(path.join(repoDir, "src"));
+    await fs.writeFile(path.join(repoDir, "src", "generated.ts"), "new\n");
+
+    const result = await restoreWorkspaceCheckpoint(runGit, repoDir, {
+      workspaceId: "ws-1",
+      checkpointId: checkpoint.id,
+    });
+
+    expect(result.removedFileCount).toBe(1);
+    expect(result.safetyCheckpoint?.reason).toBe("before-restore");
+    expect(await readRepoFile("app.ts")).toBe("one\n");
+    expect(await readRepoFile("src/generated.ts")).toBeNull();
+    const checkpoints = await listWorkspaceCheckpoints(runGit, repoDir);
+    expect(checkpoints.map((entry) => entry.reason)).toEqual(["before-restore", "yolo-start"]);
+    const list = { workspaceId: "ws-1", isRepository: true, checkpoints };
+    expect(workspaceCheckpointListSchema.safeParse(list).success).toBe(true);
+  });
+
+  test("restore refuses commits that are not checkpoints", async () => {
+    const head = await git("rev-parse", "HEAD");
+
+    await expect(
+      restoreWorkspaceCheckpoint(runGit, repoDir, { workspaceId: "ws-1", checkpointId: head }),
+    ).rejects.toThrow("Unknown checkpoint");
+  });
+});
+
+describe("workspace checkpoint service", () => {
+  test("only checkpoints workspaces that opted in", async () => {
+    await fs.writeFile(path.join(repoDir, "app.ts"), "two\n");
+
+    await expect(createService().checkpointBeforeYoloStart("ws-1")).resolves.toBeNull();
+    const checkpoint = await createService({ yoloCheckpoints: true }).checkpointBeforeYoloStart(
+      "ws-1",
+    );
+
+    expect(checkpoint?.reason).toBe("yolo-start");
+    await expect(createService().list("ws-1")).resolves.toEqual({
+      workspaceId: "ws-1",
+      isRepository: true,
+      checkpoints: [checkpoint],
+    });
+  });
+
+  test("reports folders outside a repository and rejects unknown workspaces", async () => {
+    const plainDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-no-repo-"));
+    try {
+      const service = createService({ path: plainDir, yoloCheckpoints: true });
+
+      await expect(service.checkpointBeforeYoloStart("ws-1")).resolves.toBeNull();
+      await expect(service.list("ws-1")).resolves.toEqual({
+        workspaceId: "ws-1",
+        isRepository: false,
+        checkpoints: [],
+      });
+      await expect(service.list("missing")).rejects.toThrow("Unknown workspace");
+    } finally {
+      await fs.rm(plainDir, { recursive: true, force: true });
+    }
+  });
+});
@@ -0,0 +1,151 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  pickWorkspaceConfig,
+  resolveWorkspaceConfigPath,
+  WorkspaceConfigService,
+} from "../electron/services/workspaceConfig";
+import type { PersistedState, WorkspaceRecord } from "../src/app/types";
+import type { WorkspaceConfigSnapshot } from "../src/lib/desktopApi";
+import {
+  workspaceConfigSnapshotSchema,
+  writeWorkspaceConfigInputSchema,
+} from "../src/lib/desktopSchemas";
+
+let workspaceDir = "";
+
+function workspace(overrides: Partial<WorkspaceRecord> = {}): WorkspaceRecord {
+  return {
+    id: "ws-1",
+    name: "Project",
+    path: workspaceDir,
+    createdAt: "2026-01-01T00:00:00.000Z",
+    lastOpenedAt: "2026-01-01T00:00:00.000Z",
+    defaultProvider: "openai",
+    defaultModel: "gpt-5.2",
+    defaultEnableMcp: true,
+    defaultBackupsEnabled: true,
+    yolo: false,
+    ...overrides,
+  } as WorkspaceRecord;
+}
+
+function createService(record: WorkspaceRecord = workspace()) {
+  const emitted: WorkspaceConfigSnapshot[] = [];
+  const triggers = new Map<string, () => void>();
+  const service = new WorkspaceConfigService({
+    loadState: async () => ({ version: 2, workspaces: [record], threads: [] }) as PersistedState,
+    emit: (snapshot) => emitted.push(snapshot),
+    watchFile: (filePath, onChange) => {
+      triggers.set(filePath, onChange);
+      return () => triggers.delete(filePath);
+    },
+  });
+  return { service, emitted, triggers };
+}
+
+async function waitFor(predicate: () => boolean): Promise<void> {
+  for (let attempt = 0; attempt < 50 && !predicate(); attempt += 1) {
+    await new Promise((resolve) => setTimeout(resolve, 5));
+  }
+}
+
+beforeEach(async () => {
+  workspaceDir = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-workspace-config-"));
+});
+
+afterEach(async () => {
+  await fs.rm(workspaceDir, { recursive: true, force: true });
+});
+
+describe("workspace config file", () => {
+  test("keeps only well-typed desktop keys", () => {
+    expect(
+      pickWorkspaceConfig({
+        provider: "not-a-provider",
+        model: "  claude-opus  ",
+        enableMcp: "yes",
+        yolo: true,
+        subAgentModel: "other",
+      }),
+    ).toEqual({ model: "claude-opus", yolo: true });
+  });
+
+  test("falls back to desktop defaults when the file is missing", async () => {
+    const { service } = createService();
+
+    const snapshot = await service.read("ws-1");
+
+    expect(snapshot).toEqual({
+      workspaceId: "ws-1",
+      path: resolveWorkspaceConfigPath(workspaceDir),
+      exists: false,
+      error: null,
+      config: {},
+      effective: { provider: "openai", model: "gpt-5.2", enableMcp: true, yolo: false },
+    });
+    expect(workspaceConfigSnapshotSchema.safeParse(snapshot).success).toBe(true);
+  });
+
+  test("writes merge into the file and preserve keys the desktop does not manage", async () => {
+    const configPath = resolveWorkspaceConfigPath(workspaceDir);
+    await fs.mkdir(path.dirname(configPath), { recursive: true });
+    await fs.writeFile(configPath, JSON.stringify({ model: "old", observabilityEnabled: true }));
+    const { service } = createService();
+    const input = writeWorkspaceConfigInputSchema.parse({
+      workspaceId: "ws-1",
+      config: { provider: "anthropic", model: null, enableMcp: false },
+    });
+
+    const snapshot = await service.write(input);
+
+    expect(JSON.parse(await fs.readFile(configPath, "utf8"))).toEqual({
+      observabilityEnabled: true,
+      provider: "anthropic",
+      enableMcp: false,
+    });
+    expect(snapshot.config).toEqual({ provider: "anthropic", enableMcp: false });
+    expect(snapshot.effective).toEqual({
+      provider: "anthropic",
+      model: "gpt-5.2",
+      enableMcp: false,
+      yolo: false,
+    });
+  });
+
+  test("refuses to overwrite a file that is not valid JSON", async () => {
+    const configPath = resolveWorkspaceConfigPath(workspaceDir);
+    await fs.mkdir(path.dirname(configPath), { recursive: true });
+    await fs.writeFile(configPath, "{ broken");
+    const { service } = createService();
+
+    expect((await service.read("ws-1")).error).toStartWith("Invalid JSON");
+    await expect(service.write({ workspaceId: "ws-1", config: { yolo: true } })).rejects.toThrow(
+      "Cannot update",
+    );
+    expect(await fs.readFile(configPath, "utf8")).toBe("{ broken");
+  });
+
+  test("reports external edits but not the app's own writes", async () => {
+    const configPath = resolveWorkspaceConfigPath(workspaceDir);
+    const { service, emitted, triggers } = createService();
+    await service.write({ workspaceId: "ws-1", config: { yolo: true } });
+
+    triggers.get(configPath)?.();
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    expect(emitted).toEqual([]);
+
+    await fs.writeFile(configPath, JSON.stringify({ yolo: false, model: "edited" }));
+    triggers.get(configPath)?.();
+    await waitFor(() => emitted.length > 0);
+
+    expect(emitted).toHaveLength(1);
+    expect(emitted[0]?.config).toEqual({ yolo: false, model: "edited" });
+
+    service.unwatch("ws-1");
+    expect(triggers.size).toBe(0);
+  });
+});
@@ -144,4 +144,79 @@ describe("WorkspaceDirectoryWatcher", () => {
 
     expect(events).toEqual([]);
   });
+
+  test("polls network filesystems and backs off while nothing changes", async () => {
+    const snapshots = [
+      new Map([["/repo/a.txt", "1:10"]]),
+      new Map([["/repo/a.txt", "1:10"]]),
+      new Map([
+        ["/repo/a.txt", "2:12"],
+        ["/repo/b.txt", "1:1"],
+      ]),
+    ];
+    let scans = 0;
+    const events: WorkspaceFileChangeEvent[] = [];
+    const watcher = new WorkspaceDirectoryWatcher({
+      isNetworkFilesystem: () => true,
+      minPollIntervalMs: 1,
+      maxPollIntervalMs: 4,
+      scan: async () => snapshots[Math.min(scans++, snapshots.length - 1)] ?? new Map(),
+      watch: () => {
+        throw new Error("native watching should not be used");
+      },
+    });
+    const scope = { workspaceId: "workspace-a", rootPath: "/repo" };
+
+    expect(watcher.watch(scope, "renderer", (event) => events.push(event))).toBe(true);
+    await new Promise((resolve) => setTimeout(resolve, 30));
+
+    expect(events.map((event) => event.kind)).toEqual(["modify", "add"]);
+    const [status] = watcher.getStatus("workspace-a").watches;
+    expect(status).toMatchObject({
+      mode: "polling",
+      healthy: true,
+      reason: "Network filesystem; native change events are unreliable here.",
+      subscriberCount: 1,
+      downgradedAt: null,
+    });
+    expect(status?.pollIntervalMs).toBeGreaterThan(1);
+    watcher.dispose();
+  });
+
+  test("downgrades to polling when the native watcher errors", async () => {
+    let onError: ((error: Error) => void) | undefined;
+    let closes = 0;
+    const watcher = new WorkspaceDirectoryWatcher({
+      isNetworkFilesystem: () => false,
+      minPollIntervalMs: 1,
+      now: () => Date.parse("2026-01-01T00:00:00.000Z"),
+      scan: async () => {
+        throw new Error("EIO: i/o error, scandir '/repo'");
+      },
+      watch: (_rootPath, _listener, errorListener) => {
+        onError = errorListener;
+        return {
+          close() {
+            closes += 1;
+          },
+        };
+      },
+    });
+    watcher.watch({ workspaceId: "workspace-a", rootPath: "/repo" }, "renderer", () => {});
+    expect(watcher.getStatus("workspace-a").watches[0]?.mode).toBe("native");
+
+    onError?.(new Error("ENOSPC: System limit for number of file watchers reached"));
+    await settleWatcher();
+
+    expect(closes).toBe(1);
+    expect(watcher.getStatus("workspace-a").watches[0]).toMatchObject({
+      mode: "polling",
+      healthy: false,
+      reason: "Native watching failed: ENOSPC: System limit for number of file watchers reached",
+      lastError: "EIO: i/o error, scandir '/repo'",
+      downgradedAt: "2026-01-01T00:00:00.000Z",
+    });
+    expect(watcher.getStatus("workspace-b").watches).toEqual([]);
+    watcher.dispose();
+  });
 });
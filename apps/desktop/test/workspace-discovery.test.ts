@@ -0,0 +1,112 @@
+import { afterEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  detectWorkspaceLanguage,
+  scanForWorkspaces,
+} from "../electron/services/workspaceDiscovery";
+
+const temporaryDirectories: string[] = [];
+
+async function createRoot(): Promise<string> {
+  const root = await fs.realpath(
+    await fs.mkdtemp(path.join(os.tmpdir(), "cowork-workspace-discovery-")),
+  );
+  temporaryDirectories.push(root);
+  return root;
+}
+
+async function writeFile(filePath: string, contents: string): Promise<void> {
+  await fs.mkdir(path.dirname(filePath), { recursive: true });
+  await fs.writeFile(filePath, contents, "utf8");
+}
+
+afterEach(async () => {
+  await Promise.all(
+    temporaryDirectories.splice(0).map(async (directory) => {
+      await fs.rm(directory, { force: true, recursive: true });
+    }),
+  );
+});
+
+describe("workspace discovery", () => {
+  test("finds repositories and .cowork folders with their metadata", async () => {
+    const root = await createRoot();
+    await fs.mkdir(path.join(root, "api", ".git"), { recursive: true });
+    await writeFile(path.join(root, "api", "Cargo.toml"), "[package]\n");
+    await writeFile(path.join(root, "api", "src", "main.rs"), "fn main() {}\n");
+    await writeFile(path.join(root, "api", "target", "debug", "app"), "x".repeat(1000));
+    await fs.mkdir(path.join(root, "clients", "notes", ".cowork"), { recursive: true });
+    await writeFile(path.join(root, "clients", "notes", "todo.md"), "- ship\n");
+    await writeFile(path.join(root, "loose.txt"), "not a project");
+
+    const lastCommit = {
+      sha: "abc123",
+      summary: "Initial commit",
+      author: "Ada",
+      committedAt: "2026-01-02T03:04:05Z",
+    };
+    const result = await scanForWorkspaces([root], 3, {
+      appDataPaths: [],
+      workspaces: [],
+      readLastCommit: async () => lastCommit,
+    });
+
+    expect(result).toEqual({
+      candidates: [
+        {
+          path: path.join(root, "api"),
+          name: "api",
+          markers: ["git"],
+          language: "Rust",
+          sizeBytes: "[package]\n".length + "fn main() {}\n".length,
+          sizeComplete: true,
+          lastCommit,
+        },
+        {
+          path: path.join(root, "clients", "notes"),
+          name: "notes",
+          markers: ["cowork"],
+          language: null,
+          sizeBytes: "- ship\n".length,
+          sizeComplete: true,
+          lastCommit: null,
+        },
+      ],
+      truncated: false,
+    });
+  });
+
+  test("skips registered, app data, nested and too-deep folders", async () => {
+    const root = await createRoot();
+    await fs.mkdir(path.join(root, "registered", ".git"), { recursive: true });
+    await fs.mkdir(path.join(root, "userData", "workspaces", "copy", ".git"), { recursive: true });
+    await fs.mkdir(path.join(root, "mono", ".git"), { recursive: true });
+    await fs.mkdir(path.join(root, "mono", "packages", "inner", ".git"), { recursive: true });
+    await fs.mkdir(path.join(root, "a", "b", "deep", ".git"), { recursive: true });
+    await fs.mkdir(path.join(root, "node_modules", "dep", ".git"), { recursive: true });
+    await fs.mkdir(path.join(root, ".cache", "repo", ".git"), { recursive: true });
+
+    const scan = (maxDepth: number) =>
+      scanForWorkspaces([root], maxDepth, {
+        appDataPaths: [path.join(root, "userData")],
+        workspaces: [{ path: path.join(root, "registered") }],
+        readLastCommit: async () => null,
+      });
+
+    expect((await scan(2)).candidates.map((candidate) => candidate.name)).toEqual(["mono"]);
+    expect((await scan(3)).candidates.map((candidate) => candidate.name)).toEqual([
+      "deep",
+      "mono",
+    ]);
+  });
+
+  test("guesses the language from the most specific manifest", () => {
+    expect(detectWorkspaceLanguage(["package.json", "tsconfig.json"])).toBe("TypeScript");
+    expect(detectWorkspaceLanguage(["package.json"])).toBe("JavaScript");
+    expect(detectWorkspaceLanguage(["App.csproj"])).toBe("C#");
+    expect(detectWorkspaceLanguage(["README.md"])).toBeNull();
+  });
+});
@@ -0,0 +1,156 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+const { copyWorkspaceDirectory } = await import("../electron/services/workspaceDuplicate");
+
+function transcriptLine(threadId: string, ts: string, text: string): string {
+  return JSON.stringify({
+    ts,
+    threadId,
+    direction: "client",
+    payload: { type: "user_message", text },
+  });
+}
+
+function threadRecord(id: string, workspaceId: string) {
+  return {
+    id,
+    workspaceId,
+    title: `Thread ${id}`,
+    createdAt: "2026-01-01T00:00:00.000Z",
+    lastMessageAt: "2026-01-01T00:00:00.000Z",
+  };
+}
+
+describe("workspace duplication", () => {
+  let root = "";
+  let workspacePath = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-workspace-duplicate-"));
+    userDataDir = path.join(root, "Cowork");
+    workspacePath = path.join(root, "project");
+    await fs.mkdir(path.join(userDataDir, "transcripts"), { recursive: true });
+    await fs.mkdir(path.join(workspacePath, "src"), { recursive: true });
+    await fs.writeFile(path.join(workspacePath, "src", "index.ts"), "export {};\n", "utf8");
+    const workspace = (id: string, name: string) => ({
+      id,
+      name,
+      path: workspacePath,
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastOpenedAt: "2026-01-01T00:00:00.000Z",
+      defaultProvider: "openai",
+      defaultModel: "o3",
+      yolo: false,
+      pinned: true,
+      tags: ["client"],
+    });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [workspace("ws-1", "Project"), workspace("ws-2", "Other")],
+        threads: [threadRecord("thread-1", "ws-1"), threadRecord("thread-2", "ws-2")],
+      }),
+      "utf8",
+    );
+    await fs.writeFile(
+      path.join(userDataDir, "transcripts", "thread-1.jsonl"),
+      `${transcriptLine("thread-1", "2026-01-01T00:00:00.000Z", "hello")}\n`,
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (root) {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+    root = "";
+    userDataDir = "";
+  });
+
+  test("records a copy with the settings and copies the chosen threads", async () => {
+    const ids = ["ws-copy", "thread-copy"];
+    const persistence = new PersistenceService({
+      now: () => new Date("2026-02-01T00:00:00.000Z"),
+      createId: () => ids.shift() ?? "unexpected",
+    });
+    const copyPath = path.join(root, "project-copy");
+
+    const result = await persistence.duplicateWorkspace("ws-1", {
+      path: copyPath,
+      threadIds: ["thread-1"],
+    });
+
+    expect(result.workspace).toMatchObject({
+      id: "ws-copy",
+      name: "Project (copy)",
+      path: copyPath,
+      defaultModel: "o3",
+      tags: ["client"],
+      createdAt: "2026-02-01T00:00:00.000Z",
+    });
+    expect(result.workspace.pinned).toBeUndefined();
+    expect(result.threads).toEqual([
+      expect.objectContaining({
+        id: "thread-copy",
+        workspaceId: "ws-copy",
+        title: "Thread thread-1",
+        sessionId: null,
+      }),
+    ]);
+    const events = await persistence.readTranscript("thread-copy");
+    expect(events).toEqual([expect.objectContaining({ threadId: "thread-copy" })]);
+    const state = await persistence.loadState();
+    expect(state.workspaces.map((workspace) => workspace.id)).toEqual(["ws-1", "ws-2", "ws-copy"]);
+    expect(state.threads.map((thread) => thread.id)).toContain("thread-copy");
+  });
+
+  test("refuses threads from another workspace", async () => {
+    const persistence = new PersistenceService();
+
+    await expect(
+      persistence.duplicateWorkspace("ws-1", { path: workspacePath, threadIds: ["thread-2"] }),
+    ).rejects.toThrow("Thread thread-2 does not belong to workspace ws-1");
+    await expect(
+      persistence.duplicateWorkspace("missing", { path: workspacePath, threadIds: [] }),
+    ).rejects.toThrow("Unknown workspace: missing");
+    expect((await persistence.loadState()).workspaces).toHaveLength(2);
+  });
+
+  test("copies the directory without linking it to the original", async () => {
+    const copyPath = path.join(root, "project-copy");
+
+    await copyWorkspaceDirectory(workspacePath, copyPath);
+    await fs.writeFile(path.join(copyPath, "src", "index.ts"), "changed\n", "utf8");
+
+    expect(await fs.readFile(path.join(workspacePath, "src", "index.ts"), "utf8")).toBe(
+      "export {};\n",
+    );
+    expect((await fs.readdir(root)).some((name) => name.includes(".copying-"))).toBe(false);
+    await expect(copyWorkspaceDirectory(workspacePath, copyPath)).rejects.toThrow(
+      "Workspace copy path already exists",
+    );
+    await expect(
+      copyWorkspaceDirectory(workspacePath, path.join(workspacePath, "nested")),
+    ).rejects.toThrow("Workspace copy path cannot overlap the original workspace");
+  });
+});
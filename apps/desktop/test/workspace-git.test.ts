@@ -0,0 +1,170 @@
+import { describe, expect, test } from "bun:test";
+
+import type { WorkspaceFileChangeEvent } from "../../../src/filesystem/workspaceFileEvents";
+import {
+  parseGitLastCommit,
+  parseGitStatusPorcelainV2,
+  WorkspaceGitService,
+} from "../electron/services/workspaceGit";
+import type { PersistedState, WorkspaceRecord } from "../src/app/types";
+import type { WorkspaceGitStatus } from "../src/lib/desktopApi";
+import { workspaceGitStatusSchema } from "../src/lib/desktopSchemas";
+import { formatWorkspaceGitTitle } from "../src/ui/sidebar/WorkspaceGitBadge";
+
+const STATUS_OUTPUT = [
+  "# branch.oid 4f2c9a1e0b7d3c5a8e6f1b2d4c3a5e7f9b0d1c2e",
+  "# branch.head feature/git-status",
+  "# branch.upstream origin/feature/git-status",
+  "# branch.ab +2 -1",
+  "1 .M N... 100644 100644 100644 aaa bbb src/index.ts",
+  "2 R. N... 100644 100644 100644 aaa bbb R100 src/new.ts\tsrc/old.ts",
+  "u UU N... 100644 100644 100644 100644 aaa bbb ccc conflict.ts",
+  "? notes.md",
+  "! dist/",
+  "",
+].join("\n");
+
+function gitStatus(overrides: Partial<WorkspaceGitStatus> = {}): WorkspaceGitStatus {
+  return {
+    workspaceId: "ws-1",
+    isRepository: true,
+    branch: "main",
+    upstream: null,
+    ahead: null,
+    behind: null,
+    dirtyFileCount: 0,
+    lastCommit: null,
+    ...overrides,
+  };
+}
+
+function changeEvent(changedPaths: string[]): WorkspaceFileChangeEvent {
+  return {
+    workspaceId: "ws-1",
+    rootPath: "/work/app",
+    kind: "modify",
+    changedPaths,
+    affectedDirectoryPaths: [],
+    invalidatedSubtreePaths: [],
+  };
+}
+
+describe("git output parsing", () => {
+  test("reads branch, upstream, ahead/behind, and counts changed entries", () => {
+    expect(parseGitStatusPorcelainV2(STATUS_OUTPUT)).toEqual({
+      isRepository: true,
+      branch: "feature/git-status",
+      upstream: "origin/feature/git-status",
+      ahead: 2,
+      behind: 1,
+      dirtyFileCount: 4,
+      headOid: "4f2c9a1e0b7d3c5a8e6f1b2d4c3a5e7f9b0d1c2e",
+    });
+  });
+
+  test("handles detached heads and repositories without commits", () => {
+    const detached = parseGitStatusPorcelainV2("# branch.oid abc123\n# branch.head (detached)\n");
+    const empty = parseGitStatusPorcelainV2("# branch.oid (initial)\n# branch.head main\n");
+
+    expect(detached.branch).toBeNull();
+    expect(detached.ahead).toBeNull();
+    expect(empty.headOid).toBeNull();
+    expect(empty.branch).toBe("main");
+  });
+
+  test("splits the last commit fields", () => {
+    const stdout = ["abc123", "Fix: the build", "Ada", "2026-05-01T10:00:00+02:00"].join("\x1f");
+
+    expect(parseGitLastCommit(`${stdout}\n`)).toEqual({
+      sha: "abc123",
+      summary: "Fix: the build",
+      author: "Ada",
+      committedAt: "2026-05-01T10:00:00+02:00",
+    });
+    expect(parseGitLastCommit("")).toBeNull();
+  });
+
+  test("describes the status for the sidebar tooltip", () => {
+    const status = gitStatus({
+      upstream: "origin/main",
+      ahead: 1,
+      behind: 0,
+      dirtyFileCount: 3,
+      lastCommit: {
+        sha: "abcdef1234",
+        summary: "Add git badge",
+        author: "Ada",
+        committedAt: "2026-05-01T10:00:00Z",
+      },
+    });
+
+    expect(workspaceGitStatusSchema.safeParse(status).success).toBe(true);
+    expect(formatWorkspaceGitTitle(status)).toBe(
+      [
+        "Branch: main",
+        "3 changed files",
+        "1 ahead, 0 behind origin/main",
+        "Last commit: Add git badge (abcdef1)",
+      ].join("\n"),
+    );
+  });
+});
+
+describe("workspace git service", () => {
+  function createService(readStatus: () => Promise<WorkspaceGitStatus>) {
+    const listeners = new Map<string, (event: WorkspaceFileChangeEvent) => void>();
+    const emitted: WorkspaceGitStatus[] = [];
+    const service = new WorkspaceGitService({
+      loadState: async () =>
+        ({
+          version: 2,
+          workspaces: [{ id: "ws-1", path: "/work/app" } as WorkspaceRecord],
+          threads: [],
+        }) as PersistedState,
+      emit: (status) => emitted.push(status),
+      directoryWatcher: {
+        watch: (scope, subscriberId, listener) => {
+          listeners.set(`${scope.workspaceId}:${subscriberId}`, listener);
+          return true;
+        },
+        unwatch: (scope, subscriberId) => {
+          listeners.delete(`${scope.workspaceId}:${subscriberId}`);
+        },
+      },
+      readStatus,
+      debounceMs: 5,
+    });
+    return { service, listeners, emitted };
+  }
+
+  test("emits after file changes only when the status differs", async () => {
+    let current = gitStatus();
+    const { service, listeners, emitted } = createService(async () => current);
+
+    await expect(service.getStatus("ws-1")).resolves.toEqual(current);
+    const listener = listeners.get("ws-1:workspace-git");
+    expect(listener).toBeDefined();
+
+    listener?.(changeEvent(["/work/app/README.md"]));
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    expect(emitted).toEqual([]);
+
+    current = gitStatus({ dirtyFileCount: 1 });
+    listener?.(changeEvent(["/work/app/.git/objects/ab/cdef"]));
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    expect(emitted).toEqual([]);
+
+    listener?.(changeEvent(["/work/app/README.md"]));
+    await new Promise((resolve) => setTimeout(resolve, 20));
+    expect(emitted).toEqual([current]);
+
+    service.unwatch("ws-1");
+    expect(listeners.size).toBe(0);
+  });
+
+  test("rejects unknown workspaces", async () => {
+    const { service } = createService(async () => gitStatus());
+
+    await expect(service.getStatus("missing")).rejects.toThrow("Unknown workspace");
+  });
+});
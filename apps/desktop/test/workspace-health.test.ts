@@ -0,0 +1,145 @@
+import { afterEach, beforeEach, describe, expect, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  findExposedSecretFiles,
+  summarizeWorkspaceHealth,
+  WorkspaceHealthService,
+} from "../electron/services/workspaceHealth";
+import { workspaceHealthReportSchema } from "../src/lib/desktopSchemas";
+
+const MB = 1024 * 1024;
+
+function createState(yolo: boolean) {
+  return {
+    version: 2,
+    workspaces: [
+      {
+        id: "ws-1",
+        name: "Workspace",
+        path: "/tmp/cowork-health-workspace",
+        createdAt: "2026-01-01T00:00:00.000Z",
+        lastOpenedAt: "2026-01-01T00:00:00.000Z",
+        defaultEnableMcp: true,
+        defaultBackupsEnabled: false,
+        yolo,
+      },
+    ],
+    threads: [
+      { id: "thread-1", workspaceId: "ws-1" },
+      { id: "thread-2", workspaceId: "ws-1" },
+      { id: "thread-other", workspaceId: "ws-2" },
+    ],
+  } as never;
+}
+
+function createService(opts: {
+  yolo?: boolean;
+  starts?: number;
+  crashes?: number;
+  totalBytes?: number;
+  secretFiles?: string[] | null;
+}) {
+  const requestedThreadIds: string[][] = [];
+  const service = new WorkspaceHealthService({
+    persistence: {
+      loadState: async () => createState(opts.yolo ?? false),
+      getTranscriptStats: async (threadIds) => {
+        requestedThreadIds.push(threadIds);
+        const totalBytes = opts.totalBytes ?? MB;
+        return { fileCount: threadIds.length, totalBytes, largestBytes: totalBytes / 2 };
+      },
+    },
+    serverManager: {
+      getStabilityStats: () => ({ starts: opts.starts ?? 2, crashes: opts.crashes ?? 0 }),
+    },
+    findExposedSecretFiles: async () => (opts.secretFiles === undefined ? [] : opts.secretFiles),
+    now: () => new Date("2026-01-01T12:00:00.000Z"),
+  });
+  return { service, requestedThreadIds };
+}
+
+describe("desktop workspace health", () => {
+  let workspacePath = "";
+
+  beforeEach(async () => {
+    workspacePath = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-health-"));
+  });
+
+  afterEach(async () => {
+    await fs.rm(workspacePath, { recursive: true, force: true });
+  });
+
+  test("a healthy workspace scores 100 with only the untracked index left unknown", async () => {
+    const { service, requestedThreadIds } = createService({});
+
+    const report = await service.getHealth("ws-1");
+
+    expect(workspaceHealthReportSchema.safeParse(report).success).toBe(true);
+    expect(report).toMatchObject({ workspaceId: "ws-1", score: 100, status: "ok" });
+    expect(report.checks.map((check) => [check.id, check.status])).toEqual([
+      ["serverStability", "ok"],
+      ["transcriptSize", "ok"],
+      ["indexFreshness", "unknown"],
+      ["guardrails", "ok"],
+      ["secretsExposure", "ok"],
+    ]);
+    expect(report.checks.every((check) => check.status !== "ok" || !check.recommendation)).toBe(
+      true,
+    );
+    expect(requestedThreadIds).toEqual([["thread-1", "thread-2"]]);
+  });
+
+  test("every failing check carries a recommendation", async () => {
+    const { service } = createService({
+      yolo: true,
+      starts: 4,
+      crashes: 3,
+      totalBytes: 150 * MB,
+      secretFiles: [".env"],
+    });
+
+    const report = await service.getHealth("ws-1");
+    const byId = Object.fromEntries(report.checks.map((check) => [check.id, check]));
+
+    expect(report.status).toBe("critical");
+    expect(report.score).toBe(100 - 35 - 15 - 35 - 15);
+    expect(byId.serverStability?.status).toBe("critical");
+    expect(byId.transcriptSize?.status).toBe("warning");
+    expect(byId.guardrails?.status).toBe("critical");
+    expect(byId.secretsExposure?.summary).toContain(".env");
+    for (const id of ["serverStability", "transcriptSize", "guardrails", "secretsExposure"]) {
+      expect(byId[id]?.recommendation).toBeTruthy();
+    }
+  });
+
+  test("rejects unknown workspaces", async () => {
+    const { service } = createService({});
+    await expect(service.getHealth("ws-missing")).rejects.toThrow("Unknown workspace: ws-missing");
+  });
+
+  test("score never drops below zero", () => {
+    const checks = Array.from({ length: 5 }, () => ({
+      id: "guardrails" as const,
+      status: "critical" as const,
+      summary: "bad",
+      recommendation: "fix it",
+    }));
+    expect(summarizeWorkspaceHealth("ws-1", checks, new Date()).score).toBe(0);
+  });
+
+  test("flags secret files outside a git repository but skips templates", async () => {
+    for (const name of [".env", ".env.local", ".env.example", "server.pem", "README.md"]) {
+      await fs.writeFile(path.join(workspacePath, name), "x");
+    }
+
+    await expect(findExposedSecretFiles(workspacePath)).resolves.toEqual([
+      ".env",
+      ".env.local",
+      "server.pem",
+    ]);
+    await expect(findExposedSecretFiles(path.join(workspacePath, "missing"))).resolves.toBeNull();
+  });
+});
@@ -0,0 +1,129 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  launchProfileArgsError,
+  launchProfileEnvError,
+  launchProfilesError,
+  normalizeWorkspaceLaunchProfiles,
+} from "../src/app/workspaceLaunchProfiles";
+import { setWorkspaceLaunchProfilesInputSchema } from "../src/lib/desktopSchemas";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+const staging = {
+  name: "Staging",
+  args: ["--provider", "openai", "--model", "o3"],
+  env: { OPENAI_BASE_URL: "https://staging.example.com/v1" },
+};
+
+describe("workspace launch profiles", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-launch-profiles-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+    const workspacePath = path.join(root, "alpha");
+    await fs.mkdir(workspacePath, { recursive: true });
+    const workspace = {
+      id: "ws-alpha",
+      name: "alpha",
+      path: workspacePath,
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastOpenedAt: "2026-01-01T00:00:00.000Z",
+      launchProfiles: [
+        staging,
+        { name: "Sneaky", args: ["--yolo"], env: {} },
+        { name: " Staging ", args: [], env: {} },
+      ],
+    };
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({ version: 2, workspaces: [workspace], threads: [] }),
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("only allowlisted flags with values pass", () => {
+    expect(launchProfileArgsError(staging.args)).toBeNull();
+    expect(launchProfileArgsError(["--yolo"])).toBe("Launch profile flag is not allowed: --yolo");
+    expect(launchProfileArgsError(["--model"])).toBe("Missing value for --model");
+    expect(launchProfileArgsError(["--model", "--provider"])).toBe("Missing value for --model");
+  });
+
+  test("environment variables the app manages are rejected", () => {
+    expect(launchProfileEnvError(staging.env)).toBeNull();
+    expect(launchProfileEnvError({ COWORK_SERVER_AUTH_TOKEN: "x" })).toBe(
+      "Environment variable is managed by the app: COWORK_SERVER_AUTH_TOKEN",
+    );
+    expect(launchProfileEnvError({ agent_working_dir: "/tmp" })).toBe(
+      "Environment variable is managed by the app: agent_working_dir",
+    );
+    expect(launchProfileEnvError({ "BAD-NAME": "x" })).toBe(
+      "Invalid environment variable name: BAD-NAME",
+    );
+  });
+
+  test("profile names must be unique and errors name the profile", () => {
+    expect(launchProfilesError([staging, { ...staging, name: " Staging " }])).toBe(
+      "Duplicate launch profile: Staging",
+    );
+    expect(launchProfilesError([{ ...staging, args: ["--dir", "/"] }])).toBe(
+      "Staging: Launch profile flag is not allowed: --dir",
+    );
+    expect(
+      setWorkspaceLaunchProfilesInputSchema.safeParse({
+        workspaceId: "ws-alpha",
+        profiles: [{ ...staging, args: ["--port", "1"] }],
+      }).success,
+    ).toBe(false);
+  });
+
+  test("normalizing drops invalid and duplicate persisted profiles", () => {
+    expect(normalizeWorkspaceLaunchProfiles("nope")).toBeUndefined();
+    expect(normalizeWorkspaceLaunchProfiles([{ name: "Bad", args: [1], env: {} }])).toBeUndefined();
+    expect(
+      normalizeWorkspaceLaunchProfiles([{ name: " Quick ", args: ["--model", "o3"] }, staging]),
+    ).toEqual([{ name: "Quick", args: ["--model", "o3"], env: {} }, staging]);
+  });
+
+  test("loading keeps valid profiles and saving replaces or clears them", async () => {
+    const persistence = new PersistenceService();
+
+    const { workspaces } = await persistence.loadState();
+    expect(workspaces[0]?.launchProfiles).toEqual([staging]);
+
+    const quick = { name: " Quick ", args: ["--model", "o3"], env: {} };
+    const updated = await persistence.setWorkspaceLaunchProfiles("ws-alpha", [quick]);
+    expect(updated.launchProfiles).toEqual([{ ...quick, name: "Quick" }]);
+    await expect(
+      persistence.setWorkspaceLaunchProfiles("ws-alpha", [{ ...quick, args: ["--yolo"] }]),
+    ).rejects.toThrow("Quick: Launch profile flag is not allowed: --yolo");
+
+    const cleared = await persistence.setWorkspaceLaunchProfiles("ws-alpha", []);
+    expect(cleared.launchProfiles).toBeUndefined();
+    expect((await persistence.loadState()).workspaces[0]?.launchProfiles).toBeUndefined();
+  });
+});
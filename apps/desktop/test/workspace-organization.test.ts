@@ -0,0 +1,119 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+describe("workspace organization", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-workspace-organization-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+    const workspaces = await Promise.all(
+      ["alpha", "beta", "gamma"].map(async (name, index) => {
+        const workspacePath = path.join(root, name);
+        await fs.mkdir(workspacePath, { recursive: true });
+        return {
+          id: `ws-${name}`,
+          name,
+          path: workspacePath,
+          createdAt: "2026-01-01T00:00:00.000Z",
+          lastOpenedAt: "2026-01-01T00:00:00.000Z",
+          sortOrder: 10 - index,
+          ...(name === "beta" ? { tags: [" Client ", "client", "", "Internal"] } : {}),
+        };
+      }),
+    );
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({ version: 2, workspaces, threads: [] }),
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("loading rewrites sortOrder from list order and normalizes tags", async () => {
+    const persistence = new PersistenceService();
+
+    const { workspaces } = await persistence.loadState();
+
+    expect(workspaces.map((workspace) => [workspace.id, workspace.sortOrder])).toEqual([
+      ["ws-alpha", 0],
+      ["ws-beta", 1],
+      ["ws-gamma", 2],
+    ]);
+    expect(workspaces[1]?.tags).toEqual(["Client", "Internal"]);
+    expect(workspaces[0]?.tags).toBeUndefined();
+  });
+
+  test("pinning and tagging update only the target workspace", async () => {
+    const persistence = new PersistenceService();
+
+    expect(await persistence.setWorkspacePinned("ws-gamma", true)).toMatchObject({
+      id: "ws-gamma",
+      pinned: true,
+    });
+    expect((await persistence.setWorkspaceTags("ws-alpha", ["Docs", "docs "])).tags).toEqual([
+      "Docs",
+    ]);
+    expect((await persistence.setWorkspaceTags("ws-beta", [])).tags).toBeUndefined();
+
+    const { workspaces } = await persistence.loadState();
+    expect(workspaces.map((workspace) => workspace.pinned === true)).toEqual([false, false, true]);
+    expect(workspaces.map((workspace) => workspace.tags)).toEqual([["Docs"], undefined, undefined]);
+
+    const unpinned = await persistence.setWorkspacePinned("ws-gamma", false);
+    expect(unpinned.pinned).toBeUndefined();
+    await expect(persistence.setWorkspacePinned("ws-missing", true)).rejects.toThrow(
+      "Unknown workspace: ws-missing",
+    );
+  });
+
+  test("reordering requires every workspace exactly once", async () => {
+    const persistence = new PersistenceService();
+
+    const reordered = await persistence.reorderWorkspaces(["ws-gamma", "ws-alpha", "ws-beta"]);
+
+    expect(reordered.map((workspace) => [workspace.id, workspace.sortOrder])).toEqual([
+      ["ws-gamma", 0],
+      ["ws-alpha", 1],
+      ["ws-beta", 2],
+    ]);
+    expect((await persistence.loadState()).workspaces.map((workspace) => workspace.id)).toEqual([
+      "ws-gamma",
+      "ws-alpha",
+      "ws-beta",
+    ]);
+    await expect(persistence.reorderWorkspaces(["ws-alpha", "ws-beta"])).rejects.toThrow(
+      "Workspace order must list every workspace",
+    );
+    await expect(
+      persistence.reorderWorkspaces(["ws-alpha", "ws-alpha", "ws-beta"]),
+    ).rejects.toThrow("Workspace order lists a workspace more than once");
+    await expect(
+      persistence.reorderWorkspaces(["ws-alpha", "ws-beta", "ws-missing"]),
+    ).rejects.toThrow("Unknown workspace: ws-missing");
+  });
+});
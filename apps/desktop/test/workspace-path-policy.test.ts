@@ -0,0 +1,150 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import type { WorkspaceRecord } from "../src/app/types";
+import {
+  evaluatePathPolicy,
+  normalizeWorkspacePathPolicy,
+  pathPolicyEnvValue,
+  pathPolicyError,
+} from "../src/app/workspacePathPolicy";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+const workspace: Pick<WorkspaceRecord, "path" | "additionalRoots" | "pathPolicy"> = {
+  path: "/projects/app",
+  additionalRoots: ["/projects/api"],
+  pathPolicy: {
+    allow: [{ path: "/projects/app/secrets/public" }, { path: "/data/fixtures", external: true }],
+    deny: [{ path: "/projects/app/secrets" }],
+  },
+};
+
+describe("workspace path policy", () => {
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-path-policy-"));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+    const workspacePath = path.join(root, "alpha");
+    await fs.mkdir(workspacePath, { recursive: true });
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-alpha",
+            name: "alpha",
+            path: workspacePath,
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastOpenedAt: "2026-01-01T00:00:00.000Z",
+            pathPolicy: { allow: [{ path: "relative/dir" }], deny: [{ path: "/etc" }] },
+          },
+        ],
+        threads: [],
+      }),
+      "utf8",
+    );
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  test("entries must sit inside a root unless explicitly external", () => {
+    expect(pathPolicyError(workspace, workspace.pathPolicy ?? { allow: [], deny: [] })).toBeNull();
+    expect(pathPolicyError(workspace, { allow: [{ path: "/home/me/.ssh" }], deny: [] })).toBe(
+      "/home/me/.ssh is outside the workspace; mark it external to list it",
+    );
+    const externalInside = { path: "/projects/api/src", external: true };
+    expect(pathPolicyError(workspace, { allow: [], deny: [externalInside] })).toBe(
+      "/projects/api/src is inside the workspace, so it cannot be marked external",
+    );
+    expect(
+      pathPolicyError(workspace, {
+        allow: [{ path: "/projects/app/docs" }],
+        deny: [{ path: "/projects/app/docs/" }],
+      }),
+    ).toBe("Path is listed more than once: /projects/app/docs/");
+    expect(pathPolicyError(workspace, { allow: [{ path: "docs" }], deny: [] })).toBe(
+      "Path policy entries must be absolute: docs",
+    );
+  });
+
+  test("previews explain which entry or root decides", () => {
+    expect(evaluatePathPolicy(workspace, "/projects/app/secrets/prod.env")).toEqual({
+      path: "/projects/app/secrets/prod.env",
+      decision: "deny",
+      rule: { list: "deny", path: "/projects/app/secrets" },
+      reason: "Denied by the workspace path policy entry /projects/app/secrets.",
+    });
+    expect(evaluatePathPolicy(workspace, "/projects/app/secrets/public/a.md")).toMatchObject({
+      decision: "allow",
+      rule: { list: "allow", path: "/projects/app/secrets/public" },
+    });
+    expect(evaluatePathPolicy(workspace, "/data/fixtures/users.json").decision).toBe("allow");
+    expect(evaluatePathPolicy(workspace, "/projects/api/main.go")).toMatchObject({
+      decision: "allow",
+      rule: null,
+      reason: "Inside the workspace root /projects/api.",
+    });
+    expect(evaluatePathPolicy(workspace, "/etc/hosts")).toMatchObject({
+      decision: "deny",
+      rule: null,
+    });
+  });
+
+  test("the sidecar env drops allow entries that no longer match their external flag", () => {
+    expect(pathPolicyEnvValue({ path: "/projects/app" })).toBeNull();
+    expect(JSON.parse(pathPolicyEnvValue({ ...workspace, additionalRoots: [] }) ?? "")).toEqual({
+      allow: ["/projects/app/secrets/public", "/data/fixtures"],
+      deny: ["/projects/app/secrets"],
+    });
+    expect(
+      pathPolicyEnvValue({
+        ...workspace,
+        pathPolicy: { allow: [{ path: "/projects/old-root" }], deny: [] },
+      }),
+    ).toBeNull();
+  });
+
+  test("loading drops malformed entries and saving validates against the roots", async () => {
+    expect(normalizeWorkspacePathPolicy({ allow: "nope", deny: [] })).toBeUndefined();
+
+    const persistence = new PersistenceService();
+    const { workspaces } = await persistence.loadState();
+    const root = workspaces[0]?.path ?? "";
+    expect(workspaces[0]?.pathPolicy).toEqual({ allow: [], deny: [{ path: "/etc" }] });
+
+    const updated = await persistence.setWorkspacePathPolicy("ws-alpha", {
+      allow: [],
+      deny: [{ path: ` ${path.join(root, "vendor")} ` }],
+    });
+    expect(updated.pathPolicy).toEqual({ allow: [], deny: [{ path: path.join(root, "vendor") }] });
+    await expect(
+      persistence.setWorkspacePathPolicy("ws-alpha", { allow: [{ path: "/tmp" }], deny: [] }),
+    ).rejects.toThrow("/tmp is outside the workspace; mark it external to list it");
+
+    const cleared = await persistence.setWorkspacePathPolicy("ws-alpha", { allow: [], deny: [] });
+    expect(cleared.pathPolicy).toBeUndefined();
+  });
+});
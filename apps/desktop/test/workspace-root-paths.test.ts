@@ -0,0 +1,97 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+
+import {
+  containingWorkspaceRoot,
+  normalizeAdditionalRoots,
+  workspaceRootPaths,
+} from "../src/app/workspaceRootPaths";
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+describe("workspace root paths", () => {
+  const workspace = { path: "/work/app", additionalRoots: ["/work/lib", "/work/app/vendor"] };
+
+  test("lists the primary root first", () => {
+    expect(workspaceRootPaths(workspace)).toEqual(["/work/app", "/work/lib", "/work/app/vendor"]);
+    expect(workspaceRootPaths({ path: "/work/app" })).toEqual(["/work/app"]);
+  });
+
+  test("drops blanks, duplicates, and the primary path from extra roots", () => {
+    expect(
+      normalizeAdditionalRoots([" /work/lib ", "/work/app", "", "/work/lib", 7], "/work/app"),
+    ).toEqual(["/work/lib"]);
+    expect(normalizeAdditionalRoots(["/work/app"], "/work/app")).toBeUndefined();
+    expect(normalizeAdditionalRoots("not-an-array", "/work/app")).toBeUndefined();
+  });
+
+  test("finds the deepest root containing a path", () => {
+    expect(containingWorkspaceRoot(workspace, "/work/lib/src/index.ts")).toBe("/work/lib");
+    expect(containingWorkspaceRoot(workspace, "/work/app/vendor/pkg")).toBe("/work/app/vendor");
+    expect(containingWorkspaceRoot(workspace, "/work/app/src")).toBe("/work/app");
+    expect(containingWorkspaceRoot(workspace, "/work/library")).toBeNull();
+  });
+});
+
+describe("persisted additional workspace roots", () => {
+  let root = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    root = await fs.realpath(await fs.mkdtemp(path.join(os.tmpdir(), "cowork-multi-root-")));
+    userDataDir = path.join(root, "Cowork");
+    await fs.mkdir(userDataDir, { recursive: true });
+  });
+
+  afterEach(async () => {
+    if (root) {
+      await fs.rm(root, { recursive: true, force: true });
+    }
+    root = "";
+    userDataDir = "";
+  });
+
+  test("keeps existing extra roots and drops missing ones", async () => {
+    const projectPath = path.join(root, "project");
+    const libPath = path.join(root, "lib");
+    await fs.mkdir(projectPath);
+    await fs.mkdir(libPath);
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [
+          {
+            id: "ws-1",
+            name: "Project",
+            path: projectPath,
+            additionalRoots: [libPath, path.join(root, "missing"), projectPath],
+            createdAt: "2026-01-01T00:00:00.000Z",
+            lastOpenedAt: "2026-01-01T00:00:00.000Z",
+          },
+        ],
+        threads: [],
+      }),
+      "utf8",
+    );
+
+    const state = await new PersistenceService().loadState();
+
+    expect(state.workspaces[0]?.additionalRoots).toEqual([libPath]);
+  });
+});
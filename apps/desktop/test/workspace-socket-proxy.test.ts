@@ -0,0 +1,201 @@
+import { afterEach, describe, expect, test } from "bun:test";
+
+import { WebSocket, WebSocketServer } from "ws";
+
+import { WorkspaceSocketProxy } from "../electron/services/workspaceSocketProxy";
+import type { WorkspaceProxyConnectionEvent } from "../src/lib/desktopApi";
+
+type Upstream = {
+  url: string;
+  received: Array<Record<string, unknown>>;
+  close: () => Promise<void>;
+};
+
+/** With `authToken`, rejects handshakes without it, like a server started with an auth token. */
+async function startUpstream(authToken?: string): Promise<Upstream> {
+  const server = new WebSocketServer({
+    host: "127.0.0.1",
+    port: 0,
+    verifyClient: ({ req }) => !authToken || req.headers.authorization === `Bearer ${authToken}`,
+  });
+  await new Promise((resolve) => server.once("listening", resolve));
+  const received: Array<Record<string, unknown>> = [];
+  server.on("connection", (socket) => {
+    socket.on("message", (raw) => {
+      const message = JSON.parse(raw.toString()) as Record<string, unknown>;
+      received.push(message);
+      if (message.id !== undefined) {
+        socket.send(JSON.stringify({ id: message.id, result: { method: message.method } }));
+      }
+    });
+  });
+  const address = server.address();
+  if (!address || typeof address === "string") {
+    throw new Error("missing test server address");
+  }
+  return {
+    url: `ws://127.0.0.1:${address.port}/ws`,
+    received,
+    close: async () => {
+      for (const client of server.clients) {
+        client.terminate();
+      }
+      await new Promise((resolve) => server.close(resolve));
+    },
+  };
+}
+
+async function connect(url: string): Promise<{
+  socket: WebSocket;
+  messages: Array<Record<string, unknown>>;
+  nextMessage: () => Promise<Record<string, unknown>>;
+}> {
+  const socket = new WebSocket(url, "cowork.jsonrpc.v1");
+  const messages: Array<Record<string, unknown>> = [];
+  const waiters: Array<(message: Record<string, unknown>) => void> = [];
+  socket.on("message", (raw) => {
+    const message = JSON.parse(raw.toString()) as Record<string, unknown>;
+    messages.push(message);
+    waiters.shift()?.(message);
+  });
+  await new Promise((resolve, reject) => {
+    socket.once("open", resolve);
+    socket.once("error", reject);
+  });
+  return {
+    socket,
+    messages,
+    nextMessage: () => new Promise((resolve) => waiters.push(resolve)),
+  };
+}
+
+async function waitFor(check: () => boolean, timeoutMs = 2_000): Promise<void> {
+  const deadline = Date.now() + timeoutMs;
+  while (!check()) {
+    if (Date.now() > deadline) {
+      throw new Error("timed out waiting for condition");
+    }
+    await new Promise((resolve) => setTimeout(resolve, 10));
+  }
+}
+
+describe("WorkspaceSocketProxy", () => {
+  const cleanups: Array<() => Promise<void> | void> = [];
+
+  afterEach(async () => {
+    for (const cleanup of cleanups.splice(0).reverse()) {
+      await cleanup();
+    }
+  });
+
+  test("rejects connections without the proxy token", async () => {
+    const proxy = new WorkspaceSocketProxy({
+      resolveUpstream: () => null,
+      createToken: () => "secret",
+    });
+    cleanups.push(() => proxy.dispose());
+    const url = await proxy.getUrl("ws-1");
+    expect(url).toMatch(/^ws:\/\/127\.0\.0\.1:\d+\/workspaces\/ws-1\/ws\?token=secret$/);
+
+    await expect(connect(url.replace("secret", "guess"))).rejects.toThrow();
+  });
+
+  test("buffers through a sidecar restart and replays the client handshake", async () => {
+    let upstream = await startUpstream();
+    cleanups.push(() => upstream.close());
+    const events: WorkspaceProxyConnectionEvent[] = [];
+    const proxy = new WorkspaceSocketProxy({
+      resolveUpstream: () => ({ url: upstream.url, authToken: null }),
+      onConnectionStateChanged: (event) => events.push(event),
+      retryDelayMs: 20,
+    });
+    cleanups.push(() => proxy.dispose());
+    const client = await connect(await proxy.getUrl("ws-1"));
+    cleanups.push(() => client.socket.terminate());
+
+    client.socket.send(JSON.stringify({ id: 1, method: "initialize", params: {} }));
+    expect(await client.nextMessage()).toEqual({ id: 1, result: { method: "initialize" } });
+    client.socket.send(JSON.stringify({ method: "initialized" }));
+
+    await upstream.close();
+    await waitFor(() => events.at(-1)?.state === "reconnecting");
+    client.socket.send(JSON.stringify({ id: 2, method: "thread/list" }));
+    upstream = await startUpstream();
+
+    expect(await client.nextMessage()).toEqual({ id: 2, result: { method: "thread/list" } });
+    expect(upstream.received.map((message) => message.method)).toEqual([
+      "initialize",
+      "initialized",
+      "thread/list",
+    ]);
+    expect(String(upstream.received[0]?.id)).toStartWith("cowork-proxy-initialize-");
+    expect(client.messages).toHaveLength(2);
+    expect(events.map((event) => event.state)).toEqual(["connected", "reconnecting", "connected"]);
+  });
+
+  test("authenticates to a sidecar that requires its auth token", async () => {
+    const upstream = await startUpstream("sidecar-token");
+    cleanups.push(() => upstream.close());
+    let authToken: string | null = null;
+    const events: WorkspaceProxyConnectionEvent[] = [];
+    const proxy = new WorkspaceSocketProxy({
+      resolveUpstream: () => ({ url: upstream.url, authToken }),
+      onConnectionStateChanged: (event) => events.push(event),
+      reconnectWindowMs: 100,
+      retryDelayMs: 20,
+    });
+    cleanups.push(() => proxy.dispose());
+
+    const rejected = await connect(await proxy.getUrl("ws-1"));
+    const rejectedClose = new Promise<number>((resolve) => rejected.socket.once("close", resolve));
+    expect(await rejectedClose).toBe(1011);
+
+    authToken = "sidecar-token";
+    const client = await connect(await proxy.getUrl("ws-1"));
+    cleanups.push(() => client.socket.terminate());
+    client.socket.send(JSON.stringify({ id: 1, method: "initialize", params: {} }));
+
+    expect(await client.nextMessage()).toEqual({ id: 1, result: { method: "initialize" } });
+    expect(events.at(-1)).toMatchObject({ state: "connected", clientCount: 1 });
+  });
+
+  test("disconnects clients whose sidecar does not come back", async () => {
+    const upstream = await startUpstream();
+    let available = true;
+    const events: WorkspaceProxyConnectionEvent[] = [];
+    const proxy = new WorkspaceSocketProxy({
+      resolveUpstream: () => (available ? { url: upstream.url, authToken: null } : null),
+      onConnectionStateChanged: (event) => events.push(event),
+      reconnectWindowMs: 100,
+      retryDelayMs: 20,
+    });
+    cleanups.push(() => proxy.dispose());
+    const client = await connect(await proxy.getUrl("ws-1"));
+    const closed = new Promise<number>((resolve) => client.socket.once("close", resolve));
+    await waitFor(() => events.at(-1)?.state === "connected");
+
+    available = false;
+    await upstream.close();
+
+    expect(await closed).toBe(1011);
+    expect(events.at(-1)).toMatchObject({ state: "disconnected", clientCount: 0 });
+  });
+
+  test("closeWorkspace disconnects only that workspace's clients", async () => {
+    const upstream = await startUpstream();
+    cleanups.push(() => upstream.close());
+    const proxy = new WorkspaceSocketProxy({
+      resolveUpstream: () => ({ url: upstream.url, authToken: null }),
+    });
+    cleanups.push(() => proxy.dispose());
+    const first = await connect(await proxy.getUrl("ws-1"));
+    const second = await connect(await proxy.getUrl("ws-2"));
+    cleanups.push(() => second.socket.terminate());
+    const closed = new Promise<number>((resolve) => first.socket.once("close", resolve));
+
+    proxy.closeWorkspace("ws-1");
+
+    expect(await closed).toBe(1001);
+    expect(second.socket.readyState).toBe(WebSocket.OPEN);
+  });
+});
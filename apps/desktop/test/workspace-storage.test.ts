@@ -0,0 +1,164 @@
+import { afterEach, beforeEach, describe, expect, mock, test } from "bun:test";
+import fs from "node:fs/promises";
+import os from "node:os";
+import path from "node:path";
+import zlib from "node:zlib";
+
+import { createElectronMock, setElectronMockOverrides } from "./helpers/mockElectron";
+
+let userDataDir = "";
+
+const electronMockOverrides = {
+  app: {
+    getPath: (name: string) => (name === "appData" ? path.dirname(userDataDir) : userDataDir),
+  },
+};
+
+setElectronMockOverrides(electronMockOverrides);
+
+mock.module("electron", () => createElectronMock());
+
+const { PersistenceService } = await import("../electron/services/persistence");
+
+function transcriptLine(threadId: string, text: string): string {
+  return `${JSON.stringify({
+    ts: "2026-01-01T00:00:00.000Z",
+    threadId,
+    direction: "client",
+    payload: { type: "user_message", text },
+  })}\n`;
+}
+
+function thread(id: string, workspaceId: string, lastMessageAt: string, archived = false) {
+  return {
+    id,
+    workspaceId,
+    title: id,
+    createdAt: "2026-01-01T00:00:00.000Z",
+    lastMessageAt,
+    archived,
+  };
+}
+
+describe("workspace storage", () => {
+  let transcriptsDir = "";
+  let archiveDir = "";
+
+  beforeEach(async () => {
+    setElectronMockOverrides(electronMockOverrides);
+    const root = await fs.mkdtemp(path.join(os.tmpdir(), "cowork-workspace-storage-"));
+    userDataDir = path.join(root, "Cowork");
+    transcriptsDir = path.join(userDataDir, "transcripts");
+    archiveDir = path.join(userDataDir, "transcripts-archive");
+    await fs.mkdir(transcriptsDir, { recursive: true });
+    await fs.mkdir(archiveDir, { recursive: true });
+    const workspace = (id: string) => ({
+      id,
+      name: id,
+      path: path.join(root, id),
+      createdAt: "2026-01-01T00:00:00.000Z",
+      lastOpenedAt: "2026-01-01T00:00:00.000Z",
+    });
+    await fs.mkdir(path.join(root, "ws-1"));
+    await fs.mkdir(path.join(root, "ws-2"));
+    await fs.writeFile(
+      path.join(userDataDir, "state.json"),
+      JSON.stringify({
+        version: 2,
+        workspaces: [workspace("ws-1"), workspace("ws-2")],
+        threads: [
+          thread("idle", "ws-1", "2026-01-01T00:00:00.000Z"),
+          thread("archived", "ws-1", "2026-01-01T00:00:00.000Z", true),
+          thread("recent", "ws-1", "2026-02-01T00:00:00.000Z"),
+          thread("other", "ws-2", "2026-01-01T00:00:00.000Z"),
+        ],
+      }),
+      "utf8",
+    );
+    await fs.writeFile(path.join(transcriptsDir, "idle.jsonl"), transcriptLine("idle", "a"));
+    await fs.writeFile(
+      path.join(archiveDir, "archived.jsonl.zst"),
+      zlib.zstdCompressSync(Buffer.from(transcriptLine("archived", "b"))),
+    );
+    await fs.writeFile(path.join(transcriptsDir, "recent.jsonl"), transcriptLine("recent", "c"));
+    await fs.writeFile(path.join(transcriptsDir, "recent.corrupt"), "not json\n");
+    await fs.writeFile(path.join(transcriptsDir, "other.jsonl"), transcriptLine("other", "d"));
+  });
+
+  afterEach(async () => {
+    if (userDataDir) {
+      await fs.rm(path.dirname(userDataDir), { recursive: true, force: true });
+    }
+    userDataDir = "";
+  });
+
+  async function size(filePath: string): Promise<number> {
+    return (await fs.stat(filePath)).size;
+  }
+
+  test("reports the bytes that belong to the workspace's threads", async () => {
+    const persistence = new PersistenceService();
+    const live =
+      (await size(path.join(transcriptsDir, "idle.jsonl"))) +
+      (await size(path.join(transcriptsDir, "recent.jsonl")));
+    const archived = await size(path.join(archiveDir, "archived.jsonl.zst"));
+
+    expect(await persistence.getWorkspaceStorageReport("ws-1")).toEqual({
+      workspaceId: "ws-1",
+      threadCount: 3,
+      transcriptBytes: live,
+      archiveBytes: archived,
+      quarantineBytes: 9,
+      totalBytes: live + archived + 9,
+    });
+    await expect(persistence.getWorkspaceStorageReport("ws-3")).rejects.toThrow(
+      "Unknown workspace: ws-3",
+    );
+  });
+
+  test("plans each cleanup target without changing anything", async () => {
+    const persistence = new PersistenceService({
+      now: () => new Date("2026-02-01T12:00:00.000Z"),
+    });
+
+    const actions = await persistence.planWorkspaceStorageCleanup("ws-1", [
+      "compactTranscripts",
+      "purgeArchivedThreads",
+      "clearQuarantine",
+    ]);
+
+    expect(actions).toEqual([
+      {
+        target: "compactTranscripts",
+        threadIds: ["idle"],
+        bytes: await size(path.join(transcriptsDir, "idle.jsonl")),
+      },
+      {
+        target: "purgeArchivedThreads",
+        threadIds: ["archived"],
+        bytes: await size(path.join(archiveDir, "archived.jsonl.zst")),
+      },
+      { target: "clearQuarantine", threadIds: ["recent"], bytes: 9 },
+    ]);
+    expect((await fs.readdir(transcriptsDir)).sort()).toEqual([
+      "idle.jsonl",
+      "other.jsonl",
+      "recent.corrupt",
+      "recent.jsonl",
+    ]);
+  });
+
+  test("compacts idle transcripts and clears quarantine, keeping history readable", async () => {
+    const persistence = new PersistenceService();
+
+    await persistence.compactTranscripts(["idle"]);
+    await persistence.clearTranscriptQuarantine(["recent"]);
+
+    expect((await fs.readdir(transcriptsDir)).sort()).toEqual(["other.jsonl", "recent.jsonl"]);
+    expect(await fs.readdir(archiveDir)).toContain("idle.jsonl.zst");
+    const { threads } = await persistence.loadState();
+    expect(threads.find((entry) => entry.id === "idle")?.archived).toBe(false);
+    const events = await persistence.readTranscript("idle");
+    expect(events.map((event) => (event.payload as { text: string }).text)).toEqual(["a"]);
+  });
+});
@@ -0,0 +1,79 @@
+import { describe, expect, test } from "bun:test";
+
+import { normalizeAppSettings } from "../electron/services/appSettings";
+import { YoloGrants } from "../electron/services/yoloGrants";
+
+describe("yolo grants", () => {
+  const createGrants = (options: { now?: () => number; durationMs?: number } = {}) => {
+    const expired: string[] = [];
+    let nextToken = 0;
+    const grants = new YoloGrants({
+      getDurationMs: async () => options.durationMs ?? 60 * 60_000,
+      onExpire: (workspaceId) => expired.push(workspaceId),
+      now: options.now,
+      createToken: () => `token-${++nextToken}`,
+    });
+    return { grants, expired };
+  };
+
+  test("a redeemed token keeps yolo on for the configured duration", async () => {
+    let now = Date.parse("2026-06-01T00:00:00.000Z");
+    const { grants } = createGrants({ now: () => now });
+
+    const grant = grants.issue("ws-1", 7);
+    expect(grant).toEqual({
+      workspaceId: "ws-1",
+      token: "token-1",
+      expiresAt: "2026-06-01T00:02:00.000Z",
+    });
+    expect(grants.activeUntil("ws-1")).toBeNull();
+
+    const expiresAt = await grants.redeem("ws-1", 7, grant.token);
+    expect(expiresAt.toISOString()).toBe("2026-06-01T01:00:00.000Z");
+    expect(grants.activeUntil("ws-1")).toEqual(expiresAt);
+
+    now += 60 * 60_000;
+    expect(grants.activeUntil("ws-1")).toBeNull();
+  });
+
+  test("tokens are single-use and bound to the workspace and window", async () => {
+    let now = 0;
+    const { grants } = createGrants({ now: () => now });
+
+    const other = grants.issue("ws-1", 7);
+    await expect(grants.redeem("ws-2", 7, other.token)).rejects.toThrow(
+      "Invalid yolo confirmation token.",
+    );
+    await expect(grants.redeem("ws-1", 7, other.token)).rejects.toThrow(
+      "Invalid yolo confirmation token.",
+    );
+    await expect(grants.redeem("ws-1", 8, grants.issue("ws-1", 7).token)).rejects.toThrow(
+      "Invalid yolo confirmation token.",
+    );
+
+    const stale = grants.issue("ws-1", 7);
+    now += 2 * 60_000;
+    await expect(grants.redeem("ws-1", 7, stale.token)).rejects.toThrow(
+      "Yolo confirmation expired; request a new one.",
+    );
+    expect(grants.activeUntil("ws-1")).toBeNull();
+  });
+
+  test("expiry reports the workspace, and revoking cancels it", async () => {
+    const { grants, expired } = createGrants({ durationMs: 5 });
+
+    await grants.redeem("ws-1", 7, grants.issue("ws-1", 7).token);
+    await grants.redeem("ws-2", 7, grants.issue("ws-2", 7).token);
+    grants.revoke("ws-2");
+    await Bun.sleep(30);
+
+    expect(expired).toEqual(["ws-1"]);
+    expect(grants.activeUntil("ws-1")).toBeNull();
+  });
+
+  test("the grant duration setting is clamped to between a minute and a day", () => {
+    expect(normalizeAppSettings({}).yoloGrantMinutes).toBe(60);
+    expect(normalizeAppSettings({ yoloGrantMinutes: 0 }).yoloGrantMinutes).toBe(1);
+    expect(normalizeAppSettings({ yoloGrantMinutes: 10_000 }).yoloGrantMinutes).toBe(1440);
+  });
+});
@@ -6,6 +6,8 @@ import {
   buildSidecarManifest,
   FOUNDATION_MODELS_KOFFI_TRIPLET,
   FOUNDATION_MODELS_SDK_DIR_NAME,
+  hashFileSha256,
+  listSidecarLaunchFiles,
   resolvePackagedSidecarFilename,
   resolveWindowsAiElectronPrebuildTriplet,
   SIDECAR_BUN_ENTRYPOINT_PATH,
@@ -34,7 +36,7 @@ import {
 } from "./releaseBuildUtils";
 import { tryDownloadPrebuiltHelpers } from "./winSandboxPrebuilt";
 
-const CACHE_VERSION = 11;
+const CACHE_VERSION = 12;
 
 type DesktopResourcesCache = {
   version: number;
@@ -704,7 +706,15 @@ async function main() {
       console.log(`[resources] sidecar: rebuilt ${path.relative(root, sidecarOutfile)}`);
     }
 
-    await fs.writeFile(sidecarManifestPath, `${JSON.stringify(manifest, null, 2)}\n`);
+    // The app refuses to spawn launch files that do not match these digests.
+    const sha256: Record<string, string> = {};
+    for (const launchFile of listSidecarLaunchFiles(manifest)) {
+      sha256[launchFile] = await hashFileSha256(path.join(desktopBinariesDir, launchFile));
+    }
+    await fs.writeFile(
+      sidecarManifestPath,
+      `${JSON.stringify({ ...manifest, sha256 }, null, 2)}\n`,
+    );
   } else {
     console.log("[resources] sidecar: cached");
   }
@@ -435,6 +435,7 @@ export function createRunTurn(overrides: RunTurnOverrides = {}) {
       projectRoot: path.dirname(config.projectCoworkDir),
       outputDirectory: config.outputDirectory,
       uploadsDirectory: config.uploadsDirectory,
+      additionalWorkingDirectories: config.additionalWorkingDirectories,
       toolRuntimeWritableRoots: [...resolveAdvancedMemoryWriteRoots(config)],
       targetPaths: params.agentTargetPaths,
       yolo: params.yolo,
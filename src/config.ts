@@ -141,6 +141,36 @@ function resolveSandboxConfig(envMode: string | undefined, raw: unknown): Sandbo
   };
 }
 
+/**
+ * Parse the desktop's per-workspace path policy from `AGENT_PATH_POLICY`, a JSON
+ * `{ allow, deny }` of absolute paths. Relative or malformed entries are dropped;
+ * undefined when nothing usable remains.
+ */
+function parsePathPolicyEnv(raw: string | undefined): AgentConfig["pathPolicy"] {
+  if (!raw?.trim()) return undefined;
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(raw);
+  } catch {
+    return undefined;
+  }
+  const obj = isPlainObject(parsed) ? parsed : {};
+  const entries = (value: unknown) =>
+    Array.isArray(value)
+      ? [
+          ...new Set(
+            value
+              .filter((entry): entry is string => typeof entry === "string")
+              .filter((entry) => path.isAbsolute(entry))
+              .map((entry) => path.resolve(entry)),
+          ),
+        ]
+      : [];
+  const allow = entries(obj.allow);
+  const deny = entries(obj.deny);
+  return allow.length > 0 || deny.length > 0 ? { allow, deny } : undefined;
+}
+
 function deepMerge<T extends Record<string, unknown>>(base: T, override: T): T {
   const out: Record<string, unknown> = { ...base };
   for (const [k, v] of Object.entries(override)) {
@@ -504,6 +534,16 @@ export async function loadConfig(options: LoadConfigOptions = {}): Promise<Agent
   const runtime = normalizeRuntimeNameForProvider(provider, rawRuntime);
 
   const workingDirectory = env.AGENT_WORKING_DIR || cwd;
+  const additionalWorkingDirectories = [
+    ...new Set(
+      (env.AGENT_ADDITIONAL_WORKING_DIRS ?? "")
+        .split(path.delimiter)
+        .filter((entry) => entry.trim())
+        .map((entry) => path.resolve(entry)),
+    ),
+  ].filter((entry) => entry !== path.resolve(workingDirectory));
+
+  const pathPolicy = parsePathPolicyEnv(env.AGENT_PATH_POLICY);
 
   const sandbox = resolveSandboxConfig(
     env.AGENT_SANDBOX,
@@ -863,6 +903,8 @@ export async function loadConfig(options: LoadConfigOptions = {}): Promise<Agent
         }
       : {}),
     workingDirectory,
+    ...(additionalWorkingDirectories.length > 0 ? { additionalWorkingDirectories } : {}),
+    ...(pathPolicy ? { pathPolicy } : {}),
     outputDirectory,
     uploadsDirectory,
     userName,
@@ -19,13 +19,95 @@ function looksLikeJsonBody(value: string): boolean {
   );
 }
 
+const SECRET_REPLACEMENTS: Array<[RegExp, (match: string, prefix: string) => string]> = [
+  [PRIVATE_KEY_PATTERN, () => "[redacted-secret]"],
+  [BEARER_PATTERN, (_match, scheme) => `${scheme} [redacted]`],
+  [ASSIGNMENT_SECRET_PATTERN, (_match, name) => `${name}=[redacted]`],
+  [CREDENTIAL_URL_PATTERN, (_match, prefix) => `${prefix}[redacted]@`],
+  [COMMON_SECRET_VALUE_PATTERN, () => "[redacted-secret]"],
+];
+
+/**
+ * Replaces credentials (keys, tokens, passwords, private keys) but leaves the
+ * surrounding prose readable, unlike `redactSensitiveText`.
+ */
+export function redactSecrets(value: string): { text: string; redactionCount: number } {
+  let redactionCount = 0;
+  let text = value;
+  for (const [pattern, replace] of SECRET_REPLACEMENTS) {
+    text = text.replace(pattern, (match: string, prefix: unknown) => {
+      redactionCount += 1;
+      return replace(match, typeof prefix === "string" ? prefix : "");
+    });
+  }
+  return { text, redactionCount };
+}
+
+export type SecretSpanKind = "private_key" | "bearer" | "assignment" | "credential_url" | "token";
+
+export type SecretSpan = { start: number; end: number; kind: SecretSpanKind };
+
+/** The secret inside `match`, without the name, scheme, or quotes around it. */
+function secretSpanOf(kind: SecretSpanKind, match: RegExpMatchArray): SecretSpan {
+  const index = match.index ?? 0;
+  const whole = match[0];
+  const prefix = match[1] ?? "";
+  let start = 0;
+  let end = whole.length;
+  if (kind === "bearer") {
+    start = whole.length - whole.slice(prefix.length).trimStart().length;
+  } else if (kind === "assignment") {
+    start = prefix.length + (whole.slice(prefix.length).match(/^\s*[:=]\s*/)?.[0].length ?? 0);
+    const quote = whole.charAt(start);
+    if ((quote === '"' || quote === "'") && whole.endsWith(quote) && end - start >= 2) {
+      start += 1;
+      end -= 1;
+    }
+  } else if (kind === "credential_url") {
+    start = prefix.length;
+    end -= 1;
+  }
+  return { start: index + start, end: index + end, kind };
+}
+
+const SECRET_SPAN_PATTERNS: Array<[SecretSpanKind, RegExp]> = [
+  ["private_key", PRIVATE_KEY_PATTERN],
+  ["bearer", BEARER_PATTERN],
+  ["assignment", ASSIGNMENT_SECRET_PATTERN],
+  ["credential_url", CREDENTIAL_URL_PATTERN],
+  ["token", COMMON_SECRET_VALUE_PATTERN],
+];
+
+/**
+ * The credentials `redactSecrets` would replace, as non-overlapping spans in
+ * order, so callers can substitute their own placeholder.
+ */
+export function findSecretSpans(value: string): SecretSpan[] {
+  const spans: SecretSpan[] = [];
+  for (const [kind, pattern] of SECRET_SPAN_PATTERNS) {
+    for (const match of value.matchAll(pattern)) {
+      const span = secretSpanOf(kind, match);
+      if (span.end > span.start) {
+        spans.push(span);
+      }
+    }
+  }
+  spans.sort((left, right) => left.start - right.start || right.end - left.end);
+  const merged: SecretSpan[] = [];
+  for (const span of spans) {
+    const previous = merged.at(-1);
+    if (previous && span.start < previous.end) {
+      previous.end = Math.max(previous.end, span.end);
+      continue;
+    }
+    merged.push({ ...span });
+  }
+  return merged;
+}
+
 export function redactSensitiveText(value: string): string {
-  const redacted = value
-    .replace(PRIVATE_KEY_PATTERN, "[redacted-secret]")
-    .replace(BEARER_PATTERN, "$1 [redacted]")
-    .replace(ASSIGNMENT_SECRET_PATTERN, "$1=[redacted]")
-    .replace(CREDENTIAL_URL_PATTERN, "$1[redacted]@")
-    .replace(COMMON_SECRET_VALUE_PATTERN, "[redacted-secret]")
+  const { text } = redactSecrets(value);
+  const redacted = text
     .replace(EMAIL_PATTERN, "[redacted-email]")
     .replace(BODY_ASSIGNMENT_PATTERN, "[redacted-body]");
 
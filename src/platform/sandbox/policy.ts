@@ -162,6 +162,8 @@ export interface ResolveSandboxPolicyInput {
   outputDirectory?: string;
   /** Uploads directory; writable for parity with the built-in file tools. */
   uploadsDirectory?: string;
+  /** Other roots of a multi-root workspace; writable like the working directory. */
+  additionalWorkingDirectories?: readonly string[];
   /** Cowork-managed runtime/cache roots that tool commands may mutate. */
   toolRuntimeWritableRoots?: readonly string[];
   /** Child-agent enforced scope; when present these become the only writable roots. */
@@ -257,6 +259,9 @@ function deriveWritableRootInfo(input: ResolveSandboxPolicyInput): {
   // bash can write the same locations as write/edit and maintain runtime deps
   // without forcing a full-access escalation.
   const candidates = [base];
+  for (const root of input.additionalWorkingDirectories ?? []) {
+    candidates.push(path.resolve(root));
+  }
   if (input.projectRoot) candidates.push(path.resolve(input.projectRoot));
   if (input.outputDirectory) candidates.push(path.resolve(base, input.outputDirectory));
   if (input.uploadsDirectory) candidates.push(path.resolve(base, input.uploadsDirectory));
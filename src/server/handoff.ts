@@ -0,0 +1,199 @@
+import { createHmac, timingSafeEqual } from "node:crypto";
+
+import type { JsonRpcLiteClientMessage } from "./jsonrpc/protocol";
+
+export type HandoffAccess = "read" | "readWrite";
+
+export type HandoffClaims = {
+  tokenId: string;
+  threadId: string;
+  access: HandoffAccess;
+  /** Epoch milliseconds after which the token no longer attaches. */
+  expiresAt: number;
+};
+
+/** Query parameter that carries the token on a handoff `/ws` URL. */
+export const HANDOFF_QUERY_PARAM = "handoff";
+
+const HANDOFF_SESSION_METHODS = new Set(["initialize", "initialized"]);
+const HANDOFF_READ_METHODS = new Set([
+  "thread/read",
+  "thread/resume",
+  "thread/hydrate",
+  "thread/unsubscribe",
+]);
+const HANDOFF_WRITE_METHODS = new Set(["turn/start", "turn/steer", "turn/interrupt"]);
+
+function signPayload(payload: string, signingKey: string): string {
+  return createHmac("sha256", signingKey).update(payload).digest("base64url");
+}
+
+function isHandoffClaims(value: unknown): value is HandoffClaims {
+  if (!value || typeof value !== "object") {
+    return false;
+  }
+  const claims = value as Record<string, unknown>;
+  return (
+    typeof claims.tokenId === "string" &&
+    claims.tokenId.length > 0 &&
+    typeof claims.threadId === "string" &&
+    claims.threadId.length > 0 &&
+    (claims.access === "read" || claims.access === "readWrite") &&
+    typeof claims.expiresAt === "number" &&
+    Number.isFinite(claims.expiresAt)
+  );
+}
+
+/** `<claims>.<signature>`, both base64url, signed with HMAC-SHA256. */
+export function signHandoffToken(claims: HandoffClaims, signingKey: string): string {
+  const payload = Buffer.from(
+    JSON.stringify({
+      tokenId: claims.tokenId,
+      threadId: claims.threadId,
+      access: claims.access,
+      expiresAt: claims.expiresAt,
+    }),
+  ).toString("base64url");
+  return `${payload}.${signPayload(payload, signingKey)}`;
+}
+
+/** The claims of a well-signed, unexpired token, or null. */
+export function verifyHandoffToken(
+  token: string,
+  signingKey: string,
+  now = Date.now(),
+): HandoffClaims | null {
+  const [payload, signature, ...rest] = token.split(".");
+  if (!payload || !signature || rest.length > 0) {
+    return null;
+  }
+  const expected = Buffer.from(signPayload(payload, signingKey));
+  const presented = Buffer.from(signature);
+  if (expected.length !== presented.length || !timingSafeEqual(expected, presented)) {
+    return null;
+  }
+  let claims: unknown;
+  try {
+    claims = JSON.parse(Buffer.from(payload, "base64url").toString("utf8"));
+  } catch {
+    return null;
+  }
+  if (!isHandoffClaims(claims) || claims.expiresAt <= now) {
+    return null;
+  }
+  return claims;
+}
+
+type HandoffRegistryOptions = {
+  signingKey: string;
+  /** Called once a grant is revoked or expires, so its connections can be closed. */
+  onRemoved?: (tokenId: string) => void;
+  now?: () => number;
+};
+
+/**
+ * Handoff tokens the desktop app has registered with this server. A token
+ * attaches only while it is signed with the server's key, unexpired, and
+ * still registered, so revoking it takes effect even before it expires.
+ */
+export class HandoffRegistry {
+  private readonly grants = new Map<
+    string,
+    { claims: HandoffClaims; timer: ReturnType<typeof setTimeout> }
+  >();
+  private readonly now: () => number;
+
+  constructor(private readonly options: HandoffRegistryOptions) {
+    this.now = options.now ?? Date.now;
+  }
+
+  get size(): number {
+    return this.grants.size;
+  }
+
+  /** Registers a token minted by the desktop app; returns its claims, or null if invalid. */
+  register(token: string): HandoffClaims | null {
+    const claims = verifyHandoffToken(token, this.options.signingKey, this.now());
+    if (!claims) {
+      return null;
+    }
+    this.remove(claims.tokenId, false);
+    const timer = setTimeout(
+      () => this.remove(claims.tokenId, true),
+      Math.max(0, claims.expiresAt - this.now()),
+    );
+    timer.unref?.();
+    this.grants.set(claims.tokenId, { claims, timer });
+    return claims;
+  }
+
+  revoke(tokenId: string): boolean {
+    return this.remove(tokenId, true);
+  }
+
+  revokeAll(): void {
+    for (const tokenId of [...this.grants.keys()]) {
+      this.remove(tokenId, true);
+    }
+  }
+
+  /** Claims for a presented token that may attach right now, or null. */
+  authorize(token: string): HandoffClaims | null {
+    const claims = verifyHandoffToken(token, this.options.signingKey, this.now());
+    const registered = claims ? this.grants.get(claims.tokenId)?.claims : undefined;
+    if (
+      !claims ||
+      !registered ||
+      registered.threadId !== claims.threadId ||
+      registered.access !== claims.access ||
+      registered.expiresAt !== claims.expiresAt
+    ) {
+      return null;
+    }
+    return registered;
+  }
+
+  private remove(tokenId: string, notify: boolean): boolean {
+    const grant = this.grants.get(tokenId);
+    if (!grant) {
+      return false;
+    }
+    clearTimeout(grant.timer);
+    this.grants.delete(tokenId);
+    if (notify) {
+      this.options.onRemoved?.(tokenId);
+    }
+    return true;
+  }
+}
+
+/**
+ * Why a connection attached through a handoff link may not send `message`,
+ * or null when it may. Such a connection can only read the granted thread,
+ * and with read-write access also run turns and answer prompts in it.
+ */
+export function handoffMessageDenial(
+  grant: Pick<HandoffClaims, "threadId" | "access">,
+  message: JsonRpcLiteClientMessage,
+): string | null {
+  if (!("method" in message)) {
+    return grant.access === "readWrite" ? null : "This handoff link is read-only.";
+  }
+  if (HANDOFF_SESSION_METHODS.has(message.method)) {
+    return null;
+  }
+  const isWrite = HANDOFF_WRITE_METHODS.has(message.method);
+  if (!isWrite && !HANDOFF_READ_METHODS.has(message.method)) {
+    return `${message.method} is not available over a handoff link.`;
+  }
+  if (isWrite && grant.access !== "readWrite") {
+    return "This handoff link is read-only.";
+  }
+  const params = message.params;
+  const threadId =
+    params && typeof params === "object" ? (params as Record<string, unknown>).threadId : undefined;
+  if (threadId !== grant.threadId) {
+    return "This handoff link is for a different thread.";
+  }
+  return null;
+}
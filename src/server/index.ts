@@ -12,7 +12,11 @@ import {
   initCrashReporting,
 } from "../telemetry/crashReporting";
 import { initProductAnalytics, shutdownProductAnalytics } from "../telemetry/productAnalytics";
+import { isProviderName } from "../types";
 import { VERSION } from "../version";
+import { resolveListeningHints } from "./listeningHints";
+
+export { resolveListeningHintsFromInterfaces } from "./listeningHints";
 
 // Keep server output clean by default.
 const globalSettings = globalThis as typeof globalThis & { AI_SDK_LOG_WARNINGS?: boolean };
@@ -40,13 +44,27 @@ function registerCrashReportingProcessHandlers(): void {
   });
 }
 
+/**
+ * Desktop sidecars marked adoptable keep running after the app instance that
+ * spawned them exits, so a closed stdout/stderr pipe must not crash them.
+ */
+function ignoreClosedOutputPipes(): void {
+  for (const stream of [process.stdout, process.stderr]) {
+    stream.on("error", (error: NodeJS.ErrnoException) => {
+      if (error.code !== "EPIPE") {
+        throw error;
+      }
+    });
+  }
+}
+
 function printUsage() {
   console.log(
-    "Usage: bun src/server/index.ts [--dir <directory_path>] [--host <hostname>] [--port <port>] [--yolo] [--json]",
+    "Usage: bun src/server/index.ts [--dir <directory_path>] [--root <directory_path>]... [--host <hostname>] [--port <port>] [--provider <name>] [--model <id>] [--yolo] [--json]",
   );
 }
 
-async function resolveAndValidateDir(dirArg: string): Promise<string> {
+async function resolveAndValidateDir(dirArg: string, flag = "--dir"): Promise<string> {
   const resolved = path.resolve(dirArg);
   let st: { isDirectory: () => boolean } | null = null;
   try {
@@ -54,14 +72,17 @@ async function resolveAndValidateDir(dirArg: string): Promise<string> {
   } catch {
     st = null;
   }
-  if (!st?.isDirectory()) throw new Error(`--dir is not a directory: ${resolved}`);
+  if (!st?.isDirectory()) throw new Error(`${flag} is not a directory: ${resolved}`);
   return resolved;
 }
 
 function parseArgs(argv: string[]): {
   dir?: string;
+  roots: string[];
   host: string;
   port: number;
+  provider?: string;
+  model?: string;
   yolo: boolean;
   json: boolean;
   mobileH3: boolean;
@@ -69,8 +90,11 @@ function parseArgs(argv: string[]): {
   mobileH3Port: number;
 } {
   let dir: string | undefined;
+  const roots: string[] = [];
   let host = "127.0.0.1";
   let port = 7337;
+  let provider: string | undefined;
+  let model: string | undefined;
   let yolo = false;
   let json = false;
   let mobileH3 = false;
@@ -91,6 +115,13 @@ function parseArgs(argv: string[]): {
       i++;
       continue;
     }
+    if (a === "--root") {
+      const v = argv[i + 1];
+      if (!v) throw new Error(`Missing value for ${a}`);
+      roots.push(v);
+      i++;
+      continue;
+    }
     if (a === "--port" || a === "-p") {
       const v = argv[i + 1];
       if (!v) throw new Error(`Missing value for ${a}`);
@@ -107,6 +138,21 @@ function parseArgs(argv: string[]): {
       i++;
       continue;
     }
+    if (a === "--provider") {
+      const v = argv[i + 1];
+      if (!v) throw new Error(`Missing value for ${a}`);
+      if (!isProviderName(v)) throw new Error(`Unknown provider: ${v}`);
+      provider = v;
+      i++;
+      continue;
+    }
+    if (a === "--model") {
+      const v = argv[i + 1];
+      if (!v) throw new Error(`Missing value for ${a}`);
+      model = v;
+      i++;
+      continue;
+    }
     if (a === "--yolo" || a === "-y") {
       yolo = true;
       continue;
@@ -139,85 +185,45 @@ function parseArgs(argv: string[]): {
     throw new Error(`Unknown argument: ${a}`);
   }
 
-  return { dir, host, port, yolo, json, mobileH3, mobileH3Host, mobileH3Port };
-}
-
-function pushUnique(target: string[], seen: Set<string>, address: string): void {
-  if (seen.has(address)) {
-    return;
-  }
-  seen.add(address);
-  target.push(address);
-}
-
-function isIpv4LinkLocal(address: string): boolean {
-  return address.startsWith("169.254.");
-}
-
-function isIpv6LinkLocal(address: string): boolean {
-  return address.toLowerCase().startsWith("fe80:");
-}
-
-function isPrivateIpv4(address: string): boolean {
-  const parts = address.split(".").map((part) => Number(part));
-  if (parts.length !== 4 || parts.some((part) => !Number.isInteger(part))) {
-    return false;
-  }
-  const [a, b] = parts;
-  return a === 10 || (a === 172 && b >= 16 && b <= 31) || (a === 192 && b === 168);
-}
-
-export function resolveListeningHintsFromInterfaces(
-  host: string,
-  interfaces: ReturnType<typeof os.networkInterfaces>,
-): string[] {
-  if (host !== "0.0.0.0" && host !== "::") return [host];
-
-  const seen = new Set<string>();
-  const privateIpv4: string[] = [];
-  const publicIpv4: string[] = [];
-  const nonLinkLocalIpv6: string[] = [];
-  const linkLocalIpv4: string[] = [];
-  for (const addresses of Object.values(interfaces)) {
-    for (const address of addresses ?? []) {
-      if (address.internal) continue;
-      if (address.family !== "IPv4" && address.family !== "IPv6") continue;
-      if (address.family === "IPv4") {
-        if (isIpv4LinkLocal(address.address)) {
-          pushUnique(linkLocalIpv4, seen, address.address);
-        } else if (isPrivateIpv4(address.address)) {
-          pushUnique(privateIpv4, seen, address.address);
-        } else {
-          pushUnique(publicIpv4, seen, address.address);
-        }
-        continue;
-      }
-
-      // IPv6 link-local addresses require an interface scope and are not useful
-      // in a QR code consumed by another device.
-      if (!isIpv6LinkLocal(address.address)) {
-        pushUnique(nonLinkLocalIpv6, seen, address.address);
-      }
-    }
-  }
-
-  const hints = [...privateIpv4, ...publicIpv4, ...nonLinkLocalIpv6, ...linkLocalIpv4];
-  pushUnique(hints, seen, host === "::" ? "::1" : "127.0.0.1");
-
-  return hints;
-}
-
-export function resolveListeningHints(host: string): string[] {
-  return resolveListeningHintsFromInterfaces(host, os.networkInterfaces());
+  return {
+    dir,
+    roots,
+    host,
+    port,
+    provider,
+    model,
+    yolo,
+    json,
+    mobileH3,
+    mobileH3Host,
+    mobileH3Port,
+  };
 }
 
 async function main() {
-  const { dir, host, port, yolo, json, mobileH3, mobileH3Host, mobileH3Port } = parseArgs(
-    process.argv.slice(2),
-  );
+  const {
+    dir,
+    roots,
+    host,
+    port,
+    provider,
+    model,
+    yolo,
+    json,
+    mobileH3,
+    mobileH3Host,
+    mobileH3Port,
+  } = parseArgs(process.argv.slice(2));
 
   const cwd = dir ? await resolveAndValidateDir(dir) : process.cwd();
+  // Extra roots of a multi-root workspace; tools may read and write in them like in cwd.
+  const additionalRoots = await Promise.all(
+    roots.map((root) => resolveAndValidateDir(root, "--root")),
+  );
   if (dir) process.chdir(cwd);
+  if (process.env.COWORK_DESKTOP_SIDECAR_ADOPTABLE === "1") {
+    ignoreClosedOutputPipes();
+  }
 
   const crashReportingStatus = await initCrashReporting({
     component: "cowork-server",
@@ -228,7 +234,7 @@ async function main() {
     platform: process.platform,
     arch: process.arch,
     homeDir: os.homedir(),
-    workspacePaths: [cwd],
+    workspacePaths: [cwd, ...additionalRoots],
     tags: {
       component: "cowork-server",
       appVersion: VERSION,
@@ -257,13 +263,24 @@ async function main() {
     { DEFAULT_PROVIDER_OPTIONS },
     { startAgentServer },
     { createCoworkRuntimeStartupProgressEvent },
+    { JSONRPC_PROTOCOL_VERSION },
   ] = await Promise.all([
     import("../providers/providerOptions"),
     import("./startServer"),
     import("./startupProgress"),
+    import("./jsonrpc/protocol"),
   ]);
 
-  const serverEnv: NodeJS.ProcessEnv = { ...process.env, AGENT_WORKING_DIR: cwd };
+  const serverEnv: NodeJS.ProcessEnv = {
+    ...process.env,
+    AGENT_WORKING_DIR: cwd,
+    ...(additionalRoots.length > 0
+      ? { AGENT_ADDITIONAL_WORKING_DIRS: additionalRoots.join(path.delimiter) }
+      : {}),
+    // Flags win over the environment and config files, like AGENT_PROVIDER/AGENT_MODEL do.
+    ...(provider ? { AGENT_PROVIDER: provider } : {}),
+    ...(model ? { AGENT_MODEL: model } : {}),
+  };
   const onCoworkRuntimeBootstrapProgress =
     json && serverEnv.COWORK_DESKTOP_STARTUP_EVENTS === "1"
       ? (progress: Parameters<typeof createCoworkRuntimeStartupProgressEvent>[0]) => {
@@ -271,29 +288,36 @@ async function main() {
         }
       : undefined;
 
-  const { server, mobileServer, config, url, browserAccessToken } = await startAgentServer({
-    cwd,
-    hostname: host,
-    port,
-    env: serverEnv,
-    providerOptions: DEFAULT_PROVIDER_OPTIONS,
-    yolo,
-    preloadSystemPrompt: false,
-    onCoworkRuntimeBootstrapProgress,
-    ...(mobileH3
-      ? {
-          mobileH3: {
-            hostname: mobileH3Host,
-            port: mobileH3Port,
-            hostHints: resolveListeningHints(mobileH3Host),
-          },
-        }
-      : {}),
-  });
+  // Replaced with the graceful version once the server is up; a shutdown
+  // request that races startup just exits.
+  let shutdown = () => {
+    process.exit(0);
+  };
+  const { server, mobileServer, config, url, browserAccessToken, serverAuthToken } =
+    await startAgentServer({
+      cwd,
+      hostname: host,
+      port,
+      env: serverEnv,
+      providerOptions: DEFAULT_PROVIDER_OPTIONS,
+      yolo,
+      preloadSystemPrompt: false,
+      onCoworkRuntimeBootstrapProgress,
+      onShutdownRequested: () => shutdown(),
+      ...(mobileH3
+        ? {
+            mobileH3: {
+              hostname: mobileH3Host,
+              port: mobileH3Port,
+              hostHints: resolveListeningHints(mobileH3Host),
+            },
+          }
+        : {}),
+    });
 
   // Graceful shutdown on signals so child processes are cleaned up.
   let stopping = false;
-  const shutdown = () => {
+  shutdown = () => {
     if (stopping) return;
     stopping = true;
     try {
@@ -326,6 +350,8 @@ async function main() {
     console.log(
       JSON.stringify({
         type: "server_listening",
+        version: VERSION,
+        protocolVersion: JSONRPC_PROTOCOL_VERSION,
         url,
         rpcUrl,
         host,
@@ -333,6 +359,7 @@ async function main() {
         port: server.port,
         cwd: config.workingDirectory,
         browserAccessToken: browserAccessToken ?? null,
+        authToken: serverAuthToken ?? null,
         mobileH3: mobileServer
           ? {
               url: mobileServer.url,
@@ -19,10 +19,14 @@ import { ensureDefaultGlobalSkillsReady } from "../../skills/defaultGlobalSkills
 import type { AgentConfig } from "../../types";
 import { resolveVersion } from "../../version";
 import { WorktreeService } from "../git/WorktreeService";
+import { handoffMessageDenial } from "../handoff";
 import { decodeJsonRpcMessage } from "../jsonrpc/decodeJsonRpcMessage";
 import {
   buildJsonRpcErrorResponse,
   buildJsonRpcResultResponse,
+  JSONRPC_ERROR_CODES,
+  JSONRPC_PROTOCOL_VERSION,
+  type JsonRpcLiteClientMessage,
   type JsonRpcLiteClientResponse,
   type JsonRpcLiteNotification,
 } from "../jsonrpc/protocol";
@@ -46,6 +50,7 @@ import { ResearchService } from "../research/ResearchService";
 import { ServerFileLog, shouldEnableServerFileLog } from "../serverFileLog";
 import { getSessionTaskLock } from "../session/taskLocks";
 import { type PersistedSessionRecord, SessionDb } from "../sessionDb";
+import type { generateSessionTitle as generateSessionTitleFn } from "../sessionTitleService";
 import { readSkillCatalogMtimeSnapshot } from "../skillCatalogMtime";
 import { refreshSessionsForSkillMutation } from "../skillMutationRefresh";
 import type { StartServerSocket } from "../startServer/types";
@@ -128,6 +133,14 @@ export interface StartAgentServerOptions {
   taskTerminalQuiesceTimeoutMs?: number;
   pluginInstallEventsTimeoutMs?: number;
   onCoworkRuntimeBootstrapProgress?: (progress: CoworkRuntimeBootstrapProgress) => void;
+  /**
+   * Called when a supervisor asks the server to stop via `POST /cowork/shutdown`.
+   * The endpoint is only served when `COWORK_SHUTDOWN_TOKEN` is set; it stands in
+   * for SIGTERM on Windows, where the desktop app cannot deliver signals.
+   */
+  onShutdownRequested?: () => void;
+  /** Title generator behind `POST /cowork/title`; defaults to the session title service. */
+  generateSessionTitleImpl?: typeof generateSessionTitleFn;
 }
 
 type JsonRpcRequest = { id: string | number; method: string; params?: unknown };
@@ -143,6 +156,9 @@ type JsonRpcDecodedMessage = JsonRpcRequest | JsonRpcLiteNotification | JsonRpcL
 export type HealthSnapshot = {
   ok: true;
   version: string;
+  protocolVersion: string;
+  /** Lets the desktop app confirm a registered sidecar pid before adopting it. */
+  pid: number;
   uptimeMs: number;
   cwd: string;
   activeSessions: number;
@@ -174,6 +190,8 @@ export type AgentServerRuntime = {
   getHealthSnapshot(): HealthSnapshot;
   getStartupReadiness(): RuntimeStartupReadiness;
   waitForStartupReady(): Promise<void>;
+  /** Writes out session state and the thread journal so a stop right after loses nothing. */
+  flushState(): Promise<void>;
   stop(): Promise<void>;
 };
 
@@ -918,6 +936,24 @@ export async function createAgentServerRuntime(
     await jsonRpcRequestRouter(ws, message);
   };
 
+  // Handoff sockets are scoped to one thread; anything outside it is refused here.
+  const rejectHandoffMessage = (ws: StartServerSocket, message: JsonRpcLiteClientMessage) => {
+    const denial = ws.data.handoff ? handoffMessageDenial(ws.data.handoff, message) : null;
+    if (!denial) {
+      return false;
+    }
+    if ("method" in message && "id" in message) {
+      sendJsonRpc(
+        ws,
+        buildJsonRpcErrorResponse(message.id, {
+          code: JSONRPC_ERROR_CODES.invalidRequest,
+          message: denial,
+        }),
+      );
+    }
+    return true;
+  };
+
   let stopped = false;
 
   return {
@@ -954,6 +990,9 @@ export async function createAgentServerRuntime(
         sendJsonRpc(ws, decoded.response);
         return;
       }
+      if (rejectHandoffMessage(ws, decoded.message)) {
+        return;
+      }
       jsonRpcTransport.handleMessage(ws, decoded.message, routeJsonRpcRequest);
     },
     closeConnection: (ws) => {
@@ -981,6 +1020,8 @@ export async function createAgentServerRuntime(
       return {
         ok: true,
         version: resolveVersion(env),
+        protocolVersion: JSONRPC_PROTOCOL_VERSION,
+        pid: process.pid,
         uptimeMs: Date.now() - startedAtMs,
         cwd: config.workingDirectory,
         activeSessions: registry.sessionBindings.size,
@@ -1005,6 +1046,11 @@ export async function createAgentServerRuntime(
     waitForStartupReady: async () => {
       await startupReadyPromise;
     },
+    flushState: async () => {
+      await registry.flushAll();
+      await threadJournal.flush();
+      await fileLog?.flush();
+    },
     stop: async () => {
       if (stopped) return;
       stopped = true;
@@ -447,6 +447,15 @@ export class SessionRegistry {
     }
   }
 
+  /** Waits for every live session to finish persisting, without disposing any. */
+  async flushAll(): Promise<void> {
+    await Promise.allSettled(
+      [...this.sessionBindings.values()].map((binding) =>
+        binding.runtime?.lifecycle.waitForPersistenceIdle(),
+      ),
+    );
+  }
+
   async disposeAll(reason: string): Promise<void> {
     const persistenceFlushes: Promise<void>[] = [];
     for (const [id, binding] of this.sessionBindings) {
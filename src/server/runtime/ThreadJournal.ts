@@ -143,6 +143,13 @@ export class ThreadJournal {
     });
   }
 
+  /** Waits for the writes queued so far, and any queued while waiting, to land. */
+  async flush(): Promise<void> {
+    while (this.writeQueues.size > 0) {
+      await Promise.allSettled(this.writeQueues.values());
+    }
+  }
+
   async close(): Promise<void> {
     this.closed = true;
     await Promise.allSettled(this.writeQueues.values());
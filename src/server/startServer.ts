@@ -1,5 +1,8 @@
+import { HANDOFF_QUERY_PARAM, type HandoffClaims, HandoffRegistry } from "./handoff";
+import { resolveListeningHints } from "./listeningHints";
 import { createAgentServerRuntime, type StartAgentServerOptions } from "./runtime/ServerRuntime";
-import type { StartServerSocketData } from "./startServer/types";
+import type { generateSessionTitle as generateSessionTitleFn } from "./sessionTitleService";
+import type { StartServerSocket, StartServerSocketData } from "./startServer/types";
 import type { startH3MobileServer as startH3MobileServerType } from "./transport/h3/server";
 import {
   assertLoopbackRpcRemote,
@@ -14,11 +17,21 @@ export type { StartAgentServerOptions } from "./runtime/ServerRuntime";
 
 type H3MobileServer = Awaited<ReturnType<typeof startH3MobileServerType>>;
 
+// Only the opening of a message matters for a title; longer input just costs tokens.
+const TITLE_QUERY_MAX_CHARS = 4_000;
+const HANDOFF_LISTEN_HOSTNAME = "0.0.0.0";
+const HANDOFFS_PATH = "/cowork/handoffs";
+
 async function loadH3MobileServerStarter(): Promise<typeof startH3MobileServerType> {
   const { startH3MobileServer } = await import("./transport/h3/server");
   return startH3MobileServer;
 }
 
+async function loadSessionTitleGenerator(): Promise<typeof generateSessionTitleFn> {
+  const { generateSessionTitle } = await import("./sessionTitleService");
+  return generateSessionTitle;
+}
+
 function isLoopbackHostname(hostname: string): boolean {
   const normalized = hostname.trim().toLowerCase();
   const bareHostname =
@@ -82,6 +95,7 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
   ready: Promise<void>;
   url: string;
   browserAccessToken?: string;
+  serverAuthToken?: string;
 }> {
   const hostname = opts.hostname ?? "127.0.0.1";
   const networkExposedListener = !isLoopbackHostname(hostname);
@@ -102,6 +116,10 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
   const browserAccessToken =
     runtime.env.COWORK_BROWSER_ACCESS_TOKEN?.trim() ||
     (webDesktopService || networkExposedListener ? createBrowserAccessToken() : "");
+  const shutdownToken = runtime.env.COWORK_SHUTDOWN_TOKEN?.trim() || "";
+  // Set by the desktop app for the servers it spawns, so other local processes
+  // cannot drive them without a secret even though they carry no browser origin.
+  const serverAuthToken = runtime.env.COWORK_SERVER_AUTH_TOKEN?.trim() || "";
   let mobileServer: H3MobileServer | undefined;
   const loopbackRpc = createLoopbackHttpRpcSession(runtime);
   // Flipped true once startAgentServer finishes its full boot (mobile server +
@@ -109,6 +127,116 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
   // supervisor can tell "listening but not fully wired" from "ready".
   let startupReady = false;
 
+  // Sockets attached through handoff links, closed when their link is revoked or expires.
+  const handoffSockets = new Set<StartServerSocket>();
+  let handoffServer: ReturnType<typeof Bun.serve> | null = null;
+  const handoffs = serverAuthToken
+    ? new HandoffRegistry({
+        signingKey: serverAuthToken,
+        onRemoved: (tokenId) => {
+          for (const ws of handoffSockets) {
+            if (ws.data.handoff?.tokenId === tokenId) {
+              ws.close(1008, "Handoff link revoked");
+            }
+          }
+          if (handoffs?.size === 0) {
+            void stopHandoffServer();
+          }
+        },
+      })
+    : null;
+
+  const websocketHandlers: Bun.WebSocketHandler<StartServerSocketData> = {
+    maxPayloadLength: 4 * 1024 * 1024, // 4 MB — cap inbound frames to prevent memory exhaustion
+    open(ws) {
+      if (ws.data.handoff) {
+        handoffSockets.add(ws);
+      }
+      runtime.openConnection(ws);
+    },
+    message(ws, raw) {
+      runtime.handleMessage(ws, raw);
+    },
+    close(ws) {
+      handoffSockets.delete(ws);
+      runtime.closeConnection(ws);
+    },
+    drain(ws) {
+      runtime.drainConnection(ws);
+    },
+  };
+
+  const upgradeToWebSocket = (
+    req: Request,
+    srv: Bun.Server<StartServerSocketData>,
+    url: URL,
+    corsHeaders: Record<string, string>,
+    data: Partial<StartServerSocketData> = {},
+  ): Response | undefined => {
+    const protocolResult = resolveWsProtocol({
+      offeredSubprotocols: splitWebSocketSubprotocolHeader(
+        req.headers.get("sec-websocket-protocol"),
+      ),
+      requestedProtocol: url.searchParams.get("protocol"),
+    });
+    if (!protocolResult.ok) {
+      return new Response(protocolResult.error, { status: 400, headers: corsHeaders });
+    }
+    const upgraded = srv.upgrade(req, {
+      headers: protocolResult.protocol.selectedSubprotocol
+        ? {
+            "Sec-WebSocket-Protocol": protocolResult.protocol.selectedSubprotocol,
+          }
+        : undefined,
+      data: {
+        ...data,
+        protocolMode: protocolResult.protocol.mode,
+        selectedSubprotocol: protocolResult.protocol.selectedSubprotocol,
+        connectionId: crypto.randomUUID(),
+      },
+    });
+    if (upgraded) return;
+    return new Response("WebSocket upgrade failed", { status: 400, headers: corsHeaders });
+  };
+
+  /**
+   * Handoff links point at a LAN listener of their own, so the main listener
+   * can stay on loopback. It only upgrades `/ws` for a registered token, and
+   * the socket is scoped to that token's thread and access.
+   */
+  const ensureHandoffServer = (): ReturnType<typeof Bun.serve> => {
+    handoffServer ??= Bun.serve<StartServerSocketData>({
+      hostname: HANDOFF_LISTEN_HOSTNAME,
+      port: 0,
+      fetch(req, srv) {
+        const url = new URL(req.url);
+        if (url.pathname !== "/ws") {
+          return new Response("Not found", { status: 404 });
+        }
+        const claims = handoffs?.authorize(url.searchParams.get(HANDOFF_QUERY_PARAM) ?? "");
+        if (!claims) {
+          return new Response("Handoff link is invalid, expired, or revoked", { status: 401 });
+        }
+        return upgradeToWebSocket(req, srv, url, {}, {
+          handoff: { tokenId: claims.tokenId, threadId: claims.threadId, access: claims.access },
+          workspaceControlEventsAllowed: false,
+          taskReadAllowed: false,
+          taskMutationAllowed: false,
+        });
+      },
+      websocket: websocketHandlers,
+    });
+    return handoffServer;
+  };
+
+  const stopHandoffServer = async () => {
+    const listener = handoffServer;
+    handoffServer = null;
+    await Promise.resolve(listener?.stop(true)).catch(() => {
+      // ignore
+    });
+  };
+
   const createServer = (port: number): ReturnType<typeof Bun.serve> =>
     Bun.serve<StartServerSocketData>({
       hostname,
@@ -155,6 +283,50 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
             );
           }
         }
+        if (
+          serverAuthToken &&
+          isProtectedServerPath(url.pathname) &&
+          !browserOrigin &&
+          !networkExposedListener
+        ) {
+          const presentedToken =
+            parseBearerToken(req.headers.get("authorization")) ?? readBrowserAccessToken(url, req);
+          const authorized =
+            presentedToken === serverAuthToken ||
+            (Boolean(browserAccessToken) && presentedToken === browserAccessToken);
+          if (!authorized) {
+            return new Response("Unauthorized server access", {
+              status: 401,
+              headers: corsHeaders,
+            });
+          }
+        }
+        if (url.pathname === "/cowork/shutdown" && shutdownToken && opts.onShutdownRequested) {
+          if (req.method !== "POST") {
+            return new Response("Method not allowed", { status: 405, headers: corsHeaders });
+          }
+          const remoteDenied = assertLoopbackRpcRemote(req, srv);
+          if (remoteDenied) {
+            return remoteDenied;
+          }
+          if (req.headers.get("x-cowork-shutdown-token") !== shutdownToken) {
+            return new Response("Unauthorized shutdown request", {
+              status: 401,
+              headers: corsHeaders,
+            });
+          }
+          // Answer first so the caller is not left waiting on a socket that is closing.
+          const onShutdownRequested = opts.onShutdownRequested;
+          setTimeout(onShutdownRequested, 0);
+          return new Response(null, { status: 202, headers: corsHeaders });
+        }
+        if (url.pathname === "/cowork/flush") {
+          if (req.method !== "POST") {
+            return new Response("Method not allowed", { status: 405, headers: corsHeaders });
+          }
+          await runtime.flushState();
+          return new Response(null, { status: 204, headers: corsHeaders });
+        }
         if (req.method === "GET" && url.pathname === "/cowork/health") {
           const runtimeStartup = runtime.getStartupReadiness();
           return Response.json(
@@ -168,6 +340,63 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
             { headers: corsHeaders },
           );
         }
+        if (url.pathname === "/cowork/title") {
+          if (req.method !== "POST") {
+            return new Response("Method not allowed", { status: 405, headers: corsHeaders });
+          }
+          const body = (await req.json().catch(() => null)) as { query?: unknown } | null;
+          const query = typeof body?.query === "string" ? body.query.trim() : "";
+          if (!query) {
+            return new Response("Expected a non-empty query", {
+              status: 400,
+              headers: corsHeaders,
+            });
+          }
+          const generateSessionTitle =
+            opts.generateSessionTitleImpl ?? (await loadSessionTitleGenerator());
+          const result = await generateSessionTitle({
+            config: runtime.config,
+            query: query.slice(0, TITLE_QUERY_MAX_CHARS),
+          });
+          return Response.json(result, { headers: corsHeaders });
+        }
+        if (url.pathname === HANDOFFS_PATH || url.pathname.startsWith(`${HANDOFFS_PATH}/`)) {
+          if (!handoffs) {
+            return Response.json(
+              { error: "Handoff links need a server auth token." },
+              { status: 404, headers: corsHeaders },
+            );
+          }
+          if (req.method === "POST" && url.pathname === HANDOFFS_PATH) {
+            const body = (await req.json().catch(() => null)) as { token?: unknown } | null;
+            const claims: HandoffClaims | null =
+              typeof body?.token === "string" ? handoffs.register(body.token) : null;
+            if (!claims) {
+              return Response.json(
+                { error: "Expected a valid, unexpired handoff token." },
+                { status: 400, headers: corsHeaders },
+              );
+            }
+            const listener = ensureHandoffServer();
+            return Response.json(
+              {
+                tokenId: claims.tokenId,
+                expiresAt: claims.expiresAt,
+                port: listener.port,
+                hostHints: resolveListeningHints(HANDOFF_LISTEN_HOSTNAME),
+              },
+              { headers: corsHeaders },
+            );
+          }
+          if (req.method === "DELETE" && url.pathname.startsWith(`${HANDOFFS_PATH}/`)) {
+            const tokenId = decodeURIComponent(url.pathname.slice(HANDOFFS_PATH.length + 1));
+            return Response.json(
+              { ok: true, removed: handoffs.revoke(tokenId) },
+              { headers: corsHeaders },
+            );
+          }
+          return new Response("Method not allowed", { status: 405, headers: corsHeaders });
+        }
         if (url.pathname === "/rpc") {
           const remoteDenied = assertLoopbackRpcRemote(req, srv);
           if (remoteDenied) {
@@ -188,30 +417,7 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
           const resumeSessionId = resumeSessionIdRaw?.trim()
             ? resumeSessionIdRaw.trim()
             : undefined;
-          const protocolResult = resolveWsProtocol({
-            offeredSubprotocols: splitWebSocketSubprotocolHeader(
-              req.headers.get("sec-websocket-protocol"),
-            ),
-            requestedProtocol: url.searchParams.get("protocol"),
-          });
-          if (!protocolResult.ok) {
-            return new Response(protocolResult.error, { status: 400, headers: corsHeaders });
-          }
-          const upgraded = srv.upgrade(req, {
-            headers: protocolResult.protocol.selectedSubprotocol
-              ? {
-                  "Sec-WebSocket-Protocol": protocolResult.protocol.selectedSubprotocol,
-                }
-              : undefined,
-            data: {
-              resumeSessionId,
-              protocolMode: protocolResult.protocol.mode,
-              selectedSubprotocol: protocolResult.protocol.selectedSubprotocol,
-              connectionId: crypto.randomUUID(),
-            },
-          });
-          if (upgraded) return;
-          return new Response("WebSocket upgrade failed", { status: 400, headers: corsHeaders });
+          return upgradeToWebSocket(req, srv, url, corsHeaders, { resumeSessionId });
         }
         if (req.method === "GET" && url.pathname === "/mobile-h3/trusted") {
           if (!mobileServer) {
@@ -292,21 +498,7 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
         }
         return new Response("OK", { status: 200, headers: corsHeaders });
       },
-      websocket: {
-        maxPayloadLength: 4 * 1024 * 1024, // 4 MB — cap inbound frames to prevent memory exhaustion
-        open(ws) {
-          runtime.openConnection(ws);
-        },
-        message(ws, raw) {
-          runtime.handleMessage(ws, raw);
-        },
-        close(ws) {
-          runtime.closeConnection(ws);
-        },
-        drain(ws) {
-          runtime.drainConnection(ws);
-        },
-      },
+      websocket: websocketHandlers,
     });
 
   const serveWithPortFallback = (port: number): ReturnType<typeof Bun.serve> => {
@@ -372,6 +564,8 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
     stopped = true;
     clearInterval(evictionTimer);
     loopbackRpc.closeAll();
+    handoffs?.revokeAll();
+    await stopHandoffServer();
     await runtime.stop();
     await mobileServer?.stop().catch(() => {
       // ignore
@@ -396,5 +590,6 @@ export async function startAgentServer(opts: StartAgentServerOptions): Promise<{
     ready: runtime.waitForStartupReady(),
     url,
     ...(browserAccessToken ? { browserAccessToken } : {}),
+    ...(serverAuthToken ? { serverAuthToken } : {}),
   };
 }
@@ -1,3 +1,4 @@
+import type { HandoffClaims } from "../handoff";
 import type { JsonRpcInitializeParams } from "../jsonrpc/protocol";
 import type { AgentSession } from "../session/AgentSession";
 import type { SessionRuntime } from "../session/SessionRuntime";
@@ -33,6 +34,8 @@ type ServerTransportData = {
   workspaceControlEventsAllowed?: boolean;
   taskReadAllowed?: boolean;
   taskMutationAllowed?: boolean;
+  /** Set when the socket attached through a handoff link; scopes it to one thread. */
+  handoff?: Pick<HandoffClaims, "tokenId" | "threadId" | "access">;
   rpc?: JsonRpcConnectionState;
 };
 
@@ -196,6 +196,14 @@ export interface AgentConfig {
   };
 
   workingDirectory: string;
+  /** Other roots of a multi-root workspace; file tools treat them like workingDirectory. */
+  additionalWorkingDirectories?: string[];
+  /**
+   * Absolute paths the file tools may (allow) or may not (deny) touch, from the
+   * desktop's per-workspace policy. The most specific entry wins; allow entries
+   * outside the workspace also become read/write roots.
+   */
+  pathPolicy?: { allow: string[]; deny: string[] };
   outputDirectory?: string;
   uploadsDirectory?: string;
 
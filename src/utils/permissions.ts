@@ -13,15 +13,17 @@ import type { AgentConfig } from "../types";
 import { isPathInside, PROTECTED_METADATA_DIR_NAMES, pathCrossesProtectedMetadata } from "./paths";
 
 const WRITE_ROOT_LABEL =
-  "workingDirectory/outputDirectory/uploadsDirectory/project root/active advanced-memory folder";
+  "workingDirectory/additional workspace roots/outputDirectory/uploadsDirectory/project root/active advanced-memory folder";
 const READ_ROOT_LABEL =
-  "workingDirectory/outputDirectory/uploadsDirectory/project root/skills directories/plugin roots/advanced-memory folders";
+  "workingDirectory/additional workspace roots/outputDirectory/uploadsDirectory/project root/skills directories/plugin roots/advanced-memory folders";
 
 function writeRoots(config: AgentConfig): string[] {
   const projectRoot = path.dirname(config.projectCoworkDir);
   return [
     projectRoot,
     config.workingDirectory,
+    ...(config.additionalWorkingDirectories ?? []),
+    ...(config.pathPolicy?.allow ?? []),
     ...(config.outputDirectory ? [config.outputDirectory] : []),
     ...(config.uploadsDirectory ? [config.uploadsDirectory] : []),
     ...resolveAdvancedMemoryWriteRoots(config),
@@ -56,6 +58,44 @@ function isInsideCredentialDir(resolvedTarget: string, config: AgentConfig): boo
   );
 }
 
+/**
+ * The workspace path policy's deny entry covering `resolvedTarget`, or null when
+ * the policy does not deny it. The most specific matching entry decides, and a
+ * deny beats an allow naming the same path, so `allow: [<ws>/secrets/public]`
+ * re-opens part of `deny: [<ws>/secrets]`.
+ */
+function pathPolicyDenyRule(resolvedTarget: string, config: AgentConfig): string | null {
+  const policy = config.pathPolicy;
+  if (!policy || policy.deny.length === 0) return null;
+  let best: string | null = null;
+  let denied = false;
+  for (const [rules, deny] of [
+    [policy.allow, false],
+    [policy.deny, true],
+  ] as const) {
+    for (const rule of rules) {
+      if (!isPathInside(rule, resolvedTarget)) continue;
+      if (best === null || rule.length > best.length || (rule.length === best.length && deny)) {
+        best = rule;
+        denied = deny;
+      }
+    }
+  }
+  return denied ? best : null;
+}
+
+function assertNotDeniedByPathPolicy(
+  action: string,
+  resolved: string,
+  canonicalTarget: string,
+  config: AgentConfig,
+): void {
+  const rule = pathPolicyDenyRule(resolved, config) ?? pathPolicyDenyRule(canonicalTarget, config);
+  if (rule) {
+    throw new Error(`${action} blocked: workspace path policy denies ${rule}: ${resolved}`);
+  }
+}
+
 async function pluginReadRoots(config: AgentConfig): Promise<string[]> {
   try {
     const discovery = await discoverPlugins(config);
@@ -167,12 +207,16 @@ export function isWritePathAllowed(filePath: string, config: AgentConfig): boole
   if (!isCanonicalPathInsideRoots(resolved, writeRoots(config))) {
     return false;
   }
+  if (isDeniedByPathPolicySync(resolved, config)) {
+    return false;
+  }
   return !writeTargetCrossesProtectedMetadata(config, resolved);
 }
 
 export function isReadPathAllowed(filePath: string, config: AgentConfig): boolean {
   const resolved = path.resolve(filePath);
   if (isInsideCredentialDir(resolved, config)) return false;
+  if (isDeniedByPathPolicySync(resolved, config)) return false;
   try {
     const canonicalTarget = canonicalizeExistingPrefixSync(resolved);
     const canonicalDenyDirs = credentialReadDenyDirs(config).map((dir) =>
@@ -216,6 +260,15 @@ function canonicalizeRootSync(rootPath: string): string {
   return canonicalizeExistingPrefixSync(rootPath);
 }
 
+function isDeniedByPathPolicySync(resolved: string, config: AgentConfig): boolean {
+  if (pathPolicyDenyRule(resolved, config)) return true;
+  try {
+    return pathPolicyDenyRule(canonicalizeExistingPrefixSync(resolved), config) !== null;
+  } catch {
+    return Boolean(config.pathPolicy?.deny.length);
+  }
+}
+
 function isCanonicalPathInsideRoots(filePath: string, roots: string[]): boolean {
   const resolved = path.resolve(filePath);
   if (!isPathInsideAnyRoot(resolved, roots)) {
@@ -258,6 +311,8 @@ export async function assertWritePathAllowed(
     );
   }
 
+  assertNotDeniedByPathPolicy(action, resolved, canonicalTarget, config);
+
   // Protected project metadata (.git/.cowork) is carved out of the writable set,
   // matching the shell sandbox policy, so a prompt-influenced write/edit cannot
   // plant a git hook or mutate project config/skills/memory metadata.
@@ -315,6 +370,8 @@ export async function assertReadPathAllowed(
     );
   }
 
+  assertNotDeniedByPathPolicy(action, resolved, canonicalTarget, config);
+
   await assertInsideAgentTargetPaths(resolved, config, action, targetPaths, {
     projectPathsOnly: true,
   });
@@ -47,8 +47,48 @@ function windowsDriveRelativeBase(drive: string): string {
   return cwd.replace(/\\$/, "");
 }
 
-function resolveWindowsPath(value: string): string {
+/** Paths this long need the `\\?\` prefix before Win32 file APIs accept them. */
+const WINDOWS_MAX_PATH = 260;
+const WINDOWS_LONG_PATH_PREFIX = "\\\\?\\";
+const WINDOWS_LONG_UNC_PREFIX = "\\\\?\\UNC\\";
+
+/**
+ * Drops a Win32 long-path prefix: `\\?\C:\repo` becomes `C:\repo` and
+ * `\\?\UNC\host\share` becomes `\\host\share`. Other paths are returned as is.
+ */
+export function stripWindowsLongPathPrefix(value: string): string {
   const normalized = value.replaceAll("/", "\\");
+  if (normalized.toUpperCase().startsWith(WINDOWS_LONG_UNC_PREFIX)) {
+    return `\\\\${normalized.slice(WINDOWS_LONG_UNC_PREFIX.length)}`;
+  }
+  if (normalized.startsWith(WINDOWS_LONG_PATH_PREFIX)) {
+    return normalized.slice(WINDOWS_LONG_PATH_PREFIX.length);
+  }
+  return value;
+}
+
+/** True for a network share path such as `\\host\share\dir`, with or without a long-path prefix. */
+export function isWindowsUncPath(value: string): boolean {
+  return /^\\\\[^\\?.][^\\]*\\[^\\]+/.test(
+    stripWindowsLongPathPrefix(value.trim()).replaceAll("/", "\\"),
+  );
+}
+
+/**
+ * Resolves a Windows path and, once it reaches `MAX_PATH`, adds the `\\?\`
+ * (or `\\?\UNC\` for shares) prefix Win32 needs to open it. Shorter paths
+ * keep their plain form, since some APIs, like a child process's cwd, reject prefixes.
+ */
+export function toWindowsLongPath(value: string): string {
+  const resolved = resolveWindowsPath(value.trim());
+  if (resolved.length < WINDOWS_MAX_PATH) return resolved;
+  return isWindowsUncPath(resolved)
+    ? `${WINDOWS_LONG_UNC_PREFIX}${resolved.slice(2)}`
+    : `${WINDOWS_LONG_PATH_PREFIX}${resolved}`;
+}
+
+function resolveWindowsPath(value: string): string {
+  const normalized = stripWindowsLongPathPrefix(value).replaceAll("/", "\\");
   const uncMatch = /^\\\\([^\\]+)\\([^\\]+)(?:\\(.*))?$/.exec(normalized);
   if (uncMatch) {
     const root = `\\\\${uncMatch[1]}\\${uncMatch[2]}`;
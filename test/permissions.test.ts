@@ -518,6 +518,31 @@ describe("isReadPathAllowed", () => {
     expect(isReadPathAllowed("/etc/passwd", cfg)).toBe(false);
   });
 
+  test("additional workspace roots are readable and writable", () => {
+    const extraRoot =
+      process.platform === "win32" ? "C:\\home\\user\\shared-lib" : "/home/user/shared-lib";
+    const cfg = makeConfig(PROJECT);
+    expect(isWritePathAllowed(path.join(extraRoot, "index.ts"), cfg)).toBe(false);
+
+    cfg.additionalWorkingDirectories = [extraRoot];
+    expect(isReadPathAllowed(path.join(extraRoot, "index.ts"), cfg)).toBe(true);
+    expect(isWritePathAllowed(path.join(extraRoot, "src", "index.ts"), cfg)).toBe(true);
+  });
+
+  test("workspace path policy denies, re-allows, and opens external paths", () => {
+    const secrets = path.join(PROJECT, "secrets");
+    const shared =
+      process.platform === "win32" ? "C:\\home\\user\\shared-docs" : "/home/user/shared-docs";
+    const cfg = makeConfig(PROJECT);
+    cfg.pathPolicy = { allow: [path.join(secrets, "public"), shared], deny: [secrets] };
+
+    expect(isReadPathAllowed(path.join(secrets, "prod.env"), cfg)).toBe(false);
+    expect(isWritePathAllowed(path.join(secrets, "prod.env"), cfg)).toBe(false);
+    expect(isReadPathAllowed(path.join(secrets, "public", "README.md"), cfg)).toBe(true);
+    expect(isWritePathAllowed(path.join(shared, "notes.md"), cfg)).toBe(true);
+    expect(isReadPathAllowed(path.join(PROJECT, "src", "index.ts"), cfg)).toBe(true);
+  });
+
   test("denies reads of the project credential directory (.cowork/auth)", () => {
     const cfg = makeConfig(PROJECT);
     expect(
@@ -564,6 +589,16 @@ describe("assertReadPathAllowed", () => {
     await expect(assertReadPathAllowed(target, cfg, "read")).resolves.toBe(path.resolve(target));
   });
 
+  test("names the workspace path policy entry that blocks a read", async () => {
+    const dir = await fs.mkdtemp(path.join(os.tmpdir(), "perm-policy-read-"));
+    const cfg = makeConfig(dir);
+    const vendor = path.join(dir, "vendor");
+    cfg.pathPolicy = { allow: [], deny: [vendor] };
+    await expect(
+      assertReadPathAllowed(path.join(vendor, "lib.js"), cfg, "read"),
+    ).rejects.toThrow(`read blocked: workspace path policy denies ${vendor}`);
+  });
+
   test("rejects reading a project credential file even though it sits in the workspace", async () => {
     const dir = await fs.mkdtemp(path.join(os.tmpdir(), "perm-cred-read-"));
     const cfg = makeConfig(dir);
@@ -0,0 +1,118 @@
+import { describe, expect, test } from "bun:test";
+
+import {
+  type HandoffClaims,
+  HandoffRegistry,
+  handoffMessageDenial,
+  signHandoffToken,
+  verifyHandoffToken,
+} from "../src/server/handoff";
+
+const KEY = "server-auth-token";
+const NOW = 1_700_000_000_000;
+
+function claims(overrides: Partial<HandoffClaims> = {}): HandoffClaims {
+  return {
+    tokenId: "token-1",
+    threadId: "thread-1",
+    access: "read",
+    expiresAt: NOW + 60_000,
+    ...overrides,
+  };
+}
+
+describe("handoff tokens", () => {
+  test("verify only well-signed, unexpired tokens", () => {
+    const token = signHandoffToken(claims(), KEY);
+
+    expect(verifyHandoffToken(token, KEY, NOW)).toEqual(claims());
+    expect(verifyHandoffToken(token, "other-key", NOW)).toBeNull();
+    expect(verifyHandoffToken(token, KEY, NOW + 60_000)).toBeNull();
+
+    const [, signature] = token.split(".");
+    const forged = Buffer.from(JSON.stringify(claims({ access: "readWrite" }))).toString(
+      "base64url",
+    );
+    expect(verifyHandoffToken(`${forged}.${signature}`, KEY, NOW)).toBeNull();
+    expect(verifyHandoffToken("not-a-token", KEY, NOW)).toBeNull();
+  });
+
+  test("registry authorizes registered tokens until revoked or expired", async () => {
+    let now = NOW;
+    const removed: string[] = [];
+    const registry = new HandoffRegistry({
+      signingKey: KEY,
+      onRemoved: (tokenId) => removed.push(tokenId),
+      now: () => now,
+    });
+    const token = signHandoffToken(claims(), KEY);
+    const unregistered = signHandoffToken(claims({ tokenId: "token-2" }), KEY);
+
+    expect(registry.authorize(token)).toBeNull();
+    expect(registry.register(token)?.tokenId).toBe("token-1");
+    expect(registry.authorize(token)?.threadId).toBe("thread-1");
+    expect(registry.authorize(unregistered)).toBeNull();
+
+    expect(registry.revoke("token-1")).toBe(true);
+    expect(registry.revoke("token-1")).toBe(false);
+    expect(registry.authorize(token)).toBeNull();
+    expect(removed).toEqual(["token-1"]);
+
+    now = Date.now();
+    const shortLived = signHandoffToken(claims({ tokenId: "token-3", expiresAt: now + 10 }), KEY);
+    expect(registry.register(shortLived)).not.toBeNull();
+    await Bun.sleep(30);
+    expect(registry.size).toBe(0);
+    expect(removed).toEqual(["token-1", "token-3"]);
+  });
+});
+
+describe("handoff message gating", () => {
+  const read = { threadId: "thread-1", access: "read" as const };
+  const readWrite = { threadId: "thread-1", access: "readWrite" as const };
+
+  test("read-only links can follow the granted thread only", () => {
+    expect(handoffMessageDenial(read, { id: 1, method: "initialize", params: {} })).toBeNull();
+    const ownThread = { threadId: "thread-1" };
+    const otherThread = { threadId: "thread-2" };
+    const resume = { id: 2, method: "thread/resume", params: ownThread };
+    expect(handoffMessageDenial(read, resume)).toBeNull();
+    expect(handoffMessageDenial(read, { id: 3, method: "thread/read", params: otherThread })).toBe(
+      "This handoff link is for a different thread.",
+    );
+    expect(
+      handoffMessageDenial(read, {
+        id: 4,
+        method: "turn/start",
+        params: { threadId: "thread-1", input: [] },
+      }),
+    ).toBe("This handoff link is read-only.");
+    expect(handoffMessageDenial(read, { id: "approval-1", result: { approved: true } })).toBe(
+      "This handoff link is read-only.",
+    );
+    expect(handoffMessageDenial(read, { id: 5, method: "thread/list", params: {} })).toBe(
+      "thread/list is not available over a handoff link.",
+    );
+  });
+
+  test("read-write links can run turns and answer prompts in the granted thread", () => {
+    expect(
+      handoffMessageDenial(readWrite, {
+        id: 1,
+        method: "turn/start",
+        params: { threadId: "thread-1", input: [] },
+      }),
+    ).toBeNull();
+    expect(handoffMessageDenial(readWrite, { id: "ask-1", result: { answer: "yes" } })).toBeNull();
+    expect(
+      handoffMessageDenial(readWrite, {
+        id: 2,
+        method: "turn/interrupt",
+        params: { threadId: "thread-2" },
+      }),
+    ).toBe("This handoff link is for a different thread.");
+    expect(handoffMessageDenial(readWrite, { id: 3, method: "thread/start", params: {} })).toBe(
+      "thread/start is not available over a handoff link.",
+    );
+  });
+});
@@ -7,6 +7,7 @@ import { fileURLToPath } from "node:url";
 import { getAiCoworkerPaths } from "../src/connect";
 import { DEFAULT_PROVIDER_OPTIONS } from "../src/providers";
 import { resolveListeningHintsFromInterfaces } from "../src/server/index";
+import { JSONRPC_PROTOCOL_VERSION } from "../src/server/jsonrpc/protocol";
 import { ASK_SKIP_TOKEN } from "../src/server/protocol";
 import type { AgentSession } from "../src/server/session/AgentSession";
 import { SessionDb } from "../src/server/sessionDb";
@@ -169,6 +170,8 @@ describe("Server Startup", () => {
       const body = (await response.json()) as {
         ok: boolean;
         version: string;
+        protocolVersion: string;
+        pid: number;
         uptimeMs: number;
         cwd: string;
         activeSessions: number;
@@ -181,6 +184,8 @@ describe("Server Startup", () => {
       expect(body.ok).toBe(true);
       expect(typeof body.version).toBe("string");
       expect(body.version.length).toBeGreaterThan(0);
+      expect(body.protocolVersion).toBe(JSONRPC_PROTOCOL_VERSION);
+      expect(body.pid).toBe(process.pid);
       expect(body.uptimeMs).toBeGreaterThanOrEqual(0);
       expect(body.cwd).toBe(tmpDir);
       expect(body.activeSessions).toBe(0);
@@ -236,6 +241,101 @@ describe("Server Startup", () => {
     }
   });
 
+  test("serves the shutdown endpoint only to callers holding the shutdown token", async () => {
+    const tmpDir = await makeTmpProject();
+    let shutdownRequests = 0;
+    const { server, ready } = await startAgentServer(
+      serverOpts(tmpDir, {
+        env: { COWORK_SHUTDOWN_TOKEN: "secret-token" },
+        onShutdownRequested: () => {
+          shutdownRequests += 1;
+        },
+      }),
+    );
+    try {
+      await ready;
+      const shutdownUrl = `http://127.0.0.1:${server.port}/cowork/shutdown`;
+
+      const wrongToken = await fetch(shutdownUrl, {
+        method: "POST",
+        headers: { "x-cowork-shutdown-token": "guess" },
+      });
+      const wrongMethod = await fetch(shutdownUrl);
+      const accepted = await fetch(shutdownUrl, {
+        method: "POST",
+        headers: { "x-cowork-shutdown-token": "secret-token" },
+      });
+      await new Promise((resolve) => setTimeout(resolve, 10));
+
+      expect(wrongToken.status).toBe(401);
+      expect(wrongMethod.status).toBe(405);
+      expect(accepted.status).toBe(202);
+      expect(shutdownRequests).toBe(1);
+    } finally {
+      await stopTestServer(server);
+    }
+  });
+
+  test("requires the server auth token from local clients when one is configured", async () => {
+    const tmpDir = await makeTmpProject();
+    const started = await startAgentServer(
+      serverOpts(tmpDir, { env: { COWORK_SERVER_AUTH_TOKEN: "server-secret" } }),
+    );
+    try {
+      await started.ready;
+      const healthUrl = `http://127.0.0.1:${started.server.port}/cowork/health`;
+
+      const anonymous = await fetch(healthUrl);
+      const wrongToken = await fetch(healthUrl, { headers: { authorization: "Bearer guess" } });
+      const bearer = await fetch(healthUrl, {
+        headers: { authorization: "Bearer server-secret" },
+      });
+
+      expect(started.serverAuthToken).toBe("server-secret");
+      expect(anonymous.status).toBe(401);
+      expect(wrongToken.status).toBe(401);
+      expect(bearer.status).toBe(200);
+    } finally {
+      await stopTestServer(started.server);
+    }
+  });
+
+  test("generates a title for a query through /cowork/title", async () => {
+    const tmpDir = await makeTmpProject();
+    const queries: string[] = [];
+    const started = await startAgentServer(
+      serverOpts(tmpDir, {
+        generateSessionTitleImpl: async ({ query }) => {
+          queries.push(query);
+          return { title: "Fix the login bug", source: "model", model: "test-model" };
+        },
+      }),
+    );
+    try {
+      const titleUrl = `http://127.0.0.1:${started.server.port}/cowork/title`;
+
+      const response = await fetch(titleUrl, {
+        method: "POST",
+        headers: { "content-type": "application/json" },
+        body: JSON.stringify({ query: "  please fix the login bug  " }),
+      });
+      const empty = await fetch(titleUrl, { method: "POST", body: JSON.stringify({ query: " " }) });
+      const wrongMethod = await fetch(titleUrl);
+
+      expect(response.status).toBe(200);
+      expect(await response.json()).toEqual({
+        title: "Fix the login bug",
+        source: "model",
+        model: "test-model",
+      });
+      expect(queries).toEqual(["please fix the login bug"]);
+      expect(empty.status).toBe(400);
+      expect(wrongMethod.status).toBe(405);
+    } finally {
+      await stopTestServer(started.server);
+    }
+  });
+
   test("creates projectCoworkDir on startup", async () => {
     const tmpDir = await makeTmpProject();
     // Remove the .agent dir so startServer has to create it
@@ -3,7 +3,10 @@ import path from "node:path";
 
 import {
   canonicalWorkspacePath,
+  isWindowsUncPath,
   sameWorkspacePath,
+  stripWindowsLongPathPrefix,
+  toWindowsLongPath,
   workspacePathOverlaps,
 } from "../src/utils/workspacePath";
 
@@ -147,4 +150,47 @@ describe("workspacePath", () => {
       });
     });
   });
+
+  describe("Windows long and UNC paths", () => {
+    const oneDrive = "C:\\Users\\me\\OneDrive - Contoso\\Documents\\repo";
+
+    test("long-path prefixes are stripped back to the plain form", () => {
+      expect(stripWindowsLongPathPrefix("\\\\?\\C:\\repo")).toBe("C:\\repo");
+      expect(stripWindowsLongPathPrefix("\\\\?\\UNC\\fileserver\\team\\repo")).toBe(
+        "\\\\fileserver\\team\\repo",
+      );
+      expect(stripWindowsLongPathPrefix("//?/unc/fileserver/team")).toBe("\\\\fileserver\\team");
+      expect(stripWindowsLongPathPrefix("C:\\repo")).toBe("C:\\repo");
+    });
+
+    test("network shares are detected with or without a prefix, but device paths are not", () => {
+      expect(isWindowsUncPath("\\\\fileserver\\team\\repo")).toBe(true);
+      expect(isWindowsUncPath("//fileserver/team")).toBe(true);
+      expect(isWindowsUncPath("\\\\?\\UNC\\fileserver\\team")).toBe(true);
+      expect(isWindowsUncPath("\\\\?\\C:\\repo")).toBe(false);
+      expect(isWindowsUncPath("\\\\.\\pipe\\cowork")).toBe(false);
+      expect(isWindowsUncPath(oneDrive)).toBe(false);
+    });
+
+    test("only paths reaching MAX_PATH get a long-path prefix", () => {
+      const deep = `${oneDrive}\\${"nested-folder\\".repeat(20)}file.ts`;
+      const share = `\\\\fileserver\\team\\${"nested-folder\\".repeat(20)}file.ts`;
+
+      expect(toWindowsLongPath(oneDrive)).toBe(oneDrive);
+      expect(toWindowsLongPath(deep)).toBe(`\\\\?\\${deep}`);
+      expect(toWindowsLongPath(share)).toBe(`\\\\?\\UNC\\${share.slice(2)}`);
+      expect(toWindowsLongPath(`\\\\?\\${deep}`)).toBe(`\\\\?\\${deep}`);
+    });
+
+    test("prefixed, differently cased spellings of one folder compare equal", () => {
+      const prefixedOneDrive = "\\\\?\\c:\\users\\me\\onedrive - contoso\\documents\\repo\\";
+      const share = "\\\\FileServer\\Team\\Repo";
+      const prefixedShare = "\\\\?\\UNC\\fileserver\\team\\repo";
+
+      expect(sameWorkspacePath(oneDrive, prefixedOneDrive, "win32")).toBe(true);
+      expect(sameWorkspacePath(share, prefixedShare, "win32")).toBe(true);
+      expect(sameWorkspacePath(share, "\\\\fileserver\\other\\repo", "win32")).toBe(false);
+      expect(workspacePathOverlaps("\\\\fileserver\\team", prefixedShare, "win32")).toBe(true);
+    });
+  });
 });